@@ -5,15 +5,18 @@ use tari_features::resolver::build_features;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     build_features();
+    let out_dir = std::path::PathBuf::from(std::env::var("OUT_DIR")?);
     tonic_build::configure()
         .build_client(true)
         .build_server(true)
+        .file_descriptor_set_path(out_dir.join("tari_rpc_descriptor.bin"))
         .compile_protos(
             &[
                 "proto/base_node.proto",
                 "proto/wallet.proto",
                 "proto/validator_node.proto",
                 "proto/p2pool.proto",
+                "proto/signer.proto",
             ],
             &["proto"],
         )?;
@@ -45,3 +45,15 @@ impl TryFrom<grpc::ComAndPubSignature> for ComAndPubSignature {
         Ok(Self::new(ephemeral_commitment, ephemeral_pubkey, u_a, u_x, u_y))
     }
 }
+
+impl From<ComAndPubSignature> for grpc::ComAndPubSignature {
+    fn from(sig: ComAndPubSignature) -> Self {
+        Self {
+            ephemeral_commitment: sig.ephemeral_commitment().to_vec(),
+            ephemeral_pubkey: sig.ephemeral_pubkey().to_vec(),
+            u_a: sig.u_a().to_vec(),
+            u_x: sig.u_x().to_vec(),
+            u_y: sig.u_y().to_vec(),
+        }
+    }
+}
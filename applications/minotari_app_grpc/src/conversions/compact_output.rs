@@ -0,0 +1,80 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::convert::TryFrom;
+
+use tari_common_types::types::{Commitment, FixedHash};
+use tari_core::transactions::{
+    tari_amount::MicroMinotari,
+    transaction_components::{CompactOutput, EncryptedData, OutputType, TransactionOutputVersion},
+};
+use tari_script::TariScript;
+use tari_utilities::ByteArray;
+
+use crate::tari_rpc as grpc;
+
+impl TryFrom<grpc::CompactOutput> for CompactOutput {
+    type Error = String;
+
+    fn try_from(output: grpc::CompactOutput) -> Result<Self, Self::Error> {
+        let version = u8::try_from(output.version)
+            .map_err(|_| "Invalid output version".to_string())
+            .and_then(TransactionOutputVersion::try_from)?;
+        let output_hash =
+            FixedHash::try_from(output.output_hash).map_err(|err| format!("Invalid output hash: {}", err))?;
+        let commitment = Commitment::from_canonical_bytes(&output.commitment)
+            .map_err(|err| format!("Invalid output commitment: {}", err))?;
+        let script = TariScript::from_bytes(output.script.as_slice())
+            .map_err(|err| format!("Script deserialization: {:?}", err))?;
+        let encrypted_data = EncryptedData::from_bytes(&output.encrypted_data).map_err(|err| err.to_string())?;
+        let output_type = u8::try_from(output.output_type)
+            .ok()
+            .and_then(OutputType::from_byte)
+            .ok_or_else(|| "Invalid or unrecognised output type".to_string())?;
+
+        Ok(Self {
+            version,
+            output_hash,
+            commitment,
+            script,
+            encrypted_data,
+            output_type,
+            maturity: output.maturity,
+            minimum_value_promise: MicroMinotari::from(output.minimum_value_promise),
+        })
+    }
+}
+
+impl From<CompactOutput> for grpc::CompactOutput {
+    fn from(output: CompactOutput) -> Self {
+        Self {
+            version: u32::from(output.version.as_u8()),
+            output_hash: output.output_hash.to_vec(),
+            commitment: output.commitment.to_vec(),
+            script: output.script.to_bytes(),
+            encrypted_data: output.encrypted_data.to_byte_vec(),
+            output_type: u32::from(output.output_type.as_byte()),
+            maturity: output.maturity,
+            minimum_value_promise: output.minimum_value_promise.as_u64(),
+        }
+    }
+}
@@ -53,6 +53,8 @@ impl From<ConsensusConstants> for grpc::ConsensusConstants {
             input_weight: cc.transaction_weight_params().params().input_weight,
             output_weight: cc.transaction_weight_params().params().output_weight,
             features_and_scripts_bytes_per_gram,
+            revealed_value_output_weight: transaction_weight.params().revealed_value_output_weight,
+            version: u32::from(transaction_weight.version().as_u8()),
         };
         let output_version_range = cc.output_version_range();
         let outputs = grpc::Range {
@@ -27,11 +27,15 @@ pub mod block_header;
 pub mod chain_metadata;
 pub mod com_and_pub_signature;
 pub mod commitment_signature;
+pub mod compact_output;
 pub mod consensus_constants;
 pub mod historical_block;
+pub mod network_alert;
 pub mod new_block_template;
 pub mod output_features;
+pub mod payment_proof;
 pub mod peer;
+pub mod peer_connection;
 pub mod proof_of_work;
 pub mod sidechain_feature;
 pub mod signature;
@@ -0,0 +1,79 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::convert::{TryFrom, TryInto};
+
+use tari_common_types::{
+    tari_address::TariAddress,
+    types::{FixedHash, PrivateKey},
+};
+use tari_core::transactions::{payment_proof::PaymentProof, tari_amount::MicroMinotari};
+use tari_utilities::ByteArray;
+
+use crate::tari_rpc as grpc;
+
+impl TryFrom<grpc::PaymentProof> for PaymentProof {
+    type Error = String;
+
+    fn try_from(proof: grpc::PaymentProof) -> Result<Self, Self::Error> {
+        let sender_address =
+            TariAddress::from_bytes(&proof.sender_address).map_err(|err| format!("Invalid sender address: {}", err))?;
+        let recipient_address = TariAddress::from_bytes(&proof.recipient_address)
+            .map_err(|err| format!("Invalid recipient address: {}", err))?;
+        let kernel_excess_sig = proof
+            .kernel_excess_sig
+            .ok_or_else(|| "Kernel excess signature not provided".to_string())?
+            .try_into()?;
+        let output_hash =
+            FixedHash::try_from(proof.output_hash).map_err(|err| format!("Invalid output hash: {}", err))?;
+        let sender_offset_private_key = PrivateKey::from_canonical_bytes(&proof.sender_offset_private_key)
+            .map_err(|err| format!("Invalid sender offset private key: {}", err))?;
+        let signature = proof
+            .signature
+            .ok_or_else(|| "Signature not provided".to_string())?
+            .try_into()?;
+
+        Ok(Self {
+            sender_address,
+            recipient_address,
+            amount: MicroMinotari::from(proof.amount),
+            kernel_excess_sig,
+            output_hash,
+            sender_offset_private_key,
+            signature,
+        })
+    }
+}
+
+impl From<PaymentProof> for grpc::PaymentProof {
+    fn from(proof: PaymentProof) -> Self {
+        Self {
+            sender_address: proof.sender_address.to_vec(),
+            recipient_address: proof.recipient_address.to_vec(),
+            amount: proof.amount.as_u64(),
+            kernel_excess_sig: Some(proof.kernel_excess_sig.into()),
+            output_hash: proof.output_hash.to_vec(),
+            sender_offset_private_key: proof.sender_offset_private_key.to_vec(),
+            signature: Some(proof.signature.into()),
+        }
+    }
+}
@@ -20,7 +20,11 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use tari_comms::{connectivity::ConnectivityStatus, net_address::MultiaddrWithStats, peer_manager::Peer};
+use tari_comms::{
+    connectivity::ConnectivityStatus,
+    net_address::MultiaddrWithStats,
+    peer_manager::{Peer, SyncQualityStats},
+};
 use tari_utilities::ByteArray;
 
 use crate::tari_rpc as grpc;
@@ -60,6 +64,20 @@ impl From<Peer> for grpc::Peer {
             features,
             supported_protocols,
             user_agent,
+            sync_quality: None,
+        }
+    }
+}
+
+impl From<SyncQualityStats> for grpc::SyncPeerQuality {
+    fn from(stats: SyncQualityStats) -> Self {
+        Self {
+            bytes_per_sec: stats.bytes_per_sec,
+            num_stalls: stats.num_stalls,
+            consecutive_stalls: stats.consecutive_stalls,
+            num_invalid_data: stats.num_invalid_data,
+            blacklisted_for_secs: stats.blacklisted_for.map(|d| d.as_secs()).unwrap_or_default(),
+            blacklist_reason: stats.blacklist_reason.unwrap_or_default(),
         }
     }
 }
@@ -0,0 +1,89 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use tari_comms::{
+    multiaddr::{Multiaddr, Protocol},
+    protocol::rpc::RpcSessionInfo,
+    PeerConnection,
+};
+use tari_utilities::ByteArray;
+
+use crate::tari_rpc as grpc;
+
+/// Converts a peer connection into its gRPC representation. RPC sessions are not known to the connection and must be
+/// added by the caller.
+impl From<&PeerConnection> for grpc::PeerConnectionDetails {
+    fn from(conn: &PeerConnection) -> Self {
+        Self {
+            node_id: conn.peer_node_id().to_vec(),
+            address: conn.address().to_string(),
+            transport: transport_name(conn.address()).to_string(),
+            direction: conn.direction().as_str().to_string(),
+            age_secs: conn.age().as_secs(),
+            bytes_read: conn.bytes_read(),
+            bytes_written: conn.bytes_written(),
+            num_substreams: u32::try_from(conn.substream_count()).unwrap_or(u32::MAX),
+            protocols: conn
+                .negotiated_protocols()
+                .into_iter()
+                .map(|(protocol, n)| grpc::NegotiatedProtocol {
+                    protocol: String::from_utf8_lossy(&protocol).into_owned(),
+                    num_substreams: n as u64,
+                })
+                .collect(),
+            rpc_sessions: Vec::new(),
+        }
+    }
+}
+
+impl From<&RpcSessionInfo> for grpc::RpcSessionDetails {
+    fn from(session: &RpcSessionInfo) -> Self {
+        Self {
+            protocol: String::from_utf8_lossy(&session.protocol).into_owned(),
+            age_secs: session.age.as_secs(),
+            num_requests: session.num_requests,
+            is_processing: session.current_method.is_some(),
+            current_method: session.current_method.unwrap_or_default(),
+            methods: session
+                .methods
+                .iter()
+                .map(|(method, n)| grpc::RpcMethodCount {
+                    method: *method,
+                    num_requests: *n,
+                })
+                .collect(),
+        }
+    }
+}
+
+fn transport_name(addr: &Multiaddr) -> &'static str {
+    for protocol in addr.iter() {
+        match protocol {
+            Protocol::Onion(..) | Protocol::Onion3(_) => return "tor",
+            Protocol::Memory(_) => return "memory",
+            Protocol::Tcp(_) => return "tcp",
+            Protocol::Udp(_) => return "udp",
+            _ => {},
+        }
+    }
+    "unknown"
+}
@@ -24,6 +24,7 @@ use std::convert::{TryFrom, TryInto};
 
 use tari_common_types::types::{PublicKey, Signature};
 use tari_core::transactions::transaction_components::{
+    ApplicationData,
     BuildInfo,
     CodeTemplateRegistration,
     ConfidentialOutputData,
@@ -58,6 +59,9 @@ impl From<SideChainFeature> for grpc::side_chain_feature::SideChainFeature {
             SideChainFeature::ConfidentialOutput(output_data) => {
                 grpc::side_chain_feature::SideChainFeature::ConfidentialOutput(output_data.into())
             },
+            SideChainFeature::ApplicationData(data) => {
+                grpc::side_chain_feature::SideChainFeature::ApplicationData(data.into())
+            },
         }
     }
 }
@@ -76,6 +80,9 @@ impl TryFrom<grpc::side_chain_feature::SideChainFeature> for SideChainFeature {
             grpc::side_chain_feature::SideChainFeature::ConfidentialOutput(output_data) => {
                 Ok(SideChainFeature::ConfidentialOutput(output_data.try_into()?))
             },
+            grpc::side_chain_feature::SideChainFeature::ApplicationData(data) => {
+                Ok(SideChainFeature::ApplicationData(data.try_into()?))
+            },
         }
     }
 }
@@ -168,6 +175,30 @@ impl From<ConfidentialOutputData> for grpc::ConfidentialOutputData {
     }
 }
 
+// -------------------------------- ApplicationData -------------------------------- //
+impl TryFrom<grpc::ApplicationData> for ApplicationData {
+    type Error = String;
+
+    fn try_from(value: grpc::ApplicationData) -> Result<Self, Self::Error> {
+        Ok(ApplicationData {
+            application_id: value.application_id,
+            payload: value
+                .payload
+                .try_into()
+                .map_err(|_| "Application data payload is too large")?,
+        })
+    }
+}
+
+impl From<ApplicationData> for grpc::ApplicationData {
+    fn from(value: ApplicationData) -> Self {
+        Self {
+            application_id: value.application_id,
+            payload: value.payload.to_vec(),
+        }
+    }
+}
+
 // -------------------------------- TemplateType -------------------------------- //
 impl TryFrom<grpc::TemplateType> for TemplateType {
     type Error = String;
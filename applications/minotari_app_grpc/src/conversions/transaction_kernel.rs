@@ -22,7 +22,7 @@
 
 use std::convert::{TryFrom, TryInto};
 
-use tari_common_types::types::Commitment;
+use tari_common_types::types::{Commitment, PublicKey};
 use tari_core::transactions::{
     tari_amount::MicroMinotari,
     transaction_components::{KernelFeatures, TransactionKernel, TransactionKernelVersion},
@@ -53,6 +53,14 @@ impl TryFrom<grpc::TransactionKernel> for TransactionKernel {
                     .map_err(|err| format!("Burn commitment could not be converted:{}", err))?,
             )
         };
+        let burn_claim_public_key = if kernel.burn_claim_public_key.is_empty() {
+            None
+        } else {
+            Some(
+                PublicKey::from_canonical_bytes(&kernel.burn_claim_public_key)
+                    .map_err(|err| format!("Burn claim public key could not be converted:{}", err))?,
+            )
+        };
 
         Ok(Self::new(
             TransactionKernelVersion::try_from(
@@ -65,6 +73,7 @@ impl TryFrom<grpc::TransactionKernel> for TransactionKernel {
             excess,
             excess_sig,
             commitment,
+            burn_claim_public_key,
         ))
     }
 }
@@ -89,6 +98,10 @@ impl From<TransactionKernel> for grpc::TransactionKernel {
             hash,
             version: kernel.version as u32,
             burn_commitment: commitment,
+            burn_claim_public_key: kernel
+                .burn_claim_public_key
+                .map(|key| key.as_bytes().to_vec())
+                .unwrap_or_default(),
         }
     }
 }
@@ -23,8 +23,13 @@ pub mod authentication;
 
 pub mod conversions;
 
+pub mod reflection;
+
 pub mod tls;
 #[allow(clippy::all, clippy::pedantic)]
 pub mod tari_rpc {
     tonic::include_proto!("tari.rpc");
+
+    /// The encoded file descriptor set of all Tari RPC services, used for gRPC server reflection
+    pub const FILE_DESCRIPTOR_SET: &[u8] = tonic::include_file_descriptor_set!("tari_rpc_descriptor");
 }
@@ -0,0 +1,22 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! gRPC server reflection for the Tari RPC services, allowing tools such as `grpcurl` to discover the available
+//! services and methods without access to the proto files.
+
+use tonic_reflection::server::{
+    v1::{ServerReflection, ServerReflectionServer},
+    Builder,
+    Error,
+};
+
+use crate::tari_rpc::FILE_DESCRIPTOR_SET;
+
+/// Builds a server reflection service describing all Tari RPC services as well as the standard `grpc.health.v1.Health`
+/// service.
+pub fn reflection_service() -> Result<ServerReflectionServer<impl ServerReflection>, Error> {
+    Builder::configure()
+        .register_encoded_file_descriptor_set(FILE_DESCRIPTOR_SET)
+        .register_encoded_file_descriptor_set(tonic_health::pb::FILE_DESCRIPTOR_SET)
+        .build_v1()
+}
@@ -0,0 +1,307 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! The backup archive format.
+//!
+//! An archive is a fixed header followed by the XChaCha20-Poly1305 encryption of the serialized snapshot:
+//!
+//! | field      | size (bytes) |
+//! |------------|--------------|
+//! | magic      | 8            |
+//! | version    | 1            |
+//! | salt       | 16           |
+//! | nonce      | 24           |
+//! | ciphertext | remainder    |
+//!
+//! The encryption key is derived from the passphrase and salt with Argon2id, and the header is bound to the
+//! ciphertext as associated data, so any modification of the archive is detected on decryption.
+
+use std::{
+    convert::TryFrom,
+    fs::OpenOptions,
+    io::Write,
+    mem::size_of,
+    path::{Path, PathBuf},
+};
+
+use chacha20poly1305::{
+    aead::{Aead, Payload},
+    Key,
+    KeyInit,
+    XChaCha20Poly1305,
+    XNonce,
+};
+use rand::{rngs::OsRng, RngCore};
+use tari_utilities::{hidden::Hidden, SafePassword};
+
+use super::BackupError;
+
+/// The file extension of backup archives
+pub const ARCHIVE_EXTENSION: &str = "tbak";
+
+const MAGIC: &[u8; 8] = b"TARIBAK\0";
+const VERSION: u8 = 1;
+const SALT_SIZE: usize = 16;
+const HEADER_SIZE: usize = MAGIC.len() + 1 + SALT_SIZE + size_of::<XNonce>();
+
+/// A named file in a snapshot
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveEntry {
+    pub name: String,
+    pub data: Vec<u8>,
+}
+
+impl ArchiveEntry {
+    pub fn new<T: Into<String>>(name: T, data: Vec<u8>) -> Self {
+        Self {
+            name: name.into(),
+            data,
+        }
+    }
+}
+
+/// The decrypted contents of a backup archive
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Snapshot {
+    /// Unix timestamp of when the snapshot was taken
+    pub created_at: u64,
+    pub entries: Vec<ArchiveEntry>,
+}
+
+impl Snapshot {
+    pub fn new(created_at: u64, entries: Vec<ArchiveEntry>) -> Result<Self, BackupError> {
+        for entry in &entries {
+            validate_entry_name(&entry.name)?;
+        }
+        Ok(Self { created_at, entries })
+    }
+
+    /// Serializes and encrypts the snapshot into an archive
+    pub fn encrypt(&self, passphrase: &SafePassword) -> Result<Vec<u8>, BackupError> {
+        let mut header = Vec::with_capacity(HEADER_SIZE);
+        header.extend_from_slice(MAGIC);
+        header.push(VERSION);
+        let mut salt = [0u8; SALT_SIZE];
+        OsRng.fill_bytes(&mut salt);
+        header.extend_from_slice(&salt);
+        let mut nonce = [0u8; size_of::<XNonce>()];
+        OsRng.fill_bytes(&mut nonce);
+        header.extend_from_slice(&nonce);
+
+        let cipher = derive_cipher(passphrase, &salt)?;
+        let plaintext = Hidden::hide(self.to_bytes()?);
+        let ciphertext = cipher
+            .encrypt(XNonce::from_slice(&nonce), Payload {
+                msg: plaintext.reveal(),
+                aad: &header,
+            })
+            .map_err(|e| BackupError::Encryption(e.to_string()))?;
+
+        let mut archive = header;
+        archive.extend_from_slice(&ciphertext);
+        Ok(archive)
+    }
+
+    /// Authenticates and decrypts an archive
+    pub fn decrypt(archive: &[u8], passphrase: &SafePassword) -> Result<Self, BackupError> {
+        if archive.len() < HEADER_SIZE {
+            return Err(BackupError::InvalidArchive("The archive is too short".to_string()));
+        }
+        let (header, ciphertext) = archive.split_at(HEADER_SIZE);
+        if !header.starts_with(MAGIC) {
+            return Err(BackupError::InvalidArchive("This is not a backup archive".to_string()));
+        }
+        let version = header[MAGIC.len()];
+        if version != VERSION {
+            return Err(BackupError::InvalidArchive(format!(
+                "Unsupported archive version {}",
+                version
+            )));
+        }
+        let salt = &header[MAGIC.len() + 1..MAGIC.len() + 1 + SALT_SIZE];
+        let nonce = &header[MAGIC.len() + 1 + SALT_SIZE..];
+
+        let cipher = derive_cipher(passphrase, salt)?;
+        let plaintext = Hidden::hide(
+            cipher
+                .decrypt(XNonce::from_slice(nonce), Payload {
+                    msg: ciphertext,
+                    aad: header,
+                })
+                .map_err(|_| BackupError::DecryptionFailed)?,
+        );
+        Self::from_bytes(plaintext.reveal())
+    }
+
+    /// Writes the entries of the snapshot as files in `dir`, which is created if necessary. Existing files are never
+    /// overwritten.
+    pub fn restore_to(&self, dir: &Path) -> Result<Vec<PathBuf>, BackupError> {
+        std::fs::create_dir_all(dir)?;
+        let mut written = Vec::with_capacity(self.entries.len());
+        for entry in &self.entries {
+            validate_entry_name(&entry.name)?;
+            let path = dir.join(&entry.name);
+            let mut file = OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&path)
+                .map_err(|e| match e.kind() {
+                    std::io::ErrorKind::AlreadyExists => BackupError::FileExists(path.display().to_string()),
+                    _ => e.into(),
+                })?;
+            file.write_all(&entry.data)?;
+            file.sync_all()?;
+            written.push(path);
+        }
+        Ok(written)
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>, BackupError> {
+        let size = self
+            .entries
+            .iter()
+            .map(|e| size_of::<u16>() + e.name.len() + size_of::<u64>() + e.data.len())
+            .sum::<usize>();
+        let mut buf = Vec::with_capacity(size_of::<u64>() + size_of::<u32>() + size);
+        buf.extend_from_slice(&self.created_at.to_le_bytes());
+        let count = u32::try_from(self.entries.len())
+            .map_err(|_| BackupError::InvalidArchive("Too many entries".to_string()))?;
+        buf.extend_from_slice(&count.to_le_bytes());
+        for entry in &self.entries {
+            let name_len = u16::try_from(entry.name.len())
+                .map_err(|_| BackupError::InvalidArchive(format!("Entry name '{}' is too long", entry.name)))?;
+            buf.extend_from_slice(&name_len.to_le_bytes());
+            buf.extend_from_slice(entry.name.as_bytes());
+            let data_len = u64::try_from(entry.data.len())
+                .map_err(|_| BackupError::InvalidArchive(format!("Entry '{}' is too large", entry.name)))?;
+            buf.extend_from_slice(&data_len.to_le_bytes());
+            buf.extend_from_slice(&entry.data);
+        }
+        Ok(buf)
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, BackupError> {
+        let mut reader = Reader(bytes);
+        let created_at = u64::from_le_bytes(reader.array()?);
+        let count = u32::from_le_bytes(reader.array()?);
+        let mut entries = Vec::new();
+        for _ in 0..count {
+            let name_len = usize::from(u16::from_le_bytes(reader.array()?));
+            let name = String::from_utf8(reader.take(name_len)?.to_vec())
+                .map_err(|_| BackupError::InvalidArchive("Entry name is not valid UTF-8".to_string()))?;
+            let data_len = usize::try_from(u64::from_le_bytes(reader.array()?))
+                .map_err(|_| BackupError::InvalidArchive("Entry is too large".to_string()))?;
+            let data = reader.take(data_len)?.to_vec();
+            entries.push(ArchiveEntry { name, data });
+        }
+        if !reader.0.is_empty() {
+            return Err(BackupError::InvalidArchive("Unexpected trailing data".to_string()));
+        }
+        Self::new(created_at, entries)
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], BackupError> {
+        if self.0.len() < n {
+            return Err(BackupError::InvalidArchive("Unexpected end of data".to_string()));
+        }
+        let (head, tail) = self.0.split_at(n);
+        self.0 = tail;
+        Ok(head)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], BackupError> {
+        let mut array = [0u8; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+}
+
+/// Entries are restored as files directly inside the output directory, so their names must not contain path
+/// separators or refer to a parent directory
+fn validate_entry_name(name: &str) -> Result<(), BackupError> {
+    if name.is_empty() || name == "." || name == ".." || name.contains(['/', '\\', '\0']) {
+        return Err(BackupError::InvalidArchive(format!("Invalid entry name '{}'", name)));
+    }
+    Ok(())
+}
+
+fn derive_cipher(passphrase: &SafePassword, salt: &[u8]) -> Result<XChaCha20Poly1305, BackupError> {
+    let params = argon2::Params::new(46 * 1024, 1, 1, Some(size_of::<Key>()))
+        .map_err(|e| BackupError::Encryption(e.to_string()))?;
+    let mut key = Hidden::hide([0u8; size_of::<Key>()]);
+    argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params)
+        .hash_password_into(passphrase.reveal(), salt, key.reveal_mut())
+        .map_err(|e| BackupError::Encryption(e.to_string()))?;
+    Ok(XChaCha20Poly1305::new(Key::from_slice(key.reveal())))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn snapshot() -> Snapshot {
+        Snapshot::new(1_700_000_000, vec![
+            ArchiveEntry::new("console_wallet.db", vec![1, 2, 3, 4]),
+            ArchiveEntry::new("chain_metadata.json", b"{}".to_vec()),
+            ArchiveEntry::new("empty", vec![]),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn it_roundtrips() {
+        let passphrase = SafePassword::from("correct horse");
+        let archive = snapshot().encrypt(&passphrase).unwrap();
+        assert!(archive.starts_with(MAGIC));
+        assert_eq!(Snapshot::decrypt(&archive, &passphrase).unwrap(), snapshot());
+    }
+
+    #[test]
+    fn it_rejects_a_wrong_passphrase_or_tampering() {
+        let passphrase = SafePassword::from("correct horse");
+        let archive = snapshot().encrypt(&passphrase).unwrap();
+        assert!(matches!(
+            Snapshot::decrypt(&archive, &SafePassword::from("battery staple")),
+            Err(BackupError::DecryptionFailed)
+        ));
+
+        // Every byte of the archive is authenticated, including the header
+        for index in [MAGIC.len() + 1, HEADER_SIZE - 1, archive.len() - 1] {
+            let mut tampered = archive.clone();
+            tampered[index] ^= 1;
+            assert!(matches!(
+                Snapshot::decrypt(&tampered, &passphrase),
+                Err(BackupError::DecryptionFailed)
+            ));
+        }
+        assert!(matches!(
+            Snapshot::decrypt(&archive[..HEADER_SIZE - 1], &passphrase),
+            Err(BackupError::InvalidArchive(_))
+        ));
+    }
+
+    #[test]
+    fn it_rejects_unsafe_entry_names() {
+        for name in ["", ".", "..", "../wallet.db", "dir/file", "dir\\file"] {
+            assert!(Snapshot::new(0, vec![ArchiveEntry::new(name, vec![])]).is_err());
+        }
+    }
+
+    #[test]
+    fn it_restores_without_overwriting() {
+        let dir = tempfile::tempdir().unwrap();
+        let written = snapshot().restore_to(dir.path()).unwrap();
+        assert_eq!(written.len(), 3);
+        assert_eq!(std::fs::read(dir.path().join("console_wallet.db")).unwrap(), vec![
+            1, 2, 3, 4
+        ]);
+        assert!(matches!(
+            snapshot().restore_to(dir.path()),
+            Err(BackupError::FileExists(_))
+        ));
+    }
+}
@@ -0,0 +1,28 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum BackupError {
+    #[error("No backup passphrase has been configured")]
+    NoPassphrase,
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid backup archive: {0}")]
+    InvalidArchive(String),
+    #[error("Could not decrypt the backup archive. The passphrase is wrong or the archive has been modified")]
+    DecryptionFailed,
+    #[error("Encryption error: {0}")]
+    Encryption(String),
+    #[error("Backup store error: {0}")]
+    Store(String),
+    #[error("{0} does not exist in the backup store")]
+    NotFound(String),
+    #[error("Could not collect the data to back up: {0}")]
+    Source(String),
+    #[error("Snapshot {0} failed verification after it was written")]
+    VerificationFailed(String),
+    #[error("Refusing to overwrite existing file {0}")]
+    FileExists(String),
+}
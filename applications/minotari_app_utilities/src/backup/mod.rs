@@ -0,0 +1,19 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Periodic encrypted backups of application data.
+//!
+//! A [BackupService] collects a set of named files from its [SnapshotSource] at a fixed interval, encrypts them into a
+//! single archive with a key derived from the configured passphrase, and writes the archive to a local directory, an
+//! S3-compatible object store or a WebDAV server. Each archive is read back and decrypted before older archives are
+//! rotated out, so the newest snapshot that survives rotation is always known to be restorable.
+
+mod archive;
+mod error;
+mod service;
+mod store;
+
+pub use archive::{ArchiveEntry, Snapshot, ARCHIVE_EXTENSION};
+pub use error::BackupError;
+pub use service::{restore_snapshot, BackupService, SnapshotSource};
+pub use store::BackupStore;
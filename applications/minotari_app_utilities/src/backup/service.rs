@@ -0,0 +1,209 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    future::Future,
+    path::{Path, PathBuf},
+};
+
+use futures::future::BoxFuture;
+use log::*;
+use tari_common_types::backup_config::BackupConfig;
+use tari_utilities::{epoch_time::EpochTime, SafePassword};
+use tokio::time::{self, MissedTickBehavior};
+
+use super::{ArchiveEntry, BackupError, BackupStore, Snapshot, ARCHIVE_EXTENSION};
+
+const LOG_TARGET: &str = "minotari::app_utilities::backup";
+
+/// Collects the files that make up a snapshot
+pub type SnapshotSource = Box<dyn Fn() -> BoxFuture<'static, Result<Vec<ArchiveEntry>, BackupError>> + Send + Sync>;
+
+pub struct BackupService {
+    name_prefix: String,
+    config: BackupConfig,
+    passphrase: SafePassword,
+    store: BackupStore,
+    source: SnapshotSource,
+}
+
+impl BackupService {
+    /// Creates a backup service whose archives are named `<name_prefix>-<timestamp>.tbak`. Services that share a
+    /// store must use different prefixes, since rotation only considers archives with its own prefix.
+    pub fn new<T: Into<String>>(
+        name_prefix: T,
+        config: BackupConfig,
+        source: SnapshotSource,
+    ) -> Result<Self, BackupError> {
+        let passphrase = config.passphrase.clone().ok_or(BackupError::NoPassphrase)?;
+        let store = BackupStore::from_config(&config)?;
+        Ok(Self {
+            name_prefix: name_prefix.into(),
+            config,
+            passphrase,
+            store,
+            source,
+        })
+    }
+
+    /// Takes a snapshot every configured interval, starting immediately, until `shutdown` resolves. Failed snapshots
+    /// are logged and retried at the next interval.
+    pub async fn run<S: Future<Output = ()>>(self, shutdown: S) {
+        info!(
+            target: LOG_TARGET,
+            "Backup service started, taking a snapshot every {:.0?}", self.config.interval
+        );
+        let mut interval = time::interval(self.config.interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        tokio::pin!(shutdown);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => match self.take_snapshot().await {
+                    Ok(name) => info!(target: LOG_TARGET, "Backup snapshot {} written and verified", name),
+                    Err(e) => error!(target: LOG_TARGET, "Backup snapshot failed: {}", e),
+                },
+                _ = &mut shutdown => break,
+            }
+        }
+        info!(target: LOG_TARGET, "Backup service stopped");
+    }
+
+    /// Takes a single snapshot, verifies it by reading it back from the store, then deletes archives in excess of
+    /// `max_snapshots`. Returns the name of the new archive.
+    pub async fn take_snapshot(&self) -> Result<String, BackupError> {
+        let created_at = EpochTime::now().as_u64();
+        let snapshot = Snapshot::new(created_at, (self.source)().await?)?;
+        let name = format!("{}-{:020}.{}", self.name_prefix, created_at, ARCHIVE_EXTENSION);
+        let passphrase = self.passphrase.clone();
+        let to_encrypt = snapshot.clone();
+        let archive = tokio::task::spawn_blocking(move || to_encrypt.encrypt(&passphrase))
+            .await
+            .map_err(|e| BackupError::Encryption(e.to_string()))??;
+        self.store.put(&name, archive).await?;
+
+        let stored = self.store.get(&name).await?;
+        let passphrase = self.passphrase.clone();
+        let verified = tokio::task::spawn_blocking(move || Snapshot::decrypt(&stored, &passphrase))
+            .await
+            .map_err(|e| BackupError::Encryption(e.to_string()))?;
+        if !matches!(verified, Ok(ref s) if *s == snapshot) {
+            // Leave the older snapshots untouched, they may be the only good ones
+            if let Err(e) = self.store.delete(&name).await {
+                warn!(target: LOG_TARGET, "Could not delete unverified snapshot {}: {}", name, e);
+            }
+            return Err(BackupError::VerificationFailed(name));
+        }
+
+        self.rotate().await?;
+        Ok(name)
+    }
+
+    async fn rotate(&self) -> Result<(), BackupError> {
+        let suffix = format!(".{}", ARCHIVE_EXTENSION);
+        let archives = self
+            .store
+            .list(&format!("{}-", self.name_prefix))
+            .await?
+            .into_iter()
+            .filter(|name| name.ends_with(&suffix))
+            .collect::<Vec<_>>();
+        let excess = archives.len().saturating_sub(self.config.max_snapshots.max(1));
+        for name in &archives[..excess] {
+            debug!(target: LOG_TARGET, "Deleting old backup snapshot {}", name);
+            self.store.delete(name).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Restores the archive `archive` into `output_dir`. `archive` is either the path of an archive file, or the name of
+/// an archive in the configured backup store.
+pub async fn restore_snapshot(
+    config: &BackupConfig,
+    archive: &str,
+    output_dir: &Path,
+) -> Result<Vec<PathBuf>, BackupError> {
+    let passphrase = config.passphrase.clone().ok_or(BackupError::NoPassphrase)?;
+    let data = if Path::new(archive).is_file() {
+        tokio::fs::read(archive).await?
+    } else {
+        BackupStore::from_config(config)?.get(archive).await?
+    };
+    let snapshot = tokio::task::spawn_blocking(move || Snapshot::decrypt(&data, &passphrase))
+        .await
+        .map_err(|e| BackupError::Encryption(e.to_string()))??;
+    info!(
+        target: LOG_TARGET,
+        "Restoring {} file(s) from snapshot taken at {}",
+        snapshot.entries.len(),
+        snapshot.created_at
+    );
+    snapshot.restore_to(output_dir)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn config(dir: &Path) -> BackupConfig {
+        BackupConfig {
+            enabled: true,
+            max_snapshots: 2,
+            path: dir.to_path_buf(),
+            passphrase: Some(SafePassword::from("passphrase")),
+            ..Default::default()
+        }
+    }
+
+    fn source() -> SnapshotSource {
+        Box::new(|| Box::pin(async { Ok(vec![ArchiveEntry::new("data.bin", vec![1, 2, 3])]) }))
+    }
+
+    #[test]
+    fn it_requires_a_passphrase() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = BackupConfig {
+            passphrase: None,
+            ..config(dir.path())
+        };
+        assert!(matches!(
+            BackupService::new("node", config, source()),
+            Err(BackupError::NoPassphrase)
+        ));
+    }
+
+    #[tokio::test]
+    async fn it_takes_rotates_and_restores_snapshots() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = config(&dir.path().join("backups"));
+        let service = BackupService::new("node", config.clone(), source()).unwrap();
+        let store = BackupStore::Local(config.path.clone());
+
+        // Archives with other prefixes are never rotated out
+        store.put("wallet-1.tbak", vec![]).await.unwrap();
+        for i in 1..=3 {
+            store.put(&format!("node-{:020}.tbak", i), vec![]).await.unwrap();
+        }
+        let name = service.take_snapshot().await.unwrap();
+        let mut remaining = store.list("").await.unwrap();
+        remaining.retain(|n| n.starts_with("node-"));
+        assert_eq!(remaining, vec![format!("node-{:020}.tbak", 3), name.clone()]);
+        assert_eq!(store.list("wallet-").await.unwrap(), vec!["wallet-1.tbak"]);
+
+        let restored = restore_snapshot(&config, &name, &dir.path().join("restored"))
+            .await
+            .unwrap();
+        assert_eq!(restored, vec![dir.path().join("restored").join("data.bin")]);
+        assert_eq!(std::fs::read(&restored[0]).unwrap(), vec![1, 2, 3]);
+
+        let wrong_passphrase = BackupConfig {
+            passphrase: Some(SafePassword::from("wrong")),
+            ..config
+        };
+        let archive = wrong_passphrase.path.join(&name);
+        assert!(matches!(
+            restore_snapshot(&wrong_passphrase, archive.to_str().unwrap(), &dir.path().join("other")).await,
+            Err(BackupError::DecryptionFailed)
+        ));
+    }
+}
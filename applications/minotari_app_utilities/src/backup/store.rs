@@ -0,0 +1,513 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::path::PathBuf;
+
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use reqwest::{Method, StatusCode, Url};
+use sha2::{Digest, Sha256};
+use tari_common_types::backup_config::{BackupConfig, S3Config, WebDavConfig};
+use tari_utilities::hex::to_hex;
+use tokio::fs;
+
+use super::BackupError;
+
+/// Where backup archives are kept
+pub enum BackupStore {
+    Local(PathBuf),
+    S3(S3Store),
+    WebDav(WebDavStore),
+}
+
+impl BackupStore {
+    pub fn from_config(config: &BackupConfig) -> Result<Self, BackupError> {
+        Ok(Self::remote(config.s3.as_ref(), config.webdav.as_ref())?
+            .unwrap_or_else(|| Self::Local(config.path.clone())))
+    }
+
+    /// Returns the configured remote store, if any. It is an error to configure both.
+    pub fn remote(s3: Option<&S3Config>, webdav: Option<&WebDavConfig>) -> Result<Option<Self>, BackupError> {
+        match (s3, webdav) {
+            (Some(_), Some(_)) => Err(BackupError::Store(
+                "Only one of an S3 and a WebDAV store can be configured".to_string(),
+            )),
+            (Some(s3), None) => Ok(Some(Self::S3(S3Store::new(s3.clone())?))),
+            (None, Some(webdav)) => Ok(Some(Self::WebDav(WebDavStore::new(webdav.clone())?))),
+            (None, None) => Ok(None),
+        }
+    }
+
+    pub async fn put(&self, name: &str, data: Vec<u8>) -> Result<(), BackupError> {
+        match self {
+            Self::Local(dir) => {
+                fs::create_dir_all(dir).await?;
+                // Write to a temporary file first so that a partially written archive never has a valid name
+                let tmp = dir.join(format!("{}.tmp", name));
+                fs::write(&tmp, data).await?;
+                fs::rename(&tmp, dir.join(name)).await?;
+                Ok(())
+            },
+            Self::S3(s3) => s3.put(name, data).await,
+            Self::WebDav(webdav) => webdav.put(name, data).await,
+        }
+    }
+
+    /// Fails with [BackupError::NotFound] if there is no object with the name
+    pub async fn get(&self, name: &str) -> Result<Vec<u8>, BackupError> {
+        match self {
+            Self::Local(dir) => match fs::read(dir.join(name)).await {
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Err(BackupError::NotFound(name.to_string())),
+                result => Ok(result?),
+            },
+            Self::S3(s3) => s3.get(name).await,
+            Self::WebDav(webdav) => webdav.get(name).await,
+        }
+    }
+
+    pub async fn delete(&self, name: &str) -> Result<(), BackupError> {
+        match self {
+            Self::Local(dir) => Ok(fs::remove_file(dir.join(name)).await?),
+            Self::S3(s3) => s3.delete(name).await,
+            Self::WebDav(webdav) => webdav.delete(name).await,
+        }
+    }
+
+    /// Returns the names of all stored objects that start with `prefix`, in ascending order
+    pub async fn list(&self, prefix: &str) -> Result<Vec<String>, BackupError> {
+        let mut names = match self {
+            Self::Local(dir) => {
+                let mut names = Vec::new();
+                let mut entries = match fs::read_dir(dir).await {
+                    Ok(entries) => entries,
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(names),
+                    Err(e) => return Err(e.into()),
+                };
+                while let Some(entry) = entries.next_entry().await? {
+                    if let Some(name) = entry.file_name().to_str() {
+                        if name.starts_with(prefix) {
+                            names.push(name.to_string());
+                        }
+                    }
+                }
+                names
+            },
+            Self::S3(s3) => s3.list(prefix).await?,
+            Self::WebDav(webdav) => webdav.list(prefix).await?,
+        };
+        names.sort();
+        Ok(names)
+    }
+}
+
+/// A minimal client for an S3-compatible object store, supporting only the four object operations that backups need.
+/// Requests use path-style addressing and are signed with AWS signature version 4.
+pub struct S3Store {
+    config: S3Config,
+    endpoint: Url,
+    client: reqwest::Client,
+}
+
+impl S3Store {
+    pub fn new(config: S3Config) -> Result<Self, BackupError> {
+        let endpoint = Url::parse(&config.endpoint)
+            .map_err(|e| BackupError::Store(format!("Invalid S3 endpoint '{}': {}", config.endpoint, e)))?;
+        if endpoint.host_str().is_none() {
+            return Err(BackupError::Store(format!(
+                "S3 endpoint '{}' has no host",
+                config.endpoint
+            )));
+        }
+        Ok(Self {
+            config,
+            endpoint,
+            client: reqwest::Client::new(),
+        })
+    }
+
+    async fn put(&self, name: &str, data: Vec<u8>) -> Result<(), BackupError> {
+        self.send(Method::PUT, &self.object_key(name), &[], data).await?;
+        Ok(())
+    }
+
+    async fn get(&self, name: &str) -> Result<Vec<u8>, BackupError> {
+        self.send(Method::GET, &self.object_key(name), &[], Vec::new()).await
+    }
+
+    async fn delete(&self, name: &str) -> Result<(), BackupError> {
+        self.send(Method::DELETE, &self.object_key(name), &[], Vec::new())
+            .await?;
+        Ok(())
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<String>, BackupError> {
+        let key_prefix = self.object_key(prefix);
+        let strip = key_prefix.len() - prefix.len();
+        let mut names = Vec::new();
+        let mut continuation_token = None;
+        loop {
+            let mut query = vec![("list-type", "2".to_string()), ("prefix", key_prefix.clone())];
+            if let Some(token) = continuation_token.take() {
+                query.push(("continuation-token", token));
+            }
+            let body = self.send(Method::GET, "", &query, Vec::new()).await?;
+            let body = String::from_utf8_lossy(&body);
+            names.extend(
+                xml_values(&body, "Key")
+                    .into_iter()
+                    .filter_map(|key| key.get(strip..).map(str::to_string)),
+            );
+            match xml_values(&body, "NextContinuationToken").pop() {
+                Some(token) if xml_values(&body, "IsTruncated").first().map(String::as_str) == Some("true") => {
+                    continuation_token = Some(token);
+                },
+                _ => break,
+            }
+        }
+        Ok(names)
+    }
+
+    fn object_key(&self, name: &str) -> String {
+        let prefix = self.config.prefix.trim_matches('/');
+        if prefix.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}/{}", prefix, name)
+        }
+    }
+
+    async fn send(
+        &self,
+        method: Method,
+        key: &str,
+        query: &[(&str, String)],
+        body: Vec<u8>,
+    ) -> Result<Vec<u8>, BackupError> {
+        let mut path = format!("/{}", uri_encode(&self.config.bucket, true));
+        if !key.is_empty() {
+            path.push('/');
+            path.push_str(&uri_encode(key, false));
+        }
+        let mut query = query
+            .iter()
+            .map(|(k, v)| format!("{}={}", uri_encode(k, true), uri_encode(v, true)))
+            .collect::<Vec<_>>();
+        query.sort();
+        let query = query.join("&");
+
+        let host = match self.endpoint.port() {
+            Some(port) => format!("{}:{}", self.endpoint.host_str().unwrap_or_default(), port),
+            None => self.endpoint.host_str().unwrap_or_default().to_string(),
+        };
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let payload_hash = to_hex(&Sha256::digest(&body));
+
+        let canonical_request = format!(
+            "{}\n{}\n{}\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\n{}\n{}",
+            method, path, query, host, payload_hash, amz_date, SIGNED_HEADERS, payload_hash
+        );
+        let scope = format!("{}/{}/s3/aws4_request", date, self.config.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            to_hex(&Sha256::digest(canonical_request.as_bytes()))
+        );
+        let key = signing_key(self.config.secret_key.reveal(), &date, &self.config.region, "s3");
+        let signature = to_hex(&hmac_sha256(&key, string_to_sign.as_bytes()));
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            self.config.access_key, scope, SIGNED_HEADERS, signature
+        );
+
+        let mut url = self.endpoint.clone();
+        url.set_path(&path);
+        url.set_query(Some(&query).filter(|q| !q.is_empty()).map(String::as_str));
+        let response = self
+            .client
+            .request(method.clone(), url)
+            .header("x-amz-content-sha256", payload_hash)
+            .header("x-amz-date", amz_date)
+            .header("authorization", authorization)
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| BackupError::Store(e.to_string()))?;
+        let status = response.status();
+        let bytes = response.bytes().await.map_err(|e| BackupError::Store(e.to_string()))?;
+        if status.is_success() || (method == Method::DELETE && status == StatusCode::NOT_FOUND) {
+            Ok(bytes.to_vec())
+        } else if status == StatusCode::NOT_FOUND {
+            Err(BackupError::NotFound(key.to_string()))
+        } else {
+            Err(BackupError::Store(format!(
+                "{} {} failed with {}: {}",
+                method,
+                path,
+                status,
+                String::from_utf8_lossy(&bytes)
+            )))
+        }
+    }
+}
+
+const SIGNED_HEADERS: &str = "host;x-amz-content-sha256;x-amz-date";
+
+/// A minimal WebDAV client that stores objects as the files of a single collection, using basic authentication
+pub struct WebDavStore {
+    config: WebDavConfig,
+    collection: Url,
+    client: reqwest::Client,
+}
+
+impl WebDavStore {
+    pub fn new(config: WebDavConfig) -> Result<Self, BackupError> {
+        let mut collection = Url::parse(&config.url)
+            .map_err(|e| BackupError::Store(format!("Invalid WebDAV URL '{}': {}", config.url, e)))?;
+        if collection.cannot_be_a_base() {
+            return Err(BackupError::Store(format!("Invalid WebDAV URL '{}'", config.url)));
+        }
+        if !collection.path().ends_with('/') {
+            let path = format!("{}/", collection.path());
+            collection.set_path(&path);
+        }
+        Ok(Self {
+            config,
+            collection,
+            client: reqwest::Client::new(),
+        })
+    }
+
+    async fn put(&self, name: &str, data: Vec<u8>) -> Result<(), BackupError> {
+        self.send(Method::PUT, name, &[], data).await?;
+        Ok(())
+    }
+
+    async fn get(&self, name: &str) -> Result<Vec<u8>, BackupError> {
+        self.send(Method::GET, name, &[], Vec::new()).await
+    }
+
+    async fn delete(&self, name: &str) -> Result<(), BackupError> {
+        self.send(Method::DELETE, name, &[], Vec::new()).await?;
+        Ok(())
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<String>, BackupError> {
+        let propfind = Method::from_bytes(b"PROPFIND").expect("PROPFIND is a valid method");
+        let body = self
+            .send(propfind, "", &[("depth", "1")], PROPFIND_BODY.as_bytes().to_vec())
+            .await?;
+        let body = String::from_utf8_lossy(&body);
+        let collection_path = self.collection.path();
+        Ok(xml_values_any_namespace(&body, "href")
+            .into_iter()
+            .filter_map(|href| {
+                // Servers return either absolute URLs or absolute paths
+                let path = Url::parse(&href).map(|url| url.path().to_string()).unwrap_or(href);
+                let name = uri_decode(path.strip_prefix(collection_path)?);
+                Some(name).filter(|name| !name.is_empty() && !name.contains('/') && name.starts_with(prefix))
+            })
+            .collect())
+    }
+
+    async fn send(
+        &self,
+        method: Method,
+        name: &str,
+        headers: &[(&str, &str)],
+        body: Vec<u8>,
+    ) -> Result<Vec<u8>, BackupError> {
+        let url = self
+            .collection
+            .join(&uri_encode(name, true))
+            .map_err(|e| BackupError::Store(format!("Invalid WebDAV object name '{}': {}", name, e)))?;
+        let mut request = self.client.request(method.clone(), url).body(body);
+        if !self.config.username.is_empty() {
+            request = request.basic_auth(
+                &self.config.username,
+                self.config
+                    .password
+                    .as_ref()
+                    .map(|p| String::from_utf8_lossy(p.reveal()).into_owned()),
+            );
+        }
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+        let response = request.send().await.map_err(|e| BackupError::Store(e.to_string()))?;
+        let status = response.status();
+        let bytes = response.bytes().await.map_err(|e| BackupError::Store(e.to_string()))?;
+        if status.is_success() || (method == Method::DELETE && status == StatusCode::NOT_FOUND) {
+            Ok(bytes.to_vec())
+        } else if status == StatusCode::NOT_FOUND {
+            Err(BackupError::NotFound(name.to_string()))
+        } else {
+            Err(BackupError::Store(format!(
+                "WebDAV {} {} failed with {}",
+                method, name, status
+            )))
+        }
+    }
+}
+
+const PROPFIND_BODY: &str = r#"<d:propfind xmlns:d="DAV:"><d:prop><d:resourcetype/></d:prop></d:propfind>"#;
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn signing_key(secret_key: &[u8], date: &str, region: &str, service: &str) -> Vec<u8> {
+    let mut key = b"AWS4".to_vec();
+    key.extend_from_slice(secret_key);
+    let key = hmac_sha256(&key, date.as_bytes());
+    let key = hmac_sha256(&key, region.as_bytes());
+    let key = hmac_sha256(&key, service.as_bytes());
+    hmac_sha256(&key, b"aws4_request")
+}
+
+/// Percent-encodes everything except the unreserved characters, as SigV4 requires. `/` is left as is unless
+/// `encode_slash` is set.
+fn uri_encode(input: &str, encode_slash: bool) -> String {
+    let mut encoded = String::with_capacity(input.len());
+    for byte in input.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => encoded.push(char::from(byte)),
+            b'/' if !encode_slash => encoded.push('/'),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+fn uri_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes.get(i + 1..i + 3).and_then(|hex| std::str::from_utf8(hex).ok());
+        match hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+            Some(byte) if bytes[i] == b'%' => {
+                decoded.push(byte);
+                i += 3;
+            },
+            _ => {
+                decoded.push(bytes[i]);
+                i += 1;
+            },
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Like [xml_values], but matches `<tag>` in any namespace, e.g. `<d:tag>`, since WebDAV servers choose their own
+/// namespace prefixes
+fn xml_values_any_namespace(xml: &str, tag: &str) -> Vec<String> {
+    let mut values = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start + 1..];
+        let end = match rest.find('>') {
+            Some(end) => end,
+            None => break,
+        };
+        let name = &rest[..end];
+        rest = &rest[end + 1..];
+        if name.starts_with('/') || name.rsplit(':').next() != Some(tag) {
+            continue;
+        }
+        match rest.find(&format!("</{}>", name)) {
+            Some(close) => {
+                values.push(rest[..close].replace("&amp;", "&"));
+                rest = &rest[close..];
+            },
+            None => break,
+        }
+    }
+    values
+}
+
+/// Extracts the text of every `<tag>` element. S3 list responses are simple enough that this avoids an XML parser.
+fn xml_values(xml: &str, tag: &str) -> Vec<String> {
+    let open = format!("<{}>", tag);
+    let close = format!("</{}>", tag);
+    let mut values = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find(&open) {
+        rest = &rest[start + open.len()..];
+        match rest.find(&close) {
+            Some(end) => {
+                values.push(rest[..end].replace("&amp;", "&"));
+                rest = &rest[end + close.len()..];
+            },
+            None => break,
+        }
+    }
+    values
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_derives_the_sigv4_signing_key() {
+        // Example from the AWS signature version 4 documentation
+        let key = signing_key(
+            b"wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            "20120215",
+            "us-east-1",
+            "iam",
+        );
+        assert_eq!(
+            to_hex(&key),
+            "f4780e2d9f65fa895f9c67b32ce1baf0b0d8a43505a000a1a9e090d414db404d"
+        );
+    }
+
+    #[test]
+    fn it_encodes_uris() {
+        assert_eq!(uri_encode("a b/c~d", false), "a%20b/c~d");
+        assert_eq!(uri_encode("a b/c~d", true), "a%20b%2Fc~d");
+    }
+
+    #[test]
+    fn it_extracts_xml_values() {
+        let xml = "<ListBucketResult><IsTruncated>false</IsTruncated><Contents><Key>p/a.tbak</Key></\
+                   Contents><Contents><Key>p/b&amp;c.tbak</Key></Contents></ListBucketResult>";
+        assert_eq!(xml_values(xml, "Key"), vec!["p/a.tbak", "p/b&c.tbak"]);
+        assert_eq!(xml_values(xml, "IsTruncated"), vec!["false"]);
+        assert!(xml_values(xml, "NextContinuationToken").is_empty());
+    }
+
+    #[test]
+    fn it_extracts_webdav_hrefs() {
+        let xml = "<d:multistatus xmlns:d=\"DAV:\"><d:response><d:href>/dav/tari/</d:href></d:response><d:response><d:\
+                   href>/dav/tari/a%20b.tsync</d:href></d:response><D:response><D:href>https://h/dav/tari/c.tsync</\
+                   D:href></D:response></d:multistatus>";
+        assert_eq!(xml_values_any_namespace(xml, "href"), vec![
+            "/dav/tari/",
+            "/dav/tari/a%20b.tsync",
+            "https://h/dav/tari/c.tsync"
+        ]);
+        assert_eq!(uri_decode("a%20b.tsync"), "a b.tsync");
+        assert_eq!(uri_decode("100%"), "100%");
+    }
+
+    #[tokio::test]
+    async fn it_stores_files_locally() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = BackupStore::Local(dir.path().join("backups"));
+        assert!(store.list("node-").await.unwrap().is_empty());
+        store.put("node-2.tbak", vec![2]).await.unwrap();
+        store.put("node-1.tbak", vec![1]).await.unwrap();
+        store.put("wallet-1.tbak", vec![3]).await.unwrap();
+        assert_eq!(store.list("node-").await.unwrap(), vec!["node-1.tbak", "node-2.tbak"]);
+        assert_eq!(store.get("node-2.tbak").await.unwrap(), vec![2]);
+        store.delete("node-1.tbak").await.unwrap();
+        assert_eq!(store.list("node-").await.unwrap(), vec!["node-2.tbak"]);
+    }
+}
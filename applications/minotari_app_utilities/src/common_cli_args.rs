@@ -22,7 +22,12 @@
 use std::{error::Error, path::PathBuf};
 
 use clap::Args;
-use tari_common::configuration::{ConfigOverrideProvider, Network};
+use tari_common::{
+    configuration::{ConfigOverrideProvider, Network},
+    LogFormat,
+};
+
+use crate::network_profile::{set_active_profile, NetworkProfile, ProfileError};
 
 #[derive(Args, Debug)]
 pub struct CommonCliArgs {
@@ -46,10 +51,20 @@ pub struct CommonCliArgs {
     #[clap(long, alias = "log_path")]
     pub log_path: Option<PathBuf>,
 
+    /// The log output format: `text` as configured in the log configuration file, or `json` to write structured JSON
+    /// lines from all appenders
+    #[clap(long, env = "TARI_LOG_FORMAT")]
+    pub log_format: Option<LogFormat>,
+
     /// Supply a network (overrides existing configuration)
     #[clap(long, env = "TARI_NETWORK")]
     pub network: Option<Network>,
 
+    /// The network profile to use, which is also made the active profile for later runs. Profiles are named after
+    /// their network (e.g. esmeralda), or given a custom name, in which case --network is required the first time.
+    #[clap(long, env = "TARI_PROFILE")]
+    pub profile: Option<String>,
+
     /// Overrides for properties in the config file (use the fully qualified key name!!), e.g.
     /// -p base_node.network=esmeralda
     /// -p base_node.grpc_server_allow_methods="get_tokens_in_circulation, get_sync_progress, get_mempool_stats"
@@ -88,8 +103,28 @@ impl CommonCliArgs {
     }
 
     pub fn get_base_path(&self) -> PathBuf {
-        let network = self.network.unwrap_or_default();
-        PathBuf::from(&self.base_path).join(network.to_string())
+        match self.profile {
+            Some(ref profile) => PathBuf::from(&self.base_path).join(profile),
+            None => {
+                let network = self.network.unwrap_or_default();
+                PathBuf::from(&self.base_path).join(network.to_string())
+            },
+        }
+    }
+
+    /// Resolves the network profile to start with (see [NetworkProfile::resolve]), and sets `profile` and `network`
+    /// accordingly. A profile given on the command line becomes the active profile.
+    pub fn resolve_network_profile(&mut self) -> Result<NetworkProfile, ProfileError> {
+        let base_path = PathBuf::from(&self.base_path);
+        let profile = NetworkProfile::resolve(&base_path, self.profile.as_deref(), self.network)?;
+        if self.profile.is_some() {
+            set_active_profile(&base_path, &profile)?;
+        } else {
+            profile.initialize(&base_path)?;
+        }
+        self.profile = Some(profile.name.clone());
+        self.network = Some(profile.network);
+        Ok(profile)
     }
 
     pub fn log_config_path(&self, application_name: &str) -> PathBuf {
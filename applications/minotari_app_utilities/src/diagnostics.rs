@@ -0,0 +1,303 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Diagnostic bundles for crash reports.
+//!
+//! [install_panic_handler] installs a panic hook that writes a single `.tar.gz` archive into the `diagnostics`
+//! directory of the application's base path and prints its path. The archive contains the panic message and backtrace,
+//! the last lines of each of the application's log files, the configuration file with secrets redacted, and the
+//! metadata fields the application recorded with [set_diagnostic_field]. Release builds abort on panic, which runs
+//! the hook first, so the bundle is also written for aborting panics.
+
+use std::{
+    any::Any,
+    backtrace::Backtrace,
+    collections::BTreeMap,
+    fs,
+    fs::File,
+    io,
+    io::{Read, Seek, SeekFrom},
+    panic,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        RwLock,
+    },
+    thread,
+};
+
+use flate2::{write::GzEncoder, Compression};
+
+use crate::{common_cli_args::CommonCliArgs, consts};
+
+/// The number of lines kept from the end of each log file
+const LOG_TAIL_LINES: usize = 200;
+/// The most that is read from the end of a log file to find its last lines
+const LOG_TAIL_BYTES: u64 = 512 * 1024;
+/// Configuration keys containing any of these are redacted from the bundle
+const SECRET_KEY_FRAGMENTS: &[&str] = &[
+    "password",
+    "passphrase",
+    "secret",
+    "seed",
+    "private",
+    "token",
+    "api_key",
+    "authentication",
+    "cookie",
+];
+const REDACTED: &str = "\"<redacted>\"";
+
+static DIAGNOSTIC_FIELDS: RwLock<BTreeMap<String, String>> = RwLock::new(BTreeMap::new());
+static BUNDLE_WRITTEN: AtomicBool = AtomicBool::new(false);
+
+/// Records a metadata field, such as the chain tip or wallet state, to be included in any diagnostics bundle. Setting a
+/// field again replaces its value.
+pub fn set_diagnostic_field<T: ToString>(key: &str, value: T) {
+    if let Ok(mut fields) = DIAGNOSTIC_FIELDS.write() {
+        fields.insert(key.to_string(), value.to_string());
+    }
+}
+
+/// Where the diagnostics bundle of an application is written and what goes into it
+#[derive(Debug, Clone)]
+pub struct DiagnosticsConfig {
+    /// The application name, which is also the name of its log directory, e.g. `base_node`
+    pub application: &'static str,
+    /// Bundles are written to the `diagnostics` directory within the base path
+    pub base_path: PathBuf,
+    /// The directory log4rs writes the application's logs to, i.e. `{{log_dir}}/log/<application>`
+    pub log_dir: PathBuf,
+    pub config_file: PathBuf,
+}
+
+impl DiagnosticsConfig {
+    pub fn new(application: &'static str, base_path: &Path, log_path: &Path, config_file: &Path) -> Self {
+        Self {
+            application,
+            base_path: base_path.to_path_buf(),
+            log_dir: log_path.join("log").join(application),
+            config_file: config_file.to_path_buf(),
+        }
+    }
+
+    /// The diagnostics configuration of an application started with `args`
+    pub fn from_cli(application: &'static str, args: &CommonCliArgs) -> Self {
+        let base_path = args.get_base_path();
+        let log_path = args.log_path.clone().unwrap_or_else(|| base_path.clone());
+        Self::new(application, &base_path, &log_path, &args.config_path())
+    }
+
+    fn output_dir(&self) -> PathBuf {
+        self.base_path.join("diagnostics")
+    }
+}
+
+/// Installs a panic hook that writes a diagnostics bundle for the first panic of the process, before running the
+/// previously installed hook
+pub fn install_panic_handler(config: DiagnosticsConfig) {
+    let previous_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if !BUNDLE_WRITTEN.swap(true, Ordering::SeqCst) {
+            let location = info
+                .location()
+                .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()))
+                .unwrap_or_else(|| "<unknown>".to_string());
+            let report = panic_report(info.payload(), &location, &Backtrace::force_capture());
+            match write_diagnostics_bundle(&config, &report) {
+                Ok(path) => eprintln!(
+                    "\nThe application has crashed. A diagnostics bundle was written to\n\n    {}\n\nPlease attach it \
+                     when reporting this problem at https://github.com/tari-project/tari/issues\n",
+                    path.display()
+                ),
+                Err(e) => eprintln!(
+                    "\nThe application has crashed. Could not write a diagnostics bundle: {}\n",
+                    e
+                ),
+            }
+        }
+        previous_hook(info);
+    }));
+}
+
+/// Writes a diagnostics bundle containing `report` and returns its path
+pub fn write_diagnostics_bundle(config: &DiagnosticsConfig, report: &str) -> Result<PathBuf, io::Error> {
+    let output_dir = config.output_dir();
+    fs::create_dir_all(&output_dir)?;
+    let name = format!(
+        "{}-{}",
+        config.application,
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    );
+    let path = output_dir.join(format!("{}.tar.gz", name));
+
+    let mut archive = tar::Builder::new(GzEncoder::new(File::create(&path)?, Compression::default()));
+    append_file(&mut archive, &name, "panic.txt", report.as_bytes())?;
+    append_file(&mut archive, &name, "metadata.txt", metadata_report().as_bytes())?;
+    if let Ok(contents) = fs::read_to_string(&config.config_file) {
+        append_file(&mut archive, &name, "config.toml", redact_config(&contents).as_bytes())?;
+    }
+    for log_file in log_files(&config.log_dir) {
+        let tail = match tail_lines(&log_file, LOG_TAIL_LINES) {
+            Ok(tail) => tail,
+            Err(_) => continue,
+        };
+        if let Some(file_name) = log_file.file_name().and_then(|n| n.to_str()) {
+            append_file(&mut archive, &name, &format!("logs/{}", file_name), tail.as_bytes())?;
+        }
+    }
+    archive.into_inner()?.finish()?;
+    Ok(path)
+}
+
+fn panic_report(payload: &(dyn Any + Send), location: &str, backtrace: &Backtrace) -> String {
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(|s| (*s).to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "<non-string panic payload>".to_string());
+    format!(
+        "version: {}\nplatform: {}-{}\nthread: {}\nlocation: {}\nmessage: {}\n\nbacktrace:\n{}\n",
+        consts::APP_VERSION,
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        thread::current().name().unwrap_or("<unnamed>"),
+        location,
+        message,
+        backtrace
+    )
+}
+
+fn metadata_report() -> String {
+    // The panicking thread may have poisoned the lock while recording a field, which leaves the fields intact
+    let fields = DIAGNOSTIC_FIELDS.read().unwrap_or_else(|e| e.into_inner());
+    fields
+        .iter()
+        .map(|(key, value)| format!("{}: {}\n", key, value))
+        .collect()
+}
+
+fn append_file<W: io::Write>(
+    archive: &mut tar::Builder<W>,
+    bundle_name: &str,
+    file_name: &str,
+    data: &[u8],
+) -> Result<(), io::Error> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(data.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(chrono::Utc::now().timestamp().max(0).unsigned_abs());
+    header.set_cksum();
+    archive.append_data(&mut header, format!("{}/{}", bundle_name, file_name), data)
+}
+
+/// The `.log` files in the log directory, ignoring any other files
+fn log_files(log_dir: &Path) -> Vec<PathBuf> {
+    let mut files = fs::read_dir(log_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|path| path.is_file() && path.extension().map_or(false, |ext| ext == "log"))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+/// Reads the last `count` lines of a file without reading all of a large file
+fn tail_lines(path: &Path, count: usize) -> Result<String, io::Error> {
+    let mut file = File::open(path)?;
+    let len = file.metadata()?.len();
+    let start = len.saturating_sub(LOG_TAIL_BYTES);
+    file.seek(SeekFrom::Start(start))?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+    let contents = String::from_utf8_lossy(&bytes);
+    let mut lines = contents.lines().collect::<Vec<_>>();
+    // A partial first line is dropped when reading from the middle of the file
+    if start > 0 && !lines.is_empty() {
+        lines.remove(0);
+    }
+    let skip = lines.len().saturating_sub(count);
+    Ok(lines[skip..].iter().map(|line| format!("{}\n", line)).collect())
+}
+
+/// Replaces the values of configuration keys that may hold secrets
+fn redact_config(contents: &str) -> String {
+    contents
+        .lines()
+        .map(|line| {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with('#') {
+                return line.to_string();
+            }
+            match line.split_once('=') {
+                Some((key, _)) if is_secret_key(key) => format!("{}= {}", key, REDACTED),
+                _ => line.to_string(),
+            }
+        })
+        .map(|line| line + "\n")
+        .collect()
+}
+
+fn is_secret_key(key: &str) -> bool {
+    let key = key.trim().to_ascii_lowercase();
+    SECRET_KEY_FRAGMENTS.iter().any(|fragment| key.contains(fragment))
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Write;
+
+    use flate2::read::GzDecoder;
+
+    use super::*;
+
+    #[test]
+    fn it_redacts_secrets_from_the_config() {
+        let config = "[wallet]\npassword = \"hunter2\"\n#password = \"commented\"\ngrpc_authentication = { username = \
+                      \"a\", password = \"b\" }\nnetwork = \"nextnet\"\n";
+        let redacted = redact_config(config);
+        assert!(!redacted.contains("hunter2"));
+        assert!(!redacted.contains("password = \"b\""));
+        assert!(redacted.contains("#password = \"commented\""));
+        assert!(redacted.contains("network = \"nextnet\""));
+    }
+
+    #[test]
+    fn it_writes_a_bundle_with_the_log_tails() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = DiagnosticsConfig::new(
+            "base_node",
+            temp_dir.path(),
+            temp_dir.path(),
+            &temp_dir.path().join("config.toml"),
+        );
+        fs::create_dir_all(&config.log_dir).unwrap();
+        let mut log = File::create(config.log_dir.join("base_layer.log")).unwrap();
+        for i in 0..(LOG_TAIL_LINES + 10) {
+            writeln!(log, "line {}", i).unwrap();
+        }
+        fs::write(&config.config_file, "[base_node]\nnetwork = \"esmeralda\"\n").unwrap();
+        set_diagnostic_field("tip_height", 42);
+
+        let path = write_diagnostics_bundle(&config, "a panic").unwrap();
+        let mut archive = tar::Archive::new(GzDecoder::new(File::open(path).unwrap()));
+        let mut files = BTreeMap::new();
+        for entry in archive.entries().unwrap() {
+            let mut entry = entry.unwrap();
+            let name = entry.path().unwrap().file_name().unwrap().to_string_lossy().to_string();
+            let mut contents = String::new();
+            entry.read_to_string(&mut contents).unwrap();
+            files.insert(name, contents);
+        }
+        assert_eq!(files["panic.txt"], "a panic");
+        assert!(files["metadata.txt"].contains("tip_height: 42"));
+        assert!(files["config.toml"].contains("esmeralda"));
+        let log = &files["base_layer.log"];
+        assert_eq!(log.lines().count(), LOG_TAIL_LINES);
+        assert!(log.starts_with("line 10\n"));
+        assert!(log.ends_with(&format!("line {}\n", LOG_TAIL_LINES + 9)));
+    }
+}
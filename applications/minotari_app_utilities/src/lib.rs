@@ -20,8 +20,11 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+pub mod backup;
 pub mod common_cli_args;
+pub mod diagnostics;
 pub mod identity_management;
+pub mod network_profile;
 #[cfg(feature = "miner_input")]
 pub mod parse_miner_input;
 pub mod utilities;
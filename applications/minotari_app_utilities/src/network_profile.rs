@@ -0,0 +1,256 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Network profiles keep the configuration and data of each network in its own directory under the base path, so
+//! that several networks can coexist side by side.
+//!
+//! The built-in profiles are named after the networks (`mainnet`, `nextnet`, `esmeralda`, ...). Custom profiles can
+//! use any other name, and are bound to the network they were created with. Each profile directory records its
+//! network, and a profile is never opened for a different network, so that data of one network can't be mixed with
+//! another's. The profile used when none is given on the command line is recorded in the `active_profile` file in the
+//! base path.
+
+use std::{
+    fs,
+    io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use tari_common::{
+    configuration::Network,
+    exit_codes::{ExitCode, ExitError},
+};
+use thiserror::Error;
+
+/// The file in the base path that holds the name of the active profile
+pub const ACTIVE_PROFILE_FILE: &str = "active_profile";
+/// The file in a profile directory that holds the network of the profile
+const PROFILE_NETWORK_FILE: &str = ".network";
+
+#[derive(Debug, Error)]
+pub enum ProfileError {
+    #[error("Invalid profile name '{0}'. Names may only contain letters, digits, '-' and '_'")]
+    InvalidName(String),
+    #[error("Profile '{name}' is for {profile_network}, it can't be used for {network}")]
+    NetworkMismatch {
+        name: String,
+        profile_network: Network,
+        network: Network,
+    },
+    #[error("Profile '{0}' does not exist yet. A network must be given to create it")]
+    NoNetwork(String),
+    #[error("Profile '{0}' has an invalid network: {1}")]
+    InvalidNetwork(String, String),
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+}
+
+impl From<ProfileError> for ExitError {
+    fn from(err: ProfileError) -> Self {
+        Self::new(ExitCode::ConfigError, err.to_string())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetworkProfile {
+    pub name: String,
+    pub network: Network,
+}
+
+impl NetworkProfile {
+    /// Determines the profile to start with: `profile` if given, otherwise the built-in profile of `network` if
+    /// given, otherwise the active profile, and failing that the built-in profile of the default network.
+    pub fn resolve(base_path: &Path, profile: Option<&str>, network: Option<Network>) -> Result<Self, ProfileError> {
+        let name = match (profile, network) {
+            (Some(name), _) => name.to_string(),
+            (None, Some(network)) => network.to_string(),
+            (None, None) => active_profile(base_path)?.unwrap_or_else(|| Network::default().to_string()),
+        };
+        Self::open(base_path, &name, network)
+    }
+
+    /// Opens the profile `name`. `network` is required to create a custom profile, and must match the network of the
+    /// profile otherwise.
+    pub fn open(base_path: &Path, name: &str, network: Option<Network>) -> Result<Self, ProfileError> {
+        validate_name(name)?;
+        let built_in = Network::from_str(name).ok();
+        // Aliases such as `esme` share the directory of the network they refer to
+        let name = built_in.map(|n| n.to_string()).unwrap_or_else(|| name.to_string());
+        let stored = read_profile_network(&base_path.join(&name))?;
+        let profile_network = built_in
+            .or(stored)
+            .or(network)
+            .ok_or_else(|| ProfileError::NoNetwork(name.clone()))?;
+        if let Some(network) = IntoIterator::into_iter([stored, network])
+            .flatten()
+            .find(|n| *n != profile_network)
+        {
+            return Err(ProfileError::NetworkMismatch {
+                name,
+                profile_network,
+                network,
+            });
+        }
+        Ok(Self {
+            name,
+            network: profile_network,
+        })
+    }
+
+    /// The directory of the profile
+    pub fn path(&self, base_path: &Path) -> PathBuf {
+        base_path.join(&self.name)
+    }
+
+    /// Creates the profile directory if necessary, and binds it to the network of the profile
+    pub fn initialize(&self, base_path: &Path) -> Result<(), ProfileError> {
+        let dir = self.path(base_path);
+        fs::create_dir_all(&dir)?;
+        let file = dir.join(PROFILE_NETWORK_FILE);
+        if !file.exists() {
+            fs::write(file, self.network.as_key_str())?;
+        }
+        Ok(())
+    }
+
+    /// Checks that `network`, as finally configured, is the network of the profile
+    pub fn check_network(&self, network: Network) -> Result<(), ProfileError> {
+        if network == self.network {
+            Ok(())
+        } else {
+            Err(ProfileError::NetworkMismatch {
+                name: self.name.clone(),
+                profile_network: self.network,
+                network,
+            })
+        }
+    }
+}
+
+/// The name of the active profile, if one has been set
+pub fn active_profile(base_path: &Path) -> Result<Option<String>, ProfileError> {
+    match fs::read_to_string(base_path.join(ACTIVE_PROFILE_FILE)) {
+        Ok(name) => Ok(Some(name.trim().to_string()).filter(|name| !name.is_empty())),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Makes `profile` the one used at startup when no profile or network is given, creating it if necessary
+pub fn set_active_profile(base_path: &Path, profile: &NetworkProfile) -> Result<(), ProfileError> {
+    profile.initialize(base_path)?;
+    fs::write(base_path.join(ACTIVE_PROFILE_FILE), &profile.name)?;
+    Ok(())
+}
+
+/// All the profiles that have been created in `base_path`, sorted by name
+pub fn list_profiles(base_path: &Path) -> Result<Vec<NetworkProfile>, ProfileError> {
+    let mut profiles = Vec::new();
+    let entries = match fs::read_dir(base_path) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(profiles),
+        Err(e) => return Err(e.into()),
+    };
+    for entry in entries {
+        let entry = entry?;
+        if let (Some(name), Some(network)) = (
+            entry.file_name().to_str(),
+            read_profile_network(&entry.path()).unwrap_or_default(),
+        ) {
+            profiles.push(NetworkProfile {
+                name: name.to_string(),
+                network,
+            });
+        }
+    }
+    profiles.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(profiles)
+}
+
+fn read_profile_network(dir: &Path) -> Result<Option<Network>, ProfileError> {
+    match fs::read_to_string(dir.join(PROFILE_NETWORK_FILE)) {
+        Ok(network) => Network::from_str(network.trim())
+            .map(Some)
+            .map_err(|e| ProfileError::InvalidNetwork(dir.display().to_string(), e.to_string())),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn validate_name(name: &str) -> Result<(), ProfileError> {
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err(ProfileError::InvalidName(name.to_string()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_resolves_built_in_profiles() {
+        let dir = tempfile::tempdir().unwrap();
+        let profile = NetworkProfile::resolve(dir.path(), None, Some(Network::Esmeralda)).unwrap();
+        assert_eq!(profile.name, "esmeralda");
+        assert_eq!(profile.network, Network::Esmeralda);
+        assert_eq!(
+            NetworkProfile::resolve(dir.path(), Some("esme"), None).unwrap(),
+            profile
+        );
+        assert!(matches!(
+            NetworkProfile::resolve(dir.path(), Some("esmeralda"), Some(Network::NextNet)),
+            Err(ProfileError::NetworkMismatch { .. })
+        ));
+        assert_eq!(
+            NetworkProfile::resolve(dir.path(), None, None).unwrap().network,
+            Network::default()
+        );
+    }
+
+    #[test]
+    fn it_binds_custom_profiles_to_their_network() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(matches!(
+            NetworkProfile::open(dir.path(), "testing", None),
+            Err(ProfileError::NoNetwork(_))
+        ));
+        let profile = NetworkProfile::open(dir.path(), "testing", Some(Network::Igor)).unwrap();
+        profile.initialize(dir.path()).unwrap();
+        assert_eq!(NetworkProfile::open(dir.path(), "testing", None).unwrap(), profile);
+        assert!(matches!(
+            NetworkProfile::open(dir.path(), "testing", Some(Network::MainNet)),
+            Err(ProfileError::NetworkMismatch { .. })
+        ));
+        assert!(profile.check_network(Network::Igor).is_ok());
+        assert!(profile.check_network(Network::MainNet).is_err());
+        for name in ["", "..", "a/b", "a b"] {
+            assert!(matches!(
+                NetworkProfile::open(dir.path(), name, Some(Network::Igor)),
+                Err(ProfileError::InvalidName(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn it_switches_the_active_profile() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(active_profile(dir.path()).unwrap(), None);
+        let custom = NetworkProfile::open(dir.path(), "testing", Some(Network::Igor)).unwrap();
+        set_active_profile(dir.path(), &custom).unwrap();
+        assert_eq!(NetworkProfile::resolve(dir.path(), None, None).unwrap(), custom);
+        // An explicit network selects its built-in profile, without changing the active one
+        assert_eq!(
+            NetworkProfile::resolve(dir.path(), None, Some(Network::NextNet))
+                .unwrap()
+                .name,
+            "nextnet"
+        );
+        assert_eq!(active_profile(dir.path()).unwrap().as_deref(), Some("testing"));
+
+        let nextnet = NetworkProfile::open(dir.path(), "nextnet", None).unwrap();
+        nextnet.initialize(dir.path()).unwrap();
+        assert_eq!(list_profiles(dir.path()).unwrap(), vec![nextnet, custom]);
+    }
+}
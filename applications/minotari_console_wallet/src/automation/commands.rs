@@ -38,8 +38,11 @@ use digest::Digest;
 use futures::FutureExt;
 use log::*;
 use minotari_app_grpc::tls::certs::{generate_self_signed_certs, print_warning, write_cert_to_disk};
+use minotari_app_utilities::backup::restore_snapshot;
 use minotari_wallet::{
     connectivity_service::WalletConnectivityInterface,
+    error::WalletError,
+    light_wallet::LightWalletScanner,
     output_manager_service::{
         handle::{OutputManagerEvent, OutputManagerHandle},
         service::UseOutput,
@@ -62,7 +65,7 @@ use tari_common_types::{
     emoji::EmojiId,
     key_branches::TransactionKeyManagerBranch,
     tari_address::TariAddress,
-    transaction::TxId,
+    transaction::{TransactionStatus, TxId},
     types::{Commitment, FixedHash, HashOutput, PrivateKey, PublicKey, Signature},
     wallet_types::WalletType,
 };
@@ -142,6 +145,7 @@ use crate::{
         Step3OutputsForSelf,
         Step4OutputsForLeader,
     },
+    base_node_grpc::GrpcBaseNode,
     cli::{CliCommands, CliRecipientInfo, MakeItRainTransactionType},
     init::init_wallet,
     recovery::{get_seed_from_seed_words, wallet_recovery},
@@ -150,6 +154,8 @@ use crate::{
 };
 
 pub const LOG_TARGET: &str = "wallet::automation::commands";
+/// The height up to which the chain was scanned over the gRPC interface of a trusted base node
+const GRPC_SCANNED_HEIGHT_KEY: &str = "grpc_base_node_scanned_height";
 // Pre-mine file names
 pub(crate) const FILE_EXTENSION: &str = "json";
 pub(crate) const SPEND_SESSION_INFO: &str = "step_1_session_info";
@@ -410,6 +416,101 @@ async fn wait_for_comms(connectivity_requester: &ConnectivityRequester) -> Resul
     }
 }
 
+/// Broadcasts the pending transactions and scans the chain for outputs over the gRPC interface of a trusted base
+/// node, without going through the p2p network
+async fn sync_from_grpc_base_node(
+    wallet: &WalletSqlite,
+    config: &WalletConfig,
+    address: &Multiaddr,
+    sync_to_height: u64,
+) -> Result<(), CommandError> {
+    let mut node = GrpcBaseNode::connect(address, &config.base_node_grpc_authentication).await?;
+    let tip = node.get_tip_info().await?;
+    if !tip.is_synced {
+        println!("Warning: the base node at {} has not finished syncing", address);
+    }
+
+    let mut transaction_service = wallet.transaction_service.clone();
+    for (tx_id, tx) in transaction_service.get_completed_transactions().await? {
+        if matches!(tx.status, TransactionStatus::Completed | TransactionStatus::Broadcast) {
+            match node.submit_transaction(tx.transaction).await {
+                Ok(true) => println!("Broadcast transaction {}", tx_id),
+                Ok(false) => println!("Transaction {} was rejected by the base node", tx_id),
+                Err(e) => eprintln!("Could not broadcast transaction {}: {}", tx_id, e),
+            }
+        }
+    }
+
+    let scanned_height = wallet
+        .db
+        .get_client_key_value(GRPC_SCANNED_HEIGHT_KEY.to_string())?
+        .and_then(|h| h.parse::<u64>().ok());
+    let start_height = scanned_height.map_or(0, |h| h + 1);
+    let end_height = if sync_to_height > 0 {
+        sync_to_height.min(tip.height)
+    } else {
+        tip.height
+    };
+    if start_height > end_height {
+        println!("Already scanned up to height {}", end_height);
+        return Ok(());
+    }
+
+    let mut output_service = wallet.output_manager_service.clone();
+    let known_outputs = output_service
+        .get_unspent_outputs()
+        .await?
+        .into_iter()
+        .map(|o| o.hash)
+        .collect::<Vec<_>>();
+    let view_key = wallet.key_manager_service.get_private_view_key().await?;
+    // One-sided payments to the wallet's address are paid to this script
+    let one_sided_script = push_pubkey_script(wallet.get_wallet_one_sided_address().await?.public_spend_key());
+    let mut scanner = LightWalletScanner::new(node, view_key)
+        .with_known_outputs(known_outputs)
+        .with_known_scripts([&one_sided_script]);
+    println!("Scanning heights {} to {}", start_height, end_height);
+    let result = scanner.scan(start_height, end_height).await?;
+    // The node wakes the registered device for new blocks that match while the wallet is not running
+    if let Some(registration) = wallet.push_notification_service.registration() {
+        let watch = scanner
+            .register_push_watch(
+                registration.relay_url.clone(),
+                registration.device_token.clone(),
+                registration.wake_payload().map_err(WalletError::from)?,
+            )
+            .await;
+        if let Err(e) = watch {
+            eprintln!("Could not register push notifications with the base node: {}", e);
+        }
+    }
+
+    let outputs = result
+        .recovered
+        .into_iter()
+        .map(|o| (o.output, None))
+        .collect::<Vec<_>>();
+    let mut recovered = output_service.scan_for_recoverable_outputs(outputs.clone()).await?;
+    recovered.extend(output_service.scan_outputs_for_one_sided_payments(outputs).await?);
+    let value_recovered = recovered.iter().map(|o| o.output.value).sum::<MicroMinotari>();
+    wallet.db.set_client_key_value(
+        GRPC_SCANNED_HEIGHT_KEY.to_string(),
+        result.scanned_to_height.to_string(),
+    )?;
+    println!(
+        "Completed! Height: {}, UTXOs recovered: {}, Value recovered: {}, UTXOs spent: {}",
+        result.scanned_to_height,
+        recovered.len(),
+        value_recovered,
+        result.spent.len()
+    );
+    match output_service.get_balance().await {
+        Ok(balance) => println!("{}", balance),
+        Err(e) => eprintln!("GetBalance error! {}", e),
+    }
+    Ok(())
+}
+
 async fn set_base_node_peer(
     mut wallet: WalletSqlite,
     public_key: PublicKey,
@@ -2323,6 +2424,35 @@ pub async fn command_runner(
                 debug!(target: LOG_TARGET, "Registering VN tx_id {}", tx_id);
                 tx_ids.push(tx_id);
             },
+            ListValidatorNodeRegistrations => match output_service.get_validator_node_registrations().await {
+                Ok(registrations) => {
+                    if registrations.is_empty() {
+                        println!("No validator node registrations");
+                    }
+                    for registration in registrations {
+                        println!("Validator node : {}", registration.validator_node_public_key);
+                        println!("  Status       : {}", registration.status);
+                        println!("  Commitment   : {}", registration.commitment.to_hex());
+                        println!("  Deposit      : {}", registration.value);
+                        if let Some(tx_id) = registration.tx_id {
+                            println!("  TxId         : {}", tx_id);
+                        }
+                        if let Some(mined_height) = registration.mined_height {
+                            println!(
+                                "  Mined height : {} ({} confirmations)",
+                                mined_height, registration.confirmations
+                            );
+                        }
+                        if let (Some(start), Some(end)) = (registration.start_epoch, registration.end_epoch) {
+                            println!("  Epochs       : {} to {}", start.as_u64(), end.as_u64());
+                        }
+                        if let Some(expiry_height) = registration.expiry_height {
+                            println!("  Expires at   : height {}", expiry_height);
+                        }
+                    }
+                },
+                Err(e) => eprintln!("ListValidatorNodeRegistrations error! {}", e),
+            },
             CreateTlsCerts => match generate_self_signed_certs() {
                 Ok((cacert, cert, private_key)) => {
                     print_warning();
@@ -2343,6 +2473,12 @@ pub async fn command_runner(
                 Err(err) => eprintln!("Error generating certificates: {}", err),
             },
             Sync(args) => {
+                if let Some(address) = config.base_node_grpc_address.as_ref() {
+                    if let Err(e) = sync_from_grpc_base_node(&wallet, config, address, args.sync_to_height).await {
+                        eprintln!("Sync error! {}", e);
+                    }
+                    continue;
+                }
                 let mut utxo_scanner = wallet.utxo_scanner_service.clone();
                 let mut receiver = utxo_scanner.get_event_receiver();
 
@@ -2394,6 +2530,7 @@ pub async fn command_runner(
                                 num_recovered,
                                 value_recovered,
                                 time_taken,
+                                recovered_by_branch,
                             } => {
                                 println!(
                                     "Completed! Height: {}, UTXOs recovered: {}, Value recovered: {}, Time taken: {}",
@@ -2402,6 +2539,12 @@ pub async fn command_runner(
                                     value_recovered,
                                     time_taken.as_secs()
                                 );
+                                for (branch, stats) in recovered_by_branch {
+                                    println!(
+                                        "  {}: {} UTXOs worth {}",
+                                        branch, stats.num_recovered, stats.value_recovered
+                                    );
+                                }
 
                                 break;
                             },
@@ -2530,6 +2673,7 @@ pub async fn command_runner(
                         shutdown_signal,
                         true,
                         Some(wallet_type),
+                        None,
                     )
                     .await
                     .map_err(|e| CommandError::General(e.to_string()))?;
@@ -2560,7 +2704,7 @@ pub async fn command_runner(
                     let peer_config = PeerConfig::new(selected_base_node, base_node_peers, peer_seeds);
 
                     let base_nodes = peer_config
-                        .get_base_node_peers()
+                        .get_base_node_peers(config.custom_base_node_failover)
                         .map_err(|e| CommandError::General(e.to_string()))?;
                     new_wallet
                         .set_base_node_peer(
@@ -2647,6 +2791,40 @@ pub async fn command_runner(
                 println!("removing temp wallet in: {:?}", temp_path);
                 fs::remove_dir_all(temp_path)?;
             },
+            RestoreBackup(args) => match restore_snapshot(&config.backup, &args.archive, &args.output_dir).await {
+                Ok(files) => {
+                    for file in &files {
+                        println!("Restored {}", file.display());
+                    }
+                    println!(
+                        "Restored {} file(s). Stop the wallet and move them into place to use them.",
+                        files.len()
+                    );
+                },
+                Err(e) => eprintln!("RestoreBackup error! {}", e),
+            },
+            VerifyWalletDb(_) => {
+                eprintln!(
+                    "VerifyWalletDb error! The wallet database can only be verified on its own, before the wallet \
+                     starts"
+                );
+            },
+            TxDoctor(args) => {
+                let tx_id = TxId::from(args.tx_id);
+                match transaction_service.diagnose_transaction(tx_id).await {
+                    Ok(diagnosis) => println!("{}", diagnosis),
+                    Err(e) => {
+                        eprintln!("TxDoctor error! {}", e);
+                        continue;
+                    },
+                }
+                if let Some(remediation) = args.fix {
+                    match transaction_service.apply_remediation(tx_id, remediation).await {
+                        Ok(()) => println!("Applied remediation '{}' to transaction {}", remediation, tx_id),
+                        Err(e) => eprintln!("TxDoctor error! {}", e),
+                    }
+                }
+            },
         }
     }
     if unban_peer_manager_peers {
@@ -29,6 +29,7 @@ use log::*;
 use minotari_app_grpc::tls::error::GrpcTlsError;
 use minotari_wallet::{
     error::{WalletError, WalletStorageError},
+    light_wallet::LightWalletError,
     output_manager_service::error::OutputManagerError,
     transaction_service::error::TransactionServiceError,
 };
@@ -95,6 +96,8 @@ pub enum CommandError {
     FailedSignature(#[from] SchnorrSignatureError),
     #[error("Tari script error: {0}")]
     ScriptError(#[from] ScriptError),
+    #[error("Base node gRPC error: {0}")]
+    LightWalletError(#[from] LightWalletError),
 }
 
 impl From<HexError> for CommandError {
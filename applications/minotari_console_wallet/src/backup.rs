@@ -0,0 +1,51 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Periodic encrypted backups of the wallet database
+
+use std::fs;
+
+use futures::FutureExt;
+use minotari_app_utilities::backup::{ArchiveEntry, BackupError, BackupService};
+use minotari_wallet::{storage::sqlite_utilities::backup_sqlite_database, WalletConfig, WalletSqlite};
+use tari_common::exit_codes::{ExitCode, ExitError};
+use tokio::{runtime::Handle, task};
+
+/// Starts the backup service if backups are enabled. It stops when the wallet shuts down.
+pub fn spawn_backup(handle: &Handle, config: &WalletConfig, wallet: &WalletSqlite) -> Result<(), ExitError> {
+    if !config.backup.enabled {
+        return Ok(());
+    }
+    let db_file = config.db_file.clone();
+    let name = db_file
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("console_wallet.db")
+        .to_string();
+    // The consistent copy of the database is staged next to the database itself, so that it stays on the same
+    // (presumably protected) volume
+    let staging_file = db_file.with_extension("db.backup");
+    let source = Box::new(move || {
+        let db_file = db_file.clone();
+        let staging_file = staging_file.clone();
+        let name = name.clone();
+        async move {
+            task::spawn_blocking(move || {
+                if staging_file.exists() {
+                    fs::remove_file(&staging_file)?;
+                }
+                backup_sqlite_database(&db_file, &staging_file).map_err(|e| BackupError::Source(e.to_string()))?;
+                let data = fs::read(&staging_file);
+                fs::remove_file(&staging_file)?;
+                Ok(vec![ArchiveEntry::new(name, data?)])
+            })
+            .await
+            .map_err(|e| BackupError::Source(e.to_string()))?
+        }
+        .boxed()
+    });
+    let service = BackupService::new("wallet", config.backup.clone(), source)
+        .map_err(|e| ExitError::new(ExitCode::ConfigError, format!("Invalid backup configuration: {}", e)))?;
+    handle.spawn(service.run(wallet.clone().wait_until_shutdown()));
+    Ok(())
+}
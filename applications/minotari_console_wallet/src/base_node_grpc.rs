@@ -0,0 +1,216 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Connectivity to a trusted base node over its gRPC interface instead of the p2p network, for wallets that run next
+//! to their own node. The node must have the light wallet server enabled.
+
+use std::convert::{TryFrom, TryInto};
+
+use async_trait::async_trait;
+use log::*;
+use minotari_app_grpc::{
+    authentication::ClientAuthenticationInterceptor,
+    tari_rpc::{
+        base_node_client::BaseNodeClient,
+        Empty,
+        FetchMatchingUtxosRequest,
+        GetBlockFiltersRequest,
+        GetCompactBlockOutputsRequest,
+        RegisterPushWatchRequest,
+        SubmitTransactionRequest,
+        SubmitTransactionResult,
+        UnregisterPushWatchRequest,
+    },
+};
+use minotari_wallet::light_wallet::{CompactBlockOutputs, LightWalletError, LightWalletSource, PushWatch};
+use tari_common_types::{grpc_authentication::GrpcAuthentication, types::FixedHash};
+use tari_comms::{multiaddr::Multiaddr, utils::multiaddr::multiaddr_to_socketaddr};
+use tari_core::{
+    blocks::BlockOutputFilter,
+    transactions::transaction_components::{Transaction, TransactionOutput},
+};
+use tonic::{codegen::InterceptedService, transport::Channel, Status};
+
+const LOG_TARGET: &str = "wallet::console_wallet::base_node_grpc";
+
+/// The chain tip reported by the base node
+#[derive(Debug, Clone, Copy)]
+pub struct GrpcTipInfo {
+    pub height: u64,
+    pub is_synced: bool,
+}
+
+pub struct GrpcBaseNode {
+    client: BaseNodeClient<InterceptedService<Channel, ClientAuthenticationInterceptor>>,
+}
+
+impl GrpcBaseNode {
+    pub async fn connect(address: &Multiaddr, authentication: &GrpcAuthentication) -> Result<Self, LightWalletError> {
+        let socket_address = multiaddr_to_socketaddr(address)
+            .map_err(|e| LightWalletError::ServerError(format!("Invalid base node gRPC address: {}", e)))?;
+        debug!(target: LOG_TARGET, "Connecting to base node gRPC at {}", socket_address);
+        let channel = Channel::from_shared(format!("http://{}", socket_address))
+            .map_err(|e| LightWalletError::ServerError(e.to_string()))?
+            .connect()
+            .await
+            .map_err(|e| LightWalletError::ServerError(e.to_string()))?;
+        let interceptor = ClientAuthenticationInterceptor::create(authentication)
+            .map_err(|e| LightWalletError::ServerError(e.to_string()))?;
+        Ok(Self {
+            client: BaseNodeClient::with_interceptor(channel, interceptor),
+        })
+    }
+
+    pub async fn get_tip_info(&mut self) -> Result<GrpcTipInfo, LightWalletError> {
+        let tip = self
+            .client
+            .get_tip_info(Empty {})
+            .await
+            .map_err(server_error)?
+            .into_inner();
+        let metadata = tip
+            .metadata
+            .ok_or_else(|| LightWalletError::InvalidResponse("Tip info without metadata".to_string()))?;
+        Ok(GrpcTipInfo {
+            height: metadata.best_block_height,
+            is_synced: tip.initial_sync_achieved,
+        })
+    }
+
+    /// Submits a transaction to the node's mempool. Returns true if it was accepted or is already mined.
+    pub async fn submit_transaction(&mut self, transaction: Transaction) -> Result<bool, LightWalletError> {
+        let request = SubmitTransactionRequest {
+            transaction: Some(transaction.try_into().map_err(LightWalletError::InvalidResponse)?),
+        };
+        let response = self
+            .client
+            .submit_transaction(request)
+            .await
+            .map_err(server_error)?
+            .into_inner();
+        let result = SubmitTransactionResult::try_from(response.result).unwrap_or(SubmitTransactionResult::None);
+        debug!(target: LOG_TARGET, "Transaction submission result: {:?}", result);
+        Ok(matches!(
+            result,
+            SubmitTransactionResult::Accepted | SubmitTransactionResult::AlreadyMined
+        ))
+    }
+}
+
+#[async_trait]
+impl LightWalletSource for GrpcBaseNode {
+    async fn get_tip_height(&mut self) -> Result<u64, LightWalletError> {
+        Ok(self.get_tip_info().await?.height)
+    }
+
+    async fn get_block_filters(
+        &mut self,
+        start_height: u64,
+        end_height: u64,
+    ) -> Result<Vec<(u64, BlockOutputFilter)>, LightWalletError> {
+        let mut stream = self
+            .client
+            .get_block_filters(GetBlockFiltersRequest {
+                start_height,
+                end_height,
+            })
+            .await
+            .map_err(server_error)?
+            .into_inner();
+        let mut filters = Vec::new();
+        while let Some(filter) = stream.message().await.map_err(server_error)? {
+            let block_hash = to_fixed_hash(&filter.block_hash)?;
+            filters.push((
+                filter.height,
+                BlockOutputFilter::from_parts(block_hash, filter.num_items, filter.filter),
+            ));
+        }
+        Ok(filters)
+    }
+
+    async fn get_compact_block_outputs(
+        &mut self,
+        start_height: u64,
+        end_height: u64,
+    ) -> Result<Vec<CompactBlockOutputs>, LightWalletError> {
+        let mut stream = self
+            .client
+            .get_compact_block_outputs(GetCompactBlockOutputsRequest {
+                start_height,
+                end_height,
+            })
+            .await
+            .map_err(server_error)?
+            .into_inner();
+        let mut blocks = Vec::new();
+        while let Some(block) = stream.message().await.map_err(server_error)? {
+            blocks.push(CompactBlockOutputs {
+                height: block.height,
+                block_hash: to_fixed_hash(&block.block_hash)?,
+                timestamp: block.timestamp,
+                outputs: block
+                    .outputs
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_, _>>()
+                    .map_err(LightWalletError::InvalidResponse)?,
+                spent_output_hashes: block
+                    .spent_output_hashes
+                    .iter()
+                    .map(|hash| to_fixed_hash(hash))
+                    .collect::<Result<_, _>>()?,
+            });
+        }
+        Ok(blocks)
+    }
+
+    async fn fetch_outputs(
+        &mut self,
+        output_hashes: Vec<FixedHash>,
+    ) -> Result<Vec<TransactionOutput>, LightWalletError> {
+        let mut stream = self
+            .client
+            .fetch_matching_utxos(FetchMatchingUtxosRequest {
+                hashes: output_hashes.iter().map(|hash| hash.to_vec()).collect(),
+            })
+            .await
+            .map_err(server_error)?
+            .into_inner();
+        let mut outputs = Vec::new();
+        while let Some(response) = stream.message().await.map_err(server_error)? {
+            if let Some(output) = response.output {
+                outputs.push(TransactionOutput::try_from(output).map_err(LightWalletError::InvalidResponse)?);
+            }
+        }
+        Ok(outputs)
+    }
+
+    async fn register_push_watch(&mut self, watch: PushWatch) -> Result<(), LightWalletError> {
+        self.client
+            .register_push_watch(RegisterPushWatchRequest {
+                relay_url: watch.relay_url,
+                device_token: watch.device_token,
+                payload: watch.payload,
+                items: watch.items,
+            })
+            .await
+            .map_err(server_error)?;
+        Ok(())
+    }
+
+    async fn unregister_push_watch(&mut self, device_token: String) -> Result<(), LightWalletError> {
+        self.client
+            .unregister_push_watch(UnregisterPushWatchRequest { device_token })
+            .await
+            .map_err(server_error)?;
+        Ok(())
+    }
+}
+
+fn server_error(status: Status) -> LightWalletError {
+    LightWalletError::ServerError(status.to_string())
+}
+
+fn to_fixed_hash(bytes: &[u8]) -> Result<FixedHash, LightWalletError> {
+    FixedHash::try_from(bytes).map_err(|e| LightWalletError::InvalidResponse(e.to_string()))
+}
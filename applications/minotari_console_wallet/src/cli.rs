@@ -30,6 +30,7 @@ use std::{
 use chrono::{DateTime, Utc};
 use clap::{Args, Parser, Subcommand};
 use minotari_app_utilities::{common_cli_args::CommonCliArgs, utilities::UniPublicKey};
+use minotari_wallet::transaction_service::doctor::Remediation;
 use tari_common::configuration::{ConfigOverrideProvider, Network};
 use tari_common_types::tari_address::TariAddress;
 use tari_comms::multiaddr::Multiaddr;
@@ -85,6 +86,17 @@ pub struct Cli {
     pub grpc_enabled: bool,
     #[clap(long, env = "MINOTARI_WALLET_GRPC_ADDRESS")]
     pub grpc_address: Option<String>,
+    /// Run headless as a service, serving only gRPC. Implies non-interactive mode and enables gRPC. Readiness is
+    /// signalled to systemd, and SIGTERM shuts the wallet down cleanly.
+    #[clap(long, env = "MINOTARI_WALLET_DAEMON")]
+    pub daemon: bool,
+    /// Path of the file the process id is written to in daemon mode
+    #[clap(long, requires = "daemon", parse(from_os_str))]
+    pub pid_file: Option<PathBuf>,
+    /// Only connect through Tor hidden services, without DNS seeds or update checks, and delay transaction
+    /// broadcasts. The wallet refuses to start if any setting would reveal its public IP address.
+    #[clap(long, env = "MINOTARI_WALLET_PRIVACY")]
+    pub privacy: bool,
     #[clap(subcommand)]
     pub command2: Option<CliCommands>,
     #[clap(long, alias = "profile")]
@@ -113,7 +125,7 @@ impl ConfigOverrideProvider for Cli {
         if let Some(ref addr) = self.grpc_address {
             replace_or_add_override(&mut overrides, "wallet.grpc_enabled", "true");
             replace_or_add_override(&mut overrides, "wallet.grpc_address", addr);
-        } else if self.grpc_enabled {
+        } else if self.grpc_enabled || self.daemon {
             replace_or_add_override(&mut overrides, "wallet.grpc_enabled", "true");
         } else {
             // GRPC is disabled
@@ -160,10 +172,14 @@ pub enum CliCommands {
     ClaimShaAtomicSwapRefund(ClaimShaAtomicSwapRefundArgs),
     RevalidateWalletDb,
     RegisterValidatorNode(RegisterValidatorNodeArgs),
+    ListValidatorNodeRegistrations,
     CreateTlsCerts,
     Sync(SyncArgs),
     ExportViewKeyAndSpendKey(ExportViewKeyAndSpendKeyArgs),
     ImportPaperWallet(ImportPaperWalletArgs),
+    RestoreBackup(RestoreBackupArgs),
+    VerifyWalletDb(VerifyWalletDbArgs),
+    TxDoctor(TxDoctorArgs),
 }
 
 #[derive(Debug, Args, Clone)]
@@ -399,6 +415,30 @@ pub struct ImportPaperWalletArgs {
     pub passphrase: String,
 }
 
+#[derive(Debug, Args, Clone)]
+pub struct RestoreBackupArgs {
+    /// The path of an archive file, or the name of an archive in the configured backup store
+    pub archive: String,
+    /// The directory to write the restored files to. Existing files are never overwritten.
+    #[clap(short, long)]
+    pub output_dir: PathBuf,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct VerifyWalletDbArgs {
+    /// Move inconsistent rows into `quarantined_<table>` tables. Key material is only ever reported.
+    #[clap(long)]
+    pub quarantine: bool,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct TxDoctorArgs {
+    pub tx_id: u64,
+    /// Apply a remediation offered by the diagnosis: resend, cancel or rebroadcast
+    #[clap(long)]
+    pub fix: Option<Remediation>,
+}
+
 #[derive(Debug, Args, Clone)]
 pub struct ImportTxArgs {
     #[clap(short, long)]
@@ -22,11 +22,19 @@
 
 #![allow(dead_code, unused)]
 
+use std::time::Duration;
+
 use config::Config;
 use minotari_app_utilities::consts;
 use minotari_wallet::WalletConfig;
 use tari_common::{configuration::CommonConfig, ConfigurationError, DefaultConfigLoader};
-use tari_p2p::{auto_update::AutoUpdateConfig, PeerSeedsConfig};
+use tari_common_types::backup_config::remote_store_urls;
+use tari_p2p::{
+    auto_update::AutoUpdateConfig,
+    privacy::{apply_privacy_profile, check_privacy_profile, is_loopback_address, is_loopback_url, PrivacyLeakError},
+    PeerSeedsConfig,
+};
+use tari_telemetry::TelemetryConfig;
 
 #[derive(Clone, Debug)]
 pub struct ApplicationConfig {
@@ -34,6 +42,7 @@ pub struct ApplicationConfig {
     pub auto_update: AutoUpdateConfig,
     pub wallet: WalletConfig,
     pub peer_seeds: PeerSeedsConfig,
+    pub telemetry: TelemetryConfig,
 }
 
 impl ApplicationConfig {
@@ -43,9 +52,81 @@ impl ApplicationConfig {
             auto_update: AutoUpdateConfig::load_from(cfg)?,
             wallet: WalletConfig::load_from(cfg)?,
             peer_seeds: PeerSeedsConfig::load_from(cfg)?,
+            telemetry: TelemetryConfig::load_from(cfg)?,
         };
 
         config.wallet.set_base_path(config.common.base_path());
         Ok(config)
     }
+
+    /// Restricts the wallet to the Tor hidden service transport, disables update checks and trace context propagation,
+    /// only accepts push notification relays on a loopback address and delays transaction broadcasts by a random
+    /// duration. Returns the settings that would still reveal the public IP address.
+    pub fn apply_privacy_profile(&mut self) -> Result<(), PrivacyLeakError> {
+        apply_privacy_profile(&mut self.wallet.p2p, &mut self.peer_seeds);
+        // Update checks query DNS and download over clearnet
+        self.auto_update.check_interval = None;
+        let transactions = &mut self.wallet.transaction_service_config;
+        if transactions.broadcast_delay_max.is_none() {
+            transactions.broadcast_delay_max = Some(PRIVACY_BROADCAST_DELAY_MAX);
+        }
+        self.telemetry.propagate_trace_context = false;
+        // The push notification relay is registered at runtime and posted to directly, not through Tor
+        self.wallet.push_notification_service_config.loopback_relay_only = true;
+
+        let mut leaks = match check_privacy_profile(&self.wallet.p2p, &self.peer_seeds) {
+            Ok(()) => Vec::new(),
+            Err(PrivacyLeakError(leaks)) => leaks,
+        };
+        let servers = [
+            ("gRPC server is bound to", &self.wallet.grpc_address),
+            ("JSON-RPC server is bound to", &self.wallet.json_rpc_address),
+            ("trusted base node is reached at", &self.wallet.base_node_grpc_address),
+            ("remote signer is reached at", &self.wallet.remote_signer_address),
+        ];
+        for (description, address) in servers {
+            if let Some(address) = address.as_ref().filter(|a| !is_loopback_address(a)) {
+                leaks.push(format!("the {} {}", description, address));
+            }
+        }
+        // Spans are exported directly, not through Tor
+        if let Some(endpoint) = self
+            .telemetry
+            .otlp_endpoint
+            .as_ref()
+            .filter(|url| !is_loopback_url(url))
+        {
+            leaks.push(format!("spans are exported to {}", endpoint));
+        }
+        // Backups and metadata are uploaded directly, not through Tor
+        let backup = &self.wallet.backup;
+        let metadata_sync = &self.wallet.metadata_sync;
+        let stores = [
+            (
+                "backups are written to",
+                backup.enabled,
+                backup.s3.as_ref(),
+                backup.webdav.as_ref(),
+            ),
+            (
+                "metadata is synced with",
+                metadata_sync.enabled,
+                metadata_sync.s3.as_ref(),
+                metadata_sync.webdav.as_ref(),
+            ),
+        ];
+        for (description, _, s3, webdav) in stores.into_iter().filter(|(_, enabled, ..)| *enabled) {
+            for url in remote_store_urls(s3, webdav).filter(|url| !is_loopback_url(url)) {
+                leaks.push(format!("{} {}", description, url));
+            }
+        }
+        if leaks.is_empty() {
+            Ok(())
+        } else {
+            Err(PrivacyLeakError(leaks))
+        }
+    }
 }
+
+/// The broadcast delay used by the privacy profile if none is configured
+const PRIVACY_BROADCAST_DELAY_MAX: Duration = Duration::from_secs(30);
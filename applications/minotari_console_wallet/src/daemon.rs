@@ -0,0 +1,102 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Support for running the wallet headless as a service: a PID file, readiness signalling to systemd and termination
+//! on SIGTERM. See `linux/minotari_console_wallet.service` for an example unit.
+
+use std::{
+    fs,
+    io,
+    path::{Path, PathBuf},
+    process,
+};
+
+use log::*;
+
+const LOG_TARGET: &str = "wallet::console_wallet::daemon";
+
+/// Holds the PID file of the process and removes it when dropped
+pub struct PidFile {
+    path: PathBuf,
+}
+
+impl PidFile {
+    pub fn create(path: &Path) -> Result<Self, io::Error> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, format!("{}\n", process::id()))?;
+        debug!(target: LOG_TARGET, "Wrote PID file {}", path.display());
+        Ok(Self {
+            path: path.to_path_buf(),
+        })
+    }
+}
+
+impl Drop for PidFile {
+    fn drop(&mut self) {
+        if let Err(e) = fs::remove_file(&self.path) {
+            warn!(target: LOG_TARGET, "Could not remove PID file {}: {}", self.path.display(), e);
+        }
+    }
+}
+
+/// Sends a state such as `READY=1` to the service manager, if the process was started by systemd with
+/// `Type=notify`. This does nothing otherwise.
+#[cfg(unix)]
+pub fn notify_service_manager(state: &str) {
+    use std::{env, os::unix::net::UnixDatagram};
+
+    let socket_path = match env::var_os("NOTIFY_SOCKET") {
+        Some(path) => path,
+        None => return,
+    };
+    let result = UnixDatagram::unbound().and_then(|socket| {
+        let bytes = socket_path.to_string_lossy();
+        match bytes.strip_prefix('@') {
+            // Abstract socket names are only supported on Linux
+            #[cfg(target_os = "linux")]
+            Some(name) => {
+                use std::os::{linux::net::SocketAddrExt, unix::net::SocketAddr};
+                let address = SocketAddr::from_abstract_name(name.as_bytes())?;
+                socket.send_to_addr(state.as_bytes(), &address)
+            },
+            _ => socket.send_to(state.as_bytes(), Path::new(&socket_path)),
+        }
+    });
+    match result {
+        Ok(_) => debug!(target: LOG_TARGET, "Notified the service manager: {}", state),
+        Err(e) => warn!(target: LOG_TARGET, "Could not notify the service manager of `{}`: {}", state, e),
+    }
+}
+
+#[cfg(not(unix))]
+pub fn notify_service_manager(_state: &str) {}
+
+/// Resolves when the process is asked to terminate, by SIGTERM or SIGINT
+#[cfg(unix)]
+pub async fn wait_for_termination() {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let (mut terminate, mut interrupt) = match (signal(SignalKind::terminate()), signal(SignalKind::interrupt())) {
+        (Ok(terminate), Ok(interrupt)) => (terminate, interrupt),
+        (Err(e), _) | (_, Err(e)) => {
+            error!(target: LOG_TARGET, "Could not install the signal handlers: {}", e);
+            return futures::future::pending().await;
+        },
+    };
+    tokio::select! {
+        _ = terminate.recv() => info!(target: LOG_TARGET, "Received SIGTERM"),
+        _ = interrupt.recv() => info!(target: LOG_TARGET, "Received SIGINT"),
+    }
+}
+
+/// Resolves when the process is asked to terminate, by Ctrl-C
+#[cfg(not(unix))]
+pub async fn wait_for_termination() {
+    if let Err(e) = tokio::signal::ctrl_c().await {
+        error!(target: LOG_TARGET, "Could not install the Ctrl-C handler: {}", e);
+        futures::future::pending::<()>().await;
+    }
+    info!(target: LOG_TARGET, "Received Ctrl-C");
+}
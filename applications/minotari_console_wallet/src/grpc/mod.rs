@@ -3,12 +3,23 @@
 
 mod wallet_grpc_server;
 
-use minotari_app_grpc::tari_rpc::TransactionEvent;
-use minotari_wallet::transaction_service::storage::models::{
-    CompletedTransaction,
-    InboundTransaction,
-    OutboundTransaction,
+use std::{convert::TryFrom, str::FromStr};
+
+use minotari_app_grpc::tari_rpc::{
+    self,
+    payment_recipient::PaymentType,
+    ChatMessage,
+    ChatMessageMetadata,
+    PaymentRecipient,
+    TransactionEvent,
+};
+use minotari_wallet::transaction_service::{
+    drafts::{DraftPaymentType, DraftRecipient, TransactionDraft},
+    storage::models::{CompletedTransaction, InboundTransaction, OutboundTransaction},
 };
+use tari_common_types::tari_address::TariAddress;
+use tari_contacts::contacts_service::types::{Direction, Message};
+use tari_core::transactions::transaction_components::encrypted_data::PaymentId;
 
 pub use self::wallet_grpc_server::*;
 
@@ -55,3 +66,72 @@ pub fn convert_to_transaction_event(event: String, source: TransactionWrapper) -
         },
     }
 }
+
+pub fn convert_to_chat_message(message: Message) -> ChatMessage {
+    ChatMessage {
+        message_id: message.message_id.to_vec(),
+        sender_address: message.sender_address.to_vec(),
+        receiver_address: message.receiver_address.to_vec(),
+        body: message.body.to_vec(),
+        metadata: message
+            .metadata
+            .into_iter()
+            .map(|m| ChatMessageMetadata {
+                key: m.key.to_vec(),
+                data: m.data.to_vec(),
+            })
+            .collect(),
+        is_inbound: message.direction == Direction::Inbound,
+        sent_at: message.sent_at,
+        stored_at: message.stored_at,
+        delivery_confirmation_at: message.delivery_confirmation_at.unwrap_or_default(),
+        read_confirmation_at: message.read_confirmation_at.unwrap_or_default(),
+    }
+}
+
+pub fn convert_to_draft_recipient(recipient: PaymentRecipient) -> Result<DraftRecipient, String> {
+    let address = TariAddress::from_str(&recipient.address)
+        .map_err(|_| format!("Recipient address '{}' is malformed", recipient.address))?;
+    PaymentId::from_bytes(&recipient.payment_id).map_err(|_| "Invalid payment id".to_string())?;
+    let payment_type = match PaymentType::try_from(recipient.payment_type) {
+        Ok(PaymentType::StandardMimblewimble) => DraftPaymentType::Interactive,
+        Ok(PaymentType::OneSided) => DraftPaymentType::OneSided,
+        Ok(PaymentType::OneSidedToStealthAddress) => DraftPaymentType::OneSidedToStealthAddress,
+        Err(_) => return Err(format!("Invalid payment type {}", recipient.payment_type)),
+    };
+    Ok(DraftRecipient {
+        address,
+        amount: recipient.amount.into(),
+        fee_per_gram: recipient.fee_per_gram.into(),
+        message: recipient.message,
+        payment_type,
+        payment_id: recipient.payment_id,
+    })
+}
+
+pub fn convert_to_payment_recipient(recipient: DraftRecipient) -> PaymentRecipient {
+    let payment_type = match recipient.payment_type {
+        DraftPaymentType::Interactive => PaymentType::StandardMimblewimble,
+        DraftPaymentType::OneSided => PaymentType::OneSided,
+        DraftPaymentType::OneSidedToStealthAddress => PaymentType::OneSidedToStealthAddress,
+    };
+    PaymentRecipient {
+        address: recipient.address.to_base58(),
+        amount: recipient.amount.as_u64(),
+        fee_per_gram: recipient.fee_per_gram.as_u64(),
+        message: recipient.message,
+        payment_type: payment_type.into(),
+        payment_id: recipient.payment_id,
+    }
+}
+
+pub fn convert_to_grpc_draft(draft: TransactionDraft) -> tari_rpc::TransactionDraft {
+    tari_rpc::TransactionDraft {
+        total_amount: draft.total_amount().as_u64(),
+        name: draft.name,
+        notes: draft.notes,
+        recipients: draft.recipients.into_iter().map(convert_to_payment_recipient).collect(),
+        created_at: u64::try_from(draft.created_at.timestamp()).unwrap_or_default(),
+        updated_at: u64::try_from(draft.updated_at.timestamp()).unwrap_or_default(),
+    }
+}
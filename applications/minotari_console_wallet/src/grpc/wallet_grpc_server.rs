@@ -25,6 +25,7 @@ use std::{
     str::FromStr,
 };
 
+use chrono::{NaiveDate, Utc};
 use futures::{
     channel::mpsc::{self, Sender},
     future,
@@ -33,8 +34,19 @@ use futures::{
 use log::*;
 use minotari_app_grpc::tari_rpc::{
     self,
+    chat_event_response,
     payment_recipient::PaymentType,
     wallet_server,
+    watch_deposits_request,
+    AcknowledgeDepositEventsRequest,
+    AcknowledgeDepositEventsResponse,
+    AnchorApplicationDataRequest,
+    AnchorApplicationDataResponse,
+    ApprovePaymentRequest,
+    ApprovePaymentResponse,
+    ChatEventRequest,
+    ChatEventResponse,
+    ChatReceipt,
     CheckConnectivityResponse,
     ClaimHtlcRefundRequest,
     ClaimHtlcRefundResponse,
@@ -47,29 +59,63 @@ use minotari_app_grpc::tari_rpc::{
     CreateBurnTransactionResponse,
     CreateTemplateRegistrationRequest,
     CreateTemplateRegistrationResponse,
+    DailyBalance,
+    DeleteAppDataResponse,
+    DeleteTransactionDraftRequest,
+    ExecuteTransactionDraftRequest,
+    GeneratePaymentProofRequest,
     GetAddressResponse,
+    GetAnchoredApplicationDataRequest,
+    GetAnchoredApplicationDataResponse,
+    GetAppDataRequest,
+    GetAppDataResponse,
+    GetBalanceReportRequest,
+    GetBalanceReportResponse,
     GetBalanceRequest,
     GetBalanceResponse,
+    GetChatConversationalistsResponse,
+    GetChatMessagesRequest,
+    GetChatMessagesResponse,
     GetCompletedTransactionsRequest,
     GetCompletedTransactionsResponse,
     GetConnectivityRequest,
+    GetDepositEventsResponse,
     GetIdentityRequest,
     GetIdentityResponse,
+    GetPendingApprovalsResponse,
+    GetSweepPolicyResponse,
+    GetTransactionDraftsResponse,
     GetTransactionInfoRequest,
     GetTransactionInfoResponse,
+    GetUnspentAmountsRequest,
     GetUnspentAmountsResponse,
+    GetValidatorNodeRegistrationsResponse,
     GetVersionRequest,
     GetVersionResponse,
     ImportUtxosRequest,
     ImportUtxosResponse,
+    ListAppDataKeysRequest,
+    ListAppDataKeysResponse,
+    PaymentRecipient,
+    PendingApproval,
     RegisterValidatorNodeRequest,
     RegisterValidatorNodeResponse,
+    RejectPaymentRequest,
     RevalidateRequest,
     RevalidateResponse,
+    SaveTransactionDraftRequest,
+    SendChatMessageRequest,
+    SendChatMessageResponse,
+    SendChatReadReceiptRequest,
+    SendChatReadReceiptResponse,
     SendShaAtomicSwapRequest,
     SendShaAtomicSwapResponse,
+    SetAppDataRequest,
     SetBaseNodeRequest,
     SetBaseNodeResponse,
+    SetSweepPolicyRequest,
+    SimulateScriptRequest,
+    SimulateScriptResponse,
     TransactionDirection,
     TransactionEvent,
     TransactionEventRequest,
@@ -81,23 +127,44 @@ use minotari_app_grpc::tari_rpc::{
     TransferResult,
     ValidateRequest,
     ValidateResponse,
+    WatchDepositsRequest,
+    WatchDepositsResponse,
 };
 use minotari_wallet::{
     connectivity_service::{OnlineStatus, WalletConnectivityInterface},
+    deposit_tracking_service::{
+        handle::DepositTrackingHandle,
+        tracker::{DepositEventKind, DepositWatch},
+    },
     error::WalletStorageError,
-    output_manager_service::{handle::OutputManagerHandle, UtxoSelectionCriteria},
+    output_manager_service::{
+        balance_breakdown::OutputFilter,
+        handle::OutputManagerHandle,
+        service::ValidatorNodeRegistrationStatus,
+        UtxoSelectionCriteria,
+    },
+    sweep_service::{error::SweepError, handle::SweepHandle, policy::SweepPolicy},
     transaction_service::{
+        drafts::TransactionDraft,
+        error::TransactionServiceError,
         handle::TransactionServiceHandle,
+        reporting::BalanceReport,
+        spending_policy::SpendingPolicyViolation,
         storage::models::{self, WalletTransaction},
     },
     WalletSqlite,
 };
+use tari_common::{log_filter_directives, set_log_filter_directives, LogFilterDirective};
 use tari_common_types::{
     tari_address::TariAddress,
     transaction::TxId,
-    types::{BlockHash, PublicKey, Signature},
+    types::{BlockHash, Commitment, PublicKey, Signature},
 };
 use tari_comms::{multiaddr::Multiaddr, types::CommsPublicKey, CommsNode};
+use tari_contacts::contacts_service::{
+    handle::ContactsServiceHandle,
+    types::{Confirmation, Direction, MessageBuilder, MessageDispatch, MessageId, MessageMetadata},
+};
 use tari_core::{
     consensus::{ConsensusBuilderError, ConsensusConstants, ConsensusManager},
     transactions::{
@@ -112,17 +179,26 @@ use tari_core::{
         },
     },
 };
-use tari_script::script;
+use tari_script::{script, ExecutionStack, HashValue, ScriptContext, TariScript};
 use tari_utilities::{hex::Hex, ByteArray};
 use tokio::{sync::broadcast, task};
 use tonic::{Request, Response, Status};
 
 use crate::{
-    grpc::{convert_to_transaction_event, TransactionWrapper},
+    grpc::{
+        convert_to_chat_message,
+        convert_to_draft_recipient,
+        convert_to_grpc_draft,
+        convert_to_payment_recipient,
+        convert_to_transaction_event,
+        TransactionWrapper,
+    },
     notifier::{CANCELLED, CONFIRMATION, MINED, QUEUED, RECEIVED, SENT},
 };
 
 const LOG_TARGET: &str = "wallet::ui::grpc";
+/// The longest date range a balance report can cover
+const MAX_BALANCE_REPORT_DAYS: i64 = 3660;
 
 async fn send_transaction_event(
     transaction_event: TransactionEvent,
@@ -159,10 +235,136 @@ impl WalletGrpcServer {
         self.wallet.output_manager_service.clone()
     }
 
+    fn get_deposit_tracking_service(&self) -> DepositTrackingHandle {
+        self.wallet.deposit_tracking_service.clone()
+    }
+
+    fn get_sweep_service(&self) -> SweepHandle {
+        self.wallet.sweep_service.clone()
+    }
+
+    fn get_contacts_service(&self) -> ContactsServiceHandle {
+        self.wallet.contacts_service.clone()
+    }
+
     fn comms(&self) -> &CommsNode {
         &self.wallet.comms
     }
 
+    /// Sends a transaction to each recipient concurrently, reporting the outcome of each
+    async fn send_payments(&self, recipients: Vec<PaymentRecipient>) -> Result<Vec<TransferResult>, Status> {
+        let recipients = recipients
+            .into_iter()
+            .enumerate()
+            .map(|(idx, dest)| -> Result<_, String> {
+                let address = TariAddress::from_str(&dest.address)
+                    .map_err(|_| format!("Destination address at index {} is malformed", idx))?;
+                Ok((
+                    dest.address,
+                    address,
+                    dest.amount,
+                    dest.fee_per_gram,
+                    dest.message,
+                    dest.payment_type,
+                    dest.payment_id,
+                ))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(Status::invalid_argument)?;
+
+        let mut transfers = Vec::new();
+        for (hex_address, address, amount, fee_per_gram, message, payment_type, payment_id) in recipients {
+            let payment_id = PaymentId::from_bytes(&payment_id)
+                .map_err(|_| "Invalid payment id".to_string())
+                .map_err(Status::invalid_argument)?;
+            let mut transaction_service = self.get_transaction_service();
+            transfers.push(async move {
+                (
+                    hex_address,
+                    if payment_type == PaymentType::StandardMimblewimble as i32 {
+                        transaction_service
+                            .send_transaction(
+                                address,
+                                amount.into(),
+                                UtxoSelectionCriteria::default(),
+                                OutputFeatures::default(),
+                                fee_per_gram.into(),
+                                message,
+                            )
+                            .await
+                    } else if payment_type == PaymentType::OneSided as i32 {
+                        transaction_service
+                            .send_one_sided_transaction(
+                                address,
+                                amount.into(),
+                                UtxoSelectionCriteria::default(),
+                                OutputFeatures::default(),
+                                fee_per_gram.into(),
+                                message,
+                                payment_id,
+                            )
+                            .await
+                    } else {
+                        transaction_service
+                            .send_one_sided_to_stealth_address_transaction(
+                                address,
+                                amount.into(),
+                                UtxoSelectionCriteria::default(),
+                                OutputFeatures::default(),
+                                fee_per_gram.into(),
+                                message,
+                                payment_id,
+                            )
+                            .await
+                    },
+                )
+            });
+        }
+
+        let transfers_results = future::join_all(transfers).await;
+
+        let results = transfers_results
+            .into_iter()
+            .map(|(address, result)| match result {
+                Ok(tx_id) => TransferResult {
+                    address,
+                    transaction_id: tx_id.into(),
+                    is_success: true,
+                    failure_message: Default::default(),
+                    approval_id: Default::default(),
+                },
+                Err(err) => {
+                    warn!(
+                        target: LOG_TARGET,
+                        "Failed to send transaction for address `{}`: {}", address, err
+                    );
+                    TransferResult {
+                        address,
+                        transaction_id: Default::default(),
+                        is_success: false,
+                        failure_message: err.to_string(),
+                        approval_id: match err {
+                            TransactionServiceError::SpendingPolicyViolation(
+                                SpendingPolicyViolation::ApprovalRequired { approval_id, .. },
+                            ) => approval_id,
+                            _ => Default::default(),
+                        },
+                    }
+                },
+            })
+            .collect();
+
+        Ok(results)
+    }
+
+    fn fetch_transaction_draft(&self, name: &str) -> Result<TransactionDraft, Status> {
+        self.wallet
+            .db
+            .fetch_transaction_draft(name)
+            .map_err(|e| Status::internal(e.to_string()))?
+            .ok_or_else(|| Status::not_found(format!("Transaction draft '{}' not found", name)))
+    }
+
     fn get_consensus_constants(&self) -> Result<&ConsensusConstants, WalletStorageError> {
         // If we don't have the chain metadata, we hope that VNReg consensus constants did not change - worst case, we
         // spend more than we need to or the transaction is rejected.
@@ -179,6 +381,7 @@ impl WalletGrpcServer {
 #[tonic::async_trait]
 impl wallet_server::Wallet for WalletGrpcServer {
     type GetCompletedTransactionsStream = mpsc::Receiver<Result<GetCompletedTransactionsResponse, Status>>;
+    type StreamChatEventsStream = mpsc::Receiver<Result<ChatEventResponse, Status>>;
     type StreamTransactionEventsStream = mpsc::Receiver<Result<TransactionEventResponse, Status>>;
 
     async fn get_version(&self, _: Request<GetVersionRequest>) -> Result<Response<GetVersionResponse>, Status> {
@@ -215,6 +418,10 @@ impl wallet_server::Wallet for WalletGrpcServer {
                 resp.version = update.version().to_string();
                 resp.sha = update.to_hash_hex();
                 resp.download_url = update.download_url().to_string();
+                resp.staged_path = update
+                    .staged_path()
+                    .map(|path| path.display().to_string())
+                    .unwrap_or_default();
             }
         }
 
@@ -270,8 +477,21 @@ impl wallet_server::Wallet for WalletGrpcServer {
         Ok(Response::new(SetBaseNodeResponse {}))
     }
 
-    async fn get_balance(&self, _request: Request<GetBalanceRequest>) -> Result<Response<GetBalanceResponse>, Status> {
+    async fn get_balance(&self, request: Request<GetBalanceRequest>) -> Result<Response<GetBalanceResponse>, Status> {
         let mut output_service = self.get_output_manager_service();
+        if let Some(filter) = request.into_inner().filter {
+            let breakdown = output_service
+                .get_balance_breakdown(convert_output_filter(filter)?)
+                .await
+                .map_err(|e| Status::not_found(format!("GetBalance error! {}", e)))?;
+            return Ok(Response::new(GetBalanceResponse {
+                available_balance: breakdown.available.as_u64(),
+                pending_incoming_balance: breakdown.pending_incoming.as_u64(),
+                pending_outgoing_balance: breakdown.pending_outgoing.as_u64(),
+                timelocked_balance: breakdown.time_locked.as_u64(),
+                immature_coinbase_balance: breakdown.immature_coinbase.as_u64(),
+            }));
+        }
         let balance = match output_service.get_balance().await {
             Ok(b) => b,
             Err(e) => return Err(Status::not_found(format!("GetBalance error! {}", e))),
@@ -281,15 +501,24 @@ impl wallet_server::Wallet for WalletGrpcServer {
             pending_incoming_balance: balance.pending_incoming_balance.0,
             pending_outgoing_balance: balance.pending_outgoing_balance.0,
             timelocked_balance: balance.time_locked_balance.unwrap_or_default().0,
+            immature_coinbase_balance: 0,
         }))
     }
 
     async fn get_unspent_amounts(
         &self,
-        _: Request<tari_rpc::Empty>,
+        request: Request<GetUnspentAmountsRequest>,
     ) -> Result<Response<GetUnspentAmountsResponse>, Status> {
         let mut output_service = self.get_output_manager_service();
-        let unspent_amounts = match output_service.get_unspent_outputs().await {
+        let unspent_amounts = match request.into_inner().filter {
+            Some(filter) => {
+                output_service
+                    .get_filtered_unspent_outputs(convert_output_filter(filter)?)
+                    .await
+            },
+            None => output_service.get_unspent_outputs().await,
+        };
+        let unspent_amounts = match unspent_amounts {
             Ok(uo) => uo,
             Err(e) => return Err(Status::not_found(format!("GetUnspentAmounts error! {}", e))),
         };
@@ -423,12 +652,14 @@ impl wallet_server::Wallet for WalletGrpcServer {
                         transaction_id: tx_id.as_u64(),
                         is_success: true,
                         failure_message: Default::default(),
+                        approval_id: Default::default(),
                     },
                     Err(e) => TransferResult {
                         address: Default::default(),
                         transaction_id: Default::default(),
                         is_success: false,
                         failure_message: e.to_string(),
+                        approval_id: Default::default(),
                     },
                 }
             },
@@ -439,6 +670,7 @@ impl wallet_server::Wallet for WalletGrpcServer {
                     transaction_id: Default::default(),
                     is_success: false,
                     failure_message: e.to_string(),
+                    approval_id: Default::default(),
                 }
             },
         };
@@ -473,12 +705,14 @@ impl wallet_server::Wallet for WalletGrpcServer {
                         transaction_id: tx_id.as_u64(),
                         is_success: true,
                         failure_message: Default::default(),
+                        approval_id: Default::default(),
                     },
                     Err(e) => TransferResult {
                         address: Default::default(),
                         transaction_id: Default::default(),
                         is_success: false,
                         failure_message: e.to_string(),
+                        approval_id: Default::default(),
                     },
                 }
             },
@@ -489,6 +723,7 @@ impl wallet_server::Wallet for WalletGrpcServer {
                     transaction_id: Default::default(),
                     is_success: false,
                     failure_message: e.to_string(),
+                    approval_id: Default::default(),
                 }
             },
         };
@@ -500,101 +735,7 @@ impl wallet_server::Wallet for WalletGrpcServer {
 
     async fn transfer(&self, request: Request<TransferRequest>) -> Result<Response<TransferResponse>, Status> {
         let message = request.into_inner();
-        let recipients = message
-            .recipients
-            .into_iter()
-            .enumerate()
-            .map(|(idx, dest)| -> Result<_, String> {
-                let address = TariAddress::from_str(&dest.address)
-                    .map_err(|_| format!("Destination address at index {} is malformed", idx))?;
-                Ok((
-                    dest.address,
-                    address,
-                    dest.amount,
-                    dest.fee_per_gram,
-                    dest.message,
-                    dest.payment_type,
-                    dest.payment_id,
-                ))
-            })
-            .collect::<Result<Vec<_>, _>>()
-            .map_err(Status::invalid_argument)?;
-
-        let mut transfers = Vec::new();
-        for (hex_address, address, amount, fee_per_gram, message, payment_type, payment_id) in recipients {
-            let payment_id = PaymentId::from_bytes(&payment_id)
-                .map_err(|_| "Invalid payment id".to_string())
-                .map_err(Status::invalid_argument)?;
-            let mut transaction_service = self.get_transaction_service();
-            transfers.push(async move {
-                (
-                    hex_address,
-                    if payment_type == PaymentType::StandardMimblewimble as i32 {
-                        transaction_service
-                            .send_transaction(
-                                address,
-                                amount.into(),
-                                UtxoSelectionCriteria::default(),
-                                OutputFeatures::default(),
-                                fee_per_gram.into(),
-                                message,
-                            )
-                            .await
-                    } else if payment_type == PaymentType::OneSided as i32 {
-                        transaction_service
-                            .send_one_sided_transaction(
-                                address,
-                                amount.into(),
-                                UtxoSelectionCriteria::default(),
-                                OutputFeatures::default(),
-                                fee_per_gram.into(),
-                                message,
-                                payment_id,
-                            )
-                            .await
-                    } else {
-                        transaction_service
-                            .send_one_sided_to_stealth_address_transaction(
-                                address,
-                                amount.into(),
-                                UtxoSelectionCriteria::default(),
-                                OutputFeatures::default(),
-                                fee_per_gram.into(),
-                                message,
-                                payment_id,
-                            )
-                            .await
-                    },
-                )
-            });
-        }
-
-        let transfers_results = future::join_all(transfers).await;
-
-        let results = transfers_results
-            .into_iter()
-            .map(|(address, result)| match result {
-                Ok(tx_id) => TransferResult {
-                    address,
-                    transaction_id: tx_id.into(),
-                    is_success: true,
-                    failure_message: Default::default(),
-                },
-                Err(err) => {
-                    warn!(
-                        target: LOG_TARGET,
-                        "Failed to send transaction for address `{}`: {}", address, err
-                    );
-                    TransferResult {
-                        address,
-                        transaction_id: Default::default(),
-                        is_success: false,
-                        failure_message: err.to_string(),
-                    }
-                },
-            })
-            .collect();
-
+        let results = self.send_payments(message.recipients).await?;
         Ok(Response::new(TransferResponse { results }))
     }
 
@@ -629,6 +770,7 @@ impl wallet_server::Wallet for WalletGrpcServer {
                     transaction_id: tx_id.as_u64(),
                     is_success: true,
                     failure_message: Default::default(),
+                    claim_proof: proof.to_portable_bytes(),
                     commitment: proof.commitment.to_vec(),
                     ownership_proof: proof.ownership_proof.map(CommitmentSignature::from),
                     range_proof: proof.range_proof.to_vec(),
@@ -970,6 +1112,11 @@ impl wallet_server::Wallet for WalletGrpcServer {
                 .ok_or_else(|| Status::invalid_argument("template_registration is empty"))?,
         )
         .map_err(|e| Status::invalid_argument(format!("template_registration is invalid: {}", e)))?;
+        if !template_registration.is_valid_signature() {
+            return Err(Status::invalid_argument(
+                "template_registration is not signed by the author public key",
+            ));
+        }
         let fee_per_gram = message.fee_per_gram;
 
         let message = format!("Template registration {}", template_registration.template_name);
@@ -1059,6 +1206,767 @@ impl wallet_server::Wallet for WalletGrpcServer {
         };
         Ok(Response::new(response))
     }
+
+    async fn get_validator_node_registrations(
+        &self,
+        _: Request<tari_rpc::Empty>,
+    ) -> Result<Response<GetValidatorNodeRegistrationsResponse>, Status> {
+        let mut output_service = self.get_output_manager_service();
+        let registrations = output_service
+            .get_validator_node_registrations()
+            .await
+            .map_err(|e| Status::internal(format!("GetValidatorNodeRegistrations error! {}", e)))?;
+        Ok(Response::new(GetValidatorNodeRegistrationsResponse {
+            registrations: registrations
+                .into_iter()
+                .map(|r| {
+                    let status = match r.status {
+                        ValidatorNodeRegistrationStatus::NotMined => {
+                            tari_rpc::ValidatorNodeRegistrationStatus::NotMined
+                        },
+                        ValidatorNodeRegistrationStatus::AwaitingActivation => {
+                            tari_rpc::ValidatorNodeRegistrationStatus::AwaitingActivation
+                        },
+                        ValidatorNodeRegistrationStatus::Active => tari_rpc::ValidatorNodeRegistrationStatus::Active,
+                        ValidatorNodeRegistrationStatus::Expired => tari_rpc::ValidatorNodeRegistrationStatus::Expired,
+                        ValidatorNodeRegistrationStatus::Deregistered => {
+                            tari_rpc::ValidatorNodeRegistrationStatus::Deregistered
+                        },
+                    };
+                    tari_rpc::ValidatorNodeRegistrationInfo {
+                        validator_node_public_key: r.validator_node_public_key.to_vec(),
+                        commitment: r.commitment.to_vec(),
+                        value: r.value.as_u64(),
+                        transaction_id: r.tx_id.map(|tx_id| tx_id.as_u64()).unwrap_or_default(),
+                        status: status as i32,
+                        mined_height: r.mined_height.unwrap_or_default(),
+                        confirmations: r.confirmations,
+                        start_epoch: r.start_epoch.map(|e| e.as_u64()).unwrap_or_default(),
+                        end_epoch: r.end_epoch.map(|e| e.as_u64()).unwrap_or_default(),
+                        expiry_height: r.expiry_height.unwrap_or_default(),
+                    }
+                })
+                .collect(),
+        }))
+    }
+
+    async fn anchor_application_data(
+        &self,
+        request: Request<AnchorApplicationDataRequest>,
+    ) -> Result<Response<AnchorApplicationDataResponse>, Status> {
+        let request = request.into_inner();
+        let payload = request
+            .payload
+            .try_into()
+            .map_err(|_| Status::invalid_argument("Application data payload is too large"))?;
+        let tx_id = self
+            .get_transaction_service()
+            .anchor_application_data(
+                request.application_id,
+                payload,
+                request.amount.into(),
+                UtxoSelectionCriteria::default(),
+                request.fee_per_gram.into(),
+                request.message,
+            )
+            .await
+            .map_err(|e| Status::internal(format!("AnchorApplicationData error! {}", e)))?;
+        Ok(Response::new(AnchorApplicationDataResponse {
+            transaction_id: tx_id.as_u64(),
+        }))
+    }
+
+    async fn get_anchored_application_data(
+        &self,
+        request: Request<GetAnchoredApplicationDataRequest>,
+    ) -> Result<Response<GetAnchoredApplicationDataResponse>, Status> {
+        let application_id = Some(request.into_inner().application_id).filter(|id| *id != 0);
+        let outputs = self
+            .get_output_manager_service()
+            .get_application_data_outputs(application_id)
+            .await
+            .map_err(|e| Status::internal(format!("GetAnchoredApplicationData error! {}", e)))?;
+        Ok(Response::new(GetAnchoredApplicationDataResponse {
+            data: outputs
+                .iter()
+                .filter_map(|o| {
+                    let data = o.wallet_output.features.application_data()?;
+                    Some(tari_rpc::AnchoredApplicationData {
+                        application_id: data.application_id,
+                        payload: data.payload.to_vec(),
+                        commitment: o.commitment.to_vec(),
+                        transaction_id: o.received_in_tx_id.map(|tx_id| tx_id.as_u64()).unwrap_or_default(),
+                        mined_height: o.mined_height.unwrap_or_default(),
+                        is_spent: o.spent_in_tx_id.is_some() || o.marked_deleted_at_height.is_some(),
+                    })
+                })
+                .collect(),
+        }))
+    }
+
+    async fn watch_deposits(
+        &self,
+        request: Request<WatchDepositsRequest>,
+    ) -> Result<Response<WatchDepositsResponse>, Status> {
+        let request = request.into_inner();
+        let watch = match request.watch {
+            Some(watch_deposits_request::Watch::Address(address)) => DepositWatch::Address(
+                TariAddress::from_str(&address)
+                    .map_err(|e| Status::invalid_argument(format!("Invalid address: {}", e)))?,
+            ),
+            Some(watch_deposits_request::Watch::PaymentId(payment_id)) => DepositWatch::PaymentId(
+                PaymentId::from_bytes(&payment_id).map_err(|_| Status::invalid_argument("Invalid payment id"))?,
+            ),
+            None => return Err(Status::invalid_argument("An address or payment id is required")),
+        };
+        let mut deposit_tracking_service = self.get_deposit_tracking_service();
+        let is_updated = if request.unwatch {
+            deposit_tracking_service.unwatch(watch).await
+        } else {
+            deposit_tracking_service.watch(watch).await
+        }
+        .map_err(|e| Status::internal(format!("WatchDeposits error! {}", e)))?;
+        Ok(Response::new(WatchDepositsResponse { is_updated }))
+    }
+
+    async fn get_deposit_events(
+        &self,
+        _: Request<tari_rpc::Empty>,
+    ) -> Result<Response<GetDepositEventsResponse>, Status> {
+        let events = self
+            .get_deposit_tracking_service()
+            .get_unacknowledged_events()
+            .await
+            .map_err(|e| Status::internal(format!("GetDepositEvents error! {}", e)))?;
+        Ok(Response::new(GetDepositEventsResponse {
+            events: events
+                .into_iter()
+                .map(|event| {
+                    let (kind, confirmations, mined_height, mined_in_block) = match event.kind {
+                        DepositEventKind::Seen => (tari_rpc::DepositEventKind::DepositSeen, 0, 0, vec![]),
+                        DepositEventKind::Confirmed {
+                            confirmations,
+                            mined_height,
+                            mined_in_block,
+                        } => (
+                            tari_rpc::DepositEventKind::DepositConfirmed,
+                            confirmations,
+                            mined_height,
+                            mined_in_block.to_vec(),
+                        ),
+                        DepositEventKind::Finalised {
+                            confirmations,
+                            mined_height,
+                            mined_in_block,
+                        } => (
+                            tari_rpc::DepositEventKind::DepositFinalised,
+                            confirmations,
+                            mined_height,
+                            mined_in_block.to_vec(),
+                        ),
+                        DepositEventKind::ReorgedOut { mined_in_block } => (
+                            tari_rpc::DepositEventKind::DepositReorgedOut,
+                            0,
+                            0,
+                            mined_in_block.to_vec(),
+                        ),
+                        DepositEventKind::Cancelled => (tari_rpc::DepositEventKind::DepositCancelled, 0, 0, vec![]),
+                    };
+                    tari_rpc::DepositEvent {
+                        sequence: event.sequence,
+                        transaction_id: event.tx_id.as_u64(),
+                        amount: event.amount.as_u64(),
+                        payment_id: event.payment_id.map(|p| p.to_bytes()).unwrap_or_default(),
+                        kind: kind as i32,
+                        confirmations,
+                        mined_height,
+                        mined_in_block,
+                    }
+                })
+                .collect(),
+        }))
+    }
+
+    async fn acknowledge_deposit_events(
+        &self,
+        request: Request<AcknowledgeDepositEventsRequest>,
+    ) -> Result<Response<AcknowledgeDepositEventsResponse>, Status> {
+        let num_acknowledged = self
+            .get_deposit_tracking_service()
+            .acknowledge_events(request.into_inner().sequence)
+            .await
+            .map_err(|e| Status::internal(format!("AcknowledgeDepositEvents error! {}", e)))?;
+        Ok(Response::new(AcknowledgeDepositEventsResponse {
+            num_acknowledged: num_acknowledged as u64,
+        }))
+    }
+
+    async fn set_sweep_policy(
+        &self,
+        request: Request<SetSweepPolicyRequest>,
+    ) -> Result<Response<tari_rpc::Empty>, Status> {
+        let policy = request
+            .into_inner()
+            .policy
+            .map(|policy| {
+                Ok::<_, Status>(SweepPolicy {
+                    cold_address: TariAddress::from_str(&policy.cold_address)
+                        .map_err(|e| Status::invalid_argument(format!("Invalid cold address: {}", e)))?,
+                    sweep_threshold: MicroMinotari::from(policy.sweep_threshold),
+                    float: MicroMinotari::from(policy.float),
+                    top_up_floor: MicroMinotari::from(policy.top_up_floor),
+                    fee_per_gram: MicroMinotari::from(policy.fee_per_gram),
+                })
+            })
+            .transpose()?;
+        self.get_sweep_service().set_policy(policy).await.map_err(|e| match e {
+            SweepError::InvalidPolicy(_) => Status::invalid_argument(e.to_string()),
+            _ => Status::internal(format!("SetSweepPolicy error! {}", e)),
+        })?;
+        Ok(Response::new(tari_rpc::Empty {}))
+    }
+
+    async fn get_sweep_policy(&self, _: Request<tari_rpc::Empty>) -> Result<Response<GetSweepPolicyResponse>, Status> {
+        let mut sweep_service = self.get_sweep_service();
+        let policy = sweep_service
+            .get_policy()
+            .await
+            .map_err(|e| Status::internal(format!("GetSweepPolicy error! {}", e)))?;
+        let sweeps = sweep_service
+            .get_sweeps()
+            .await
+            .map_err(|e| Status::internal(format!("GetSweepPolicy error! {}", e)))?;
+        Ok(Response::new(GetSweepPolicyResponse {
+            policy: policy.map(|policy| tari_rpc::SweepPolicy {
+                cold_address: policy.cold_address.to_base58(),
+                sweep_threshold: policy.sweep_threshold.as_u64(),
+                float: policy.float.as_u64(),
+                top_up_floor: policy.top_up_floor.as_u64(),
+                fee_per_gram: policy.fee_per_gram.as_u64(),
+            }),
+            sweeps: sweeps
+                .into_iter()
+                .map(|sweep| tari_rpc::Sweep {
+                    transaction_id: sweep.tx_id.as_u64(),
+                    amount: sweep.amount.as_u64(),
+                    cold_address: sweep.cold_address.to_base58(),
+                    timestamp: u64::try_from(sweep.timestamp.timestamp()).unwrap_or_default(),
+                })
+                .collect(),
+        }))
+    }
+
+    async fn simulate_script(
+        &self,
+        request: Request<SimulateScriptRequest>,
+    ) -> Result<Response<SimulateScriptResponse>, Status> {
+        let request = request.into_inner();
+        let script = TariScript::from_bytes(&request.script)
+            .map_err(|e| Status::invalid_argument(format!("Script is malformed: {}", e)))?;
+        let input_data = ExecutionStack::from_bytes(&request.input_data)
+            .map_err(|e| Status::invalid_argument(format!("Input data is malformed: {}", e)))?;
+        let prev_block_hash: HashValue = if request.prev_block_hash.is_empty() {
+            Default::default()
+        } else {
+            request
+                .prev_block_hash
+                .as_slice()
+                .try_into()
+                .map_err(|_| Status::invalid_argument("Previous block hash must be 32 bytes"))?
+        };
+        let commitment = if request.commitment.is_empty() {
+            Commitment::default()
+        } else {
+            Commitment::from_canonical_bytes(&request.commitment)
+                .map_err(|_| Status::invalid_argument("Commitment is malformed"))?
+        };
+
+        let context = ScriptContext::new(request.block_height, &prev_block_hash, &commitment);
+        let simulation = script.simulate(&input_data, &context);
+        let stack = simulation.stack.to_bytes();
+        let response = match simulation.failure {
+            None => SimulateScriptResponse {
+                is_success: true,
+                stack,
+                execution_cost: simulation.cost,
+                ..Default::default()
+            },
+            Some(failure) => SimulateScriptResponse {
+                is_success: false,
+                stack,
+                execution_cost: simulation.cost,
+                failure_message: failure.error.to_string(),
+                failed_at_opcode: failure.opcode_index.is_some(),
+                failed_opcode_index: failure.opcode_index.unwrap_or_default() as u64,
+                failed_opcode: failure
+                    .opcode_index
+                    .and_then(|i| script.opcode(i))
+                    .map(|op| op.to_string())
+                    .unwrap_or_default(),
+            },
+        };
+        Ok(Response::new(response))
+    }
+
+    async fn get_balance_report(
+        &self,
+        request: Request<GetBalanceReportRequest>,
+    ) -> Result<Response<GetBalanceReportResponse>, Status> {
+        let request = request.into_inner();
+        let from = parse_report_date(&request.from_date, "from_date")?;
+        let to = if request.to_date.is_empty() {
+            Utc::now().date_naive()
+        } else {
+            parse_report_date(&request.to_date, "to_date")?
+        };
+        if from > to {
+            return Err(Status::invalid_argument("from_date must not be after to_date"));
+        }
+        if BalanceReport::num_days(from, to) > MAX_BALANCE_REPORT_DAYS {
+            return Err(Status::invalid_argument(format!(
+                "The date range may not exceed {} days",
+                MAX_BALANCE_REPORT_DAYS
+            )));
+        }
+
+        let transactions = self
+            .get_transaction_service()
+            .get_completed_transactions()
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+        let report = BalanceReport::new(transactions.values(), from, to)
+            .ok_or_else(|| Status::invalid_argument("from_date must not be after to_date"))?;
+        Ok(Response::new(GetBalanceReportResponse {
+            opening_balance: report.opening_balance.as_u64(),
+            closing_balance: report.closing_balance.as_u64(),
+            total_inflow: report.total_inflow.as_u64(),
+            total_outflow: report.total_outflow.as_u64(),
+            total_fees_paid: report.total_fees_paid.as_u64(),
+            total_mined: report.total_mined.as_u64(),
+            days: report
+                .days
+                .into_iter()
+                .map(|day| DailyBalance {
+                    date: day.date.format("%Y-%m-%d").to_string(),
+                    inflow: day.inflow.as_u64(),
+                    outflow: day.outflow.as_u64(),
+                    fees: day.fees.as_u64(),
+                    mined: day.mined.as_u64(),
+                    balance: day.balance.as_u64(),
+                })
+                .collect(),
+        }))
+    }
+
+    async fn save_transaction_draft(
+        &self,
+        request: Request<SaveTransactionDraftRequest>,
+    ) -> Result<Response<tari_rpc::TransactionDraft>, Status> {
+        let request = request.into_inner();
+        let name = request.name.trim().to_string();
+        if name.is_empty() {
+            return Err(Status::invalid_argument("A transaction draft needs a name"));
+        }
+        if request.recipients.is_empty() {
+            return Err(Status::invalid_argument(
+                "A transaction draft needs at least one recipient",
+            ));
+        }
+        let recipients = request
+            .recipients
+            .into_iter()
+            .map(convert_to_draft_recipient)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(Status::invalid_argument)?;
+
+        let now = Utc::now().naive_utc();
+        self.wallet
+            .db
+            .save_transaction_draft(TransactionDraft {
+                name: name.clone(),
+                notes: request.notes,
+                recipients,
+                created_at: now,
+                updated_at: now,
+            })
+            .map_err(|e| Status::internal(e.to_string()))?;
+        let draft = self.fetch_transaction_draft(&name)?;
+        Ok(Response::new(convert_to_grpc_draft(draft)))
+    }
+
+    async fn get_transaction_drafts(
+        &self,
+        _: Request<tari_rpc::Empty>,
+    ) -> Result<Response<GetTransactionDraftsResponse>, Status> {
+        let drafts = self
+            .wallet
+            .db
+            .fetch_transaction_drafts()
+            .map_err(|e| Status::internal(e.to_string()))?;
+        Ok(Response::new(GetTransactionDraftsResponse {
+            drafts: drafts.into_iter().map(convert_to_grpc_draft).collect(),
+        }))
+    }
+
+    async fn delete_transaction_draft(
+        &self,
+        request: Request<DeleteTransactionDraftRequest>,
+    ) -> Result<Response<tari_rpc::Empty>, Status> {
+        let name = request.into_inner().name;
+        let deleted = self
+            .wallet
+            .db
+            .delete_transaction_draft(&name)
+            .map_err(|e| Status::internal(e.to_string()))?;
+        if !deleted {
+            return Err(Status::not_found(format!("Transaction draft '{}' not found", name)));
+        }
+        Ok(Response::new(tari_rpc::Empty {}))
+    }
+
+    async fn execute_transaction_draft(
+        &self,
+        request: Request<ExecuteTransactionDraftRequest>,
+    ) -> Result<Response<TransferResponse>, Status> {
+        let draft = self.fetch_transaction_draft(&request.into_inner().name)?;
+        info!(
+            target: LOG_TARGET,
+            "Executing transaction draft '{}' with {} recipient(s) and a total of {}",
+            draft.name,
+            draft.recipients.len(),
+            draft.total_amount()
+        );
+        let recipients = draft.recipients.into_iter().map(convert_to_payment_recipient).collect();
+        let results = self.send_payments(recipients).await?;
+        Ok(Response::new(TransferResponse { results }))
+    }
+
+    async fn get_pending_approvals(
+        &self,
+        _: Request<tari_rpc::Empty>,
+    ) -> Result<Response<GetPendingApprovalsResponse>, Status> {
+        let approvals = self
+            .get_transaction_service()
+            .get_pending_approvals()
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?
+            .into_iter()
+            .map(|approval| PendingApproval {
+                approval_id: approval.approval_id,
+                destination: approval
+                    .destination
+                    .map(|address| address.to_base58())
+                    .unwrap_or_default(),
+                amount: approval.amount.as_u64(),
+                requested_at: u64::try_from(approval.requested_at.timestamp()).unwrap_or_default(),
+            })
+            .collect();
+        Ok(Response::new(GetPendingApprovalsResponse { approvals }))
+    }
+
+    async fn approve_payment(
+        &self,
+        request: Request<ApprovePaymentRequest>,
+    ) -> Result<Response<ApprovePaymentResponse>, Status> {
+        let approval_id = request.into_inner().approval_id;
+        info!(target: LOG_TARGET, "Approving held payment {}", approval_id);
+        let tx_id = self
+            .get_transaction_service()
+            .approve_payment(approval_id)
+            .await
+            .map_err(spending_policy_status)?;
+        Ok(Response::new(ApprovePaymentResponse {
+            transaction_id: tx_id.as_u64(),
+        }))
+    }
+
+    async fn reject_payment(
+        &self,
+        request: Request<RejectPaymentRequest>,
+    ) -> Result<Response<tari_rpc::Empty>, Status> {
+        let approval_id = request.into_inner().approval_id;
+        info!(target: LOG_TARGET, "Rejecting held payment {}", approval_id);
+        self.get_transaction_service()
+            .reject_payment(approval_id)
+            .await
+            .map_err(spending_policy_status)?;
+        Ok(Response::new(tari_rpc::Empty {}))
+    }
+
+    async fn generate_payment_proof(
+        &self,
+        request: Request<GeneratePaymentProofRequest>,
+    ) -> Result<Response<tari_rpc::PaymentProof>, Status> {
+        let tx_id = TxId::from(request.into_inner().transaction_id);
+        let proof = self
+            .get_transaction_service()
+            .generate_payment_proof(tx_id)
+            .await
+            .map_err(|e| match e {
+                TransactionServiceError::PaymentProofUnavailable { .. } => Status::failed_precondition(e.to_string()),
+                _ => Status::internal(e.to_string()),
+            })?;
+        Ok(Response::new(proof.into()))
+    }
+
+    async fn set_app_data(&self, request: Request<SetAppDataRequest>) -> Result<Response<tari_rpc::Empty>, Status> {
+        let request = request.into_inner();
+        self.wallet
+            .db
+            .set_app_value(&request.namespace, &request.key, request.value)
+            .map_err(app_data_status)?;
+        Ok(Response::new(tari_rpc::Empty {}))
+    }
+
+    async fn get_app_data(&self, request: Request<GetAppDataRequest>) -> Result<Response<GetAppDataResponse>, Status> {
+        let request = request.into_inner();
+        let value = self
+            .wallet
+            .db
+            .get_app_value(&request.namespace, &request.key)
+            .map_err(app_data_status)?;
+        Ok(Response::new(GetAppDataResponse {
+            found: value.is_some(),
+            value: value.unwrap_or_default(),
+        }))
+    }
+
+    async fn delete_app_data(
+        &self,
+        request: Request<GetAppDataRequest>,
+    ) -> Result<Response<DeleteAppDataResponse>, Status> {
+        let request = request.into_inner();
+        let deleted = self
+            .wallet
+            .db
+            .clear_app_value(&request.namespace, &request.key)
+            .map_err(app_data_status)?;
+        Ok(Response::new(DeleteAppDataResponse { deleted }))
+    }
+
+    async fn list_app_data_keys(
+        &self,
+        request: Request<ListAppDataKeysRequest>,
+    ) -> Result<Response<ListAppDataKeysResponse>, Status> {
+        let keys = self
+            .wallet
+            .db
+            .get_app_keys(&request.into_inner().namespace)
+            .map_err(app_data_status)?;
+        Ok(Response::new(ListAppDataKeysResponse { keys }))
+    }
+
+    async fn get_log_filters(
+        &self,
+        _: Request<tari_rpc::Empty>,
+    ) -> Result<Response<tari_rpc::LogFiltersResponse>, Status> {
+        let directives = log_filter_directives().map_err(|e| Status::internal(e.to_string()))?;
+        Ok(Response::new(tari_rpc::LogFiltersResponse {
+            directives: directives.iter().map(ToString::to_string).collect(),
+        }))
+    }
+
+    async fn set_log_filters(
+        &self,
+        request: Request<tari_rpc::SetLogFiltersRequest>,
+    ) -> Result<Response<tari_rpc::LogFiltersResponse>, Status> {
+        let request = request.into_inner();
+        let directives = request
+            .directives
+            .iter()
+            .map(|d| d.parse::<LogFilterDirective>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(Status::invalid_argument)?;
+        info!(
+            target: LOG_TARGET,
+            "Setting log filters: [{}] (reset: {})",
+            request.directives.join(", "),
+            request.reset
+        );
+        let directives =
+            set_log_filter_directives(directives, request.reset).map_err(|e| Status::internal(e.to_string()))?;
+        Ok(Response::new(tari_rpc::LogFiltersResponse {
+            directives: directives.iter().map(ToString::to_string).collect(),
+        }))
+    }
+
+    async fn send_chat_message(
+        &self,
+        request: Request<SendChatMessageRequest>,
+    ) -> Result<Response<SendChatMessageResponse>, Status> {
+        let request = request.into_inner();
+        let receiver_address = TariAddress::from_str(&request.receiver_address)
+            .map_err(|_| Status::invalid_argument("Receiver address is malformed"))?;
+        let sender_address = self
+            .wallet
+            .get_wallet_interactive_address()
+            .await
+            .map_err(|e| Status::internal(format!("{:?}", e)))?;
+
+        let mut builder = MessageBuilder::new()
+            .receiver_address(receiver_address)
+            .sender_address(sender_address)
+            .message(request.body)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+        for metadata in request.metadata {
+            let metadata = MessageMetadata {
+                key: metadata
+                    .key
+                    .try_into()
+                    .map_err(|_| Status::invalid_argument("Metadata key is too long"))?,
+                data: metadata
+                    .data
+                    .try_into()
+                    .map_err(|_| Status::invalid_argument("Metadata data is too long"))?,
+            };
+            builder = builder
+                .metadata(metadata)
+                .map_err(|e| Status::invalid_argument(e.to_string()))?;
+        }
+        let message = builder.build();
+        let message_id = message.message_id.to_vec();
+
+        self.get_contacts_service()
+            .send_message(message)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+        Ok(Response::new(SendChatMessageResponse { message_id }))
+    }
+
+    async fn get_chat_messages(
+        &self,
+        request: Request<GetChatMessagesRequest>,
+    ) -> Result<Response<GetChatMessagesResponse>, Status> {
+        let request = request.into_inner();
+        let address =
+            TariAddress::from_str(&request.address).map_err(|_| Status::invalid_argument("Address is malformed"))?;
+        let messages = self
+            .get_contacts_service()
+            .get_messages(address, request.limit, request.page)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+        Ok(Response::new(GetChatMessagesResponse {
+            messages: messages.into_iter().map(convert_to_chat_message).collect(),
+        }))
+    }
+
+    async fn send_chat_read_receipt(
+        &self,
+        request: Request<SendChatReadReceiptRequest>,
+    ) -> Result<Response<SendChatReadReceiptResponse>, Status> {
+        let message_id = MessageId::try_from(request.into_inner().message_id)
+            .map_err(|_| Status::invalid_argument("Message id is malformed"))?;
+        let mut contacts_service = self.get_contacts_service();
+        let message = contacts_service
+            .get_message(&message_id)
+            .await
+            .map_err(|e| Status::not_found(e.to_string()))?;
+        if message.direction != Direction::Inbound {
+            return Err(Status::invalid_argument(
+                "Read receipts can only be sent for received messages",
+            ));
+        }
+        contacts_service
+            .send_read_confirmation(message.sender_address, message_id)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+        Ok(Response::new(SendChatReadReceiptResponse {}))
+    }
+
+    async fn get_chat_conversationalists(
+        &self,
+        _: Request<tari_rpc::Empty>,
+    ) -> Result<Response<GetChatConversationalistsResponse>, Status> {
+        let addresses = self
+            .get_contacts_service()
+            .get_conversationalists()
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+        Ok(Response::new(GetChatConversationalistsResponse {
+            addresses: addresses.iter().map(|a| a.to_vec()).collect(),
+        }))
+    }
+
+    async fn stream_chat_events(
+        &self,
+        _request: Request<ChatEventRequest>,
+    ) -> Result<Response<Self::StreamChatEventsStream>, Status> {
+        let (mut sender, receiver) = mpsc::channel(100);
+        let mut message_events = self.get_contacts_service().get_messages_event_stream();
+
+        task::spawn(async move {
+            loop {
+                let event = match message_events.recv().await {
+                    Ok(dispatch) => match (*dispatch).clone() {
+                        MessageDispatch::Message(message) => {
+                            chat_event_response::Event::Message(convert_to_chat_message(message))
+                        },
+                        MessageDispatch::DeliveryConfirmation(confirmation) => {
+                            chat_event_response::Event::DeliveryReceipt(convert_to_chat_receipt(confirmation))
+                        },
+                        MessageDispatch::ReadConfirmation(confirmation) => {
+                            chat_event_response::Event::ReadReceipt(convert_to_chat_receipt(confirmation))
+                        },
+                        MessageDispatch::Presence(_) => continue,
+                    },
+                    Err(broadcast::error::RecvError::Lagged(n)) => {
+                        warn!(target: LOG_TARGET, "Missed {} chat events", n);
+                        continue;
+                    },
+                    Err(broadcast::error::RecvError::Closed) => break,
+                };
+                if sender.send(Ok(ChatEventResponse { event: Some(event) })).await.is_err() {
+                    debug!(target: LOG_TARGET, "Chat event stream closed by the client");
+                    break;
+                }
+            }
+        });
+        Ok(Response::new(receiver))
+    }
+}
+
+fn parse_report_date(date: &str, name: &str) -> Result<NaiveDate, Status> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map_err(|e| Status::invalid_argument(format!("{} must be formatted as YYYY-MM-DD: {}", name, e)))
+}
+
+fn app_data_status(error: WalletStorageError) -> Status {
+    match error {
+        WalletStorageError::InvalidAppData(_) => Status::invalid_argument(error.to_string()),
+        _ => Status::internal(error.to_string()),
+    }
+}
+
+/// Maps spending policy violations to a status that tells the client what to do about them
+fn spending_policy_status(error: TransactionServiceError) -> Status {
+    match error {
+        TransactionServiceError::SpendingPolicyViolation(SpendingPolicyViolation::UnknownApproval(_)) => {
+            Status::not_found(error.to_string())
+        },
+        TransactionServiceError::SpendingPolicyViolation(_) => Status::failed_precondition(error.to_string()),
+        _ => Status::internal(error.to_string()),
+    }
+}
+
+fn convert_output_filter(filter: tari_rpc::OutputFilter) -> Result<OutputFilter, Status> {
+    let payment_id = if filter.payment_id.is_empty() {
+        None
+    } else {
+        Some(PaymentId::from_bytes(&filter.payment_id).map_err(|_| Status::invalid_argument("Invalid payment id"))?)
+    };
+    Ok(OutputFilter {
+        only_matured_coinbase: filter.only_matured_coinbase,
+        only_one_sided: filter.only_one_sided,
+        exclude_time_locked: filter.exclude_time_locked,
+        payment_id,
+    })
+}
+
+fn convert_to_chat_receipt(confirmation: Confirmation) -> ChatReceipt {
+    ChatReceipt {
+        message_id: confirmation.message_id.to_vec(),
+        timestamp: confirmation.timestamp,
+    }
 }
 
 async fn handle_completed_tx(
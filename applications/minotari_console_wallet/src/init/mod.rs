@@ -35,6 +35,7 @@ use minotari_wallet::{
     output_manager_service::storage::database::OutputManagerDatabase,
     storage::{
         database::{WalletBackend, WalletDatabase},
+        integrity,
         sqlite_utilities::initialize_sqlite_database_backends,
     },
     wallet::{derive_comms_secret_key, read_or_create_master_seed, read_or_create_wallet_type},
@@ -67,7 +68,13 @@ use tari_comms::{
 use tari_core::{
     consensus::ConsensusManager,
     transactions::{
-        key_manager::{TariKeyId, TransactionKeyManagerInterface, LEDGER_NOT_SUPPORTED},
+        key_manager::{
+            RemoteSigner,
+            TariKeyId,
+            TransactionKeyManagerInterface,
+            TransactionKeyManagerWrapper,
+            LEDGER_NOT_SUPPORTED,
+        },
         transaction_components::TransactionError,
         CryptoFactories,
     },
@@ -75,7 +82,10 @@ use tari_core::{
 use tari_crypto::{keys::PublicKey as PublicKeyTrait, ristretto::RistrettoPublicKey};
 use tari_key_manager::{
     cipher_seed::CipherSeed,
-    key_manager_service::{storage::database::KeyManagerBackend, KeyManagerInterface},
+    key_manager_service::{
+        storage::database::{KeyManagerBackend, KeyManagerDatabase},
+        KeyManagerInterface,
+    },
     mnemonic::MnemonicLanguage,
 };
 use tari_p2p::{auto_update::AutoUpdateConfig, peer_seeds::SeedPeer, PeerSeedsConfig, TransportType};
@@ -273,6 +283,7 @@ pub async fn change_password(
         shutdown_signal,
         non_interactive_mode,
         None,
+        None,
     )
     .await?;
 
@@ -288,6 +299,61 @@ pub async fn change_password(
     })
 }
 
+/// Verifies the wallet database without starting the wallet, moving inconsistent rows out of the way if
+/// `quarantine` is set
+pub async fn verify_wallet_db(
+    config: &ApplicationConfig,
+    password: SafePassword,
+    quarantine: bool,
+) -> Result<(), ExitError> {
+    let (wallet_backend, transaction_backend, output_manager_backend, _contacts_backend, key_manager_backend) =
+        initialize_sqlite_database_backends(&config.wallet.db_file, password, config.wallet.db_connection_pool_size)?;
+    let wallet_db = WalletDatabase::new(wallet_backend.clone());
+    let master_seed = wallet_db
+        .get_master_seed()
+        .map_err(|e| {
+            ExitError::new(
+                ExitCode::DatabaseError,
+                format!("The master seed could not be read: {}", e),
+            )
+        })?
+        .ok_or_else(|| ExitError::new(ExitCode::DatabaseError, "The wallet database has no master seed"))?;
+    let wallet_type = wallet_db.get_wallet_type()?.unwrap_or_default();
+    let key_manager = TransactionKeyManagerWrapper::new(
+        master_seed,
+        KeyManagerDatabase::new(key_manager_backend.clone()),
+        CryptoFactories::default(),
+        Arc::new(wallet_type),
+    )
+    .map_err(|e| ExitError::new(ExitCode::KeyManagerServiceError, e))?;
+
+    let report = integrity::verify_wallet_db(
+        &wallet_backend,
+        &transaction_backend,
+        &output_manager_backend,
+        &key_manager_backend,
+        &key_manager,
+        quarantine,
+    )
+    .await?;
+    for issue in &report.issues {
+        println!("{}", issue);
+    }
+    println!(
+        "Checked {} rows, {} inconsistent",
+        report.rows_checked,
+        report.issues.len()
+    );
+    if report.is_consistent() {
+        Ok(())
+    } else {
+        Err(ExitError::new(
+            ExitCode::DatabaseError,
+            "The wallet database has inconsistent rows",
+        ))
+    }
+}
+
 /// Populates the PeerConfig struct from:
 /// 1. The custom peer in the wallet config if it exists
 /// 2. The custom peer in the wallet db if it exists
@@ -380,6 +446,8 @@ pub(crate) fn wallet_mode(cli: &Cli, boot_mode: WalletBoot) -> WalletMode {
     }
 
     match (cli.non_interactive_mode, cli.input_file.clone(), cli.command2.clone()) {
+        // Daemon mode
+        (true, None, None) if cli.daemon => WalletMode::Daemon,
         // TUI mode
         (false, None, None) => WalletMode::Tui,
         // GRPC mode
@@ -405,6 +473,7 @@ pub async fn init_wallet(
     shutdown_signal: ShutdownSignal,
     non_interactive_mode: bool,
     wallet_type: Option<WalletType>,
+    remote_signer: Option<Arc<dyn RemoteSigner>>,
 ) -> Result<WalletSqlite, ExitError> {
     fs::create_dir_all(
         config
@@ -470,6 +539,7 @@ pub async fn init_wallet(
         shutdown_signal,
         master_seed,
         wallet_type,
+        remote_signer,
         user_agent,
     )
     .await
@@ -921,6 +991,24 @@ pub fn prompt_wallet_type(
     }
 }
 
+/// The private view key of a new or recovered remote signer wallet, which the signer does not give out. It is printed
+/// by `minotari_remote_signer --print-view-key`.
+pub fn remote_signer_view_key(
+    non_interactive: bool,
+    view_private_key: Option<String>,
+) -> Result<PrivateKey, ExitError> {
+    match view_private_key {
+        Some(view_key) => PrivateKey::from_hex(&view_key)
+            .map_err(|e| ExitError::new(ExitCode::InputError, format!("Invalid view key: {}", e))),
+        None if non_interactive => Err(ExitError::new(
+            ExitCode::InputError,
+            "The view key of a remote signer wallet must be given with `--view-private-key` in non-interactive mode",
+        )),
+        None => prompt_private_key("Enter the view key printed by `minotari_remote_signer --print-view-key`:")
+            .ok_or_else(|| ExitError::new(ExitCode::InputError, "Invalid view key")),
+    }
+}
+
 pub fn prompt_ledger_account(boot_mode: WalletBoot) -> Option<u64> {
     let question = match boot_mode {
         WalletBoot::Recovery => "\r\nPlease enter the account number you previously used for your device.",
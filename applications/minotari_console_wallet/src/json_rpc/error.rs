@@ -0,0 +1,55 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use minotari_wallet::{
+    output_manager_service::error::OutputManagerError,
+    transaction_service::error::TransactionServiceError,
+};
+use serde_json::{json, Value};
+
+#[derive(Debug, thiserror::Error)]
+pub enum JsonRpcError {
+    #[error("Parse error: {0}")]
+    ParseError(String),
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+    #[error("Method not found: {0}")]
+    MethodNotFound(String),
+    #[error("Invalid params: {0}")]
+    InvalidParams(String),
+    #[error("Wallet error: {0}")]
+    WalletError(String),
+}
+
+impl JsonRpcError {
+    /// The JSON-RPC 2.0 error code, see <https://www.jsonrpc.org/specification#error_object>. Wallet errors use the
+    /// start of the implementation-defined server error range.
+    pub fn code(&self) -> i64 {
+        match self {
+            JsonRpcError::ParseError(_) => -32700,
+            JsonRpcError::InvalidRequest(_) => -32600,
+            JsonRpcError::MethodNotFound(_) => -32601,
+            JsonRpcError::InvalidParams(_) => -32602,
+            JsonRpcError::WalletError(_) => -32000,
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "code": self.code(),
+            "message": self.to_string(),
+        })
+    }
+}
+
+impl From<OutputManagerError> for JsonRpcError {
+    fn from(err: OutputManagerError) -> Self {
+        JsonRpcError::WalletError(err.to_string())
+    }
+}
+
+impl From<TransactionServiceError> for JsonRpcError {
+    fn from(err: TransactionServiceError) -> Self {
+        JsonRpcError::WalletError(err.to_string())
+    }
+}
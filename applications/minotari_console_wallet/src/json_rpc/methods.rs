@@ -0,0 +1,396 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::str::FromStr;
+
+use log::*;
+use minotari_wallet::{
+    output_manager_service::UtxoSelectionCriteria,
+    transaction_service::storage::models::{CompletedTransaction, InboundTransaction, OutboundTransaction},
+    WalletSqlite,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use tari_common_types::{
+    tari_address::{TariAddress, TariAddressFeatures},
+    transaction::TransactionDirection,
+};
+use tari_core::transactions::{
+    tari_amount::MicroMinotari,
+    transaction_components::{encrypted_data::PaymentId, OutputFeatures},
+};
+
+use crate::json_rpc::JsonRpcError;
+
+const LOG_TARGET: &str = "wallet::json_rpc::methods";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GetBalanceResult {
+    /// The spendable balance plus funds that are due to be received
+    pub balance: u64,
+    /// The balance that can be spent right now
+    pub unlocked_balance: u64,
+    pub time_locked_balance: u64,
+    pub pending_incoming_balance: u64,
+    pub pending_outgoing_balance: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TransferDestination {
+    pub address: String,
+    pub amount: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TransferParams {
+    pub destinations: Vec<TransferDestination>,
+    /// Defaults to the wallet's configured fee per gram
+    #[serde(default)]
+    pub fee_per_gram: Option<u64>,
+    #[serde(default)]
+    pub payment_id: Option<u64>,
+    #[serde(default)]
+    pub message: String,
+    /// Send interactively instead of as a one-sided stealth payment, which requires the recipient to be online
+    #[serde(default)]
+    pub interactive: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TransferResult {
+    /// One transaction is created per destination, in the order the destinations were given
+    pub tx_id_list: Vec<u64>,
+    pub amount_list: Vec<u64>,
+}
+
+/// Selects which transfer categories to return, mirroring the `wallet-rpc` flags. `in` and `out` are mined
+/// transactions, `pool` and `pending` are unmined incoming and outgoing transactions respectively.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct GetTransfersParams {
+    #[serde(rename = "in", default)]
+    pub incoming: bool,
+    #[serde(default)]
+    pub out: bool,
+    #[serde(default)]
+    pub pending: bool,
+    #[serde(default)]
+    pub failed: bool,
+    #[serde(default)]
+    pub pool: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TransferEntry {
+    pub txid: u64,
+    /// The counterparty's address
+    pub address: String,
+    pub amount: u64,
+    pub fee: u64,
+    pub height: Option<u64>,
+    pub confirmations: Option<u64>,
+    pub timestamp: i64,
+    pub payment_id: Option<String>,
+    pub note: String,
+    #[serde(rename = "type")]
+    pub transfer_type: &'static str,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GetTransfersResult {
+    #[serde(rename = "in", skip_serializing_if = "Vec::is_empty")]
+    pub incoming: Vec<TransferEntry>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub out: Vec<TransferEntry>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub pending: Vec<TransferEntry>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub failed: Vec<TransferEntry>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub pool: Vec<TransferEntry>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ValidateAddressParams {
+    pub address: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ValidateAddressResult {
+    pub valid: bool,
+    pub nettype: Option<String>,
+    pub interactive: bool,
+    pub one_sided: bool,
+}
+
+/// Implements the JSON-RPC methods on top of the wallet service handles.
+#[derive(Clone)]
+pub struct JsonRpcMethods {
+    wallet: WalletSqlite,
+    default_fee_per_gram: MicroMinotari,
+}
+
+impl JsonRpcMethods {
+    pub fn new(wallet: WalletSqlite, default_fee_per_gram: MicroMinotari) -> Self {
+        Self {
+            wallet,
+            default_fee_per_gram,
+        }
+    }
+
+    /// Dispatches a single method call
+    pub async fn call(&self, method: &str, params: Value) -> Result<Value, JsonRpcError> {
+        debug!(target: LOG_TARGET, "JSON-RPC call `{}`", method);
+        let result = match method {
+            "get_balance" => to_json(self.get_balance().await?)?,
+            "transfer" => to_json(self.transfer(parse_params(params)?).await?)?,
+            "get_transfers" => to_json(self.get_transfers(parse_params(params)?).await?)?,
+            "validate_address" => to_json(validate_address(parse_params(params)?))?,
+            _ => return Err(JsonRpcError::MethodNotFound(method.to_string())),
+        };
+        Ok(result)
+    }
+
+    async fn get_balance(&self) -> Result<GetBalanceResult, JsonRpcError> {
+        let balance = self.wallet.output_manager_service.clone().get_balance().await?;
+        let time_locked_balance = balance.time_locked_balance.unwrap_or_default();
+        Ok(GetBalanceResult {
+            balance: (balance.available_balance + balance.pending_incoming_balance).as_u64(),
+            unlocked_balance: balance
+                .available_balance
+                .as_u64()
+                .saturating_sub(time_locked_balance.as_u64()),
+            time_locked_balance: time_locked_balance.as_u64(),
+            pending_incoming_balance: balance.pending_incoming_balance.as_u64(),
+            pending_outgoing_balance: balance.pending_outgoing_balance.as_u64(),
+        })
+    }
+
+    async fn transfer(&self, params: TransferParams) -> Result<TransferResult, JsonRpcError> {
+        if params.destinations.is_empty() {
+            return Err(JsonRpcError::InvalidParams("No destinations provided".to_string()));
+        }
+        let destinations = params
+            .destinations
+            .into_iter()
+            .enumerate()
+            .map(|(idx, dest)| {
+                let address = TariAddress::from_str(&dest.address).map_err(|_| {
+                    JsonRpcError::InvalidParams(format!("Destination address at index {} is malformed", idx))
+                })?;
+                Ok((address, MicroMinotari::from(dest.amount)))
+            })
+            .collect::<Result<Vec<_>, JsonRpcError>>()?;
+        let fee_per_gram = params
+            .fee_per_gram
+            .map(MicroMinotari::from)
+            .unwrap_or(self.default_fee_per_gram);
+        let payment_id = params.payment_id.map(PaymentId::U64).unwrap_or(PaymentId::Empty);
+
+        let mut result = TransferResult {
+            tx_id_list: Vec::with_capacity(destinations.len()),
+            amount_list: Vec::with_capacity(destinations.len()),
+        };
+        let mut transaction_service = self.wallet.transaction_service.clone();
+        for (address, amount) in destinations {
+            let tx_id = if params.interactive {
+                transaction_service
+                    .send_transaction(
+                        address,
+                        amount,
+                        UtxoSelectionCriteria::default(),
+                        OutputFeatures::default(),
+                        fee_per_gram,
+                        params.message.clone(),
+                    )
+                    .await?
+            } else {
+                transaction_service
+                    .send_one_sided_to_stealth_address_transaction(
+                        address,
+                        amount,
+                        UtxoSelectionCriteria::default(),
+                        OutputFeatures::default(),
+                        fee_per_gram,
+                        params.message.clone(),
+                        payment_id.clone(),
+                    )
+                    .await?
+            };
+            result.tx_id_list.push(tx_id.as_u64());
+            result.amount_list.push(amount.as_u64());
+        }
+        Ok(result)
+    }
+
+    async fn get_transfers(&self, params: GetTransfersParams) -> Result<GetTransfersResult, JsonRpcError> {
+        let mut transaction_service = self.wallet.transaction_service.clone();
+        let mut result = GetTransfersResult::default();
+
+        if params.incoming || params.out || params.pending || params.pool {
+            for tx in transaction_service.get_completed_transactions().await?.into_values() {
+                let is_inbound = tx.direction == TransactionDirection::Inbound;
+                let is_mined = tx.mined_height.is_some();
+                match (is_inbound, is_mined) {
+                    (true, true) if params.incoming => result.incoming.push(completed_entry(tx, "in")),
+                    (true, false) if params.pool => result.pool.push(completed_entry(tx, "pool")),
+                    (false, true) if params.out => result.out.push(completed_entry(tx, "out")),
+                    (false, false) if params.pending => result.pending.push(completed_entry(tx, "pending")),
+                    _ => {},
+                }
+            }
+        }
+        if params.pool {
+            for tx in transaction_service
+                .get_pending_inbound_transactions()
+                .await?
+                .into_values()
+            {
+                result.pool.push(inbound_entry(tx));
+            }
+        }
+        if params.pending {
+            for tx in transaction_service
+                .get_pending_outbound_transactions()
+                .await?
+                .into_values()
+            {
+                result.pending.push(outbound_entry(tx));
+            }
+        }
+        if params.failed {
+            for tx in transaction_service
+                .get_cancelled_completed_transactions()
+                .await?
+                .into_values()
+            {
+                result.failed.push(completed_entry(tx, "failed"));
+            }
+        }
+
+        for entries in [
+            &mut result.incoming,
+            &mut result.out,
+            &mut result.pending,
+            &mut result.failed,
+            &mut result.pool,
+        ] {
+            entries.sort_by_key(|entry| entry.timestamp);
+        }
+        Ok(result)
+    }
+}
+
+fn validate_address(params: ValidateAddressParams) -> ValidateAddressResult {
+    match TariAddress::from_str(&params.address) {
+        Ok(address) => {
+            let features = address.features();
+            ValidateAddressResult {
+                valid: true,
+                nettype: Some(address.network().to_string()),
+                interactive: features.contains(TariAddressFeatures::INTERACTIVE),
+                one_sided: features.contains(TariAddressFeatures::ONE_SIDED),
+            }
+        },
+        Err(_) => ValidateAddressResult::default(),
+    }
+}
+
+fn completed_entry(tx: CompletedTransaction, transfer_type: &'static str) -> TransferEntry {
+    let address = if tx.direction == TransactionDirection::Inbound {
+        &tx.source_address
+    } else {
+        &tx.destination_address
+    };
+    TransferEntry {
+        txid: tx.tx_id.as_u64(),
+        address: address.to_base58(),
+        amount: tx.amount.as_u64(),
+        fee: tx.fee.as_u64(),
+        height: tx.mined_height,
+        confirmations: tx.confirmations,
+        timestamp: tx.timestamp.timestamp(),
+        payment_id: tx.payment_id.map(|id| id.to_string()),
+        note: tx.message,
+        transfer_type,
+    }
+}
+
+fn inbound_entry(tx: InboundTransaction) -> TransferEntry {
+    TransferEntry {
+        txid: tx.tx_id.as_u64(),
+        address: tx.source_address.to_base58(),
+        amount: tx.amount.as_u64(),
+        fee: 0,
+        height: None,
+        confirmations: None,
+        timestamp: tx.timestamp.timestamp(),
+        payment_id: None,
+        note: tx.message,
+        transfer_type: "pool",
+    }
+}
+
+fn outbound_entry(tx: OutboundTransaction) -> TransferEntry {
+    TransferEntry {
+        txid: tx.tx_id.as_u64(),
+        address: tx.destination_address.to_base58(),
+        amount: tx.amount.as_u64(),
+        fee: tx.fee.as_u64(),
+        height: None,
+        confirmations: None,
+        timestamp: tx.timestamp.timestamp(),
+        payment_id: None,
+        note: tx.message,
+        transfer_type: "pending",
+    }
+}
+
+/// Parses method params, treating absent params as an empty object so that methods with all-optional params can be
+/// called without any
+fn parse_params<T: DeserializeOwned>(params: Value) -> Result<T, JsonRpcError> {
+    let params = if params.is_null() {
+        Value::Object(Default::default())
+    } else {
+        params
+    };
+    serde_json::from_value(params).map_err(|e| JsonRpcError::InvalidParams(e.to_string()))
+}
+
+fn to_json<T: Serialize>(result: T) -> Result<Value, JsonRpcError> {
+    serde_json::to_value(result).map_err(|e| JsonRpcError::WalletError(e.to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn it_parses_absent_params_as_defaults() {
+        let params: GetTransfersParams = parse_params(Value::Null).unwrap();
+        assert!(!params.incoming && !params.out && !params.pending && !params.failed && !params.pool);
+
+        let params: GetTransfersParams = parse_params(json!({"in": true, "pool": true})).unwrap();
+        assert!(params.incoming && params.pool);
+        assert!(!params.out);
+    }
+
+    #[test]
+    fn it_rejects_invalid_params() {
+        let err = parse_params::<TransferParams>(json!({"destinations": "nope"})).unwrap_err();
+        assert_eq!(err.code(), -32602);
+        let err = parse_params::<ValidateAddressParams>(Value::Null).unwrap_err();
+        assert_eq!(err.code(), -32602);
+    }
+
+    #[test]
+    fn it_validates_addresses() {
+        let result = validate_address(ValidateAddressParams {
+            address: "not an address".to_string(),
+        });
+        assert!(!result.valid);
+        assert!(result.nettype.is_none());
+    }
+}
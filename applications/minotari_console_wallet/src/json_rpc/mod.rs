@@ -0,0 +1,17 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! A JSON-RPC 2.0 compatibility server for the console wallet.
+//!
+//! Many exchange and merchant backends are written against the Monero `wallet-rpc` API and cannot easily adopt gRPC.
+//! This server exposes a subset of that method set (`get_balance`, `transfer`, `get_transfers` and
+//! `validate_address`) over HTTP so that those integrations can be pointed at a Minotari wallet with minimal changes.
+//! Amounts are always in micro Minotari.
+
+mod error;
+mod methods;
+mod server;
+
+pub use error::JsonRpcError;
+pub use methods::JsonRpcMethods;
+pub use server::run_json_rpc_server;
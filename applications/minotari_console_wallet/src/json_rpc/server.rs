@@ -0,0 +1,221 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::{convert::Infallible, future::Future, sync::Arc};
+
+use hyper::{
+    header,
+    service::{make_service_fn, service_fn},
+    Body,
+    Method,
+    Request,
+    Response,
+    Server,
+    StatusCode,
+};
+use log::*;
+use minotari_app_grpc::authentication::{salted_password::create_salted_hashed_password, BasicAuthCredentials};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tari_common_types::grpc_authentication::GrpcAuthentication;
+use tari_comms::{multiaddr::Multiaddr, utils::multiaddr::multiaddr_to_socketaddr};
+
+use crate::json_rpc::{JsonRpcError, JsonRpcMethods};
+
+const LOG_TARGET: &str = "wallet::json_rpc::server";
+
+/// Requests must declare their body size, and larger bodies are rejected before being read
+const MAX_REQUEST_BODY_SIZE: u64 = 1024 * 1024;
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcRequest {
+    jsonrpc: String,
+    #[serde(default)]
+    id: Value,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+struct JsonRpcHandler {
+    methods: JsonRpcMethods,
+    auth: GrpcAuthentication, // this contains a hashed PHC password in the case of basic authentication
+}
+
+/// Runs the JSON-RPC server on the given address until `shutdown` resolves
+pub async fn run_json_rpc_server<F>(
+    methods: JsonRpcMethods,
+    listener_addr: Multiaddr,
+    auth: GrpcAuthentication,
+    shutdown: F,
+) -> Result<(), String>
+where
+    F: Future<Output = ()>,
+{
+    info!(target: LOG_TARGET, "Starting JSON-RPC on {}", listener_addr);
+    let address = multiaddr_to_socketaddr(&listener_addr).map_err(|e| e.to_string())?;
+    // The server password needs to be hashed for later client verification
+    let auth = match auth {
+        GrpcAuthentication::None => auth,
+        GrpcAuthentication::Basic { username, password } => GrpcAuthentication::Basic {
+            username,
+            password: create_salted_hashed_password(password.reveal())
+                .map_err(|e| format!("Unable to prepare JSON-RPC authentication: {}", e))?
+                .as_str()
+                .into(),
+        },
+    };
+    let handler = Arc::new(JsonRpcHandler { methods, auth });
+
+    let service = make_service_fn(move |_conn| {
+        let handler = handler.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |req| {
+                let handler = handler.clone();
+                async move { Ok::<_, Infallible>(handler.handle(req).await) }
+            }))
+        }
+    });
+
+    Server::try_bind(&address)
+        .map_err(|e| format!("Unable to bind JSON-RPC server to {}: {}", address, e))?
+        .serve(service)
+        .with_graceful_shutdown(shutdown)
+        .await
+        .map_err(|e| format!("JSON-RPC server returned error: {}", e))?;
+
+    info!(target: LOG_TARGET, "Stopping JSON-RPC");
+    Ok(())
+}
+
+impl JsonRpcHandler {
+    async fn handle(&self, req: Request<Body>) -> Response<Body> {
+        if req.method() != Method::POST {
+            return plain_response(StatusCode::METHOD_NOT_ALLOWED);
+        }
+        if let Err(err) = self.authenticate(&req) {
+            warn!(target: LOG_TARGET, "JSON-RPC authentication failed: {}", err);
+            return Response::builder()
+                .status(StatusCode::UNAUTHORIZED)
+                .header(header::WWW_AUTHENTICATE, "Basic realm=\"minotari_console_wallet\"")
+                .body(Body::empty())
+                .expect("static response is valid");
+        }
+        let content_length = req
+            .headers()
+            .get(header::CONTENT_LENGTH)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<u64>().ok());
+        match content_length {
+            None => return plain_response(StatusCode::LENGTH_REQUIRED),
+            Some(len) if len > MAX_REQUEST_BODY_SIZE => return plain_response(StatusCode::PAYLOAD_TOO_LARGE),
+            Some(_) => {},
+        }
+
+        let body = match hyper::body::to_bytes(req.into_body()).await {
+            Ok(body) => body,
+            Err(err) => {
+                debug!(target: LOG_TARGET, "Failed to read JSON-RPC request body: {}", err);
+                return plain_response(StatusCode::BAD_REQUEST);
+            },
+        };
+        let response = match serde_json::from_slice::<Value>(&body) {
+            Ok(Value::Array(batch)) if !batch.is_empty() => {
+                let mut responses = Vec::with_capacity(batch.len());
+                for request in batch {
+                    responses.push(self.handle_call(request).await);
+                }
+                Value::Array(responses)
+            },
+            Ok(request) => self.handle_call(request).await,
+            Err(err) => error_response(Value::Null, &JsonRpcError::ParseError(err.to_string())),
+        };
+
+        Response::builder()
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(response.to_string()))
+            .expect("static response is valid")
+    }
+
+    async fn handle_call(&self, request: Value) -> Value {
+        let request = match serde_json::from_value::<JsonRpcRequest>(request) {
+            Ok(request) if request.jsonrpc == "2.0" => request,
+            Ok(request) => {
+                return error_response(
+                    request.id,
+                    &JsonRpcError::InvalidRequest("`jsonrpc` must be \"2.0\"".to_string()),
+                )
+            },
+            Err(err) => return error_response(Value::Null, &JsonRpcError::InvalidRequest(err.to_string())),
+        };
+        match self.methods.call(&request.method, request.params).await {
+            Ok(result) => json!({
+                "jsonrpc": "2.0",
+                "id": request.id,
+                "result": result,
+            }),
+            Err(err) => {
+                debug!(target: LOG_TARGET, "JSON-RPC `{}` failed: {}", request.method, err);
+                error_response(request.id, &err)
+            },
+        }
+    }
+
+    fn authenticate(&self, req: &Request<Body>) -> Result<(), String> {
+        let (username, phc_password) = match self.auth.username_password() {
+            Some(credentials) => credentials,
+            None => return Ok(()),
+        };
+        let header = req
+            .headers()
+            .get(header::AUTHORIZATION)
+            .ok_or("Missing authorization header")?
+            .to_str()
+            .map_err(|e| e.to_string())?;
+        let (header_username, header_password) =
+            BasicAuthCredentials::parse_header(header).map_err(|e| e.to_string())?;
+        BasicAuthCredentials::new(username.to_owned(), phc_password.clone())
+            .map_err(|e| e.to_string())?
+            .constant_time_validate(&header_username, &header_password)
+            .map_err(|e| e.to_string())
+    }
+}
+
+fn error_response(id: Value, err: &JsonRpcError) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": err.to_json(),
+    })
+}
+
+fn plain_response(status: StatusCode) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .body(Body::empty())
+        .expect("static response is valid")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_builds_error_responses() {
+        let response = error_response(json!(7), &JsonRpcError::MethodNotFound("get_height".to_string()));
+        assert_eq!(response["jsonrpc"], "2.0");
+        assert_eq!(response["id"], 7);
+        assert_eq!(response["error"]["code"], -32601);
+        assert_eq!(response["error"]["message"], "Method not found: get_height");
+        assert!(response.get("result").is_none());
+    }
+
+    #[test]
+    fn it_deserializes_requests_without_params() {
+        let request: JsonRpcRequest =
+            serde_json::from_value(json!({"jsonrpc": "2.0", "id": "a", "method": "get_balance"})).unwrap();
+        assert_eq!(request.method, "get_balance");
+        assert_eq!(request.id, "a");
+        assert!(request.params.is_null());
+    }
+}
@@ -24,16 +24,24 @@
 minotari_app_utilities::deny_non_64_bit_archs!();
 
 mod automation;
+mod backup;
+mod base_node_grpc;
 mod cli;
 mod config;
+mod daemon;
 mod grpc;
 mod init;
+mod json_rpc;
+mod metadata_sync;
 mod notifier;
 mod recovery;
+mod remote_signer;
 mod ui;
 mod utils;
 mod wallet_modes;
 
+use std::sync::Arc;
+
 pub use cli::{
     BurnMinotariArgs,
     Cli,
@@ -52,27 +60,38 @@ use init::{
     set_peer_and_get_base_node_peer_config,
     start_wallet,
     tari_splash_screen,
+    verify_wallet_db,
     WalletBoot,
 };
 use log::*;
-use minotari_app_utilities::{common_cli_args::CommonCliArgs, consts};
+use minotari_app_utilities::{common_cli_args::CommonCliArgs, consts, diagnostics::set_diagnostic_field};
 use minotari_wallet::transaction_service::config::TransactionRoutingMechanism;
 use recovery::{get_seed_from_seed_words, prompt_private_key_from_seed_words};
+use remote_signer::GrpcRemoteSigner;
 use tari_common::{
     configuration::bootstrap::ApplicationType,
     exit_codes::{ExitCode, ExitError},
+    set_log_field,
 };
 use tari_common_types::wallet_types::WalletType;
+use tari_core::transactions::key_manager::RemoteSigner;
 use tari_key_manager::cipher_seed::CipherSeed;
 #[cfg(all(unix, feature = "libtor"))]
 use tari_libtor::tor::Tor;
 use tari_shutdown::Shutdown;
 use tari_utilities::SafePassword;
 use tokio::runtime::Runtime;
-use wallet_modes::{command_mode, grpc_mode, recovery_mode, script_mode, tui_mode, WalletMode};
+use wallet_modes::{command_mode, daemon_mode, grpc_mode, recovery_mode, script_mode, tui_mode, WalletMode};
 
 pub use crate::config::ApplicationConfig;
-use crate::init::{boot_with_password, confirm_direct_only_send, confirm_seed_words, prompt_wallet_type, wallet_mode};
+use crate::init::{
+    boot_with_password,
+    confirm_direct_only_send,
+    confirm_seed_words,
+    prompt_wallet_type,
+    remote_signer_view_key,
+    wallet_mode,
+};
 
 pub const LOG_TARGET: &str = "wallet::console_wallet::main";
 
@@ -89,7 +108,9 @@ pub fn run_wallet(shutdown: &mut Shutdown, runtime: Runtime, config: &mut Applic
             config: config_path.into_os_string().into_string().unwrap(),
             log_config: None,
             network: None,
+            profile: None,
             log_path: None,
+            log_format: None,
             config_property_overrides: vec![],
         },
         password: None,
@@ -104,6 +125,9 @@ pub fn run_wallet(shutdown: &mut Shutdown, runtime: Runtime, config: &mut Applic
         command_mode_auto_exit: false,
         grpc_enabled: true,
         grpc_address: None,
+        daemon: false,
+        pid_file: None,
+        privacy: false,
         command2: None,
         profile_with_tokio_console: false,
         view_private_key: None,
@@ -136,13 +160,33 @@ pub fn run_wallet_with_cli(
     // check for recovery based on existence of wallet file
     let (mut boot_mode, password) = boot_with_password(&cli, &config.wallet)?;
 
-    let wallet_type = prompt_wallet_type(
-        boot_mode,
-        &config.wallet,
-        cli.non_interactive_mode,
-        cli.view_private_key.clone(),
-        cli.spend_key.clone(),
-    );
+    let remote_signer = runtime
+        .block_on(GrpcRemoteSigner::connect(&config.wallet))
+        .map_err(|e| {
+            ExitError::new(
+                ExitCode::WalletError,
+                format!("Could not connect to the remote signer: {}", e),
+            )
+        })?;
+
+    // A new or recovered wallet takes its keys from the remote signer, if one is configured
+    let wallet_type = match (&remote_signer, boot_mode) {
+        (Some(signer), WalletBoot::New | WalletBoot::Recovery) => {
+            let view_key = remote_signer_view_key(cli.non_interactive_mode, cli.view_private_key.clone())?;
+            Some(
+                runtime
+                    .block_on(signer.get_wallet_type(config.wallet.network, view_key))
+                    .map_err(|e| ExitError::new(ExitCode::WalletError, e))?,
+            )
+        },
+        _ => prompt_wallet_type(
+            boot_mode,
+            &config.wallet,
+            cli.non_interactive_mode,
+            cli.view_private_key.clone(),
+            cli.spend_key.clone(),
+        ),
+    };
 
     let recovery_seed = get_recovery_seed(boot_mode, &cli, &wallet_type)?;
 
@@ -161,11 +205,18 @@ pub fn run_wallet_with_cli(
         ));
     }
 
+    // The database is verified before the wallet starts, so that nothing changes it in the meantime
+    if let Some(CliCommands::VerifyWalletDb(args)) = &cli.command2 {
+        info!(target: LOG_TARGET, "Wallet database verification requested.");
+        return runtime.block_on(verify_wallet_db(config, password, args.quarantine));
+    }
+
     // Run our own Tor instance, if configured
     // This is currently only possible on linux/macos
     #[cfg(all(unix, feature = "libtor"))]
     if config.wallet.use_libtor && config.wallet.p2p.transport.is_tor() {
-        let tor = Tor::initialize()?;
+        let mut tor = Tor::initialize()?;
+        tor.set_bridges(&config.wallet.p2p.transport.tor)?;
         tor.update_comms_transport(&mut config.wallet.p2p.transport)?;
         tor.run_background();
         debug!(
@@ -177,6 +228,7 @@ pub fn run_wallet_with_cli(
     let on_init = matches!(boot_mode, WalletBoot::New);
     let not_recovery = recovery_seed.is_none();
     let hardware_wallet = matches!(wallet_type, Some(WalletType::Ledger(_)));
+    let remote_signer_wallet = matches!(wallet_type, Some(WalletType::RemoteSigner(_)));
 
     // initialize wallet
     let mut wallet = runtime.block_on(init_wallet(
@@ -189,7 +241,17 @@ pub fn run_wallet_with_cli(
         shutdown_signal,
         cli.non_interactive_mode,
         wallet_type,
+        remote_signer.map(|signer| Arc::new(signer) as Arc<dyn RemoteSigner>),
     ))?;
+    set_log_field("node_id", wallet.comms.node_identity().node_id());
+    set_diagnostic_field("node_id", wallet.comms.node_identity().node_id());
+    set_diagnostic_field("hardware_wallet", hardware_wallet);
+    if let Ok(birthday) = wallet.db.get_wallet_birthday() {
+        set_diagnostic_field("wallet_birthday", birthday);
+    }
+    if let Ok(Some(metadata)) = wallet.db.get_chain_metadata() {
+        set_diagnostic_field("last_known_tip_height", metadata.best_block_height());
+    }
 
     if !cli.non_interactive_mode &&
         config.wallet.transaction_service_config.transaction_routing_mechanism ==
@@ -206,7 +268,7 @@ pub fn run_wallet_with_cli(
     }
 
     // if wallet is being set for the first time, wallet seed words are prompted on the screen
-    if !cli.non_interactive_mode && not_recovery && on_init && !hardware_wallet {
+    if !cli.non_interactive_mode && not_recovery && on_init && !hardware_wallet && !remote_signer_wallet {
         match confirm_seed_words(&mut wallet) {
             Ok(()) => {
                 print!("\x1Bc"); // Clear the screen
@@ -229,10 +291,21 @@ pub fn run_wallet_with_cli(
         &mut wallet,
         cli.non_interactive_mode,
     ))?;
-    let base_nodes_peers = base_node_config.get_base_node_peers()?;
+    let base_nodes_peers = base_node_config.get_base_node_peers(config.wallet.custom_base_node_failover)?;
 
     let wallet_mode = wallet_mode(&cli, boot_mode);
 
+    let _telemetry_guard = {
+        let _runtime = runtime.enter();
+        tari_telemetry::install(
+            ApplicationType::ConsoleWallet.as_config_str(),
+            &wallet.comms.node_identity().node_id().to_string(),
+            &config.telemetry,
+            cli.profile_with_tokio_console,
+        )
+        .map_err(|e| ExitError::new(ExitCode::ConfigError, e))?
+    };
+
     // start wallet
     runtime.block_on(start_wallet(&mut wallet, &base_nodes_peers, &wallet_mode))?;
 
@@ -243,6 +316,7 @@ pub fn run_wallet_with_cli(
     let result = match wallet_mode {
         WalletMode::Tui => tui_mode(handle, &config.wallet, &base_node_config, wallet.clone()),
         WalletMode::Grpc => grpc_mode(handle, &config.wallet, wallet.clone()),
+        WalletMode::Daemon => daemon_mode(handle, &config.wallet, wallet.clone(), cli.pid_file.as_deref()),
         WalletMode::Script(path) => script_mode(handle, &cli, &config.wallet, &base_node_config, wallet.clone(), path),
         WalletMode::Command(command) => command_mode(
             handle,
@@ -282,7 +356,9 @@ fn get_recovery_seed(
     cli: &Cli,
     wallet_type: &Option<WalletType>,
 ) -> Result<Option<CipherSeed>, ExitError> {
-    if matches!(boot_mode, WalletBoot::Recovery) && !matches!(wallet_type, Some(WalletType::Ledger(_))) {
+    if matches!(boot_mode, WalletBoot::Recovery) &&
+        !matches!(wallet_type, Some(WalletType::Ledger(_) | WalletType::RemoteSigner(_)))
+    {
         let seed = if let Some(ref seed_words) = cli.seed_words {
             get_seed_from_seed_words(seed_words, None)?
         } else {
@@ -24,11 +24,14 @@ use std::process;
 
 use clap::Parser;
 use log::*;
-use minotari_app_utilities::consts;
+use minotari_app_utilities::{
+    consts,
+    diagnostics::{install_panic_handler, set_diagnostic_field, DiagnosticsConfig},
+};
 use minotari_console_wallet::{run_wallet_with_cli, ApplicationConfig, Cli};
 use tari_common::{
     configuration::bootstrap::{grpc_default_port, ApplicationType},
-    exit_codes::ExitError,
+    exit_codes::{ExitCode, ExitError},
     initialize_logging,
     load_configuration,
 };
@@ -69,18 +72,29 @@ fn main() {
 }
 
 fn main_inner() -> Result<(), ExitError> {
-    let cli = Cli::parse();
+    let mut cli = Cli::parse();
+    if cli.daemon {
+        cli.non_interactive_mode = true;
+    }
+    let profile = cli.common.resolve_network_profile()?;
     let base_path = cli.common.get_base_path();
     initialize_logging(
         &cli.common.log_config_path("wallet"),
         cli.common.log_path.as_ref().unwrap_or(&base_path),
         include_str!("../log4rs_sample.yml"),
+        cli.common.log_format.unwrap_or_default(),
     )?;
+    install_panic_handler(DiagnosticsConfig::from_cli("wallet", &cli.common));
+    set_diagnostic_field("network", profile.network);
     info!(
         target: LOG_TARGET,
         "Starting Minotari Console Wallet version: {}",
         consts::APP_VERSION
     );
+    info!(
+        target: LOG_TARGET,
+        "Using network profile '{}' ({})", profile.name, profile.network
+    );
 
     let cfg = load_configuration(
         cli.common.config_path(),
@@ -90,14 +104,16 @@ fn main_inner() -> Result<(), ExitError> {
         cli.common.network,
     )?;
 
-    if cli.profile_with_tokio_console {
-        // Uncomment to enable tokio tracing via tokio-console
-        console_subscriber::init();
-    }
-
     let mut config = ApplicationConfig::load_from(&cfg)?;
+    profile.check_network(config.wallet.network)?;
 
     setup_grpc_config(&mut config);
+    if cli.privacy {
+        config
+            .apply_privacy_profile()
+            .map_err(|e| ExitError::new(ExitCode::ConfigError, e))?;
+        info!(target: LOG_TARGET, "Using the hidden-service-only privacy profile");
+    }
 
     let runtime = tokio::runtime::Builder::new_multi_thread()
         .enable_all()
@@ -119,4 +135,7 @@ fn setup_grpc_config(config: &mut ApplicationConfig) {
             .unwrap(),
         );
     }
+    if config.wallet.json_rpc_address.is_none() {
+        config.wallet.json_rpc_address = Some("/ip4/127.0.0.1/tcp/18145".parse().unwrap());
+    }
 }
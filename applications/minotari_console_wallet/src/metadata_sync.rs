@@ -0,0 +1,98 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Syncing of contacts, drafts and application values with the wallet's other devices through a remote store
+
+use std::{future::Future, time::Duration};
+
+use log::*;
+use minotari_app_utilities::backup::{BackupError, BackupStore};
+use minotari_wallet::{
+    metadata_sync::MetadataSync,
+    storage::sqlite_db::wallet::WalletSqliteDatabase,
+    WalletConfig,
+    WalletSqlite,
+};
+use tari_common::exit_codes::{ExitCode, ExitError};
+use tokio::{
+    runtime::Handle,
+    time::{self, MissedTickBehavior},
+};
+
+const LOG_TARGET: &str = "wallet::console_wallet::metadata_sync";
+
+/// Starts syncing the wallet metadata if it is enabled. It stops when the wallet shuts down.
+pub fn spawn_metadata_sync(handle: &Handle, config: &WalletConfig, wallet: &WalletSqlite) -> Result<(), ExitError> {
+    let config = &config.metadata_sync;
+    if !config.enabled {
+        return Ok(());
+    }
+    let store = BackupStore::remote(config.s3.as_ref(), config.webdav.as_ref())
+        .map_err(|e| {
+            ExitError::new(
+                ExitCode::ConfigError,
+                format!("Invalid metadata sync configuration: {}", e),
+            )
+        })?
+        .ok_or_else(|| {
+            ExitError::new(
+                ExitCode::ConfigError,
+                "Metadata sync is enabled, but no S3 or WebDAV store is configured",
+            )
+        })?;
+    let sync = match MetadataSync::new(wallet.db.clone(), wallet.contacts_service.clone()) {
+        Ok(sync) => sync,
+        Err(e) => {
+            warn!(target: LOG_TARGET, "Metadata sync is not available for this wallet: {}", e);
+            return Ok(());
+        },
+    };
+    handle.spawn(run_metadata_sync(
+        sync,
+        store,
+        config.interval,
+        wallet.clone().wait_until_shutdown(),
+    ));
+    Ok(())
+}
+
+/// Syncs every `interval`, starting immediately so that a new device gets the metadata straight away, until
+/// `shutdown` resolves. Failed syncs are logged and retried at the next interval.
+async fn run_metadata_sync<S: Future<Output = ()>>(
+    mut sync: MetadataSync<WalletSqliteDatabase>,
+    store: BackupStore,
+    interval: Duration,
+    shutdown: S,
+) {
+    info!(target: LOG_TARGET, "Metadata sync started, syncing every {:.0?}", interval);
+    let mut interval = time::interval(interval);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    tokio::pin!(shutdown);
+    loop {
+        tokio::select! {
+            _ = interval.tick() => match sync_once(&mut sync, &store).await {
+                Ok(0) => debug!(target: LOG_TARGET, "Wallet metadata is in sync"),
+                Ok(num_applied) => info!(target: LOG_TARGET, "Applied {} synced metadata change(s)", num_applied),
+                Err(e) => warn!(target: LOG_TARGET, "Metadata sync failed: {}", e),
+            },
+            _ = &mut shutdown => break,
+        }
+    }
+    info!(target: LOG_TARGET, "Metadata sync stopped");
+}
+
+async fn sync_once(sync: &mut MetadataSync<WalletSqliteDatabase>, store: &BackupStore) -> Result<usize, String> {
+    let name = sync.blob_name().to_string();
+    let stored = match store.get(&name).await {
+        Ok(data) => Some(data),
+        Err(BackupError::NotFound(_)) => None,
+        Err(e) => return Err(e.to_string()),
+    };
+    let outcome = sync.merge_stored(stored.as_deref()).await.map_err(|e| e.to_string())?;
+    if let Some(data) = &outcome.upload {
+        store.put(&name, data.clone()).await.map_err(|e| e.to_string())?;
+    }
+    let num_applied = outcome.num_applied;
+    sync.complete(outcome).map_err(|e| e.to_string())?;
+    Ok(num_applied)
+}
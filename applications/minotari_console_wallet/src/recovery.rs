@@ -195,6 +195,7 @@ pub async fn wallet_recovery(
                 num_recovered,
                 value_recovered,
                 time_taken,
+                recovered_by_branch,
             }) => {
                 let rate = (final_height as f32) * 1000f32 / (time_taken.as_millis() as f32);
                 let stats = format!(
@@ -203,6 +204,14 @@ pub async fn wallet_recovery(
                 );
                 info!(target: LOG_TARGET, "{}", stats);
                 println!("{}", stats);
+                for (branch, branch_stats) in recovered_by_branch {
+                    let s = format!(
+                        "  {}: {} outputs worth {}",
+                        branch, branch_stats.num_recovered, branch_stats.value_recovered
+                    );
+                    info!(target: LOG_TARGET, "{}", s);
+                    println!("{}", s);
+                }
             },
             Err(e @ broadcast::error::RecvError::Lagged(_)) => {
                 debug!(target: LOG_TARGET, "Error receiving Wallet recovery events: {}", e);
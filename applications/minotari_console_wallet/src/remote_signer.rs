@@ -0,0 +1,242 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! The client of a `minotari_remote_signer`, which holds the spend key of a `WalletType::RemoteSigner` wallet
+
+use std::{convert::TryFrom, str::FromStr};
+
+use log::*;
+use minotari_app_grpc::tari_rpc::{
+    self as grpc,
+    remote_signer_client::RemoteSignerClient,
+    sign_transaction_input::ScriptKey,
+    Empty,
+    GetDhSharedSecretRequest,
+    GetOneSidedMetadataSignatureRequest,
+    RemoteKey,
+    SignTransactionInput,
+    SignTransactionOutput,
+    SignTransactionRequest,
+};
+use minotari_wallet::WalletConfig;
+use tari_common::configuration::Network;
+use tari_common_types::{
+    key_branches::TransactionKeyManagerBranch,
+    tari_address::TariAddress,
+    types::{ComAndPubSignature, PrivateKey, PublicKey},
+    wallet_types::{RemoteSignerWallet, WalletType},
+};
+use tari_comms::{types::CommsDHKE, utils::multiaddr::multiaddr_to_socketaddr};
+use tari_core::transactions::{
+    key_manager::{RemoteScriptKey, RemoteSigner, RemoteSignerChangeOutput, RemoteSignerInput},
+    tari_amount::MicroMinotari,
+    transaction_components::{RangeProofType, Transaction},
+};
+use tari_crypto::keys::PublicKey as PublicKeyTrait;
+use tari_key_manager::key_manager_service::KeyManagerServiceError;
+use tari_utilities::ByteArray;
+use tonic::{
+    transport::{Certificate, Channel, ClientTlsConfig, Endpoint, Identity},
+    Status,
+};
+
+const LOG_TARGET: &str = "wallet::console_wallet::remote_signer";
+
+#[derive(Clone)]
+pub struct GrpcRemoteSigner {
+    client: RemoteSignerClient<Channel>,
+}
+
+impl GrpcRemoteSigner {
+    /// Connects to the remote signer configured in the wallet config, authenticating both ends with TLS
+    pub async fn connect(config: &WalletConfig) -> Result<Option<Self>, KeyManagerServiceError> {
+        let address = match &config.remote_signer_address {
+            Some(address) => address,
+            None => return Ok(None),
+        };
+        let socket_address = multiaddr_to_socketaddr(address)
+            .map_err(|e| KeyManagerServiceError::RemoteSignerError(format!("Invalid address: {}", e)))?;
+        debug!(target: LOG_TARGET, "Connecting to the remote signer at {}", socket_address);
+
+        let read = |file_name: &str| {
+            let path = config.config_dir.join(file_name);
+            std::fs::read(&path).map_err(|e| {
+                KeyManagerServiceError::RemoteSignerError(format!("Could not load the file `{:?}`: {}", path, e))
+            })
+        };
+        let tls = ClientTlsConfig::new()
+            .ca_certificate(Certificate::from_pem(read(&config.remote_signer_ca_cert_filename)?))
+            .identity(Identity::from_pem(
+                read(&config.remote_signer_client_cert_filename)?,
+                read(&config.remote_signer_client_key_filename)?,
+            ))
+            .domain_name(config.remote_signer_tls_domain_name.clone());
+        let channel = Endpoint::from_str(&format!("https://{}", socket_address))
+            .map_err(remote_signer_error)?
+            .tls_config(tls)
+            .map_err(remote_signer_error)?
+            .connect()
+            .await
+            .map_err(remote_signer_error)?;
+        Ok(Some(Self {
+            client: RemoteSignerClient::new(channel),
+        }))
+    }
+
+    /// The wallet type of a new or recovered wallet whose spend key is held by this signer. The signer does not give
+    /// out the private view key, so it is provided by the user and checked against the signer's public view key.
+    pub async fn get_wallet_type(
+        &self,
+        network: Network,
+        view_key: PrivateKey,
+    ) -> Result<WalletType, KeyManagerServiceError> {
+        let response = self
+            .client
+            .clone()
+            .get_wallet_keys(Empty {})
+            .await
+            .map_err(status_error)?
+            .into_inner();
+        let signer_network = Network::from_str(&response.network).map_err(remote_signer_error)?;
+        if signer_network != network {
+            return Err(KeyManagerServiceError::RemoteSignerError(format!(
+                "The signer is on network '{}', the wallet is on '{}'",
+                signer_network, network
+            )));
+        }
+        let public_view_key =
+            PublicKey::from_canonical_bytes(&response.public_view_key).map_err(remote_signer_error)?;
+        if PublicKey::from_secret_key(&view_key) != public_view_key {
+            return Err(KeyManagerServiceError::RemoteSignerError(
+                "The view key is not the view key of the signer's wallet".to_string(),
+            ));
+        }
+        Ok(WalletType::RemoteSigner(RemoteSignerWallet {
+            public_alpha: PublicKey::from_canonical_bytes(&response.public_spend_key).map_err(remote_signer_error)?,
+            view_key,
+            network,
+        }))
+    }
+}
+
+#[async_trait::async_trait]
+impl RemoteSigner for GrpcRemoteSigner {
+    async fn get_public_key(
+        &self,
+        branch: TransactionKeyManagerBranch,
+        index: u64,
+    ) -> Result<PublicKey, KeyManagerServiceError> {
+        let response = self
+            .client
+            .clone()
+            .get_public_key(remote_key(branch, index))
+            .await
+            .map_err(status_error)?
+            .into_inner();
+        PublicKey::from_canonical_bytes(&response.public_key).map_err(remote_signer_error)
+    }
+
+    async fn get_dh_shared_secret(
+        &self,
+        branch: TransactionKeyManagerBranch,
+        index: u64,
+        public_key: &PublicKey,
+    ) -> Result<CommsDHKE, KeyManagerServiceError> {
+        let response = self
+            .client
+            .clone()
+            .get_dh_shared_secret(GetDhSharedSecretRequest {
+                key: Some(remote_key(branch, index)),
+                public_key: public_key.to_vec(),
+            })
+            .await
+            .map_err(status_error)?
+            .into_inner();
+        CommsDHKE::from_canonical_bytes(&response.shared_secret).map_err(remote_signer_error)
+    }
+
+    async fn get_one_sided_metadata_signature(
+        &self,
+        txo_version: u8,
+        value: MicroMinotari,
+        sender_offset_index: u64,
+        commitment_mask: &PrivateKey,
+        receiver_address: &TariAddress,
+        metadata_signature_message_common: &[u8; 32],
+        range_proof_type: RangeProofType,
+    ) -> Result<ComAndPubSignature, KeyManagerServiceError> {
+        let response = self
+            .client
+            .clone()
+            .get_one_sided_metadata_signature(GetOneSidedMetadataSignatureRequest {
+                txo_version: u32::from(txo_version),
+                value: value.as_u64(),
+                sender_offset_index,
+                commitment_mask: commitment_mask.to_vec(),
+                receiver_address: receiver_address.to_vec(),
+                metadata_signature_message_common: metadata_signature_message_common.to_vec(),
+                range_proof_type: u32::from(range_proof_type.as_byte()),
+            })
+            .await
+            .map_err(status_error)?
+            .into_inner();
+        ComAndPubSignature::try_from(response).map_err(KeyManagerServiceError::RemoteSignerError)
+    }
+
+    async fn sign_transaction(
+        &self,
+        transaction: &Transaction,
+        inputs: &[RemoteSignerInput],
+        change_outputs: &[RemoteSignerChangeOutput],
+        partial_script_offset: &PrivateKey,
+    ) -> Result<Transaction, KeyManagerServiceError> {
+        let inputs = inputs
+            .iter()
+            .map(|input| SignTransactionInput {
+                value: input.value.as_u64(),
+                commitment_mask: input.commitment_mask.to_vec(),
+                script_key: input.script_key.as_ref().map(|script_key| match script_key {
+                    RemoteScriptKey::Managed { branch, index } => ScriptKey::Managed(remote_key(*branch, *index)),
+                    RemoteScriptKey::Derived { commitment_mask } => {
+                        ScriptKey::DerivedCommitmentMask(commitment_mask.to_vec())
+                    },
+                }),
+            })
+            .collect();
+        let change_outputs = change_outputs
+            .iter()
+            .map(|output| SignTransactionOutput {
+                value: output.value.as_u64(),
+                commitment_mask: output.commitment_mask.to_vec(),
+            })
+            .collect();
+        let response = self
+            .client
+            .clone()
+            .sign_transaction(SignTransactionRequest {
+                transaction: Some(grpc::Transaction::try_from(transaction.clone()).map_err(remote_signer_error)?),
+                inputs,
+                change_outputs,
+                partial_script_offset: partial_script_offset.to_vec(),
+            })
+            .await
+            .map_err(status_error)?
+            .into_inner();
+        Transaction::try_from(response).map_err(remote_signer_error)
+    }
+}
+
+fn remote_key(branch: TransactionKeyManagerBranch, index: u64) -> RemoteKey {
+    RemoteKey {
+        branch: u32::from(branch.as_byte()),
+        index,
+    }
+}
+
+fn status_error(status: Status) -> KeyManagerServiceError {
+    KeyManagerServiceError::RemoteSignerError(status.message().to_string())
+}
+
+fn remote_signer_error<E: ToString>(e: E) -> KeyManagerServiceError {
+    KeyManagerServiceError::RemoteSignerError(e.to_string())
+}
@@ -47,6 +47,7 @@ use crate::{
             send_tab::SendTab,
             tabs_container::TabsContainer,
             transactions_tab::TransactionsTab,
+            utxos_tab::UtxosTab,
             Component,
         },
         state::AppState,
@@ -113,6 +114,7 @@ impl<B: Backend> App<B> {
                 )),
             )
             .add("Receive".into(), Box::new(ReceiveTab::new()))
+            .add("UTXOs".into(), Box::new(UtxosTab::new()))
             .add("Burn".into(), Box::new(BurnTab::new(&app_state)))
             .add("Templates".into(), Box::new(RegisterTemplateTab::new(&app_state)))
             .add("Contacts".into(), Box::new(ContactsTab::new()))
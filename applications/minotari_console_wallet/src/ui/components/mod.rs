@@ -32,6 +32,7 @@ pub mod send_tab;
 mod styles;
 pub mod tabs_container;
 pub mod transactions_tab;
+pub mod utxos_tab;
 pub use self::component::*;
 pub mod burn_tab;
 pub mod contacts_tab;
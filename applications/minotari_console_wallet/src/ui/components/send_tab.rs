@@ -584,8 +584,8 @@ impl<B: Backend> Component<B> for SendTab {
             'm' => self.send_input_mode = SendInputMode::Message,
             'p' => self.send_input_mode = SendInputMode::PaymentId,
             's' | 'o' => {
-                if let WalletType::Ledger(_) = self.wallet_type {
-                    // If we're a ledger wallet, then ignore interactive send requests
+                if let WalletType::Ledger(_) | WalletType::RemoteSigner(_) = self.wallet_type {
+                    // If we're a ledger or remote signer wallet, then ignore interactive send requests
                     if c == 's' {
                         return;
                     }
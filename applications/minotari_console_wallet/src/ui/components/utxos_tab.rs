@@ -0,0 +1,536 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::collections::HashSet;
+
+use minotari_wallet::output_manager_service::UtxoSelectionCriteria;
+use tari_common_types::types::Commitment;
+use tari_core::transactions::tari_amount::MicroMinotari;
+use tari_utilities::hex::Hex;
+use tokio::{runtime::Handle, sync::watch};
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, Borders, Clear, ListItem, Paragraph, Wrap},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    ui::{
+        components::{Component, KeyHandled},
+        state::{AppState, UiTransactionSendStatus},
+        ui_utxo::UiUtxo,
+        widgets::{centered_rect_absolute, draw_dialog, MultiColumnList, WindowedListState},
+        MAX_WIDTH,
+    },
+    utils::formatting::display_compressed_string,
+};
+
+/// Lists the unspent outputs of the wallet, and joins, splits or spends a selection of them
+pub struct UtxosTab {
+    input_mode: UtxoInputMode,
+    split_count_field: String,
+    address_field: String,
+    amount_field: String,
+    /// The hex encoded commitments of the selected outputs, so that the selection survives a refresh of the list
+    selected: HashSet<String>,
+    utxos_list_state: WindowedListState,
+    confirmation_dialog: Option<ConfirmationDialogType>,
+    send_result_watch: Option<watch::Receiver<UiTransactionSendStatus>>,
+    error_message: Option<String>,
+    success_message: Option<String>,
+}
+
+impl UtxosTab {
+    pub fn new() -> Self {
+        Self {
+            input_mode: UtxoInputMode::None,
+            split_count_field: String::new(),
+            address_field: String::new(),
+            amount_field: String::new(),
+            selected: HashSet::new(),
+            utxos_list_state: WindowedListState::new(),
+            confirmation_dialog: None,
+            send_result_watch: None,
+            error_message: None,
+            success_message: None,
+        }
+    }
+
+    fn draw_utxos<B>(&mut self, f: &mut Frame<B>, area: Rect, app_state: &AppState)
+    where B: Backend {
+        let (count, total) = self.selection_summary(app_state);
+        let block = Block::default().borders(Borders::ALL).title(Span::styled(
+            format!("Unspent Outputs ({} selected, total {})", count, total),
+            Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+        ));
+        f.render_widget(block, area);
+        let list_areas = Layout::default()
+            .constraints([Constraint::Length(2), Constraint::Min(42)].as_ref())
+            .margin(1)
+            .split(area);
+
+        let instructions = Paragraph::new(Spans::from(vec![
+            Span::raw("Use "),
+            Span::styled("Up↑/Down↓ Keys", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" to move, "),
+            Span::styled("Space", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" to select an output, "),
+            Span::styled("A", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" to select (a)ll, "),
+            Span::styled("C", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" to (c)lear the selection, "),
+            Span::styled("J", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" to (j)oin, "),
+            Span::styled("S", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" to (s)plit, "),
+            Span::styled("P", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" to (p)ay from the selection and "),
+            Span::styled("R", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" to (r)efresh."),
+        ]))
+        .wrap(Wrap { trim: true });
+        f.render_widget(instructions, list_areas[0]);
+        self.utxos_list_state.set_num_items(app_state.get_utxos().len());
+        let mut list_state = self
+            .utxos_list_state
+            .update_list_state((list_areas[1].height as usize).saturating_sub(3));
+        let window = self.utxos_list_state.get_start_end();
+        let windowed_view = app_state.get_utxos_slice(window.0, window.1);
+
+        let column_list = self.create_column_view(windowed_view);
+        column_list.render(f, list_areas[1], &mut list_state);
+    }
+
+    fn create_column_view(&self, windowed_view: &[UiUtxo]) -> MultiColumnList<Vec<ListItem>> {
+        let mut column0_items = Vec::new();
+        let mut column1_items = Vec::new();
+        let mut column2_items = Vec::new();
+        let mut column3_items = Vec::new();
+        let mut column4_items = Vec::new();
+        let mut column5_items = Vec::new();
+        let mut column6_items = Vec::new();
+        let mut column7_items = Vec::new();
+        for u in windowed_view {
+            let selected = if self.selected.contains(&u.commitment.to_hex()) {
+                "[x]"
+            } else {
+                "[ ]"
+            };
+            column0_items.push(ListItem::new(Span::raw(selected)));
+            column1_items.push(ListItem::new(Span::raw(display_compressed_string(
+                u.commitment.to_hex(),
+                8,
+                8,
+            ))));
+            column2_items.push(ListItem::new(Span::raw(format!("{}", u.value))));
+            column3_items.push(ListItem::new(Span::raw(u.maturity.to_string())));
+            column4_items.push(ListItem::new(Span::raw(
+                u.mined_height.map(|h| h.to_string()).unwrap_or_default(),
+            )));
+            column5_items.push(ListItem::new(Span::raw(u.status.clone())));
+            column6_items.push(ListItem::new(Span::raw(u.source.clone())));
+            column7_items.push(ListItem::new(Span::raw(u.label.clone())));
+        }
+        MultiColumnList::new()
+            .highlight_style(Style::default().add_modifier(Modifier::BOLD).fg(Color::Magenta))
+            .heading_style(Style::default().fg(Color::Magenta))
+            .max_width(MAX_WIDTH)
+            .add_column(None, Some(3), column0_items)
+            .add_column(None, Some(1), Vec::new())
+            .add_column(Some("Commitment"), Some(19), column1_items)
+            .add_column(None, Some(1), Vec::new())
+            .add_column(Some("Value"), Some(20), column2_items)
+            .add_column(None, Some(1), Vec::new())
+            .add_column(Some("Maturity"), Some(9), column3_items)
+            .add_column(None, Some(1), Vec::new())
+            .add_column(Some("Mined"), Some(9), column4_items)
+            .add_column(None, Some(1), Vec::new())
+            .add_column(Some("Status"), Some(24), column5_items)
+            .add_column(None, Some(1), Vec::new())
+            .add_column(Some("Source"), Some(18), column6_items)
+            .add_column(None, Some(1), Vec::new())
+            .add_column(Some("Label"), None, column7_items)
+    }
+
+    // casting here is okay as we only use it to draw widths
+    #[allow(clippy::cast_possible_truncation)]
+    fn draw_input<B>(&self, f: &mut Frame<B>, area: Rect)
+    where B: Backend {
+        let (title, fields): (&str, Vec<(&str, &String, bool)>) = match self.input_mode {
+            UtxoInputMode::None => return,
+            UtxoInputMode::SplitCount => ("Split Outputs", vec![(
+                "Number of outputs:",
+                &self.split_count_field,
+                true,
+            )]),
+            UtxoInputMode::SpendAddress | UtxoInputMode::SpendAmount => ("Pay From Selection", vec![
+                (
+                    "Tari Address:",
+                    &self.address_field,
+                    self.input_mode == UtxoInputMode::SpendAddress,
+                ),
+                (
+                    "Amount (uT):",
+                    &self.amount_field,
+                    self.input_mode == UtxoInputMode::SpendAmount,
+                ),
+            ]),
+        };
+        let popup_area = centered_rect_absolute(120, 4 + 3 * fields.len() as u16, area);
+        f.render_widget(Clear, popup_area);
+        let block = Block::default().borders(Borders::ALL).title(Span::styled(
+            title,
+            Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+        ));
+        f.render_widget(block, popup_area);
+
+        let mut constraints = vec![Constraint::Length(2)];
+        constraints.extend(fields.iter().map(|_| Constraint::Length(3)));
+        let vert_chunks = Layout::default()
+            .constraints(constraints.as_ref())
+            .margin(1)
+            .split(popup_area);
+        let instructions = Paragraph::new(Spans::from(vec![
+            Span::raw("Press "),
+            Span::styled("Enter", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" to continue and "),
+            Span::styled("Esc", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" to cancel."),
+        ]));
+        f.render_widget(instructions, vert_chunks[0]);
+
+        for (i, (label, value, active)) in fields.into_iter().enumerate() {
+            let chunk = vert_chunks[i + 1];
+            let input = Paragraph::new(value.as_str())
+                .style(if active {
+                    Style::default().fg(Color::Magenta)
+                } else {
+                    Style::default()
+                })
+                .block(Block::default().borders(Borders::ALL).title(label));
+            f.render_widget(input, chunk);
+            if active {
+                f.set_cursor(chunk.x + value.width() as u16 + 1, chunk.y + 1);
+            }
+        }
+    }
+
+    /// The number and total value of the selected outputs that are still in the list
+    fn selection_summary(&self, app_state: &AppState) -> (usize, MicroMinotari) {
+        app_state
+            .get_utxos()
+            .iter()
+            .filter(|u| self.selected.contains(&u.commitment.to_hex()))
+            .fold((0, MicroMinotari::from(0)), |(count, total), u| {
+                (count + 1, total + u.value)
+            })
+    }
+
+    fn selected_commitments(&self, app_state: &AppState) -> Vec<Commitment> {
+        app_state
+            .get_utxos()
+            .iter()
+            .filter(|u| self.selected.contains(&u.commitment.to_hex()))
+            .map(|u| u.commitment.clone())
+            .collect()
+    }
+
+    fn on_key_confirmation_dialog(&mut self, c: char, app_state: &mut AppState) -> KeyHandled {
+        let dialog = match self.confirmation_dialog {
+            Some(dialog) => dialog,
+            None => return KeyHandled::NotHandled,
+        };
+        match c {
+            'n' => self.confirmation_dialog = None,
+            'y' => {
+                self.confirmation_dialog = None;
+                let commitments = self.selected_commitments(app_state);
+                let result = match dialog {
+                    ConfirmationDialogType::Join => Handle::current().block_on(app_state.join_utxos(commitments)),
+                    ConfirmationDialogType::Split(split_count) => {
+                        Handle::current().block_on(app_state.split_utxos(commitments, split_count))
+                    },
+                };
+                match result {
+                    Ok(tx_id) => {
+                        self.selected.clear();
+                        self.success_message =
+                            Some(format!("Transaction {} submitted.\nPress Enter to continue.", tx_id));
+                    },
+                    Err(e) => self.error_message = Some(format!("{}\nPress Enter to continue.", e)),
+                }
+            },
+            _ => (),
+        }
+        KeyHandled::Handled
+    }
+
+    fn on_key_input(&mut self, c: char, app_state: &mut AppState) -> KeyHandled {
+        match self.input_mode {
+            UtxoInputMode::None => return KeyHandled::NotHandled,
+            UtxoInputMode::SplitCount => match c {
+                '\n' => {
+                    self.input_mode = UtxoInputMode::None;
+                    match self.split_count_field.parse::<usize>() {
+                        Ok(split_count) if split_count > 1 => {
+                            self.confirmation_dialog = Some(ConfirmationDialogType::Split(split_count));
+                        },
+                        _ => {
+                            self.error_message = Some(
+                                "Number of outputs should be a number greater than 1\nPress Enter to continue."
+                                    .to_string(),
+                            )
+                        },
+                    }
+                    self.split_count_field.clear();
+                },
+                c if c.is_ascii_digit() => self.split_count_field.push(c),
+                _ => (),
+            },
+            UtxoInputMode::SpendAddress => match c {
+                '\n' => self.input_mode = UtxoInputMode::SpendAmount,
+                c => self.address_field.push(c),
+            },
+            UtxoInputMode::SpendAmount => match c {
+                '\n' => {
+                    self.input_mode = UtxoInputMode::None;
+                    self.spend_from_selection(app_state);
+                },
+                c if c.is_ascii_digit() => self.amount_field.push(c),
+                _ => (),
+            },
+        }
+        KeyHandled::Handled
+    }
+
+    fn spend_from_selection(&mut self, app_state: &mut AppState) {
+        let amount = match self.amount_field.parse::<MicroMinotari>() {
+            Ok(amount) => amount,
+            Err(_) => {
+                self.error_message = Some("Amount should be an integer\nPress Enter to continue.".to_string());
+                return;
+            },
+        };
+        let (tx, rx) = watch::channel(UiTransactionSendStatus::Initiated);
+        let fee_per_gram = app_state.get_default_fee_per_gram().as_u64();
+        let selection_criteria = UtxoSelectionCriteria::specific(self.selected_commitments(app_state));
+        match Handle::current().block_on(app_state.send_transaction(
+            self.address_field.clone(),
+            amount.as_u64(),
+            selection_criteria,
+            fee_per_gram,
+            String::new(),
+            tx,
+        )) {
+            Err(e) => self.error_message = Some(format!("Error sending transaction:\n{}\nPress Enter to continue.", e)),
+            Ok(_) => {
+                self.address_field.clear();
+                self.amount_field.clear();
+                self.selected.clear();
+                self.send_result_watch = Some(rx);
+            },
+        }
+    }
+
+    fn draw_send_result<B>(&mut self, f: &mut Frame<B>, area: Rect)
+    where B: Backend {
+        let rx = match self.send_result_watch.take() {
+            Some(rx) => rx,
+            None => return,
+        };
+        let status = match (*rx.borrow()).clone() {
+            UiTransactionSendStatus::Initiated => "Initiated",
+            UiTransactionSendStatus::DiscoveryInProgress => "Discovery In Progress",
+            UiTransactionSendStatus::Error(e) => {
+                self.error_message = Some(format!("Error sending transaction: {}, Press Enter to continue.", e));
+                return;
+            },
+            UiTransactionSendStatus::SentDirect |
+            UiTransactionSendStatus::SentViaSaf |
+            UiTransactionSendStatus::TransactionComplete => {
+                self.success_message = Some("Transaction successfully sent!\nPress Enter to continue".to_string());
+                return;
+            },
+            UiTransactionSendStatus::Queued => {
+                self.success_message =
+                    Some("Transaction queued for further retry sending.\nPress Enter to continue".to_string());
+                return;
+            },
+        };
+        draw_dialog(
+            f,
+            area,
+            "Please Wait".to_string(),
+            format!("Transaction Send Status: {}", status),
+            Color::Green,
+            120,
+            10,
+        );
+        self.send_result_watch = Some(rx);
+    }
+}
+
+impl<B: Backend> Component<B> for UtxosTab {
+    fn draw(&mut self, f: &mut Frame<B>, area: Rect, app_state: &AppState) {
+        self.draw_utxos(f, area, app_state);
+        self.draw_input(f, area);
+        self.draw_send_result(f, area);
+
+        if let Some(msg) = self.error_message.clone() {
+            draw_dialog(f, area, "Error!".to_string(), msg, Color::Red, 120, 9);
+        }
+        if let Some(msg) = self.success_message.clone() {
+            draw_dialog(f, area, "Success!".to_string(), msg, Color::Green, 120, 9);
+        }
+
+        match self.confirmation_dialog {
+            None => (),
+            Some(dialog) => {
+                let (count, total) = self.selection_summary(app_state);
+                let action = match dialog {
+                    ConfirmationDialogType::Join => "join".to_string(),
+                    ConfirmationDialogType::Split(split_count) => format!("split into {} outputs", split_count),
+                };
+                draw_dialog(
+                    f,
+                    area,
+                    "Confirm".to_string(),
+                    format!(
+                        "Are you sure you want to {} the {} selected outputs with a total of {}?\n(Y)es / (N)o",
+                        action, count, total
+                    ),
+                    Color::Red,
+                    120,
+                    9,
+                );
+            },
+        }
+    }
+
+    fn on_key(&mut self, app_state: &mut AppState, c: char) {
+        if self.error_message.is_some() || self.success_message.is_some() {
+            if '\n' == c {
+                self.error_message = None;
+                self.success_message = None;
+            }
+            return;
+        }
+
+        if self.send_result_watch.is_some() {
+            return;
+        }
+
+        if self.on_key_confirmation_dialog(c, app_state) == KeyHandled::Handled {
+            return;
+        }
+
+        if self.on_key_input(c, app_state) == KeyHandled::Handled {
+            return;
+        }
+
+        let (count, _) = self.selection_summary(app_state);
+        match c {
+            ' ' => {
+                if let Some(u) = self
+                    .utxos_list_state
+                    .selected()
+                    .and_then(|i| app_state.get_utxos().get(i))
+                {
+                    let commitment = u.commitment.to_hex();
+                    if !self.selected.remove(&commitment) {
+                        self.selected.insert(commitment);
+                    }
+                }
+            },
+            'a' => {
+                self.selected = app_state.get_utxos().iter().map(|u| u.commitment.to_hex()).collect();
+            },
+            'c' => self.selected.clear(),
+            'j' => {
+                if count < 2 {
+                    self.error_message =
+                        Some("Select at least two outputs to join\nPress Enter to continue.".to_string());
+                } else {
+                    self.confirmation_dialog = Some(ConfirmationDialogType::Join);
+                }
+            },
+            's' => {
+                if count == 0 {
+                    self.error_message = Some("Select the outputs to split\nPress Enter to continue.".to_string());
+                } else {
+                    self.input_mode = UtxoInputMode::SplitCount;
+                }
+            },
+            'p' => {
+                if count == 0 {
+                    self.error_message = Some("Select the outputs to pay from\nPress Enter to continue.".to_string());
+                } else {
+                    self.input_mode = UtxoInputMode::SpendAddress;
+                }
+            },
+            'r' => {
+                if let Err(e) = Handle::current().block_on(app_state.refresh_utxos_state()) {
+                    self.error_message = Some(format!("{}\nPress Enter to continue.", e));
+                }
+            },
+            _ => (),
+        }
+    }
+
+    fn on_up(&mut self, app_state: &mut AppState) {
+        self.utxos_list_state.set_num_items(app_state.get_utxos().len());
+        self.utxos_list_state.previous();
+    }
+
+    fn on_down(&mut self, app_state: &mut AppState) {
+        self.utxos_list_state.set_num_items(app_state.get_utxos().len());
+        self.utxos_list_state.next();
+    }
+
+    fn on_esc(&mut self, _: &mut AppState) {
+        if self.confirmation_dialog.is_some() {
+            return;
+        }
+        if self.input_mode == UtxoInputMode::None {
+            self.utxos_list_state.select(None);
+        } else {
+            self.input_mode = UtxoInputMode::None;
+            self.split_count_field.clear();
+            self.address_field.clear();
+            self.amount_field.clear();
+        }
+    }
+
+    fn on_backspace(&mut self, _app_state: &mut AppState) {
+        match self.input_mode {
+            UtxoInputMode::SplitCount => {
+                let _ = self.split_count_field.pop();
+            },
+            UtxoInputMode::SpendAddress => {
+                let _ = self.address_field.pop();
+            },
+            UtxoInputMode::SpendAmount => {
+                let _ = self.amount_field.pop();
+            },
+            UtxoInputMode::None => {},
+        }
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub enum UtxoInputMode {
+    None,
+    SplitCount,
+    SpendAddress,
+    SpendAmount,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ConfirmationDialogType {
+    Join,
+    Split(usize),
+}
@@ -32,6 +32,7 @@ pub mod state;
 mod ui_burnt_proof;
 mod ui_contact;
 mod ui_error;
+mod ui_utxo;
 mod widgets;
 
 use std::io::{stdout, Stdout};
@@ -61,6 +62,8 @@ pub fn run(app: App<CrosstermBackend<Stdout>>) -> Result<(), ExitError> {
             app.app_state.refresh_contacts_state().await?;
             trace!(target: LOG_TARGET, "Refreshing burnt proofs state");
             app.app_state.refresh_burnt_proofs_state().await?;
+            trace!(target: LOG_TARGET, "Refreshing UTXOs state");
+            app.app_state.refresh_utxos_state().await?;
             trace!(target: LOG_TARGET, "Refreshing connected peers state");
             app.app_state.refresh_connected_peers_state().await?;
             trace!(target: LOG_TARGET, "Checking connectivity");
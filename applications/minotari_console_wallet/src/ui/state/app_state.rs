@@ -48,7 +48,7 @@ use tari_common::configuration::Network;
 use tari_common_types::{
     tari_address::TariAddress,
     transaction::{TransactionDirection, TransactionStatus, TxId},
-    types::PublicKey,
+    types::{Commitment, PublicKey},
     wallet_types::WalletType,
 };
 use tari_comms::{
@@ -63,6 +63,7 @@ use tari_core::transactions::{
     transaction_components::{encrypted_data::PaymentId, OutputFeatures, TemplateType, TransactionError},
     weight::TransactionWeight,
 };
+use tari_p2p::auto_update::SoftwareUpdateNotifier;
 use tari_shutdown::ShutdownSignal;
 use tari_utilities::hex::Hex;
 use tokio::{
@@ -82,6 +83,7 @@ use crate::{
         ui_burnt_proof::UiBurntProof,
         ui_contact::UiContact,
         ui_error::UiError,
+        ui_utxo::UiUtxo,
     },
     utils::db::{CUSTOM_BASE_NODE_ADDRESS_KEY, CUSTOM_BASE_NODE_PUBLIC_KEY_KEY},
     wallet_modes::PeerConfig,
@@ -176,6 +178,14 @@ impl AppState {
         Ok(())
     }
 
+    pub async fn refresh_utxos_state(&mut self) -> Result<(), UiError> {
+        let mut inner = self.inner.write().await;
+        inner.refresh_utxos_state().await?;
+        drop(inner);
+        self.update_cache().await;
+        Ok(())
+    }
+
     pub async fn refresh_connected_peers_state(&mut self) -> Result<(), UiError> {
         self.check_connectivity().await;
         let mut inner = self.inner.write().await;
@@ -442,6 +452,41 @@ impl AppState {
         Ok(())
     }
 
+    /// Joins the outputs with the given commitments into one, paying the default fee
+    pub async fn join_utxos(&mut self, commitments: Vec<Commitment>) -> Result<TxId, UiError> {
+        let inner = self.inner.read().await;
+        let mut output_manager_service = inner.wallet.output_manager_service.clone();
+        let mut transaction_service = inner.wallet.transaction_service.clone();
+        drop(inner);
+
+        let (tx_id, tx, amount) = output_manager_service
+            .create_coin_join(commitments, self.get_default_fee_per_gram())
+            .await?;
+        transaction_service
+            .submit_transaction(tx_id, tx, amount, "Coin join".to_string())
+            .await?;
+        self.refresh_utxos_state().await?;
+        Ok(tx_id)
+    }
+
+    /// Splits the outputs with the given commitments into `split_count` outputs of equal value, paying the default
+    /// fee
+    pub async fn split_utxos(&mut self, commitments: Vec<Commitment>, split_count: usize) -> Result<TxId, UiError> {
+        let inner = self.inner.read().await;
+        let mut output_manager_service = inner.wallet.output_manager_service.clone();
+        let mut transaction_service = inner.wallet.transaction_service.clone();
+        drop(inner);
+
+        let (tx_id, tx, amount) = output_manager_service
+            .create_coin_split_even(commitments, split_count, self.get_default_fee_per_gram())
+            .await?;
+        transaction_service
+            .submit_transaction(tx_id, tx, amount, "Coin split".to_string())
+            .await?;
+        self.refresh_utxos_state().await?;
+        Ok(tx_id)
+    }
+
     pub async fn cancel_transaction(&mut self, tx_id: TxId) -> Result<(), UiError> {
         let inner = self.inner.write().await;
         let mut tx_service_handle = inner.wallet.transaction_service.clone();
@@ -495,6 +540,18 @@ impl AppState {
         &self.cached_data.contacts[start..end]
     }
 
+    pub fn get_utxos(&self) -> &[UiUtxo] {
+        self.cached_data.utxos.as_slice()
+    }
+
+    pub fn get_utxos_slice(&self, start: usize, end: usize) -> &[UiUtxo] {
+        if self.cached_data.utxos.is_empty() || start > end || end > self.cached_data.utxos.len() {
+            return &[];
+        }
+
+        &self.cached_data.utxos[start..end]
+    }
+
     pub fn get_burnt_proofs_slice(&self, start: usize, end: usize) -> &[UiBurntProof] {
         if self.cached_data.burnt_proofs.is_empty() || start >= end {
             return &[];
@@ -914,6 +971,22 @@ impl AppStateInner {
         Ok(())
     }
 
+    pub async fn refresh_utxos_state(&mut self) -> Result<(), UiError> {
+        let mut utxos = self
+            .wallet
+            .output_manager_service
+            .get_unspent_outputs()
+            .await?
+            .into_iter()
+            .map(UiUtxo::from)
+            .collect::<Vec<_>>();
+        utxos.sort_by(|a, b| b.value.cmp(&a.value));
+
+        self.data.utxos = utxos;
+        self.updated = true;
+        Ok(())
+    }
+
     pub async fn refresh_network_id(&mut self) -> Result<(), UiError> {
         let wallet_id = self.wallet.get_wallet_id().await?;
         let qr_link = format!(
@@ -1031,6 +1104,14 @@ impl AppStateInner {
         self.wallet.base_node_service.get_event_stream()
     }
 
+    /// A notifier of new software versions, which never changes if update checks are disabled
+    pub fn get_software_update_notifier(&self) -> SoftwareUpdateNotifier {
+        match self.wallet.get_software_updater() {
+            Some(updater) => updater.update_notifier().clone(),
+            None => watch::channel(None).1,
+        }
+    }
+
     pub async fn set_base_node_peer(&mut self, peer: Peer) -> Result<(), UiError> {
         self.wallet
             .set_base_node_peer(
@@ -1257,6 +1338,7 @@ struct AppStateData {
     my_identity: MyIdentity,
     contacts: Vec<UiContact>,
     burnt_proofs: Vec<UiBurntProof>,
+    utxos: Vec<UiUtxo>,
     connected_peers: Vec<Peer>,
     balance: Balance,
     base_node_state: BaseNodeState,
@@ -1337,6 +1419,7 @@ impl AppStateData {
             my_identity: identity,
             contacts: Vec::new(),
             burnt_proofs: vec![],
+            utxos: Vec::new(),
             connected_peers: Vec::new(),
             balance: Balance::zero(),
             base_node_state: BaseNodeState::default(),
@@ -110,6 +110,10 @@ impl BalanceEnquiryDebouncer {
                                         if let Err(e) = inner.refresh_balance(balance).await {
                                             warn!(target: LOG_TARGET, "Error refresh app_state: {}", e);
                                         }
+                                        // The outputs change whenever the balance does
+                                        if let Err(e) = inner.refresh_utxos_state().await {
+                                            warn!(target: LOG_TARGET, "Error refresh app_state: {}", e);
+                                        }
                                     }
                                     Err(e) => {
                                         warn!(target: LOG_TARGET, "Could not obtain balance ({})", e);
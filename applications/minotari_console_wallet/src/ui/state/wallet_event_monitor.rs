@@ -77,6 +77,7 @@ impl WalletEventMonitor {
         let mut base_node_events = self.app_state_inner.read().await.get_base_node_event_stream();
 
         let mut contacts_liveness_events = self.app_state_inner.read().await.get_contacts_liveness_event_stream();
+        let mut software_updates = self.app_state_inner.read().await.get_software_update_notifier();
         let mut utxo_scanner_events = self
             .app_state_inner
             .read()
@@ -252,8 +253,18 @@ impl WalletEventMonitor {
                     match result {
                         Ok(msg) => {
                             trace!(target: LOG_TARGET, "Wallet Event Monitor received base node event {:?}", msg);
-                            if let BaseNodeEvent::BaseNodeStateChanged(state) = (*msg).clone() {
+                            match (*msg).clone() {
+                                BaseNodeEvent::BaseNodeStateChanged(state) => {
                                     self.trigger_base_node_state_refresh(state).await;
+                                },
+                                BaseNodeEvent::TipDivergesFromBeacons(local_height, beacon_height) => {
+                                    self.add_notification(format!(
+                                        "Warning: base node tip #{} differs from the network status beacons (#{})",
+                                        local_height, beacon_height
+                                    ))
+                                    .await;
+                                },
+                                _ => {},
                             }
                         },
                         Err(broadcast::error::RecvError::Lagged(n)) => {
@@ -287,7 +298,7 @@ impl WalletEventMonitor {
                                     );
                                     self.trigger_contacts_refresh().await;
                                 }
-                                ContactsLivenessEvent::NetworkSilence => {},
+                                ContactsLivenessEvent::StatusChanged(_) | ContactsLivenessEvent::NetworkSilence => {},
                             }
                         }
                         Err(broadcast::error::RecvError::Lagged(n)) => {
@@ -296,6 +307,20 @@ impl WalletEventMonitor {
                         Err(broadcast::error::RecvError::Closed) => {}
                     }
                 }
+                Ok(_) = software_updates.changed() => {
+                    let update = software_updates.borrow().clone();
+                    if let Some(update) = update {
+                        let mut notification = format!(
+                            "Version {} of the wallet is available: {}",
+                            update.version(),
+                            update.download_url()
+                        );
+                        if let Some(path) = update.staged_path() {
+                            notification.push_str(&format!(" (verified download staged at {})", path.display()));
+                        }
+                        self.add_notification(notification).await;
+                    }
+                },
                 _ = shutdown_signal.wait() => {
                     info!(
                         target: LOG_TARGET,
@@ -0,0 +1,39 @@
+//  Copyright 2024 The Tari Project
+//  SPDX-License-Identifier: BSD-3-Clause
+
+use minotari_wallet::output_manager_service::storage::models::DbWalletOutput;
+use tari_common_types::types::Commitment;
+use tari_core::transactions::{tari_amount::MicroMinotari, transaction_components::encrypted_data::PaymentId};
+
+#[derive(Debug, Clone)]
+pub struct UiUtxo {
+    pub commitment: Commitment,
+    pub value: MicroMinotari,
+    pub maturity: u64,
+    pub mined_height: Option<u64>,
+    pub status: String,
+    pub source: String,
+    /// The payment id and sub-address the output was received with, if any
+    pub label: String,
+}
+
+impl From<DbWalletOutput> for UiUtxo {
+    fn from(output: DbWalletOutput) -> Self {
+        let mut labels = Vec::new();
+        if output.payment_id != PaymentId::Empty {
+            labels.push(output.payment_id.to_string());
+        }
+        if let Some(index) = output.sub_address_index {
+            labels.push(format!("sub-address #{}", index));
+        }
+        Self {
+            commitment: output.commitment,
+            value: output.wallet_output.value,
+            maturity: output.wallet_output.features.maturity,
+            mined_height: output.mined_height,
+            status: output.status.to_string(),
+            source: output.source.to_string(),
+            label: labels.join(", "),
+        }
+    }
+}
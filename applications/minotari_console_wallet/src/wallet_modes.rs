@@ -22,24 +22,38 @@
 
 #![allow(dead_code, unused)]
 
-use std::{fs, io::Stdout, path::PathBuf};
+use std::{
+    fs,
+    future::Future,
+    io::Stdout,
+    path::{Path, PathBuf},
+};
 
 use clap::Parser;
 use log::*;
-use minotari_app_grpc::{authentication::ServerAuthenticationInterceptor, tls::identity::read_identity};
+use minotari_app_grpc::{
+    authentication::ServerAuthenticationInterceptor,
+    reflection::reflection_service,
+    tari_rpc::wallet_server::WalletServer,
+    tls::identity::read_identity,
+};
 use minotari_wallet::{WalletConfig, WalletSqlite};
 use rand::{rngs::OsRng, seq::SliceRandom};
 use tari_common::exit_codes::{ExitCode, ExitError};
 use tari_common_types::grpc_authentication::GrpcAuthentication;
 use tari_comms::{multiaddr::Multiaddr, peer_manager::Peer, utils::multiaddr::multiaddr_to_socketaddr};
-use tokio::{runtime::Handle, sync::broadcast};
+use tokio::{runtime::Handle, sync::broadcast, task::JoinHandle};
 use tonic::transport::{Identity, Server, ServerTlsConfig};
 use tui::backend::CrosstermBackend;
 
 use crate::{
     automation::commands::command_runner,
+    backup::spawn_backup,
     cli::{Cli, CliCommands},
+    daemon::{notify_service_manager, wait_for_termination, PidFile},
     grpc::WalletGrpcServer,
+    json_rpc::{run_json_rpc_server, JsonRpcMethods},
+    metadata_sync::spawn_metadata_sync,
     notifier::Notifier,
     recovery::wallet_recovery,
     ui,
@@ -53,6 +67,7 @@ pub const LOG_TARGET: &str = "wallet::app::main";
 pub enum WalletMode {
     Tui,
     Grpc,
+    Daemon,
     Script(PathBuf),
     Command(Box<CliCommands>),
     RecoveryDaemon,
@@ -88,12 +103,26 @@ impl PeerConfig {
     }
 
     /// Get the prioritised base node peer from the PeerConfig.
-    /// 1. Custom Base Node
+    /// 1. Custom Base Node, followed by the service peers or peer seeds as failover peers if `custom_failover` is set
     /// 2. All configured Base Node Peers (a random node will be prioritised)
     /// 3. All configured Peer Seeds (a random node will be prioritised)
-    pub fn get_base_node_peers(&self) -> Result<Vec<Peer>, ExitError> {
+    pub fn get_base_node_peers(&self, custom_failover: bool) -> Result<Vec<Peer>, ExitError> {
         if let Some(base_node) = self.base_node_custom.clone() {
-            Ok(vec![base_node])
+            let mut peers = vec![base_node.clone()];
+            if custom_failover {
+                let failover_peers = if self.base_node_peers.is_empty() {
+                    &self.peer_seeds
+                } else {
+                    &self.base_node_peers
+                };
+                peers.extend(
+                    failover_peers
+                        .iter()
+                        .filter(|p| p.public_key != base_node.public_key)
+                        .cloned(),
+                );
+            }
+            Ok(peers)
         } else if !self.base_node_peers.is_empty() {
             Ok(self.base_node_peers.clone())
         } else if !self.peer_seeds.is_empty() {
@@ -321,6 +350,10 @@ pub fn tui_mode(
 ) -> Result<(), ExitError> {
     let (events_broadcaster, _events_listener) = broadcast::channel(100);
 
+    spawn_json_rpc(&handle, config, &wallet);
+    spawn_backup(&handle, config, &wallet)?;
+    spawn_metadata_sync(&handle, config, &wallet)?;
+
     if config.grpc_enabled {
         #[cfg(feature = "grpc")]
         if let Some(address) = config.grpc_address.clone() {
@@ -448,32 +481,13 @@ pub fn recovery_mode(
 }
 
 pub fn grpc_mode(handle: Handle, config: &WalletConfig, wallet: WalletSqlite) -> Result<(), ExitError> {
+    let json_rpc = spawn_json_rpc(&handle, config, &wallet);
+    spawn_backup(&handle, config, &wallet)?;
+    spawn_metadata_sync(&handle, config, &wallet)?;
     info!(target: LOG_TARGET, "Starting grpc server");
     if let Some(address) = config.grpc_address.as_ref().filter(|_| config.grpc_enabled).cloned() {
         #[cfg(feature = "grpc")]
-        {
-            let grpc = WalletGrpcServer::new(wallet.clone()).map_err(|e| ExitError {
-                exit_code: ExitCode::UnknownError,
-                details: Some(e.to_string()),
-            })?;
-            let auth = config.grpc_authentication.clone();
-
-            let mut tls_identity = None;
-            if config.grpc_tls_enabled {
-                match handle
-                    .block_on(read_identity(config.config_dir.clone()))
-                    .map(Some)
-                    .map_err(|e| ExitError::new(ExitCode::TlsConfigurationError, e.to_string()))
-                {
-                    Ok(identity) => tls_identity = identity,
-                    Err(e) => return Err(e),
-                }
-            }
-
-            handle
-                .block_on(run_grpc(grpc, address, auth, tls_identity, wallet))
-                .map_err(|e| ExitError::new(ExitCode::GrpcError, e))?;
-        }
+        serve_grpc(&handle, config, wallet.clone(), address, wallet.wait_until_shutdown())?;
         #[cfg(not(feature = "grpc"))]
         return Err(ExitError::new(
             ExitCode::GrpcError,
@@ -481,17 +495,119 @@ pub fn grpc_mode(handle: Handle, config: &WalletConfig, wallet: WalletSqlite) ->
         ));
     } else {
         println!("GRPC server is disabled");
+        if let Some(json_rpc) = json_rpc {
+            handle
+                .block_on(json_rpc)
+                .map_err(|e| ExitError::new(ExitCode::UnknownError, e))?
+                .map_err(|e| ExitError::new(ExitCode::NetworkError, e))?;
+        }
     }
     info!(target: LOG_TARGET, "Shutting down");
     Ok(())
 }
 
-async fn run_grpc(
+/// Runs the wallet headless as a service, serving only gRPC until the process is asked to terminate. The service
+/// manager is notified once the wallet is ready and again when it starts shutting down; the wallet itself is shut
+/// down by the caller, which lets the running protocols persist their state.
+pub fn daemon_mode(
+    handle: Handle,
+    config: &WalletConfig,
+    wallet: WalletSqlite,
+    pid_file: Option<&Path>,
+) -> Result<(), ExitError> {
+    let _pid_file = pid_file
+        .map(PidFile::create)
+        .transpose()
+        .map_err(|e| ExitError::new(ExitCode::IOError, format!("Could not write the PID file: {}", e)))?;
+    spawn_backup(&handle, config, &wallet)?;
+    spawn_metadata_sync(&handle, config, &wallet)?;
+    let address = config
+        .grpc_address
+        .as_ref()
+        .filter(|_| config.grpc_enabled)
+        .cloned()
+        .ok_or_else(|| ExitError::new(ExitCode::ConfigError, "Daemon mode requires a gRPC address"))?;
+    #[cfg(not(feature = "grpc"))]
+    return Err(ExitError::new(
+        ExitCode::GrpcError,
+        "Daemon mode requires gRPC, which is not supported in this build",
+    ));
+
+    #[cfg(feature = "grpc")]
+    {
+        info!(target: LOG_TARGET, "Starting daemon, serving gRPC on {}", address);
+        let wallet_shutdown = wallet.clone().wait_until_shutdown();
+        let shutdown_signal = async move {
+            tokio::select! {
+                _ = wallet_shutdown => {},
+                _ = wait_for_termination() => {},
+            }
+            notify_service_manager("STOPPING=1");
+        };
+        notify_service_manager("READY=1");
+        serve_grpc(&handle, config, wallet, address, shutdown_signal)?;
+        info!(target: LOG_TARGET, "Daemon stopped serving gRPC");
+        Ok(())
+    }
+}
+
+/// Serves the wallet gRPC interface until `shutdown_signal` resolves
+fn serve_grpc<F: Future<Output = ()>>(
+    handle: &Handle,
+    config: &WalletConfig,
+    wallet: WalletSqlite,
+    address: Multiaddr,
+    shutdown_signal: F,
+) -> Result<(), ExitError> {
+    let grpc = WalletGrpcServer::new(wallet).map_err(|e| ExitError {
+        exit_code: ExitCode::UnknownError,
+        details: Some(e.to_string()),
+    })?;
+    let auth = config.grpc_authentication.clone();
+
+    let mut tls_identity = None;
+    if config.grpc_tls_enabled {
+        match handle
+            .block_on(read_identity(config.config_dir.clone()))
+            .map(Some)
+            .map_err(|e| ExitError::new(ExitCode::TlsConfigurationError, e.to_string()))
+        {
+            Ok(identity) => tls_identity = identity,
+            Err(e) => return Err(e),
+        }
+    }
+
+    handle
+        .block_on(run_grpc(grpc, address, auth, tls_identity, shutdown_signal))
+        .map_err(|e| ExitError::new(ExitCode::GrpcError, e))
+}
+
+/// Spawns the JSON-RPC compatibility server if it is enabled
+fn spawn_json_rpc(
+    handle: &Handle,
+    config: &WalletConfig,
+    wallet: &WalletSqlite,
+) -> Option<JoinHandle<Result<(), String>>> {
+    let address = config
+        .json_rpc_address
+        .as_ref()
+        .filter(|_| config.json_rpc_enabled)
+        .cloned()?;
+    let methods = JsonRpcMethods::new(wallet.clone(), config.fee_per_gram.into());
+    Some(handle.spawn(run_json_rpc_server(
+        methods,
+        address,
+        config.json_rpc_authentication.clone(),
+        wallet.clone().wait_until_shutdown(),
+    )))
+}
+
+async fn run_grpc<F: Future<Output = ()>>(
     grpc: WalletGrpcServer,
     grpc_listener_addr: Multiaddr,
     auth_config: GrpcAuthentication,
     tls_identity: Option<Identity>,
-    wallet: WalletSqlite,
+    shutdown_signal: F,
 ) -> Result<(), String> {
     // Do not remove this println!
     const CUCUMBER_TEST_MARKER_A: &str = "Minotari Console Wallet running... (gRPC mode started)";
@@ -501,7 +617,12 @@ async fn run_grpc(
     let address = multiaddr_to_socketaddr(&grpc_listener_addr).map_err(|e| e.to_string())?;
     let auth = ServerAuthenticationInterceptor::new(auth_config)
         .ok_or("Unable to prepare server gRPC authentication".to_string())?;
-    let service = minotari_app_grpc::tari_rpc::wallet_server::WalletServer::with_interceptor(grpc, auth);
+    let service = WalletServer::with_interceptor(grpc, auth);
+    // The health and reflection services are deliberately not behind the authentication interceptor so that
+    // orchestration probes and tooling can reach them without credentials
+    let (mut health_reporter, health_service) = tonic_health::server::health_reporter();
+    health_reporter.set_serving::<WalletServer<WalletGrpcServer>>().await;
+    let reflection_service = reflection_service().map_err(|e| e.to_string())?;
 
     let mut server_builder = if let Some(identity) = tls_identity {
         Server::builder()
@@ -513,7 +634,9 @@ async fn run_grpc(
 
     server_builder
         .add_service(service)
-        .serve_with_shutdown(address, wallet.wait_until_shutdown())
+        .add_service(health_service)
+        .add_service(reflection_service)
+        .serve_with_shutdown(address, shutdown_signal)
         .await
         .map_err(|e| format!("GRPC server returned error:{}", e))?;
 
@@ -640,10 +763,14 @@ mod test {
                 CliCommands::ClaimShaAtomicSwapRefund(_) => {},
                 CliCommands::RevalidateWalletDb => {},
                 CliCommands::RegisterValidatorNode(_) => {},
+                CliCommands::ListValidatorNodeRegistrations => {},
                 CliCommands::CreateTlsCerts => {},
                 CliCommands::PreMineSpendBackupUtxo(_) => {},
                 CliCommands::Sync(_) => {},
                 CliCommands::ExportViewKeyAndSpendKey(_) => {},
+                CliCommands::RestoreBackup(_) => {},
+                CliCommands::VerifyWalletDb(_) => {},
+                CliCommands::TxDoctor(_) => {},
             }
         }
         assert!(
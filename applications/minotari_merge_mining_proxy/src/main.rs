@@ -40,7 +40,10 @@ use std::io::stdout;
 use clap::Parser;
 use crossterm::{execute, terminal::SetTitle};
 use log::*;
-use minotari_app_utilities::consts;
+use minotari_app_utilities::{
+    consts,
+    diagnostics::{install_panic_handler, DiagnosticsConfig},
+};
 use tari_common::initialize_logging;
 
 const LOG_TARGET: &str = "minotari_mm_proxy::proxy";
@@ -58,7 +61,9 @@ async fn main() -> Result<(), anyhow::Error> {
         &cli.common.log_config_path("proxy"),
         cli.common.log_path.as_ref().unwrap_or(&base_path),
         include_str!("../log4rs_sample.yml"),
+        cli.common.log_format.unwrap_or_default(),
     )?;
+    install_panic_handler(DiagnosticsConfig::from_cli("proxy", &cli.common));
     info!(
         target: LOG_TARGET,
         "Starting Minotari Merge Mining Proxy version: {}",
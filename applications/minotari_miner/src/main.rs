@@ -25,7 +25,10 @@ use std::io::stdout;
 use clap::Parser;
 use crossterm::{execute, terminal::SetTitle};
 use log::*;
-use minotari_app_utilities::consts;
+use minotari_app_utilities::{
+    consts,
+    diagnostics::{install_panic_handler, DiagnosticsConfig},
+};
 use run_miner::start_miner;
 use tari_common::{exit_codes::ExitError, initialize_logging};
 
@@ -66,7 +69,9 @@ async fn main_inner() -> Result<(), ExitError> {
         &cli.common.log_config_path("miner"),
         &cli.common.get_base_path(),
         include_str!("../log4rs_sample.yml"),
+        cli.common.log_format.unwrap_or_default(),
     )?;
+    install_panic_handler(DiagnosticsConfig::from_cli("miner", &cli.common));
     info!(
         target: LOG_TARGET,
         "Starting Minotari Miner version: {}",
@@ -0,0 +1,63 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Periodic encrypted backups of the node identity and chain metadata. The blockchain itself is not backed up, as it
+//! can always be synced again from the network.
+
+use std::path::Path;
+
+use futures::FutureExt;
+use minotari_app_utilities::backup::{ArchiveEntry, BackupError, BackupService};
+use tari_common::exit_codes::{ExitCode, ExitError};
+use tari_core::chain_storage::{async_db::AsyncBlockchainDb, LMDBDatabase};
+use tari_shutdown::ShutdownSignal;
+use tokio::task;
+
+use crate::{builder::BaseNodeContext, config::BaseNodeConfig};
+
+/// Starts the backup service if backups are enabled
+pub fn install(ctx: &BaseNodeContext, config: &BaseNodeConfig, shutdown: ShutdownSignal) -> Result<(), ExitError> {
+    if !config.backup.enabled {
+        return Ok(());
+    }
+    let db: AsyncBlockchainDb<LMDBDatabase> = ctx.blockchain_db().into();
+    let files = [config.identity_file.clone(), config.tor_identity_file.clone()];
+    let source = Box::new(move || {
+        let db = db.clone();
+        let files = files.clone();
+        async move {
+            let metadata = db
+                .get_chain_metadata()
+                .await
+                .map_err(|e| BackupError::Source(e.to_string()))?;
+            let mut entries = vec![ArchiveEntry::new(
+                "chain_metadata.json",
+                serde_json::to_vec_pretty(&metadata).map_err(|e| BackupError::Source(e.to_string()))?,
+            )];
+            for file in &files {
+                if let Some(entry) = read_file_entry(file).await? {
+                    entries.push(entry);
+                }
+            }
+            Ok(entries)
+        }
+        .boxed()
+    });
+    let service = BackupService::new("base_node", config.backup.clone(), source)
+        .map_err(|e| ExitError::new(ExitCode::ConfigError, format!("Invalid backup configuration: {}", e)))?;
+    task::spawn(service.run(shutdown));
+    Ok(())
+}
+
+/// Reads a file into an archive entry named after the file, or returns `None` if the file does not exist
+async fn read_file_entry(path: &Path) -> Result<Option<ArchiveEntry>, BackupError> {
+    let name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| BackupError::Source(format!("{} is not a file", path.display())))?;
+    match tokio::fs::read(path).await {
+        Ok(data) => Ok(Some(ArchiveEntry::new(name, data))),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
@@ -31,7 +31,10 @@ use tari_common::{
 use tari_comms::{
     multiaddr::{Error as MultiaddrError, Multiaddr},
     peer_manager::Peer,
-    protocol::rpc::RpcServer,
+    protocol::{
+        rpc::{RpcRateLimit, RpcServer},
+        Capabilities,
+    },
     tor::TorIdentity,
     NodeIdentity,
     UnspawnedCommsNode,
@@ -41,6 +44,7 @@ use tari_core::{
     base_node,
     base_node::{
         chain_metadata_service::ChainMetadataServiceInitializer,
+        network_monitor::NetworkMonitorInitializer,
         service::BaseNodeServiceInitializer,
         state_machine_service::initializer::BaseNodeStateMachineInitializer,
         LocalNodeCommsInterface,
@@ -59,7 +63,11 @@ use tari_p2p::{
     initialization,
     initialization::P2pInitializer,
     peer_seeds::SeedPeer,
-    services::liveness::{config::LivenessConfig, LivenessInitializer},
+    services::{
+        gossip::{GossipInitializer, GOSSIP_CAPABILITY, GOSSIP_CAPABILITY_VERSION},
+        liveness::{config::LivenessConfig, LivenessInitializer},
+    },
+    tari_message::TariMessageType,
     P2pConfig,
     TransportType,
 };
@@ -96,6 +104,7 @@ where B: BlockchainBackend + 'static
         let (publisher, peer_message_subscriptions) = pubsub_connector(buf_size);
         let peer_message_subscriptions = Arc::new(peer_message_subscriptions);
         let mempool_config = base_node_config.mempool.service.clone();
+        let propagation_delay_max = mempool_config.propagation_delay_max;
 
         let sync_peers = base_node_config
             .force_sync_peers
@@ -117,15 +126,22 @@ where B: BlockchainBackend + 'static
         p2p_config.transport.tor.identity = tor_identity;
 
         let user_agent = format!("tari/basenode/{}", consts::APP_VERSION_NUMBER);
-        let mut handles = StackBuilder::new(self.interrupt_signal)
-            .add_initializer(P2pInitializer::new(
-                p2p_config.clone(),
-                user_agent,
-                peer_seeds.clone(),
-                base_node_config.network,
-                self.node_identity.clone(),
-                publisher,
-            ))
+        let mut capabilities = Capabilities::new();
+        if base_node_config.gossip.enabled {
+            capabilities.add(GOSSIP_CAPABILITY, [GOSSIP_CAPABILITY_VERSION]);
+        }
+        let mut stack = StackBuilder::new(self.interrupt_signal)
+            .add_initializer(
+                P2pInitializer::new(
+                    p2p_config.clone(),
+                    user_agent,
+                    peer_seeds.clone(),
+                    base_node_config.network,
+                    self.node_identity.clone(),
+                    publisher,
+                )
+                .with_capabilities(capabilities),
+            )
             .add_initializer(SoftwareUpdaterService::new(
                 ApplicationType::BaseNode,
                 consts::APP_VERSION_NUMBER
@@ -142,10 +158,10 @@ where B: BlockchainBackend + 'static
                 self.randomx_factory.clone(),
                 base_node_config.state_machine.clone(),
             ))
-            .add_initializer(MempoolServiceInitializer::new(
-                self.mempool.clone(),
-                peer_message_subscriptions.clone(),
-            ))
+            .add_initializer(
+                MempoolServiceInitializer::new(self.mempool.clone(), peer_message_subscriptions.clone())
+                    .with_propagation_delay(propagation_delay_max),
+            )
             .add_initializer(mempool_sync)
             .add_initializer(LivenessInitializer::new(
                 LivenessConfig {
@@ -153,7 +169,7 @@ where B: BlockchainBackend + 'static
                     monitored_peers: sync_peers.clone(),
                     ..Default::default()
                 },
-                peer_message_subscriptions,
+                peer_message_subscriptions.clone(),
             ))
             .add_initializer(ChainMetadataServiceInitializer)
             .add_initializer(BaseNodeStateMachineInitializer::new(
@@ -164,8 +180,17 @@ where B: BlockchainBackend + 'static
                 self.randomx_factory,
                 self.app_config.base_node.bypass_range_proof_verification,
             ))
-            .build()
-            .await?;
+            .add_initializer(NetworkMonitorInitializer::new(base_node_config.network_monitor.clone()));
+
+        if base_node_config.gossip.enabled {
+            stack = stack.add_initializer(GossipInitializer::new(
+                base_node_config.gossip.clone(),
+                vec![TariMessageType::NewBlock, TariMessageType::NewTransaction],
+                peer_message_subscriptions.clone(),
+            ));
+        }
+
+        let mut handles = stack.build().await?;
 
         let comms = handles
             .take_handle::<UnspawnedCommsNode>()
@@ -227,10 +252,19 @@ where B: BlockchainBackend + 'static
     ) -> UnspawnedCommsNode {
         let dht = handles.expect_handle::<Dht>();
         let base_node_service = handles.expect_handle::<LocalNodeCommsInterface>();
-        let rpc_server = RpcServer::builder()
+        let mut rpc_server = RpcServer::builder()
             .with_maximum_simultaneous_sessions(config.rpc_max_simultaneous_sessions)
-            .with_maximum_sessions_per_client(config.rpc_max_sessions_per_peer)
-            .finish();
+            .with_maximum_sessions_per_client(config.rpc_max_sessions_per_peer);
+        if let Some(limit) = config.rpc_max_requests_per_minute_per_peer {
+            rpc_server = rpc_server.with_per_peer_rate_limit(RpcRateLimit::per_minute(limit));
+        }
+        if let Some(limit) = config.rpc_max_requests_per_minute_per_method {
+            rpc_server = rpc_server.with_per_method_rate_limit(RpcRateLimit::per_minute(limit));
+        }
+        if let Some(limit) = config.rpc_max_pending_requests {
+            rpc_server = rpc_server.with_maximum_pending_requests(limit);
+        }
+        let rpc_server = rpc_server.finish();
 
         // Add your RPC services here ‍🏴‍☠️️☮️🌊
         let rpc_server = rpc_server
@@ -30,7 +30,12 @@ use tari_common::{
 use tari_comms::{peer_manager::NodeIdentity, protocol::rpc::RpcServerHandle, CommsNode};
 use tari_comms_dht::Dht;
 use tari_core::{
-    base_node::{state_machine_service::states::StatusInfo, LocalNodeCommsInterface, StateMachineHandle},
+    base_node::{
+        network_monitor::NetworkMonitorHandle,
+        state_machine_service::states::StatusInfo,
+        LocalNodeCommsInterface,
+        StateMachineHandle,
+    },
     chain_storage::{create_lmdb_database, BlockchainDatabase, ChainStorageError, LMDBDatabase, Validators},
     consensus::ConsensusManager,
     mempool::{service::LocalMempoolService, Mempool},
@@ -63,6 +68,7 @@ pub struct BaseNodeContext {
     base_node_comms: CommsNode,
     base_node_dht: Dht,
     base_node_handles: ServiceHandles,
+    mempool: Mempool,
 }
 
 impl BaseNodeContext {
@@ -91,6 +97,11 @@ impl BaseNodeContext {
         self.base_node_handles.expect_handle()
     }
 
+    /// Returns the Mempool
+    pub fn mempool(&self) -> Mempool {
+        self.mempool.clone()
+    }
+
     /// Returns the CommsNode.
     pub fn base_node_comms(&self) -> &CommsNode {
         &self.base_node_comms
@@ -106,6 +117,11 @@ impl BaseNodeContext {
         self.base_node_handles.expect_handle()
     }
 
+    /// Returns the network partition and eclipse monitor handle
+    pub fn network_monitor(&self) -> NetworkMonitorHandle {
+        self.base_node_handles.expect_handle()
+    }
+
     /// Returns this node's identity.
     pub fn base_node_identity(&self) -> Arc<NodeIdentity> {
         self.base_node_comms.node_identity()
@@ -260,7 +276,7 @@ async fn build_node_context(
         app_config: &app_config,
         node_identity: base_node_identity,
         db: blockchain_db.clone(),
-        mempool,
+        mempool: mempool.clone(),
         rules: rules.clone(),
         factories: factories.clone(),
         randomx_factory,
@@ -279,5 +295,6 @@ async fn build_node_context(
         base_node_comms,
         base_node_dht,
         base_node_handles,
+        mempool,
     })
 }
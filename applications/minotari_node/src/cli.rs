@@ -43,6 +43,9 @@ pub struct Cli {
     /// Watch a command in the non-interactive mode.
     #[clap(long)]
     pub watch: Option<String>,
+    /// Start in the full screen status dashboard instead of the status line. Ignored in non-interactive mode.
+    #[clap(long, conflicts_with = "watch")]
+    pub dashboard: bool,
     #[clap(long, alias = "profile")]
     pub profile_with_tokio_console: bool,
     #[clap(long, env = "MINOTARI_NODE_ENABLE_GRPC", alias = "enable-grpc")]
@@ -51,6 +54,20 @@ pub struct Cli {
     pub mining_enabled: bool,
     #[clap(long, env = "MINOTARI_NODE_SECOND_LAYER_GRPC_ENABLED", alias = "enable-second-layer")]
     pub second_layer_grpc_enabled: bool,
+    #[clap(
+        long,
+        env = "MINOTARI_NODE_LIGHT_WALLET_SERVER_ENABLED",
+        alias = "enable-light-wallet-server"
+    )]
+    pub light_wallet_server_enabled: bool,
+    /// Run a localnet node in regtest mode, where blocks are only produced on demand by the `GenerateBlocks` GRPC
+    /// method
+    #[clap(long, env = "MINOTARI_NODE_REGTEST")]
+    pub regtest: bool,
+    /// Only connect through Tor hidden services, without DNS seeds or update checks, and delay transaction
+    /// propagation. The node refuses to start if any setting would reveal its public IP address.
+    #[clap(long, env = "MINOTARI_NODE_PRIVACY")]
+    pub privacy: bool,
 }
 
 impl ConfigOverrideProvider for Cli {
@@ -80,6 +97,14 @@ impl ConfigOverrideProvider for Cli {
             replace_or_add_override(&mut overrides, "base_node.grpc_enabled", "true");
             replace_or_add_override(&mut overrides, "base_node.second_layer_grpc_enabled", "true");
         }
+        if self.light_wallet_server_enabled {
+            replace_or_add_override(&mut overrides, "base_node.grpc_enabled", "true");
+            replace_or_add_override(&mut overrides, "base_node.light_wallet_server_enabled", "true");
+        }
+        if self.regtest {
+            replace_or_add_override(&mut overrides, "base_node.grpc_enabled", "true");
+            replace_or_add_override(&mut overrides, "base_node.regtest_enabled", "true");
+        }
         overrides
     }
 }
@@ -28,6 +28,7 @@ pub struct CliLoop {
     reader: CommandReader,
     commands: Vec<String>,
     watch_task: Option<WatchCommand>,
+    start_in_dashboard: bool,
     non_interactive: bool,
     first_signal: bool,
     done: bool,
@@ -35,7 +36,12 @@ pub struct CliLoop {
 }
 
 impl CliLoop {
-    pub fn new(context: CommandContext, watch_command: Option<String>, non_interactive: bool) -> Self {
+    pub fn new(
+        context: CommandContext,
+        watch_command: Option<String>,
+        start_in_dashboard: bool,
+        non_interactive: bool,
+    ) -> Self {
         let parser = Parser::new();
         let commands = parser.get_commands();
         let cli_config = Config::builder()
@@ -65,6 +71,7 @@ impl CliLoop {
             reader,
             commands,
             watch_task: Some(watch_task),
+            start_in_dashboard,
             non_interactive,
             first_signal: false,
             done: false,
@@ -85,6 +92,11 @@ impl CliLoop {
         if self.non_interactive {
             self.watch_loop_non_interactive().await;
         } else {
+            if self.start_in_dashboard {
+                if let Err(err) = self.context.handle_command_str("dashboard").await {
+                    println!("Could not show the dashboard: {}", err);
+                }
+            }
             while !self.done {
                 self.watch_loop().await;
                 self.execute_command().await;
@@ -155,6 +167,9 @@ impl CliLoop {
                                     update.download_url(),
                                     update.to_hash_hex()
                                 );
+                                if let Some(path) = update.staged_path() {
+                                    println!("The verified download is staged at {}", path.display());
+                                }
                             }
                         }
                     }
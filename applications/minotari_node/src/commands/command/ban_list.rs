@@ -0,0 +1,84 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{fs, path::PathBuf};
+
+use anyhow::Error;
+use async_trait::async_trait;
+use clap::Parser;
+use minotari_app_utilities::utilities::UniPublicKey;
+use tari_p2p::ban_list::{export_ban_list, import_ban_list, SignedBanList};
+
+use super::{CommandContext, HandleCommand};
+
+/// Exports the banned peers as a signed ban list file that can be imported by other nodes
+#[derive(Debug, Parser)]
+pub struct ArgsExport {
+    /// the file to write the ban list to
+    output_file: PathBuf,
+}
+
+#[async_trait]
+impl HandleCommand<ArgsExport> for CommandContext {
+    async fn handle_command(&mut self, args: ArgsExport) -> Result<(), Error> {
+        let ban_list = export_ban_list(&self.comms.peer_manager(), &self.base_node_identity).await?;
+        fs::write(&args.output_file, ban_list.to_json()?)?;
+        println!(
+            "Exported {} banned peer(s) to {}",
+            ban_list.entries.len(),
+            args.output_file.display()
+        );
+        Ok(())
+    }
+}
+
+/// Imports a signed ban list file and merges it with the local bans
+#[derive(Debug, Parser)]
+pub struct ArgsImport {
+    /// the ban list file to import
+    input_file: PathBuf,
+    /// only accept the ban list if it was signed by one of these public keys (hex or emoji id)
+    #[clap(long)]
+    trusted_signer: Vec<UniPublicKey>,
+}
+
+#[async_trait]
+impl HandleCommand<ArgsImport> for CommandContext {
+    async fn handle_command(&mut self, args: ArgsImport) -> Result<(), Error> {
+        let ban_list = SignedBanList::from_json(&fs::read_to_string(&args.input_file)?)?;
+        let signer = ban_list.signer.clone();
+        let trusted_signers = args.trusted_signer.into_iter().map(Into::into).collect::<Vec<_>>();
+        let summary = import_ban_list(
+            &self.comms.peer_manager(),
+            &mut self.comms.connectivity(),
+            &self.base_node_identity,
+            ban_list,
+            &trusted_signers,
+        )
+        .await?;
+        println!(
+            "Imported ban list signed by {}: {} peer(s) banned, {} skipped",
+            signer, summary.num_banned, summary.num_skipped
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,395 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    io::{self, Stdout},
+    time::Duration,
+};
+
+use anyhow::Error;
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use clap::Parser;
+use crossterm::{
+    event::{Event, EventStream, KeyCode, KeyEvent, KeyModifiers},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use futures::StreamExt;
+use minotari_app_utilities::consts;
+use tari_comms::connection_manager::SelfLivenessStatus;
+use tari_core::{base_node::state_machine_service::states::PeerMetadata, chain_storage::Reorg, mempool::StatsResponse};
+use tari_utilities::hex::Hex;
+use tokio::time;
+use tui::{
+    backend::{Backend, CrosstermBackend},
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, Borders, Paragraph, Row, Table},
+    Frame,
+    Terminal,
+};
+
+use super::{CommandContext, HandleCommand};
+use crate::utils::format_duration_basic;
+
+/// The number of most recent reorgs shown
+const MAX_REORGS: u16 = 5;
+
+/// Shows a full screen dashboard of the node's sync state, tip, peers, mempool and recent reorgs, refreshed
+/// periodically. Press q or Esc to return to the shell.
+#[derive(Debug, Parser)]
+pub struct Args {
+    /// Refresh interval in seconds. The status line interval if not given.
+    #[clap(short, long)]
+    pub interval: Option<u64>,
+}
+
+#[async_trait]
+impl HandleCommand<Args> for CommandContext {
+    async fn handle_command(&mut self, args: Args) -> Result<(), Error> {
+        self.dashboard(args).await
+    }
+}
+
+struct PeerRow {
+    node_id: String,
+    address: String,
+    direction: String,
+    age: Duration,
+    latency: Option<Duration>,
+    height: Option<u64>,
+    user_agent: String,
+}
+
+struct DashboardSnapshot {
+    state: String,
+    bootstrapped: bool,
+    tip_height: u64,
+    tip_hash: String,
+    tip_time: String,
+    liveness: String,
+    mempool: Option<StatsResponse>,
+    num_wallets: usize,
+    peers: Vec<PeerRow>,
+    /// `None` if reorg tracking is off
+    reorgs: Option<Vec<Reorg>>,
+}
+
+impl CommandContext {
+    pub async fn dashboard(&mut self, args: Args) -> Result<(), Error> {
+        let interval = args
+            .interval
+            .map(Duration::from_secs)
+            .unwrap_or(self.config.base_node.status_line_interval);
+        enable_raw_mode()?;
+        let mut stdout = io::stdout();
+        execute!(stdout, EnterAlternateScreen)?;
+        let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+        terminal.clear()?;
+
+        let result = self.dashboard_loop(&mut terminal, interval).await;
+
+        disable_raw_mode().ok();
+        execute!(terminal.backend_mut(), LeaveAlternateScreen).ok();
+        terminal.show_cursor().ok();
+        result
+    }
+
+    async fn dashboard_loop(
+        &mut self,
+        terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+        interval: Duration,
+    ) -> Result<(), Error> {
+        let mut events = EventStream::new();
+        let mut shutdown_signal = self.shutdown.to_signal();
+        loop {
+            let snapshot = self.dashboard_snapshot().await?;
+            terminal.draw(|f| draw_dashboard(f, &snapshot))?;
+            tokio::select! {
+                _ = time::sleep(interval) => {},
+                event = events.next() => {
+                    if is_exit_event(event) {
+                        return Ok(());
+                    }
+                },
+                _ = shutdown_signal.wait() => return Ok(()),
+            }
+        }
+    }
+
+    #[allow(clippy::cast_possible_wrap)]
+    async fn dashboard_snapshot(&mut self) -> Result<DashboardSnapshot, Error> {
+        let (state, bootstrapped) = {
+            let info = self.state_machine_info.borrow();
+            (info.state_info.to_string(), info.bootstrapped)
+        };
+
+        let metadata = self.node_service.get_metadata().await?;
+        let tip_height = metadata.best_block_height();
+        let tip_time = self
+            .node_service
+            .get_header(tip_height)
+            .await?
+            .map(|header| {
+                DateTime::<Utc>::from_naive_utc_and_offset(
+                    NaiveDateTime::from_timestamp_opt(header.header().timestamp.as_u64() as i64, 0).unwrap_or_default(),
+                    Utc,
+                )
+                .to_rfc2822()
+            })
+            .unwrap_or_default();
+
+        let liveness = match self.comms.liveness_status() {
+            SelfLivenessStatus::Disabled => "disabled".to_string(),
+            SelfLivenessStatus::Checking => "checking".to_string(),
+            SelfLivenessStatus::Unreachable => "unreachable".to_string(),
+            SelfLivenessStatus::Live(latency) => format!("live ({:.2?})", latency),
+        };
+
+        let mempool = time::timeout(Duration::from_secs(5), self.mempool_service.get_mempool_stats())
+            .await
+            .ok()
+            .and_then(Result::ok);
+
+        let connections = self.comms.connectivity().get_active_connections().await?;
+        let num_wallets = connections.iter().filter(|c| !c.peer_features().is_node()).count();
+        let peer_manager = self.comms.peer_manager();
+        let mut peers = Vec::new();
+        for conn in connections.iter().filter(|c| c.peer_features().is_node()) {
+            let peer = peer_manager.find_by_node_id(conn.peer_node_id()).await?;
+            let height = peer
+                .as_ref()
+                .and_then(|p| p.get_metadata(1))
+                .and_then(|v| bincode::deserialize::<PeerMetadata>(v).ok())
+                .map(|metadata| metadata.metadata.best_block_height());
+            let latency = self
+                .liveness
+                .get_avg_latency(conn.peer_node_id().clone())
+                .await
+                .ok()
+                .flatten();
+            peers.push(PeerRow {
+                node_id: conn.peer_node_id().short_str(),
+                address: conn.address().to_string(),
+                direction: conn.direction().to_string(),
+                age: conn.age(),
+                latency,
+                height,
+                user_agent: peer.map(|p| p.user_agent).unwrap_or_default(),
+            });
+        }
+        // Fastest peers first, those without a measured latency last
+        peers.sort_by_key(|p| p.latency.unwrap_or(Duration::MAX));
+
+        let reorgs = if self.config.base_node.storage.track_reorgs {
+            let mut reorgs = self.blockchain_db.inner().fetch_all_reorgs()?;
+            reorgs.sort_by(|a, b| b.local_time.cmp(&a.local_time));
+            reorgs.truncate(usize::from(MAX_REORGS));
+            Some(reorgs)
+        } else {
+            None
+        };
+
+        Ok(DashboardSnapshot {
+            state,
+            bootstrapped,
+            tip_height,
+            tip_hash: metadata.best_block_hash().to_hex(),
+            tip_time,
+            liveness,
+            mempool,
+            num_wallets,
+            peers,
+            reorgs,
+        })
+    }
+}
+
+fn is_exit_event(event: Option<Result<Event, io::Error>>) -> bool {
+    match event {
+        Some(Ok(Event::Key(KeyEvent { code, modifiers, .. }))) => {
+            matches!(code, KeyCode::Char('q') | KeyCode::Esc) ||
+                (code == KeyCode::Char('c') && modifiers.contains(KeyModifiers::CONTROL))
+        },
+        Some(Ok(_)) => false,
+        // The terminal is gone
+        Some(Err(_)) | None => true,
+    }
+}
+
+fn draw_dashboard<B: Backend>(f: &mut Frame<B>, snapshot: &DashboardSnapshot) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+                Constraint::Length(6),
+                Constraint::Min(6),
+                Constraint::Length(MAX_REORGS + 3),
+                Constraint::Length(1),
+            ]
+            .as_ref(),
+        )
+        .split(f.size());
+    let top = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(65), Constraint::Percentage(35)].as_ref())
+        .split(chunks[0]);
+
+    draw_chain(f, top[0], snapshot);
+    draw_mempool(f, top[1], snapshot);
+    draw_peers(f, chunks[1], snapshot);
+    draw_reorgs(f, chunks[2], snapshot);
+
+    let help = Paragraph::new(Spans::from(vec![
+        Span::styled(
+            format!(" Minotari Base Node v{} ", consts::APP_VERSION_NUMBER),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Span::raw("  q/Esc: back to the shell"),
+    ]));
+    f.render_widget(help, chunks[3]);
+}
+
+fn field<'a>(name: &'a str, value: String) -> Spans<'a> {
+    Spans::from(vec![
+        Span::styled(format!("{:<12}", name), Style::default().fg(Color::Magenta)),
+        Span::raw(value),
+    ])
+}
+
+fn draw_chain<B: Backend>(f: &mut Frame<B>, area: Rect, snapshot: &DashboardSnapshot) {
+    let state_style = if snapshot.bootstrapped {
+        Style::default().fg(Color::Green)
+    } else {
+        Style::default().fg(Color::Yellow)
+    };
+    let text = vec![
+        Spans::from(vec![
+            Span::styled(format!("{:<12}", "State"), Style::default().fg(Color::Magenta)),
+            Span::styled(snapshot.state.clone(), state_style),
+        ]),
+        field("Tip height", snapshot.tip_height.to_string()),
+        field("Tip hash", snapshot.tip_hash.clone()),
+        field("Tip time", snapshot.tip_time.clone()),
+    ];
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(Span::styled("Chain", Style::default().add_modifier(Modifier::BOLD)));
+    f.render_widget(Paragraph::new(text).block(block), area);
+}
+
+fn draw_mempool<B: Backend>(f: &mut Frame<B>, area: Rect, snapshot: &DashboardSnapshot) {
+    let text = match &snapshot.mempool {
+        Some(stats) => vec![
+            field("Unconfirmed", format!("{} txs", stats.unconfirmed_txs)),
+            field("Weight", format!("{} g", stats.unconfirmed_weight)),
+            field("Reorg pool", format!("{} txs", stats.reorg_txs)),
+            field("Liveness", snapshot.liveness.clone()),
+        ],
+        None => vec![
+            Spans::from("Mempool query timed out"),
+            field("Liveness", snapshot.liveness.clone()),
+        ],
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(Span::styled("Mempool", Style::default().add_modifier(Modifier::BOLD)));
+    f.render_widget(Paragraph::new(text).block(block), area);
+}
+
+fn draw_peers<B: Backend>(f: &mut Frame<B>, area: Rect, snapshot: &DashboardSnapshot) {
+    let rows = snapshot.peers.iter().map(|peer| {
+        Row::new(vec![
+            peer.node_id.clone(),
+            peer.latency
+                .map(|l| format!("{:.0?}", l))
+                .unwrap_or_else(|| "-".to_string()),
+            peer.height.map(|h| h.to_string()).unwrap_or_else(|| "-".to_string()),
+            peer.direction.clone(),
+            format_duration_basic(peer.age),
+            peer.address.clone(),
+            peer.user_agent.clone(),
+        ])
+    });
+    let title = format!(
+        "Peers ({} nodes, {} wallets)",
+        snapshot.peers.len(),
+        snapshot.num_wallets
+    );
+    let table = Table::new(rows)
+        .header(
+            Row::new(vec![
+                "Node",
+                "Latency",
+                "Height",
+                "Direction",
+                "Age",
+                "Address",
+                "User agent",
+            ])
+            .style(Style::default().fg(Color::Magenta)),
+        )
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(Span::styled(title, Style::default().add_modifier(Modifier::BOLD))),
+        )
+        .widths(&[
+            Constraint::Length(14),
+            Constraint::Length(9),
+            Constraint::Length(8),
+            Constraint::Length(9),
+            Constraint::Length(10),
+            Constraint::Percentage(35),
+            Constraint::Percentage(25),
+        ]);
+    f.render_widget(table, area);
+}
+
+fn draw_reorgs<B: Backend>(f: &mut Frame<B>, area: Rect, snapshot: &DashboardSnapshot) {
+    let block = Block::default().borders(Borders::ALL).title(Span::styled(
+        "Recent reorgs",
+        Style::default().add_modifier(Modifier::BOLD),
+    ));
+    let reorgs = match &snapshot.reorgs {
+        Some(reorgs) if !reorgs.is_empty() => reorgs,
+        Some(_) => {
+            f.render_widget(Paragraph::new("No reorgs since tracking started").block(block), area);
+            return;
+        },
+        None => {
+            f.render_widget(
+                Paragraph::new(
+                    "Reorg tracking is off. Set `track_reorgs = true` in [base_node.storage] to turn it on.",
+                )
+                .block(block),
+                area,
+            );
+            return;
+        },
+    };
+    let rows = reorgs.iter().map(|reorg| {
+        Row::new(vec![
+            reorg.local_time.to_string(),
+            format!("#{} ({})", reorg.new_height, short_hash(&reorg.new_hash.to_hex())),
+            format!("#{} ({})", reorg.prev_height, short_hash(&reorg.prev_hash.to_hex())),
+            format!("{} added, {} removed", reorg.num_blocks_added, reorg.num_blocks_removed),
+        ])
+    });
+    let table = Table::new(rows)
+        .header(Row::new(vec!["Time", "New tip", "Previous tip", "Depth"]).style(Style::default().fg(Color::Magenta)))
+        .block(block)
+        .widths(&[
+            Constraint::Length(20),
+            Constraint::Length(28),
+            Constraint::Length(28),
+            Constraint::Min(20),
+        ]);
+    f.render_widget(table, area);
+}
+
+fn short_hash(hash: &str) -> &str {
+    hash.get(..16).unwrap_or(hash)
+}
@@ -0,0 +1,171 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::{fs::File, io::BufWriter, path::PathBuf};
+
+use anyhow::{anyhow, Error};
+use async_trait::async_trait;
+use clap::Parser;
+use tari_core::chain_storage::{
+    chain_data_export::{ChainDataFormat, ChainDataWriter},
+    MinedKernel,
+    MinedOutput,
+};
+
+use super::{CommandContext, HandleCommand};
+
+/// The number of blocks fetched from the database at a time
+const EXPORT_BATCH_SIZE: u64 = 100;
+
+/// Exports a range of blocks as newline-delimited canonical JSON or length-prefixed protobuf
+#[derive(Debug, Parser)]
+pub struct ArgsBlocks {
+    /// the first block height to export
+    start: u64,
+    /// the last block height to export (inclusive), defaults to the tip
+    end: Option<u64>,
+    /// the file to write the blocks to
+    #[clap(long, short)]
+    output_file: PathBuf,
+    /// the file format, `json` or `proto`
+    #[clap(long, short, default_value_t = ChainDataFormat::Json)]
+    format: ChainDataFormat,
+}
+
+/// Exports the kernels mined in a range of blocks as newline-delimited canonical JSON or length-prefixed protobuf
+#[derive(Debug, Parser)]
+pub struct ArgsKernels {
+    /// the first block height to export kernels from
+    start: u64,
+    /// the last block height to export kernels from (inclusive), defaults to the tip
+    end: Option<u64>,
+    /// the file to write the kernels to
+    #[clap(long, short)]
+    output_file: PathBuf,
+    /// the file format, `json` or `proto`
+    #[clap(long, short, default_value_t = ChainDataFormat::Json)]
+    format: ChainDataFormat,
+}
+
+/// Exports the unspent outputs at the tip as newline-delimited canonical JSON or length-prefixed protobuf
+#[derive(Debug, Parser)]
+pub struct ArgsUtxos {
+    /// the file to write the unspent outputs to
+    #[clap(long, short)]
+    output_file: PathBuf,
+    /// the file format, `json` or `proto`
+    #[clap(long, short, default_value_t = ChainDataFormat::Json)]
+    format: ChainDataFormat,
+}
+
+#[async_trait]
+impl HandleCommand<ArgsBlocks> for CommandContext {
+    async fn handle_command(&mut self, args: ArgsBlocks) -> Result<(), Error> {
+        let end = self.export_range_end(args.start, args.end).await?;
+        let mut writer = ChainDataWriter::new(BufWriter::new(File::create(&args.output_file)?), args.format);
+        for batch_start in (args.start..=end).step_by(usize::try_from(EXPORT_BATCH_SIZE)?) {
+            let batch_end = end.min(batch_start + EXPORT_BATCH_SIZE - 1);
+            let blocks = self.blockchain_db.fetch_blocks(batch_start..=batch_end, false).await?;
+            for block in blocks {
+                writer.write(block.into_block())?;
+            }
+        }
+        let num_records = writer.num_records();
+        writer.finish()?;
+        println!(
+            "Exported {} block(s) from #{} to #{} to {} ({})",
+            num_records,
+            args.start,
+            end,
+            args.output_file.display(),
+            args.format
+        );
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl HandleCommand<ArgsKernels> for CommandContext {
+    async fn handle_command(&mut self, args: ArgsKernels) -> Result<(), Error> {
+        let end = self.export_range_end(args.start, args.end).await?;
+        let mut writer = ChainDataWriter::new(BufWriter::new(File::create(&args.output_file)?), args.format);
+        for batch_start in (args.start..=end).step_by(usize::try_from(EXPORT_BATCH_SIZE)?) {
+            let batch_end = end.min(batch_start + EXPORT_BATCH_SIZE - 1);
+            let headers = self.blockchain_db.fetch_headers(batch_start..=batch_end).await?;
+            for header in headers {
+                let header_hash = header.hash();
+                for kernel in self.blockchain_db.fetch_kernels_in_block(header_hash).await? {
+                    writer.write(MinedKernel {
+                        mined_height: header.height,
+                        header_hash,
+                        kernel,
+                    })?;
+                }
+            }
+        }
+        let num_records = writer.num_records();
+        writer.finish()?;
+        println!(
+            "Exported {} kernel(s) from blocks #{} to #{} to {} ({})",
+            num_records,
+            args.start,
+            end,
+            args.output_file.display(),
+            args.format
+        );
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl HandleCommand<ArgsUtxos> for CommandContext {
+    async fn handle_command(&mut self, args: ArgsUtxos) -> Result<(), Error> {
+        let metadata = self.blockchain_db.get_chain_metadata().await?;
+        let tip_hash = *metadata.best_block_hash();
+        let mut writer = ChainDataWriter::new(BufWriter::new(File::create(&args.output_file)?), args.format);
+        for batch_start in (0..=metadata.best_block_height()).step_by(usize::try_from(EXPORT_BATCH_SIZE)?) {
+            let batch_end = metadata.best_block_height().min(batch_start + EXPORT_BATCH_SIZE - 1);
+            let headers = self.blockchain_db.fetch_headers(batch_start..=batch_end).await?;
+            for header in headers {
+                let header_hash = header.hash();
+                // The spend state is taken at the tip when the export started, so blocks added during the export do
+                // not change it
+                let outputs = self
+                    .blockchain_db
+                    .fetch_outputs_in_block_with_spend_state(header_hash, Some(tip_hash))
+                    .await?;
+                for (output, _) in outputs.into_iter().filter(|(_, spent)| !spent) {
+                    writer.write(MinedOutput {
+                        mined_height: header.height,
+                        header_hash,
+                        output,
+                    })?;
+                }
+            }
+        }
+        let num_records = writer.num_records();
+        writer.finish()?;
+        println!(
+            "Exported {} unspent output(s) at tip #{} to {} ({})",
+            num_records,
+            metadata.best_block_height(),
+            args.output_file.display(),
+            args.format
+        );
+        Ok(())
+    }
+}
+
+impl CommandContext {
+    async fn export_range_end(&self, start: u64, end: Option<u64>) -> Result<u64, Error> {
+        let tip = self.blockchain_db.get_chain_metadata().await?.best_block_height();
+        let end = end.unwrap_or(tip);
+        if end > tip {
+            return Err(anyhow!("End height #{} is beyond the tip #{}", end, tip));
+        }
+        if start > end {
+            return Err(anyhow!("Start height #{} is after end height #{}", start, end));
+        }
+        Ok(end)
+    }
+}
@@ -113,6 +113,12 @@ impl CommandContext {
             if let Some(dt) = peer.last_seen() {
                 println!("Last seen: {}", dt);
             }
+            if let Some(rtt) = peer_manager.latency_tracker().get_stats(&peer.node_id) {
+                println!("RTT: {}", rtt);
+            }
+            if let Some(sync_quality) = peer_manager.sync_quality_tracker().get_stats(&peer.node_id) {
+                println!("Sync quality: {}", sync_quality);
+            }
         }
         Ok(())
     }
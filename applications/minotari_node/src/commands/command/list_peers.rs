@@ -54,7 +54,8 @@ impl CommandContext {
                 _ => false,
             })
         }
-        let mut peers = self.comms.peer_manager().perform_query(query).await?;
+        let peer_manager = self.comms.peer_manager();
+        let mut peers = peer_manager.perform_query(query).await?;
         let num_peers = peers.len();
         println!();
         let mut table = Table::new();
@@ -99,6 +100,15 @@ impl CommandContext {
                     s.push(format!("last seen: {}", duration));
                 }
 
+                if let Some(rtt) = peer_manager.latency_tracker().get_stats(&peer.node_id) {
+                    s.push(format!(
+                        "rtt: {:.0?} (p90 <={:.0?}){}",
+                        rtt.avg,
+                        rtt.p90,
+                        if rtt.is_persistently_slow() { " SLOW" } else { "" }
+                    ));
+                }
+
                 if s.is_empty() {
                     "--".to_string()
                 } else {
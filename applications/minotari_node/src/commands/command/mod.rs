@@ -21,13 +21,16 @@
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 mod add_peer;
+mod ban_list;
 mod ban_peer;
 mod block_timing;
 mod check_db;
 mod check_for_updates;
 mod create_tls_certs;
+mod dashboard;
 mod dial_peer;
 mod discover_peer;
+mod export_chain_data;
 mod get_block;
 mod get_chain_metadata;
 mod get_db_stats;
@@ -45,12 +48,17 @@ mod list_reorgs;
 mod list_validator_nodes;
 mod period_stats;
 mod ping_peer;
+mod probe_broadcast;
 mod quit;
+mod reindex;
 mod reset_offline_peers;
+mod restore_backup;
 mod rewind_blockchain;
 mod search_kernel;
 mod search_utxo;
+mod simulate_fee_market;
 mod status;
+mod tor;
 mod unban_all_peers;
 mod version;
 mod watch_command;
@@ -72,7 +80,7 @@ use tari_comms::{
     CommsNode,
     NodeIdentity,
 };
-use tari_comms_dht::{DhtDiscoveryRequester, MetricsCollectorHandle};
+use tari_comms_dht::{BroadcastProbeRequester, DhtDiscoveryRequester, DhtRequester, MetricsCollectorHandle};
 use tari_core::{
     base_node::{state_machine_service::states::StatusInfo, LocalNodeCommsInterface},
     blocks::ChainHeader,
@@ -103,6 +111,7 @@ pub enum Command {
     Version(version::Args),
     CheckForUpdates(check_for_updates::Args),
     Status(status::Args),
+    Dashboard(dashboard::Args),
     GetChainMetadata(get_chain_metadata::Args),
     GetDbStats(get_db_stats::Args),
     GetPeer(get_peer::Args),
@@ -111,20 +120,30 @@ pub enum Command {
     PingPeer(ping_peer::Args),
     ResetOfflinePeers(reset_offline_peers::Args),
     RewindBlockchain(rewind_blockchain::Args),
+    Reindex(reindex::Args),
+    RestoreBackup(restore_backup::Args),
     AddPeer(add_peer::ArgsAddPeer),
     BanPeer(ban_peer::ArgsBan),
     UnbanPeer(ban_peer::ArgsUnban),
     UnbanAllPeers(unban_all_peers::Args),
     ListBannedPeers(list_banned_peers::Args),
+    ExportBanList(ban_list::ArgsExport),
+    ImportBanList(ban_list::ArgsImport),
     ListConnections(list_connections::Args),
     ListHeaders(list_headers::Args),
     CheckDb(check_db::Args),
     PeriodStats(period_stats::Args),
+    SimulateFeeMarket(simulate_fee_market::Args),
     HeaderStats(header_stats::Args),
     BlockTiming(block_timing::Args),
     ListReorgs(list_reorgs::Args),
     DiscoverPeer(discover_peer::Args),
+    #[clap(hide = true)]
+    ProbeBroadcast(probe_broadcast::Args),
     GetBlock(get_block::Args),
+    ExportBlocks(export_chain_data::ArgsBlocks),
+    ExportKernels(export_chain_data::ArgsKernels),
+    ExportUtxos(export_chain_data::ArgsUtxos),
     SearchUtxo(search_utxo::Args),
     SearchKernel(search_kernel::Args),
     GetMempoolStats(get_mempool_stats::Args),
@@ -135,14 +154,23 @@ pub enum Command {
     GetNetworkStats(get_network_stats::Args),
     ListValidatorNodes(list_validator_nodes::Args),
     CreateTlsCerts(create_tls_certs::Args),
+    TorStatus(tor::ArgsStatus),
+    RotateTorIdentity(tor::ArgsRotate),
     Quit(quit::Args),
     Exit(quit::Args),
     Watch(watch_command::Args),
 }
 
+/// Diagnostic commands that are left out of the help and completions
+const HIDDEN_COMMANDS: [&str; 1] = ["probe-broadcast"];
+
 impl Command {
     pub fn variants() -> Vec<String> {
-        Command::VARIANTS.iter().map(|s| s.to_string()).collect()
+        Command::VARIANTS
+            .iter()
+            .filter(|s| !HIDDEN_COMMANDS.contains(*s))
+            .map(|s| s.to_string())
+            .collect()
     }
 }
 
@@ -156,7 +184,9 @@ pub struct CommandContext {
     consensus_rules: ConsensusManager,
     blockchain_db: AsyncBlockchainDb<LMDBDatabase>,
     discovery_service: DhtDiscoveryRequester,
+    dht_requester: DhtRequester,
     dht_metrics_collector: MetricsCollectorHandle,
+    broadcast_probe: BroadcastProbeRequester,
     rpc_server: RpcServerHandle,
     base_node_identity: Arc<NodeIdentity>,
     comms: CommsNode,
@@ -176,7 +206,9 @@ impl CommandContext {
             consensus_rules: ctx.consensus_rules().clone(),
             blockchain_db: ctx.blockchain_db().into(),
             discovery_service: ctx.base_node_dht().discovery_service_requester(),
+            dht_requester: ctx.base_node_dht().dht_requester(),
             dht_metrics_collector: ctx.base_node_dht().metrics_collector(),
+            broadcast_probe: ctx.base_node_dht().broadcast_probe_requester(),
             rpc_server: ctx.rpc_server(),
             base_node_identity: ctx.base_node_identity(),
             comms: ctx.base_node_comms().clone(),
@@ -194,6 +226,14 @@ impl CommandContext {
         let args: Args = line.parse()?;
         if let Command::Watch(command) = args.command {
             Ok(Some(command))
+        } else if let Command::Dashboard(dashboard) = args.command {
+            // The dashboard runs until it is closed, so it is not subject to a timeout
+            self.handle_command(dashboard).await?;
+            Ok(None)
+        } else if let Command::Reindex(reindex) = args.command {
+            // A reindex re-applies the whole chain and reports its progress, so it is not subject to a timeout
+            self.handle_command(reindex).await?;
+            Ok(None)
         } else {
             let time_out = match args.command {
                 // These commands should complete quickly, some of them like 'discover-peer' returns immediately
@@ -210,8 +250,11 @@ impl CommandContext {
                 Command::DialPeer(_) |
                 Command::PingPeer(_) |
                 Command::DiscoverPeer(_) |
+                Command::ProbeBroadcast(_) |
                 Command::ListPeers(_) |
                 Command::ListBannedPeers(_) |
+                Command::ExportBanList(_) |
+                Command::ImportBanList(_) |
                 Command::ListConnections(_) |
                 Command::GetNetworkStats(_) |
                 Command::BlockTiming(_) |
@@ -228,13 +271,23 @@ impl CommandContext {
                 Command::GetMempoolState(_) |
                 Command::GetMempoolTx(_) |
                 Command::Status(_) |
+                Command::Dashboard(_) |
                 Command::Watch(_) |
                 Command::ListValidatorNodes(_) |
                 Command::CreateTlsCerts(_) |
+                Command::TorStatus(_) |
+                Command::RotateTorIdentity(_) |
+                Command::RestoreBackup(_) |
                 Command::Quit(_) |
                 Command::Exit(_) => 30,
                 // These commands involve intense blockchain db operations and needs a lot of time to complete
-                Command::CheckDb(_) | Command::PeriodStats(_) | Command::RewindBlockchain(_) => 600,
+                Command::CheckDb(_) |
+                Command::PeriodStats(_) |
+                Command::SimulateFeeMarket(_) |
+                Command::ExportBlocks(_) |
+                Command::ExportKernels(_) |
+                Command::ExportUtxos(_) |
+                Command::RewindBlockchain(_) => 600,
             };
             let fut = self.handle_command(args.command);
             if let Err(e) = time::timeout(Duration::from_secs(time_out), fut).await? {
@@ -263,6 +316,7 @@ impl HandleCommand<Command> for CommandContext {
             Command::Version(args) => self.handle_command(args).await,
             Command::CheckForUpdates(args) => self.handle_command(args).await,
             Command::Status(args) => self.handle_command(args).await,
+            Command::Dashboard(args) => self.handle_command(args).await,
             Command::GetChainMetadata(args) => self.handle_command(args).await,
             Command::GetDbStats(args) => self.handle_command(args).await,
             Command::GetPeer(args) => self.handle_command(args).await,
@@ -276,15 +330,22 @@ impl HandleCommand<Command> for CommandContext {
             Command::UnbanPeer(args) => self.handle_command(args).await,
             Command::ResetOfflinePeers(args) => self.handle_command(args).await,
             Command::RewindBlockchain(args) => self.handle_command(args).await,
+            Command::Reindex(args) => self.handle_command(args).await,
+            Command::RestoreBackup(args) => self.handle_command(args).await,
             Command::UnbanAllPeers(args) => self.handle_command(args).await,
             Command::ListHeaders(args) => self.handle_command(args).await,
             Command::CheckDb(args) => self.handle_command(args).await,
             Command::PeriodStats(args) => self.handle_command(args).await,
+            Command::SimulateFeeMarket(args) => self.handle_command(args).await,
             Command::HeaderStats(args) => self.handle_command(args).await,
             Command::BlockTiming(args) => self.handle_command(args).await,
             Command::ListReorgs(args) => self.handle_command(args).await,
             Command::DiscoverPeer(args) => self.handle_command(args).await,
+            Command::ProbeBroadcast(args) => self.handle_command(args).await,
             Command::GetBlock(args) => self.handle_command(args).await,
+            Command::ExportBlocks(args) => self.handle_command(args).await,
+            Command::ExportKernels(args) => self.handle_command(args).await,
+            Command::ExportUtxos(args) => self.handle_command(args).await,
             Command::SearchUtxo(args) => self.handle_command(args).await,
             Command::SearchKernel(args) => self.handle_command(args).await,
             Command::ListConnections(args) => self.handle_command(args).await,
@@ -293,10 +354,14 @@ impl HandleCommand<Command> for CommandContext {
             Command::GetMempoolTx(args) => self.handle_command(args).await,
             Command::Whoami(args) => self.handle_command(args).await,
             Command::ListBannedPeers(args) => self.handle_command(args).await,
+            Command::ExportBanList(args) => self.handle_command(args).await,
+            Command::ImportBanList(args) => self.handle_command(args).await,
             Command::Quit(args) | Command::Exit(args) => self.handle_command(args).await,
             Command::Watch(args) => self.handle_command(args).await,
             Command::ListValidatorNodes(args) => self.handle_command(args).await,
             Command::CreateTlsCerts(args) => self.handle_command(args).await,
+            Command::TorStatus(args) => self.handle_command(args).await,
+            Command::RotateTorIdentity(args) => self.handle_command(args).await,
         }
     }
 }
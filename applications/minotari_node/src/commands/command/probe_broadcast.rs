@@ -0,0 +1,46 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::time::Duration;
+
+use anyhow::Error;
+use async_trait::async_trait;
+use clap::Parser;
+use tokio::task;
+
+use super::{CommandContext, HandleCommand};
+
+/// Propagate a probe through the network and report how many nodes it reached, in how many hops and how quickly.
+/// Only nodes with `respond_to_broadcast_probes` enabled propagate probes and send receipts.
+#[derive(Debug, Parser)]
+pub struct Args {
+    /// The number of seconds to wait for receipts
+    #[clap(default_value_t = 30)]
+    wait: u64,
+}
+
+#[async_trait]
+impl HandleCommand<Args> for CommandContext {
+    async fn handle_command(&mut self, args: Args) -> Result<(), Error> {
+        self.probe_broadcast(Duration::from_secs(args.wait))
+    }
+}
+
+impl CommandContext {
+    /// Function to process the probe-broadcast command
+    pub fn probe_broadcast(&mut self, wait: Duration) -> Result<(), Error> {
+        let mut broadcast_probe = self.broadcast_probe.clone();
+        task::spawn(async move {
+            println!("📡 Broadcast probe sent, waiting {:.0?} for receipts.", wait);
+            match broadcast_probe.probe(wait).await {
+                Ok(report) => {
+                    println!("{}", report);
+                },
+                Err(err) => {
+                    println!("☠️ {}", err);
+                },
+            }
+        });
+        Ok(())
+    }
+}
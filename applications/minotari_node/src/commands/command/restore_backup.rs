@@ -0,0 +1,61 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::path::PathBuf;
+
+use anyhow::Error;
+use async_trait::async_trait;
+use clap::Parser;
+use minotari_app_utilities::backup::restore_snapshot;
+
+use super::{CommandContext, HandleCommand};
+
+/// Decrypts a backup snapshot and writes its files to a directory. The snapshot is decrypted with the configured
+/// backup passphrase and its integrity is verified before anything is written. Existing files are never overwritten.
+#[derive(Debug, Parser)]
+pub struct Args {
+    /// The path of an archive file, or the name of an archive in the configured backup store
+    archive: String,
+    /// The directory to write the restored files to
+    output_dir: PathBuf,
+}
+
+#[async_trait]
+impl HandleCommand<Args> for CommandContext {
+    async fn handle_command(&mut self, args: Args) -> Result<(), Error> {
+        self.restore_backup(&args.archive, args.output_dir).await
+    }
+}
+
+impl CommandContext {
+    pub async fn restore_backup(&self, archive: &str, output_dir: PathBuf) -> Result<(), Error> {
+        let files = restore_snapshot(&self.config.base_node.backup, archive, &output_dir).await?;
+        for file in &files {
+            println!("Restored {}", file.display());
+        }
+        println!(
+            "Restored {} file(s). Stop the node and move them into place to use them.",
+            files.len()
+        );
+        Ok(())
+    }
+}
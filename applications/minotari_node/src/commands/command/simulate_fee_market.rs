@@ -0,0 +1,167 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::num::NonZeroU64;
+
+use anyhow::{anyhow, Error};
+use async_trait::async_trait;
+use clap::Parser;
+use tari_core::{
+    mempool::fee_market_simulator::{split_block_body, FeeMarketParams, FeeMarketReport, FeeMarketSimulator},
+    transactions::{tari_amount::MicroMinotari, weight::TransactionWeight},
+};
+
+use super::{CommandContext, HandleCommand};
+
+/// The number of blocks fetched from the database at a time
+const BLOCK_PAGE_SIZE: u64 = 100;
+
+/// Replays the transactions mined in a range of blocks through the block template builder under alternative block
+/// weight and fee parameters, and compares the fee revenue and confirmation latencies with the actual chain. Each
+/// transaction arrives at the height it was mined at; blocks do not record which inputs and outputs belong to which
+/// kernel, so they are dealt out evenly to the kernels of a block.
+#[derive(Debug, Parser)]
+pub struct Args {
+    /// The first height to replay
+    start_height: u64,
+    /// The last height to replay
+    end_height: u64,
+    /// The maximum weight of the transactions in a block, excluding the coinbase. Defaults to the consensus value.
+    #[clap(long)]
+    max_block_weight: Option<u64>,
+    /// Transactions paying less than this many µT per gram are rejected
+    #[clap(long, default_value_t)]
+    min_fee_per_gram: u64,
+    /// Weight in grams per kernel
+    #[clap(long)]
+    kernel_weight: Option<u64>,
+    /// Weight in grams per input
+    #[clap(long)]
+    input_weight: Option<u64>,
+    /// Weight in grams per output, excluding its script and features
+    #[clap(long)]
+    output_weight: Option<u64>,
+    /// Bytes of output scripts and features per gram
+    #[clap(long)]
+    features_and_scripts_bytes_per_gram: Option<NonZeroU64>,
+    /// Empty blocks simulated after the last height so that pending transactions can be confirmed
+    #[clap(long, default_value_t)]
+    drain_blocks: u64,
+}
+
+#[async_trait]
+impl HandleCommand<Args> for CommandContext {
+    async fn handle_command(&mut self, args: Args) -> Result<(), Error> {
+        self.simulate_fee_market(args).await
+    }
+}
+
+impl CommandContext {
+    pub async fn simulate_fee_market(&mut self, args: Args) -> Result<(), Error> {
+        if args.start_height > args.end_height {
+            return Err(anyhow!("The start height is greater than the end height"));
+        }
+        let tip_height = self.node_service.get_metadata().await?.best_block_height();
+        if args.end_height > tip_height {
+            return Err(anyhow!("The end height is above the tip height {}", tip_height));
+        }
+
+        let constants = self.consensus_rules.consensus_constants(args.end_height);
+        let consensus_weight = *constants.transaction_weight_params();
+        let mut weight_params = *consensus_weight.params();
+        if let Some(weight) = args.kernel_weight {
+            weight_params.kernel_weight = weight;
+        }
+        if let Some(weight) = args.input_weight {
+            weight_params.input_weight = weight;
+        }
+        if let Some(weight) = args.output_weight {
+            weight_params.output_weight = weight;
+        }
+        if let Some(bytes_per_gram) = args.features_and_scripts_bytes_per_gram {
+            weight_params.features_and_scripts_bytes_per_gram = bytes_per_gram;
+        }
+        let consensus_max_weight = constants.max_block_weight_excluding_coinbase()?;
+        let params = FeeMarketParams {
+            max_transaction_weight: args.max_block_weight.unwrap_or(consensus_max_weight),
+            transaction_weight: TransactionWeight::with_version(consensus_weight.version(), weight_params),
+            min_fee_per_gram: args.min_fee_per_gram,
+        };
+
+        let mut simulator = FeeMarketSimulator::new(params);
+        let mut actual = FeeMarketReport::default();
+        let mut page_start = args.start_height;
+        while page_start <= args.end_height {
+            let page_end = (page_start + BLOCK_PAGE_SIZE - 1).min(args.end_height);
+            for block in self.node_service.get_blocks(page_start..=page_end, true).await? {
+                let height = block.header().height;
+                let transactions = split_block_body(&block.block().body);
+                actual.blocks += 1;
+                for transaction in &transactions {
+                    actual.transactions_included += 1;
+                    actual.total_fees += transaction.body.get_total_fee()?;
+                    actual.total_weight += transaction.calculate_weight(&consensus_weight)?;
+                    actual.latencies.push(0);
+                }
+                simulator.add_transactions(height, transactions)?;
+                simulator.mine_block(height)?;
+            }
+            page_start = page_end + 1;
+        }
+        for height in (args.end_height + 1)..=(args.end_height + args.drain_blocks) {
+            simulator.mine_block(height)?;
+        }
+        let simulated = simulator.report();
+
+        println!(
+            "Replayed {} block(s) from height {} to {} with a maximum transaction weight of {} per block",
+            actual.blocks, args.start_height, args.end_height, params.max_transaction_weight
+        );
+        println!();
+        println!("{:<28} {:>20} {:>20}", "", "Actual", "Simulated");
+        print_row("Blocks", actual.blocks, simulated.blocks);
+        print_row(
+            "Transactions included",
+            actual.transactions_included,
+            simulated.transactions_included,
+        );
+        print_row("Transactions rejected", 0, simulated.transactions_rejected);
+        print_row("Transactions pending", 0, simulated.transactions_pending);
+        print_row("Fee revenue", actual.total_fees, simulated.total_fees);
+        print_row("Mean fee per block", mean_fee(&actual), mean_fee(&simulated));
+        print_row(
+            "Mean block fullness",
+            format!("{:.1}%", actual.mean_fullness(consensus_max_weight) * 100.0),
+            format!("{:.1}%", simulated.mean_fullness(params.max_transaction_weight) * 100.0),
+        );
+        print_row(
+            "Mean latency (blocks)",
+            format!("{:.2}", actual.mean_latency()),
+            format!("{:.2}", simulated.mean_latency()),
+        );
+        for percentile in [50, 90, 99] {
+            print_row(
+                &format!("P{} latency (blocks)", percentile),
+                actual.latency_percentile(percentile),
+                simulated.latency_percentile(percentile),
+            );
+        }
+        print_row(
+            "Max latency (blocks)",
+            actual.latency_percentile(100),
+            simulated.latency_percentile(100),
+        );
+        Ok(())
+    }
+}
+
+fn mean_fee(report: &FeeMarketReport) -> MicroMinotari {
+    if report.blocks == 0 {
+        return MicroMinotari::zero();
+    }
+    report.total_fees / report.blocks
+}
+
+fn print_row<A: ToString, B: ToString>(name: &str, actual: A, simulated: B) {
+    println!("{:<28} {:>20} {:>20}", name, actual.to_string(), simulated.to_string());
+}
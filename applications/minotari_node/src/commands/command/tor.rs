@@ -0,0 +1,81 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use anyhow::{anyhow, Error};
+use async_trait::async_trait;
+use clap::Parser;
+use minotari_app_utilities::identity_management::save_as_json;
+use tari_comms::{tor::HiddenServiceControllerHandle, transports::predicate::is_onion_address};
+
+use super::{CommandContext, HandleCommand};
+
+/// Displays the health of the Tor control port connection and hidden service
+#[derive(Debug, Parser)]
+pub struct ArgsStatus {}
+
+#[async_trait]
+impl HandleCommand<ArgsStatus> for CommandContext {
+    async fn handle_command(&mut self, _: ArgsStatus) -> Result<(), Error> {
+        let status = self.hidden_service()?.get_health_status().await?;
+        print!("{}", status);
+        Ok(())
+    }
+}
+
+/// Replaces the onion service key with a newly generated one and announces the new address to the network
+#[derive(Debug, Parser)]
+pub struct ArgsRotate {}
+
+#[async_trait]
+impl HandleCommand<ArgsRotate> for CommandContext {
+    async fn handle_command(&mut self, _: ArgsRotate) -> Result<(), Error> {
+        let tor_identity = self.hidden_service()?.rotate_identity().await?;
+        let onion_address = tor_identity.try_get_onion_address()?;
+
+        // Replace the previous onion address, keeping any other (e.g. dual-stack TCP) addresses
+        let node_identity = &self.base_node_identity;
+        let addresses = node_identity
+            .public_addresses()
+            .into_iter()
+            .filter(|addr| !is_onion_address(addr))
+            .chain(Some(onion_address.clone()))
+            .collect();
+        node_identity.set_public_addresses(addresses);
+
+        let base_node_config = &self.config.base_node;
+        save_as_json(&base_node_config.tor_identity_file, &tor_identity)?;
+        save_as_json(&base_node_config.identity_file, &**node_identity)?;
+
+        // Publish the updated peer identity
+        self.dht_requester.send_join().await?;
+        println!("Onion service key rotated. New address: {}", onion_address);
+        Ok(())
+    }
+}
+
+impl CommandContext {
+    fn hidden_service(&self) -> Result<HiddenServiceControllerHandle, Error> {
+        self.comms
+            .hidden_service()
+            .ok_or_else(|| anyhow!("This node is not using the Tor transport"))
+    }
+}
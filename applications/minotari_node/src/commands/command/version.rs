@@ -52,6 +52,9 @@ impl CommandContext {
                 update.download_url(),
                 update.to_hash_hex()
             );
+            if let Some(path) = update.staged_path() {
+                println!("The verified download is staged at {}", path.display());
+            }
         }
         Ok(())
     }
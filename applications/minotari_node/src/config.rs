@@ -33,19 +33,36 @@ use tari_common::{
     DefaultConfigLoader,
     SubConfigPath,
 };
-use tari_common_types::grpc_authentication::GrpcAuthentication;
+use tari_common_types::{
+    backup_config::{remote_store_urls, BackupConfig},
+    grpc_authentication::GrpcAuthentication,
+};
 use tari_comms::multiaddr::Multiaddr;
 use tari_core::{
-    base_node::BaseNodeStateMachineConfig,
+    base_node::{network_monitor::NetworkMonitorConfig, BaseNodeStateMachineConfig},
     chain_storage::BlockchainDatabaseConfig,
     mempool::MempoolConfig,
 };
-use tari_p2p::{auto_update::AutoUpdateConfig, P2pConfig, PeerSeedsConfig};
+use tari_p2p::{
+    auto_update::AutoUpdateConfig,
+    privacy::{apply_privacy_profile, check_privacy_profile, is_loopback_address, is_loopback_url, PrivacyLeakError},
+    services::gossip::GossipConfig,
+    P2pConfig,
+    PeerSeedsConfig,
+};
 use tari_storage::lmdb_store::LMDBConfig;
+use tari_telemetry::TelemetryConfig;
 
-use crate::grpc_method::GrpcMethod;
+#[cfg(feature = "explorer")]
+use crate::explorer::ExplorerConfig;
 #[cfg(feature = "metrics")]
 use crate::metrics::MetricsConfig;
+use crate::{
+    grpc::public_server::PublicGrpcConfig,
+    grpc_method::GrpcMethod,
+    light_wallet_push::LightWalletPushConfig,
+    status_beacon::StatusBeaconConfig,
+};
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ApplicationConfig {
@@ -55,6 +72,9 @@ pub struct ApplicationConfig {
     pub peer_seeds: PeerSeedsConfig,
     #[cfg(feature = "metrics")]
     pub metrics: MetricsConfig,
+    pub telemetry: TelemetryConfig,
+    #[cfg(feature = "explorer")]
+    pub explorer: ExplorerConfig,
 }
 
 impl ApplicationConfig {
@@ -66,6 +86,9 @@ impl ApplicationConfig {
             base_node: BaseNodeConfig::load_from(cfg)?,
             #[cfg(feature = "metrics")]
             metrics: MetricsConfig::load_from(cfg)?,
+            telemetry: TelemetryConfig::load_from(cfg)?,
+            #[cfg(feature = "explorer")]
+            explorer: ExplorerConfig::load_from(cfg)?,
         };
 
         config.base_node.set_base_path(config.common.base_path());
@@ -75,8 +98,79 @@ impl ApplicationConfig {
     pub fn network(&self) -> Network {
         self.base_node.network
     }
+
+    /// Restricts the node to the Tor hidden service transport, disables update checks and trace context propagation,
+    /// only accepts light wallet push relays on a loopback address and delays transaction propagation by a random
+    /// duration. Returns the settings that would still reveal the public IP address.
+    pub fn apply_privacy_profile(&mut self) -> Result<(), PrivacyLeakError> {
+        apply_privacy_profile(&mut self.base_node.p2p, &mut self.peer_seeds);
+        // Update checks query DNS and download over clearnet
+        self.auto_update.check_interval = None;
+        if self.base_node.mempool.service.propagation_delay_max.is_none() {
+            self.base_node.mempool.service.propagation_delay_max = Some(PRIVACY_PROPAGATION_DELAY_MAX);
+        }
+        self.telemetry.propagate_trace_context = false;
+        // Push relays are registered by light wallets at runtime and posted to directly, not through Tor
+        self.base_node.light_wallet_push.loopback_relays_only = true;
+
+        let mut leaks = match check_privacy_profile(&self.base_node.p2p, &self.peer_seeds) {
+            Ok(()) => Vec::new(),
+            Err(PrivacyLeakError(leaks)) => leaks,
+        };
+        if let Some(address) = self.base_node.grpc_address.as_ref().filter(|a| !is_loopback_address(a)) {
+            leaks.push(format!("the gRPC server is bound to {}", address));
+        }
+        if let Some(address) = self
+            .base_node
+            .status_beacon
+            .http_bind_address
+            .filter(|a| self.base_node.status_beacon.enabled && !a.ip().is_loopback())
+        {
+            leaks.push(format!("the status beacon server is bound to {}", address));
+        }
+        // Spans are exported directly, not through Tor
+        if let Some(endpoint) = self
+            .telemetry
+            .otlp_endpoint
+            .as_ref()
+            .filter(|url| !is_loopback_url(url))
+        {
+            leaks.push(format!("spans are exported to {}", endpoint));
+        }
+        // Backups are uploaded directly, not through Tor
+        let backup = &self.base_node.backup;
+        if backup.enabled {
+            for url in remote_store_urls(backup.s3.as_ref(), backup.webdav.as_ref()).filter(|url| !is_loopback_url(url))
+            {
+                leaks.push(format!("backups are written to {}", url));
+            }
+        }
+        #[cfg(feature = "explorer")]
+        {
+            if let Some(address) = self.explorer.server_bind_address.filter(|a| !a.ip().is_loopback()) {
+                leaks.push(format!("the explorer server is bound to {}", address));
+            }
+        }
+        #[cfg(feature = "metrics")]
+        {
+            if let Some(address) = self.metrics.server_bind_address.filter(|a| !a.ip().is_loopback()) {
+                leaks.push(format!("the metrics server is bound to {}", address));
+            }
+            if let Some(endpoint) = &self.metrics.push_endpoint {
+                leaks.push(format!("metrics are pushed to {}", endpoint));
+            }
+        }
+        if leaks.is_empty() {
+            Ok(())
+        } else {
+            Err(PrivacyLeakError(leaks))
+        }
+    }
 }
 
+/// The propagation delay used by the privacy profile if none is configured
+const PRIVACY_PROPAGATION_DELAY_MAX: Duration = Duration::from_secs(10);
+
 #[derive(Clone, Serialize, Deserialize, Debug)]
 #[serde(deny_unknown_fields)]
 #[allow(clippy::struct_excessive_bools)]
@@ -94,10 +188,18 @@ pub struct BaseNodeConfig {
     pub grpc_authentication: GrpcAuthentication,
     /// GRPC tls enabled
     pub grpc_tls_enabled: bool,
+    /// The public server profile for the GRPC server, with per-IP rate limits and only read-only methods
+    pub grpc_public_server: PublicGrpcConfig,
     /// Enable mining on the base node, overriding other settings regarding mining
     pub mining_enabled: bool,
     /// Enable second layer specific grpc methods.
     pub second_layer_grpc_enabled: bool,
+    /// Enable the light wallet server grpc methods (block filters, compact outputs and targeted output fetches).
+    /// Should be combined with `grpc_authentication` when the GRPC server is reachable by untrusted clients.
+    pub light_wallet_server_enabled: bool,
+    /// Regtest mode, only permitted on localnet. Enables the `GenerateBlocks` grpc method, which mines blocks on
+    /// demand with trivial difficulty and fixed timestamps.
+    pub regtest_enabled: bool,
     /// A path to the file that stores the base node identity and secret key
     pub identity_file: PathBuf,
     /// Spin up and use a built-in Tor instance. This only works on macos/linux - requires that the wallet was built
@@ -130,6 +232,10 @@ pub struct BaseNodeConfig {
     pub storage: BlockchainDatabaseConfig,
     /// The mempool config settings
     pub mempool: MempoolConfig,
+    /// The gossip config settings, used to propagate blocks and transactions over topic meshes
+    pub gossip: GossipConfig,
+    /// The network partition and eclipse monitor config settings
+    pub network_monitor: NetworkMonitorConfig,
     /// The time interval between status line updates in the CLI
     #[serde(with = "serializers::seconds")]
     pub status_line_interval: Duration,
@@ -142,6 +248,17 @@ pub struct BaseNodeConfig {
     pub state_machine: BaseNodeStateMachineConfig,
     /// Obscure GRPC error responses
     pub report_grpc_error: bool,
+    /// The periodic encrypted backup settings
+    pub backup: BackupConfig,
+    /// The signed network status beacon settings
+    pub status_beacon: StatusBeaconConfig,
+    /// The light wallet push watch settings
+    pub light_wallet_push: LightWalletPushConfig,
+    /// How often to check the configuration file for changes. Changes to the mempool limits, the peer limits and the
+    /// metrics settings are applied while running, other changes are logged as requiring a restart. `None` or zero
+    /// disables the check.
+    #[serde(with = "serializers::optional_seconds")]
+    pub config_reload_interval: Option<Duration>,
 }
 
 impl Default for BaseNodeConfig {
@@ -158,8 +275,11 @@ impl Default for BaseNodeConfig {
             grpc_server_allow_methods: vec![GrpcMethod::GetVersion].into(),
             grpc_authentication: GrpcAuthentication::default(),
             grpc_tls_enabled: false,
+            grpc_public_server: PublicGrpcConfig::default(),
             mining_enabled: false,
             second_layer_grpc_enabled: false,
+            light_wallet_server_enabled: false,
+            regtest_enabled: false,
             identity_file: PathBuf::from("config/base_node_id.json"),
             use_libtor: true,
             tor_identity_file: PathBuf::from("config/base_node_tor_id.json"),
@@ -175,11 +295,17 @@ impl Default for BaseNodeConfig {
             messaging_request_timeout: Duration::from_secs(60),
             storage: Default::default(),
             mempool: Default::default(),
+            gossip: Default::default(),
+            network_monitor: Default::default(),
             status_line_interval: Duration::from_secs(5),
             buffer_size: 1_500,
             metadata_auto_ping_interval: Duration::from_secs(30),
             state_machine: Default::default(),
             report_grpc_error: false,
+            backup: BackupConfig::default(),
+            status_beacon: StatusBeaconConfig::default(),
+            light_wallet_push: LightWalletPushConfig::default(),
+            config_reload_interval: Some(Duration::from_secs(10)),
         }
     }
 }
@@ -207,6 +333,7 @@ impl BaseNodeConfig {
         if !self.lmdb_path.is_absolute() {
             self.lmdb_path = self.data_dir.join(self.lmdb_path.as_path());
         }
+        self.backup.set_base_path(&self.data_dir);
         self.p2p.set_base_path(base_path);
     }
 }
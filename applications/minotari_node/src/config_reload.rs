@@ -0,0 +1,254 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Applies changes to the configuration file while the node is running.
+//!
+//! The configuration file is checked for changes every `config_reload_interval`. When it has changed, it is loaded
+//! again with the same command-line overrides and compared with the previous version. The new values of reloadable
+//! settings are sent as [ConfigUpdate]s to the services that apply them, and any other changed keys are logged as
+//! requiring a restart. Log levels are not part of the configuration file; log4rs reloads its own configuration.
+
+use std::time::Duration;
+
+use anyhow::anyhow;
+use log::*;
+use tari_common::configuration::{
+    reload::{changed_keys, ConfigFileWatcher, ReloadReport},
+    utils::load_configuration_with_overrides,
+    ConfigOverrideProvider,
+    Network,
+};
+use tari_comms::connectivity::ConnectivityRequester;
+use tari_core::mempool::{Mempool, UnconfirmedPoolConfig};
+use tari_shutdown::ShutdownSignal;
+use tokio::{
+    sync::broadcast,
+    task,
+    time::{self, MissedTickBehavior},
+};
+
+#[cfg(feature = "metrics")]
+use crate::metrics::MetricsConfig;
+use crate::{builder::BaseNodeContext, cli::Cli, config::ApplicationConfig};
+
+const LOG_TARGET: &str = "minotari::base_node::config_reload";
+
+/// The configuration keys whose changes are applied without a restart
+const RELOADABLE_KEYS: &[&str] = &[
+    "base_node.mempool.unconfirmed_pool",
+    "base_node.p2p.max_inbound_connections_per_subnet",
+    #[cfg(feature = "metrics")]
+    "metrics",
+];
+
+/// A change to a reloadable setting
+#[derive(Debug, Clone)]
+pub enum ConfigUpdate {
+    UnconfirmedPool(UnconfirmedPoolConfig),
+    MaxInboundConnectionsPerSubnet(Option<usize>),
+    #[cfg(feature = "metrics")]
+    Metrics(MetricsConfig),
+}
+
+impl ConfigUpdate {
+    /// The updates for the settings in `report.applied`, taking their values from `config`
+    fn from_report(report: &ReloadReport, config: &ApplicationConfig) -> Vec<Self> {
+        let mut updates = Vec::new();
+        if report.is_applied("base_node.mempool.unconfirmed_pool") {
+            updates.push(Self::UnconfirmedPool(config.base_node.mempool.unconfirmed_pool.clone()));
+        }
+        if report.is_applied("base_node.p2p.max_inbound_connections_per_subnet") {
+            updates.push(Self::MaxInboundConnectionsPerSubnet(
+                config.base_node.p2p.max_inbound_connections_per_subnet,
+            ));
+        }
+        #[cfg(feature = "metrics")]
+        if report.is_applied("metrics") {
+            updates.push(Self::Metrics(config.metrics.clone()));
+        }
+        updates
+    }
+}
+
+/// Watches the configuration file and sends the changes to reloadable settings to its subscribers
+pub struct ConfigReloader {
+    watcher: ConfigFileWatcher,
+    overrides: ConfigOverrides,
+    network: Network,
+    interval: Option<Duration>,
+    updates: broadcast::Sender<ConfigUpdate>,
+}
+
+impl ConfigReloader {
+    pub fn new(cli: &Cli, config: &ApplicationConfig) -> Self {
+        let network = config.network();
+        let (updates, _) = broadcast::channel(10);
+        Self {
+            watcher: ConfigFileWatcher::new(cli.common.config_path()),
+            overrides: ConfigOverrides(cli.get_config_property_overrides(&network)),
+            network,
+            interval: config.base_node.config_reload_interval.filter(|i| !i.is_zero()),
+            updates,
+        }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<ConfigUpdate> {
+        self.updates.subscribe()
+    }
+
+    /// Checks the configuration file for changes until `shutdown` is triggered. The subscribers' channels are closed
+    /// when this returns.
+    pub async fn run(mut self, shutdown: ShutdownSignal) {
+        let interval = match self.interval {
+            Some(interval) if self.watcher.path().exists() => interval,
+            _ => {
+                debug!(target: LOG_TARGET, "Configuration reloading is disabled");
+                shutdown.await;
+                return;
+            },
+        };
+        // The running configuration may have been adjusted after it was loaded, so changes are detected against the
+        // configuration file as it was loaded here
+        let mut current = match self.load() {
+            Ok(config) => config,
+            Err(e) => {
+                warn!(target: LOG_TARGET, "Configuration reloading is disabled: {}", e);
+                shutdown.await;
+                return;
+            },
+        };
+        info!(
+            target: LOG_TARGET,
+            "Checking {} for changes every {:.0?}",
+            self.watcher.path().display(),
+            interval
+        );
+
+        let mut interval = time::interval(interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        tokio::pin!(shutdown);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {
+                    if !self.watcher.has_changed() {
+                        continue;
+                    }
+                    match self.reload(&current) {
+                        Ok((config, report)) => {
+                            log_report(&report);
+                            current = config;
+                        },
+                        Err(e) => warn!(target: LOG_TARGET, "Configuration file not reloaded: {}", e),
+                    }
+                },
+                _ = &mut shutdown => break,
+            }
+        }
+    }
+
+    /// Loads the configuration file, compares it with `current` and sends the updates for the changed reloadable
+    /// settings. Returns the new configuration and which changes were applied.
+    fn reload(&self, current: &ApplicationConfig) -> Result<(ApplicationConfig, ReloadReport), anyhow::Error> {
+        let config = self.load()?;
+        let changed = changed_keys(&serde_json::to_value(current)?, &serde_json::to_value(&config)?);
+        let report = ReloadReport::classify(changed, RELOADABLE_KEYS);
+        for update in ConfigUpdate::from_report(&report, &config) {
+            // Sending only fails if there are no subscribers
+            let _result = self.updates.send(update);
+        }
+        Ok((config, report))
+    }
+
+    fn load(&self) -> Result<ApplicationConfig, anyhow::Error> {
+        let cfg = load_configuration_with_overrides(self.watcher.path(), &self.overrides, Some(self.network))
+            .map_err(|e| anyhow!("{}", e))?;
+        let config = ApplicationConfig::load_from(&cfg).map_err(|e| anyhow!("{}", e))?;
+        if config.network() != self.network {
+            return Err(anyhow!(
+                "The network can't be changed from {} to {} while running",
+                self.network,
+                config.network()
+            ));
+        }
+        Ok(config)
+    }
+}
+
+fn log_report(report: &ReloadReport) {
+    if report.is_empty() {
+        debug!(target: LOG_TARGET, "Configuration file changed, but no settings were changed");
+        return;
+    }
+    if !report.applied.is_empty() {
+        info!(
+            target: LOG_TARGET,
+            "Applied configuration changes: {}",
+            report.applied.join(", ")
+        );
+    }
+    if !report.restart_required.is_empty() {
+        warn!(
+            target: LOG_TARGET,
+            "Configuration changes that require a restart to take effect: {}",
+            report.restart_required.join(", ")
+        );
+    }
+}
+
+/// The command-line overrides the node was started with, reapplied whenever the configuration file is reloaded
+struct ConfigOverrides(Vec<(String, String)>);
+
+impl ConfigOverrideProvider for ConfigOverrides {
+    fn get_config_property_overrides(&self, _network: &Network) -> Vec<(String, String)> {
+        self.0.clone()
+    }
+}
+
+/// Applies the mempool and peer limit updates from `reloader`, and starts checking the configuration file for changes
+pub fn install(ctx: &BaseNodeContext, reloader: ConfigReloader, shutdown: ShutdownSignal) {
+    task::spawn(apply_mempool_updates(ctx.mempool(), reloader.subscribe()));
+    task::spawn(apply_connectivity_updates(
+        ctx.base_node_comms().connectivity(),
+        reloader.subscribe(),
+    ));
+    task::spawn(reloader.run(shutdown));
+}
+
+async fn apply_mempool_updates(mempool: Mempool, mut updates: broadcast::Receiver<ConfigUpdate>) {
+    while let Some(update) = next_update(&mut updates).await {
+        if let ConfigUpdate::UnconfirmedPool(config) = update {
+            if let Err(e) = mempool.set_unconfirmed_pool_config(config).await {
+                error!(target: LOG_TARGET, "Could not apply the unconfirmed pool config: {}", e);
+            }
+        }
+    }
+}
+
+async fn apply_connectivity_updates(
+    mut connectivity: ConnectivityRequester,
+    mut updates: broadcast::Receiver<ConfigUpdate>,
+) {
+    while let Some(update) = next_update(&mut updates).await {
+        if let ConfigUpdate::MaxInboundConnectionsPerSubnet(max_connections) = update {
+            if let Err(e) = connectivity
+                .set_max_inbound_connections_per_subnet(max_connections)
+                .await
+            {
+                error!(target: LOG_TARGET, "Could not apply the subnet connection limit: {}", e);
+            }
+        }
+    }
+}
+
+/// Waits for the next update, or returns `None` once the reloader has stopped
+pub(crate) async fn next_update(updates: &mut broadcast::Receiver<ConfigUpdate>) -> Option<ConfigUpdate> {
+    loop {
+        match updates.recv().await {
+            Ok(update) => return Some(update),
+            Err(broadcast::error::RecvError::Lagged(n)) => {
+                warn!(target: LOG_TARGET, "{} configuration updates were missed", n);
+            },
+            Err(broadcast::error::RecvError::Closed) => return None,
+        }
+    }
+}
@@ -0,0 +1,57 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Keeps the chain state in the node's diagnostics bundle up to date. The panic hook cannot query the database, as the
+//! panicking thread may hold its locks, so the state is recorded periodically instead.
+
+use std::time::Duration;
+
+use minotari_app_utilities::diagnostics::set_diagnostic_field;
+use tari_core::{
+    base_node::state_machine_service::states::StatusInfo,
+    chain_storage::{async_db::AsyncBlockchainDb, LMDBDatabase},
+};
+use tari_shutdown::ShutdownSignal;
+use tari_utilities::hex::Hex;
+use tokio::{
+    sync::watch,
+    task,
+    time::{self, MissedTickBehavior},
+};
+
+use crate::builder::BaseNodeContext;
+
+const RECORD_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Starts recording the chain state for diagnostics bundles
+pub fn install(ctx: &BaseNodeContext, shutdown: ShutdownSignal) {
+    let db: AsyncBlockchainDb<LMDBDatabase> = ctx.blockchain_db().into();
+    task::spawn(record_chain_state(db, ctx.get_state_machine_info_channel(), shutdown));
+}
+
+async fn record_chain_state(
+    db: AsyncBlockchainDb<LMDBDatabase>,
+    status: watch::Receiver<StatusInfo>,
+    shutdown: ShutdownSignal,
+) {
+    let mut interval = time::interval(RECORD_INTERVAL);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    tokio::pin!(shutdown);
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                {
+                    let status = status.borrow();
+                    set_diagnostic_field("state", &status.state_info);
+                    set_diagnostic_field("bootstrapped", status.bootstrapped);
+                }
+                if let Ok(metadata) = db.get_chain_metadata().await {
+                    set_diagnostic_field("tip_height", metadata.best_block_height());
+                    set_diagnostic_field("tip_hash", metadata.best_block_hash().to_hex());
+                    set_diagnostic_field("pruning_horizon", metadata.pruning_horizon());
+                }
+            },
+            _ = &mut shutdown => break,
+        }
+    }
+}
@@ -0,0 +1,39 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! A minimal, read-only block explorer served over HTTP straight from the node's own database. It is intended to give
+//! testnet operators quick visibility into the chain and mempool without running a separate explorer stack.
+
+mod pages;
+mod server;
+
+use std::net::SocketAddr;
+
+use serde::{Deserialize, Serialize};
+use tari_common::SubConfigPath;
+use tari_shutdown::ShutdownSignal;
+use tokio::task;
+
+use crate::builder::BaseNodeContext;
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExplorerConfig {
+    override_from: Option<String>,
+    /// The address the explorer is served on. The explorer is disabled if this is not set.
+    pub server_bind_address: Option<SocketAddr>,
+}
+
+impl SubConfigPath for ExplorerConfig {
+    fn main_key_prefix() -> &'static str {
+        "explorer"
+    }
+}
+
+/// Starts the explorer server if a bind address has been configured
+pub fn install(ctx: &BaseNodeContext, config: &ExplorerConfig, shutdown: ShutdownSignal) {
+    if let Some(addr) = config.server_bind_address {
+        let explorer = server::Explorer::new(ctx.blockchain_db().into(), ctx.local_mempool());
+        task::spawn(server::run_explorer_server(explorer, addr, shutdown));
+    }
+}
@@ -0,0 +1,234 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! HTML rendering for the explorer. Pages are plain server-rendered HTML with no scripts or external assets.
+
+use std::fmt::Write;
+
+use chrono::NaiveDateTime;
+use tari_core::{
+    blocks::HistoricalBlock,
+    mempool::{StateResponse, StatsResponse},
+    transactions::transaction_components::{TransactionInput, TransactionKernel, TransactionOutput},
+};
+use tari_utilities::hex::Hex;
+
+const STYLE: &str = "body{font-family:sans-serif;margin:2em;max-width:80em}table{border-collapse:collapse;width:100%\
+                     }td,th{border-bottom:1px solid #ddd;padding:4px;text-align:left}code{word-break:break-all}nav \
+                     form{display:inline;margin-right:1em}";
+
+fn layout(title: &str, content: &str) -> String {
+    format!(
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>{title} - Minotari \
+         Explorer</title><style>{STYLE}</style></head><body><nav><a href=\"/\">Blocks</a> | <a \
+         href=\"/mempool\">Mempool</a> | <form action=\"/search\"><input name=\"q\" size=\"48\" placeholder=\"Height, \
+         block hash or output commitment\"><button>Search</button></form><form action=\"/kernel\"><input \
+         name=\"nonce\" size=\"24\" placeholder=\"Kernel public nonce\"><input name=\"signature\" size=\"24\" \
+         placeholder=\"Kernel signature\"><button>Find \
+         kernel</button></form></nav><h1>{title}</h1>{content}</body></html>",
+        title = escape(title),
+    )
+}
+
+pub fn index(blocks: &[HistoricalBlock], stats: &StatsResponse) -> String {
+    let mut content = format!(
+        "<p>Mempool: {} unconfirmed transaction(s), weight {}</p><table><tr><th>Height</th><th>Hash</th><th>Time \
+         (UTC)</th><th>PoW</th><th>Outputs</th><th>Kernels</th><th>Fees</th></tr>",
+        stats.unconfirmed_txs, stats.unconfirmed_weight
+    );
+    for block in blocks {
+        let header = block.header();
+        let body = &block.block().body;
+        let _ = write!(
+            content,
+            "<tr><td><a href=\"/block/{height}\">{height}</a></td><td><a \
+             href=\"/block/{hash}\"><code>{hash}</code></a></td><td>{time}</td><td>{pow}</td><td>{outputs}</\
+             td><td>{kernels}</td><td>{fees}</td></tr>",
+            height = header.height,
+            hash = block.hash(),
+            time = format_timestamp(header.timestamp.as_u64()),
+            pow = header.pow_algo(),
+            outputs = body.outputs().len(),
+            kernels = body.kernels().len(),
+            fees = block.block().calculate_fees(),
+        );
+    }
+    content.push_str("</table>");
+    layout("Recent blocks", &content)
+}
+
+pub fn block(block: &HistoricalBlock) -> String {
+    let header = block.header();
+    let accumulated = block.accumulated_data();
+    let body = &block.block().body;
+    let mut content = String::from("<table>");
+    let rows = [
+        ("Hash", format!("<code>{}</code>", block.hash())),
+        ("Height", header.height.to_string()),
+        ("Confirmations", block.confirmations().to_string()),
+        (
+            "Previous block",
+            format!("<a href=\"/block/{0}\"><code>{0}</code></a>", header.prev_hash),
+        ),
+        ("Time (UTC)", format_timestamp(header.timestamp.as_u64())),
+        ("Version", header.version.to_string()),
+        ("PoW algorithm", header.pow_algo().to_string()),
+        ("Nonce", header.nonce.to_string()),
+        ("Achieved difficulty", accumulated.achieved_difficulty.to_string()),
+        (
+            "Total accumulated difficulty",
+            accumulated.total_accumulated_difficulty.to_string(),
+        ),
+        ("Output MR", format!("<code>{}</code>", header.output_mr)),
+        ("Kernel MR", format!("<code>{}</code>", header.kernel_mr)),
+        ("Input MR", format!("<code>{}</code>", header.input_mr)),
+        ("Total fees", block.block().calculate_fees().to_string()),
+    ];
+    for (name, value) in rows {
+        let _ = write!(content, "<tr><th>{}</th><td>{}</td></tr>", name, value);
+    }
+    content.push_str("</table>");
+
+    let _ = write!(
+        content,
+        "<h2 id=\"inputs\">Inputs ({})</h2><table><tr><th>Commitment</th><th>Spent output hash</th></tr>",
+        body.inputs().len()
+    );
+    for input in body.inputs() {
+        content.push_str(&input_row(input));
+    }
+    let _ = write!(
+        content,
+        "</table><h2 id=\"outputs\">Outputs ({})</h2><table><tr><th>Commitment</th><th>Type</th><th>Minimum value \
+         promise</th></tr>",
+        body.outputs().len()
+    );
+    for output in body.outputs() {
+        content.push_str(&output_row(output));
+    }
+    let _ = write!(
+        content,
+        "</table><h2 id=\"kernels\">Kernels ({})</h2><table><tr><th>Excess</th><th>Public \
+         nonce</th><th>Signature</th><th>Fee</th><th>Lock height</th></tr>",
+        body.kernels().len()
+    );
+    for kernel in body.kernels() {
+        content.push_str(&kernel_row(kernel));
+    }
+    content.push_str("</table>");
+    layout(&format!("Block {}", header.height), &content)
+}
+
+pub fn mempool(stats: &StatsResponse, state: &StateResponse) -> String {
+    let mut content = format!(
+        "<p>{} unconfirmed transaction(s), {} in the reorg pool, total weight {}</p><table><tr><th>Excess signature \
+         nonce</th><th>Inputs</th><th>Outputs</th><th>Kernels</th><th>Fee</th></tr>",
+        stats.unconfirmed_txs, stats.reorg_txs, stats.unconfirmed_weight
+    );
+    for tx in &state.unconfirmed_pool {
+        let nonce = tx
+            .first_kernel_excess_sig()
+            .map(|sig| sig.get_public_nonce().to_hex())
+            .unwrap_or_default();
+        let fee = tx
+            .body
+            .get_total_fee()
+            .map(|fee| fee.to_string())
+            .unwrap_or_else(|_| "-".to_string());
+        let _ = write!(
+            content,
+            "<tr><td><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            nonce,
+            tx.body.inputs().len(),
+            tx.body.outputs().len(),
+            tx.body.kernels().len(),
+            fee
+        );
+    }
+    content.push_str("</table>");
+    layout("Mempool", &content)
+}
+
+pub fn error(message: &str) -> String {
+    layout("Error", &format!("<p>{}</p>", escape(message)))
+}
+
+fn input_row(input: &TransactionInput) -> String {
+    let commitment = input.commitment().map(|c| c.to_hex()).unwrap_or_default();
+    format!(
+        "<tr><td><code>{}</code></td><td><code>{}</code></td></tr>",
+        commitment,
+        input.output_hash()
+    )
+}
+
+fn output_row(output: &TransactionOutput) -> String {
+    format!(
+        "<tr><td><code>{}</code></td><td>{}</td><td>{}</td></tr>",
+        output.commitment.to_hex(),
+        output.features.output_type,
+        output.minimum_value_promise
+    )
+}
+
+fn kernel_row(kernel: &TransactionKernel) -> String {
+    format!(
+        "<tr><td><code>{}</code></td><td><code>{}</code></td><td><code>{}</code></td><td>{}</td><td>{}</td></tr>",
+        kernel.excess.to_hex(),
+        kernel.excess_sig.get_public_nonce().to_hex(),
+        kernel.excess_sig.get_signature().to_hex(),
+        kernel.fee,
+        kernel.lock_height
+    )
+}
+
+// converting u64 to i64 is okay as this is only for viewing timestamps.
+#[allow(clippy::cast_possible_wrap)]
+fn format_timestamp(timestamp: u64) -> String {
+    NaiveDateTime::from_timestamp_opt(timestamp as i64, 0)
+        .map(|t| t.to_string())
+        .unwrap_or_default()
+}
+
+/// Escapes text for inclusion in HTML. Any text that may have come from the request must pass through this.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod test {
+    use tari_common::configuration::Network;
+    use tari_core::blocks::genesis_block::get_genesis_block;
+
+    use super::*;
+
+    #[test]
+    fn it_escapes_html() {
+        assert_eq!(
+            escape("<script>alert('x' & \"y\")</script>"),
+            "&lt;script&gt;alert(&#39;x&#39; &amp; &quot;y&quot;)&lt;/script&gt;"
+        );
+        assert!(error("<b>").contains("&lt;b&gt;"));
+    }
+
+    #[test]
+    fn it_renders_a_block() {
+        let genesis = get_genesis_block(Network::LocalNet);
+        let historical = HistoricalBlock::new(genesis.block().clone(), 1, genesis.accumulated_data().clone());
+        let page = block(&historical);
+        assert!(page.contains(&genesis.hash().to_string()));
+        assert!(page.contains(&format!("Outputs ({})", genesis.block().body.outputs().len())));
+        assert!(page.contains(&format!("Kernels ({})", genesis.block().body.kernels().len())));
+    }
+}
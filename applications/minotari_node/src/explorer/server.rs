@@ -0,0 +1,262 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::{collections::HashMap, convert::Infallible, net::SocketAddr, sync::Arc};
+
+use hyper::{
+    header,
+    service::{make_service_fn, service_fn},
+    Body,
+    Method,
+    Request,
+    Response,
+    Server,
+    StatusCode,
+};
+use log::*;
+use tari_common_types::types::{Commitment, FixedHash, PrivateKey, PublicKey, Signature};
+use tari_core::{
+    chain_storage::{async_db::AsyncBlockchainDb, ChainStorageError, LMDBDatabase},
+    mempool::{service::LocalMempoolService, MempoolServiceError},
+};
+use tari_shutdown::ShutdownSignal;
+use tari_utilities::hex::Hex;
+use thiserror::Error;
+
+use super::pages;
+
+const LOG_TARGET: &str = "minotari::base_node::explorer";
+
+/// The number of blocks listed on the explorer's front page
+const RECENT_BLOCKS: u64 = 20;
+
+#[derive(Debug, Error)]
+enum ExplorerError {
+    #[error("{0}")]
+    NotFound(String),
+    #[error("{0}")]
+    BadRequest(String),
+    #[error("Blockchain database error: {0}")]
+    ChainStorage(#[from] ChainStorageError),
+    #[error("Mempool error: {0}")]
+    Mempool(#[from] MempoolServiceError),
+}
+
+impl ExplorerError {
+    fn status(&self) -> StatusCode {
+        match self {
+            ExplorerError::NotFound(_) => StatusCode::NOT_FOUND,
+            ExplorerError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            ExplorerError::ChainStorage(e) if e.is_value_not_found() => StatusCode::NOT_FOUND,
+            ExplorerError::ChainStorage(_) | ExplorerError::Mempool(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+enum Page {
+    Html(String),
+    Redirect(String),
+}
+
+pub struct Explorer {
+    db: AsyncBlockchainDb<LMDBDatabase>,
+    mempool: LocalMempoolService,
+}
+
+/// Serves the explorer on the given address until `shutdown` is triggered
+pub async fn run_explorer_server(explorer: Explorer, addr: SocketAddr, shutdown: ShutdownSignal) {
+    info!(target: LOG_TARGET, "Starting block explorer on http://{}", addr);
+    let explorer = Arc::new(explorer);
+    let service = make_service_fn(move |_conn| {
+        let explorer = explorer.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |req| {
+                let explorer = explorer.clone();
+                async move { Ok::<_, Infallible>(explorer.handle(req).await) }
+            }))
+        }
+    });
+
+    let server = match Server::try_bind(&addr) {
+        Ok(server) => server,
+        Err(err) => {
+            error!(target: LOG_TARGET, "Unable to bind block explorer to {}: {}", addr, err);
+            return;
+        },
+    };
+    if let Err(err) = server.serve(service).with_graceful_shutdown(shutdown).await {
+        error!(target: LOG_TARGET, "Block explorer server returned error: {}", err);
+    }
+    info!(target: LOG_TARGET, "Block explorer stopped");
+}
+
+impl Explorer {
+    pub fn new(db: AsyncBlockchainDb<LMDBDatabase>, mempool: LocalMempoolService) -> Self {
+        Self { db, mempool }
+    }
+
+    async fn handle(&self, req: Request<Body>) -> Response<Body> {
+        if req.method() != Method::GET {
+            return Response::builder()
+                .status(StatusCode::METHOD_NOT_ALLOWED)
+                .body(Body::empty())
+                .expect("static response is valid");
+        }
+        let query = parse_query(req.uri().query().unwrap_or_default());
+        let path = req.uri().path().trim_end_matches('/');
+        let result = match path.strip_prefix("/block/") {
+            Some(id) => self.block(id).await,
+            None => match path {
+                "" => self.index().await,
+                "/mempool" => self.mempool().await,
+                "/search" => {
+                    self.search(query.get("q").map(String::as_str).unwrap_or_default())
+                        .await
+                },
+                "/kernel" => {
+                    self.kernel(
+                        query.get("nonce").map(String::as_str).unwrap_or_default(),
+                        query.get("signature").map(String::as_str).unwrap_or_default(),
+                    )
+                    .await
+                },
+                _ => Err(ExplorerError::NotFound("Page not found".to_string())),
+            },
+        };
+
+        let (status, page) = match result {
+            Ok(Page::Html(html)) => (StatusCode::OK, html),
+            Ok(Page::Redirect(location)) => {
+                return Response::builder()
+                    .status(StatusCode::SEE_OTHER)
+                    .header(header::LOCATION, location)
+                    .body(Body::empty())
+                    .expect("static response is valid");
+            },
+            Err(err) => {
+                debug!(target: LOG_TARGET, "Explorer request for {} failed: {}", req.uri(), err);
+                (err.status(), pages::error(&err.to_string()))
+            },
+        };
+        Response::builder()
+            .status(status)
+            .header(header::CONTENT_TYPE, "text/html; charset=utf-8")
+            .body(Body::from(page))
+            .expect("static response is valid")
+    }
+
+    async fn index(&self) -> Result<Page, ExplorerError> {
+        let tip = self.db.fetch_tip_header().await?.height();
+        let start = tip.saturating_sub(RECENT_BLOCKS - 1);
+        let mut blocks = self.db.fetch_blocks(start..=tip, true).await?;
+        blocks.reverse();
+        let stats = self.mempool.clone().get_mempool_stats().await?;
+        Ok(Page::Html(pages::index(&blocks, &stats)))
+    }
+
+    async fn block(&self, id: &str) -> Result<Page, ExplorerError> {
+        let block = match id.parse::<u64>() {
+            Ok(height) => {
+                let tip = self.db.fetch_tip_header().await?.height();
+                if height > tip {
+                    return Err(ExplorerError::NotFound(format!("No block at height {}", height)));
+                }
+                self.db.fetch_block(height, false).await?
+            },
+            Err(_) => {
+                let hash = FixedHash::from_hex(id)
+                    .map_err(|_| ExplorerError::BadRequest(format!("'{}' is not a block height or hash", id)))?;
+                self.db
+                    .fetch_block_by_hash(hash, false)
+                    .await?
+                    .ok_or_else(|| ExplorerError::NotFound(format!("No block with hash {}", hash)))?
+            },
+        };
+        Ok(Page::Html(pages::block(&block)))
+    }
+
+    async fn search(&self, query: &str) -> Result<Page, ExplorerError> {
+        let query = query.trim();
+        if query.parse::<u64>().is_ok() {
+            return Ok(Page::Redirect(format!("/block/{}", query)));
+        }
+        if let Ok(hash) = FixedHash::from_hex(query) {
+            if self.db.fetch_header_by_block_hash(hash).await?.is_some() {
+                return Ok(Page::Redirect(format!("/block/{}", hash)));
+            }
+        }
+        if let Ok(commitment) = Commitment::from_hex(query) {
+            if let Some(block) = self.db.fetch_block_with_utxo(commitment).await? {
+                return Ok(Page::Redirect(format!("/block/{}#outputs", block.hash())));
+            }
+        }
+        Err(ExplorerError::NotFound(format!(
+            "No block height, block hash or unspent output commitment matches '{}'",
+            query
+        )))
+    }
+
+    async fn kernel(&self, nonce: &str, signature: &str) -> Result<Page, ExplorerError> {
+        let nonce = PublicKey::from_hex(nonce.trim())
+            .map_err(|_| ExplorerError::BadRequest("The kernel public nonce is not valid hex".to_string()))?;
+        let signature = PrivateKey::from_hex(signature.trim())
+            .map_err(|_| ExplorerError::BadRequest("The kernel signature is not valid hex".to_string()))?;
+        let block = self
+            .db
+            .fetch_block_with_kernel(Signature::new(nonce, signature))
+            .await?
+            .ok_or_else(|| ExplorerError::NotFound("No kernel with this excess signature was found".to_string()))?;
+        Ok(Page::Redirect(format!("/block/{}#kernels", block.hash())))
+    }
+
+    async fn mempool(&self) -> Result<Page, ExplorerError> {
+        let mut mempool = self.mempool.clone();
+        let stats = mempool.get_mempool_stats().await?;
+        let state = mempool.get_mempool_state().await?;
+        Ok(Page::Html(pages::mempool(&stats, &state)))
+    }
+}
+
+/// Splits a URL query string into its parameters. Only hex strings and integers are expected as values, so no
+/// percent-decoding is done beyond treating `+` as a space.
+fn parse_query(query: &str) -> HashMap<String, String> {
+    query
+        .split('&')
+        .filter_map(|pair| {
+            let (key, value) = pair.split_once('=')?;
+            Some((key.to_string(), value.replace('+', " ")))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_parses_query_strings() {
+        let query = parse_query("nonce=aa&signature=bb+&ignored");
+        assert_eq!(query.len(), 2);
+        assert_eq!(query.get("nonce").unwrap(), "aa");
+        assert_eq!(query.get("signature").unwrap(), "bb ");
+        assert!(parse_query("").is_empty());
+    }
+
+    #[test]
+    fn it_maps_errors_to_status_codes() {
+        assert_eq!(ExplorerError::NotFound(String::new()).status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            ExplorerError::ChainStorage(ChainStorageError::ValueNotFound {
+                entity: "BlockHeader",
+                field: "height",
+                value: "1".to_string(),
+            })
+            .status(),
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(
+            ExplorerError::ChainStorage(ChainStorageError::AccessError("".to_string())).status(),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+}
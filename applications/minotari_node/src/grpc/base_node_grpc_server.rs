@@ -23,7 +23,9 @@
 use std::{
     cmp,
     convert::{TryFrom, TryInto},
+    path::PathBuf,
     str::FromStr,
+    time::Duration,
 };
 
 use borsh::{BorshDeserialize, BorshSerialize};
@@ -34,43 +36,65 @@ use minotari_app_grpc::{
     tari_rpc,
     tari_rpc::{CalcType, Sorting},
 };
-use minotari_app_utilities::consts;
+use minotari_app_utilities::{
+    consts,
+    network_profile::{set_active_profile, NetworkProfile},
+};
+use tari_common::{configuration::Network, log_filter_directives, set_log_filter_directives, LogFilterDirective};
 use tari_common_types::{
+    epoch::VnEpoch,
     key_branches::TransactionKeyManagerBranch,
     tari_address::TariAddress,
     types::{Commitment, FixedHash, PublicKey, Signature},
 };
-use tari_comms::{Bytes, CommsNode};
+use tari_comms::{
+    peer_manager::{Peer, SyncPeerQualityTracker},
+    protocol::rpc::RpcServerHandle,
+    Bytes,
+    CommsNode,
+};
+use tari_comms_dht::BroadcastProbeRequester;
 use tari_core::{
     base_node::{
         comms_interface::CommsInterfaceError,
+        network_monitor::NetworkMonitorHandle,
         state_machine_service::states::StateInfo,
         LocalNodeCommsInterface,
         StateMachineHandle,
     },
-    blocks::{Block, BlockHeader, NewBlockTemplate},
+    blocks::{Block, BlockHeader, BlockOutputFilter, HistoricalBlock, NewBlockTemplate},
     chain_storage::ChainStorageError,
     consensus::{emission::Emission, ConsensusManager, NetworkConsensus},
     iterators::NonOverlappingIntegerPairIter,
     mempool::{service::LocalMempoolService, TxStorageResponse},
     proof_of_work::PowAlgorithm,
     transactions::{
+        generate_coinbase,
         generate_coinbase_with_wallet_output,
         key_manager::{create_memory_db_key_manager, TariKeyId, TransactionKeyManagerInterface, TxoStage},
+        payment_proof::PaymentProof,
+        tari_amount::MicroMinotari,
         transaction_components::{
             encrypted_data::PaymentId,
             CoinBaseExtra,
+            CompactOutput,
             KernelBuilder,
             RangeProofType,
             Transaction,
             TransactionKernel,
             TransactionKernelVersion,
         },
+        CryptoFactories,
     },
 };
 use tari_key_manager::key_manager_service::KeyManagerInterface;
-use tari_p2p::{auto_update::SoftwareUpdaterHandle, services::liveness::LivenessHandle};
-use tari_utilities::{hex::Hex, message_format::MessageFormat, ByteArray};
+use tari_p2p::{
+    auto_update::{SoftwareUpdate, SoftwareUpdaterHandle},
+    ban_list,
+    ban_list::{BanListError, SignedBanList},
+    services::liveness::LivenessHandle,
+};
+use tari_utilities::{epoch_time::EpochTime, hex::Hex, message_format::MessageFormat, ByteArray};
 use tokio::task;
 use tonic::{Request, Response, Status};
 
@@ -78,10 +102,12 @@ use crate::{
     builder::BaseNodeContext,
     grpc::{
         blocks::{block_fees, block_heights, block_size, GET_BLOCKS_MAX_HEIGHTS, GET_BLOCKS_PAGE_SIZE},
+        chain_delta::{next_cursor_hashes, ResumePoint, CHAIN_DELTA_CURSOR_DEPTH},
         hash_rate::HashRateMovingAverage,
         helpers::{mean, median},
     },
     grpc_method::GrpcMethod,
+    light_wallet_push::{PushWatch, PushWatchRegistry},
     BaseNodeConfig,
 };
 
@@ -103,6 +129,20 @@ const LIST_HEADERS_PAGE_SIZE: usize = 10;
 const LIST_HEADERS_DEFAULT_NUM_HEADERS: u64 = 10;
 
 const BLOCK_TIMING_MAX_BLOCKS: u64 = 10_000;
+// The maximum number of blocks that can be scanned for burnt outputs in a single request
+const GET_BURNT_BY_CLAIM_KEY_MAX_HEIGHTS: u64 = 10_000;
+const GET_BURNT_BY_CLAIM_KEY_PAGE_SIZE: usize = 100;
+// The maximum number of blocks that can be requested from the light wallet server methods in a single request
+const LIGHT_WALLET_MAX_HEIGHTS: u64 = 10_000;
+// The number of blocks fetched from the database at a time by the light wallet server methods
+const LIGHT_WALLET_PAGE_SIZE: usize = 100;
+// The maximum number of blocks that can be generated in a single regtest request
+const REGTEST_MAX_GENERATE_BLOCKS: u64 = 1_000;
+// The time to wait for broadcast probe receipts if none is provided, and the longest that can be requested
+const PROBE_BROADCAST_DEFAULT_WAIT_SECS: u64 = 30;
+const PROBE_BROADCAST_MAX_WAIT_SECS: u64 = 300;
+// The maximum number of blocks returned by a single GetChainDelta request, which is also the default
+const CHAIN_DELTA_MAX_BLOCKS: u64 = 100;
 
 pub struct BaseNodeGrpcServer {
     node_service: LocalNodeCommsInterface,
@@ -113,12 +153,22 @@ pub struct BaseNodeGrpcServer {
     software_updater: SoftwareUpdaterHandle,
     comms: CommsNode,
     liveness: LivenessHandle,
+    network_monitor: NetworkMonitorHandle,
+    rpc_server: RpcServerHandle,
+    broadcast_probe: BroadcastProbeRequester,
     report_grpc_error: bool,
     config: BaseNodeConfig,
+    profiles_path: Option<PathBuf>,
+    push_watches: PushWatchRegistry,
 }
 
 impl BaseNodeGrpcServer {
-    pub fn from_base_node_context(ctx: &BaseNodeContext, config: BaseNodeConfig) -> Self {
+    /// `profiles_path` is the directory that holds the network profiles, if the node was started with one
+    pub fn from_base_node_context(
+        ctx: &BaseNodeContext,
+        config: BaseNodeConfig,
+        profiles_path: Option<PathBuf>,
+    ) -> Self {
         Self {
             node_service: ctx.local_node(),
             mempool_service: ctx.local_mempool(),
@@ -128,16 +178,35 @@ impl BaseNodeGrpcServer {
             software_updater: ctx.software_updater(),
             comms: ctx.base_node_comms().clone(),
             liveness: ctx.liveness(),
+            network_monitor: ctx.network_monitor(),
+            rpc_server: ctx.rpc_server(),
+            broadcast_probe: ctx.base_node_dht().broadcast_probe_requester(),
             report_grpc_error: ctx.get_report_grpc_error(),
+            push_watches: PushWatchRegistry::new(config.light_wallet_push.clone()),
             config,
+            profiles_path,
         }
     }
 
+    /// Serves push watch registrations from the registry the push notifier is watching
+    pub fn with_push_watches(mut self, push_watches: PushWatchRegistry) -> Self {
+        self.push_watches = push_watches;
+        self
+    }
+
     pub fn report_error_flag(&self) -> bool {
         self.report_grpc_error
     }
 
     fn is_method_enabled(&self, grpc_method: GrpcMethod) -> bool {
+        // On-demand block generation must never be reachable outside of regtest mode, whatever the allow list says
+        if grpc_method == GrpcMethod::GenerateBlocks {
+            return self.config.regtest_enabled;
+        }
+        // The public server profile replaces all other method settings
+        if self.config.grpc_public_server.enabled {
+            return self.config.grpc_public_server.is_method_allowed(grpc_method);
+        }
         let mining_method = [
             GrpcMethod::GetVersion,
             GrpcMethod::GetNewBlockTemplate,
@@ -160,16 +229,34 @@ impl BaseNodeGrpcServer {
             GrpcMethod::GetTipInfo,
             GrpcMethod::GetActiveValidatorNodes,
             GrpcMethod::GetShardKey,
+            GrpcMethod::GetValidatorNodeSet,
             GrpcMethod::GetTemplateRegistrations,
             GrpcMethod::GetHeaderByHash,
             GrpcMethod::GetSideChainUtxos,
         ];
+
+        let light_wallet_methods = [
+            GrpcMethod::GetVersion,
+            GrpcMethod::GetConstants,
+            GrpcMethod::GetTipInfo,
+            GrpcMethod::GetHeaderByHash,
+            GrpcMethod::GetBlockFilters,
+            GrpcMethod::GetCompactBlockOutputs,
+            GrpcMethod::FetchMatchingUtxos,
+            GrpcMethod::SubmitTransaction,
+            GrpcMethod::TransactionState,
+            GrpcMethod::RegisterPushWatch,
+            GrpcMethod::UnregisterPushWatch,
+        ];
         if self.config.mining_enabled && mining_method.contains(&grpc_method) {
             return true;
         }
         if self.config.second_layer_grpc_enabled && second_layer_methods.contains(&grpc_method) {
             return true;
         }
+        if self.config.light_wallet_server_enabled && light_wallet_methods.contains(&grpc_method) {
+            return true;
+        }
         self.config.grpc_server_allow_methods.to_vec().contains(&grpc_method)
     }
 
@@ -206,7 +293,9 @@ impl BaseNodeGrpcServer {}
 impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
     type FetchMatchingUtxosStream = mpsc::Receiver<Result<tari_rpc::FetchMatchingUtxosResponse, Status>>;
     type GetActiveValidatorNodesStream = mpsc::Receiver<Result<tari_rpc::GetActiveValidatorNodesResponse, Status>>;
+    type GetBlockFiltersStream = mpsc::Receiver<Result<tari_rpc::BlockFilterResponse, Status>>;
     type GetBlocksStream = mpsc::Receiver<Result<tari_rpc::HistoricalBlock, Status>>;
+    type GetCompactBlockOutputsStream = mpsc::Receiver<Result<tari_rpc::CompactBlockOutputsResponse, Status>>;
     type GetMempoolTransactionsStream = mpsc::Receiver<Result<tari_rpc::GetMempoolTransactionsResponse, Status>>;
     type GetNetworkDifficultyStream = mpsc::Receiver<Result<tari_rpc::NetworkDifficultyResponse, Status>>;
     type GetPeersStream = mpsc::Receiver<Result<tari_rpc::GetPeersResponse, Status>>;
@@ -216,6 +305,7 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
     type ListHeadersStream = mpsc::Receiver<Result<tari_rpc::BlockHeaderResponse, Status>>;
     type SearchKernelsStream = mpsc::Receiver<Result<tari_rpc::HistoricalBlock, Status>>;
     type SearchUtxosStream = mpsc::Receiver<Result<tari_rpc::HistoricalBlock, Status>>;
+    type StreamSoftwareUpdatesStream = mpsc::Receiver<Result<tari_rpc::SoftwareUpdate, Status>>;
 
     #[allow(clippy::too_many_lines)]
     async fn get_network_difficulty(
@@ -911,6 +1001,7 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
                 coinbase_kernel.lock_height,
                 &coinbase_kernel.features,
                 &None,
+                &None,
             );
             last_kernel = coinbase_kernel;
         }
@@ -1109,6 +1200,7 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
                 coinbase_kernel.lock_height,
                 &coinbase_kernel.features,
                 &None,
+                &None,
             );
             last_kernel = coinbase_kernel;
         }
@@ -1534,13 +1626,13 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         let report_error_flag = self.report_error_flag();
         trace!(target: LOG_TARGET, "Incoming GRPC request for get all peers");
 
-        let peers = self
-            .comms
-            .peer_manager()
+        let peer_manager = self.comms.peer_manager();
+        let peers = peer_manager
             .all()
             .await
             .map_err(|e| obscure_error_if_true(report_error_flag, Status::internal(e.to_string())))?;
-        let peers: Vec<tari_rpc::Peer> = peers.into_iter().map(|p| p.into()).collect();
+        let sync_quality = peer_manager.sync_quality_tracker();
+        let peers: Vec<tari_rpc::Peer> = peers.into_iter().map(|p| peer_to_grpc(p, sync_quality)).collect();
         let (mut tx, rx) = mpsc::channel(peers.len());
         task::spawn(async move {
             for peer in peers {
@@ -1953,18 +2045,32 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         _request: Request<tari_rpc::Empty>,
     ) -> Result<Response<tari_rpc::SoftwareUpdate>, Status> {
         self.check_method_enabled(GrpcMethod::CheckForUpdates)?;
-        let mut resp = tari_rpc::SoftwareUpdate::default();
-
-        if let Some(ref update) = *self.software_updater.update_notifier().borrow() {
-            resp.has_update = true;
-            resp.version = update.version().to_string();
-            resp.sha = update.to_hash_hex();
-            resp.download_url = update.download_url().to_string();
-        }
-
+        let resp = software_update_to_grpc(self.software_updater.update_notifier().borrow().as_ref());
         Ok(Response::new(resp))
     }
 
+    async fn stream_software_updates(
+        &self,
+        _request: Request<tari_rpc::Empty>,
+    ) -> Result<Response<Self::StreamSoftwareUpdatesStream>, Status> {
+        self.check_method_enabled(GrpcMethod::StreamSoftwareUpdates)?;
+        let mut update_notifier = self.software_updater.update_notifier().clone();
+        let (mut tx, rx) = mpsc::channel(1);
+        task::spawn(async move {
+            loop {
+                let update = software_update_to_grpc(update_notifier.borrow_and_update().as_ref());
+                if tx.send(Ok(update)).await.is_err() {
+                    debug!(target: LOG_TARGET, "StreamSoftwareUpdates client disconnected");
+                    break;
+                }
+                if update_notifier.changed().await.is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(Response::new(rx))
+    }
+
     async fn get_tokens_in_circulation(
         &self,
         request: Request<tari_rpc::GetBlocksRequest>,
@@ -2225,8 +2331,9 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
             );
         }
 
+        let sync_quality = peer_manager.sync_quality_tracker();
         let resp = tari_rpc::ListConnectedPeersResponse {
-            connected_peers: peers.into_iter().map(Into::into).collect(),
+            connected_peers: peers.into_iter().map(|p| peer_to_grpc(p, sync_quality)).collect(),
         };
 
         Ok(Response::new(resp))
@@ -2282,6 +2389,50 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         }
     }
 
+    async fn get_validator_node_set(
+        &self,
+        request: Request<tari_rpc::GetValidatorNodeSetRequest>,
+    ) -> Result<Response<tari_rpc::GetValidatorNodeSetResponse>, Status> {
+        self.check_method_enabled(GrpcMethod::GetValidatorNodeSet)?;
+        let report_error_flag = self.report_error_flag();
+        let mut handler = self.node_service.clone();
+        let height = match request.into_inner().at {
+            Some(tari_rpc::get_validator_node_set_request::At::Height(height)) => height,
+            Some(tari_rpc::get_validator_node_set_request::At::Epoch(epoch)) => {
+                let tip_height = handler
+                    .get_metadata()
+                    .await
+                    .map_err(|e| obscure_error_if_true(report_error_flag, Status::internal(e.to_string())))?
+                    .best_block_height();
+                self.consensus_rules
+                    .consensus_constants(tip_height)
+                    .epoch_to_block_height(VnEpoch(epoch))
+            },
+            None => return Err(Status::invalid_argument("Either a height or an epoch is required")),
+        };
+
+        let vn_set = handler.get_validator_node_set(height).await.map_err(|e| {
+            warn!(target: LOG_TARGET, "Error fetching the validator node set at height {}: {}", height, e);
+            obscure_error_if_true(report_error_flag, Status::not_found(e.to_string()))
+        })?;
+        Ok(Response::new(tari_rpc::GetValidatorNodeSetResponse {
+            epoch: vn_set.epoch.as_u64(),
+            epoch_start_height: vn_set.header.height,
+            epoch_start_block_hash: vn_set.header.hash().to_vec(),
+            validator_node_merkle_root: vn_set.header.validator_node_mr.to_vec(),
+            validator_nodes: vn_set
+                .members
+                .into_iter()
+                .map(|member| tari_rpc::ValidatorNodeSetMember {
+                    public_key: member.public_key.to_vec(),
+                    shard_key: member.shard_key.to_vec(),
+                    merkle_proof_path: member.merkle_proof.path().to_vec(),
+                    merkle_proof_node_index: member.merkle_proof.node_index(),
+                })
+                .collect(),
+        }))
+    }
+
     async fn get_active_validator_nodes(
         &self,
         request: Request<tari_rpc::GetActiveValidatorNodesRequest>,
@@ -2535,6 +2686,832 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         );
         Ok(Response::new(rx))
     }
+
+    async fn export_ban_list(
+        &self,
+        _: Request<tari_rpc::Empty>,
+    ) -> Result<Response<tari_rpc::ExportBanListResponse>, Status> {
+        self.check_method_enabled(GrpcMethod::ExportBanList)?;
+        let report_error_flag = self.report_error_flag();
+        let ban_list = ban_list::export_ban_list(&self.comms.peer_manager(), self.comms.node_identity_ref())
+            .await
+            .map_err(|err| obscure_error_if_true(report_error_flag, Status::internal(err.to_string())))?;
+        let num_entries = u32::try_from(ban_list.entries.len()).unwrap_or(u32::MAX);
+        let ban_list = ban_list
+            .to_json()
+            .map_err(|err| obscure_error_if_true(report_error_flag, Status::internal(err.to_string())))?;
+
+        Ok(Response::new(tari_rpc::ExportBanListResponse { ban_list, num_entries }))
+    }
+
+    async fn import_ban_list(
+        &self,
+        request: Request<tari_rpc::ImportBanListRequest>,
+    ) -> Result<Response<tari_rpc::ImportBanListResponse>, Status> {
+        self.check_method_enabled(GrpcMethod::ImportBanList)?;
+        let request = request.into_inner();
+        let report_error_flag = self.report_error_flag();
+        let ban_list = SignedBanList::from_json(&request.ban_list)
+            .map_err(|err| obscure_error_if_true(report_error_flag, Status::invalid_argument(err.to_string())))?;
+        let trusted_signers = request
+            .trusted_signers
+            .iter()
+            .map(|pk| PublicKey::from_canonical_bytes(pk))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| obscure_error_if_true(report_error_flag, Status::invalid_argument(err.to_string())))?;
+
+        let summary = ban_list::import_ban_list(
+            &self.comms.peer_manager(),
+            &mut self.comms.connectivity(),
+            self.comms.node_identity_ref(),
+            ban_list,
+            &trusted_signers,
+        )
+        .await
+        .map_err(|err| match err {
+            err @ (BanListError::InvalidSignature |
+            BanListError::MalformedSignature |
+            BanListError::UnsupportedVersion(_) |
+            BanListError::UntrustedSigner(_)) => {
+                obscure_error_if_true(report_error_flag, Status::invalid_argument(err.to_string()))
+            },
+            err => obscure_error_if_true(report_error_flag, Status::internal(err.to_string())),
+        })?;
+
+        Ok(Response::new(tari_rpc::ImportBanListResponse {
+            num_banned: u32::try_from(summary.num_banned).unwrap_or(u32::MAX),
+            num_skipped: u32::try_from(summary.num_skipped).unwrap_or(u32::MAX),
+        }))
+    }
+
+    async fn get_network_alerts(
+        &self,
+        _: Request<tari_rpc::Empty>,
+    ) -> Result<Response<tari_rpc::GetNetworkAlertsResponse>, Status> {
+        self.check_method_enabled(GrpcMethod::GetNetworkAlerts)?;
+        let alerts = self.network_monitor.get_alerts().iter().map(Into::into).collect();
+        Ok(Response::new(tari_rpc::GetNetworkAlertsResponse { alerts }))
+    }
+
+    async fn get_peer_connection_details(
+        &self,
+        _: Request<tari_rpc::Empty>,
+    ) -> Result<Response<tari_rpc::GetPeerConnectionDetailsResponse>, Status> {
+        self.check_method_enabled(GrpcMethod::GetPeerConnectionDetails)?;
+        let report_error_flag = self.report_error_flag();
+        let connections = self
+            .comms
+            .connectivity()
+            .get_active_connections()
+            .await
+            .map_err(|err| obscure_error_if_true(report_error_flag, Status::internal(err.to_string())))?;
+        let sessions = self
+            .rpc_server
+            .clone()
+            .get_active_sessions()
+            .await
+            .map_err(|err| obscure_error_if_true(report_error_flag, Status::internal(err.to_string())))?;
+
+        let connections = connections
+            .iter()
+            .map(|conn| {
+                let mut details = tari_rpc::PeerConnectionDetails::from(conn);
+                details.rpc_sessions = sessions
+                    .iter()
+                    .filter(|s| s.node_id == *conn.peer_node_id())
+                    .map(Into::into)
+                    .collect();
+                details
+            })
+            .collect();
+
+        Ok(Response::new(tari_rpc::GetPeerConnectionDetailsResponse {
+            connections,
+        }))
+    }
+
+    async fn get_burnt_by_claim_key(
+        &self,
+        request: Request<tari_rpc::GetBurntByClaimKeyRequest>,
+    ) -> Result<Response<tari_rpc::GetBurntByClaimKeyResponse>, Status> {
+        self.check_method_enabled(GrpcMethod::GetBurntByClaimKey)?;
+        let report_error_flag = self.report_error_flag();
+        let request = request.into_inner();
+        let claim_public_key = PublicKey::from_canonical_bytes(&request.claim_public_key)
+            .map_err(|e| obscure_error_if_true(report_error_flag, Status::invalid_argument(e.to_string())))?;
+        let num_requested = request.end_height.checked_sub(request.start_height).ok_or_else(|| {
+            obscure_error_if_true(
+                report_error_flag,
+                Status::invalid_argument("Start height is more than end height"),
+            )
+        })?;
+        if num_requested > GET_BURNT_BY_CLAIM_KEY_MAX_HEIGHTS {
+            return Err(obscure_error_if_true(
+                report_error_flag,
+                Status::invalid_argument(format!(
+                    "Number of blocks requested exceeds maximum. Expected less than {} but got {}",
+                    GET_BURNT_BY_CLAIM_KEY_MAX_HEIGHTS, num_requested
+                )),
+            ));
+        }
+        trace!(
+            target: LOG_TARGET,
+            "Incoming GRPC request for GetBurntByClaimKey: claim_public_key: {} start_height: {} end_height: {}",
+            claim_public_key,
+            request.start_height,
+            request.end_height
+        );
+
+        let page_iter = NonOverlappingIntegerPairIter::new(
+            request.start_height,
+            request.end_height.saturating_add(1),
+            GET_BURNT_BY_CLAIM_KEY_PAGE_SIZE,
+        )
+        .map_err(|e| obscure_error_if_true(report_error_flag, Status::invalid_argument(e)))?;
+
+        let mut handler = self.node_service.clone();
+        let mut commitments = Vec::new();
+        let mut total_commitment = Commitment::default();
+        let mut total_minimum_value_promise = MicroMinotari::zero();
+        for (start, end) in page_iter {
+            let blocks = handler.get_blocks(start..=end, false).await.map_err(|e| {
+                warn!(target: LOG_TARGET, "Base node service error: {:?}", e);
+                obscure_error_if_true(report_error_flag, Status::internal(e.to_string()))
+            })?;
+            for block in &blocks {
+                for output in block.block().body.outputs() {
+                    if !output.is_burned() || output.features.burn_claim_public_key() != Some(&claim_public_key) {
+                        continue;
+                    }
+                    total_commitment = &total_commitment + &output.commitment;
+                    total_minimum_value_promise =
+                        total_minimum_value_promise.saturating_add(output.minimum_value_promise);
+                    commitments.push(output.commitment.to_vec());
+                }
+            }
+        }
+
+        Ok(Response::new(tari_rpc::GetBurntByClaimKeyResponse {
+            num_outputs: u64::try_from(commitments.len()).unwrap_or(u64::MAX),
+            total_minimum_value_promise: total_minimum_value_promise.as_u64(),
+            total_commitment: total_commitment.to_vec(),
+            commitments,
+        }))
+    }
+
+    async fn get_block_filters(
+        &self,
+        request: Request<tari_rpc::GetBlockFiltersRequest>,
+    ) -> Result<Response<Self::GetBlockFiltersStream>, Status> {
+        self.check_method_enabled(GrpcMethod::GetBlockFilters)?;
+        let report_error_flag = self.report_error_flag();
+        let request = request.into_inner();
+        trace!(
+            target: LOG_TARGET,
+            "Incoming GRPC request for GetBlockFilters: start_height: {} end_height: {}",
+            request.start_height,
+            request.end_height
+        );
+
+        let (tx, rx) = mpsc::channel(LIGHT_WALLET_PAGE_SIZE);
+        stream_light_wallet_blocks(
+            self.node_service.clone(),
+            request.start_height,
+            request.end_height,
+            report_error_flag,
+            tx,
+            move |block| {
+                let filter = BlockOutputFilter::from_block(block.block());
+                tari_rpc::BlockFilterResponse {
+                    height: block.header().height,
+                    block_hash: filter.block_hash().to_vec(),
+                    num_items: filter.num_items(),
+                    filter: filter.data().to_vec(),
+                }
+            },
+        )
+        .await?;
+
+        Ok(Response::new(rx))
+    }
+
+    async fn get_compact_block_outputs(
+        &self,
+        request: Request<tari_rpc::GetCompactBlockOutputsRequest>,
+    ) -> Result<Response<Self::GetCompactBlockOutputsStream>, Status> {
+        self.check_method_enabled(GrpcMethod::GetCompactBlockOutputs)?;
+        let report_error_flag = self.report_error_flag();
+        let request = request.into_inner();
+        trace!(
+            target: LOG_TARGET,
+            "Incoming GRPC request for GetCompactBlockOutputs: start_height: {} end_height: {}",
+            request.start_height,
+            request.end_height
+        );
+
+        let (tx, rx) = mpsc::channel(LIGHT_WALLET_PAGE_SIZE);
+        stream_light_wallet_blocks(
+            self.node_service.clone(),
+            request.start_height,
+            request.end_height,
+            report_error_flag,
+            tx,
+            move |block| {
+                let body = &block.block().body;
+                tari_rpc::CompactBlockOutputsResponse {
+                    height: block.header().height,
+                    block_hash: block.hash().to_vec(),
+                    timestamp: block.header().timestamp.as_u64(),
+                    outputs: body
+                        .outputs()
+                        .iter()
+                        .map(|output| CompactOutput::from(output).into())
+                        .collect(),
+                    spent_output_hashes: body.inputs().iter().map(|input| input.output_hash().to_vec()).collect(),
+                }
+            },
+        )
+        .await?;
+
+        Ok(Response::new(rx))
+    }
+
+    async fn register_push_watch(
+        &self,
+        request: Request<tari_rpc::RegisterPushWatchRequest>,
+    ) -> Result<Response<tari_rpc::Empty>, Status> {
+        self.check_method_enabled(GrpcMethod::RegisterPushWatch)?;
+        let report_error_flag = self.report_error_flag();
+        let request = request.into_inner();
+        trace!(
+            target: LOG_TARGET,
+            "Incoming GRPC request for RegisterPushWatch: {} item(s)",
+            request.items.len()
+        );
+        self.push_watches
+            .register(PushWatch {
+                relay_url: request.relay_url,
+                device_token: request.device_token,
+                payload: request.payload,
+                items: request.items.into_iter().collect(),
+            })
+            .map_err(|e| obscure_error_if_true(report_error_flag, Status::invalid_argument(e.to_string())))?;
+        Ok(Response::new(tari_rpc::Empty {}))
+    }
+
+    async fn unregister_push_watch(
+        &self,
+        request: Request<tari_rpc::UnregisterPushWatchRequest>,
+    ) -> Result<Response<tari_rpc::Empty>, Status> {
+        self.check_method_enabled(GrpcMethod::UnregisterPushWatch)?;
+        trace!(target: LOG_TARGET, "Incoming GRPC request for UnregisterPushWatch");
+        self.push_watches.unregister(&request.into_inner().device_token);
+        Ok(Response::new(tari_rpc::Empty {}))
+    }
+
+    async fn generate_blocks(
+        &self,
+        request: Request<tari_rpc::GenerateBlocksRequest>,
+    ) -> Result<Response<tari_rpc::GenerateBlocksResponse>, Status> {
+        self.check_method_enabled(GrpcMethod::GenerateBlocks)?;
+        let request = request.into_inner();
+        debug!(
+            target: LOG_TARGET,
+            "Incoming GRPC request for GenerateBlocks: num_blocks: {}", request.num_blocks
+        );
+        if request.num_blocks == 0 || request.num_blocks > REGTEST_MAX_GENERATE_BLOCKS {
+            return Err(Status::invalid_argument(format!(
+                "num_blocks must be between 1 and {}",
+                REGTEST_MAX_GENERATE_BLOCKS
+            )));
+        }
+        let address = TariAddress::from_str(&request.wallet_payment_address)
+            .map_err(|e| Status::invalid_argument(format!("Invalid wallet payment address: {}", e)))?;
+        let key_manager =
+            create_memory_db_key_manager().map_err(|e| Status::internal(format!("Key manager error: '{}'", e)))?;
+
+        let mut handler = self.node_service.clone();
+        let mut block_hashes = Vec::with_capacity(usize::try_from(request.num_blocks).unwrap_or_default());
+        for _ in 0..request.num_blocks {
+            // Localnet difficulty is fixed at the minimum, so the template can be mined with any nonce
+            let mut template = handler
+                .get_new_block_template(PowAlgorithm::Sha3x, 0)
+                .await
+                .map_err(|e| Status::internal(e.to_string()))?;
+            let height = template.header.height;
+            let (coinbase_output, coinbase_kernel) = generate_coinbase(
+                template.total_fees,
+                template.reward,
+                height,
+                &CoinBaseExtra::default(),
+                &key_manager,
+                &address,
+                true,
+                self.consensus_rules.consensus_constants(height),
+                RangeProofType::RevealedValue,
+                PaymentId::Empty,
+            )
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+            template.body.add_output(coinbase_output);
+            template.body.add_kernel(coinbase_kernel);
+            template.body.sort();
+
+            let parent = handler
+                .get_header(height.saturating_sub(1))
+                .await
+                .map_err(|e| Status::internal(e.to_string()))?
+                .ok_or_else(|| Status::not_found(format!("Header at height {} not found", height.saturating_sub(1))))?;
+            let mut block = handler
+                .get_new_block(template)
+                .await
+                .map_err(|e| Status::internal(e.to_string()))?;
+            // Each block is timestamped one second after its parent rather than with the wall clock, so that the same
+            // sequence of requests always produces the same headers
+            block.header.timestamp = parent
+                .header()
+                .timestamp
+                .checked_add(EpochTime::from(1))
+                .ok_or_else(|| Status::internal("Block timestamp overflow"))?;
+            block.header.nonce = 0;
+
+            let block_hash = handler
+                .submit_block(block)
+                .await
+                .map_err(|e| Status::internal(e.to_string()))?;
+            info!(target: LOG_TARGET, "Regtest: generated block #{} ({})", height, block_hash);
+            block_hashes.push(block_hash.to_vec());
+        }
+
+        Ok(Response::new(tari_rpc::GenerateBlocksResponse { block_hashes }))
+    }
+
+    async fn set_active_profile(
+        &self,
+        request: Request<tari_rpc::SetActiveProfileRequest>,
+    ) -> Result<Response<tari_rpc::SetActiveProfileResponse>, Status> {
+        self.check_method_enabled(GrpcMethod::SetActiveProfile)?;
+        let request = request.into_inner();
+        debug!(
+            target: LOG_TARGET,
+            "Incoming GRPC request for SetActiveProfile: name: {}, network: {}", request.name, request.network
+        );
+        let profiles_path = self
+            .profiles_path
+            .as_ref()
+            .ok_or_else(|| Status::failed_precondition("The node was not started with a network profile"))?;
+        let network = if request.network.is_empty() {
+            None
+        } else {
+            Some(Network::from_str(&request.network).map_err(|e| Status::invalid_argument(e.to_string()))?)
+        };
+        let profile = NetworkProfile::open(profiles_path, &request.name, network)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+        set_active_profile(profiles_path, &profile).map_err(|e| Status::internal(e.to_string()))?;
+        info!(
+            target: LOG_TARGET,
+            "Network profile '{}' ({}) will be used from the next start", profile.name, profile.network
+        );
+
+        Ok(Response::new(tari_rpc::SetActiveProfileResponse {
+            name: profile.name,
+            network: profile.network.to_string(),
+        }))
+    }
+
+    async fn get_log_filters(
+        &self,
+        _: Request<tari_rpc::Empty>,
+    ) -> Result<Response<tari_rpc::LogFiltersResponse>, Status> {
+        self.check_method_enabled(GrpcMethod::GetLogFilters)?;
+        let directives = log_filter_directives().map_err(|e| Status::internal(e.to_string()))?;
+        Ok(Response::new(tari_rpc::LogFiltersResponse {
+            directives: directives.iter().map(ToString::to_string).collect(),
+        }))
+    }
+
+    async fn set_log_filters(
+        &self,
+        request: Request<tari_rpc::SetLogFiltersRequest>,
+    ) -> Result<Response<tari_rpc::LogFiltersResponse>, Status> {
+        self.check_method_enabled(GrpcMethod::SetLogFilters)?;
+        let request = request.into_inner();
+        let directives = request
+            .directives
+            .iter()
+            .map(|d| d.parse::<LogFilterDirective>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(Status::invalid_argument)?;
+        info!(
+            target: LOG_TARGET,
+            "Incoming GRPC request for SetLogFilters: [{}] (reset: {})",
+            request.directives.join(", "),
+            request.reset
+        );
+        let directives =
+            set_log_filter_directives(directives, request.reset).map_err(|e| Status::internal(e.to_string()))?;
+        Ok(Response::new(tari_rpc::LogFiltersResponse {
+            directives: directives.iter().map(ToString::to_string).collect(),
+        }))
+    }
+
+    async fn get_utxo_set_stats(
+        &self,
+        _: Request<tari_rpc::Empty>,
+    ) -> Result<Response<tari_rpc::UtxoSetStatsResponse>, Status> {
+        self.check_method_enabled(GrpcMethod::GetUtxoSetStats)?;
+        let report_error_flag = self.report_error_flag();
+        let mut handler = self.node_service.clone();
+        let (stats, tip_height) = handler.get_utxo_set_stats().await.map_err(|e| {
+            error!(target: LOG_TARGET, "Error fetching UTXO set stats: {}", e);
+            obscure_error_if_true(report_error_flag, Status::internal(e.to_string()))
+        })?;
+
+        let output_types = stats
+            .output_type_counts()
+            .into_iter()
+            .map(|(output_type, count)| tari_rpc::OutputTypeCount {
+                output_type: u32::from(output_type.as_byte()),
+                name: output_type.to_string(),
+                count,
+            })
+            .collect();
+        let revealed_values = stats
+            .revealed_value_buckets
+            .iter()
+            .map(|(magnitude, count)| tari_rpc::RevealedValueBucket {
+                min_value: if *magnitude == 0 { 0 } else { 10u64.pow(*magnitude) },
+                max_value: 10u64.checked_pow(magnitude + 1).map_or(u64::MAX, |v| v - 1),
+                count: *count,
+            })
+            .collect();
+        let ages = stats
+            .age_distribution(tip_height)
+            .into_iter()
+            .map(|bucket| tari_rpc::UtxoAgeBucket {
+                min_age: bucket.min_age,
+                max_age: bucket.max_age,
+                count: bucket.count,
+            })
+            .collect();
+
+        Ok(Response::new(tari_rpc::UtxoSetStatsResponse {
+            tip_height,
+            utxo_count: stats.count,
+            total_size: stats.total_size,
+            output_types,
+            revealed_values,
+            ages,
+        }))
+    }
+
+    async fn verify_payment_proof(
+        &self,
+        request: Request<tari_rpc::PaymentProof>,
+    ) -> Result<Response<tari_rpc::VerifyPaymentProofResponse>, Status> {
+        self.check_method_enabled(GrpcMethod::VerifyPaymentProof)?;
+        let report_error_flag = self.report_error_flag();
+        let proof = PaymentProof::try_from(request.into_inner()).map_err(|e| {
+            obscure_error_if_true(
+                report_error_flag,
+                Status::invalid_argument(format!("Invalid payment proof: {}", e)),
+            )
+        })?;
+        let invalid = |error: String| {
+            Ok(Response::new(tari_rpc::VerifyPaymentProofResponse {
+                is_valid: false,
+                error,
+                ..Default::default()
+            }))
+        };
+
+        let mut handler = self.node_service.clone();
+        let mined_info = match handler.get_output_mined_info(proof.output_hash).await.map_err(|e| {
+            error!(target: LOG_TARGET, "Error fetching output: {}", e);
+            obscure_error_if_true(report_error_flag, Status::internal(e.to_string()))
+        })? {
+            Some(mined_info) => mined_info,
+            None => return invalid("The output has not been mined".to_string()),
+        };
+        if let Err(e) = proof.verify_output(&mined_info.output, &CryptoFactories::default()) {
+            return invalid(e.to_string());
+        }
+        let kernel_blocks = handler
+            .get_blocks_with_kernels(vec![proof.kernel_excess_sig.clone()])
+            .await
+            .map_err(|e| {
+                error!(target: LOG_TARGET, "Error fetching kernel: {}", e);
+                obscure_error_if_true(report_error_flag, Status::internal(e.to_string()))
+            })?;
+        if !kernel_blocks
+            .iter()
+            .any(|block| *block.hash() == mined_info.header_hash)
+        {
+            return invalid("The kernel was not mined in the same block as the output".to_string());
+        }
+        let tip_height = handler
+            .get_metadata()
+            .await
+            .map_err(|e| obscure_error_if_true(report_error_flag, Status::internal(e.to_string())))?
+            .best_block_height();
+
+        Ok(Response::new(tari_rpc::VerifyPaymentProofResponse {
+            is_valid: true,
+            error: String::new(),
+            mined_height: mined_info.mined_height,
+            block_hash: mined_info.header_hash.to_vec(),
+            confirmations: tip_height.saturating_sub(mined_info.mined_height) + 1,
+        }))
+    }
+
+    async fn probe_broadcast(
+        &self,
+        request: Request<tari_rpc::ProbeBroadcastRequest>,
+    ) -> Result<Response<tari_rpc::ProbeBroadcastResponse>, Status> {
+        self.check_method_enabled(GrpcMethod::ProbeBroadcast)?;
+        let report_error_flag = self.report_error_flag();
+        let wait_secs = match request.into_inner().wait_secs {
+            0 => PROBE_BROADCAST_DEFAULT_WAIT_SECS,
+            wait_secs if wait_secs > PROBE_BROADCAST_MAX_WAIT_SECS => {
+                return Err(Status::invalid_argument(format!(
+                    "The wait time cannot be more than {} seconds",
+                    PROBE_BROADCAST_MAX_WAIT_SECS
+                )));
+            },
+            wait_secs => wait_secs,
+        };
+
+        let report = self
+            .broadcast_probe
+            .clone()
+            .probe(Duration::from_secs(wait_secs))
+            .await
+            .map_err(|e| {
+                warn!(target: LOG_TARGET, "Error sending broadcast probe: {}", e);
+                obscure_error_if_true(report_error_flag, Status::internal(e.to_string()))
+            })?;
+        Ok(Response::new(tari_rpc::ProbeBroadcastResponse {
+            probe_id: report.probe_id,
+            num_known_nodes: report.num_known_nodes as u64,
+            receipts: report
+                .receipts
+                .iter()
+                .map(|receipt| tari_rpc::BroadcastProbeReceipt {
+                    responder: receipt.responder.to_vec(),
+                    received_from: receipt.received_from.to_vec(),
+                    latency_ms: u64::try_from(receipt.latency.as_millis()).unwrap_or(u64::MAX),
+                    hops: report.hops.get(&receipt.responder).map_or(0, |hops| *hops as u64),
+                })
+                .collect(),
+        }))
+    }
+
+    #[allow(clippy::too_many_lines)]
+    async fn get_chain_delta(
+        &self,
+        request: Request<tari_rpc::GetChainDeltaRequest>,
+    ) -> Result<Response<tari_rpc::GetChainDeltaResponse>, Status> {
+        self.check_method_enabled(GrpcMethod::GetChainDelta)?;
+        let report_error_flag = self.report_error_flag();
+        let request = request.into_inner();
+        let max_blocks = match request.max_blocks {
+            0 => CHAIN_DELTA_MAX_BLOCKS,
+            max_blocks => max_blocks.min(CHAIN_DELTA_MAX_BLOCKS),
+        };
+        let (cursor_height, cursor_hashes) = match request.cursor {
+            Some(cursor) => {
+                let hashes = cursor
+                    .block_hashes
+                    .into_iter()
+                    .take(CHAIN_DELTA_CURSOR_DEPTH)
+                    .map(FixedHash::try_from)
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| {
+                        obscure_error_if_true(
+                            report_error_flag,
+                            Status::invalid_argument(format!("Invalid cursor block hash '{}'", e)),
+                        )
+                    })?;
+                (Some(cursor.height), hashes)
+            },
+            None => (None, Vec::new()),
+        };
+        trace!(
+            target: LOG_TARGET,
+            "Incoming GRPC request for GetChainDelta: cursor height: {:?}, max_blocks: {}",
+            cursor_height,
+            max_blocks
+        );
+
+        let mut handler = self.node_service.clone();
+        let tip_height = handler
+            .get_metadata()
+            .await
+            .map_err(|e| obscure_error_if_true(report_error_flag, Status::internal(e.to_string())))?
+            .best_block_height();
+
+        let mut reorg = None;
+        let resume_height = match cursor_height {
+            Some(cursor_height) => {
+                let lowest_height = cursor_height.saturating_sub(cursor_hashes.len().saturating_sub(1) as u64);
+                if lowest_height > tip_height {
+                    return Err(Status::unavailable(format!(
+                        "The cursor is above the tip of this node at height {}",
+                        tip_height
+                    )));
+                }
+                let main_chain = if cursor_hashes.is_empty() {
+                    Vec::new()
+                } else {
+                    handler
+                        .get_headers(lowest_height..=cursor_height.min(tip_height))
+                        .await
+                        .map_err(|e| obscure_error_if_true(report_error_flag, Status::internal(e.to_string())))?
+                };
+                let resume_point = ResumePoint::find(cursor_height, &cursor_hashes, |height| {
+                    main_chain
+                        .iter()
+                        .find(|header| header.height() == height)
+                        .map(|header| *header.hash())
+                });
+                let resume_height = resume_point.resume_height(cursor_height).ok_or_else(|| {
+                    Status::failed_precondition(
+                        "None of the cursor blocks are in the main chain. Resume from a cursor with a lower height \
+                         and no block hashes.",
+                    )
+                })?;
+                if let ResumePoint::Reorg { fork_height } = resume_point {
+                    reorg = main_chain
+                        .iter()
+                        .find(|header| header.height() == fork_height)
+                        .map(|header| tari_rpc::ChainReorgMarker {
+                            fork_height,
+                            fork_block_hash: header.hash().to_vec(),
+                        });
+                }
+                Some(resume_height)
+            },
+            None => None,
+        };
+
+        let start_height = resume_height.map_or(0, |height| height.saturating_add(1));
+        let end_height = start_height.saturating_add(max_blocks - 1).min(tip_height);
+        let blocks = if start_height > end_height {
+            Vec::new()
+        } else {
+            handler
+                .get_blocks(start_height..=end_height, true)
+                .await
+                .map_err(|e| obscure_error_if_true(report_error_flag, Status::internal(e.to_string())))?
+        };
+        // The chain can be reorganised after the cursor was checked
+        let resume_hash = resume_height.and_then(|height| {
+            let depth = usize::try_from(cursor_height.unwrap_or(height).saturating_sub(height)).ok()?;
+            cursor_hashes.get(depth)
+        });
+        if let (Some(first), Some(resume_hash)) = (blocks.first(), resume_hash) {
+            if first.header().prev_hash != *resume_hash {
+                return Err(Status::aborted(
+                    "The chain was reorganised while the request was handled. Retry the request.",
+                ));
+            }
+        }
+
+        let new_block_hashes = blocks.iter().rev().map(|block| *block.hash()).collect::<Vec<_>>();
+        let next_cursor = blocks
+            .last()
+            .map(|block| block.header().height)
+            .or(resume_height)
+            .map(|height| tari_rpc::ChainDeltaCursor {
+                height,
+                block_hashes: next_cursor_hashes(
+                    &new_block_hashes,
+                    cursor_height.unwrap_or_default(),
+                    &cursor_hashes,
+                    resume_height.unwrap_or_default(),
+                )
+                .iter()
+                .map(|hash| hash.to_vec())
+                .collect(),
+            });
+        let blocks = blocks
+            .iter()
+            .map(|block| {
+                let header = block.header();
+                let body = &block.block().body;
+                tari_rpc::BlockDelta {
+                    height: header.height,
+                    block_hash: block.hash().to_vec(),
+                    prev_hash: header.prev_hash.to_vec(),
+                    timestamp: header.timestamp.as_u64(),
+                    kernels: body.kernels().iter().cloned().map(Into::into).collect(),
+                    outputs: body
+                        .outputs()
+                        .iter()
+                        .map(|output| CompactOutput::from(output).into())
+                        .collect(),
+                    spent_output_hashes: body.inputs().iter().map(|input| input.output_hash().to_vec()).collect(),
+                }
+            })
+            .collect();
+
+        Ok(Response::new(tari_rpc::GetChainDeltaResponse {
+            reorg,
+            blocks,
+            next_cursor,
+            tip_height,
+        }))
+    }
+}
+
+/// Converts a peer, including the sync quality statistics recorded for it
+fn peer_to_grpc(peer: Peer, sync_quality: &SyncPeerQualityTracker) -> tari_rpc::Peer {
+    let stats = sync_quality.get_stats(&peer.node_id);
+    let mut peer = tari_rpc::Peer::from(peer);
+    peer.sync_quality = stats.map(Into::into);
+    peer
+}
+
+fn software_update_to_grpc(update: Option<&SoftwareUpdate>) -> tari_rpc::SoftwareUpdate {
+    match update {
+        Some(update) => tari_rpc::SoftwareUpdate {
+            has_update: true,
+            version: update.version().to_string(),
+            sha: update.to_hash_hex(),
+            download_url: update.download_url().to_string(),
+            staged_path: update
+                .staged_path()
+                .map(|path| path.display().to_string())
+                .unwrap_or_default(),
+        },
+        None => tari_rpc::SoftwareUpdate::default(),
+    }
+}
+
+/// Validates a light wallet height range and spawns a task that streams one response per block in the range, built
+/// by `to_response`. Blocks are fetched in compact form since light wallet responses only need spent output hashes.
+async fn stream_light_wallet_blocks<T, F>(
+    mut handler: LocalNodeCommsInterface,
+    start_height: u64,
+    end_height: u64,
+    report_error_flag: bool,
+    mut tx: mpsc::Sender<Result<T, Status>>,
+    to_response: F,
+) -> Result<(), Status>
+where
+    T: Send + 'static,
+    F: Fn(&HistoricalBlock) -> T + Send + 'static,
+{
+    let num_requested = end_height.checked_sub(start_height).ok_or_else(|| {
+        obscure_error_if_true(
+            report_error_flag,
+            Status::invalid_argument("Start height is more than end height"),
+        )
+    })?;
+    if num_requested >= LIGHT_WALLET_MAX_HEIGHTS {
+        return Err(obscure_error_if_true(
+            report_error_flag,
+            Status::invalid_argument(format!(
+                "Number of blocks requested exceeds maximum. Expected less than {} but got {}",
+                LIGHT_WALLET_MAX_HEIGHTS,
+                num_requested.saturating_add(1)
+            )),
+        ));
+    }
+    let page_iter =
+        NonOverlappingIntegerPairIter::new(start_height, end_height.saturating_add(1), LIGHT_WALLET_PAGE_SIZE)
+            .map_err(|e| obscure_error_if_true(report_error_flag, Status::invalid_argument(e)))?;
+
+    task::spawn(async move {
+        for (start, end) in page_iter {
+            let blocks = match handler.get_blocks(start..=end, true).await {
+                Ok(blocks) => blocks,
+                Err(e) => {
+                    warn!(target: LOG_TARGET, "Base node service error: {:?}", e);
+                    let _ignore = tx
+                        .send(Err(obscure_error_if_true(
+                            report_error_flag,
+                            Status::internal(e.to_string()),
+                        )))
+                        .await;
+                    return;
+                },
+            };
+            if blocks.is_empty() {
+                return;
+            }
+            for block in &blocks {
+                if tx.send(Ok(to_response(block))).await.is_err() {
+                    trace!(
+                        target: LOG_TARGET,
+                        "[light wallet] Client has disconnected before stream completed"
+                    );
+                    return;
+                }
+            }
+        }
+    });
+    Ok(())
 }
 
 enum BlockGroupType {
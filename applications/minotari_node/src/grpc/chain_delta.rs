@@ -0,0 +1,134 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Resume cursors for indexers that follow the chain with `GetChainDelta`. A cursor holds the height of the last block
+//! that the indexer processed and the hashes of that block and the blocks below it. When the block at the cursor is no
+//! longer in the main chain, the highest cursor block that still is tells the indexer how far to roll back.
+
+use std::convert::TryFrom;
+
+use tari_common_types::types::FixedHash;
+
+/// The number of block hashes kept in a cursor, which is the deepest reorg that an indexer can follow
+pub const CHAIN_DELTA_CURSOR_DEPTH: usize = 20;
+
+/// Where an indexer resumes following the chain from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResumePoint {
+    /// The block at the cursor is in the main chain
+    Continue,
+    /// The block at the cursor is no longer in the main chain. Everything above `fork_height`, which is the highest
+    /// cursor block that is, has been reorged out.
+    Reorg { fork_height: u64 },
+    /// None of the cursor blocks are in the main chain
+    Unknown,
+}
+
+impl ResumePoint {
+    /// Compares the cursor hashes, ordered from `cursor_height` down, with the hashes of the main chain at the same
+    /// heights. A cursor without hashes is trusted to be in the main chain.
+    pub fn find<F>(cursor_height: u64, cursor_hashes: &[FixedHash], main_chain_hash: F) -> Self
+    where F: Fn(u64) -> Option<FixedHash> {
+        if cursor_hashes.is_empty() {
+            return ResumePoint::Continue;
+        }
+        let in_main_chain = cursor_hashes.iter().zip(0u64..).find_map(|(hash, depth)| {
+            let height = cursor_height.checked_sub(depth)?;
+            (main_chain_hash(height)? == *hash).then_some(height)
+        });
+        match in_main_chain {
+            Some(height) if height == cursor_height => ResumePoint::Continue,
+            Some(fork_height) => ResumePoint::Reorg { fork_height },
+            None => ResumePoint::Unknown,
+        }
+    }
+
+    /// The height of the last block that the indexer has in common with the main chain
+    pub fn resume_height(self, cursor_height: u64) -> Option<u64> {
+        match self {
+            ResumePoint::Continue => Some(cursor_height),
+            ResumePoint::Reorg { fork_height } => Some(fork_height),
+            ResumePoint::Unknown => None,
+        }
+    }
+}
+
+/// The hashes of the next cursor: the hashes of the new blocks, ordered from the highest down, followed by the hashes
+/// of the previous cursor from its resume height down
+pub fn next_cursor_hashes(
+    new_block_hashes: &[FixedHash],
+    cursor_height: u64,
+    cursor_hashes: &[FixedHash],
+    resume_height: u64,
+) -> Vec<FixedHash> {
+    let skip = usize::try_from(cursor_height.saturating_sub(resume_height)).unwrap_or(usize::MAX);
+    new_block_hashes
+        .iter()
+        .chain(cursor_hashes.iter().skip(skip))
+        .take(CHAIN_DELTA_CURSOR_DEPTH)
+        .copied()
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn hash(n: u8) -> FixedHash {
+        FixedHash::from([n; 32])
+    }
+
+    /// A main chain where the block at each height has the hash `height`, up to and including `tip`
+    fn main_chain(tip: u64) -> impl Fn(u64) -> Option<FixedHash> {
+        move |height| (height <= tip).then(|| hash(u8::try_from(height).unwrap()))
+    }
+
+    #[test]
+    fn it_continues_from_a_cursor_in_the_main_chain() {
+        let cursor = [hash(5), hash(4), hash(3)];
+        assert_eq!(ResumePoint::find(5, &cursor, main_chain(10)), ResumePoint::Continue);
+        // A cursor without hashes is trusted
+        assert_eq!(ResumePoint::find(5, &[], main_chain(10)), ResumePoint::Continue);
+    }
+
+    #[test]
+    fn it_finds_the_fork_after_a_reorg() {
+        let cursor = [hash(50), hash(40), hash(3), hash(2)];
+        let point = ResumePoint::find(5, &cursor, main_chain(10));
+        assert_eq!(point, ResumePoint::Reorg { fork_height: 3 });
+        assert_eq!(point.resume_height(5), Some(3));
+        // The main chain can be shorter than the cursor after a reorg
+        assert_eq!(ResumePoint::find(5, &cursor, main_chain(4)), ResumePoint::Reorg {
+            fork_height: 3
+        });
+    }
+
+    #[test]
+    fn it_does_not_resume_from_a_cursor_that_is_not_in_the_main_chain() {
+        let cursor = [hash(50), hash(40)];
+        let point = ResumePoint::find(5, &cursor, main_chain(10));
+        assert_eq!(point, ResumePoint::Unknown);
+        assert_eq!(point.resume_height(5), None);
+        // Cursor hashes below the genesis block are ignored
+        assert_eq!(
+            ResumePoint::find(0, &[hash(9), hash(8)], main_chain(10)),
+            ResumePoint::Unknown
+        );
+    }
+
+    #[test]
+    fn it_builds_the_next_cursor() {
+        let cursor = [hash(50), hash(40), hash(3), hash(2)];
+        assert_eq!(next_cursor_hashes(&[hash(5), hash(4)], 5, &cursor, 3), vec![
+            hash(5),
+            hash(4),
+            hash(3),
+            hash(2)
+        ]);
+        let new_blocks = (0..30).rev().map(hash).collect::<Vec<_>>();
+        assert_eq!(
+            next_cursor_hashes(&new_blocks, 5, &cursor, 5),
+            new_blocks[..CHAIN_DELTA_CURSOR_DEPTH].to_vec()
+        );
+    }
+}
@@ -0,0 +1,70 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use log::*;
+use minotari_app_grpc::tari_rpc::base_node_server::BaseNodeServer;
+use tari_core::base_node::state_machine_service::states::StatusInfo;
+use tari_shutdown::ShutdownSignal;
+use tokio::sync::watch;
+use tonic_health::server::HealthReporter;
+
+use crate::grpc::base_node_grpc_server::BaseNodeGrpcServer;
+
+const LOG_TARGET: &str = "minotari::base_node::grpc::health";
+
+/// Keeps the `grpc.health.v1.Health` serving status of the base node service in step with the state machine. The
+/// base node service is reported as `SERVING` only once the node is synced, so that readiness probes do not route
+/// traffic to a node that is still syncing. The overall server status (the empty service name) remains `SERVING`
+/// for as long as the gRPC server is up.
+pub async fn report_sync_status(
+    mut reporter: HealthReporter,
+    mut status_info: watch::Receiver<StatusInfo>,
+    mut shutdown: ShutdownSignal,
+) {
+    let mut is_serving = None;
+    loop {
+        let is_synced = status_info.borrow().state_info.is_synced();
+        if is_serving != Some(is_synced) {
+            debug!(
+                target: LOG_TARGET,
+                "Base node gRPC health status changed to {}",
+                if is_synced { "SERVING" } else { "NOT_SERVING" }
+            );
+            if is_synced {
+                reporter.set_serving::<BaseNodeServer<BaseNodeGrpcServer>>().await;
+            } else {
+                reporter.set_not_serving::<BaseNodeServer<BaseNodeGrpcServer>>().await;
+            }
+            is_serving = Some(is_synced);
+        }
+
+        tokio::select! {
+            changed = status_info.changed() => {
+                if changed.is_err() {
+                    break;
+                }
+            },
+            _ = shutdown.wait() => break,
+        }
+    }
+    reporter.set_not_serving::<BaseNodeServer<BaseNodeGrpcServer>>().await;
+}
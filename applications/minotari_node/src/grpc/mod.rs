@@ -22,5 +22,8 @@
 
 pub mod base_node_grpc_server;
 pub mod blocks;
+pub mod chain_delta;
 pub mod hash_rate;
+pub mod health;
 pub mod helpers;
+pub mod public_server;
@@ -0,0 +1,231 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! The public server profile for the gRPC server, for volunteers that expose their node to light wallets. Requests are
+//! rate limited per client IP address with a token bucket, only read-only methods can be enabled, and responses and
+//! concurrent streams are capped.
+
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use log::*;
+use serde::{Deserialize, Serialize};
+use tari_common::configuration::ConfigList;
+use tonic::{service::Interceptor, Request, Status};
+
+use crate::grpc_method::GrpcMethod;
+
+const LOG_TARGET: &str = "minotari::base_node::grpc::public_server";
+
+/// Buckets that have been idle for this long are full again and are removed
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PublicGrpcConfig {
+    /// Enable the public server profile. When enabled, only the read-only methods in `allow_methods` are available,
+    /// regardless of the other gRPC settings.
+    pub enabled: bool,
+    /// The sustained number of requests per second allowed from each IP address
+    pub requests_per_second: u32,
+    /// The number of requests that each IP address may make in a burst
+    pub burst: u32,
+    /// The methods that are available. Methods that are not read-only are ignored.
+    pub allow_methods: ConfigList<GrpcMethod>,
+    /// The maximum size of a single response message in bytes
+    pub max_response_size: usize,
+    /// The maximum number of concurrent streams on a single connection
+    pub max_concurrent_streams: u32,
+}
+
+impl Default for PublicGrpcConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            requests_per_second: 10,
+            burst: 50,
+            allow_methods: vec![
+                GrpcMethod::GetVersion,
+                GrpcMethod::GetConstants,
+                GrpcMethod::GetTipInfo,
+                GrpcMethod::GetHeaderByHash,
+                GrpcMethod::GetBlockFilters,
+                GrpcMethod::GetCompactBlockOutputs,
+                GrpcMethod::FetchMatchingUtxos,
+                GrpcMethod::TransactionState,
+            ]
+            .into(),
+            max_response_size: 4 * 1024 * 1024,
+            max_concurrent_streams: 8,
+        }
+    }
+}
+
+impl PublicGrpcConfig {
+    /// Whether the method is available on the public server
+    pub fn is_method_allowed(&self, method: GrpcMethod) -> bool {
+        method.is_read_only() && self.allow_methods.contains(&method)
+    }
+
+    /// The methods in `allow_methods` that are ignored because they are not read-only
+    pub fn ignored_methods(&self) -> Vec<GrpcMethod> {
+        self.allow_methods
+            .iter()
+            .copied()
+            .filter(|m| !m.is_read_only())
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+#[derive(Debug, Default)]
+struct RateLimiterState {
+    buckets: HashMap<IpAddr, TokenBucket>,
+    last_pruned: Option<Instant>,
+}
+
+/// A token bucket per client IP address, shared between all connections
+#[derive(Debug, Clone)]
+pub struct IpRateLimiter {
+    requests_per_second: f64,
+    burst: f64,
+    state: Arc<Mutex<RateLimiterState>>,
+}
+
+impl IpRateLimiter {
+    pub fn new(requests_per_second: u32, burst: u32) -> Self {
+        Self {
+            requests_per_second: f64::from(requests_per_second),
+            burst: f64::from(burst.max(1)),
+            state: Arc::new(Mutex::new(RateLimiterState::default())),
+        }
+    }
+
+    /// Takes a token from the bucket of the address, returning false if it is empty
+    pub fn check(&self, addr: IpAddr) -> bool {
+        self.check_at(addr, Instant::now())
+    }
+
+    fn check_at(&self, addr: IpAddr, now: Instant) -> bool {
+        let mut state = self.state.lock().expect("IpRateLimiter lock poisoned");
+        self.prune_idle(&mut state, now);
+        let bucket = state.buckets.entry(addr).or_insert_with(|| TokenBucket {
+            tokens: self.burst,
+            last_refill: now,
+        });
+        self.refill(bucket, now);
+        if bucket.tokens < 1.0 {
+            return false;
+        }
+        bucket.tokens -= 1.0;
+        true
+    }
+
+    fn refill(&self, bucket: &mut TokenBucket, now: Instant) {
+        let elapsed = now.saturating_duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.requests_per_second).min(self.burst);
+        bucket.last_refill = now;
+    }
+
+    /// Removes buckets that have fully refilled, as they are indistinguishable from a new bucket
+    fn prune_idle(&self, state: &mut RateLimiterState, now: Instant) {
+        if state
+            .last_pruned
+            .is_some_and(|t| now.saturating_duration_since(t) < PRUNE_INTERVAL)
+        {
+            return;
+        }
+        state.last_pruned = Some(now);
+        state.buckets.retain(|_, bucket| {
+            self.refill(bucket, now);
+            bucket.tokens < self.burst
+        });
+    }
+}
+
+/// Rate limits requests by client IP address before passing them to the inner interceptor. Requests are passed
+/// through unchanged if no limiter is set.
+#[derive(Clone)]
+pub struct RateLimitInterceptor<I> {
+    inner: I,
+    limiter: Option<IpRateLimiter>,
+}
+
+impl<I> RateLimitInterceptor<I> {
+    pub fn new(inner: I, limiter: Option<IpRateLimiter>) -> Self {
+        Self { inner, limiter }
+    }
+}
+
+impl<I: Interceptor> Interceptor for RateLimitInterceptor<I> {
+    fn call(&mut self, request: Request<()>) -> Result<Request<()>, Status> {
+        if let Some(limiter) = &self.limiter {
+            // Connections without a remote address, such as over a unix socket, are local and not limited
+            if let Some(addr) = request.remote_addr() {
+                if !limiter.check(addr.ip()) {
+                    debug!(target: LOG_TARGET, "gRPC request rate limit exceeded by {}", addr.ip());
+                    return Err(Status::resource_exhausted("Request rate limit exceeded"));
+                }
+            }
+        }
+        self.inner.call(request)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::net::Ipv4Addr;
+
+    use super::*;
+
+    #[test]
+    fn it_limits_requests_per_ip() {
+        let limiter = IpRateLimiter::new(2, 4);
+        let now = Instant::now();
+        let a = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+        let b = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2));
+        for _ in 0..4 {
+            assert!(limiter.check_at(a, now));
+        }
+        assert!(!limiter.check_at(a, now));
+        // Other addresses are unaffected
+        assert!(limiter.check_at(b, now));
+        // Tokens are refilled at the sustained rate
+        assert!(limiter.check_at(a, now + Duration::from_millis(500)));
+        assert!(!limiter.check_at(a, now + Duration::from_millis(500)));
+    }
+
+    #[test]
+    fn it_prunes_idle_buckets() {
+        let limiter = IpRateLimiter::new(1, 1);
+        let now = Instant::now();
+        assert!(limiter.check_at(IpAddr::V4(Ipv4Addr::LOCALHOST), now));
+        assert!(limiter.check_at(IpAddr::V4(Ipv4Addr::BROADCAST), now + PRUNE_INTERVAL));
+        assert_eq!(limiter.state.lock().unwrap().buckets.len(), 1);
+    }
+
+    #[test]
+    fn it_only_allows_read_only_methods() {
+        let config = PublicGrpcConfig {
+            allow_methods: vec![GrpcMethod::GetTipInfo, GrpcMethod::SubmitBlock].into(),
+            ..Default::default()
+        };
+        assert!(config.is_method_allowed(GrpcMethod::GetTipInfo));
+        assert!(!config.is_method_allowed(GrpcMethod::SubmitBlock));
+        assert!(!config.is_method_allowed(GrpcMethod::GetBlocks));
+        assert_eq!(config.ignored_methods(), vec![GrpcMethod::SubmitBlock]);
+        assert!(PublicGrpcConfig::default()
+            .allow_methods
+            .iter()
+            .all(|m| m.is_read_only()));
+    }
+}
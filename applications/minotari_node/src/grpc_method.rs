@@ -38,6 +38,7 @@ pub enum GrpcMethod {
     #[default]
     GetVersion,
     CheckForUpdates,
+    StreamSoftwareUpdates,
     GetTokensInCirculation,
     GetNetworkDifficulty,
     GetNewBlockTemplate,
@@ -65,11 +66,29 @@ pub enum GrpcMethod {
     GetShardKey,
     GetTemplateRegistrations,
     GetSideChainUtxos,
+    ExportBanList,
+    ImportBanList,
+    GetNetworkAlerts,
+    GetPeerConnectionDetails,
+    GetBurntByClaimKey,
+    GetBlockFilters,
+    GetCompactBlockOutputs,
+    GenerateBlocks,
+    SetActiveProfile,
+    GetLogFilters,
+    SetLogFilters,
+    GetUtxoSetStats,
+    VerifyPaymentProof,
+    GetValidatorNodeSet,
+    ProbeBroadcast,
+    GetChainDelta,
+    RegisterPushWatch,
+    UnregisterPushWatch,
 }
 
 impl GrpcMethod {
     /// All the GRPC methods as a fixed array
-    pub const ALL_VARIANTS: [GrpcMethod; 36] = [
+    pub const ALL_VARIANTS: [GrpcMethod; 55] = [
         GrpcMethod::ListHeaders,
         GrpcMethod::GetHeaderByHash,
         GrpcMethod::GetBlocks,
@@ -79,6 +98,7 @@ impl GrpcMethod {
         GrpcMethod::GetBlockFees,
         GrpcMethod::GetVersion,
         GrpcMethod::CheckForUpdates,
+        GrpcMethod::StreamSoftwareUpdates,
         GrpcMethod::GetTokensInCirculation,
         GrpcMethod::GetNetworkDifficulty,
         GrpcMethod::GetNewBlockTemplate,
@@ -106,11 +126,52 @@ impl GrpcMethod {
         GrpcMethod::GetShardKey,
         GrpcMethod::GetTemplateRegistrations,
         GrpcMethod::GetSideChainUtxos,
+        GrpcMethod::ExportBanList,
+        GrpcMethod::ImportBanList,
+        GrpcMethod::GetNetworkAlerts,
+        GrpcMethod::GetPeerConnectionDetails,
+        GrpcMethod::GetBurntByClaimKey,
+        GrpcMethod::GetBlockFilters,
+        GrpcMethod::GetCompactBlockOutputs,
+        GrpcMethod::GenerateBlocks,
+        GrpcMethod::SetActiveProfile,
+        GrpcMethod::GetLogFilters,
+        GrpcMethod::SetLogFilters,
+        GrpcMethod::GetUtxoSetStats,
+        GrpcMethod::VerifyPaymentProof,
+        GrpcMethod::GetValidatorNodeSet,
+        GrpcMethod::ProbeBroadcast,
+        GrpcMethod::GetChainDelta,
+        GrpcMethod::RegisterPushWatch,
+        GrpcMethod::UnregisterPushWatch,
     ];
+
+    /// Whether the method only reads state. Methods that change the chain, the mempool, the peer database or the node
+    /// configuration, or that generate mining templates, are not read-only.
+    pub fn is_read_only(self) -> bool {
+        !matches!(
+            self,
+            GrpcMethod::GetNewBlockTemplate |
+                GrpcMethod::GetNewBlock |
+                GrpcMethod::GetNewBlockWithCoinbases |
+                GrpcMethod::GetNewBlockTemplateWithCoinbases |
+                GrpcMethod::GetNewBlockBlob |
+                GrpcMethod::SubmitBlock |
+                GrpcMethod::SubmitBlockBlob |
+                GrpcMethod::SubmitTransaction |
+                GrpcMethod::ImportBanList |
+                GrpcMethod::GenerateBlocks |
+                GrpcMethod::SetActiveProfile |
+                GrpcMethod::SetLogFilters |
+                GrpcMethod::ProbeBroadcast |
+                GrpcMethod::RegisterPushWatch |
+                GrpcMethod::UnregisterPushWatch
+        )
+    }
 }
 
 impl IntoIterator for GrpcMethod {
-    type IntoIter = std::array::IntoIter<GrpcMethod, 36>;
+    type IntoIter = std::array::IntoIter<GrpcMethod, 55>;
     type Item = GrpcMethod;
 
     fn into_iter(self) -> Self::IntoIter {
@@ -134,6 +195,7 @@ impl FromStr for GrpcMethod {
             "get_block_fees" => Ok(GrpcMethod::GetBlockFees),
             "get_version" => Ok(GrpcMethod::GetVersion),
             "check_for_updates" => Ok(GrpcMethod::CheckForUpdates),
+            "stream_software_updates" => Ok(GrpcMethod::StreamSoftwareUpdates),
             "get_tokens_in_circulation" => Ok(GrpcMethod::GetTokensInCirculation),
             "get_network_difficulty" => Ok(GrpcMethod::GetNetworkDifficulty),
             "get_new_block_template" => Ok(GrpcMethod::GetNewBlockTemplate),
@@ -161,6 +223,24 @@ impl FromStr for GrpcMethod {
             "get_shard_key" => Ok(GrpcMethod::GetShardKey),
             "get_template_registrations" => Ok(GrpcMethod::GetTemplateRegistrations),
             "get_side_chain_utxos" => Ok(GrpcMethod::GetSideChainUtxos),
+            "export_ban_list" => Ok(GrpcMethod::ExportBanList),
+            "import_ban_list" => Ok(GrpcMethod::ImportBanList),
+            "get_network_alerts" => Ok(GrpcMethod::GetNetworkAlerts),
+            "get_peer_connection_details" => Ok(GrpcMethod::GetPeerConnectionDetails),
+            "get_burnt_by_claim_key" => Ok(GrpcMethod::GetBurntByClaimKey),
+            "get_block_filters" => Ok(GrpcMethod::GetBlockFilters),
+            "get_compact_block_outputs" => Ok(GrpcMethod::GetCompactBlockOutputs),
+            "generate_blocks" => Ok(GrpcMethod::GenerateBlocks),
+            "set_active_profile" => Ok(GrpcMethod::SetActiveProfile),
+            "get_log_filters" => Ok(GrpcMethod::GetLogFilters),
+            "set_log_filters" => Ok(GrpcMethod::SetLogFilters),
+            "get_utxo_set_stats" => Ok(GrpcMethod::GetUtxoSetStats),
+            "verify_payment_proof" => Ok(GrpcMethod::VerifyPaymentProof),
+            "get_validator_node_set" => Ok(GrpcMethod::GetValidatorNodeSet),
+            "probe_broadcast" => Ok(GrpcMethod::ProbeBroadcast),
+            "get_chain_delta" => Ok(GrpcMethod::GetChainDelta),
+            "register_push_watch" => Ok(GrpcMethod::RegisterPushWatch),
+            "unregister_push_watch" => Ok(GrpcMethod::UnregisterPushWatch),
             _ => Err(format!("'{}' not supported", s)),
         }
     }
@@ -229,6 +309,7 @@ mod tests {
                 GrpcMethod::GetBlockFees => count += 1,
                 GrpcMethod::GetVersion => count += 1,
                 GrpcMethod::CheckForUpdates => count += 1,
+                GrpcMethod::StreamSoftwareUpdates => count += 1,
                 GrpcMethod::GetTokensInCirculation => count += 1,
                 GrpcMethod::GetNetworkDifficulty => count += 1,
                 GrpcMethod::GetNewBlockTemplate => count += 1,
@@ -256,6 +337,24 @@ mod tests {
                 GrpcMethod::GetShardKey => count += 1,
                 GrpcMethod::GetTemplateRegistrations => count += 1,
                 GrpcMethod::GetSideChainUtxos => count += 1,
+                GrpcMethod::ExportBanList => count += 1,
+                GrpcMethod::ImportBanList => count += 1,
+                GrpcMethod::GetNetworkAlerts => count += 1,
+                GrpcMethod::GetPeerConnectionDetails => count += 1,
+                GrpcMethod::GetBurntByClaimKey => count += 1,
+                GrpcMethod::GetBlockFilters => count += 1,
+                GrpcMethod::GetCompactBlockOutputs => count += 1,
+                GrpcMethod::GenerateBlocks => count += 1,
+                GrpcMethod::SetActiveProfile => count += 1,
+                GrpcMethod::GetLogFilters => count += 1,
+                GrpcMethod::SetLogFilters => count += 1,
+                GrpcMethod::GetUtxoSetStats => count += 1,
+                GrpcMethod::VerifyPaymentProof => count += 1,
+                GrpcMethod::GetValidatorNodeSet => count += 1,
+                GrpcMethod::ProbeBroadcast => count += 1,
+                GrpcMethod::GetChainDelta => count += 1,
+                GrpcMethod::RegisterPushWatch => count += 1,
+                GrpcMethod::UnregisterPushWatch => count += 1,
             }
         }
         assert_eq!(count, GrpcMethod::ALL_VARIANTS.len());
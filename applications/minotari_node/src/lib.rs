@@ -26,39 +26,67 @@ minotari_app_utilities::deny_non_64_bit_archs!();
 #[macro_use]
 mod table;
 
+mod backup;
 mod bootstrap;
 mod builder;
 pub mod cli;
 mod commands;
 pub mod config;
+mod config_reload;
+mod diagnostics;
+#[cfg(feature = "explorer")]
+mod explorer;
 mod grpc;
 mod grpc_method;
+mod light_wallet_push;
 #[cfg(feature = "metrics")]
 mod metrics;
 mod recovery;
+pub mod setup_wizard;
+mod status_beacon;
 mod utils;
-use std::{process, sync::Arc};
+use std::{path::Path, process, sync::Arc};
 
 use commands::{cli_loop::CliLoop, command::CommandContext};
 use futures::FutureExt;
 pub use grpc_method::GrpcMethod;
 use log::*;
-use minotari_app_grpc::{authentication::ServerAuthenticationInterceptor, tls::identity::read_identity};
+use minotari_app_grpc::{
+    authentication::ServerAuthenticationInterceptor,
+    reflection::reflection_service,
+    tls::identity::read_identity,
+};
 use minotari_app_utilities::common_cli_args::CommonCliArgs;
 use tari_common::{
-    configuration::bootstrap::{grpc_default_port, ApplicationType},
+    configuration::{
+        bootstrap::{grpc_default_port, ApplicationType},
+        Network,
+    },
     exit_codes::{ExitCode, ExitError},
 };
 use tari_common_types::grpc_authentication::GrpcAuthentication;
 use tari_comms::{multiaddr::Multiaddr, utils::multiaddr::multiaddr_to_socketaddr, NodeIdentity};
+use tari_core::base_node::state_machine_service::states::StatusInfo;
 use tari_shutdown::{Shutdown, ShutdownSignal};
-use tokio::task;
-use tonic::transport::{Identity, Server, ServerTlsConfig};
+use tokio::{sync::watch, task};
+use tonic::{
+    service::interceptor::InterceptedService,
+    transport::{Identity, Server, ServerTlsConfig},
+};
 
-use crate::cli::Cli;
-pub use crate::config::{ApplicationConfig, BaseNodeConfig, DatabaseType};
+#[cfg(feature = "explorer")]
+pub use crate::explorer::ExplorerConfig;
 #[cfg(feature = "metrics")]
 pub use crate::metrics::MetricsConfig;
+pub use crate::{
+    builder::{configure_and_initialize_node, BaseNodeContext},
+    config::{ApplicationConfig, BaseNodeConfig, DatabaseType},
+};
+use crate::{
+    cli::Cli,
+    config_reload::ConfigReloader,
+    grpc::public_server::{IpRateLimiter, PublicGrpcConfig, RateLimitInterceptor},
+};
 
 const LOG_TARGET: &str = "minotari::base_node::app";
 
@@ -79,17 +107,23 @@ pub async fn run_base_node(
             config: config_path.into_os_string().into_string().unwrap(),
             log_config: None,
             log_path: None,
+            log_format: None,
             network: None,
+            profile: None,
             config_property_overrides: vec![],
         },
         init: true,
         rebuild_db: false,
         non_interactive_mode: true,
         watch: None,
+        dashboard: false,
         profile_with_tokio_console: false,
         grpc_enabled: false,
         mining_enabled: false,
         second_layer_grpc_enabled: false,
+        light_wallet_server_enabled: false,
+        regtest: false,
+        privacy: false,
     };
 
     run_base_node_with_cli(node_identity, config, cli, shutdown).await
@@ -102,16 +136,37 @@ pub async fn run_base_node_with_cli(
     cli: Cli,
     shutdown: Shutdown,
 ) -> Result<(), ExitError> {
+    if config.base_node.regtest_enabled && config.base_node.network != Network::LocalNet {
+        return Err(ExitError::new(
+            ExitCode::ConfigError,
+            "Regtest mode is only available on localnet",
+        ));
+    }
+
+    let config_reloader = ConfigReloader::new(&cli, &config);
+
     #[cfg(feature = "metrics")]
     {
-        metrics::install(
+        let metrics_server = metrics::install(
             ApplicationType::BaseNode,
             &node_identity,
             &config.metrics,
             shutdown.to_signal(),
         );
+        task::spawn(metrics::apply_config_updates(
+            metrics_server,
+            config_reloader.subscribe(),
+        ));
     }
 
+    let _telemetry_guard = tari_telemetry::install(
+        ApplicationType::BaseNode.as_config_str(),
+        &node_identity.node_id().to_string(),
+        &config.telemetry,
+        cli.profile_with_tokio_console,
+    )
+    .map_err(|e| ExitError::new(ExitCode::ConfigError, e))?;
+
     log_mdc::insert("node-public-key", node_identity.public_key().to_string());
     log_mdc::insert("node-id", node_identity.node_id().to_string());
     if let Some(grpc) = config.base_node.grpc_address.as_ref() {
@@ -130,15 +185,50 @@ pub async fn run_base_node_with_cli(
     // Build, node, build!
     let ctx = builder::configure_and_initialize_node(config.clone(), node_identity, shutdown.to_signal()).await?;
 
+    #[cfg(feature = "explorer")]
+    explorer::install(&ctx, &config.explorer, shutdown.to_signal());
+    backup::install(&ctx, &config.base_node, shutdown.to_signal())?;
+    status_beacon::install(&ctx, &config.base_node.status_beacon, shutdown.to_signal());
+    let push_watches = light_wallet_push::install(&ctx, &config.base_node.light_wallet_push, shutdown.to_signal());
+    config_reload::install(&ctx, config_reloader, shutdown.to_signal());
+    diagnostics::install(&ctx, shutdown.to_signal());
+
     if config.base_node.grpc_enabled {
         let grpc_address = config.base_node.grpc_address.clone().unwrap_or_else(|| {
             let port = grpc_default_port(ApplicationType::BaseNode, config.base_node.network);
             format!("/ip4/127.0.0.1/tcp/{}", port).parse().unwrap()
         });
         // Go, GRPC, go go
-        let grpc =
-            grpc::base_node_grpc_server::BaseNodeGrpcServer::from_base_node_context(&ctx, config.base_node.clone());
+        // The base path is the directory of the network profile, within the directory of all profiles
+        let profiles_path = config.common.base_path().parent().map(Path::to_path_buf);
+        let grpc = grpc::base_node_grpc_server::BaseNodeGrpcServer::from_base_node_context(
+            &ctx,
+            config.base_node.clone(),
+            profiles_path,
+        )
+        .with_push_watches(push_watches);
         let auth = config.base_node.grpc_authentication.clone();
+        let public_server = &config.base_node.grpc_public_server;
+        if public_server.enabled {
+            info!(
+                target: LOG_TARGET,
+                "GRPC public server profile enabled: {} request(s) per second per IP address (burst {})",
+                public_server.requests_per_second,
+                public_server.burst
+            );
+            for method in public_server.ignored_methods() {
+                warn!(
+                    target: LOG_TARGET,
+                    "`{}` is not read-only and is not available on the GRPC public server", method
+                );
+            }
+        } else if config.base_node.light_wallet_server_enabled && matches!(auth, GrpcAuthentication::None) {
+            warn!(
+                target: LOG_TARGET,
+                "The light wallet server is enabled without GRPC authentication. Configure `grpc_authentication` if \
+                 the GRPC server is reachable by untrusted clients."
+            );
+        }
 
         let mut tls_identity = None;
         if config.base_node.grpc_tls_enabled {
@@ -147,12 +237,20 @@ pub async fn run_base_node_with_cli(
                 .map(Some)
                 .map_err(|e| ExitError::new(ExitCode::TlsConfigurationError, e.to_string()))?;
         }
-        task::spawn(run_grpc(grpc, grpc_address, auth, tls_identity, shutdown.to_signal()));
+        task::spawn(run_grpc(
+            grpc,
+            grpc_address,
+            auth,
+            config.base_node.grpc_public_server.clone(),
+            tls_identity,
+            ctx.get_state_machine_info_channel(),
+            shutdown.to_signal(),
+        ));
     }
 
     // Run, node, run!
     let context = CommandContext::new(&ctx, shutdown);
-    let main_loop = CliLoop::new(context, cli.watch, cli.non_interactive_mode);
+    let main_loop = CliLoop::new(context, cli.watch, cli.dashboard, cli.non_interactive_mode);
     if cli.non_interactive_mode {
         println!("Node started in non-interactive mode (pid = {})", process::id());
     } else {
@@ -182,7 +280,9 @@ async fn run_grpc(
     grpc: grpc::base_node_grpc_server::BaseNodeGrpcServer,
     grpc_address: Multiaddr,
     auth_config: GrpcAuthentication,
+    public_server: PublicGrpcConfig,
     tls_identity: Option<Identity>,
+    status_info: watch::Receiver<StatusInfo>,
     interrupt_signal: ShutdownSignal,
 ) -> Result<(), anyhow::Error> {
     info!(target: LOG_TARGET, "Starting GRPC on {}", grpc_address);
@@ -190,16 +290,38 @@ async fn run_grpc(
     let grpc_address = multiaddr_to_socketaddr(&grpc_address)?;
     let auth = ServerAuthenticationInterceptor::new(auth_config)
         .ok_or(anyhow::anyhow!("Unable to prepare server gRPC authentication"))?;
-    let service = minotari_app_grpc::tari_rpc::base_node_server::BaseNodeServer::with_interceptor(grpc, auth);
+    let mut service = minotari_app_grpc::tari_rpc::base_node_server::BaseNodeServer::new(grpc);
+    let mut limiter = None;
+    if public_server.enabled {
+        service = service.max_encoding_message_size(public_server.max_response_size);
+        limiter = Some(IpRateLimiter::new(public_server.requests_per_second, public_server.burst));
+    }
+    let service = InterceptedService::new(service, RateLimitInterceptor::new(auth, limiter));
+    // The health and reflection services are deliberately not behind the authentication interceptor so that
+    // orchestration probes and tooling can reach them without credentials
+    let (health_reporter, health_service) = tonic_health::server::health_reporter();
+    task::spawn(grpc::health::report_sync_status(
+        health_reporter,
+        status_info,
+        interrupt_signal.clone(),
+    ));
+    let reflection_service = reflection_service()?;
 
     let mut server_builder = if let Some(identity) = tls_identity {
         Server::builder().tls_config(ServerTlsConfig::new().identity(identity))?
     } else {
         Server::builder()
     };
+    if public_server.enabled {
+        server_builder = server_builder
+            .max_concurrent_streams(Some(public_server.max_concurrent_streams))
+            .concurrency_limit_per_connection(usize::try_from(public_server.max_concurrent_streams)?);
+    }
 
     server_builder
         .add_service(service)
+        .add_service(health_service)
+        .add_service(reflection_service)
         .serve_with_shutdown(grpc_address, interrupt_signal.map(|_| ()))
         .await
         .map_err(|err| {
@@ -0,0 +1,301 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Push watches for light wallets. A mobile wallet is usually suspended while it is in the background, so it cannot
+//! notice new blocks itself. Instead it registers its block filter items with the light wallet server, together with
+//! a push relay, a device token and a notification that it has already encrypted. Whenever a new block contains one
+//! of the items, the node posts the notification to the relay, which wakes the app. The node learns which items the
+//! wallet is watching, but not the contents of the notification. Watches are kept in memory only.
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use log::*;
+use serde::{Deserialize, Serialize};
+use tari_common::configuration::serializers;
+use tari_core::{
+    base_node::{
+        comms_interface::{BlockEvent, BlockEventReceiver},
+        state_machine_service::states::StatusInfo,
+    },
+    blocks::{Block, BlockOutputFilter},
+    chain_storage::BlockAddResult,
+};
+use tari_p2p::{
+    privacy::is_loopback_url,
+    push_relay::{post_to_relay, PushRelayRequest},
+};
+use tari_shutdown::ShutdownSignal;
+use thiserror::Error;
+use tokio::{
+    sync::{broadcast, watch},
+    task,
+};
+
+use crate::builder::BaseNodeContext;
+
+const LOG_TARGET: &str = "minotari::base_node::light_wallet_push";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LightWalletPushConfig {
+    /// Accept push watches from light wallets and post notifications for them
+    pub enabled: bool,
+    /// The maximum number of watches held at once
+    pub max_watches: usize,
+    /// The maximum number of filter items in a single watch
+    pub max_items_per_watch: usize,
+    /// The timeout for a single request to a relay
+    #[serde(with = "serializers::seconds")]
+    pub relay_request_timeout: Duration,
+    /// Only accept relays on a loopback address. Set by the privacy profile, since relays are registered at runtime
+    /// and would otherwise be contacted over clearnet.
+    pub loopback_relays_only: bool,
+}
+
+impl Default for LightWalletPushConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_watches: 1_000,
+            max_items_per_watch: 1_000,
+            relay_request_timeout: Duration::from_secs(10),
+            loopback_relays_only: false,
+        }
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum PushWatchError {
+    #[error("Push watches are not enabled on this node")]
+    Disabled,
+    #[error("A push watch needs a relay URL, a device token and at least one filter item")]
+    Incomplete,
+    #[error("A push watch may have at most {0} filter items")]
+    TooManyItems(usize),
+    #[error("This node is not accepting more push watches")]
+    TooManyWatches,
+    #[error("This node only accepts a push relay on a loopback address, not {0}")]
+    ClearnetRelay(String),
+}
+
+/// A registered watch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PushWatch {
+    pub relay_url: String,
+    pub device_token: String,
+    /// The hex encoded encrypted notification, posted to the relay as is
+    pub payload: String,
+    pub items: HashSet<Vec<u8>>,
+}
+
+/// The watches registered over gRPC, keyed by device token
+#[derive(Clone)]
+pub struct PushWatchRegistry {
+    config: LightWalletPushConfig,
+    watches: Arc<RwLock<HashMap<String, PushWatch>>>,
+}
+
+impl PushWatchRegistry {
+    pub fn new(config: LightWalletPushConfig) -> Self {
+        Self {
+            config,
+            watches: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Registers a watch, replacing any previous watch for the same device token
+    pub fn register(&self, watch: PushWatch) -> Result<(), PushWatchError> {
+        if !self.config.enabled {
+            return Err(PushWatchError::Disabled);
+        }
+        if watch.relay_url.is_empty() || watch.device_token.is_empty() || watch.items.is_empty() {
+            return Err(PushWatchError::Incomplete);
+        }
+        if watch.items.len() > self.config.max_items_per_watch {
+            return Err(PushWatchError::TooManyItems(self.config.max_items_per_watch));
+        }
+        if self.config.loopback_relays_only && !is_loopback_url(&watch.relay_url) {
+            return Err(PushWatchError::ClearnetRelay(watch.relay_url));
+        }
+        let mut watches = self.watches.write().expect("PushWatchRegistry lock poisoned");
+        if watches.len() >= self.config.max_watches && !watches.contains_key(&watch.device_token) {
+            return Err(PushWatchError::TooManyWatches);
+        }
+        watches.insert(watch.device_token.clone(), watch);
+        Ok(())
+    }
+
+    /// Removes the watch for the device token, returning true if there was one
+    pub fn unregister(&self, device_token: &str) -> bool {
+        self.watches
+            .write()
+            .expect("PushWatchRegistry lock poisoned")
+            .remove(device_token)
+            .is_some()
+    }
+
+    /// The relay requests for the watches that match any of the block items
+    fn matches(&self, block_items: &HashSet<Vec<u8>>) -> Vec<(String, PushRelayRequest)> {
+        self.watches
+            .read()
+            .expect("PushWatchRegistry lock poisoned")
+            .values()
+            .filter(|watch| !watch.items.is_disjoint(block_items))
+            .map(|watch| {
+                (watch.relay_url.clone(), PushRelayRequest {
+                    device_token: watch.device_token.clone(),
+                    payload: watch.payload.clone(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Creates the push watch registry and, if push watches are enabled, starts posting notifications for new blocks
+pub fn install(ctx: &BaseNodeContext, config: &LightWalletPushConfig, shutdown: ShutdownSignal) -> PushWatchRegistry {
+    let registry = PushWatchRegistry::new(config.clone());
+    if config.enabled {
+        let notifier = PushNotifier {
+            registry: registry.clone(),
+            status: ctx.get_state_machine_info_channel(),
+            relay_request_timeout: config.relay_request_timeout,
+        };
+        task::spawn(notifier.run(ctx.local_node().get_block_event_stream(), shutdown));
+    }
+    registry
+}
+
+struct PushNotifier {
+    registry: PushWatchRegistry,
+    status: watch::Receiver<StatusInfo>,
+    relay_request_timeout: Duration,
+}
+
+impl PushNotifier {
+    async fn run(self, mut block_events: BlockEventReceiver, shutdown: ShutdownSignal) {
+        tokio::pin!(shutdown);
+        loop {
+            tokio::select! {
+                event = block_events.recv() => match event {
+                    Ok(event) => self.handle_event(&event),
+                    Err(broadcast::error::RecvError::Lagged(n)) => {
+                        warn!(target: LOG_TARGET, "Push notifier missed {} block events", n);
+                    },
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+                _ = &mut shutdown => break,
+            }
+        }
+    }
+
+    fn handle_event(&self, event: &BlockEvent) {
+        // Blocks added during initial sync are old news to the wallets
+        if !self.status.borrow().state_info.is_synced() {
+            return;
+        }
+        match event {
+            BlockEvent::ValidBlockAdded(block, BlockAddResult::Ok(_)) => self.notify(block),
+            BlockEvent::ValidBlockAdded(_, BlockAddResult::ChainReorg { added, .. }) => {
+                for block in added {
+                    self.notify(block.block());
+                }
+            },
+            _ => {},
+        }
+    }
+
+    fn notify(&self, block: &Block) {
+        let block_items = BlockOutputFilter::block_items(block).into_iter().collect();
+        for (relay_url, request) in self.registry.matches(&block_items) {
+            let timeout = self.relay_request_timeout;
+            let height = block.header.height;
+            // A slow relay must not hold up the notifications of other wallets
+            task::spawn(async move {
+                match post_to_relay(&relay_url, &request, timeout).await {
+                    Ok(()) => debug!(target: LOG_TARGET, "Posted push notification for block #{}", height),
+                    Err(e) => debug!(target: LOG_TARGET, "Failed to post push notification to relay: {}", e),
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn watch(device_token: &str, items: &[&[u8]]) -> PushWatch {
+        PushWatch {
+            relay_url: "http://127.0.0.1:8080".to_string(),
+            device_token: device_token.to_string(),
+            payload: "00".to_string(),
+            items: items.iter().map(|item| item.to_vec()).collect(),
+        }
+    }
+
+    fn config() -> LightWalletPushConfig {
+        LightWalletPushConfig {
+            enabled: true,
+            max_watches: 2,
+            max_items_per_watch: 2,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn it_matches_watches_against_block_items() {
+        let registry = PushWatchRegistry::new(config());
+        registry.register(watch("a", &[b"1", b"2"])).unwrap();
+        registry.register(watch("b", &[b"3"])).unwrap();
+        let block_items = [b"2".to_vec(), b"4".to_vec()].into_iter().collect();
+        let matches = registry.matches(&block_items);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].1.device_token, "a");
+
+        assert!(registry.unregister("a"));
+        assert!(!registry.unregister("a"));
+        assert!(registry.matches(&block_items).is_empty());
+    }
+
+    #[test]
+    fn it_limits_watches() {
+        let registry = PushWatchRegistry::new(config());
+        assert_eq!(
+            registry.register(watch("a", &[b"1", b"2", b"3"])),
+            Err(PushWatchError::TooManyItems(2))
+        );
+        assert_eq!(registry.register(watch("a", &[])), Err(PushWatchError::Incomplete));
+        registry.register(watch("a", &[b"1"])).unwrap();
+        registry.register(watch("b", &[b"1"])).unwrap();
+        assert_eq!(
+            registry.register(watch("c", &[b"1"])),
+            Err(PushWatchError::TooManyWatches)
+        );
+        // Replacing an existing watch is always allowed
+        registry.register(watch("b", &[b"2"])).unwrap();
+
+        let disabled = PushWatchRegistry::new(LightWalletPushConfig::default());
+        assert_eq!(disabled.register(watch("a", &[b"1"])), Err(PushWatchError::Disabled));
+    }
+
+    #[test]
+    fn it_rejects_clearnet_relays_under_the_privacy_profile() {
+        let registry = PushWatchRegistry::new(LightWalletPushConfig {
+            loopback_relays_only: true,
+            ..config()
+        });
+        let mut clearnet = watch("a", &[b"1"]);
+        clearnet.relay_url = "https://relay.example.com/push".to_string();
+        assert_eq!(
+            registry.register(clearnet),
+            Err(PushWatchError::ClearnetRelay(
+                "https://relay.example.com/push".to_string()
+            ))
+        );
+        registry.register(watch("a", &[b"1"])).unwrap();
+    }
+}
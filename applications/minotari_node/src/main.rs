@@ -65,17 +65,29 @@
 /// `discover-peer` - Attempts to discover a peer on the network, a public key or emoji id needs to be specified
 /// `get-block` - Retrieves a block, the height of the block needs to be specified
 /// `get-mempool-stats` - Displays information about the mempool
+/// `dashboard` - Shows a full screen dashboard of the node's status
 /// `get-mempool-state` - Displays state information for the mempool
 /// `whoami` - Displays identity information about this Base Node and it's wallet
 /// `quit` - Exits the Base Node
 /// `exit` - Same as quit
-use std::{process, sync::Arc};
+use std::{path::PathBuf, process, sync::Arc};
 
 use clap::Parser;
 use log::*;
-use minotari_app_utilities::{consts, identity_management::setup_node_identity, utilities::setup_runtime};
-use minotari_node::{cli::Cli, run_base_node_with_cli, ApplicationConfig};
-use tari_common::{exit_codes::ExitError, initialize_logging, load_configuration};
+use minotari_app_utilities::{
+    consts,
+    diagnostics::{install_panic_handler, set_diagnostic_field, DiagnosticsConfig},
+    identity_management::setup_node_identity,
+    network_profile::active_profile,
+    utilities::setup_runtime,
+};
+use minotari_node::{cli::Cli, run_base_node_with_cli, setup_wizard, ApplicationConfig};
+use tari_common::{
+    exit_codes::{ExitCode, ExitError},
+    initialize_logging,
+    load_configuration,
+    set_log_field,
+};
 use tari_comms::peer_manager::PeerFeatures;
 #[cfg(all(unix, feature = "libtor"))]
 use tari_libtor::tor::Tor;
@@ -102,31 +114,54 @@ fn main() {
 }
 
 fn main_inner() -> Result<(), ExitError> {
-    let cli = Cli::parse();
+    let mut cli = Cli::parse();
+    // On the very first interactive start, the network is part of the setup
+    if !cli.non_interactive_mode &&
+        cli.common.network.is_none() &&
+        cli.common.profile.is_none() &&
+        !cli.common.config_path().exists() &&
+        active_profile(&PathBuf::from(&cli.common.base_path))?.is_none()
+    {
+        let network = setup_wizard::prompt_network()?;
+        // Naming the profile makes it the active profile for later runs
+        cli.common.network = Some(network);
+        cli.common.profile = Some(network.to_string());
+    }
+    let profile = cli.common.resolve_network_profile()?;
     let base_path = cli.common.get_base_path();
     initialize_logging(
         &cli.common.log_config_path("base_node"),
         cli.common.log_path.as_ref().unwrap_or(&base_path),
         include_str!("../log4rs_sample.yml"),
+        cli.common.log_format.unwrap_or_default(),
     )?;
+    install_panic_handler(DiagnosticsConfig::from_cli("base_node", &cli.common));
+    set_diagnostic_field("network", profile.network);
 
     info!(
         target: LOG_TARGET,
         "Starting Minotari Base Node version: {}",
         consts::APP_VERSION
     );
+    info!(
+        target: LOG_TARGET,
+        "Using network profile '{}' ({})", profile.name, profile.network
+    );
 
     let config_path = cli.common.config_path();
-    let cfg = load_configuration(config_path, true, cli.non_interactive_mode, &cli, cli.common.network)?;
-
-    if cli.profile_with_tokio_console {
-        console_subscriber::init();
+    if !cli.non_interactive_mode && !config_path.exists() {
+        setup_wizard::run_setup_wizard(&config_path, &cli, profile.network)?;
     }
+    let cfg = load_configuration(config_path, true, cli.non_interactive_mode, &cli, cli.common.network)?;
 
-    #[cfg(all(unix, feature = "libtor"))]
     let mut config = ApplicationConfig::load_from(&cfg)?;
-    #[cfg(not(all(unix, feature = "libtor")))]
-    let config = ApplicationConfig::load_from(&cfg)?;
+    profile.check_network(config.base_node.network)?;
+    if cli.privacy {
+        config
+            .apply_privacy_profile()
+            .map_err(|e| ExitError::new(ExitCode::ConfigError, e))?;
+        info!(target: LOG_TARGET, "Using the hidden-service-only privacy profile");
+    }
     debug!(target: LOG_TARGET, "Using base node configuration: {:?}", config);
 
     // Load or create the Node identity
@@ -136,6 +171,8 @@ fn main_inner() -> Result<(), ExitError> {
         cli.non_interactive_mode || cli.init,
         PeerFeatures::COMMUNICATION_NODE,
     )?;
+    set_log_field("node_id", node_identity.node_id());
+    set_diagnostic_field("node_id", node_identity.node_id());
 
     if cli.init {
         info!(target: LOG_TARGET, "Default configuration created. Done.");
@@ -152,7 +189,8 @@ fn main_inner() -> Result<(), ExitError> {
     // This is currently only possible on linux/macos
     #[cfg(all(unix, feature = "libtor"))]
     if config.base_node.use_libtor && config.base_node.p2p.transport.is_tor() {
-        let tor = Tor::initialize()?;
+        let mut tor = Tor::initialize()?;
+        tor.set_bridges(&config.base_node.p2p.transport.tor)?;
         tor.update_comms_transport(&mut config.base_node.p2p.transport)?;
         tor.run_background();
         debug!(
@@ -26,30 +26,73 @@ use serde::{Deserialize, Serialize};
 use tari_common::{configuration::bootstrap::ApplicationType, SubConfigPath};
 use tari_comms::NodeIdentity;
 use tari_metrics::{server::MetricsServerBuilder, Registry};
-use tari_shutdown::ShutdownSignal;
-use tokio::task;
+use tari_shutdown::{Shutdown, ShutdownSignal};
+use tokio::{sync::broadcast, task};
+
+use crate::config_reload::{next_update, ConfigUpdate};
 
 pub fn install(
     application: ApplicationType,
     identity: &NodeIdentity,
     config: &MetricsConfig,
     shutdown: ShutdownSignal,
-) {
+) -> MetricsServer {
     let metrics_registry = create_metrics_registry(application, identity);
     tari_metrics::set_default_registry(metrics_registry);
 
-    let mut metrics = MetricsServerBuilder::new();
+    let mut server = MetricsServer {
+        server_shutdown: Shutdown::new(),
+        app_shutdown: shutdown,
+    };
+    server.start(config);
+    server
+}
 
-    if let Some(addr) = config.server_bind_address.as_ref() {
-        metrics = metrics.with_scrape_server(addr);
+/// The running metrics scrape server and push gateway client, which can be restarted with a new configuration. The
+/// server is stopped when this is dropped.
+pub struct MetricsServer {
+    server_shutdown: Shutdown,
+    app_shutdown: ShutdownSignal,
+}
+
+impl MetricsServer {
+    /// Stops the current server and starts a new one with `config`
+    pub fn restart(&mut self, config: &MetricsConfig) {
+        self.server_shutdown.trigger();
+        self.server_shutdown = Shutdown::new();
+        self.start(config);
     }
 
-    if let Some(endpoint) = config.push_endpoint.as_ref() {
-        // http://localhost:9091/metrics/job/base-node
-        metrics = metrics.with_push_gateway(endpoint);
+    fn start(&self, config: &MetricsConfig) {
+        let mut metrics = MetricsServerBuilder::new();
+
+        if let Some(addr) = config.server_bind_address.as_ref() {
+            metrics = metrics.with_scrape_server(addr);
+        }
+
+        if let Some(endpoint) = config.push_endpoint.as_ref() {
+            // http://localhost:9091/metrics/job/base-node
+            metrics = metrics.with_push_gateway(endpoint);
+        }
+
+        let app_shutdown = self.app_shutdown.clone();
+        let server_shutdown = self.server_shutdown.to_signal();
+        task::spawn(metrics.start(async move {
+            tokio::select! {
+                _ = app_shutdown => {},
+                _ = server_shutdown => {},
+            }
+        }));
     }
+}
 
-    task::spawn(metrics.start(shutdown));
+/// Restarts `server` whenever the metrics settings are reloaded. The server keeps running until the reloader stops.
+pub async fn apply_config_updates(mut server: MetricsServer, mut updates: broadcast::Receiver<ConfigUpdate>) {
+    while let Some(update) = next_update(&mut updates).await {
+        if let ConfigUpdate::Metrics(config) = update {
+            server.restart(&config);
+        }
+    }
 }
 
 fn create_metrics_registry(application: ApplicationType, identity: &NodeIdentity) -> Registry {
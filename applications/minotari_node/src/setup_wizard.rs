@@ -0,0 +1,448 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! The guided setup that creates the configuration file when the base node is first started interactively. It asks
+//! for the choices that matter to an operator (network, Tor, pruning, data directory and gRPC exposure), applies them
+//! to the default configuration template and only writes the file once it loads as a valid configuration.
+
+use std::{
+    fs,
+    io::{self, Write},
+    net::{SocketAddr, TcpStream},
+    path::Path,
+    str::FromStr,
+    time::Duration,
+};
+
+use log::*;
+use tari_common::{
+    configuration::{
+        bootstrap::{grpc_default_port, ApplicationType},
+        utils::{get_default_config, load_configuration_with_overrides, write_config_to},
+        Network,
+    },
+    exit_codes::{ExitCode, ExitError},
+};
+
+use crate::{cli::Cli, ApplicationConfig};
+
+const LOG_TARGET: &str = "minotari::base_node::setup_wizard";
+
+/// The default control port of a Tor daemon running on this machine
+const TOR_CONTROL_PORT: u16 = 9051;
+/// The number of blocks kept by a pruned node unless another horizon is entered
+const DEFAULT_PRUNING_HORIZON: u64 = 1000;
+/// The address peers connect to when the TCP transport is chosen
+const TCP_LISTENER_ADDRESS: &str = "/ip4/0.0.0.0/tcp/18189";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransportChoice {
+    /// The Tor daemon running on this machine
+    Tor,
+    /// The Tor instance built into the node
+    BuiltInTor,
+    Tcp,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GrpcExposure {
+    Disabled,
+    /// Only reachable from this machine
+    Local,
+    /// Reachable on all interfaces, behind basic authentication
+    Public,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetupChoices {
+    pub transport: TransportChoice,
+    /// 0 for an archival node
+    pub pruning_horizon: u64,
+    /// The data directory, if not the default
+    pub data_dir: Option<String>,
+    pub grpc: GrpcExposure,
+    /// The username and password required for public gRPC
+    pub grpc_credentials: Option<(String, String)>,
+    /// Enables the gRPC methods used for mining
+    pub mining: bool,
+}
+
+/// Asks which network to run on. Used on the very first start, when neither a network nor a profile is given.
+pub fn prompt_network() -> Result<Network, ExitError> {
+    let networks = [
+        Network::MainNet,
+        Network::NextNet,
+        Network::StageNet,
+        Network::Esmeralda,
+        Network::Igor,
+        Network::LocalNet,
+    ];
+    let names = networks.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+    let default = networks.iter().position(|n| *n == Network::default()).unwrap_or(0);
+    println!("Welcome to the Minotari base node setup.");
+    let index = choose("Which network would you like to run on?", &names, default)?;
+    Ok(networks[index])
+}
+
+/// Walks the user through the setup and writes the resulting configuration to `config_path`
+pub fn run_setup_wizard(config_path: &Path, cli: &Cli, network: Network) -> Result<(), ExitError> {
+    println!(
+        "No configuration was found at {}. A few questions will set one up for {}; press enter to accept the default \
+         in brackets.",
+        config_path.display(),
+        network
+    );
+    let choices = prompt_choices()?;
+    let config = render_config(&choices, network);
+    write_validated_config(config_path, &config, cli, network)?;
+    println!(
+        "Configuration written to {}. All other settings can be changed there.",
+        config_path.display()
+    );
+    Ok(())
+}
+
+fn prompt_choices() -> Result<SetupChoices, ExitError> {
+    let transport = prompt_transport()?;
+
+    let pruned = ask_yes_no(
+        "Run a pruned node? A pruned node only keeps recent blocks and needs far less disk space, but cannot serve \
+         the full history to other nodes.",
+        true,
+    )?;
+    let pruning_horizon = if pruned {
+        ask_parsed("How many recent blocks should be kept?", DEFAULT_PRUNING_HORIZON)?
+    } else {
+        0
+    };
+
+    let data_dir = ask(
+        "Where should the blockchain data be stored? Relative paths are relative to the base path.",
+        "data/base_node",
+    )?;
+    let data_dir = Some(data_dir).filter(|dir| dir != "data/base_node");
+
+    let grpc = match choose(
+        "Should the gRPC interface be enabled? It is used by wallets, miners and monitoring tools.",
+        &[
+            "No".to_string(),
+            "Yes, only reachable from this machine".to_string(),
+            "Yes, reachable from other machines (requires a username and password)".to_string(),
+        ],
+        0,
+    )? {
+        0 => GrpcExposure::Disabled,
+        1 => GrpcExposure::Local,
+        _ => GrpcExposure::Public,
+    };
+    let grpc_credentials = if grpc == GrpcExposure::Public {
+        let username = ask("gRPC username", "admin")?;
+        let password = loop {
+            let password = ask("gRPC password (stored in the configuration file)", "")?;
+            if !password.is_empty() {
+                break password;
+            }
+            println!("A password is required.");
+        };
+        Some((username, password))
+    } else {
+        None
+    };
+    let mining = grpc != GrpcExposure::Disabled &&
+        ask_yes_no(
+            "Would you like to mine? This enables additional gRPC methods that are used to monitor and submit blocks.",
+            false,
+        )?;
+
+    Ok(SetupChoices {
+        transport,
+        pruning_horizon,
+        data_dir,
+        grpc,
+        grpc_credentials,
+        mining,
+    })
+}
+
+fn prompt_transport() -> Result<TransportChoice, ExitError> {
+    let tor_address = SocketAddr::from(([127, 0, 0, 1], TOR_CONTROL_PORT));
+    if TcpStream::connect_timeout(&tor_address, Duration::from_secs(1)).is_ok() {
+        debug!(target: LOG_TARGET, "Found a Tor control port at {}", tor_address);
+        if ask_yes_no(
+            &format!(
+                "A Tor daemon was found on port {}. Connect to the network over Tor?",
+                TOR_CONTROL_PORT
+            ),
+            true,
+        )? {
+            return Ok(TransportChoice::Tor);
+        }
+    } else if cfg!(all(unix, feature = "libtor")) {
+        println!("No Tor daemon was found on this machine, but the node can run its own Tor instance.");
+        if ask_yes_no("Connect to the network over the built-in Tor?", true)? {
+            return Ok(TransportChoice::BuiltInTor);
+        }
+    } else {
+        println!(
+            "No Tor daemon was found on port {}. Start one and run the setup again to use Tor.",
+            TOR_CONTROL_PORT
+        );
+    }
+    println!(
+        "Using plain TCP. Peers will connect on {}, so make sure the port is reachable.",
+        TCP_LISTENER_ADDRESS
+    );
+    Ok(TransportChoice::Tcp)
+}
+
+/// Applies the choices to the default configuration template
+pub fn render_config(choices: &SetupChoices, network: Network) -> String {
+    let mut config = get_default_config(choices.mining).join("\n");
+
+    let transport = "base_node.p2p.transport";
+    match choices.transport {
+        TransportChoice::Tor => {
+            set_config_value(&mut config, "base_node", "use_libtor", "false");
+            set_config_value(&mut config, transport, "type", &quote("tor"));
+            set_config_value(
+                &mut config,
+                transport,
+                "tor.control_address",
+                &quote(&format!("/ip4/127.0.0.1/tcp/{}", TOR_CONTROL_PORT)),
+            );
+        },
+        TransportChoice::BuiltInTor => {
+            set_config_value(&mut config, "base_node", "use_libtor", "true");
+            set_config_value(&mut config, transport, "type", &quote("tor"));
+        },
+        TransportChoice::Tcp => {
+            set_config_value(&mut config, "base_node", "use_libtor", "false");
+            set_config_value(&mut config, transport, "type", &quote("tcp"));
+            set_config_value(
+                &mut config,
+                transport,
+                "tcp.listener_address",
+                &quote(TCP_LISTENER_ADDRESS),
+            );
+        },
+    }
+
+    set_config_value(
+        &mut config,
+        "base_node.storage",
+        "pruning_horizon",
+        &choices.pruning_horizon.to_string(),
+    );
+    if let Some(data_dir) = &choices.data_dir {
+        set_config_value(&mut config, "base_node", "data_dir", &quote(data_dir));
+    }
+
+    let port = grpc_default_port(ApplicationType::BaseNode, network);
+    match choices.grpc {
+        GrpcExposure::Disabled => set_config_value(&mut config, "base_node", "grpc_enabled", "false"),
+        GrpcExposure::Local | GrpcExposure::Public => {
+            let host = if choices.grpc == GrpcExposure::Local {
+                "127.0.0.1"
+            } else {
+                "0.0.0.0"
+            };
+            set_config_value(&mut config, "base_node", "grpc_enabled", "true");
+            set_config_value(
+                &mut config,
+                "base_node",
+                "grpc_address",
+                &quote(&format!("/ip4/{}/tcp/{}", host, port)),
+            );
+        },
+    }
+    if let Some((username, password)) = &choices.grpc_credentials {
+        set_config_value(
+            &mut config,
+            "base_node",
+            "grpc_authentication",
+            &format!("{{ username = {}, password = {} }}", quote(username), quote(password)),
+        );
+    }
+    if choices.mining {
+        set_config_value(&mut config, "base_node", "mining_enabled", "true");
+    }
+
+    config
+}
+
+/// Sets `key` in `section` of the configuration template, replacing the commented-out default if there is one
+pub fn set_config_value(config: &mut String, section: &str, key: &str, value: &str) {
+    let header = format!("[{}]", section);
+    let mut lines = config.lines().map(ToString::to_string).collect::<Vec<_>>();
+    let setting = format!("{} = {}", key, value);
+    match lines.iter().position(|line| line.trim() == header) {
+        Some(start) => {
+            let end = lines[start + 1..]
+                .iter()
+                .position(|line| is_section_header(line))
+                .map_or(lines.len(), |i| start + 1 + i);
+            match lines[start + 1..end].iter().position(|line| is_setting_of(line, key)) {
+                Some(i) => lines[start + 1 + i] = setting,
+                None => lines.insert(start + 1, setting),
+            }
+        },
+        None => {
+            lines.push(String::new());
+            lines.push(header);
+            lines.push(setting);
+        },
+    }
+    *config = lines.join("\n");
+    config.push('\n');
+}
+
+fn is_section_header(line: &str) -> bool {
+    let line = line.trim();
+    line.starts_with('[') && line.ends_with(']') && !line.contains('=')
+}
+
+/// Whether the line sets `key`, possibly commented out
+fn is_setting_of(line: &str, key: &str) -> bool {
+    line.trim_start()
+        .trim_start_matches('#')
+        .trim_start()
+        .strip_prefix(key)
+        .map_or(false, |rest| rest.trim_start().starts_with('='))
+}
+
+/// A TOML string. JSON string escapes are valid TOML.
+fn quote(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| format!("\"{}\"", value))
+}
+
+/// Writes the configuration next to `config_path` first and only moves it into place if it loads
+fn write_validated_config(config_path: &Path, config: &str, cli: &Cli, network: Network) -> Result<(), ExitError> {
+    let staging_path = config_path.with_extension("setup.toml");
+    write_config_to(&staging_path, &[config]).map_err(|e| {
+        ExitError::new(
+            ExitCode::ConfigError,
+            format!("Could not write the configuration: {}", e),
+        )
+    })?;
+    let validation = load_configuration_with_overrides(&staging_path, cli, Some(network))
+        .map_err(|e| e.to_string())
+        .and_then(|cfg| ApplicationConfig::load_from(&cfg).map_err(|e| e.to_string()));
+    if let Err(e) = validation {
+        let _ = fs::remove_file(&staging_path);
+        return Err(ExitError::new(
+            ExitCode::ConfigError,
+            format!("The configuration from the setup is invalid: {}", e),
+        ));
+    }
+    fs::rename(&staging_path, config_path).map_err(|e| {
+        ExitError::new(
+            ExitCode::ConfigError,
+            format!("Could not write the configuration: {}", e),
+        )
+    })?;
+    info!(target: LOG_TARGET, "Created configuration file {}", config_path.display());
+    Ok(())
+}
+
+/// Prints the prompt and reads a line, returning it trimmed
+fn read_answer(prompt: &str) -> Result<String, ExitError> {
+    print!("{} ", prompt);
+    io::stdout().flush().map_err(input_error)?;
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).map_err(input_error)?;
+    Ok(input.trim().to_string())
+}
+
+fn ask(question: &str, default: &str) -> Result<String, ExitError> {
+    if default.is_empty() {
+        return read_answer(&format!("{}:", question));
+    }
+    let answer = read_answer(&format!("{} [{}]:", question, default))?;
+    Ok(if answer.is_empty() { default.to_string() } else { answer })
+}
+
+fn ask_yes_no(question: &str, default: bool) -> Result<bool, ExitError> {
+    let hint = if default { "[Y/n]" } else { "[y/N]" };
+    loop {
+        match read_answer(&format!("{} {}", question, hint))?.to_lowercase().as_str() {
+            "" => return Ok(default),
+            "y" | "yes" => return Ok(true),
+            "n" | "no" => return Ok(false),
+            _ => println!("Please answer yes or no."),
+        }
+    }
+}
+
+fn ask_parsed<T: FromStr + ToString>(question: &str, default: T) -> Result<T, ExitError> {
+    loop {
+        match ask(question, &default.to_string())?.parse() {
+            Ok(value) => return Ok(value),
+            Err(_) => println!("That is not a valid value."),
+        }
+    }
+}
+
+/// Asks to choose one of `options` by number, returning its index
+fn choose(question: &str, options: &[String], default: usize) -> Result<usize, ExitError> {
+    println!("{}", question);
+    for (i, option) in options.iter().enumerate() {
+        println!("  {}) {}", i + 1, option);
+    }
+    loop {
+        match ask("Choice", &(default + 1).to_string())?.parse::<usize>() {
+            Ok(choice) if (1..=options.len()).contains(&choice) => return Ok(choice - 1),
+            _ => println!("Please enter a number between 1 and {}.", options.len()),
+        }
+    }
+}
+
+fn input_error(e: io::Error) -> ExitError {
+    ExitError::new(ExitCode::IOError, format!("Could not read the answer: {}", e))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_replaces_commented_defaults_within_the_section() {
+        let mut config = concat!(
+            "[base_node]\n",
+            "#data_dir = \"data/base_node\"\n",
+            "# The type is\n",
+            "[base_node.p2p]\n",
+            "#data_dir = \"x\"\n"
+        )
+        .to_string();
+        set_config_value(&mut config, "base_node", "data_dir", "\"/srv/tari\"");
+        assert_eq!(
+            config,
+            "[base_node]\ndata_dir = \"/srv/tari\"\n# The type is\n[base_node.p2p]\n#data_dir = \"x\"\n"
+        );
+
+        set_config_value(&mut config, "base_node.p2p", "type", "\"tcp\"");
+        assert!(config.contains("[base_node.p2p]\ntype = \"tcp\"\n#data_dir"));
+
+        set_config_value(&mut config, "base_node.storage", "pruning_horizon", "0");
+        assert!(config.ends_with("[base_node.storage]\npruning_horizon = 0\n"));
+    }
+
+    #[test]
+    fn it_renders_the_choices() {
+        let choices = SetupChoices {
+            transport: TransportChoice::Tcp,
+            pruning_horizon: 1000,
+            data_dir: Some("/srv/tari".to_string()),
+            grpc: GrpcExposure::Public,
+            grpc_credentials: Some(("admin".to_string(), "pass\"word".to_string())),
+            mining: true,
+        };
+        let config = render_config(&choices, Network::Esmeralda);
+        assert!(config.contains("\ntype = \"tcp\"\n"));
+        assert!(config.contains("\npruning_horizon = 1000\n"));
+        assert!(config.contains("\ndata_dir = \"/srv/tari\"\n"));
+        assert!(config.contains("\ngrpc_enabled = true\n"));
+        assert!(config.contains("\ngrpc_address = \"/ip4/0.0.0.0/tcp/18142\"\n"));
+        assert!(config.contains("grpc_authentication = { username = \"admin\", password = \"pass\\\"word\" }"));
+    }
+}
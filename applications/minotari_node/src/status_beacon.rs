@@ -0,0 +1,220 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Publishes signed network status beacons. Well-known nodes can enable this so that wallets are able to detect when
+//! their base node is far behind or on a minority fork. The beacon is flooded to connected peers over the DHT and the
+//! latest beacon is optionally served as JSON over HTTP.
+
+use std::{
+    convert::{Infallible, TryFrom},
+    net::SocketAddr,
+    sync::Arc,
+    time::Duration,
+};
+
+use hyper::{
+    header,
+    service::{make_service_fn, service_fn},
+    Body,
+    Method,
+    Request,
+    Response,
+    Server,
+    StatusCode,
+};
+use log::*;
+use minotari_app_utilities::consts;
+use serde::{Deserialize, Serialize};
+use tari_common::configuration::{serializers, Network};
+use tari_comms::NodeIdentity;
+use tari_comms_dht::{
+    domain_message::OutboundDomainMessage,
+    envelope::NodeDestination,
+    outbound::{OutboundEncryption, OutboundMessageRequester},
+};
+use tari_core::{
+    base_node::state_machine_service::states::StatusInfo,
+    chain_storage::{async_db::AsyncBlockchainDb, LMDBDatabase},
+};
+use tari_p2p::{
+    status_beacon::{SignedStatusBeacon, StatusBeaconMessage},
+    tari_message::TariMessageType,
+};
+use tari_shutdown::ShutdownSignal;
+use tari_utilities::epoch_time::EpochTime;
+use tokio::{
+    sync::watch,
+    task,
+    time::{self, MissedTickBehavior},
+};
+
+use crate::builder::BaseNodeContext;
+
+const LOG_TARGET: &str = "minotari::base_node::status_beacon";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StatusBeaconConfig {
+    /// Publish signed status beacons
+    pub enabled: bool,
+    /// The time between beacons
+    #[serde(with = "serializers::seconds")]
+    pub interval: Duration,
+    /// Serve the latest beacon as JSON on this address, if set
+    pub http_bind_address: Option<SocketAddr>,
+}
+
+impl Default for StatusBeaconConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval: Duration::from_secs(60),
+            http_bind_address: None,
+        }
+    }
+}
+
+/// Starts publishing status beacons if enabled
+pub fn install(ctx: &BaseNodeContext, config: &StatusBeaconConfig, shutdown: ShutdownSignal) {
+    if !config.enabled {
+        return;
+    }
+    let (beacon_tx, beacon_rx) = watch::channel(None);
+    let publisher = BeaconPublisher {
+        db: ctx.blockchain_db().into(),
+        node_identity: ctx.base_node_identity(),
+        outbound: ctx.base_node_dht().outbound_requester(),
+        status: ctx.get_state_machine_info_channel(),
+        network: ctx.network(),
+        beacon_tx,
+    };
+    task::spawn(publisher.run(config.interval, shutdown.clone()));
+    if let Some(addr) = config.http_bind_address {
+        task::spawn(run_beacon_server(addr, beacon_rx, shutdown));
+    }
+}
+
+struct BeaconPublisher {
+    db: AsyncBlockchainDb<LMDBDatabase>,
+    node_identity: Arc<NodeIdentity>,
+    outbound: OutboundMessageRequester,
+    status: watch::Receiver<StatusInfo>,
+    network: Network,
+    beacon_tx: watch::Sender<Option<SignedStatusBeacon>>,
+}
+
+impl BeaconPublisher {
+    async fn run(mut self, interval: Duration, shutdown: ShutdownSignal) {
+        let mut interval = time::interval(interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        tokio::pin!(shutdown);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => self.publish().await,
+                _ = &mut shutdown => break,
+            }
+        }
+    }
+
+    async fn publish(&mut self) {
+        // A node that is still syncing would report a misleading tip
+        if !self.status.borrow().state_info.is_synced() {
+            debug!(target: LOG_TARGET, "Node is not synced, not publishing a status beacon");
+            return;
+        }
+        let metadata = match self.db.get_chain_metadata().await {
+            Ok(metadata) => metadata,
+            Err(e) => {
+                warn!(target: LOG_TARGET, "Failed to fetch chain metadata for status beacon: {}", e);
+                return;
+            },
+        };
+        let beacon = SignedStatusBeacon::sign(
+            self.node_identity.secret_key(),
+            self.network,
+            metadata.best_block_height(),
+            metadata.best_block_hash().as_slice(),
+            EpochTime::now().as_u64(),
+            consts::APP_VERSION_NUMBER.to_string(),
+        );
+        let message = match StatusBeaconMessage::try_from(beacon.clone()) {
+            Ok(message) => message,
+            Err(e) => {
+                error!(target: LOG_TARGET, "Failed to encode status beacon: {}", e);
+                return;
+            },
+        };
+        if let Err(e) = self
+            .outbound
+            .flood(
+                NodeDestination::Unknown,
+                OutboundEncryption::ClearText,
+                vec![],
+                OutboundDomainMessage::new(&TariMessageType::StatusBeacon, message),
+                format!("Status beacon at #{}", metadata.best_block_height()),
+            )
+            .await
+        {
+            debug!(target: LOG_TARGET, "Failed to flood status beacon: {}", e);
+        }
+        trace!(
+            target: LOG_TARGET,
+            "Published status beacon at #{}",
+            metadata.best_block_height()
+        );
+        let _result = self.beacon_tx.send(Some(beacon));
+    }
+}
+
+/// Serves the latest beacon on the given address until `shutdown` is triggered
+async fn run_beacon_server(
+    addr: SocketAddr,
+    beacon_rx: watch::Receiver<Option<SignedStatusBeacon>>,
+    shutdown: ShutdownSignal,
+) {
+    info!(target: LOG_TARGET, "Serving status beacons on http://{}", addr);
+    let service = make_service_fn(move |_conn| {
+        let beacon_rx = beacon_rx.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |req| {
+                let beacon = beacon_rx.borrow().clone();
+                async move { Ok::<_, Infallible>(beacon_response(&req, beacon)) }
+            }))
+        }
+    });
+
+    let server = match Server::try_bind(&addr) {
+        Ok(server) => server,
+        Err(err) => {
+            error!(target: LOG_TARGET, "Unable to bind status beacon server to {}: {}", addr, err);
+            return;
+        },
+    };
+    if let Err(err) = server.serve(service).with_graceful_shutdown(shutdown).await {
+        error!(target: LOG_TARGET, "Status beacon server returned error: {}", err);
+    }
+    info!(target: LOG_TARGET, "Status beacon server stopped");
+}
+
+fn beacon_response(req: &Request<Body>, beacon: Option<SignedStatusBeacon>) -> Response<Body> {
+    let status = if req.method() != Method::GET {
+        StatusCode::METHOD_NOT_ALLOWED
+    } else {
+        match beacon.map(|beacon| beacon.to_json()) {
+            Some(Ok(json)) => {
+                return Response::builder()
+                    .status(StatusCode::OK)
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(json))
+                    .expect("static response is valid");
+            },
+            Some(Err(_)) => StatusCode::INTERNAL_SERVER_ERROR,
+            // No beacon has been published yet
+            None => StatusCode::SERVICE_UNAVAILABLE,
+        }
+    };
+    Response::builder()
+        .status(status)
+        .body(Body::empty())
+        .expect("static response is valid")
+}
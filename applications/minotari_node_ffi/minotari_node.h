@@ -0,0 +1,216 @@
+// Copyright 2024. The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+// This file was generated by cargo-bindgen. Please do not edit manually.
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * An embedded base node. The node owns its own tokio runtime, which lives for as long as this handle does.
+ */
+struct TariBaseNode;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Creates an embedded base node from the configuration found under `base_path`. A default configuration and node
+ * identity are created if they do not exist yet. The node is not started.
+ *
+ * ## Arguments
+ * `base_path` - The base path of the node's data, the network name is appended to it
+ * `network` - The network name, e.g. "mainnet", "nextnet" or "esmeralda"
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `*mut TariBaseNode` - Pointer to the created TariBaseNode. Note that it will be ptr::null_mut() if an error occurred
+ *
+ * # Safety
+ * The ```base_node_destroy``` function must be called when finished with a TariBaseNode to prevent a memory leak
+ */
+struct TariBaseNode *base_node_create(const char *base_path,
+                                      const char *network,
+                                      int *error_out);
+
+/**
+ * Starts the base node, including the comms stack and the base node state machine. This call returns once the node
+ * has been initialized; syncing continues in the background.
+ *
+ * ## Arguments
+ * `node` - The pointer to a TariBaseNode
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `bool` - Returns true if the node was started
+ *
+ * # Safety
+ * None
+ */
+bool base_node_start(struct TariBaseNode *node,
+                     int *error_out);
+
+/**
+ * Stops the base node and waits for the state machine and comms stack to shut down. A stopped node may be started
+ * again with ```base_node_start```.
+ *
+ * ## Arguments
+ * `node` - The pointer to a TariBaseNode
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `bool` - Returns true if the node was stopped
+ *
+ * # Safety
+ * None
+ */
+bool base_node_stop(struct TariBaseNode *node,
+                    int *error_out);
+
+/**
+ * Returns whether the base node is currently running
+ *
+ * ## Arguments
+ * `node` - The pointer to a TariBaseNode
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `bool` - Returns true if the node has been started and not stopped
+ *
+ * # Safety
+ * None
+ */
+bool base_node_is_running(const struct TariBaseNode *node,
+                          int *error_out);
+
+/**
+ * Gets the height of the base node's chain tip
+ *
+ * ## Arguments
+ * `node` - The pointer to a running TariBaseNode
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `c_ulonglong` - The tip height. Note that it will be zero if an error occurred
+ *
+ * # Safety
+ * None
+ */
+unsigned long long base_node_get_tip_height(const struct TariBaseNode *node,
+                                            int *error_out);
+
+/**
+ * Gets the hash of the base node's chain tip as a hex string
+ *
+ * ## Arguments
+ * `node` - The pointer to a running TariBaseNode
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `*mut c_char` - The hex encoded tip hash. Note that it will be ptr::null_mut() if an error occurred
+ *
+ * # Safety
+ * The ```string_destroy``` function must be called when finished with the string to prevent a memory leak
+ */
+char *base_node_get_tip_hash(const struct TariBaseNode *node,
+                             int *error_out);
+
+/**
+ * Gets the current state of the base node state machine
+ *
+ * ## Arguments
+ * `node` - The pointer to a running TariBaseNode
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `c_int` - The state of the node:
+ * 0 - Starting up
+ * 1 - Connecting to sync peers
+ * 2 - Syncing headers
+ * 3 - Syncing horizon state
+ * 4 - Syncing blocks
+ * 5 - Sync failed
+ * 6 - Listening
+ * Note that it will be -1 if an error occurred
+ *
+ * # Safety
+ * None
+ */
+int base_node_get_sync_state(const struct TariBaseNode *node,
+                             int *error_out);
+
+/**
+ * Returns whether the base node is synced with the network
+ *
+ * ## Arguments
+ * `node` - The pointer to a running TariBaseNode
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `bool` - Returns true if the node is listening and synced
+ *
+ * # Safety
+ * None
+ */
+bool base_node_is_synced(const struct TariBaseNode *node,
+                         int *error_out);
+
+/**
+ * Gets the number of base nodes the node is currently connected to
+ *
+ * ## Arguments
+ * `node` - The pointer to a running TariBaseNode
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `c_uint` - The number of connected peers. Note that it will be zero if an error occurred
+ *
+ * # Safety
+ * None
+ */
+unsigned int base_node_get_connected_peer_count(const struct TariBaseNode *node,
+                                                int *error_out);
+
+/**
+ * Frees memory for a string returned by this library
+ *
+ * ## Arguments
+ * `s` - The pointer to the string
+ *
+ * ## Returns
+ * `()` - Does not return a value, equivalent to void in C
+ *
+ * # Safety
+ * None
+ */
+void string_destroy(char *s);
+
+/**
+ * Frees memory for a TariBaseNode, stopping the node first if it is still running
+ *
+ * ## Arguments
+ * `node` - The pointer to a TariBaseNode
+ *
+ * ## Returns
+ * `()` - Does not return a value, equivalent to void in C
+ *
+ * # Safety
+ * None
+ */
+void base_node_destroy(struct TariBaseNode *node);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
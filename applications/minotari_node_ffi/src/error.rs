@@ -0,0 +1,81 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use tari_common::exit_codes::ExitError;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum InterfaceError {
+    #[error("An error has occurred due to one of the parameters being null: `{0}`")]
+    NullError(String),
+    #[error("An error has occurred due to conversion failing for: `{0}`")]
+    Conversion(String),
+    #[error("An invalid network was passed in: `{0}`")]
+    InvalidNetwork(String),
+    #[error("An error has occurred when trying to create the tokio runtime: `{0}`")]
+    TokioError(String),
+    #[error("An error has occurred while loading the base node configuration: `{0}`")]
+    ConfigError(String),
+    #[error("The base node could not be started: `{0}`")]
+    StartupError(String),
+    #[error("The base node is already running")]
+    AlreadyRunning,
+    #[error("The base node is not running")]
+    NotRunning,
+    #[error("An error has occurred while querying the base node: `{0}`")]
+    QueryError(String),
+}
+
+impl From<ExitError> for InterfaceError {
+    fn from(err: ExitError) -> Self {
+        InterfaceError::StartupError(err.to_string())
+    }
+}
+
+/// This struct is meant to hold an error for use by applications embedding the base node. The error has an integer
+/// code and string message
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct BaseNodeFfiError {
+    pub code: i32,
+    pub message: String,
+}
+
+impl From<InterfaceError> for BaseNodeFfiError {
+    fn from(v: InterfaceError) -> Self {
+        let code = match v {
+            InterfaceError::NullError(_) => 1,
+            InterfaceError::Conversion(_) => 2,
+            InterfaceError::InvalidNetwork(_) => 3,
+            InterfaceError::TokioError(_) => 4,
+            InterfaceError::ConfigError(_) => 5,
+            InterfaceError::StartupError(_) => 6,
+            InterfaceError::AlreadyRunning => 7,
+            InterfaceError::NotRunning => 8,
+            InterfaceError::QueryError(_) => 9,
+        };
+        Self {
+            code,
+            message: format!("{:?}", v),
+        }
+    }
+}
@@ -0,0 +1,575 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! # Minotari Base Node FFI
+//!
+//! A small C interface that allows desktop applications (e.g. GUI wallets) to embed a Minotari base node and manage
+//! it in-process. The node is configured from the usual `config.toml` found under the supplied base path, and can be
+//! started, queried for its tip, sync state and peer connections, and stopped again.
+//!
+//! The embedding application is responsible for configuring logging. The gRPC server and the interactive CLI are not
+//! started by this interface.
+
+#![cfg_attr(not(debug_assertions), deny(unused_variables))]
+#![cfg_attr(not(debug_assertions), deny(unused_imports))]
+#![cfg_attr(not(debug_assertions), deny(dead_code))]
+#![cfg_attr(not(debug_assertions), deny(unused_extern_crates))]
+#![deny(unused_must_use)]
+#![deny(unreachable_patterns)]
+#![deny(unknown_lints)]
+
+mod error;
+
+use core::ptr;
+use std::{
+    convert::TryFrom,
+    ffi::{CStr, CString},
+    str::FromStr,
+    sync::Arc,
+};
+
+use libc::{c_char, c_int, c_uint, c_ulonglong};
+use log::*;
+use minotari_app_utilities::{
+    common_cli_args::CommonCliArgs,
+    identity_management::setup_node_identity,
+    utilities::setup_runtime,
+};
+use minotari_node::{cli::Cli, configure_and_initialize_node, ApplicationConfig, BaseNodeContext};
+use tari_common::{configuration::Network, load_configuration, network_check::set_network_if_choice_valid};
+use tari_comms::{peer_manager::PeerFeatures, NodeIdentity};
+use tari_core::base_node::state_machine_service::states::StateInfo;
+use tari_shutdown::Shutdown;
+use tari_utilities::hex::Hex;
+use tokio::runtime::Runtime;
+
+use crate::error::{BaseNodeFfiError, InterfaceError};
+
+const LOG_TARGET: &str = "minotari::base_node::ffi";
+
+/// An embedded base node. The node owns its own tokio runtime, which lives for as long as this handle does.
+pub struct TariBaseNode {
+    runtime: Runtime,
+    config: Arc<ApplicationConfig>,
+    node_identity: Arc<NodeIdentity>,
+    running: Option<RunningBaseNode>,
+}
+
+struct RunningBaseNode {
+    shutdown: Shutdown,
+    context: BaseNodeContext,
+}
+
+impl TariBaseNode {
+    fn running(&self) -> Result<&RunningBaseNode, InterfaceError> {
+        self.running.as_ref().ok_or(InterfaceError::NotRunning)
+    }
+}
+
+unsafe fn c_str_to_string(s: *const c_char, name: &str) -> Result<String, InterfaceError> {
+    if s.is_null() {
+        return Err(InterfaceError::NullError(name.to_string()));
+    }
+    CStr::from_ptr(s)
+        .to_str()
+        .map(|s| s.to_string())
+        .map_err(|_| InterfaceError::Conversion(name.to_string()))
+}
+
+fn load_base_node(base_path: String, network: Network) -> Result<TariBaseNode, InterfaceError> {
+    let cli = Cli {
+        common: CommonCliArgs {
+            base_path,
+            config: "config/config.toml".to_string(),
+            log_config: None,
+            log_path: None,
+            log_format: None,
+            network: Some(network),
+            profile: None,
+            config_property_overrides: vec![],
+        },
+        init: false,
+        rebuild_db: false,
+        non_interactive_mode: true,
+        watch: None,
+        dashboard: false,
+        profile_with_tokio_console: false,
+        grpc_enabled: false,
+        mining_enabled: false,
+        second_layer_grpc_enabled: false,
+        light_wallet_server_enabled: false,
+        regtest: false,
+        privacy: false,
+    };
+
+    let cfg = load_configuration(cli.common.config_path(), true, true, &cli, cli.common.network)
+        .map_err(|e| InterfaceError::ConfigError(e.to_string()))?;
+    let config = ApplicationConfig::load_from(&cfg).map_err(|e| InterfaceError::ConfigError(e.to_string()))?;
+    let node_identity = setup_node_identity(
+        &config.base_node.identity_file,
+        config.base_node.p2p.public_addresses.clone().into_vec(),
+        true,
+        PeerFeatures::COMMUNICATION_NODE,
+    )?;
+    let runtime = setup_runtime().map_err(|e| InterfaceError::TokioError(e.to_string()))?;
+
+    Ok(TariBaseNode {
+        runtime,
+        config: Arc::new(config),
+        node_identity,
+        running: None,
+    })
+}
+
+/// Creates an embedded base node from the configuration found under `base_path`. A default configuration and node
+/// identity are created if they do not exist yet. The node is not started.
+///
+/// ## Arguments
+/// `base_path` - The base path of the node's data, the network name is appended to it
+/// `network` - The network name, e.g. "mainnet", "nextnet" or "esmeralda"
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `*mut TariBaseNode` - Pointer to the created TariBaseNode. Note that it will be ptr::null_mut() if an error occurred
+///
+/// # Safety
+/// The ```base_node_destroy``` function must be called when finished with a TariBaseNode to prevent a memory leak
+#[no_mangle]
+pub unsafe extern "C" fn base_node_create(
+    base_path: *const c_char,
+    network: *const c_char,
+    error_out: *mut c_int,
+) -> *mut TariBaseNode {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    let result = c_str_to_string(base_path, "base_path").and_then(|base_path| {
+        let network = c_str_to_string(network, "network")?;
+        let network = Network::from_str(&network).map_err(|e| InterfaceError::InvalidNetwork(e.to_string()))?;
+        set_network_if_choice_valid(network).map_err(|e| InterfaceError::InvalidNetwork(e.to_string()))?;
+        load_base_node(base_path, network)
+    });
+    match result {
+        Ok(node) => Box::into_raw(Box::new(node)),
+        Err(e) => {
+            error = BaseNodeFfiError::from(e).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            ptr::null_mut()
+        },
+    }
+}
+
+/// Starts the base node, including the comms stack and the base node state machine. This call returns once the node
+/// has been initialized; syncing continues in the background.
+///
+/// ## Arguments
+/// `node` - The pointer to a TariBaseNode
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `bool` - Returns true if the node was started
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn base_node_start(node: *mut TariBaseNode, error_out: *mut c_int) -> bool {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if node.is_null() {
+        error = BaseNodeFfiError::from(InterfaceError::NullError("node".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return false;
+    }
+    let node = &mut *node;
+    if node.running.is_some() {
+        error = BaseNodeFfiError::from(InterfaceError::AlreadyRunning).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return false;
+    }
+
+    let shutdown = Shutdown::new();
+    let result = node.runtime.block_on(configure_and_initialize_node(
+        node.config.clone(),
+        node.node_identity.clone(),
+        shutdown.to_signal(),
+    ));
+    match result {
+        Ok(context) => {
+            info!(target: LOG_TARGET, "Embedded Minotari base node has STARTED");
+            node.running = Some(RunningBaseNode { shutdown, context });
+            true
+        },
+        Err(e) => {
+            error!(target: LOG_TARGET, "Embedded base node failed to start: {}", e);
+            error = BaseNodeFfiError::from(InterfaceError::from(e)).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            false
+        },
+    }
+}
+
+/// Stops the base node and waits for the state machine and comms stack to shut down. A stopped node may be started
+/// again with ```base_node_start```.
+///
+/// ## Arguments
+/// `node` - The pointer to a TariBaseNode
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `bool` - Returns true if the node was stopped
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn base_node_stop(node: *mut TariBaseNode, error_out: *mut c_int) -> bool {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if node.is_null() {
+        error = BaseNodeFfiError::from(InterfaceError::NullError("node".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return false;
+    }
+    let node = &mut *node;
+    match node.running.take() {
+        Some(RunningBaseNode { mut shutdown, context }) => {
+            shutdown.trigger();
+            node.runtime.block_on(context.wait_for_shutdown());
+            info!(target: LOG_TARGET, "Embedded Minotari base node has STOPPED");
+            true
+        },
+        None => {
+            error = BaseNodeFfiError::from(InterfaceError::NotRunning).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            false
+        },
+    }
+}
+
+/// Returns whether the base node is currently running
+///
+/// ## Arguments
+/// `node` - The pointer to a TariBaseNode
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `bool` - Returns true if the node has been started and not stopped
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn base_node_is_running(node: *const TariBaseNode, error_out: *mut c_int) -> bool {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if node.is_null() {
+        error = BaseNodeFfiError::from(InterfaceError::NullError("node".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return false;
+    }
+    (*node).running.is_some()
+}
+
+/// Gets the height of the base node's chain tip
+///
+/// ## Arguments
+/// `node` - The pointer to a running TariBaseNode
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `c_ulonglong` - The tip height. Note that it will be zero if an error occurred
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn base_node_get_tip_height(node: *const TariBaseNode, error_out: *mut c_int) -> c_ulonglong {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if node.is_null() {
+        error = BaseNodeFfiError::from(InterfaceError::NullError("node".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return 0;
+    }
+    let result = (*node).running().and_then(|running| {
+        running
+            .context
+            .blockchain_db()
+            .get_chain_metadata()
+            .map_err(|e| InterfaceError::QueryError(e.to_string()))
+    });
+    match result {
+        Ok(metadata) => metadata.best_block_height(),
+        Err(e) => {
+            error = BaseNodeFfiError::from(e).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            0
+        },
+    }
+}
+
+/// Gets the hash of the base node's chain tip as a hex string
+///
+/// ## Arguments
+/// `node` - The pointer to a running TariBaseNode
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `*mut c_char` - The hex encoded tip hash. Note that it will be ptr::null_mut() if an error occurred
+///
+/// # Safety
+/// The ```string_destroy``` function must be called when finished with the string to prevent a memory leak
+#[no_mangle]
+pub unsafe extern "C" fn base_node_get_tip_hash(node: *const TariBaseNode, error_out: *mut c_int) -> *mut c_char {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if node.is_null() {
+        error = BaseNodeFfiError::from(InterfaceError::NullError("node".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
+    }
+    let result = (*node).running().and_then(|running| {
+        let metadata = running
+            .context
+            .blockchain_db()
+            .get_chain_metadata()
+            .map_err(|e| InterfaceError::QueryError(e.to_string()))?;
+        CString::new(metadata.best_block_hash().to_hex())
+            .map_err(|_| InterfaceError::Conversion("best_block_hash".to_string()))
+    });
+    match result {
+        Ok(hash) => hash.into_raw(),
+        Err(e) => {
+            error = BaseNodeFfiError::from(e).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            ptr::null_mut()
+        },
+    }
+}
+
+/// Gets the current state of the base node state machine
+///
+/// ## Arguments
+/// `node` - The pointer to a running TariBaseNode
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `c_int` - The state of the node:
+/// 0 - Starting up
+/// 1 - Connecting to sync peers
+/// 2 - Syncing headers
+/// 3 - Syncing horizon state
+/// 4 - Syncing blocks
+/// 5 - Sync failed
+/// 6 - Listening
+/// Note that it will be -1 if an error occurred
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn base_node_get_sync_state(node: *const TariBaseNode, error_out: *mut c_int) -> c_int {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if node.is_null() {
+        error = BaseNodeFfiError::from(InterfaceError::NullError("node".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return -1;
+    }
+    match (*node).running() {
+        Ok(running) => match running.context.get_state_machine_info_channel().borrow().state_info {
+            StateInfo::StartUp => 0,
+            StateInfo::Connecting(_) => 1,
+            StateInfo::HeaderSync(_) => 2,
+            StateInfo::HorizonSync(_) => 3,
+            StateInfo::BlockSync(_) => 4,
+            StateInfo::SyncFailed(_) => 5,
+            StateInfo::Listening(_) => 6,
+        },
+        Err(e) => {
+            error = BaseNodeFfiError::from(e).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            -1
+        },
+    }
+}
+
+/// Returns whether the base node is synced with the network
+///
+/// ## Arguments
+/// `node` - The pointer to a running TariBaseNode
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `bool` - Returns true if the node is listening and synced
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn base_node_is_synced(node: *const TariBaseNode, error_out: *mut c_int) -> bool {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if node.is_null() {
+        error = BaseNodeFfiError::from(InterfaceError::NullError("node".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return false;
+    }
+    match (*node).running() {
+        Ok(running) => running
+            .context
+            .get_state_machine_info_channel()
+            .borrow()
+            .state_info
+            .is_synced(),
+        Err(e) => {
+            error = BaseNodeFfiError::from(e).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            false
+        },
+    }
+}
+
+/// Gets the number of base nodes the node is currently connected to
+///
+/// ## Arguments
+/// `node` - The pointer to a running TariBaseNode
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `c_uint` - The number of connected peers. Note that it will be zero if an error occurred
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn base_node_get_connected_peer_count(
+    node: *const TariBaseNode,
+    error_out: *mut c_int,
+) -> c_uint {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if node.is_null() {
+        error = BaseNodeFfiError::from(InterfaceError::NullError("node".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return 0;
+    }
+    let node = &*node;
+    let result = node.running().and_then(|running| {
+        let mut connectivity = running.context.base_node_comms().connectivity();
+        let status = node
+            .runtime
+            .block_on(connectivity.get_connectivity_status())
+            .map_err(|e| InterfaceError::QueryError(e.to_string()))?;
+        c_uint::try_from(status.num_connected_nodes())
+            .map_err(|_| InterfaceError::Conversion("num_connected_nodes".to_string()))
+    });
+    match result {
+        Ok(count) => count,
+        Err(e) => {
+            error = BaseNodeFfiError::from(e).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            0
+        },
+    }
+}
+
+/// Frees memory for a string returned by this library
+///
+/// ## Arguments
+/// `s` - The pointer to the string
+///
+/// ## Returns
+/// `()` - Does not return a value, equivalent to void in C
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn string_destroy(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+/// Frees memory for a TariBaseNode, stopping the node first if it is still running
+///
+/// ## Arguments
+/// `node` - The pointer to a TariBaseNode
+///
+/// ## Returns
+/// `()` - Does not return a value, equivalent to void in C
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn base_node_destroy(node: *mut TariBaseNode) {
+    if !node.is_null() {
+        let mut node = Box::from_raw(node);
+        if let Some(RunningBaseNode { mut shutdown, context }) = node.running.take() {
+            shutdown.trigger();
+            node.runtime.block_on(context.wait_for_shutdown());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::ffi::CString;
+
+    use libc::c_int;
+
+    use super::*;
+
+    #[test]
+    fn it_rejects_null_and_invalid_arguments() {
+        unsafe {
+            let mut error: c_int = 0;
+            let error_ptr = &mut error as *mut c_int;
+            let base_path = CString::new("/tmp/minotari_node_ffi").unwrap();
+            let network = CString::new("not-a-network").unwrap();
+
+            let node = base_node_create(ptr::null(), network.as_ptr(), error_ptr);
+            assert!(node.is_null());
+            assert_eq!(
+                error,
+                BaseNodeFfiError::from(InterfaceError::NullError(String::new())).code
+            );
+
+            let node = base_node_create(base_path.as_ptr(), network.as_ptr(), error_ptr);
+            assert!(node.is_null());
+            assert_eq!(
+                error,
+                BaseNodeFfiError::from(InterfaceError::InvalidNetwork(String::new())).code
+            );
+
+            assert!(!base_node_is_running(ptr::null(), error_ptr));
+            assert_eq!(
+                error,
+                BaseNodeFfiError::from(InterfaceError::NullError(String::new())).code
+            );
+            assert_eq!(base_node_get_sync_state(ptr::null(), error_ptr), -1);
+            assert!(!base_node_stop(ptr::null_mut(), error_ptr));
+        }
+    }
+}
@@ -0,0 +1,42 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use clap::Parser;
+use minotari_app_utilities::common_cli_args::CommonCliArgs;
+use tari_common::configuration::{ConfigOverrideProvider, Network};
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+#[clap(propagate_version = true)]
+pub struct Cli {
+    #[clap(flatten)]
+    pub common: CommonCliArgs,
+    #[clap(short, long, alias = "non-interactive", env = "TARI_NON_INTERACTIVE")]
+    pub non_interactive_mode: bool,
+    /// Print the private view key of the wallet, to configure the wallet with, and exit
+    #[clap(long)]
+    pub print_view_key: bool,
+}
+
+impl ConfigOverrideProvider for Cli {
+    /// Get the configuration property overrides for the given network. In case of duplicates, the final override
+    /// added to the list will have preference.
+    fn get_config_property_overrides(&self, network: &Network) -> Vec<(String, String)> {
+        // Config file overrides
+        let mut overrides = vec![("remote_signer.override_from".to_string(), network.to_string())];
+        overrides.push(("remote_signer.network".to_string(), network.to_string()));
+        // Command-line overrides
+        let command_line_overrides = self.common.get_config_property_overrides(network);
+        command_line_overrides.iter().for_each(|(k, v)| {
+            replace_or_add_override(&mut overrides, k, v);
+        });
+        overrides
+    }
+}
+
+fn replace_or_add_override(overrides: &mut Vec<(String, String)>, key: &str, value: &str) {
+    if let Some(index) = overrides.iter().position(|(k, _)| k == key) {
+        overrides.remove(index);
+    }
+    overrides.push((key.to_string(), value.to_string()));
+}
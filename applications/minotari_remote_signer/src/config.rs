@@ -0,0 +1,61 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tari_common::{configuration::Network, SubConfigPath};
+use tari_comms::multiaddr::Multiaddr;
+
+use crate::policy::SignerPolicyConfig;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RemoteSignerConfig {
+    override_from: Option<String>,
+    /// The gRPC address that the signer listens on
+    pub grpc_address: Multiaddr,
+    /// Selected network
+    pub network: Network,
+    /// The relative path to store persistent config. The server certificate and key are read from `server.pem` and
+    /// `server.key` in this directory.
+    pub config_dir: PathBuf,
+    /// The CA certificate that client certificates must be signed with, relative to the config directory
+    pub client_ca_cert_filename: String,
+    /// A file holding the seed words of the wallet. The seed words are prompted for when it is not set.
+    pub seed_words_file: Option<PathBuf>,
+    /// The journal of the sender offsets that one-sided payments have been signed with, relative to the config
+    /// directory. It must be kept with the seed, a signer that loses it could reuse a sender offset.
+    pub sender_offset_journal_filename: String,
+    /// The payments that the signer signs
+    pub policy: SignerPolicyConfig,
+}
+
+impl Default for RemoteSignerConfig {
+    fn default() -> Self {
+        Self {
+            override_from: None,
+            grpc_address: "/ip4/127.0.0.1/tcp/18160".parse().unwrap(),
+            network: Network::default(),
+            config_dir: PathBuf::from("config/remote_signer"),
+            client_ca_cert_filename: "client_ca.pem".to_string(),
+            seed_words_file: None,
+            sender_offset_journal_filename: "sender_offsets.json".to_string(),
+            policy: SignerPolicyConfig::default(),
+        }
+    }
+}
+
+impl SubConfigPath for RemoteSignerConfig {
+    fn main_key_prefix() -> &'static str {
+        "remote_signer"
+    }
+}
+
+impl RemoteSignerConfig {
+    pub fn set_base_path<P: AsRef<Path>>(&mut self, base_path: P) {
+        if !self.config_dir.is_absolute() {
+            self.config_dir = base_path.as_ref().join(self.config_dir.as_path());
+        }
+    }
+}
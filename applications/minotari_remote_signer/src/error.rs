@@ -0,0 +1,39 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use tari_core::transactions::transaction_components::TransactionError;
+use tari_key_manager::key_manager_service::KeyManagerServiceError;
+use tonic::Status;
+
+use crate::policy::PolicyViolation;
+
+#[derive(Debug, thiserror::Error)]
+pub enum SignerError {
+    #[error("The payment is not allowed by the policy: {0}")]
+    PolicyViolation(#[from] PolicyViolation),
+    #[error("The branch `{0}` is not held by the signer")]
+    BranchNotHeld(String),
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+    #[error("The sender offset at index {0} has already been used for another payment")]
+    SenderOffsetReused(u64),
+    #[error("Sender offset journal error: {0}")]
+    JournalError(String),
+    #[error("Key manager error: {0}")]
+    KeyManagerError(#[from] KeyManagerServiceError),
+    #[error("Transaction error: {0}")]
+    TransactionError(#[from] TransactionError),
+}
+
+impl From<SignerError> for Status {
+    fn from(err: SignerError) -> Self {
+        match err {
+            SignerError::PolicyViolation(_) => Status::permission_denied(err.to_string()),
+            SignerError::BranchNotHeld(_) | SignerError::InvalidRequest(_) => Status::invalid_argument(err.to_string()),
+            SignerError::SenderOffsetReused(_) => Status::failed_precondition(err.to_string()),
+            SignerError::KeyManagerError(_) | SignerError::TransactionError(_) | SignerError::JournalError(_) => {
+                Status::internal(err.to_string())
+            },
+        }
+    }
+}
@@ -0,0 +1,169 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use tari_common_types::{
+    tari_address::TariAddress,
+    types::{Commitment, PublicKey},
+};
+use tari_core::transactions::tari_amount::MicroMinotari;
+use tari_script::TariScript;
+
+use crate::error::SignerError;
+
+/// A one-sided payment that the signer signed the metadata of
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedPayment {
+    pub receiver_address: TariAddress,
+    pub value: MicroMinotari,
+    pub commitment: Commitment,
+    pub script: TariScript,
+    pub sender_offset_public_key: PublicKey,
+    /// Set once a transaction that pays it has been signed
+    pub spent: bool,
+}
+
+/// The one-sided payments that the signer has signed, by the index of their sender offset. The script offset of a
+/// transaction is the sum of its script keys less its sender offsets, and the wallet knows everything in it apart from
+/// the spend key and the signer's sender offsets. Each sender offset is therefore only ever spent to in one
+/// transaction, and the journal is kept on disk so that a restart of the signer does not forget them.
+#[derive(Debug, Default)]
+pub struct SenderOffsetJournal {
+    path: Option<PathBuf>,
+    payments: BTreeMap<u64, SignedPayment>,
+}
+
+impl SenderOffsetJournal {
+    /// Opens the journal at `path`, which is created on the first payment if it does not exist
+    pub fn open(path: PathBuf) -> Result<Self, SignerError> {
+        let payments = if path.exists() {
+            let contents = fs::read_to_string(&path).map_err(|e| journal_error(&path, e))?;
+            serde_json::from_str(&contents).map_err(|e| journal_error(&path, e))?
+        } else {
+            BTreeMap::new()
+        };
+        Ok(Self {
+            path: Some(path),
+            payments,
+        })
+    }
+
+    /// Records the payment signed with the sender offset at `index`. Signing the same payment again is allowed until
+    /// it is spent, but a sender offset is never used for a second payment.
+    pub fn record(&mut self, index: u64, payment: SignedPayment) -> Result<(), SignerError> {
+        match self.payments.get(&index) {
+            Some(signed) if signed.spent || *signed != payment => Err(SignerError::SenderOffsetReused(index)),
+            Some(_) => Ok(()),
+            None => {
+                self.payments.insert(index, payment);
+                self.save()
+            },
+        }
+    }
+
+    /// The payment that has not been spent yet with the given sender offset, and the index of the sender offset
+    pub fn find_unspent(&self, sender_offset_public_key: &PublicKey) -> Option<(u64, &SignedPayment)> {
+        self.payments
+            .iter()
+            .find(|(_, payment)| !payment.spent && payment.sender_offset_public_key == *sender_offset_public_key)
+            .map(|(index, payment)| (*index, payment))
+    }
+
+    /// Marks the payments as spent, once a transaction that pays them has been signed
+    pub fn mark_spent(&mut self, indexes: &[u64]) -> Result<(), SignerError> {
+        for index in indexes {
+            if let Some(payment) = self.payments.get_mut(index) {
+                payment.spent = true;
+            }
+        }
+        self.save()
+    }
+
+    /// Writes the journal to a temporary file first, so that a crash cannot leave it half written
+    fn save(&self) -> Result<(), SignerError> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let contents = serde_json::to_string_pretty(&self.payments).map_err(|e| journal_error(path, e))?;
+        let temp_path = path.with_extension("tmp");
+        fs::write(&temp_path, contents).map_err(|e| journal_error(&temp_path, e))?;
+        fs::rename(&temp_path, path).map_err(|e| journal_error(path, e))
+    }
+}
+
+fn journal_error<E: ToString>(path: &Path, e: E) -> SignerError {
+    SignerError::JournalError(format!("{:?}: {}", path, e.to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::OsRng;
+    use tari_common::configuration::Network;
+    use tari_crypto::keys::PublicKey as PublicKeyTrait;
+    use tari_script::push_pubkey_script;
+    use tempfile::tempdir;
+
+    use super::*;
+
+    fn payment(value: u64) -> SignedPayment {
+        let (_, view_key) = PublicKey::random_keypair(&mut OsRng);
+        let (_, spend_key) = PublicKey::random_keypair(&mut OsRng);
+        let (_, sender_offset_public_key) = PublicKey::random_keypair(&mut OsRng);
+        SignedPayment {
+            receiver_address: TariAddress::new_dual_address_with_default_features(
+                view_key,
+                spend_key.clone(),
+                Network::LocalNet,
+            ),
+            value: MicroMinotari(value),
+            commitment: Commitment::from_public_key(&spend_key),
+            script: push_pubkey_script(&spend_key),
+            sender_offset_public_key,
+            spent: false,
+        }
+    }
+
+    #[test]
+    fn it_never_reuses_a_sender_offset() {
+        let mut journal = SenderOffsetJournal::default();
+        let first = payment(100);
+        journal.record(1, first.clone()).unwrap();
+        // The same payment can be signed again until it is spent
+        journal.record(1, first.clone()).unwrap();
+        assert!(matches!(
+            journal.record(1, payment(100)),
+            Err(SignerError::SenderOffsetReused(1))
+        ));
+
+        assert_eq!(journal.find_unspent(&first.sender_offset_public_key), Some((1, &first)));
+        journal.mark_spent(&[1]).unwrap();
+        assert_eq!(journal.find_unspent(&first.sender_offset_public_key), None);
+        assert!(matches!(
+            journal.record(1, first),
+            Err(SignerError::SenderOffsetReused(1))
+        ));
+    }
+
+    #[test]
+    fn it_remembers_the_sender_offsets_across_restarts() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("sender_offsets.json");
+        let first = payment(100);
+        let mut journal = SenderOffsetJournal::open(path.clone()).unwrap();
+        journal.record(1, first.clone()).unwrap();
+        journal.mark_spent(&[1]).unwrap();
+
+        let mut journal = SenderOffsetJournal::open(path).unwrap();
+        assert_eq!(journal.find_unspent(&first.sender_offset_public_key), None);
+        assert!(matches!(
+            journal.record(1, payment(200)),
+            Err(SignerError::SenderOffsetReused(1))
+        ));
+    }
+}
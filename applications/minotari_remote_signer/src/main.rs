@@ -0,0 +1,154 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! A signer that holds the spend key of a wallet in a separate process, possibly on another machine. A console wallet
+//! of type `WalletType::RemoteSigner` connects to it over mutually authenticated TLS and asks it for every signature
+//! that needs the spend key. It never signs a raw challenge: transactions are signed as a whole, and only if what they
+//! pay out is allowed by the configured policy. The wallet is given the private view key out of band, see
+//! `--print-view-key`.
+
+use std::{str::FromStr, sync::Arc};
+
+use clap::Parser;
+use log::*;
+use minotari_app_grpc::{tari_rpc::remote_signer_server::RemoteSignerServer, tls::identity::read_identity};
+use minotari_app_utilities::{
+    consts,
+    diagnostics::{install_panic_handler, DiagnosticsConfig},
+};
+use tari_common::{
+    exit_codes::{ExitCode, ExitError},
+    initialize_logging,
+    load_configuration,
+    DefaultConfigLoader,
+};
+use tari_comms::utils::multiaddr::multiaddr_to_socketaddr;
+use tari_core::transactions::key_manager::create_memory_db_key_manager_from_seed;
+use tari_key_manager::{cipher_seed::CipherSeed, mnemonic::Mnemonic, SeedWords};
+use tari_utilities::hex::Hex;
+use tonic::transport::{Certificate, Server, ServerTlsConfig};
+
+use crate::{
+    cli::Cli,
+    config::RemoteSignerConfig,
+    journal::SenderOffsetJournal,
+    policy::SignerPolicy,
+    server::RemoteSignerService,
+    signer::Signer,
+};
+
+pub const LOG_TARGET: &str = "minotari::remote_signer::main";
+
+mod cli;
+mod config;
+mod error;
+mod journal;
+mod policy;
+mod server;
+mod signer;
+
+/// Application entry point
+#[tokio::main]
+async fn main() {
+    match main_inner().await {
+        Ok(_) => std::process::exit(0),
+        Err(err) => {
+            error!(target: LOG_TARGET, "Fatal error: {:?}", err);
+            let exit_code = err.exit_code;
+            error!(target: LOG_TARGET, "Exiting with code: {:?}", exit_code);
+            std::process::exit(exit_code as i32)
+        },
+    }
+}
+
+async fn main_inner() -> Result<(), ExitError> {
+    let cli = Cli::parse();
+    initialize_logging(
+        &cli.common.log_config_path("remote_signer"),
+        &cli.common.get_base_path(),
+        include_str!("../log4rs_sample.yml"),
+        cli.common.log_format.unwrap_or_default(),
+    )?;
+    install_panic_handler(DiagnosticsConfig::from_cli("remote_signer", &cli.common));
+    info!(
+        target: LOG_TARGET,
+        "Starting Minotari Remote Signer version: {}",
+        consts::APP_VERSION
+    );
+
+    let config_path = cli.common.config_path();
+    let cfg = load_configuration(
+        config_path.as_path(),
+        true,
+        cli.non_interactive_mode,
+        &cli,
+        cli.common.network,
+    )?;
+    let mut config = RemoteSignerConfig::load_from(&cfg)?;
+    config.set_base_path(cli.common.get_base_path());
+    let policy = SignerPolicy::from_config(&config.policy).map_err(|e| ExitError::new(ExitCode::ConfigError, e))?;
+
+    let seed = read_seed(&config, cli.non_interactive_mode).await?;
+    let key_manager = create_memory_db_key_manager_from_seed(seed, 64)
+        .map_err(|e| ExitError::new(ExitCode::KeyManagerServiceError, e))?;
+    let journal = SenderOffsetJournal::open(config.config_dir.join(&config.sender_offset_journal_filename))
+        .map_err(|e| ExitError::new(ExitCode::IOError, e))?;
+    let signer = Arc::new(Signer::new(key_manager, config.network, policy, journal));
+
+    if cli.print_view_key {
+        let view_key = signer
+            .get_private_view_key()
+            .await
+            .map_err(|e| ExitError::new(ExitCode::KeyManagerServiceError, e))?;
+        println!("{}", view_key.to_hex());
+        return Ok(());
+    }
+
+    let identity = read_identity(config.config_dir.clone())
+        .await
+        .map_err(|e| ExitError::new(ExitCode::TlsConfigurationError, e))?;
+    let client_ca_file = config.config_dir.join(&config.client_ca_cert_filename);
+    let client_ca = tokio::fs::read(&client_ca_file).await.map_err(|e| {
+        ExitError::new(
+            ExitCode::TlsConfigurationError,
+            format!("Could not load the file `{:?}`: {}", client_ca_file, e),
+        )
+    })?;
+    // Only wallets with a client certificate signed by the configured CA can connect
+    let tls_config = ServerTlsConfig::new()
+        .identity(identity)
+        .client_ca_root(Certificate::from_pem(client_ca));
+
+    let grpc_address =
+        multiaddr_to_socketaddr(&config.grpc_address).map_err(|e| ExitError::new(ExitCode::ConfigError, e))?;
+    info!(target: LOG_TARGET, "Remote signer listening on {}", grpc_address);
+    Server::builder()
+        .tls_config(tls_config)
+        .map_err(|e| ExitError::new(ExitCode::TlsConfigurationError, e))?
+        .add_service(RemoteSignerServer::new(RemoteSignerService::new(signer)))
+        .serve(grpc_address)
+        .await
+        .map_err(|e| ExitError::new(ExitCode::GrpcError, e))?;
+    Ok(())
+}
+
+/// Reads the seed words from the configured file, or prompts for them if no file is configured
+async fn read_seed(config: &RemoteSignerConfig, non_interactive: bool) -> Result<CipherSeed, ExitError> {
+    let seed_words = match &config.seed_words_file {
+        Some(path) => tokio::fs::read_to_string(path).await.map_err(|e| {
+            ExitError::new(
+                ExitCode::IOError,
+                format!("Could not read the seed words file `{:?}`: {}", path, e),
+            )
+        })?,
+        None if non_interactive => {
+            return Err(ExitError::new(
+                ExitCode::ConfigError,
+                "A seed words file must be configured in non-interactive mode",
+            ));
+        },
+        None => rpassword::prompt_password_stdout("Seed words: ").map_err(|e| ExitError::new(ExitCode::IOError, e))?,
+    };
+    let seed_words = SeedWords::from_str(seed_words.trim()).map_err(|e| ExitError::new(ExitCode::InputError, e))?;
+    CipherSeed::from_mnemonic(&seed_words, None).map_err(|e| ExitError::new(ExitCode::InputError, e))
+}
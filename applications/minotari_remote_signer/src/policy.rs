@@ -0,0 +1,241 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    collections::VecDeque,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+use tari_common_types::tari_address::TariAddress;
+use tari_core::transactions::tari_amount::MicroMinotari;
+
+/// The window that the daily limit applies to
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SignerPolicyConfig {
+    /// The most that a single transaction may spend on payments and its fee, in µT. Transactions of any amount are
+    /// signed if not set.
+    pub max_amount: Option<u64>,
+    /// The most that is spent by the transactions signed in any 24 hours, in µT. There is no daily limit if not set.
+    pub daily_limit: Option<u64>,
+    /// The addresses that payments may be sent to, as base58, emoji or hex. Payments to any address are signed if
+    /// empty.
+    pub allowed_destinations: Vec<String>,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum PolicyViolation {
+    #[error("The amount {amount} exceeds the maximum of {max_amount}")]
+    AmountTooLarge {
+        amount: MicroMinotari,
+        max_amount: MicroMinotari,
+    },
+    #[error(
+        "Signing {amount} would exceed the daily limit of {daily_limit}, {signed} was signed in the last 24 hours"
+    )]
+    DailyLimitExceeded {
+        amount: MicroMinotari,
+        signed: MicroMinotari,
+        daily_limit: MicroMinotari,
+    },
+    #[error("The destination {0} is not allowed")]
+    DestinationNotAllowed(TariAddress),
+}
+
+/// Decides which transactions the signer signs. The amounts signed for the daily limit are only kept in memory, so a
+/// restart of the signer resets them.
+#[derive(Debug, Default)]
+pub struct SignerPolicy {
+    max_amount: Option<MicroMinotari>,
+    daily_limit: Option<MicroMinotari>,
+    allowed_destinations: Vec<TariAddress>,
+    signed: VecDeque<(Instant, MicroMinotari)>,
+}
+
+impl SignerPolicy {
+    pub fn from_config(config: &SignerPolicyConfig) -> Result<Self, String> {
+        let allowed_destinations = config
+            .allowed_destinations
+            .iter()
+            .map(|address| {
+                TariAddress::from_str(address).map_err(|e| format!("Invalid allowed destination '{}': {}", address, e))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            max_amount: config.max_amount.map(MicroMinotari::from),
+            daily_limit: config.daily_limit.map(MicroMinotari::from),
+            allowed_destinations,
+            signed: VecDeque::new(),
+        })
+    }
+
+    /// Checks that a payment of `amount` to `destination` is allowed, without counting it towards the daily limit. This
+    /// is done before the metadata of a payment is signed, so that the wallet learns of a refusal early.
+    pub fn check(&self, amount: MicroMinotari, destination: &TariAddress) -> Result<(), PolicyViolation> {
+        self.check_amount(amount)?;
+        self.check_destination(destination)
+    }
+
+    /// Checks a transaction that spends `amount` on payments to `destinations` and its fee against the policy, and
+    /// counts it towards the daily limit if it is allowed
+    pub fn approve(
+        &mut self,
+        amount: MicroMinotari,
+        destinations: &[TariAddress],
+        now: Instant,
+    ) -> Result<(), PolicyViolation> {
+        self.check_amount(amount)?;
+        for destination in destinations {
+            self.check_destination(destination)?;
+        }
+        while self
+            .signed
+            .front()
+            .is_some_and(|(signed_at, _)| now.saturating_duration_since(*signed_at) >= DAY)
+        {
+            self.signed.pop_front();
+        }
+        if let Some(daily_limit) = self.daily_limit {
+            let signed = self
+                .signed
+                .iter()
+                .fold(MicroMinotari::zero(), |total, (_, amount)| total + *amount);
+            if signed + amount > daily_limit {
+                return Err(PolicyViolation::DailyLimitExceeded {
+                    amount,
+                    signed,
+                    daily_limit,
+                });
+            }
+        }
+        self.signed.push_back((now, amount));
+        Ok(())
+    }
+
+    fn check_amount(&self, amount: MicroMinotari) -> Result<(), PolicyViolation> {
+        match self.max_amount {
+            Some(max_amount) if amount > max_amount => Err(PolicyViolation::AmountTooLarge { amount, max_amount }),
+            _ => Ok(()),
+        }
+    }
+
+    fn check_destination(&self, destination: &TariAddress) -> Result<(), PolicyViolation> {
+        if !self.allowed_destinations.is_empty() &&
+            !self
+                .allowed_destinations
+                .iter()
+                .any(|allowed| is_same_destination(allowed, destination))
+        {
+            return Err(PolicyViolation::DestinationNotAllowed(destination.clone()));
+        }
+        Ok(())
+    }
+}
+
+/// Addresses are compared by their keys, so that an address is allowed whatever features it advertises
+fn is_same_destination(allowed: &TariAddress, destination: &TariAddress) -> bool {
+    allowed.network() == destination.network() &&
+        allowed.public_spend_key() == destination.public_spend_key() &&
+        allowed.public_view_key() == destination.public_view_key()
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::OsRng;
+    use tari_common::configuration::Network;
+    use tari_common_types::{tari_address::TariAddressFeatures, types::PublicKey};
+    use tari_crypto::keys::PublicKey as PublicKeyTrait;
+
+    use super::*;
+
+    fn random_address() -> TariAddress {
+        let (_, view_key) = PublicKey::random_keypair(&mut OsRng);
+        let (_, spend_key) = PublicKey::random_keypair(&mut OsRng);
+        TariAddress::new_dual_address_with_default_features(view_key, spend_key, Network::LocalNet)
+    }
+
+    #[test]
+    fn it_limits_the_amount() {
+        let mut policy = SignerPolicy::from_config(&SignerPolicyConfig {
+            max_amount: Some(1_000),
+            ..Default::default()
+        })
+        .unwrap();
+        let now = Instant::now();
+        assert!(policy.approve(MicroMinotari(1_000), &[random_address()], now).is_ok());
+        assert!(policy.check(MicroMinotari(1_001), &random_address()).is_err());
+        assert_eq!(
+            policy.approve(MicroMinotari(1_001), &[random_address()], now),
+            Err(PolicyViolation::AmountTooLarge {
+                amount: MicroMinotari(1_001),
+                max_amount: MicroMinotari(1_000),
+            })
+        );
+    }
+
+    #[test]
+    fn it_only_allows_the_listed_destinations() {
+        let allowed = random_address();
+        let mut policy = SignerPolicy::from_config(&SignerPolicyConfig {
+            allowed_destinations: vec![allowed.to_base58()],
+            ..Default::default()
+        })
+        .unwrap();
+        let now = Instant::now();
+        assert!(policy.check(MicroMinotari(1), &allowed).is_ok());
+        assert!(policy.approve(MicroMinotari(1), &[allowed.clone()], now).is_ok());
+        // The same keys with other features are the same destination
+        let one_sided = TariAddress::new_dual_address(
+            allowed.public_view_key().unwrap().clone(),
+            allowed.public_spend_key().clone(),
+            Network::LocalNet,
+            TariAddressFeatures::create_one_sided_only(),
+        );
+        assert!(policy.approve(MicroMinotari(1), &[one_sided], now).is_ok());
+        let other = random_address();
+        assert_eq!(
+            policy.check(MicroMinotari(1), &other),
+            Err(PolicyViolation::DestinationNotAllowed(other.clone()))
+        );
+        // A transaction is refused if any of its payments goes elsewhere
+        assert_eq!(
+            policy.approve(MicroMinotari(2), &[allowed, other.clone()], now),
+            Err(PolicyViolation::DestinationNotAllowed(other))
+        );
+    }
+
+    #[test]
+    fn it_enforces_a_rolling_daily_limit() {
+        let mut policy = SignerPolicy::from_config(&SignerPolicyConfig {
+            daily_limit: Some(1_000),
+            ..Default::default()
+        })
+        .unwrap();
+        let start = Instant::now();
+        let address = [random_address()];
+        assert!(policy.approve(MicroMinotari(600), &address, start).is_ok());
+        let later = start + Duration::from_secs(60 * 60);
+        assert!(policy.approve(MicroMinotari(400), &address, later).is_ok());
+        // Checking a payment does not count towards the limit
+        assert!(policy.check(MicroMinotari(1), &address[0]).is_ok());
+        assert!(matches!(
+            policy.approve(MicroMinotari(1), &address, later),
+            Err(PolicyViolation::DailyLimitExceeded { .. })
+        ));
+        // The first transaction drops out of the window after a day
+        assert!(policy.approve(MicroMinotari(600), &address, start + DAY).is_ok());
+    }
+
+    #[test]
+    fn it_rejects_invalid_destinations() {
+        assert!(SignerPolicy::from_config(&SignerPolicyConfig {
+            allowed_destinations: vec!["not an address".to_string()],
+            ..Default::default()
+        })
+        .is_err());
+    }
+}
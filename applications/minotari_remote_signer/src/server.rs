@@ -0,0 +1,203 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::{convert::TryFrom, sync::Arc};
+
+use log::*;
+use minotari_app_grpc::tari_rpc::{
+    self as grpc,
+    remote_signer_server,
+    sign_transaction_input::ScriptKey,
+    GetDhSharedSecretRequest,
+    GetDhSharedSecretResponse,
+    GetOneSidedMetadataSignatureRequest,
+    GetPublicKeyResponse,
+    GetWalletKeysResponse,
+    RemoteKey,
+    SignTransactionRequest,
+};
+use tari_common_types::{
+    tari_address::TariAddress,
+    types::{PrivateKey, PublicKey},
+};
+use tari_core::transactions::{
+    key_manager::{RemoteScriptKey, RemoteSignerChangeOutput, RemoteSignerInput, TransactionKeyManagerBranch},
+    tari_amount::MicroMinotari,
+    transaction_components::{RangeProofType, Transaction},
+};
+use tari_utilities::ByteArray;
+use tonic::{Request, Response, Status};
+
+use crate::signer::Signer;
+
+const LOG_TARGET: &str = "minotari::remote_signer::server";
+
+pub struct RemoteSignerService {
+    signer: Arc<Signer>,
+}
+
+impl RemoteSignerService {
+    pub fn new(signer: Arc<Signer>) -> Self {
+        Self { signer }
+    }
+}
+
+#[tonic::async_trait]
+impl remote_signer_server::RemoteSigner for RemoteSignerService {
+    async fn get_wallet_keys(&self, _request: Request<grpc::Empty>) -> Result<Response<GetWalletKeysResponse>, Status> {
+        let (public_spend_key, public_view_key) = self.signer.get_wallet_keys().await?;
+        Ok(Response::new(GetWalletKeysResponse {
+            public_spend_key: public_spend_key.to_vec(),
+            public_view_key: public_view_key.to_vec(),
+            network: self.signer.network().to_string(),
+        }))
+    }
+
+    async fn get_public_key(&self, request: Request<RemoteKey>) -> Result<Response<GetPublicKeyResponse>, Status> {
+        let (branch, index) = convert_remote_key(Some(request.into_inner()))?;
+        let public_key = self.signer.get_public_key(branch, index).await?;
+        Ok(Response::new(GetPublicKeyResponse {
+            public_key: public_key.to_vec(),
+        }))
+    }
+
+    async fn get_dh_shared_secret(
+        &self,
+        request: Request<GetDhSharedSecretRequest>,
+    ) -> Result<Response<GetDhSharedSecretResponse>, Status> {
+        let request = request.into_inner();
+        let (branch, index) = convert_remote_key(request.key)?;
+        let public_key = PublicKey::from_canonical_bytes(&request.public_key)
+            .map_err(|e| Status::invalid_argument(format!("Invalid public key: {}", e)))?;
+        let shared_secret = self.signer.get_dh_shared_secret(branch, index, &public_key).await?;
+        Ok(Response::new(GetDhSharedSecretResponse {
+            shared_secret: shared_secret.as_bytes().to_vec(),
+        }))
+    }
+
+    async fn get_one_sided_metadata_signature(
+        &self,
+        request: Request<GetOneSidedMetadataSignatureRequest>,
+    ) -> Result<Response<grpc::ComAndPubSignature>, Status> {
+        let request = request.into_inner();
+        let receiver_address = TariAddress::from_bytes(&request.receiver_address)
+            .map_err(|e| Status::invalid_argument(format!("Invalid receiver address: {}", e)))?;
+        let range_proof_type = u8::try_from(request.range_proof_type)
+            .ok()
+            .and_then(RangeProofType::from_byte)
+            .ok_or_else(|| Status::invalid_argument("Invalid range proof type"))?;
+        let value = MicroMinotari::from(request.value);
+        let result = self
+            .signer
+            .get_one_sided_metadata_signature(
+                convert_version(request.txo_version)?,
+                value,
+                request.sender_offset_index,
+                &convert_private_key(&request.commitment_mask, "commitment mask")?,
+                &receiver_address,
+                &convert_message(&request.metadata_signature_message_common, "metadata signature message")?,
+                range_proof_type,
+            )
+            .await;
+        match result {
+            Ok(signature) => {
+                info!(target: LOG_TARGET, "Signed a payment of {} to {}", value, receiver_address);
+                Ok(Response::new(signature.into()))
+            },
+            Err(e) => {
+                warn!(
+                    target: LOG_TARGET,
+                    "Refused to sign a payment of {} to {}: {}", value, receiver_address, e
+                );
+                Err(e.into())
+            },
+        }
+    }
+
+    async fn sign_transaction(
+        &self,
+        request: Request<SignTransactionRequest>,
+    ) -> Result<Response<grpc::Transaction>, Status> {
+        let request = request.into_inner();
+        let transaction = request
+            .transaction
+            .ok_or_else(|| Status::invalid_argument("The transaction is missing"))
+            .and_then(|transaction| {
+                Transaction::try_from(transaction)
+                    .map_err(|e| Status::invalid_argument(format!("Invalid transaction: {}", e)))
+            })?;
+        let inputs = request
+            .inputs
+            .into_iter()
+            .map(|input| {
+                let script_key = match input.script_key {
+                    Some(ScriptKey::Managed(key)) => {
+                        let (branch, index) = convert_remote_key(Some(key))?;
+                        Some(RemoteScriptKey::Managed { branch, index })
+                    },
+                    Some(ScriptKey::DerivedCommitmentMask(commitment_mask)) => Some(RemoteScriptKey::Derived {
+                        commitment_mask: convert_private_key(&commitment_mask, "derived commitment mask")?,
+                    }),
+                    None => None,
+                };
+                Ok(RemoteSignerInput {
+                    value: MicroMinotari::from(input.value),
+                    commitment_mask: convert_private_key(&input.commitment_mask, "commitment mask")?,
+                    script_key,
+                })
+            })
+            .collect::<Result<Vec<_>, Status>>()?;
+        let change_outputs = request
+            .change_outputs
+            .into_iter()
+            .map(|output| {
+                Ok(RemoteSignerChangeOutput {
+                    value: MicroMinotari::from(output.value),
+                    commitment_mask: convert_private_key(&output.commitment_mask, "commitment mask")?,
+                })
+            })
+            .collect::<Result<Vec<_>, Status>>()?;
+        let partial_script_offset = convert_private_key(&request.partial_script_offset, "partial script offset")?;
+
+        match self
+            .signer
+            .sign_transaction(transaction, &inputs, &change_outputs, &partial_script_offset)
+            .await
+        {
+            Ok(transaction) => {
+                info!(
+                    target: LOG_TARGET,
+                    "Signed a transaction spending {} input(s)",
+                    transaction.body.inputs().len()
+                );
+                let transaction = grpc::Transaction::try_from(transaction).map_err(Status::internal)?;
+                Ok(Response::new(transaction))
+            },
+            Err(e) => {
+                warn!(target: LOG_TARGET, "Refused to sign a transaction: {}", e);
+                Err(e.into())
+            },
+        }
+    }
+}
+
+fn convert_remote_key(key: Option<RemoteKey>) -> Result<(TransactionKeyManagerBranch, u64), Status> {
+    let key = key.ok_or_else(|| Status::invalid_argument("The key is missing"))?;
+    let branch = u8::try_from(key.branch)
+        .ok()
+        .and_then(TransactionKeyManagerBranch::from_byte)
+        .ok_or_else(|| Status::invalid_argument(format!("Unknown branch {}", key.branch)))?;
+    Ok((branch, key.index))
+}
+
+fn convert_private_key(bytes: &[u8], name: &str) -> Result<PrivateKey, Status> {
+    PrivateKey::from_canonical_bytes(bytes).map_err(|e| Status::invalid_argument(format!("Invalid {}: {}", name, e)))
+}
+
+fn convert_message(bytes: &[u8], name: &str) -> Result<[u8; 32], Status> {
+    <[u8; 32]>::try_from(bytes).map_err(|_| Status::invalid_argument(format!("The {} must be 32 bytes", name)))
+}
+
+fn convert_version(version: u32) -> Result<u8, Status> {
+    u8::try_from(version).map_err(|_| Status::invalid_argument(format!("Invalid version {}", version)))
+}
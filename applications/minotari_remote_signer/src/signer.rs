@@ -0,0 +1,454 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::{convert::TryFrom, time::Instant};
+
+use tari_common::configuration::Network;
+use tari_common_types::{
+    tari_address::TariAddress,
+    types::{ComAndPubSignature, Commitment, CommitmentFactory, PrivateKey, PublicKey},
+};
+use tari_comms::types::CommsDHKE;
+use tari_core::transactions::{
+    key_manager::{
+        is_remote_signer_branch,
+        MemoryDbKeyManager,
+        RemoteScriptKey,
+        RemoteSignerChangeOutput,
+        RemoteSignerInput,
+        SecretTransactionKeyManagerInterface,
+        TariKeyId,
+        TransactionKeyManagerBranch,
+        TransactionKeyManagerInterface,
+    },
+    tari_amount::MicroMinotari,
+    transaction_components::{RangeProofType, Transaction, TransactionInput, TransactionOutputVersion},
+};
+use tari_crypto::{commitment::HomomorphicCommitmentFactory, keys::PublicKey as PublicKeyTrait};
+use tari_key_manager::key_manager_service::{KeyId, KeyManagerInterface};
+use tari_script::push_pubkey_script;
+use tari_utilities::hex::Hex;
+use tokio::sync::Mutex;
+
+use crate::{
+    error::SignerError,
+    journal::{SenderOffsetJournal, SignedPayment},
+    policy::SignerPolicy,
+};
+
+/// Holds the spend key of a wallet and signs on its behalf. Only the keys of the branches that a
+/// `WalletType::RemoteSigner` wallet does not hold itself are ever used, and never to sign a raw challenge: inputs are
+/// only signed as part of a whole transaction, once the policy has allowed what it pays out.
+pub struct Signer {
+    key_manager: MemoryDbKeyManager,
+    network: Network,
+    policy: Mutex<SignerPolicy>,
+    journal: Mutex<SenderOffsetJournal>,
+}
+
+impl Signer {
+    pub fn new(
+        key_manager: MemoryDbKeyManager,
+        network: Network,
+        policy: SignerPolicy,
+        journal: SenderOffsetJournal,
+    ) -> Self {
+        Self {
+            key_manager,
+            network,
+            policy: Mutex::new(policy),
+            journal: Mutex::new(journal),
+        }
+    }
+
+    pub fn network(&self) -> Network {
+        self.network
+    }
+
+    /// The public spend and view keys of the wallet
+    pub async fn get_wallet_keys(&self) -> Result<(PublicKey, PublicKey), SignerError> {
+        let spend_key = self.key_manager.get_spend_key().await?;
+        let view_key = self.key_manager.get_view_key().await?;
+        Ok((spend_key.pub_key, view_key.pub_key))
+    }
+
+    /// The private view key of the wallet, which the operator gives to the wallet out of band
+    pub async fn get_private_view_key(&self) -> Result<PrivateKey, SignerError> {
+        Ok(self.key_manager.get_private_view_key().await?)
+    }
+
+    pub async fn get_public_key(
+        &self,
+        branch: TransactionKeyManagerBranch,
+        index: u64,
+    ) -> Result<PublicKey, SignerError> {
+        let key_id = managed_key_id(branch, index)?;
+        Ok(self.key_manager.get_public_key_at_key_id(&key_id).await?)
+    }
+
+    pub async fn get_dh_shared_secret(
+        &self,
+        branch: TransactionKeyManagerBranch,
+        index: u64,
+        public_key: &PublicKey,
+    ) -> Result<CommsDHKE, SignerError> {
+        let key_id = managed_key_id(branch, index)?;
+        Ok(self
+            .key_manager
+            .get_diffie_hellman_shared_secret(&key_id, public_key)
+            .await?)
+    }
+
+    /// Signs a stealth one-sided payment if its amount and destination are allowed by the policy. The stealth script
+    /// is built here from the receiver address, so the wallet cannot have the signer sign for a different destination
+    /// than the one checked. The payment is recorded in the journal, and only counts towards the daily limit once a
+    /// transaction that pays it is signed.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn get_one_sided_metadata_signature(
+        &self,
+        txo_version: u8,
+        value: MicroMinotari,
+        sender_offset_index: u64,
+        commitment_mask: &PrivateKey,
+        receiver_address: &TariAddress,
+        metadata_signature_message_common: &[u8; 32],
+        range_proof_type: RangeProofType,
+    ) -> Result<ComAndPubSignature, SignerError> {
+        if receiver_address.network() != self.network {
+            return Err(SignerError::InvalidRequest(format!(
+                "The receiver address is for network '{}', the signer is on '{}'",
+                receiver_address.network(),
+                self.network
+            )));
+        }
+        let txo_version = TransactionOutputVersion::try_from(txo_version).map_err(SignerError::InvalidRequest)?;
+        self.policy.lock().await.check(value, receiver_address)?;
+
+        let commitment_mask_key_id = self.key_manager.import_key(commitment_mask.clone()).await?;
+        let script_spending_key = self
+            .key_manager
+            .stealth_address_script_spending_key(&commitment_mask_key_id, receiver_address.public_spend_key())
+            .await?;
+        let script = push_pubkey_script(&script_spending_key);
+        let sender_offset_key_id =
+            managed_key_id(TransactionKeyManagerBranch::OneSidedSenderOffset, sender_offset_index)?;
+        self.journal.lock().await.record(sender_offset_index, SignedPayment {
+            receiver_address: receiver_address.clone(),
+            value,
+            commitment: CommitmentFactory::default().commit_value(commitment_mask, value.as_u64()),
+            script: script.clone(),
+            sender_offset_public_key: self.key_manager.get_public_key_at_key_id(&sender_offset_key_id).await?,
+            spent: false,
+        })?;
+        Ok(self
+            .key_manager
+            .get_one_sided_metadata_signature(
+                &commitment_mask_key_id,
+                value,
+                &sender_offset_key_id,
+                &txo_version,
+                metadata_signature_message_common,
+                range_proof_type,
+                &script,
+                receiver_address,
+            )
+            .await?)
+    }
+
+    /// Signs the inputs of a transaction and sets its script offset. Every input must be opened, and every output must
+    /// either be change back to the wallet or a one-sided payment from the journal, so that the signer knows exactly
+    /// what the transaction spends on payments and its fee before the policy is applied to it.
+    #[allow(clippy::too_many_lines)]
+    pub async fn sign_transaction(
+        &self,
+        mut transaction: Transaction,
+        inputs: &[RemoteSignerInput],
+        change_outputs: &[RemoteSignerChangeOutput],
+        partial_script_offset: &PrivateKey,
+    ) -> Result<Transaction, SignerError> {
+        // The journal is held until the payments are marked as spent, so that they cannot be paid twice
+        let mut journal = self.journal.lock().await;
+        let factory = CommitmentFactory::default();
+
+        if inputs.len() != transaction.body.inputs().len() {
+            return Err(SignerError::InvalidRequest(
+                "Every input of the transaction must be opened".to_string(),
+            ));
+        }
+        let mut opened = Vec::<Commitment>::with_capacity(inputs.len());
+        let mut total_input_value = MicroMinotari::zero();
+        let mut total_script_public_key = PublicKey::default();
+        let mut script_offset = partial_script_offset.clone();
+        let mut script_signatures = Vec::new();
+        for input in inputs {
+            let commitment = factory.commit_value(&input.commitment_mask, input.value.as_u64());
+            let transaction_input = find_input(&transaction, &commitment)?;
+            if opened.contains(&commitment) {
+                return Err(SignerError::InvalidRequest(format!(
+                    "The input {} is opened twice",
+                    commitment.to_hex()
+                )));
+            }
+            total_input_value = checked_add(total_input_value, input.value)?;
+            let script_public_key = transaction_input.run_script(None)?;
+            total_script_public_key = &total_script_public_key + &script_public_key;
+
+            match &input.script_key {
+                Some(script_key) => {
+                    let script_key_id = match script_key {
+                        RemoteScriptKey::Managed { branch, index } => managed_key_id(*branch, *index)?,
+                        RemoteScriptKey::Derived { commitment_mask } => self.derived_key_id(commitment_mask).await?,
+                    };
+                    if self.key_manager.get_public_key_at_key_id(&script_key_id).await? != script_public_key {
+                        return Err(SignerError::InvalidRequest(format!(
+                            "The script key of the input {} does not match its script",
+                            commitment.to_hex()
+                        )));
+                    }
+                    let script_message = TransactionInput::build_script_signature_message(
+                        &transaction_input.version,
+                        transaction_input.script()?,
+                        &transaction_input.input_data,
+                    );
+                    let commitment_mask_key_id = self.key_manager.import_key(input.commitment_mask.clone()).await?;
+                    let script_signature = self
+                        .key_manager
+                        .get_script_signature(
+                            &script_key_id,
+                            &commitment_mask_key_id,
+                            &input.value.into(),
+                            &transaction_input.version,
+                            &script_message,
+                        )
+                        .await?;
+                    script_signatures.push((commitment.clone(), script_signature));
+                    script_offset = &script_offset + &self.key_manager.get_private_key(&script_key_id).await?;
+                },
+                // The wallet holds the script key of this input and has signed it itself
+                None => transaction_input.validate_script_signature(&script_public_key, &factory)?,
+            }
+            opened.push(commitment);
+        }
+
+        let mut total_change_value = MicroMinotari::zero();
+        let mut total_payment_value = MicroMinotari::zero();
+        let mut payments = Vec::new();
+        let mut total_sender_offset_public_key = PublicKey::default();
+        for output in transaction.body.outputs() {
+            total_sender_offset_public_key = &total_sender_offset_public_key + &output.sender_offset_public_key;
+            let change = change_outputs.iter().find(|change| {
+                factory.commit_value(&change.commitment_mask, change.value.as_u64()) == output.commitment
+            });
+            if let Some(change) = change {
+                // Change must stay spendable with the spend key only, or the wallet could move it past the policy
+                let script_key_id = self.derived_key_id(&change.commitment_mask).await?;
+                let script_public_key = self.key_manager.get_public_key_at_key_id(&script_key_id).await?;
+                if output.script != push_pubkey_script(&script_public_key) {
+                    return Err(SignerError::InvalidRequest(format!(
+                        "The change output {} is not spendable by the signer",
+                        output.commitment.to_hex()
+                    )));
+                }
+                total_change_value = checked_add(total_change_value, change.value)?;
+                continue;
+            }
+            match journal.find_unspent(&output.sender_offset_public_key) {
+                Some((index, payment))
+                    if payment.commitment == output.commitment && payment.script == output.script =>
+                {
+                    let sender_offset_key_id =
+                        managed_key_id(TransactionKeyManagerBranch::OneSidedSenderOffset, index)?;
+                    script_offset = script_offset - self.key_manager.get_private_key(&sender_offset_key_id).await?;
+                    total_payment_value = checked_add(total_payment_value, payment.value)?;
+                    payments.push((index, payment.receiver_address.clone()));
+                },
+                _ => {
+                    return Err(SignerError::InvalidRequest(format!(
+                        "The output {} is neither change nor a one-sided payment that the signer has signed",
+                        output.commitment.to_hex()
+                    )));
+                },
+            }
+        }
+        // Without a sender offset that only the signer knows, the script offset would reveal the spend key
+        if payments.is_empty() {
+            return Err(SignerError::InvalidRequest(
+                "The transaction does not pay any one-sided payment that the signer has signed".to_string(),
+            ));
+        }
+        let amount = total_input_value
+            .checked_sub(total_change_value)
+            .filter(|amount| *amount >= total_payment_value)
+            .ok_or_else(|| SignerError::InvalidRequest("The outputs are worth more than the inputs".to_string()))?;
+
+        // The partial script offset of the wallet must make the script offset balance the transaction
+        if PublicKey::from_secret_key(&script_offset) != total_script_public_key - total_sender_offset_public_key {
+            return Err(SignerError::InvalidRequest(
+                "The partial script offset does not balance the transaction".to_string(),
+            ));
+        }
+
+        for (commitment, script_signature) in script_signatures {
+            transaction
+                .body
+                .update_script_signature(&commitment, script_signature)?;
+        }
+        transaction.script_offset = script_offset;
+
+        let (indexes, destinations): (Vec<_>, Vec<_>) = payments.into_iter().unzip();
+        self.policy
+            .lock()
+            .await
+            .approve(amount, &destinations, Instant::now())?;
+        journal.mark_spent(&indexes)?;
+        Ok(transaction)
+    }
+
+    /// The stealth key derived from the spend key and the commitment mask
+    async fn derived_key_id(&self, commitment_mask: &PrivateKey) -> Result<TariKeyId, SignerError> {
+        let commitment_mask_key_id = self.key_manager.import_key(commitment_mask.clone()).await?;
+        let key_id = KeyId::Derived {
+            key: (&commitment_mask_key_id).into(),
+        };
+        // Fail early if the key cannot be derived
+        let _key = self.key_manager.get_private_key(&key_id).await?;
+        Ok(key_id)
+    }
+}
+
+fn checked_add(total: MicroMinotari, value: MicroMinotari) -> Result<MicroMinotari, SignerError> {
+    total
+        .checked_add(value)
+        .ok_or_else(|| SignerError::InvalidRequest("The values overflow".to_string()))
+}
+
+fn find_input<'a>(transaction: &'a Transaction, commitment: &Commitment) -> Result<&'a TransactionInput, SignerError> {
+    transaction
+        .body
+        .inputs()
+        .iter()
+        .find(|input| input.commitment().ok() == Some(commitment))
+        .ok_or_else(|| {
+            SignerError::InvalidRequest(format!(
+                "The opened input {} is not in the transaction",
+                commitment.to_hex()
+            ))
+        })
+}
+
+fn managed_key_id(branch: TransactionKeyManagerBranch, index: u64) -> Result<TariKeyId, SignerError> {
+    let branch = branch.get_branch_key();
+    if !is_remote_signer_branch(&branch) {
+        return Err(SignerError::BranchNotHeld(branch));
+    }
+    Ok(KeyId::Managed { branch, index })
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::OsRng;
+    use tari_core::transactions::key_manager::create_memory_db_key_manager;
+    use tari_crypto::keys::SecretKey;
+
+    use super::*;
+    use crate::policy::SignerPolicyConfig;
+
+    fn signer(policy: SignerPolicyConfig) -> Signer {
+        Signer::new(
+            create_memory_db_key_manager().unwrap(),
+            Network::LocalNet,
+            SignerPolicy::from_config(&policy).unwrap(),
+            SenderOffsetJournal::default(),
+        )
+    }
+
+    fn random_address() -> TariAddress {
+        let (_, view_key) = PublicKey::random_keypair(&mut OsRng);
+        let (_, spend_key) = PublicKey::random_keypair(&mut OsRng);
+        TariAddress::new_dual_address_with_default_features(view_key, spend_key, Network::LocalNet)
+    }
+
+    async fn sign_payment(
+        signer: &Signer,
+        value: u64,
+        sender_offset_index: u64,
+        receiver_address: &TariAddress,
+    ) -> Result<ComAndPubSignature, SignerError> {
+        signer
+            .get_one_sided_metadata_signature(
+                0,
+                MicroMinotari(value),
+                sender_offset_index,
+                &PrivateKey::random(&mut OsRng),
+                receiver_address,
+                &[0u8; 32],
+                RangeProofType::BulletProofPlus,
+            )
+            .await
+    }
+
+    #[tokio::test]
+    async fn it_only_uses_the_signer_branches() {
+        let signer = signer(SignerPolicyConfig::default());
+        assert!(signer
+            .get_public_key(TransactionKeyManagerBranch::Spend, 0)
+            .await
+            .is_ok());
+        assert!(matches!(
+            signer
+                .get_public_key(TransactionKeyManagerBranch::CommitmentMask, 0)
+                .await,
+            Err(SignerError::BranchNotHeld(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn it_only_shares_the_public_wallet_keys() {
+        let signer = signer(SignerPolicyConfig::default());
+        let (_, public_view_key) = signer.get_wallet_keys().await.unwrap();
+        let view_key = signer.get_private_view_key().await.unwrap();
+        assert_eq!(public_view_key, PublicKey::from_secret_key(&view_key));
+    }
+
+    #[tokio::test]
+    async fn it_never_uses_a_sender_offset_for_two_payments() {
+        let signer = signer(SignerPolicyConfig::default());
+        assert!(sign_payment(&signer, 100, 1, &random_address()).await.is_ok());
+        assert!(matches!(
+            sign_payment(&signer, 100, 1, &random_address()).await,
+            Err(SignerError::SenderOffsetReused(1))
+        ));
+        assert!(sign_payment(&signer, 100, 2, &random_address()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn it_refuses_payments_that_the_policy_does_not_allow() {
+        let allowed = random_address();
+        let signer = signer(SignerPolicyConfig {
+            max_amount: Some(1_000),
+            allowed_destinations: vec![allowed.to_base58()],
+            ..Default::default()
+        });
+        assert!(matches!(
+            sign_payment(&signer, 1_001, 1, &allowed).await,
+            Err(SignerError::PolicyViolation(_))
+        ));
+        assert!(matches!(
+            sign_payment(&signer, 100, 2, &random_address()).await,
+            Err(SignerError::PolicyViolation(_))
+        ));
+        assert!(sign_payment(&signer, 100, 3, &allowed).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn it_refuses_transactions_that_do_not_pay_a_signed_payment() {
+        let signer = signer(SignerPolicyConfig::default());
+        let transaction = Transaction::new(vec![], vec![], vec![], PrivateKey::default(), PrivateKey::default());
+        assert!(matches!(
+            signer
+                .sign_transaction(transaction, &[], &[], &PrivateKey::default())
+                .await,
+            Err(SignerError::InvalidRequest(_))
+        ));
+    }
+}
@@ -0,0 +1,94 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::{path::PathBuf, time::Duration};
+
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+#[clap(
+    author,
+    version,
+    about = "Generates transaction load against a set of wallets and reports confirmation latency"
+)]
+pub struct Cli {
+    /// gRPC address of a wallet to drive, e.g. http://127.0.0.1:18143. Repeat to drive several wallets; transactions
+    /// are sent round-robin from each wallet to the others, so at least two wallets are recommended.
+    #[clap(long = "wallet", required = true)]
+    pub wallets: Vec<String>,
+    /// Username for wallet gRPC basic authentication, used for all wallets
+    #[clap(long, requires = "password")]
+    pub username: Option<String>,
+    /// Password for wallet gRPC basic authentication, used for all wallets
+    #[clap(long, requires = "username")]
+    pub password: Option<String>,
+    /// Target number of transactions submitted per second across all wallets
+    #[clap(long, default_value_t = 1.0)]
+    pub tx_rate: f64,
+    /// How long to generate load for, in seconds
+    #[clap(long, default_value_t = 60)]
+    pub duration: u64,
+    /// Number of recipient outputs per transfer
+    #[clap(long, default_value_t = 1)]
+    pub outputs_per_tx: usize,
+    /// Fraction of transfers, between 0 and 1, that are sent one-sided to a stealth address. The remainder are
+    /// interactive.
+    #[clap(long, default_value_t = 1.0)]
+    pub one_sided_ratio: f64,
+    /// Amount sent to each recipient, in µT
+    #[clap(long, default_value_t = 10_000)]
+    pub amount: u64,
+    /// Fee per gram, in µT
+    #[clap(long, default_value_t = 5)]
+    pub fee_per_gram: u64,
+    /// How long to keep waiting for outstanding transactions to be mined after load generation ends, in seconds
+    #[clap(long, default_value_t = 600)]
+    pub confirmation_timeout: u64,
+    /// How often the wallets are polled for transaction status, in seconds
+    #[clap(long, default_value_t = 5)]
+    pub poll_interval: u64,
+    /// Also write the report as JSON to this file
+    #[clap(long)]
+    pub report: Option<PathBuf>,
+}
+
+impl Cli {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.tx_rate.is_finite() && self.tx_rate > 0.0) {
+            return Err("--tx-rate must be greater than zero".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.one_sided_ratio) {
+            return Err("--one-sided-ratio must be between 0 and 1".to_string());
+        }
+        if self.outputs_per_tx == 0 {
+            return Err("--outputs-per-tx must be at least 1".to_string());
+        }
+        if self.poll_interval == 0 {
+            return Err("--poll-interval must be at least 1".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn submit_interval(&self) -> Duration {
+        Duration::from_secs_f64(1.0 / self.tx_rate)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_validates_arguments() {
+        let cli = Cli::parse_from(["tari_stress_test", "--wallet", "http://127.0.0.1:18143"]);
+        assert!(cli.validate().is_ok());
+        assert_eq!(cli.submit_interval(), Duration::from_secs(1));
+
+        let cli = Cli::parse_from(["tari_stress_test", "--wallet", "a", "--one-sided-ratio", "1.5"]);
+        assert!(cli.validate().is_err());
+        let cli = Cli::parse_from(["tari_stress_test", "--wallet", "a", "--tx-rate", "0"]);
+        assert!(cli.validate().is_err());
+        let cli = Cli::parse_from(["tari_stress_test", "--wallet", "a", "--outputs-per-tx", "0"]);
+        assert!(cli.validate().is_err());
+    }
+}
@@ -0,0 +1,239 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, Context};
+use minotari_wallet_grpc_client::{
+    grpc::{
+        payment_recipient::PaymentType,
+        Empty,
+        GetTransactionInfoRequest,
+        PaymentRecipient,
+        TransactionStatus,
+        TransferRequest,
+    },
+    GrpcAuthentication,
+    WalletGrpcClient,
+};
+use rand::Rng;
+use tari_common_types::tari_address::TariAddress;
+use tokio::time::{self, MissedTickBehavior};
+use tonic::transport::Channel;
+
+use crate::{
+    cli::Cli,
+    report::{LatencySummary, Report},
+};
+
+struct Wallet {
+    grpc_address: String,
+    client: WalletGrpcClient<Channel>,
+    interactive_address: String,
+    one_sided_address: String,
+}
+
+/// Transactions awaiting confirmation, keyed by the index of the sending wallet and the transaction id, with the time
+/// they were submitted
+type Pending = HashMap<(usize, u64), Instant>;
+
+pub struct LoadGenerator {
+    cli: Cli,
+    wallets: Vec<Wallet>,
+}
+
+impl LoadGenerator {
+    pub async fn connect(cli: Cli) -> Result<Self, anyhow::Error> {
+        let auth = match (&cli.username, &cli.password) {
+            (Some(username), Some(password)) => GrpcAuthentication::Basic {
+                username: username.clone(),
+                password: password.as_str().into(),
+            },
+            _ => GrpcAuthentication::None,
+        };
+        let mut wallets = Vec::with_capacity(cli.wallets.len());
+        for grpc_address in &cli.wallets {
+            let mut client = WalletGrpcClient::connect_with_auth(grpc_address, &auth)
+                .await
+                .with_context(|| format!("Could not connect to wallet at {}", grpc_address))?;
+            let addresses = client.get_address(Empty {}).await?.into_inner();
+            wallets.push(Wallet {
+                grpc_address: grpc_address.clone(),
+                client,
+                interactive_address: to_base58(&addresses.interactive_address)?,
+                one_sided_address: to_base58(&addresses.one_sided_address)?,
+            });
+        }
+        Ok(Self { cli, wallets })
+    }
+
+    /// Generates load for the configured duration, then waits for outstanding transactions to be mined or for the
+    /// confirmation timeout to expire
+    pub async fn run(mut self) -> Report {
+        let mut report = Report {
+            wallets: self.wallets.len(),
+            target_tx_rate: self.cli.tx_rate,
+            ..Default::default()
+        };
+        let mut pending = Pending::new();
+        let mut latencies = Vec::new();
+        let poll_interval = Duration::from_secs(self.cli.poll_interval);
+
+        let start = Instant::now();
+        let load_end = start + Duration::from_secs(self.cli.duration);
+        let mut submit = time::interval(self.cli.submit_interval());
+        submit.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let mut poll = time::interval(poll_interval);
+        poll.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let mut transfers = 0usize;
+        while Instant::now() < load_end {
+            tokio::select! {
+                _ = submit.tick() => {
+                    let sender = transfers % self.wallets.len();
+                    self.submit(sender, &mut pending, &mut report).await;
+                    transfers += 1;
+                },
+                _ = poll.tick() => self.poll(&mut pending, &mut latencies, &mut report).await,
+            }
+        }
+        let load_duration = start.elapsed().as_secs_f64();
+        report.duration_secs = load_duration;
+        report.achieved_tx_rate = transfers as f64 / load_duration;
+        println!(
+            "Load generation finished. Waiting for {} outstanding transaction(s)...",
+            pending.len()
+        );
+
+        let deadline = Instant::now() + Duration::from_secs(self.cli.confirmation_timeout);
+        while !pending.is_empty() && Instant::now() < deadline {
+            time::sleep(poll_interval).await;
+            self.poll(&mut pending, &mut latencies, &mut report).await;
+        }
+        report.timed_out = pending.len();
+        report.latency = LatencySummary::from_latencies(&latencies);
+        report
+    }
+
+    async fn submit(&mut self, sender: usize, pending: &mut Pending, report: &mut Report) {
+        let one_sided = rand::thread_rng().gen_bool(self.cli.one_sided_ratio);
+        let recipients = self
+            .recipients_for(sender)
+            .map(|wallet| PaymentRecipient {
+                address: if one_sided {
+                    wallet.one_sided_address.clone()
+                } else {
+                    wallet.interactive_address.clone()
+                },
+                amount: self.cli.amount,
+                fee_per_gram: self.cli.fee_per_gram,
+                message: "stress test".to_string(),
+                payment_type: if one_sided {
+                    PaymentType::OneSidedToStealthAddress as i32
+                } else {
+                    PaymentType::StandardMimblewimble as i32
+                },
+                payment_id: vec![],
+            })
+            .collect::<Vec<_>>();
+        let num_recipients = recipients.len();
+
+        let wallet = &mut self.wallets[sender];
+        match wallet.client.transfer(TransferRequest { recipients }).await {
+            Ok(response) => {
+                let submitted_at = Instant::now();
+                for result in response.into_inner().results {
+                    if result.is_success {
+                        pending.insert((sender, result.transaction_id), submitted_at);
+                        report.submitted += 1;
+                        if one_sided {
+                            report.submitted_one_sided += 1;
+                        } else {
+                            report.submitted_interactive += 1;
+                        }
+                    } else {
+                        report.submit_failures += 1;
+                        eprintln!(
+                            "Wallet {} failed to send to {}: {}",
+                            wallet.grpc_address, result.address, result.failure_message
+                        );
+                    }
+                }
+            },
+            Err(status) => {
+                report.submit_failures += num_recipients;
+                eprintln!("Transfer from wallet {} failed: {}", wallet.grpc_address, status);
+            },
+        }
+    }
+
+    /// The wallets that receive the outputs of a transfer from `sender`. Recipients are spread over the other wallets,
+    /// or are the sender itself if it is the only wallet.
+    fn recipients_for(&self, sender: usize) -> impl Iterator<Item = &Wallet> {
+        let num_wallets = self.wallets.len();
+        (0..self.cli.outputs_per_tx).map(move |k| {
+            if num_wallets == 1 {
+                &self.wallets[0]
+            } else {
+                let offset = 1 + k % (num_wallets - 1);
+                &self.wallets[(sender + offset) % num_wallets]
+            }
+        })
+    }
+
+    async fn poll(&mut self, pending: &mut Pending, latencies: &mut Vec<Duration>, report: &mut Report) {
+        for (index, wallet) in self.wallets.iter_mut().enumerate() {
+            let transaction_ids = pending
+                .keys()
+                .filter(|(sender, _)| *sender == index)
+                .map(|(_, tx_id)| *tx_id)
+                .collect::<Vec<_>>();
+            if transaction_ids.is_empty() {
+                continue;
+            }
+            let transactions = match wallet
+                .client
+                .get_transaction_info(GetTransactionInfoRequest { transaction_ids })
+                .await
+            {
+                Ok(response) => response.into_inner().transactions,
+                Err(status) => {
+                    eprintln!("Could not poll wallet {}: {}", wallet.grpc_address, status);
+                    continue;
+                },
+            };
+            for tx in transactions {
+                let key = (index, tx.tx_id);
+                match tx.status() {
+                    TransactionStatus::MinedUnconfirmed |
+                    TransactionStatus::MinedConfirmed |
+                    TransactionStatus::OneSidedUnconfirmed |
+                    TransactionStatus::OneSidedConfirmed => {
+                        if let Some(submitted_at) = pending.remove(&key) {
+                            latencies.push(submitted_at.elapsed());
+                            report.mined += 1;
+                        }
+                    },
+                    TransactionStatus::Rejected => {
+                        if pending.remove(&key).is_some() {
+                            report.rejected += 1;
+                        }
+                    },
+                    _ => {
+                        if tx.is_cancelled && pending.remove(&key).is_some() {
+                            report.rejected += 1;
+                        }
+                    },
+                }
+            }
+        }
+    }
+}
+
+fn to_base58(address: &[u8]) -> Result<String, anyhow::Error> {
+    TariAddress::from_bytes(address)
+        .map(|a| a.to_base58())
+        .map_err(|e| anyhow!("Wallet returned an invalid address: {}", e))
+}
@@ -0,0 +1,39 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Drives one or more wallets over gRPC to generate a configurable transaction load, then reports how long the
+//! transactions took to be mined. Intended for pre-release network soak testing.
+
+mod cli;
+mod generator;
+mod report;
+
+use std::{fs, process};
+
+use clap::Parser;
+
+use crate::{cli::Cli, generator::LoadGenerator};
+
+#[tokio::main]
+async fn main() {
+    if let Err(err) = main_inner().await {
+        eprintln!("{:#}", err);
+        process::exit(1);
+    }
+}
+
+async fn main_inner() -> Result<(), anyhow::Error> {
+    let cli = Cli::parse();
+    cli.validate().map_err(anyhow::Error::msg)?;
+    let report_path = cli.report.clone();
+
+    let generator = LoadGenerator::connect(cli).await?;
+    let report = generator.run().await;
+
+    println!("{}", report);
+    if let Some(path) = report_path {
+        fs::write(&path, serde_json::to_string_pretty(&report)?)?;
+        println!("Report written to {}", path.display());
+    }
+    Ok(())
+}
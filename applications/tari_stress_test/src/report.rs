@@ -0,0 +1,118 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::{fmt, time::Duration};
+
+use serde::Serialize;
+
+/// Confirmation latency percentiles, in seconds
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct LatencySummary {
+    pub min: f64,
+    pub mean: f64,
+    pub p50: f64,
+    pub p90: f64,
+    pub p99: f64,
+    pub max: f64,
+}
+
+impl LatencySummary {
+    /// Summarises the given latencies. Returns `None` if there are none.
+    pub fn from_latencies(latencies: &[Duration]) -> Option<Self> {
+        if latencies.is_empty() {
+            return None;
+        }
+        let mut sorted = latencies.to_vec();
+        sorted.sort();
+        let total = sorted.iter().sum::<Duration>();
+        Some(Self {
+            min: sorted[0].as_secs_f64(),
+            mean: total.as_secs_f64() / sorted.len() as f64,
+            p50: percentile(&sorted, 50).as_secs_f64(),
+            p90: percentile(&sorted, 90).as_secs_f64(),
+            p99: percentile(&sorted, 99).as_secs_f64(),
+            max: sorted[sorted.len() - 1].as_secs_f64(),
+        })
+    }
+}
+
+/// Nearest-rank percentile of a sorted, non-empty slice
+fn percentile(sorted: &[Duration], percent: usize) -> Duration {
+    let rank = (percent * sorted.len() + 99) / 100;
+    sorted[rank.saturating_sub(1).min(sorted.len() - 1)]
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Report {
+    pub wallets: usize,
+    pub duration_secs: f64,
+    /// Transfers submitted per second
+    pub target_tx_rate: f64,
+    pub achieved_tx_rate: f64,
+    /// Transactions accepted by the sending wallet, one per recipient output
+    pub submitted: usize,
+    pub submitted_one_sided: usize,
+    pub submitted_interactive: usize,
+    /// Recipient outputs that the sending wallet refused, or whose transfer failed at the gRPC layer
+    pub submit_failures: usize,
+    pub mined: usize,
+    /// Transactions that were rejected or cancelled after submission
+    pub rejected: usize,
+    /// Transactions that were still not mined when the confirmation timeout expired
+    pub timed_out: usize,
+    pub latency: Option<LatencySummary>,
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Wallets:               {}", self.wallets)?;
+        writeln!(f, "Load duration:         {:.1}s", self.duration_secs)?;
+        writeln!(
+            f,
+            "Transaction rate:      {:.2} tx/s (target {:.2} tx/s)",
+            self.achieved_tx_rate, self.target_tx_rate
+        )?;
+        writeln!(
+            f,
+            "Submitted:             {} ({} one-sided, {} interactive)",
+            self.submitted, self.submitted_one_sided, self.submitted_interactive
+        )?;
+        writeln!(f, "Submit failures:       {}", self.submit_failures)?;
+        writeln!(f, "Mined:                 {}", self.mined)?;
+        writeln!(f, "Rejected or cancelled: {}", self.rejected)?;
+        writeln!(f, "Timed out:             {}", self.timed_out)?;
+        match &self.latency {
+            Some(l) => write!(
+                f,
+                "Mined latency (s):     min {:.1} / mean {:.1} / p50 {:.1} / p90 {:.1} / p99 {:.1} / max {:.1}",
+                l.min, l.mean, l.p50, l.p90, l.p99, l.max
+            ),
+            None => write!(f, "Mined latency (s):     n/a"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_summarises_latencies() {
+        assert!(LatencySummary::from_latencies(&[]).is_none());
+
+        let latencies = (1..=100).rev().map(Duration::from_secs).collect::<Vec<_>>();
+        let summary = LatencySummary::from_latencies(&latencies).unwrap();
+        assert_eq!(summary, LatencySummary {
+            min: 1.0,
+            mean: 50.5,
+            p50: 50.0,
+            p90: 90.0,
+            p99: 99.0,
+            max: 100.0,
+        });
+
+        let summary = LatencySummary::from_latencies(&[Duration::from_secs(7)]).unwrap();
+        assert_eq!(summary.p50, 7.0);
+        assert_eq!(summary.p99, 7.0);
+    }
+}
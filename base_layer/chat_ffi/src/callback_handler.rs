@@ -99,6 +99,7 @@ impl CallbackHandler {
                                     );
                                     self.trigger_read_confirmation_received(c.clone());
                                 }
+                                MessageDispatch::Presence(_) => {},
                             };
                         },
                         Err(e) => {
@@ -121,7 +122,7 @@ impl CallbackHandler {
                                     );
                                     self.trigger_contact_status_change(data.deref().clone());
                                 }
-                                ContactsLivenessEvent::NetworkSilence => {},
+                                ContactsLivenessEvent::StatusChanged(_) | ContactsLivenessEvent::NetworkSilence => {},
                             }
                         },
                         Err(e) => {
@@ -0,0 +1,113 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use tari_common::configuration::serializers;
+use tari_utilities::SafePassword;
+
+/// Settings for the periodic encrypted backup service, shared by the base node and the wallet
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BackupConfig {
+    /// Take periodic encrypted snapshots
+    pub enabled: bool,
+    /// The time between snapshots
+    #[serde(with = "serializers::seconds")]
+    pub interval: Duration,
+    /// The number of snapshots to keep. Older snapshots are deleted once a new snapshot has been verified.
+    pub max_snapshots: usize,
+    /// The directory that snapshots are written to, if no S3 or WebDAV server is configured. Relative paths are
+    /// relative to the application data directory.
+    pub path: PathBuf,
+    /// Write snapshots to an S3-compatible object store instead of the local `path`
+    pub s3: Option<S3Config>,
+    /// Write snapshots to a WebDAV server instead of the local `path`
+    pub webdav: Option<WebDavConfig>,
+    /// The passphrase that snapshots are encrypted with. Backups are not taken if this is not set.
+    #[serde(deserialize_with = "deserialize_safe_password_option")]
+    pub passphrase: Option<SafePassword>,
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval: Duration::from_secs(6 * 60 * 60),
+            max_snapshots: 7,
+            path: PathBuf::from("backups"),
+            s3: None,
+            webdav: None,
+            passphrase: None,
+        }
+    }
+}
+
+impl BackupConfig {
+    pub fn set_base_path<P: AsRef<Path>>(&mut self, base_path: P) {
+        if !self.path.is_absolute() {
+            self.path = base_path.as_ref().join(self.path.as_path());
+        }
+    }
+}
+
+/// An S3-compatible object store. Requests are signed with AWS signature version 4 and use path-style addressing, so
+/// that self-hosted stores such as MinIO work without DNS configuration.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct S3Config {
+    /// The endpoint URL, e.g. `https://s3.eu-west-1.amazonaws.com` or `http://127.0.0.1:9000`
+    pub endpoint: String,
+    pub bucket: String,
+    /// The key prefix that snapshots are stored under
+    #[serde(default)]
+    pub prefix: String,
+    #[serde(default = "default_region")]
+    pub region: String,
+    pub access_key: String,
+    #[serde(deserialize_with = "deserialize_safe_password")]
+    pub secret_key: SafePassword,
+}
+
+/// A WebDAV collection, such as a Nextcloud folder. Objects are stored directly in the collection, which must exist.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WebDavConfig {
+    /// The URL of the collection, e.g. `https://cloud.example.com/remote.php/dav/files/alice/tari`
+    pub url: String,
+    /// The user name for basic authentication. Requests are not authenticated if this is empty.
+    #[serde(default)]
+    pub username: String,
+    #[serde(default, deserialize_with = "deserialize_safe_password_option")]
+    pub password: Option<SafePassword>,
+}
+
+/// The URLs of the configured S3 and WebDAV stores
+pub fn remote_store_urls<'a>(
+    s3: Option<&'a S3Config>,
+    webdav: Option<&'a WebDavConfig>,
+) -> impl Iterator<Item = &'a str> {
+    s3.map(|s3| s3.endpoint.as_str())
+        .into_iter()
+        .chain(webdav.map(|webdav| webdav.url.as_str()))
+}
+
+fn default_region() -> String {
+    "us-east-1".to_string()
+}
+
+fn deserialize_safe_password<'de, D>(deserializer: D) -> Result<SafePassword, D::Error>
+where D: serde::Deserializer<'de> {
+    let password: String = Deserialize::deserialize(deserializer)?;
+    Ok(SafePassword::from(password))
+}
+
+fn deserialize_safe_password_option<'de, D>(deserializer: D) -> Result<Option<SafePassword>, D::Error>
+where D: serde::Deserializer<'de> {
+    let password: Option<String> = Deserialize::deserialize(deserializer)?;
+    Ok(password.map(SafePassword::from))
+}
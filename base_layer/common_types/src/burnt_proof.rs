@@ -20,14 +20,190 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use tari_crypto::ristretto::RistrettoComSig;
+use tari_crypto::ristretto::{RistrettoComSig, RistrettoSecretKey};
+use tari_utilities::{ByteArray, ByteArrayError};
+use thiserror::Error;
 
 use crate::types::{BulletRangeProof, Commitment, PublicKey};
 
+/// The current version of the portable claim proof encoding
+pub const BURNT_PROOF_VERSION: u8 = 1;
+
+const CLAIM_PUBLIC_KEY_FLAG: u8 = 0b0000_0001;
+const OWNERSHIP_PROOF_FLAG: u8 = 0b0000_0010;
+const KEY_SIZE: usize = 32;
+
 #[derive(Debug, Clone)]
 pub struct BurntProof {
     pub reciprocal_claim_public_key: PublicKey,
     pub commitment: Commitment,
     pub ownership_proof: Option<RistrettoComSig>,
     pub range_proof: BulletRangeProof,
+    /// The claim key that the burn kernel commits to, if the burn is claimable
+    pub claim_public_key: Option<PublicKey>,
+}
+
+impl BurntProof {
+    /// Encodes the proof into a self-contained, versioned byte string that can be handed to a second layer to claim
+    /// the burnt funds.
+    ///
+    /// Layout: `version | reciprocal_claim_public_key | commitment | flags | [claim_public_key] |
+    /// [ownership_proof (nonce, u, v)] | range_proof`
+    pub fn to_portable_bytes(&self) -> Vec<u8> {
+        let mut flags = 0u8;
+        if self.claim_public_key.is_some() {
+            flags |= CLAIM_PUBLIC_KEY_FLAG;
+        }
+        if self.ownership_proof.is_some() {
+            flags |= OWNERSHIP_PROOF_FLAG;
+        }
+
+        let mut buf = Vec::with_capacity(2 + KEY_SIZE * 6 + self.range_proof.as_bytes().len());
+        buf.push(BURNT_PROOF_VERSION);
+        buf.extend_from_slice(self.reciprocal_claim_public_key.as_bytes());
+        buf.extend_from_slice(self.commitment.as_bytes());
+        buf.push(flags);
+        if let Some(claim_public_key) = &self.claim_public_key {
+            buf.extend_from_slice(claim_public_key.as_bytes());
+        }
+        if let Some(proof) = &self.ownership_proof {
+            buf.extend_from_slice(proof.public_nonce().as_bytes());
+            buf.extend_from_slice(proof.u().as_bytes());
+            buf.extend_from_slice(proof.v().as_bytes());
+        }
+        buf.extend_from_slice(self.range_proof.as_bytes());
+        buf
+    }
+
+    /// Decodes a proof previously encoded with [BurntProof::to_portable_bytes]
+    pub fn from_portable_bytes(bytes: &[u8]) -> Result<Self, BurntProofError> {
+        let (version, rest) = bytes.split_first().ok_or(BurntProofError::UnexpectedEnd)?;
+        if *version != BURNT_PROOF_VERSION {
+            return Err(BurntProofError::UnsupportedVersion(*version));
+        }
+        let (reciprocal_claim_public_key, rest) = take(rest, KEY_SIZE)?;
+        let (commitment, rest) = take(rest, KEY_SIZE)?;
+        let (flags, mut rest) = rest.split_first().ok_or(BurntProofError::UnexpectedEnd)?;
+        if flags & !(CLAIM_PUBLIC_KEY_FLAG | OWNERSHIP_PROOF_FLAG) != 0 {
+            return Err(BurntProofError::InvalidFlags(*flags));
+        }
+
+        let mut claim_public_key = None;
+        if flags & CLAIM_PUBLIC_KEY_FLAG != 0 {
+            let (key, remaining) = take(rest, KEY_SIZE)?;
+            claim_public_key = Some(PublicKey::from_canonical_bytes(key)?);
+            rest = remaining;
+        }
+
+        let mut ownership_proof = None;
+        if flags & OWNERSHIP_PROOF_FLAG != 0 {
+            let (nonce, remaining) = take(rest, KEY_SIZE)?;
+            let (u, remaining) = take(remaining, KEY_SIZE)?;
+            let (v, remaining) = take(remaining, KEY_SIZE)?;
+            ownership_proof = Some(RistrettoComSig::new(
+                Commitment::from_canonical_bytes(nonce)?,
+                RistrettoSecretKey::from_canonical_bytes(u)?,
+                RistrettoSecretKey::from_canonical_bytes(v)?,
+            ));
+            rest = remaining;
+        }
+
+        if rest.is_empty() {
+            return Err(BurntProofError::UnexpectedEnd);
+        }
+
+        Ok(Self {
+            reciprocal_claim_public_key: PublicKey::from_canonical_bytes(reciprocal_claim_public_key)?,
+            commitment: Commitment::from_canonical_bytes(commitment)?,
+            ownership_proof,
+            range_proof: BulletRangeProof::from_canonical_bytes(rest)?,
+            claim_public_key,
+        })
+    }
+}
+
+fn take(bytes: &[u8], len: usize) -> Result<(&[u8], &[u8]), BurntProofError> {
+    if bytes.len() < len {
+        return Err(BurntProofError::UnexpectedEnd);
+    }
+    Ok(bytes.split_at(len))
+}
+
+#[derive(Debug, Error)]
+pub enum BurntProofError {
+    #[error("Unsupported burnt proof version {0}")]
+    UnsupportedVersion(u8),
+    #[error("Invalid burnt proof flags {0:#04x}")]
+    InvalidFlags(u8),
+    #[error("Burnt proof ended unexpectedly")]
+    UnexpectedEnd,
+    #[error("Invalid burnt proof field: {0}")]
+    InvalidField(#[from] ByteArrayError),
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::OsRng;
+    use tari_crypto::keys::{PublicKey as PublicKeyTrait, SecretKey};
+
+    use super::*;
+
+    fn random_proof(with_claim_key: bool) -> BurntProof {
+        let (_, reciprocal_claim_public_key) = PublicKey::random_keypair(&mut OsRng);
+        let (_, commitment_key) = PublicKey::random_keypair(&mut OsRng);
+        let (_, nonce) = PublicKey::random_keypair(&mut OsRng);
+        let claim_public_key = with_claim_key.then(|| PublicKey::random_keypair(&mut OsRng).1);
+        BurntProof {
+            reciprocal_claim_public_key,
+            commitment: Commitment::from_public_key(&commitment_key),
+            ownership_proof: with_claim_key.then(|| {
+                RistrettoComSig::new(
+                    Commitment::from_public_key(&nonce),
+                    RistrettoSecretKey::random(&mut OsRng),
+                    RistrettoSecretKey::random(&mut OsRng),
+                )
+            }),
+            range_proof: BulletRangeProof(vec![7u8; 64]),
+            claim_public_key,
+        }
+    }
+
+    #[test]
+    fn it_round_trips_portable_bytes() {
+        for with_claim_key in [true, false] {
+            let proof = random_proof(with_claim_key);
+            let decoded = BurntProof::from_portable_bytes(&proof.to_portable_bytes()).unwrap();
+            assert_eq!(decoded.reciprocal_claim_public_key, proof.reciprocal_claim_public_key);
+            assert_eq!(decoded.commitment, proof.commitment);
+            assert_eq!(decoded.ownership_proof, proof.ownership_proof);
+            assert_eq!(decoded.range_proof, proof.range_proof);
+            assert_eq!(decoded.claim_public_key, proof.claim_public_key);
+        }
+    }
+
+    #[test]
+    fn it_rejects_malformed_portable_bytes() {
+        let bytes = random_proof(true).to_portable_bytes();
+        assert!(matches!(
+            BurntProof::from_portable_bytes(&[]),
+            Err(BurntProofError::UnexpectedEnd)
+        ));
+
+        let mut bad_version = bytes.clone();
+        bad_version[0] = 0;
+        assert!(matches!(
+            BurntProof::from_portable_bytes(&bad_version),
+            Err(BurntProofError::UnsupportedVersion(0))
+        ));
+
+        let mut bad_flags = bytes.clone();
+        bad_flags[1 + KEY_SIZE * 2] = 0xff;
+        assert!(matches!(
+            BurntProof::from_portable_bytes(&bad_flags),
+            Err(BurntProofError::InvalidFlags(0xff))
+        ));
+
+        let truncated = &bytes[..1 + KEY_SIZE * 5];
+        assert!(BurntProof::from_portable_bytes(truncated).is_err());
+    }
 }
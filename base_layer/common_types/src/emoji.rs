@@ -29,6 +29,9 @@ use std::{
 };
 
 use once_cell::sync::Lazy;
+use tari_address_utils::emoji::DICT_SIZE;
+// The emoji table is shared with `tari_address_utils`
+pub use tari_address_utils::emoji::{emoji_set, EMOJI};
 use tari_crypto::tari_utilities::ByteArray;
 use thiserror::Error;
 
@@ -74,27 +77,8 @@ use crate::{
 #[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
 pub struct EmojiId(PublicKey);
 
-const DICT_SIZE: usize = 256; // number of elements in the symbol dictionary
 const DATA_BYTES: usize = 32; // number of bytes used for the key data
 
-// The emoji table, mapping byte values to emoji characters
-pub const EMOJI: [char; DICT_SIZE] = [
-    '🐢', '📟', '🌈', '🌊', '🎯', '🐋', '🌙', '🤔', '🌕', '⭐', '🎋', '🌰', '🌴', '🌵', '🌲', '🌸', '🌹', '🌻', '🌽',
-    '🍀', '🍁', '🍄', '🥑', '🍆', '🍇', '🍈', '🍉', '🍊', '🍋', '🍌', '🍍', '🍎', '🍐', '🍑', '🍒', '🍓', '🍔', '🍕',
-    '🍗', '🍚', '🍞', '🍟', '🥝', '🍣', '🍦', '🍩', '🍪', '🍫', '🍬', '🍭', '🍯', '🥐', '🍳', '🥄', '🍵', '🍶', '🍷',
-    '🍸', '🍾', '🍺', '🍼', '🎀', '🎁', '🎂', '🎃', '🤖', '🎈', '🎉', '🎒', '🎓', '🎠', '🎡', '🎢', '🎣', '🎤', '🎥',
-    '🎧', '🎨', '🎩', '🎪', '🎬', '🎭', '🎮', '🎰', '🎱', '🎲', '🎳', '🎵', '🎷', '🎸', '🎹', '🎺', '🎻', '🎼', '🎽',
-    '🎾', '🎿', '🏀', '🏁', '🏆', '🏈', '⚽', '🏠', '🏥', '🏦', '🏭', '🏰', '🐀', '🐉', '🐊', '🐌', '🐍', '🦁', '🐐',
-    '🐑', '🐔', '🙈', '🐗', '🐘', '🐙', '🐚', '🐛', '🐜', '🐝', '🐞', '🦋', '🐣', '🐨', '🦀', '🐪', '🐬', '🐭', '🐮',
-    '🐯', '🐰', '🦆', '🦂', '🐴', '🐵', '🐶', '🐷', '🐸', '🐺', '🐻', '🐼', '🐽', '🐾', '👀', '👅', '👑', '👒', '🧢',
-    '💅', '👕', '👖', '👗', '👘', '👙', '💃', '👛', '👞', '👟', '👠', '🥊', '👢', '👣', '🤡', '👻', '👽', '👾', '🤠',
-    '👃', '💄', '💈', '💉', '💊', '💋', '👂', '💍', '💎', '💐', '💔', '🔒', '🧩', '💡', '💣', '💤', '💦', '💨', '💩',
-    '➕', '💯', '💰', '💳', '💵', '💺', '💻', '💼', '📈', '📜', '📌', '📎', '📖', '📿', '📡', '⏰', '📱', '📷', '🔋',
-    '🔌', '🚰', '🔑', '🔔', '🔥', '🔦', '🔧', '🔨', '🔩', '🔪', '🔫', '🔬', '🔭', '🔮', '🔱', '🗽', '😂', '😇', '😈',
-    '🤑', '😍', '😎', '😱', '😷', '🤢', '👍', '👶', '🚀', '🚁', '🚂', '🚚', '🚑', '🚒', '🚓', '🛵', '🚗', '🚜', '🚢',
-    '🚦', '🚧', '🚨', '🚪', '🚫', '🚲', '🚽', '🚿', '🧲',
-];
-
 // The reverse table, mapping emoji to characters to byte values
 pub static REVERSE_EMOJI: Lazy<HashMap<char, u8>> = Lazy::new(|| {
     let mut m = HashMap::with_capacity(DICT_SIZE);
@@ -104,11 +88,6 @@ pub static REVERSE_EMOJI: Lazy<HashMap<char, u8>> = Lazy::new(|| {
     m
 });
 
-/// Returns the current emoji set as a character array
-pub const fn emoji_set() -> [char; DICT_SIZE] {
-    EMOJI
-}
-
 #[derive(Debug, Error, PartialEq)]
 pub enum EmojiIdError {
     #[error("Invalid size")]
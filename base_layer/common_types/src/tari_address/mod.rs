@@ -898,4 +898,37 @@ mod test {
             Err(TariAddressError::InvalidAddressString)
         );
     }
+
+    #[test]
+    /// The standalone address utilities must agree with this implementation
+    fn address_utils_agree() {
+        use tari_address_utils::{AddressNetwork, TariAddressBytes};
+
+        for network in [
+            Network::MainNet,
+            Network::StageNet,
+            Network::NextNet,
+            Network::LocalNet,
+            Network::Igor,
+            Network::Esmeralda,
+        ] {
+            let utils_network = AddressNetwork::try_from(network.as_byte()).unwrap();
+            assert_eq!(utils_network.as_key_str(), network.as_key_str());
+        }
+
+        let mut rng = rand::thread_rng();
+        let view_key = PublicKey::from_secret_key(&PrivateKey::random(&mut rng));
+        let spend_key = PublicKey::from_secret_key(&PrivateKey::random(&mut rng));
+        for address in [
+            TariAddress::new_dual_address_with_default_features(view_key, spend_key.clone(), Network::NextNet),
+            TariAddress::new_single_address_with_interactive_only(spend_key, Network::MainNet),
+        ] {
+            let utils_address = TariAddressBytes::from_bytes(&address.to_vec()).unwrap();
+            assert_eq!(utils_address.to_emoji_string(), address.to_emoji_string());
+            assert_eq!(utils_address.to_base58(), address.to_base58());
+            assert_eq!(utils_address.to_hex(), address.to_hex());
+            assert_eq!(utils_address.features(), address.features().as_u8());
+            assert_eq!(TariAddress::from_str(&utils_address.to_base58()).unwrap(), address);
+        }
+    }
 }
@@ -37,6 +37,7 @@ pub enum WalletType {
     DerivedKeys,
     Ledger(LedgerWallet),
     ProvidedKeys(ProvidedKeysWallet),
+    RemoteSigner(RemoteSignerWallet),
 }
 
 impl Display for WalletType {
@@ -45,6 +46,7 @@ impl Display for WalletType {
             WalletType::DerivedKeys => write!(f, "Derived wallet"),
             WalletType::Ledger(ledger_wallet) => write!(f, "Ledger({ledger_wallet})"),
             WalletType::ProvidedKeys(provided_keys_wallet) => write!(f, "Provided Keys ({provided_keys_wallet})"),
+            WalletType::RemoteSigner(remote_signer_wallet) => write!(f, "Remote signer ({remote_signer_wallet})"),
         }
     }
 }
@@ -93,3 +95,20 @@ impl LedgerWallet {
         }
     }
 }
+
+/// A wallet whose spend key is held by a remote signer daemon. Like a Ledger wallet it keeps the view key, so that it
+/// can scan for and decrypt its outputs, but every signature that needs the spend key is requested from the signer.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct RemoteSignerWallet {
+    pub public_alpha: PublicKey,
+    pub view_key: PrivateKey,
+    pub network: Network,
+}
+
+impl Display for RemoteSignerWallet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "network '{}', ", self.network)?;
+        write!(f, "public_alpha '{}'", self.public_alpha)?;
+        Ok(())
+    }
+}
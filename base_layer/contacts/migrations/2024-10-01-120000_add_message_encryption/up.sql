@@ -0,0 +1 @@
+ALTER TABLE messages ADD encrypted INTEGER NOT NULL DEFAULT 0;
@@ -86,4 +86,8 @@ pub enum ContactsServiceStorageError {
     UnknownError,
     #[error("Byte size conversion error: `{0}`")]
     MaxSizeBytesError(#[from] MaxSizeBytesError),
+    #[error("Aead error: `{0}`")]
+    AeadError(String),
+    #[error("Message `{0}` is encrypted, but no cipher was provided")]
+    MissingCipher(String),
 }
@@ -126,6 +126,8 @@ impl Display for ContactsLivenessData {
 #[allow(clippy::large_enum_variant)]
 pub enum ContactsLivenessEvent {
     StatusUpdated(Box<ContactsLivenessData>),
+    /// The online status of a contact differs from the one last published for it
+    StatusChanged(Box<ContactsLivenessData>),
     NetworkSilence,
 }
 
@@ -57,6 +57,7 @@ where T: ContactsBackend
     backend: Option<T>,
     contacts_auto_ping_interval: Duration,
     contacts_online_ping_window: usize,
+    publish_presence: bool,
     subscription_factory: Arc<SubscriptionFactory>,
 }
 
@@ -73,9 +74,17 @@ where T: ContactsBackend
             backend: Some(backend),
             contacts_auto_ping_interval,
             contacts_online_ping_window: online_ping_window,
+            publish_presence: true,
             subscription_factory,
         }
     }
+
+    /// Sets whether this wallet announces that it is online to its contacts (the default), which only see it if they
+    /// have added this wallet as a contact too
+    pub fn with_presence_publishing(mut self, publish_presence: bool) -> Self {
+        self.publish_presence = publish_presence;
+        self
+    }
 }
 
 #[async_trait]
@@ -102,6 +111,7 @@ where T: ContactsBackend + 'static
 
         let contacts_auto_ping_interval = self.contacts_auto_ping_interval;
         let contacts_online_ping_window = self.contacts_online_ping_window;
+        let publish_presence = self.publish_presence;
         let subscription_factory = self.subscription_factory.clone();
         context.spawn_when_ready(move |handles| async move {
             let liveness = handles.expect_handle::<LivenessHandle>();
@@ -120,6 +130,7 @@ where T: ContactsBackend + 'static
                 message_publisher,
                 contacts_auto_ping_interval,
                 contacts_online_ping_window,
+                publish_presence,
             )
             .start();
             futures::pin_mut!(service);
@@ -21,6 +21,7 @@
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use std::{
+    collections::HashMap,
     convert::TryFrom,
     fmt::{Display, Error, Formatter},
     ops::Sub,
@@ -34,6 +35,7 @@ use log::*;
 use tari_common_types::tari_address::TariAddress;
 use tari_comms::{
     connectivity::{ConnectivityEvent, ConnectivityRequester},
+    peer_manager::NodeId,
     types::CommsPublicKey,
 };
 use tari_comms_dht::{domain_message::OutboundDomainMessage, outbound::OutboundEncryption, Dht};
@@ -49,14 +51,17 @@ use tari_p2p::{
 use tari_service_framework::reply_channel;
 use tari_shutdown::ShutdownSignal;
 use tari_utilities::{epoch_time::EpochTime, ByteArray};
-use tokio::sync::broadcast;
+use tokio::{
+    sync::broadcast,
+    time::{self, MissedTickBehavior},
+};
 
 use crate::contacts_service::{
     error::ContactsServiceError,
     handle::{ContactsLivenessData, ContactsLivenessEvent, ContactsServiceRequest, ContactsServiceResponse},
     proto,
     storage::database::{ContactsBackend, ContactsDatabase},
-    types::{Confirmation, Contact, Message, MessageDispatch},
+    types::{Confirmation, Contact, Message, MessageDispatch, Presence},
 };
 
 const LOG_TARGET: &str = "contacts::contacts_service";
@@ -68,6 +73,7 @@ pub enum ContactMessageType {
     Ping,
     Pong,
     NoMessage,
+    Presence,
 }
 
 impl Display for ContactMessageType {
@@ -76,6 +82,7 @@ impl Display for ContactMessageType {
             ContactMessageType::Ping => write!(f, "Ping"),
             ContactMessageType::Pong => write!(f, "Pong"),
             ContactMessageType::NoMessage => write!(f, "NoMessage"),
+            ContactMessageType::Presence => write!(f, "Presence"),
         }
     }
 }
@@ -129,6 +136,7 @@ where T: ContactsBackend + 'static
     shutdown_signal: Option<ShutdownSignal>,
     liveness: LivenessHandle,
     liveness_data: Vec<ContactsLivenessData>,
+    published_statuses: HashMap<NodeId, ContactOnlineStatus>,
     connectivity: ConnectivityRequester,
     dht: Dht,
     subscription_factory: Arc<SubscriptionFactory>,
@@ -137,6 +145,7 @@ where T: ContactsBackend + 'static
     number_of_rounds_no_pings: u16,
     contacts_auto_ping_interval: Duration,
     contacts_online_ping_window: usize,
+    publish_presence: bool,
 }
 
 impl<T> ContactsService<T>
@@ -157,6 +166,7 @@ where T: ContactsBackend + 'static
         message_publisher: broadcast::Sender<Arc<MessageDispatch>>,
         contacts_auto_ping_interval: Duration,
         contacts_online_ping_window: usize,
+        publish_presence: bool,
     ) -> Self {
         Self {
             db,
@@ -164,6 +174,7 @@ where T: ContactsBackend + 'static
             shutdown_signal: Some(shutdown_signal),
             liveness,
             liveness_data: Vec::new(),
+            published_statuses: HashMap::new(),
             connectivity,
             dht,
             subscription_factory,
@@ -172,6 +183,7 @@ where T: ContactsBackend + 'static
             number_of_rounds_no_pings: 0,
             contacts_auto_ping_interval,
             contacts_online_ping_window,
+            publish_presence,
         }
     }
 
@@ -202,6 +214,9 @@ where T: ContactsBackend + 'static
             .expect("Contacts Service initialized without shutdown signal");
         pin_mut!(shutdown);
 
+        let mut presence_interval = time::interval(self.presence_interval());
+        presence_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
         // Add all contacts as monitored peers to the liveness service
         let result = self.db.get_contacts();
         if let Ok(ref contacts) = result {
@@ -240,6 +255,10 @@ where T: ContactsBackend + 'static
                     self.handle_connectivity_event(event);
                 },
 
+                _ = presence_interval.tick(), if self.publish_presence => {
+                    self.announce_presence().await;
+                },
+
                 _ = shutdown.wait() => {
                     info!(target: LOG_TARGET, "Contacts service shutting down because it received the shutdown signal");
                     break;
@@ -273,6 +292,7 @@ where T: ContactsBackend + 'static
             },
             ContactsServiceRequest::RemoveContact(pk) => {
                 let result = self.db.remove_contact(pk.clone())?;
+                self.published_statuses.remove(&result.node_id);
                 self.liveness
                     .check_remove_monitored_peer(result.node_id.clone())
                     .await?;
@@ -417,6 +437,7 @@ where T: ContactsBackend + 'static
                             .event_publisher
                             .send(Arc::new(ContactsLivenessEvent::StatusUpdated(Box::new(data.clone()))));
                         trace!(target: LOG_TARGET, "{}", data);
+                        self.publish_if_status_changed(&data);
                     }
                 };
             },
@@ -453,6 +474,7 @@ where T: ContactsBackend + 'static
                 MessageDispatch::DeliveryConfirmation(_) | MessageDispatch::ReadConfirmation(_) => {
                     self.handle_confirmation(dispatch.clone()).await
                 },
+                MessageDispatch::Presence(p) => self.handle_presence(p, source_public_key),
             }
         } else {
             Err(ContactsServiceError::MessageSourceDoesNotMatchOrigin)
@@ -483,12 +505,93 @@ where T: ContactsBackend + 'static
 
     fn is_online(&self, last_seen: NaiveDateTime) -> bool {
         #[allow(clippy::cast_possible_wrap)]
-        let ping_window = chrono::Duration::seconds(
-            (self.contacts_online_ping_window as u64 * self.contacts_auto_ping_interval.as_secs()) as i64,
-        );
+        let ping_window = chrono::Duration::seconds(self.online_window().as_secs() as i64);
         Utc::now().naive_utc().sub(last_seen) <= ping_window
     }
 
+    /// How long a contact may be not seen before being determined to be offline
+    fn online_window(&self) -> Duration {
+        Duration::from_secs(self.contacts_online_ping_window as u64 * self.contacts_auto_ping_interval.as_secs())
+    }
+
+    /// Presence is announced twice per online window, so that contacts keep seeing this wallet as online
+    fn presence_interval(&self) -> Duration {
+        let rounds = u32::try_from(self.contacts_online_ping_window / 2)
+            .unwrap_or(u32::MAX)
+            .max(1);
+        self.contacts_auto_ping_interval
+            .saturating_mul(rounds)
+            .max(Duration::from_secs(1))
+    }
+
+    /// Announces that this wallet is online to every contact
+    async fn announce_presence(&mut self) {
+        let contacts = match self.db.get_contacts() {
+            Ok(contacts) => contacts,
+            Err(e) => {
+                warn!(target: LOG_TARGET, "Could not load contacts to announce presence: {}", e);
+                return;
+            },
+        };
+        let presence = Presence::new(EpochTime::now().as_u64());
+        for contact in contacts {
+            let msg = OutboundDomainMessage::from(MessageDispatch::Presence(presence.clone()));
+            if let Err(e) = self.deliver_message(contact.address.clone(), msg).await {
+                debug!(target: LOG_TARGET, "Could not announce presence to {}: {}", contact.address, e);
+            }
+        }
+    }
+
+    /// Marks the sender of `presence` as online. Presence from anyone who is not a contact is ignored, so that the
+    /// status of a wallet is only known to its mutual contacts.
+    fn handle_presence(
+        &mut self,
+        presence: Presence,
+        source_public_key: CommsPublicKey,
+    ) -> Result<(), ContactsServiceError> {
+        if !presence.is_fresh(EpochTime::now().as_u64(), self.online_window()) {
+            trace!(target: LOG_TARGET, "Ignoring stale presence from {}", source_public_key);
+            return Ok(());
+        }
+        let node_id = NodeId::from_public_key(&source_public_key);
+        let Some(contact) = self.db.get_contacts()?.into_iter().find(|c| c.node_id == node_id) else {
+            trace!(target: LOG_TARGET, "Ignoring presence from {}, who is not a contact", node_id);
+            return Ok(());
+        };
+
+        let last_seen = Utc::now().naive_utc();
+        self.db.update_contact_last_seen(&node_id, last_seen, contact.latency)?;
+        let data = ContactsLivenessData::new(
+            contact.address,
+            node_id,
+            contact.latency,
+            Some(last_seen),
+            ContactMessageType::Presence,
+            ContactOnlineStatus::Online,
+        );
+        trace!(target: LOG_TARGET, "{}", data);
+        self.publish_if_status_changed(&data);
+        // Send only fails if there are no subscribers.
+        let _size = self
+            .event_publisher
+            .send(Arc::new(ContactsLivenessEvent::StatusUpdated(Box::new(data))));
+        Ok(())
+    }
+
+    /// Publishes a [ContactsLivenessEvent::StatusChanged] event if the online status in `data` differs from the one
+    /// last published for the contact
+    fn publish_if_status_changed(&mut self, data: &ContactsLivenessData) {
+        let status = data.online_status();
+        if self.published_statuses.get(data.node_id()) == Some(&status) {
+            return;
+        }
+        self.published_statuses.insert(data.node_id().clone(), status);
+        // Send only fails if there are no subscribers.
+        let _size = self
+            .event_publisher
+            .send(Arc::new(ContactsLivenessEvent::StatusChanged(Box::new(data.clone()))));
+    }
+
     fn update_with_ping_pong(
         &mut self,
         event: &PingPongEvent,
@@ -528,6 +631,7 @@ where T: ContactsBackend + 'static
             self.liveness_data.push(data.clone());
 
             trace!(target: LOG_TARGET, "{}", data);
+            self.publish_if_status_changed(&data);
             // Send only fails if there are no subscribers.
             let _size = self
                 .event_publisher
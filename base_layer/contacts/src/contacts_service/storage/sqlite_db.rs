@@ -22,12 +22,13 @@
 
 use std::{convert::TryFrom, sync::Arc};
 
+use chacha20poly1305::XChaCha20Poly1305;
 use diesel::result::Error as DieselError;
 use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
 use log::warn;
 use tari_common_sqlite::{error::SqliteStorageError, sqlite_connection_pool::PooledDbConnection};
-use tari_common_types::tari_address::TariAddress;
-use tari_utilities::ByteArray;
+use tari_common_types::{encryption::Encryptable, tari_address::TariAddress};
+use tari_utilities::{hex::Hex, ByteArray};
 
 use crate::contacts_service::{
     error::ContactsServiceStorageError,
@@ -49,6 +50,7 @@ const LOG_TARGET: &str = "contacts::contacts_service::storage::sqlite_db";
 #[derive(Clone)]
 pub struct ContactsServiceSqliteDatabase<TContactServiceDbConnection> {
     database_connection: Arc<TContactServiceDbConnection>,
+    cipher: Option<XChaCha20Poly1305>,
 }
 
 impl<TContactServiceDbConnection: PooledDbConnection<Error = SqliteStorageError>>
@@ -57,6 +59,7 @@ impl<TContactServiceDbConnection: PooledDbConnection<Error = SqliteStorageError>
     pub fn new(database_connection: TContactServiceDbConnection) -> Self {
         Self {
             database_connection: Arc::new(database_connection),
+            cipher: None,
         }
     }
 
@@ -70,6 +73,49 @@ impl<TContactServiceDbConnection: PooledDbConnection<Error = SqliteStorageError>
         db
     }
 
+    /// Creates a backend that encrypts the body and metadata of every message with `cipher`. Messages that were
+    /// stored without encryption are encrypted in place.
+    pub fn init_with_cipher(database_connection: TContactServiceDbConnection, cipher: XChaCha20Poly1305) -> Self {
+        let mut db = Self::init(database_connection);
+        db.cipher = Some(cipher);
+
+        if let Err(e) = db.encrypt_stored_messages() {
+            warn!(target: LOG_TARGET, "Stored messages could not be encrypted: {}", e)
+        }
+
+        db
+    }
+
+    fn encrypt_stored_messages(&self) -> Result<(), ContactsServiceStorageError> {
+        let cipher = match &self.cipher {
+            Some(cipher) => cipher,
+            None => return Ok(()),
+        };
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        for message in MessagesSql::find_unencrypted(&mut conn)? {
+            message
+                .encrypt(cipher)
+                .map_err(ContactsServiceStorageError::AeadError)?
+                .update_encrypted(&mut conn)?;
+        }
+        Ok(())
+    }
+
+    fn to_message(&self, message: MessagesSql) -> Result<Message, ContactsServiceStorageError> {
+        if message.encrypted == 0 {
+            return Message::try_from(message);
+        }
+        let cipher = self
+            .cipher
+            .as_ref()
+            .ok_or_else(|| ContactsServiceStorageError::MissingCipher(message.message_id.to_hex()))?;
+        Message::try_from(
+            message
+                .decrypt(cipher)
+                .map_err(ContactsServiceStorageError::AeadError)?,
+        )
+    }
+
     fn run_migrations(&self) -> Result<Vec<String>, SqliteStorageError> {
         let mut conn = self.database_connection.get_pooled_connection()?;
         conn.run_pending_migrations(MIGRATIONS)
@@ -115,8 +161,8 @@ where TContactServiceDbConnection: PooledDbConnection<Error = SqliteStorageError
                 match MessagesSql::find_by_address(&address.to_vec(), *limit, *page, &mut conn) {
                     Ok(messages_sql) => {
                         let mut messages = vec![];
-                        for m in &messages_sql {
-                            messages.push(Message::try_from(m.clone())?);
+                        for m in messages_sql {
+                            messages.push(self.to_message(m)?);
                         }
                         Some(DbValue::Messages(messages))
                     },
@@ -125,7 +171,7 @@ where TContactServiceDbConnection: PooledDbConnection<Error = SqliteStorageError
                 }
             },
             DbKey::Message(id) => match MessagesSql::find_by_message_id(&id.to_vec(), &mut conn) {
-                Ok(c) => Some(DbValue::Message(Box::new(Message::try_from(c)?))),
+                Ok(c) => Some(DbValue::Message(Box::new(self.to_message(c)?))),
                 Err(ContactsServiceStorageError::DieselError(DieselError::NotFound)) => None,
                 Err(e) => return Err(e),
             },
@@ -217,7 +263,13 @@ where TContactServiceDbConnection: PooledDbConnection<Error = SqliteStorageError
             },
             WriteOperation::Insert(i) => {
                 if let DbValue::Message(m) = *i {
-                    MessagesSqlInsert::try_from(*m)?.commit(&mut conn)?;
+                    let mut message = MessagesSqlInsert::try_from(*m)?;
+                    if let Some(cipher) = &self.cipher {
+                        message = message
+                            .encrypt(cipher)
+                            .map_err(ContactsServiceStorageError::AeadError)?;
+                    }
+                    message.commit(&mut conn)?;
                 }
             },
         }
@@ -230,7 +282,8 @@ where TContactServiceDbConnection: PooledDbConnection<Error = SqliteStorageError
 mod test {
     use std::convert::TryInto;
 
-    use rand::rngs::OsRng;
+    use chacha20poly1305::{Key, KeyInit};
+    use rand::{rngs::OsRng, RngCore};
     use tari_common::configuration::Network;
     use tari_common_sqlite::connection::{DbConnection, DbConnectionUrl};
     use tari_common_types::types::{PrivateKey, PublicKey};
@@ -238,6 +291,7 @@ mod test {
     use tari_test_utils::{paths::with_temp_dir, random::string};
 
     use super::*;
+    use crate::contacts_service::types::MessageBuilder;
 
     #[test]
     fn test_crud() {
@@ -294,4 +348,63 @@ mod test {
             assert_eq!(c_updated.favourite, i32::from(true));
         });
     }
+
+    #[test]
+    fn it_encrypts_messages_at_rest() {
+        with_temp_dir(|dir_path| {
+            let db_path = format!("{}/{}.sqlite3", dir_path.to_str().unwrap(), string(8).as_str());
+            let url: DbConnectionUrl = db_path.try_into().unwrap();
+            let db = DbConnection::connect_url(&url).unwrap();
+
+            let address = TariAddress::new_single_address_with_interactive_only(
+                PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng)),
+                Network::default(),
+            );
+            let messages = ["Hello".to_string(), "Are you there?".to_string()]
+                .iter()
+                .map(|body| {
+                    MessageBuilder::new()
+                        .receiver_address(address.clone())
+                        .sender_address(address.clone())
+                        .message(body.clone())
+                        .unwrap()
+                        .build()
+                })
+                .collect::<Vec<_>>();
+
+            // A message stored before the database had a cipher is encrypted when one is provided
+            let plain_backend = ContactsServiceSqliteDatabase::init(db.clone());
+            plain_backend
+                .write(WriteOperation::Insert(Box::new(DbValue::Message(Box::new(
+                    messages[0].clone(),
+                )))))
+                .unwrap();
+            let mut key = [0u8; 32];
+            OsRng.fill_bytes(&mut key);
+            let cipher = XChaCha20Poly1305::new(Key::from_slice(&key));
+            let backend = ContactsServiceSqliteDatabase::init_with_cipher(db.clone(), cipher);
+            backend
+                .write(WriteOperation::Insert(Box::new(DbValue::Message(Box::new(
+                    messages[1].clone(),
+                )))))
+                .unwrap();
+
+            let mut conn = db.get_pooled_connection().unwrap();
+            for message in &messages {
+                let stored = MessagesSql::find_by_message_id(&message.message_id.to_vec(), &mut conn).unwrap();
+                assert_eq!(stored.encrypted, 1);
+                assert_ne!(stored.body, message.body.to_vec());
+
+                let fetched = match backend.fetch(&DbKey::Message(message.message_id.to_vec())).unwrap() {
+                    Some(DbValue::Message(m)) => m,
+                    _ => panic!("Message not found"),
+                };
+                assert_eq!(fetched.body, message.body);
+                assert!(matches!(
+                    plain_backend.fetch(&DbKey::Message(message.message_id.to_vec())),
+                    Err(ContactsServiceStorageError::MissingCipher(_))
+                ));
+            }
+        });
+    }
 }
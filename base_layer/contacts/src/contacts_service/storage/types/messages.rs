@@ -22,11 +22,15 @@
 
 use std::convert::TryFrom;
 
+use chacha20poly1305::XChaCha20Poly1305;
 use chrono::NaiveDateTime;
 use diesel::prelude::*;
 use tari_common_sqlite::util::diesel_ext::ExpectedRowsExtension;
-use tari_common_types::tari_address::TariAddress;
-use tari_utilities::ByteArray;
+use tari_common_types::{
+    encryption::{decrypt_bytes_integral_nonce, encrypt_bytes_integral_nonce, Encryptable},
+    tari_address::TariAddress,
+};
+use tari_utilities::{ByteArray, Hidden};
 
 use crate::{
     contacts_service::{
@@ -49,6 +53,7 @@ pub struct MessagesSqlInsert {
     pub stored_at: NaiveDateTime,
     pub sent_at: NaiveDateTime,
     pub direction: i32,
+    pub encrypted: i32,
 }
 
 #[derive(Clone, Debug, Queryable, PartialEq, Eq, QueryableByName)]
@@ -65,6 +70,7 @@ pub struct MessagesSql {
     pub delivery_confirmation_at: Option<NaiveDateTime>,
     pub read_confirmation_at: Option<NaiveDateTime>,
     pub direction: i32,
+    pub encrypted: i32,
 }
 #[derive(Clone, Debug, AsChangeset, PartialEq, Eq)]
 #[diesel(table_name = messages)]
@@ -165,6 +171,66 @@ impl MessagesSql {
             .distinct()
             .load(conn)?)
     }
+
+    /// Find all the messages that were stored before the database had a cipher
+    pub fn find_unencrypted(conn: &mut SqliteConnection) -> Result<Vec<MessagesSql>, ContactsServiceStorageError> {
+        Ok(messages::table
+            .filter(messages::encrypted.eq(0))
+            .load::<MessagesSql>(conn)?)
+    }
+
+    /// Replace the body and metadata of this message with its encrypted form
+    pub fn update_encrypted(self, conn: &mut SqliteConnection) -> Result<(), ContactsServiceStorageError> {
+        diesel::update(messages::table.filter(messages::message_id.eq(&self.message_id)))
+            .set((
+                messages::body.eq(&self.body),
+                messages::metadata.eq(&self.metadata),
+                messages::encrypted.eq(self.encrypted),
+            ))
+            .execute(conn)
+            .num_rows_affected_or_not_found(1)?;
+        Ok(())
+    }
+}
+
+impl Encryptable<XChaCha20Poly1305> for MessagesSqlInsert {
+    fn domain(&self, field_name: &'static str) -> Vec<u8> {
+        [Self::CHAT_MESSAGE, self.message_id.as_slice(), field_name.as_bytes()].concat()
+    }
+
+    fn encrypt(mut self, cipher: &XChaCha20Poly1305) -> Result<Self, String> {
+        self.body = encrypt_bytes_integral_nonce(cipher, self.domain("body"), Hidden::hide(self.body))?;
+        self.metadata = encrypt_bytes_integral_nonce(cipher, self.domain("metadata"), Hidden::hide(self.metadata))?;
+        self.encrypted = 1;
+        Ok(self)
+    }
+
+    fn decrypt(mut self, cipher: &XChaCha20Poly1305) -> Result<Self, String> {
+        self.body = decrypt_bytes_integral_nonce(cipher, self.domain("body"), &self.body)?;
+        self.metadata = decrypt_bytes_integral_nonce(cipher, self.domain("metadata"), &self.metadata)?;
+        self.encrypted = 0;
+        Ok(self)
+    }
+}
+
+impl Encryptable<XChaCha20Poly1305> for MessagesSql {
+    fn domain(&self, field_name: &'static str) -> Vec<u8> {
+        [Self::CHAT_MESSAGE, self.message_id.as_slice(), field_name.as_bytes()].concat()
+    }
+
+    fn encrypt(mut self, cipher: &XChaCha20Poly1305) -> Result<Self, String> {
+        self.body = encrypt_bytes_integral_nonce(cipher, self.domain("body"), Hidden::hide(self.body))?;
+        self.metadata = encrypt_bytes_integral_nonce(cipher, self.domain("metadata"), Hidden::hide(self.metadata))?;
+        self.encrypted = 1;
+        Ok(self)
+    }
+
+    fn decrypt(mut self, cipher: &XChaCha20Poly1305) -> Result<Self, String> {
+        self.body = decrypt_bytes_integral_nonce(cipher, self.domain("body"), &self.body)?;
+        self.metadata = decrypt_bytes_integral_nonce(cipher, self.domain("metadata"), &self.metadata)?;
+        self.encrypted = 0;
+        Ok(self)
+    }
 }
 
 /// Conversion from an Message to the Sql datatype form
@@ -193,8 +259,8 @@ impl TryFrom<MessagesSql> for Message {
             .ok_or(ContactsServiceStorageError::ConversionError)?,
             sent_at: o.sent_at.timestamp() as u64,
             stored_at: o.stored_at.timestamp() as u64,
-            delivery_confirmation_at: Some(o.stored_at.timestamp() as u64),
-            read_confirmation_at: Some(o.stored_at.timestamp() as u64),
+            delivery_confirmation_at: o.delivery_confirmation_at.map(|d| d.timestamp() as u64),
+            read_confirmation_at: o.read_confirmation_at.map(|r| r.timestamp() as u64),
             message_id: MessageId::try_from(o.message_id)?,
         })
     }
@@ -218,6 +284,7 @@ impl TryFrom<Message> for MessagesSqlInsert {
             sent_at: NaiveDateTime::from_timestamp_opt(o.sent_at as i64, 0)
                 .ok_or(ContactsServiceStorageError::ConversionError)?,
             direction: i32::from(o.direction.as_byte()),
+            encrypted: 0,
         })
     }
 }
@@ -27,7 +27,7 @@ use tari_p2p::tari_message::TariMessageType;
 
 use crate::contacts_service::{
     proto,
-    types::{Confirmation, Message},
+    types::{Confirmation, Message, Presence},
 };
 
 #[derive(Clone)]
@@ -35,6 +35,7 @@ pub enum MessageDispatch {
     Message(Message),
     DeliveryConfirmation(Confirmation),
     ReadConfirmation(Confirmation),
+    Presence(Presence),
 }
 
 impl TryFrom<proto::MessageDispatch> for MessageDispatch {
@@ -49,6 +50,7 @@ impl TryFrom<proto::MessageDispatch> for MessageDispatch {
             Some(proto::message_dispatch::Contents::ReadConfirmation(c)) => {
                 MessageDispatch::ReadConfirmation(Confirmation::try_from(c).map_err(|e| e.to_string())?)
             },
+            Some(proto::message_dispatch::Contents::Presence(p)) => MessageDispatch::Presence(p.into()),
             None => return Err("We didn't get any known type of chat message".to_string()),
         })
     }
@@ -62,6 +64,7 @@ impl From<MessageDispatch> for proto::MessageDispatch {
                 proto::message_dispatch::Contents::DeliveryConfirmation(c.into())
             },
             MessageDispatch::ReadConfirmation(c) => proto::message_dispatch::Contents::ReadConfirmation(c.into()),
+            MessageDispatch::Presence(p) => proto::message_dispatch::Contents::Presence(p.into()),
         };
 
         Self {
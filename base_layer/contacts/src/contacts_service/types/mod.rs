@@ -34,3 +34,6 @@ pub use message_dispatch::MessageDispatch;
 
 mod confirmation;
 pub use confirmation::Confirmation;
+
+mod presence;
+pub use presence::Presence;
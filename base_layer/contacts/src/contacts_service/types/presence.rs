@@ -0,0 +1,65 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::{fmt::Display, time::Duration};
+
+use crate::contacts_service::proto;
+
+/// An announcement that the sender is online. Presence is only sent to contacts, and only accepted from contacts, so
+/// it is only ever exchanged between wallets that have added each other.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Presence {
+    /// When the announcement was made, in seconds since the Unix epoch
+    pub timestamp: u64,
+}
+
+impl Presence {
+    pub fn new(timestamp: u64) -> Self {
+        Self { timestamp }
+    }
+
+    /// Returns true if the announcement was made no more than `window` before or after `now`. Announcements can be
+    /// delayed by store and forward, and older ones say nothing about whether the sender is online now.
+    pub fn is_fresh(&self, now: u64, window: Duration) -> bool {
+        let window = window.as_secs();
+        self.timestamp.saturating_add(window) >= now && self.timestamp <= now.saturating_add(window)
+    }
+}
+
+impl From<proto::Presence> for Presence {
+    fn from(presence: proto::Presence) -> Self {
+        Self {
+            timestamp: presence.timestamp,
+        }
+    }
+}
+
+impl From<Presence> for proto::Presence {
+    fn from(presence: Presence) -> Self {
+        Self {
+            timestamp: presence.timestamp,
+        }
+    }
+}
+
+impl Display for Presence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Presence: timestamp: {}", self.timestamp)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_rejects_stale_and_future_announcements() {
+        let window = Duration::from_secs(60);
+        assert!(Presence::new(1_000).is_fresh(1_000, window));
+        assert!(Presence::new(940).is_fresh(1_000, window));
+        assert!(!Presence::new(939).is_fresh(1_000, window));
+        assert!(Presence::new(1_060).is_fresh(1_000, window));
+        assert!(!Presence::new(1_061).is_fresh(1_000, window));
+        assert!(Presence::new(u64::MAX).is_fresh(u64::MAX, window));
+    }
+}
@@ -23,5 +23,6 @@ diesel::table! {
         delivery_confirmation_at -> Nullable<Timestamp>,
         read_confirmation_at -> Nullable<Timestamp>,
         direction -> Integer,
+        encrypted -> Integer,
     }
 }
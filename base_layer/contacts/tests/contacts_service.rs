@@ -96,6 +96,12 @@ pub fn setup_contacts_service<T: ContactsBackend + 'static>(
         rpc_max_simultaneous_sessions: 0,
         rpc_max_sessions_per_peer: 0,
         listener_self_liveness_check_interval: None,
+        cover_traffic_interval: None,
+        traffic_padding: false,
+        rpc_max_pending_requests: None,
+        rpc_max_requests_per_minute_per_method: None,
+        rpc_max_requests_per_minute_per_peer: None,
+        max_inbound_connections_per_subnet: None,
     };
     let peer_message_subscription_factory = Arc::new(subscription_factory);
     let shutdown = Shutdown::new();
@@ -0,0 +1,59 @@
+//  Copyright 2026. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use tari_core::{
+    covenants::Covenant,
+    transactions::{
+        tari_amount::MicroMinotari,
+        transaction_components::{OutputFeatures, TransactionInput, TransactionOutput},
+    },
+};
+
+// Input layout: `block_height (8 bytes LE) | covenant bytes`
+fuzz_target!(|data: &[u8]| {
+    if data.len() < 8 {
+        return;
+    }
+    let (height, mut covenant_bytes) = data.split_at(8);
+    let height = u64::from_le_bytes(height.try_into().expect("8 bytes"));
+
+    let Ok(covenant) = Covenant::from_bytes(&mut covenant_bytes) else {
+        return;
+    };
+    let encoded = covenant.to_bytes();
+    assert_eq!(
+        Covenant::from_bytes(&mut encoded.as_slice()).expect("encoded covenant must decode"),
+        covenant
+    );
+
+    let mut burn_output = TransactionOutput::default();
+    burn_output.features = OutputFeatures::create_burn_output();
+    let mut timelocked_output = TransactionOutput::default();
+    timelocked_output.features.maturity = height;
+    let outputs = [TransactionOutput::default(), burn_output, timelocked_output];
+
+    // Evaluation may reject the covenant, but must never panic
+    let _result = covenant.execute(height, &TransactionInput::default(), &outputs, MicroMinotari::zero());
+});
@@ -60,6 +60,9 @@ pub enum NodeCommsRequest {
     GetShardKey { height: u64, public_key: PublicKey },
     FetchTemplateRegistrations { start_height: u64, end_height: u64 },
     FetchUnspentUtxosInBlock { block_hash: BlockHash },
+    FetchUtxoSetStats,
+    FetchOutputMinedInfo(HashOutput),
+    FetchValidatorNodeSet { height: u64 },
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -113,6 +116,9 @@ impl Display for NodeCommsRequest {
             FetchUnspentUtxosInBlock { block_hash } => {
                 write!(f, "FetchUnspentUtxosInBlock ({})", block_hash)
             },
+            FetchUtxoSetStats => write!(f, "FetchUtxoSetStats"),
+            FetchOutputMinedInfo(hash) => write!(f, "FetchOutputMinedInfo({})", hash),
+            FetchValidatorNodeSet { height } => write!(f, "FetchValidatorNodeSet ({})", height),
         }
     }
 }
@@ -32,7 +32,7 @@ use tari_common_types::{
 
 use crate::{
     blocks::{Block, ChainHeader, HistoricalBlock, NewBlockTemplate},
-    chain_storage::TemplateRegistrationEntry,
+    chain_storage::{OutputMinedInfo, TemplateRegistrationEntry, UtxoSetStats, ValidatorNodeSet},
     proof_of_work::Difficulty,
     transactions::transaction_components::{Transaction, TransactionKernel, TransactionOutput},
 };
@@ -61,6 +61,12 @@ pub enum NodeCommsResponse {
     FetchValidatorNodesKeysResponse(Vec<(PublicKey, [u8; 32])>),
     GetShardKeyResponse(Option<[u8; 32]>),
     FetchTemplateRegistrationsResponse(Vec<TemplateRegistrationEntry>),
+    UtxoSetStats {
+        stats: Box<UtxoSetStats>,
+        tip_height: u64,
+    },
+    OutputMinedInfo(Box<Option<OutputMinedInfo>>),
+    ValidatorNodeSet(Box<ValidatorNodeSet>),
 }
 
 impl Display for NodeCommsResponse {
@@ -98,6 +104,11 @@ impl Display for NodeCommsResponse {
             FetchValidatorNodesKeysResponse(_) => write!(f, "FetchValidatorNodesKeysResponse"),
             GetShardKeyResponse(_) => write!(f, "GetShardKeyResponse"),
             FetchTemplateRegistrationsResponse(_) => write!(f, "FetchTemplateRegistrationsResponse"),
+            UtxoSetStats { stats, tip_height } => {
+                write!(f, "UtxoSetStats({} outputs at height {})", stats.count, tip_height)
+            },
+            OutputMinedInfo(_) => write!(f, "OutputMinedInfo"),
+            ValidatorNodeSet(set) => write!(f, "ValidatorNodeSet({} nodes)", set.members.len()),
         }
     }
 }
@@ -436,6 +436,22 @@ where B: BlockchainBackend + 'static
                 let utxos = self.blockchain_db.fetch_outputs_in_block(block_hash).await?;
                 Ok(NodeCommsResponse::TransactionOutputs(utxos))
             },
+            NodeCommsRequest::FetchUtxoSetStats => {
+                let stats = self.blockchain_db.fetch_utxo_set_stats().await?;
+                let tip_height = self.blockchain_db.get_chain_metadata().await?.best_block_height();
+                Ok(NodeCommsResponse::UtxoSetStats {
+                    stats: Box::new(stats),
+                    tip_height,
+                })
+            },
+            NodeCommsRequest::FetchOutputMinedInfo(output_hash) => {
+                let mined_info = self.blockchain_db.fetch_output(output_hash).await?;
+                Ok(NodeCommsResponse::OutputMinedInfo(Box::new(mined_info)))
+            },
+            NodeCommsRequest::FetchValidatorNodeSet { height } => {
+                let vn_set = self.blockchain_db.fetch_validator_node_set(height).await?;
+                Ok(NodeCommsResponse::ValidatorNodeSet(Box::new(vn_set)))
+            },
         }
     }
 
@@ -38,7 +38,7 @@ use crate::{
         NodeCommsResponse,
     },
     blocks::{Block, ChainHeader, HistoricalBlock, NewBlockTemplate},
-    chain_storage::TemplateRegistrationEntry,
+    chain_storage::{OutputMinedInfo, TemplateRegistrationEntry, UtxoSetStats, ValidatorNodeSet},
     proof_of_work::PowAlgorithm,
     transactions::transaction_components::{TransactionKernel, TransactionOutput},
 };
@@ -342,4 +342,40 @@ impl LocalNodeCommsInterface {
             _ => Err(CommsInterfaceError::UnexpectedApiResponse),
         }
     }
+
+    /// Fetches the statistics of the UTXO set and the tip height they are at
+    pub async fn get_utxo_set_stats(&mut self) -> Result<(UtxoSetStats, u64), CommsInterfaceError> {
+        match self.request_sender.call(NodeCommsRequest::FetchUtxoSetStats).await?? {
+            NodeCommsResponse::UtxoSetStats { stats, tip_height } => Ok((*stats, tip_height)),
+            _ => Err(CommsInterfaceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Fetches the output with the given hash and the block it was mined in, whether or not it has been spent
+    pub async fn get_output_mined_info(
+        &mut self,
+        output_hash: HashOutput,
+    ) -> Result<Option<OutputMinedInfo>, CommsInterfaceError> {
+        match self
+            .request_sender
+            .call(NodeCommsRequest::FetchOutputMinedInfo(output_hash))
+            .await??
+        {
+            NodeCommsResponse::OutputMinedInfo(mined_info) => Ok(*mined_info),
+            _ => Err(CommsInterfaceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Returns the validator node set of the epoch that `height` is in, with Merkle proofs against the header of the
+    /// first block of the epoch
+    pub async fn get_validator_node_set(&mut self, height: u64) -> Result<ValidatorNodeSet, CommsInterfaceError> {
+        match self
+            .request_sender
+            .call(NodeCommsRequest::FetchValidatorNodeSet { height })
+            .await??
+        {
+            NodeCommsResponse::ValidatorNodeSet(vn_set) => Ok(*vn_set),
+            _ => Err(CommsInterfaceError::UnexpectedApiResponse),
+        }
+    }
 }
@@ -42,6 +42,9 @@ pub use comms_interface::LocalNodeCommsInterface;
 #[cfg(feature = "metrics")]
 mod metrics;
 
+#[cfg(feature = "base_node")]
+pub mod network_monitor;
+
 #[cfg(feature = "base_node")]
 pub mod service;
 
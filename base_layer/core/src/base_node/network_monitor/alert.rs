@@ -0,0 +1,95 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    fmt::{Display, Error, Formatter},
+    time::Duration,
+};
+
+/// A condition indicating that the node may be partitioned from the rest of the network or eclipsed by its peers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NetworkAlert {
+    /// Fewer base nodes are connected than the configured minimum
+    LowPeerCount { connected: usize, required: usize },
+    /// The connected base nodes are spread across too few network address groups
+    LowAddressDiversity { groups: usize, required: usize },
+    /// A single network address group holds too large a share of the connected base nodes
+    DominantAddressGroup {
+        group: String,
+        connections: usize,
+        total: usize,
+    },
+    /// The local tip differs from the median height claimed by connected peers
+    ChainTipDivergence { local_height: u64, median_peer_height: u64 },
+    /// Neither the local tip nor any height claimed by a peer has advanced recently
+    ChainStalled { height: u64, stalled_for: Duration },
+}
+
+impl NetworkAlert {
+    /// Returns true if both alerts describe the same condition, regardless of the observed values.
+    pub fn is_same_kind(&self, other: &NetworkAlert) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+}
+
+impl Display for NetworkAlert {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        #[allow(clippy::enum_glob_use)]
+        use NetworkAlert::*;
+        match self {
+            LowPeerCount { connected, required } => {
+                write!(
+                    f,
+                    "Only {} base node(s) connected, expected at least {}",
+                    connected, required
+                )
+            },
+            LowAddressDiversity { groups, required } => write!(
+                f,
+                "Connected base nodes span only {} address group(s), expected at least {}",
+                groups, required
+            ),
+            DominantAddressGroup {
+                group,
+                connections,
+                total,
+            } => write!(
+                f,
+                "Address group {} holds {} of {} connected base nodes",
+                group, connections, total
+            ),
+            ChainTipDivergence {
+                local_height,
+                median_peer_height,
+            } => write!(
+                f,
+                "Local tip height {} diverges from median peer height {}",
+                local_height, median_peer_height
+            ),
+            ChainStalled { height, stalled_for } => write!(
+                f,
+                "Chain has not advanced past height {} for {:.0?}",
+                height, stalled_for
+            ),
+        }
+    }
+}
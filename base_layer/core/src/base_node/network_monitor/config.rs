@@ -0,0 +1,66 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tari_common::configuration::serializers;
+
+/// Configuration for the network partition and eclipse monitor.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct NetworkMonitorConfig {
+    /// True to periodically check the connection set and peer chain metadata for signs of a network partition or
+    /// eclipse attack. Default: true
+    pub enabled: bool,
+    /// The interval between checks. Default: 60 seconds
+    #[serde(with = "serializers::seconds")]
+    pub check_interval: Duration,
+    /// Raise an alert if fewer than this many base nodes are connected. Default: 3
+    pub min_connected_nodes: usize,
+    /// Raise an alert if connected base nodes are spread across fewer than this many address groups (IPv4 /16,
+    /// IPv6 /32). Onion addresses each count as a distinct group. Default: 3
+    pub min_address_groups: usize,
+    /// Raise an alert if a single address group holds more than this fraction of connected base nodes. Default: 0.5
+    pub max_address_group_share: f64,
+    /// Raise an alert if the local tip height differs from the median height claimed by connected peers by more
+    /// than this many blocks. Default: 10
+    pub max_tip_divergence: u64,
+    /// Raise an alert if neither the local tip nor any height claimed by a peer has advanced for this long.
+    /// Default: 30 minutes
+    #[serde(with = "serializers::seconds")]
+    pub chain_stall_timeout: Duration,
+}
+
+impl Default for NetworkMonitorConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            check_interval: Duration::from_secs(60),
+            min_connected_nodes: 3,
+            min_address_groups: 3,
+            max_address_group_share: 0.5,
+            max_tip_divergence: 10,
+            chain_stall_timeout: Duration::from_secs(30 * 60),
+        }
+    }
+}
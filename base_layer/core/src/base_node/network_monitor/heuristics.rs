@@ -0,0 +1,221 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{collections::HashMap, time::Duration};
+
+use tari_comms::multiaddr::{Multiaddr, Protocol};
+
+use crate::base_node::network_monitor::{NetworkAlert, NetworkMonitorConfig};
+
+/// The observations used to evaluate the partition and eclipse heuristics.
+pub(super) struct NetworkSnapshot<'a> {
+    /// The local tip height
+    pub local_height: u64,
+    /// The address of each connected base node
+    pub connected_addresses: &'a [Multiaddr],
+    /// The most recent height claimed by each connected base node that has sent chain metadata
+    pub peer_heights: &'a [u64],
+    /// The time since the local tip or any claimed peer height last advanced
+    pub stalled_for: Duration,
+}
+
+pub(super) fn evaluate(config: &NetworkMonitorConfig, snapshot: &NetworkSnapshot<'_>) -> Vec<NetworkAlert> {
+    let mut alerts = Vec::new();
+    let num_connected = snapshot.connected_addresses.len();
+
+    if num_connected < config.min_connected_nodes {
+        alerts.push(NetworkAlert::LowPeerCount {
+            connected: num_connected,
+            required: config.min_connected_nodes,
+        });
+    } else {
+        // Address diversity is only meaningful once enough peers are connected, otherwise the low peer count alert
+        // already describes the problem
+        let groups = address_groups(snapshot.connected_addresses);
+        if groups.len() < config.min_address_groups {
+            alerts.push(NetworkAlert::LowAddressDiversity {
+                groups: groups.len(),
+                required: config.min_address_groups,
+            });
+        }
+
+        if let Some((group, connections)) = groups.into_iter().max_by_key(|(_, n)| *n) {
+            #[allow(clippy::cast_precision_loss)]
+            let share = connections as f64 / num_connected as f64;
+            if share > config.max_address_group_share {
+                alerts.push(NetworkAlert::DominantAddressGroup {
+                    group,
+                    connections,
+                    total: num_connected,
+                });
+            }
+        }
+    }
+
+    if let Some(median_peer_height) = median(snapshot.peer_heights) {
+        if snapshot.local_height.abs_diff(median_peer_height) > config.max_tip_divergence {
+            alerts.push(NetworkAlert::ChainTipDivergence {
+                local_height: snapshot.local_height,
+                median_peer_height,
+            });
+        }
+    }
+
+    if snapshot.stalled_for >= config.chain_stall_timeout {
+        alerts.push(NetworkAlert::ChainStalled {
+            height: snapshot.local_height,
+            stalled_for: snapshot.stalled_for,
+        });
+    }
+
+    alerts
+}
+
+/// Returns the address group of the given address. Peers in the same group are likely to be operated by the same
+/// party or to share a network path. IPv4 addresses are grouped by /16 and IPv6 addresses by /32. Onion and DNS
+/// addresses cannot be grouped meaningfully and are each considered to be their own group.
+pub(super) fn address_group(address: &Multiaddr) -> String {
+    match address.iter().next() {
+        Some(Protocol::Ip4(ip)) => {
+            let octets = ip.octets();
+            format!("{}.{}.0.0/16", octets[0], octets[1])
+        },
+        Some(Protocol::Ip6(ip)) => {
+            let segments = ip.segments();
+            format!("{:x}:{:x}::/32", segments[0], segments[1])
+        },
+        _ => address.to_string(),
+    }
+}
+
+fn address_groups(addresses: &[Multiaddr]) -> HashMap<String, usize> {
+    let mut groups = HashMap::new();
+    for address in addresses {
+        *groups.entry(address_group(address)).or_insert(0) += 1;
+    }
+    groups
+}
+
+fn median(values: &[u64]) -> Option<u64> {
+    if values.is_empty() {
+        return None;
+    }
+    let mut values = values.to_vec();
+    values.sort_unstable();
+    Some(values[values.len() / 2])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn addresses(addrs: &[&str]) -> Vec<Multiaddr> {
+        addrs.iter().map(|a| a.parse().unwrap()).collect()
+    }
+
+    fn snapshot<'a>(connected_addresses: &'a [Multiaddr], peer_heights: &'a [u64]) -> NetworkSnapshot<'a> {
+        NetworkSnapshot {
+            local_height: 100,
+            connected_addresses,
+            peer_heights,
+            stalled_for: Duration::from_secs(0),
+        }
+    }
+
+    #[test]
+    fn it_groups_addresses() {
+        let addrs = addresses(&[
+            "/ip4/10.1.2.3/tcp/18189",
+            "/ip4/10.1.200.4/tcp/18189",
+            "/ip6/2001:db8::1/tcp/18189",
+        ]);
+        assert_eq!(address_group(&addrs[0]), "10.1.0.0/16");
+        assert_eq!(address_group(&addrs[0]), address_group(&addrs[1]));
+        assert_eq!(address_group(&addrs[2]), "2001:db8::/32");
+    }
+
+    #[test]
+    fn it_does_not_alert_for_a_healthy_connection_set() {
+        let config = NetworkMonitorConfig::default();
+        let addrs = addresses(&[
+            "/ip4/10.1.2.3/tcp/18189",
+            "/ip4/10.2.2.3/tcp/18189",
+            "/ip4/10.3.2.3/tcp/18189",
+            "/ip4/10.4.2.3/tcp/18189",
+        ]);
+        let alerts = evaluate(&config, &snapshot(&addrs, &[99, 100, 101, 100]));
+        assert!(alerts.is_empty());
+    }
+
+    #[test]
+    fn it_alerts_on_low_peer_count() {
+        let config = NetworkMonitorConfig::default();
+        let addrs = addresses(&["/ip4/10.1.2.3/tcp/18189"]);
+        let alerts = evaluate(&config, &snapshot(&addrs, &[]));
+        assert_eq!(alerts, vec![NetworkAlert::LowPeerCount {
+            connected: 1,
+            required: config.min_connected_nodes
+        }]);
+    }
+
+    #[test]
+    fn it_alerts_when_connections_are_concentrated() {
+        let config = NetworkMonitorConfig::default();
+        let addrs = addresses(&[
+            "/ip4/10.1.2.3/tcp/18189",
+            "/ip4/10.1.3.3/tcp/18189",
+            "/ip4/10.1.4.3/tcp/18189",
+            "/ip4/10.2.2.3/tcp/18189",
+        ]);
+        let alerts = evaluate(&config, &snapshot(&addrs, &[]));
+        assert_eq!(alerts.len(), 2);
+        assert!(matches!(alerts[0], NetworkAlert::LowAddressDiversity { groups: 2, .. }));
+        assert!(matches!(alerts[1], NetworkAlert::DominantAddressGroup {
+            connections: 3,
+            total: 4,
+            ..
+        }));
+    }
+
+    #[test]
+    fn it_alerts_on_tip_divergence_and_stall() {
+        let config = NetworkMonitorConfig::default();
+        let addrs = addresses(&[
+            "/ip4/10.1.2.3/tcp/18189",
+            "/ip4/10.2.2.3/tcp/18189",
+            "/ip4/10.3.2.3/tcp/18189",
+        ]);
+        let mut snapshot = snapshot(&addrs, &[150, 151, 90]);
+        snapshot.stalled_for = config.chain_stall_timeout;
+        let alerts = evaluate(&config, &snapshot);
+        assert_eq!(alerts, vec![
+            NetworkAlert::ChainTipDivergence {
+                local_height: 100,
+                median_peer_height: 150
+            },
+            NetworkAlert::ChainStalled {
+                height: 100,
+                stalled_for: config.chain_stall_timeout
+            }
+        ]);
+    }
+}
@@ -0,0 +1,94 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Network partition and eclipse detection.
+//!
+//! The network monitor periodically compares the local chain tip with the heights claimed by connected peers and
+//! checks the size and address diversity of the connection set. A [NetworkAlert] is raised on the state machine event
+//! stream when a condition first appears, and the current set of alerts is available from the
+//! [NetworkMonitorHandle].
+
+const LOG_TARGET: &str = "c::bn::network_monitor";
+
+mod alert;
+pub use alert::NetworkAlert;
+
+mod config;
+pub use config::NetworkMonitorConfig;
+
+mod error;
+pub use error::NetworkMonitorError;
+
+mod handle;
+pub use handle::NetworkMonitorHandle;
+
+mod heuristics;
+
+mod service;
+use log::*;
+use service::NetworkMonitorService;
+use tari_comms::connectivity::ConnectivityRequester;
+use tari_service_framework::{async_trait, ServiceInitializationError, ServiceInitializer, ServiceInitializerContext};
+use tokio::sync::watch;
+
+use crate::base_node::{chain_metadata_service::ChainMetadataHandle, LocalNodeCommsInterface, StateMachineHandle};
+
+pub struct NetworkMonitorInitializer {
+    config: NetworkMonitorConfig,
+}
+
+impl NetworkMonitorInitializer {
+    pub fn new(config: NetworkMonitorConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl ServiceInitializer for NetworkMonitorInitializer {
+    async fn initialize(&mut self, context: ServiceInitializerContext) -> Result<(), ServiceInitializationError> {
+        debug!(target: LOG_TARGET, "Initializing Network Monitor");
+        let (alerts_tx, alerts_rx) = watch::channel(Vec::new());
+        context.register_handle(NetworkMonitorHandle::new(alerts_rx));
+
+        if !self.config.enabled {
+            debug!(target: LOG_TARGET, "Network Monitor is disabled");
+            return Ok(());
+        }
+
+        let config = self.config.clone();
+        context.spawn_when_ready(move |handles| async move {
+            let chain_metadata = handles.expect_handle::<ChainMetadataHandle>();
+            let service = NetworkMonitorService::new(
+                config,
+                handles.expect_handle::<LocalNodeCommsInterface>(),
+                handles.expect_handle::<ConnectivityRequester>(),
+                handles.expect_handle::<StateMachineHandle>(),
+                chain_metadata.get_event_stream(),
+                alerts_tx,
+            );
+            service.run(handles.get_shutdown_signal()).await;
+        });
+
+        debug!(target: LOG_TARGET, "Network Monitor initialized");
+        Ok(())
+    }
+}
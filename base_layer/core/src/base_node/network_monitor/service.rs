@@ -0,0 +1,169 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{collections::HashMap, sync::Arc, time::Instant};
+
+use log::*;
+use tari_comms::{connectivity::ConnectivityRequester, peer_manager::NodeId};
+use tari_shutdown::ShutdownSignal;
+use tokio::{
+    sync::{broadcast, watch},
+    time,
+    time::MissedTickBehavior,
+};
+
+use crate::base_node::{
+    chain_metadata_service::ChainMetadataEvent,
+    comms_interface::LocalNodeCommsInterface,
+    network_monitor::{
+        error::NetworkMonitorError,
+        heuristics::{self, NetworkSnapshot},
+        NetworkAlert,
+        NetworkMonitorConfig,
+        LOG_TARGET,
+    },
+    state_machine_service::StateMachineHandle,
+};
+
+/// Periodically evaluates the partition and eclipse heuristics against the local chain tip, the heights claimed by
+/// connected peers and the connection set.
+pub struct NetworkMonitorService {
+    config: NetworkMonitorConfig,
+    local_node: LocalNodeCommsInterface,
+    connectivity: ConnectivityRequester,
+    state_machine: StateMachineHandle,
+    chain_metadata_events: broadcast::Receiver<Arc<ChainMetadataEvent>>,
+    alerts: watch::Sender<Vec<NetworkAlert>>,
+    peer_heights: HashMap<NodeId, u64>,
+    best_seen_height: u64,
+    last_progress: Instant,
+}
+
+impl NetworkMonitorService {
+    pub fn new(
+        config: NetworkMonitorConfig,
+        local_node: LocalNodeCommsInterface,
+        connectivity: ConnectivityRequester,
+        state_machine: StateMachineHandle,
+        chain_metadata_events: broadcast::Receiver<Arc<ChainMetadataEvent>>,
+        alerts: watch::Sender<Vec<NetworkAlert>>,
+    ) -> Self {
+        Self {
+            config,
+            local_node,
+            connectivity,
+            state_machine,
+            chain_metadata_events,
+            alerts,
+            peer_heights: HashMap::new(),
+            best_seen_height: 0,
+            last_progress: Instant::now(),
+        }
+    }
+
+    pub async fn run(mut self, mut shutdown_signal: ShutdownSignal) {
+        let mut check_interval = time::interval(self.config.check_interval);
+        check_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        // Skip the immediate first tick, the node has not yet had a chance to connect to peers
+        check_interval.tick().await;
+
+        loop {
+            tokio::select! {
+                Ok(event) = self.chain_metadata_events.recv() => {
+                    self.handle_chain_metadata_event(&event);
+                },
+                _ = check_interval.tick() => {
+                    if let Err(err) = self.check().await {
+                        warn!(target: LOG_TARGET, "Network monitor check failed: {}", err);
+                    }
+                },
+                _ = shutdown_signal.wait() => {
+                    info!(target: LOG_TARGET, "Network monitor shutting down because it received the shutdown signal");
+                    break;
+                }
+            }
+        }
+    }
+
+    fn handle_chain_metadata_event(&mut self, event: &ChainMetadataEvent) {
+        if let ChainMetadataEvent::PeerChainMetadataReceived(peer_metadata) = event {
+            let height = peer_metadata.claimed_chain_metadata().best_block_height();
+            self.peer_heights.insert(peer_metadata.node_id().clone(), height);
+            self.record_height(height);
+        }
+    }
+
+    fn record_height(&mut self, height: u64) {
+        if height > self.best_seen_height {
+            self.best_seen_height = height;
+            self.last_progress = Instant::now();
+        }
+    }
+
+    async fn check(&mut self) -> Result<(), NetworkMonitorError> {
+        if !self.state_machine.get_status_info_watch().borrow().bootstrapped {
+            debug!(target: LOG_TARGET, "Node is not bootstrapped yet, skipping network monitor check");
+            self.last_progress = Instant::now();
+            return Ok(());
+        }
+
+        let local_height = self.local_node.get_metadata().await?.best_block_height();
+        self.record_height(local_height);
+
+        let connections = self
+            .connectivity
+            .get_active_connections()
+            .await?
+            .into_iter()
+            .filter(|conn| conn.peer_features().is_node())
+            .collect::<Vec<_>>();
+        // Only consider heights claimed by peers that are still connected
+        self.peer_heights
+            .retain(|node_id, _| connections.iter().any(|conn| conn.peer_node_id() == node_id));
+
+        let connected_addresses = connections
+            .iter()
+            .map(|conn| conn.address().clone())
+            .collect::<Vec<_>>();
+        let peer_heights = self.peer_heights.values().copied().collect::<Vec<_>>();
+        let alerts = heuristics::evaluate(&self.config, &NetworkSnapshot {
+            local_height,
+            connected_addresses: &connected_addresses,
+            peer_heights: &peer_heights,
+            stalled_for: self.last_progress.elapsed(),
+        });
+
+        let previous_alerts = self.alerts.borrow().clone();
+        for alert in &alerts {
+            if previous_alerts.iter().all(|prev| !prev.is_same_kind(alert)) {
+                warn!(target: LOG_TARGET, "Network alert raised: {}", alert);
+                self.state_machine.publish_network_alert(alert.clone());
+            }
+        }
+        if !previous_alerts.is_empty() && alerts.is_empty() {
+            info!(target: LOG_TARGET, "All network alerts have cleared");
+        }
+
+        let _result = self.alerts.send(alerts);
+        Ok(())
+    }
+}
@@ -31,7 +31,10 @@ use tokio::sync::mpsc;
 
 use crate::{
     base_node::{
-        rpc::{sync_utxos_by_block_task::SyncUtxosByBlockTask, BaseNodeWalletService},
+        rpc::{
+            sync_utxos_by_block_task::{full_outputs_from_height, SyncUtxosByBlockTask},
+            BaseNodeWalletService,
+        },
         state_machine_service::states::StateInfo,
         StateMachineHandle,
     },
@@ -180,6 +183,7 @@ impl<B: BlockchainBackend + 'static> BaseNodeWalletService for BaseNodeWalletRpc
         &self,
         request: Request<TransactionProto>,
     ) -> Result<Response<TxSubmissionResponse>, RpcStatus> {
+        let peer = request.context().peer_node_id().clone();
         let message = request.into_message();
         let transaction =
             Transaction::try_from(message).map_err(|_| RpcStatus::bad_request("Transaction was invalid"))?;
@@ -194,7 +198,7 @@ impl<B: BlockchainBackend + 'static> BaseNodeWalletService for BaseNodeWalletRpc
         };
 
         let response = match mempool
-            .submit_transaction(transaction.clone())
+            .submit_transaction_from_peer(transaction.clone(), peer)
             .await
             .rpc_status_internal_error(LOG_TARGET)?
         {
@@ -541,6 +545,7 @@ impl<B: BlockchainBackend + 'static> BaseNodeWalletService for BaseNodeWalletRpc
             .rpc_status_internal_error(LOG_TARGET)?;
 
         Ok(Response::new(TipInfoResponse {
+            full_outputs_from_height: full_outputs_from_height(&metadata),
             metadata: Some(metadata.into()),
             is_synced,
         }))
@@ -23,6 +23,7 @@
 use std::{convert::TryInto, time::Instant};
 
 use log::*;
+use tari_common_types::chain_metadata::ChainMetadata;
 use tari_comms::protocol::rpc::{RpcStatus, RpcStatusResultExt};
 use tari_utilities::hex::Hex;
 use tokio::{sync::mpsc, task};
@@ -36,6 +37,16 @@ use crate::{
 
 const LOG_TARGET: &str = "c::base_node::sync_rpc::sync_utxo_by_block_task";
 
+/// The lowest height from which the node keeps the full outputs of every block. The outputs of a block can only have
+/// been spent, and pruned, in the same or a later block, so they are complete above the pruned height.
+pub(crate) fn full_outputs_from_height(metadata: &ChainMetadata) -> u64 {
+    if metadata.pruned_height() == 0 {
+        0
+    } else {
+        metadata.pruned_height() + 1
+    }
+}
+
 pub(crate) struct SyncUtxosByBlockTask<B> {
     db: AsyncBlockchainDb<B>,
 }
@@ -82,6 +93,21 @@ where B: BlockchainBackend + 'static
             )));
         }
 
+        if request.require_full_outputs {
+            let metadata = self
+                .db
+                .get_chain_metadata()
+                .await
+                .rpc_status_internal_error(LOG_TARGET)?;
+            let full_outputs_from_height = full_outputs_from_height(&metadata);
+            if start_header.height < full_outputs_from_height {
+                return Err(RpcStatus::not_found(&format!(
+                    "Full outputs are only retained from height {}",
+                    full_outputs_from_height
+                )));
+            }
+        }
+
         task::spawn(async move {
             if let Err(err) = self.start_streaming(&mut tx, start_header, end_header).await {
                 let _result = tx.send(Err(err)).await;
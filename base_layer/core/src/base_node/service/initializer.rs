@@ -29,7 +29,7 @@ use tari_comms_dht::Dht;
 use tari_p2p::{
     comms_connector::{PeerMessage, SubscriptionFactory},
     domain_message::DomainMessage,
-    services::utils::map_decode,
+    services::{gossip::GossipHandle, utils::map_decode},
     tari_message::TariMessageType,
 };
 use tari_service_framework::{
@@ -192,6 +192,8 @@ where T: BlockchainBackend + 'static
             let outbound_message_service = dht.outbound_requester();
 
             let state_machine = handles.expect_handle::<StateMachineHandle>();
+            // Blocks are propagated over the gossip mesh if the gossip service is running
+            let gossip = handles.get_handle::<GossipHandle>();
 
             let inbound_nch = InboundNodeCommsHandlers::new(
                 block_event_sender,
@@ -219,6 +221,7 @@ where T: BlockchainBackend + 'static
                 state_machine,
                 connectivity,
                 config,
+                gossip,
             )
             .start(streams);
             futures::pin_mut!(service);
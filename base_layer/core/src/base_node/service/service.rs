@@ -35,7 +35,7 @@ use tari_comms_dht::{
     envelope::NodeDestination,
     outbound::{DhtOutboundError, OutboundEncryption, OutboundMessageRequester, SendMessageParams},
 };
-use tari_p2p::{domain_message::DomainMessage, tari_message::TariMessageType};
+use tari_p2p::{domain_message::DomainMessage, services::gossip::GossipHandle, tari_message::TariMessageType};
 use tari_service_framework::reply_channel::RequestContext;
 use tari_utilities::hex::Hex;
 use tokio::{
@@ -100,6 +100,7 @@ pub(super) struct BaseNodeService<B> {
     state_machine_handle: StateMachineHandle,
     connectivity: ConnectivityRequester,
     base_node_config: BaseNodeStateMachineConfig,
+    gossip: Option<GossipHandle>,
 }
 
 impl<B> BaseNodeService<B>
@@ -112,6 +113,7 @@ where B: BlockchainBackend + 'static
         state_machine_handle: StateMachineHandle,
         connectivity: ConnectivityRequester,
         base_node_config: BaseNodeStateMachineConfig,
+        gossip: Option<GossipHandle>,
     ) -> Self {
         let (timeout_sender, timeout_receiver) = mpsc::channel(100);
         Self {
@@ -124,6 +126,7 @@ where B: BlockchainBackend + 'static
             state_machine_handle,
             connectivity,
             base_node_config,
+            gossip,
         }
     }
 
@@ -244,8 +247,9 @@ where B: BlockchainBackend + 'static
 
     fn spawn_handle_outbound_block(&self, new_block: NewBlock, excluded_peers: Vec<NodeId>) {
         let outbound_message_service = self.outbound_message_service.clone();
+        let gossip = self.gossip.clone();
         task::spawn(async move {
-            let result = handle_outbound_block(outbound_message_service, new_block, excluded_peers).await;
+            let result = handle_outbound_block(outbound_message_service, gossip, new_block, excluded_peers).await;
 
             if let Err(e) = result {
                 error!(target: LOG_TARGET, "Failed to handle outbound block message {:?}", e);
@@ -645,18 +649,26 @@ async fn handle_outbound_request(
 
 async fn handle_outbound_block(
     mut outbound_message_service: OutboundMessageRequester,
+    gossip: Option<GossipHandle>,
     new_block: NewBlock,
     exclude_peers: Vec<NodeId>,
 ) -> Result<(), CommsInterfaceError> {
+    let new_block = shared_protos::core::NewBlock::try_from(new_block).map_err(CommsInterfaceError::InternalError)?;
+    if let Some(mut gossip) = gossip {
+        let num_peers = gossip
+            .publish(TariMessageType::NewBlock, new_block, exclude_peers)
+            .await
+            .map_err(|e| CommsInterfaceError::InternalError(e.to_string()))?;
+        debug!(target: LOG_TARGET, "Block published to {} peer(s) over gossip", num_peers);
+        return Ok(());
+    }
+
     let result = outbound_message_service
         .propagate(
             NodeDestination::Unknown,
             OutboundEncryption::ClearText,
             exclude_peers,
-            OutboundDomainMessage::new(
-                &TariMessageType::NewBlock,
-                shared_protos::core::NewBlock::try_from(new_block).map_err(CommsInterfaceError::InternalError)?,
-            ),
+            OutboundDomainMessage::new(&TariMessageType::NewBlock, new_block),
             "Outbound new block from base node".to_string(),
         )
         .await;
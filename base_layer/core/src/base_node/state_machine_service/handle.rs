@@ -25,7 +25,10 @@ use std::sync::Arc;
 use tari_shutdown::ShutdownSignal;
 use tokio::sync::{broadcast, watch};
 
-use crate::base_node::state_machine_service::states::{StateEvent, StatusInfo};
+use crate::base_node::{
+    network_monitor::NetworkAlert,
+    state_machine_service::states::{StateEvent, StatusInfo},
+};
 
 #[derive(Clone)]
 pub struct StateMachineHandle {
@@ -61,6 +64,13 @@ impl StateMachineHandle {
         self.status_event_receiver.clone()
     }
 
+    /// Publishes a network alert to state change event subscribers. Alerts do not cause a state transition.
+    pub fn publish_network_alert(&self, alert: NetworkAlert) {
+        let _size = self
+            .state_change_event_subscriber
+            .send(Arc::new(StateEvent::NetworkAlert(alert)));
+    }
+
     pub fn shutdown_signal(&self) -> ShutdownSignal {
         self.shutdown_signal.clone()
     }
@@ -51,12 +51,22 @@ impl BlockSync {
         &mut self,
         shared: &mut BaseNodeStateMachine<B>,
     ) -> StateEvent {
+        // Try persistently slow peers last. Peers blacklisted for sending invalid data are skipped and peers that keep
+        // stalling are tried after all others.
+        let latency_tracker = shared.peer_manager.latency_tracker().clone();
+        self.sync_peers
+            .sort_by_key(|p| latency_tracker.is_persistently_slow(p.node_id()));
+        let sync_quality = shared.peer_manager.sync_quality_tracker().clone();
+        sync_quality.rank_sync_peers(&mut self.sync_peers, |p| p.node_id());
+
         let mut synchronizer = BlockSynchronizer::new(
             shared.config.blockchain_sync_config.clone(),
             shared.db.clone(),
             shared.connectivity.clone(),
             &mut self.sync_peers,
             shared.sync_validators.block_body.clone(),
+            latency_tracker,
+            sync_quality,
         );
 
         let status_event_sender = shared.status_event_sender.clone();
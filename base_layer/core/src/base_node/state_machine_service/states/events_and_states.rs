@@ -26,6 +26,7 @@ use randomx_rs::RandomXFlag;
 use tari_common_types::chain_metadata::ChainMetadata;
 
 use crate::base_node::{
+    network_monitor::NetworkAlert,
     state_machine_service::states::{
         BlockSync,
         DecideNextSync,
@@ -67,6 +68,7 @@ pub enum StateEvent {
     BlockSyncFailed,
     FallenBehind(SyncStatus),
     NetworkSilence,
+    NetworkAlert(NetworkAlert),
     FatalError(String),
     Continue,
     UserQuit,
@@ -154,6 +156,7 @@ impl Display for StateEvent {
             BlockSyncFailed => write!(f, "Block Synchronization Failed"),
             FallenBehind(s) => write!(f, "Fallen behind main chain - {}", s),
             NetworkSilence => write!(f, "Network Silence"),
+            NetworkAlert(alert) => write!(f, "Network Alert - {}", alert),
             Continue => write!(f, "Continuing"),
             FatalError(e) => write!(f, "Fatal Error - {}", e),
             UserQuit => write!(f, "User Termination"),
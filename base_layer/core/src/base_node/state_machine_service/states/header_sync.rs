@@ -107,6 +107,13 @@ impl HeaderSyncState {
             Err(e) => return StateEvent::FatalError(format!("{}", e)),
         }
 
+        // Prefer peers with a low measured RTT and try persistently slow peers last. Peers blacklisted for sending
+        // invalid data are skipped and peers that keep stalling are tried after all others.
+        let latency_tracker = shared.peer_manager.latency_tracker().clone();
+        latency_tracker.sort_by_latency(&mut self.sync_peers, |p| p.node_id());
+        let sync_quality = shared.peer_manager.sync_quality_tracker().clone();
+        sync_quality.rank_sync_peers(&mut self.sync_peers, |p| p.node_id());
+
         let mut synchronizer = HeaderSynchronizer::new(
             shared.config.blockchain_sync_config.clone(),
             shared.db.clone(),
@@ -115,6 +122,8 @@ impl HeaderSyncState {
             &mut self.sync_peers,
             shared.randomx_factory.clone(),
             &self.local_metadata,
+            latency_tracker,
+            sync_quality,
         );
 
         let status_event_sender = shared.status_event_sender.clone();
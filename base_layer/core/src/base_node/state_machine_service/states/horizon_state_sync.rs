@@ -64,6 +64,12 @@ impl HorizonStateSync {
                 .accumulated_difficulty()
                 .cmp(&a.claimed_chain_metadata().accumulated_difficulty())
         });
+        // Try persistently slow peers last. Peers blacklisted for sending invalid data are skipped and peers that keep
+        // stalling are tried after all others.
+        let latency_tracker = shared.peer_manager.latency_tracker().clone();
+        sync_peers.sort_by_key(|p| latency_tracker.is_persistently_slow(p.node_id()));
+        let sync_quality = shared.peer_manager.sync_quality_tracker().clone();
+        sync_quality.rank_sync_peers(sync_peers, |p| p.node_id());
 
         // Target horizon sync height based on the last header we have synced
         let last_header = match shared.db.fetch_last_header().await {
@@ -100,6 +106,8 @@ impl HorizonStateSync {
             target_horizon_sync_height,
             prover,
             validator,
+            latency_tracker,
+            sync_quality,
         );
 
         let status_event_sender = shared.status_event_sender.clone();
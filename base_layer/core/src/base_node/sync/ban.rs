@@ -23,9 +23,15 @@
 use std::time::Duration;
 
 use log::*;
-use tari_comms::{connectivity::ConnectivityRequester, peer_manager::NodeId};
+use tari_comms::{
+    connectivity::ConnectivityRequester,
+    peer_manager::{NodeId, SyncPeerQualityTracker},
+};
 
-use crate::base_node::BlockchainSyncConfig;
+use crate::{
+    base_node::BlockchainSyncConfig,
+    common::{BanPeriod, BanReason},
+};
 
 const LOG_TARGET: &str = "c::bn::sync";
 
@@ -34,11 +40,42 @@ const LOG_TARGET: &str = "c::bn::sync";
 pub struct PeerBanManager {
     config: BlockchainSyncConfig,
     connectivity: ConnectivityRequester,
+    sync_quality: SyncPeerQualityTracker,
 }
 
 impl PeerBanManager {
-    pub fn new(config: BlockchainSyncConfig, connectivity: ConnectivityRequester) -> Self {
-        Self { config, connectivity }
+    pub fn new(
+        config: BlockchainSyncConfig,
+        connectivity: ConnectivityRequester,
+        sync_quality: SyncPeerQualityTracker,
+    ) -> Self {
+        Self {
+            config,
+            connectivity,
+            sync_quality,
+        }
+    }
+
+    /// Records that the sync peer was too slow to continue syncing from
+    pub fn record_stall(&self, node_id: &NodeId) {
+        self.sync_quality.record_stall(node_id);
+    }
+
+    /// Bans the sync peer for the period of the ban reason. A long ban is given for invalid data, so the peer is also
+    /// blacklisted as a sync peer, which applies even if it is on the allow list for sync.
+    pub async fn ban_sync_peer(&mut self, node_id: &NodeId, ban_reason: BanReason) {
+        let duration = match ban_reason.ban_duration {
+            BanPeriod::Short => self.config.short_ban_period,
+            BanPeriod::Long => {
+                self.sync_quality.blacklist(
+                    node_id,
+                    ban_reason.reason.clone(),
+                    self.config.sync_peer_blacklist_period,
+                );
+                self.config.ban_period
+            },
+        };
+        self.ban_peer_if_required(node_id, ban_reason.reason, duration).await;
     }
 
     pub async fn ban_peer_if_required(&mut self, node_id: &NodeId, ban_reason: String, ban_duration: Duration) {
@@ -0,0 +1,189 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::collections::{BTreeMap, BTreeSet};
+
+/// An inclusive range of heights that is requested from a sync peer at once
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HeightBatch {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl HeightBatch {
+    pub fn num_heights(&self) -> u64 {
+        self.end - self.start + 1
+    }
+}
+
+/// Hands out the batches of a height range to the sync peers that are downloading it concurrently, and releases the
+/// downloaded batches in height order so that they can be validated and committed one after the other, however the
+/// downloads interleave. A batch that fails or times out is queued again for the next peer that asks for work.
+pub struct BatchScheduler<T> {
+    queued: BTreeSet<HeightBatch>,
+    in_flight: BTreeSet<HeightBatch>,
+    downloaded: BTreeMap<u64, (HeightBatch, T)>,
+    next_height: u64,
+    end_height: u64,
+    max_pending: usize,
+}
+
+impl<T> BatchScheduler<T> {
+    /// Splits `start_height..=end_height` into batches of `batch_size` heights. At most `max_pending` batches are
+    /// downloading or waiting to be released at a time, which bounds the memory used while an earlier batch is slow.
+    pub fn new(start_height: u64, end_height: u64, batch_size: u64, max_pending: usize) -> Self {
+        let batch_size = batch_size.max(1);
+        let mut queued = BTreeSet::new();
+        let mut start = start_height;
+        while start <= end_height {
+            let end = start.saturating_add(batch_size - 1).min(end_height);
+            queued.insert(HeightBatch { start, end });
+            match end.checked_add(1) {
+                Some(next) => start = next,
+                None => break,
+            }
+        }
+        Self {
+            queued,
+            in_flight: BTreeSet::new(),
+            downloaded: BTreeMap::new(),
+            next_height: start_height,
+            end_height,
+            max_pending: max_pending.max(1),
+        }
+    }
+
+    /// Assigns the lowest queued batch to a sync peer whose best block is at `best_height`. Nothing is assigned if the
+    /// peer does not claim to have the whole batch, or if too many batches are pending, unless the batch is the next
+    /// one to be released.
+    pub fn assign(&mut self, best_height: u64) -> Option<HeightBatch> {
+        let batch = *self.queued.iter().next()?;
+        if batch.end > best_height {
+            return None;
+        }
+        if batch.start != self.next_height && self.in_flight.len() + self.downloaded.len() >= self.max_pending {
+            return None;
+        }
+        self.queued.remove(&batch);
+        self.in_flight.insert(batch);
+        Some(batch)
+    }
+
+    /// Stores the data downloaded for an assigned batch until it is next in line
+    pub fn complete(&mut self, batch: HeightBatch, data: T) {
+        if self.in_flight.remove(&batch) {
+            self.downloaded.insert(batch.start, (batch, data));
+        }
+    }
+
+    /// Queues an assigned batch again after its download failed
+    pub fn requeue(&mut self, batch: HeightBatch) {
+        if self.in_flight.remove(&batch) {
+            self.queued.insert(batch);
+        }
+    }
+
+    /// Queues the heights of a released batch from `height` again, e.g. after the data from that height onwards
+    /// could not be used
+    pub fn requeue_from(&mut self, batch: HeightBatch, height: u64) {
+        if height < batch.start || height > batch.end {
+            return;
+        }
+        self.queued.insert(HeightBatch {
+            start: height,
+            end: batch.end,
+        });
+        self.next_height = height;
+    }
+
+    /// Releases the downloaded batch that starts at the next height to be validated, if it has been downloaded
+    pub fn next_ready(&mut self) -> Option<(HeightBatch, T)> {
+        let (batch, data) = self.downloaded.remove(&self.next_height)?;
+        self.next_height = batch.end.saturating_add(1);
+        Some((batch, data))
+    }
+
+    /// True once every batch has been released
+    pub fn is_finished(&self) -> bool {
+        self.next_height > self.end_height
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn batch(start: u64, end: u64) -> HeightBatch {
+        HeightBatch { start, end }
+    }
+
+    #[test]
+    fn it_releases_batches_in_height_order() {
+        let mut scheduler = BatchScheduler::new(1, 25, 10, 10);
+        assert_eq!(scheduler.assign(100), Some(batch(1, 10)));
+        assert_eq!(scheduler.assign(100), Some(batch(11, 20)));
+        assert_eq!(scheduler.assign(100), Some(batch(21, 25)));
+        assert_eq!(scheduler.assign(100), None);
+
+        scheduler.complete(batch(21, 25), "c");
+        scheduler.complete(batch(11, 20), "b");
+        assert!(scheduler.next_ready().is_none());
+        scheduler.complete(batch(1, 10), "a");
+        assert_eq!(scheduler.next_ready(), Some((batch(1, 10), "a")));
+        assert_eq!(scheduler.next_ready(), Some((batch(11, 20), "b")));
+        assert!(!scheduler.is_finished());
+        assert_eq!(scheduler.next_ready(), Some((batch(21, 25), "c")));
+        assert!(scheduler.is_finished());
+    }
+
+    #[test]
+    fn it_reassigns_failed_batches_first() {
+        let mut scheduler = BatchScheduler::<()>::new(1, 30, 10, 10);
+        assert_eq!(scheduler.assign(100), Some(batch(1, 10)));
+        assert_eq!(scheduler.assign(100), Some(batch(11, 20)));
+        scheduler.requeue(batch(1, 10));
+        assert_eq!(scheduler.assign(100), Some(batch(1, 10)));
+        assert_eq!(scheduler.assign(100), Some(batch(21, 30)));
+    }
+
+    #[test]
+    fn it_only_assigns_batches_a_peer_has() {
+        let mut scheduler = BatchScheduler::<()>::new(1, 30, 10, 10);
+        assert_eq!(scheduler.assign(15), Some(batch(1, 10)));
+        assert_eq!(scheduler.assign(15), None);
+        assert_eq!(scheduler.assign(30), Some(batch(11, 20)));
+    }
+
+    #[test]
+    fn it_limits_the_pending_batches_except_the_next_one() {
+        let mut scheduler = BatchScheduler::new(1, 40, 10, 2);
+        assert_eq!(scheduler.assign(100), Some(batch(1, 10)));
+        assert_eq!(scheduler.assign(100), Some(batch(11, 20)));
+        assert_eq!(scheduler.assign(100), None);
+        scheduler.complete(batch(1, 10), ());
+        assert_eq!(scheduler.next_ready(), Some((batch(1, 10), ())));
+        assert_eq!(scheduler.assign(100), Some(batch(21, 30)));
+        assert_eq!(scheduler.assign(100), None);
+
+        // The data of the released batch could not be used from height 6, which is assigned despite the limit
+        scheduler.requeue_from(batch(1, 10), 6);
+        assert_eq!(scheduler.assign(100), Some(batch(6, 10)));
+        assert_eq!(scheduler.assign(100), None);
+    }
+
+    #[test]
+    fn it_requeues_the_rest_of_a_released_batch() {
+        let mut scheduler = BatchScheduler::new(1, 10, 10, 2);
+        assert_eq!(scheduler.assign(100), Some(batch(1, 10)));
+        scheduler.complete(batch(1, 10), ());
+        assert_eq!(scheduler.next_ready(), Some((batch(1, 10), ())));
+        assert!(scheduler.is_finished());
+
+        scheduler.requeue_from(batch(1, 10), 6);
+        assert!(!scheduler.is_finished());
+        assert_eq!(scheduler.assign(100), Some(batch(6, 10)));
+        scheduler.complete(batch(6, 10), ());
+        assert_eq!(scheduler.next_ready(), Some((batch(6, 10), ())));
+        assert!(scheduler.is_finished());
+    }
+}
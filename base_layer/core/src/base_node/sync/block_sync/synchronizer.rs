@@ -21,27 +21,40 @@
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use std::{
+    collections::HashSet,
     convert::{TryFrom, TryInto},
     sync::Arc,
     time::{Duration, Instant},
 };
 
-use futures::StreamExt;
+use futures::{stream::FuturesUnordered, StreamExt};
 use log::*;
-use tari_comms::{connectivity::ConnectivityRequester, peer_manager::NodeId, protocol::rpc::RpcClient, PeerConnection};
+use tari_common_types::types::HashOutput;
+use tari_comms::{
+    connectivity::ConnectivityRequester,
+    peer_manager::{NodeId, PeerLatencyTracker, SyncPeerQualityTracker},
+    protocol::rpc::RpcClient,
+};
 use tari_utilities::hex::Hex;
-use tokio::task;
+use tokio::{task, time};
+use tracing::instrument;
 
 use super::error::BlockSyncError;
 use crate::{
     base_node::{
-        sync::{ban::PeerBanManager, hooks::Hooks, rpc, SyncPeer},
+        sync::{
+            ban::PeerBanManager,
+            batch_scheduler::{BatchScheduler, HeightBatch},
+            hooks::Hooks,
+            rpc,
+            SyncPeer,
+        },
         BlockchainSyncConfig,
     },
     blocks::{Block, ChainBlock},
     chain_storage::{async_db::AsyncBlockchainDb, BlockchainBackend},
-    common::{rolling_avg::RollingAverageTime, BanPeriod},
-    proto::base_node::SyncBlocksRequest,
+    common::rolling_avg::RollingAverageTime,
+    proto::base_node::{BlockBodyResponse, SyncBlocksRequest},
     transactions::aggregated_body::AggregateBody,
     validation::{BlockBodyValidator, ValidationError},
 };
@@ -58,6 +71,8 @@ pub struct BlockSynchronizer<'a, B> {
     block_validator: Arc<dyn BlockBodyValidator<B>>,
     hooks: Hooks,
     peer_ban_manager: PeerBanManager,
+    latency_tracker: PeerLatencyTracker,
+    sync_quality: SyncPeerQualityTracker,
 }
 
 impl<'a, B: BlockchainBackend + 'static> BlockSynchronizer<'a, B> {
@@ -67,8 +82,10 @@ impl<'a, B: BlockchainBackend + 'static> BlockSynchronizer<'a, B> {
         connectivity: ConnectivityRequester,
         sync_peers: &'a mut Vec<SyncPeer>,
         block_validator: Arc<dyn BlockBodyValidator<B>>,
+        latency_tracker: PeerLatencyTracker,
+        sync_quality: SyncPeerQualityTracker,
     ) -> Self {
-        let peer_ban_manager = PeerBanManager::new(config.clone(), connectivity.clone());
+        let peer_ban_manager = PeerBanManager::new(config.clone(), connectivity.clone(), sync_quality.clone());
         Self {
             config,
             db,
@@ -77,6 +94,8 @@ impl<'a, B: BlockchainBackend + 'static> BlockSynchronizer<'a, B> {
             block_validator,
             hooks: Default::default(),
             peer_ban_manager,
+            latency_tracker,
+            sync_quality,
         }
     }
 
@@ -95,6 +114,7 @@ impl<'a, B: BlockchainBackend + 'static> BlockSynchronizer<'a, B> {
         self.hooks.add_on_complete_hook(hook);
     }
 
+    #[instrument(name = "block_sync::synchronize", level = "debug", skip_all, err)]
     pub async fn synchronize(&mut self) -> Result<(), BlockSyncError> {
         let mut max_latency = self.config.initial_max_sync_latency;
         let mut sync_round = 0;
@@ -143,10 +163,14 @@ impl<'a, B: BlockchainBackend + 'static> BlockSynchronizer<'a, B> {
         );
         let mut latency_counter = 0usize;
         for node_id in sync_peer_node_ids {
-            let peer_index = self.get_sync_peer_index(&node_id).ok_or(BlockSyncError::PeerNotFound)?;
+            // The peer may have been removed while helping another sync peer
+            let peer_index = match self.get_sync_peer_index(&node_id) {
+                Some(index) => index,
+                None => continue,
+            };
             let sync_peer = &self.sync_peers[peer_index];
             self.hooks.call_on_starting_hook(sync_peer);
-            let mut conn = match self.connect_to_sync_peer(node_id.clone()).await {
+            let client = match self.connect_to_sync_peer(node_id.clone()).await {
                 Ok(val) => val,
                 Err(e) => {
                     warn!(
@@ -157,23 +181,6 @@ impl<'a, B: BlockchainBackend + 'static> BlockSynchronizer<'a, B> {
                     continue;
                 },
             };
-            let config = RpcClient::builder()
-                .with_deadline(self.config.rpc_deadline)
-                .with_deadline_grace_period(Duration::from_secs(5));
-            let mut client = match conn
-                .connect_rpc_using_builder::<rpc::BaseNodeSyncRpcClient>(config)
-                .await
-            {
-                Ok(val) => val,
-                Err(e) => {
-                    warn!(
-                        target: LOG_TARGET,
-                        "Failed to obtain RPC connection from sync peer `{}`: {}", node_id, e
-                    );
-                    self.remove_sync_peer(&node_id);
-                    continue;
-                },
-            };
             let latency = client
                 .get_last_request_latency()
                 .expect("unreachable panic: last request latency must be set after connect");
@@ -183,25 +190,17 @@ impl<'a, B: BlockchainBackend + 'static> BlockSynchronizer<'a, B> {
                 target: LOG_TARGET,
                 "Attempting to synchronize blocks with `{}` latency: {:.2?}", node_id, latency
             );
-            match self.synchronize_blocks(sync_peer, client, max_latency).await {
-                Ok(_) => return Ok(()),
+            let helpers = self.connect_to_helper_peers(&node_id).await;
+            match self.synchronize_blocks(sync_peer, client, helpers, max_latency).await {
+                Ok(_) => {
+                    self.sync_quality.record_sync_completed(&node_id);
+                    return Ok(());
+                },
                 Err(err) => {
                     warn!(target: LOG_TARGET, "{}", err);
-                    let ban_reason = BlockSyncError::get_ban_reason(&err);
-                    if let Some(reason) = ban_reason {
-                        let duration = match reason.ban_duration {
-                            BanPeriod::Short => self.config.short_ban_period,
-                            BanPeriod::Long => self.config.ban_period,
-                        };
-                        warn!(target: LOG_TARGET, "{}", err);
-                        self.peer_ban_manager
-                            .ban_peer_if_required(&node_id, reason.reason, duration)
-                            .await;
-                    }
+                    self.handle_sync_peer_error(&node_id, &err).await;
                     if let BlockSyncError::MaxLatencyExceeded { .. } = err {
                         latency_counter += 1;
-                    } else {
-                        self.remove_sync_peer(&node_id);
                     }
                 },
             }
@@ -216,9 +215,62 @@ impl<'a, B: BlockchainBackend + 'static> BlockSynchronizer<'a, B> {
         }
     }
 
-    async fn connect_to_sync_peer(&self, peer: NodeId) -> Result<PeerConnection, BlockSyncError> {
-        let connection = self.connectivity.dial_peer(peer).await?;
-        Ok(connection)
+    async fn connect_to_sync_peer(&self, peer: NodeId) -> Result<rpc::BaseNodeSyncRpcClient, BlockSyncError> {
+        let mut connection = self.connectivity.dial_peer(peer).await?;
+        let config = RpcClient::builder()
+            .with_deadline(self.config.rpc_deadline)
+            .with_deadline_grace_period(Duration::from_secs(5))
+            .with_latency_tracker(self.latency_tracker.clone())
+            .with_sync_quality_tracker(self.sync_quality.clone());
+        let client = connection
+            .connect_rpc_using_builder::<rpc::BaseNodeSyncRpcClient>(config)
+            .await?;
+        Ok(client)
+    }
+
+    /// Connects to the other sync peers that block bodies are downloaded from alongside `sync_peer`, up to
+    /// `max_concurrent_sync_peers` in total
+    async fn connect_to_helper_peers(&mut self, sync_peer: &NodeId) -> Vec<(SyncPeer, rpc::BaseNodeSyncRpcClient)> {
+        let candidates = self
+            .sync_peers
+            .iter()
+            .filter(|p| p.node_id() != sync_peer)
+            .take(self.config.max_concurrent_sync_peers.saturating_sub(1))
+            .cloned()
+            .collect::<Vec<_>>();
+        let mut helpers = Vec::with_capacity(candidates.len());
+        for mut peer in candidates {
+            match self.connect_to_sync_peer(peer.node_id().clone()).await {
+                Ok(client) => {
+                    if let Some(latency) = client.get_last_request_latency() {
+                        peer.set_latency(latency);
+                    }
+                    helpers.push((peer, client));
+                },
+                Err(e) => {
+                    warn!(
+                        target: LOG_TARGET,
+                        "Failed to connect to sync peer `{}`: {}",
+                        peer.node_id(),
+                        e
+                    );
+                    self.remove_sync_peer(peer.node_id());
+                },
+            }
+        }
+        helpers
+    }
+
+    /// Bans the sync peer if the error warrants it, and removes it from the sync peers unless it was only slow
+    async fn handle_sync_peer_error(&mut self, node_id: &NodeId, err: &BlockSyncError) {
+        if let Some(reason) = err.get_ban_reason() {
+            self.peer_ban_manager.ban_sync_peer(node_id, reason).await;
+        }
+        if matches!(err, BlockSyncError::MaxLatencyExceeded { .. }) {
+            self.peer_ban_manager.record_stall(node_id);
+        } else {
+            self.remove_sync_peer(node_id);
+        }
     }
 
     #[allow(clippy::too_many_lines)]
@@ -226,6 +278,7 @@ impl<'a, B: BlockchainBackend + 'static> BlockSynchronizer<'a, B> {
         &mut self,
         mut sync_peer: SyncPeer,
         mut client: rpc::BaseNodeSyncRpcClient,
+        helpers: Vec<(SyncPeer, rpc::BaseNodeSyncRpcClient)>,
         max_latency: Duration,
     ) -> Result<(), BlockSyncError> {
         info!(target: LOG_TARGET, "Starting block sync from peer {}", sync_peer);
@@ -249,171 +302,63 @@ impl<'a, B: BlockchainBackend + 'static> BlockSynchronizer<'a, B> {
         let best_full_block_hash = chain_header.accumulated_data().hash;
         debug!(
             target: LOG_TARGET,
-            "Starting block sync from peer `{}`. Current best block is #{} `{}`. Syncing to #{} ({}).",
+            "Starting block sync from peer `{}` and {} other peer(s). Current best block is #{} `{}`. Syncing to #{} \
+             ({}).",
             sync_peer,
+            helpers.len(),
             best_height,
             best_full_block_hash.to_hex(),
             tip_height,
             tip_hash.to_hex()
         );
-        let request = SyncBlocksRequest {
-            start_hash: best_full_block_hash.to_vec(),
-            // To the tip!
-            end_hash: tip_hash.to_vec(),
-        };
 
-        let mut block_stream = client.sync_blocks(request).await?;
-        let mut prev_hash = best_full_block_hash;
-        let mut current_block = None;
-        let mut last_sync_timer = Instant::now();
-        let mut avg_latency = RollingAverageTime::new(20);
-        while let Some(block_result) = block_stream.next().await {
-            let latency = last_sync_timer.elapsed();
-            avg_latency.add_sample(latency);
-            let block_body_response = block_result?;
-
-            let header = self
-                .db
-                .fetch_chain_header_by_block_hash(block_body_response.hash.clone().try_into()?)
-                .await?
-                .ok_or_else(|| {
-                    BlockSyncError::UnknownHeaderHash(format!(
-                        "Peer sent hash ({}) for block header we do not have",
-                        block_body_response.hash.to_hex()
-                    ))
-                })?;
-
-            let current_height = header.height();
-            let header_hash = *header.hash();
-            let timestamp = header.timestamp();
-
-            if header.header().prev_hash != prev_hash {
-                return Err(BlockSyncError::BlockWithoutParent {
-                    expected: prev_hash.to_hex(),
-                    got: header.header().prev_hash.to_hex(),
-                });
-            }
-
-            prev_hash = header_hash;
-
-            let body = block_body_response
-                .body
-                .map(AggregateBody::try_from)
-                .ok_or_else(|| BlockSyncError::InvalidBlockBody("Peer sent empty block".to_string()))?
-                .map_err(BlockSyncError::InvalidBlockBody)?;
-
-            debug!(
-                target: LOG_TARGET,
-                "Validating block body #{} (PoW = {}, {}, latency: {:.2?})",
-                current_height,
-                header.header().pow_algo(),
-                body.to_counts_string(),
-                latency
-            );
-
-            let timer = Instant::now();
-            let (header, header_accum_data) = header.into_parts();
-            let block = Block::new(header, body);
-
-            // Validate the block inside a tokio task
-            let task_block = block.clone();
-            let db = self.db.inner().clone();
-            let validator = self.block_validator.clone();
-            let res = task::spawn_blocking(move || {
-                let txn = db.db_read_access()?;
-                let smt = db.smt().clone();
-                validator.validate_body(&*txn, &task_block, smt)
-            })
-            .await?;
-
-            let block = match res {
-                Ok(block) => block,
-                Err(err @ ValidationError::BadBlockFound { .. }) | Err(err @ ValidationError::FatalStorageError(_)) => {
-                    return Err(err.into());
-                },
-                Err(err) => {
-                    // Add to bad blocks
-                    if let Err(err) = self
-                        .db
-                        .write_transaction()
-                        .delete_orphan(header_hash)
-                        .insert_bad_block(header_hash, current_height, err.to_string())
-                        .commit()
-                        .await
-                    {
-                        error!(target: LOG_TARGET, "Failed to insert bad block: {}", err);
-                    }
-                    return Err(err.into());
-                },
+        let current_block = if helpers.is_empty() {
+            let request = SyncBlocksRequest {
+                start_hash: best_full_block_hash.to_vec(),
+                // To the tip!
+                end_hash: tip_hash.to_vec(),
             };
 
-            let block = ChainBlock::try_construct(Arc::new(block), header_accum_data)
-                .map(Arc::new)
-                .ok_or(BlockSyncError::FailedToConstructChainBlock)?;
-
-            debug!(
-                target: LOG_TARGET,
-                "Validated in {:.0?}. Storing block body #{} (PoW = {}, {})",
-                timer.elapsed(),
-                block.header().height,
-                block.header().pow_algo(),
-                block.block().body.to_counts_string(),
-            );
-            trace!(
-                target: LOG_TARGET,
-                "{}",block
-            );
-
-            let timer = Instant::now();
-            self.db
-                .write_transaction()
-                .delete_orphan(header_hash)
-                .insert_tip_block_body(block.clone(), self.db.inner().smt())
-                .set_best_block(
-                    block.height(),
-                    header_hash,
-                    block.accumulated_data().total_accumulated_difficulty,
-                    block.header().prev_hash,
-                    timestamp,
-                )
-                .commit()
-                .await?;
-
-            // Average time between receiving blocks from the peer - used to detect a slow sync peer
-            let last_avg_latency = avg_latency.calculate_average_with_min_samples(5);
-            if let Some(latency) = last_avg_latency {
-                sync_peer.set_latency(latency);
-            }
-            // Includes time to add block to database, used to show blocks/s on status line
-            sync_peer.add_sample(last_sync_timer.elapsed());
-            self.hooks
-                .call_on_progress_block_hooks(block.clone(), tip_height, &sync_peer);
-
-            debug!(
-                target: LOG_TARGET,
-                "Block body #{} added in {:.0?}, Tot_acc_diff {}, Monero {}, SHA3 {}, latency: {:.2?}",
-                block.height(),
-                timer.elapsed(),
-                block
-                    .accumulated_data()
-                    .total_accumulated_difficulty,
-                block.accumulated_data().accumulated_randomx_difficulty,
-                block.accumulated_data().accumulated_sha3x_difficulty,
-                latency
-            );
-            if let Some(avg_latency) = last_avg_latency {
-                if avg_latency > max_latency {
-                    return Err(BlockSyncError::MaxLatencyExceeded {
-                        peer: sync_peer.node_id().clone(),
-                        latency: avg_latency,
-                        max_latency,
-                    });
+            let mut block_stream = client.sync_blocks(request).await?;
+            let mut prev_hash = best_full_block_hash;
+            let mut current_block = None;
+            let mut last_sync_timer = Instant::now();
+            let mut avg_latency = RollingAverageTime::new(20);
+            while let Some(block_result) = block_stream.next().await {
+                let latency = last_sync_timer.elapsed();
+                avg_latency.add_sample(latency);
+                let block = self
+                    .validate_and_commit_block(block_result?, &mut prev_hash, latency)
+                    .await?;
+
+                // Average time between receiving blocks from the peer - used to detect a slow sync peer
+                let last_avg_latency = avg_latency.calculate_average_with_min_samples(5);
+                if let Some(latency) = last_avg_latency {
+                    sync_peer.set_latency(latency);
+                }
+                // Includes time to add block to database, used to show blocks/s on status line
+                sync_peer.add_sample(last_sync_timer.elapsed());
+                self.hooks
+                    .call_on_progress_block_hooks(block.clone(), tip_height, &sync_peer);
+
+                if let Some(avg_latency) = last_avg_latency {
+                    if avg_latency > max_latency {
+                        return Err(BlockSyncError::MaxLatencyExceeded {
+                            peer: sync_peer.node_id().clone(),
+                            latency: avg_latency,
+                            max_latency,
+                        });
+                    }
                 }
-            }
 
-            current_block = Some(block);
-            last_sync_timer = Instant::now();
-        }
+                current_block = Some(block);
+                last_sync_timer = Instant::now();
+            }
+            current_block
+        } else {
+            self.synchronize_blocks_concurrently(&sync_peer, client, helpers, best_height, tip_height, max_latency)
+                .await?
+        };
 
         let accumulated_difficulty = self.db.get_chain_metadata().await?.accumulated_difficulty();
         if accumulated_difficulty < sync_peer.claimed_chain_metadata().accumulated_difficulty() {
@@ -433,6 +378,287 @@ impl<'a, B: BlockchainBackend + 'static> BlockSynchronizer<'a, B> {
         Ok(())
     }
 
+    /// Downloads the block bodies up to `tip_height` in batches from the sync peer and its helpers at the same time.
+    /// Each peer downloads one batch at a time, and the batches are validated and committed in height order as they
+    /// become available. A peer that is too slow to deliver its batch is no longer given work, and its batch is
+    /// assigned to the next peer that is free. Errors of the sync peer itself end the sync round, while a helper that
+    /// misbehaves is dealt with here and the sync continues without it.
+    #[allow(clippy::too_many_lines)]
+    async fn synchronize_blocks_concurrently(
+        &mut self,
+        sync_peer: &SyncPeer,
+        client: rpc::BaseNodeSyncRpcClient,
+        helpers: Vec<(SyncPeer, rpc::BaseNodeSyncRpcClient)>,
+        best_height: u64,
+        tip_height: u64,
+        max_latency: Duration,
+    ) -> Result<Option<Arc<ChainBlock>>, BlockSyncError> {
+        let best_full_block_hash = self.db.fetch_chain_header(best_height).await?.accumulated_data().hash;
+        let num_peers = helpers.len() + 1;
+        let mut scheduler =
+            BatchScheduler::new(best_height + 1, tip_height, self.config.block_batch_size, num_peers * 2);
+        let mut idle_peers = vec![(sync_peer.clone(), client)];
+        idle_peers.extend(helpers);
+        let mut dropped_peers = HashSet::new();
+        let mut downloads = FuturesUnordered::new();
+        let mut latency_error = None;
+        let mut prev_hash = best_full_block_hash;
+        let mut current_block = None;
+
+        loop {
+            // Hand out the next batches to the peers that are not downloading. The sync peer is expected to supply
+            // all the blocks up to the tip, as it would when syncing on its own.
+            let mut i = 0;
+            while i < idle_peers.len() {
+                let peer_best_height = if idle_peers[i].0.node_id() == sync_peer.node_id() {
+                    u64::MAX
+                } else {
+                    idle_peers[i].0.claimed_chain_metadata().best_block_height()
+                };
+                match scheduler.assign(peer_best_height) {
+                    Some(batch) => {
+                        let (peer, client) = idle_peers.swap_remove(i);
+                        let start_hash = *self.db.fetch_chain_header(batch.start - 1).await?.hash();
+                        let end_hash = *self.db.fetch_chain_header(batch.end).await?.hash();
+                        trace!(
+                            target: LOG_TARGET,
+                            "Downloading blocks #{} to #{} from peer `{}`",
+                            batch.start,
+                            batch.end,
+                            peer.node_id()
+                        );
+                        downloads.push(download_block_batch(
+                            peer,
+                            client,
+                            batch,
+                            start_hash,
+                            end_hash,
+                            self.config.batch_timeout,
+                            max_latency,
+                        ));
+                    },
+                    None => i += 1,
+                }
+            }
+
+            // Validate and commit the downloaded batches that are next in line
+            while let Some((batch, (mut peer, blocks))) = scheduler.next_ready() {
+                let latency = peer.latency().unwrap_or_default();
+                for (height, block_body) in (batch.start..=batch.end).zip(blocks) {
+                    let timer = Instant::now();
+                    match self
+                        .validate_and_commit_block(block_body, &mut prev_hash, latency)
+                        .await
+                    {
+                        Ok(block) => {
+                            peer.add_sample(timer.elapsed());
+                            self.hooks
+                                .call_on_progress_block_hooks(block.clone(), tip_height, &peer);
+                            current_block = Some(block);
+                        },
+                        Err(err) if peer.node_id() != sync_peer.node_id() && err.get_ban_reason().is_some() => {
+                            warn!(
+                                target: LOG_TARGET,
+                                "Helper sync peer `{}` sent an invalid block #{}: {}",
+                                peer.node_id(),
+                                height,
+                                err
+                            );
+                            self.handle_sync_peer_error(peer.node_id(), &err).await;
+                            idle_peers.retain(|(p, _)| p.node_id() != peer.node_id());
+                            dropped_peers.insert(peer.node_id().clone());
+                            scheduler.requeue_from(batch, height);
+                            break;
+                        },
+                        Err(err) => return Err(err),
+                    }
+                }
+            }
+
+            if scheduler.is_finished() {
+                break;
+            }
+
+            // Wait for the next batch to be downloaded
+            let (peer, client, batch, result) = match downloads.next().await {
+                Some(download) => download,
+                None => {
+                    return Err(latency_error.unwrap_or_else(|| {
+                        BlockSyncError::NoMoreSyncPeers(
+                            "No sync peer is able to supply the remaining blocks".to_string(),
+                        )
+                    }));
+                },
+            };
+            if dropped_peers.contains(peer.node_id()) {
+                scheduler.requeue(batch);
+                continue;
+            }
+            match result {
+                Ok(blocks) => {
+                    scheduler.complete(batch, (peer.clone(), blocks));
+                    idle_peers.push((peer, client));
+                },
+                Err(err @ BlockSyncError::MaxLatencyExceeded { .. }) => {
+                    warn!(
+                        target: LOG_TARGET,
+                        "{}. Blocks #{} to #{} are assigned to another sync peer", err, batch.start, batch.end
+                    );
+                    scheduler.requeue(batch);
+                    self.peer_ban_manager.record_stall(peer.node_id());
+                    latency_error = Some(err);
+                },
+                Err(err) => {
+                    if peer.node_id() == sync_peer.node_id() {
+                        return Err(err);
+                    }
+                    warn!(
+                        target: LOG_TARGET,
+                        "Helper sync peer `{}` failed to supply blocks #{} to #{}: {}",
+                        peer.node_id(),
+                        batch.start,
+                        batch.end,
+                        err
+                    );
+                    scheduler.requeue(batch);
+                    self.handle_sync_peer_error(peer.node_id(), &err).await;
+                },
+            }
+        }
+
+        Ok(current_block)
+    }
+
+    /// Validates the block body against its header, which must follow `prev_hash`, and commits it as the new tip
+    #[allow(clippy::too_many_lines)]
+    async fn validate_and_commit_block(
+        &self,
+        block_body_response: BlockBodyResponse,
+        prev_hash: &mut HashOutput,
+        latency: Duration,
+    ) -> Result<Arc<ChainBlock>, BlockSyncError> {
+        let header = self
+            .db
+            .fetch_chain_header_by_block_hash(block_body_response.hash.clone().try_into()?)
+            .await?
+            .ok_or_else(|| {
+                BlockSyncError::UnknownHeaderHash(format!(
+                    "Peer sent hash ({}) for block header we do not have",
+                    block_body_response.hash.to_hex()
+                ))
+            })?;
+
+        let current_height = header.height();
+        let header_hash = *header.hash();
+        let timestamp = header.timestamp();
+
+        if header.header().prev_hash != *prev_hash {
+            return Err(BlockSyncError::BlockWithoutParent {
+                expected: prev_hash.to_hex(),
+                got: header.header().prev_hash.to_hex(),
+            });
+        }
+
+        let body = block_body_response
+            .body
+            .map(AggregateBody::try_from)
+            .ok_or_else(|| BlockSyncError::InvalidBlockBody("Peer sent empty block".to_string()))?
+            .map_err(BlockSyncError::InvalidBlockBody)?;
+
+        debug!(
+            target: LOG_TARGET,
+            "Validating block body #{} (PoW = {}, {}, latency: {:.2?})",
+            current_height,
+            header.header().pow_algo(),
+            body.to_counts_string(),
+            latency
+        );
+
+        let timer = Instant::now();
+        let (header, header_accum_data) = header.into_parts();
+        let block = Block::new(header, body);
+
+        // Validate the block inside a tokio task
+        let task_block = block.clone();
+        let db = self.db.inner().clone();
+        let validator = self.block_validator.clone();
+        let res = task::spawn_blocking(move || {
+            let txn = db.db_read_access()?;
+            let smt = db.smt().clone();
+            validator.validate_body(&*txn, &task_block, smt)
+        })
+        .await?;
+
+        let block = match res {
+            Ok(block) => block,
+            Err(err @ ValidationError::BadBlockFound { .. }) | Err(err @ ValidationError::FatalStorageError(_)) => {
+                return Err(err.into());
+            },
+            Err(err) => {
+                // Add to bad blocks
+                if let Err(err) = self
+                    .db
+                    .write_transaction()
+                    .delete_orphan(header_hash)
+                    .insert_bad_block(header_hash, current_height, err.to_string())
+                    .commit()
+                    .await
+                {
+                    error!(target: LOG_TARGET, "Failed to insert bad block: {}", err);
+                }
+                return Err(err.into());
+            },
+        };
+
+        let block = ChainBlock::try_construct(Arc::new(block), header_accum_data)
+            .map(Arc::new)
+            .ok_or(BlockSyncError::FailedToConstructChainBlock)?;
+
+        debug!(
+            target: LOG_TARGET,
+            "Validated in {:.0?}. Storing block body #{} (PoW = {}, {})",
+            timer.elapsed(),
+            block.header().height,
+            block.header().pow_algo(),
+            block.block().body.to_counts_string(),
+        );
+        trace!(
+            target: LOG_TARGET,
+            "{}",block
+        );
+
+        let timer = Instant::now();
+        self.db
+            .write_transaction()
+            .delete_orphan(header_hash)
+            .insert_tip_block_body(block.clone(), self.db.inner().smt())
+            .set_best_block(
+                block.height(),
+                header_hash,
+                block.accumulated_data().total_accumulated_difficulty,
+                block.header().prev_hash,
+                timestamp,
+            )
+            .commit()
+            .await?;
+        *prev_hash = header_hash;
+
+        debug!(
+            target: LOG_TARGET,
+            "Block body #{} added in {:.0?}, Tot_acc_diff {}, Monero {}, SHA3 {}, latency: {:.2?}",
+            block.height(),
+            timer.elapsed(),
+            block
+                .accumulated_data()
+                .total_accumulated_difficulty,
+            block.accumulated_data().accumulated_randomx_difficulty,
+            block.accumulated_data().accumulated_sha3x_difficulty,
+            latency
+        );
+
+        Ok(block)
+    }
+
     // Sync peers are also removed from the list of sync peers if the ban duration is longer than the short ban period.
     fn remove_sync_peer(&mut self, node_id: &NodeId) {
         if let Some(pos) = self.sync_peers.iter().position(|p| p.node_id() == node_id) {
@@ -445,3 +671,79 @@ impl<'a, B: BlockchainBackend + 'static> BlockSynchronizer<'a, B> {
         self.sync_peers.iter().position(|p| p.node_id() == node_id)
     }
 }
+
+/// Downloads the bodies of a batch of blocks, giving up once the batch takes longer than `batch_timeout`. The peer and
+/// its client are handed back with the result so that the peer can be assigned its next batch.
+async fn download_block_batch(
+    mut sync_peer: SyncPeer,
+    mut client: rpc::BaseNodeSyncRpcClient,
+    batch: HeightBatch,
+    start_hash: HashOutput,
+    end_hash: HashOutput,
+    batch_timeout: Duration,
+    max_latency: Duration,
+) -> (
+    SyncPeer,
+    rpc::BaseNodeSyncRpcClient,
+    HeightBatch,
+    Result<Vec<BlockBodyResponse>, BlockSyncError>,
+) {
+    let timer = Instant::now();
+    let result = time::timeout(
+        batch_timeout,
+        fetch_block_batch(&mut sync_peer, &mut client, batch, start_hash, end_hash, max_latency),
+    )
+    .await;
+    let result = result.unwrap_or_else(|_| {
+        Err(BlockSyncError::MaxLatencyExceeded {
+            peer: sync_peer.node_id().clone(),
+            latency: timer.elapsed(),
+            max_latency: batch_timeout,
+        })
+    });
+    (sync_peer, client, batch, result)
+}
+
+async fn fetch_block_batch(
+    sync_peer: &mut SyncPeer,
+    client: &mut rpc::BaseNodeSyncRpcClient,
+    batch: HeightBatch,
+    start_hash: HashOutput,
+    end_hash: HashOutput,
+    max_latency: Duration,
+) -> Result<Vec<BlockBodyResponse>, BlockSyncError> {
+    let request = SyncBlocksRequest {
+        start_hash: start_hash.to_vec(),
+        end_hash: end_hash.to_vec(),
+    };
+    let mut block_stream = client.sync_blocks(request).await?;
+    let mut blocks = Vec::new();
+    let mut last_sync_timer = Instant::now();
+    let mut avg_latency = RollingAverageTime::new(20);
+    while let Some(block_result) = block_stream.next().await {
+        let latency = last_sync_timer.elapsed();
+        avg_latency.add_sample(latency);
+        blocks.push(block_result?);
+        if let Some(avg_latency) = avg_latency.calculate_average_with_min_samples(5) {
+            sync_peer.set_latency(avg_latency);
+            if avg_latency > max_latency {
+                return Err(BlockSyncError::MaxLatencyExceeded {
+                    peer: sync_peer.node_id().clone(),
+                    latency: avg_latency,
+                    max_latency,
+                });
+            }
+        }
+        last_sync_timer = Instant::now();
+    }
+    if blocks.len() as u64 != batch.num_heights() {
+        return Err(BlockSyncError::PeerDidNotSupplyAllClaimedBlocks(format!(
+            "Sent {} of the {} blocks from #{} to #{}",
+            blocks.len(),
+            batch.num_heights(),
+            batch.start,
+            batch.end
+        )));
+    }
+    Ok(blocks)
+}
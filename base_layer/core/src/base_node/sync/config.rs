@@ -52,6 +52,22 @@ pub struct BlockchainSyncConfig {
     /// sync.
     #[serde(with = "serializers::seconds")]
     pub rpc_deadline: Duration,
+    /// The maximum number of sync peers that header batches and block bodies are downloaded from at the same time.
+    /// Batches are still validated in height order, so a slow peer only holds up the batch it was assigned. Set to 1
+    /// to sync from a single peer.
+    pub max_concurrent_sync_peers: usize,
+    /// The number of headers requested from a sync peer at a time when syncing from several peers
+    pub header_batch_size: u64,
+    /// The number of block bodies requested from a sync peer at a time when syncing from several peers
+    pub block_batch_size: u64,
+    /// If a sync peer has not delivered its batch of headers or blocks within this time, the batch is assigned to
+    /// another sync peer
+    #[serde(with = "serializers::seconds")]
+    pub batch_timeout: Duration,
+    /// A peer that sent invalid headers, blocks or horizon state is not selected for sync for this period. This also
+    /// applies to peers in `forced_sync_peers`, unless all of them are blacklisted.
+    #[serde(with = "serializers::seconds")]
+    pub sync_peer_blacklist_period: Duration,
 }
 
 impl Default for BlockchainSyncConfig {
@@ -64,6 +80,11 @@ impl Default for BlockchainSyncConfig {
             forced_sync_peers: Default::default(),
             validation_concurrency: 6,
             rpc_deadline: Duration::from_secs(240), // Syncing many full blocks over tor require this
+            max_concurrent_sync_peers: 4,
+            header_batch_size: 1000,
+            block_batch_size: 50,
+            batch_timeout: Duration::from_secs(120),
+            sync_peer_blacklist_period: Duration::from_secs(60 * 60), // 1 hour
         }
     }
 }
@@ -97,6 +97,8 @@ pub enum BlockHeaderSyncError {
     },
     #[error("All sync peers exceeded max allowed latency")]
     AllSyncPeersExceedLatency,
+    #[error("Peer sent headers #{start} to #{end} that are not on the chain being synced")]
+    NotOnSyncChain { start: u64, end: u64 },
 }
 
 impl BlockHeaderSyncError {
@@ -109,7 +111,9 @@ impl BlockHeaderSyncError {
             BlockHeaderSyncError::AllSyncPeersExceedLatency |
             BlockHeaderSyncError::ConnectivityError(_) |
             BlockHeaderSyncError::NotInSync |
-            BlockHeaderSyncError::PeerNotFound => None,
+            BlockHeaderSyncError::PeerNotFound |
+            // The peer may be following another chain
+            BlockHeaderSyncError::NotOnSyncChain { .. } => None,
             BlockHeaderSyncError::ChainStorageError(e) => e.get_ban_reason(),
 
             // short ban
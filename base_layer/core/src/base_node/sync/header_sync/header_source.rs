@@ -0,0 +1,320 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    collections::{HashMap, VecDeque},
+    convert::TryFrom,
+    time::{Duration, Instant},
+};
+
+use futures::{future::BoxFuture, stream::FuturesUnordered, FutureExt, StreamExt};
+use log::*;
+use tari_common_types::types::HashOutput;
+use tari_comms::{peer_manager::NodeId, protocol::rpc::Streaming};
+use tari_utilities::hex::Hex;
+use tokio::time;
+
+use super::BlockHeaderSyncError;
+use crate::{
+    base_node::sync::{
+        ban::PeerBanManager,
+        batch_scheduler::{BatchScheduler, HeightBatch},
+        rpc,
+        BlockchainSyncConfig,
+        SyncPeer,
+    },
+    blocks::BlockHeader,
+    common::rolling_avg::RollingAverageTime,
+    proto::{base_node::SyncHeadersRequest, core::BlockHeader as ProtoBlockHeader},
+};
+
+const LOG_TARGET: &str = "c::bn::header_sync";
+
+type BatchDownload = (
+    SyncPeer,
+    rpc::BaseNodeSyncRpcClient,
+    HeightBatch,
+    Result<Vec<BlockHeader>, BlockHeaderSyncError>,
+);
+
+/// The headers of the sync peer's chain that follow the last valid header, in height order
+pub enum HeaderSource {
+    /// Streamed from the sync peer alone
+    Stream(Streaming<ProtoBlockHeader>),
+    /// Downloaded in batches from the sync peer and other sync peers at the same time
+    Concurrent(Box<ConcurrentHeaderDownload>),
+}
+
+impl HeaderSource {
+    pub async fn next_header(&mut self) -> Result<Option<BlockHeader>, BlockHeaderSyncError> {
+        match self {
+            HeaderSource::Stream(stream) => match stream.next().await {
+                Some(header) => Ok(Some(
+                    BlockHeader::try_from(header?).map_err(BlockHeaderSyncError::ReceivedInvalidHeader)?,
+                )),
+                None => Ok(None),
+            },
+            HeaderSource::Concurrent(download) => download.next_header().await,
+        }
+    }
+}
+
+/// Downloads the headers of the sync peer's chain in batches from several sync peers at the same time. The hashes at
+/// the batch boundaries are fetched from the sync peer, and a batch is only accepted if it links those hashes, so every
+/// header handed out is on the sync peer's chain and an invalid header is the sync peer's fault. Other peers that fail,
+/// are too slow or follow a different chain are no longer given work and their batches are assigned to the next peer
+/// that is free, while the errors of the sync peer itself are returned.
+pub struct ConcurrentHeaderDownload {
+    config: BlockchainSyncConfig,
+    sync_peer: NodeId,
+    /// A separate RPC session with the sync peer, so that boundary hashes can be fetched while it downloads a batch
+    boundary_client: rpc::BaseNodeSyncRpcClient,
+    boundary_hashes: HashMap<u64, HashOutput>,
+    scheduler: BatchScheduler<Vec<BlockHeader>>,
+    idle_peers: Vec<(SyncPeer, rpc::BaseNodeSyncRpcClient)>,
+    downloads: FuturesUnordered<BoxFuture<'static, BatchDownload>>,
+    ready: VecDeque<BlockHeader>,
+    peer_ban_manager: PeerBanManager,
+    max_latency: Duration,
+    latency_error: Option<BlockHeaderSyncError>,
+}
+
+impl ConcurrentHeaderDownload {
+    /// Downloads the headers after `start_header` up to the best block claimed by the sync peer
+    pub fn new(
+        config: BlockchainSyncConfig,
+        peer_ban_manager: PeerBanManager,
+        sync_peer: (SyncPeer, rpc::BaseNodeSyncRpcClient),
+        helpers: Vec<(SyncPeer, rpc::BaseNodeSyncRpcClient)>,
+        boundary_client: rpc::BaseNodeSyncRpcClient,
+        start_header: (u64, HashOutput),
+        max_latency: Duration,
+    ) -> Self {
+        let (start_height, start_hash) = start_header;
+        let scheduler = BatchScheduler::new(
+            start_height + 1,
+            sync_peer.0.claimed_chain_metadata().best_block_height(),
+            config.header_batch_size,
+            (helpers.len() + 1) * 2,
+        );
+        let sync_peer_node_id = sync_peer.0.node_id().clone();
+        let mut idle_peers = vec![sync_peer];
+        idle_peers.extend(helpers);
+        Self {
+            config,
+            sync_peer: sync_peer_node_id,
+            boundary_client,
+            boundary_hashes: HashMap::from([(start_height, start_hash)]),
+            scheduler,
+            idle_peers,
+            downloads: FuturesUnordered::new(),
+            ready: VecDeque::new(),
+            peer_ban_manager,
+            max_latency,
+            latency_error: None,
+        }
+    }
+
+    pub async fn next_header(&mut self) -> Result<Option<BlockHeader>, BlockHeaderSyncError> {
+        loop {
+            if let Some(header) = self.ready.pop_front() {
+                return Ok(Some(header));
+            }
+            if let Some((_, headers)) = self.scheduler.next_ready() {
+                self.ready.extend(headers);
+                continue;
+            }
+            if self.scheduler.is_finished() {
+                return Ok(None);
+            }
+
+            self.assign_batches().await?;
+            let (peer, client, batch, result) = match self.downloads.next().await {
+                Some(download) => download,
+                None => {
+                    return Err(self.latency_error.take().unwrap_or_else(|| {
+                        BlockHeaderSyncError::NoMoreSyncPeers(
+                            "No sync peer is able to supply the remaining headers".to_string(),
+                        )
+                    }));
+                },
+            };
+            match result {
+                Ok(headers) => {
+                    self.scheduler.complete(batch, headers);
+                    self.idle_peers.push((peer, client));
+                },
+                Err(err @ BlockHeaderSyncError::MaxLatencyExceeded { .. }) => {
+                    warn!(
+                        target: LOG_TARGET,
+                        "{}. Headers #{} to #{} are assigned to another sync peer", err, batch.start, batch.end
+                    );
+                    self.scheduler.requeue(batch);
+                    self.peer_ban_manager.record_stall(peer.node_id());
+                    self.latency_error = Some(err);
+                },
+                Err(err) => {
+                    self.scheduler.requeue(batch);
+                    if *peer.node_id() == self.sync_peer {
+                        return Err(err);
+                    }
+                    warn!(
+                        target: LOG_TARGET,
+                        "Helper sync peer `{}` failed to supply headers #{} to #{}: {}",
+                        peer.node_id(),
+                        batch.start,
+                        batch.end,
+                        err
+                    );
+                    if let Some(reason) = err.get_ban_reason() {
+                        self.peer_ban_manager.ban_sync_peer(peer.node_id(), reason).await;
+                    }
+                },
+            }
+        }
+    }
+
+    /// Hands out the next batches to the peers that are not downloading. The sync peer is expected to supply all the
+    /// headers, as it would when syncing on its own.
+    async fn assign_batches(&mut self) -> Result<(), BlockHeaderSyncError> {
+        let mut i = 0;
+        while i < self.idle_peers.len() {
+            let peer_best_height = if *self.idle_peers[i].0.node_id() == self.sync_peer {
+                u64::MAX
+            } else {
+                self.idle_peers[i].0.claimed_chain_metadata().best_block_height()
+            };
+            let batch = match self.scheduler.assign(peer_best_height) {
+                Some(batch) => batch,
+                None => {
+                    i += 1;
+                    continue;
+                },
+            };
+            let start_hash = self.boundary_hash(batch.start - 1).await?;
+            let end_hash = self.boundary_hash(batch.end).await?;
+            let (peer, client) = self.idle_peers.swap_remove(i);
+            trace!(
+                target: LOG_TARGET,
+                "Downloading headers #{} to #{} from peer `{}`",
+                batch.start,
+                batch.end,
+                peer.node_id()
+            );
+            self.downloads.push(
+                download_header_batch(
+                    peer,
+                    client,
+                    batch,
+                    (start_hash, end_hash),
+                    self.config.batch_timeout,
+                    self.max_latency,
+                )
+                .boxed(),
+            );
+        }
+        Ok(())
+    }
+
+    /// The hash of the sync peer's header at `height`
+    async fn boundary_hash(&mut self, height: u64) -> Result<HashOutput, BlockHeaderSyncError> {
+        if let Some(hash) = self.boundary_hashes.get(&height) {
+            return Ok(*hash);
+        }
+        let header = self.boundary_client.get_header_by_height(height).await?;
+        let header = BlockHeader::try_from(header).map_err(BlockHeaderSyncError::ReceivedInvalidHeader)?;
+        if header.height != height {
+            return Err(BlockHeaderSyncError::InvalidBlockHeight {
+                expected: height,
+                actual: header.height,
+            });
+        }
+        let hash = header.hash();
+        self.boundary_hashes.insert(height, hash);
+        Ok(hash)
+    }
+}
+
+/// Downloads a batch of headers that must start after and end on the given boundary hashes, giving up once the batch
+/// takes longer than `batch_timeout`. The peer and its client are handed back with the result so that the peer can be
+/// assigned its next batch.
+async fn download_header_batch(
+    mut sync_peer: SyncPeer,
+    mut client: rpc::BaseNodeSyncRpcClient,
+    batch: HeightBatch,
+    boundary_hashes: (HashOutput, HashOutput),
+    batch_timeout: Duration,
+    max_latency: Duration,
+) -> BatchDownload {
+    let timer = Instant::now();
+    let result = time::timeout(
+        batch_timeout,
+        fetch_header_batch(&mut sync_peer, &mut client, batch, boundary_hashes, max_latency),
+    )
+    .await;
+    let result = result.unwrap_or_else(|_| {
+        Err(BlockHeaderSyncError::MaxLatencyExceeded {
+            peer: sync_peer.node_id().clone(),
+            latency: timer.elapsed(),
+            max_latency: batch_timeout,
+        })
+    });
+    (sync_peer, client, batch, result)
+}
+
+async fn fetch_header_batch(
+    sync_peer: &mut SyncPeer,
+    client: &mut rpc::BaseNodeSyncRpcClient,
+    batch: HeightBatch,
+    (start_hash, end_hash): (HashOutput, HashOutput),
+    max_latency: Duration,
+) -> Result<Vec<BlockHeader>, BlockHeaderSyncError> {
+    let request = SyncHeadersRequest {
+        start_hash: start_hash.to_vec(),
+        count: batch.num_heights(),
+    };
+    let mut header_stream = client.sync_headers(request).await?;
+    let mut headers = Vec::new();
+    let mut prev_hash = start_hash;
+    let mut last_sync_timer = Instant::now();
+    let mut avg_latency = RollingAverageTime::new(20);
+    while let Some(header) = header_stream.next().await {
+        let latency = last_sync_timer.elapsed();
+        avg_latency.add_sample(latency);
+        let header = BlockHeader::try_from(header?).map_err(BlockHeaderSyncError::ReceivedInvalidHeader)?;
+        let expected_height = batch.start + headers.len() as u64;
+        if header.height != expected_height || expected_height > batch.end {
+            return Err(BlockHeaderSyncError::InvalidBlockHeight {
+                expected: expected_height,
+                actual: header.height,
+            });
+        }
+        if header.prev_hash != prev_hash {
+            return Err(BlockHeaderSyncError::ChainLinkBroken {
+                height: header.height,
+                actual: header.prev_hash.to_hex(),
+                expected: prev_hash.to_hex(),
+            });
+        }
+        prev_hash = header.hash();
+        headers.push(header);
+        if let Some(avg_latency) = avg_latency.calculate_average_with_min_samples(5) {
+            sync_peer.set_latency(avg_latency);
+            if avg_latency > max_latency {
+                return Err(BlockHeaderSyncError::MaxLatencyExceeded {
+                    peer: sync_peer.node_id().clone(),
+                    latency: avg_latency,
+                    max_latency,
+                });
+            }
+        }
+        last_sync_timer = Instant::now();
+    }
+    if prev_hash != end_hash {
+        return Err(BlockHeaderSyncError::NotOnSyncChain {
+            start: batch.start,
+            end: batch.end,
+        });
+    }
+    Ok(headers)
+}
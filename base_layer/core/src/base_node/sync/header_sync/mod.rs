@@ -25,5 +25,7 @@ pub use error::BlockHeaderSyncError;
 
 mod validator;
 
+mod header_source;
+
 mod synchronizer;
 pub use synchronizer::{AttemptSyncResult, HeaderSyncStatus, HeaderSynchronizer};
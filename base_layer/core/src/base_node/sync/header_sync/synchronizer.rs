@@ -25,19 +25,23 @@ use std::{
     time::{Duration, Instant},
 };
 
-use futures::StreamExt;
 use log::*;
 use primitive_types::U256;
 use tari_common_types::{chain_metadata::ChainMetadata, types::HashOutput};
 use tari_comms::{
     connectivity::ConnectivityRequester,
-    peer_manager::NodeId,
+    peer_manager::{NodeId, PeerLatencyTracker, SyncPeerQualityTracker},
     protocol::rpc::{RpcClient, RpcError},
     PeerConnection,
 };
 use tari_utilities::hex::Hex;
+use tracing::instrument;
 
-use super::{validator::BlockHeaderSyncValidator, BlockHeaderSyncError};
+use super::{
+    header_source::{ConcurrentHeaderDownload, HeaderSource},
+    validator::BlockHeaderSyncValidator,
+    BlockHeaderSyncError,
+};
 use crate::{
     base_node::sync::{
         ban::PeerBanManager,
@@ -49,7 +53,7 @@ use crate::{
     },
     blocks::{BlockHeader, ChainBlock, ChainHeader},
     chain_storage::{async_db::AsyncBlockchainDb, BlockchainBackend, ChainStorageError},
-    common::{rolling_avg::RollingAverageTime, BanPeriod},
+    common::rolling_avg::RollingAverageTime,
     consensus::ConsensusManager,
     proof_of_work::randomx_factory::RandomXFactory,
     proto::{
@@ -71,6 +75,8 @@ pub struct HeaderSynchronizer<'a, B> {
     hooks: Hooks,
     local_cached_metadata: &'a ChainMetadata,
     peer_ban_manager: PeerBanManager,
+    latency_tracker: PeerLatencyTracker,
+    sync_quality: SyncPeerQualityTracker,
 }
 
 impl<'a, B: BlockchainBackend + 'static> HeaderSynchronizer<'a, B> {
@@ -82,8 +88,10 @@ impl<'a, B: BlockchainBackend + 'static> HeaderSynchronizer<'a, B> {
         sync_peers: &'a mut Vec<SyncPeer>,
         randomx_factory: RandomXFactory,
         local_metadata: &'a ChainMetadata,
+        latency_tracker: PeerLatencyTracker,
+        sync_quality: SyncPeerQualityTracker,
     ) -> Self {
-        let peer_ban_manager = PeerBanManager::new(config.clone(), connectivity.clone());
+        let peer_ban_manager = PeerBanManager::new(config.clone(), connectivity.clone(), sync_quality.clone());
         Self {
             config,
             header_validator: BlockHeaderSyncValidator::new(db.clone(), consensus_rules, randomx_factory),
@@ -93,6 +101,8 @@ impl<'a, B: BlockchainBackend + 'static> HeaderSynchronizer<'a, B> {
             hooks: Default::default(),
             local_cached_metadata: local_metadata,
             peer_ban_manager,
+            latency_tracker,
+            sync_quality,
         }
     }
 
@@ -111,6 +121,7 @@ impl<'a, B: BlockchainBackend + 'static> HeaderSynchronizer<'a, B> {
         self.hooks.add_on_rewind_hook(hook);
     }
 
+    #[instrument(name = "header_sync::synchronize", level = "debug", skip_all, err)]
     pub async fn synchronize(&mut self) -> Result<(SyncPeer, AttemptSyncResult), BlockHeaderSyncError> {
         debug!(target: LOG_TARGET, "Starting header sync.",);
 
@@ -154,20 +165,18 @@ impl<'a, B: BlockchainBackend + 'static> HeaderSynchronizer<'a, B> {
         let mut latency_counter = 0usize;
         for node_id in sync_peer_node_ids {
             match self.connect_and_attempt_sync(&node_id, max_latency).await {
-                Ok((peer, sync_result)) => return Ok((peer, sync_result)),
+                Ok((peer, sync_result)) => {
+                    self.sync_quality.record_sync_completed(&node_id);
+                    return Ok((peer, sync_result));
+                },
                 Err(err) => {
                     let ban_reason = BlockHeaderSyncError::get_ban_reason(&err);
                     if let Some(reason) = ban_reason {
                         warn!(target: LOG_TARGET, "{}", err);
-                        let duration = match reason.ban_duration {
-                            BanPeriod::Short => self.config.short_ban_period,
-                            BanPeriod::Long => self.config.ban_period,
-                        };
-                        self.peer_ban_manager
-                            .ban_peer_if_required(&node_id, reason.reason, duration)
-                            .await;
+                        self.peer_ban_manager.ban_sync_peer(&node_id, reason).await;
                     }
                     if let BlockHeaderSyncError::MaxLatencyExceeded { .. } = err {
+                        self.peer_ban_manager.record_stall(&node_id);
                         latency_counter += 1;
                     } else {
                         self.remove_sync_peer(&node_id);
@@ -196,18 +205,11 @@ impl<'a, B: BlockchainBackend + 'static> HeaderSynchronizer<'a, B> {
         let sync_peer = &self.sync_peers[peer_index];
         self.hooks.call_on_starting_hook(sync_peer);
 
-        let mut conn = self.dial_sync_peer(node_id).await?;
         debug!(
             target: LOG_TARGET,
             "Attempting to synchronize headers with `{}`", node_id
         );
-
-        let config = RpcClient::builder()
-            .with_deadline(self.config.rpc_deadline)
-            .with_deadline_grace_period(Duration::from_secs(5));
-        let mut client = conn
-            .connect_rpc_using_builder::<rpc::BaseNodeSyncRpcClient>(config)
-            .await?;
+        let client = self.connect_sync_client(node_id).await?;
 
         let latency = client
             .get_last_request_latency()
@@ -215,7 +217,7 @@ impl<'a, B: BlockchainBackend + 'static> HeaderSynchronizer<'a, B> {
         self.sync_peers[peer_index].set_latency(latency);
         if latency > max_latency {
             return Err(BlockHeaderSyncError::MaxLatencyExceeded {
-                peer: conn.peer_node_id().clone(),
+                peer: node_id.clone(),
                 latency,
                 max_latency,
             });
@@ -240,6 +242,56 @@ impl<'a, B: BlockchainBackend + 'static> HeaderSynchronizer<'a, B> {
         Ok(conn)
     }
 
+    async fn connect_sync_client(&self, node_id: &NodeId) -> Result<rpc::BaseNodeSyncRpcClient, BlockHeaderSyncError> {
+        let mut conn = self.dial_sync_peer(node_id).await?;
+        let config = RpcClient::builder()
+            .with_deadline(self.config.rpc_deadline)
+            .with_deadline_grace_period(Duration::from_secs(5))
+            .with_latency_tracker(self.latency_tracker.clone())
+            .with_sync_quality_tracker(self.sync_quality.clone());
+        let client = conn
+            .connect_rpc_using_builder::<rpc::BaseNodeSyncRpcClient>(config)
+            .await?;
+        Ok(client)
+    }
+
+    /// Connects to the other sync peers that have headers above `height`, which header batches are downloaded from
+    /// alongside the sync peer, up to `max_concurrent_sync_peers` in total
+    async fn connect_to_helper_peers(
+        &mut self,
+        sync_peer: &NodeId,
+        height: u64,
+    ) -> Vec<(SyncPeer, rpc::BaseNodeSyncRpcClient)> {
+        let candidates = self
+            .sync_peers
+            .iter()
+            .filter(|p| p.node_id() != sync_peer && p.claimed_chain_metadata().best_block_height() > height)
+            .take(self.config.max_concurrent_sync_peers.saturating_sub(1))
+            .cloned()
+            .collect::<Vec<_>>();
+        let mut helpers = Vec::with_capacity(candidates.len());
+        for mut peer in candidates {
+            match self.connect_sync_client(peer.node_id()).await {
+                Ok(client) => {
+                    if let Some(latency) = client.get_last_request_latency() {
+                        peer.set_latency(latency);
+                    }
+                    helpers.push((peer, client));
+                },
+                Err(e) => {
+                    warn!(
+                        target: LOG_TARGET,
+                        "Failed to connect to sync peer `{}`: {}",
+                        peer.node_id(),
+                        e
+                    );
+                    self.remove_sync_peer(peer.node_id());
+                },
+            }
+        }
+        helpers
+    }
+
     async fn attempt_sync(
         &mut self,
         sync_peer: &SyncPeer,
@@ -616,13 +668,43 @@ impl<'a, B: BlockchainBackend + 'static> HeaderSynchronizer<'a, B> {
             start_header_height,
             sync_peer.node_id()
         );
-        let request = SyncHeadersRequest {
-            start_hash: start_header_hash.to_vec(),
-            // To the tip!
-            count: 0,
+        let claimed_height = sync_peer.claimed_chain_metadata().best_block_height();
+        let helpers = if claimed_height.saturating_sub(start_header_height) > self.config.header_batch_size {
+            self.connect_to_helper_peers(sync_peer.node_id(), start_header_height)
+                .await
+        } else {
+            Vec::new()
+        };
+        let mut header_source = if helpers.is_empty() {
+            let request = SyncHeadersRequest {
+                start_hash: start_header_hash.to_vec(),
+                // To the tip!
+                count: 0,
+            };
+            HeaderSource::Stream(client.sync_headers(request).await?)
+        } else {
+            debug!(
+                target: LOG_TARGET,
+                "Downloading header batches up to #{} from peer `{}` and {} other peer(s)",
+                claimed_height,
+                sync_peer.node_id(),
+                helpers.len()
+            );
+            let boundary_client = self.connect_sync_client(sync_peer.node_id()).await?;
+            HeaderSource::Concurrent(Box::new(ConcurrentHeaderDownload::new(
+                self.config.clone(),
+                PeerBanManager::new(
+                    self.config.clone(),
+                    self.connectivity.clone(),
+                    self.sync_quality.clone(),
+                ),
+                (sync_peer.clone(), client.clone()),
+                helpers,
+                boundary_client,
+                (start_header_height, start_header_hash),
+                max_latency,
+            )))
         };
-
-        let mut header_stream = client.sync_headers(request).await?;
         debug!(
             target: LOG_TARGET,
             "Reading headers from peer `{}`",
@@ -634,10 +716,9 @@ impl<'a, B: BlockchainBackend + 'static> HeaderSynchronizer<'a, B> {
         let mut last_total_accumulated_difficulty = U256::zero();
         let mut avg_latency = RollingAverageTime::new(20);
         let mut prev_height: Option<u64> = None;
-        while let Some(header) = header_stream.next().await {
+        while let Some(header) = header_source.next_header().await? {
             let latency = last_sync_timer.elapsed();
             avg_latency.add_sample(latency);
-            let header = BlockHeader::try_from(header?).map_err(BlockHeaderSyncError::ReceivedInvalidHeader)?;
             debug!(
                 target: LOG_TARGET,
                 "Validating header #{} (Pow: {}) with hash: ({}). Latency: {:.2?}",
@@ -103,6 +103,8 @@ pub enum HorizonSyncError {
     ByteArrayError(String),
     #[error("FixedHash size error: {0}")]
     MrHashError(#[from] MrHashError),
+    #[error("The horizon state resumed from the sync checkpoint is invalid: {0}")]
+    ResumedStateInvalid(String),
 }
 
 impl From<ByteArrayError> for HorizonSyncError {
@@ -136,6 +138,8 @@ impl HorizonSyncError {
             HorizonSyncError::PeerNotFound |
             HorizonSyncError::JoinError(_) |
             HorizonSyncError::MrHashError(_) => None,
+            // The checkpoint may hold outputs received from an earlier sync peer
+            HorizonSyncError::ResumedStateInvalid(_) => None,
 
             // short ban
             err @ HorizonSyncError::MaxLatencyExceeded { .. } |
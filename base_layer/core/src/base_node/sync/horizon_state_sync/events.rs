@@ -39,9 +39,13 @@ impl HorizonSyncInfo {
     }
 
     pub fn to_progress_string(&self) -> String {
-        use HorizonSyncStatus::{Finalizing, Kernels, Outputs, Starting};
+        use HorizonSyncStatus::{Finalizing, Kernels, Outputs, Resuming, Starting};
         match self.status {
             Starting => "Starting horizon sync".to_string(),
+            Resuming { height, outputs, .. } => format!(
+                "Resuming output sync from height {} with {} outputs already verified",
+                height, outputs
+            ),
             Kernels {
                 current,
                 total,
@@ -88,6 +92,17 @@ impl Display for HorizonSyncInfo {
 
         match self.status.clone() {
             HorizonSyncStatus::Starting => write!(f, "Starting horizon state synchronization"),
+            HorizonSyncStatus::Resuming {
+                height,
+                outputs,
+                sync_peer,
+            } => write!(
+                f,
+                "Resuming horizon output sync from height {} with {} outputs already verified, from {}",
+                height,
+                outputs,
+                sync_peer.node_id()
+            ),
             HorizonSyncStatus::Kernels {
                 current,
                 total,
@@ -121,6 +136,14 @@ impl Display for HorizonSyncInfo {
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum HorizonSyncStatus {
     Starting,
+    /// Output sync is resuming from the checkpoint of an interrupted sync
+    Resuming {
+        /// The height of the first block whose outputs are requested
+        height: u64,
+        /// The number of outputs received and range proof verified before the interruption
+        outputs: u64,
+        sync_peer: SyncPeer,
+    },
     Kernels {
         current: u64,
         total: u64,
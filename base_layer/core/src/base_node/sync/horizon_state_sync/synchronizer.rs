@@ -30,11 +30,17 @@ use std::{
 use futures::StreamExt;
 use log::*;
 use tari_common_types::types::{Commitment, FixedHash, RangeProofService};
-use tari_comms::{connectivity::ConnectivityRequester, peer_manager::NodeId, protocol::rpc::RpcClient, PeerConnection};
+use tari_comms::{
+    connectivity::ConnectivityRequester,
+    peer_manager::{NodeId, PeerLatencyTracker, SyncPeerQualityTracker},
+    protocol::rpc::RpcClient,
+    PeerConnection,
+};
 use tari_crypto::commitment::HomomorphicCommitment;
 use tari_mmr::sparse_merkle_tree::{DeleteResult, NodeKey, ValueHash};
 use tari_utilities::{hex::Hex, ByteArray};
 use tokio::task;
+use tracing::instrument;
 
 use super::error::HorizonSyncError;
 use crate::{
@@ -48,8 +54,14 @@ use crate::{
         SyncPeer,
     },
     blocks::{BlockHeader, ChainHeader, UpdateBlockAccumulatedData},
-    chain_storage::{async_db::AsyncBlockchainDb, BlockchainBackend, ChainStorageError, MmrTree},
-    common::{rolling_avg::RollingAverageTime, BanPeriod},
+    chain_storage::{
+        async_db::AsyncBlockchainDb,
+        BlockchainBackend,
+        ChainStorageError,
+        HorizonSyncCheckpoint,
+        MmrTree,
+    },
+    common::rolling_avg::RollingAverageTime,
     consensus::ConsensusManager,
     output_mr_hash_from_smt,
     proto::base_node::{sync_utxos_response::Txo, SyncKernelsRequest, SyncUtxosRequest, SyncUtxosResponse},
@@ -67,6 +79,8 @@ use crate::{
 const LOG_TARGET: &str = "c::bn::state_machine_service::states::horizon_state_sync";
 
 const MAX_LATENCY_INCREASES: usize = 5;
+/// The minimum number of TXOs received between output sync checkpoints
+const OUTPUT_CHECKPOINT_INTERVAL: u64 = 10_000;
 
 pub struct HorizonStateSynchronization<'a, B> {
     config: BlockchainSyncConfig,
@@ -82,6 +96,8 @@ pub struct HorizonStateSynchronization<'a, B> {
     final_state_validator: Arc<dyn FinalHorizonStateValidation<B>>,
     max_latency: Duration,
     peer_ban_manager: PeerBanManager,
+    latency_tracker: PeerLatencyTracker,
+    sync_quality: SyncPeerQualityTracker,
 }
 
 impl<'a, B: BlockchainBackend + 'static> HorizonStateSynchronization<'a, B> {
@@ -95,8 +111,10 @@ impl<'a, B: BlockchainBackend + 'static> HorizonStateSynchronization<'a, B> {
         horizon_sync_height: u64,
         prover: Arc<RangeProofService>,
         final_state_validator: Arc<dyn FinalHorizonStateValidation<B>>,
+        latency_tracker: PeerLatencyTracker,
+        sync_quality: SyncPeerQualityTracker,
     ) -> Self {
-        let peer_ban_manager = PeerBanManager::new(config.clone(), connectivity.clone());
+        let peer_ban_manager = PeerBanManager::new(config.clone(), connectivity.clone(), sync_quality.clone());
         Self {
             max_latency: config.initial_max_sync_latency,
             config,
@@ -111,6 +129,8 @@ impl<'a, B: BlockchainBackend + 'static> HorizonStateSynchronization<'a, B> {
             hooks: Hooks::default(),
             final_state_validator,
             peer_ban_manager,
+            latency_tracker,
+            sync_quality,
         }
     }
 
@@ -124,6 +144,7 @@ impl<'a, B: BlockchainBackend + 'static> HorizonStateSynchronization<'a, B> {
         self.hooks.add_on_progress_horizon_hook(hook);
     }
 
+    #[instrument(name = "horizon_sync::synchronize", level = "debug", skip_all, err)]
     pub async fn synchronize(&mut self) -> Result<(), HorizonSyncError> {
         if self.sync_peers.is_empty() {
             return Err(HorizonSyncError::NoSyncPeers);
@@ -181,22 +202,20 @@ impl<'a, B: BlockchainBackend + 'static> HorizonStateSynchronization<'a, B> {
         let mut latency_counter = 0usize;
         for node_id in sync_peer_node_ids {
             match self.connect_and_attempt_sync(&node_id, to_header).await {
-                Ok(_) => return Ok(()),
+                Ok(_) => {
+                    self.sync_quality.record_sync_completed(&node_id);
+                    return Ok(());
+                },
                 // Try another peer
                 Err(err) => {
                     let ban_reason = HorizonSyncError::get_ban_reason(&err);
 
                     if let Some(reason) = ban_reason {
-                        let duration = match reason.ban_duration {
-                            BanPeriod::Short => self.config.short_ban_period,
-                            BanPeriod::Long => self.config.ban_period,
-                        };
                         warn!(target: LOG_TARGET, "{}", err);
-                        self.peer_ban_manager
-                            .ban_peer_if_required(&node_id, reason.reason, duration)
-                            .await;
+                        self.peer_ban_manager.ban_sync_peer(&node_id, reason).await;
                     }
                     if let HorizonSyncError::MaxLatencyExceeded { .. } = err {
+                        self.peer_ban_manager.record_stall(&node_id);
                         latency_counter += 1;
                     } else {
                         self.remove_sync_peer(&node_id);
@@ -252,7 +271,9 @@ impl<'a, B: BlockchainBackend + 'static> HorizonStateSynchronization<'a, B> {
 
         let config = RpcClient::builder()
             .with_deadline(self.config.rpc_deadline)
-            .with_deadline_grace_period(Duration::from_secs(5));
+            .with_deadline_grace_period(Duration::from_secs(5))
+            .with_latency_tracker(self.latency_tracker.clone())
+            .with_sync_quality_tracker(self.sync_quality.clone());
 
         let mut client = conn
             .connect_rpc_using_builder::<rpc::BaseNodeSyncRpcClient>(config)
@@ -303,8 +324,27 @@ impl<'a, B: BlockchainBackend + 'static> HorizonStateSynchronization<'a, B> {
         match self.synchronize_outputs(sync_peer, client, to_header).await {
             Ok(_) => Ok(()),
             Err(err) => {
-                // We need to clean up the outputs
-                let _ = self.clean_up_failed_output_sync(to_header).await;
+                // The outputs received after the last checkpoint are cleaned up, so that the next attempt can resume
+                // from it. If the synced state is invalid, all of the outputs are cleaned up.
+                let checkpoint = match err {
+                    HorizonSyncError::InvalidMrRoot { .. } | HorizonSyncError::ResumedStateInvalid(_) => None,
+                    _ => self.db.fetch_horizon_sync_checkpoint().await.ok().flatten(),
+                };
+                let stop_hash = match checkpoint {
+                    Some(checkpoint) => Some(checkpoint.last_block_hash),
+                    None => {
+                        let _ = self
+                            .db
+                            .write_transaction()
+                            .set_horizon_sync_checkpoint(None)
+                            .commit()
+                            .await;
+                        self.db.fetch_tip_header().await.ok().map(|h| *h.hash())
+                    },
+                };
+                if let Some(stop_hash) = stop_hash {
+                    self.clean_up_failed_output_sync(to_header, stop_hash).await;
+                }
                 let mut smt = self.db.inner().smt_write_access()?;
                 *smt = cloned_backup_smt;
                 Err(err)
@@ -312,17 +352,13 @@ impl<'a, B: BlockchainBackend + 'static> HorizonStateSynchronization<'a, B> {
         }
     }
 
-    /// We clean up a failed output sync attempt and ignore any errors that occur during the clean up process.
-    async fn clean_up_failed_output_sync(&mut self, to_header: &BlockHeader) {
-        let tip_header = if let Ok(header) = self.db.fetch_tip_header().await {
-            header
-        } else {
-            return;
-        };
+    /// We clean up the outputs of a failed output sync attempt in the blocks from `from_header` back to, but excluding,
+    /// the block with `stop_hash`, and ignore any errors that occur during the clean up process.
+    async fn clean_up_failed_output_sync(&mut self, from_header: &BlockHeader, stop_hash: FixedHash) {
         let db = self.db().clone();
         let mut txn = db.write_transaction();
-        let mut current_header = to_header.clone();
-        loop {
+        let mut current_header = from_header.clone();
+        while current_header.hash() != stop_hash {
             if let Ok(outputs) = self.db.fetch_outputs_in_block(current_header.hash()).await {
                 for (count, output) in (1..=outputs.len()).zip(outputs.iter()) {
                     // Note: We do not need to clean up the SMT as it was not saved in the database yet, however, we
@@ -361,7 +397,7 @@ impl<'a, B: BlockchainBackend + 'static> HorizonStateSynchronization<'a, B> {
                     current_header = previous_header;
                 } else {
                     warn!(target: LOG_TARGET, "Could not clean up failed output sync, previous_header link missing frm db");
-                    break;
+                    return;
                 }
             } else {
                 warn!(
@@ -369,13 +405,78 @@ impl<'a, B: BlockchainBackend + 'static> HorizonStateSynchronization<'a, B> {
                     "Could not clean up failed output sync, header '{}' not in db",
                     current_header.prev_hash.to_hex()
                 );
-                break;
+                return;
             }
-            if &current_header.hash() == tip_header.hash() {
-                debug!(target: LOG_TARGET, "Finished cleaning up failed output sync");
-                break;
+        }
+        debug!(target: LOG_TARGET, "Finished cleaning up failed output sync");
+    }
+
+    /// Loads the checkpoint of an interrupted output sync and restores `output_smt` to it. If the checkpoint cannot be
+    /// resumed towards `to_header`, the outputs of the interrupted sync are cleaned up instead.
+    async fn load_output_checkpoint(
+        &mut self,
+        tip_header: &ChainHeader,
+        to_header: &BlockHeader,
+        output_smt: &mut OutputSmt,
+    ) -> Result<Option<HorizonSyncCheckpoint>, HorizonSyncError> {
+        let db = self.db().clone();
+        let checkpoint = match db.fetch_horizon_sync_checkpoint().await? {
+            Some(checkpoint) => checkpoint,
+            None => return Ok(None),
+        };
+        // Peers only leave out the outputs spent before the end of the requested range, so the interrupted sync can be
+        // resumed towards the same or a later horizon header on the current chain
+        let horizon_header = db.fetch_header_by_block_hash(checkpoint.horizon_header_hash).await?;
+        let last_header = db.fetch_header_by_block_hash(checkpoint.last_block_hash).await?;
+        let is_resumable = horizon_header.map_or(false, |h| h.height <= to_header.height) &&
+            last_header
+                .as_ref()
+                .map_or(false, |h| h.height > tip_header.height() && h.height < to_header.height);
+        if !is_resumable {
+            warn!(
+                target: LOG_TARGET,
+                "Discarding horizon sync checkpoint at height {}, as it cannot be resumed towards height {}",
+                checkpoint.last_block_height,
+                to_header.height
+            );
+            if let Some(last_header) = last_header {
+                self.clean_up_failed_output_sync(&last_header, *tip_header.hash()).await;
             }
+            db.write_transaction()
+                .set_horizon_sync_checkpoint(None)
+                .commit()
+                .await?;
+            return Ok(None);
         }
+
+        info!(
+            target: LOG_TARGET,
+            "Resuming output sync from height {} ({} outputs verified, {} spent)",
+            checkpoint.last_block_height + 1,
+            checkpoint.outputs_verified,
+            checkpoint.spent_received
+        );
+        for height in (tip_header.height() + 1)..=checkpoint.last_block_height {
+            let header = db.fetch_chain_header(height).await?;
+            for output in db.fetch_outputs_in_block(*header.hash()).await? {
+                if output.is_burned() {
+                    continue;
+                }
+                let smt_key = NodeKey::try_from(output.commitment.as_bytes())?;
+                let smt_node = ValueHash::try_from(output.smt_hash(height).as_slice())?;
+                output_smt.insert(smt_key, smt_node)?;
+            }
+        }
+        for (_, commitment) in &checkpoint.spent_outputs {
+            let smt_key = NodeKey::try_from(commitment.as_bytes())?;
+            if let DeleteResult::KeyNotFound = output_smt.delete(&smt_key)? {
+                return Err(HorizonSyncError::ResumedStateInvalid(format!(
+                    "Spent output {} is not in the output SMT",
+                    commitment.to_hex()
+                )));
+            }
+        }
+        Ok(Some(checkpoint))
     }
 
     async fn prune_if_needed(&mut self) -> Result<(), HorizonSyncError> {
@@ -573,6 +674,20 @@ impl<'a, B: BlockchainBackend + 'static> HorizonStateSynchronization<'a, B> {
         info!(target: LOG_TARGET, "Starting output sync from peer {}", sync_peer);
         let db = self.db().clone();
         let tip_header = db.fetch_tip_header().await?;
+        let mut output_smt = (*db.inner().smt_write_access()?).clone();
+        let checkpoint = self
+            .load_output_checkpoint(&tip_header, to_header, &mut output_smt)
+            .await?;
+        let is_resumed = checkpoint.is_some();
+        let (start_height, mut utxo_counter, mut stxo_counter, mut inputs_to_delete) = match checkpoint {
+            Some(checkpoint) => (
+                checkpoint.last_block_height + 1,
+                checkpoint.outputs_verified,
+                checkpoint.spent_received,
+                checkpoint.spent_outputs,
+            ),
+            None => (tip_header.height() + 1, 0, 0, Vec::new()),
+        };
 
         // Estimate the number of outputs to be downloaded; this cannot be known exactly until the sync is complete.
         let mut current_header = to_header.clone();
@@ -592,8 +707,16 @@ impl<'a, B: BlockchainBackend + 'static> HorizonStateSynchronization<'a, B> {
             }
         }
 
+        if is_resumed {
+            let info = HorizonSyncInfo::new(vec![sync_peer.node_id().clone()], HorizonSyncStatus::Resuming {
+                height: start_height,
+                outputs: utxo_counter,
+                sync_peer: sync_peer.clone(),
+            });
+            self.hooks.call_on_progress_horizon_hooks(info);
+        }
         let info = HorizonSyncInfo::new(vec![sync_peer.node_id().clone()], HorizonSyncStatus::Outputs {
-            current: 0,
+            current: utxo_counter,
             total: self.num_outputs,
             sync_peer: sync_peer.clone(),
         });
@@ -611,7 +734,7 @@ impl<'a, B: BlockchainBackend + 'static> HorizonStateSynchronization<'a, B> {
             latency.unwrap_or_default().as_millis(),
         );
 
-        let start_chain_header = db.fetch_chain_header(tip_header.height() + 1).await?;
+        let start_chain_header = db.fetch_chain_header(start_height).await?;
         let req = SyncUtxosRequest {
             start_header_hash: start_chain_header.hash().to_vec(),
             end_header_hash: to_header.hash().to_vec(),
@@ -619,14 +742,13 @@ impl<'a, B: BlockchainBackend + 'static> HorizonStateSynchronization<'a, B> {
         let mut output_stream = client.sync_utxos(req).await?;
 
         let mut txn = db.write_transaction();
-        let mut utxo_counter = 0u64;
-        let mut stxo_counter = 0u64;
         let timer = Instant::now();
-        let mut output_smt = (*db.inner().smt_write_access()?).clone();
         let mut last_sync_timer = Instant::now();
         let mut avg_latency = RollingAverageTime::new(20);
 
-        let mut inputs_to_delete = Vec::new();
+        // The block of the previous TXO and the number of TXOs received since the last checkpoint
+        let mut last_block = None;
+        let mut txos_since_checkpoint = 0u64;
         while let Some(response) = output_stream.next().await {
             let latency = last_sync_timer.elapsed();
             avg_latency.add_sample(latency);
@@ -642,6 +764,28 @@ impl<'a, B: BlockchainBackend + 'static> HorizonStateSynchronization<'a, B> {
                     HorizonSyncError::IncorrectResponse("Peer sent mined header we do not know of".into())
                 })?;
 
+            // Peers send the TXOs block by block, so the previous block is complete once a TXO of the next one arrives
+            if let Some((last_block_hash, last_block_height)) = last_block {
+                if last_block_hash != current_header.hash() && txos_since_checkpoint >= OUTPUT_CHECKPOINT_INTERVAL {
+                    txn.set_horizon_sync_checkpoint(Some(HorizonSyncCheckpoint {
+                        horizon_header_hash: to_header.hash(),
+                        last_block_hash,
+                        last_block_height,
+                        outputs_verified: utxo_counter,
+                        spent_received: stxo_counter,
+                        spent_outputs: inputs_to_delete.clone(),
+                    }));
+                    txn.commit().await?;
+                    debug!(
+                        target: LOG_TARGET,
+                        "Output sync checkpoint at height {} ({} outputs verified)", last_block_height, utxo_counter
+                    );
+                    txos_since_checkpoint = 0;
+                }
+            }
+            last_block = Some((current_header.hash(), current_header.height));
+            txos_since_checkpoint += 1;
+
             let proto_output = res
                 .txo
                 .ok_or_else(|| HorizonSyncError::IncorrectResponse("Peer sent no transaction output data".into()))?;
@@ -721,6 +865,15 @@ impl<'a, B: BlockchainBackend + 'static> HorizonStateSynchronization<'a, B> {
                             // we need to abort the sync
                             inputs_to_delete.push((output_hash, commitment));
                         },
+                        // Outputs received before the checkpoint that were spent before the end of that sync's range
+                        // were left out by the peer
+                        None if is_resumed => {
+                            debug!(
+                                target: LOG_TARGET,
+                                "STXO `{}` spent before the checkpoint ignored",
+                                commitment.to_hex()
+                            );
+                        },
                         None => {
                             return Err(HorizonSyncError::IncorrectResponse(
                                 "Peer sent unknown commitment hash".into(),
@@ -760,7 +913,15 @@ impl<'a, B: BlockchainBackend + 'static> HorizonStateSynchronization<'a, B> {
         //      it.
         // 3. In both cases it would be impossible to verify the SMT per block, as we would not be able to update the
         //    SMT with the outputs that were created and spent within the tranche.
-        HorizonStateSynchronization::<B>::check_output_smt_root_hash(&mut output_smt, to_header)?;
+        if let Err(err) = HorizonStateSynchronization::<B>::check_output_smt_root_hash(&mut output_smt, to_header) {
+            if is_resumed {
+                return Err(HorizonSyncError::ResumedStateInvalid(err.to_string()));
+            }
+            return Err(err);
+        }
+        // The spent outputs are pruned below, after which the checkpoint could no longer be resumed from
+        txn.set_horizon_sync_checkpoint(None);
+        txn.commit().await?;
 
         // Commit in chunks to avoid locking the database for too long
         let inputs_to_delete_len = inputs_to_delete.len();
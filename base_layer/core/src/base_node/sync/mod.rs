@@ -28,6 +28,9 @@ mod config;
 #[cfg(feature = "base_node")]
 pub use self::config::BlockchainSyncConfig;
 
+#[cfg(feature = "base_node")]
+mod batch_scheduler;
+
 #[cfg(feature = "base_node")]
 mod block_sync;
 #[cfg(feature = "base_node")]
@@ -0,0 +1,328 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Compact block output filters for light wallets.
+//!
+//! A filter is a Golomb-coded set (as in BIP-158) over the commitments and scripts of a block's outputs and the
+//! hashes of the outputs it spends. A light wallet downloads one small filter per block and only fetches blocks whose
+//! filter matches one of its known commitments, output hashes or one-sided scripts, without revealing which items it
+//! is looking for. Filters have false positives (roughly 1 in [`BLOCK_FILTER_M`] per query item) but no false
+//! negatives.
+
+use std::{cmp::Ordering, convert::TryFrom};
+
+use blake2::Blake2b;
+use digest::consts::U32;
+use tari_common_types::types::FixedHash;
+use tari_crypto::hashing::DomainSeparatedHasher;
+use tari_utilities::ByteArray;
+
+use crate::blocks::{Block, BlocksHashDomain};
+
+/// Number of bits of each hashed item that are stored verbatim
+pub const BLOCK_FILTER_P: u8 = 19;
+/// Inverse false positive rate
+pub const BLOCK_FILTER_M: u64 = 784_931;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockOutputFilter {
+    block_hash: FixedHash,
+    num_items: u32,
+    data: Vec<u8>,
+}
+
+impl BlockOutputFilter {
+    /// Builds the filter for a block. Compact inputs are sufficient since only the spent output hash is used.
+    pub fn from_block(block: &Block) -> Self {
+        Self::new(block.hash(), Self::block_items(block))
+    }
+
+    /// The raw items committed to by the filter of `block`
+    pub fn block_items(block: &Block) -> Vec<Vec<u8>> {
+        let outputs = block
+            .body
+            .outputs()
+            .iter()
+            .flat_map(|output| [output.commitment.as_bytes().to_vec(), output.script.to_bytes()]);
+        let inputs = block.body.inputs().iter().map(|input| input.output_hash().to_vec());
+        outputs.chain(inputs).collect()
+    }
+
+    /// Builds a filter over arbitrary items, keyed by `block_hash`
+    pub fn new<I, T>(block_hash: FixedHash, items: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<[u8]>,
+    {
+        let mut items = items.into_iter().map(|item| item.as_ref().to_vec()).collect::<Vec<_>>();
+        items.sort_unstable();
+        items.dedup();
+        let num_items = u32::try_from(items.len()).unwrap_or(u32::MAX);
+        let range = u64::from(num_items) * BLOCK_FILTER_M;
+        let mut values = items
+            .iter()
+            .map(|item| hash_to_range(&block_hash, item, range))
+            .collect::<Vec<_>>();
+        values.sort_unstable();
+
+        let mut writer = BitWriter::default();
+        let mut last = 0u64;
+        for value in values {
+            let delta = value - last;
+            last = value;
+            writer.write_unary(delta >> BLOCK_FILTER_P);
+            writer.write_bits(delta, BLOCK_FILTER_P);
+        }
+
+        Self {
+            block_hash,
+            num_items,
+            data: writer.into_bytes(),
+        }
+    }
+
+    /// Reconstructs a filter received from a server
+    pub fn from_parts(block_hash: FixedHash, num_items: u32, data: Vec<u8>) -> Self {
+        Self {
+            block_hash,
+            num_items,
+            data,
+        }
+    }
+
+    pub fn block_hash(&self) -> &FixedHash {
+        &self.block_hash
+    }
+
+    /// The number of distinct items in the filter
+    pub fn num_items(&self) -> u32 {
+        self.num_items
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn matches(&self, item: &[u8]) -> bool {
+        self.matches_any([item])
+    }
+
+    /// Returns true if any of the query items are (probably) in the filter. A malformed filter matches everything so
+    /// that a misbehaving server can only cost the client bandwidth, never hide outputs.
+    pub fn matches_any<I, T>(&self, items: I) -> bool
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<[u8]>,
+    {
+        if self.num_items == 0 {
+            return false;
+        }
+        let range = u64::from(self.num_items) * BLOCK_FILTER_M;
+        let mut queries = items
+            .into_iter()
+            .map(|item| hash_to_range(&self.block_hash, item.as_ref(), range))
+            .collect::<Vec<_>>();
+        if queries.is_empty() {
+            return false;
+        }
+        queries.sort_unstable();
+
+        let mut reader = BitReader::new(&self.data);
+        let mut queries = queries.into_iter().peekable();
+        let mut value = 0u64;
+        for _ in 0..self.num_items {
+            let delta = match (reader.read_unary(), reader.read_bits(BLOCK_FILTER_P)) {
+                (Some(quotient), Some(remainder)) => (quotient << BLOCK_FILTER_P) | remainder,
+                _ => return true,
+            };
+            value = match value.checked_add(delta) {
+                Some(v) => v,
+                None => return true,
+            };
+            while let Some(query) = queries.peek() {
+                match query.cmp(&value) {
+                    Ordering::Less => {
+                        queries.next();
+                    },
+                    Ordering::Equal => return true,
+                    Ordering::Greater => break,
+                }
+            }
+            if queries.peek().is_none() {
+                return false;
+            }
+        }
+        false
+    }
+}
+
+fn hash_to_range(block_hash: &FixedHash, item: &[u8], range: u64) -> u64 {
+    let hash = DomainSeparatedHasher::<Blake2b<U32>, BlocksHashDomain>::new_with_label("block_output_filter")
+        .chain(block_hash.as_slice())
+        .chain(item)
+        .finalize();
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&hash.as_ref()[..8]);
+    let value = u64::from_le_bytes(bytes);
+    // Maps the hash uniformly onto [0, range) without a modulo
+    u64::try_from((u128::from(value) * u128::from(range)) >> 64).unwrap_or(u64::MAX)
+}
+
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    bit_len: usize,
+}
+
+impl BitWriter {
+    fn write_bit(&mut self, bit: bool) {
+        if self.bit_len % 8 == 0 {
+            self.bytes.push(0);
+        }
+        if bit {
+            let last = self.bytes.len() - 1;
+            self.bytes[last] |= 0x80 >> (self.bit_len % 8);
+        }
+        self.bit_len += 1;
+    }
+
+    fn write_unary(&mut self, n: u64) {
+        for _ in 0..n {
+            self.write_bit(true);
+        }
+        self.write_bit(false);
+    }
+
+    fn write_bits(&mut self, value: u64, n: u8) {
+        for i in (0..n).rev() {
+            self.write_bit((value >> i) & 1 == 1);
+        }
+    }
+
+    fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, position: 0 }
+    }
+
+    fn read_bit(&mut self) -> Option<bool> {
+        let byte = self.bytes.get(self.position / 8)?;
+        let bit = byte & (0x80 >> (self.position % 8)) != 0;
+        self.position += 1;
+        Some(bit)
+    }
+
+    fn read_unary(&mut self) -> Option<u64> {
+        let mut n = 0u64;
+        while self.read_bit()? {
+            n += 1;
+        }
+        Some(n)
+    }
+
+    fn read_bits(&mut self, n: u8) -> Option<u64> {
+        let mut value = 0u64;
+        for _ in 0..n {
+            value = (value << 1) | u64::from(self.read_bit()?);
+        }
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::{rngs::OsRng, RngCore};
+
+    use super::*;
+
+    fn random_items(n: usize) -> Vec<Vec<u8>> {
+        (0..n)
+            .map(|_| {
+                let mut item = vec![0u8; 32];
+                OsRng.fill_bytes(&mut item);
+                item
+            })
+            .collect()
+    }
+
+    #[test]
+    fn it_matches_every_item_in_the_filter() {
+        let block_hash = FixedHash::from([7u8; 32]);
+        let items = random_items(250);
+        let filter = BlockOutputFilter::new(block_hash, &items);
+        assert_eq!(filter.num_items(), 250);
+        for item in &items {
+            assert!(filter.matches(item));
+        }
+        assert!(filter.matches_any(random_items(10).iter().chain(items.iter().take(1))));
+    }
+
+    #[test]
+    fn it_rarely_matches_items_not_in_the_filter() {
+        let block_hash = FixedHash::from([1u8; 32]);
+        let filter = BlockOutputFilter::new(block_hash, random_items(100));
+        let false_positives = random_items(10_000).iter().filter(|item| filter.matches(item)).count();
+        // The expected false positive count is 10_000 / BLOCK_FILTER_M, i.e. almost always zero
+        assert!(false_positives < 3, "{} false positives", false_positives);
+    }
+
+    #[test]
+    fn it_deduplicates_items() {
+        let block_hash = FixedHash::zero();
+        let item = vec![1u8, 2, 3];
+        let filter = BlockOutputFilter::new(block_hash, [item.clone(), item.clone()]);
+        assert_eq!(filter.num_items(), 1);
+        assert!(filter.matches(&item));
+    }
+
+    #[test]
+    fn it_round_trips_through_parts() {
+        let block_hash = FixedHash::from([3u8; 32]);
+        let items = random_items(20);
+        let filter = BlockOutputFilter::new(block_hash, &items);
+        let received = BlockOutputFilter::from_parts(block_hash, filter.num_items(), filter.data().to_vec());
+        assert_eq!(received, filter);
+        assert!(received.matches(&items[5]));
+        // A filter keyed by a different block hash does not match the same items
+        let other = BlockOutputFilter::from_parts(FixedHash::zero(), filter.num_items(), filter.data().to_vec());
+        assert!(!items.iter().all(|item| other.matches(item)));
+    }
+
+    #[test]
+    fn empty_and_malformed_filters() {
+        let empty = BlockOutputFilter::new(FixedHash::zero(), Vec::<Vec<u8>>::new());
+        assert_eq!(empty.num_items(), 0);
+        assert!(!empty.matches(b"anything"));
+        // A truncated filter matches everything rather than hiding outputs
+        let truncated = BlockOutputFilter::from_parts(FixedHash::zero(), 10, vec![0u8; 2]);
+        assert!(truncated.matches(b"anything"));
+    }
+}
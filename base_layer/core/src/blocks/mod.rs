@@ -39,6 +39,9 @@ pub use error::BlockError;
 mod block;
 pub use block::{Block, BlockBuilder, BlockValidationError, NewBlock};
 
+mod block_filter;
+pub use block_filter::{BlockOutputFilter, BLOCK_FILTER_M, BLOCK_FILTER_P};
+
 #[cfg(any(feature = "base_node", feature = "base_node_proto"))]
 mod block_header;
 #[cfg(any(feature = "base_node", feature = "base_node_proto"))]
@@ -59,8 +59,11 @@ use crate::{
         DbTotalSizeStats,
         DbTransaction,
         HorizonData,
+        HorizonSyncCheckpoint,
         MmrTree,
         TargetDifficulties,
+        UtxoSetStats,
+        ValidatorNodeSet,
     },
     common::rolling_vec::RollingVec,
     proof_of_work::{PowAlgorithm, TargetDifficultyWindow},
@@ -162,6 +165,8 @@ impl<B: BlockchainBackend + 'static> AsyncBlockchainDb<B> {
 
     make_async_fn!(fetch_horizon_data() -> HorizonData, "fetch_horizon_data");
 
+    make_async_fn!(fetch_horizon_sync_checkpoint() -> Option<HorizonSyncCheckpoint>, "fetch_horizon_sync_checkpoint");
+
     //---------------------------------- TXO --------------------------------------------//
 
     make_async_fn!(fetch_output(output_hash: HashOutput) -> Option<OutputMinedInfo>, "fetch_output");
@@ -184,6 +189,8 @@ impl<B: BlockchainBackend + 'static> AsyncBlockchainDb<B> {
 
     make_async_fn!(utxo_count() -> usize, "utxo_count");
 
+    make_async_fn!(fetch_utxo_set_stats() -> UtxoSetStats, "fetch_utxo_set_stats");
+
     //---------------------------------- Kernel --------------------------------------------//
     make_async_fn!(fetch_kernel_by_excess_sig(excess_sig: Signature) -> Option<(TransactionKernel, HashOutput)>, "fetch_kernel_by_excess_sig");
 
@@ -266,6 +273,8 @@ impl<B: BlockchainBackend + 'static> AsyncBlockchainDb<B> {
 
     make_async_fn!(rewind_to_height(height: u64) -> Vec<Arc<ChainBlock>>, "rewind_to_height");
 
+    make_async_fn!(reindex<F: Fn(u64, u64)>(on_progress: F) -> u64, "reindex");
+
     make_async_fn!(rewind_to_hash(hash: BlockHash) -> Vec<Arc<ChainBlock>>, "rewind_to_hash");
 
     make_async_fn!(fetch_block_timestamps(start_hash: HashOutput) -> RollingVec<EpochTime>, "fetch_block_timestamps");
@@ -284,6 +293,8 @@ impl<B: BlockchainBackend + 'static> AsyncBlockchainDb<B> {
 
     make_async_fn!(get_shard_key(height:u64, public_key: PublicKey) -> Option<[u8;32]>, "get_shard_key");
 
+    make_async_fn!(fetch_validator_node_set(height: u64) -> ValidatorNodeSet, "fetch_validator_node_set");
+
     make_async_fn!(fetch_template_registrations<T: RangeBounds<u64>>(range: T) -> Vec<TemplateRegistrationEntry>, "fetch_template_registrations");
 
     make_async_fn!(swap_to_highest_pow_chain() -> (), "swap to highest proof-of-work chain");
@@ -342,6 +353,11 @@ impl<'a, B: BlockchainBackend + 'static> AsyncDbTransaction<'a, B> {
         self
     }
 
+    pub fn set_horizon_sync_checkpoint(&mut self, checkpoint: Option<HorizonSyncCheckpoint>) -> &mut Self {
+        self.transaction.set_horizon_sync_checkpoint(checkpoint);
+        self
+    }
+
     pub fn insert_kernel_via_horizon_sync(
         &mut self,
         kernel: TransactionKernel,
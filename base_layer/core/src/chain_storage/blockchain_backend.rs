@@ -17,10 +17,13 @@ use crate::{
         DbTransaction,
         DbValue,
         HorizonData,
+        HorizonSyncCheckpoint,
         InputMinedInfo,
         MmrTree,
         OutputMinedInfo,
+        ReindexCheckpoint,
         Reorg,
+        UtxoSetStats,
     },
     transactions::transaction_components::{TransactionInput, TransactionKernel, TransactionOutput},
     OutputSmt,
@@ -132,6 +135,8 @@ pub trait BlockchainBackend: Send + Sync {
     fn fetch_chain_metadata(&self) -> Result<ChainMetadata, ChainStorageError>;
     /// Returns the UTXO count
     fn utxo_count(&self) -> Result<usize, ChainStorageError>;
+    /// Returns the statistics of the UTXO set
+    fn fetch_utxo_set_stats(&self) -> Result<UtxoSetStats, ChainStorageError>;
     /// Returns the kernel count
     fn kernel_count(&self) -> Result<usize, ChainStorageError>;
 
@@ -157,6 +162,12 @@ pub trait BlockchainBackend: Send + Sync {
 
     fn fetch_horizon_data(&self) -> Result<Option<HorizonData>, ChainStorageError>;
 
+    /// Fetches the progress of an interrupted horizon sync, if any
+    fn fetch_horizon_sync_checkpoint(&self) -> Result<Option<HorizonSyncCheckpoint>, ChainStorageError>;
+
+    /// Fetches the reindex that has not completed yet, if any
+    fn fetch_reindex_checkpoint(&self) -> Result<Option<ReindexCheckpoint>, ChainStorageError>;
+
     /// Returns basic database stats for each internal database, such as number of entries and page sizes. This call may
     /// not apply to every database implementation.
     fn get_stats(&self) -> Result<DbBasicStats, ChainStorageError>;
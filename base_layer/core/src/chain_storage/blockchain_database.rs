@@ -47,7 +47,7 @@ use tari_mmr::{
 };
 use tari_utilities::{epoch_time::EpochTime, hex::Hex, ByteArray};
 
-use super::TemplateRegistrationEntry;
+use super::{TemplateRegistrationEntry, ValidatorNodeSet, ValidatorNodeSetMember};
 use crate::{
     blocks::{
         Block,
@@ -75,12 +75,15 @@ use crate::{
         DbBasicStats,
         DbTotalSizeStats,
         HorizonData,
+        HorizonSyncCheckpoint,
         InputMinedInfo,
         MmrTree,
         Optional,
         OrNotFound,
+        ReindexCheckpoint,
         Reorg,
         TargetDifficulties,
+        UtxoSetStats,
     },
     common::{rolling_vec::RollingVec, BanPeriod},
     consensus::{
@@ -106,6 +109,7 @@ use crate::{
     PrunedInputMmr,
     PrunedKernelMmr,
     ValidatorNodeBMT,
+    ValidatorNodeBmtProof,
 };
 
 const LOG_TARGET: &str = "c::cs::database";
@@ -117,6 +121,10 @@ pub struct BlockchainDatabaseConfig {
     pub orphan_storage_capacity: usize,
     pub pruning_horizon: u64,
     pub pruning_interval: u64,
+    /// The number of blocks beyond the pruning horizon for which a pruned node keeps the spent outputs, with their
+    /// scripts and encrypted data, and the inputs spending them. This lets the node serve light wallets scanning for
+    /// one-sided payments further back than the horizon, without making horizon sync download any more data.
+    pub output_retention_window: u64,
     pub track_reorgs: bool,
     pub cleanup_orphans_at_startup: bool,
 }
@@ -127,6 +135,7 @@ impl Default for BlockchainDatabaseConfig {
             orphan_storage_capacity: BLOCKCHAIN_DATABASE_ORPHAN_STORAGE_CAPACITY,
             pruning_horizon: BLOCKCHAIN_DATABASE_PRUNING_HORIZON,
             pruning_interval: BLOCKCHAIN_DATABASE_PRUNED_MODE_PRUNING_INTERVAL,
+            output_retention_window: 0,
             track_reorgs: false,
             cleanup_orphans_at_startup: false,
         }
@@ -289,6 +298,11 @@ where B: BlockchainBackend
             *smt = blockchain_db.db_write_access()?.calculate_tip_smt()?;
             warn!(target: LOG_TARGET, "Finished loading SMT into memory from stored db");
         }
+        if blockchain_db.fetch_reindex_checkpoint()?.is_some() {
+            warn!(target: LOG_TARGET, "Resuming the reindex that was interrupted");
+            let num_blocks = blockchain_db.reindex(|_, _| {})?;
+            warn!(target: LOG_TARGET, "Finished the reindex, {} block(s) re-applied", num_blocks);
+        }
         if config.cleanup_orphans_at_startup {
             match blockchain_db.cleanup_all_orphans() {
                 Ok(_) => info!(target: LOG_TARGET, "Orphan database cleaned out at startup.",),
@@ -533,6 +547,12 @@ where B: BlockchainBackend
         db.utxo_count()
     }
 
+    /// Returns the statistics of the current unspent set
+    pub fn fetch_utxo_set_stats(&self) -> Result<UtxoSetStats, ChainStorageError> {
+        let db = self.db_read_access()?;
+        db.fetch_utxo_set_stats()
+    }
+
     /// Returns the block header at the given block height.
     pub fn fetch_header(&self, height: u64) -> Result<Option<BlockHeader>, ChainStorageError> {
         let db = self.db_read_access()?;
@@ -1052,7 +1072,12 @@ where B: BlockchainBackend
                 db.fetch_chain_metadata()?.best_block_height()
             );
             // If blocks were added and the node is in pruned mode, perform pruning
-            prune_database_if_needed(&mut *db, self.config.pruning_horizon, self.config.pruning_interval)?;
+            prune_database_if_needed(
+                &mut *db,
+                self.config.pruning_horizon,
+                self.config.output_retention_window,
+                self.config.pruning_interval,
+            )?;
         }
 
         // Clean up orphan pool
@@ -1238,6 +1263,138 @@ where B: BlockchainBackend
         rewind_to_hash(&mut *db, hash, self.smt.clone())
     }
 
+    /// Rebuilds the block body indexes, the kernel MMR and the output SMT from the blocks stored in the database,
+    /// re-validating each block, without fetching anything from the network. Only the stored headers and block bodies
+    /// are read, never the indexes that are being rebuilt. The indexes are cleared and the genesis block re-applied
+    /// first, and then each stored block is re-applied in place in its own database transaction, which also moves the
+    /// chain tip up to it. A reindex that is interrupted therefore resumes from the block after the tip, which happens
+    /// when the node is next started if it is not called again before that. `on_progress` is called with the height of
+    /// each re-applied block and the height that the reindex ends at. Returns the number of blocks that were
+    /// re-applied.
+    ///
+    /// If a stored block fails validation, it and the stored blocks above it are deleted, so that the node fetches
+    /// them again from its peers.
+    pub fn reindex<F: Fn(u64, u64)>(&self, on_progress: F) -> Result<u64, ChainStorageError> {
+        if self.is_add_block_disabled() {
+            return Err(ChainStorageError::AddBlockOperationLocked);
+        }
+        self.set_disable_add_block_flag();
+        let result = self
+            .start_reindex()
+            .and_then(|num_blocks| Ok(num_blocks + self.resume_reindex(&on_progress)?));
+        self.clear_disable_add_block_flag();
+        result
+    }
+
+    /// Clears the indexes and re-applies the genesis block, unless a reindex has already been started. Returns the
+    /// number of blocks that were re-applied.
+    fn start_reindex(&self) -> Result<u64, ChainStorageError> {
+        let mut db = self.db_write_access()?;
+        if db.fetch_reindex_checkpoint()?.is_some() {
+            return Ok(0);
+        }
+        let metadata = db.fetch_chain_metadata()?;
+        if metadata.is_pruned_node() {
+            return Err(ChainStorageError::InvalidOperation(
+                "A pruned node cannot be reindexed, because the outputs spent below its pruned height have been \
+                 deleted"
+                    .to_string(),
+            ));
+        }
+        info!(
+            target: LOG_TARGET,
+            "Reindex: clearing the block body indexes and re-applying the stored blocks up to height {}",
+            metadata.best_block_height()
+        );
+
+        let genesis_block = fetch_stored_block(&*db, 0)?;
+        *self.smt_write_access()? = OutputSmt::new();
+        let mut txn = DbTransaction::new();
+        txn.clear_block_body_indexes();
+        reapply_stored_block(&mut txn, genesis_block, self.smt());
+        txn.set_reindex_checkpoint(Some(ReindexCheckpoint {
+            target_hash: *metadata.best_block_hash(),
+            target_height: metadata.best_block_height(),
+        }));
+        if let Err(e) = db.write(txn) {
+            *self.smt_write_access()? = db.calculate_tip_smt()?;
+            return Err(e);
+        }
+        Ok(1)
+    }
+
+    /// Re-applies the stored blocks above the chain tip up to the end of the reindex that has been started, if any.
+    /// Returns the number of blocks that were re-applied.
+    fn resume_reindex<F: Fn(u64, u64)>(&self, on_progress: &F) -> Result<u64, ChainStorageError> {
+        let Some(checkpoint) = self.db_read_access()?.fetch_reindex_checkpoint()? else {
+            return Ok(0);
+        };
+        let mut num_blocks = 0;
+        loop {
+            let mut db = self.db_write_access()?;
+            let metadata = db.fetch_chain_metadata()?;
+            if metadata.best_block_height() >= checkpoint.target_height {
+                let mut txn = DbTransaction::new();
+                txn.set_reindex_checkpoint(None);
+                db.write(txn)?;
+                info!(
+                    target: LOG_TARGET,
+                    "Reindex: completed at height {}",
+                    metadata.best_block_height()
+                );
+                return Ok(num_blocks);
+            }
+
+            let height = metadata.best_block_height() + 1;
+            let block = fetch_stored_block(&*db, height)?;
+            if let Err(e) = self
+                .validators
+                .block
+                .validate_body_with_metadata(&*db, &block, &metadata, self.smt())
+            {
+                error!(
+                    target: LOG_TARGET,
+                    "Reindex: block #{} ({}) failed validation: {}. It and the stored blocks above it are deleted.",
+                    height,
+                    block.hash(),
+                    e
+                );
+                let mut txn = DbTransaction::new();
+                for stored_height in (height..=db.fetch_last_header()?.height).rev() {
+                    txn.delete_stored_block_body(*db.fetch_chain_header_by_height(stored_height)?.hash())
+                        .delete_header(stored_height);
+                }
+                txn.set_reindex_checkpoint(None);
+                db.write(txn)?;
+                *self.smt_write_access()? = db.calculate_tip_smt()?;
+                return Err(e.into());
+            }
+
+            let mut txn = DbTransaction::new();
+            reapply_stored_block(&mut txn, block, self.smt());
+            if let Err(e) = db.write(txn) {
+                *self.smt_write_access()? = db.calculate_tip_smt()?;
+                return Err(e);
+            }
+            num_blocks += 1;
+            if height % 1000 == 0 {
+                info!(
+                    target: LOG_TARGET,
+                    "Reindex: re-applied block #{} of {}", height, checkpoint.target_height
+                );
+            }
+            // The lock is not held while the caller is told, so that it is not poisoned if the caller panics
+            drop(db);
+            on_progress(height, checkpoint.target_height);
+        }
+    }
+
+    /// Returns the reindex that has not completed yet, if any
+    pub fn fetch_reindex_checkpoint(&self) -> Result<Option<ReindexCheckpoint>, ChainStorageError> {
+        let db = self.db_read_access()?;
+        db.fetch_reindex_checkpoint()
+    }
+
     /// This method will compare all chain tips the node currently knows about. This includes
     /// all tips in the orphan pool and the main active chain. It will swap the main active
     /// chain to the highest pow chain
@@ -1261,6 +1418,11 @@ where B: BlockchainBackend
         Ok(db.fetch_horizon_data()?.unwrap_or_default())
     }
 
+    pub fn fetch_horizon_sync_checkpoint(&self) -> Result<Option<HorizonSyncCheckpoint>, ChainStorageError> {
+        let db = self.db_read_access()?;
+        db.fetch_horizon_sync_checkpoint()
+    }
+
     pub fn get_stats(&self) -> Result<DbBasicStats, ChainStorageError> {
         let lock = self.db_read_access()?;
         lock.get_stats()
@@ -1290,6 +1452,43 @@ where B: BlockchainBackend
         db.fetch_active_validator_nodes(height)
     }
 
+    /// Returns the validator node set of the epoch that `height` is in, with a Merkle proof for each validator node
+    /// against the validator node Merkle root in the header of the first block of the epoch
+    pub fn fetch_validator_node_set(&self, height: u64) -> Result<ValidatorNodeSet, ChainStorageError> {
+        let constants = self.consensus_manager.consensus_constants(height);
+        let epoch = constants.block_height_to_epoch(height);
+        let epoch_height = constants.epoch_to_block_height(epoch);
+        let db = self.db_read_access()?;
+        let header = fetch_header(&*db, epoch_height)?;
+        let nodes = db.fetch_active_validator_nodes(epoch_height)?;
+        drop(db);
+
+        let vn_bmt = ValidatorNodeBMT::create(nodes.iter().map(validator_node_leaf_hash).collect());
+        // A registration that was spent after the start of the epoch is no longer in the set that is read back, so the
+        // root is compared to make sure that every proof is valid against the header
+        if !nodes.is_empty() && FixedHash::try_from(vn_bmt.get_merkle_root())? != header.validator_node_mr {
+            return Err(ChainStorageError::InvalidQuery(format!(
+                "The validator node set of epoch {} can no longer be reconstructed to match the header at height {}",
+                epoch.as_u64(),
+                epoch_height
+            )));
+        }
+        let members = nodes
+            .into_iter()
+            .enumerate()
+            .map(|(i, (public_key, shard_key))| {
+                let merkle_proof = ValidatorNodeBmtProof::generate_proof(&vn_bmt, i)
+                    .map_err(|e| ChainStorageError::InvalidQuery(e.to_string()))?;
+                Ok(ValidatorNodeSetMember {
+                    public_key,
+                    shard_key,
+                    merkle_proof,
+                })
+            })
+            .collect::<Result<_, ChainStorageError>>()?;
+        Ok(ValidatorNodeSet { epoch, header, members })
+    }
+
     pub fn fetch_template_registrations<T: RangeBounds<u64>>(
         &self,
         range: T,
@@ -1470,18 +1669,19 @@ pub fn calculate_mmr_roots<T: BlockchainBackend>(
 }
 
 pub fn calculate_validator_node_mr(validator_nodes: &[(PublicKey, [u8; 32])]) -> tari_mmr::Hash {
-    fn hash_node((pk, s): &(PublicKey, [u8; 32])) -> Vec<u8> {
-        DomainSeparatedConsensusHasher::<TransactionHashDomain, Blake2b<U32>>::new("validator_node")
-            .chain(pk)
-            .chain(s)
-            .finalize()
-            .to_vec()
-    }
-
-    let vn_bmt = ValidatorNodeBMT::create(validator_nodes.iter().map(hash_node).collect::<Vec<_>>());
+    let vn_bmt = ValidatorNodeBMT::create(validator_nodes.iter().map(validator_node_leaf_hash).collect::<Vec<_>>());
     vn_bmt.get_merkle_root()
 }
 
+/// The leaf of the validator node Merkle tree for a validator node's public key and shard key
+pub fn validator_node_leaf_hash((pk, s): &(PublicKey, [u8; 32])) -> Vec<u8> {
+    DomainSeparatedConsensusHasher::<TransactionHashDomain, Blake2b<U32>>::new("validator_node")
+        .chain(pk)
+        .chain(s)
+        .finalize()
+        .to_vec()
+}
+
 pub fn fetch_header<T: BlockchainBackend>(db: &T, block_num: u64) -> Result<BlockHeader, ChainStorageError> {
     fetch!(db, block_num, HeaderHeight)
 }
@@ -1625,6 +1825,24 @@ fn insert_best_block(
     Ok(())
 }
 
+/// Re-applies a stored block in place, rebuilding the indexes of its body, and moves the chain tip up to it
+fn reapply_stored_block(txn: &mut DbTransaction, block: Arc<ChainBlock>, smt: Arc<RwLock<OutputSmt>>) {
+    let height = block.height();
+    let block_hash = *block.hash();
+    let accumulated_difficulty = block.accumulated_data().total_accumulated_difficulty;
+    let expected_prev_best_block = block.header().prev_hash;
+    let timestamp = block.header().timestamp().as_u64();
+    txn.delete_stored_block_body(block_hash)
+        .insert_tip_block_body(block, smt)
+        .set_best_block(
+            height,
+            block_hash,
+            accumulated_difficulty,
+            expected_prev_best_block,
+            timestamp,
+        );
+}
+
 fn store_pruning_horizon<T: BlockchainBackend>(db: &mut T, pruning_horizon: u64) -> Result<(), ChainStorageError> {
     let mut txn = DbTransaction::new();
     txn.set_pruning_horizon(pruning_horizon);
@@ -1659,6 +1877,25 @@ pub fn fetch_target_difficulty_for_next_block<T: BlockchainBackend>(
     Ok(target_difficulties)
 }
 
+/// Fetches a block from its stored header and block body alone. Unlike `fetch_block`, the block does not need to be
+/// in the indexed chain, and the inputs are left compact.
+fn fetch_stored_block<T: BlockchainBackend>(db: &T, height: u64) -> Result<Arc<ChainBlock>, ChainStorageError> {
+    let (header, accumulated_data) = db.fetch_chain_header_by_height(height)?.into_parts();
+    let block = header
+        .into_builder()
+        .add_inputs(db.fetch_inputs_in_block(&accumulated_data.hash)?)
+        .add_outputs(db.fetch_outputs_in_block(&accumulated_data.hash)?)
+        .add_kernels(db.fetch_kernels_in_block(&accumulated_data.hash)?)
+        .build();
+    let block = ChainBlock::try_construct(Arc::new(block), accumulated_data).ok_or_else(|| {
+        ChainStorageError::DataInconsistencyDetected {
+            function: "fetch_stored_block",
+            details: format!("The stored block body at height {} does not match its header", height),
+        }
+    })?;
+    Ok(Arc::new(block))
+}
+
 fn fetch_block<T: BlockchainBackend>(db: &T, height: u64, compact: bool) -> Result<HistoricalBlock, ChainStorageError> {
     let mark = Instant::now();
     let (tip_height, _is_pruned) = check_for_valid_height(db, height)?;
@@ -2477,6 +2714,7 @@ fn cleanup_orphans<T: BlockchainBackend>(db: &mut T, orphan_storage_capacity: us
 fn prune_database_if_needed<T: BlockchainBackend>(
     db: &mut T,
     pruning_horizon: u64,
+    output_retention_window: u64,
     pruning_interval: u64,
 ) -> Result<(), ChainStorageError> {
     let metadata = db.fetch_chain_metadata()?;
@@ -2484,12 +2722,19 @@ fn prune_database_if_needed<T: BlockchainBackend>(
         return Ok(());
     }
 
-    let prune_to_height_target = metadata.best_block_height().saturating_sub(pruning_horizon);
+    // Spent outputs can only be pruned together with the inputs that spend them, so whole blocks in the output
+    // retention window are kept
+    let prune_to_height_target = metadata
+        .best_block_height()
+        .saturating_sub(pruning_horizon)
+        .saturating_sub(output_retention_window);
     debug!(
         target: LOG_TARGET,
-        "Blockchain height: {}, pruning horizon: {}, pruned height: {}, prune to height target: {}, pruning interval: {}",
+        "Blockchain height: {}, pruning horizon: {}, output retention window: {}, pruned height: {}, prune to height \
+         target: {}, pruning interval: {}",
         metadata.best_block_height(),
         metadata.pruning_horizon(),
+        output_retention_window,
         metadata.pruned_height(),
         prune_to_height_target,
         pruning_interval,
@@ -2627,6 +2872,7 @@ mod test {
                 create_main_chain,
                 create_new_blockchain,
                 create_orphan_chain,
+                create_store_with_consensus_and_validators_and_config,
                 create_test_blockchain_db,
                 update_block_and_smt,
                 TempDatabase,
@@ -2667,6 +2913,40 @@ mod test {
         }
     }
 
+    #[tokio::test]
+    async fn it_keeps_the_output_retention_window_when_pruning() {
+        let rules = ConsensusManager::builder(Network::LocalNet).build().unwrap();
+        let validators = Validators::new(
+            MockValidator::new(true),
+            MockValidator::new(true),
+            MockValidator::new(true),
+        );
+        let config = BlockchainDatabaseConfig {
+            pruning_horizon: 3,
+            pruning_interval: 1,
+            output_retention_window: 2,
+            ..Default::default()
+        };
+        let smt = Arc::new(RwLock::new(OutputSmt::new()));
+        let db = create_store_with_consensus_and_validators_and_config(rules, validators, config, smt);
+        create_main_chain(&db, &[
+            ("A->GB", 1, 120),
+            ("B->A", 1, 120),
+            ("C->B", 1, 120),
+            ("D->C", 1, 120),
+            ("E->D", 1, 120),
+            ("F->E", 1, 120),
+            ("G->F", 1, 120),
+        ])
+        .await;
+        let metadata = db.get_chain_metadata().unwrap();
+        assert_eq!(metadata.best_block_height(), 7);
+        // Without the retention window, the node would have been pruned to height 4
+        assert_eq!(metadata.pruned_height(), 2);
+        // Horizon sync only needs the configured horizon
+        assert_eq!(metadata.pruning_horizon(), 3);
+    }
+
     mod get_orphan_link_main_chain {
         use super::*;
 
@@ -32,7 +32,7 @@ use tari_utilities::hex::Hex;
 
 use crate::{
     blocks::{Block, BlockHeader, BlockHeaderAccumulatedData, ChainBlock, ChainHeader, UpdateBlockAccumulatedData},
-    chain_storage::{error::ChainStorageError, HorizonData, Reorg},
+    chain_storage::{error::ChainStorageError, HorizonData, HorizonSyncCheckpoint, ReindexCheckpoint, Reorg},
     transactions::transaction_components::{OutputType, TransactionKernel, TransactionOutput},
     OutputSmt,
 };
@@ -158,6 +158,20 @@ impl DbTransaction {
         self
     }
 
+    /// Deletes the stored kernels, outputs and inputs of a block, without touching the indexes built from them
+    pub fn delete_stored_block_body(&mut self, block_hash: BlockHash) -> &mut Self {
+        self.operations
+            .push(WriteOperation::DeleteStoredBlockBody { block_hash });
+        self
+    }
+
+    /// Clears the indexes built from the stored block bodies, the block accumulated data and the UTXO set statistics,
+    /// so that they can be rebuilt by re-applying the stored blocks
+    pub fn clear_block_body_indexes(&mut self) -> &mut Self {
+        self.operations.push(WriteOperation::ClearBlockBodyIndexes);
+        self
+    }
+
     pub fn update_block_accumulated_data(
         &mut self,
         header_hash: HashOutput,
@@ -259,6 +273,20 @@ impl DbTransaction {
         self
     }
 
+    /// Records or, if `None`, clears the progress of an interrupted horizon sync
+    pub fn set_horizon_sync_checkpoint(&mut self, checkpoint: Option<HorizonSyncCheckpoint>) -> &mut Self {
+        self.operations
+            .push(WriteOperation::SetHorizonSyncCheckpoint { checkpoint });
+        self
+    }
+
+    /// Records or, if `None`, clears a reindex that has not completed yet
+    pub fn set_reindex_checkpoint(&mut self, checkpoint: Option<ReindexCheckpoint>) -> &mut Self {
+        self.operations
+            .push(WriteOperation::SetReindexCheckpoint { checkpoint });
+        self
+    }
+
     pub(crate) fn operations(&self) -> &[WriteOperation] {
         &self.operations
     }
@@ -333,6 +361,10 @@ pub enum WriteOperation {
     DeleteAllInputsInBlock {
         block_hash: BlockHash,
     },
+    DeleteStoredBlockBody {
+        block_hash: BlockHash,
+    },
+    ClearBlockBodyIndexes,
     SetAccumulatedDataForOrphan(BlockHeaderAccumulatedData),
     SetBestBlock {
         height: u64,
@@ -348,6 +380,12 @@ pub enum WriteOperation {
     SetHorizonData {
         horizon_data: HorizonData,
     },
+    SetHorizonSyncCheckpoint {
+        checkpoint: Option<HorizonSyncCheckpoint>,
+    },
+    SetReindexCheckpoint {
+        checkpoint: Option<ReindexCheckpoint>,
+    },
     InsertReorg {
         reorg: Reorg,
     },
@@ -423,6 +461,8 @@ impl fmt::Display for WriteOperation {
             ),
             DeleteAllKernelsInBlock { block_hash } => write!(f, "Delete kernels in block {}", block_hash),
             DeleteAllInputsInBlock { block_hash } => write!(f, "Delete outputs in block {}", block_hash),
+            DeleteStoredBlockBody { block_hash } => write!(f, "Delete stored block body {}", block_hash),
+            ClearBlockBodyIndexes => write!(f, "Clear block body indexes"),
             SetAccumulatedDataForOrphan(accumulated_data) => {
                 write!(f, "Set accumulated data for orphan {}", accumulated_data)
             },
@@ -445,6 +485,18 @@ impl fmt::Display for WriteOperation {
                 write!(f, "Insert bad block #{} {} for {}", height, hash, reason)
             },
             SetHorizonData { .. } => write!(f, "Set horizon data"),
+            SetHorizonSyncCheckpoint { checkpoint } => match checkpoint {
+                Some(checkpoint) => write!(
+                    f,
+                    "Set horizon sync checkpoint at height {}",
+                    checkpoint.last_block_height
+                ),
+                None => write!(f, "Clear horizon sync checkpoint"),
+            },
+            SetReindexCheckpoint { checkpoint } => match checkpoint {
+                Some(checkpoint) => write!(f, "Set reindex checkpoint to height {}", checkpoint.target_height),
+                None => write!(f, "Clear reindex checkpoint"),
+            },
             InsertReorg { .. } => write!(f, "Insert reorg"),
             ClearAllReorgs => write!(f, "Clear all reorgs"),
         }
@@ -0,0 +1,23 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use serde::{Deserialize, Serialize};
+use tari_common_types::types::{Commitment, HashOutput};
+
+/// The output sync progress of an interrupted horizon sync. Kernels are committed per block and resume from the local
+/// kernel MMR size, but the outputs can only be verified against the output SMT once all of them have been received,
+/// so the progress made towards that is recorded here for the next attempt to resume from.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HorizonSyncCheckpoint {
+    /// The hash of the header the interrupted sync was syncing to
+    pub horizon_header_hash: HashOutput,
+    /// The hash of the last block whose outputs and spent commitments have all been received and committed
+    pub last_block_hash: HashOutput,
+    pub last_block_height: u64,
+    /// The number of unspent outputs that have been range proof verified and committed
+    pub outputs_verified: u64,
+    /// The number of spent commitments that have been received
+    pub spent_received: u64,
+    /// The outputs spent within the synced range, which are only pruned once the output SMT has been verified
+    pub spent_outputs: Vec<(HashOutput, Commitment)>,
+}
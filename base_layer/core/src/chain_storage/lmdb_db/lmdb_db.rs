@@ -97,10 +97,13 @@ use crate::{
         DbBasicStats,
         DbSize,
         HorizonData,
+        HorizonSyncCheckpoint,
         InputMinedInfo,
         MmrTree,
+        ReindexCheckpoint,
         Reorg,
         TemplateRegistrationEntry,
+        UtxoSetStats,
         ValidatorNodeEntry,
     },
     consensus::{ConsensusConstants, ConsensusManager},
@@ -413,6 +416,12 @@ impl LMDBDatabase {
                 DeleteAllInputsInBlock { block_hash } => {
                     self.delete_all_inputs_in_block(&write_txn, block_hash)?;
                 },
+                DeleteStoredBlockBody { block_hash } => {
+                    self.delete_stored_block_body(&write_txn, block_hash)?;
+                },
+                ClearBlockBodyIndexes => {
+                    self.clear_block_body_indexes(&write_txn)?;
+                },
                 SetBestBlock {
                     height,
                     hash,
@@ -479,6 +488,20 @@ impl LMDBDatabase {
                         &MetadataValue::HorizonData(horizon_data.clone()),
                     )?;
                 },
+                SetHorizonSyncCheckpoint { checkpoint } => {
+                    self.set_metadata(
+                        &write_txn,
+                        MetadataKey::HorizonSyncCheckpoint,
+                        &MetadataValue::HorizonSyncCheckpoint(checkpoint.clone()),
+                    )?;
+                },
+                SetReindexCheckpoint { checkpoint } => {
+                    self.set_metadata(
+                        &write_txn,
+                        MetadataKey::ReindexCheckpoint,
+                        &MetadataValue::ReindexCheckpoint(checkpoint.clone()),
+                    )?;
+                },
                 InsertBadBlock { hash, height, reason } => {
                     self.insert_bad_block_and_cleanup(&write_txn, hash, *height, reason.to_string())?;
                 },
@@ -948,6 +971,7 @@ impl LMDBDatabase {
         debug!(target: LOG_TARGET, "Deleted {} outputs...", output_rows.len());
         let inputs = lmdb_delete_keys_starting_with::<TransactionInputRowData>(txn, &self.inputs_db, block_hash)?;
         debug!(target: LOG_TARGET, "Deleted {} input(s)...", inputs.len());
+        let mut utxo_set_stats = fetch_utxo_set_stats(txn, &self.metadata_db)?;
 
         for utxo in &output_rows {
             trace!(target: LOG_TARGET, "Deleting UTXO `{}`", to_hex(utxo.hash.as_slice()));
@@ -968,6 +992,7 @@ impl LMDBDatabase {
             if utxo.output.is_burned() {
                 continue;
             }
+            utxo_set_stats.remove_output(&utxo.output, utxo.mined_height)?;
             let smt_key = NodeKey::try_from(utxo.output.commitment.as_bytes())?;
             match output_smt.delete(&smt_key)? {
                 DeleteResult::Deleted(_value_hash) => {},
@@ -1013,6 +1038,7 @@ impl LMDBDatabase {
                 }
             })?;
 
+            utxo_set_stats.add_output(&utxo_mined_info.output, utxo_mined_info.mined_height)?;
             let rp_hash = match utxo_mined_info.output.proof {
                 Some(proof) => proof.hash(),
                 None => FixedHash::zero(),
@@ -1049,6 +1075,11 @@ impl LMDBDatabase {
                 "utxo_commitment_index",
             )?;
         }
+        self.set_metadata(
+            txn,
+            MetadataKey::UtxoSetStats,
+            &MetadataValue::UtxoSetStats(utxo_set_stats),
+        )?;
         Ok(())
     }
 
@@ -1214,6 +1245,7 @@ impl LMDBDatabase {
         }
 
         let (inputs, outputs, kernels) = body.dissolve();
+        let mut utxo_set_stats = fetch_utxo_set_stats(txn, &self.metadata_db)?;
 
         let data = if header.height == 0 {
             BlockAccumulatedData::default()
@@ -1264,6 +1296,7 @@ impl LMDBDatabase {
                     );
                     return Err(e.into());
                 }
+                utxo_set_stats.add_output(&output, header.height)?;
             }
 
             let output_hash = output.hash();
@@ -1308,6 +1341,15 @@ impl LMDBDatabase {
                     return Err(ChainStorageError::UnspendableInput);
                 },
             };
+            let spent_output_hash = input_with_output_data.output_hash();
+            let spent_output = self
+                .fetch_output_in_txn(txn, spent_output_hash.as_slice())?
+                .ok_or_else(|| ChainStorageError::ValueNotFound {
+                    entity: "UTXO",
+                    field: "hash",
+                    value: spent_output_hash.to_hex(),
+                })?;
+            utxo_set_stats.remove_output(&spent_output.output, spent_output.mined_height)?;
 
             let features = input_with_output_data.features()?;
             if let Some(vn_reg) = features
@@ -1341,6 +1383,11 @@ impl LMDBDatabase {
             header.height,
             &BlockAccumulatedData::new(kernel_mmr.get_pruned_hash_set()?, total_kernel_sum),
         )?;
+        self.set_metadata(
+            txn,
+            MetadataKey::UtxoSetStats,
+            &MetadataValue::UtxoSetStats(utxo_set_stats),
+        )?;
 
         Ok(())
     }
@@ -1463,6 +1510,51 @@ impl LMDBDatabase {
         Ok(())
     }
 
+    /// Deletes the kernel, output and input rows of a block by its hash alone, so that the indexes built from them
+    /// are neither read nor updated
+    fn delete_stored_block_body(
+        &self,
+        txn: &WriteTransaction<'_>,
+        block_hash: &BlockHash,
+    ) -> Result<(), ChainStorageError> {
+        let kernels =
+            lmdb_delete_keys_starting_with::<TransactionKernelRowData>(txn, &self.kernels_db, block_hash.as_slice())?;
+        let outputs =
+            lmdb_delete_keys_starting_with::<TransactionOutputRowData>(txn, &self.utxos_db, block_hash.as_slice())?;
+        let inputs =
+            lmdb_delete_keys_starting_with::<TransactionInputRowData>(txn, &self.inputs_db, block_hash.as_slice())?;
+        debug!(
+            target: LOG_TARGET,
+            "Deleted {} kernel(s), {} output(s) and {} input(s) stored for block {}",
+            kernels.len(),
+            outputs.len(),
+            inputs.len(),
+            block_hash.to_hex()
+        );
+        Ok(())
+    }
+
+    fn clear_block_body_indexes(&self, txn: &WriteTransaction<'_>) -> Result<(), ChainStorageError> {
+        for db in [
+            &self.block_accumulated_data_db,
+            &self.txos_hash_to_index_db,
+            &self.kernel_excess_index,
+            &self.kernel_excess_sig_index,
+            &self.utxo_commitment_index,
+            &self.deleted_txo_hash_to_header_index,
+            &self.validator_nodes,
+            &self.validator_nodes_mapping,
+            &self.template_registrations,
+        ] {
+            lmdb_clear(txn, db)?;
+        }
+        self.set_metadata(
+            txn,
+            MetadataKey::UtxoSetStats,
+            &MetadataValue::UtxoSetStats(UtxoSetStats::default()),
+        )
+    }
+
     fn prune_outputs_spent_at_hash(
         &self,
         write_txn: &WriteTransaction<'_>,
@@ -2185,6 +2277,11 @@ impl BlockchainBackend for LMDBDatabase {
         lmdb_len(&txn, &self.utxo_commitment_index)
     }
 
+    fn fetch_utxo_set_stats(&self) -> Result<UtxoSetStats, ChainStorageError> {
+        let txn = self.read_transaction()?;
+        fetch_utxo_set_stats(&txn, &self.metadata_db)
+    }
+
     fn kernel_count(&self) -> Result<usize, ChainStorageError> {
         let txn = self.read_transaction()?;
         lmdb_len(&txn, &self.kernels_db)
@@ -2376,6 +2473,34 @@ impl BlockchainBackend for LMDBDatabase {
         Ok(Some(fetch_horizon_data(&txn, &self.metadata_db)?))
     }
 
+    fn fetch_horizon_sync_checkpoint(&self) -> Result<Option<HorizonSyncCheckpoint>, ChainStorageError> {
+        let txn = self.read_transaction()?;
+        let k = MetadataKey::HorizonSyncCheckpoint;
+        let val: Option<MetadataValue> = lmdb_get(&txn, &self.metadata_db, &k.as_u32())?;
+        match val {
+            Some(MetadataValue::HorizonSyncCheckpoint(checkpoint)) => Ok(checkpoint),
+            None => Ok(None),
+            Some(k) => Err(ChainStorageError::DataInconsistencyDetected {
+                function: "fetch_horizon_sync_checkpoint",
+                details: format!("Received incorrect value {:?} for key horizon sync checkpoint", k),
+            }),
+        }
+    }
+
+    fn fetch_reindex_checkpoint(&self) -> Result<Option<ReindexCheckpoint>, ChainStorageError> {
+        let txn = self.read_transaction()?;
+        let k = MetadataKey::ReindexCheckpoint;
+        let val: Option<MetadataValue> = lmdb_get(&txn, &self.metadata_db, &k.as_u32())?;
+        match val {
+            Some(MetadataValue::ReindexCheckpoint(checkpoint)) => Ok(checkpoint),
+            None => Ok(None),
+            Some(k) => Err(ChainStorageError::DataInconsistencyDetected {
+                function: "fetch_reindex_checkpoint",
+                details: format!("Received incorrect value {:?} for key reindex checkpoint", k),
+            }),
+        }
+    }
+
     fn get_stats(&self) -> Result<DbBasicStats, ChainStorageError> {
         let global = self.env.stat()?;
         let env_info = self.env.info()?;
@@ -2592,6 +2717,21 @@ fn fetch_horizon_data(txn: &ConstTransaction<'_>, db: &Database) -> Result<Horiz
         }),
     }
 }
+
+/// Fetches the UTXO set statistics from the provided metadata db, which are empty before the first block body is added
+fn fetch_utxo_set_stats(txn: &ConstTransaction<'_>, db: &Database) -> Result<UtxoSetStats, ChainStorageError> {
+    let k = MetadataKey::UtxoSetStats;
+    let val: Option<MetadataValue> = lmdb_get(txn, db, &k.as_u32())?;
+    match val {
+        Some(MetadataValue::UtxoSetStats(stats)) => Ok(stats),
+        None => Ok(UtxoSetStats::default()),
+        Some(k) => Err(ChainStorageError::DataInconsistencyDetected {
+            function: "fetch_utxo_set_stats",
+            details: format!("Received incorrect value {:?} for key UTXO set stats", k),
+        }),
+    }
+}
+
 // Fetches the best block hash from the provided metadata db.
 fn fetch_best_block(txn: &ConstTransaction<'_>, db: &Database) -> Result<BlockHash, ChainStorageError> {
     let k = MetadataKey::BestBlock;
@@ -2661,6 +2801,9 @@ enum MetadataKey {
     HorizonData,
     BestBlockTimestamp,
     MigrationVersion,
+    UtxoSetStats,
+    HorizonSyncCheckpoint,
+    ReindexCheckpoint,
 }
 
 impl MetadataKey {
@@ -2681,6 +2824,9 @@ impl fmt::Display for MetadataKey {
             MetadataKey::HorizonData => write!(f, "Database info"),
             MetadataKey::BestBlockTimestamp => write!(f, "Chain tip block timestamp"),
             MetadataKey::MigrationVersion => write!(f, "Migration version"),
+            MetadataKey::UtxoSetStats => write!(f, "UTXO set statistics"),
+            MetadataKey::HorizonSyncCheckpoint => write!(f, "Horizon sync checkpoint"),
+            MetadataKey::ReindexCheckpoint => write!(f, "Reindex checkpoint"),
         }
     }
 }
@@ -2696,6 +2842,9 @@ enum MetadataValue {
     HorizonData(HorizonData),
     BestBlockTimestamp(u64),
     MigrationVersion(u64),
+    UtxoSetStats(UtxoSetStats),
+    HorizonSyncCheckpoint(Option<HorizonSyncCheckpoint>),
+    ReindexCheckpoint(Option<ReindexCheckpoint>),
 }
 
 impl fmt::Display for MetadataValue {
@@ -2709,12 +2858,21 @@ impl fmt::Display for MetadataValue {
             MetadataValue::HorizonData(_) => write!(f, "Horizon data"),
             MetadataValue::BestBlockTimestamp(timestamp) => write!(f, "Chain tip block timestamp is {}", timestamp),
             MetadataValue::MigrationVersion(n) => write!(f, "Migration version {}", n),
+            MetadataValue::UtxoSetStats(stats) => write!(f, "UTXO set statistics for {} outputs", stats.count),
+            MetadataValue::HorizonSyncCheckpoint(Some(checkpoint)) => {
+                write!(f, "Horizon sync checkpoint at height {}", checkpoint.last_block_height)
+            },
+            MetadataValue::HorizonSyncCheckpoint(None) => write!(f, "No horizon sync checkpoint"),
+            MetadataValue::ReindexCheckpoint(Some(checkpoint)) => {
+                write!(f, "Reindex checkpoint to height {}", checkpoint.target_height)
+            },
+            MetadataValue::ReindexCheckpoint(None) => write!(f, "No reindex checkpoint"),
         }
     }
 }
 
 fn run_migrations(db: &LMDBDatabase) -> Result<(), ChainStorageError> {
-    const MIGRATION_VERSION: u64 = 1;
+    const MIGRATION_VERSION: u64 = 2;
     let txn = db.read_transaction()?;
 
     let k = MetadataKey::MigrationVersion;
@@ -2731,6 +2889,9 @@ fn run_migrations(db: &LMDBDatabase) -> Result<(), ChainStorageError> {
 
     if n < MIGRATION_VERSION {
         // Add migrations here
+        if n < 2 {
+            rebuild_utxo_set_stats(db)?;
+        }
         info!(target: LOG_TARGET, "Migrated database to version {}", MIGRATION_VERSION);
         let txn = db.write_transaction()?;
         lmdb_replace(
@@ -2745,3 +2906,25 @@ fn run_migrations(db: &LMDBDatabase) -> Result<(), ChainStorageError> {
 
     Ok(())
 }
+
+/// Builds the UTXO set statistics of a database from before they were maintained when adding blocks
+fn rebuild_utxo_set_stats(db: &LMDBDatabase) -> Result<(), ChainStorageError> {
+    info!(target: LOG_TARGET, "Building UTXO set statistics");
+    let txn = db.write_transaction()?;
+    let output_hashes = lmdb_filter_map_values(&txn, &db.utxo_commitment_index, |hash: HashOutput| Some(hash))?;
+    let mut stats = UtxoSetStats::default();
+    for output_hash in output_hashes {
+        let output =
+            db.fetch_output_in_txn(&txn, output_hash.as_slice())?
+                .ok_or_else(|| ChainStorageError::ValueNotFound {
+                    entity: "UTXO",
+                    field: "hash",
+                    value: output_hash.to_hex(),
+                })?;
+        stats.add_output(&output.output, output.mined_height)?;
+    }
+    info!(target: LOG_TARGET, "UTXO set statistics built for {} outputs", stats.count);
+    db.set_metadata(&txn, MetadataKey::UtxoSetStats, &MetadataValue::UtxoSetStats(stats))?;
+    txn.commit()?;
+    Ok(())
+}
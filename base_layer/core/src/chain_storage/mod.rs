@@ -44,6 +44,7 @@ pub use blockchain_database::{
     fetch_header,
     fetch_headers,
     fetch_target_difficulty_for_next_block,
+    validator_node_leaf_hash,
     BlockchainDatabase,
     BlockchainDatabaseConfig,
     MmrRoots,
@@ -67,6 +68,12 @@ pub use error::{ChainStorageError, Optional, OrNotFound};
 mod horizon_data;
 pub use horizon_data::HorizonData;
 
+mod horizon_sync_checkpoint;
+pub use horizon_sync_checkpoint::HorizonSyncCheckpoint;
+
+mod reindex_checkpoint;
+pub use reindex_checkpoint::ReindexCheckpoint;
+
 mod reorg;
 pub use reorg::Reorg;
 
@@ -88,6 +95,15 @@ use tari_common_types::types::HashOutput;
 mod template_registation;
 pub use template_registation::TemplateRegistrationEntry;
 
+mod validator_node_set;
+pub use validator_node_set::{ValidatorNodeSet, ValidatorNodeSetMember};
+
+mod utxo_set_stats;
+pub use utxo_set_stats::{UtxoAgeBucket, UtxoSetStats, UTXO_AGE_BUCKET_SIZE};
+
+pub mod chain_data_export;
+pub use chain_data_export::{MinedKernel, MinedOutput};
+
 #[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq)]
 pub struct ChainTipData {
     pub hash: HashOutput,
@@ -0,0 +1,14 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use serde::{Deserialize, Serialize};
+use tari_common_types::types::HashOutput;
+
+/// A reindex that has not completed yet. The chain tip is moved up with each stored block that is re-applied, in the
+/// same database transaction, so the tip is where an interrupted reindex resumes from and this records where it ends.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReindexCheckpoint {
+    /// The chain tip when the reindex started, which the stored blocks are re-applied up to
+    pub target_hash: HashOutput,
+    pub target_height: u64,
+}
@@ -582,6 +582,136 @@ mod clear_all_pending_headers {
     }
 }
 
+mod reindex {
+    use std::{
+        panic::{self, AssertUnwindSafe},
+        path::Path,
+        sync::RwLock,
+    };
+
+    use tari_test_utils::paths::create_temporary_data_path;
+
+    use super::*;
+    use crate::{
+        chain_storage::{BlockchainDatabaseConfig, Validators},
+        test_helpers::create_consensus_rules,
+        transactions::key_manager::create_memory_db_key_manager,
+        validation::{mocks::MockValidator, DifficultyCalculator},
+        OutputSmt,
+    };
+
+    /// Opens the blockchain database stored at `path`, which is kept when the database is dropped
+    fn open(path: &Path) -> BlockchainDatabase<TempDatabase> {
+        let mut backend = TempDatabase::from_path(path);
+        backend.disable_delete_on_drop();
+        let rules = create_consensus_rules();
+        let validators = Validators::new(
+            MockValidator::new(true),
+            MockValidator::new(true),
+            MockValidator::new(true),
+        );
+        BlockchainDatabase::new(
+            backend,
+            rules.clone(),
+            validators,
+            BlockchainDatabaseConfig::default(),
+            DifficultyCalculator::new(rules, Default::default()),
+            Arc::new(RwLock::new(OutputSmt::new())),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn it_reapplies_the_stored_blocks_in_place() {
+        let db = setup();
+        let key_manager = create_memory_db_key_manager().unwrap();
+        let (blocks, _) = add_many_chained_blocks(5, &db, &key_manager).await;
+        let tip = db.fetch_tip_header().unwrap();
+        let utxo_count = db.utxo_count().unwrap();
+
+        let reported = RwLock::new(Vec::new());
+        let num_blocks = db
+            .reindex(|height, target_height| reported.write().unwrap().push((height, target_height)))
+            .unwrap();
+        // The genesis block is re-applied along with the stored blocks above it
+        assert_eq!(num_blocks, 6);
+        assert_eq!(
+            reported.into_inner().unwrap(),
+            (1..=5).map(|h| (h, 5)).collect::<Vec<_>>()
+        );
+
+        assert_eq!(db.fetch_tip_header().unwrap().hash(), tip.hash());
+        assert_eq!(db.utxo_count().unwrap(), utxo_count);
+        assert!(db.fetch_reindex_checkpoint().unwrap().is_none());
+        for block in blocks {
+            let stored = db.fetch_block(block.header.height, true).unwrap();
+            assert_eq!(stored.hash(), &block.hash());
+        }
+        // The rebuilt indexes and output SMT accept the next block
+        add_many_chained_blocks(1, &db, &key_manager).await;
+    }
+
+    #[tokio::test]
+    async fn it_resumes_an_interrupted_reindex_at_startup() {
+        let path = create_temporary_data_path();
+        let key_manager = create_memory_db_key_manager().unwrap();
+        let db = open(&path);
+        add_many_chained_blocks(5, &db, &key_manager).await;
+        let tip = db.fetch_tip_header().unwrap();
+        let utxo_count = db.utxo_count().unwrap();
+
+        // The node stops while block #3 is being reported
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            db.reindex(|height, _| assert_ne!(height, 3, "interrupted"))
+        }));
+        assert!(result.is_err());
+        assert_eq!(db.fetch_tip_header().unwrap().height(), 3);
+        assert_eq!(db.fetch_reindex_checkpoint().unwrap().unwrap().target_height, 5);
+        drop(db);
+
+        let db = open(&path);
+        assert!(db.fetch_reindex_checkpoint().unwrap().is_none());
+        assert_eq!(db.fetch_tip_header().unwrap().hash(), tip.hash());
+        assert_eq!(db.utxo_count().unwrap(), utxo_count);
+        add_many_chained_blocks(1, &db, &key_manager).await;
+
+        drop(db);
+        // Deletes the database
+        drop(TempDatabase::from_path(&path));
+    }
+}
+
+mod horizon_sync_checkpoint {
+    use tari_common_types::types::{Commitment, FixedHash};
+
+    use super::*;
+    use crate::chain_storage::{DbTransaction, HorizonSyncCheckpoint};
+
+    #[test]
+    fn it_sets_and_clears_the_checkpoint() {
+        let db = setup();
+        assert!(db.fetch_horizon_sync_checkpoint().unwrap().is_none());
+
+        let checkpoint = HorizonSyncCheckpoint {
+            horizon_header_hash: FixedHash::from([1u8; 32]),
+            last_block_hash: FixedHash::from([2u8; 32]),
+            last_block_height: 10,
+            outputs_verified: 25,
+            spent_received: 3,
+            spent_outputs: vec![(FixedHash::from([3u8; 32]), Commitment::default())],
+        };
+        let mut txn = DbTransaction::new();
+        txn.set_horizon_sync_checkpoint(Some(checkpoint.clone()));
+        db.write(txn).unwrap();
+        assert_eq!(db.fetch_horizon_sync_checkpoint().unwrap(), Some(checkpoint));
+
+        let mut txn = DbTransaction::new();
+        txn.set_horizon_sync_checkpoint(None);
+        db.write(txn).unwrap();
+        assert!(db.fetch_horizon_sync_checkpoint().unwrap().is_none());
+    }
+}
+
 mod validator_node_merkle_root {
     use std::convert::TryFrom;
 
@@ -591,7 +721,7 @@ mod validator_node_merkle_root {
 
     use super::*;
     use crate::{
-        chain_storage::calculate_validator_node_mr,
+        chain_storage::{calculate_validator_node_mr, validator_node_leaf_hash},
         transactions::{
             key_manager::create_memory_db_key_manager,
             transaction_components::{OutputFeatures, ValidatorNodeSignature},
@@ -651,5 +781,15 @@ mod validator_node_merkle_root {
 
         let tip = db.fetch_tip_header().unwrap();
         assert_eq!(tip.header().validator_node_mr, merkle_root);
+
+        let vn_set = db.fetch_validator_node_set(tip.height()).unwrap();
+        assert_eq!(vn_set.header.height, consts.epoch_length());
+        assert_eq!(vn_set.members.len(), 1);
+        let member = &vn_set.members[0];
+        assert_eq!(member.shard_key, shard_key);
+        let leaf = validator_node_leaf_hash(&(member.public_key.clone(), member.shard_key));
+        assert!(member
+            .merkle_proof
+            .verify(&vn_set.header.validator_node_mr.to_vec(), leaf));
     }
 }
@@ -0,0 +1,153 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::{collections::BTreeMap, io};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    borsh::SerializedSize,
+    transactions::transaction_components::{OutputType, RangeProofType, TransactionOutput},
+};
+
+/// The number of mined heights in each bucket of the age distribution, roughly a day of blocks
+pub const UTXO_AGE_BUCKET_SIZE: u64 = 720;
+
+/// Statistics of the unspent output set. These are updated as block bodies are added to and removed from the tip, so
+/// they are cheap to fetch.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UtxoSetStats {
+    /// The number of unspent outputs
+    pub count: u64,
+    /// The total serialized size of the unspent outputs in bytes
+    pub total_size: u64,
+    /// The number of unspent outputs per output type byte
+    pub output_types: BTreeMap<u8, u64>,
+    /// The number of unspent outputs with a revealed value, such as coinbases, per decimal order of magnitude of the
+    /// value in µT, i.e. bucket `n` holds the values in `[10^n, 10^(n+1))`. Bucket 0 also holds zero values. Burnt
+    /// outputs never enter the unspent set and are not counted.
+    pub revealed_value_buckets: BTreeMap<u32, u64>,
+    /// The number of unspent outputs per bucket of [UTXO_AGE_BUCKET_SIZE] mined heights
+    pub mined_height_buckets: BTreeMap<u64, u64>,
+}
+
+/// The number of unspent outputs whose age in blocks is in `min_age..=max_age`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UtxoAgeBucket {
+    pub min_age: u64,
+    pub max_age: u64,
+    pub count: u64,
+}
+
+impl UtxoSetStats {
+    /// Adds an output mined at `mined_height` to the unspent set
+    pub fn add_output(&mut self, output: &TransactionOutput, mined_height: u64) -> Result<(), io::Error> {
+        self.count += 1;
+        self.total_size += output.get_serialized_size()? as u64;
+        *self
+            .output_types
+            .entry(output.features.output_type.as_byte())
+            .or_default() += 1;
+        if let Some(bucket) = revealed_value_bucket(output) {
+            *self.revealed_value_buckets.entry(bucket).or_default() += 1;
+        }
+        *self
+            .mined_height_buckets
+            .entry(mined_height / UTXO_AGE_BUCKET_SIZE)
+            .or_default() += 1;
+        Ok(())
+    }
+
+    /// Removes an output mined at `mined_height` from the unspent set, i.e. when it is spent or its block is rewound
+    pub fn remove_output(&mut self, output: &TransactionOutput, mined_height: u64) -> Result<(), io::Error> {
+        self.count = self.count.saturating_sub(1);
+        self.total_size = self.total_size.saturating_sub(output.get_serialized_size()? as u64);
+        decrement(&mut self.output_types, output.features.output_type.as_byte());
+        if let Some(bucket) = revealed_value_bucket(output) {
+            decrement(&mut self.revealed_value_buckets, bucket);
+        }
+        decrement(&mut self.mined_height_buckets, mined_height / UTXO_AGE_BUCKET_SIZE);
+        Ok(())
+    }
+
+    /// The number of unspent outputs per output type. Output types unknown to this version are left out.
+    pub fn output_type_counts(&self) -> Vec<(OutputType, u64)> {
+        self.output_types
+            .iter()
+            .filter_map(|(byte, count)| OutputType::from_byte(*byte).map(|t| (t, *count)))
+            .collect()
+    }
+
+    /// The age distribution of the unspent outputs relative to `tip_height`, youngest first
+    pub fn age_distribution(&self, tip_height: u64) -> Vec<UtxoAgeBucket> {
+        self.mined_height_buckets
+            .iter()
+            .rev()
+            .map(|(bucket, count)| {
+                let first_height = bucket * UTXO_AGE_BUCKET_SIZE;
+                let last_height = (first_height + UTXO_AGE_BUCKET_SIZE - 1).min(tip_height);
+                UtxoAgeBucket {
+                    min_age: tip_height.saturating_sub(last_height),
+                    max_age: tip_height.saturating_sub(first_height),
+                    count: *count,
+                }
+            })
+            .collect()
+    }
+}
+
+fn revealed_value_bucket(output: &TransactionOutput) -> Option<u32> {
+    if output.features.range_proof_type != RangeProofType::RevealedValue {
+        return None;
+    }
+    Some(output.minimum_value_promise.as_u64().checked_ilog10().unwrap_or(0))
+}
+
+fn decrement<K: Ord>(map: &mut BTreeMap<K, u64>, key: K) {
+    if let Some(count) = map.get_mut(&key) {
+        *count = count.saturating_sub(1);
+        if *count == 0 {
+            map.remove(&key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::transactions::{tari_amount::MicroMinotari, transaction_components::OutputFeatures};
+
+    #[test]
+    fn it_adds_and_removes_outputs() {
+        let standard = TransactionOutput::default();
+        let mut coinbase = TransactionOutput::default();
+        coinbase.features = OutputFeatures::create_coinbase(10, None, RangeProofType::RevealedValue);
+        coinbase.minimum_value_promise = MicroMinotari::from(2_500_000);
+
+        let mut stats = UtxoSetStats::default();
+        stats.add_output(&standard, 5).unwrap();
+        stats.add_output(&coinbase, 1000).unwrap();
+        assert_eq!(stats.count, 2);
+        assert_eq!(stats.output_type_counts(), vec![
+            (OutputType::Standard, 1),
+            (OutputType::Coinbase, 1)
+        ]);
+        assert_eq!(stats.revealed_value_buckets.get(&6), Some(&1));
+        assert_eq!(stats.age_distribution(1000), vec![
+            UtxoAgeBucket {
+                min_age: 0,
+                max_age: 280,
+                count: 1
+            },
+            UtxoAgeBucket {
+                min_age: 281,
+                max_age: 1000,
+                count: 1
+            },
+        ]);
+
+        stats.remove_output(&coinbase, 1000).unwrap();
+        stats.remove_output(&standard, 5).unwrap();
+        assert_eq!(stats, UtxoSetStats::default());
+    }
+}
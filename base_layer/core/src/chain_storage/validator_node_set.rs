@@ -0,0 +1,25 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use tari_common_types::{epoch::VnEpoch, types::PublicKey};
+
+use crate::{blocks::BlockHeader, ValidatorNodeBmtProof};
+
+/// The validator nodes that are registered in an epoch. The header of the first block of the epoch commits to them in
+/// its validator node Merkle root.
+#[derive(Debug, Clone)]
+pub struct ValidatorNodeSet {
+    pub epoch: VnEpoch,
+    /// The header of the first block of the epoch
+    pub header: BlockHeader,
+    pub members: Vec<ValidatorNodeSetMember>,
+}
+
+/// A validator node in a [ValidatorNodeSet], with the proof that it is a leaf of the header's validator node Merkle
+/// root. The leaf is [crate::chain_storage::validator_node_leaf_hash] of the public key and shard key.
+#[derive(Debug, Clone)]
+pub struct ValidatorNodeSetMember {
+    pub public_key: PublicKey,
+    pub shard_key: [u8; 32],
+    pub merkle_proof: ValidatorNodeBmtProof,
+}
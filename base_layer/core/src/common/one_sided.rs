@@ -27,9 +27,9 @@ use tari_comms::types::CommsDHKE;
 use tari_crypto::{
     hash_domain,
     hashing::{DomainSeparatedHash, DomainSeparatedHasher},
-    keys::SecretKey as SKtrait,
+    keys::{PublicKey as PKtrait, SecretKey as SKtrait},
 };
-use tari_hashing::WalletOutputEncryptionKeysDomain;
+use tari_hashing::{KeyManagerTransactionsHashDomain, WalletOutputEncryptionKeysDomain};
 use tari_utilities::{byte_array::ByteArrayError, ByteArray};
 
 hash_domain!(
@@ -87,6 +87,19 @@ pub fn shared_secret_to_output_spending_key(shared_secret: &CommsDHKE) -> Result
     )
 }
 
+/// Generate the script spending key of a stealth one-sided output from its commitment mask and the recipient's public
+/// spend key
+pub fn stealth_address_script_spending_key(
+    commitment_mask: &PrivateKey,
+    spend_key: &PublicKey,
+) -> Result<PublicKey, ByteArrayError> {
+    let hash = DomainSeparatedHasher::<Blake2b<U64>, KeyManagerTransactionsHashDomain>::new_with_label("script key")
+        .chain(commitment_mask.as_bytes())
+        .finalize();
+    let private_key = PrivateKey::from_uniform_bytes(hash.as_ref())?;
+    Ok(spend_key + &PublicKey::from_secret_key(&private_key))
+}
+
 /// Stealth address domain separated hasher using Diffie-Hellman shared secret
 pub fn diffie_hellman_stealth_domain_hasher(diffie_hellman: CommsDHKE) -> DomainSeparatedHash<Blake2b<U64>> {
     WalletHasher::new_with_label("stealth_address")
@@ -34,6 +34,7 @@ use tari_utilities::epoch_time::EpochTime;
 use crate::{
     borsh::SerializedSize,
     consensus::network::NetworkConsensus,
+    covenants::CovenantVersion,
     proof_of_work::{Difficulty, PowAlgorithm},
     transactions::{
         tari_amount::{uT, MicroMinotari},
@@ -97,8 +98,15 @@ pub struct ConsensusConstants {
     transaction_weight: TransactionWeight,
     /// Maximum byte size of TariScript
     max_script_byte_size: usize,
+    /// The execution budget of input scripts. Scripts are not metered if this is not set.
+    script_execution_budget: Option<ScriptExecutionBudget>,
     /// Maximum byte size of encrypted data
     max_extra_encrypted_data_byte_size: usize,
+    /// The application identifiers that outputs may carry application data for. Application data is not permitted if
+    /// this is not set.
+    permitted_application_ids: Option<RangeInclusive<u32>>,
+    /// Maximum byte size of an application data payload
+    max_application_data_byte_size: usize,
     /// Range of valid transaction input versions
     input_version_range: RangeInclusive<TransactionInputVersion>,
     /// Range of valid transaction output (and features) versions
@@ -130,6 +138,16 @@ pub struct OutputVersionRange {
     pub outputs: RangeInclusive<TransactionOutputVersion>,
     pub features: RangeInclusive<OutputFeaturesVersion>,
     pub opcode: RangeInclusive<OpcodeVersion>,
+    pub covenant: RangeInclusive<CovenantVersion>,
+}
+
+/// The execution budget that an input script receives
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScriptExecutionBudget {
+    /// The budget every input script receives, regardless of its size
+    pub base: u64,
+    /// The additional budget an input script receives per serialized byte
+    pub per_byte: u64,
 }
 
 /// All V0 for Inputs, Outputs + Features, Kernels
@@ -144,6 +162,7 @@ fn version_zero() -> (
         outputs: TransactionOutputVersion::V0..=TransactionOutputVersion::V0,
         features: OutputFeaturesVersion::V0..=OutputFeaturesVersion::V0,
         opcode: OpcodeVersion::V0..=OpcodeVersion::V0,
+        covenant: CovenantVersion::V0..=CovenantVersion::V0,
     };
 
     (input_version_range, output_version_range, kernel_version_range)
@@ -161,6 +180,12 @@ const PRE_MINE_VALUE: u64 = 0; // 6_030_157_777_181_012;
 const INITIAL_EMISSION: MicroMinotari = MicroMinotari(13_952_877_857);
 const ESMERALDA_INITIAL_EMISSION: MicroMinotari = INITIAL_EMISSION;
 pub const MAINNET_PRE_MINE_VALUE: MicroMinotari = MicroMinotari((21_000_000_000 - 14_700_000_000) * 1_000_000);
+const SCRIPT_EXECUTION_BUDGET: ScriptExecutionBudget = ScriptExecutionBudget {
+    base: 1_000,
+    per_byte: 8,
+};
+/// Application identifiers below this range are reserved for protocols defined by Tari
+const CUSTOM_APPLICATION_IDS: RangeInclusive<u32> = 0x0001_0000..=u32::MAX;
 
 // The target time used by the difficulty adjustment algorithms, their target time is the target block interval * PoW
 // algorithm count
@@ -277,11 +302,34 @@ impl ConsensusConstants {
         self.max_script_byte_size
     }
 
+    /// The execution budget for a script of the given serialized size, or None if scripts are not metered at this
+    /// height. Script bytes are paid for through the transaction weight of the output that carries them, so larger
+    /// scripts are allowed proportionally more execution.
+    pub fn script_execution_budget(&self, script_byte_size: usize) -> Option<u64> {
+        let budget = self.script_execution_budget?;
+        let script_byte_size = u64::try_from(script_byte_size).unwrap_or(u64::MAX);
+        Some(
+            budget
+                .base
+                .saturating_add(budget.per_byte.saturating_mul(script_byte_size)),
+        )
+    }
+
     /// The maximum serialized byte size of TariScript
     pub fn max_extra_encrypted_data_byte_size(&self) -> usize {
         self.max_extra_encrypted_data_byte_size
     }
 
+    /// The application identifiers that outputs may carry application data for, if application data is permitted
+    pub fn permitted_application_ids(&self) -> Option<&RangeInclusive<u32>> {
+        self.permitted_application_ids.as_ref()
+    }
+
+    /// The maximum byte size of an application data payload
+    pub fn max_application_data_byte_size(&self) -> usize {
+        self.max_application_data_byte_size
+    }
+
     /// This is the min initial difficulty that can be requested for the pow
     pub fn min_pow_difficulty(&self, pow_algo: PowAlgorithm) -> Difficulty {
         match self.proof_of_work.get(&pow_algo) {
@@ -385,7 +433,11 @@ impl ConsensusConstants {
             max_difficulty: Difficulty::min(),
             target_time: 240,
         });
-        let (input_version_range, output_version_range, kernel_version_range) = version_zero();
+        let (input_version_range, mut output_version_range, mut kernel_version_range) = version_zero();
+        // Threshold multisig scripts and value range covenants are enabled on test networks first
+        output_version_range.opcode = OpcodeVersion::V0..=OpcodeVersion::V1;
+        output_version_range.covenant = CovenantVersion::V0..=CovenantVersion::V1;
+        kernel_version_range = TransactionKernelVersion::V0..=TransactionKernelVersion::V1;
         let consensus_constants = vec![ConsensusConstants {
             effective_from_height: 0,
             coinbase_min_maturity: 2,
@@ -405,7 +457,10 @@ impl ConsensusConstants {
             pre_mine_value: 0.into(),
             transaction_weight: TransactionWeight::latest(),
             max_script_byte_size: 512,
+            script_execution_budget: Some(SCRIPT_EXECUTION_BUDGET),
             max_extra_encrypted_data_byte_size: 240,
+            permitted_application_ids: Some(CUSTOM_APPLICATION_IDS),
+            max_application_data_byte_size: 256,
             input_version_range,
             output_version_range,
             kernel_version_range,
@@ -448,7 +503,7 @@ impl ConsensusConstants {
             target_time: randomx_target_time,
         });
         let (input_version_range, output_version_range, kernel_version_range) = version_zero();
-        let consensus_constants = vec![ConsensusConstants {
+        let mut consensus_constants = vec![ConsensusConstants {
             effective_from_height: 0,
             coinbase_min_maturity: 6,
             blockchain_version: 0,
@@ -470,7 +525,10 @@ impl ConsensusConstants {
             pre_mine_value: 0.into(), // IGOR_PRE_MINE_VALUE.into(),
             transaction_weight: TransactionWeight::v1(),
             max_script_byte_size: 512,
+            script_execution_budget: None,
             max_extra_encrypted_data_byte_size: 256,
+            permitted_application_ids: None,
+            max_application_data_byte_size: 0,
             input_version_range,
             output_version_range,
             kernel_version_range,
@@ -485,8 +543,28 @@ impl ConsensusConstants {
             vn_registration_shuffle_interval: VnEpoch(100),
             coinbase_output_features_extra_max_length: 64,
         }];
+        // Scripts are metered, and the threshold signature opcode, the output value range and fee ceiling covenant
+        // filters, claimable burns, application data and version 1 outputs with room for typed encrypted data records
+        // (payment id, memo, refund address) are allowed from this height, so that the blocks before it stay valid and
+        // nodes that do not know about them do not fork off before it
+        let mut hard_fork = consensus_constants[0].clone();
+        hard_fork.effective_from_height = 250_000;
+        hard_fork.script_execution_budget = Some(SCRIPT_EXECUTION_BUDGET);
+        hard_fork.output_version_range.outputs = TransactionOutputVersion::V0..=TransactionOutputVersion::V1;
+        hard_fork.output_version_range.opcode = OpcodeVersion::V0..=OpcodeVersion::V1;
+        hard_fork.output_version_range.covenant = CovenantVersion::V0..=CovenantVersion::V1;
+        hard_fork.kernel_version_range = TransactionKernelVersion::V0..=TransactionKernelVersion::V1;
+        hard_fork.permitted_application_ids = Some(CUSTOM_APPLICATION_IDS);
+        hard_fork.max_application_data_byte_size = 256;
+        hard_fork.max_extra_encrypted_data_byte_size = 1024;
+        consensus_constants.push(hard_fork);
         #[cfg(any(test, debug_assertions))]
-        assert_hybrid_pow_constants(&consensus_constants, &[target_time], &[randomx_split], &[sha3x_split]);
+        assert_hybrid_pow_constants(
+            &consensus_constants,
+            &[target_time, target_time],
+            &[randomx_split, randomx_split],
+            &[sha3x_split, sha3x_split],
+        );
         consensus_constants
     }
 
@@ -509,7 +587,7 @@ impl ConsensusConstants {
             target_time: 240,
         });
         let (input_version_range, output_version_range, kernel_version_range) = version_zero();
-        let consensus_constants = vec![ConsensusConstants {
+        let mut consensus_constants = vec![ConsensusConstants {
             effective_from_height: 0,
             coinbase_min_maturity: 6,
             blockchain_version: 0,
@@ -528,7 +606,10 @@ impl ConsensusConstants {
             pre_mine_value: MAINNET_PRE_MINE_VALUE,
             transaction_weight: TransactionWeight::v1(),
             max_script_byte_size: 512,
+            script_execution_budget: None,
             max_extra_encrypted_data_byte_size: 256,
+            permitted_application_ids: None,
+            max_application_data_byte_size: 0,
             input_version_range,
             output_version_range,
             kernel_version_range,
@@ -542,8 +623,17 @@ impl ConsensusConstants {
             vn_registration_shuffle_interval: VnEpoch(100),
             coinbase_output_features_extra_max_length: 64,
         }];
+        // Scripts are metered, and claimable burns and application data are allowed from this height, so that the
+        // blocks before it stay valid and nodes that do not know about them do not fork off before it
+        let mut hard_fork = consensus_constants[0].clone();
+        hard_fork.effective_from_height = 160_000;
+        hard_fork.script_execution_budget = Some(SCRIPT_EXECUTION_BUDGET);
+        hard_fork.kernel_version_range = TransactionKernelVersion::V0..=TransactionKernelVersion::V1;
+        hard_fork.permitted_application_ids = Some(CUSTOM_APPLICATION_IDS);
+        hard_fork.max_application_data_byte_size = 256;
+        consensus_constants.push(hard_fork);
         #[cfg(any(test, debug_assertions))]
-        assert_hybrid_pow_constants(&consensus_constants, &[120], &[50], &[50]);
+        assert_hybrid_pow_constants(&consensus_constants, &[120, 120], &[50, 50], &[50, 50]);
         consensus_constants
     }
 
@@ -566,7 +656,7 @@ impl ConsensusConstants {
             target_time: 240,
         });
         let (input_version_range, output_version_range, kernel_version_range) = version_zero();
-        let consensus_constants = vec![ConsensusConstants {
+        let mut consensus_constants = vec![ConsensusConstants {
             effective_from_height: 0,
             coinbase_min_maturity: 360,
             blockchain_version: 0,
@@ -585,7 +675,10 @@ impl ConsensusConstants {
             pre_mine_value: PRE_MINE_VALUE.into(),
             transaction_weight: TransactionWeight::v1(),
             max_script_byte_size: 512,
+            script_execution_budget: None,
             max_extra_encrypted_data_byte_size: 256,
+            permitted_application_ids: None,
+            max_application_data_byte_size: 0,
             input_version_range,
             output_version_range,
             kernel_version_range,
@@ -599,8 +692,15 @@ impl ConsensusConstants {
             vn_registration_shuffle_interval: VnEpoch(100),
             coinbase_output_features_extra_max_length: 64,
         }];
+        // Scripts are metered and claimable burns are allowed from this height, so that the blocks before it stay
+        // valid and nodes that do not know about them do not fork off before it
+        let mut hard_fork = consensus_constants[0].clone();
+        hard_fork.effective_from_height = 120_000;
+        hard_fork.script_execution_budget = Some(SCRIPT_EXECUTION_BUDGET);
+        hard_fork.kernel_version_range = TransactionKernelVersion::V0..=TransactionKernelVersion::V1;
+        consensus_constants.push(hard_fork);
         #[cfg(any(test, debug_assertions))]
-        assert_hybrid_pow_constants(&consensus_constants, &[120], &[50], &[50]);
+        assert_hybrid_pow_constants(&consensus_constants, &[120, 120], &[50, 50], &[50, 50]);
         consensus_constants
     }
 
@@ -617,7 +717,7 @@ impl ConsensusConstants {
             target_time: 240,
         });
         let (input_version_range, output_version_range, kernel_version_range) = version_zero();
-        let consensus_constants = vec![ConsensusConstants {
+        let mut consensus_constants = vec![ConsensusConstants {
             effective_from_height: 0,
             coinbase_min_maturity: 360,
             blockchain_version: 0,
@@ -636,7 +736,10 @@ impl ConsensusConstants {
             pre_mine_value: PRE_MINE_VALUE.into(),
             transaction_weight: TransactionWeight::v1(),
             max_script_byte_size: 512,
+            script_execution_budget: None,
             max_extra_encrypted_data_byte_size: 256,
+            permitted_application_ids: None,
+            max_application_data_byte_size: 0,
             input_version_range,
             output_version_range,
             kernel_version_range,
@@ -650,8 +753,15 @@ impl ConsensusConstants {
             vn_registration_shuffle_interval: VnEpoch(100),
             coinbase_output_features_extra_max_length: 64,
         }];
+        // Scripts are metered and claimable burns are allowed from this height, so that the blocks before it stay
+        // valid and nodes that do not know about them do not fork off before it
+        let mut hard_fork = consensus_constants[0].clone();
+        hard_fork.effective_from_height = 120_000;
+        hard_fork.script_execution_budget = Some(SCRIPT_EXECUTION_BUDGET);
+        hard_fork.kernel_version_range = TransactionKernelVersion::V0..=TransactionKernelVersion::V1;
+        consensus_constants.push(hard_fork);
         #[cfg(any(test, debug_assertions))]
-        assert_hybrid_pow_constants(&consensus_constants, &[120], &[50], &[50]);
+        assert_hybrid_pow_constants(&consensus_constants, &[120, 120], &[50, 50], &[50, 50]);
         consensus_constants
     }
 
@@ -670,7 +780,7 @@ impl ConsensusConstants {
             target_time: 240,
         });
         let (input_version_range, output_version_range, kernel_version_range) = version_zero();
-        let consensus_constants = vec![ConsensusConstants {
+        let mut consensus_constants = vec![ConsensusConstants {
             effective_from_height: 0,
             coinbase_min_maturity: 720,
             blockchain_version: 0,
@@ -689,7 +799,10 @@ impl ConsensusConstants {
             pre_mine_value: MAINNET_PRE_MINE_VALUE,
             transaction_weight: TransactionWeight::v1(),
             max_script_byte_size: 512,
+            script_execution_budget: None,
             max_extra_encrypted_data_byte_size: 256,
+            permitted_application_ids: None,
+            max_application_data_byte_size: 0,
             input_version_range,
             output_version_range,
             kernel_version_range,
@@ -703,8 +816,15 @@ impl ConsensusConstants {
             vn_registration_shuffle_interval: VnEpoch(100),
             coinbase_output_features_extra_max_length: 64,
         }];
+        // Scripts are metered and claimable burns are allowed from this height, so that the blocks before it stay
+        // valid and nodes that do not know about them do not fork off before it
+        let mut hard_fork = consensus_constants[0].clone();
+        hard_fork.effective_from_height = 120_000;
+        hard_fork.script_execution_budget = Some(SCRIPT_EXECUTION_BUDGET);
+        hard_fork.kernel_version_range = TransactionKernelVersion::V0..=TransactionKernelVersion::V1;
+        consensus_constants.push(hard_fork);
         #[cfg(any(test, debug_assertions))]
-        assert_hybrid_pow_constants(&consensus_constants, &[120], &[50], &[50]);
+        assert_hybrid_pow_constants(&consensus_constants, &[120, 120], &[50, 50], &[50, 50]);
         consensus_constants
     }
 
@@ -840,11 +960,26 @@ impl ConsensusConstantsBuilder {
         self
     }
 
+    pub fn with_script_execution_budget(mut self, budget: Option<ScriptExecutionBudget>) -> Self {
+        self.consensus.script_execution_budget = budget;
+        self
+    }
+
     pub fn with_max_block_transaction_weight(mut self, weight: u64) -> Self {
         self.consensus.max_block_transaction_weight = weight;
         self
     }
 
+    pub fn with_transaction_weight(mut self, transaction_weight: TransactionWeight) -> Self {
+        self.consensus.transaction_weight = transaction_weight;
+        self
+    }
+
+    pub fn with_effective_from_height(mut self, height: u64) -> Self {
+        self.consensus.effective_from_height = height;
+        self
+    }
+
     pub fn with_consensus_constants(mut self, consensus: ConsensusConstants) -> Self {
         self.consensus = consensus;
         self
@@ -887,11 +1022,26 @@ impl ConsensusConstantsBuilder {
         self
     }
 
+    pub fn with_application_data(
+        mut self,
+        permitted_application_ids: Option<RangeInclusive<u32>>,
+        max_byte_size: usize,
+    ) -> Self {
+        self.consensus.permitted_application_ids = permitted_application_ids;
+        self.consensus.max_application_data_byte_size = max_byte_size;
+        self
+    }
+
     pub fn with_blockchain_version(mut self, version: u16) -> Self {
         self.consensus.blockchain_version = version;
         self
     }
 
+    pub fn with_output_versions(mut self, outputs: RangeInclusive<TransactionOutputVersion>) -> Self {
+        self.consensus.output_version_range.outputs = outputs;
+        self
+    }
+
     pub fn build(self) -> ConsensusConstants {
         self.consensus
     }
@@ -901,14 +1051,30 @@ impl ConsensusConstantsBuilder {
 mod test {
     use std::convert::TryFrom;
 
+    use tari_common::configuration::Network;
+    use tari_common_types::types::PublicKey;
+    use tari_script::{Opcode, OpcodeVersion, TariScript};
+
+    use super::CUSTOM_APPLICATION_IDS;
     use crate::{
+        borsh::SerializedSize,
         consensus::{
             emission::{Emission, EmissionSchedule},
             ConsensusConstants,
+            ConsensusConstantsBuilder,
+            ConsensusManager,
         },
+        covenants::CovenantVersion,
         transactions::{
             tari_amount::{uT, MicroMinotari},
-            transaction_components::{OutputType, RangeProofType},
+            transaction_components::{
+                encrypted_data::MAX_EXTRA_ENCRYPTED_DATA_SIZE,
+                OutputType,
+                RangeProofType,
+                TransactionKernelVersion,
+                TransactionOutputVersion,
+            },
+            weight::{TransactionWeight, TransactionWeightVersion},
         },
     };
 
@@ -1129,4 +1295,253 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn script_execution_budget_bounds_worst_case_scripts() {
+        let multisig = TariScript::new(vec![Opcode::CheckMultiSig(
+            1,
+            14,
+            vec![PublicKey::default(); 14],
+            Box::new([0u8; 32]),
+        )])
+        .unwrap();
+        let point_spam = TariScript::new(vec![Opcode::ToRistrettoPoint; 128]).unwrap();
+
+        let all_constants = [
+            ConsensusConstants::localnet(),
+            ConsensusConstants::igor(),
+            ConsensusConstants::esmeralda(),
+            ConsensusConstants::stagenet(),
+            ConsensusConstants::nextnet(),
+            ConsensusConstants::mainnet(),
+        ];
+        for constants in all_constants.iter().flatten() {
+            let size = multisig.get_serialized_size().unwrap();
+            assert!(size <= constants.max_script_byte_size());
+            let Some(budget) = constants.script_execution_budget(size) else {
+                continue;
+            };
+            assert!(multisig.execution_cost() <= budget);
+
+            let size = point_spam.get_serialized_size().unwrap();
+            assert!(point_spam.execution_cost() > constants.script_execution_budget(size).unwrap());
+        }
+    }
+
+    #[test]
+    fn scripts_are_only_metered_from_the_hard_fork() {
+        let networks = [
+            ConsensusConstants::igor(),
+            ConsensusConstants::esmeralda(),
+            ConsensusConstants::stagenet(),
+            ConsensusConstants::nextnet(),
+            ConsensusConstants::mainnet(),
+        ];
+        for constants in networks {
+            assert_eq!(constants[0].effective_from_height(), 0);
+            assert_eq!(constants[0].script_execution_budget(0), None);
+            let hard_fork = constants.last().unwrap();
+            assert!(hard_fork.effective_from_height() > 0);
+            assert!(hard_fork.script_execution_budget(0).is_some());
+        }
+        assert!(ConsensusConstants::localnet()[0].script_execution_budget(0).is_some());
+    }
+
+    #[test]
+    fn claimable_burns_are_only_allowed_from_the_hard_fork() {
+        let networks = [
+            ConsensusConstants::igor(),
+            ConsensusConstants::esmeralda(),
+            ConsensusConstants::stagenet(),
+            ConsensusConstants::nextnet(),
+            ConsensusConstants::mainnet(),
+        ];
+        for constants in networks {
+            assert!(!constants[0]
+                .kernel_version_range()
+                .contains(&TransactionKernelVersion::V1));
+            let hard_fork = constants.last().unwrap();
+            assert!(hard_fork.kernel_version_range().contains(&TransactionKernelVersion::V1));
+        }
+        assert!(ConsensusConstants::localnet()[0]
+            .kernel_version_range()
+            .contains(&TransactionKernelVersion::V1));
+    }
+
+    #[test]
+    fn application_data_is_only_permitted_from_the_hard_fork() {
+        for constants in [ConsensusConstants::igor(), ConsensusConstants::esmeralda()] {
+            assert!(constants[0].permitted_application_ids().is_none());
+            let hard_fork = constants.last().unwrap();
+            assert_eq!(hard_fork.permitted_application_ids(), Some(&CUSTOM_APPLICATION_IDS));
+        }
+        for constants in [
+            ConsensusConstants::stagenet(),
+            ConsensusConstants::nextnet(),
+            ConsensusConstants::mainnet(),
+        ] {
+            assert!(constants.iter().all(|c| c.permitted_application_ids().is_none()));
+        }
+        assert!(ConsensusConstants::localnet()[0].permitted_application_ids().is_some());
+    }
+
+    #[test]
+    fn threshold_signature_opcode_is_only_permitted_from_the_hard_fork() {
+        let igor = ConsensusConstants::igor();
+        assert_eq!(
+            igor[0].output_version_range().opcode,
+            OpcodeVersion::V0..=OpcodeVersion::V0
+        );
+        assert_eq!(
+            igor.last().unwrap().output_version_range().opcode,
+            OpcodeVersion::V0..=OpcodeVersion::V1
+        );
+        for constants in [
+            ConsensusConstants::esmeralda(),
+            ConsensusConstants::stagenet(),
+            ConsensusConstants::nextnet(),
+            ConsensusConstants::mainnet(),
+        ] {
+            assert!(constants
+                .iter()
+                .all(|c| c.output_version_range().opcode == (OpcodeVersion::V0..=OpcodeVersion::V0)));
+        }
+        assert_eq!(
+            ConsensusConstants::localnet()[0].output_version_range().opcode,
+            OpcodeVersion::V0..=OpcodeVersion::V1
+        );
+    }
+
+    #[test]
+    fn covenant_version_1_filters_are_only_permitted_from_the_hard_fork() {
+        let igor = ConsensusConstants::igor();
+        assert_eq!(
+            igor[0].output_version_range().covenant,
+            CovenantVersion::V0..=CovenantVersion::V0
+        );
+        assert_eq!(
+            igor.last().unwrap().output_version_range().covenant,
+            CovenantVersion::V0..=CovenantVersion::V1
+        );
+        for constants in [
+            ConsensusConstants::esmeralda(),
+            ConsensusConstants::stagenet(),
+            ConsensusConstants::nextnet(),
+            ConsensusConstants::mainnet(),
+        ] {
+            assert!(constants
+                .iter()
+                .all(|c| c.output_version_range().covenant == (CovenantVersion::V0..=CovenantVersion::V0)));
+        }
+        assert_eq!(
+            ConsensusConstants::localnet()[0].output_version_range().covenant,
+            CovenantVersion::V0..=CovenantVersion::V1
+        );
+    }
+
+    #[test]
+    fn typed_record_outputs_are_only_permitted_from_the_hard_fork() {
+        let igor = ConsensusConstants::igor();
+        assert!(!igor[0]
+            .output_version_range()
+            .outputs
+            .contains(&TransactionOutputVersion::V1));
+        assert!(igor
+            .last()
+            .unwrap()
+            .output_version_range()
+            .outputs
+            .contains(&TransactionOutputVersion::V1));
+        for constants in [
+            ConsensusConstants::localnet(),
+            ConsensusConstants::esmeralda(),
+            ConsensusConstants::stagenet(),
+            ConsensusConstants::nextnet(),
+            ConsensusConstants::mainnet(),
+        ] {
+            assert!(constants
+                .iter()
+                .all(|c| !c.output_version_range().outputs.contains(&TransactionOutputVersion::V1)));
+        }
+    }
+
+    #[test]
+    fn transaction_weight_version_is_selected_by_height() {
+        let all_constants = [
+            ConsensusConstants::localnet(),
+            ConsensusConstants::igor(),
+            ConsensusConstants::esmeralda(),
+            ConsensusConstants::stagenet(),
+            ConsensusConstants::nextnet(),
+            ConsensusConstants::mainnet(),
+        ];
+        for constants in all_constants.iter().flatten() {
+            assert_eq!(
+                constants.transaction_weight_params().version(),
+                TransactionWeightVersion::V1
+            );
+        }
+
+        let consensus_manager = ConsensusManager::builder(Network::LocalNet)
+            .add_consensus_constants(ConsensusConstantsBuilder::new(Network::LocalNet).build())
+            .add_consensus_constants(
+                ConsensusConstantsBuilder::new(Network::LocalNet)
+                    .with_effective_from_height(100)
+                    .with_transaction_weight(TransactionWeight::v2())
+                    .build(),
+            )
+            .add_consensus_constants(
+                ConsensusConstantsBuilder::new(Network::LocalNet)
+                    .with_effective_from_height(200)
+                    .with_transaction_weight(TransactionWeight::v3())
+                    .build(),
+            )
+            .build()
+            .unwrap();
+        assert_eq!(
+            consensus_manager
+                .consensus_constants(99)
+                .transaction_weight_params()
+                .version(),
+            TransactionWeightVersion::V1
+        );
+        assert_eq!(
+            consensus_manager
+                .consensus_constants(100)
+                .transaction_weight_params()
+                .version(),
+            TransactionWeightVersion::V2
+        );
+        assert_eq!(
+            consensus_manager
+                .consensus_constants(200)
+                .transaction_weight_params()
+                .version(),
+            TransactionWeightVersion::V3
+        );
+    }
+
+    #[test]
+    fn extra_encrypted_data_size_is_within_hard_limit() {
+        let all_constants = [
+            ConsensusConstants::localnet(),
+            ConsensusConstants::igor(),
+            ConsensusConstants::esmeralda(),
+            ConsensusConstants::stagenet(),
+            ConsensusConstants::nextnet(),
+            ConsensusConstants::mainnet(),
+        ];
+        for constants in all_constants.iter().flatten() {
+            assert!(constants.max_extra_encrypted_data_byte_size() <= MAX_EXTRA_ENCRYPTED_DATA_SIZE);
+        }
+    }
+
+    #[test]
+    fn extra_encrypted_data_size_is_only_raised_from_the_hard_fork() {
+        let constants = ConsensusConstants::igor();
+        assert_eq!(constants[0].max_extra_encrypted_data_byte_size(), 256);
+        let hard_fork = constants.last().unwrap();
+        assert!(hard_fork.effective_from_height() > 0);
+        assert_eq!(hard_fork.max_extra_encrypted_data_byte_size(), 1024);
+    }
 }
@@ -24,7 +24,7 @@
 pub(crate) mod chain_strength_comparer;
 
 pub mod consensus_constants;
-pub use consensus_constants::{ConsensusConstants, ConsensusConstantsBuilder};
+pub use consensus_constants::{ConsensusConstants, ConsensusConstantsBuilder, ScriptExecutionBudget};
 
 mod consensus_manager;
 pub use consensus_manager::{ConsensusBuilderError, ConsensusManager, ConsensusManagerBuilder, ConsensusManagerError};
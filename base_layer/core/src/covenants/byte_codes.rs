@@ -67,7 +67,7 @@ pub(super) fn is_valid_filter_code(code: u8) -> bool {
 }
 
 /// Array with all possible covenant filter bytecodes.
-pub(super) const ALL_FILTERS: [u8; 10] = [
+pub(super) const ALL_FILTERS: [u8; 12] = [
     FILTER_IDENTITY,
     FILTER_AND,
     FILTER_OR,
@@ -78,6 +78,8 @@ pub(super) const ALL_FILTERS: [u8; 10] = [
     FILTER_FIELDS_HASHED_EQ,
     FILTER_FIELD_EQ,
     FILTER_ABSOLUTE_HEIGHT,
+    FILTER_OUTPUT_VALUE_RANGE,
+    FILTER_FEE_CEILING,
 ];
 
 /// Identity filter.
@@ -101,6 +103,10 @@ pub const FILTER_FIELDS_HASHED_EQ: u8 = 0x32;
 pub const FILTER_FIELD_EQ: u8 = 0x33;
 /// Absolute height filter.
 pub const FILTER_ABSOLUTE_HEIGHT: u8 = 0x34;
+/// Output value range filter.
+pub const FILTER_OUTPUT_VALUE_RANGE: u8 = 0x35;
+/// Fee ceiling filter.
+pub const FILTER_FEE_CEILING: u8 = 0x36;
 
 //---------------------------------- FIELD byte codes --------------------------------------------//
 /// Field commitment.
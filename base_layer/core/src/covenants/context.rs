@@ -27,7 +27,7 @@ use crate::{
         filters::CovenantFilter,
         token::{CovenantToken, CovenantTokenCollection},
     },
-    transactions::transaction_components::TransactionInput,
+    transactions::{tari_amount::MicroMinotari, transaction_components::TransactionInput},
 };
 
 /// The covenant execution context provides a reference to the transaction input being verified, the tokenized covenant
@@ -37,14 +37,21 @@ pub struct CovenantContext<'a> {
     input: &'a TransactionInput,
     tokens: CovenantTokenCollection,
     block_height: u64,
+    spending_fee: MicroMinotari,
 }
 
 impl<'a> CovenantContext<'a> {
-    pub fn new(tokens: CovenantTokenCollection, input: &'a TransactionInput, block_height: u64) -> Self {
+    pub fn new(
+        tokens: CovenantTokenCollection,
+        input: &'a TransactionInput,
+        block_height: u64,
+        spending_fee: MicroMinotari,
+    ) -> Self {
         Self {
             input,
             tokens,
             block_height,
+            spending_fee,
         }
     }
 
@@ -84,6 +91,11 @@ impl<'a> CovenantContext<'a> {
         self.block_height
     }
 
+    /// Fee paid by the kernels of the transaction spending the input
+    pub fn spending_fee(&self) -> MicroMinotari {
+        self.spending_fee
+    }
+
     /// Transaction input
     pub fn input(&self) -> &TransactionInput {
         self.input
@@ -37,11 +37,14 @@ use crate::{
         decoder::CovenantTokenDecoder,
         encoder::CovenantTokenEncoder,
         error::CovenantError,
-        filters::Filter,
+        filters::{CovenantFilter, CovenantVersion, Filter},
         output_set::OutputSet,
         token::{CovenantToken, CovenantTokenCollection},
     },
-    transactions::transaction_components::{TransactionInput, TransactionOutput},
+    transactions::{
+        tari_amount::MicroMinotari,
+        transaction_components::{TransactionInput, TransactionOutput},
+    },
 };
 
 const MAX_COVENANT_BYTES: usize = 4096;
@@ -126,12 +129,14 @@ impl Covenant {
     }
 
     /// It executes the covenant on the transaction input being spent, it filters the transaction outputs which should
-    /// generate at least one match. An empty covenant is an identity and matches all outputs.
+    /// generate at least one match. An empty covenant is an identity and matches all outputs. `spending_fee` is the fee
+    /// paid by the kernels of the body that spends the input.
     pub fn execute(
         &self,
         block_height: u64,
         input: &TransactionInput,
         outputs: &[TransactionOutput],
+        spending_fee: MicroMinotari,
     ) -> Result<usize, CovenantError> {
         if self.tokens.is_empty() {
             // Empty covenants always pass
@@ -139,7 +144,7 @@ impl Covenant {
         }
 
         let tokens = CovenantTokenCollection::from_iter(self.tokens.clone());
-        let mut cx = CovenantContext::new(tokens, input, block_height);
+        let mut cx = CovenantContext::new(tokens, input, block_height, spending_fee);
         let root = cx.require_next_filter()?;
         let mut output_set = OutputSet::new(outputs);
         root.filter(&mut cx, &mut output_set)?;
@@ -173,6 +178,16 @@ impl Covenant {
     pub fn is_empty(&self) -> bool {
         self.tokens.is_empty()
     }
+
+    /// The highest version of any filter used in this covenant. An empty covenant is version 0.
+    pub fn get_version(&self) -> CovenantVersion {
+        self.tokens
+            .iter()
+            .filter_map(CovenantToken::as_filter)
+            .map(CovenantFilter::get_version)
+            .max()
+            .unwrap_or(CovenantVersion::V0)
+    }
 }
 
 impl FromIterator<CovenantToken> for Covenant {
@@ -193,8 +208,13 @@ mod test {
         covenants::{
             test::{create_input, create_outputs},
             Covenant,
+            CovenantVersion,
+        },
+        transactions::{
+            key_manager::create_memory_db_key_manager,
+            tari_amount::MicroMinotari,
+            test_helpers::UtxoTestParams,
         },
-        transactions::{key_manager::create_memory_db_key_manager, test_helpers::UtxoTestParams},
     };
 
     #[tokio::test]
@@ -203,7 +223,7 @@ mod test {
         let outputs = create_outputs(10, UtxoTestParams::default(), &key_manager).await;
         let input = create_input(&key_manager).await;
         let covenant = covenant!().unwrap();
-        let num_matching_outputs = covenant.execute(0, &input, &outputs).unwrap();
+        let num_matching_outputs = covenant.execute(0, &input, &outputs, MicroMinotari::zero()).unwrap();
         assert_eq!(num_matching_outputs, 10);
     }
 
@@ -221,10 +241,19 @@ mod test {
             @field::features_maturity))
         )
         .unwrap();
-        let num_matching_outputs = covenant.execute(0, &input, &outputs).unwrap();
+        let num_matching_outputs = covenant.execute(0, &input, &outputs, MicroMinotari::zero()).unwrap();
         assert_eq!(num_matching_outputs, 3);
     }
 
+    #[test]
+    fn it_reports_the_highest_filter_version() {
+        assert_eq!(covenant!().unwrap().get_version(), CovenantVersion::V0);
+        let covenant = covenant!(or(absolute_height(@uint(42)), identity())).unwrap();
+        assert_eq!(covenant.get_version(), CovenantVersion::V0);
+        let covenant = covenant!(and(absolute_height(@uint(42)), output_value_range(@uint(0), @uint(1000)))).unwrap();
+        assert_eq!(covenant.get_version(), CovenantVersion::V1);
+    }
+
     #[tokio::test]
     async fn test_borsh_de_serialization() {
         let key_manager = create_memory_db_key_manager().unwrap();
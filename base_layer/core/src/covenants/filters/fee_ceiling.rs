@@ -0,0 +1,74 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::covenants::{context::CovenantContext, error::CovenantError, filters::Filter, output_set::OutputSet};
+
+/// Holding struct for the "fee ceiling" filter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeeCeilingFilter;
+
+impl Filter for FeeCeilingFilter {
+    // The fee ceiling filter removes all outputs in the mutable output set if the fee paid by the transaction spending
+    // the input, as provided in the covenant context, exceeds the maximum fee argument.
+    fn filter(&self, context: &mut CovenantContext<'_>, output_set: &mut OutputSet<'_>) -> Result<(), CovenantError> {
+        let max_fee = context.next_arg()?.require_uint()?;
+        if context.spending_fee().as_u64() > max_fee {
+            output_set.clear();
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        covenant,
+        covenants::test::{create_input, create_outputs},
+        transactions::{key_manager::create_memory_db_key_manager, tari_amount::MicroMinotari},
+    };
+
+    async fn filter_with_fee(fee: u64) -> usize {
+        let key_manager = create_memory_db_key_manager().unwrap();
+        let covenant = covenant!(fee_ceiling(@uint(100))).unwrap();
+        let input = create_input(&key_manager).await;
+        let tokens = covenant.tokens().to_vec();
+        let mut context = CovenantContext::new(tokens.into(), &input, 0, MicroMinotari(fee));
+        context.next_filter().unwrap();
+        let outputs = create_outputs(10, Default::default(), &key_manager).await;
+
+        let mut output_set = OutputSet::new(&outputs);
+        FeeCeilingFilter.filter(&mut context, &mut output_set).unwrap();
+        output_set.len()
+    }
+
+    #[tokio::test]
+    async fn it_filters_all_out_if_fee_exceeds_ceiling() {
+        assert_eq!(filter_with_fee(101).await, 0);
+    }
+
+    #[tokio::test]
+    async fn it_filters_all_in_if_fee_within_ceiling() {
+        assert_eq!(filter_with_fee(100).await, 10);
+        assert_eq!(filter_with_fee(0).await, 10);
+    }
+}
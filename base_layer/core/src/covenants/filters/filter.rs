@@ -25,6 +25,7 @@ use std::io;
 use super::{
     absolute_height::AbsoluteHeightFilter,
     and::AndFilter,
+    fee_ceiling::FeeCeilingFilter,
     field_eq::FieldEqFilter,
     fields_hashed_eq::FieldsHashedEqFilter,
     fields_preserved::FieldsPreservedFilter,
@@ -32,6 +33,7 @@ use super::{
     not::NotFilter,
     or::OrFilter,
     output_hash_eq::OutputHashEqFilter,
+    output_value_range::OutputValueRangeFilter,
     xor::XorFilter,
 };
 use crate::covenants::{
@@ -61,6 +63,8 @@ pub enum CovenantFilter {
     FieldEq(FieldEqFilter),
     FieldsHashedEq(FieldsHashedEqFilter),
     AbsoluteHeight(AbsoluteHeightFilter),
+    OutputValueRange(OutputValueRangeFilter),
+    FeeCeiling(FeeCeilingFilter),
 }
 
 impl CovenantFilter {
@@ -91,6 +95,8 @@ impl CovenantFilter {
             FieldEq(_) => FILTER_FIELD_EQ,
             FieldsHashedEq(_) => FILTER_FIELDS_HASHED_EQ,
             AbsoluteHeight(_) => FILTER_ABSOLUTE_HEIGHT,
+            OutputValueRange(_) => FILTER_OUTPUT_VALUE_RANGE,
+            FeeCeiling(_) => FILTER_FEE_CEILING,
         }
     }
 
@@ -108,6 +114,8 @@ impl CovenantFilter {
             FILTER_FIELD_EQ => Ok(Self::field_eq()),
             FILTER_FIELDS_HASHED_EQ => Ok(Self::fields_hashed_eq()),
             FILTER_ABSOLUTE_HEIGHT => Ok(Self::absolute_height()),
+            FILTER_OUTPUT_VALUE_RANGE => Ok(Self::output_value_range()),
+            FILTER_FEE_CEILING => Ok(Self::fee_ceiling()),
             _ => Err(CovenantDecodeError::UnknownFilterByteCode { code }),
         }
     }
@@ -161,6 +169,28 @@ impl CovenantFilter {
     pub fn absolute_height() -> Self {
         CovenantFilter::AbsoluteHeight(AbsoluteHeightFilter)
     }
+
+    /// Return the "output value range" covenant filter.
+    pub fn output_value_range() -> Self {
+        CovenantFilter::OutputValueRange(OutputValueRangeFilter)
+    }
+
+    /// Return the "fee ceiling" covenant filter.
+    pub fn fee_ceiling() -> Self {
+        CovenantFilter::FeeCeiling(FeeCeilingFilter)
+    }
+
+    /// The covenant version in which this filter was introduced. Consensus rejects outputs with covenants that use
+    /// filters outside of the permitted version range.
+    pub fn get_version(&self) -> CovenantVersion {
+        #[allow(clippy::enum_glob_use)]
+        use CovenantFilter::*;
+        match self {
+            Identity(_) | And(_) | Or(_) | Xor(_) | Not(_) | OutputHashEq(_) | FieldsPreserved(_) | FieldEq(_) |
+            FieldsHashedEq(_) | AbsoluteHeight(_) => CovenantVersion::V0,
+            OutputValueRange(_) | FeeCeiling(_) => CovenantVersion::V1,
+        }
+    }
 }
 
 impl Filter for CovenantFilter {
@@ -179,6 +209,15 @@ impl Filter for CovenantFilter {
             FieldEq(fields_eq) => fields_eq.filter(context, output_set),
             FieldsHashedEq(fields_hashed_eq) => fields_hashed_eq.filter(context, output_set),
             AbsoluteHeight(abs_height) => abs_height.filter(context, output_set),
+            OutputValueRange(value_range) => value_range.filter(context, output_set),
+            FeeCeiling(fee_ceiling) => fee_ceiling.filter(context, output_set),
         }
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(u8)]
+pub enum CovenantVersion {
+    V0 = 0,
+    V1 = 1,
+}
@@ -22,6 +22,7 @@
 
 mod absolute_height;
 mod and;
+mod fee_ceiling;
 mod field_eq;
 mod fields_hashed_eq;
 mod fields_preserved;
@@ -29,10 +30,12 @@ mod identity;
 mod not;
 mod or;
 mod output_hash_eq;
+mod output_value_range;
 mod xor;
 
 pub use absolute_height::AbsoluteHeightFilter;
 pub use and::AndFilter;
+pub use fee_ceiling::FeeCeilingFilter;
 pub use field_eq::FieldEqFilter;
 pub use fields_hashed_eq::FieldsHashedEqFilter;
 pub use fields_preserved::FieldsPreservedFilter;
@@ -40,10 +43,11 @@ pub use identity::IdentityFilter;
 pub use not::NotFilter;
 pub use or::OrFilter;
 pub use output_hash_eq::OutputHashEqFilter;
+pub use output_value_range::OutputValueRangeFilter;
 pub use xor::XorFilter;
 
 mod filter;
-pub use filter::{CovenantFilter, Filter};
+pub use filter::{CovenantFilter, CovenantVersion, Filter};
 
 #[cfg(test)]
 mod test;
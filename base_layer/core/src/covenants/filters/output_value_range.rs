@@ -0,0 +1,103 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::{
+    covenants::{context::CovenantContext, error::CovenantError, filters::Filter, output_set::OutputSet},
+    transactions::transaction_components::RangeProofType,
+};
+
+/// Holding struct for the "output value range" filter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputValueRangeFilter;
+
+impl Filter for OutputValueRangeFilter {
+    // Filters out all outputs whose value cannot be shown to lie within the inclusive range given by the next two
+    // arguments in the covenant context. Output values are hidden by their commitments, so the lower bound is checked
+    // against the minimum value promise and the upper bound can only be met by outputs with a revealed value.
+    fn filter(&self, context: &mut CovenantContext<'_>, output_set: &mut OutputSet<'_>) -> Result<(), CovenantError> {
+        let min = context.next_arg()?.require_uint()?;
+        let max = context.next_arg()?.require_uint()?;
+        if min > max {
+            return Err(CovenantError::InvalidArgument {
+                filter: "output_value_range",
+                details: format!("Minimum value {} is greater than maximum value {}", min, max),
+            });
+        }
+        output_set.retain(|output| {
+            let promise = output.minimum_value_promise.as_u64();
+            let is_revealed = output.features.range_proof_type == RangeProofType::RevealedValue;
+            Ok(promise >= min && is_revealed && promise <= max)
+        })?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        covenant,
+        covenants::{filters::test::setup_filter_test, test::create_input},
+        transactions::{key_manager::create_memory_db_key_manager, tari_amount::MicroMinotari},
+    };
+
+    #[tokio::test]
+    async fn it_filters_outputs_with_revealed_values_in_range() {
+        let key_manager = create_memory_db_key_manager().unwrap();
+        let covenant = covenant!(output_value_range(@uint(100), @uint(1000))).unwrap();
+        let input = create_input(&key_manager).await;
+        let (mut context, outputs) = setup_filter_test(
+            &covenant,
+            &input,
+            0,
+            |outputs| {
+                for (output, value) in outputs.iter_mut().zip([50u64, 100, 500, 1000, 1001]) {
+                    output.features.range_proof_type = RangeProofType::RevealedValue;
+                    output.minimum_value_promise = MicroMinotari(value);
+                }
+                // Not revealed, so the upper bound cannot be established
+                outputs[5].minimum_value_promise = MicroMinotari(500);
+            },
+            &key_manager,
+        )
+        .await;
+
+        let mut output_set = OutputSet::new(&outputs);
+        OutputValueRangeFilter.filter(&mut context, &mut output_set).unwrap();
+
+        assert_eq!(output_set.get_selected_indexes(), vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn it_errors_if_range_is_invalid() {
+        let key_manager = create_memory_db_key_manager().unwrap();
+        let covenant = covenant!(output_value_range(@uint(1000), @uint(100))).unwrap();
+        let input = create_input(&key_manager).await;
+        let (mut context, outputs) = setup_filter_test(&covenant, &input, 0, |_| {}, &key_manager).await;
+
+        let mut output_set = OutputSet::new(&outputs);
+        let err = OutputValueRangeFilter
+            .filter(&mut context, &mut output_set)
+            .unwrap_err();
+        assert!(matches!(err, CovenantError::InvalidArgument { .. }));
+    }
+}
@@ -26,6 +26,7 @@ use crate::{
     covenants::{context::CovenantContext, Covenant},
     transactions::{
         key_manager::MemoryDbKeyManager,
+        tari_amount::MicroMinotari,
         test_helpers::{TestParams, UtxoTestParams},
         transaction_components::{
             BuildInfo,
@@ -60,7 +61,7 @@ pub async fn create_input(key_manager: &MemoryDbKeyManager) -> TransactionInput
 
 pub fn create_context<'a>(covenant: &Covenant, input: &'a TransactionInput, block_height: u64) -> CovenantContext<'a> {
     let tokens = covenant.tokens().to_vec();
-    CovenantContext::new(tokens.into(), input, block_height)
+    CovenantContext::new(tokens.into(), input, block_height, MicroMinotari::zero())
 }
 
 pub fn make_sample_sidechain_feature() -> SideChainFeature {
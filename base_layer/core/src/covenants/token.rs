@@ -34,6 +34,7 @@ use crate::{
             AbsoluteHeightFilter,
             AndFilter,
             CovenantFilter,
+            FeeCeilingFilter,
             FieldEqFilter,
             FieldsHashedEqFilter,
             FieldsPreservedFilter,
@@ -41,6 +42,7 @@ use crate::{
             NotFilter,
             OrFilter,
             OutputHashEqFilter,
+            OutputValueRangeFilter,
             XorFilter,
         },
         Covenant,
@@ -162,6 +164,18 @@ impl CovenantToken {
         CovenantFilter::AbsoluteHeight(AbsoluteHeightFilter).into()
     }
 
+    #[allow(dead_code)]
+    /// Helper for creating a new instance wrapping an `OutputValueRangeFilter`.
+    pub fn output_value_range() -> Self {
+        CovenantFilter::OutputValueRange(OutputValueRangeFilter).into()
+    }
+
+    #[allow(dead_code)]
+    /// Helper for creating a new instance wrapping a `FeeCeilingFilter`.
+    pub fn fee_ceiling() -> Self {
+        CovenantFilter::FeeCeiling(FeeCeilingFilter).into()
+    }
+
     #[allow(dead_code)]
     /// Helper for creating a new instance wrapping an `HashFilter`.
     pub fn hash(hash: FixedHash) -> Self {
@@ -68,6 +68,7 @@ mod domain_hashing {
         error::MerkleMountainRangeError,
         pruned_hashset::PrunedHashSet,
         sparse_merkle_tree::SparseMerkleTree,
+        BalancedBinaryMerkleProof,
         BalancedBinaryMerkleTree,
         Hash,
         MerkleMountainRange,
@@ -90,6 +91,7 @@ mod domain_hashing {
 
     pub type ValidatorNodeBmtHasherBlake256 = DomainSeparatedHasher<Blake2b<U32>, ValidatorNodeBmtHashDomain>;
     pub type ValidatorNodeBMT = BalancedBinaryMerkleTree<ValidatorNodeBmtHasherBlake256>;
+    pub type ValidatorNodeBmtProof = BalancedBinaryMerkleProof<ValidatorNodeBmtHasherBlake256>;
 
     #[inline]
     pub fn kernel_mr_hash_from_mmr(kernel_mmr: &KernelMmr) -> Result<FixedHash, MrHashError> {
@@ -20,8 +20,10 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
-use tari_common::SubConfigPath;
+use tari_common::{configuration::serializers, SubConfigPath};
 
 use crate::mempool::{reorg_pool::ReorgPoolConfig, unconfirmed_pool::UnconfirmedPoolConfig};
 
@@ -52,6 +54,10 @@ pub struct MempoolServiceConfig {
     pub initial_sync_max_transactions: usize,
     /// The maximum number of blocks added via sync or re-org to triggering a sync
     pub block_sync_trigger: usize,
+    /// If set, transactions are propagated to peers after a random delay of up to this many seconds, so that the
+    /// propagation timing does not reveal which node a transaction originated from. Default: None
+    #[serde(with = "serializers::optional_seconds")]
+    pub propagation_delay_max: Option<Duration>,
 }
 
 impl Default for MempoolServiceConfig {
@@ -60,6 +66,7 @@ impl Default for MempoolServiceConfig {
             initial_sync_num_peers: 2,
             initial_sync_max_transactions: 10_000,
             block_sync_trigger: 5,
+            propagation_delay_max: None,
         }
     }
 }
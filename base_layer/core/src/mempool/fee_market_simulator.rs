@@ -0,0 +1,235 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! A fee market simulator for consensus parameter proposals.
+//!
+//! Transactions are replayed through the unconfirmed pool's block template selection, one simulated block per height,
+//! under an alternative maximum block weight and transaction weight formula. The simulator reports the fees the
+//! simulated blocks would have earned and how many blocks each transaction would have waited to be confirmed.
+
+use std::{collections::HashMap, sync::Arc};
+
+use tari_common_types::types::PrivateKey;
+
+use crate::{
+    blocks::{Block, BlockHeader},
+    mempool::{unconfirmed_pool::UnconfirmedPool, MempoolError, UnconfirmedPoolConfig},
+    transactions::{
+        aggregated_body::AggregateBody,
+        tari_amount::MicroMinotari,
+        transaction_components::Transaction,
+        weight::TransactionWeight,
+    },
+};
+
+/// The parameters a fee market is simulated under
+#[derive(Debug, Clone, Copy)]
+pub struct FeeMarketParams {
+    /// The maximum weight of the transactions in a block, excluding the coinbase
+    pub max_transaction_weight: u64,
+    pub transaction_weight: TransactionWeight,
+    /// Transactions paying less than this per gram are rejected as they arrive
+    pub min_fee_per_gram: u64,
+}
+
+/// The outcome of a fee market simulation
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FeeMarketReport {
+    pub blocks: u64,
+    pub transactions_included: u64,
+    pub transactions_rejected: u64,
+    /// Transactions still waiting in the unconfirmed pool when the report was made
+    pub transactions_pending: u64,
+    pub total_fees: MicroMinotari,
+    pub total_weight: u64,
+    /// The number of blocks each included transaction waited after arriving, sorted ascending
+    pub latencies: Vec<u64>,
+}
+
+impl FeeMarketReport {
+    pub fn mean_latency(&self) -> f64 {
+        if self.latencies.is_empty() {
+            return 0.0;
+        }
+        self.latencies.iter().sum::<u64>() as f64 / self.latencies.len() as f64
+    }
+
+    /// The latency that `percentile` percent of the included transactions did not exceed
+    pub fn latency_percentile(&self, percentile: u8) -> u64 {
+        if self.latencies.is_empty() {
+            return 0;
+        }
+        let index = (self.latencies.len() - 1) * usize::from(percentile.min(100)) / 100;
+        self.latencies[index]
+    }
+
+    /// The mean weight of the simulated blocks as a fraction of the maximum
+    pub fn mean_fullness(&self, max_transaction_weight: u64) -> f64 {
+        if self.blocks == 0 || max_transaction_weight == 0 {
+            return 0.0;
+        }
+        self.total_weight as f64 / (self.blocks * max_transaction_weight) as f64
+    }
+}
+
+pub struct FeeMarketSimulator {
+    params: FeeMarketParams,
+    pool: UnconfirmedPool,
+    /// The height each pending transaction arrived at, by the signature of its first kernel
+    arrivals: HashMap<PrivateKey, u64>,
+    report: FeeMarketReport,
+}
+
+impl FeeMarketSimulator {
+    pub fn new(params: FeeMarketParams) -> Self {
+        Self {
+            params,
+            pool: UnconfirmedPool::new(UnconfirmedPoolConfig {
+                // Nothing is evicted, so that every transaction is either included or left pending
+                storage_capacity: usize::MAX,
+                ..Default::default()
+            }),
+            arrivals: HashMap::new(),
+            report: FeeMarketReport::default(),
+        }
+    }
+
+    /// Adds transactions that arrived in time for the block at `height`
+    pub fn add_transactions<I: IntoIterator<Item = Transaction>>(
+        &mut self,
+        height: u64,
+        transactions: I,
+    ) -> Result<(), MempoolError> {
+        for transaction in transactions {
+            let signature = match transaction.body.kernels().first() {
+                Some(kernel) => kernel.excess_sig.get_signature().clone(),
+                None => continue,
+            };
+            let weight = transaction.calculate_weight(&self.params.transaction_weight)?;
+            let fee = transaction.body.get_total_fee()?.as_u64();
+            if weight == 0 || fee / weight < self.params.min_fee_per_gram {
+                self.report.transactions_rejected += 1;
+                continue;
+            }
+            self.pool
+                .insert(Arc::new(transaction), None, &self.params.transaction_weight, false)
+                .map_err(|e| MempoolError::InternalError(e.to_string()))?;
+            self.arrivals.insert(signature, height);
+        }
+        Ok(())
+    }
+
+    /// Builds the block at `height` from the highest priority pending transactions, as a block template would be
+    pub fn mine_block(&mut self, height: u64) -> Result<(), MempoolError> {
+        let selected = self
+            .pool
+            .fetch_highest_priority_txs(self.params.max_transaction_weight)
+            .map_err(|e| MempoolError::InternalError(e.to_string()))?
+            .retrieved_transactions;
+        let mut body = AggregateBody::empty();
+        for transaction in &selected {
+            self.report.transactions_included += 1;
+            self.report.total_fees += transaction.body.get_total_fee()?;
+            self.report.total_weight += transaction.calculate_weight(&self.params.transaction_weight)?;
+            if let Some(arrival) = transaction
+                .body
+                .kernels()
+                .first()
+                .and_then(|k| self.arrivals.remove(k.excess_sig.get_signature()))
+            {
+                self.report.latencies.push(height.saturating_sub(arrival));
+            }
+            body.add_inputs(transaction.body.inputs().clone());
+            body.add_outputs(transaction.body.outputs().clone());
+            body.add_kernels(transaction.body.kernels().clone());
+        }
+        let block = Block::new(BlockHeader::new(0), body);
+        self.pool
+            .remove_published_and_discard_deprecated_transactions(&block)
+            .map_err(|e| MempoolError::InternalError(e.to_string()))?;
+        self.report.blocks += 1;
+        Ok(())
+    }
+
+    /// The outcome of the blocks simulated so far
+    pub fn report(&self) -> FeeMarketReport {
+        let mut report = self.report.clone();
+        report.transactions_pending = self.pool.len() as u64;
+        report.latencies.sort_unstable();
+        report
+    }
+}
+
+/// Splits the body of a mined block into one transaction per non-coinbase kernel. Blocks do not record which inputs and
+/// outputs belong to which kernel, so the non-coinbase inputs and outputs are dealt out to the transactions in turn.
+/// This keeps the block's total weight and fees, but not the exact weight of each transaction.
+pub fn split_block_body(body: &AggregateBody) -> Vec<Transaction> {
+    let kernels = body.kernels().iter().filter(|k| !k.is_coinbase()).collect::<Vec<_>>();
+    if kernels.is_empty() {
+        return Vec::new();
+    }
+    let mut transactions = kernels
+        .iter()
+        .map(|kernel| {
+            Transaction::new(
+                Vec::new(),
+                Vec::new(),
+                vec![(*kernel).clone()],
+                PrivateKey::default(),
+                PrivateKey::default(),
+            )
+        })
+        .collect::<Vec<_>>();
+    let num_transactions = transactions.len();
+    for (i, input) in body.inputs().iter().enumerate() {
+        transactions[i % num_transactions].body.add_input(input.clone());
+    }
+    for (i, output) in body.outputs().iter().filter(|o| !o.is_coinbase()).enumerate() {
+        transactions[i % num_transactions].body.add_output(output.clone());
+    }
+    transactions
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        transactions::{key_manager::create_memory_db_key_manager, tari_amount::MicroMinotari},
+        tx,
+    };
+
+    #[tokio::test]
+    async fn it_delays_low_fee_transactions_when_blocks_are_full() {
+        let key_manager = create_memory_db_key_manager().unwrap();
+        let weighting = TransactionWeight::latest();
+        let mut transactions = Vec::new();
+        for fee_per_gram in [5, 10, 20] {
+            let tx = tx!(MicroMinotari(100_000), fee: MicroMinotari(fee_per_gram), inputs: 1, outputs: 1, &key_manager)
+                .unwrap()
+                .0;
+            transactions.push(tx);
+        }
+        // Only one of the transactions fits in a block
+        let tx_weight = transactions
+            .iter()
+            .map(|tx| tx.calculate_weight(&weighting).unwrap())
+            .max()
+            .unwrap();
+
+        let mut simulator = FeeMarketSimulator::new(FeeMarketParams {
+            max_transaction_weight: tx_weight,
+            transaction_weight: weighting,
+            min_fee_per_gram: 6,
+        });
+        simulator.add_transactions(1, transactions).unwrap();
+        simulator.mine_block(1).unwrap();
+        simulator.mine_block(2).unwrap();
+        let report = simulator.report();
+        assert_eq!(report.blocks, 2);
+        assert_eq!(report.transactions_rejected, 1);
+        assert_eq!(report.transactions_included, 2);
+        assert_eq!(report.transactions_pending, 0);
+        assert_eq!(report.latencies, vec![0, 1]);
+        assert_eq!(report.latency_percentile(100), 1);
+    }
+}
@@ -24,7 +24,9 @@ use std::sync::{Arc, RwLock};
 
 use log::debug;
 use tari_common_types::types::{FixedHash, PrivateKey, Signature};
+use tari_comms::peer_manager::NodeId;
 use tokio::task;
+use tracing::instrument;
 
 use crate::{
     blocks::Block,
@@ -37,6 +39,7 @@ use crate::{
         StateResponse,
         StatsResponse,
         TxStorageResponse,
+        UnconfirmedPoolConfig,
     },
     transactions::transaction_components::Transaction,
     validation::TransactionValidator,
@@ -61,6 +64,7 @@ impl Mempool {
     }
 
     /// Insert an unconfirmed transaction into the Mempool.
+    #[instrument(name = "mempool::insert", level = "debug", skip_all, err)]
     pub async fn insert(&self, tx: Arc<Transaction>) -> Result<TxStorageResponse, MempoolError> {
         self.with_write_access(|storage| {
             storage
@@ -70,6 +74,21 @@ impl Mempool {
         .await
     }
 
+    /// Inserts an unconfirmed transaction that was submitted by `source_peer` into the Mempool, taking the priority
+    /// whitelist into account.
+    pub async fn insert_from(
+        &self,
+        tx: Arc<Transaction>,
+        source_peer: Option<NodeId>,
+    ) -> Result<TxStorageResponse, MempoolError> {
+        self.with_write_access(move |storage| {
+            storage
+                .insert_from(tx, source_peer.as_ref())
+                .map_err(|e| MempoolError::InternalError(e.to_string()))
+        })
+        .await
+    }
+
     /// Inserts all transactions into the mempool.
     pub async fn insert_all(&self, transactions: Vec<Arc<Transaction>>) -> Result<(), MempoolError> {
         self.with_write_access(|storage| {
@@ -107,6 +126,12 @@ impl Mempool {
             .await
     }
 
+    /// Replaces the limits and minimum fee of the unconfirmed pool
+    pub async fn set_unconfirmed_pool_config(&self, config: UnconfirmedPoolConfig) -> Result<(), MempoolError> {
+        self.with_write_access(move |storage| storage.set_unconfirmed_pool_config(config))
+            .await
+    }
+
     /// After a sync event, we can move all orphan transactions to the unconfirmed pool after validation
     pub async fn process_sync(&self) -> Result<(), MempoolError> {
         self.with_write_access(move |storage| storage.process_sync()).await
@@ -24,6 +24,7 @@ use std::{sync::Arc, time::Instant};
 
 use log::*;
 use tari_common_types::types::{FixedHash, PrivateKey, Signature};
+use tari_comms::peer_manager::NodeId;
 use tari_utilities::hex::Hex;
 
 use crate::{
@@ -32,7 +33,13 @@ use crate::{
     mempool::{
         error::MempoolError,
         reorg_pool::ReorgPool,
-        unconfirmed_pool::{RetrieveResults, TransactionKey, UnconfirmedPool, UnconfirmedPoolError},
+        unconfirmed_pool::{
+            RetrieveResults,
+            TransactionKey,
+            UnconfirmedPool,
+            UnconfirmedPoolConfig,
+            UnconfirmedPoolError,
+        },
         FeePerGramStat,
         MempoolConfig,
         StateResponse,
@@ -75,6 +82,25 @@ impl MempoolStorage {
 
     /// Insert an unconfirmed transaction into the Mempool.
     pub fn insert(&mut self, tx: Arc<Transaction>) -> Result<TxStorageResponse, UnconfirmedPoolError> {
+        self.insert_from(tx, None)
+    }
+
+    /// Insert an unconfirmed transaction that was submitted by `source_peer` into the Mempool. Transactions that match
+    /// the priority whitelist are accepted regardless of fee and are prioritised over all other transactions.
+    pub fn insert_from(
+        &mut self,
+        tx: Arc<Transaction>,
+        source_peer: Option<&NodeId>,
+    ) -> Result<TxStorageResponse, UnconfirmedPoolError> {
+        let is_prioritised = self.unconfirmed_pool.is_prioritised(&tx, source_peer);
+        self.insert_with_priority(tx, is_prioritised)
+    }
+
+    fn insert_with_priority(
+        &mut self,
+        tx: Arc<Transaction>,
+        is_prioritised: bool,
+    ) -> Result<TxStorageResponse, UnconfirmedPoolError> {
         let tx_id = tx
             .body
             .kernels()
@@ -91,7 +117,7 @@ impl MempoolStorage {
             },
         };
         // This check is almost free, so lets check this before we do any expensive validation.
-        if tx_fee.as_u64() < self.unconfirmed_pool.config.min_fee {
+        if !is_prioritised && tx_fee.as_u64() < self.unconfirmed_pool.config.min_fee {
             debug!(target: LOG_TARGET, "Tx: ({}) fee too low, rejecting",tx_id);
             return Ok(TxStorageResponse::NotStoredFeeTooLow);
         }
@@ -105,7 +131,7 @@ impl MempoolStorage {
                 );
                 let timer = Instant::now();
                 let weight = self.get_transaction_weighting();
-                self.unconfirmed_pool.insert(tx, None, &weight)?;
+                self.unconfirmed_pool.insert(tx, None, &weight, is_prioritised)?;
                 debug!(
                     target: LOG_TARGET,
                     "Transaction {} inserted in {:.2?}",
@@ -117,7 +143,8 @@ impl MempoolStorage {
             Err(ValidationError::UnknownInputs(dependent_outputs)) => {
                 if self.unconfirmed_pool.contains_all_outputs(&dependent_outputs) {
                     let weight = self.get_transaction_weighting();
-                    self.unconfirmed_pool.insert(tx, Some(dependent_outputs), &weight)?;
+                    self.unconfirmed_pool
+                        .insert(tx, Some(dependent_outputs), &weight, is_prioritised)?;
                     Ok(TxStorageResponse::UnconfirmedPool)
                 } else {
                     warn!(target: LOG_TARGET, "Validation failed due to unknown inputs");
@@ -164,13 +191,24 @@ impl MempoolStorage {
         &mut self,
         transactions: Vec<(TransactionKey, Arc<Transaction>)>,
     ) -> Result<(), MempoolError> {
-        for (tx_key, _) in &transactions {
+        // Transactions that were prioritised because of the wallet that submitted them must remain prioritised
+        let transactions = transactions
+            .into_iter()
+            .map(|(tx_key, tx)| {
+                let is_prioritised = self.unconfirmed_pool.is_prioritised_transaction(tx_key) ||
+                    self.unconfirmed_pool.is_prioritised(&tx, None);
+                (tx_key, tx, is_prioritised)
+            })
+            .collect::<Vec<_>>();
+        for (tx_key, _, _) in &transactions {
             self.unconfirmed_pool
                 .remove_transaction(*tx_key)
                 .map_err(|e| MempoolError::InternalError(e.to_string()))?;
         }
-        self.insert_txs(transactions.iter().map(|(_, tx)| tx.clone()).collect())
-            .map_err(|e| MempoolError::InternalError(e.to_string()))?;
+        for (_, tx, is_prioritised) in transactions {
+            self.insert_with_priority(tx, is_prioritised)
+                .map_err(|e| MempoolError::InternalError(e.to_string()))?;
+        }
 
         Ok(())
     }
@@ -283,6 +321,12 @@ impl MempoolStorage {
         Ok(())
     }
 
+    /// Replaces the configuration of the unconfirmed pool, e.g. after the configuration file has been reloaded
+    pub fn set_unconfirmed_pool_config(&mut self, config: UnconfirmedPoolConfig) -> Result<(), MempoolError> {
+        self.unconfirmed_pool.set_config(config)?;
+        Ok(())
+    }
+
     /// After a sync event, we need to try to add in all the transaction form the reorg pool.
     pub fn process_sync(&mut self) -> Result<(), MempoolError> {
         debug!(target: LOG_TARGET, "Mempool processing sync finished");
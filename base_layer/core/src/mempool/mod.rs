@@ -42,6 +42,8 @@ mod rpc;
 pub use rpc::create_mempool_rpc_service;
 #[cfg(feature = "base_node")]
 pub use rpc::{MempoolRpcClient, MempoolRpcServer, MempoolRpcService, MempoolService};
+#[cfg(feature = "base_node")]
+pub mod fee_market_simulator;
 #[cfg(feature = "metrics")]
 mod metrics;
 #[cfg(feature = "base_node")]
@@ -57,6 +59,8 @@ pub use mempool::Mempool;
 
 #[cfg(feature = "base_node")]
 pub use self::config::{MempoolConfig, MempoolServiceConfig};
+#[cfg(feature = "base_node")]
+pub use self::unconfirmed_pool::UnconfirmedPoolConfig;
 
 #[cfg(any(feature = "base_node", feature = "mempool_proto"))]
 pub mod proto;
@@ -41,7 +41,14 @@ use crate::transactions::{
 pub struct FeePriority(Vec<u8>);
 
 impl FeePriority {
-    pub fn new(transaction: &Transaction, insert_epoch: u64, weight: u64) -> Result<Self, TransactionError> {
+    /// Prioritised transactions have a higher priority than all other transactions, and are ordered by fee and age
+    /// amongst themselves
+    pub fn new(
+        transaction: &Transaction,
+        insert_epoch: u64,
+        weight: u64,
+        is_prioritised: bool,
+    ) -> Result<Self, TransactionError> {
         let fee_per_byte = transaction
             .body
             .get_total_fee()?
@@ -55,9 +62,10 @@ impl FeePriority {
         let fee_priority = fee_per_byte.to_be_bytes();
         let age_priority = (u64::MAX - insert_epoch).to_be_bytes();
 
-        let mut priority = vec![0u8; 8 + 8 + 64];
-        priority[..8].copy_from_slice(&fee_priority[..]);
-        priority[8..16].copy_from_slice(&age_priority[..]);
+        let mut priority = vec![0u8; 1 + 8 + 8 + 64];
+        priority[0] = u8::from(is_prioritised);
+        priority[1..9].copy_from_slice(&fee_priority[..]);
+        priority[9..17].copy_from_slice(&age_priority[..]);
         // Use the aggregate signature and nonce.
         // If a transaction has many kernels, unless they are all identical, the fee priority will be different.
         let (agg_sig, agg_nonce) = transaction
@@ -69,8 +77,8 @@ impl FeePriority {
                 (PrivateKey::default(), PublicKey::default()),
                 |(agg_sk, agg_nonce), (sig, nonce)| (agg_sk + sig, agg_nonce + nonce),
             );
-        priority[16..48].copy_from_slice(agg_sig.as_bytes());
-        priority[48..80].copy_from_slice(agg_nonce.as_bytes());
+        priority[17..49].copy_from_slice(agg_sig.as_bytes());
+        priority[49..81].copy_from_slice(agg_nonce.as_bytes());
         Ok(Self(priority))
     }
 }
@@ -84,6 +92,8 @@ pub struct PrioritizedTransaction {
     pub fee_per_byte: u64,
     pub weight: u64,
     pub dependent_output_hashes: Vec<HashOutput>,
+    /// True if the transaction is whitelisted by the node operator, see [crate::mempool::UnconfirmedPoolConfig]
+    pub is_prioritised: bool,
 }
 
 impl PrioritizedTransaction {
@@ -92,27 +102,41 @@ impl PrioritizedTransaction {
         weighting: &TransactionWeight,
         transaction: Arc<Transaction>,
         dependent_outputs: Option<Vec<HashOutput>>,
+        is_prioritised: bool,
     ) -> Result<PrioritizedTransaction, TransactionError> {
         let weight = transaction.calculate_weight(weighting)?;
+        // Consolidations are prioritised by their discounted fee weight, but still take up their full block weight
+        let fee_weight = transaction.calculate_fee_weight(weighting)?;
         let insert_epoch = match SystemTime::now().duration_since(UNIX_EPOCH) {
             Ok(n) => n.as_secs(),
             Err(_) => 0,
         };
         Ok(Self {
             key,
-            priority: FeePriority::new(&transaction, insert_epoch, weight)?,
+            priority: FeePriority::new(&transaction, insert_epoch, fee_weight, is_prioritised)?,
             fee_per_byte: transaction
                 .body
                 .get_total_fee()?
                 .as_u64()
                 .saturating_mul(1000)
-                .checked_div(weight)
+                .checked_div(fee_weight)
                 .ok_or(TransactionError::ZeroWeight)?,
             weight,
             transaction,
             dependent_output_hashes: dependent_outputs.unwrap_or_default(),
+            is_prioritised,
         })
     }
+
+    /// The fee per byte that a branch of transactions ending in this one is selected for block templates by, given the
+    /// fee per byte of the branch. Branches that end in a prioritised transaction are selected before all others.
+    pub fn selection_fee_per_byte(&self, fee_per_byte: u64) -> u64 {
+        if self.is_prioritised {
+            u64::MAX
+        } else {
+            fee_per_byte
+        }
+    }
 }
 
 impl Display for PrioritizedTransaction {
@@ -148,10 +172,22 @@ mod tests {
         let weighting = TransactionWeight::latest();
         let epoch = u64::MAX / 2;
         let tx = create_tx_with_fee(2 * uT, &key_manager).await;
-        let p1 = FeePriority::new(&tx, epoch, tx.calculate_weight(&weighting).expect("Failed to get tx")).unwrap();
+        let p1 = FeePriority::new(
+            &tx,
+            epoch,
+            tx.calculate_weight(&weighting).expect("Failed to get tx"),
+            false,
+        )
+        .unwrap();
 
         let tx = create_tx_with_fee(3 * uT, &key_manager).await;
-        let p2 = FeePriority::new(&tx, epoch, tx.calculate_weight(&weighting).expect("Failed to get tx")).unwrap();
+        let p2 = FeePriority::new(
+            &tx,
+            epoch,
+            tx.calculate_weight(&weighting).expect("Failed to get tx"),
+            false,
+        )
+        .unwrap();
 
         assert!(p2 > p1);
     }
@@ -162,19 +198,42 @@ mod tests {
         let weighting = TransactionWeight::latest();
         let epoch = u64::MAX / 2;
         let tx = create_tx_with_fee(2 * uT, &key_manager).await;
-        let p1 = FeePriority::new(&tx, epoch, tx.calculate_weight(&weighting).expect("Failed to get tx")).unwrap();
+        let p1 = FeePriority::new(
+            &tx,
+            epoch,
+            tx.calculate_weight(&weighting).expect("Failed to get tx"),
+            false,
+        )
+        .unwrap();
 
         let tx = create_tx_with_fee(2 * uT, &key_manager).await;
         let p2 = FeePriority::new(
             &tx,
             epoch - 1,
             tx.calculate_weight(&weighting).expect("Failed to get tx"),
+            false,
         )
         .unwrap();
 
         assert!(p2 > p1);
     }
 
+    #[tokio::test]
+    async fn whitelisting_increases_priority_over_fee() {
+        let key_manager = create_memory_db_key_manager().unwrap();
+        let weighting = TransactionWeight::latest();
+        let epoch = u64::MAX / 2;
+        let tx = create_tx_with_fee(2 * uT, &key_manager).await;
+        let weight = tx.calculate_weight(&weighting).expect("Failed to get tx");
+        let p1 = FeePriority::new(&tx, epoch, weight, true).unwrap();
+
+        let tx = create_tx_with_fee(100 * uT, &key_manager).await;
+        let weight = tx.calculate_weight(&weighting).expect("Failed to get tx");
+        let p2 = FeePriority::new(&tx, epoch, weight, false).unwrap();
+
+        assert!(p1 > p2);
+    }
+
     #[test]
     fn prioritized_from_empty_transaction() {
         let weighting = TransactionWeight::latest();
@@ -189,6 +248,7 @@ mod tests {
                 Default::default(),
             )),
             None,
+            false,
         ) {
             Ok(_) => panic!("Empty transaction should not be valid"),
             Err(e) => assert_eq!(e, TransactionError::ZeroWeight),
@@ -202,6 +262,7 @@ mod tests {
             &Transaction::new(vec![], vec![], vec![], Default::default(), Default::default()),
             SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs(),
             weight,
+            false,
         ) {
             Ok(_) => panic!("Empty transaction should not be valid"),
             Err(e) => assert_eq!(e, TransactionError::ZeroWeight),
@@ -21,6 +21,7 @@
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use tari_common_types::types::Signature;
+use tari_comms::peer_manager::NodeId;
 use tari_service_framework::{reply_channel::TrySenderService, Service};
 
 use crate::{
@@ -83,6 +84,23 @@ impl MempoolHandle {
         }
     }
 
+    /// Submits a transaction on behalf of `peer`, allowing the mempool to prioritise transactions from whitelisted
+    /// wallets.
+    pub async fn submit_transaction_from_peer(
+        &mut self,
+        transaction: Transaction,
+        peer: NodeId,
+    ) -> Result<TxStorageResponse, MempoolServiceError> {
+        match self
+            .inner
+            .call(MempoolRequest::SubmitTransactionFromPeer(transaction, peer))
+            .await??
+        {
+            MempoolResponse::TxStorage(response) => Ok(response),
+            _ => Err(MempoolServiceError::InvalidResponse("Incorrect response".to_string())),
+        }
+    }
+
     pub async fn get_fee_per_gram_stats(
         &mut self,
         count: usize,
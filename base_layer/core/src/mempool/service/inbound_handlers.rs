@@ -61,7 +61,14 @@ impl MempoolInboundHandlers {
     /// Handle inbound Mempool service requests from remote nodes and local services.
     pub async fn handle_request(&mut self, request: MempoolRequest) -> Result<MempoolResponse, MempoolServiceError> {
         trace!(target: LOG_TARGET, "Handling remote request: {}", request);
-        use MempoolRequest::{GetFeePerGramStats, GetState, GetStats, GetTxStateByExcessSig, SubmitTransaction};
+        use MempoolRequest::{
+            GetFeePerGramStats,
+            GetState,
+            GetStats,
+            GetTxStateByExcessSig,
+            SubmitTransaction,
+            SubmitTransactionFromPeer,
+        };
         match request {
             GetStats => Ok(MempoolResponse::Stats(self.mempool.stats().await?)),
             GetState => Ok(MempoolResponse::State(self.mempool.state().await?)),
@@ -81,6 +88,20 @@ impl MempoolInboundHandlers {
                 );
                 Ok(MempoolResponse::TxStorage(self.submit_transaction(tx, None).await?))
             },
+            SubmitTransactionFromPeer(tx, peer) => {
+                let first_tx_kernel_excess_sig = tx
+                    .first_kernel_excess_sig()
+                    .ok_or(MempoolServiceError::TransactionNoKernels)?
+                    .get_signature()
+                    .to_hex();
+                debug!(
+                    target: LOG_TARGET,
+                    "Transaction ({}) submitted by peer {} using request.", first_tx_kernel_excess_sig, peer,
+                );
+                Ok(MempoolResponse::TxStorage(
+                    self.submit_transaction(tx, Some(peer)).await?,
+                ))
+            },
             GetFeePerGramStats { count, tip_height } => {
                 let stats = self.mempool.get_fee_per_gram_stats(count, tip_height).await?;
                 Ok(MempoolResponse::FeePerGramStats { response: stats })
@@ -134,7 +155,7 @@ impl MempoolInboundHandlers {
             );
             return Ok(tx_storage);
         }
-        match self.mempool.insert(tx.clone()).await {
+        match self.mempool.insert_from(tx.clone(), source_peer.clone()).await {
             Ok(tx_storage) => {
                 #[cfg(feature = "metrics")]
                 if tx_storage.is_stored() {
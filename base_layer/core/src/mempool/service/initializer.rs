@@ -20,7 +20,7 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{convert::TryFrom, sync::Arc};
+use std::{convert::TryFrom, sync::Arc, time::Duration};
 
 use futures::{Stream, StreamExt};
 use log::*;
@@ -28,6 +28,7 @@ use tari_comms_dht::Dht;
 use tari_p2p::{
     comms_connector::{PeerMessage, SubscriptionFactory},
     domain_message::DomainMessage,
+    services::gossip::GossipHandle,
     tari_message::TariMessageType,
 };
 use tari_service_framework::{
@@ -62,6 +63,7 @@ const SUBSCRIPTION_LABEL: &str = "Mempool";
 pub struct MempoolServiceInitializer {
     mempool: Mempool,
     inbound_message_subscription_factory: Arc<SubscriptionFactory>,
+    propagation_delay_max: Option<Duration>,
 }
 
 impl MempoolServiceInitializer {
@@ -70,9 +72,16 @@ impl MempoolServiceInitializer {
         Self {
             mempool,
             inbound_message_subscription_factory,
+            propagation_delay_max: None,
         }
     }
 
+    /// Delay the propagation of each outbound transaction by a random duration of up to `delay_max`
+    pub fn with_propagation_delay(mut self, delay_max: Option<Duration>) -> Self {
+        self.propagation_delay_max = delay_max;
+        self
+    }
+
     /// Create a stream of 'New Transaction` messages
     fn inbound_transaction_stream(&self) -> impl Stream<Item = DomainMessage<Transaction>> {
         self.inbound_message_subscription_factory
@@ -133,9 +142,12 @@ impl ServiceInitializer for MempoolServiceInitializer {
         context.register_handle(outbound_mp_interface);
         context.register_handle(local_mp_interface);
 
+        let propagation_delay_max = self.propagation_delay_max;
         context.spawn_until_shutdown(move |handles| {
             let outbound_message_service = handles.expect_handle::<Dht>().outbound_requester();
             let base_node = handles.expect_handle::<LocalNodeCommsInterface>();
+            // Transactions are propagated over the gossip mesh if the gossip service is running
+            let gossip = handles.get_handle::<GossipHandle>();
 
             let streams = MempoolStreams {
                 outbound_tx_stream,
@@ -145,7 +157,9 @@ impl ServiceInitializer for MempoolServiceInitializer {
                 request_receiver,
             };
             debug!(target: LOG_TARGET, "Mempool service started");
-            MempoolService::new(outbound_message_service, inbound_handlers).start(streams)
+            MempoolService::new(outbound_message_service, inbound_handlers, gossip)
+                .with_propagation_delay(propagation_delay_max)
+                .start(streams)
         });
 
         Ok(())
@@ -24,6 +24,7 @@ use core::fmt::{Display, Error, Formatter};
 
 use serde::{Deserialize, Serialize};
 use tari_common_types::types::Signature;
+use tari_comms::peer_manager::NodeId;
 use tari_utilities::hex::Hex;
 
 use crate::{common::waiting_requests::RequestKey, transactions::transaction_components::Transaction};
@@ -36,6 +37,7 @@ pub enum MempoolRequest {
     GetState,
     GetTxStateByExcessSig(Signature),
     SubmitTransaction(Transaction),
+    SubmitTransactionFromPeer(Transaction, NodeId),
     GetFeePerGramStats { count: usize, tip_height: u64 },
 }
 
@@ -54,6 +56,13 @@ impl Display for MempoolRequest {
                     .unwrap_or_else(|| "No kernels!".to_string());
                 write!(f, "SubmitTransaction ({})", sig_hex)
             },
+            MempoolRequest::SubmitTransactionFromPeer(tx, peer) => {
+                let sig_hex = tx
+                    .first_kernel_excess_sig()
+                    .map(|sig| sig.get_signature().to_hex())
+                    .unwrap_or_else(|| "No kernels!".to_string());
+                write!(f, "SubmitTransactionFromPeer ({}, {})", sig_hex, peer)
+            },
             MempoolRequest::GetFeePerGramStats { count, tip_height } => {
                 write!(f, "GetFeePerGramStats(count: {}, tip_height: {})", *count, *tip_height)
             },
@@ -20,20 +20,21 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{convert::TryFrom, sync::Arc};
+use std::{convert::TryFrom, sync::Arc, time::Duration};
 
 use futures::{pin_mut, stream::StreamExt, Stream};
 use log::*;
+use rand::{rngs::OsRng, Rng};
 use tari_comms::peer_manager::NodeId;
 use tari_comms_dht::{
     domain_message::OutboundDomainMessage,
     envelope::NodeDestination,
     outbound::{DhtOutboundError, OutboundEncryption, OutboundMessageRequester},
 };
-use tari_p2p::{domain_message::DomainMessage, tari_message::TariMessageType};
+use tari_p2p::{domain_message::DomainMessage, services::gossip::GossipHandle, tari_message::TariMessageType};
 use tari_service_framework::{reply_channel, reply_channel::RequestContext};
 use tari_utilities::hex::Hex;
-use tokio::{sync::mpsc, task};
+use tokio::{sync::mpsc, task, time};
 
 use crate::{
     base_node::comms_interface::{BlockEvent, BlockEventReceiver},
@@ -63,16 +64,30 @@ pub struct MempoolStreams<STxIn, SLocalReq> {
 pub struct MempoolService {
     outbound_message_service: OutboundMessageRequester,
     inbound_handlers: MempoolInboundHandlers,
+    gossip: Option<GossipHandle>,
+    propagation_delay_max: Option<Duration>,
 }
 
 impl MempoolService {
-    pub fn new(outbound_message_service: OutboundMessageRequester, inbound_handlers: MempoolInboundHandlers) -> Self {
+    pub fn new(
+        outbound_message_service: OutboundMessageRequester,
+        inbound_handlers: MempoolInboundHandlers,
+        gossip: Option<GossipHandle>,
+    ) -> Self {
         Self {
             outbound_message_service,
             inbound_handlers,
+            gossip,
+            propagation_delay_max: None,
         }
     }
 
+    /// Delay the propagation of each outbound transaction by a random duration of up to `delay_max`
+    pub fn with_propagation_delay(mut self, delay_max: Option<Duration>) -> Self {
+        self.propagation_delay_max = delay_max.filter(|d| !d.is_zero());
+        self
+    }
+
     pub async fn start<STxIn, SLocalReq>(
         mut self,
         streams: MempoolStreams<STxIn, SLocalReq>,
@@ -88,6 +103,10 @@ impl MempoolService {
         pin_mut!(local_request_stream);
         let mut block_event_stream = streams.block_event_stream;
         let mut request_receiver = streams.request_receiver;
+        // Delayed transactions come back through this channel. The sender is dropped if there is no delay, which
+        // disables the branch below.
+        let (delayed_tx_sender, mut delayed_tx_stream) = mpsc::unbounded_channel();
+        let delayed_tx_sender = self.propagation_delay_max.map(|max| (max, delayed_tx_sender));
 
         loop {
             tokio::select! {
@@ -99,6 +118,20 @@ impl MempoolService {
 
                 // Outbound tx messages from the OutboundMempoolServiceInterface
                 Some((txn, excluded_peers)) = outbound_tx_stream.recv() => {
+                    match &delayed_tx_sender {
+                        Some((max, sender)) => {
+                            Self::spawn_delayed_outbound_tx(*max, sender.clone(), txn, excluded_peers);
+                        },
+                        None => {
+                            let _res = self.handle_outbound_tx(txn, excluded_peers).await.map_err(|e|
+                                error!(target: LOG_TARGET, "Error sending outbound tx message: {}", e)
+                            );
+                        },
+                    }
+                },
+
+                // Outbound tx messages whose random propagation delay has passed
+                Some((txn, excluded_peers)) = delayed_tx_stream.recv() => {
                     let _res = self.handle_outbound_tx(txn, excluded_peers).await.map_err(|e|
                         error!(target: LOG_TARGET, "Error sending outbound tx message: {}", e)
                     );
@@ -153,6 +186,20 @@ impl MempoolService {
         });
     }
 
+    fn spawn_delayed_outbound_tx(
+        delay_max: Duration,
+        sender: mpsc::UnboundedSender<(Arc<Transaction>, Vec<NodeId>)>,
+        tx: Arc<Transaction>,
+        exclude_peers: Vec<NodeId>,
+    ) {
+        let delay = OsRng.gen_range(Duration::ZERO..=delay_max);
+        trace!(target: LOG_TARGET, "Delaying outbound tx by {:.2?}", delay);
+        task::spawn(async move {
+            time::sleep(delay).await;
+            let _result = sender.send((tx, exclude_peers));
+        });
+    }
+
     fn spawn_handle_block_event(&self, block_event: Arc<BlockEvent>) {
         let mut inbound_handlers = self.inbound_handlers.clone();
         task::spawn(async move {
@@ -199,6 +246,18 @@ impl MempoolService {
         tx: Arc<Transaction>,
         exclude_peers: Vec<NodeId>,
     ) -> Result<(), MempoolServiceError> {
+        if let Some(gossip) = self.gossip.as_mut() {
+            let msg = proto::types::Transaction::try_from(tx).map_err(MempoolServiceError::ConversionError)?;
+            return gossip
+                .publish(TariMessageType::NewTransaction, msg, exclude_peers)
+                .await
+                .map(|_| ())
+                .map_err(|e| {
+                    error!(target: LOG_TARGET, "Handle outbound tx failure. {:?}", e);
+                    MempoolServiceError::OutboundMessageService(e.to_string())
+                });
+        }
+
         let result = self
             .outbound_message_service
             .flood(
@@ -124,7 +124,14 @@ impl MempoolServiceMock {
     }
 
     async fn handle_request(&self, req: MempoolRequest) -> Result<MempoolResponse, MempoolServiceError> {
-        use MempoolRequest::{GetFeePerGramStats, GetState, GetStats, GetTxStateByExcessSig, SubmitTransaction};
+        use MempoolRequest::{
+            GetFeePerGramStats,
+            GetState,
+            GetStats,
+            GetTxStateByExcessSig,
+            SubmitTransaction,
+            SubmitTransactionFromPeer,
+        };
 
         self.state.inc_call_count();
         match req {
@@ -133,7 +140,7 @@ impl MempoolServiceMock {
             GetTxStateByExcessSig(_) => Ok(MempoolResponse::TxStorage(
                 self.state.get_tx_state_by_excess_sig.lock().await.clone(),
             )),
-            SubmitTransaction(_) => Ok(MempoolResponse::TxStorage(
+            SubmitTransaction(_) | SubmitTransactionFromPeer(..) => Ok(MempoolResponse::TxStorage(
                 self.state.submit_transaction.lock().await.clone(),
             )),
             GetFeePerGramStats { .. } => {
@@ -27,7 +27,8 @@ use std::{
 
 use log::*;
 use serde::{Deserialize, Serialize};
-use tari_common_types::types::{FixedHash, HashOutput, PrivateKey, Signature};
+use tari_common_types::types::{Commitment, FixedHash, HashOutput, PrivateKey, PublicKey, Signature};
+use tari_comms::peer_manager::NodeId;
 use tokio::time::Instant;
 
 use crate::{
@@ -51,7 +52,7 @@ pub const LOG_TARGET: &str = "c::mp::unconfirmed_pool::unconfirmed_pool_storage"
 pub type TransactionKey = usize;
 
 /// Configuration for the UnconfirmedPool
-#[derive(Clone, Copy, Serialize, Deserialize, Debug)]
+#[derive(Clone, Serialize, Deserialize, Debug)]
 #[serde(deny_unknown_fields)]
 pub struct UnconfirmedPoolConfig {
     /// The maximum number of transactions that can be stored in the Unconfirmed Transaction pool
@@ -61,6 +62,12 @@ pub struct UnconfirmedPoolConfig {
     pub weight_tx_skip_count: usize,
     /// The minimum fee accepted by this mempool
     pub min_fee: u64,
+    /// Transactions with any of these kernel excesses are accepted regardless of their fee, are the last to be
+    /// evicted, and are selected for block templates before all other transactions
+    pub priority_kernel_excesses: Vec<Commitment>,
+    /// The public keys of wallets whose transactions are prioritised in the same way when the wallet submits them to
+    /// this node
+    pub priority_wallets: Vec<PublicKey>,
 }
 
 impl Default for UnconfirmedPoolConfig {
@@ -69,6 +76,8 @@ impl Default for UnconfirmedPoolConfig {
             storage_capacity: 40_000,
             weight_tx_skip_count: 20,
             min_fee: 0,
+            priority_kernel_excesses: Vec::new(),
+            priority_wallets: Vec::new(),
         }
     }
 }
@@ -114,14 +123,47 @@ impl UnconfirmedPool {
         }
     }
 
+    /// Returns true if the transaction has a whitelisted kernel excess, or was submitted by a whitelisted wallet
+    pub fn is_prioritised(&self, tx: &Transaction, source_peer: Option<&NodeId>) -> bool {
+        let has_priority_excess = tx
+            .body
+            .kernels()
+            .iter()
+            .any(|kernel| self.config.priority_kernel_excesses.contains(&kernel.excess));
+        let is_from_priority_wallet = source_peer.is_some_and(|peer| {
+            self.config
+                .priority_wallets
+                .iter()
+                .any(|public_key| NodeId::from_public_key(public_key) == *peer)
+        });
+        has_priority_excess || is_from_priority_wallet
+    }
+
+    /// Returns true if the stored transaction was prioritised when it was inserted
+    pub(crate) fn is_prioritised_transaction(&self, tx_key: TransactionKey) -> bool {
+        self.tx_by_key.get(&tx_key).is_some_and(|tx| tx.is_prioritised)
+    }
+
+    /// Replaces the configuration of the pool. If the new storage capacity is smaller than the number of stored
+    /// transactions, the lowest priority transactions are removed.
+    pub fn set_config(&mut self, config: UnconfirmedPoolConfig) -> Result<(), UnconfirmedPoolError> {
+        self.config = config;
+        while self.tx_by_key.len() > self.config.storage_capacity {
+            self.remove_lowest_priority_tx()?;
+        }
+        Ok(())
+    }
+
     /// Insert a new transaction into the UnconfirmedPool. Low priority transactions will be removed to make space for
     /// higher priority transactions. The lowest priority transactions will be removed when the maximum capacity is
     /// reached and the new transaction has a higher priority than the currently stored lowest priority transaction.
+    /// Prioritised transactions have a higher priority than all other transactions.
     pub fn insert(
         &mut self,
         tx: Arc<Transaction>,
         dependent_outputs: Option<Vec<HashOutput>>,
         transaction_weighting: &TransactionWeight,
+        is_prioritised: bool,
     ) -> Result<(), UnconfirmedPoolError> {
         if tx
             .body
@@ -133,7 +175,8 @@ impl UnconfirmedPool {
         }
 
         let new_key = self.get_next_key();
-        let prioritized_tx = PrioritizedTransaction::new(new_key, transaction_weighting, tx, dependent_outputs)?;
+        let prioritized_tx =
+            PrioritizedTransaction::new(new_key, transaction_weighting, tx, dependent_outputs, is_prioritised)?;
         if self.tx_by_key.len() >= self.config.storage_capacity {
             if prioritized_tx.priority < *self.lowest_priority()? {
                 return Ok(());
@@ -172,7 +215,7 @@ impl UnconfirmedPool {
         transaction_weighting: &TransactionWeight,
     ) -> Result<(), UnconfirmedPoolError> {
         for tx in txs {
-            self.insert(tx, None, transaction_weighting)?;
+            self.insert(tx, None, transaction_weighting, false)?;
         }
         Ok(())
     }
@@ -231,7 +274,7 @@ impl UnconfirmedPool {
                 &mut potentional_to_add,
                 &mut depended_on,
                 &mut recompute,
-                prioritized_transaction.fee_per_byte,
+                prioritized_transaction.selection_fee_per_byte(prioritized_transaction.fee_per_byte),
             )?;
             if curr_skip_count >= self.config.weight_tx_skip_count {
                 break;
@@ -266,7 +309,8 @@ impl UnconfirmedPool {
                             .or_insert_with(|| vec![tx_key]);
                     }
                 }
-                let fee_per_byte = total_transaction_fees.saturating_mul(1000) / total_transaction_weight;
+                let fee_per_byte = prioritized_transaction
+                    .selection_fee_per_byte(total_transaction_fees.saturating_mul(1000) / total_transaction_weight);
                 complete_transaction_branch.insert(
                     *tx_key,
                     (
@@ -334,7 +378,11 @@ impl UnconfirmedPool {
                 let (_, total_transaction_weight, total_transaction_fees) = complete_transaction_branch
                     .get(&tx_key)
                     .ok_or(UnconfirmedPoolError::StorageOutofSync)?;
-                let fee_per_byte = total_transaction_fees.saturating_mul(1000) / *total_transaction_weight;
+                let fee_per_byte = self
+                    .tx_by_key
+                    .get(&tx_key)
+                    .ok_or(UnconfirmedPoolError::StorageOutofSync)?
+                    .selection_fee_per_byte(total_transaction_fees.saturating_mul(1000) / *total_transaction_weight);
                 potentional_to_add.push((fee_per_byte, tx_key));
                 continue;
             }
@@ -943,6 +991,7 @@ mod test {
             storage_capacity: 4,
             weight_tx_skip_count: 3,
             min_fee: 0,
+            ..Default::default()
         });
 
         let tx_weight = TransactionWeight::latest();
@@ -973,6 +1022,97 @@ mod test {
         assert!(unconfirmed_pool.check_data_consistency());
     }
 
+    #[tokio::test]
+    async fn test_set_config_evicts_lowest_priority_txs() {
+        let key_manager = create_memory_db_key_manager().unwrap();
+        let tx1 = Arc::new(
+            tx!(MicroMinotari(5_000), fee: MicroMinotari(5), inputs: 2, outputs: 1, &key_manager)
+                .expect("Failed to get tx")
+                .0,
+        );
+        let tx2 = Arc::new(
+            tx!(MicroMinotari(5_000), fee: MicroMinotari(4), inputs: 4, outputs: 1, &key_manager)
+                .expect("Failed to get tx")
+                .0,
+        );
+        let tx3 = Arc::new(
+            tx!(MicroMinotari(5_000), fee: MicroMinotari(20), inputs: 5, outputs: 1, &key_manager)
+                .expect("Failed to get tx")
+                .0,
+        );
+
+        let mut unconfirmed_pool = UnconfirmedPool::new(UnconfirmedPoolConfig {
+            storage_capacity: 10,
+            weight_tx_skip_count: 3,
+            min_fee: 0,
+            ..Default::default()
+        });
+        let tx_weight = TransactionWeight::latest();
+        unconfirmed_pool
+            .insert_many([tx1.clone(), tx2.clone(), tx3.clone()], &tx_weight)
+            .expect("Failed to insert many");
+
+        unconfirmed_pool
+            .set_config(UnconfirmedPoolConfig {
+                storage_capacity: 2,
+                weight_tx_skip_count: 3,
+                min_fee: 0,
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(unconfirmed_pool.len(), 2);
+        assert!(unconfirmed_pool.has_tx_with_excess_sig(&tx1.body.kernels()[0].excess_sig));
+        assert!(!unconfirmed_pool.has_tx_with_excess_sig(&tx2.body.kernels()[0].excess_sig));
+        assert!(unconfirmed_pool.has_tx_with_excess_sig(&tx3.body.kernels()[0].excess_sig));
+        assert!(unconfirmed_pool.check_data_consistency());
+    }
+
+    #[tokio::test]
+    async fn test_prioritised_txs_are_kept_and_selected_first() {
+        let key_manager = create_memory_db_key_manager().unwrap();
+        let tx1 = Arc::new(
+            tx!(MicroMinotari(5_000), fee: MicroMinotari(5), inputs: 2, outputs: 1, &key_manager)
+                .expect("Failed to get tx")
+                .0,
+        );
+        let tx2 = Arc::new(
+            tx!(MicroMinotari(5_000), fee: MicroMinotari(4), inputs: 2, outputs: 1, &key_manager)
+                .expect("Failed to get tx")
+                .0,
+        );
+        let tx3 = Arc::new(
+            tx!(MicroMinotari(5_000), fee: MicroMinotari(20), inputs: 2, outputs: 1, &key_manager)
+                .expect("Failed to get tx")
+                .0,
+        );
+
+        let mut unconfirmed_pool = UnconfirmedPool::new(UnconfirmedPoolConfig {
+            storage_capacity: 2,
+            weight_tx_skip_count: 3,
+            priority_kernel_excesses: vec![tx2.body.kernels()[0].excess.clone()],
+            ..Default::default()
+        });
+        assert!(unconfirmed_pool.is_prioritised(&tx2, None));
+        assert!(!unconfirmed_pool.is_prioritised(&tx1, None));
+
+        let tx_weight = TransactionWeight::latest();
+        for tx in [tx1.clone(), tx2.clone(), tx3.clone()] {
+            let is_prioritised = unconfirmed_pool.is_prioritised(&tx, None);
+            unconfirmed_pool.insert(tx, None, &tx_weight, is_prioritised).unwrap();
+        }
+        // The prioritised transaction has the lowest fee, but the lowest fee transaction that is not prioritised is
+        // evicted instead
+        assert!(!unconfirmed_pool.has_tx_with_excess_sig(&tx1.body.kernels()[0].excess_sig));
+        assert!(unconfirmed_pool.has_tx_with_excess_sig(&tx2.body.kernels()[0].excess_sig));
+        assert!(unconfirmed_pool.has_tx_with_excess_sig(&tx3.body.kernels()[0].excess_sig));
+
+        // There is only space for one transaction in the block template
+        let desired_weight = tx2.calculate_weight(&tx_weight).expect("Failed to get tx");
+        let results = unconfirmed_pool.fetch_highest_priority_txs(desired_weight).unwrap();
+        assert_eq!(results.retrieved_transactions, vec![tx2]);
+        assert!(unconfirmed_pool.check_data_consistency());
+    }
+
     #[tokio::test]
     async fn test_double_spend_inputs() {
         let key_manager = create_memory_db_key_manager().unwrap();
@@ -1042,6 +1182,7 @@ mod test {
             storage_capacity: 4,
             weight_tx_skip_count: 3,
             min_fee: 0,
+            ..Default::default()
         });
 
         let tx_weight = TransactionWeight::latest();
@@ -1102,6 +1243,7 @@ mod test {
             storage_capacity: 10,
             weight_tx_skip_count: 3,
             min_fee: 0,
+            ..Default::default()
         });
         unconfirmed_pool
             .insert_many(
@@ -1178,6 +1320,7 @@ mod test {
             storage_capacity: 10,
             weight_tx_skip_count: 3,
             min_fee: 0,
+            ..Default::default()
         });
         unconfirmed_pool
             .insert_many(
@@ -1233,6 +1376,7 @@ mod test {
             storage_capacity: 10,
             weight_tx_skip_count: 3,
             min_fee: 0,
+            ..Default::default()
         });
         let txns = vec![
             Arc::new(tx1.clone()),
@@ -32,6 +32,7 @@ use tari_utilities::ByteArray;
 use super::core as proto;
 use crate::{
     blocks::{Block, BlockHeaderAccumulatedData, HistoricalBlock, NewBlock},
+    chain_storage::{MinedKernel, MinedOutput},
     proof_of_work::{AccumulatedDifficulty, Difficulty},
 };
 
@@ -226,3 +227,65 @@ impl TryFrom<NewBlock> for proto::NewBlock {
         })
     }
 }
+
+//---------------------------------- MinedKernel --------------------------------------------//
+
+impl TryFrom<proto::MinedKernel> for MinedKernel {
+    type Error = String;
+
+    fn try_from(mined_kernel: proto::MinedKernel) -> Result<Self, Self::Error> {
+        Ok(Self {
+            mined_height: mined_kernel.mined_height,
+            header_hash: mined_kernel
+                .header_hash
+                .try_into()
+                .map_err(|_| "Malformed header hash".to_string())?,
+            kernel: mined_kernel
+                .kernel
+                .map(TryInto::try_into)
+                .ok_or_else(|| "Kernel not provided".to_string())??,
+        })
+    }
+}
+
+impl From<MinedKernel> for proto::MinedKernel {
+    fn from(mined_kernel: MinedKernel) -> Self {
+        Self {
+            mined_height: mined_kernel.mined_height,
+            header_hash: mined_kernel.header_hash.to_vec(),
+            kernel: Some(mined_kernel.kernel.into()),
+        }
+    }
+}
+
+//---------------------------------- MinedOutput --------------------------------------------//
+
+impl TryFrom<proto::MinedOutput> for MinedOutput {
+    type Error = String;
+
+    fn try_from(mined_output: proto::MinedOutput) -> Result<Self, Self::Error> {
+        Ok(Self {
+            mined_height: mined_output.mined_height,
+            header_hash: mined_output
+                .header_hash
+                .try_into()
+                .map_err(|_| "Malformed header hash".to_string())?,
+            output: mined_output
+                .output
+                .map(TryInto::try_into)
+                .ok_or_else(|| "Output not provided".to_string())??,
+        })
+    }
+}
+
+impl TryFrom<MinedOutput> for proto::MinedOutput {
+    type Error = String;
+
+    fn try_from(mined_output: MinedOutput) -> Result<Self, Self::Error> {
+        Ok(Self {
+            mined_height: mined_output.mined_height,
+            header_hash: mined_output.header_hash.to_vec(),
+            output: Some(mined_output.output.try_into()?),
+        })
+    }
+}
@@ -31,6 +31,7 @@ use tari_utilities::ByteArray;
 use crate::{
     proto,
     transactions::transaction_components::{
+        ApplicationData,
         BuildInfo,
         CodeTemplateRegistration,
         ConfidentialOutputData,
@@ -62,6 +63,9 @@ impl From<SideChainFeature> for proto::types::side_chain_feature::SideChainFeatu
             SideChainFeature::ConfidentialOutput(output_data) => {
                 proto::types::side_chain_feature::SideChainFeature::ConfidentialOutput(output_data.into())
             },
+            SideChainFeature::ApplicationData(data) => {
+                proto::types::side_chain_feature::SideChainFeature::ApplicationData(data.into())
+            },
         }
     }
 }
@@ -80,6 +84,9 @@ impl TryFrom<proto::types::side_chain_feature::SideChainFeature> for SideChainFe
             proto::types::side_chain_feature::SideChainFeature::ConfidentialOutput(output_data) => {
                 Ok(SideChainFeature::ConfidentialOutput(output_data.try_into()?))
             },
+            proto::types::side_chain_feature::SideChainFeature::ApplicationData(data) => {
+                Ok(SideChainFeature::ApplicationData(data.try_into()?))
+            },
         }
     }
 }
@@ -172,6 +179,30 @@ impl From<ConfidentialOutputData> for proto::types::ConfidentialOutputData {
     }
 }
 
+// -------------------------------- ApplicationData -------------------------------- //
+impl TryFrom<proto::types::ApplicationData> for ApplicationData {
+    type Error = String;
+
+    fn try_from(value: proto::types::ApplicationData) -> Result<Self, Self::Error> {
+        Ok(ApplicationData {
+            application_id: value.application_id,
+            payload: value
+                .payload
+                .try_into()
+                .map_err(|_| "Application data payload is too large")?,
+        })
+    }
+}
+
+impl From<ApplicationData> for proto::types::ApplicationData {
+    fn from(value: ApplicationData) -> Self {
+        Self {
+            application_id: value.application_id,
+            payload: value.payload.to_vec(),
+        }
+    }
+}
+
 // -------------------------------- TemplateType -------------------------------- //
 impl TryFrom<proto::types::TemplateType> for TemplateType {
     type Error = String;
@@ -82,6 +82,11 @@ impl TryFrom<proto::types::TransactionKernel> for TransactionKernel {
             },
             None => None,
         };
+        let burn_claim_public_key = if kernel.burn_claim_public_key.is_empty() {
+            None
+        } else {
+            Some(PublicKey::from_canonical_bytes(&kernel.burn_claim_public_key).map_err(|e| e.to_string())?)
+        };
 
         Ok(TransactionKernel::new(
             TransactionKernelVersion::try_from(
@@ -94,6 +99,7 @@ impl TryFrom<proto::types::TransactionKernel> for TransactionKernel {
             excess,
             excess_sig,
             commitment,
+            burn_claim_public_key,
         ))
     }
 }
@@ -109,6 +115,7 @@ impl From<TransactionKernel> for proto::types::TransactionKernel {
             lock_height: kernel.lock_height,
             version: kernel.version as u32,
             burn_commitment: commitment,
+            burn_claim_public_key: kernel.burn_claim_public_key.map(|key| key.to_vec()).unwrap_or_default(),
         }
     }
 }
@@ -55,12 +55,15 @@ use crate::{
         DbTransaction,
         DbValue,
         HorizonData,
+        HorizonSyncCheckpoint,
         InputMinedInfo,
         LMDBDatabase,
         MmrTree,
         OutputMinedInfo,
+        ReindexCheckpoint,
         Reorg,
         TemplateRegistrationEntry,
+        UtxoSetStats,
         Validators,
     },
     consensus::{chain_strength_comparer::ChainStrengthComparerBuilder, ConsensusConstantsBuilder, ConsensusManager},
@@ -350,6 +353,10 @@ impl BlockchainBackend for TempDatabase {
         self.db.as_ref().unwrap().utxo_count()
     }
 
+    fn fetch_utxo_set_stats(&self) -> Result<UtxoSetStats, ChainStorageError> {
+        self.db.as_ref().unwrap().fetch_utxo_set_stats()
+    }
+
     fn kernel_count(&self) -> Result<usize, ChainStorageError> {
         self.db.as_ref().unwrap().kernel_count()
     }
@@ -389,6 +396,14 @@ impl BlockchainBackend for TempDatabase {
         self.db.as_ref().unwrap().fetch_horizon_data()
     }
 
+    fn fetch_horizon_sync_checkpoint(&self) -> Result<Option<HorizonSyncCheckpoint>, ChainStorageError> {
+        self.db.as_ref().unwrap().fetch_horizon_sync_checkpoint()
+    }
+
+    fn fetch_reindex_checkpoint(&self) -> Result<Option<ReindexCheckpoint>, ChainStorageError> {
+        self.db.as_ref().unwrap().fetch_reindex_checkpoint()
+    }
+
     fn get_stats(&self) -> Result<DbBasicStats, ChainStorageError> {
         self.db.as_ref().unwrap().get_stats()
     }
@@ -23,7 +23,7 @@
 //! Common test helper functions that are small and useful enough to be included in the main crate, rather than the
 //! integration test folder.
 
-use std::{iter, path::Path, sync::Arc};
+use std::{fs::File, io::BufReader, iter, path::Path, sync::Arc};
 
 use blake2::Blake2b;
 pub use block_spec::{BlockSpec, BlockSpecs};
@@ -42,6 +42,7 @@ use tari_utilities::epoch_time::EpochTime;
 
 use crate::{
     blocks::{Block, BlockHeader, BlockHeaderAccumulatedData, ChainHeader},
+    chain_storage::chain_data_export::{ChainDataExportError, ChainDataFormat, ChainDataReader, ChainDataRecord},
     consensus::{ConsensusConstants, ConsensusManager},
     proof_of_work::{sha3x_difficulty, AchievedTargetDifficulty, Difficulty},
     transactions::{
@@ -182,6 +183,16 @@ pub fn create_peer_manager<P: AsRef<Path>>(data_path: P) -> Arc<PeerManager> {
     Arc::new(PeerManager::new(LMDBWrapper::new(Arc::new(peer_database)), None).unwrap())
 }
 
+/// Loads all records of a chain data export, such as one written by the base node `export-blocks`, `export-kernels`
+/// or `export-utxos` commands, for use as a test fixture
+pub fn load_chain_data_export<T: ChainDataRecord, P: AsRef<Path>>(
+    path: P,
+    format: ChainDataFormat,
+) -> Result<Vec<T>, ChainDataExportError> {
+    let file = File::open(path)?;
+    ChainDataReader::new(BufReader::new(file), format).read_all()
+}
+
 pub fn create_chain_header(header: BlockHeader, prev_accum: &BlockHeaderAccumulatedData) -> ChainHeader {
     let achieved_target_diff = AchievedTargetDifficulty::try_construct(
         header.pow_algo(),
@@ -379,6 +379,13 @@ impl AggregateBody {
             .map_err(|e| TransactionError::SerializationError(e.to_string()))
     }
 
+    /// Returns the weight in grams that the fee of a body is charged for, which is discounted for consolidations
+    pub fn calculate_fee_weight(&self, transaction_weight: &TransactionWeight) -> Result<u64, TransactionError> {
+        transaction_weight
+            .calculate_fee_weight_body(self)
+            .map_err(|e| TransactionError::SerializationError(e.to_string()))
+    }
+
     pub fn sum_features_and_scripts_size(&self) -> std::io::Result<usize> {
         Ok(self
             .outputs
@@ -419,6 +426,12 @@ impl AggregateBody {
             .fold(0, |max_timelock, kernel| max(max_timelock, kernel.lock_height))
     }
 
+    pub fn max_kernel_fee(&self) -> MicroMinotari {
+        self.kernels()
+            .iter()
+            .fold(MicroMinotari::zero(), |max_fee, kernel| max(max_fee, kernel.fee))
+    }
+
     /// Returns the height of the minimum height where the body is spendable. This is calculated from the
     /// kernel lock_heights and the maturity of the input UTXOs.
     pub fn min_spendable_height(&self) -> Result<u64, TransactionError> {
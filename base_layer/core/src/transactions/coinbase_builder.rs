@@ -272,6 +272,7 @@ where TKeyManagerInterface: TransactionKeyManagerInterface
             metadata.lock_height,
             &metadata.kernel_features,
             &metadata.burn_commitment,
+            &metadata.burn_claim_public_key,
         );
         let public_nonce = self
             .key_manager
@@ -844,6 +845,7 @@ mod test {
             coinbase_kernel2.lock_height,
             &coinbase_kernel2.features,
             &None,
+            &None,
         );
         let excess = key_manager
             .get_txo_kernel_signature_excess_with_offset(&output.spending_key_id, &new_nonce.key_id)
@@ -1003,6 +1005,7 @@ mod test {
             kernel_1.lock_height,
             &kernel_1.features,
             &None,
+            &None,
         );
 
         let mut kernel_signature = key_manager
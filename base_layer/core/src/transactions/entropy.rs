@@ -0,0 +1,114 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use rand::{rngs::OsRng, CryptoRng, RngCore};
+use tari_common_types::types::PrivateKey;
+use tari_crypto::keys::SecretKey;
+use tari_utilities::epoch_time::EpochTime;
+
+/// A cryptographically secure RNG that can be used as a trait object
+pub trait TransactionRng: RngCore + CryptoRng + Send {}
+
+impl<R: RngCore + CryptoRng + Send> TransactionRng for R {}
+
+/// The source of randomness and time used by the key manager and the transaction builder. This is the operating
+/// system RNG and clock, unless a deterministic source is injected with the `deterministic_transactions` feature so
+/// that constructing the same transaction twice yields byte-identical results, e.g. for golden-file tests of the
+/// serialisation formats.
+///
+/// This handle can be cloned cheaply, clones share the same source.
+#[derive(Clone, Default)]
+pub struct TransactionEntropy {
+    source: Option<Arc<Mutex<DeterministicSource>>>,
+}
+
+#[cfg_attr(not(any(test, feature = "deterministic_transactions")), allow(dead_code))]
+struct DeterministicSource {
+    rng: Box<dyn TransactionRng>,
+    clock: Box<dyn FnMut() -> EpochTime + Send>,
+}
+
+impl TransactionEntropy {
+    /// Uses the given RNG and clock instead of the operating system's. This must never be used for real funds.
+    #[cfg(any(test, feature = "deterministic_transactions"))]
+    pub fn deterministic<R, C>(rng: R, clock: C) -> Self
+    where
+        R: TransactionRng + 'static,
+        C: FnMut() -> EpochTime + Send + 'static,
+    {
+        Self {
+            source: Some(Arc::new(Mutex::new(DeterministicSource {
+                rng: Box::new(rng),
+                clock: Box::new(clock),
+            }))),
+        }
+    }
+
+    pub fn is_deterministic(&self) -> bool {
+        self.source.is_some()
+    }
+
+    /// Calls `f` with the RNG
+    pub fn with_rng<T, F>(&self, f: F) -> T
+    where F: FnOnce(&mut dyn TransactionRng) -> T {
+        match &self.source {
+            Some(source) => {
+                let mut source = source.lock().expect("TransactionEntropy lock poisoned");
+                f(source.rng.as_mut())
+            },
+            None => f(&mut OsRng),
+        }
+    }
+
+    pub fn next_u64(&self) -> u64 {
+        self.with_rng(|rng| rng.next_u64())
+    }
+
+    pub fn random_private_key(&self) -> PrivateKey {
+        self.with_rng(|mut rng| PrivateKey::random(&mut rng))
+    }
+
+    /// The current time, for callers that timestamp the transactions they construct
+    pub fn now(&self) -> EpochTime {
+        match &self.source {
+            Some(source) => {
+                let mut source = source.lock().expect("TransactionEntropy lock poisoned");
+                (source.clock)()
+            },
+            None => EpochTime::now(),
+        }
+    }
+}
+
+impl fmt::Debug for TransactionEntropy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TransactionEntropy")
+            .field("deterministic", &self.is_deterministic())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn it_repeats_a_deterministic_source() {
+        let entropy = || TransactionEntropy::deterministic(StdRng::seed_from_u64(1), || EpochTime::from(1_700_000_000));
+        let (a, b) = (entropy(), entropy());
+        assert!(a.is_deterministic());
+        assert_eq!(a.next_u64(), b.next_u64());
+        assert_eq!(a.random_private_key(), b.random_private_key());
+        assert_eq!(a.now(), EpochTime::from(1_700_000_000));
+        // Clones share the source
+        assert_ne!(a.clone().next_u64(), a.next_u64());
+        assert!(!TransactionEntropy::default().is_deterministic());
+    }
+}
@@ -21,7 +21,10 @@
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use super::{tari_amount::MicroMinotari, weight::TransactionWeight};
-use crate::transactions::aggregated_body::AggregateBody;
+use crate::transactions::{
+    aggregated_body::AggregateBody,
+    transaction_components::{KernelFeatures, OutputType},
+};
 
 #[derive(Debug, Clone, Copy)]
 pub struct Fee(TransactionWeight);
@@ -53,8 +56,27 @@ impl Fee {
         MicroMinotari::from(weight.saturating_mul(fee_per_gram.0))
     }
 
+    /// Same as `calculate`, but takes the kernel features and output types of the transaction so that consolidation
+    /// transactions are charged for a discounted weight, see [TransactionWeight::is_consolidation].
+    pub fn calculate_with_features(
+        &self,
+        fee_per_gram: MicroMinotari,
+        kernel_features: &[KernelFeatures],
+        num_inputs: usize,
+        output_types: &[OutputType],
+        rounded_features_and_scripts_byte_size: usize,
+    ) -> MicroMinotari {
+        let weight = self.weighting().calculate_fee_weight(
+            kernel_features,
+            num_inputs,
+            output_types,
+            rounded_features_and_scripts_byte_size,
+        );
+        MicroMinotari::from(weight.saturating_mul(fee_per_gram.0))
+    }
+
     pub fn calculate_body(&self, fee_per_gram: MicroMinotari, body: &AggregateBody) -> std::io::Result<MicroMinotari> {
-        let weight = self.weighting().calculate_body(body)?;
+        let weight = self.weighting().calculate_fee_weight_body(body)?;
         Ok(MicroMinotari::from(weight) * fee_per_gram)
     }
 
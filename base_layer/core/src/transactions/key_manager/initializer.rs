@@ -37,7 +37,10 @@ use tari_key_manager::{
 };
 use tari_service_framework::{async_trait, ServiceInitializationError, ServiceInitializer, ServiceInitializerContext};
 
-use crate::transactions::{key_manager::TransactionKeyManagerWrapper, CryptoFactories};
+use crate::transactions::{
+    key_manager::{RemoteSigner, TransactionKeyManagerWrapper},
+    CryptoFactories,
+};
 
 /// Initializes the key manager service by implementing the [ServiceInitializer] trait.
 pub struct TransactionKeyManagerInitializer<T>
@@ -47,6 +50,7 @@ where T: KeyManagerBackend<PublicKey>
     master_seed: CipherSeed,
     crypto_factories: CryptoFactories,
     wallet_type: Arc<WalletType>,
+    remote_signer: Option<Arc<dyn RemoteSigner>>,
 }
 
 impl<T> TransactionKeyManagerInitializer<T>
@@ -64,8 +68,15 @@ where T: KeyManagerBackend<PublicKey> + 'static
             master_seed,
             crypto_factories,
             wallet_type,
+            remote_signer: None,
         }
     }
+
+    /// Sets the signer that holds the spend key of a `WalletType::RemoteSigner` wallet
+    pub fn with_remote_signer(mut self, remote_signer: Option<Arc<dyn RemoteSigner>>) -> Self {
+        self.remote_signer = remote_signer;
+        self
+    }
 }
 
 #[async_trait]
@@ -84,6 +95,9 @@ where T: KeyManagerBackend<PublicKey> + 'static
             self.crypto_factories.clone(),
             self.wallet_type.clone(),
         )?;
+        if let Some(remote_signer) = self.remote_signer.take() {
+            key_manager.set_remote_signer(remote_signer).await;
+        }
         context.register_handle(key_manager);
 
         Ok(())
@@ -35,7 +35,6 @@ use minotari_ledger_wallet_comms::accessor_methods::{
     ledger_get_script_signature,
     ScriptSignatureKey,
 };
-use rand::{rngs::OsRng, RngCore};
 use strum::IntoEnumIterator;
 use tari_common_types::{
     key_branches::{
@@ -74,6 +73,7 @@ use tari_key_manager::{
         KeyDigest,
         KeyId,
         KeyManagerServiceError,
+        SerializedKeyString,
     },
 };
 use tari_script::{CheckSigSchnorrSignature, TariScript};
@@ -83,20 +83,32 @@ use tokio::sync::RwLock;
 const LOG_TARGET: &str = "c::bn::key_manager::key_manager_service";
 const TRANSACTION_KEY_MANAGER_MAX_SEARCH_DEPTH: u64 = 1_000_000;
 const HASHER_LABEL_STEALTH_KEY: &str = "script key";
+const HASHER_LABEL_SUB_ADDRESS: &str = "sub address";
 
 pub const LEDGER_NOT_SUPPORTED: &str = "Ledger is not supported in this build, please enable the \"ledger\" feature.";
 
 use crate::{
     common::ConfidentialOutputHasher,
-    one_sided::diffie_hellman_stealth_domain_hasher,
+    one_sided::{diffie_hellman_stealth_domain_hasher, stealth_address_script_spending_key},
     transactions::{
-        key_manager::{interface::TxoStage, TariKeyId},
+        key_manager::{
+            interface::TxoStage,
+            remote_signer::{
+                is_remote_signer_branch,
+                RemoteScriptKey,
+                RemoteSigner,
+                RemoteSignerChangeOutput,
+                RemoteSignerInput,
+            },
+            TariKeyId,
+        },
         tari_amount::MicroMinotari,
         transaction_components::{
-            encrypted_data::PaymentId,
+            encrypted_data::{EncryptedDataRecord, PaymentId},
             EncryptedData,
             KernelFeatures,
             RangeProofType,
+            Transaction,
             TransactionError,
             TransactionInput,
             TransactionInputVersion,
@@ -104,8 +116,10 @@ use crate::{
             TransactionKernelVersion,
             TransactionOutput,
             TransactionOutputVersion,
+            WalletOutput,
         },
         CryptoFactories,
+        TransactionEntropy,
     },
 };
 
@@ -115,6 +129,8 @@ pub struct TransactionKeyManagerInner<TBackend> {
     master_seed: CipherSeed,
     crypto_factories: CryptoFactories,
     wallet_type: Arc<WalletType>,
+    entropy: TransactionEntropy,
+    remote_signer: Option<Arc<dyn RemoteSigner>>,
 }
 
 impl<TBackend> TransactionKeyManagerInner<TBackend>
@@ -136,11 +152,33 @@ where TBackend: KeyManagerBackend<PublicKey> + 'static
             master_seed,
             crypto_factories,
             wallet_type,
+            entropy: TransactionEntropy::default(),
+            remote_signer: None,
         };
         km.add_standard_core_branches()?;
         Ok(km)
     }
 
+    /// Replaces the source of randomness, see `TransactionEntropy`
+    #[cfg(any(test, feature = "deterministic_transactions"))]
+    pub fn set_entropy(&mut self, entropy: TransactionEntropy) {
+        self.entropy = entropy;
+    }
+
+    /// Sets the signer that holds the spend key of a `WalletType::RemoteSigner` wallet
+    pub fn set_remote_signer(&mut self, remote_signer: Arc<dyn RemoteSigner>) {
+        self.remote_signer = Some(remote_signer);
+    }
+
+    fn remote_signer(&self) -> Result<&dyn RemoteSigner, KeyManagerServiceError> {
+        self.remote_signer.as_deref().ok_or_else(|| {
+            KeyManagerServiceError::RemoteSignerError(format!(
+                "No remote signer is connected, wallet_type: {}",
+                self.wallet_type
+            ))
+        })
+    }
+
     fn add_standard_core_branches(&mut self) -> Result<(), KeyManagerServiceError> {
         for branch in TransactionKeyManagerBranch::iter() {
             self.add_key_manager_branch(&branch.get_branch_key())?;
@@ -184,7 +222,7 @@ where TBackend: KeyManagerBackend<PublicKey> + 'static
                 KERNEL_NONCE |
                 SENDER_OFFSET |
                 ONE_SIDED_SENDER_OFFSET |
-                RANDOM_KEY => OsRng.next_u64(),
+                RANDOM_KEY => self.entropy.next_u64(),
                 _ => {
                     let mut km = self
                         .key_managers
@@ -217,7 +255,7 @@ where TBackend: KeyManagerBackend<PublicKey> + 'static
                 }
                 #[cfg(feature = "ledger")]
                 {
-                    let random_index = OsRng.next_u64();
+                    let random_index = self.entropy.next_u64();
 
                     let branch = TransactionKeyManagerBranch::RandomKey;
                     let public_key = ledger_get_public_key(ledger.account, random_index, branch)
@@ -231,8 +269,22 @@ where TBackend: KeyManagerBackend<PublicKey> + 'static
                     })
                 }
             },
+            WalletType::RemoteSigner(_) => {
+                let random_index = self.entropy.next_u64();
+                let public_key = self
+                    .remote_signer()?
+                    .get_public_key(TransactionKeyManagerBranch::RandomKey, random_index)
+                    .await?;
+                Ok(KeyAndId {
+                    key_id: KeyId::Managed {
+                        branch: TransactionKeyManagerBranch::RandomKey.get_branch_key(),
+                        index: random_index,
+                    },
+                    pub_key: public_key,
+                })
+            },
             _ => {
-                let random_private_key = PrivateKey::random(&mut OsRng);
+                let random_private_key = self.entropy.random_private_key();
                 let key_id = self.import_key(random_private_key).await?;
                 let public_key = self.get_public_key_at_key_id(&key_id).await?;
                 Ok(KeyAndId {
@@ -335,6 +387,20 @@ where TBackend: KeyManagerBackend<PublicKey> + 'static
                     },
                     _ => {},
                 },
+                WalletType::RemoteSigner(wallet) => match TransactionKeyManagerBranch::from_key(branch) {
+                    TransactionKeyManagerBranch::Spend => {
+                        return Ok(wallet.public_alpha.clone());
+                    },
+                    TransactionKeyManagerBranch::DataEncryption => {
+                        return Ok(PublicKey::from_secret_key(&wallet.view_key));
+                    },
+                    remote_branch @ (TransactionKeyManagerBranch::OneSidedSenderOffset |
+                    TransactionKeyManagerBranch::RandomKey |
+                    TransactionKeyManagerBranch::PreMine) => {
+                        return self.remote_signer()?.get_public_key(remote_branch, *index).await;
+                    },
+                    _ => {},
+                },
             };
             let km = self
                 .key_managers
@@ -411,6 +477,17 @@ where TBackend: KeyManagerBackend<PublicKey> + 'static
                             );
                         }
                     },
+                    WalletType::RemoteSigner(wallet) => {
+                        if &TransactionKeyManagerBranch::DataEncryption.get_branch_key() == branch {
+                            return Ok(wallet.view_key.clone());
+                        }
+
+                        if is_remote_signer_branch(branch) {
+                            return Err(KeyManagerServiceError::RemoteSignerPrivateKeyInaccessible(
+                                key_id.to_string(),
+                            ));
+                        }
+                    },
                 }
 
                 let km = self
@@ -432,6 +509,9 @@ where TBackend: KeyManagerBackend<PublicKey> + 'static
                     WalletType::Ledger(_) => {
                         Err(KeyManagerServiceError::LedgerPrivateKeyInaccessible(key_id.to_string()))
                     },
+                    WalletType::RemoteSigner(_) => Err(KeyManagerServiceError::RemoteSignerPrivateKeyInaccessible(
+                        key_id.to_string(),
+                    )),
                     WalletType::DerivedKeys => {
                         let km = self
                             .key_managers
@@ -505,6 +585,7 @@ where TBackend: KeyManagerBackend<PublicKey> + 'static
                 "Key manager set to use ledger, ledger alpha public key missing".to_string(),
             ))?,
             WalletType::ProvidedKeys(wallet) => wallet.public_spend_key.clone(),
+            WalletType::RemoteSigner(wallet) => wallet.public_alpha.clone(),
         };
         Ok(KeyAndId { key_id, pub_key: key })
     }
@@ -542,6 +623,11 @@ where TBackend: KeyManagerBackend<PublicKey> + 'static
         Ok(key_id)
     }
 
+    pub async fn get_imported_key_ids(&self) -> Result<Vec<TariKeyId>, KeyManagerServiceError> {
+        let public_keys = self.db.get_imported_public_keys()?;
+        Ok(public_keys.into_iter().map(|key| KeyId::Imported { key }).collect())
+    }
+
     pub async fn get_private_view_key(&self) -> Result<PrivateKey, KeyManagerServiceError> {
         match &*self.wallet_type {
             WalletType::DerivedKeys => {
@@ -556,7 +642,49 @@ where TBackend: KeyManagerBackend<PublicKey> + 'static
                 .clone()
                 .ok_or(KeyManagerServiceError::LedgerViewKeyInaccessible(format!("{}", ledger))),
             WalletType::ProvidedKeys(wallet) => Ok(wallet.view_key.clone()),
+            WalletType::RemoteSigner(wallet) => Ok(wallet.view_key.clone()),
+        }
+    }
+
+    /// The private offset added to the spend key for the sub-address at `index`. It is derived from the private view
+    /// key so that any view-key holder can scan for sub-address outputs, and it is fully recoverable from the seed.
+    async fn get_sub_address_offset(&self, index: u64) -> Result<PrivateKey, KeyManagerServiceError> {
+        let view_key = self.get_private_view_key().await?;
+        let hasher = DomainSeparatedHasher::<Blake2b<U64>, KeyManagerTransactionsHashDomain>::new_with_label(
+            HASHER_LABEL_SUB_ADDRESS,
+        );
+        let hasher = hasher.chain(view_key.as_bytes()).chain(index.to_le_bytes()).finalize();
+        PrivateKey::from_uniform_bytes(hasher.as_ref())
+            .map_err(|_| KeyManagerServiceError::UnknownError("Invalid sub-address offset".to_string()))
+    }
+
+    /// Returns the public spend key of the sub-address at `index`. Index 0 is the wallet's primary spend key.
+    pub async fn get_sub_address_spend_key(&self, index: u64) -> Result<PublicKey, KeyManagerServiceError> {
+        let spend_key = self.get_spend_key().await?.pub_key;
+        if index == 0 {
+            return Ok(spend_key);
         }
+        let offset = self.get_sub_address_offset(index).await?;
+        Ok(spend_key + PublicKey::from_secret_key(&offset))
+    }
+
+    /// Imports the script private key of a stealth output that was sent to the sub-address at `index`, returning the
+    /// imported key id so the output can be spent.
+    pub async fn import_sub_address_script_key(
+        &self,
+        commitment_mask_key_id: &TariKeyId,
+        index: u64,
+    ) -> Result<TariKeyId, KeyManagerServiceError> {
+        let stealth_key_id = TariKeyId::Derived {
+            key: SerializedKeyString::from(commitment_mask_key_id.to_string()),
+        };
+        let private_key = self.get_private_key(&stealth_key_id).await?;
+        let private_key = if index == 0 {
+            private_key
+        } else {
+            private_key + self.get_sub_address_offset(index).await?
+        };
+        self.import_key(private_key).await
     }
 
     async fn get_private_comms_key(&self) -> Result<PrivateKey, KeyManagerServiceError> {
@@ -585,7 +713,7 @@ where TBackend: KeyManagerBackend<PublicKey> + 'static
                     Ok(key)
                 }
             },
-            WalletType::Ledger(_) => {
+            WalletType::Ledger(_) | WalletType::RemoteSigner(_) => {
                 let km = self
                     .key_managers
                     .get(&branch)
@@ -762,6 +890,15 @@ where TBackend: KeyManagerBackend<PublicKey> + 'static
                 }
             }
         }
+        if let (WalletType::RemoteSigner(_), KeyId::Managed { branch, index }) = (&*self.wallet_type, secret_key_id) {
+            if is_remote_signer_branch(branch) {
+                let shared_secret = self
+                    .remote_signer()?
+                    .get_dh_shared_secret(TransactionKeyManagerBranch::from_key(branch), *index, public_key)
+                    .await?;
+                return Ok(shared_secret);
+            }
+        }
 
         let secret_key = self.get_private_key(secret_key_id).await?;
         let shared_secret = CommsDHKE::new(&secret_key, public_key);
@@ -808,6 +945,10 @@ where TBackend: KeyManagerBackend<PublicKey> + 'static
                     secret_key_id,
                 )),
             },
+            WalletType::RemoteSigner(_) => {
+                let dh = self.get_diffie_hellman_shared_secret(secret_key_id, public_key).await?;
+                Ok(diffie_hellman_stealth_domain_hasher(dh))
+            },
             _ => {
                 let secret_key = self.get_private_key(secret_key_id).await?;
                 let dh = CommsDHKE::new(&secret_key, public_key);
@@ -822,8 +963,8 @@ where TBackend: KeyManagerBackend<PublicKey> + 'static
         amount: &PrivateKey,
         claim_public_key: &PublicKey,
     ) -> Result<RistrettoComSig, TransactionError> {
-        let nonce_a = PrivateKey::random(&mut OsRng);
-        let nonce_x = PrivateKey::random(&mut OsRng);
+        let nonce_a = self.entropy.random_private_key();
+        let nonce_x = self.entropy.random_private_key();
         let pub_nonce = self.crypto_factories.commitment.commit(&nonce_x, &nonce_a);
 
         let commitment = self.get_commitment(commitment_mask_key_id, amount).await?;
@@ -909,10 +1050,13 @@ where TBackend: KeyManagerBackend<PublicKey> + 'static
                     Ok(signature)
                 }
             },
+            WalletType::RemoteSigner(_) if requires_remote_signer(script_key_id) => {
+                Err(remote_signer_only_signs_transactions("get_script_signature"))
+            },
             _ => {
-                let r_a = PrivateKey::random(&mut OsRng);
-                let r_x = PrivateKey::random(&mut OsRng);
-                let r_y = PrivateKey::random(&mut OsRng);
+                let r_a = self.entropy.random_private_key();
+                let r_x = self.entropy.random_private_key();
+                let r_y = self.entropy.random_private_key();
                 let ephemeral_commitment = self.crypto_factories.commitment.commit(&r_x, &r_a);
                 let ephemeral_pubkey = PublicKey::from_secret_key(&r_y);
                 let script_private_key = self.get_private_key(script_key_id).await?;
@@ -952,8 +1096,8 @@ where TBackend: KeyManagerBackend<PublicKey> + 'static
     ) -> Result<ComAndPubSignature, TransactionError> {
         let private_commitment_mask = self.get_private_key(commitment_mask_id).await?;
         let commitment = self.get_commitment(commitment_mask_id, value).await?;
-        let r_a = PrivateKey::random(&mut OsRng);
-        let r_x = PrivateKey::random(&mut OsRng);
+        let r_a = self.entropy.random_private_key();
+        let r_x = self.entropy.random_private_key();
         let ephemeral_commitment = self.crypto_factories.commitment.commit(&r_x, &r_a);
         let challenge = TransactionInput::finalize_script_signature_challenge(
             txi_version,
@@ -1010,7 +1154,8 @@ where TBackend: KeyManagerBackend<PublicKey> + 'static
         }
 
         let commitment_private_key = self.get_private_key(commitment_mask_key_id).await?;
-        let proof_bytes_result = if min_value == 0 {
+        // A deterministic source seeds the proof nonces, as the proof is otherwise always randomised
+        let proof_bytes_result = if min_value == 0 && !self.entropy.is_deterministic() {
             self.crypto_factories
                 .range_proof
                 .construct_proof(&commitment_private_key, value)
@@ -1024,9 +1169,13 @@ where TBackend: KeyManagerBackend<PublicKey> + 'static
                 minimum_value_promise: min_value,
             };
 
+            let seed_nonce = self
+                .entropy
+                .is_deterministic()
+                .then(|| self.entropy.random_private_key());
             self.crypto_factories
                 .range_proof
-                .construct_extended_proof(vec![extended_witness], None)
+                .construct_extended_proof(vec![extended_witness], seed_nonce)
         };
 
         let proof_bytes = proof_bytes_result
@@ -1151,7 +1300,144 @@ where TBackend: KeyManagerBackend<PublicKey> + 'static
                     Ok(script_offset)
                 }
             },
+            WalletType::RemoteSigner(_) => {
+                if script_key_ids
+                    .iter()
+                    .chain(sender_offset_key_ids)
+                    .any(requires_remote_signer)
+                {
+                    return Err(remote_signer_only_signs_transactions("get_script_offset"));
+                }
+                let mut script_offset = PrivateKey::default();
+                for script_key_id in script_key_ids {
+                    script_offset = &script_offset + self.get_private_key(script_key_id).await?;
+                }
+                for sender_offset_key_id in sender_offset_key_ids {
+                    script_offset = script_offset - self.get_private_key(sender_offset_key_id).await?;
+                }
+                Ok(script_offset)
+            },
+        }
+    }
+
+    /// Signs the inputs of a transaction that is complete apart from its script signatures and script offset, and sets
+    /// the script offset. A `WalletType::RemoteSigner` wallet sends the transaction to its signer instead, which works
+    /// out what it pays out from the openings of the inputs and change outputs, and only signs it if its policy allows.
+    pub async fn sign_transaction_inputs(
+        &self,
+        mut transaction: Transaction,
+        inputs: &[&WalletOutput],
+        change_outputs: &[&WalletOutput],
+        sender_offset_key_ids: &[TariKeyId],
+    ) -> Result<Transaction, TransactionError> {
+        if let WalletType::RemoteSigner(_) = &*self.wallet_type {
+            return self
+                .sign_transaction_with_remote_signer(transaction, inputs, change_outputs, sender_offset_key_ids)
+                .await;
+        }
+        for input in inputs {
+            self.sign_transaction_input(&mut transaction, input).await?;
         }
+        let script_key_ids = inputs
+            .iter()
+            .map(|input| input.script_key_id.clone())
+            .collect::<Vec<_>>();
+        transaction.script_offset = self.get_script_offset(&script_key_ids, sender_offset_key_ids).await?;
+        Ok(transaction)
+    }
+
+    async fn sign_transaction_input(
+        &self,
+        transaction: &mut Transaction,
+        input: &WalletOutput,
+    ) -> Result<(), TransactionError> {
+        let value = input.value.into();
+        let version = TransactionInputVersion::get_current_version();
+        let script_message =
+            TransactionInput::build_script_signature_message(&version, &input.script, &input.input_data);
+        let script_signature = self
+            .get_script_signature(
+                &input.script_key_id,
+                &input.spending_key_id,
+                &value,
+                &version,
+                &script_message,
+            )
+            .await?;
+        let commitment = self.get_commitment(&input.spending_key_id, &value).await?;
+        transaction.body.update_script_signature(&commitment, script_signature)
+    }
+
+    async fn sign_transaction_with_remote_signer(
+        &self,
+        mut transaction: Transaction,
+        inputs: &[&WalletOutput],
+        change_outputs: &[&WalletOutput],
+        sender_offset_key_ids: &[TariKeyId],
+    ) -> Result<Transaction, TransactionError> {
+        let mut partial_script_offset = PrivateKey::default();
+        let mut remote_inputs = Vec::with_capacity(inputs.len());
+        for input in inputs {
+            let script_key = match &input.script_key_id {
+                TariKeyId::Managed { branch, index } if is_remote_signer_branch(branch) => {
+                    Some(RemoteScriptKey::Managed {
+                        branch: TransactionKeyManagerBranch::from_key(branch),
+                        index: *index,
+                    })
+                },
+                TariKeyId::Derived { key } => {
+                    let key_id = TariKeyId::from_str(key.to_string().as_str())
+                        .map_err(|_| KeyManagerServiceError::KeySerializationError)?;
+                    Some(RemoteScriptKey::Derived {
+                        commitment_mask: self.get_private_key(&key_id).await?,
+                    })
+                },
+                script_key_id => {
+                    // The wallet holds the script key, so it signs the input itself
+                    self.sign_transaction_input(&mut transaction, input).await?;
+                    partial_script_offset = &partial_script_offset + self.get_private_key(script_key_id).await?;
+                    None
+                },
+            };
+            remote_inputs.push(RemoteSignerInput {
+                value: input.value,
+                commitment_mask: self.get_private_key(&input.spending_key_id).await?,
+                script_key,
+            });
+        }
+        for sender_offset_key_id in sender_offset_key_ids {
+            match sender_offset_key_id {
+                // The signer knows its sender offsets from the one-sided payments that it signed the metadata of
+                TariKeyId::Managed { branch, .. } if is_remote_signer_branch(branch) => {},
+                TariKeyId::Derived { .. } => {
+                    return Err(self.key_id_not_supported_error(
+                        "sign_transaction_inputs",
+                        "a sender offset of the remote signer's branches or one held by the wallet",
+                        sender_offset_key_id,
+                    ));
+                },
+                _ => {
+                    partial_script_offset = partial_script_offset - self.get_private_key(sender_offset_key_id).await?;
+                },
+            }
+        }
+        let mut remote_change_outputs = Vec::with_capacity(change_outputs.len());
+        for output in change_outputs {
+            remote_change_outputs.push(RemoteSignerChangeOutput {
+                value: output.value,
+                commitment_mask: self.get_private_key(&output.spending_key_id).await?,
+            });
+        }
+        let transaction = self
+            .remote_signer()?
+            .sign_transaction(
+                &transaction,
+                &remote_inputs,
+                &remote_change_outputs,
+                &partial_script_offset,
+            )
+            .await?;
+        Ok(transaction)
     }
 
     async fn get_metadata_signature_ephemeral_private_key_pair(
@@ -1231,9 +1517,14 @@ where TBackend: KeyManagerBackend<PublicKey> + 'static
                     }
                 }
             },
+            WalletType::RemoteSigner(_) if requires_remote_signer(private_key_id) => {
+                Err(remote_signer_only_signs_transactions("sign_script_message"))
+            },
             _ => {
                 let private_key = self.get_private_key(private_key_id).await?;
-                let signature = CheckSigSchnorrSignature::sign(&private_key, challenge, &mut OsRng)?;
+                let signature = self
+                    .entropy
+                    .with_rng(|mut rng| CheckSigSchnorrSignature::sign(&private_key, challenge, &mut rng))?;
 
                 Ok(signature)
             },
@@ -1303,6 +1594,11 @@ where TBackend: KeyManagerBackend<PublicKey> + 'static
                     }
                 }
             },
+            WalletType::RemoteSigner(_)
+                if requires_remote_signer(private_key_id) || requires_remote_signer(nonce_key_id) =>
+            {
+                Err(remote_signer_only_signs_transactions("sign_with_nonce_and_challenge"))
+            },
             _ => {
                 let private_key = self.get_private_key(private_key_id).await?;
                 let private_nonce = self.get_private_key(nonce_key_id).await?;
@@ -1415,6 +1711,25 @@ where TBackend: KeyManagerBackend<PublicKey> + 'static
                     Ok(comm_and_pub_sig)
                 }
             },
+            WalletType::RemoteSigner(_) => {
+                let sender_offset_key_index = sender_offset_key_id
+                    .managed_index()
+                    .ok_or_else(|| TransactionError::KeyManagerError("Invalid index for sender offset".to_string()))?;
+                let commitment_mask = self.get_private_key(commitment_mask_key_id).await?;
+                let comm_and_pub_sig = self
+                    .remote_signer()?
+                    .get_one_sided_metadata_signature(
+                        txo_version.as_u8(),
+                        value,
+                        sender_offset_key_index,
+                        &commitment_mask,
+                        receiver_address,
+                        metadata_signature_message_common,
+                        range_proof_type,
+                    )
+                    .await?;
+                Ok(comm_and_pub_sig)
+            },
         }
     }
 
@@ -1597,13 +1912,16 @@ where TBackend: KeyManagerBackend<PublicKey> + 'static
         let value_key = value.into();
         let commitment = self.get_commitment(commitment_mask_key_id, &value_key).await?;
         let commitment_private_key = self.get_private_key(commitment_mask_key_id).await?;
-        let data = EncryptedData::encrypt_data(
-            &recovery_key,
-            &commitment,
-            value.into(),
-            &commitment_private_key,
-            payment_id,
-        )?;
+        let data = self.entropy.with_rng(|rng| {
+            EncryptedData::encrypt_data_with_rng(
+                &recovery_key,
+                &commitment,
+                value.into(),
+                &commitment_private_key,
+                payment_id,
+                rng,
+            )
+        })?;
         Ok(data)
     }
 
@@ -1617,8 +1935,12 @@ where TBackend: KeyManagerBackend<PublicKey> + 'static
         } else {
             self.get_private_view_key().await?
         };
-        let (value, private_key, payment_id) =
-            EncryptedData::decrypt_data(&recovery_key, output.commitment(), output.encrypted_data())?;
+        let (value, private_key, payment_id) = EncryptedData::decrypt_output_data(
+            &recovery_key,
+            output.commitment(),
+            output.encrypted_data(),
+            output.version,
+        )?;
         self.crypto_factories
             .range_proof
             .verify_mask(output.commitment(), &private_key, value.into())?;
@@ -1637,19 +1959,79 @@ where TBackend: KeyManagerBackend<PublicKey> + 'static
         Ok((key, value, payment_id))
     }
 
+    pub async fn encrypt_records_for_recovery(
+        &self,
+        commitment_mask_key_id: &TariKeyId,
+        custom_recovery_key_id: Option<&TariKeyId>,
+        value: u64,
+        records: &[EncryptedDataRecord],
+    ) -> Result<EncryptedData, TransactionError> {
+        let recovery_key = if let Some(key_id) = custom_recovery_key_id {
+            self.get_private_key(key_id).await?
+        } else {
+            self.get_private_view_key().await?
+        };
+        let value_key = value.into();
+        let commitment = self.get_commitment(commitment_mask_key_id, &value_key).await?;
+        let commitment_private_key = self.get_private_key(commitment_mask_key_id).await?;
+        let data = self.entropy.with_rng(|rng| {
+            EncryptedData::encrypt_records_with_rng(
+                &recovery_key,
+                &commitment,
+                value.into(),
+                &commitment_private_key,
+                records,
+                rng,
+            )
+        })?;
+        Ok(data)
+    }
+
+    /// Decrypts the typed records of an output, reading them in the format signalled by the output version. Unlike
+    /// `try_output_key_recovery`, this does not verify the mask or import the spending key.
+    pub async fn decrypt_output_records(
+        &self,
+        commitment: &Commitment,
+        encrypted_data: &EncryptedData,
+        txo_version: &TransactionOutputVersion,
+        custom_recovery_key_id: Option<&TariKeyId>,
+    ) -> Result<Vec<EncryptedDataRecord>, TransactionError> {
+        let recovery_key = if let Some(key_id) = custom_recovery_key_id {
+            self.get_private_key(key_id).await?
+        } else {
+            self.get_private_view_key().await?
+        };
+        let (_, _, records) =
+            EncryptedData::decrypt_output_records(&recovery_key, commitment, encrypted_data, *txo_version)?;
+        Ok(records)
+    }
+
     pub async fn stealth_address_script_spending_key(
         &self,
         commitment_mask_key_id: &TariKeyId,
         spend_key: &PublicKey,
     ) -> Result<PublicKey, TransactionError> {
         let private_key = self.get_private_key(commitment_mask_key_id).await?;
-        let hasher =
-            DomainSeparatedHasher::<Blake2b<U64>, KeyManagerTransactionsHashDomain>::new_with_label("script key");
-        let hasher = hasher.chain(private_key.as_bytes()).finalize();
-        let private_key = PrivateKey::from_uniform_bytes(hasher.as_ref())
+        let public_key = stealth_address_script_spending_key(&private_key, spend_key)
             .map_err(|_| KeyManagerServiceError::UnknownError("Invalid commitment mask private key".to_string()))?;
-        let public_key = PublicKey::from_secret_key(&private_key);
-        let public_key = spend_key + &public_key;
         Ok(public_key)
     }
 }
+
+/// The error for a signature that would need a key of the remote signer outside of a whole transaction, which the
+/// signer refuses to make because it could not apply its policy to it
+fn remote_signer_only_signs_transactions(caller: &str) -> TransactionError {
+    TransactionError::KeyManagerError(format!(
+        "{}: The remote signer only signs whole transactions, see `sign_transaction_inputs`",
+        caller
+    ))
+}
+
+/// Returns true if the private key of the key id is held by the remote signer of a `WalletType::RemoteSigner` wallet
+fn requires_remote_signer(key_id: &TariKeyId) -> bool {
+    match key_id {
+        KeyId::Managed { branch, .. } => is_remote_signer_branch(branch),
+        KeyId::Derived { .. } => true,
+        KeyId::Imported { .. } | KeyId::Zero => false,
+    }
+}
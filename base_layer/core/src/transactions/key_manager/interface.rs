@@ -37,15 +37,17 @@ use tari_script::{CheckSigSchnorrSignature, TariScript};
 use crate::transactions::{
     tari_amount::MicroMinotari,
     transaction_components::{
-        encrypted_data::PaymentId,
+        encrypted_data::{EncryptedDataRecord, PaymentId},
         EncryptedData,
         KernelFeatures,
         RangeProofType,
+        Transaction,
         TransactionError,
         TransactionInputVersion,
         TransactionKernelVersion,
         TransactionOutput,
         TransactionOutputVersion,
+        WalletOutput,
     },
 };
 
@@ -107,6 +109,16 @@ pub trait TransactionKeyManagerInterface: KeyManagerInterface<PublicKey> {
 
     async fn get_comms_key(&self) -> Result<KeyAndId<PublicKey>, KeyManagerServiceError>;
 
+    /// Gets the public spend key of the deterministic sub-address at `index`, index 0 being the primary spend key
+    async fn get_sub_address_spend_key(&self, index: u64) -> Result<PublicKey, KeyManagerServiceError>;
+
+    /// Imports the script key of a stealth output received on the sub-address at `index`
+    async fn import_sub_address_script_key(
+        &self,
+        commitment_mask_key_id: &TariKeyId,
+        index: u64,
+    ) -> Result<TariKeyId, KeyManagerServiceError>;
+
     async fn get_next_commitment_mask_and_script_key(
         &self,
     ) -> Result<(KeyAndId<PublicKey>, KeyAndId<PublicKey>), KeyManagerServiceError>;
@@ -117,6 +129,9 @@ pub trait TransactionKeyManagerInterface: KeyManagerInterface<PublicKey> {
         public_script_key: Option<&PublicKey>,
     ) -> Result<Option<TariKeyId>, KeyManagerServiceError>;
 
+    /// Gets the ids of all the keys that were imported into the key manager
+    async fn get_imported_key_ids(&self) -> Result<Vec<TariKeyId>, KeyManagerServiceError>;
+
     async fn get_diffie_hellman_shared_secret(
         &self,
         secret_key_id: &TariKeyId,
@@ -195,12 +210,38 @@ pub trait TransactionKeyManagerInterface: KeyManagerInterface<PublicKey> {
         custom_recovery_key_id: Option<&TariKeyId>,
     ) -> Result<(TariKeyId, MicroMinotari, PaymentId), TransactionError>;
 
+    async fn encrypt_records_for_recovery(
+        &self,
+        commitment_mask_key_id: &TariKeyId,
+        custom_recovery_key_id: Option<&TariKeyId>,
+        value: u64,
+        records: &[EncryptedDataRecord],
+    ) -> Result<EncryptedData, TransactionError>;
+
+    async fn decrypt_output_records(
+        &self,
+        commitment: &Commitment,
+        encrypted_data: &EncryptedData,
+        txo_version: &TransactionOutputVersion,
+        custom_recovery_key_id: Option<&TariKeyId>,
+    ) -> Result<Vec<EncryptedDataRecord>, TransactionError>;
+
     async fn get_script_offset(
         &self,
         script_key_ids: &[TariKeyId],
         sender_offset_key_ids: &[TariKeyId],
     ) -> Result<PrivateKey, TransactionError>;
 
+    /// Signs the inputs of a transaction that is otherwise complete, and sets its script offset. `inputs` are the
+    /// outputs that the transaction spends and `change_outputs` the ones that it pays back to the wallet.
+    async fn sign_transaction_inputs(
+        &self,
+        transaction: Transaction,
+        inputs: &[&WalletOutput],
+        change_outputs: &[&WalletOutput],
+        sender_offset_key_ids: &[TariKeyId],
+    ) -> Result<Transaction, TransactionError>;
+
     async fn get_metadata_signature_ephemeral_commitment(
         &self,
         nonce_id: &TariKeyId,
@@ -296,9 +337,9 @@ mod test {
     use rand::{distributions::Alphanumeric, rngs::OsRng, Rng};
     use tari_common_types::types::{PrivateKey, PublicKey};
     use tari_crypto::keys::{PublicKey as PK, SecretKey as SK};
-    use tari_key_manager::key_manager_service::KeyId;
+    use tari_key_manager::key_manager_service::{KeyId, KeyManagerInterface};
 
-    use crate::transactions::key_manager::TariKeyId;
+    use crate::transactions::key_manager::{create_memory_db_key_manager, TariKeyId, TransactionKeyManagerInterface};
 
     fn random_string(len: usize) -> String {
         iter::repeat(())
@@ -335,4 +376,31 @@ mod test {
         assert_eq!(zero_key_id, TariKeyId::from_str(&zero_key_id_str).unwrap());
         assert_eq!(derived_key_id, TariKeyId::from_str(&derived_key_id_str).unwrap());
     }
+
+    #[tokio::test]
+    async fn sub_address_script_key_matches_stealth_script_key() {
+        let key_manager = create_memory_db_key_manager().unwrap();
+        let spend_key = key_manager.get_spend_key().await.unwrap().pub_key;
+        assert_eq!(key_manager.get_sub_address_spend_key(0).await.unwrap(), spend_key);
+        let sub_address_spend_key = key_manager.get_sub_address_spend_key(7).await.unwrap();
+        assert_ne!(sub_address_spend_key, spend_key);
+        assert_eq!(
+            key_manager.get_sub_address_spend_key(7).await.unwrap(),
+            sub_address_spend_key
+        );
+
+        let (commitment_mask, _) = key_manager.get_next_commitment_mask_and_script_key().await.unwrap();
+        let script_public_key = key_manager
+            .stealth_address_script_spending_key(&commitment_mask.key_id, &sub_address_spend_key)
+            .await
+            .unwrap();
+        let script_key_id = key_manager
+            .import_sub_address_script_key(&commitment_mask.key_id, 7)
+            .await
+            .unwrap();
+        assert_eq!(
+            key_manager.get_public_key_at_key_id(&script_key_id).await.unwrap(),
+            script_public_key
+        );
+    }
 }
@@ -49,4 +49,13 @@ pub use memory_db_key_manager::{
 
 mod error;
 pub use error::CoreKeyManagerError;
+
+mod remote_signer;
+pub use remote_signer::{
+    is_remote_signer_branch,
+    RemoteScriptKey,
+    RemoteSigner,
+    RemoteSignerChangeOutput,
+    RemoteSignerInput,
+};
 pub use tari_common_types::key_branches::TransactionKeyManagerBranch;
@@ -0,0 +1,102 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! The interface to a signer that holds the wallet's spend key in another process. A wallet of type
+//! `WalletType::RemoteSigner` keeps its view key and the keys of the non-secret branches. The signer never signs a
+//! raw challenge for it: spends are sent to it as whole transactions, so that it can work out what they pay out and
+//! apply its policy before it signs anything.
+
+use tari_common_types::{
+    key_branches::TransactionKeyManagerBranch,
+    tari_address::TariAddress,
+    types::{ComAndPubSignature, PrivateKey, PublicKey},
+};
+use tari_comms::types::CommsDHKE;
+use tari_key_manager::key_manager_service::KeyManagerServiceError;
+
+use crate::transactions::{
+    tari_amount::MicroMinotari,
+    transaction_components::{RangeProofType, Transaction},
+};
+
+/// Returns true if the private keys of the branch are held by the remote signer
+pub fn is_remote_signer_branch(branch: &str) -> bool {
+    matches!(
+        TransactionKeyManagerBranch::from_key(branch),
+        TransactionKeyManagerBranch::Spend |
+            TransactionKeyManagerBranch::OneSidedSenderOffset |
+            TransactionKeyManagerBranch::RandomKey |
+            TransactionKeyManagerBranch::PreMine
+    )
+}
+
+/// The script key that a script signature is made with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RemoteScriptKey {
+    /// A key of one of the signer's branches
+    Managed {
+        branch: TransactionKeyManagerBranch,
+        index: u64,
+    },
+    /// A stealth script key, which the signer derives from the spend key and the commitment mask
+    Derived { commitment_mask: PrivateKey },
+}
+
+/// The opening of an input of a transaction that is sent to the signer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteSignerInput {
+    pub value: MicroMinotari,
+    pub commitment_mask: PrivateKey,
+    /// The key that the signer makes the script signature with, or None if the wallet has signed the input itself
+    pub script_key: Option<RemoteScriptKey>,
+}
+
+/// The opening of an output of a transaction that pays change back to the wallet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteSignerChangeOutput {
+    pub value: MicroMinotari,
+    pub commitment_mask: PrivateKey,
+}
+
+#[async_trait::async_trait]
+pub trait RemoteSigner: Send + Sync {
+    async fn get_public_key(
+        &self,
+        branch: TransactionKeyManagerBranch,
+        index: u64,
+    ) -> Result<PublicKey, KeyManagerServiceError>;
+
+    async fn get_dh_shared_secret(
+        &self,
+        branch: TransactionKeyManagerBranch,
+        index: u64,
+        public_key: &PublicKey,
+    ) -> Result<CommsDHKE, KeyManagerServiceError>;
+
+    /// Signs the metadata of a stealth one-sided payment of `value` to `receiver_address`. The signer builds the
+    /// stealth script itself, and refuses destinations that its policy does not allow. The payment is only counted
+    /// against the policy once the transaction that pays it is signed.
+    #[allow(clippy::too_many_arguments)]
+    async fn get_one_sided_metadata_signature(
+        &self,
+        txo_version: u8,
+        value: MicroMinotari,
+        sender_offset_index: u64,
+        commitment_mask: &PrivateKey,
+        receiver_address: &TariAddress,
+        metadata_signature_message_common: &[u8; 32],
+        range_proof_type: RangeProofType,
+    ) -> Result<ComAndPubSignature, KeyManagerServiceError>;
+
+    /// Signs the inputs of `transaction` and sets its script offset. The transaction must be complete apart from that,
+    /// and every output must either be a one-sided payment that the signer signed the metadata of, or be one of
+    /// `change_outputs`. `partial_script_offset` is the script keys that the wallet holds less the sender offsets that
+    /// it holds.
+    async fn sign_transaction(
+        &self,
+        transaction: &Transaction,
+        inputs: &[RemoteSignerInput],
+        change_outputs: &[RemoteSignerChangeOutput],
+        partial_script_offset: &PrivateKey,
+    ) -> Result<Transaction, KeyManagerServiceError>;
+}
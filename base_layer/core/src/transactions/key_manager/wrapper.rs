@@ -44,24 +44,29 @@ use tari_key_manager::{
 use tari_script::{CheckSigSchnorrSignature, TariScript};
 use tokio::sync::RwLock;
 
+#[cfg(any(test, feature = "deterministic_transactions"))]
+use crate::transactions::TransactionEntropy;
 use crate::transactions::{
     key_manager::{
         interface::{SecretTransactionKeyManagerInterface, TxoStage},
+        RemoteSigner,
         TariKeyId,
         TransactionKeyManagerInner,
         TransactionKeyManagerInterface,
     },
     tari_amount::MicroMinotari,
     transaction_components::{
-        encrypted_data::PaymentId,
+        encrypted_data::{EncryptedDataRecord, PaymentId},
         EncryptedData,
         KernelFeatures,
         RangeProofType,
+        Transaction,
         TransactionError,
         TransactionInputVersion,
         TransactionKernelVersion,
         TransactionOutput,
         TransactionOutputVersion,
+        WalletOutput,
     },
     CryptoFactories,
 };
@@ -102,6 +107,20 @@ where TBackend: KeyManagerBackend<PublicKey> + 'static
     pub async fn get_wallet_type(&self) -> Arc<WalletType> {
         self.transaction_key_manager_inner.read().await.get_wallet_type()
     }
+
+    /// Replaces the source of randomness used to derive random keys and nonces, see `TransactionEntropy`
+    #[cfg(any(test, feature = "deterministic_transactions"))]
+    pub async fn set_entropy(&self, entropy: TransactionEntropy) {
+        self.transaction_key_manager_inner.write().await.set_entropy(entropy);
+    }
+
+    /// Sets the signer that holds the spend key of a `WalletType::RemoteSigner` wallet
+    pub async fn set_remote_signer(&self, remote_signer: Arc<dyn RemoteSigner>) {
+        self.transaction_key_manager_inner
+            .write()
+            .await
+            .set_remote_signer(remote_signer);
+    }
 }
 
 #[async_trait::async_trait]
@@ -228,6 +247,26 @@ where TBackend: KeyManagerBackend<PublicKey> + 'static
         self.transaction_key_manager_inner.read().await.get_comms_key().await
     }
 
+    async fn get_sub_address_spend_key(&self, index: u64) -> Result<PublicKey, KeyManagerServiceError> {
+        self.transaction_key_manager_inner
+            .read()
+            .await
+            .get_sub_address_spend_key(index)
+            .await
+    }
+
+    async fn import_sub_address_script_key(
+        &self,
+        commitment_mask_key_id: &TariKeyId,
+        index: u64,
+    ) -> Result<TariKeyId, KeyManagerServiceError> {
+        self.transaction_key_manager_inner
+            .read()
+            .await
+            .import_sub_address_script_key(commitment_mask_key_id, index)
+            .await
+    }
+
     async fn get_next_commitment_mask_and_script_key(
         &self,
     ) -> Result<(KeyAndId<PublicKey>, KeyAndId<PublicKey>), KeyManagerServiceError> {
@@ -250,6 +289,14 @@ where TBackend: KeyManagerBackend<PublicKey> + 'static
             .await
     }
 
+    async fn get_imported_key_ids(&self) -> Result<Vec<TariKeyId>, KeyManagerServiceError> {
+        self.transaction_key_manager_inner
+            .read()
+            .await
+            .get_imported_key_ids()
+            .await
+    }
+
     async fn get_diffie_hellman_shared_secret(
         &self,
         secret_key_id: &TariKeyId,
@@ -416,6 +463,34 @@ where TBackend: KeyManagerBackend<PublicKey> + 'static
             .await
     }
 
+    async fn encrypt_records_for_recovery(
+        &self,
+        commitment_mask_key_id: &TariKeyId,
+        custom_recovery_key_id: Option<&TariKeyId>,
+        value: u64,
+        records: &[EncryptedDataRecord],
+    ) -> Result<EncryptedData, TransactionError> {
+        self.transaction_key_manager_inner
+            .read()
+            .await
+            .encrypt_records_for_recovery(commitment_mask_key_id, custom_recovery_key_id, value, records)
+            .await
+    }
+
+    async fn decrypt_output_records(
+        &self,
+        commitment: &Commitment,
+        encrypted_data: &EncryptedData,
+        txo_version: &TransactionOutputVersion,
+        custom_recovery_key_id: Option<&TariKeyId>,
+    ) -> Result<Vec<EncryptedDataRecord>, TransactionError> {
+        self.transaction_key_manager_inner
+            .read()
+            .await
+            .decrypt_output_records(commitment, encrypted_data, txo_version, custom_recovery_key_id)
+            .await
+    }
+
     async fn get_script_offset(
         &self,
         script_key_ids: &[TariKeyId],
@@ -428,6 +503,20 @@ where TBackend: KeyManagerBackend<PublicKey> + 'static
             .await
     }
 
+    async fn sign_transaction_inputs(
+        &self,
+        transaction: Transaction,
+        inputs: &[&WalletOutput],
+        change_outputs: &[&WalletOutput],
+        sender_offset_key_ids: &[TariKeyId],
+    ) -> Result<Transaction, TransactionError> {
+        self.transaction_key_manager_inner
+            .read()
+            .await
+            .sign_transaction_inputs(transaction, inputs, change_outputs, sender_offset_key_ids)
+            .await
+    }
+
     async fn get_metadata_signature_ephemeral_commitment(
         &self,
         nonce_id: &TariKeyId,
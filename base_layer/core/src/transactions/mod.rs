@@ -15,6 +15,9 @@ pub use coinbase_builder::{
     CoinbaseBuilder,
 };
 
+mod entropy;
+pub use entropy::{TransactionEntropy, TransactionRng};
+
 pub mod fee;
 pub mod tari_amount;
 pub mod transaction_components;
@@ -22,6 +25,17 @@ pub mod transaction_components;
 mod format_currency;
 pub use format_currency::format_currency;
 
+mod partial_transaction;
+pub use partial_transaction::{
+    PartialTransaction,
+    PartialTransactionError,
+    PartialTransactionVersion,
+    SignatureSlot,
+    PARTIAL_TRANSACTION_MAGIC,
+};
+
+pub mod payment_proof;
+
 pub mod transaction_protocol;
 pub use transaction_protocol::{recipient::ReceiverTransactionProtocol, sender::SenderTransactionProtocol};
 
@@ -0,0 +1,253 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! A versioned, canonical binary container for partially constructed transactions (PSTT).
+//!
+//! The encoding is the 4-byte magic `PSTT`, followed by a single version byte and the borsh encoding of the
+//! [PartialTransaction] body. Borsh is canonical, so a given partial transaction always encodes to the same bytes, and
+//! decoding rejects trailing data. This lets hardware wallets, offline signers and multiparty flows exchange partial
+//! transactions without agreeing on an ad-hoc serde format.
+
+use std::collections::HashSet;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use thiserror::Error;
+
+use crate::transactions::{
+    transaction_components::{TransactionInput, TransactionKernel, TransactionOutput},
+    transaction_protocol::TransactionMetadata,
+};
+
+/// The magic bytes that prefix every encoded partial transaction
+pub const PARTIAL_TRANSACTION_MAGIC: [u8; 4] = *b"PSTT";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(u8)]
+pub enum PartialTransactionVersion {
+    V0 = 0,
+}
+
+impl PartialTransactionVersion {
+    pub fn get_current_version() -> Self {
+        Self::V0
+    }
+
+    pub fn as_u8(self) -> u8 {
+        self as u8
+    }
+}
+
+impl TryFrom<u8> for PartialTransactionVersion {
+    type Error = PartialTransactionError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(PartialTransactionVersion::V0),
+            v => Err(PartialTransactionError::UnsupportedVersion(v)),
+        }
+    }
+}
+
+/// A signature that still has to be provided before the transaction can be finalized. Indexes refer to the position of
+/// the component in the partial transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, BorshSerialize, BorshDeserialize)]
+pub enum SignatureSlot {
+    /// The script signature of the input at this index
+    InputScript(u32),
+    /// The metadata signature of the output at this index
+    OutputMetadata(u32),
+    /// The excess signature of the kernel at this index
+    Kernel(u32),
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum PartialTransactionError {
+    #[error("Missing or invalid PSTT magic bytes")]
+    InvalidMagic,
+    #[error("Unsupported PSTT version {0}")]
+    UnsupportedVersion(u8),
+    #[error("Malformed PSTT body: {0}")]
+    Malformed(String),
+    #[error("Unexpected trailing bytes after PSTT body")]
+    TrailingBytes,
+    #[error("Signature slot {0:?} does not refer to an existing transaction component")]
+    InvalidSignatureSlot(SignatureSlot),
+    #[error("Signature slot {0:?} is listed more than once")]
+    DuplicateSignatureSlot(SignatureSlot),
+}
+
+/// A partially constructed transaction, along with the signatures that are still missing from it
+#[derive(Debug, Clone, Default, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct PartialTransaction {
+    pub metadata: TransactionMetadata,
+    pub inputs: Vec<TransactionInput>,
+    pub outputs: Vec<TransactionOutput>,
+    pub kernels: Vec<TransactionKernel>,
+    pub missing_signatures: Vec<SignatureSlot>,
+}
+
+impl PartialTransaction {
+    pub fn new(metadata: TransactionMetadata) -> Self {
+        Self {
+            metadata,
+            ..Default::default()
+        }
+    }
+
+    /// Returns true if no signatures are outstanding
+    pub fn is_complete(&self) -> bool {
+        self.missing_signatures.is_empty()
+    }
+
+    /// Marks the signature in the given slot as provided. Returns false if the slot was not outstanding.
+    pub fn mark_signed(&mut self, slot: SignatureSlot) -> bool {
+        let len = self.missing_signatures.len();
+        self.missing_signatures.retain(|s| *s != slot);
+        self.missing_signatures.len() != len
+    }
+
+    /// Checks that every missing signature slot refers to an existing component, and that no slot is listed twice
+    pub fn validate_signature_slots(&self) -> Result<(), PartialTransactionError> {
+        let mut seen = HashSet::with_capacity(self.missing_signatures.len());
+        for slot in &self.missing_signatures {
+            let (index, len) = match *slot {
+                SignatureSlot::InputScript(i) => (i, self.inputs.len()),
+                SignatureSlot::OutputMetadata(i) => (i, self.outputs.len()),
+                SignatureSlot::Kernel(i) => (i, self.kernels.len()),
+            };
+            if usize::try_from(index).unwrap_or(usize::MAX) >= len {
+                return Err(PartialTransactionError::InvalidSignatureSlot(*slot));
+            }
+            if !seen.insert(*slot) {
+                return Err(PartialTransactionError::DuplicateSignatureSlot(*slot));
+            }
+        }
+        Ok(())
+    }
+
+    /// Encodes the partial transaction in the current PSTT version
+    pub fn to_bytes(&self) -> Result<Vec<u8>, PartialTransactionError> {
+        self.validate_signature_slots()?;
+        let mut buf = Vec::with_capacity(PARTIAL_TRANSACTION_MAGIC.len() + 1);
+        buf.extend_from_slice(&PARTIAL_TRANSACTION_MAGIC);
+        buf.push(PartialTransactionVersion::get_current_version().as_u8());
+        self.serialize(&mut buf)
+            .map_err(|e| PartialTransactionError::Malformed(e.to_string()))?;
+        Ok(buf)
+    }
+
+    /// Decodes a PSTT encoded partial transaction, rejecting unknown versions, trailing bytes and invalid signature
+    /// slots
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, PartialTransactionError> {
+        let body = bytes
+            .strip_prefix(&PARTIAL_TRANSACTION_MAGIC[..])
+            .ok_or(PartialTransactionError::InvalidMagic)?;
+        let (version, mut body) = body
+            .split_first()
+            .ok_or_else(|| PartialTransactionError::Malformed("missing version byte".to_string()))?;
+        match PartialTransactionVersion::try_from(*version)? {
+            PartialTransactionVersion::V0 => {},
+        }
+        let tx = Self::deserialize(&mut body).map_err(|e| PartialTransactionError::Malformed(e.to_string()))?;
+        if !body.is_empty() {
+            return Err(PartialTransactionError::TrailingBytes);
+        }
+        tx.validate_signature_slots()?;
+        Ok(tx)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::transactions::tari_amount::MicroMinotari;
+
+    fn partial_transaction() -> PartialTransaction {
+        let mut tx = PartialTransaction::new(TransactionMetadata::new(MicroMinotari(100), 5));
+        tx.inputs.push(TransactionInput::default());
+        tx.outputs.push(TransactionOutput::default());
+        tx.kernels.push(TransactionKernel::default());
+        tx.missing_signatures = vec![SignatureSlot::InputScript(0), SignatureSlot::Kernel(0)];
+        tx
+    }
+
+    #[test]
+    fn it_round_trips() {
+        let tx = partial_transaction();
+        let bytes = tx.to_bytes().unwrap();
+        assert_eq!(&bytes[..4], b"PSTT");
+        assert_eq!(bytes[4], 0);
+        let decoded = PartialTransaction::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded, tx);
+        assert_eq!(decoded.to_bytes().unwrap(), bytes);
+    }
+
+    #[test]
+    fn it_rejects_malformed_containers() {
+        let mut bytes = partial_transaction().to_bytes().unwrap();
+
+        let mut bad_magic = bytes.clone();
+        bad_magic[0] = b'X';
+        assert_eq!(
+            PartialTransaction::from_bytes(&bad_magic),
+            Err(PartialTransactionError::InvalidMagic)
+        );
+
+        let mut bad_version = bytes.clone();
+        bad_version[4] = 9;
+        assert_eq!(
+            PartialTransaction::from_bytes(&bad_version),
+            Err(PartialTransactionError::UnsupportedVersion(9))
+        );
+
+        bytes.push(0);
+        assert_eq!(
+            PartialTransaction::from_bytes(&bytes),
+            Err(PartialTransactionError::TrailingBytes)
+        );
+    }
+
+    #[test]
+    fn it_tracks_signature_slots() {
+        let mut tx = partial_transaction();
+        assert!(!tx.is_complete());
+        assert!(tx.mark_signed(SignatureSlot::InputScript(0)));
+        assert!(!tx.mark_signed(SignatureSlot::InputScript(0)));
+        assert!(tx.mark_signed(SignatureSlot::Kernel(0)));
+        assert!(tx.is_complete());
+
+        tx.missing_signatures.push(SignatureSlot::OutputMetadata(1));
+        assert_eq!(
+            tx.to_bytes(),
+            Err(PartialTransactionError::InvalidSignatureSlot(
+                SignatureSlot::OutputMetadata(1)
+            ))
+        );
+        tx.missing_signatures = vec![SignatureSlot::OutputMetadata(0), SignatureSlot::OutputMetadata(0)];
+        assert_eq!(
+            tx.to_bytes(),
+            Err(PartialTransactionError::DuplicateSignatureSlot(
+                SignatureSlot::OutputMetadata(0)
+            ))
+        );
+    }
+}
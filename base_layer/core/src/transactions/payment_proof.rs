@@ -0,0 +1,256 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Proofs that a wallet paid a given amount to a given address.
+//!
+//! A proof of payment reveals the one-time sender offset private key of a one-sided output. With it, and the
+//! recipient's public view key, anyone can derive the Diffie-Hellman shared secret that the output was created from,
+//! and from that the output's commitment mask and script. The opening of that one output is revealed, but nothing
+//! else about the sender's or the recipient's wallets. The proof is signed with the sender's spend key so that it can
+//! be attributed to the sender's address.
+
+use blake2::Blake2b;
+use digest::consts::U64;
+use serde::{Deserialize, Serialize};
+use tari_common_types::{
+    tari_address::TariAddress,
+    types::{FixedHash, PrivateKey, PublicKey, Signature},
+};
+use tari_comms::types::CommsDHKE;
+use tari_crypto::{
+    commitment::HomomorphicCommitmentFactory,
+    hash_domain,
+    hashing::DomainSeparatedHasher,
+    keys::PublicKey as PublicKeyT,
+};
+use tari_script::push_pubkey_script;
+use tari_utilities::ByteArray;
+use thiserror::Error;
+
+use crate::{
+    one_sided::{shared_secret_to_output_spending_key, stealth_address_script_spending_key},
+    transactions::{tari_amount::MicroMinotari, transaction_components::TransactionOutput, CryptoFactories},
+};
+
+hash_domain!(
+    PaymentProofHashDomain,
+    "com.tari.base_layer.core.transactions.payment_proof",
+    0
+);
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum PaymentProofError {
+    #[error("The proof is not signed by the sender's spend key")]
+    InvalidSignature,
+    #[error("The recipient address has no view key, so it cannot receive one-sided payments")]
+    MissingRecipientViewKey,
+    #[error("The output hash does not match the proof")]
+    OutputHashMismatch,
+    #[error("The sender offset key does not match the output")]
+    SenderOffsetMismatch,
+    #[error("The output does not commit to the proven amount")]
+    CommitmentMismatch,
+    #[error("The output script does not pay the recipient address")]
+    ScriptMismatch,
+    #[error("Invalid key: {0}")]
+    InvalidKey(String),
+}
+
+/// A signed proof that the sender paid `amount` to `recipient_address` in the output with `output_hash`, which was
+/// mined with the kernel that has `kernel_excess_sig`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaymentProof {
+    pub sender_address: TariAddress,
+    pub recipient_address: TariAddress,
+    pub amount: MicroMinotari,
+    pub kernel_excess_sig: Signature,
+    pub output_hash: FixedHash,
+    pub sender_offset_private_key: PrivateKey,
+    /// Signed by the private key of the sender address' public spend key
+    pub signature: Signature,
+}
+
+impl PaymentProof {
+    /// Creates an unsigned proof. The sender signs [PaymentProof::signing_challenge] and sets the signature.
+    pub fn new(
+        sender_address: TariAddress,
+        recipient_address: TariAddress,
+        amount: MicroMinotari,
+        kernel_excess_sig: Signature,
+        output_hash: FixedHash,
+        sender_offset_private_key: PrivateKey,
+    ) -> Self {
+        Self {
+            sender_address,
+            recipient_address,
+            amount,
+            kernel_excess_sig,
+            output_hash,
+            sender_offset_private_key,
+            signature: Signature::default(),
+        }
+    }
+
+    /// The challenge that the sender signs with the given public nonce
+    pub fn signing_challenge(&self, public_nonce: &PublicKey) -> [u8; 64] {
+        let sender_offset_public_key = PublicKey::from_secret_key(&self.sender_offset_private_key);
+        let hasher = DomainSeparatedHasher::<Blake2b<U64>, PaymentProofHashDomain>::new_with_label("signature")
+            .chain(self.sender_address.public_spend_key().as_bytes())
+            .chain(public_nonce.as_bytes())
+            .chain(self.recipient_address.to_vec())
+            .chain(self.amount.as_u64().to_le_bytes())
+            .chain(self.kernel_excess_sig.get_public_nonce().as_bytes())
+            .chain(self.kernel_excess_sig.get_signature().as_bytes())
+            .chain(self.output_hash.as_slice())
+            .chain(sender_offset_public_key.as_bytes());
+        digest::Digest::finalize(hasher).into()
+    }
+
+    /// Verifies that the proof is signed by the sender
+    pub fn verify_signature(&self) -> Result<(), PaymentProofError> {
+        let challenge = self.signing_challenge(self.signature.get_public_nonce());
+        if self
+            .signature
+            .verify_raw_uniform(self.sender_address.public_spend_key(), &challenge)
+        {
+            Ok(())
+        } else {
+            Err(PaymentProofError::InvalidSignature)
+        }
+    }
+
+    /// Verifies the proof against the output it refers to: the output must have been created with the revealed sender
+    /// offset key for the recipient address, and must commit to the proven amount. This does not check that the
+    /// output or the kernel were mined, which has to be checked against the chain.
+    pub fn verify_output(
+        &self,
+        output: &TransactionOutput,
+        factories: &CryptoFactories,
+    ) -> Result<(), PaymentProofError> {
+        self.verify_signature()?;
+        if output.hash() != self.output_hash {
+            return Err(PaymentProofError::OutputHashMismatch);
+        }
+        if PublicKey::from_secret_key(&self.sender_offset_private_key) != output.sender_offset_public_key {
+            return Err(PaymentProofError::SenderOffsetMismatch);
+        }
+        let view_key = self
+            .recipient_address
+            .public_view_key()
+            .ok_or(PaymentProofError::MissingRecipientViewKey)?;
+        let shared_secret = CommsDHKE::new(&self.sender_offset_private_key, view_key);
+        let commitment_mask = shared_secret_to_output_spending_key(&shared_secret)
+            .map_err(|e| PaymentProofError::InvalidKey(e.to_string()))?;
+        if factories
+            .commitment
+            .commit_value(&commitment_mask, self.amount.as_u64()) !=
+            output.commitment
+        {
+            return Err(PaymentProofError::CommitmentMismatch);
+        }
+        let spend_key = self.recipient_address.public_spend_key();
+        let stealth_script_key = stealth_address_script_spending_key(&commitment_mask, spend_key)
+            .map_err(|e| PaymentProofError::InvalidKey(e.to_string()))?;
+        if output.script != push_pubkey_script(spend_key) && output.script != push_pubkey_script(&stealth_script_key) {
+            return Err(PaymentProofError::ScriptMismatch);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::OsRng;
+    use tari_common::configuration::Network;
+    use tari_crypto::keys::SecretKey;
+
+    use super::*;
+
+    fn random_keypair() -> (PrivateKey, PublicKey) {
+        PublicKey::random_keypair(&mut OsRng)
+    }
+
+    fn create_proof(stealth: bool) -> (PaymentProof, TransactionOutput) {
+        let (sender_spend_key, sender_spend_public_key) = random_keypair();
+        let (_, recipient_view_key) = random_keypair();
+        let (_, recipient_spend_key) = random_keypair();
+        let sender_address =
+            TariAddress::new_single_address_with_interactive_only(sender_spend_public_key, Network::LocalNet);
+        let recipient_address = TariAddress::new_dual_address_with_default_features(
+            recipient_view_key.clone(),
+            recipient_spend_key.clone(),
+            Network::LocalNet,
+        );
+        let amount = MicroMinotari(5_000);
+
+        let (sender_offset_private_key, sender_offset_public_key) = random_keypair();
+        let shared_secret = CommsDHKE::new(&sender_offset_private_key, &recipient_view_key);
+        let commitment_mask = shared_secret_to_output_spending_key(&shared_secret).unwrap();
+        let script_key = if stealth {
+            stealth_address_script_spending_key(&commitment_mask, &recipient_spend_key).unwrap()
+        } else {
+            recipient_spend_key
+        };
+        let output = TransactionOutput {
+            commitment: CryptoFactories::default()
+                .commitment
+                .commit_value(&commitment_mask, amount.as_u64()),
+            script: push_pubkey_script(&script_key),
+            sender_offset_public_key,
+            ..Default::default()
+        };
+
+        let mut proof = PaymentProof::new(
+            sender_address,
+            recipient_address,
+            amount,
+            Signature::default(),
+            output.hash(),
+            sender_offset_private_key,
+        );
+        let (nonce, public_nonce) = random_keypair();
+        let challenge = proof.signing_challenge(&public_nonce);
+        proof.signature = Signature::sign_raw_uniform(&sender_spend_key, nonce, &challenge).unwrap();
+        (proof, output)
+    }
+
+    #[test]
+    fn it_verifies_payments_to_an_address() {
+        let factories = CryptoFactories::default();
+        for stealth in [false, true] {
+            let (proof, output) = create_proof(stealth);
+            proof.verify_output(&output, &factories).unwrap();
+        }
+    }
+
+    #[test]
+    fn it_rejects_a_tampered_proof() {
+        let factories = CryptoFactories::default();
+        let (proof, output) = create_proof(true);
+
+        let mut tampered = proof.clone();
+        tampered.amount = MicroMinotari(50_000);
+        assert_eq!(
+            tampered.verify_output(&output, &factories),
+            Err(PaymentProofError::InvalidSignature)
+        );
+
+        let mut other_output = output.clone();
+        other_output.commitment = factories
+            .commitment
+            .commit_value(&PrivateKey::random(&mut OsRng), 5_000);
+        assert_eq!(
+            proof.verify_output(&other_output, &factories),
+            Err(PaymentProofError::OutputHashMismatch)
+        );
+
+        let mut other_sender = proof;
+        let (_, public_key) = random_keypair();
+        other_sender.sender_address =
+            TariAddress::new_single_address_with_interactive_only(public_key, Network::LocalNet);
+        assert_eq!(
+            other_sender.verify_output(&output, &factories),
+            Err(PaymentProofError::InvalidSignature)
+        );
+    }
+}
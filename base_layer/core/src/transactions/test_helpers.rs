@@ -306,6 +306,7 @@ pub async fn create_random_signature_from_secret_key(
         tx_meta.lock_height,
         &tx_meta.kernel_features,
         &tx_meta.burn_commitment,
+        &tx_meta.burn_claim_public_key,
     );
     let kernel_signature = key_manager
         .get_partial_txo_kernel_signature(
@@ -802,7 +803,7 @@ pub async fn create_coinbase_kernel(
     let kernel_version = TransactionKernelVersion::get_current_version();
     let kernel_features = KernelFeatures::COINBASE_KERNEL;
     let kernel_message =
-        TransactionKernel::build_kernel_signature_message(&kernel_version, 0.into(), 0, &kernel_features, &None);
+        TransactionKernel::build_kernel_signature_message(&kernel_version, 0.into(), 0, &kernel_features, &None, &None);
     let public_nonce = key_manager
         .get_next_key(TransactionKeyManagerBranch::KernelNonce.get_branch_key())
         .await
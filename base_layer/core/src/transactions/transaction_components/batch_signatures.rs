@@ -0,0 +1,177 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Batch verification of kernel excess signatures and output metadata signatures.
+//!
+//! Every signature reduces to one or more linear equations over the Ristretto group. Weighting each equation with an
+//! independent random scalar and summing them gives a single equation that, except with negligible probability, only
+//! holds if every individual equation holds. That equation is checked with a single multiscalar multiplication, which
+//! is much cheaper than verifying each signature on its own. When a batch fails it is bisected until the offending
+//! signature is found, so the error returned is the same one individual verification would produce.
+
+use rand::rngs::OsRng;
+use tari_common_types::types::{CommitmentFactory, PrivateKey, PublicKey};
+use tari_crypto::{
+    commitment::HomomorphicCommitmentFactory,
+    keys::{PublicKey as PublicKeyTrait, SecretKey},
+};
+
+use crate::transactions::transaction_components::{TransactionError, TransactionKernel, TransactionOutput};
+
+/// Verifies the excess signatures of all the given kernels as a single batch
+pub fn batch_verify_kernel_signatures(kernels: &[&TransactionKernel]) -> Result<(), TransactionError> {
+    verify_with_bisection(kernels, &kernel_batch_holds, &|kernel| kernel.verify_signature())
+}
+
+/// Verifies the metadata signatures of all the given outputs as a single batch
+pub fn batch_verify_metadata_signatures(outputs: &[&TransactionOutput]) -> Result<(), TransactionError> {
+    verify_with_bisection(outputs, &metadata_batch_holds, &|output| {
+        output.verify_metadata_signature()
+    })
+}
+
+/// Checks `items` as a batch, bisecting on failure until the failing item is found. The error returned is the one from
+/// verifying that item individually.
+fn verify_with_bisection<T, B, S>(items: &[T], batch_holds: &B, verify_single: &S) -> Result<(), TransactionError>
+where
+    B: Fn(&[T]) -> bool,
+    S: Fn(&T) -> Result<(), TransactionError>,
+{
+    if items.is_empty() || batch_holds(items) {
+        return Ok(());
+    }
+    if let [item] = items {
+        verify_single(item)?;
+        // The batch equation is stricter than individual verification for degenerate inputs, so defer to the latter
+        return Ok(());
+    }
+    let (left, right) = items.split_at(items.len() / 2);
+    verify_with_bisection(left, batch_holds, verify_single)?;
+    verify_with_bisection(right, batch_holds, verify_single)
+}
+
+/// Each kernel signature satisfies `s*G = R + e*P`. The batch checks `(sum w_i*s_i)*G = sum w_i*R_i + w_i*e_i*P_i`.
+fn kernel_batch_holds(kernels: &[&TransactionKernel]) -> bool {
+    let mut s_sum = PrivateKey::default();
+    let mut scalars = Vec::with_capacity(kernels.len() * 2);
+    let mut points = Vec::with_capacity(kernels.len() * 2);
+    for kernel in kernels {
+        let e = match PrivateKey::from_uniform_bytes(&kernel.signature_challenge()) {
+            Ok(e) => e,
+            Err(_) => return false,
+        };
+        let w = PrivateKey::random(&mut OsRng);
+        s_sum = s_sum + &w * kernel.excess_sig.get_signature();
+        scalars.push(&w * &e);
+        points.push(kernel.excess.as_public_key().clone());
+        scalars.push(w);
+        points.push(kernel.excess_sig.get_public_nonce().clone());
+    }
+    PublicKey::from_secret_key(&s_sum) == PublicKey::batch_mul(&scalars, &points)
+}
+
+/// Each metadata signature satisfies `R_C + e*C = u_x*G + u_a*H` and `R_P + e*P = u_y*G`. Both equations of every
+/// signature get their own weight and are folded into one check.
+fn metadata_batch_holds(outputs: &[&TransactionOutput]) -> bool {
+    let mut g_sum = PrivateKey::default();
+    let mut h_sum = PrivateKey::default();
+    let mut scalars = Vec::with_capacity(outputs.len() * 4);
+    let mut points = Vec::with_capacity(outputs.len() * 4);
+    for output in outputs {
+        let sig = &output.metadata_signature;
+        // Mirror the rejection of degenerate statements in individual verification
+        if output.commitment.as_public_key() == &PublicKey::default() ||
+            output.sender_offset_public_key == PublicKey::default()
+        {
+            return false;
+        }
+        let e = match PrivateKey::from_uniform_bytes(&output.metadata_signature_challenge()) {
+            Ok(e) => e,
+            Err(_) => return false,
+        };
+        if e == PrivateKey::default() {
+            return false;
+        }
+        let w = PrivateKey::random(&mut OsRng);
+        let v = PrivateKey::random(&mut OsRng);
+        g_sum = g_sum + &w * sig.u_x() + &v * sig.u_y();
+        h_sum = h_sum + &w * sig.u_a();
+
+        scalars.push(&w * &e);
+        points.push(output.commitment.as_public_key().clone());
+        scalars.push(w);
+        points.push(sig.ephemeral_commitment().as_public_key().clone());
+        scalars.push(&v * &e);
+        points.push(output.sender_offset_public_key.clone());
+        scalars.push(v);
+        points.push(sig.ephemeral_pubkey().clone());
+    }
+    let lhs = CommitmentFactory::default().commit(&g_sum, &h_sum);
+    lhs.as_public_key() == &PublicKey::batch_mul(&scalars, &points)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::transactions::{
+        key_manager::create_memory_db_key_manager,
+        tari_amount::MicroMinotari,
+        test_helpers::{create_test_kernel, create_tx},
+        transaction_components::KernelFeatures,
+    };
+
+    #[test]
+    fn it_batch_verifies_kernel_signatures() {
+        let kernels = (0..10)
+            .map(|i| create_test_kernel(MicroMinotari(100 + i), i, KernelFeatures::empty()))
+            .collect::<Vec<_>>();
+        let refs = kernels.iter().collect::<Vec<_>>();
+        batch_verify_kernel_signatures(&refs).unwrap();
+        batch_verify_kernel_signatures(&[]).unwrap();
+
+        let mut bad = kernels.clone();
+        bad[7].fee = MicroMinotari(1);
+        let refs = bad.iter().collect::<Vec<_>>();
+        assert_eq!(
+            batch_verify_kernel_signatures(&refs).unwrap_err(),
+            bad[7].verify_signature().unwrap_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn it_batch_verifies_metadata_signatures() {
+        let key_manager = create_memory_db_key_manager().unwrap();
+        let (tx, _, _) = create_tx(5000.into(), 3.into(), 1, 2, 1, 6, Default::default(), &key_manager)
+            .await
+            .unwrap();
+        let outputs = tx.body.outputs().iter().collect::<Vec<_>>();
+        batch_verify_metadata_signatures(&outputs).unwrap();
+
+        let mut bad = tx.body.outputs().clone();
+        bad[4].minimum_value_promise = MicroMinotari(1);
+        let refs = bad.iter().collect::<Vec<_>>();
+        assert!(batch_verify_metadata_signatures(&refs).is_err());
+        bad[4] = tx.body.outputs()[4].clone();
+        let refs = bad.iter().collect::<Vec<_>>();
+        batch_verify_metadata_signatures(&refs).unwrap();
+    }
+}
@@ -0,0 +1,62 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use serde::{Deserialize, Serialize};
+use tari_common_types::types::{Commitment, FixedHash};
+use tari_script::TariScript;
+
+use crate::transactions::{
+    tari_amount::MicroMinotari,
+    transaction_components::{EncryptedData, OutputType, TransactionOutput, TransactionOutputVersion},
+};
+
+/// The parts of a transaction output a light wallet needs to recognise its own outputs: the commitment, encrypted data
+/// and version (which says how the encrypted data is laid out) are enough to trial-decrypt with the view key, and the
+/// script tells the wallet how the output can be spent.
+/// Range proofs and signatures are left out, which makes a compact output a fraction of the size of a full one. The
+/// full output can be fetched by hash once the wallet has found a match.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompactOutput {
+    pub version: TransactionOutputVersion,
+    pub output_hash: FixedHash,
+    pub commitment: Commitment,
+    pub script: TariScript,
+    pub encrypted_data: EncryptedData,
+    pub output_type: OutputType,
+    pub maturity: u64,
+    pub minimum_value_promise: MicroMinotari,
+}
+
+impl From<&TransactionOutput> for CompactOutput {
+    fn from(output: &TransactionOutput) -> Self {
+        Self {
+            version: output.version,
+            output_hash: output.hash(),
+            commitment: output.commitment.clone(),
+            script: output.script.clone(),
+            encrypted_data: output.encrypted_data.clone(),
+            output_type: output.features.output_type,
+            maturity: output.features.maturity,
+            minimum_value_promise: output.minimum_value_promise,
+        }
+    }
+}
@@ -60,15 +60,24 @@ use tari_utilities::{
 use thiserror::Error;
 use zeroize::{Zeroize, Zeroizing};
 
-use super::EncryptedDataKey;
-use crate::transactions::tari_amount::MicroMinotari;
+use super::{EncryptedDataKey, TransactionOutputVersion};
+use crate::transactions::{tari_amount::MicroMinotari, TransactionRng};
 // Useful size constants, each in bytes
 const SIZE_NONCE: usize = size_of::<XNonce>();
 const SIZE_VALUE: usize = size_of::<u64>();
 const SIZE_MASK: usize = PrivateKey::KEY_LEN;
 const SIZE_TAG: usize = size_of::<Tag>();
 pub const STATIC_ENCRYPTED_DATA_SIZE_TOTAL: usize = SIZE_NONCE + SIZE_VALUE + SIZE_MASK + SIZE_TAG;
-const MAX_ENCRYPTED_DATA_SIZE: usize = 256 + STATIC_ENCRYPTED_DATA_SIZE_TOTAL;
+/// The hard upper bound on the extra encrypted data size. The limit in force is set per consensus version by
+/// `max_extra_encrypted_data_byte_size`, which may never exceed this value.
+pub const MAX_EXTRA_ENCRYPTED_DATA_SIZE: usize = 1024;
+const MAX_ENCRYPTED_DATA_SIZE: usize = MAX_EXTRA_ENCRYPTED_DATA_SIZE + STATIC_ENCRYPTED_DATA_SIZE_TOTAL;
+
+// Typed record payloads start with the record format version
+const RECORDS_VERSION: u8 = 1;
+const SIZE_RECORDS_HEADER: usize = size_of::<u8>();
+const SIZE_RECORD_TAG: usize = 1;
+const SIZE_RECORD_LENGTH: usize = size_of::<u16>();
 
 // Number of hex characters of encrypted data to display on each side of ellipsis when truncating
 const DISPLAY_CUTOFF: usize = 16;
@@ -193,8 +202,116 @@ impl Display for PaymentId {
     }
 }
 
+/// A typed record stored in the extra encrypted data of an output.
+///
+/// Records are encoded as `tag (u8) | length (u16, little endian) | value`, after a header holding the record format
+/// version. Records are only carried by outputs whose version signals them (see
+/// `TransactionOutputVersion::has_encrypted_data_records`), so that wallets that only read a bare payment id never
+/// receive them, and a record payload is encrypted with its own associated data, so that it can never be mistaken for a
+/// bare payment id, which may hold arbitrary bytes. Unknown tags are preserved so that newer record types survive a
+/// round trip through older software.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub enum EncryptedDataRecord {
+    PaymentId(PaymentId),
+    Memo(String),
+    RefundAddress(TariAddress),
+    Unknown { tag: u8, data: Vec<u8> },
+}
+
+impl EncryptedDataRecord {
+    const TAG_MEMO: u8 = 2;
+    const TAG_PAYMENT_ID: u8 = 1;
+    const TAG_REFUND_ADDRESS: u8 = 3;
+
+    pub fn tag(&self) -> u8 {
+        match self {
+            EncryptedDataRecord::PaymentId(_) => Self::TAG_PAYMENT_ID,
+            EncryptedDataRecord::Memo(_) => Self::TAG_MEMO,
+            EncryptedDataRecord::RefundAddress(_) => Self::TAG_REFUND_ADDRESS,
+            EncryptedDataRecord::Unknown { tag, .. } => *tag,
+        }
+    }
+
+    fn value_bytes(&self) -> Vec<u8> {
+        match self {
+            EncryptedDataRecord::PaymentId(v) => v.to_bytes(),
+            EncryptedDataRecord::Memo(v) => v.as_bytes().to_vec(),
+            EncryptedDataRecord::RefundAddress(v) => v.to_vec(),
+            EncryptedDataRecord::Unknown { data, .. } => data.clone(),
+        }
+    }
+
+    fn from_tag_and_value(tag: u8, value: &[u8]) -> Result<Self, EncryptedDataError> {
+        match tag {
+            Self::TAG_PAYMENT_ID => Ok(EncryptedDataRecord::PaymentId(PaymentId::from_bytes(value)?)),
+            Self::TAG_MEMO => Ok(EncryptedDataRecord::Memo(
+                String::from_utf8(value.to_vec()).map_err(|e| EncryptedDataError::ByteArrayError(e.to_string()))?,
+            )),
+            Self::TAG_REFUND_ADDRESS => Ok(EncryptedDataRecord::RefundAddress(
+                TariAddress::from_bytes(value).map_err(|e| EncryptedDataError::ByteArrayError(e.to_string()))?,
+            )),
+            _ => Ok(EncryptedDataRecord::Unknown {
+                tag,
+                data: value.to_vec(),
+            }),
+        }
+    }
+
+    /// Encodes the records into an extra encrypted data payload
+    pub fn encode_all(records: &[EncryptedDataRecord]) -> Result<Vec<u8>, EncryptedDataError> {
+        let mut bytes = Vec::with_capacity(SIZE_RECORDS_HEADER);
+        bytes.push(RECORDS_VERSION);
+        for record in records {
+            let value = record.value_bytes();
+            let length = u16::try_from(value.len()).map_err(|_| {
+                EncryptedDataError::IncorrectLength(format!("Record with tag {} is too long", record.tag()))
+            })?;
+            bytes.push(record.tag());
+            bytes.extend_from_slice(&length.to_le_bytes());
+            bytes.extend_from_slice(&value);
+        }
+        Ok(bytes)
+    }
+
+    /// Decodes an extra encrypted data payload that was encoded with `encode_all` into records
+    pub fn decode_all(bytes: &[u8]) -> Result<Vec<EncryptedDataRecord>, EncryptedDataError> {
+        let malformed = |reason: &str| EncryptedDataError::ByteArrayError(format!("Malformed records: {}", reason));
+        let (version, mut body) = bytes.split_first().ok_or_else(|| malformed("missing version"))?;
+        if *version != RECORDS_VERSION {
+            return Err(malformed(&format!("unsupported version {}", version)));
+        }
+        let mut records = Vec::new();
+        while !body.is_empty() {
+            if body.len() < SIZE_RECORD_TAG + SIZE_RECORD_LENGTH {
+                return Err(malformed("truncated record header"));
+            }
+            let tag = body[0];
+            let length = usize::from(u16::from_le_bytes([body[1], body[2]]));
+            let value = body
+                .get(SIZE_RECORD_TAG + SIZE_RECORD_LENGTH..SIZE_RECORD_TAG + SIZE_RECORD_LENGTH + length)
+                .ok_or_else(|| malformed("truncated record value"))?;
+            records.push(Self::from_tag_and_value(tag, value)?);
+            body = &body[SIZE_RECORD_TAG + SIZE_RECORD_LENGTH + length..];
+        }
+        Ok(records)
+    }
+
+    /// Returns the payment id held in the records, or `PaymentId::Empty` if there is none
+    pub fn payment_id(records: &[EncryptedDataRecord]) -> PaymentId {
+        records
+            .iter()
+            .find_map(|r| match r {
+                EncryptedDataRecord::PaymentId(v) => Some(v.clone()),
+                _ => None,
+            })
+            .unwrap_or(PaymentId::Empty)
+    }
+}
+
 /// AEAD associated data
 const ENCRYPTED_DATA_AAD: &[u8] = b"TARI_AAD_VALUE_AND_MASK_EXTEND_NONCE_VARIANT";
+/// AEAD associated data for encrypted data holding typed records instead of a bare payment id
+const ENCRYPTED_RECORDS_AAD: &[u8] = b"TARI_AAD_VALUE_AND_MASK_EXTEND_NONCE_VARIANT_RECORDS";
 
 impl EncryptedData {
     /// Encrypt the value and mask (with fixed length) using XChaCha20-Poly1305 with a secure random nonce
@@ -208,28 +325,94 @@ impl EncryptedData {
         value: MicroMinotari,
         mask: &PrivateKey,
         payment_id: PaymentId,
+    ) -> Result<EncryptedData, EncryptedDataError> {
+        Self::encrypt_data_with_rng(encryption_key, commitment, value, mask, payment_id, &mut OsRng)
+    }
+
+    /// Encrypt the value and mask as in `encrypt_data`, taking the nonce from the given RNG
+    pub fn encrypt_data_with_rng<R: TransactionRng + ?Sized>(
+        encryption_key: &PrivateKey,
+        commitment: &Commitment,
+        value: MicroMinotari,
+        mask: &PrivateKey,
+        payment_id: PaymentId,
+        rng: &mut R,
+    ) -> Result<EncryptedData, EncryptedDataError> {
+        Self::encrypt_payload(
+            encryption_key,
+            commitment,
+            value,
+            mask,
+            &payment_id.to_bytes(),
+            ENCRYPTED_DATA_AAD,
+            rng,
+        )
+    }
+
+    /// Encrypt the value and mask together with typed records, see `encrypt_data`. The output holding the data must
+    /// have a version that signals records, see `decrypt_output_records`.
+    pub fn encrypt_records(
+        encryption_key: &PrivateKey,
+        commitment: &Commitment,
+        value: MicroMinotari,
+        mask: &PrivateKey,
+        records: &[EncryptedDataRecord],
+    ) -> Result<EncryptedData, EncryptedDataError> {
+        Self::encrypt_records_with_rng(encryption_key, commitment, value, mask, records, &mut OsRng)
+    }
+
+    /// Encrypt the value and mask together with typed records as in `encrypt_records`, taking the nonce from the given
+    /// RNG
+    pub fn encrypt_records_with_rng<R: TransactionRng + ?Sized>(
+        encryption_key: &PrivateKey,
+        commitment: &Commitment,
+        value: MicroMinotari,
+        mask: &PrivateKey,
+        records: &[EncryptedDataRecord],
+        rng: &mut R,
+    ) -> Result<EncryptedData, EncryptedDataError> {
+        let payload = Zeroizing::new(EncryptedDataRecord::encode_all(records)?);
+        Self::encrypt_payload(
+            encryption_key,
+            commitment,
+            value,
+            mask,
+            &payload,
+            ENCRYPTED_RECORDS_AAD,
+            rng,
+        )
+    }
+
+    fn encrypt_payload<R: TransactionRng + ?Sized>(
+        encryption_key: &PrivateKey,
+        commitment: &Commitment,
+        value: MicroMinotari,
+        mask: &PrivateKey,
+        payload: &[u8],
+        aad: &[u8],
+        rng: &mut R,
     ) -> Result<EncryptedData, EncryptedDataError> {
         // Encode the value and mask
-        let mut bytes = Zeroizing::new(vec![0; SIZE_VALUE + SIZE_MASK + payment_id.get_size()]);
+        let mut bytes = Zeroizing::new(vec![0; SIZE_VALUE + SIZE_MASK + payload.len()]);
         bytes[..SIZE_VALUE].clone_from_slice(value.as_u64().to_le_bytes().as_ref());
         bytes[SIZE_VALUE..SIZE_VALUE + SIZE_MASK].clone_from_slice(mask.as_bytes());
-        bytes[SIZE_VALUE + SIZE_MASK..].clone_from_slice(&payment_id.to_bytes());
+        bytes[SIZE_VALUE + SIZE_MASK..].clone_from_slice(payload);
 
         // Produce a secure random nonce
-        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let nonce = XChaCha20Poly1305::generate_nonce(rng);
 
         // Set up the AEAD
         let aead_key = kdf_aead(encryption_key, commitment);
         let cipher = XChaCha20Poly1305::new(GenericArray::from_slice(aead_key.reveal()));
 
         // Encrypt in place
-        let tag = cipher.encrypt_in_place_detached(&nonce, ENCRYPTED_DATA_AAD, bytes.as_mut_slice())?;
+        let tag = cipher.encrypt_in_place_detached(&nonce, aad, bytes.as_mut_slice())?;
 
         // Put everything together: nonce, ciphertext, tag
-        let mut data = vec![0; STATIC_ENCRYPTED_DATA_SIZE_TOTAL + payment_id.get_size()];
+        let mut data = vec![0; STATIC_ENCRYPTED_DATA_SIZE_TOTAL + payload.len()];
         data[..SIZE_TAG].clone_from_slice(&tag);
         data[SIZE_TAG..SIZE_TAG + SIZE_NONCE].clone_from_slice(&nonce);
-        data[SIZE_TAG + SIZE_NONCE..SIZE_TAG + SIZE_NONCE + SIZE_VALUE + SIZE_MASK + payment_id.get_size()]
+        data[SIZE_TAG + SIZE_NONCE..SIZE_TAG + SIZE_NONCE + SIZE_VALUE + SIZE_MASK + payload.len()]
             .clone_from_slice(bytes.as_slice());
 
         Ok(Self {
@@ -246,6 +429,60 @@ impl EncryptedData {
         commitment: &Commitment,
         encrypted_data: &EncryptedData,
     ) -> Result<(MicroMinotari, PrivateKey, PaymentId), EncryptedDataError> {
+        let (value, mask, payload) =
+            Self::decrypt_payload(encryption_key, commitment, encrypted_data, ENCRYPTED_DATA_AAD)?;
+        Ok((value, mask, PaymentId::from_bytes(&payload)?))
+    }
+
+    /// Authenticate and decrypt the value and mask together with the typed records of data produced by
+    /// `encrypt_records`, see `decrypt_data`
+    pub fn decrypt_records(
+        encryption_key: &PrivateKey,
+        commitment: &Commitment,
+        encrypted_data: &EncryptedData,
+    ) -> Result<(MicroMinotari, PrivateKey, Vec<EncryptedDataRecord>), EncryptedDataError> {
+        let (value, mask, payload) =
+            Self::decrypt_payload(encryption_key, commitment, encrypted_data, ENCRYPTED_RECORDS_AAD)?;
+        Ok((value, mask, EncryptedDataRecord::decode_all(&payload)?))
+    }
+
+    /// Authenticate and decrypt the value, mask and payment id from the encrypted data of an output, which holds typed
+    /// records or a bare payment id as signalled by the output version
+    pub fn decrypt_output_data(
+        encryption_key: &PrivateKey,
+        commitment: &Commitment,
+        encrypted_data: &EncryptedData,
+        version: TransactionOutputVersion,
+    ) -> Result<(MicroMinotari, PrivateKey, PaymentId), EncryptedDataError> {
+        let (value, mask, records) = Self::decrypt_output_records(encryption_key, commitment, encrypted_data, version)?;
+        Ok((value, mask, EncryptedDataRecord::payment_id(&records)))
+    }
+
+    /// Authenticate and decrypt the encrypted data of an output, which holds typed records or a bare payment id as
+    /// signalled by the output version. A bare payment id yields a single payment id record.
+    pub fn decrypt_output_records(
+        encryption_key: &PrivateKey,
+        commitment: &Commitment,
+        encrypted_data: &EncryptedData,
+        version: TransactionOutputVersion,
+    ) -> Result<(MicroMinotari, PrivateKey, Vec<EncryptedDataRecord>), EncryptedDataError> {
+        if version.has_encrypted_data_records() {
+            return Self::decrypt_records(encryption_key, commitment, encrypted_data);
+        }
+        let (value, mask, payment_id) = Self::decrypt_data(encryption_key, commitment, encrypted_data)?;
+        let records = match payment_id {
+            PaymentId::Empty => Vec::new(),
+            payment_id => vec![EncryptedDataRecord::PaymentId(payment_id)],
+        };
+        Ok((value, mask, records))
+    }
+
+    fn decrypt_payload(
+        encryption_key: &PrivateKey,
+        commitment: &Commitment,
+        encrypted_data: &EncryptedData,
+        aad: &[u8],
+    ) -> Result<(MicroMinotari, PrivateKey, Zeroizing<Vec<u8>>), EncryptedDataError> {
         // Extract the nonce, ciphertext, and tag
         let tag = Tag::from_slice(&encrypted_data.as_bytes()[..SIZE_TAG]);
         let nonce = XNonce::from_slice(&encrypted_data.as_bytes()[SIZE_TAG..SIZE_TAG + SIZE_NONCE]);
@@ -264,7 +501,7 @@ impl EncryptedData {
         let cipher = XChaCha20Poly1305::new(GenericArray::from_slice(aead_key.reveal()));
 
         // Decrypt in place
-        cipher.decrypt_in_place_detached(nonce, ENCRYPTED_DATA_AAD, bytes.as_mut_slice(), tag)?;
+        cipher.decrypt_in_place_detached(nonce, aad, bytes.as_mut_slice(), tag)?;
 
         // Decode the value and mask
         let mut value_bytes = [0u8; SIZE_VALUE];
@@ -272,7 +509,7 @@ impl EncryptedData {
         Ok((
             u64::from_le_bytes(value_bytes).into(),
             PrivateKey::from_canonical_bytes(&bytes[SIZE_VALUE..SIZE_VALUE + SIZE_MASK])?,
-            PaymentId::from_bytes(&bytes[SIZE_VALUE + SIZE_MASK..])?,
+            Zeroizing::new(bytes[SIZE_VALUE + SIZE_MASK..].to_vec()),
         ))
     }
 
@@ -388,6 +625,7 @@ fn kdf_aead(encryption_key: &PrivateKey, commitment: &Commitment) -> EncryptedDa
 
 #[cfg(test)]
 mod test {
+    use rand::{rngs::StdRng, SeedableRng};
     use tari_common_types::types::CommitmentFactory;
     use tari_crypto::commitment::HomomorphicCommitmentFactory;
 
@@ -524,6 +762,133 @@ mod test {
         }
     }
 
+    #[test]
+    fn it_encrypts_and_decrypts_records() {
+        let address = TariAddress::from_base58("f3S7XTiyKQauZpDUjdR8NbcQ33MYJigiWiS44ccZCxwAAjk").unwrap();
+        let records = vec![
+            EncryptedDataRecord::PaymentId(PaymentId::U64(42)),
+            EncryptedDataRecord::Memo("Invoice #1024".to_string()),
+            EncryptedDataRecord::RefundAddress(address),
+            EncryptedDataRecord::Unknown {
+                tag: 200,
+                data: vec![1, 2, 3],
+            },
+        ];
+        let mask = PrivateKey::random(&mut OsRng);
+        let commitment = CommitmentFactory::default().commit(&mask, &PrivateKey::from(123u64));
+        let encryption_key = PrivateKey::random(&mut OsRng);
+        let amount = MicroMinotari::from(123);
+
+        let encrypted_data =
+            EncryptedData::encrypt_records(&encryption_key, &commitment, amount, &mask, &records).unwrap();
+        let (value, decrypted_mask, decrypted_records) =
+            EncryptedData::decrypt_records(&encryption_key, &commitment, &encrypted_data).unwrap();
+        assert_eq!(value, amount);
+        assert_eq!(decrypted_mask, mask);
+        assert_eq!(decrypted_records, records);
+
+        // The output version says which format to read, so records are never read as a bare payment id
+        let (_, _, decrypted_records) = EncryptedData::decrypt_output_records(
+            &encryption_key,
+            &commitment,
+            &encrypted_data,
+            TransactionOutputVersion::V1,
+        )
+        .unwrap();
+        assert_eq!(EncryptedDataRecord::payment_id(&decrypted_records), PaymentId::U64(42));
+        assert!(EncryptedData::decrypt_output_records(
+            &encryption_key,
+            &commitment,
+            &encrypted_data,
+            TransactionOutputVersion::V0
+        )
+        .is_err());
+
+        // A bare payment id is read back as a single record
+        let encrypted_data =
+            EncryptedData::encrypt_data(&encryption_key, &commitment, amount, &mask, PaymentId::U64(7)).unwrap();
+        let (_, _, decrypted_records) = EncryptedData::decrypt_output_records(
+            &encryption_key,
+            &commitment,
+            &encrypted_data,
+            TransactionOutputVersion::V0,
+        )
+        .unwrap();
+        assert_eq!(decrypted_records, vec![EncryptedDataRecord::PaymentId(PaymentId::U64(
+            7
+        ))]);
+        assert!(EncryptedData::decrypt_records(&encryption_key, &commitment, &encrypted_data).is_err());
+
+        // Records up to the hard size limit fit
+        let memo = EncryptedDataRecord::Memo(
+            "a".repeat(MAX_EXTRA_ENCRYPTED_DATA_SIZE - SIZE_RECORDS_HEADER - SIZE_RECORD_TAG - SIZE_RECORD_LENGTH),
+        );
+        assert!(EncryptedData::encrypt_records(&encryption_key, &commitment, amount, &mask, &[memo]).is_ok());
+        let memo = EncryptedDataRecord::Memo("a".repeat(MAX_EXTRA_ENCRYPTED_DATA_SIZE));
+        assert!(EncryptedData::encrypt_records(&encryption_key, &commitment, amount, &mask, &[memo]).is_err());
+    }
+
+    #[test]
+    fn it_rejects_malformed_record_payloads() {
+        assert!(EncryptedDataRecord::decode_all(&[]).is_err());
+        assert!(EncryptedDataRecord::decode_all(&[RECORDS_VERSION, 2, 3]).is_err());
+        let mut bytes = EncryptedDataRecord::encode_all(&[EncryptedDataRecord::Memo("memo".to_string())]).unwrap();
+        assert_eq!(EncryptedDataRecord::decode_all(&bytes).unwrap(), vec![
+            EncryptedDataRecord::Memo("memo".to_string())
+        ]);
+        // Truncated value
+        bytes.pop();
+        assert!(EncryptedDataRecord::decode_all(&bytes).is_err());
+        // Unsupported version
+        let mut bytes = EncryptedDataRecord::encode_all(&[]).unwrap();
+        bytes[0] = RECORDS_VERSION + 1;
+        assert!(EncryptedDataRecord::decode_all(&bytes).is_err());
+    }
+
+    #[test]
+    fn it_never_reads_an_open_payment_id_as_records() {
+        let mask = PrivateKey::random(&mut OsRng);
+        let commitment = CommitmentFactory::default().commit(&mask, &PrivateKey::from(123u64));
+        let encryption_key = PrivateKey::random(&mut OsRng);
+        let amount = MicroMinotari::from(123);
+
+        // Open data that happens to be a well-formed record payload stays open data
+        let records = [EncryptedDataRecord::Memo("an invoice memo".to_string())];
+        let payment_id = PaymentId::Open(EncryptedDataRecord::encode_all(&records).unwrap());
+        let encrypted_data =
+            EncryptedData::encrypt_data(&encryption_key, &commitment, amount, &mask, payment_id.clone()).unwrap();
+        let (_, _, decrypted_payment_id) =
+            EncryptedData::decrypt_data(&encryption_key, &commitment, &encrypted_data).unwrap();
+        assert_eq!(decrypted_payment_id, payment_id);
+        assert!(EncryptedData::decrypt_records(&encryption_key, &commitment, &encrypted_data).is_err());
+
+        // Records still fail to decrypt with the wrong key
+        let encrypted_data = EncryptedData::encrypt_records(&encryption_key, &commitment, amount, &mask, &[]).unwrap();
+        let wrong_key = PrivateKey::random(&mut OsRng);
+        assert!(EncryptedData::decrypt_records(&wrong_key, &commitment, &encrypted_data).is_err());
+        assert!(EncryptedData::decrypt_data(&wrong_key, &commitment, &encrypted_data).is_err());
+    }
+
+    #[test]
+    fn it_encrypts_records_with_the_given_rng() {
+        let mask = PrivateKey::random(&mut OsRng);
+        let commitment = CommitmentFactory::default().commit(&mask, &PrivateKey::from(123u64));
+        let encryption_key = PrivateKey::random(&mut OsRng);
+        let records = [EncryptedDataRecord::Memo("memo".to_string())];
+        let encrypt = || {
+            EncryptedData::encrypt_records_with_rng(
+                &encryption_key,
+                &commitment,
+                MicroMinotari::from(123),
+                &mask,
+                &records,
+                &mut StdRng::seed_from_u64(1),
+            )
+            .unwrap()
+        };
+        assert_eq!(encrypt(), encrypt());
+    }
+
     #[test]
     fn payment_id_display() {
         assert_eq!(PaymentId::Empty.to_string(), "None");
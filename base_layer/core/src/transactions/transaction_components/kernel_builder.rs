@@ -23,11 +23,11 @@
 // Portions of this file were originally copyrighted (c) 2018 The Grin Developers, issued under the Apache License,
 // Version 2.0, available at http://www.apache.org/licenses/LICENSE-2.0.
 
-use tari_common_types::types::{Commitment, Signature};
+use tari_common_types::types::{Commitment, PublicKey, Signature};
 
 use crate::transactions::{
     tari_amount::MicroMinotari,
-    transaction_components::{KernelFeatures, TransactionError, TransactionKernel},
+    transaction_components::{KernelFeatures, TransactionError, TransactionKernel, TransactionKernelVersion},
 };
 
 /// A version of Transaction kernel with optional fields. This struct is only used in constructing transaction kernels
@@ -38,6 +38,7 @@ pub struct KernelBuilder {
     excess: Option<Commitment>,
     excess_sig: Option<Signature>,
     burn_commitment: Option<Commitment>,
+    burn_claim_public_key: Option<PublicKey>,
 }
 
 /// Implementation of the transaction kernel
@@ -65,6 +66,12 @@ impl KernelBuilder {
         self
     }
 
+    /// Build a transaction kernel with the provided claim public key of a claimable burn
+    pub fn with_burn_claim_public_key(mut self, burn_claim_public_key: Option<PublicKey>) -> KernelBuilder {
+        self.burn_claim_public_key = burn_claim_public_key;
+        self
+    }
+
     /// Build a transaction kernel with the provided lock height
     pub fn with_lock_height(mut self, lock_height: u64) -> KernelBuilder {
         self.lock_height = lock_height;
@@ -89,13 +96,15 @@ impl KernelBuilder {
                 "Kernel does not contain an excess or signature".to_string(),
             ));
         }
-        Ok(TransactionKernel::new_current_version(
+        Ok(TransactionKernel::new(
+            TransactionKernelVersion::for_features(&self.features),
             self.features,
             self.fee,
             self.lock_height,
             self.excess.unwrap(),
             self.excess_sig.unwrap(),
             self.burn_commitment,
+            self.burn_claim_public_key,
         ))
     }
 }
@@ -109,6 +118,7 @@ impl Default for KernelBuilder {
             excess: None,
             excess_sig: None,
             burn_commitment: None,
+            burn_claim_public_key: None,
         }
     }
 }
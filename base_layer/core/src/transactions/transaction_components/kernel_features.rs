@@ -33,6 +33,8 @@ bitflags! {
         const COINBASE_KERNEL = 1u8;
         /// Burned output transaction
         const BURN_KERNEL = 2u8;
+        /// Burned output that can be claimed with the claim public key of the burned output
+        const BURN_CLAIMABLE = 4u8;
     }
 }
 
@@ -47,11 +49,21 @@ impl KernelFeatures {
         KernelFeatures::BURN_KERNEL
     }
 
+    /// Creates a flag for a burn that commits to a claim public key
+    pub fn create_claimable_burn() -> KernelFeatures {
+        KernelFeatures::BURN_KERNEL | KernelFeatures::BURN_CLAIMABLE
+    }
+
     /// Does this feature include the burned flag?
     pub fn is_burned(&self) -> bool {
         self.contains(KernelFeatures::BURN_KERNEL)
     }
 
+    /// Does this feature include the claimable burn flag?
+    pub fn is_claimable_burn(&self) -> bool {
+        self.contains(KernelFeatures::BURN_CLAIMABLE)
+    }
+
     /// Does this feature include the coinbase flag?
     pub fn is_coinbase(&self) -> bool {
         self.contains(KernelFeatures::COINBASE_KERNEL)
@@ -80,8 +92,12 @@ mod test {
             Some(super::KernelFeatures::COINBASE_KERNEL | super::KernelFeatures::BURN_KERNEL)
         );
         let x = super::KernelFeatures::from_bits(4);
+        assert_eq!(x, Some(super::KernelFeatures::BURN_CLAIMABLE));
+        let x = super::KernelFeatures::from_bits(6);
+        assert_eq!(x, Some(super::KernelFeatures::create_claimable_burn()));
+        let x = super::KernelFeatures::from_bits(8);
         assert_eq!(x, None);
-        for i in 5..=u8::MAX {
+        for i in 8..=u8::MAX {
             assert_eq!(None, super::KernelFeatures::from_bits(i));
         }
     }
@@ -23,8 +23,10 @@
 // Portions of this file were originally copyrighted (c) 2018 The Grin Developers, issued under the Apache License,
 // Version 2.0, available at http://www.apache.org/licenses/LICENSE-2.0.
 
+pub use batch_signatures::{batch_verify_kernel_signatures, batch_verify_metadata_signatures};
 use blake2::Blake2b;
 use chacha20poly1305::Key;
+pub use compact_output::CompactOutput;
 use digest::consts::U32;
 pub use encrypted_data::{EncryptedData, EncryptedDataError};
 pub use error::TransactionError;
@@ -35,10 +37,12 @@ pub use output_features::OutputFeatures;
 pub use output_features_version::OutputFeaturesVersion;
 pub use output_type::OutputType;
 pub use range_proof_type::RangeProofType;
+pub use script_templates::{DelayedRefundScript, EscrowParty, EscrowScript, HtlcScript, ScriptSpendPath};
 pub use side_chain::*;
 use tari_common_types::types::{ComAndPubSignature, Commitment, FixedHash, PublicKey};
 use tari_script::TariScript;
 use tari_utilities::{hidden_type, safe_array::SafeArray, Hidden};
+pub use threshold_script::{sign_threshold_script_message, threshold_multisig_input_data, threshold_multisig_script};
 pub use transaction::Transaction;
 pub use transaction_builder::TransactionBuilder;
 pub use transaction_input::{SpentOutput, TransactionInput};
@@ -48,10 +52,13 @@ pub use transaction_kernel_version::TransactionKernelVersion;
 pub use transaction_output::TransactionOutput;
 pub use transaction_output_version::TransactionOutputVersion;
 pub use unblinded_output::UnblindedOutput;
+pub use vault::{VaultOpening, VaultScript};
 pub use wallet_output::WalletOutput;
 pub use wallet_output_builder::WalletOutputBuilder;
 use zeroize::Zeroize;
 
+mod batch_signatures;
+mod compact_output;
 pub mod encrypted_data;
 mod error;
 mod kernel_builder;
@@ -62,7 +69,9 @@ pub use output_features::CoinBaseExtra;
 mod output_features_version;
 mod output_type;
 mod range_proof_type;
+mod script_templates;
 mod side_chain;
+mod threshold_script;
 
 mod transaction;
 mod transaction_builder;
@@ -73,6 +82,7 @@ mod transaction_kernel_version;
 pub mod transaction_output;
 mod transaction_output_version;
 mod unblinded_output;
+mod vault;
 mod wallet_output;
 mod wallet_output_builder;
 
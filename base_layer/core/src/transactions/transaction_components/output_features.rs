@@ -35,6 +35,8 @@ use super::OutputFeaturesVersion;
 use crate::transactions::transaction_components::{
     range_proof_type::RangeProofType,
     side_chain::SideChainFeature,
+    ApplicationData,
+    ApplicationDataPayload,
     BuildInfo,
     CodeTemplateRegistration,
     ConfidentialOutputData,
@@ -190,6 +192,18 @@ impl OutputFeatures {
         }
     }
 
+    /// Creates features for a standard output that anchors application data to the base layer
+    pub fn for_application_data(application_id: u32, payload: ApplicationDataPayload) -> OutputFeatures {
+        OutputFeatures {
+            output_type: OutputType::Standard,
+            sidechain_feature: Some(SideChainFeature::ApplicationData(ApplicationData::new(
+                application_id,
+                payload,
+            ))),
+            ..Default::default()
+        }
+    }
+
     pub fn validator_node_registration(&self) -> Option<&ValidatorNodeRegistration> {
         self.sidechain_feature
             .as_ref()
@@ -202,6 +216,18 @@ impl OutputFeatures {
             .and_then(|s| s.code_template_registration())
     }
 
+    /// The public key that can claim a burned output on a second layer, if any
+    pub fn burn_claim_public_key(&self) -> Option<&PublicKey> {
+        self.sidechain_feature
+            .as_ref()
+            .and_then(|s| s.confidential_output_data())
+            .map(|d| &d.claim_public_key)
+    }
+
+    pub fn application_data(&self) -> Option<&ApplicationData> {
+        self.sidechain_feature.as_ref().and_then(|s| s.application_data())
+    }
+
     pub fn is_coinbase(&self) -> bool {
         matches!(self.output_type, OutputType::Coinbase)
     }
@@ -0,0 +1,394 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Constructors for the common TariScript spending patterns.
+//!
+//! Each template builds its script, parses it back from an output's script and provides a [ScriptSpendPath] per
+//! spending path. A spend path carries the input data to supply with the input, the script public key left on the
+//! stack (the spender must sign the input with its secret key) and the lowest height from which the path is valid.
+//!
+//! - [HtlcScript]: `HashSha256 PushHash(hash) Equal IfThen PushPubKey(receiver_key) Else
+//!   CheckHeightVerify(refund_height) PushPubKey(refund_key) EndIf`
+//! - [EscrowScript]: `CheckMultiSigVerifyAggregatePubKey(2, 3, [buyer_key, seller_key, arbiter_key], message)`
+//! - [DelayedRefundScript]: `IfThen CheckHeightVerify(refund_height) PushPubKey(depositor_key) Else
+//!   PushPubKey(recipient_key) EndIf`
+
+use sha2::{Digest, Sha256};
+use tari_common_types::types::PublicKey;
+use tari_script::{
+    CheckSigSchnorrSignature,
+    ExecutionStack,
+    HashValue,
+    Message,
+    Opcode,
+    ScriptError,
+    StackItem,
+    TariScript,
+};
+use tari_utilities::ByteArray;
+
+/// Everything needed to spend an output through one of the paths of a script template
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptSpendPath {
+    /// The input data to provide with the spending input
+    pub input_data: ExecutionStack,
+    /// The public key the script leaves on the stack. The input's script signature must be made with its secret key.
+    pub script_key: PublicKey,
+    /// The lowest block height at which this path succeeds
+    pub min_height: u64,
+}
+
+/// A hash-time-locked contract. The receiver can claim the output by revealing the SHA-256 pre-image of `hash`, and
+/// the refund key can reclaim it from `refund_height`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HtlcScript {
+    /// The SHA-256 hash of the pre-image
+    pub hash: HashValue,
+    /// The key that can claim the output with the pre-image
+    pub receiver_key: PublicKey,
+    /// The key that can reclaim the output from the refund height
+    pub refund_key: PublicKey,
+    /// The absolute height from which the refund path is valid
+    pub refund_height: u64,
+}
+
+impl HtlcScript {
+    pub fn new(hash: HashValue, receiver_key: PublicKey, refund_key: PublicKey, refund_height: u64) -> Self {
+        Self {
+            hash,
+            receiver_key,
+            refund_key,
+            refund_height,
+        }
+    }
+
+    /// Creates an HTLC locked to the SHA-256 hash of the given pre-image
+    pub fn from_pre_image(
+        pre_image: &PublicKey,
+        receiver_key: PublicKey,
+        refund_key: PublicKey,
+        refund_height: u64,
+    ) -> Self {
+        Self::new(Self::hash_pre_image(pre_image), receiver_key, refund_key, refund_height)
+    }
+
+    /// The hash that the pre-image must match
+    pub fn hash_pre_image(pre_image: &PublicKey) -> HashValue {
+        Sha256::digest(pre_image.as_bytes()).into()
+    }
+
+    /// Builds the HTLC script
+    pub fn to_script(&self) -> Result<TariScript, ScriptError> {
+        TariScript::new(vec![
+            Opcode::HashSha256,
+            Opcode::PushHash(Box::new(self.hash)),
+            Opcode::Equal,
+            Opcode::IfThen,
+            Opcode::PushPubKey(Box::new(self.receiver_key.clone())),
+            Opcode::Else,
+            Opcode::CheckHeightVerify(self.refund_height),
+            Opcode::PushPubKey(Box::new(self.refund_key.clone())),
+            Opcode::EndIf,
+        ])
+    }
+
+    /// Parses the HTLC parameters from a script, returning None if the script is not an HTLC script
+    pub fn from_script(script: &TariScript) -> Option<Self> {
+        use Opcode::{CheckHeightVerify, Else, EndIf, Equal, HashSha256, IfThen, PushHash, PushPubKey};
+        match script.as_slice() {
+            [HashSha256, PushHash(hash), Equal, IfThen, PushPubKey(receiver), Else, CheckHeightVerify(height), PushPubKey(refund), EndIf] => {
+                Some(Self::new(
+                    **hash,
+                    receiver.as_ref().clone(),
+                    refund.as_ref().clone(),
+                    *height,
+                ))
+            },
+            _ => None,
+        }
+    }
+
+    /// The spend path for the receiver, who reveals the pre-image
+    pub fn claim_path(&self, pre_image: PublicKey) -> ScriptSpendPath {
+        ScriptSpendPath {
+            input_data: ExecutionStack::new(vec![StackItem::PublicKey(pre_image)]),
+            script_key: self.receiver_key.clone(),
+            min_height: 0,
+        }
+    }
+
+    /// The spend path for the refund key. The pushed value is hashed and fails the pre-image comparison.
+    pub fn refund_path(&self) -> ScriptSpendPath {
+        ScriptSpendPath {
+            input_data: ExecutionStack::new(vec![StackItem::Hash([0u8; 32])]),
+            script_key: self.refund_key.clone(),
+            min_height: self.refund_height,
+        }
+    }
+}
+
+/// The party that co-signs an arbitrated escrow spend with the arbiter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EscrowParty {
+    Buyer,
+    Seller,
+}
+
+/// A 2-of-2 escrow between buyer and seller, with an arbiter that can settle a dispute together with either party
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EscrowScript {
+    pub buyer_key: PublicKey,
+    pub seller_key: PublicKey,
+    pub arbiter_key: PublicKey,
+    /// The message every signer signs to authorise the spend
+    pub message: Message,
+}
+
+impl EscrowScript {
+    pub fn new(buyer_key: PublicKey, seller_key: PublicKey, arbiter_key: PublicKey, message: Message) -> Self {
+        Self {
+            buyer_key,
+            seller_key,
+            arbiter_key,
+            message,
+        }
+    }
+
+    /// Builds the escrow script
+    pub fn to_script(&self) -> Result<TariScript, ScriptError> {
+        TariScript::new(vec![Opcode::CheckMultiSigVerifyAggregatePubKey(
+            2,
+            3,
+            vec![
+                self.buyer_key.clone(),
+                self.seller_key.clone(),
+                self.arbiter_key.clone(),
+            ],
+            Box::new(self.message),
+        )])
+    }
+
+    /// Parses the escrow parameters from a script, returning None if the script is not an escrow script
+    pub fn from_script(script: &TariScript) -> Option<Self> {
+        match script.as_slice() {
+            [Opcode::CheckMultiSigVerifyAggregatePubKey(2, 3, keys, message)] => match keys.as_slice() {
+                [buyer, seller, arbiter] => Some(Self::new(buyer.clone(), seller.clone(), arbiter.clone(), **message)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// The spend path where buyer and seller agree. The script key is the sum of their public keys.
+    pub fn release_path(
+        &self,
+        buyer_signature: CheckSigSchnorrSignature,
+        seller_signature: CheckSigSchnorrSignature,
+    ) -> ScriptSpendPath {
+        ScriptSpendPath {
+            // Signatures must be in the same order as the public keys in the script
+            input_data: ExecutionStack::new(vec![
+                StackItem::Signature(buyer_signature),
+                StackItem::Signature(seller_signature),
+            ]),
+            script_key: &self.buyer_key + &self.seller_key,
+            min_height: 0,
+        }
+    }
+
+    /// The spend path where the arbiter settles a dispute with one of the parties. The script key is the sum of the
+    /// party's and the arbiter's public keys.
+    pub fn arbitrated_path(
+        &self,
+        party: EscrowParty,
+        party_signature: CheckSigSchnorrSignature,
+        arbiter_signature: CheckSigSchnorrSignature,
+    ) -> ScriptSpendPath {
+        let party_key = match party {
+            EscrowParty::Buyer => &self.buyer_key,
+            EscrowParty::Seller => &self.seller_key,
+        };
+        ScriptSpendPath {
+            input_data: ExecutionStack::new(vec![
+                StackItem::Signature(party_signature),
+                StackItem::Signature(arbiter_signature),
+            ]),
+            script_key: party_key + &self.arbiter_key,
+            min_height: 0,
+        }
+    }
+}
+
+/// A deposit the recipient can spend at any time, and that the depositor can reclaim from `refund_height`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DelayedRefundScript {
+    pub recipient_key: PublicKey,
+    pub depositor_key: PublicKey,
+    /// The absolute height from which the depositor may reclaim the deposit
+    pub refund_height: u64,
+}
+
+impl DelayedRefundScript {
+    pub fn new(recipient_key: PublicKey, depositor_key: PublicKey, refund_height: u64) -> Self {
+        Self {
+            recipient_key,
+            depositor_key,
+            refund_height,
+        }
+    }
+
+    /// Builds the deposit script
+    pub fn to_script(&self) -> Result<TariScript, ScriptError> {
+        TariScript::new(vec![
+            Opcode::IfThen,
+            Opcode::CheckHeightVerify(self.refund_height),
+            Opcode::PushPubKey(Box::new(self.depositor_key.clone())),
+            Opcode::Else,
+            Opcode::PushPubKey(Box::new(self.recipient_key.clone())),
+            Opcode::EndIf,
+        ])
+    }
+
+    /// Parses the deposit parameters from a script, returning None if the script is not a delayed refund script
+    pub fn from_script(script: &TariScript) -> Option<Self> {
+        use Opcode::{CheckHeightVerify, Else, EndIf, IfThen, PushPubKey};
+        match script.as_slice() {
+            [IfThen, CheckHeightVerify(height), PushPubKey(depositor), Else, PushPubKey(recipient), EndIf] => Some(
+                Self::new(recipient.as_ref().clone(), depositor.as_ref().clone(), *height),
+            ),
+            _ => None,
+        }
+    }
+
+    /// The spend path for the recipient
+    pub fn recipient_path(&self) -> ScriptSpendPath {
+        ScriptSpendPath {
+            input_data: ExecutionStack::new(vec![StackItem::Number(0)]),
+            script_key: self.recipient_key.clone(),
+            min_height: 0,
+        }
+    }
+
+    /// The spend path for the depositor once the refund height is reached
+    pub fn refund_path(&self) -> ScriptSpendPath {
+        ScriptSpendPath {
+            input_data: ExecutionStack::new(vec![StackItem::Number(1)]),
+            script_key: self.depositor_key.clone(),
+            min_height: self.refund_height,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::OsRng;
+    use tari_common_types::types::{Commitment, PrivateKey};
+    use tari_crypto::keys::{PublicKey as PublicKeyTrait, SecretKey};
+    use tari_script::ScriptContext;
+
+    use super::*;
+
+    fn context_at(height: u64) -> ScriptContext {
+        ScriptContext::new(height, &[0u8; 32], &Commitment::default())
+    }
+
+    fn run(script: &TariScript, path: &ScriptSpendPath, height: u64) -> Result<StackItem, ScriptError> {
+        script.execute_with_context(&path.input_data, &context_at(height))
+    }
+
+    #[test]
+    fn htlc_paths() {
+        let pre_image = PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng));
+        let (_, receiver_key) = PublicKey::random_keypair(&mut OsRng);
+        let (_, refund_key) = PublicKey::random_keypair(&mut OsRng);
+        let htlc = HtlcScript::from_pre_image(&pre_image, receiver_key.clone(), refund_key.clone(), 500);
+        let script = htlc.to_script().unwrap();
+        assert_eq!(HtlcScript::from_script(&script), Some(htlc.clone()));
+
+        let claim = htlc.claim_path(pre_image);
+        assert_eq!(run(&script, &claim, 0).unwrap(), StackItem::PublicKey(receiver_key));
+
+        let wrong_pre_image = PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng));
+        let bad_claim = htlc.claim_path(wrong_pre_image);
+        assert_eq!(run(&script, &bad_claim, 0).unwrap_err(), ScriptError::VerifyFailed);
+
+        let refund = htlc.refund_path();
+        assert_eq!(refund.min_height, 500);
+        assert_eq!(run(&script, &refund, 499).unwrap_err(), ScriptError::VerifyFailed);
+        assert_eq!(run(&script, &refund, 500).unwrap(), StackItem::PublicKey(refund_key));
+    }
+
+    #[test]
+    fn escrow_paths() {
+        let (k_buyer, buyer_key) = PublicKey::random_keypair(&mut OsRng);
+        let (k_seller, seller_key) = PublicKey::random_keypair(&mut OsRng);
+        let (k_arbiter, arbiter_key) = PublicKey::random_keypair(&mut OsRng);
+        let message = [7u8; 32];
+        let escrow = EscrowScript::new(buyer_key, seller_key, arbiter_key, message);
+        let script = escrow.to_script().unwrap();
+        assert_eq!(EscrowScript::from_script(&script), Some(escrow.clone()));
+
+        let sign = |k: &PrivateKey| CheckSigSchnorrSignature::sign(k, message, &mut OsRng).unwrap();
+
+        let release = escrow.release_path(sign(&k_buyer), sign(&k_seller));
+        assert_eq!(
+            run(&script, &release, 0).unwrap(),
+            StackItem::PublicKey(release.script_key.clone())
+        );
+        assert_eq!(release.script_key, PublicKey::from_secret_key(&(&k_buyer + &k_seller)));
+
+        for (party, k_party) in [(EscrowParty::Buyer, &k_buyer), (EscrowParty::Seller, &k_seller)] {
+            let arbitrated = escrow.arbitrated_path(party, sign(k_party), sign(&k_arbiter));
+            assert_eq!(
+                run(&script, &arbitrated, 0).unwrap(),
+                StackItem::PublicKey(PublicKey::from_secret_key(&(k_party + &k_arbiter)))
+            );
+        }
+
+        let (k_eve, _) = PublicKey::random_keypair(&mut OsRng);
+        let forged = escrow.release_path(sign(&k_eve), sign(&k_seller));
+        assert_eq!(run(&script, &forged, 0).unwrap_err(), ScriptError::VerifyFailed);
+    }
+
+    #[test]
+    fn delayed_refund_paths() {
+        let (_, recipient_key) = PublicKey::random_keypair(&mut OsRng);
+        let (_, depositor_key) = PublicKey::random_keypair(&mut OsRng);
+        let deposit = DelayedRefundScript::new(recipient_key.clone(), depositor_key.clone(), 1000);
+        let script = deposit.to_script().unwrap();
+        assert_eq!(DelayedRefundScript::from_script(&script), Some(deposit.clone()));
+        assert_eq!(DelayedRefundScript::from_script(&TariScript::default()), None);
+
+        let recipient = deposit.recipient_path();
+        assert_eq!(
+            run(&script, &recipient, 0).unwrap(),
+            StackItem::PublicKey(recipient_key)
+        );
+
+        let refund = deposit.refund_path();
+        assert_eq!(run(&script, &refund, 999).unwrap_err(), ScriptError::VerifyFailed);
+        assert_eq!(
+            run(&script, &refund, 1000).unwrap(),
+            StackItem::PublicKey(depositor_key)
+        );
+    }
+}
@@ -0,0 +1,31 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use tari_max_size::MaxSizeBytes;
+
+/// The largest application data payload that can be encoded. The consensus constants set the size that is permitted.
+pub const MAX_APPLICATION_DATA_PAYLOAD_SIZE: usize = 1024;
+
+pub type ApplicationDataPayload = MaxSizeBytes<MAX_APPLICATION_DATA_PAYLOAD_SIZE>;
+
+/// Arbitrary data that a second layer or external protocol anchors to the base layer, such as a commitment to its
+/// state. The payload counts towards the features and scripts size of the output, so it is paid for in fees.
+#[derive(Debug, Clone, Hash, PartialEq, Deserialize, Serialize, Eq, BorshSerialize, BorshDeserialize)]
+pub struct ApplicationData {
+    /// Identifies the protocol that the payload belongs to. Only identifiers in the range permitted by the consensus
+    /// constants are valid.
+    pub application_id: u32,
+    #[serde(with = "tari_utilities::serde::hex")]
+    pub payload: ApplicationDataPayload,
+}
+
+impl ApplicationData {
+    pub fn new(application_id: u32, payload: ApplicationDataPayload) -> Self {
+        Self {
+            application_id,
+            payload,
+        }
+    }
+}
@@ -23,11 +23,13 @@
 mod sidechain_feature;
 pub use sidechain_feature::SideChainFeature;
 
+mod application_data;
 mod confidential_output;
 mod template_registration;
 mod validator_node_registration;
 mod validator_node_signature;
 
+pub use application_data::{ApplicationData, ApplicationDataPayload, MAX_APPLICATION_DATA_PAYLOAD_SIZE};
 use blake2::Blake2b;
 pub use confidential_output::ConfidentialOutputData;
 use digest::consts::U32;
@@ -24,7 +24,7 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
 
 use crate::transactions::transaction_components::{
-    side_chain::confidential_output::ConfidentialOutputData,
+    side_chain::{application_data::ApplicationData, confidential_output::ConfidentialOutputData},
     CodeTemplateRegistration,
     ValidatorNodeRegistration,
 };
@@ -34,6 +34,7 @@ pub enum SideChainFeature {
     ValidatorNodeRegistration(ValidatorNodeRegistration),
     CodeTemplateRegistration(CodeTemplateRegistration),
     ConfidentialOutput(ConfidentialOutputData),
+    ApplicationData(ApplicationData),
 }
 
 impl SideChainFeature {
@@ -57,4 +58,11 @@ impl SideChainFeature {
             _ => None,
         }
     }
+
+    pub fn application_data(&self) -> Option<&ApplicationData> {
+        match self {
+            Self::ApplicationData(v) => Some(v),
+            _ => None,
+        }
+    }
 }
@@ -20,11 +20,23 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use blake2::Blake2b;
 use borsh::{BorshDeserialize, BorshSerialize};
+use digest::consts::U64;
+use rand::rngs::OsRng;
 use serde::{Deserialize, Serialize};
-use tari_common_types::types::{PublicKey, Signature};
+use tari_common_types::types::{PrivateKey, PublicKey, Signature};
+use tari_crypto::{hash_domain, keys::PublicKey as PublicKeyT};
 use tari_max_size::{MaxSizeBytes, MaxSizeString};
 
+use crate::consensus::DomainSeparatedConsensusHasher;
+
+hash_domain!(
+    TemplateRegistrationHashDomain,
+    "com.tari.base_layer.core.transactions.side_chain.template_registration",
+    0
+);
+
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Deserialize, Serialize, BorshSerialize, BorshDeserialize)]
 pub struct CodeTemplateRegistration {
     pub author_public_key: PublicKey,
@@ -37,6 +49,40 @@ pub struct CodeTemplateRegistration {
     pub binary_url: MaxSizeString<255>,
 }
 
+impl CodeTemplateRegistration {
+    /// Sets the author public key to that of `author_private_key` and signs the registration with it
+    pub fn sign(&mut self, author_private_key: &PrivateKey) {
+        let (secret_nonce, public_nonce) = PublicKey::random_keypair(&mut OsRng);
+        self.author_public_key = PublicKey::from_secret_key(author_private_key);
+        let challenge = self.construct_challenge(&public_nonce);
+        self.author_signature = Signature::sign_raw_uniform(author_private_key, secret_nonce, &challenge)
+            .expect("Sign cannot fail with 64-byte challenge and a RistrettoPublicKey");
+    }
+
+    /// The challenge commits to every field of the registration, so that the author vouches for the binary, where it
+    /// is published and the source it was built from
+    fn construct_challenge(&self, public_nonce: &PublicKey) -> [u8; 64] {
+        DomainSeparatedConsensusHasher::<TemplateRegistrationHashDomain, Blake2b<U64>>::new("author_signature")
+            .chain(&self.author_public_key)
+            .chain(public_nonce)
+            .chain(&self.template_name)
+            .chain(&self.template_version)
+            .chain(&self.template_type)
+            .chain(&self.build_info)
+            .chain(&self.binary_sha)
+            .chain(&self.binary_url)
+            .finalize()
+            .into()
+    }
+
+    /// Returns true if the registration is signed by the author public key
+    pub fn is_valid_signature(&self) -> bool {
+        let challenge = self.construct_challenge(self.author_signature.get_public_nonce());
+        self.author_signature
+            .verify_raw_uniform(&self.author_public_key, &challenge)
+    }
+}
+
 // -------------------------------- TemplateType -------------------------------- //
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Deserialize, Serialize, BorshSerialize, BorshDeserialize)]
@@ -56,3 +102,56 @@ pub struct BuildInfo {
     pub repo_url: MaxSizeString<255>,
     pub commit_hash: MaxSizeBytes<32>,
 }
+
+#[cfg(test)]
+mod test {
+    use tari_crypto::keys::SecretKey;
+
+    use super::*;
+
+    fn create_instance() -> CodeTemplateRegistration {
+        let mut reg = CodeTemplateRegistration {
+            author_public_key: Default::default(),
+            author_signature: Default::default(),
+            template_name: "test".try_into().unwrap(),
+            template_version: 1,
+            template_type: TemplateType::Wasm { abi_version: 1 },
+            build_info: BuildInfo {
+                repo_url: "https://github.com/tari-project/tari.git".try_into().unwrap(),
+                commit_hash: vec![1u8; 32].try_into().unwrap(),
+            },
+            binary_sha: vec![2u8; 32].try_into().unwrap(),
+            binary_url: "https://example.com/test.wasm".try_into().unwrap(),
+        };
+        reg.sign(&PrivateKey::random(&mut OsRng));
+        reg
+    }
+
+    mod is_valid_signature {
+        use super::*;
+
+        #[test]
+        fn it_returns_true_for_valid_signature() {
+            let reg = create_instance();
+            assert!(reg.is_valid_signature());
+        }
+
+        #[test]
+        fn it_returns_false_if_the_registration_was_changed() {
+            let mut reg = create_instance();
+            reg.binary_url = "https://example.com/other.wasm".try_into().unwrap();
+            assert!(!reg.is_valid_signature());
+
+            let mut reg = create_instance();
+            reg.template_version = 2;
+            assert!(!reg.is_valid_signature());
+        }
+
+        #[test]
+        fn it_returns_false_for_another_author() {
+            let mut reg = create_instance();
+            reg.author_public_key = PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng));
+            assert!(!reg.is_valid_signature());
+        }
+    }
+}
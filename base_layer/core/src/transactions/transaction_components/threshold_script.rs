@@ -0,0 +1,121 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Helpers for building outputs that are spendable by any `m` of `n` signers using the `CheckMultiSigThreshold`
+//! opcode. Unlike aggregated multisig outputs, the signers do not need to take part in an interactive key aggregation
+//! round; each signer independently signs the script message of the output being spent.
+
+use rand::rngs::OsRng;
+use tari_common_types::types::{Commitment, PrivateKey, PublicKey};
+use tari_script::{
+    script_message,
+    CheckSigSchnorrSignature,
+    ExecutionStack,
+    Opcode,
+    ScriptError,
+    StackItem,
+    TariScript,
+    MAX_MULTISIG_LIMIT,
+};
+
+use crate::transactions::transaction_components::TransactionError;
+
+/// Builds a script that can only be spent with valid signatures from at least `m` of the given `public_keys`. When the
+/// threshold is met, `script_key` is left on the stack as the script public key, so the party assembling the spending
+/// transaction must own the corresponding secret to produce the script signature.
+pub fn threshold_multisig_script(
+    m: u8,
+    public_keys: &[PublicKey],
+    script_key: &PublicKey,
+) -> Result<TariScript, TransactionError> {
+    let n = u8::try_from(public_keys.len()).map_err(|_| ScriptError::ValueExceedsBounds)?;
+    if m == 0 || m > n || n > MAX_MULTISIG_LIMIT {
+        return Err(ScriptError::ValueExceedsBounds.into());
+    }
+    let script = TariScript::new(vec![
+        Opcode::CheckMultiSigThreshold(m, n, public_keys.to_vec()),
+        Opcode::IfThen,
+        Opcode::PushPubKey(Box::new(script_key.clone())),
+        Opcode::Else,
+        Opcode::Return,
+        Opcode::EndIf,
+    ])?;
+    Ok(script)
+}
+
+/// Signs the script message of the output with the given commitment, producing one of the signatures required to
+/// spend a threshold multisig output.
+pub fn sign_threshold_script_message(
+    secret_key: &PrivateKey,
+    commitment: &Commitment,
+) -> Result<CheckSigSchnorrSignature, TransactionError> {
+    let signature = CheckSigSchnorrSignature::sign(secret_key, script_message(commitment), &mut OsRng)?;
+    Ok(signature)
+}
+
+/// Builds the input data to spend a threshold multisig output. The signatures must be given in the same order as
+/// their public keys appear in the script.
+pub fn threshold_multisig_input_data(signatures: &[CheckSigSchnorrSignature]) -> ExecutionStack {
+    ExecutionStack::new(signatures.iter().cloned().map(StackItem::Signature).collect())
+}
+
+#[cfg(test)]
+mod test {
+    use tari_common_types::types::CommitmentFactory;
+    use tari_crypto::{commitment::HomomorphicCommitmentFactory, keys::PublicKey as PublicKeyTrait};
+    use tari_script::ScriptContext;
+
+    use super::*;
+
+    #[test]
+    fn it_spends_with_threshold_signatures() {
+        let keys = (0..3)
+            .map(|_| PublicKey::random_keypair(&mut OsRng))
+            .collect::<Vec<_>>();
+        let public_keys = keys.iter().map(|(_, p)| p.clone()).collect::<Vec<_>>();
+        let (_, script_key) = PublicKey::random_keypair(&mut OsRng);
+        let script = threshold_multisig_script(2, &public_keys, &script_key).unwrap();
+
+        let commitment = CommitmentFactory::default().commit_value(&PrivateKey::default(), 100);
+        let context = ScriptContext::new(1, &Default::default(), &commitment);
+        let signatures = [&keys[0].0, &keys[2].0]
+            .into_iter()
+            .map(|k| sign_threshold_script_message(k, &commitment).unwrap())
+            .collect::<Vec<_>>();
+
+        let input_data = threshold_multisig_input_data(&signatures);
+        let result = script.execute_with_context(&input_data, &context).unwrap();
+        assert_eq!(result, StackItem::PublicKey(script_key));
+
+        let input_data = threshold_multisig_input_data(&signatures[..1]);
+        assert!(script.execute_with_context(&input_data, &context).is_err());
+    }
+
+    #[test]
+    fn it_rejects_invalid_thresholds() {
+        let (_, script_key) = PublicKey::random_keypair(&mut OsRng);
+        let public_keys = vec![script_key.clone(); 2];
+        assert!(threshold_multisig_script(0, &public_keys, &script_key).is_err());
+        assert!(threshold_multisig_script(3, &public_keys, &script_key).is_err());
+        assert!(threshold_multisig_script(1, &[], &script_key).is_err());
+    }
+}
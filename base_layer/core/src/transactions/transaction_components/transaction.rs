@@ -80,6 +80,11 @@ impl Transaction {
         self.body.calculate_weight(transaction_weight)
     }
 
+    /// Returns the weight that the fee of a transaction is charged for, which is discounted for consolidations
+    pub fn calculate_fee_weight(&self, transaction_weight: &TransactionWeight) -> Result<u64, TransactionError> {
+        self.body.calculate_fee_weight(transaction_weight)
+    }
+
     /// Returns the maximum maturity of the input UTXOs
     pub fn max_input_maturity(&self) -> Result<u64, TransactionError> {
         self.body.max_input_maturity()
@@ -341,12 +341,22 @@ impl TransactionInput {
     /// This will run the script contained in the TransactionInput, returning the resulting
     /// public key if execution succeeds, or otherwise a script error. An error is returned if this is a compact input.
     pub fn run_script(&self, context: Option<ScriptContext>) -> Result<PublicKey, TransactionError> {
+        self.run_script_with_budget(context, u64::MAX)
+    }
+
+    /// Runs the script as [run_script](TransactionInput::run_script) does, failing if the cost of the executed opcodes
+    /// exceeds `budget`.
+    pub fn run_script_with_budget(
+        &self,
+        context: Option<ScriptContext>,
+        budget: u64,
+    ) -> Result<PublicKey, TransactionError> {
         let context = context.unwrap_or_default();
 
         match self.spent_output {
             SpentOutput::OutputHash(_) => Err(TransactionError::CompactInputMissingData("script".to_string())),
             SpentOutput::OutputData { ref script, .. } => {
-                match script.execute_with_context(&self.input_data, &context)? {
+                match script.execute_with_budget(&self.input_data, &context, budget)? {
                     StackItem::PublicKey(pubkey) => Ok(pubkey),
                     item => Err(TransactionError::ScriptExecutionError(format!(
                         "The script executed successfully but it did not leave a public key on the stack. Remaining \
@@ -25,13 +25,21 @@
 
 use std::{
     cmp::Ordering,
-    fmt::{Display, Formatter},
+    fmt::{self, Display, Formatter},
+    io,
 };
 
 use blake2::Blake2b;
 use borsh::{BorshDeserialize, BorshSerialize};
 use digest::consts::{U32, U64};
-use serde::{Deserialize, Serialize};
+use serde::{
+    de::{self, value::MapAccessDeserializer, MapAccess, SeqAccess, Visitor},
+    ser::SerializeStruct,
+    Deserialize,
+    Deserializer,
+    Serialize,
+    Serializer,
+};
 use tari_common_types::types::{Commitment, FixedHash, PublicKey, Signature};
 use tari_hashing::TransactionHashDomain;
 use tari_utilities::{hex::Hex, message_format::MessageFormat};
@@ -51,7 +59,10 @@ use crate::{
 /// [Mimblewimble TLU post](https://tlu.tarilabs.com/protocols/mimblewimble-1/sources/PITCHME.link.html?highlight=mimblewimble#mimblewimble).
 /// The kernel also tracks other transaction metadata, such as the lock height for the transaction (i.e. the earliest
 /// this transaction can be mined) and the transaction fee, in cleartext.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize, Default)]
+///
+/// Version 0 kernels are serialized exactly as they were before claimable burns existed, so that their hashes and
+/// stored encodings do not change. Only later versions carry the claim public key.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct TransactionKernel {
     pub version: TransactionKernelVersion,
     /// Options for a kernel's structure or use
@@ -69,6 +80,8 @@ pub struct TransactionKernel {
     pub excess_sig: Signature,
     /// This is an optional field that must be set if the transaction contains a burned output.
     pub burn_commitment: Option<Commitment>,
+    /// The claim public key of a claimable burn, which the excess signature commits to. Only V1 kernels have one.
+    pub burn_claim_public_key: Option<PublicKey>,
 }
 
 impl TransactionKernel {
@@ -80,6 +93,7 @@ impl TransactionKernel {
         excess: Commitment,
         excess_sig: Signature,
         burn_commitment: Option<Commitment>,
+        burn_claim_public_key: Option<PublicKey>,
     ) -> TransactionKernel {
         TransactionKernel {
             version,
@@ -89,6 +103,7 @@ impl TransactionKernel {
             excess,
             excess_sig,
             burn_commitment,
+            burn_claim_public_key,
         }
     }
 
@@ -116,6 +131,7 @@ impl TransactionKernel {
             excess,
             excess_sig,
             burn_commitment,
+            None,
         )
     }
 
@@ -130,16 +146,7 @@ impl TransactionKernel {
 
     pub fn verify_signature(&self) -> Result<(), TransactionError> {
         let excess = self.excess.as_public_key();
-        let r = self.excess_sig.get_public_nonce();
-        let c = TransactionKernel::build_kernel_signature_challenge(
-            &self.version,
-            r,
-            excess,
-            self.fee,
-            self.lock_height,
-            &self.features,
-            &self.burn_commitment,
-        );
+        let c = self.signature_challenge();
         if self.excess_sig.verify_raw_uniform(excess, &c) {
             Ok(())
         } else {
@@ -149,6 +156,20 @@ impl TransactionKernel {
         }
     }
 
+    /// The challenge that the excess signature of this kernel signs
+    pub fn signature_challenge(&self) -> [u8; 64] {
+        TransactionKernel::build_kernel_signature_challenge(
+            &self.version,
+            self.excess_sig.get_public_nonce(),
+            self.excess.as_public_key(),
+            self.fee,
+            self.lock_height,
+            &self.features,
+            &self.burn_commitment,
+            &self.burn_claim_public_key,
+        )
+    }
+
     /// This gets the burn commitment if it exists
     pub fn get_burn_commitment(&self) -> Result<&Commitment, TransactionError> {
         match self.burn_commitment {
@@ -173,6 +194,7 @@ impl TransactionKernel {
             tx_meta.lock_height,
             &tx_meta.kernel_features,
             &tx_meta.burn_commitment,
+            &tx_meta.burn_claim_public_key,
         )
     }
 
@@ -183,6 +205,8 @@ impl TransactionKernel {
     ///  Lock height
     ///  Features of the kernel
     ///  Burn commitment if present
+    ///  Burn claim public key (V1 onwards)
+    #[allow(clippy::too_many_arguments)]
     pub fn build_kernel_signature_challenge(
         version: &TransactionKernelVersion,
         sum_public_nonces: &PublicKey,
@@ -191,11 +215,18 @@ impl TransactionKernel {
         lock_height: u64,
         features: &KernelFeatures,
         burn_commitment: &Option<Commitment>,
+        burn_claim_public_key: &Option<PublicKey>,
     ) -> [u8; 64] {
         // We build the message separately to help with hardware wallet support. This reduces the amount of data that
         // needs to be transferred in order to sign the signature.
-        let message =
-            TransactionKernel::build_kernel_signature_message(version, fee, lock_height, features, burn_commitment);
+        let message = TransactionKernel::build_kernel_signature_message(
+            version,
+            fee,
+            lock_height,
+            features,
+            burn_commitment,
+            burn_claim_public_key,
+        );
         TransactionKernel::finalize_kernel_signature_challenge(version, sum_public_nonces, total_excess, &message)
     }
 
@@ -211,7 +242,7 @@ impl TransactionKernel {
             .chain(total_excess)
             .chain(message);
         match version {
-            TransactionKernelVersion::V0 => common.finalize().into(),
+            TransactionKernelVersion::V0 | TransactionKernelVersion::V1 => common.finalize().into(),
         }
     }
 
@@ -223,6 +254,7 @@ impl TransactionKernel {
         lock_height: u64,
         features: &KernelFeatures,
         burn_commitment: &Option<Commitment>,
+        burn_claim_public_key: &Option<PublicKey>,
     ) -> [u8; 32] {
         let common = DomainSeparatedConsensusHasher::<TransactionHashDomain, Blake2b<U32>>::new("kernel_message")
             .chain(version)
@@ -232,10 +264,162 @@ impl TransactionKernel {
             .chain(burn_commitment);
         match version {
             TransactionKernelVersion::V0 => common.finalize().into(),
+            TransactionKernelVersion::V1 => common.chain(burn_claim_public_key).finalize().into(),
+        }
+    }
+}
+
+impl BorshSerialize for TransactionKernel {
+    fn serialize<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        BorshSerialize::serialize(&self.version, writer)?;
+        BorshSerialize::serialize(&self.features, writer)?;
+        BorshSerialize::serialize(&self.fee, writer)?;
+        BorshSerialize::serialize(&self.lock_height, writer)?;
+        BorshSerialize::serialize(&self.excess, writer)?;
+        BorshSerialize::serialize(&self.excess_sig, writer)?;
+        BorshSerialize::serialize(&self.burn_commitment, writer)?;
+        match self.version {
+            TransactionKernelVersion::V0 => Ok(()),
+            TransactionKernelVersion::V1 => BorshSerialize::serialize(&self.burn_claim_public_key, writer),
         }
     }
 }
 
+impl BorshDeserialize for TransactionKernel {
+    fn deserialize_reader<R>(reader: &mut R) -> Result<Self, io::Error>
+    where R: io::Read {
+        let version = TransactionKernelVersion::deserialize_reader(reader)?;
+        let features = BorshDeserialize::deserialize_reader(reader)?;
+        let fee = BorshDeserialize::deserialize_reader(reader)?;
+        let lock_height = BorshDeserialize::deserialize_reader(reader)?;
+        let excess = BorshDeserialize::deserialize_reader(reader)?;
+        let excess_sig = BorshDeserialize::deserialize_reader(reader)?;
+        let burn_commitment = BorshDeserialize::deserialize_reader(reader)?;
+        let burn_claim_public_key = match version {
+            TransactionKernelVersion::V0 => None,
+            TransactionKernelVersion::V1 => BorshDeserialize::deserialize_reader(reader)?,
+        };
+        Ok(TransactionKernel::new(
+            version,
+            features,
+            fee,
+            lock_height,
+            excess,
+            excess_sig,
+            burn_commitment,
+            burn_claim_public_key,
+        ))
+    }
+}
+
+const KERNEL_FIELDS: &[&str] = &[
+    "version",
+    "features",
+    "fee",
+    "lock_height",
+    "excess",
+    "excess_sig",
+    "burn_commitment",
+    "burn_claim_public_key",
+];
+
+impl Serialize for TransactionKernel {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where S: Serializer {
+        let num_fields = match self.version {
+            TransactionKernelVersion::V0 => KERNEL_FIELDS.len() - 1,
+            TransactionKernelVersion::V1 => KERNEL_FIELDS.len(),
+        };
+        let mut state = serializer.serialize_struct("TransactionKernel", num_fields)?;
+        state.serialize_field("version", &self.version)?;
+        state.serialize_field("features", &self.features)?;
+        state.serialize_field("fee", &self.fee)?;
+        state.serialize_field("lock_height", &self.lock_height)?;
+        state.serialize_field("excess", &self.excess)?;
+        state.serialize_field("excess_sig", &self.excess_sig)?;
+        state.serialize_field("burn_commitment", &self.burn_commitment)?;
+        if self.version != TransactionKernelVersion::V0 {
+            state.serialize_field("burn_claim_public_key", &self.burn_claim_public_key)?;
+        }
+        state.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for TransactionKernel {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where D: Deserializer<'de> {
+        deserializer.deserialize_struct("TransactionKernel", KERNEL_FIELDS, TransactionKernelVisitor)
+    }
+}
+
+/// The fields of a kernel in self-describing formats, where the claim public key may simply be missing
+#[derive(Deserialize)]
+struct TransactionKernelFields {
+    version: TransactionKernelVersion,
+    features: KernelFeatures,
+    fee: MicroMinotari,
+    lock_height: u64,
+    excess: Commitment,
+    excess_sig: Signature,
+    burn_commitment: Option<Commitment>,
+    #[serde(default)]
+    burn_claim_public_key: Option<PublicKey>,
+}
+
+struct TransactionKernelVisitor;
+
+impl<'de> Visitor<'de> for TransactionKernelVisitor {
+    type Value = TransactionKernel;
+
+    fn expecting(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a transaction kernel")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where A: SeqAccess<'de> {
+        fn next<'de, A: SeqAccess<'de>, T: Deserialize<'de>>(seq: &mut A, index: usize) -> Result<T, A::Error> {
+            seq.next_element()?
+                .ok_or_else(|| de::Error::invalid_length(index, &TransactionKernelVisitor))
+        }
+        let version = next(&mut seq, 0)?;
+        let features = next(&mut seq, 1)?;
+        let fee = next(&mut seq, 2)?;
+        let lock_height = next(&mut seq, 3)?;
+        let excess = next(&mut seq, 4)?;
+        let excess_sig = next(&mut seq, 5)?;
+        let burn_commitment = next(&mut seq, 6)?;
+        let burn_claim_public_key = match version {
+            TransactionKernelVersion::V0 => None,
+            TransactionKernelVersion::V1 => next(&mut seq, 7)?,
+        };
+        Ok(TransactionKernel::new(
+            version,
+            features,
+            fee,
+            lock_height,
+            excess,
+            excess_sig,
+            burn_commitment,
+            burn_claim_public_key,
+        ))
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where A: MapAccess<'de> {
+        let fields = TransactionKernelFields::deserialize(MapAccessDeserializer::new(map))?;
+        Ok(TransactionKernel::new(
+            fields.version,
+            fields.features,
+            fields.fee,
+            fields.lock_height,
+            fields.excess,
+            fields.excess_sig,
+            fields.burn_commitment,
+            fields.burn_claim_public_key,
+        ))
+    }
+}
+
 impl Display for TransactionKernel {
     fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
         write!(
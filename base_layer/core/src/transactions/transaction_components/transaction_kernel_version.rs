@@ -6,11 +6,15 @@ use std::convert::TryFrom;
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
 
+use crate::transactions::transaction_components::KernelFeatures;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, PartialOrd, BorshSerialize, BorshDeserialize)]
 #[repr(u8)]
 #[borsh(use_discriminant = true)]
 pub enum TransactionKernelVersion {
     V0 = 0,
+    /// Commits to the claim public key of claimable burns
+    V1 = 1,
 }
 
 impl TransactionKernelVersion {
@@ -21,6 +25,16 @@ impl TransactionKernelVersion {
     pub fn as_u8(self) -> u8 {
         self as u8
     }
+
+    /// The version that a kernel with the given features must have. Claimable burns need V1, because only V1 kernels
+    /// commit to the claim public key.
+    pub fn for_features(features: &KernelFeatures) -> Self {
+        if features.is_claimable_burn() {
+            Self::V1
+        } else {
+            Self::get_current_version()
+        }
+    }
 }
 
 impl Default for TransactionKernelVersion {
@@ -35,6 +49,7 @@ impl TryFrom<u8> for TransactionKernelVersion {
     fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
             0 => Ok(TransactionKernelVersion::V0),
+            1 => Ok(TransactionKernelVersion::V1),
             v => Err(format!("Unknown kernel version {}!", v)),
         }
     }
@@ -47,6 +62,7 @@ mod test {
     #[test]
     fn test_try_from() {
         assert_eq!(TransactionKernelVersion::try_from(0), Ok(TransactionKernelVersion::V0));
-        assert!(TransactionKernelVersion::try_from(1).is_err());
+        assert_eq!(TransactionKernelVersion::try_from(1), Ok(TransactionKernelVersion::V1));
+        assert!(TransactionKernelVersion::try_from(2).is_err());
     }
 }
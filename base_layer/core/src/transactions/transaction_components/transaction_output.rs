@@ -305,8 +305,9 @@ impl TransactionOutput {
         }
     }
 
-    fn verify_metadata_signature_internal(&self) -> Result<[u8; 64], TransactionError> {
-        let challenge = TransactionOutput::build_metadata_signature_challenge(
+    /// The challenge that the metadata signature of this output signs
+    pub fn metadata_signature_challenge(&self) -> [u8; 64] {
+        TransactionOutput::build_metadata_signature_challenge(
             &self.version,
             &self.script,
             &self.features,
@@ -317,7 +318,11 @@ impl TransactionOutput {
             &self.covenant,
             &self.encrypted_data,
             self.minimum_value_promise,
-        );
+        )
+    }
+
+    fn verify_metadata_signature_internal(&self) -> Result<[u8; 64], TransactionError> {
+        let challenge = self.metadata_signature_challenge();
 
         if !self.metadata_signature.verify_challenge(
             &self.commitment,
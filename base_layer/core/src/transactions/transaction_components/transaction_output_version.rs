@@ -33,7 +33,7 @@ use serde::{Deserialize, Serialize};
 #[borsh(use_discriminant = true)]
 pub enum TransactionOutputVersion {
     V0 = 0,
-    /// Currently only used in tests, this can be used as the next version
+    /// The extra encrypted data holds typed records rather than a bare payment id
     V1 = 1,
 }
 
@@ -45,6 +45,11 @@ impl TransactionOutputVersion {
     pub fn as_u8(self) -> u8 {
         self as u8
     }
+
+    /// Whether the extra encrypted data of outputs of this version holds typed records rather than a bare payment id
+    pub fn has_encrypted_data_records(self) -> bool {
+        matches!(self, TransactionOutputVersion::V1)
+    }
 }
 
 impl Default for TransactionOutputVersion {
@@ -0,0 +1,181 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Vault outputs lock funds to an owner key that can only spend them once a delay has passed since they were mined,
+//! while a pre-committed recovery key can sweep them at any time. If the owner's keys are compromised, the recovery
+//! key holder has until the delay has passed to claw the funds back.
+//!
+//! The vault script is
+//! `IfThen PushPubKey(recovery_key) Else CheckRelativeHeightVerify(unlock_delay) PushPubKey(owner_key) EndIf`
+//! and the spender selects the path with the input data: `1` for the clawback path and `0` for the owner path.
+//!
+//! The clawback is built by the recovery key holder's own wallet, because the owner's wallet is the one that is assumed
+//! to be compromised. The owner exports a [`VaultOpening`] to the recovery key holder when the vault is created, which
+//! holds everything needed to spend the vault except the recovery key.
+
+use serde::{Deserialize, Serialize};
+use tari_common_types::types::{PrivateKey, PublicKey};
+use tari_script::{ExecutionStack, Opcode, ScriptError, StackItem, TariScript};
+
+use super::TransactionOutput;
+use crate::transactions::tari_amount::MicroMinotari;
+
+/// The parameters of a vault script
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VaultScript {
+    /// The key that can spend the output once the unlock delay has passed
+    pub owner_key: PublicKey,
+    /// The key that can sweep the output at any time
+    pub recovery_key: PublicKey,
+    /// The number of blocks after the output was mined before the owner key may spend it
+    pub unlock_delay: u64,
+}
+
+impl VaultScript {
+    pub fn new(owner_key: PublicKey, recovery_key: PublicKey, unlock_delay: u64) -> Self {
+        Self {
+            owner_key,
+            recovery_key,
+            unlock_delay,
+        }
+    }
+
+    /// Builds the vault script
+    pub fn to_script(&self) -> Result<TariScript, ScriptError> {
+        TariScript::new(vec![
+            Opcode::IfThen,
+            Opcode::PushPubKey(Box::new(self.recovery_key.clone())),
+            Opcode::Else,
+            Opcode::CheckRelativeHeightVerify(self.unlock_delay),
+            Opcode::PushPubKey(Box::new(self.owner_key.clone())),
+            Opcode::EndIf,
+        ])
+    }
+
+    /// Parses the vault parameters from a script, returning None if the script is not a vault script
+    pub fn from_script(script: &TariScript) -> Option<Self> {
+        use Opcode::{CheckRelativeHeightVerify, Else, EndIf, IfThen, PushPubKey};
+        match script.as_slice() {
+            [IfThen, PushPubKey(recovery), Else, CheckRelativeHeightVerify(delay), PushPubKey(owner), EndIf] => {
+                Some(Self::new(owner.as_ref().clone(), recovery.as_ref().clone(), *delay))
+            },
+            _ => None,
+        }
+    }
+
+    /// The height from which the owner may spend a vault that was mined at `mined_height`
+    pub fn unlock_height(&self, mined_height: u64) -> u64 {
+        mined_height.saturating_add(self.unlock_delay)
+    }
+
+    /// The input data that selects the owner path
+    pub fn owner_input_data() -> ExecutionStack {
+        ExecutionStack::new(vec![StackItem::Number(0)])
+    }
+
+    /// The input data that selects the clawback path
+    pub fn clawback_input_data() -> ExecutionStack {
+        ExecutionStack::new(vec![StackItem::Number(1)])
+    }
+}
+
+/// The opening of a vault output, which the owner hands to the recovery key holder so that they can claw the vault back
+/// from their own wallet. It does not let the recovery key holder spend the vault through the owner path.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VaultOpening {
+    pub output: TransactionOutput,
+    pub value: MicroMinotari,
+    pub commitment_mask: PrivateKey,
+}
+
+impl VaultOpening {
+    pub fn new(output: TransactionOutput, value: MicroMinotari, commitment_mask: PrivateKey) -> Self {
+        Self {
+            output,
+            value,
+            commitment_mask,
+        }
+    }
+
+    /// The vault parameters, or None if the output is not a vault
+    pub fn vault(&self) -> Option<VaultScript> {
+        VaultScript::from_script(&self.output.script)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::OsRng;
+    use tari_common_types::types::Commitment;
+    use tari_crypto::keys::PublicKey as PublicKeyTrait;
+    use tari_script::ScriptContext;
+
+    use super::*;
+
+    fn context_at(height: u64, mined_height: u64) -> ScriptContext {
+        ScriptContext::new(height, &[0u8; 32], &Commitment::default()).with_mined_height(mined_height)
+    }
+
+    #[test]
+    fn it_roundtrips_the_script() {
+        let (_, owner_key) = PublicKey::random_keypair(&mut OsRng);
+        let (_, recovery_key) = PublicKey::random_keypair(&mut OsRng);
+        let vault = VaultScript::new(owner_key, recovery_key, 720);
+        assert_eq!(vault.unlock_height(100), 820);
+        let script = vault.to_script().unwrap();
+        assert_eq!(VaultScript::from_script(&script), Some(vault));
+        assert_eq!(VaultScript::from_script(&TariScript::default()), None);
+    }
+
+    #[test]
+    fn it_only_lets_the_owner_spend_after_the_unlock_delay() {
+        let (_, owner_key) = PublicKey::random_keypair(&mut OsRng);
+        let (_, recovery_key) = PublicKey::random_keypair(&mut OsRng);
+        let vault = VaultScript::new(owner_key.clone(), recovery_key.clone(), 1000);
+        let script = vault.to_script().unwrap();
+
+        // The delay counts from the height that the vault was mined at
+        for mined_height in [0, 5000] {
+            let err = script
+                .execute_with_context(
+                    &VaultScript::owner_input_data(),
+                    &context_at(mined_height + 999, mined_height),
+                )
+                .unwrap_err();
+            assert_eq!(err, ScriptError::VerifyFailed);
+            let result = script
+                .execute_with_context(
+                    &VaultScript::owner_input_data(),
+                    &context_at(mined_height + 1000, mined_height),
+                )
+                .unwrap();
+            assert_eq!(result, StackItem::PublicKey(owner_key.clone()));
+        }
+
+        for height in [10, 999, 1000, 5000] {
+            let result = script
+                .execute_with_context(&VaultScript::clawback_input_data(), &context_at(height, 10))
+                .unwrap();
+            assert_eq!(result, StackItem::PublicKey(recovery_key.clone()));
+        }
+    }
+}
@@ -204,11 +204,6 @@ impl WalletOutput {
         key_manager: &KM,
     ) -> Result<TransactionInput, TransactionError> {
         let value = self.value.into();
-        let commitment = key_manager.get_commitment(&self.spending_key_id, &value).await?;
-        let rangeproof_hash = match &self.range_proof {
-            Some(rp) => rp.hash(),
-            None => FixedHash::zero(),
-        };
         let version = TransactionInputVersion::get_current_version();
         let script_message = TransactionInput::build_script_signature_message(&version, &self.script, &self.input_data);
         let script_signature = key_manager
@@ -220,6 +215,23 @@ impl WalletOutput {
                 &script_message,
             )
             .await?;
+        let mut input = self.to_unsigned_transaction_input(key_manager).await?;
+        input.script_signature = script_signature;
+        Ok(input)
+    }
+
+    /// Commits an KeyManagerOutput into a Transaction input without a script signature, for the key manager to sign
+    /// once the transaction is complete
+    pub async fn to_unsigned_transaction_input<KM: TransactionKeyManagerInterface>(
+        &self,
+        key_manager: &KM,
+    ) -> Result<TransactionInput, TransactionError> {
+        let value = self.value.into();
+        let commitment = key_manager.get_commitment(&self.spending_key_id, &value).await?;
+        let rangeproof_hash = match &self.range_proof {
+            Some(rp) => rp.hash(),
+            None => FixedHash::zero(),
+        };
 
         Ok(TransactionInput::new_current_version(
             SpentOutput::OutputData {
@@ -235,7 +247,7 @@ impl WalletOutput {
                 minimum_value_promise: self.minimum_value_promise,
             },
             self.input_data.clone(),
-            script_signature,
+            ComAndPubSignature::default(),
         ))
     }
 
@@ -33,7 +33,7 @@ use crate::{
         key_manager::{TariKeyId, TransactionKeyManagerInterface},
         tari_amount::MicroMinotari,
         transaction_components::{
-            encrypted_data::PaymentId,
+            encrypted_data::{EncryptedDataRecord, PaymentId},
             EncryptedData,
             OutputFeatures,
             TransactionError,
@@ -137,6 +137,27 @@ impl WalletOutputBuilder {
         Ok(self)
     }
 
+    /// Same as `encrypt_data_for_recovery`, storing typed records instead of a bare payment id. This moves the output
+    /// to the version that signals records, so it must be called before signing.
+    pub async fn encrypt_records_for_recovery<KM: TransactionKeyManagerInterface>(
+        mut self,
+        key_manager: &KM,
+        custom_recovery_key_id: Option<&TariKeyId>,
+        records: &[EncryptedDataRecord],
+    ) -> Result<Self, TransactionError> {
+        self.encrypted_data = key_manager
+            .encrypt_records_for_recovery(
+                &self.commitment_mask_key_id,
+                custom_recovery_key_id,
+                self.value.as_u64(),
+                records,
+            )
+            .await?;
+        self.payment_id = EncryptedDataRecord::payment_id(records);
+        self.version = TransactionOutputVersion::V1;
+        Ok(self)
+    }
+
     pub fn with_script_key(mut self, script_key_id: TariKeyId) -> Self {
         self.script_key_id = Some(script_key_id);
         self
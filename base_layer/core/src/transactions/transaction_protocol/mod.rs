@@ -85,6 +85,7 @@
 
 // #![allow(clippy::op_ref)]
 
+use borsh::{BorshDeserialize, BorshSerialize};
 use derivative::Derivative;
 use serde::{Deserialize, Serialize};
 use tari_common_types::types::PrivateKey;
@@ -98,10 +99,10 @@ pub mod recipient;
 pub mod sender;
 pub mod single_receiver;
 pub mod transaction_initializer;
-use tari_common_types::types::Commitment;
+use tari_common_types::types::{Commitment, PublicKey};
 use tari_key_manager::key_manager_service::KeyManagerServiceError;
 
-use crate::transactions::transaction_components::KernelFeatures;
+use crate::transactions::transaction_components::{KernelFeatures, TransactionKernelVersion};
 
 #[derive(Clone, Debug, PartialEq, Error, Deserialize, Serialize)]
 pub enum TransactionProtocolError {
@@ -158,7 +159,7 @@ impl From<KeyManagerServiceError> for TransactionProtocolError {
 }
 
 /// Transaction metadata, this includes all the fields that needs to be signed on the kernel
-#[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize, Serialize, BorshSerialize, BorshDeserialize)]
 pub struct TransactionMetadata {
     /// The absolute fee for the transaction
     pub fee: MicroMinotari,
@@ -168,6 +169,9 @@ pub struct TransactionMetadata {
     pub kernel_features: KernelFeatures,
     /// optional burn commitment if present
     pub burn_commitment: Option<Commitment>,
+    /// The claim public key that a claimable burn commits to
+    #[serde(default)]
+    pub burn_claim_public_key: Option<PublicKey>,
 }
 
 impl TransactionMetadata {
@@ -177,6 +181,7 @@ impl TransactionMetadata {
             lock_height,
             kernel_features: KernelFeatures::default(),
             burn_commitment: None,
+            burn_claim_public_key: None,
         }
     }
 
@@ -186,8 +191,14 @@ impl TransactionMetadata {
             lock_height,
             kernel_features,
             burn_commitment: None,
+            burn_claim_public_key: None,
         }
     }
+
+    /// The version of the kernel that these features are signed for
+    pub fn kernel_version(&self) -> TransactionKernelVersion {
+        TransactionKernelVersion::for_features(&self.kernel_features)
+    }
 }
 
 #[derive(Derivative, Clone)]
@@ -22,7 +22,7 @@
 
 use std::convert::TryFrom;
 
-use tari_common_types::types::Commitment;
+use tari_common_types::types::{Commitment, PublicKey};
 use tari_utilities::ByteArray;
 
 use super::protocol as proto;
@@ -41,12 +41,21 @@ impl TryFrom<proto::TransactionMetadata> for TransactionMetadata {
                     .map_err(|e| format!("burned_commitment.data: {}", e))
             })
             .transpose()?;
+        let burn_claim_public_key = if metadata.burn_claim_public_key.is_empty() {
+            None
+        } else {
+            Some(
+                PublicKey::from_canonical_bytes(&metadata.burn_claim_public_key)
+                    .map_err(|e| format!("burn_claim_public_key: {}", e))?,
+            )
+        };
         Ok(Self {
             fee: metadata.fee.into(),
             lock_height: metadata.lock_height,
             kernel_features: KernelFeatures::from_bits(kernel_features)
                 .ok_or_else(|| "Invalid or unrecognised kernel feature flag".to_string())?,
             burn_commitment: commitment,
+            burn_claim_public_key,
         })
     }
 }
@@ -62,6 +71,10 @@ impl From<TransactionMetadata> for proto::TransactionMetadata {
             kernel_features: u32::from(metadata.kernel_features.bits()),
             // optional burn commitment if present
             burned_commitment: commitment,
+            burn_claim_public_key: metadata
+                .burn_claim_public_key
+                .map(|key| key.to_vec())
+                .unwrap_or_default(),
         }
     }
 }
@@ -435,7 +435,7 @@ impl SenderTransactionProtocol {
                     .await?;
 
                 let output_version = TransactionOutputVersion::get_current_version();
-                let kernel_version = TransactionKernelVersion::get_current_version();
+                let kernel_version = info.metadata.kernel_version();
 
                 Ok(SingleRoundSenderData {
                     tx_id: info.tx_id,
@@ -606,20 +606,21 @@ impl SenderTransactionProtocol {
 
         let mut offset = info.recipient_partial_kernel_offset.clone();
         let mut signature = info.recipient_partial_kernel_signature.clone();
-        let mut script_keys = Vec::new();
         let mut sender_offset_keys = Vec::new();
-        let kernel_version = TransactionKernelVersion::get_current_version();
+        let kernel_version = info.metadata.kernel_version();
 
         let kernel_message = TransactionKernel::build_kernel_signature_message(
-            &TransactionKernelVersion::get_current_version(),
+            &kernel_version,
             info.metadata.fee,
             info.metadata.lock_height,
             &info.metadata.kernel_features,
             &info.metadata.burn_commitment,
+            &info.metadata.burn_claim_public_key,
         );
 
         for input in &info.inputs {
-            tx_builder.add_input(input.output.to_transaction_input(key_manager).await?);
+            // The inputs are signed once the transaction is complete, so that a remote signer can be given all of it
+            tx_builder.add_input(input.output.to_unsigned_transaction_input(key_manager).await?);
             signature = &signature +
                 &key_manager
                     .get_partial_txo_kernel_signature(
@@ -637,7 +638,6 @@ impl SenderTransactionProtocol {
                 &key_manager
                     .get_txo_private_kernel_offset(&input.output.spending_key_id, &input.kernel_nonce)
                     .await?;
-            script_keys.push(input.output.script_key_id.clone());
         }
 
         for output in &info.outputs {
@@ -698,10 +698,9 @@ impl SenderTransactionProtocol {
         if let Some(received_output) = &info.recipient_output {
             tx_builder.add_output(received_output.clone());
         }
-        let script_offset = key_manager.get_script_offset(&script_keys, &sender_offset_keys).await?;
 
         tx_builder.add_offset(offset);
-        tx_builder.add_script_offset(script_offset);
+        tx_builder.add_script_offset(PrivateKey::default());
         let excess = PedersenCommitment::from_public_key(&total_public_excess);
 
         let kernel = KernelBuilder::new()
@@ -709,11 +708,24 @@ impl SenderTransactionProtocol {
             .with_features(info.metadata.kernel_features)
             .with_lock_height(info.metadata.lock_height)
             .with_burn_commitment(info.metadata.burn_commitment.clone())
+            .with_burn_claim_public_key(info.metadata.burn_claim_public_key.clone())
             .with_excess(&excess)
             .with_signature(signature)
             .build()?;
         tx_builder.with_kernel(kernel);
-        tx_builder.build().map_err(TPE::from)
+        let transaction = tx_builder.build()?;
+
+        let inputs = info.inputs.iter().map(|input| &input.output).collect::<Vec<_>>();
+        let change_outputs = info
+            .outputs
+            .iter()
+            .chain(info.change_output.as_ref())
+            .map(|output| &output.output)
+            .collect::<Vec<_>>();
+        key_manager
+            .sign_transaction_inputs(transaction, &inputs, &change_outputs, &sender_offset_keys)
+            .await
+            .map_err(TPE::from)
     }
 
     /// Performs sanity checks on the collected transaction pieces prior to building the final Transaction instance
@@ -74,6 +74,7 @@ impl SingleReceiverTransactionProtocol {
             tx_meta.lock_height,
             &tx_meta.kernel_features,
             &tx_meta.burn_commitment,
+            &tx_meta.burn_claim_public_key,
         );
         let signature = key_manager
             .get_partial_txo_kernel_signature(
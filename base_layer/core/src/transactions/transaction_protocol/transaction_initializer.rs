@@ -55,6 +55,7 @@ use crate::{
             KernelFeatures,
             TransactionMetadata,
         },
+        TransactionEntropy,
     },
 };
 
@@ -105,6 +106,7 @@ pub struct SenderTransactionInitializer<KM> {
     fee: Fee,
     key_manager: KM,
     sender_address: TariAddress,
+    entropy: TransactionEntropy,
 }
 
 pub struct BuildError<KM> {
@@ -137,6 +139,7 @@ where KM: TransactionKeyManagerInterface
             tx_id: None,
             sender_address: TariAddress::default(),
             key_manager,
+            entropy: TransactionEntropy::default(),
         }
     }
 
@@ -305,8 +308,6 @@ where KM: TransactionKeyManagerInterface
     async fn add_change_if_required(
         &mut self,
     ) -> Result<(MicroMinotari, MicroMinotari, Option<(WalletOutput, TariKeyId)>), String> {
-        // The number of outputs excluding a possible residual change output
-        let num_outputs = self.sender_custom_outputs.len() + usize::from(self.recipient.is_some());
         let num_inputs = self.inputs.len();
         let total_being_spent = self
             .inputs
@@ -331,11 +332,21 @@ where KM: TransactionKeyManagerInterface
         let features_and_scripts_size_without_change = self
             .get_total_features_and_scripts_size_for_outputs()
             .map_err(|e| e.to_string())?;
-        let fee_without_change = self.fee().calculate(
+        let kernel_features = [self.kernel_features];
+        // The types of the outputs excluding a possible residual change output
+        let mut output_types = self
+            .sender_custom_outputs
+            .iter()
+            .map(|o| o.output.features.output_type)
+            .collect::<Vec<_>>();
+        if self.recipient.is_some() {
+            output_types.push(self.get_recipient_output_features().output_type);
+        }
+        let fee_without_change = self.fee().calculate_with_features(
             fee_per_gram,
-            1,
+            &kernel_features,
             num_inputs,
-            num_outputs,
+            &output_types,
             features_and_scripts_size_without_change,
         );
 
@@ -368,9 +379,17 @@ where KM: TransactionKeyManagerInterface
             )),
             Some(MicroMinotari(0)) => Ok((fee_without_change, MicroMinotari(0), None)),
             Some(v) => {
-                let change_fee = self
-                    .fee()
-                    .calculate(fee_per_gram, 0, 0, 1, change_features_and_scripts_size);
+                // The fee is calculated for the transaction as a whole, since adding a change output can change whether
+                // the transaction is discounted as a consolidation
+                output_types.push(output_features.output_type);
+                let fee_with_change = self.fee().calculate_with_features(
+                    fee_per_gram,
+                    &kernel_features,
+                    num_inputs,
+                    &output_types,
+                    features_and_scripts_size_without_change + change_features_and_scripts_size,
+                );
+                let change_fee = fee_with_change.saturating_sub(fee_without_change);
                 let change_amount = v.checked_sub(change_fee);
                 match change_amount {
                     // You can't win. Just add the change to the fee (which is less than the cost of adding another
@@ -472,6 +491,14 @@ where KM: TransactionKeyManagerInterface
         self
     }
 
+    /// Replaces the source of randomness used for the tx_id, see `TransactionEntropy`. The key manager's source has to
+    /// be replaced as well for the transaction to be reproducible.
+    #[cfg(any(test, feature = "deterministic_transactions"))]
+    pub fn with_entropy(&mut self, entropy: TransactionEntropy) -> &mut Self {
+        self.entropy = entropy;
+        self
+    }
+
     fn check_value<T>(name: &str, val: &Option<T>, vec: &mut Vec<String>) {
         if val.is_none() {
             vec.push(name.to_string());
@@ -546,7 +573,7 @@ where KM: TransactionKeyManagerInterface
         // we need some random data here, the public excess of the commitment is random.
         let tx_id = match self.tx_id {
             Some(id) => id,
-            None => TxId::new_random(),
+            None => TxId::from(self.entropy.next_u64()),
         };
 
         // The fee should be less than the amount being sent. This isn't a protocol requirement, but it's what you want
@@ -568,6 +595,15 @@ where KM: TransactionKeyManagerInterface
             }
         }
 
+        // A claimable burn commits to the claim public key of the burned output in its kernel
+        let burn_claim_public_key = if self.kernel_features.is_claimable_burn() {
+            self.recipient
+                .as_ref()
+                .and_then(|data| data.recipient_output_features.burn_claim_public_key().cloned())
+        } else {
+            None
+        };
+
         // cached data
 
         // Everything is here. Let's send some Minotari!
@@ -586,6 +622,7 @@ where KM: TransactionKeyManagerInterface
                 lock_height: self.lock_height.unwrap(),
                 kernel_features: self.kernel_features,
                 burn_commitment: self.burn_commitment.clone(),
+                burn_claim_public_key,
             },
             inputs: self.inputs,
             outputs: self.sender_custom_outputs,
@@ -605,19 +642,23 @@ where KM: TransactionKeyManagerInterface
 
 #[cfg(test)]
 mod test {
+    use rand::{rngs::StdRng, SeedableRng};
     use tari_common_types::tari_address::TariAddress;
+    use tari_key_manager::cipher_seed::CipherSeed;
     use tari_script::{inputs, script};
+    use tari_utilities::epoch_time::EpochTime;
 
     use crate::{
         covenants::Covenant,
         test_helpers::create_consensus_constants,
         transactions::{
             fee::Fee,
-            key_manager::create_memory_db_key_manager,
+            key_manager::{create_memory_db_key_manager, create_memory_db_key_manager_from_seed},
             tari_amount::*,
             test_helpers::{create_test_input, create_wallet_output_with_data, TestParams, UtxoTestParams},
             transaction_components::{OutputFeatures, MAX_TRANSACTION_INPUTS},
             transaction_protocol::{sender::SenderState, transaction_initializer::SenderTransactionInitializer},
+            TransactionEntropy,
         },
     };
 
@@ -1009,4 +1050,39 @@ mod test {
             panic!("There was a recipient, we should be ready to send a message");
         }
     }
+
+    async fn build_deterministic_transaction(seed: CipherSeed) -> Vec<u8> {
+        let key_manager = create_memory_db_key_manager_from_seed(seed, 64).unwrap();
+        let entropy = TransactionEntropy::deterministic(StdRng::seed_from_u64(42), || EpochTime::from(1_700_000_000));
+        key_manager.set_entropy(entropy.clone()).await;
+        let input = create_test_input(MicroMinotari(5_000), 0, &key_manager, vec![]).await;
+        let change = TestParams::new(&key_manager).await;
+        let mut builder = SenderTransactionInitializer::new(&create_consensus_constants(0), key_manager.clone());
+        builder
+            .with_entropy(entropy)
+            .with_lock_height(0)
+            .with_fee_per_gram(MicroMinotari(5))
+            .with_input(input)
+            .await
+            .unwrap()
+            .with_change_data(
+                script!(Nop).unwrap(),
+                inputs!(change.script_key_pk),
+                change.script_key_id.clone(),
+                change.commitment_mask_key_id.clone(),
+                Covenant::default(),
+                TariAddress::default(),
+            );
+        let mut stp = builder.build().await.unwrap();
+        stp.finalize(&key_manager).await.unwrap();
+        serde_json::to_vec(&stp.into_transaction().unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn deterministic_construction_is_reproducible() {
+        let seed = CipherSeed::new();
+        let first = build_deterministic_transaction(seed.clone()).await;
+        let second = build_deterministic_transaction(seed).await;
+        assert_eq!(first, second);
+    }
 }
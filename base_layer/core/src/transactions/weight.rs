@@ -22,7 +22,35 @@
 
 use std::{convert::TryFrom, num::NonZeroU64};
 
-use crate::transactions::aggregated_body::AggregateBody;
+use crate::transactions::{
+    aggregated_body::AggregateBody,
+    transaction_components::{KernelFeatures, OutputType, RangeProofType},
+};
+
+/// The formula used to turn a transaction body into a weight. A new version is activated at a fork height by setting
+/// it on the consensus constants that take effect from that height.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionWeightVersion {
+    /// Every output is charged `output_weight`
+    V1,
+    /// Outputs with a revealed value range proof carry no proof bytes and are charged `revealed_value_output_weight`
+    /// instead of `output_weight`
+    V2,
+    /// Same as `V2`, and pure consolidation transactions (a single kernel and standard output spending at least
+    /// `consolidation_min_inputs` inputs) are charged `consolidation_fee_weight_percentage` percent of their weight
+    /// when calculating fees and fee priority. The block weight is not discounted.
+    V3,
+}
+
+impl TransactionWeightVersion {
+    pub fn as_u8(self) -> u8 {
+        match self {
+            TransactionWeightVersion::V1 => 1,
+            TransactionWeightVersion::V2 => 2,
+            TransactionWeightVersion::V3 => 3,
+        }
+    }
+}
 
 #[derive(Debug, Clone, Copy)]
 pub struct WeightParams {
@@ -34,6 +62,15 @@ pub struct WeightParams {
     pub output_weight: u64,
     /// Features and scripts per byte weight
     pub features_and_scripts_bytes_per_gram: NonZeroU64,
+    /// Weight in grams per output with a revealed value range proof, excl. TariScript and OutputFeatures. Only
+    /// charged from `TransactionWeightVersion::V2`.
+    pub revealed_value_output_weight: u64,
+    /// The minimum number of inputs a transaction must spend to be a consolidation transaction. Only used from
+    /// `TransactionWeightVersion::V3`.
+    pub consolidation_min_inputs: u64,
+    /// The percentage of the weight of a consolidation transaction that its fee is charged for. Only used from
+    /// `TransactionWeightVersion::V3`.
+    pub consolidation_fee_weight_percentage: u64,
 }
 
 impl WeightParams {
@@ -44,57 +81,207 @@ impl WeightParams {
             output_weight: 53,
             // SAFETY: the value isn't 0. NonZeroU64::new(x).expect(...) is not const so cannot be used in const fn
             features_and_scripts_bytes_per_gram: unsafe { NonZeroU64::new_unchecked(16) },
+            revealed_value_output_weight: 53,
+            consolidation_min_inputs: 0,
+            consolidation_fee_weight_percentage: 100,
+        }
+    }
+
+    pub const fn v2() -> Self {
+        Self {
+            kernel_weight: 10,
+            input_weight: 8,
+            output_weight: 53,
+            // SAFETY: the value isn't 0. NonZeroU64::new(x).expect(...) is not const so cannot be used in const fn
+            features_and_scripts_bytes_per_gram: unsafe { NonZeroU64::new_unchecked(16) },
+            // The commitment, sender offset public key and signatures without the ~600 byte bulletproof+ range proof
+            revealed_value_output_weight: 15,
+            consolidation_min_inputs: 0,
+            consolidation_fee_weight_percentage: 100,
+        }
+    }
+
+    pub const fn v3() -> Self {
+        Self {
+            consolidation_min_inputs: 5,
+            consolidation_fee_weight_percentage: 50,
+            ..Self::v2()
         }
     }
 }
 
 #[derive(Debug, Clone, Copy)]
-pub struct TransactionWeight(WeightParams);
+pub struct TransactionWeight {
+    version: TransactionWeightVersion,
+    params: WeightParams,
+}
 
 impl TransactionWeight {
-    /// Constructor
+    /// Constructor for a v1 `TransactionWeight` with custom weight params
     pub fn new(weight_params: WeightParams) -> Self {
-        Self(weight_params)
+        Self::with_version(TransactionWeightVersion::V1, weight_params)
+    }
+
+    /// Constructor for a `TransactionWeight` with the given formula version and weight params
+    pub fn with_version(version: TransactionWeightVersion, weight_params: WeightParams) -> Self {
+        Self {
+            version,
+            params: weight_params,
+        }
     }
 
-    /// Creates a new `TransactionWeight` with latest weight params
+    /// Creates a new `TransactionWeight` with the latest formula and weight params active on any network
     pub fn latest() -> Self {
-        Self(WeightParams::v1())
+        Self::v1()
     }
 
-    /// Creates a new `TransactionWeight` with v1 weight params
+    /// Creates a new `TransactionWeight` with the v1 formula and weight params
     pub fn v1() -> Self {
-        Self(WeightParams::v1())
+        Self::with_version(TransactionWeightVersion::V1, WeightParams::v1())
+    }
+
+    /// Creates a new `TransactionWeight` with the v2 formula and weight params
+    pub fn v2() -> Self {
+        Self::with_version(TransactionWeightVersion::V2, WeightParams::v2())
+    }
+
+    /// Creates a new `TransactionWeight` with the v3 formula and weight params
+    pub fn v3() -> Self {
+        Self::with_version(TransactionWeightVersion::V3, WeightParams::v3())
+    }
+
+    pub fn version(&self) -> TransactionWeightVersion {
+        self.version
     }
 
     /// Calculate the weight in grams of a transaction based on the number of kernels, inputs, outputs and rounded up
     /// features_and_scripts size. A warning to ensure that the _per output_ rounded up features_and_scripts size must
     /// be used or the calculation will be incorrect. If possible, use calculate_body instead to ensure correctness.
+    /// All outputs are charged as outputs with a bulletproof+ range proof, so this never underestimates the weight.
     pub fn calculate(
         &self,
         num_kernels: usize,
         num_inputs: usize,
         num_outputs: usize,
         rounded_up_features_and_scripts_byte_size: usize,
+    ) -> u64 {
+        self.calculate_with_revealed_value_outputs(
+            num_kernels,
+            num_inputs,
+            num_outputs,
+            0,
+            rounded_up_features_and_scripts_byte_size,
+        )
+    }
+
+    /// Same as `calculate`, where `num_revealed_value_outputs` of the `num_outputs` outputs have a revealed value
+    /// range proof.
+    pub fn calculate_with_revealed_value_outputs(
+        &self,
+        num_kernels: usize,
+        num_inputs: usize,
+        num_outputs: usize,
+        num_revealed_value_outputs: usize,
+        rounded_up_features_and_scripts_byte_size: usize,
     ) -> u64 {
         let params = self.params();
+        let outputs_weight = match self.version {
+            TransactionWeightVersion::V1 => params.output_weight * num_outputs as u64,
+            TransactionWeightVersion::V2 | TransactionWeightVersion::V3 => {
+                let num_revealed_value_outputs = num_revealed_value_outputs.min(num_outputs);
+                params.output_weight * (num_outputs - num_revealed_value_outputs) as u64 +
+                    params.revealed_value_output_weight * num_revealed_value_outputs as u64
+            },
+        };
         params.kernel_weight * num_kernels as u64 +
             params.input_weight * num_inputs as u64 +
-            params.output_weight * num_outputs as u64 +
+            outputs_weight +
             rounded_up_features_and_scripts_byte_size as u64 / params.features_and_scripts_bytes_per_gram.get()
     }
 
     pub fn calculate_body(&self, body: &AggregateBody) -> std::io::Result<u64> {
         let rounded_up_features_and_scripts_bytes_size =
             self.calculate_normalised_total_features_and_scripts_size(body)?;
-        Ok(self.calculate(
+        let num_revealed_value_outputs = body
+            .outputs()
+            .iter()
+            .filter(|o| o.features.range_proof_type == RangeProofType::RevealedValue)
+            .count();
+        Ok(self.calculate_with_revealed_value_outputs(
             body.kernels().len(),
             body.inputs().len(),
             body.outputs().len(),
+            num_revealed_value_outputs,
             rounded_up_features_and_scripts_bytes_size,
         ))
     }
 
+    /// Returns true if a transaction with these kernels, inputs and outputs is a consolidation transaction that is
+    /// charged a reduced fee. A consolidation transaction spends at least `consolidation_min_inputs` inputs into a
+    /// single standard output under a single kernel that is neither a burn nor a coinbase, so it shrinks the UTXO set.
+    /// The output may belong to the spender or to a recipient. Always false before `TransactionWeightVersion::V3`.
+    pub fn is_consolidation(
+        &self,
+        kernel_features: &[KernelFeatures],
+        num_inputs: usize,
+        output_types: &[OutputType],
+    ) -> bool {
+        self.version == TransactionWeightVersion::V3 &&
+            num_inputs as u64 >= self.params().consolidation_min_inputs.max(2) &&
+            matches!(kernel_features, [features] if !features.is_burned() && !features.is_coinbase()) &&
+            matches!(output_types, [OutputType::Standard])
+    }
+
+    /// Same as `is_consolidation` for a transaction body
+    pub fn is_consolidation_body(&self, body: &AggregateBody) -> bool {
+        let kernel_features = body.kernels().iter().map(|k| k.features).collect::<Vec<_>>();
+        let output_types = body
+            .outputs()
+            .iter()
+            .map(|o| o.features.output_type)
+            .collect::<Vec<_>>();
+        self.is_consolidation(&kernel_features, body.inputs().len(), &output_types)
+    }
+
+    /// Calculates the weight in grams that the fee of a transaction with these kernels, inputs and outputs is charged
+    /// for. This is the same as `calculate`, except that consolidation transactions are discounted.
+    pub fn calculate_fee_weight(
+        &self,
+        kernel_features: &[KernelFeatures],
+        num_inputs: usize,
+        output_types: &[OutputType],
+        rounded_up_features_and_scripts_byte_size: usize,
+    ) -> u64 {
+        let weight = self.calculate(
+            kernel_features.len(),
+            num_inputs,
+            output_types.len(),
+            rounded_up_features_and_scripts_byte_size,
+        );
+        if self.is_consolidation(kernel_features, num_inputs, output_types) {
+            self.apply_consolidation_discount(weight)
+        } else {
+            weight
+        }
+    }
+
+    /// Calculates the weight in grams that the fee of a transaction body is charged for. This is the same as
+    /// `calculate_body`, except that consolidation transactions are discounted.
+    pub fn calculate_fee_weight_body(&self, body: &AggregateBody) -> std::io::Result<u64> {
+        let weight = self.calculate_body(body)?;
+        if self.is_consolidation_body(body) {
+            Ok(self.apply_consolidation_discount(weight))
+        } else {
+            Ok(weight)
+        }
+    }
+
+    fn apply_consolidation_discount(&self, weight: u64) -> u64 {
+        let percentage = self.params().consolidation_fee_weight_percentage.min(100);
+        // A transaction always has a non-zero fee weight
+        (weight.saturating_mul(percentage) / 100).max(1)
+    }
+
     fn calculate_normalised_total_features_and_scripts_size(&self, body: &AggregateBody) -> std::io::Result<usize> {
         // When calculating the total block size vs each individual transaction the div operator in `calculate` above
         // will yield a different result due to integer rounding.
@@ -131,13 +318,13 @@ impl TransactionWeight {
     }
 
     pub fn params(&self) -> &WeightParams {
-        &self.0
+        &self.params
     }
 }
 
 impl From<WeightParams> for TransactionWeight {
     fn from(params: WeightParams) -> Self {
-        Self(params)
+        Self::new(params)
     }
 }
 
@@ -170,4 +357,93 @@ mod test {
         let body = AggregateBody::empty();
         assert_eq!(weighting.calculate_body(&body).unwrap(), 0);
     }
+
+    #[test]
+    fn v1_and_v2_agree_without_revealed_value_outputs() {
+        let v1 = TransactionWeight::v1();
+        let v2 = TransactionWeight::v2();
+        for (kernels, inputs, outputs, size) in [(1, 0, 1, 64), (1, 2, 2, 128), (3, 10, 7, 1024)] {
+            assert_eq!(
+                v1.calculate(kernels, inputs, outputs, size),
+                v2.calculate(kernels, inputs, outputs, size)
+            );
+            assert_eq!(
+                v1.calculate_with_revealed_value_outputs(kernels, inputs, outputs, 0, size),
+                v2.calculate_with_revealed_value_outputs(kernels, inputs, outputs, 0, size)
+            );
+        }
+    }
+
+    #[test]
+    fn v3_discounts_the_fee_weight_of_consolidations() {
+        let v2 = TransactionWeight::v2();
+        let v3 = TransactionWeight::v3();
+        let min_inputs = usize::try_from(v3.params().consolidation_min_inputs).unwrap();
+        let plain = [KernelFeatures::empty()];
+        let standard = [OutputType::Standard];
+
+        // The block weight is unchanged
+        assert_eq!(v2.calculate(1, min_inputs, 1, 64), v3.calculate(1, min_inputs, 1, 64));
+        assert!(v3.is_consolidation(&plain, min_inputs, &standard));
+        assert_eq!(
+            v3.calculate_fee_weight(&plain, min_inputs, &standard, 64),
+            v3.calculate(1, min_inputs, 1, 64) * v3.params().consolidation_fee_weight_percentage / 100
+        );
+
+        // Too few inputs, a second output, multiple kernels, burns, coinbases and non-standard outputs are charged in
+        // full
+        let two_plain = [KernelFeatures::empty(), KernelFeatures::empty()];
+        let burn = [KernelFeatures::create_burn()];
+        let coinbase = [KernelFeatures::create_coinbase()];
+        let two_standard = [OutputType::Standard, OutputType::Standard];
+        let burn_output = [OutputType::Burn];
+        let cases: [(&[KernelFeatures], usize, &[OutputType]); 6] = [
+            (&plain, min_inputs - 1, &standard),
+            (&plain, min_inputs, &two_standard),
+            (&two_plain, min_inputs, &standard),
+            (&burn, min_inputs, &standard),
+            (&coinbase, min_inputs, &standard),
+            (&plain, min_inputs, &burn_output),
+        ];
+        for (kernel_features, inputs, output_types) in cases {
+            assert!(!v3.is_consolidation(kernel_features, inputs, output_types));
+            assert_eq!(
+                v3.calculate_fee_weight(kernel_features, inputs, output_types, 64),
+                v3.calculate(kernel_features.len(), inputs, output_types.len(), 64)
+            );
+        }
+
+        // Earlier versions never discount
+        assert!(!v2.is_consolidation(&plain, min_inputs, &standard));
+        assert_eq!(
+            v2.calculate_fee_weight(&plain, min_inputs, &standard, 64),
+            v2.calculate(1, min_inputs, 1, 64)
+        );
+    }
+
+    #[test]
+    fn v2_discounts_revealed_value_outputs() {
+        let v1 = TransactionWeight::v1();
+        let v2 = TransactionWeight::v2();
+        let discount = v2.params().output_weight - v2.params().revealed_value_output_weight;
+
+        // v1 ignores the range proof type entirely
+        assert_eq!(
+            v1.calculate_with_revealed_value_outputs(1, 1, 2, 2, 64),
+            v1.calculate(1, 1, 2, 64)
+        );
+        assert_eq!(
+            v2.calculate_with_revealed_value_outputs(1, 1, 2, 1, 64),
+            v1.calculate(1, 1, 2, 64) - discount
+        );
+        assert_eq!(
+            v2.calculate_with_revealed_value_outputs(1, 1, 2, 2, 64),
+            v1.calculate(1, 1, 2, 64) - 2 * discount
+        );
+        // More revealed value outputs than outputs are clamped
+        assert_eq!(
+            v2.calculate_with_revealed_value_outputs(1, 1, 2, 5, 64),
+            v2.calculate_with_revealed_value_outputs(1, 1, 2, 2, 64)
+        );
+    }
 }
@@ -20,18 +20,20 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::collections::HashSet;
+use std::collections::HashMap;
 
 use log::warn;
 use tari_common_types::types::FixedHash;
+use tari_script::{Opcode, ScriptContext};
 use tari_utilities::hex::Hex;
 
 use crate::{
+    borsh::SerializedSize,
     chain_storage::BlockchainBackend,
     consensus::{ConsensusConstants, ConsensusManager},
     transactions::{
         aggregated_body::AggregateBody,
-        transaction_components::{TransactionError, TransactionInput, TransactionOutput},
+        transaction_components::{TransactionError, TransactionInput, TransactionKernelVersion, TransactionOutput},
     },
     validation::{
         helpers::{
@@ -101,6 +103,7 @@ impl AggregateBodyChainLinkedValidator {
 
         validate_input_maturity(&body, height)?;
         check_inputs_are_utxos(db, &body)?;
+        check_relative_height_locks(db, &body, constants, height)?;
         check_outputs(db, constants, &body)?;
         verify_no_duplicated_inputs_outputs(&body)?;
         check_total_burned(&body)?;
@@ -252,6 +255,38 @@ fn check_inputs_are_utxos<B: BlockchainBackend>(db: &B, body: &AggregateBody) ->
     Ok(())
 }
 
+/// Scripts with a `CheckRelativeHeightVerify` are run again with the height that their UTXO was mined at, which the
+/// internal consistency validator does not know. An input spending an output of the same body is mined at `height`.
+fn check_relative_height_locks<B: BlockchainBackend>(
+    db: &B,
+    body: &AggregateBody,
+    constants: &ConsensusConstants,
+    height: u64,
+) -> Result<(), ValidationError> {
+    for input in body.inputs() {
+        let script = input.script()?;
+        if !script
+            .as_slice()
+            .iter()
+            .any(|op| matches!(op, Opcode::CheckRelativeHeightVerify(_)))
+        {
+            continue;
+        }
+        let mined_height = db
+            .fetch_output(&input.output_hash())?
+            .map_or(height, |output| output.mined_height);
+        let context = ScriptContext::new(height, &[0u8; 32], input.commitment()?).with_mined_height(mined_height);
+        let script_size = script
+            .get_serialized_size()
+            .map_err(|e| ValidationError::SerializationError(format!("Failed to get serialized script size: {}", e)))?;
+        match constants.script_execution_budget(script_size) {
+            Some(budget) => input.run_script_with_budget(Some(context), budget)?,
+            None => input.run_script(Some(context))?,
+        };
+    }
+    Ok(())
+}
+
 /// This function checks:
 /// 1. that the output type is permitted
 /// 2. the byte size of TariScript does not exceed the maximum
@@ -295,19 +330,52 @@ fn verify_no_duplicated_inputs_outputs(body: &AggregateBody) -> Result<(), Valid
 /// sum.
 #[allow(clippy::mutable_key_type)]
 fn check_total_burned(body: &AggregateBody) -> Result<(), ValidationError> {
-    let mut burned_outputs = HashSet::new();
+    // Maps each burned commitment to the claim public key of the burned output, if it has one
+    let mut burned_outputs = HashMap::new();
     for output in body.outputs() {
         if output.is_burned() {
             // we dont care about duplicate commitments are they should have already been checked
-            burned_outputs.insert(output.commitment.clone());
+            burned_outputs.insert(
+                output.commitment.clone(),
+                output.features.burn_claim_public_key().cloned(),
+            );
         }
     }
     for kernel in body.kernels() {
-        if kernel.is_burned() && !burned_outputs.remove(kernel.get_burn_commitment()?) {
+        if kernel.features.is_claimable_burn() && !kernel.is_burned() {
+            return Err(ValidationError::InvalidBurnError(
+                "Claimable burn kernel is not a burn kernel".to_string(),
+            ));
+        }
+        // Only V1 kernels commit to the claim public key in their signature
+        if kernel.features.is_claimable_burn() && kernel.version == TransactionKernelVersion::V0 {
+            return Err(ValidationError::InvalidBurnError(
+                "Claimable burn kernel must be at least version 1".to_string(),
+            ));
+        }
+        if kernel.features.is_claimable_burn() != kernel.burn_claim_public_key.is_some() {
             return Err(ValidationError::InvalidBurnError(
-                "Burned kernel does not match burned output".to_string(),
+                "Only claimable burn kernels commit to a claim public key".to_string(),
             ));
         }
+        if kernel.is_burned() {
+            match burned_outputs.remove(kernel.get_burn_commitment()?) {
+                None => {
+                    return Err(ValidationError::InvalidBurnError(
+                        "Burned kernel does not match burned output".to_string(),
+                    ));
+                },
+                Some(claim_public_key)
+                    if kernel.features.is_claimable_burn() && claim_public_key != kernel.burn_claim_public_key =>
+                {
+                    return Err(ValidationError::InvalidBurnError(
+                        "Claimable burn kernel does not commit to the claim public key of the burned output"
+                            .to_string(),
+                    ));
+                },
+                Some(_) => {},
+            }
+        }
     }
 
     if !burned_outputs.is_empty() {
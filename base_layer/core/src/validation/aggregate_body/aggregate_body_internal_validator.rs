@@ -20,7 +20,7 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{collections::HashSet, convert::TryInto};
+use std::{collections::HashMap, convert::TryInto};
 
 use log::{trace, warn};
 use tari_common_types::types::{Commitment, CommitmentFactory, HashOutput, PrivateKey, PublicKey, RangeProofService};
@@ -33,22 +33,28 @@ use tari_script::ScriptContext;
 use tari_utilities::hex::Hex;
 
 use crate::{
+    borsh::SerializedSize,
     consensus::{ConsensusConstants, ConsensusManager},
     transactions::{
         aggregated_body::AggregateBody,
         tari_amount::MicroMinotari,
         transaction_components::{
+            batch_verify_kernel_signatures,
+            batch_verify_metadata_signatures,
             transaction_output::batch_verify_range_proofs,
             KernelSum,
             TransactionError,
             TransactionInput,
             TransactionKernel,
+            TransactionKernelVersion,
             TransactionOutput,
         },
         CryptoFactories,
     },
     validation::{
         helpers::{
+            check_application_data,
+            check_code_template_registration,
             check_covenant_length,
             check_permitted_output_types,
             check_permitted_range_proof_types,
@@ -102,6 +108,7 @@ impl AggregateBodyInternalConsistencyValidator {
         prev_header: Option<HashOutput>,
         height: u64,
     ) -> Result<(), ValidationError> {
+        let is_block = total_reward.is_some();
         let total_reward = total_reward.unwrap_or(MicroMinotari::zero());
 
         // old internal validator
@@ -118,6 +125,8 @@ impl AggregateBodyInternalConsistencyValidator {
             check_covenant_length(&output.covenant, constants.max_covenant_length())?;
             check_permitted_range_proof_types(constants, output)?;
             check_validator_node_registration_utxo(constants, output)?;
+            check_code_template_registration(output)?;
+            check_application_data(constants, output)?;
         }
 
         check_weight(body, height, constants)?;
@@ -136,8 +145,15 @@ impl AggregateBodyInternalConsistencyValidator {
         verify_metadata_signatures(body)?;
 
         let script_offset_g = PublicKey::from_secret_key(script_offset);
-        validate_script_and_script_offset(body, script_offset_g, &self.factories.commitment, prev_header, height)?;
-        validate_covenants(body, height)?;
+        validate_script_and_script_offset(
+            body,
+            script_offset_g,
+            &self.factories.commitment,
+            prev_header,
+            height,
+            constants,
+        )?;
+        validate_covenants(body, height, is_block)?;
 
         check_total_burned(body)?;
 
@@ -149,12 +165,11 @@ impl AggregateBodyInternalConsistencyValidator {
 /// will be added to the public key used in the signature verification.
 fn verify_kernel_signatures(body: &AggregateBody) -> Result<(), ValidationError> {
     trace!(target: LOG_TARGET, "Checking kernel signatures",);
-    for kernel in body.kernels() {
-        kernel.verify_signature().map_err(|e| {
-            warn!(target: LOG_TARGET, "Kernel ({}) signature failed {:?}.", kernel, e);
-            e
-        })?;
-    }
+    let kernels = body.kernels().iter().collect::<Vec<_>>();
+    batch_verify_kernel_signatures(&kernels).map_err(|e| {
+        warn!(target: LOG_TARGET, "Kernel signature batch failed {:?}.", e);
+        e
+    })?;
     Ok(())
 }
 
@@ -270,9 +285,8 @@ fn validate_range_proofs(
 
 fn verify_metadata_signatures(body: &AggregateBody) -> Result<(), ValidationError> {
     trace!(target: LOG_TARGET, "Checking sender signatures");
-    for o in body.outputs() {
-        o.verify_metadata_signature()?;
-    }
+    let outputs = body.outputs().iter().collect::<Vec<_>>();
+    batch_verify_metadata_signatures(&outputs)?;
     Ok(())
 }
 
@@ -283,6 +297,7 @@ fn validate_script_and_script_offset(
     factory: &CommitmentFactory,
     prev_header: Option<HashOutput>,
     height: u64,
+    constants: &ConsensusConstants,
 ) -> Result<(), ValidationError> {
     trace!(target: LOG_TARGET, "Checking script and script offset");
     // lets count up the input script public keys
@@ -290,7 +305,17 @@ fn validate_script_and_script_offset(
     let prev_hash: [u8; 32] = prev_header.unwrap_or_default().as_slice().try_into().unwrap_or([0; 32]);
     for input in body.inputs() {
         let context = ScriptContext::new(height, &prev_hash, input.commitment()?);
-        input_keys = input_keys + input.run_and_verify_script(factory, Some(context))?;
+        let script_size = input
+            .script()?
+            .get_serialized_size()
+            .map_err(|e| ValidationError::SerializationError(format!("Failed to get serialized script size: {}", e)))?;
+        // Scripts are not metered below the height that the execution budget was activated at
+        let script_key = match constants.script_execution_budget(script_size) {
+            Some(budget) => input.run_script_with_budget(Some(context), budget)?,
+            None => input.run_script(Some(context))?,
+        };
+        input.validate_script_signature(&script_key, factory)?;
+        input_keys = input_keys + script_key;
     }
 
     // Now lets gather the output public keys and hashes.
@@ -308,9 +333,17 @@ fn validate_script_and_script_offset(
     Ok(())
 }
 
-fn validate_covenants(body: &AggregateBody, height: u64) -> Result<(), ValidationError> {
+/// The inputs of a transaction are spent by its own kernels, so covenants see their total fee. The inputs of a block
+/// cannot be tied to the kernels of the transactions that spent them, so the largest kernel fee in the block stands in
+/// for the spending transaction's fee, which a single kernel transaction can never exceed.
+fn validate_covenants(body: &AggregateBody, height: u64, is_block: bool) -> Result<(), ValidationError> {
+    let spending_fee = if is_block {
+        body.max_kernel_fee()
+    } else {
+        body.get_total_fee()?
+    };
     for input in body.inputs() {
-        input.covenant()?.execute(height, input, body.outputs())?;
+        input.covenant()?.execute(height, input, body.outputs(), spending_fee)?;
     }
     Ok(())
 }
@@ -368,19 +401,52 @@ fn check_maturity(height: u64, inputs: &[TransactionInput]) -> Result<(), Transa
 /// sum.
 #[allow(clippy::mutable_key_type)]
 fn check_total_burned(body: &AggregateBody) -> Result<(), ValidationError> {
-    let mut burned_outputs = HashSet::new();
+    // Maps each burned commitment to the claim public key of the burned output, if it has one
+    let mut burned_outputs = HashMap::new();
     for output in body.outputs() {
         if output.is_burned() {
             // we dont care about duplicate commitments are they should have already been checked
-            burned_outputs.insert(output.commitment.clone());
+            burned_outputs.insert(
+                output.commitment.clone(),
+                output.features.burn_claim_public_key().cloned(),
+            );
         }
     }
     for kernel in body.kernels() {
-        if kernel.is_burned() && !burned_outputs.remove(kernel.get_burn_commitment()?) {
+        if kernel.features.is_claimable_burn() && !kernel.is_burned() {
+            return Err(ValidationError::InvalidBurnError(
+                "Claimable burn kernel is not a burn kernel".to_string(),
+            ));
+        }
+        // Only V1 kernels commit to the claim public key in their signature
+        if kernel.features.is_claimable_burn() && kernel.version == TransactionKernelVersion::V0 {
+            return Err(ValidationError::InvalidBurnError(
+                "Claimable burn kernel must be at least version 1".to_string(),
+            ));
+        }
+        if kernel.features.is_claimable_burn() != kernel.burn_claim_public_key.is_some() {
             return Err(ValidationError::InvalidBurnError(
-                "Burned kernel does not match burned output".to_string(),
+                "Only claimable burn kernels commit to a claim public key".to_string(),
             ));
         }
+        if kernel.is_burned() {
+            match burned_outputs.remove(kernel.get_burn_commitment()?) {
+                None => {
+                    return Err(ValidationError::InvalidBurnError(
+                        "Burned kernel does not match burned output".to_string(),
+                    ));
+                },
+                Some(claim_public_key)
+                    if kernel.features.is_claimable_burn() && claim_public_key != kernel.burn_claim_public_key =>
+                {
+                    return Err(ValidationError::InvalidBurnError(
+                        "Claimable burn kernel does not commit to the claim public key of the burned output"
+                            .to_string(),
+                    ));
+                },
+                Some(_) => {},
+            }
+        }
     }
 
     if !burned_outputs.is_empty() {
@@ -440,13 +506,14 @@ mod test {
     use std::iter;
 
     use futures::StreamExt;
-    use rand::seq::SliceRandom;
+    use rand::{rngs::OsRng, seq::SliceRandom};
     use tari_common::configuration::Network;
     use tari_common_types::types::RANGE_PROOF_AGGREGATION_FACTOR;
     use tari_script::script;
 
     use super::*;
     use crate::{
+        covenant,
         covenants::Covenant,
         transactions::{
             key_manager::create_memory_db_key_manager,
@@ -564,6 +631,106 @@ mod test {
         assert!(check_total_burned(&body2).is_err());
     }
 
+    #[tokio::test]
+    async fn check_burned_requires_claim_key_for_claimable_burns() {
+        let key_manager = create_memory_db_key_manager().unwrap();
+        let (_, claim_public_key) = PublicKey::random_keypair(&mut OsRng);
+        let (claimable_output, _, _) = test_helpers::create_utxo(
+            100.into(),
+            &key_manager,
+            &OutputFeatures::create_burn_confidential_output(claim_public_key.clone()),
+            &script!(Nop).unwrap(),
+            &Covenant::default(),
+            0.into(),
+        )
+        .await;
+        let (plain_output, _, _) = test_helpers::create_utxo(
+            101.into(),
+            &key_manager,
+            &OutputFeatures::create_burn_output(),
+            &script!(Nop).unwrap(),
+            &Covenant::default(),
+            0.into(),
+        )
+        .await;
+
+        let mut kernel = test_helpers::create_test_kernel(0.into(), 0, KernelFeatures::create_claimable_burn());
+        kernel.burn_commitment = Some(claimable_output.commitment.clone());
+        kernel.version = TransactionKernelVersion::V1;
+        kernel.burn_claim_public_key = Some(claim_public_key);
+        let body = AggregateBody::new(Vec::new(), vec![claimable_output.clone()], vec![kernel.clone()]);
+        assert!(check_total_burned(&body).is_ok());
+
+        // The kernel must be at least version 1, so that its signature commits to the claim key
+        let mut v0_kernel = kernel.clone();
+        v0_kernel.version = TransactionKernelVersion::V0;
+        let body = AggregateBody::new(Vec::new(), vec![claimable_output.clone()], vec![v0_kernel]);
+        assert!(check_total_burned(&body).is_err());
+
+        // The kernel must commit to the claim key of the burned output
+        let mut other_key_kernel = kernel.clone();
+        other_key_kernel.burn_claim_public_key = Some(PublicKey::random_keypair(&mut OsRng).1);
+        let body = AggregateBody::new(Vec::new(), vec![claimable_output.clone()], vec![other_key_kernel]);
+        assert!(check_total_burned(&body).is_err());
+
+        // A plain burn kernel may still burn an output with a claim key
+        let mut plain_kernel = test_helpers::create_test_kernel(0.into(), 0, KernelFeatures::create_burn());
+        plain_kernel.burn_commitment = Some(claimable_output.commitment.clone());
+        let body = AggregateBody::new(Vec::new(), vec![claimable_output], vec![plain_kernel]);
+        assert!(check_total_burned(&body).is_ok());
+
+        // A claimable burn kernel must burn an output with a claim key
+        kernel.burn_commitment = Some(plain_output.commitment.clone());
+        let body = AggregateBody::new(Vec::new(), vec![plain_output.clone()], vec![kernel]);
+        assert!(check_total_burned(&body).is_err());
+
+        // The claimable flag is only valid on burn kernels
+        let mut kernel = test_helpers::create_test_kernel(0.into(), 0, KernelFeatures::BURN_CLAIMABLE);
+        kernel.version = TransactionKernelVersion::V1;
+        kernel.burn_claim_public_key = Some(PublicKey::random_keypair(&mut OsRng).1);
+        kernel.burn_commitment = Some(plain_output.commitment.clone());
+        let body = AggregateBody::new(Vec::new(), vec![], vec![kernel]);
+        assert!(check_total_burned(&body).is_err());
+    }
+
+    #[tokio::test]
+    async fn fee_ceiling_covenants_see_the_spending_transaction_fee() {
+        let key_manager = create_memory_db_key_manager().unwrap();
+        let params = test_helpers::TestParams::new(&key_manager).await;
+        let input = params
+            .create_output(
+                test_helpers::UtxoTestParams {
+                    covenant: covenant!(fee_ceiling(@uint(100))).unwrap(),
+                    ..Default::default()
+                },
+                &key_manager,
+            )
+            .await
+            .unwrap()
+            .to_transaction_input(&key_manager)
+            .await
+            .unwrap();
+        let output = TransactionOutput::default();
+        let kernel_with_fee = |fee: u64| test_helpers::create_test_kernel(fee.into(), 0, KernelFeatures::empty());
+
+        // A transaction is held to the total fee of its kernels
+        let body = AggregateBody::new(vec![input.clone()], vec![output.clone()], vec![kernel_with_fee(60)]);
+        assert!(validate_covenants(&body, 0, false).is_ok());
+        let body = AggregateBody::new(vec![input.clone()], vec![output.clone()], vec![
+            kernel_with_fee(60),
+            kernel_with_fee(60),
+        ]);
+        assert!(validate_covenants(&body, 0, false).is_err());
+
+        // A block is held to its largest kernel fee rather than the fees of every transaction in it
+        assert!(validate_covenants(&body, 0, true).is_ok());
+        let body = AggregateBody::new(vec![input], vec![output], vec![
+            kernel_with_fee(60),
+            kernel_with_fee(101),
+        ]);
+        assert!(validate_covenants(&body, 0, true).is_err());
+    }
+
     mod transaction_ordering {
         use super::*;
 
@@ -25,6 +25,7 @@ use std::sync::{Arc, RwLock};
 use log::error;
 use tari_common_types::chain_metadata::ChainMetadata;
 use tari_utilities::hex::Hex;
+use tracing::instrument;
 
 use super::BlockBodyInternalConsistencyValidator;
 use crate::{
@@ -63,6 +64,13 @@ impl BlockBodyFullValidator {
         }
     }
 
+    #[instrument(
+        name = "block_body_full_validator::validate",
+        level = "debug",
+        skip_all,
+        err,
+        fields(height = block.header.height)
+    )]
     pub fn validate<B: BlockchainBackend>(
         &self,
         backend: &B,
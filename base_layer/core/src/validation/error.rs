@@ -125,6 +125,12 @@ pub enum ValidationError {
     ValidatorNodeRegistrationMinLockHeight { min: u64, actual: u64 },
     #[error("Validator node registration signature failed verification")]
     InvalidValidatorNodeSignature,
+    #[error("Code template registration is not signed by its author")]
+    InvalidTemplateRegistrationSignature,
+    #[error("Invalid code template registration: {0}")]
+    InvalidTemplateRegistration(String),
+    #[error("Invalid application data: {0}")]
+    InvalidApplicationData(String),
     #[error(
         "An unexpected number of timestamps were provided to the header validator. THIS IS A BUG. Expected \
          {expected}, got {actual}"
@@ -182,6 +188,9 @@ impl ValidationError {
             err @ ValidationError::ValidatorNodeRegistrationMinDepositAmount { .. } |
             err @ ValidationError::ValidatorNodeRegistrationMinLockHeight { .. } |
             err @ ValidationError::InvalidValidatorNodeSignature |
+            err @ ValidationError::InvalidTemplateRegistrationSignature |
+            err @ ValidationError::InvalidTemplateRegistration(_) |
+            err @ ValidationError::InvalidApplicationData(_) |
             err @ ValidationError::DifficultyError(_) |
             err @ ValidationError::CoinbaseExceedsMaxLimit |
             err @ ValidationError::CovenantTooLarge { .. } => Some(BanReason {
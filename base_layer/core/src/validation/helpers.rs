@@ -45,6 +45,7 @@ use crate::{
     transactions::transaction_components::{
         encrypted_data::STATIC_ENCRYPTED_DATA_SIZE_TOTAL,
         EncryptedData,
+        OutputType,
         TransactionInput,
         TransactionKernel,
         TransactionOutput,
@@ -379,6 +380,80 @@ pub fn check_permitted_output_types(
     Ok(())
 }
 
+/// Checks that a code template registration is signed by its author and identifies its binary by the full hash and
+/// the URL it is published at
+pub fn check_code_template_registration(output: &TransactionOutput) -> Result<(), ValidationError> {
+    if let Some(reg) = output.features.code_template_registration() {
+        if output.features.output_type != OutputType::CodeTemplateRegistration {
+            return Err(ValidationError::InvalidTemplateRegistration(format!(
+                "output type is {}",
+                output.features.output_type
+            )));
+        }
+        if reg.template_name.is_empty() {
+            return Err(ValidationError::InvalidTemplateRegistration(
+                "template name is empty".to_string(),
+            ));
+        }
+        if reg.binary_sha.len() != 32 {
+            return Err(ValidationError::InvalidTemplateRegistration(format!(
+                "binary hash is {} bytes, expected 32",
+                reg.binary_sha.len()
+            )));
+        }
+        if reg.binary_url.is_empty() {
+            return Err(ValidationError::InvalidTemplateRegistration(
+                "binary URL is empty".to_string(),
+            ));
+        }
+        if !reg.is_valid_signature() {
+            return Err(ValidationError::InvalidTemplateRegistrationSignature);
+        }
+    }
+    Ok(())
+}
+
+/// Checks that application data is carried by a standard output, for a permitted application identifier, and within
+/// the permitted size
+pub fn check_application_data(
+    constants: &ConsensusConstants,
+    output: &TransactionOutput,
+) -> Result<(), ValidationError> {
+    let Some(data) = output.features.application_data() else {
+        return Ok(());
+    };
+    if output.features.output_type != OutputType::Standard {
+        return Err(ValidationError::InvalidApplicationData(format!(
+            "output type is {}",
+            output.features.output_type
+        )));
+    }
+    match constants.permitted_application_ids() {
+        None => {
+            return Err(ValidationError::InvalidApplicationData(
+                "application data is not permitted".to_string(),
+            ))
+        },
+        Some(ids) if !ids.contains(&data.application_id) => {
+            return Err(ValidationError::InvalidApplicationData(format!(
+                "application id {} is not in the permitted range {}..={}",
+                data.application_id,
+                ids.start(),
+                ids.end()
+            )))
+        },
+        Some(_) => {},
+    }
+    if data.payload.len() > constants.max_application_data_byte_size() {
+        return Err(ValidationError::InvalidApplicationData(format!(
+            "payload is {} bytes, the maximum is {}",
+            data.payload.len(),
+            constants.max_application_data_byte_size()
+        )));
+    }
+    Ok(())
+}
+
 pub fn check_covenant_length(covenant: &Covenant, max_token_len: u32) -> Result<(), ValidationError> {
     if covenant.num_tokens() > max_token_len as usize {
         return Err(ValidationError::CovenantTooLarge {
@@ -472,6 +547,19 @@ pub fn validate_output_version(
         }
     }
 
+    let covenant_version = output.covenant.get_version();
+    if !consensus_constants
+        .output_version_range()
+        .covenant
+        .contains(&covenant_version)
+    {
+        let msg = format!(
+            "Transaction output covenant version is not allowed by consensus ({:?})",
+            covenant_version
+        );
+        return Err(ValidationError::ConsensusError(msg));
+    }
+
     Ok(())
 }
 
@@ -560,6 +648,117 @@ mod test {
         }
     }
 
+    mod check_code_template_registration {
+        use rand::rngs::OsRng;
+        use tari_common_types::types::PrivateKey;
+        use tari_crypto::keys::SecretKey;
+
+        use super::*;
+        use crate::transactions::transaction_components::{
+            BuildInfo,
+            CodeTemplateRegistration,
+            OutputFeatures,
+            SideChainFeature,
+            TemplateType,
+        };
+
+        fn create_output(sign: bool) -> TransactionOutput {
+            let mut reg = CodeTemplateRegistration {
+                author_public_key: Default::default(),
+                author_signature: Default::default(),
+                template_name: "test".try_into().unwrap(),
+                template_version: 1,
+                template_type: TemplateType::Wasm { abi_version: 1 },
+                build_info: BuildInfo {
+                    repo_url: "https://github.com/tari-project/tari.git".try_into().unwrap(),
+                    commit_hash: vec![1u8; 32].try_into().unwrap(),
+                },
+                binary_sha: vec![2u8; 32].try_into().unwrap(),
+                binary_url: "https://example.com/test.wasm".try_into().unwrap(),
+            };
+            if sign {
+                reg.sign(&PrivateKey::random(&mut OsRng));
+            }
+            TransactionOutput {
+                features: OutputFeatures::for_template_registration(reg),
+                ..Default::default()
+            }
+        }
+
+        #[test]
+        fn it_accepts_a_signed_registration() {
+            check_code_template_registration(&create_output(true)).unwrap();
+            check_code_template_registration(&TransactionOutput::default()).unwrap();
+        }
+
+        #[test]
+        fn it_rejects_an_unsigned_registration() {
+            let err = check_code_template_registration(&create_output(false)).unwrap_err();
+            assert!(matches!(err, ValidationError::InvalidTemplateRegistrationSignature));
+        }
+
+        #[test]
+        fn it_rejects_a_truncated_binary_hash() {
+            let mut output = create_output(true);
+            if let Some(SideChainFeature::CodeTemplateRegistration(reg)) = output.features.sidechain_feature.as_mut() {
+                reg.binary_sha = vec![2u8; 16].try_into().unwrap();
+            }
+            let err = check_code_template_registration(&output).unwrap_err();
+            assert!(matches!(err, ValidationError::InvalidTemplateRegistration(_)));
+        }
+    }
+
+    mod check_application_data {
+        use tari_common::configuration::Network;
+
+        use super::*;
+        use crate::{
+            consensus::ConsensusConstantsBuilder,
+            transactions::transaction_components::{OutputFeatures, OutputType},
+        };
+
+        fn create_output(application_id: u32, payload_size: usize) -> TransactionOutput {
+            TransactionOutput {
+                features: OutputFeatures::for_application_data(
+                    application_id,
+                    vec![1u8; payload_size].try_into().unwrap(),
+                ),
+                ..Default::default()
+            }
+        }
+
+        fn constants() -> ConsensusConstants {
+            ConsensusConstantsBuilder::new(Network::LocalNet)
+                .with_application_data(Some(100..=200), 32)
+                .build()
+        }
+
+        #[test]
+        fn it_accepts_permitted_application_data() {
+            check_application_data(&constants(), &create_output(100, 32)).unwrap();
+            check_application_data(&constants(), &TransactionOutput::default()).unwrap();
+        }
+
+        #[test]
+        fn it_rejects_invalid_application_data() {
+            let err = check_application_data(&constants(), &create_output(99, 1)).unwrap_err();
+            assert!(matches!(err, ValidationError::InvalidApplicationData(_)));
+            let err = check_application_data(&constants(), &create_output(200, 33)).unwrap_err();
+            assert!(matches!(err, ValidationError::InvalidApplicationData(_)));
+
+            let mut output = create_output(150, 1);
+            output.features.output_type = OutputType::Burn;
+            let err = check_application_data(&constants(), &output).unwrap_err();
+            assert!(matches!(err, ValidationError::InvalidApplicationData(_)));
+
+            let disabled = ConsensusConstantsBuilder::new(Network::LocalNet)
+                .with_application_data(None, 32)
+                .build();
+            let err = check_application_data(&disabled, &create_output(150, 1)).unwrap_err();
+            assert!(matches!(err, ValidationError::InvalidApplicationData(_)));
+        }
+    }
+
     mod check_coinbase_maturity {
         use futures::executor::block_on;
 
@@ -98,7 +98,7 @@ fn test_kernel_order() {
         let pub_key = PublicKey::from_secret_key(&pvt_key);
         let commitment = Commitment::from_public_key(&pub_key);
         let sig = Signature::new(pub_key, pvt_key);
-        let kernel = TransactionKernel::new(version, features, 0.into(), 0, commitment, sig, None);
+        let kernel = TransactionKernel::new(version, features, 0.into(), 0, commitment, sig, None, None);
         kernels.push(kernel);
     }
     kernels.sort();
@@ -95,6 +95,7 @@ pub async fn create_coinbase(
         0,
         &kernel_features,
         &None,
+        &None,
     );
 
     let sig = key_manager
@@ -394,6 +394,7 @@ pub fn state_event(event: &StateEvent) -> String {
         StateEvent::BlockSyncFailed => "BlockSyncFailed".to_string(),
         StateEvent::FallenBehind(_) => "FallenBehind".to_string(),
         StateEvent::NetworkSilence => "NetworkSilence".to_string(),
+        StateEvent::NetworkAlert(_) => "NetworkAlert".to_string(),
         StateEvent::FatalError(_) => "FatalError".to_string(),
         StateEvent::Continue => "Continue".to_string(),
         StateEvent::UserQuit => "UserQuit".to_string(),
@@ -414,6 +414,7 @@ async fn test_sync_utxos_by_block() {
     let msg = SyncUtxosByBlockRequest {
         start_header_hash: block0.header().hash().to_vec(),
         end_header_hash: block3.header.hash().to_vec(),
+        require_full_outputs: false,
     };
 
     let req = request_mock.request_with_context(Default::default(), msg);
@@ -440,6 +441,7 @@ async fn test_sync_utxos_by_block() {
     let msg = SyncUtxosByBlockRequest {
         start_header_hash: block1.header.hash().to_vec(),
         end_header_hash: block2.header.hash().to_vec(),
+        require_full_outputs: false,
     };
 
     let req = request_mock.request_with_context(Default::default(), msg);
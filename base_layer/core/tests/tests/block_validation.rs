@@ -1333,6 +1333,7 @@ async fn test_fee_overflow() {
                         excess_sig: kernel.excess_sig.clone(),
                         excess: kernel.excess.clone(),
                         burn_commitment: kernel.burn_commitment.clone(),
+                        burn_claim_public_key: kernel.burn_claim_public_key.clone(),
                     });
                 }
                 AggregateBody::new(inputs, outputs, kernels)
@@ -47,6 +47,7 @@ async fn test_initial_horizon_sync_from_archival_node_happy_path() {
                 orphan_storage_capacity: 5,
                 pruning_horizon,
                 pruning_interval: 5,
+                output_retention_window: 0,
                 track_reorgs: false,
                 cleanup_orphans_at_startup: false,
             },
@@ -300,6 +301,7 @@ async fn test_consecutive_horizon_sync_from_prune_node_happy_path() {
                 orphan_storage_capacity: 5,
                 pruning_horizon: pruning_horizon_alice,
                 pruning_interval: 5,
+                output_retention_window: 0,
                 track_reorgs: false,
                 cleanup_orphans_at_startup: false,
             },
@@ -308,6 +310,7 @@ async fn test_consecutive_horizon_sync_from_prune_node_happy_path() {
                 orphan_storage_capacity: 5,
                 pruning_horizon: pruning_horizon_carol,
                 pruning_interval: 5,
+                output_retention_window: 0,
                 track_reorgs: false,
                 cleanup_orphans_at_startup: false,
             },
@@ -678,6 +681,7 @@ async fn test_initial_horizon_sync_from_prune_node_happy_path() {
                 orphan_storage_capacity: 5,
                 pruning_horizon: pruning_horizon_alice,
                 pruning_interval: 5,
+                output_retention_window: 0,
                 track_reorgs: false,
                 cleanup_orphans_at_startup: false,
             },
@@ -686,6 +690,7 @@ async fn test_initial_horizon_sync_from_prune_node_happy_path() {
                 orphan_storage_capacity: 5,
                 pruning_horizon: pruning_horizon_carol,
                 pruning_interval: 5,
+                output_retention_window: 0,
                 track_reorgs: false,
                 cleanup_orphans_at_startup: false,
             },
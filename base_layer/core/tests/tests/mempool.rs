@@ -1266,6 +1266,7 @@ async fn consensus_validation_large_tx() {
         tx_meta.lock_height,
         &tx_meta.kernel_features,
         &tx_meta.burn_commitment,
+        &tx_meta.burn_claim_public_key,
     );
     for (output, nonce_id) in wallet_outputs {
         outputs.push(output.to_transaction_output(&key_manager).await.unwrap());
@@ -1427,6 +1428,7 @@ async fn validation_reject_min_fee() {
         tx_meta.lock_height,
         &tx_meta.kernel_features,
         &tx_meta.burn_commitment,
+        &tx_meta.burn_claim_public_key,
     );
 
     let tx_output = wallet_output.to_transaction_output(&key_manager).await.unwrap();
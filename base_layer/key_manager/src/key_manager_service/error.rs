@@ -65,6 +65,10 @@ pub enum KeyManagerServiceError {
     StorageError(#[from] StorageError),
     #[error("The imported private key cannot be accessed or read: `{0}")]
     ImportedPrivateKeyInaccessible(String),
+    #[error("Remote signer error: `{0}`")]
+    RemoteSignerError(String),
+    #[error("The private key is held by the remote signer and cannot be accessed or read: `{0}`")]
+    RemoteSignerPrivateKeyInaccessible(String),
 }
 
 impl From<RangeProofError> for KeyManagerServiceError {
@@ -39,4 +39,6 @@ pub trait KeyManagerBackend<PK: PublicKey>: Send + Sync + Clone {
     fn insert_imported_key(&self, public_key: PK, private_key: PK::K) -> Result<(), KeyManagerStorageError>;
     /// This method will retrieve  public private key pair from the database
     fn get_imported_key(&self, public_key: &PK) -> Result<PK::K, KeyManagerStorageError>;
+    /// This method will retrieve the public keys of all the imported key pairs in the database
+    fn get_imported_public_keys(&self) -> Result<Vec<PK>, KeyManagerStorageError>;
 }
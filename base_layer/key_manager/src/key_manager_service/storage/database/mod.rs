@@ -96,4 +96,9 @@ where
     pub fn get_imported_key(&self, public_key: &PK) -> Result<PK::K, KeyManagerStorageError> {
         self.db.get_imported_key(public_key)
     }
+
+    /// Retrieves the public keys of all imported keys
+    pub fn get_imported_public_keys(&self) -> Result<Vec<PK>, KeyManagerStorageError> {
+        self.db.get_imported_public_keys()
+    }
 }
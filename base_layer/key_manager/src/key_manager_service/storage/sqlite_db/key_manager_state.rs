@@ -68,8 +68,13 @@ impl TryFrom<KeyManagerStateSql> for KeyManagerState {
     type Error = KeyManagerStorageError;
 
     fn try_from(km: KeyManagerStateSql) -> Result<Self, Self::Error> {
-        let mut bytes: [u8; 8] = [0u8; 8];
-        bytes.copy_from_slice(&km.primary_key_index[..8]);
+        let bytes: [u8; 8] = km
+            .primary_key_index
+            .get(..8)
+            .and_then(|b| b.try_into().ok())
+            .ok_or_else(|| KeyManagerStorageError::ConversionError {
+                reason: "Primary key index is shorter than 8 bytes".to_string(),
+            })?;
         Ok(Self {
             branch_seed: km.branch_seed,
             primary_key_index: u64::from_le_bytes(bytes),
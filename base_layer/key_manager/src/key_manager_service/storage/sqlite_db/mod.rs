@@ -26,10 +26,14 @@ use chacha20poly1305::XChaCha20Poly1305;
 use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
 pub use key_manager_state::{KeyManagerStateSql, NewKeyManagerStateSql};
 use log::*;
-use tari_common_sqlite::{error::SqliteStorageError, sqlite_connection_pool::PooledDbConnection};
+use tari_common_sqlite::{
+    error::SqliteStorageError,
+    integrity::{IntegrityIssue, IntegrityReport},
+    sqlite_connection_pool::PooledDbConnection,
+};
 use tari_common_types::encryption::Encryptable;
 use tari_crypto::keys::PublicKey;
-use tari_utilities::acquire_read_lock;
+use tari_utilities::{acquire_read_lock, hex::Hex};
 use tokio::time::Instant;
 
 use crate::key_manager_service::{
@@ -88,6 +92,43 @@ impl<TKeyManagerDbConnection: PooledDbConnection<Error = SqliteStorageError> + C
             })
             .map_err(|e| SqliteStorageError::DieselR2d2Error(e.to_string()))
     }
+
+    /// Checks that every encrypted field can be authenticated and decrypted, and that every imported private key
+    /// matches its public key. Inconsistent rows are only reported: they hold key material, and moving them out of
+    /// the way could lead to key reuse or lost funds.
+    pub fn verify_integrity<PK: PublicKey>(&self) -> Result<IntegrityReport, KeyManagerStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        let cipher = acquire_read_lock!(self.cipher);
+        let mut report = IntegrityReport::default();
+
+        for state in KeyManagerStateSql::index(&mut conn)? {
+            report.rows_checked += 1;
+            let id = state.id;
+            let result = state
+                .decrypt(&cipher)
+                .map_err(KeyManagerStorageError::AeadError)
+                .and_then(KeyManagerState::try_from);
+            if let Err(e) = result {
+                report.issues.push(IntegrityIssue::new("key_manager_states", id, e));
+            }
+        }
+
+        for key in ImportedKeySql::index(&mut conn)? {
+            report.rows_checked += 1;
+            let id = key.id;
+            match key.to_imported_key::<PK>(&cipher) {
+                Ok(imported) if PK::from_secret_key(&imported.private_key) == imported.public_key => {},
+                Ok(_) => report.issues.push(IntegrityIssue::new(
+                    "imported_keys",
+                    id,
+                    "The private key does not match the public key",
+                )),
+                Err(e) => report.issues.push(IntegrityIssue::new("imported_keys", id, e)),
+            }
+        }
+
+        Ok(report)
+    }
 }
 
 impl<TKeyManagerDbConnection, PK> KeyManagerBackend<PK> for KeyManagerSqliteDatabase<TKeyManagerDbConnection>
@@ -252,6 +293,15 @@ where
 
         Ok(unencrypted_key.private_key)
     }
+
+    fn get_imported_public_keys(&self) -> Result<Vec<PK>, KeyManagerStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        // The public keys are not encrypted
+        ImportedKeySql::index(&mut conn)?
+            .iter()
+            .map(|key| PK::from_hex(&key.public_key).map_err(Into::into))
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -259,7 +259,10 @@ mod test {
                     hashes_sig_url:
                         "https://raw.githubusercontent.com/tari-project/tari/development/meta/hashes.txt.sig"
                             .to_string(),
+                    manifest_url: String::new(),
+                    manifest_sig_url: String::new(),
                     check_interval: Some(Duration::from_secs(30)),
+                    staging_dir: None,
                 }
             }
         }
@@ -30,4 +30,12 @@ pub enum AutoUpdateError {
     DownloadError(#[from] reqwest::Error),
     #[error("Failed to verify signature: {0}")]
     SignatureError(#[from] pgp::errors::Error),
+    #[error("The release manifest is not signed by a maintainer")]
+    ManifestNotSigned,
+    #[error("Invalid release manifest: {0}")]
+    InvalidManifest(String),
+    #[error("The downloaded file does not match the signed hash")]
+    HashMismatch,
+    #[error("Failed to stage the update: {0}")]
+    StagingError(#[from] std::io::Error),
 }
@@ -0,0 +1,132 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! A signed release manifest as an alternative to DNS TXT update records.
+//!
+//! The manifest is a JSON document listing the released binaries, which is checked against a detached maintainer PGP
+//! signature before any of its contents are used:
+//!
+//! ```json
+//! {
+//!   "releases": [
+//!     {
+//!       "application": "base-node",
+//!       "arch": "linux-x86_64",
+//!       "version": "1.8.0",
+//!       "hash": "<hex SHA-256 of the file>",
+//!       "file": "minotari_suite-1.8.0-linux-x86_64.zip"
+//!     }
+//!   ]
+//! }
+//! ```
+
+use serde::Deserialize;
+use tari_common::configuration::bootstrap::ApplicationType;
+use tari_utilities::hex::from_hex;
+
+use super::{dns::UpdateSpec, error::AutoUpdateError, Version};
+
+#[derive(Debug, Clone, Deserialize)]
+struct ReleaseManifest {
+    releases: Vec<ManifestRelease>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ManifestRelease {
+    application: String,
+    arch: String,
+    version: String,
+    hash: String,
+    file: String,
+}
+
+/// Returns the newest release in the manifest for the application and architecture that is newer than
+/// `current_version`, and the name of its file. The manifest signature must already have been verified.
+pub fn find_update(
+    manifest: &str,
+    app: ApplicationType,
+    arch: &str,
+    current_version: &Version,
+) -> Result<Option<(UpdateSpec, String)>, AutoUpdateError> {
+    let manifest = serde_json::from_str::<ReleaseManifest>(manifest)
+        .map_err(|e| AutoUpdateError::InvalidManifest(e.to_string()))?;
+    let mut best = None::<(UpdateSpec, String)>;
+    for release in manifest.releases {
+        // Releases of other applications may use names this version does not know
+        if release.application.parse::<ApplicationType>().ok() != Some(app) || release.arch != arch {
+            continue;
+        }
+        let version = release
+            .version
+            .parse::<Version>()
+            .map_err(|e| AutoUpdateError::InvalidManifest(format!("Invalid version `{}`: {}", release.version, e)))?;
+        if version <= *current_version || best.as_ref().map_or(false, |(spec, _)| spec.version >= version) {
+            continue;
+        }
+        let hash = from_hex(&release.hash)
+            .map_err(|e| AutoUpdateError::InvalidManifest(format!("Invalid hash for version {}: {}", version, e)))?;
+        validate_file_name(&release.file)?;
+        best = Some((
+            UpdateSpec {
+                application: app,
+                arch: release.arch,
+                version,
+                hash,
+            },
+            release.file,
+        ));
+    }
+    Ok(best)
+}
+
+/// File names are joined to the download URL and the staging directory, so they may not contain a path
+pub fn validate_file_name(file: &str) -> Result<(), AutoUpdateError> {
+    if file.is_empty() || file == "." || file == ".." || file.contains(['/', '\\']) {
+        return Err(AutoUpdateError::InvalidManifest(format!(
+            "Invalid file name `{}`",
+            file
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const MANIFEST: &str = r#"{
+        "releases": [
+            { "application": "base-node", "arch": "linux-x86_64", "version": "1.8.0", "hash": "0a0b", "file": "a.zip" },
+            { "application": "base-node", "arch": "linux-x86_64", "version": "1.9.0", "hash": "0c0d", "file": "b.zip" },
+            { "application": "base-node", "arch": "windows-x86_64", "version": "2.0.0", "hash": "0e0f", "file": "c.zip" },
+            { "application": "future-app", "arch": "linux-x86_64", "version": "3.0.0", "hash": "1011", "file": "d.zip" }
+        ]
+    }"#;
+
+    #[test]
+    fn it_finds_the_newest_matching_release() {
+        let current = "1.7.0".parse().unwrap();
+        let (spec, file) = find_update(MANIFEST, ApplicationType::BaseNode, "linux-x86_64", &current)
+            .unwrap()
+            .unwrap();
+        assert_eq!(spec.version, Version::new(1, 9, 0));
+        assert_eq!(spec.hash, vec![0x0c, 0x0d]);
+        assert_eq!(file, "b.zip");
+
+        let current = "1.9.0".parse().unwrap();
+        assert!(
+            find_update(MANIFEST, ApplicationType::BaseNode, "linux-x86_64", &current)
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn it_rejects_file_names_with_a_path() {
+        let manifest = r#"{ "releases": [
+            { "application": "miner", "arch": "x", "version": "9.0.0", "hash": "00", "file": "../../bin/sh" }
+        ] }"#;
+        let current = "1.0.0".parse().unwrap();
+        assert!(find_update(manifest, ApplicationType::Miner, "x", &current).is_err());
+    }
+}
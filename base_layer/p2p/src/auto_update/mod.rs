@@ -21,16 +21,18 @@
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 mod dns;
+mod manifest;
 mod signature;
 
 mod service;
-pub use service::{SoftwareUpdaterHandle, SoftwareUpdaterService};
+pub use service::{SoftwareUpdateNotifier, SoftwareUpdaterHandle, SoftwareUpdaterService};
 
 mod error;
 use std::{
     fmt,
     fmt::{Display, Formatter},
     io,
+    path::{Path, PathBuf},
     str::FromStr,
     time::Duration,
 };
@@ -42,6 +44,7 @@ use reqwest::IntoUrl;
 // Re-exports of foreign types used in public interface
 pub use semver::Version;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use tari_common::{
     configuration::{
         bootstrap::ApplicationType,
@@ -72,8 +75,14 @@ pub struct AutoUpdateConfig {
     pub download_base_url: String,
     pub hashes_url: String,
     pub hashes_sig_url: String,
+    /// A signed JSON release manifest, which is used instead of the DNS update records if set
+    pub manifest_url: String,
+    pub manifest_sig_url: String,
     #[serde(with = "optional_seconds")]
     pub check_interval: Option<Duration>,
+    /// New releases are downloaded to this directory and checked against their signed hash, if set. They are never
+    /// run or installed.
+    pub staging_dir: Option<PathBuf>,
 }
 
 impl Default for AutoUpdateConfig {
@@ -86,7 +95,10 @@ impl Default for AutoUpdateConfig {
             download_base_url: String::new(),
             hashes_url: String::new(),
             hashes_sig_url: String::new(),
+            manifest_url: String::new(),
+            manifest_sig_url: String::new(),
             check_interval: None,
+            staging_dir: None,
         }
     }
 }
@@ -99,7 +111,7 @@ impl SubConfigPath for AutoUpdateConfig {
 
 impl AutoUpdateConfig {
     pub fn is_update_enabled(&self) -> bool {
-        !self.update_uris.is_empty()
+        !self.update_uris.is_empty() || !self.manifest_url.is_empty()
     }
 }
 
@@ -109,6 +121,9 @@ pub async fn check_for_updates(
     version: &Version,
     config: AutoUpdateConfig,
 ) -> Result<Option<SoftwareUpdate>, AutoUpdateError> {
+    if !config.manifest_url.is_empty() {
+        return check_manifest_for_updates(app, arch, version, &config).await;
+    }
     let download_base_url = config.download_base_url.clone();
     let hashes_url = config.hashes_url.clone();
     let hashes_sig_url = config.hashes_sig_url.clone();
@@ -122,8 +137,8 @@ pub async fn check_for_updates(
                 update_spec
             );
             let (hashes, sig) = future::join(
-                download_hashes_file(&hashes_url),
-                download_hashes_sig_file(&hashes_sig_url),
+                download_text_file(&hashes_url),
+                download_signature_file(&hashes_sig_url),
             )
             .await;
             let hashes = hashes?;
@@ -132,11 +147,14 @@ pub async fn check_for_updates(
             verifier
                 .verify_signed_update(&sig, &hashes, &update_spec)
                 .map(|(_, filename)| {
+                    manifest::validate_file_name(&filename)?;
                     let download_url = format!("{}/{}", download_base_url, filename);
                     log::info!(target: LOG_TARGET, "Valid update found at {}", download_url);
                     Ok(SoftwareUpdate {
                         spec: update_spec,
                         download_url,
+                        file_name: filename,
+                        staged_path: None,
                     })
                 })
                 .transpose()
@@ -148,10 +166,63 @@ pub async fn check_for_updates(
     }
 }
 
+async fn check_manifest_for_updates(
+    app: ApplicationType,
+    arch: &str,
+    version: &Version,
+    config: &AutoUpdateConfig,
+) -> Result<Option<SoftwareUpdate>, AutoUpdateError> {
+    let (manifest, sig) = future::join(
+        download_text_file(&config.manifest_url),
+        download_signature_file(&config.manifest_sig_url),
+    )
+    .await;
+    let manifest = manifest?;
+    let sig = sig?;
+    let verifier = SignedMessageVerifier::new(maintainers().collect());
+    if verifier.verify_signature(&sig, &manifest).is_none() {
+        return Err(AutoUpdateError::ManifestNotSigned);
+    }
+    match manifest::find_update(&manifest, app, arch, version)? {
+        Some((spec, file_name)) => {
+            let download_url = format!("{}/{}", config.download_base_url, file_name);
+            log::info!(target: LOG_TARGET, "Valid update found at {}", download_url);
+            Ok(Some(SoftwareUpdate {
+                spec,
+                download_url,
+                file_name,
+                staged_path: None,
+            }))
+        },
+        None => {
+            log::info!("No new updates for {} ({} {})", app, arch, version);
+            Ok(None)
+        },
+    }
+}
+
+/// Downloads the update into `staging_dir` and checks it against its signed SHA-256 hash, returning the path of the
+/// staged file. The file is only written, never made executable or run.
+pub async fn stage_update(update: &SoftwareUpdate, staging_dir: &Path) -> Result<PathBuf, AutoUpdateError> {
+    let bytes = http_download(update.download_url()).await?.bytes().await?;
+    if Sha256::digest(&bytes).as_slice() != update.hash() {
+        return Err(AutoUpdateError::HashMismatch);
+    }
+    tokio::fs::create_dir_all(staging_dir).await?;
+    // Write to a temporary name first, so a staged file is always complete
+    let partial_path = staging_dir.join(format!("{}.part", update.file_name));
+    let path = staging_dir.join(&update.file_name);
+    tokio::fs::write(&partial_path, &bytes).await?;
+    tokio::fs::rename(&partial_path, &path).await?;
+    Ok(path)
+}
+
 #[derive(Debug, Clone)]
 pub struct SoftwareUpdate {
     spec: UpdateSpec,
     download_url: String,
+    file_name: String,
+    staged_path: Option<PathBuf>,
 }
 
 impl SoftwareUpdate {
@@ -175,6 +246,11 @@ impl SoftwareUpdate {
     pub fn app(&self) -> &ApplicationType {
         &self.spec.application
     }
+
+    /// The verified download of the update, if it has been staged
+    pub fn staged_path(&self) -> Option<&Path> {
+        self.staged_path.as_deref()
+    }
 }
 
 impl Display for SoftwareUpdate {
@@ -183,13 +259,13 @@ impl Display for SoftwareUpdate {
     }
 }
 
-async fn download_hashes_file<T: IntoUrl>(url: T) -> Result<String, AutoUpdateError> {
+async fn download_text_file<T: IntoUrl>(url: T) -> Result<String, AutoUpdateError> {
     let resp = http_download(url).await?;
     let txt = resp.text().await?;
     Ok(txt)
 }
 
-async fn download_hashes_sig_file<T: IntoUrl>(url: T) -> Result<pgp::StandaloneSignature, AutoUpdateError> {
+async fn download_signature_file<T: IntoUrl>(url: T) -> Result<pgp::StandaloneSignature, AutoUpdateError> {
     let resp = http_download(url).await?;
     let sig_bytes = resp.bytes().await?;
     let cursor = io::Cursor::new(&sig_bytes);
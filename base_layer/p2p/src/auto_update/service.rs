@@ -124,13 +124,24 @@ impl SoftwareUpdaterService {
             };
 
             // Only notify of new or newer updates
-            if let Some(update) = maybe_update {
+            if let Some(mut update) = maybe_update {
                 if last_version
                     .as_ref()
                     .map(|up| up.version() < update.version())
                     .unwrap_or(true)
                 {
-                    let _result = notifier.send(Some(update.clone()));
+                    if let Some(staging_dir) = self.config.staging_dir.as_ref() {
+                        match auto_update::stage_update(&update, staging_dir).await {
+                            Ok(path) => {
+                                info!(target: LOG_TARGET, "Staged version {} at {}", update.version(), path.display());
+                                update.staged_path = Some(path);
+                            },
+                            Err(err) => {
+                                warn!(target: LOG_TARGET, "Unable to stage version {}: {}", update.version(), err);
+                            },
+                        }
+                    }
+                    let _result = notifier.send(Some(update));
                 }
             }
         }
@@ -140,7 +151,11 @@ impl SoftwareUpdaterService {
         log::info!(
             target: LOG_TARGET,
             "Checking for updates ({})...",
-            self.config.update_uris.as_slice().join(", ")
+            if self.config.manifest_url.is_empty() {
+                self.config.update_uris.as_slice().join(", ")
+            } else {
+                self.config.manifest_url.clone()
+            }
         );
         if !self.config.is_update_enabled() {
             warn!(
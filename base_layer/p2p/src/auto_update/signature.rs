@@ -52,7 +52,12 @@ impl SignedMessageVerifier {
             .find(|(hash, _)| update.hash == *hash)
     }
 
-    fn verify_signature(&self, signature: &pgp::StandaloneSignature, message: &str) -> Option<&pgp::SignedPublicKey> {
+    /// Returns the maintainer that signed the message, if any
+    pub fn verify_signature(
+        &self,
+        signature: &pgp::StandaloneSignature,
+        message: &str,
+    ) -> Option<&pgp::SignedPublicKey> {
         self.maintainers
             .iter()
             .find(|pk| signature.verify(pk, message.as_bytes()).is_ok())
@@ -0,0 +1,302 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Signed ban lists that can be exported by one node operator and imported by others, allowing the community to respond
+//! collectively to spam waves.
+
+use std::{collections::HashSet, time::Duration};
+
+use log::*;
+use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
+use tari_comms::{
+    connectivity::{ConnectivityError, ConnectivityRequester},
+    net_address::MultiaddressesWithStats,
+    peer_manager::{NodeId, Peer, PeerFeatures, PeerManager, PeerManagerError, PeerQuery},
+    types::{CommsPublicKey, CommsSecretKey},
+    NodeIdentity,
+};
+use tari_crypto::{hash_domain, keys::PublicKey, signatures::SchnorrSignature};
+use tari_utilities::{epoch_time::EpochTime, hex::Hex, ByteArray};
+use thiserror::Error;
+
+const LOG_TARGET: &str = "p2p::ban_list";
+
+hash_domain!(BanListHashDomain, "com.tari.p2p.ban_list", 0);
+
+pub type BanListSignature = SchnorrSignature<CommsPublicKey, CommsSecretKey, BanListHashDomain>;
+
+#[derive(Debug, Error)]
+pub enum BanListError {
+    #[error("Unsupported ban list version {0}")]
+    UnsupportedVersion(u8),
+    #[error("Malformed ban list signature")]
+    MalformedSignature,
+    #[error("Ban list signature is invalid")]
+    InvalidSignature,
+    #[error("Ban list signer {0} is not trusted")]
+    UntrustedSigner(CommsPublicKey),
+    #[error("Malformed ban list: {0}")]
+    MalformedList(#[from] serde_json::Error),
+    #[error("Peer manager error: {0}")]
+    PeerManagerError(#[from] PeerManagerError),
+    #[error("Connectivity error: {0}")]
+    ConnectivityError(#[from] ConnectivityError),
+}
+
+/// A single banned peer
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BanListEntry {
+    pub public_key: CommsPublicKey,
+    pub reason: String,
+    /// Unix timestamp in seconds after which the ban expires
+    pub banned_until: u64,
+}
+
+/// A list of banned peers signed by the node that exported it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedBanList {
+    pub version: u8,
+    /// Unix timestamp in seconds
+    pub issued_at: u64,
+    pub signer: CommsPublicKey,
+    pub entries: Vec<BanListEntry>,
+    pub public_nonce: String,
+    pub signature: String,
+}
+
+impl SignedBanList {
+    pub const LATEST_VERSION: u8 = 0;
+
+    pub fn sign(secret_key: &CommsSecretKey, issued_at: u64, entries: Vec<BanListEntry>) -> Self {
+        let signer = CommsPublicKey::from_secret_key(secret_key);
+        let message = Self::construct_message(Self::LATEST_VERSION, issued_at, &signer, &entries);
+        let signature = BanListSignature::sign(secret_key, message, &mut OsRng)
+            .expect("unreachable panic: ban list message is hashed to the correct length");
+        Self {
+            version: Self::LATEST_VERSION,
+            issued_at,
+            signer,
+            entries,
+            public_nonce: signature.get_public_nonce().to_hex(),
+            signature: signature.get_signature().to_hex(),
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, BanListError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn to_json(&self) -> Result<String, BanListError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Verifies that the list was signed by the signer it claims. If `trusted_signers` is not empty, the signer must
+    /// also be one of them.
+    pub fn verify(&self, trusted_signers: &[CommsPublicKey]) -> Result<(), BanListError> {
+        if self.version != Self::LATEST_VERSION {
+            return Err(BanListError::UnsupportedVersion(self.version));
+        }
+        if !trusted_signers.is_empty() && !trusted_signers.contains(&self.signer) {
+            return Err(BanListError::UntrustedSigner(self.signer.clone()));
+        }
+
+        let public_nonce =
+            CommsPublicKey::from_hex(&self.public_nonce).map_err(|_| BanListError::MalformedSignature)?;
+        let signature = CommsSecretKey::from_hex(&self.signature).map_err(|_| BanListError::MalformedSignature)?;
+        let signature = BanListSignature::new(public_nonce, signature);
+
+        let message = Self::construct_message(self.version, self.issued_at, &self.signer, &self.entries);
+        if !signature.verify(&self.signer, message) {
+            return Err(BanListError::InvalidSignature);
+        }
+        Ok(())
+    }
+
+    fn construct_message(version: u8, issued_at: u64, signer: &CommsPublicKey, entries: &[BanListEntry]) -> Vec<u8> {
+        let mut message = Vec::new();
+        message.push(version);
+        message.extend_from_slice(&issued_at.to_le_bytes());
+        message.extend_from_slice(signer.as_bytes());
+        for entry in entries {
+            message.extend_from_slice(entry.public_key.as_bytes());
+            message.extend_from_slice(&entry.banned_until.to_le_bytes());
+            message.extend_from_slice(&(entry.reason.len() as u32).to_le_bytes());
+            message.extend_from_slice(entry.reason.as_bytes());
+        }
+        message
+    }
+}
+
+/// The result of importing a ban list
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BanListImportSummary {
+    /// Number of peers that were banned
+    pub num_banned: usize,
+    /// Number of entries that were skipped because they had expired, referred to this node or were already banned for
+    /// longer locally
+    pub num_skipped: usize,
+}
+
+/// Exports all currently banned peers as a ban list signed by this node
+pub async fn export_ban_list(
+    peer_manager: &PeerManager,
+    node_identity: &NodeIdentity,
+) -> Result<SignedBanList, BanListError> {
+    let banned = peer_manager
+        .perform_query(PeerQuery::new().select_where(|p| p.is_banned()))
+        .await?;
+    let entries = banned
+        .into_iter()
+        .filter_map(|peer| {
+            let banned_until = u64::try_from(peer.banned_until()?.timestamp()).ok()?;
+            Some(BanListEntry {
+                public_key: peer.public_key.clone(),
+                reason: peer.reason_banned().to_string(),
+                banned_until,
+            })
+        })
+        .collect();
+    Ok(SignedBanList::sign(
+        node_identity.secret_key(),
+        EpochTime::now().as_u64(),
+        entries,
+    ))
+}
+
+/// Verifies the ban list and merges it with the local bans. Peers that are not yet known are added to the peer
+/// manager so that the ban is enforced if they connect later. Existing bans that last longer than the imported ban are
+/// left unchanged.
+pub async fn import_ban_list(
+    peer_manager: &PeerManager,
+    connectivity: &mut ConnectivityRequester,
+    node_identity: &NodeIdentity,
+    ban_list: SignedBanList,
+    trusted_signers: &[CommsPublicKey],
+) -> Result<BanListImportSummary, BanListError> {
+    ban_list.verify(trusted_signers)?;
+
+    let now = EpochTime::now().as_u64();
+    let mut summary = BanListImportSummary::default();
+    let mut seen = HashSet::new();
+    for entry in ban_list.entries {
+        if entry.banned_until <= now ||
+            entry.public_key == *node_identity.public_key() ||
+            !seen.insert(entry.public_key.clone())
+        {
+            summary.num_skipped += 1;
+            continue;
+        }
+
+        let node_id = NodeId::from_public_key(&entry.public_key);
+        match peer_manager.find_by_node_id(&node_id).await? {
+            Some(peer) => {
+                let banned_for_longer = peer
+                    .banned_until()
+                    .and_then(|dt| u64::try_from(dt.timestamp()).ok())
+                    .is_some_and(|until| until >= entry.banned_until);
+                if banned_for_longer {
+                    summary.num_skipped += 1;
+                    continue;
+                }
+            },
+            None => {
+                peer_manager
+                    .add_peer(Peer::new(
+                        entry.public_key.clone(),
+                        node_id.clone(),
+                        MultiaddressesWithStats::empty(),
+                        Default::default(),
+                        PeerFeatures::COMMUNICATION_NODE,
+                        Default::default(),
+                        Default::default(),
+                    ))
+                    .await?;
+            },
+        }
+
+        let duration = Duration::from_secs(entry.banned_until - now);
+        let reason = format!("Imported from {}: {}", ban_list.signer, entry.reason);
+        connectivity.ban_peer_until(node_id, duration, reason).await?;
+        summary.num_banned += 1;
+    }
+
+    debug!(
+        target: LOG_TARGET,
+        "Imported ban list from {}: {} banned, {} skipped", ban_list.signer, summary.num_banned, summary.num_skipped
+    );
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn create_entries() -> Vec<BanListEntry> {
+        (0..3)
+            .map(|i| BanListEntry {
+                public_key: CommsPublicKey::random_keypair(&mut OsRng).1,
+                reason: format!("spam {}", i),
+                banned_until: 1_700_000_000 + i,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn it_verifies_a_signed_list() {
+        let (sk, pk) = CommsPublicKey::random_keypair(&mut OsRng);
+        let list = SignedBanList::sign(&sk, 1_700_000_000, create_entries());
+        let list = SignedBanList::from_json(&list.to_json().unwrap()).unwrap();
+        assert_eq!(list.signer, pk);
+        assert_eq!(list.entries.len(), 3);
+        list.verify(&[]).unwrap();
+        list.verify(&[pk]).unwrap();
+    }
+
+    #[test]
+    fn it_rejects_an_untrusted_signer() {
+        let (sk, _) = CommsPublicKey::random_keypair(&mut OsRng);
+        let (_, other) = CommsPublicKey::random_keypair(&mut OsRng);
+        let list = SignedBanList::sign(&sk, 1_700_000_000, create_entries());
+        let err = list.verify(&[other]).unwrap_err();
+        assert!(matches!(err, BanListError::UntrustedSigner(_)));
+    }
+
+    #[test]
+    fn it_rejects_a_tampered_list() {
+        let (sk, _) = CommsPublicKey::random_keypair(&mut OsRng);
+        let mut list = SignedBanList::sign(&sk, 1_700_000_000, create_entries());
+        list.entries[0].reason = "something else".to_string();
+        let err = list.verify(&[]).unwrap_err();
+        assert!(matches!(err, BanListError::InvalidSignature));
+    }
+
+    #[test]
+    fn it_rejects_a_substituted_signer() {
+        let (sk, _) = CommsPublicKey::random_keypair(&mut OsRng);
+        let (_, other) = CommsPublicKey::random_keypair(&mut OsRng);
+        let mut list = SignedBanList::sign(&sk, 1_700_000_000, create_entries());
+        list.signer = other;
+        let err = list.verify(&[]).unwrap_err();
+        assert!(matches!(err, BanListError::InvalidSignature));
+    }
+}
@@ -66,6 +66,13 @@ pub struct PeerSeedsConfig {
     /// All DNS seed records must pass DNSSEC validation
     #[serde(default)]
     pub dns_seeds_use_dnssec: bool,
+    /// URLs of signed JSON seed lists. These are fetched over HTTPS if no peers could be obtained from the DNS seeds.
+    #[serde(default)]
+    pub https_seeds: StringList,
+    /// The hex-encoded public key that HTTPS seed lists must be signed with. This should be pinned per network. HTTPS
+    /// seeds are not used if this is not set.
+    #[serde(default)]
+    pub https_seeds_public_key: Option<String>,
 }
 
 impl Default for PeerSeedsConfig {
@@ -83,6 +90,8 @@ impl Default for PeerSeedsConfig {
             )
             .expect("string is valid"),
             dns_seeds_use_dnssec: false,
+            https_seeds: StringList::default(),
+            https_seeds_public_key: None,
         }
     }
 }
@@ -139,6 +148,32 @@ pub struct P2pConfig {
     /// The maximum allowed RPC sessions per peer.
     /// Default: 10
     pub rpc_max_sessions_per_peer: usize,
+    /// The maximum number of RPC requests per minute that a single peer may make. Requests that exceed this rate are
+    /// rejected with a "slow down" response. Set to None to disable.
+    /// Default: None
+    pub rpc_max_requests_per_minute_per_peer: Option<u32>,
+    /// The maximum number of RPC requests per minute that a single peer may make to any one RPC method. Set to None to
+    /// disable.
+    /// Default: None
+    pub rpc_max_requests_per_minute_per_method: Option<u32>,
+    /// The maximum number of RPC requests being processed across all sessions at any one time. Additional requests are
+    /// rejected with a "slow down" response until the load decreases. Set to None to disable.
+    /// Default: None
+    pub rpc_max_pending_requests: Option<usize>,
+    /// The maximum number of inbound connections allowed from a single /24 (IPv4) or /48 (IPv6) subnet. Additional
+    /// connections from that subnet are rejected. Set to None to disable.
+    /// Default: None
+    pub max_inbound_connections_per_subnet: Option<usize>,
+    /// Set to true to enable the traffic padding privacy mode. Outbound messages are padded to fixed size buckets,
+    /// making it harder for a network observer to infer the kind of message being sent. Only applies to peers that
+    /// support padded messaging.
+    /// Default: false
+    pub traffic_padding: bool,
+    /// If traffic padding is enabled, a cover message is sent to a peer when no messages have been sent to it for this
+    /// long (plus a random jitter). Set to None to disable cover traffic.
+    /// Default: 30 seconds
+    #[serde(with = "serializers::optional_seconds")]
+    pub cover_traffic_interval: Option<Duration>,
 }
 
 impl Default for P2pConfig {
@@ -163,6 +198,12 @@ impl Default for P2pConfig {
             auxiliary_tcp_listener_address: None,
             rpc_max_simultaneous_sessions: 100,
             rpc_max_sessions_per_peer: 10,
+            rpc_max_requests_per_minute_per_peer: None,
+            rpc_max_requests_per_minute_per_method: None,
+            rpc_max_pending_requests: None,
+            max_inbound_connections_per_subnet: None,
+            traffic_padding: false,
+            cover_traffic_interval: Some(Duration::from_secs(30)),
         }
     }
 }
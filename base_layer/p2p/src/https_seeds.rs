@@ -0,0 +1,229 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Signed peer seed lists that are fetched over HTTPS. These are used as a fallback when DNS seeds cannot be resolved,
+//! for example when DNS is blocked or poisoned. The list is signed by a key that is pinned per network in the peer
+//! seeds configuration, so the HTTPS host itself does not need to be trusted.
+
+use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
+use tari_common::configuration::Network;
+use tari_comms::types::{CommsPublicKey, CommsSecretKey};
+use tari_crypto::{hash_domain, signatures::SchnorrSignature};
+use tari_utilities::{epoch_time::EpochTime, hex::Hex, ByteArray};
+use thiserror::Error;
+
+use crate::peer_seeds::SeedPeer;
+
+hash_domain!(SeedListHashDomain, "com.tari.p2p.seed_list", 0);
+
+pub type SeedListSignature = SchnorrSignature<CommsPublicKey, CommsSecretKey, SeedListHashDomain>;
+
+/// Signed lists issued more than this many seconds in the future are rejected
+const MAX_FUTURE_ISSUED_AT_SECS: u64 = 24 * 60 * 60;
+
+#[derive(Debug, Error)]
+pub enum HttpsSeedError {
+    #[error("Unsupported seed list version {0}")]
+    UnsupportedVersion(u8),
+    #[error("Seed list was issued in the future")]
+    IssuedInFuture,
+    #[error("Malformed seed list signature")]
+    MalformedSignature,
+    #[error("Seed list signature is invalid")]
+    InvalidSignature,
+    #[error("Malformed seed list: {0}")]
+    MalformedList(#[from] serde_json::Error),
+    #[error("Invalid seed list public key: {0}")]
+    InvalidPublicKey(String),
+    #[cfg(feature = "https-seeds")]
+    #[error("Failed to download seed list: {0}")]
+    DownloadError(#[from] reqwest::Error),
+}
+
+/// A list of seed peers, signed by the seed list key for a network.
+///
+/// Example JSON:
+/// ```json
+/// {
+///   "version": 0,
+///   "issued_at": 1700000000,
+///   "peers": ["06e98e9c5eb52bd504836edec1878eccf12eb9f26a5fe5ec0e279423156e657a::/ip4/1.2.3.4/tcp/18189"],
+///   "public_nonce": "<hex>",
+///   "signature": "<hex>"
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedSeedList {
+    pub version: u8,
+    /// Unix timestamp in seconds
+    pub issued_at: u64,
+    pub peers: Vec<SeedPeer>,
+    pub public_nonce: String,
+    pub signature: String,
+}
+
+impl SignedSeedList {
+    pub const LATEST_VERSION: u8 = 0;
+
+    /// Signs the given peers for the given network
+    pub fn sign(secret_key: &CommsSecretKey, network: Network, issued_at: u64, peers: Vec<SeedPeer>) -> Self {
+        let message = Self::construct_message(Self::LATEST_VERSION, network, issued_at, &peers);
+        let signature = SeedListSignature::sign(secret_key, message, &mut OsRng)
+            .expect("unreachable panic: seed list message is hashed to the correct length");
+        Self {
+            version: Self::LATEST_VERSION,
+            issued_at,
+            peers,
+            public_nonce: signature.get_public_nonce().to_hex(),
+            signature: signature.get_signature().to_hex(),
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, HttpsSeedError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn to_json(&self) -> Result<String, HttpsSeedError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Verifies the list against the pinned public key for the network, returning the seed peers if valid.
+    pub fn verify(self, public_key: &CommsPublicKey, network: Network) -> Result<Vec<SeedPeer>, HttpsSeedError> {
+        if self.version != Self::LATEST_VERSION {
+            return Err(HttpsSeedError::UnsupportedVersion(self.version));
+        }
+        if self.issued_at > EpochTime::now().as_u64().saturating_add(MAX_FUTURE_ISSUED_AT_SECS) {
+            return Err(HttpsSeedError::IssuedInFuture);
+        }
+
+        let public_nonce =
+            CommsPublicKey::from_hex(&self.public_nonce).map_err(|_| HttpsSeedError::MalformedSignature)?;
+        let signature = CommsSecretKey::from_hex(&self.signature).map_err(|_| HttpsSeedError::MalformedSignature)?;
+        let signature = SeedListSignature::new(public_nonce, signature);
+
+        let message = Self::construct_message(self.version, network, self.issued_at, &self.peers);
+        if !signature.verify(public_key, message) {
+            return Err(HttpsSeedError::InvalidSignature);
+        }
+
+        Ok(self.peers)
+    }
+
+    fn construct_message(version: u8, network: Network, issued_at: u64, peers: &[SeedPeer]) -> Vec<u8> {
+        let mut message = Vec::new();
+        message.push(version);
+        message.push(network.as_byte());
+        message.extend_from_slice(&issued_at.to_le_bytes());
+        for peer in peers {
+            message.extend_from_slice(peer.public_key.as_bytes());
+            message.extend_from_slice(&(peer.addresses.len() as u32).to_le_bytes());
+            for address in &peer.addresses {
+                let address = address.to_vec();
+                message.extend_from_slice(&(address.len() as u32).to_le_bytes());
+                message.extend_from_slice(&address);
+            }
+        }
+        message
+    }
+}
+
+/// Parses a hex-encoded seed list public key
+pub fn parse_seed_list_public_key(hex: &str) -> Result<CommsPublicKey, HttpsSeedError> {
+    CommsPublicKey::from_hex(hex.trim()).map_err(|e| HttpsSeedError::InvalidPublicKey(e.to_string()))
+}
+
+/// Downloads the signed seed list at the given URL and verifies it against the pinned public key
+#[cfg(feature = "https-seeds")]
+pub async fn fetch_signed_seed_list(
+    url: &str,
+    public_key: &CommsPublicKey,
+    network: Network,
+) -> Result<Vec<SeedPeer>, HttpsSeedError> {
+    let json = reqwest::get(url).await?.error_for_status()?.text().await?;
+    SignedSeedList::from_json(&json)?.verify(public_key, network)
+}
+
+#[cfg(test)]
+mod test {
+    use tari_crypto::keys::PublicKey;
+
+    use super::*;
+
+    fn create_peers() -> Vec<SeedPeer> {
+        vec![
+            "06e98e9c5eb52bd504836edec1878eccf12eb9f26a5fe5ec0e279423156e657a::/ip4/127.0.0.1/tcp/8000"
+                .parse()
+                .unwrap(),
+            "a062ae2345b0db0df9fb1504b99511e23d98f8513f9b5503efcc6dad8eca7e47::/onion3/\
+             bsmuof2cn4y2ysz253gzsvg3s72fcgh4f3qcm3hdlxdtcwe6al2dicyd:1234"
+                .parse()
+                .unwrap(),
+        ]
+    }
+
+    #[test]
+    fn it_verifies_a_signed_list() {
+        let (sk, pk) = CommsPublicKey::random_keypair(&mut OsRng);
+        let list = SignedSeedList::sign(&sk, Network::Esmeralda, 1_700_000_000, create_peers());
+        let json = list.to_json().unwrap();
+        let peers = SignedSeedList::from_json(&json)
+            .unwrap()
+            .verify(&pk, Network::Esmeralda)
+            .unwrap();
+        assert_eq!(peers.len(), 2);
+    }
+
+    #[test]
+    fn it_rejects_a_list_signed_by_another_key() {
+        let (sk, _) = CommsPublicKey::random_keypair(&mut OsRng);
+        let (_, pk) = CommsPublicKey::random_keypair(&mut OsRng);
+        let list = SignedSeedList::sign(&sk, Network::Esmeralda, 1_700_000_000, create_peers());
+        let err = list.verify(&pk, Network::Esmeralda).unwrap_err();
+        assert!(matches!(err, HttpsSeedError::InvalidSignature));
+    }
+
+    #[test]
+    fn it_rejects_a_list_for_another_network() {
+        let (sk, pk) = CommsPublicKey::random_keypair(&mut OsRng);
+        let list = SignedSeedList::sign(&sk, Network::Esmeralda, 1_700_000_000, create_peers());
+        let err = list.verify(&pk, Network::MainNet).unwrap_err();
+        assert!(matches!(err, HttpsSeedError::InvalidSignature));
+    }
+
+    #[test]
+    fn it_rejects_a_tampered_list() {
+        let (sk, pk) = CommsPublicKey::random_keypair(&mut OsRng);
+        let mut list = SignedSeedList::sign(&sk, Network::Esmeralda, 1_700_000_000, create_peers());
+        list.peers.pop();
+        let err = list.verify(&pk, Network::Esmeralda).unwrap_err();
+        assert!(matches!(err, HttpsSeedError::InvalidSignature));
+    }
+
+    #[test]
+    fn it_rejects_a_list_issued_in_the_future() {
+        let (sk, pk) = CommsPublicKey::random_keypair(&mut OsRng);
+        let list = SignedSeedList::sign(&sk, Network::Esmeralda, u64::MAX, create_peers());
+        let err = list.verify(&pk, Network::Esmeralda).unwrap_err();
+        assert!(matches!(err, HttpsSeedError::IssuedInFuture));
+    }
+}
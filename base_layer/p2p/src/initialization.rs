@@ -46,15 +46,16 @@ use tari_comms::{
     peer_manager::{NodeIdentity, Peer, PeerFeatures, PeerFlags, PeerManagerError},
     pipeline,
     protocol::{
-        messaging::{MessagingEventSender, MessagingProtocolExtension},
+        messaging::{MessagingEventSender, MessagingProtocolExtension, TrafficPaddingConfig},
         rpc::RpcServer,
+        Capabilities,
         NodeNetworkInfo,
         ProtocolId,
     },
     tor,
     tor::{HiddenServiceControllerError, TorIdentity},
     transports::{
-        predicate::FalsePredicate,
+        predicate::{is_onion_address, FalsePredicate},
         HiddenServiceTransport,
         MemoryTransport,
         SocksConfig,
@@ -83,6 +84,7 @@ use crate::{
     comms_connector::{InboundDomainConnector, PubsubDomainConnector},
     config::{P2pConfig, PeerSeedsConfig},
     dns::DnsClientError,
+    https_seeds,
     peer_seeds::{DnsSeedResolver, SeedPeer},
     transport::{TorTransportConfig, TransportType},
     TransportConfig,
@@ -260,16 +262,37 @@ pub async fn spawn_comms_using_transport<F: Fn(TorIdentity) + Send + Sync + Unpi
             let tor_config = transport_config.tor;
             debug!(target: LOG_TARGET, "Building TOR comms stack ({:?})", tor_config);
             let listener_address_override = tor_config.listener_address_override.clone();
+            let public_tcp_listener_address = tor_config.public_tcp_listener_address.clone();
             let hidden_service_ctl = initialize_hidden_service(tor_config)?;
+            let hidden_service_handle = hidden_service_ctl.get_handle();
             // Set the listener address to be the address (usually local) to which tor will forward all traffic
             let instant = Instant::now();
             let transport = HiddenServiceTransport::new(hidden_service_ctl, after_comms);
             debug!(target: LOG_TARGET, "TOR transport initialized in {:.0?}", instant.elapsed());
 
+            let comms = match public_tcp_listener_address {
+                Some(addr) => {
+                    debug!(target: LOG_TARGET, "Enabling dual-stack public TCP listener on {}", addr);
+                    if comms.node_identity().public_addresses().iter().all(is_onion_address) {
+                        warn!(
+                            target: LOG_TARGET,
+                            "A public TCP listener is configured but no TCP public address is set in \
+                             `p2p.public_addresses`. Clearnet peers will not be able to discover this node's TCP \
+                             address."
+                        );
+                    }
+                    comms
+                        .with_public_tcp_listener_address(addr)
+                        .prefer_onion_dial_addresses(true)
+                },
+                None => comms,
+            };
+
             comms
                 .with_listener_address(
                     listener_address_override.unwrap_or_else(|| multiaddr![Ip4([127, 0, 0, 1]), Tcp(0u16)]),
                 )
+                .with_hidden_service_handle(hidden_service_handle)
                 .spawn_with_transport(transport)
                 .await?
         },
@@ -377,14 +400,19 @@ async fn configure_comms_and_dht(
         .build();
 
     let (messaging_events_sender, _) = broadcast::channel(1);
-    comms = comms.add_protocol_extension(
-        MessagingProtocolExtension::new(
-            MESSAGING_PROTOCOL_ID.clone(),
-            messaging_events_sender,
-            messaging_pipeline,
-        )
-        .with_ban_duration(config.dht.ban_duration_short),
-    );
+    let mut messaging = MessagingProtocolExtension::new(
+        MESSAGING_PROTOCOL_ID.clone(),
+        messaging_events_sender,
+        messaging_pipeline,
+    )
+    .with_ban_duration(config.dht.ban_duration_short);
+    if config.traffic_padding {
+        messaging = messaging.with_traffic_padding(TrafficPaddingConfig {
+            cover_traffic_interval: config.cover_traffic_interval,
+            ..Default::default()
+        });
+    }
+    comms = comms.add_protocol_extension(messaging);
 
     Ok((comms, dht))
 }
@@ -454,6 +482,7 @@ pub struct P2pInitializer {
     network: Network,
     node_identity: Arc<NodeIdentity>,
     connector: Option<PubsubDomainConnector>,
+    capabilities: Capabilities,
 }
 
 impl P2pInitializer {
@@ -472,9 +501,16 @@ impl P2pInitializer {
             network,
             node_identity,
             connector: Some(connector),
+            capabilities: Capabilities::new(),
         }
     }
 
+    /// Set the optional protocol capabilities that this node advertises to peers
+    pub fn with_capabilities(mut self, capabilities: Capabilities) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+
     // Following are inlined due to Rust ICE: https://github.com/rust-lang/rust/issues/73537
     fn try_parse_seed_peers(peer_seeds_str: &[String]) -> Result<Vec<Peer>, ServiceInitializationError> {
         peer_seeds_str
@@ -552,7 +588,15 @@ impl P2pInitializer {
         for dns in dns_seed_name_servers {
             let res = match (dns_seeds_use_dnssec, dns == &DnsNameServer::System) {
                 (true, false) => DnsSeedResolver::connect_secure(dns.clone()).await,
-                (_, _) => DnsSeedResolver::connect(dns.clone()).await,
+                (true, true) => {
+                    // The system resolver does not validate DNSSEC, so using it would silently bypass validation
+                    warn!(
+                        target: LOG_TARGET,
+                        "Skipping DNS entry '{}' because it cannot be used with DNSSEC validation", dns
+                    );
+                    continue;
+                },
+                (false, _) => DnsSeedResolver::connect(dns.clone()).await,
             };
             match res {
                 Ok(val) => {
@@ -576,6 +620,56 @@ impl P2pInitializer {
             dns_errors
         ))))
     }
+
+    /// Fetches signed seed lists over HTTPS. This is used as a fallback when no peers could be obtained from DNS seeds.
+    #[cfg_attr(not(feature = "https-seeds"), allow(unused_variables))]
+    async fn try_fetch_https_seeds(
+        config: &PeerSeedsConfig,
+        network: Network,
+    ) -> Result<Vec<Peer>, ServiceInitializationError> {
+        if config.https_seeds.is_empty() {
+            debug!(target: LOG_TARGET, "No HTTPS seeds configured");
+            return Ok(Vec::new());
+        }
+        let Some(public_key) = config.https_seeds_public_key.as_deref() else {
+            warn!(
+                target: LOG_TARGET,
+                "HTTPS seeds are configured but 'https_seeds_public_key' is not set. HTTPS seeds will not be used."
+            );
+            return Ok(Vec::new());
+        };
+        let public_key = https_seeds::parse_seed_list_public_key(public_key)?;
+
+        #[cfg(feature = "https-seeds")]
+        for url in config.https_seeds.iter() {
+            let start = Instant::now();
+            match https_seeds::fetch_signed_seed_list(url, &public_key, network).await {
+                Ok(peers) => {
+                    debug!(
+                        target: LOG_TARGET,
+                        "Found {} peer(s) from HTTPS seed `{}` in {:.0?}",
+                        peers.len(),
+                        url,
+                        start.elapsed()
+                    );
+                    return Ok(peers.into_iter().map(Into::into).collect());
+                },
+                Err(err) => {
+                    warn!(target: LOG_TARGET, "HTTPS seed `{}` failed: {}", url, err);
+                },
+            }
+        }
+
+        #[cfg(not(feature = "https-seeds"))]
+        {
+            warn!(
+                target: LOG_TARGET,
+                "HTTPS seeds are configured but this application was not built with the 'https-seeds' feature"
+            );
+        }
+
+        Ok(Vec::new())
+    }
 }
 
 #[async_trait]
@@ -593,13 +687,15 @@ impl ServiceInitializer for P2pInitializer {
                 minor_version: MINOR_NETWORK_VERSION,
                 network_wire_byte: self.network.as_wire_byte(),
                 user_agent: self.user_agent.clone(),
+                capabilities: self.capabilities.clone(),
             })
             .with_minimize_connections(if self.config.dht.minimize_connections {
                 Some(self.config.dht.num_neighbouring_nodes + self.config.dht.num_random_nodes)
             } else {
                 None
             })
-            .set_self_liveness_check(config.listener_self_liveness_check_interval);
+            .set_self_liveness_check(config.listener_self_liveness_check_interval)
+            .with_max_inbound_connections_per_subnet(config.max_inbound_connections_per_subnet);
 
         if config.allow_test_addresses || config.dht.peer_validator_config.allow_test_addresses {
             // The default is false, so ensure that both settings are true in this case
@@ -613,13 +709,22 @@ impl ServiceInitializer for P2pInitializer {
         let peer_manager = comms.peer_manager();
         let node_identity = comms.node_identity();
 
-        let peers = match Self::try_resolve_dns_seeds(&self.seed_config).await {
+        let mut peers = match Self::try_resolve_dns_seeds(&self.seed_config).await {
             Ok(peers) => peers,
             Err(err) => {
                 warn!(target: LOG_TARGET, "Failed to resolve DNS seeds: {}", err);
                 Vec::new()
             },
         };
+        if peers.is_empty() {
+            peers = match Self::try_fetch_https_seeds(&self.seed_config, self.network).await {
+                Ok(peers) => peers,
+                Err(err) => {
+                    warn!(target: LOG_TARGET, "Failed to fetch HTTPS seeds: {}", err);
+                    Vec::new()
+                },
+            };
+        }
         add_seed_peers(&peer_manager, &node_identity, peers).await?;
 
         let peers = Self::try_parse_seed_peers(&self.seed_config.peer_seeds)?;
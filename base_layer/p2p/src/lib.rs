@@ -26,15 +26,20 @@ mod test_utils;
 
 #[cfg(feature = "auto-update")]
 pub mod auto_update;
+pub mod ban_list;
 pub mod comms_connector;
 mod config;
 pub mod domain_message;
+pub mod https_seeds;
 pub mod initialization;
 pub mod peer;
 pub mod peer_seeds;
+pub mod privacy;
 pub mod proto;
+pub mod push_relay;
 pub mod services;
 mod socks_authentication;
+pub mod status_beacon;
 pub mod tari_message;
 mod tor_authentication;
 pub mod transport;
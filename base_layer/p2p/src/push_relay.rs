@@ -0,0 +1,48 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Push notification relays. A relay forwards an opaque, end-to-end encrypted payload to a mobile device identified by
+//! a device token, so that a backgrounded wallet app can be woken up. Notifications are posted to the relay by the
+//! wallet itself while it is running, and by a light wallet server on its behalf while it is not.
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum PushRelayError {
+    #[error("Malformed push relay request: {0}")]
+    MalformedRequest(#[from] serde_json::Error),
+    #[error("Push relay responded with status {0}")]
+    UnexpectedStatus(u16),
+    #[cfg(feature = "push-relay")]
+    #[error("Failed to post to push relay: {0}")]
+    RequestError(#[from] reqwest::Error),
+}
+
+/// The request body posted to a relay
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PushRelayRequest {
+    pub device_token: String,
+    /// The hex encoded encrypted payload, which the relay cannot read
+    pub payload: String,
+}
+
+/// Posts a notification to the relay at the given URL
+#[cfg(feature = "push-relay")]
+pub async fn post_to_relay(
+    relay_url: &str,
+    request: &PushRelayRequest,
+    timeout: std::time::Duration,
+) -> Result<(), PushRelayError> {
+    let response = reqwest::Client::new()
+        .post(relay_url)
+        .timeout(timeout)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(serde_json::to_string(request)?)
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        return Err(PushRelayError::UnexpectedStatus(response.status().as_u16()));
+    }
+    Ok(())
+}
@@ -0,0 +1,71 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tari_common::configuration::serializers;
+
+/// Configuration for the topic-based gossip service
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GossipConfig {
+    /// True to propagate blocks and transactions using per-topic meshes and lazy gossip, otherwise the DHT propagation
+    /// strategies are used. Default: true
+    pub enabled: bool,
+    /// The target number of peers in the mesh of each topic. Default: 6
+    pub mesh_n: usize,
+    /// Peers are grafted into a topic mesh when it has fewer than this many peers. Default: 4
+    pub mesh_n_low: usize,
+    /// Peers are pruned from a topic mesh when it has more than this many peers. Default: 12
+    pub mesh_n_high: usize,
+    /// The number of non-mesh peers to send IHAVE gossip to on each heartbeat. Default: 6
+    pub gossip_lazy: usize,
+    /// The interval between heartbeats, in which meshes are maintained and IHAVE gossip is emitted. Default: 1s
+    #[serde(with = "serializers::seconds")]
+    pub heartbeat_interval: Duration,
+    /// The number of heartbeats for which full messages are kept to answer IWANT requests. Default: 5
+    pub history_length: usize,
+    /// The number of most recent heartbeats whose message IDs are included in IHAVE gossip. Default: 3
+    pub history_gossip: usize,
+    /// The maximum number of message IDs accepted in a single IHAVE or IWANT. Default: 500
+    pub max_ids_per_message: usize,
+    /// Peers whose score is below this threshold are removed from meshes and their gossip is ignored. Default: -10.0
+    pub graylist_threshold: f64,
+}
+
+impl Default for GossipConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            mesh_n: 6,
+            mesh_n_low: 4,
+            mesh_n_high: 12,
+            gossip_lazy: 6,
+            heartbeat_interval: Duration::from_secs(1),
+            history_length: 5,
+            history_gossip: 3,
+            max_ids_per_message: 500,
+            graylist_threshold: -10.0,
+        }
+    }
+}
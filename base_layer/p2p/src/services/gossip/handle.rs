@@ -0,0 +1,94 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use tari_comms::{peer_manager::NodeId, BytesMut};
+use tari_comms_dht::domain_message::OutboundDomainMessage;
+use tari_service_framework::reply_channel::SenderService;
+use tower::Service;
+
+use super::error::GossipError;
+use crate::tari_message::TariMessageType;
+
+/// Request types made through the `GossipHandle` and are handled by the `GossipService`
+#[derive(Debug)]
+pub enum GossipRequest {
+    /// Publish (or forward) an encoded message on a topic, excluding the given peers
+    Publish {
+        topic: TariMessageType,
+        body: BytesMut,
+        exclude_peers: Vec<NodeId>,
+    },
+    /// Report that a peer delivered an invalid message on a gossip topic
+    ReportInvalidMessage(NodeId),
+}
+
+/// Response type for `GossipService`
+#[derive(Debug)]
+pub enum GossipResponse {
+    /// The message was published to this many peers
+    Published(usize),
+    /// Indicates that the request succeeded
+    Ok,
+}
+
+#[derive(Clone)]
+pub struct GossipHandle {
+    handle: SenderService<GossipRequest, Result<GossipResponse, GossipError>>,
+}
+
+impl GossipHandle {
+    pub fn new(handle: SenderService<GossipRequest, Result<GossipResponse, GossipError>>) -> Self {
+        Self { handle }
+    }
+
+    /// Publish a message on the given topic to the topic mesh and to any connected base nodes that do not support
+    /// gossip. Messages that have already been published or received are not sent again. Returns the number of peers
+    /// the message was sent to.
+    pub async fn publish<T: prost::Message>(
+        &mut self,
+        topic: TariMessageType,
+        message: T,
+        exclude_peers: Vec<NodeId>,
+    ) -> Result<usize, GossipError> {
+        let body = OutboundDomainMessage::new(&topic, message).into_propagation_body();
+        match self
+            .handle
+            .call(GossipRequest::Publish {
+                topic,
+                body,
+                exclude_peers,
+            })
+            .await??
+        {
+            GossipResponse::Published(n) => Ok(n),
+            _ => Err(GossipError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Report that the given peer delivered an invalid message, reducing its gossip score
+    pub async fn report_invalid_message(&mut self, peer: NodeId) -> Result<(), GossipError> {
+        match self.handle.call(GossipRequest::ReportInvalidMessage(peer)).await?? {
+            GossipResponse::Ok => Ok(()),
+            _ => Err(GossipError::UnexpectedApiResponse),
+        }
+    }
+}
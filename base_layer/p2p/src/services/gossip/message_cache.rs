@@ -0,0 +1,155 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::collections::{HashMap, VecDeque};
+
+use tari_comms::BytesMut;
+
+use crate::tari_message::TariMessageType;
+
+/// The ID of a gossiped message. This is the DHT dedup hash of the cleartext envelope body, so it is the same on every
+/// node that propagates the message.
+pub type MessageId = [u8; 32];
+
+#[derive(Debug, Clone)]
+pub struct CachedMessage {
+    pub topic: TariMessageType,
+    pub body: BytesMut,
+}
+
+/// Caches full messages for a sliding window of heartbeats so that they can be served in response to IWANT requests.
+#[derive(Debug)]
+pub struct MessageCache {
+    messages: HashMap<MessageId, CachedMessage>,
+    /// Message IDs received in each heartbeat window, most recent window first
+    history: VecDeque<Vec<MessageId>>,
+    history_length: usize,
+    history_gossip: usize,
+}
+
+impl MessageCache {
+    pub fn new(history_length: usize, history_gossip: usize) -> Self {
+        let history_length = history_length.max(1);
+        let mut history = VecDeque::with_capacity(history_length);
+        history.push_front(Vec::new());
+        Self {
+            messages: HashMap::new(),
+            history,
+            history_length,
+            history_gossip: history_gossip.min(history_length),
+        }
+    }
+
+    /// Adds a message to the current window. Returns false if the message is already cached.
+    pub fn put(&mut self, id: MessageId, topic: TariMessageType, body: BytesMut) -> bool {
+        if self.messages.contains_key(&id) {
+            return false;
+        }
+        self.messages.insert(id, CachedMessage { topic, body });
+        if let Some(window) = self.history.front_mut() {
+            window.push(id);
+        }
+        true
+    }
+
+    pub fn get(&self, id: &MessageId) -> Option<&CachedMessage> {
+        self.messages.get(id)
+    }
+
+    pub fn contains(&self, id: &MessageId) -> bool {
+        self.messages.contains_key(id)
+    }
+
+    /// Returns the IDs of messages for the given topic that were cached within the last `history_gossip` windows
+    pub fn gossip_ids(&self, topic: TariMessageType) -> Vec<MessageId> {
+        self.history
+            .iter()
+            .take(self.history_gossip)
+            .flatten()
+            .filter(|id| self.messages.get(*id).is_some_and(|m| m.topic == topic))
+            .copied()
+            .collect()
+    }
+
+    /// Starts a new window, evicting messages from windows older than `history_length`
+    pub fn shift(&mut self) {
+        self.history.push_front(Vec::new());
+        while self.history.len() > self.history_length {
+            if let Some(window) = self.history.pop_back() {
+                for id in window {
+                    self.messages.remove(&id);
+                }
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn body(n: u8) -> BytesMut {
+        BytesMut::from(&[n; 8][..])
+    }
+
+    #[test]
+    fn it_returns_gossip_ids_for_recent_windows() {
+        let mut cache = MessageCache::new(3, 2);
+        assert!(cache.put([1; 32], TariMessageType::NewBlock, body(1)));
+        assert!(!cache.put([1; 32], TariMessageType::NewBlock, body(1)));
+        assert!(cache.put([2; 32], TariMessageType::NewTransaction, body(2)));
+        cache.shift();
+        assert!(cache.put([3; 32], TariMessageType::NewBlock, body(3)));
+
+        let mut ids = cache.gossip_ids(TariMessageType::NewBlock);
+        ids.sort_unstable();
+        assert_eq!(ids, vec![[1; 32], [3; 32]]);
+        assert_eq!(cache.gossip_ids(TariMessageType::NewTransaction), vec![[2; 32]]);
+
+        cache.shift();
+        // The first window is no longer gossiped but is still cached
+        assert_eq!(cache.gossip_ids(TariMessageType::NewBlock), vec![[3; 32]]);
+        assert!(cache.contains(&[1; 32]));
+    }
+
+    #[test]
+    fn it_evicts_old_windows() {
+        let mut cache = MessageCache::new(2, 1);
+        cache.put([1; 32], TariMessageType::NewBlock, body(1));
+        cache.shift();
+        cache.put([2; 32], TariMessageType::NewBlock, body(2));
+        assert_eq!(cache.len(), 2);
+        cache.shift();
+        assert!(!cache.contains(&[1; 32]));
+        assert_eq!(cache.get(&[2; 32]).unwrap().body, body(2));
+        cache.shift();
+        assert!(cache.is_empty());
+    }
+}
@@ -0,0 +1,140 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! # Gossip Service
+//!
+//! Propagates messages on a set of topics (`TariMessageType`s) using a gossipsub-style protocol. For each topic the
+//! node maintains a mesh of between `mesh_n_low` and `mesh_n_high` peers to which full messages are sent. On every
+//! heartbeat, the IDs of recently seen messages are advertised (IHAVE) to a few non-mesh peers, which may request
+//! (IWANT) any messages they missed. Peers are scored on first deliveries, broken IHAVE promises and invalid messages,
+//! and graylisted peers are removed from meshes.
+//!
+//! Nodes advertise the [GOSSIP_CAPABILITY] during the identity exchange, and subscriptions are announced to peers for
+//! which the capability was negotiated. Peers are only considered part of the gossip network once they have announced
+//! their subscriptions in a `GossipControl` message. Connected base nodes that have not done so continue to receive
+//! every published message in full.
+
+pub mod config;
+pub use self::config::GossipConfig;
+
+pub mod error;
+
+mod handle;
+pub use handle::{GossipHandle, GossipRequest, GossipResponse};
+
+mod message_cache;
+mod service;
+mod state;
+
+use std::sync::Arc;
+
+use futures::{Stream, StreamExt};
+use log::*;
+use tari_comms::connectivity::ConnectivityRequester;
+use tari_comms_dht::Dht;
+use tari_service_framework::{
+    async_trait,
+    reply_channel,
+    ServiceInitializationError,
+    ServiceInitializer,
+    ServiceInitializerContext,
+};
+
+use self::{service::GossipService, state::GossipState};
+use crate::{
+    comms_connector::{PeerMessage, TopicSubscriptionFactory},
+    domain_message::DomainMessage,
+    proto::gossip::GossipControl,
+    services::utils::map_decode,
+    tari_message::TariMessageType,
+};
+
+const LOG_TARGET: &str = "p2p::services::gossip";
+
+/// The capability advertised by nodes that run the gossip service
+pub const GOSSIP_CAPABILITY: &str = "t/gossip";
+/// The version of the gossip capability implemented by this node
+pub const GOSSIP_CAPABILITY_VERSION: u32 = 1;
+
+/// Initializer for the Gossip service handle and service future.
+pub struct GossipInitializer {
+    config: Option<GossipConfig>,
+    topics: Vec<TariMessageType>,
+    inbound_message_subscription_factory: Arc<TopicSubscriptionFactory<TariMessageType, Arc<PeerMessage>>>,
+}
+
+impl GossipInitializer {
+    /// Create a new GossipInitializer that subscribes to the given topics
+    pub fn new(
+        config: GossipConfig,
+        topics: Vec<TariMessageType>,
+        inbound_message_subscription_factory: Arc<TopicSubscriptionFactory<TariMessageType, Arc<PeerMessage>>>,
+    ) -> Self {
+        Self {
+            config: Some(config),
+            topics,
+            inbound_message_subscription_factory,
+        }
+    }
+
+    /// Get a stream of inbound GossipControl messages
+    fn control_stream(&self) -> impl Stream<Item = DomainMessage<Result<GossipControl, prost::DecodeError>>> {
+        self.inbound_message_subscription_factory
+            .get_subscription(TariMessageType::GossipControl, "Gossip")
+            .map(map_decode::<GossipControl>)
+    }
+}
+
+#[async_trait]
+impl ServiceInitializer for GossipInitializer {
+    async fn initialize(&mut self, context: ServiceInitializerContext) -> Result<(), ServiceInitializationError> {
+        debug!(target: LOG_TARGET, "Initializing Gossip Service");
+        let (sender, receiver) = reply_channel::unbounded();
+
+        context.register_handle(GossipHandle::new(sender));
+
+        let config = self.config.take().expect("Gossip service initialized more than once.");
+        let state = GossipState::new(config.clone(), self.topics.iter().copied().collect());
+        let control_stream = self.control_stream();
+
+        context.spawn_when_ready(|handles| async move {
+            let dht = handles.expect_handle::<Dht>();
+            let connectivity = handles.expect_handle::<ConnectivityRequester>();
+
+            let service = GossipService::new(
+                config,
+                state,
+                receiver,
+                control_stream,
+                connectivity,
+                dht.outbound_requester(),
+                dht.dht_requester(),
+                handles.get_shutdown_signal(),
+            );
+            service.run().await;
+            debug!(target: LOG_TARGET, "Gossip service has shut down");
+        });
+
+        debug!(target: LOG_TARGET, "Gossip service initialized");
+        Ok(())
+    }
+}
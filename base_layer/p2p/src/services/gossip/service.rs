@@ -0,0 +1,452 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    collections::{HashMap, HashSet},
+    convert::TryFrom,
+    time::Instant,
+};
+
+use futures::{pin_mut, stream::StreamExt, Stream};
+use log::*;
+use tari_comms::{
+    connectivity::{ConnectivityEvent, ConnectivityRequester, ConnectivitySelection},
+    peer_manager::{NodeId, PeerFeatures},
+    BytesMut,
+};
+use tari_comms_dht::{
+    create_message_hash,
+    domain_message::OutboundDomainMessage,
+    outbound::{OutboundMessageRequester, SendMessageParams},
+    DhtRequester,
+};
+use tari_service_framework::reply_channel::RequestContext;
+use tari_shutdown::ShutdownSignal;
+use tari_utilities::hex::to_hex;
+use tokio::{time, time::MissedTickBehavior};
+
+use super::{
+    config::GossipConfig,
+    error::GossipError,
+    message_cache::{MessageCache, MessageId},
+    state::{GossipState, MeshChanges},
+    GossipRequest,
+    GossipResponse,
+    GOSSIP_CAPABILITY,
+    LOG_TARGET,
+};
+use crate::{
+    domain_message::DomainMessage,
+    proto::gossip::{GossipControl, GossipIHave, GossipIWant, GossipSubscriptions},
+    tari_message::TariMessageType,
+};
+
+/// The number of heartbeats that a peer has to deliver a message that it advertised and we requested
+const PROMISE_EXPIRY_HEARTBEATS: u32 = 3;
+
+/// Service responsible for maintaining topic meshes and propagating messages over them.
+pub struct GossipService<TRequestStream, TControlStream> {
+    config: GossipConfig,
+    state: GossipState,
+    message_cache: MessageCache,
+    request_rx: Option<TRequestStream>,
+    control_stream: Option<TControlStream>,
+    connectivity: ConnectivityRequester,
+    outbound_messaging: OutboundMessageRequester,
+    dht_requester: DhtRequester,
+    shutdown_signal: ShutdownSignal,
+}
+
+impl<TRequestStream, TControlStream> GossipService<TRequestStream, TControlStream>
+where
+    TRequestStream: Stream<Item = RequestContext<GossipRequest, Result<GossipResponse, GossipError>>>,
+    TControlStream: Stream<Item = DomainMessage<Result<GossipControl, prost::DecodeError>>>,
+{
+    pub fn new(
+        config: GossipConfig,
+        state: GossipState,
+        request_rx: TRequestStream,
+        control_stream: TControlStream,
+        connectivity: ConnectivityRequester,
+        outbound_messaging: OutboundMessageRequester,
+        dht_requester: DhtRequester,
+        shutdown_signal: ShutdownSignal,
+    ) -> Self {
+        Self {
+            message_cache: MessageCache::new(config.history_length, config.history_gossip),
+            config,
+            state,
+            request_rx: Some(request_rx),
+            control_stream: Some(control_stream),
+            connectivity,
+            outbound_messaging,
+            dht_requester,
+            shutdown_signal,
+        }
+    }
+
+    pub async fn run(mut self) {
+        debug!(target: LOG_TARGET, "Gossip service started");
+        debug!(target: LOG_TARGET, "Config = {:?}", self.config);
+        let request_stream = self.request_rx.take().expect("request_rx cannot be None").fuse();
+        pin_mut!(request_stream);
+        let control_stream = self
+            .control_stream
+            .take()
+            .expect("control_stream cannot be None")
+            .fuse();
+        pin_mut!(control_stream);
+
+        let mut connectivity_events = self.connectivity.get_event_subscription();
+        let mut heartbeat = time::interval(self.config.heartbeat_interval);
+        heartbeat.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                // Requests from the handle
+                Some(request_context) = request_stream.next() => {
+                    let (request, reply_tx) = request_context.split();
+                    let _result = reply_tx.send(self.handle_request(request).await);
+                },
+
+                // Incoming control messages from the Comms layer
+                Some(msg) = control_stream.next() => {
+                    if let Err(err) = self.handle_control_message(msg).await {
+                        warn!(target: LOG_TARGET, "Failed to handle incoming gossip control message: {}", err);
+                    }
+                },
+
+                Ok(event) = connectivity_events.recv() => {
+                    if let Err(err) = self.handle_connectivity_event(&event).await {
+                        warn!(target: LOG_TARGET, "Failed to handle connectivity event: {}", err);
+                    }
+                },
+
+                _ = heartbeat.tick() => {
+                    if let Err(err) = self.heartbeat().await {
+                        warn!(target: LOG_TARGET, "Error during gossip heartbeat: {}", err);
+                    }
+                },
+
+                _ = self.shutdown_signal.wait() => {
+                    info!(target: LOG_TARGET, "Gossip service shutting down because the shutdown signal was received");
+                    break;
+                }
+            }
+        }
+    }
+
+    async fn handle_request(&mut self, request: GossipRequest) -> Result<GossipResponse, GossipError> {
+        match request {
+            GossipRequest::Publish {
+                topic,
+                body,
+                exclude_peers,
+            } => {
+                let num_sent = self.publish(topic, body, exclude_peers).await?;
+                Ok(GossipResponse::Published(num_sent))
+            },
+            GossipRequest::ReportInvalidMessage(peer) => {
+                self.state.record_invalid_message(&peer);
+                Ok(GossipResponse::Ok)
+            },
+        }
+    }
+
+    async fn publish(
+        &mut self,
+        topic: TariMessageType,
+        body: BytesMut,
+        exclude_peers: Vec<NodeId>,
+    ) -> Result<usize, GossipError> {
+        let id = create_message_hash(&[], &body);
+        self.state.fulfil_promise(&id);
+        if !self.message_cache.put(id, topic, body.clone()) {
+            trace!(target: LOG_TARGET, "Message on topic {:?} already published", topic);
+            return Ok(0);
+        }
+        // The excluded peers are the peers that delivered this message to us
+        for peer in &exclude_peers {
+            self.state.record_first_delivery(peer, topic);
+        }
+
+        let exclude = exclude_peers.into_iter().collect::<HashSet<_>>();
+        let mut targets = self.state.publish_targets(topic, &exclude);
+        // Base nodes that have not announced gossip subscriptions receive the full message so that gossip can be
+        // rolled out alongside older nodes
+        let legacy_nodes = self
+            .connectivity
+            .select_connections(ConnectivitySelection::all_nodes(exclude.into_iter().collect()))
+            .await?;
+        targets.extend(
+            legacy_nodes
+                .iter()
+                .map(|conn| conn.peer_node_id())
+                .filter(|node_id| !self.state.is_gossip_peer(node_id))
+                .cloned(),
+        );
+
+        if targets.is_empty() {
+            debug!(target: LOG_TARGET, "No peers to publish message on topic {:?} to", topic);
+            return Ok(0);
+        }
+
+        let num_targets = targets.len();
+        debug!(
+            target: LOG_TARGET,
+            "Publishing message {} on topic {:?} to {} peer(s)",
+            to_hex(&id[..8]),
+            topic,
+            num_targets
+        );
+        self.outbound_messaging
+            .send_raw_no_wait(
+                SendMessageParams::new()
+                    .selected_peers(targets)
+                    .with_debug_info(format!("Gossip publish on topic {:?}", topic))
+                    .finish(),
+                body,
+            )
+            .await?;
+        Ok(num_targets)
+    }
+
+    async fn handle_connectivity_event(&mut self, event: &ConnectivityEvent) -> Result<(), GossipError> {
+        match event {
+            ConnectivityEvent::PeerConnected(conn)
+                if conn.peer_features().contains(PeerFeatures::COMMUNICATION_NODE) &&
+                    conn.capabilities().contains(GOSSIP_CAPABILITY) =>
+            {
+                let control = GossipControl {
+                    subscriptions: Some(self.subscriptions_message()),
+                    ..Default::default()
+                };
+                self.send_control(conn.peer_node_id().clone(), control).await?;
+            },
+            ConnectivityEvent::PeerDisconnected(node_id, _) | ConnectivityEvent::PeerBanned(node_id) => {
+                self.state.remove_peer(node_id);
+            },
+            _ => {},
+        }
+        Ok(())
+    }
+
+    async fn heartbeat(&mut self) -> Result<(), GossipError> {
+        let mut controls = HashMap::<NodeId, GossipControl>::new();
+
+        let MeshChanges { grafts, prunes } = self.state.maintain_meshes();
+        for (peer, topic) in grafts {
+            controls.entry(peer).or_default().graft.push(topic as i32);
+        }
+        for (peer, topic) in prunes {
+            controls.entry(peer).or_default().prune.push(topic as i32);
+        }
+
+        let topics = self.state.subscriptions().iter().copied().collect::<Vec<_>>();
+        for topic in topics {
+            let ids = self.message_cache.gossip_ids(topic);
+            if ids.is_empty() {
+                continue;
+            }
+            let message_ids = ids
+                .iter()
+                .take(self.config.max_ids_per_message)
+                .map(|id| id.to_vec())
+                .collect::<Vec<_>>();
+            for peer in self.state.select_lazy_peers(topic) {
+                controls.entry(peer).or_default().ihave.push(GossipIHave {
+                    topic: topic as i32,
+                    message_ids: message_ids.clone(),
+                });
+            }
+        }
+
+        for (peer, control) in controls {
+            self.send_control(peer, control).await?;
+        }
+
+        self.message_cache.shift();
+
+        for (id, peer) in self.state.take_expired_promises(Instant::now()) {
+            if !self.has_seen(&id).await? {
+                debug!(
+                    target: LOG_TARGET,
+                    "Peer {} did not deliver message {} that it advertised",
+                    peer,
+                    to_hex(&id[..8])
+                );
+                self.state.record_broken_promise(&peer);
+            }
+        }
+
+        self.state.decay_scores();
+        Ok(())
+    }
+
+    async fn handle_control_message(
+        &mut self,
+        msg: DomainMessage<Result<GossipControl, prost::DecodeError>>,
+    ) -> Result<(), GossipError> {
+        let DomainMessage::<_> { source_peer, inner, .. } = msg;
+        let node_id = source_peer.node_id;
+        let control = match inner {
+            Ok(control) => control,
+            Err(err) => {
+                self.state.record_invalid_message(&node_id);
+                return Err(err.into());
+            },
+        };
+
+        if let Some(subscriptions) = control.subscriptions {
+            let is_new = !self.state.is_gossip_peer(&node_id);
+            let topics = parse_topics(&subscriptions.topics);
+            trace!(
+                target: LOG_TARGET,
+                "Peer {} is subscribed to {} topic(s)",
+                node_id,
+                topics.len()
+            );
+            self.state.set_peer_topics(node_id.clone(), topics);
+            if is_new {
+                // Reply in case the peer connected before our gossip service was ready to announce
+                let control = GossipControl {
+                    subscriptions: Some(self.subscriptions_message()),
+                    ..Default::default()
+                };
+                self.send_control(node_id.clone(), control).await?;
+            }
+        }
+
+        let mut reply = GossipControl::default();
+        for topic in parse_topics(&control.graft) {
+            if !self.state.handle_graft(&node_id, topic) {
+                reply.prune.push(topic as i32);
+            }
+        }
+        for topic in parse_topics(&control.prune) {
+            self.state.handle_prune(&node_id, topic);
+        }
+
+        if !control.ihave.is_empty() && !self.state.is_graylisted(&node_id) {
+            let wanted = self.handle_ihave(&node_id, control.ihave).await?;
+            if !wanted.is_empty() {
+                reply.iwant = Some(GossipIWant { message_ids: wanted });
+            }
+        }
+
+        if let Some(iwant) = control.iwant {
+            if !self.state.is_graylisted(&node_id) {
+                self.handle_iwant(&node_id, iwant).await?;
+            }
+        }
+
+        if !reply.prune.is_empty() || reply.iwant.is_some() {
+            self.send_control(node_id, reply).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the IDs of advertised messages that we have not yet seen and should request from the peer
+    async fn handle_ihave(&mut self, node_id: &NodeId, ihave: Vec<GossipIHave>) -> Result<Vec<Vec<u8>>, GossipError> {
+        let expires_at = Instant::now() + self.config.heartbeat_interval * PROMISE_EXPIRY_HEARTBEATS;
+        let mut wanted = Vec::new();
+        for have in ihave {
+            let is_subscribed = TariMessageType::try_from(have.topic)
+                .map(|topic| self.state.is_subscribed(topic))
+                .unwrap_or(false);
+            if !is_subscribed {
+                continue;
+            }
+            for id in have.message_ids {
+                if wanted.len() >= self.config.max_ids_per_message {
+                    return Ok(wanted);
+                }
+                let Ok(id) = MessageId::try_from(id.as_slice()) else {
+                    continue;
+                };
+                if self.state.has_promise(&id) || self.has_seen(&id).await? {
+                    continue;
+                }
+                self.state.add_promise(id, node_id.clone(), expires_at);
+                wanted.push(id.to_vec());
+            }
+        }
+        Ok(wanted)
+    }
+
+    async fn handle_iwant(&mut self, node_id: &NodeId, iwant: GossipIWant) -> Result<(), GossipError> {
+        for id in iwant.message_ids.into_iter().take(self.config.max_ids_per_message) {
+            let Some(msg) = MessageId::try_from(id.as_slice())
+                .ok()
+                .and_then(|id| self.message_cache.get(&id))
+            else {
+                continue;
+            };
+            let body = msg.body.clone();
+            self.outbound_messaging
+                .send_raw_no_wait(
+                    SendMessageParams::new()
+                        .direct_node_id(node_id.clone())
+                        .with_debug_info("Gossip IWANT response".to_string())
+                        .finish(),
+                    body,
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn has_seen(&mut self, id: &MessageId) -> Result<bool, GossipError> {
+        if self.message_cache.contains(id) {
+            return Ok(true);
+        }
+        let hit_count = self.dht_requester.get_message_cache_hit_count(id.to_vec()).await?;
+        Ok(hit_count > 0)
+    }
+
+    fn subscriptions_message(&self) -> GossipSubscriptions {
+        GossipSubscriptions {
+            topics: self.state.subscriptions().iter().map(|t| *t as i32).collect(),
+        }
+    }
+
+    async fn send_control(&mut self, node_id: NodeId, control: GossipControl) -> Result<(), GossipError> {
+        self.outbound_messaging
+            .send_message(
+                SendMessageParams::new()
+                    .direct_node_id(node_id)
+                    .with_debug_info("Gossip control".to_string())
+                    .finish(),
+                OutboundDomainMessage::new(&TariMessageType::GossipControl, control),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+fn parse_topics(topics: &[i32]) -> HashSet<TariMessageType> {
+    topics
+        .iter()
+        .filter_map(|t| TariMessageType::try_from(*t).ok())
+        .collect()
+}
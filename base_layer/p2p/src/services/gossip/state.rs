@@ -0,0 +1,407 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    collections::{HashMap, HashSet},
+    time::Instant,
+};
+
+use rand::{rngs::OsRng, seq::SliceRandom};
+use tari_comms::peer_manager::NodeId;
+
+use super::{config::GossipConfig, message_cache::MessageId};
+use crate::tari_message::TariMessageType;
+
+/// Score awarded to a mesh peer each time it is the first to deliver a message to us
+const FIRST_DELIVERY_WEIGHT: f64 = 1.0;
+/// The maximum score that a peer can accumulate from first deliveries
+const FIRST_DELIVERY_CAP: f64 = 20.0;
+/// Score deducted when a peer advertises a message in IHAVE but does not deliver it after we request it
+const BROKEN_PROMISE_WEIGHT: f64 = -2.0;
+/// Score deducted when a peer delivers an invalid message
+const INVALID_MESSAGE_WEIGHT: f64 = -20.0;
+/// The factor applied to all scores on each heartbeat so that old behaviour is eventually forgotten
+const SCORE_DECAY: f64 = 0.9;
+/// Scores closer to zero than this are reset to zero on decay
+const SCORE_ZERO_THRESHOLD: f64 = 0.01;
+
+/// GRAFT and PRUNE messages that should be sent to peers as a result of mesh maintenance
+#[derive(Debug, Default)]
+pub struct MeshChanges {
+    pub grafts: Vec<(NodeId, TariMessageType)>,
+    pub prunes: Vec<(NodeId, TariMessageType)>,
+}
+
+/// Tracks topic subscriptions, per-topic meshes, peer scores and outstanding IWANT promises.
+#[derive(Debug)]
+pub struct GossipState {
+    config: GossipConfig,
+    subscriptions: HashSet<TariMessageType>,
+    peer_topics: HashMap<NodeId, HashSet<TariMessageType>>,
+    meshes: HashMap<TariMessageType, HashSet<NodeId>>,
+    scores: HashMap<NodeId, f64>,
+    promises: HashMap<MessageId, (NodeId, Instant)>,
+}
+
+impl GossipState {
+    pub fn new(config: GossipConfig, subscriptions: HashSet<TariMessageType>) -> Self {
+        let meshes = subscriptions.iter().map(|t| (*t, HashSet::new())).collect();
+        Self {
+            config,
+            subscriptions,
+            peer_topics: HashMap::new(),
+            meshes,
+            scores: HashMap::new(),
+            promises: HashMap::new(),
+        }
+    }
+
+    pub fn subscriptions(&self) -> &HashSet<TariMessageType> {
+        &self.subscriptions
+    }
+
+    pub fn is_subscribed(&self, topic: TariMessageType) -> bool {
+        self.subscriptions.contains(&topic)
+    }
+
+    /// Returns true if the peer has announced its subscriptions i.e. it supports gossip
+    pub fn is_gossip_peer(&self, peer: &NodeId) -> bool {
+        self.peer_topics.contains_key(peer)
+    }
+
+    /// Sets the topics that the peer has announced. The peer is removed from the mesh of any topic it is no longer
+    /// subscribed to.
+    pub fn set_peer_topics(&mut self, peer: NodeId, topics: HashSet<TariMessageType>) {
+        for (topic, mesh) in &mut self.meshes {
+            if !topics.contains(topic) {
+                mesh.remove(&peer);
+            }
+        }
+        self.peer_topics.insert(peer, topics);
+    }
+
+    /// Removes all state for a disconnected peer. The score is retained so that reconnecting does not reset it.
+    pub fn remove_peer(&mut self, peer: &NodeId) {
+        self.peer_topics.remove(peer);
+        for mesh in self.meshes.values_mut() {
+            mesh.remove(peer);
+        }
+        self.promises.retain(|_, (p, _)| p != peer);
+    }
+
+    pub fn score(&self, peer: &NodeId) -> f64 {
+        self.scores.get(peer).copied().unwrap_or(0.0)
+    }
+
+    pub fn is_graylisted(&self, peer: &NodeId) -> bool {
+        self.score(peer) < self.config.graylist_threshold
+    }
+
+    fn adjust_score(&mut self, peer: &NodeId, delta: f64, cap: Option<f64>) {
+        let score = self.scores.entry(peer.clone()).or_insert(0.0);
+        *score += delta;
+        if let Some(cap) = cap {
+            *score = score.min(cap);
+        }
+    }
+
+    /// Credits a peer that was the first to deliver a message on a topic for which it is in our mesh
+    pub fn record_first_delivery(&mut self, peer: &NodeId, topic: TariMessageType) {
+        if self.meshes.get(&topic).is_some_and(|m| m.contains(peer)) {
+            self.adjust_score(peer, FIRST_DELIVERY_WEIGHT, Some(FIRST_DELIVERY_CAP));
+        }
+    }
+
+    /// Penalises a peer that delivered an invalid message
+    pub fn record_invalid_message(&mut self, peer: &NodeId) {
+        self.adjust_score(peer, INVALID_MESSAGE_WEIGHT, None);
+    }
+
+    pub fn mesh_peers(&self, topic: TariMessageType) -> impl Iterator<Item = &NodeId> {
+        self.meshes.get(&topic).into_iter().flatten()
+    }
+
+    /// Handles a GRAFT from a peer. Returns true if the peer was added to (or is already in) the mesh, or false if the
+    /// GRAFT was rejected and the peer should be sent a PRUNE. A GRAFT is rejected if the peer is graylisted or either
+    /// side is not subscribed to the topic.
+    pub fn handle_graft(&mut self, peer: &NodeId, topic: TariMessageType) -> bool {
+        if self.is_graylisted(peer) || !self.peer_topics.get(peer).is_some_and(|t| t.contains(&topic)) {
+            return false;
+        }
+        // An oversubscribed mesh is pruned back to mesh_n on the next heartbeat
+        match self.meshes.get_mut(&topic) {
+            Some(mesh) => {
+                mesh.insert(peer.clone());
+                true
+            },
+            None => false,
+        }
+    }
+
+    pub fn handle_prune(&mut self, peer: &NodeId, topic: TariMessageType) {
+        if let Some(mesh) = self.meshes.get_mut(&topic) {
+            mesh.remove(peer);
+        }
+    }
+
+    fn eligible_peers<'a>(
+        &'a self,
+        topic: TariMessageType,
+        exclude: &'a HashSet<NodeId>,
+    ) -> impl Iterator<Item = &'a NodeId> + 'a {
+        self.peer_topics
+            .iter()
+            .filter(move |(peer, topics)| {
+                topics.contains(&topic) && !exclude.contains(*peer) && !self.is_graylisted(peer)
+            })
+            .map(|(peer, _)| peer)
+    }
+
+    /// Maintains the mesh for each subscribed topic, removing graylisted peers, grafting peers when a mesh is below
+    /// `mesh_n_low` and pruning the lowest scoring peers when a mesh is above `mesh_n_high`.
+    pub fn maintain_meshes(&mut self) -> MeshChanges {
+        let mut changes = MeshChanges::default();
+        let topics = self.subscriptions.iter().copied().collect::<Vec<_>>();
+        for topic in topics {
+            let mesh = self.meshes.get(&topic).cloned().unwrap_or_default();
+
+            let graylisted = mesh
+                .iter()
+                .filter(|p| self.is_graylisted(p))
+                .cloned()
+                .collect::<Vec<_>>();
+            let mut mesh = mesh;
+            for peer in graylisted {
+                mesh.remove(&peer);
+                changes.prunes.push((peer, topic));
+            }
+
+            if mesh.len() < self.config.mesh_n_low {
+                let mut candidates = self.eligible_peers(topic, &mesh).cloned().collect::<Vec<_>>();
+                candidates.shuffle(&mut OsRng);
+                let needed = self.config.mesh_n.saturating_sub(mesh.len());
+                for peer in candidates.into_iter().take(needed) {
+                    mesh.insert(peer.clone());
+                    changes.grafts.push((peer, topic));
+                }
+            }
+
+            if mesh.len() > self.config.mesh_n_high {
+                let mut peers = mesh.iter().cloned().collect::<Vec<_>>();
+                // Shuffle first so that peers with equal scores are pruned at random
+                peers.shuffle(&mut OsRng);
+                peers.sort_by(|a, b| self.score(b).total_cmp(&self.score(a)));
+                for peer in peers.into_iter().skip(self.config.mesh_n) {
+                    mesh.remove(&peer);
+                    changes.prunes.push((peer, topic));
+                }
+            }
+
+            self.meshes.insert(topic, mesh);
+        }
+        changes
+    }
+
+    /// Selects up to `gossip_lazy` random non-mesh peers to send IHAVE gossip to for the given topic
+    pub fn select_lazy_peers(&self, topic: TariMessageType) -> Vec<NodeId> {
+        let mesh = self.meshes.get(&topic).cloned().unwrap_or_default();
+        let mut peers = self.eligible_peers(topic, &mesh).cloned().collect::<Vec<_>>();
+        peers.shuffle(&mut OsRng);
+        peers.truncate(self.config.gossip_lazy);
+        peers
+    }
+
+    /// Returns the peers that a message on the given topic should be sent to in full. If we are subscribed to the
+    /// topic these are the mesh peers, otherwise `mesh_n` random peers that are subscribed to the topic.
+    pub fn publish_targets(&self, topic: TariMessageType, exclude: &HashSet<NodeId>) -> Vec<NodeId> {
+        if self.is_subscribed(topic) {
+            return self
+                .mesh_peers(topic)
+                .filter(|p| !exclude.contains(*p))
+                .cloned()
+                .collect();
+        }
+        let mut peers = self.eligible_peers(topic, exclude).cloned().collect::<Vec<_>>();
+        peers.shuffle(&mut OsRng);
+        peers.truncate(self.config.mesh_n);
+        peers
+    }
+
+    pub fn has_promise(&self, id: &MessageId) -> bool {
+        self.promises.contains_key(id)
+    }
+
+    /// Records that the peer advertised the message and that we requested it
+    pub fn add_promise(&mut self, id: MessageId, peer: NodeId, expires_at: Instant) {
+        self.promises.insert(id, (peer, expires_at));
+    }
+
+    /// Removes the promise for a message that has been received
+    pub fn fulfil_promise(&mut self, id: &MessageId) {
+        self.promises.remove(id);
+    }
+
+    /// Removes and returns the promises that have expired
+    pub fn take_expired_promises(&mut self, now: Instant) -> Vec<(MessageId, NodeId)> {
+        let expired = self
+            .promises
+            .iter()
+            .filter(|(_, (_, expires_at))| *expires_at <= now)
+            .map(|(id, (peer, _))| (*id, peer.clone()))
+            .collect::<Vec<_>>();
+        for (id, _) in &expired {
+            self.promises.remove(id);
+        }
+        expired
+    }
+
+    /// Penalises a peer that did not deliver a message it advertised
+    pub fn record_broken_promise(&mut self, peer: &NodeId) {
+        self.adjust_score(peer, BROKEN_PROMISE_WEIGHT, None);
+    }
+
+    /// Decays all scores towards zero
+    pub fn decay_scores(&mut self) {
+        for score in self.scores.values_mut() {
+            *score *= SCORE_DECAY;
+        }
+        self.scores.retain(|_, score| score.abs() >= SCORE_ZERO_THRESHOLD);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::test_utils::make_node_identity;
+
+    fn random_node_id() -> NodeId {
+        make_node_identity().node_id().clone()
+    }
+
+    fn topics() -> HashSet<TariMessageType> {
+        [TariMessageType::NewBlock].into_iter().collect()
+    }
+
+    fn create_state_with_peers(n: usize) -> (GossipState, Vec<NodeId>) {
+        let mut state = GossipState::new(GossipConfig::default(), topics());
+        let peers = (0..n).map(|_| random_node_id()).collect::<Vec<_>>();
+        for peer in &peers {
+            state.set_peer_topics(peer.clone(), topics());
+        }
+        (state, peers)
+    }
+
+    #[test]
+    fn it_grafts_peers_up_to_mesh_n() {
+        let (mut state, _) = create_state_with_peers(10);
+        let changes = state.maintain_meshes();
+        assert_eq!(changes.grafts.len(), GossipConfig::default().mesh_n);
+        assert!(changes.prunes.is_empty());
+        assert_eq!(state.mesh_peers(TariMessageType::NewBlock).count(), 6);
+
+        // Mesh is within bounds so nothing changes
+        let changes = state.maintain_meshes();
+        assert!(changes.grafts.is_empty());
+        assert!(changes.prunes.is_empty());
+
+        // Lazy peers are never mesh peers
+        let lazy = state.select_lazy_peers(TariMessageType::NewBlock);
+        assert_eq!(lazy.len(), 4);
+        assert!(lazy
+            .iter()
+            .all(|p| !state.mesh_peers(TariMessageType::NewBlock).any(|m| m == p)));
+    }
+
+    #[test]
+    fn it_prunes_lowest_scoring_peers_when_oversubscribed() {
+        let (mut state, peers) = create_state_with_peers(13);
+        for peer in &peers {
+            assert!(state.handle_graft(peer, TariMessageType::NewBlock));
+        }
+        assert_eq!(state.mesh_peers(TariMessageType::NewBlock).count(), 13);
+        for peer in &peers[..6] {
+            state.record_first_delivery(peer, TariMessageType::NewBlock);
+        }
+        let changes = state.maintain_meshes();
+        assert_eq!(changes.prunes.len(), 7);
+        assert!(changes.grafts.is_empty());
+        assert!(state
+            .mesh_peers(TariMessageType::NewBlock)
+            .all(|p| state.score(p) > 0.0));
+    }
+
+    #[test]
+    fn it_graylists_and_removes_misbehaving_peers() {
+        let (mut state, peers) = create_state_with_peers(3);
+        assert!(state.handle_graft(&peers[0], TariMessageType::NewBlock));
+        state.record_invalid_message(&peers[0]);
+        assert!(state.is_graylisted(&peers[0]));
+        assert!(!state.handle_graft(&peers[0], TariMessageType::NewBlock));
+
+        let changes = state.maintain_meshes();
+        assert!(changes.prunes.contains(&(peers[0].clone(), TariMessageType::NewBlock)));
+        assert!(!changes.grafts.iter().any(|(p, _)| *p == peers[0]));
+
+        // Scores decay back towards zero
+        for _ in 0..50 {
+            state.decay_scores();
+        }
+        assert!(!state.is_graylisted(&peers[0]));
+    }
+
+    #[test]
+    fn it_expires_promises() {
+        let (mut state, peers) = create_state_with_peers(1);
+        let now = Instant::now();
+        state.add_promise([1; 32], peers[0].clone(), now + Duration::from_secs(1));
+        state.add_promise([2; 32], peers[0].clone(), now + Duration::from_secs(5));
+        state.fulfil_promise(&[2; 32]);
+        assert!(state.take_expired_promises(now).is_empty());
+        let expired = state.take_expired_promises(now + Duration::from_secs(2));
+        assert_eq!(expired, vec![([1; 32], peers[0].clone())]);
+        assert!(!state.has_promise(&[1; 32]));
+    }
+
+    #[test]
+    fn it_selects_publish_targets() {
+        let (mut state, peers) = create_state_with_peers(8);
+        state.maintain_meshes();
+        let exclude = state
+            .mesh_peers(TariMessageType::NewBlock)
+            .take(1)
+            .cloned()
+            .collect::<HashSet<_>>();
+        let targets = state.publish_targets(TariMessageType::NewBlock, &exclude);
+        assert_eq!(targets.len(), 5);
+        assert!(targets.iter().all(|p| !exclude.contains(p)));
+
+        // Not subscribed to this topic, so fan out to peers that are
+        state.set_peer_topics(
+            peers[0].clone(),
+            [TariMessageType::NewTransaction].into_iter().collect(),
+        );
+        let targets = state.publish_targets(TariMessageType::NewTransaction, &HashSet::new());
+        assert_eq!(targets, vec![peers[0].clone()]);
+    }
+}
@@ -35,6 +35,10 @@ pub struct LivenessConfig {
     pub monitored_peers: Vec<NodeId>,
     /// Number of ping failures to tolerate before disconnecting the peer. A value of zero disables this feature.
     pub max_allowed_ping_failures: usize,
+    /// Peers (other than monitored peers) that keep responding to pings are pinged progressively less often, up to
+    /// once every `max_ping_interval_multiplier` rounds. A value of 1 pings every peer at the auto ping interval.
+    /// (Default: 4)
+    pub max_ping_interval_multiplier: u32,
 }
 
 impl Default for LivenessConfig {
@@ -44,6 +48,7 @@ impl Default for LivenessConfig {
             num_peers_per_round: 8,
             monitored_peers: Default::default(),
             max_allowed_ping_failures: 2,
+            max_ping_interval_multiplier: 4,
         }
     }
 }
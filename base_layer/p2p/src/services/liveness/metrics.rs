@@ -0,0 +1,80 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use once_cell::sync::Lazy;
+use tari_metrics::{Histogram, IntCounter, IntGauge};
+
+pub fn pings_sent() -> IntCounter {
+    static METER: Lazy<IntCounter> = Lazy::new(|| {
+        tari_metrics::register_int_counter("p2p::liveness::pings_sent", "The number of pings sent to peers").unwrap()
+    });
+
+    METER.clone()
+}
+
+pub fn pongs_received() -> IntCounter {
+    static METER: Lazy<IntCounter> = Lazy::new(|| {
+        tari_metrics::register_int_counter(
+            "p2p::liveness::pongs_received",
+            "The number of pongs received in response to our pings",
+        )
+        .unwrap()
+    });
+
+    METER.clone()
+}
+
+pub fn ping_failures() -> IntCounter {
+    static METER: Lazy<IntCounter> = Lazy::new(|| {
+        tari_metrics::register_int_counter(
+            "p2p::liveness::ping_failures",
+            "The number of pings that were not answered within the inflight TTL",
+        )
+        .unwrap()
+    });
+
+    METER.clone()
+}
+
+pub fn round_trip_time() -> Histogram {
+    static METER: Lazy<Histogram> = Lazy::new(|| {
+        tari_metrics::register_histogram(
+            "p2p::liveness::round_trip_time",
+            "The time in seconds between sending a ping and receiving the corresponding pong",
+        )
+        .unwrap()
+    });
+
+    METER.clone()
+}
+
+pub fn scheduled_peers() -> IntGauge {
+    static METER: Lazy<IntGauge> = Lazy::new(|| {
+        tari_metrics::register_int_gauge(
+            "p2p::liveness::scheduled_peers",
+            "The number of connected peers in the adaptive ping schedule",
+        )
+        .unwrap()
+    });
+
+    METER.clone()
+}
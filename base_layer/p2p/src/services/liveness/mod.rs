@@ -29,8 +29,9 @@
 //! - handling requests to the Liveness backend. Types of requests can be found in the [LivenessRequest] enum, and
 //! - reading incoming [PingPong] messages and processing them.
 //!
-//! In future, this service may be expanded to included periodic pings to maintain
-//! latency and availability statistics for peers.
+//! When an auto ping interval is configured, connected peers are pinged periodically to maintain latency and
+//! availability statistics. Monitored peers (e.g. sync peers) are pinged every round, while other peers are pinged
+//! less often the longer they remain responsive.
 //!
 //! [LivenessRequest]: ./messages/enum.LivenessRequets.html
 //! [PingPong]: ./messages/enum.PingPong.html
@@ -51,6 +52,9 @@ pub use handle::{
 };
 
 mod message;
+#[cfg(feature = "metrics")]
+mod metrics;
+mod schedule;
 mod service;
 
 mod state;
@@ -0,0 +1,185 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::collections::HashMap;
+
+use tari_comms::peer_manager::NodeId;
+
+/// Decides which connected peers are due a ping in each round. Peers that reliably respond are pinged progressively
+/// less often, up to once every `max_interval_multiplier` rounds, and drop back to every round as soon as a ping
+/// fails. Monitored peers are not scheduled here because they are pinged every round.
+#[derive(Debug, Clone)]
+pub struct PingScheduler {
+    max_interval_multiplier: u32,
+    peers: HashMap<NodeId, PeerSchedule>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct PeerSchedule {
+    /// The number of rounds between pings to this peer
+    interval: u32,
+    /// The number of rounds until this peer is due a ping. Zero or less means that the peer is due (or overdue).
+    due_in: i64,
+}
+
+impl PingScheduler {
+    pub fn new(max_interval_multiplier: u32) -> Self {
+        Self {
+            max_interval_multiplier: max_interval_multiplier.max(1),
+            peers: HashMap::new(),
+        }
+    }
+
+    /// Starts a new round. Peers that are no longer connected are forgotten and newly connected peers are due
+    /// immediately. Returns the peers that are due a ping, most overdue first.
+    pub fn next_round<'a, I: IntoIterator<Item = &'a NodeId>>(&mut self, connected: I) -> Vec<NodeId> {
+        let mut peers = HashMap::with_capacity(self.peers.len());
+        for node_id in connected {
+            let mut schedule = self
+                .peers
+                .remove(node_id)
+                .unwrap_or(PeerSchedule { interval: 1, due_in: 1 });
+            schedule.due_in -= 1;
+            peers.insert(node_id.clone(), schedule);
+        }
+        self.peers = peers;
+
+        let mut due = self
+            .peers
+            .iter()
+            .filter(|(_, s)| s.due_in <= 0)
+            .map(|(n, s)| (n.clone(), s.due_in))
+            .collect::<Vec<_>>();
+        due.sort_by_key(|(_, due_in)| *due_in);
+        due.into_iter().map(|(n, _)| n).collect()
+    }
+
+    /// Records that a ping was sent to the peer in this round
+    pub fn on_ping_sent(&mut self, node_id: &NodeId) {
+        if let Some(schedule) = self.peers.get_mut(node_id) {
+            schedule.due_in = i64::from(schedule.interval);
+        }
+    }
+
+    /// Records a pong from the peer. The peer will be pinged half as often, up to the maximum interval.
+    pub fn on_pong(&mut self, node_id: &NodeId) {
+        let max = self.max_interval_multiplier;
+        if let Some(schedule) = self.peers.get_mut(node_id) {
+            schedule.interval = schedule.interval.saturating_mul(2).min(max);
+        }
+    }
+
+    /// Records a failed ping to the peer. The peer will be pinged every round until it responds again.
+    pub fn on_ping_failed(&mut self, node_id: &NodeId) {
+        if let Some(schedule) = self.peers.get_mut(node_id) {
+            schedule.interval = 1;
+            schedule.due_in = schedule.due_in.min(1);
+        }
+    }
+
+    /// Returns the current ping interval of the peer in rounds, or None if the peer is not scheduled
+    pub fn interval_of(&self, node_id: &NodeId) -> Option<u32> {
+        self.peers.get(node_id).map(|s| s.interval)
+    }
+
+    pub fn num_scheduled(&self) -> usize {
+        self.peers.len()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tari_comms::types::CommsPublicKey;
+    use tari_crypto::keys::PublicKey;
+
+    use super::*;
+
+    fn random_node_id() -> NodeId {
+        let (_, pk) = CommsPublicKey::random_keypair(&mut rand::rngs::OsRng);
+        NodeId::from_public_key(&pk)
+    }
+
+    #[test]
+    fn it_pings_new_peers_immediately() {
+        let mut scheduler = PingScheduler::new(8);
+        let peers = vec![random_node_id(), random_node_id()];
+        let due = scheduler.next_round(&peers);
+        assert_eq!(due.len(), 2);
+        assert_eq!(scheduler.num_scheduled(), 2);
+    }
+
+    #[test]
+    fn it_backs_off_stable_peers() {
+        let mut scheduler = PingScheduler::new(4);
+        let peer = random_node_id();
+        let peers = vec![peer.clone()];
+
+        let mut pinged_rounds = vec![];
+        for round in 0..12 {
+            if scheduler.next_round(&peers).contains(&peer) {
+                scheduler.on_ping_sent(&peer);
+                scheduler.on_pong(&peer);
+                pinged_rounds.push(round);
+            }
+        }
+        // Pinged at intervals of 1, 2, 4, 4 ... rounds
+        assert_eq!(pinged_rounds, vec![0, 1, 3, 7, 11]);
+        assert_eq!(scheduler.interval_of(&peer), Some(4));
+    }
+
+    #[test]
+    fn it_resets_the_interval_on_failure() {
+        let mut scheduler = PingScheduler::new(8);
+        let peer = random_node_id();
+        let peers = vec![peer.clone()];
+        scheduler.next_round(&peers);
+        scheduler.on_ping_sent(&peer);
+        scheduler.on_pong(&peer);
+        scheduler.on_pong(&peer);
+        assert_eq!(scheduler.interval_of(&peer), Some(4));
+
+        scheduler.on_ping_failed(&peer);
+        assert_eq!(scheduler.interval_of(&peer), Some(1));
+        assert_eq!(scheduler.next_round(&peers), vec![peer]);
+    }
+
+    #[test]
+    fn it_forgets_disconnected_peers() {
+        let mut scheduler = PingScheduler::new(8);
+        let peer1 = random_node_id();
+        let peer2 = random_node_id();
+        scheduler.next_round([&peer1, &peer2]);
+        scheduler.next_round([&peer2]);
+        assert_eq!(scheduler.num_scheduled(), 1);
+        assert!(scheduler.interval_of(&peer1).is_none());
+    }
+
+    #[test]
+    fn it_returns_the_most_overdue_peers_first() {
+        let mut scheduler = PingScheduler::new(8);
+        let peer1 = random_node_id();
+        let peer2 = random_node_id();
+        scheduler.next_round([&peer1]);
+        let due = scheduler.next_round([&peer1, &peer2]);
+        assert_eq!(due, vec![peer1, peer2]);
+    }
+}
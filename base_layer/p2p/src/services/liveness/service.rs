@@ -22,7 +22,7 @@
 
 use std::{iter, sync::Arc, time::Instant};
 
-use futures::{future::Either, pin_mut, stream::StreamExt, Stream};
+use futures::{future, future::Either, pin_mut, stream::StreamExt, Stream};
 use log::*;
 use tari_comms::{
     connectivity::{ConnectivityRequester, ConnectivitySelection},
@@ -44,6 +44,7 @@ use super::{
     config::LivenessConfig,
     error::LivenessError,
     message::{PingPong, PingPongMessage},
+    schedule::PingScheduler,
     state::LivenessState,
     LivenessRequest,
     LivenessResponse,
@@ -61,6 +62,7 @@ pub struct LivenessService<THandleStream, TPingStream> {
     request_rx: Option<THandleStream>,
     ping_stream: Option<TPingStream>,
     state: LivenessState,
+    scheduler: PingScheduler,
     connectivity: ConnectivityRequester,
     outbound_messaging: OutboundMessageRequester,
     event_publisher: LivenessEventSender,
@@ -89,6 +91,7 @@ where
             request_rx: Some(request_rx),
             ping_stream: Some(ping_stream),
             state,
+            scheduler: PingScheduler::new(config.max_ping_interval_multiplier),
             connectivity,
             outbound_messaging,
             event_publisher,
@@ -219,6 +222,15 @@ where
                     message_tag,
                 );
 
+                self.scheduler.on_pong(&node_id);
+                #[cfg(feature = "metrics")]
+                super::metrics::pongs_received().inc();
+                if let Some(latency) = maybe_latency {
+                    #[cfg(feature = "metrics")]
+                    super::metrics::round_trip_time().observe(latency.as_secs_f64());
+                    self.peer_manager.latency_tracker().record(&node_id, latency);
+                }
+
                 let pong_event = PingPongEvent::new(node_id.clone(), maybe_latency, ping_pong_msg.metadata.into());
                 self.publish_event(LivenessEvent::ReceivedPong(Box::new(pong_event)));
 
@@ -236,6 +248,8 @@ where
         let msg = PingPongMessage::ping_with_metadata(self.state.metadata().clone());
         self.state.add_inflight_ping(msg.nonce, node_id.clone());
         debug!(target: LOG_TARGET, "Sending ping to peer '{}'", node_id.short_str(),);
+        #[cfg(feature = "metrics")]
+        super::metrics::pings_sent().inc();
 
         self.outbound_messaging
             .send_direct_node_id(
@@ -310,17 +324,8 @@ where
 
     async fn start_ping_round(&mut self) -> Result<(), LivenessError> {
         let monitored_peers = { self.monitored_peers.read().await.clone() };
-        let selected_peers = self
-            .connectivity
-            .select_connections(ConnectivitySelection::random_nodes(
-                self.config.num_peers_per_round,
-                Default::default(),
-            ))
-            .await?
-            .into_iter()
-            .map(|c| c.peer_node_id().clone())
-            .chain(monitored_peers)
-            .collect::<Vec<_>>();
+        let scheduled_peers = self.select_scheduled_peers(&monitored_peers).await?;
+        let selected_peers = scheduled_peers.into_iter().chain(monitored_peers).collect::<Vec<_>>();
 
         if selected_peers.is_empty() {
             debug!(
@@ -331,23 +336,67 @@ where
 
         let len_peers = selected_peers.len();
 
-        for peer in selected_peers {
-            let msg = PingPongMessage::ping_with_metadata(self.state.metadata().clone());
-            self.state.add_inflight_ping(msg.nonce, peer.clone());
-            self.outbound_messaging
-                .send_direct_node_id(
-                    peer,
-                    OutboundDomainMessage::new(&TariMessageType::PingPong, msg),
-                    "Start ping round".to_string(),
-                )
-                .await?;
-        }
+        // Queue the pings for the whole round at once rather than waiting for each to be accepted in turn
+        let pings = selected_peers
+            .into_iter()
+            .map(|peer| {
+                let msg = PingPongMessage::ping_with_metadata(self.state.metadata().clone());
+                self.state.add_inflight_ping(msg.nonce, peer.clone());
+                let mut outbound_messaging = self.outbound_messaging.clone();
+                async move {
+                    outbound_messaging
+                        .send_direct_node_id(
+                            peer,
+                            OutboundDomainMessage::new(&TariMessageType::PingPong, msg),
+                            "Start ping round".to_string(),
+                        )
+                        .await
+                }
+            })
+            .collect::<Vec<_>>();
+        future::try_join_all(pings).await?;
+        #[cfg(feature = "metrics")]
+        super::metrics::pings_sent().inc_by(len_peers as u64);
 
         self.publish_event(LivenessEvent::PingRoundBroadcast(len_peers));
 
         Ok(())
     }
 
+    /// Returns up to `num_peers_per_round` connected nodes that are due a ping according to the adaptive schedule
+    async fn select_scheduled_peers(&mut self, monitored_peers: &[NodeId]) -> Result<Vec<NodeId>, LivenessError> {
+        if self.config.num_peers_per_round == 0 {
+            return Ok(Vec::new());
+        }
+
+        for (node_id, _) in self.state.failed_pings_iter() {
+            self.scheduler.on_ping_failed(node_id);
+        }
+
+        let connected = self
+            .connectivity
+            .select_connections(ConnectivitySelection::all_nodes(monitored_peers.to_vec()))
+            .await?;
+        let due = self.scheduler.next_round(connected.iter().map(|c| c.peer_node_id()));
+        #[cfg(feature = "metrics")]
+        super::metrics::scheduled_peers().set(i64::try_from(self.scheduler.num_scheduled()).unwrap_or(i64::MAX));
+
+        let selected = due
+            .into_iter()
+            .take(self.config.num_peers_per_round)
+            .collect::<Vec<_>>();
+        for node_id in &selected {
+            self.scheduler.on_ping_sent(node_id);
+        }
+        trace!(
+            target: LOG_TARGET,
+            "{} of {} connected peer(s) selected for this ping round",
+            selected.len(),
+            self.scheduler.num_scheduled()
+        );
+        Ok(selected)
+    }
+
     async fn disconnect_failed_peers(&mut self) -> Result<(), LivenessError> {
         let max_allowed_ping_failures = self.config.max_allowed_ping_failures;
         for node_id in self
@@ -151,6 +151,8 @@ impl LivenessState {
         self.inflight_pings = inflight;
 
         for (_, (node_id, _)) in expired {
+            #[cfg(feature = "metrics")]
+            super::metrics::ping_failures().inc();
             self.failed_pings
                 .entry(node_id)
                 .and_modify(|v| {
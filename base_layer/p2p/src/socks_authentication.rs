@@ -60,9 +60,11 @@ impl FromStr for SocksAuthentication {
             "username_password" => {
                 let (username, password) = maybe_value
                     .and_then(|value| {
-                        let (un, pwd) = parse_key_value(value, ':');
+                        // Usernames are case-sensitive, so they are not normalised like the auth type
+                        let mut parts = value.splitn(2, ':');
+                        let un = parts.next().expect("splitn always emits at least one part");
                         // If pwd is None, return None
-                        pwd.map(|p| (un, p))
+                        parts.next().map(|p| (un.to_string(), p))
                     })
                     .ok_or_else(|| {
                         "invalid format for 'username-password' socks authentication type. It should be in the format \
@@ -102,3 +104,25 @@ impl From<SocksAuthentication> for socks::Authentication {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_parses_username_password_auth() {
+        let auth = SocksAuthentication::from_str("username_password=MyUser:pass:word").unwrap();
+        match auth {
+            SocksAuthentication::UsernamePassword { username, password } => {
+                assert_eq!(username, "MyUser");
+                assert_eq!(password, "pass:word");
+            },
+            SocksAuthentication::None => panic!("Expected username/password auth"),
+        }
+        assert!(matches!(
+            SocksAuthentication::from_str("NONE").unwrap(),
+            SocksAuthentication::None
+        ));
+        SocksAuthentication::from_str("username_password=nopassword").unwrap_err();
+    }
+}
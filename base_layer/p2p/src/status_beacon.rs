@@ -0,0 +1,386 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Signed network status beacons. Well-known nodes periodically publish their tip over the DHT and HTTP, so that
+//! wallets can detect when their base node is on a minority fork or far behind the rest of the network.
+
+use std::{collections::HashMap, convert::TryFrom, time::Duration};
+
+use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
+use tari_common::configuration::Network;
+use tari_comms::types::{CommsPublicKey, CommsSecretKey};
+use tari_crypto::{hash_domain, keys::PublicKey, signatures::SchnorrSignature};
+use tari_utilities::{epoch_time::EpochTime, hex::Hex, ByteArray};
+use thiserror::Error;
+
+use crate::proto::status_beacon as proto;
+pub use crate::proto::status_beacon::StatusBeacon as StatusBeaconMessage;
+
+hash_domain!(StatusBeaconHashDomain, "com.tari.p2p.status_beacon", 0);
+
+pub type StatusBeaconSignature = SchnorrSignature<CommsPublicKey, CommsSecretKey, StatusBeaconHashDomain>;
+
+/// Beacons with a timestamp further than this in the future are rejected
+const MAX_FUTURE_TIMESTAMP_SECS: u64 = 5 * 60;
+const TIP_HASH_LENGTH: usize = 32;
+const MAX_SOFTWARE_VERSION_LENGTH: usize = 64;
+
+#[derive(Debug, Error)]
+pub enum StatusBeaconError {
+    #[error("Unsupported status beacon version {0}")]
+    UnsupportedVersion(u8),
+    #[error("Status beacon is for network {actual}, expected {expected}")]
+    WrongNetwork { expected: Network, actual: Network },
+    #[error("Status beacon was issued in the future")]
+    IssuedInFuture,
+    #[error("Malformed status beacon: {0}")]
+    MalformedBeacon(String),
+    #[error("Malformed status beacon signature")]
+    MalformedSignature,
+    #[error("Status beacon signature is invalid")]
+    InvalidSignature,
+    #[error("Status beacon signer {0} is not trusted")]
+    UntrustedSigner(CommsPublicKey),
+    #[error("Malformed status beacon JSON: {0}")]
+    MalformedJson(#[from] serde_json::Error),
+    #[cfg(feature = "status-beacon")]
+    #[error("Failed to download status beacon: {0}")]
+    DownloadError(#[from] reqwest::Error),
+}
+
+/// The tip of a node at a point in time, signed by the node
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedStatusBeacon {
+    pub version: u8,
+    pub network: Network,
+    pub tip_height: u64,
+    /// Hex encoded hash of the tip block
+    pub tip_hash: String,
+    /// Unix timestamp in seconds
+    pub timestamp: u64,
+    pub software_version: String,
+    pub signer: CommsPublicKey,
+    pub public_nonce: String,
+    pub signature: String,
+}
+
+impl SignedStatusBeacon {
+    pub const LATEST_VERSION: u8 = 0;
+
+    pub fn sign(
+        secret_key: &CommsSecretKey,
+        network: Network,
+        tip_height: u64,
+        tip_hash: &[u8],
+        timestamp: u64,
+        software_version: String,
+    ) -> Self {
+        let signer = CommsPublicKey::from_secret_key(secret_key);
+        let message = Self::construct_message(
+            Self::LATEST_VERSION,
+            network,
+            tip_height,
+            tip_hash,
+            timestamp,
+            &software_version,
+            &signer,
+        );
+        let signature = StatusBeaconSignature::sign(secret_key, message, &mut OsRng)
+            .expect("unreachable panic: status beacon message is hashed to the correct length");
+        Self {
+            version: Self::LATEST_VERSION,
+            network,
+            tip_height,
+            tip_hash: tip_hash.to_hex(),
+            timestamp,
+            software_version,
+            signer,
+            public_nonce: signature.get_public_nonce().to_hex(),
+            signature: signature.get_signature().to_hex(),
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, StatusBeaconError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn to_json(&self) -> Result<String, StatusBeaconError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Verifies that the beacon was signed by the signer it claims for the given network. If `trusted_signers` is
+    /// not empty, the signer must also be one of them.
+    pub fn verify(&self, network: Network, trusted_signers: &[CommsPublicKey]) -> Result<(), StatusBeaconError> {
+        if self.version != Self::LATEST_VERSION {
+            return Err(StatusBeaconError::UnsupportedVersion(self.version));
+        }
+        if self.network != network {
+            return Err(StatusBeaconError::WrongNetwork {
+                expected: network,
+                actual: self.network,
+            });
+        }
+        if self.timestamp > EpochTime::now().as_u64().saturating_add(MAX_FUTURE_TIMESTAMP_SECS) {
+            return Err(StatusBeaconError::IssuedInFuture);
+        }
+        if self.software_version.len() > MAX_SOFTWARE_VERSION_LENGTH {
+            return Err(StatusBeaconError::MalformedBeacon(
+                "Software version is too long".to_string(),
+            ));
+        }
+        if !trusted_signers.is_empty() && !trusted_signers.contains(&self.signer) {
+            return Err(StatusBeaconError::UntrustedSigner(self.signer.clone()));
+        }
+        let tip_hash = Vec::<u8>::from_hex(&self.tip_hash)
+            .ok()
+            .filter(|hash| hash.len() == TIP_HASH_LENGTH)
+            .ok_or_else(|| StatusBeaconError::MalformedBeacon("Invalid tip hash".to_string()))?;
+
+        let public_nonce =
+            CommsPublicKey::from_hex(&self.public_nonce).map_err(|_| StatusBeaconError::MalformedSignature)?;
+        let signature = CommsSecretKey::from_hex(&self.signature).map_err(|_| StatusBeaconError::MalformedSignature)?;
+        let signature = StatusBeaconSignature::new(public_nonce, signature);
+
+        let message = Self::construct_message(
+            self.version,
+            self.network,
+            self.tip_height,
+            &tip_hash,
+            self.timestamp,
+            &self.software_version,
+            &self.signer,
+        );
+        if !signature.verify(&self.signer, message) {
+            return Err(StatusBeaconError::InvalidSignature);
+        }
+        Ok(())
+    }
+
+    /// The age of the beacon at the given unix timestamp
+    pub fn age(&self, now: u64) -> Duration {
+        Duration::from_secs(now.saturating_sub(self.timestamp))
+    }
+
+    fn construct_message(
+        version: u8,
+        network: Network,
+        tip_height: u64,
+        tip_hash: &[u8],
+        timestamp: u64,
+        software_version: &str,
+        signer: &CommsPublicKey,
+    ) -> Vec<u8> {
+        let mut message = Vec::new();
+        message.push(version);
+        message.push(network.as_byte());
+        message.extend_from_slice(&tip_height.to_le_bytes());
+        message.extend_from_slice(tip_hash);
+        message.extend_from_slice(&timestamp.to_le_bytes());
+        message.extend_from_slice(&u64::try_from(software_version.len()).unwrap_or(u64::MAX).to_le_bytes());
+        message.extend_from_slice(software_version.as_bytes());
+        message.extend_from_slice(signer.as_bytes());
+        message
+    }
+}
+
+impl TryFrom<proto::StatusBeacon> for SignedStatusBeacon {
+    type Error = StatusBeaconError;
+
+    fn try_from(beacon: proto::StatusBeacon) -> Result<Self, Self::Error> {
+        let version = u8::try_from(beacon.version)
+            .map_err(|_| StatusBeaconError::MalformedBeacon("Invalid version".to_string()))?;
+        let network = u8::try_from(beacon.network)
+            .ok()
+            .and_then(|network| Network::try_from(network).ok())
+            .ok_or_else(|| StatusBeaconError::MalformedBeacon("Invalid network".to_string()))?;
+        let signer = CommsPublicKey::from_canonical_bytes(&beacon.signer)
+            .map_err(|_| StatusBeaconError::MalformedBeacon("Invalid signer".to_string()))?;
+        let public_nonce = CommsPublicKey::from_canonical_bytes(&beacon.public_nonce)
+            .map_err(|_| StatusBeaconError::MalformedSignature)?;
+        let signature = CommsSecretKey::from_canonical_bytes(&beacon.signature)
+            .map_err(|_| StatusBeaconError::MalformedSignature)?;
+        Ok(Self {
+            version,
+            network,
+            tip_height: beacon.tip_height,
+            tip_hash: beacon.tip_hash.to_hex(),
+            timestamp: beacon.timestamp,
+            software_version: beacon.software_version,
+            signer,
+            public_nonce: public_nonce.to_hex(),
+            signature: signature.to_hex(),
+        })
+    }
+}
+
+impl TryFrom<SignedStatusBeacon> for proto::StatusBeacon {
+    type Error = StatusBeaconError;
+
+    fn try_from(beacon: SignedStatusBeacon) -> Result<Self, Self::Error> {
+        let tip_hash = Vec::<u8>::from_hex(&beacon.tip_hash)
+            .map_err(|_| StatusBeaconError::MalformedBeacon("Invalid tip hash".to_string()))?;
+        let public_nonce =
+            CommsPublicKey::from_hex(&beacon.public_nonce).map_err(|_| StatusBeaconError::MalformedSignature)?;
+        let signature =
+            CommsSecretKey::from_hex(&beacon.signature).map_err(|_| StatusBeaconError::MalformedSignature)?;
+        Ok(Self {
+            version: u32::from(beacon.version),
+            network: u32::from(beacon.network.as_byte()),
+            tip_height: beacon.tip_height,
+            tip_hash,
+            timestamp: beacon.timestamp,
+            software_version: beacon.software_version,
+            signer: beacon.signer.to_vec(),
+            public_nonce: public_nonce.to_vec(),
+            signature: signature.to_vec(),
+        })
+    }
+}
+
+/// The tip that the beacons agree on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BeaconConsensus {
+    /// The median tip height of the beacons
+    pub tip_height: u64,
+    /// The number of beacons that the consensus is based on
+    pub num_beacons: usize,
+}
+
+impl BeaconConsensus {
+    /// Calculates the consensus of the latest beacon of each signer that is no older than `max_age`. The beacons must
+    /// already be verified. Returns None if there are fewer than `min_beacons` such beacons.
+    pub fn from_beacons<'a, I>(beacons: I, now: u64, max_age: Duration, min_beacons: usize) -> Option<Self>
+    where I: IntoIterator<Item = &'a SignedStatusBeacon> {
+        let mut latest = HashMap::<&CommsPublicKey, &SignedStatusBeacon>::new();
+        for beacon in beacons {
+            if beacon.age(now) > max_age {
+                continue;
+            }
+            let entry = latest.entry(&beacon.signer).or_insert(beacon);
+            if beacon.timestamp > entry.timestamp {
+                *entry = beacon;
+            }
+        }
+        if latest.is_empty() || latest.len() < min_beacons {
+            return None;
+        }
+        let mut heights = latest.values().map(|b| b.tip_height).collect::<Vec<_>>();
+        heights.sort_unstable();
+        Some(Self {
+            tip_height: heights[(heights.len() - 1) / 2],
+            num_beacons: heights.len(),
+        })
+    }
+
+    /// The number of blocks that `tip_height` differs from the consensus by
+    pub fn divergence(&self, tip_height: u64) -> u64 {
+        self.tip_height.abs_diff(tip_height)
+    }
+}
+
+/// Downloads the status beacon at the given URL and verifies it
+#[cfg(feature = "status-beacon")]
+pub async fn fetch_status_beacon(
+    url: &str,
+    network: Network,
+    trusted_signers: &[CommsPublicKey],
+) -> Result<SignedStatusBeacon, StatusBeaconError> {
+    let json = reqwest::get(url).await?.error_for_status()?.text().await?;
+    let beacon = SignedStatusBeacon::from_json(&json)?;
+    beacon.verify(network, trusted_signers)?;
+    Ok(beacon)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const NOW: u64 = 1_700_000_000;
+
+    fn create_beacon(sk: &CommsSecretKey, tip_height: u64, timestamp: u64) -> SignedStatusBeacon {
+        SignedStatusBeacon::sign(
+            sk,
+            Network::Esmeralda,
+            tip_height,
+            &[1u8; 32],
+            timestamp,
+            "1.7.0".to_string(),
+        )
+    }
+
+    #[test]
+    fn it_verifies_a_signed_beacon() {
+        let (sk, pk) = CommsPublicKey::random_keypair(&mut OsRng);
+        let beacon = create_beacon(&sk, 100, NOW);
+        let json = beacon.to_json().unwrap();
+        let beacon = SignedStatusBeacon::from_json(&json).unwrap();
+        beacon.verify(Network::Esmeralda, &[]).unwrap();
+        beacon.verify(Network::Esmeralda, &[pk]).unwrap();
+    }
+
+    #[test]
+    fn it_roundtrips_through_proto() {
+        let (sk, _) = CommsPublicKey::random_keypair(&mut OsRng);
+        let beacon = create_beacon(&sk, 100, NOW);
+        let proto = proto::StatusBeacon::try_from(beacon.clone()).unwrap();
+        let decoded = SignedStatusBeacon::try_from(proto).unwrap();
+        assert_eq!(decoded, beacon);
+        decoded.verify(Network::Esmeralda, &[]).unwrap();
+    }
+
+    #[test]
+    fn it_rejects_invalid_beacons() {
+        let (sk, _) = CommsPublicKey::random_keypair(&mut OsRng);
+        let (_, other_pk) = CommsPublicKey::random_keypair(&mut OsRng);
+
+        let mut beacon = create_beacon(&sk, 100, NOW);
+        beacon.tip_height += 1;
+        assert!(matches!(
+            beacon.verify(Network::Esmeralda, &[]),
+            Err(StatusBeaconError::InvalidSignature)
+        ));
+
+        let beacon = create_beacon(&sk, 100, NOW);
+        assert!(matches!(
+            beacon.verify(Network::MainNet, &[]),
+            Err(StatusBeaconError::WrongNetwork { .. })
+        ));
+        assert!(matches!(
+            beacon.verify(Network::Esmeralda, &[other_pk]),
+            Err(StatusBeaconError::UntrustedSigner(_))
+        ));
+
+        let beacon = create_beacon(&sk, 100, u64::MAX);
+        assert!(matches!(
+            beacon.verify(Network::Esmeralda, &[]),
+            Err(StatusBeaconError::IssuedInFuture)
+        ));
+    }
+
+    #[test]
+    fn it_calculates_the_consensus_of_recent_beacons() {
+        let keys = (0..4)
+            .map(|_| CommsPublicKey::random_keypair(&mut OsRng).0)
+            .collect::<Vec<_>>();
+        let max_age = Duration::from_secs(600);
+        let beacons = vec![
+            create_beacon(&keys[0], 100, NOW - 60),
+            // Only the latest beacon of a signer is used
+            create_beacon(&keys[0], 90, NOW - 120),
+            create_beacon(&keys[1], 102, NOW - 30),
+            create_beacon(&keys[2], 5, NOW),
+            // Too old
+            create_beacon(&keys[3], 1, NOW - 601),
+        ];
+
+        let consensus = BeaconConsensus::from_beacons(&beacons, NOW, max_age, 1).unwrap();
+        assert_eq!(consensus.num_beacons, 3);
+        assert_eq!(consensus.tip_height, 100);
+        assert_eq!(consensus.divergence(90), 10);
+        assert_eq!(consensus.divergence(110), 10);
+
+        assert!(BeaconConsensus::from_beacons(&beacons, NOW, max_age, 4).is_none());
+        assert!(BeaconConsensus::from_beacons(&[], NOW, max_age, 0).is_none());
+    }
+}
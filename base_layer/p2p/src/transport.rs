@@ -152,6 +152,17 @@ pub struct TorTransportConfig {
     pub forward_address: Option<Multiaddr>,
     /// If set, the listener will bind to this address instead of the forward_address.
     pub listener_address_override: Option<Multiaddr>,
+    /// If set, a public TCP listener is bound to this address in addition to the hidden service (dual-stack). The
+    /// public TCP address(es) of this node must be set in `p2p.public_addresses` so that they are advertised alongside
+    /// the onion address. Outbound dials prefer onion addresses when this is enabled.
+    pub public_tcp_listener_address: Option<Multiaddr>,
+    /// Bridge lines for the built-in Tor instance (`use_libtor`), in torrc format, e.g.
+    /// `obfs4 192.0.2.1:443 <fingerprint> cert=<cert> iat-mode=0`. If any are set, Tor only connects through bridges.
+    /// An external Tor instance must be configured in its own torrc.
+    pub bridges: Vec<String>,
+    /// Pluggable transports for the bridges of the built-in Tor instance, in the torrc `ClientTransportPlugin`
+    /// format, e.g. `obfs4 exec /usr/bin/obfs4proxy`
+    pub client_transport_plugins: Vec<String>,
     /// The tor identity to use to create the hidden service. If None, a new one will be generated.
     #[serde(skip)]
     pub identity: Option<TorIdentity>,
@@ -185,6 +196,11 @@ impl TorTransportConfig {
     pub fn to_socks_auth(&self) -> socks::Authentication {
         self.socks_auth.clone().into()
     }
+
+    /// Returns true if a public TCP listener is configured alongside the hidden service
+    pub fn is_dual_stack(&self) -> bool {
+        self.public_tcp_listener_address.is_some()
+    }
 }
 
 impl Default for TorTransportConfig {
@@ -199,6 +215,9 @@ impl Default for TorTransportConfig {
             proxy_bypass_for_outbound_tcp: true,
             forward_address: None,
             listener_address_override: None,
+            public_tcp_listener_address: None,
+            bridges: vec![],
+            client_transport_plugins: vec![],
             identity: None,
         }
     }
@@ -0,0 +1,353 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Parsing, validation and encoding of Tari addresses.
+//!
+//! A single address is `network | features | spend key | checksum` (35 bytes) and a dual address is
+//! `network | features | view key | spend key | checksum` (67 bytes). Addresses are displayed as emoji strings, base58
+//! (with the network and features bytes encoded separately) or hex. Public keys are checked to be canonical
+//! compressed Ristretto points.
+
+use alloc::{string::String, vec::Vec};
+use core::{fmt, str::FromStr};
+
+use curve25519_dalek::ristretto::CompressedRistretto;
+
+use crate::{
+    dammsum::{compute_checksum, validate_checksum},
+    emoji::{bytes_to_emoji_string, emoji_string_to_bytes},
+    network::AddressNetwork,
+};
+
+/// The number of bytes in a serialized dual (view and spend key) address
+pub const TARI_ADDRESS_DUAL_SIZE: usize = 67;
+/// The number of bytes in a serialized single (spend key only) address
+pub const TARI_ADDRESS_SINGLE_SIZE: usize = 35;
+/// The feature bit of addresses that accept one-sided payments
+pub const FEATURE_ONE_SIDED: u8 = 1;
+/// The feature bit of addresses that accept interactive payments
+pub const FEATURE_INTERACTIVE: u8 = 2;
+
+const PUBLIC_KEY_SIZE: usize = 32;
+const SINGLE_MIN_BASE58_SIZE: usize = 45;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressError {
+    InvalidSize,
+    InvalidNetwork,
+    InvalidFeatures,
+    InvalidChecksum,
+    InvalidEmoji,
+    InvalidCharacter,
+    CannotRecoverPublicKey,
+    CannotRecoverNetwork,
+    CannotRecoverFeature,
+    InvalidAddressString,
+}
+
+impl fmt::Display for AddressError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            AddressError::InvalidSize => "Invalid size",
+            AddressError::InvalidNetwork => "Invalid network",
+            AddressError::InvalidFeatures => "Invalid features",
+            AddressError::InvalidChecksum => "Invalid checksum",
+            AddressError::InvalidEmoji => "Invalid emoji character",
+            AddressError::InvalidCharacter => "Invalid text character",
+            AddressError::CannotRecoverPublicKey => "Cannot recover public key",
+            AddressError::CannotRecoverNetwork => "Cannot recover network",
+            AddressError::CannotRecoverFeature => "Cannot recover feature",
+            AddressError::InvalidAddressString => "Could not recover TariAddress from string",
+        };
+        f.write_str(msg)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AddressError {}
+
+/// A validated Tari address, holding the raw bytes of its public keys
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TariAddressBytes {
+    network: AddressNetwork,
+    features: u8,
+    public_view_key: Option<[u8; PUBLIC_KEY_SIZE]>,
+    public_spend_key: [u8; PUBLIC_KEY_SIZE],
+}
+
+impl TariAddressBytes {
+    /// Parses and validates a serialized address, including its checksum
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, AddressError> {
+        if bytes.len() != TARI_ADDRESS_SINGLE_SIZE && bytes.len() != TARI_ADDRESS_DUAL_SIZE {
+            return Err(AddressError::InvalidSize);
+        }
+        if validate_checksum(bytes).is_err() {
+            return Err(AddressError::InvalidChecksum);
+        }
+        let network = AddressNetwork::try_from(bytes[0]).map_err(|_| AddressError::InvalidNetwork)?;
+        let features = bytes[1];
+        if features & !(FEATURE_ONE_SIDED | FEATURE_INTERACTIVE) != 0 {
+            return Err(AddressError::InvalidFeatures);
+        }
+        let (public_view_key, spend_key_start) = if bytes.len() == TARI_ADDRESS_DUAL_SIZE {
+            (Some(public_key(&bytes[2..34])?), 34)
+        } else {
+            (None, 2)
+        };
+        let public_spend_key = public_key(&bytes[spend_key_start..spend_key_start + PUBLIC_KEY_SIZE])?;
+        Ok(Self {
+            network,
+            features,
+            public_view_key,
+            public_spend_key,
+        })
+    }
+
+    /// Serializes the address, including its checksum
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.size());
+        bytes.push(self.network.as_byte());
+        bytes.push(self.features);
+        if let Some(view_key) = &self.public_view_key {
+            bytes.extend_from_slice(view_key);
+        }
+        bytes.extend_from_slice(&self.public_spend_key);
+        let checksum = compute_checksum(&bytes);
+        bytes.push(checksum);
+        bytes
+    }
+
+    pub fn size(&self) -> usize {
+        if self.public_view_key.is_some() {
+            TARI_ADDRESS_DUAL_SIZE
+        } else {
+            TARI_ADDRESS_SINGLE_SIZE
+        }
+    }
+
+    pub fn network(&self) -> AddressNetwork {
+        self.network
+    }
+
+    pub fn features(&self) -> u8 {
+        self.features
+    }
+
+    pub fn supports_one_sided(&self) -> bool {
+        self.features & FEATURE_ONE_SIDED != 0
+    }
+
+    pub fn supports_interactive(&self) -> bool {
+        self.features & FEATURE_INTERACTIVE != 0
+    }
+
+    pub fn public_view_key(&self) -> Option<&[u8; PUBLIC_KEY_SIZE]> {
+        self.public_view_key.as_ref()
+    }
+
+    pub fn public_spend_key(&self) -> &[u8; PUBLIC_KEY_SIZE] {
+        &self.public_spend_key
+    }
+
+    pub fn from_emoji_string(emoji: &str) -> Result<Self, AddressError> {
+        let count = emoji.chars().count();
+        if count != TARI_ADDRESS_SINGLE_SIZE && count != TARI_ADDRESS_DUAL_SIZE {
+            return Err(AddressError::InvalidSize);
+        }
+        let bytes = emoji_string_to_bytes(emoji).ok_or(AddressError::InvalidEmoji)?;
+        Self::from_bytes(&bytes)
+    }
+
+    pub fn to_emoji_string(&self) -> String {
+        bytes_to_emoji_string(&self.to_bytes())
+    }
+
+    /// Parses the base58 encoding, in which the network and features are encoded as separate characters
+    pub fn from_base58(base58: &str) -> Result<Self, AddressError> {
+        if base58.len() < SINGLE_MIN_BASE58_SIZE {
+            return Err(AddressError::InvalidSize);
+        }
+        let (first, rest) = base58.split_at_checked(2).ok_or(AddressError::InvalidCharacter)?;
+        let (network, features) = first.split_at_checked(1).ok_or(AddressError::InvalidCharacter)?;
+        let mut bytes = bs58::decode(network)
+            .into_vec()
+            .map_err(|_| AddressError::CannotRecoverNetwork)?;
+        let mut features = bs58::decode(features)
+            .into_vec()
+            .map_err(|_| AddressError::CannotRecoverFeature)?;
+        let mut rest = bs58::decode(rest)
+            .into_vec()
+            .map_err(|_| AddressError::CannotRecoverPublicKey)?;
+        bytes.append(&mut features);
+        bytes.append(&mut rest);
+        Self::from_bytes(&bytes)
+    }
+
+    pub fn to_base58(&self) -> String {
+        let bytes = self.to_bytes();
+        let mut base58 = bs58::encode(&bytes[0..1]).into_string();
+        base58.push_str(&bs58::encode(&bytes[1..2]).into_string());
+        base58.push_str(&bs58::encode(&bytes[2..]).into_string());
+        base58
+    }
+
+    pub fn from_hex(hex: &str) -> Result<Self, AddressError> {
+        let bytes = decode_hex(hex).ok_or(AddressError::CannotRecoverPublicKey)?;
+        Self::from_bytes(&bytes)
+    }
+
+    pub fn to_hex(&self) -> String {
+        const HEX: &[u8; 16] = b"0123456789abcdef";
+        self.to_bytes()
+            .iter()
+            .flat_map(|b| [HEX[usize::from(b >> 4)], HEX[usize::from(b & 0x0f)]])
+            .map(char::from)
+            .collect()
+    }
+}
+
+/// Accepts the same encodings as `TariAddress`, in the same order: emoji (ignoring `|` separators), base58, then hex
+impl FromStr for TariAddressBytes {
+    type Err = AddressError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let emoji = s.trim().replace('|', "");
+        if let Ok(address) = Self::from_emoji_string(&emoji) {
+            Ok(address)
+        } else if let Ok(address) = Self::from_base58(s) {
+            Ok(address)
+        } else if let Ok(address) = Self::from_hex(s) {
+            Ok(address)
+        } else {
+            Err(AddressError::InvalidAddressString)
+        }
+    }
+}
+
+impl fmt::Display for TariAddressBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_emoji_string())
+    }
+}
+
+fn public_key(bytes: &[u8]) -> Result<[u8; PUBLIC_KEY_SIZE], AddressError> {
+    let compressed = CompressedRistretto::from_slice(bytes).map_err(|_| AddressError::CannotRecoverPublicKey)?;
+    if compressed.decompress().is_none() {
+        return Err(AddressError::CannotRecoverPublicKey);
+    }
+    Ok(compressed.to_bytes())
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    let hex = hex.strip_prefix("0x").unwrap_or(hex);
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            let hi = char::from(pair[0]).to_digit(16)?;
+            let lo = char::from(pair[1]).to_digit(16)?;
+            u8::try_from(hi * 16 + lo).ok()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::ToString;
+
+    use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+
+    use super::*;
+
+    fn key(n: u64) -> [u8; PUBLIC_KEY_SIZE] {
+        (RISTRETTO_BASEPOINT_POINT * curve25519_dalek::Scalar::from(n))
+            .compress()
+            .to_bytes()
+    }
+
+    fn address_bytes(network: AddressNetwork, features: u8, view_key: Option<u64>, spend_key: u64) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.push(network.as_byte());
+        bytes.push(features);
+        if let Some(view_key) = view_key {
+            bytes.extend_from_slice(&key(view_key));
+        }
+        bytes.extend_from_slice(&key(spend_key));
+        let checksum = compute_checksum(&bytes);
+        bytes.push(checksum);
+        bytes
+    }
+
+    #[test]
+    fn it_round_trips_all_encodings() {
+        for view_key in [None, Some(7)] {
+            let bytes = address_bytes(AddressNetwork::Esmeralda, FEATURE_INTERACTIVE, view_key, 11);
+            let address = TariAddressBytes::from_bytes(&bytes).unwrap();
+            assert_eq!(address.to_bytes(), bytes);
+            assert_eq!(address.public_spend_key(), &key(11));
+            assert_eq!(address.public_view_key().is_some(), view_key.is_some());
+            assert!(address.supports_interactive());
+            assert!(!address.supports_one_sided());
+
+            let emoji = address.to_emoji_string();
+            assert_eq!(emoji.chars().count(), bytes.len());
+            assert_eq!(TariAddressBytes::from_emoji_string(&emoji).unwrap(), address);
+            assert_eq!(TariAddressBytes::from_base58(&address.to_base58()).unwrap(), address);
+            assert_eq!(TariAddressBytes::from_hex(&address.to_hex()).unwrap(), address);
+
+            for encoded in [emoji, address.to_base58(), address.to_hex()] {
+                assert_eq!(encoded.parse::<TariAddressBytes>().unwrap(), address);
+            }
+            assert_eq!(address.to_string(), address.to_emoji_string());
+        }
+    }
+
+    #[test]
+    fn it_rejects_invalid_addresses() {
+        let bytes = address_bytes(AddressNetwork::MainNet, FEATURE_ONE_SIDED, Some(3), 5);
+
+        let mut bad_checksum = bytes.clone();
+        bad_checksum[10] ^= 1;
+        assert_eq!(
+            TariAddressBytes::from_bytes(&bad_checksum),
+            Err(AddressError::InvalidChecksum)
+        );
+        assert_eq!(
+            TariAddressBytes::from_bytes(&bytes[1..]),
+            Err(AddressError::InvalidSize)
+        );
+
+        let mut bad_network = bytes[..bytes.len() - 1].to_vec();
+        bad_network[0] = 0x99;
+        bad_network.push(compute_checksum(&bad_network));
+        assert_eq!(
+            TariAddressBytes::from_bytes(&bad_network),
+            Err(AddressError::InvalidNetwork)
+        );
+
+        let mut bad_features = bytes[..bytes.len() - 1].to_vec();
+        bad_features[1] = 4;
+        bad_features.push(compute_checksum(&bad_features));
+        assert_eq!(
+            TariAddressBytes::from_bytes(&bad_features),
+            Err(AddressError::InvalidFeatures)
+        );
+
+        // Not a canonical point encoding
+        let mut bad_key = bytes[..bytes.len() - 1].to_vec();
+        bad_key[2] = 1;
+        bad_key[3..34].fill(0);
+        bad_key.push(compute_checksum(&bad_key));
+        assert_eq!(
+            TariAddressBytes::from_bytes(&bad_key),
+            Err(AddressError::CannotRecoverPublicKey)
+        );
+
+        assert_eq!(
+            "not an address".parse::<TariAddressBytes>(),
+            Err(AddressError::InvalidAddressString)
+        );
+    }
+}
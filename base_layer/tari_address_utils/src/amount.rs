@@ -0,0 +1,128 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Formatting and parsing of Minotari amounts, compatible with `MicroMinotari`'s `Display` and `FromStr`
+//! implementations.
+
+use alloc::{format, string::String};
+use core::fmt;
+
+const MICRO_MINOTARI_PER_MINOTARI: u64 = 1_000_000;
+const DECIMALS: usize = 6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AmountError {
+    InvalidNumber,
+    TooManyDecimals,
+    Overflow,
+}
+
+impl fmt::Display for AmountError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AmountError::InvalidNumber => f.write_str("Invalid amount"),
+            AmountError::TooManyDecimals => write!(f, "Too many decimals, at most {} are allowed", DECIMALS),
+            AmountError::Overflow => f.write_str("Amount is too large"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AmountError {}
+
+/// Formats an amount of µT the way `MicroMinotari` displays it: amounts below 1 T in µT, larger amounts in T with six
+/// decimals, e.g. `999 µT` and `1.500000 T`
+pub fn format_micro_minotari(value: u64) -> String {
+    if value < MICRO_MINOTARI_PER_MINOTARI {
+        format!("{} µT", value)
+    } else {
+        format!(
+            "{}.{:06} T",
+            value / MICRO_MINOTARI_PER_MINOTARI,
+            value % MICRO_MINOTARI_PER_MINOTARI
+        )
+    }
+}
+
+/// Parses an amount into µT. Commas and spaces are ignored. Amounts ending in `µT` or `uT`, and plain integers, are
+/// taken to be µT, while amounts ending in `T` or containing a decimal point are taken to be T.
+pub fn parse_micro_minotari(s: &str) -> Result<u64, AmountError> {
+    let processed = s.replace([',', ' '], "").to_ascii_lowercase();
+    let is_micro_minotari = if processed.ends_with("ut") || processed.ends_with("µt") {
+        true
+    } else if processed.ends_with('t') {
+        false
+    } else {
+        !processed.contains('.')
+    };
+
+    let processed = processed.replace("ut", "").replace("µt", "").replace('t', "");
+    if is_micro_minotari {
+        return parse_digits(&processed);
+    }
+
+    let (whole, fraction) = processed.split_once('.').unwrap_or((&processed, ""));
+    if whole.is_empty() && fraction.is_empty() {
+        return Err(AmountError::InvalidNumber);
+    }
+    if fraction.len() > DECIMALS {
+        return Err(AmountError::TooManyDecimals);
+    }
+    let whole = if whole.is_empty() { 0 } else { parse_digits(whole)? };
+    let fraction = if fraction.is_empty() {
+        0
+    } else {
+        parse_digits(fraction)? *
+            10u64.pow(u32::try_from(DECIMALS - fraction.len()).map_err(|_| AmountError::Overflow)?)
+    };
+    whole
+        .checked_mul(MICRO_MINOTARI_PER_MINOTARI)
+        .and_then(|v| v.checked_add(fraction))
+        .ok_or(AmountError::Overflow)
+}
+
+fn parse_digits(s: &str) -> Result<u64, AmountError> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(AmountError::InvalidNumber);
+    }
+    s.parse::<u64>().map_err(|_| AmountError::Overflow)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_formats_amounts() {
+        assert_eq!(format_micro_minotari(0), "0 µT");
+        assert_eq!(format_micro_minotari(999_999), "999999 µT");
+        assert_eq!(format_micro_minotari(1_000_000), "1.000000 T");
+        assert_eq!(format_micro_minotari(1_500_001), "1.500001 T");
+    }
+
+    #[test]
+    fn it_parses_amounts() {
+        assert_eq!(parse_micro_minotari("1,000 µT"), Ok(1_000));
+        assert_eq!(parse_micro_minotari("1000ut"), Ok(1_000));
+        assert_eq!(parse_micro_minotari("1000"), Ok(1_000));
+        assert_eq!(parse_micro_minotari("1.5 T"), Ok(1_500_000));
+        assert_eq!(parse_micro_minotari("2T"), Ok(2_000_000));
+        assert_eq!(parse_micro_minotari("0.000001"), Ok(1));
+        assert_eq!(parse_micro_minotari(".5"), Ok(500_000));
+        assert_eq!(parse_micro_minotari("0.0000001"), Err(AmountError::TooManyDecimals));
+        assert_eq!(parse_micro_minotari("-1 T"), Err(AmountError::InvalidNumber));
+        assert_eq!(parse_micro_minotari("abc"), Err(AmountError::InvalidNumber));
+        assert_eq!(parse_micro_minotari("18446744073709551616"), Err(AmountError::Overflow));
+        assert_eq!(
+            parse_micro_minotari("18446744073709.551616 T"),
+            Err(AmountError::Overflow)
+        );
+    }
+
+    #[test]
+    fn it_parses_what_it_formats() {
+        for value in [0, 1, 999_999, 1_000_000, 123_456_789, u64::MAX] {
+            assert_eq!(parse_micro_minotari(&format_micro_minotari(value)), Ok(value));
+        }
+    }
+}
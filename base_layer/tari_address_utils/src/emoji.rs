@@ -0,0 +1,72 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! The emoji alphabet used for emoji IDs and the emoji encoding of Tari addresses. Each of the 256 characters encodes
+//! one byte.
+
+use alloc::{string::String, vec::Vec};
+
+/// The number of elements in the symbol dictionary
+pub const DICT_SIZE: usize = 256;
+
+// The emoji table, mapping byte values to emoji characters
+pub const EMOJI: [char; DICT_SIZE] = [
+    '🐢', '📟', '🌈', '🌊', '🎯', '🐋', '🌙', '🤔', '🌕', '⭐', '🎋', '🌰', '🌴', '🌵', '🌲', '🌸', '🌹', '🌻', '🌽',
+    '🍀', '🍁', '🍄', '🥑', '🍆', '🍇', '🍈', '🍉', '🍊', '🍋', '🍌', '🍍', '🍎', '🍐', '🍑', '🍒', '🍓', '🍔', '🍕',
+    '🍗', '🍚', '🍞', '🍟', '🥝', '🍣', '🍦', '🍩', '🍪', '🍫', '🍬', '🍭', '🍯', '🥐', '🍳', '🥄', '🍵', '🍶', '🍷',
+    '🍸', '🍾', '🍺', '🍼', '🎀', '🎁', '🎂', '🎃', '🤖', '🎈', '🎉', '🎒', '🎓', '🎠', '🎡', '🎢', '🎣', '🎤', '🎥',
+    '🎧', '🎨', '🎩', '🎪', '🎬', '🎭', '🎮', '🎰', '🎱', '🎲', '🎳', '🎵', '🎷', '🎸', '🎹', '🎺', '🎻', '🎼', '🎽',
+    '🎾', '🎿', '🏀', '🏁', '🏆', '🏈', '⚽', '🏠', '🏥', '🏦', '🏭', '🏰', '🐀', '🐉', '🐊', '🐌', '🐍', '🦁', '🐐',
+    '🐑', '🐔', '🙈', '🐗', '🐘', '🐙', '🐚', '🐛', '🐜', '🐝', '🐞', '🦋', '🐣', '🐨', '🦀', '🐪', '🐬', '🐭', '🐮',
+    '🐯', '🐰', '🦆', '🦂', '🐴', '🐵', '🐶', '🐷', '🐸', '🐺', '🐻', '🐼', '🐽', '🐾', '👀', '👅', '👑', '👒', '🧢',
+    '💅', '👕', '👖', '👗', '👘', '👙', '💃', '👛', '👞', '👟', '👠', '🥊', '👢', '👣', '🤡', '👻', '👽', '👾', '🤠',
+    '👃', '💄', '💈', '💉', '💊', '💋', '👂', '💍', '💎', '💐', '💔', '🔒', '🧩', '💡', '💣', '💤', '💦', '💨', '💩',
+    '➕', '💯', '💰', '💳', '💵', '💺', '💻', '💼', '📈', '📜', '📌', '📎', '📖', '📿', '📡', '⏰', '📱', '📷', '🔋',
+    '🔌', '🚰', '🔑', '🔔', '🔥', '🔦', '🔧', '🔨', '🔩', '🔪', '🔫', '🔬', '🔭', '🔮', '🔱', '🗽', '😂', '😇', '😈',
+    '🤑', '😍', '😎', '😱', '😷', '🤢', '👍', '👶', '🚀', '🚁', '🚂', '🚚', '🚑', '🚒', '🚓', '🛵', '🚗', '🚜', '🚢',
+    '🚦', '🚧', '🚨', '🚪', '🚫', '🚲', '🚽', '🚿', '🧲',
+];
+
+/// Returns the current emoji set as a character array
+pub const fn emoji_set() -> [char; DICT_SIZE] {
+    EMOJI
+}
+
+/// Returns the byte encoded by an emoji character, if it is part of the emoji set
+pub fn emoji_to_byte(emoji: char) -> Option<u8> {
+    EMOJI
+        .iter()
+        .position(|c| *c == emoji)
+        .and_then(|i| u8::try_from(i).ok())
+}
+
+/// Encodes bytes as an emoji string
+pub fn bytes_to_emoji_string(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| EMOJI[usize::from(*b)]).collect()
+}
+
+/// Decodes an emoji string into bytes, returning `None` if it contains a character outside the emoji set
+pub fn emoji_string_to_bytes(emoji: &str) -> Option<Vec<u8>> {
+    emoji.chars().map(emoji_to_byte).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn emoji_set_is_unique() {
+        for (i, c) in EMOJI.iter().enumerate() {
+            assert_eq!(emoji_to_byte(*c).map(usize::from), Some(i));
+        }
+    }
+
+    #[test]
+    fn it_round_trips_bytes() {
+        let bytes = (0..=u8::MAX).collect::<Vec<_>>();
+        let emoji = bytes_to_emoji_string(&bytes);
+        assert_eq!(emoji.chars().count(), DICT_SIZE);
+        assert_eq!(emoji_string_to_bytes(&emoji).unwrap(), bytes);
+        assert!(emoji_string_to_bytes("🎅").is_none());
+    }
+}
@@ -0,0 +1,219 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! C ABI for address validation, conversion and amount formatting. Strings returned by these functions must be freed
+//! with `tari_address_utils_string_destroy`. See `tari_address_utils.h` for the C declarations.
+
+use std::{
+    ffi::{CStr, CString},
+    os::raw::{c_char, c_int},
+    ptr,
+    str::FromStr,
+};
+
+use crate::{
+    address::{AddressError, TariAddressBytes},
+    amount::{format_micro_minotari, parse_micro_minotari, AmountError},
+};
+
+/// The error codes written to `error_out`
+const ERROR_NULL_POINTER: c_int = 1;
+const ERROR_INVALID_UTF8: c_int = 2;
+const ERROR_INVALID_STRING: c_int = 3;
+
+fn address_error_code(e: AddressError) -> c_int {
+    match e {
+        AddressError::InvalidSize => 101,
+        AddressError::InvalidNetwork => 102,
+        AddressError::InvalidFeatures => 103,
+        AddressError::InvalidChecksum => 104,
+        AddressError::InvalidEmoji => 105,
+        AddressError::InvalidCharacter => 106,
+        AddressError::CannotRecoverPublicKey => 107,
+        AddressError::CannotRecoverNetwork => 108,
+        AddressError::CannotRecoverFeature => 109,
+        AddressError::InvalidAddressString => 110,
+    }
+}
+
+fn amount_error_code(e: AmountError) -> c_int {
+    match e {
+        AmountError::InvalidNumber => 201,
+        AmountError::TooManyDecimals => 202,
+        AmountError::Overflow => 203,
+    }
+}
+
+unsafe fn set_error(error_out: *mut c_int, mut error: c_int) {
+    if !error_out.is_null() {
+        ptr::swap(error_out, &mut error as *mut c_int);
+    }
+}
+
+unsafe fn read_str<'a>(s: *const c_char, error_out: *mut c_int) -> Option<&'a str> {
+    if s.is_null() {
+        set_error(error_out, ERROR_NULL_POINTER);
+        return None;
+    }
+    match CStr::from_ptr(s).to_str() {
+        Ok(s) => Some(s),
+        Err(_) => {
+            set_error(error_out, ERROR_INVALID_UTF8);
+            None
+        },
+    }
+}
+
+unsafe fn parse_address(address: *const c_char, error_out: *mut c_int) -> Option<TariAddressBytes> {
+    set_error(error_out, 0);
+    let address = read_str(address, error_out)?;
+    match TariAddressBytes::from_str(address) {
+        Ok(address) => Some(address),
+        Err(e) => {
+            set_error(error_out, address_error_code(e));
+            None
+        },
+    }
+}
+
+unsafe fn into_c_string(s: String, error_out: *mut c_int) -> *mut c_char {
+    match CString::new(s) {
+        Ok(s) => s.into_raw(),
+        Err(_) => {
+            set_error(error_out, ERROR_INVALID_STRING);
+            ptr::null_mut()
+        },
+    }
+}
+
+/// Validates an address given as an emoji, base58 or hex string.
+///
+/// ## Arguments
+/// `address` - The address string
+/// `network_out` - Set to the network byte of the address if it is valid, may be null
+/// `features_out` - Set to the feature bits of the address if it is valid, may be null
+/// `error_out` - Set to the reason the address is invalid, may be null
+///
+/// ## Returns
+/// `bool` - True if the address is valid
+///
+/// # Safety
+/// `address` must be a valid null terminated string
+#[no_mangle]
+pub unsafe extern "C" fn tari_address_validate(
+    address: *const c_char,
+    network_out: *mut u8,
+    features_out: *mut u8,
+    error_out: *mut c_int,
+) -> bool {
+    let Some(address) = parse_address(address, error_out) else {
+        return false;
+    };
+    if !network_out.is_null() {
+        *network_out = address.network().as_byte();
+    }
+    if !features_out.is_null() {
+        *features_out = address.features();
+    }
+    true
+}
+
+/// Converts an address given as an emoji, base58 or hex string to its emoji representation.
+///
+/// ## Returns
+/// `*mut c_char` - The emoji string, or null if the address is invalid
+///
+/// # Safety
+/// `address` must be a valid null terminated string. The result must be freed with
+/// `tari_address_utils_string_destroy`
+#[no_mangle]
+pub unsafe extern "C" fn tari_address_to_emoji(address: *const c_char, error_out: *mut c_int) -> *mut c_char {
+    match parse_address(address, error_out) {
+        Some(address) => into_c_string(address.to_emoji_string(), error_out),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Converts an address given as an emoji, base58 or hex string to its base58 representation.
+///
+/// ## Returns
+/// `*mut c_char` - The base58 string, or null if the address is invalid
+///
+/// # Safety
+/// `address` must be a valid null terminated string. The result must be freed with
+/// `tari_address_utils_string_destroy`
+#[no_mangle]
+pub unsafe extern "C" fn tari_address_to_base58(address: *const c_char, error_out: *mut c_int) -> *mut c_char {
+    match parse_address(address, error_out) {
+        Some(address) => into_c_string(address.to_base58(), error_out),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Formats an amount of µT, e.g. `999 µT` or `1.500000 T`.
+///
+/// # Safety
+/// The result must be freed with `tari_address_utils_string_destroy`
+#[no_mangle]
+pub unsafe extern "C" fn tari_amount_format(micro_minotari: u64) -> *mut c_char {
+    into_c_string(format_micro_minotari(micro_minotari), ptr::null_mut())
+}
+
+/// Parses an amount such as `1.5 T` or `1000 µT` into µT.
+///
+/// ## Returns
+/// `u64` - The amount in µT, or 0 with `error_out` set if it could not be parsed
+///
+/// # Safety
+/// `amount` must be a valid null terminated string
+#[no_mangle]
+pub unsafe extern "C" fn tari_amount_parse(amount: *const c_char, error_out: *mut c_int) -> u64 {
+    set_error(error_out, 0);
+    let Some(amount) = read_str(amount, error_out) else {
+        return 0;
+    };
+    match parse_micro_minotari(amount) {
+        Ok(value) => value,
+        Err(e) => {
+            set_error(error_out, amount_error_code(e));
+            0
+        },
+    }
+}
+
+/// Frees a string returned by this library
+///
+/// # Safety
+/// `s` must have been returned by this library and not freed before
+#[no_mangle]
+pub unsafe extern "C" fn tari_address_utils_string_destroy(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_reports_errors_through_the_c_abi() {
+        let mut error = -1;
+        let amount = CString::new("1.5 T").unwrap();
+        assert_eq!(unsafe { tari_amount_parse(amount.as_ptr(), &mut error) }, 1_500_000);
+        assert_eq!(error, 0);
+
+        let address = CString::new("not an address").unwrap();
+        let mut network = 0xff;
+        assert!(!unsafe { tari_address_validate(address.as_ptr(), &mut network, ptr::null_mut(), &mut error) });
+        assert_eq!(error, address_error_code(AddressError::InvalidAddressString));
+        assert_eq!(network, 0xff);
+
+        assert!(unsafe { tari_address_to_emoji(ptr::null(), &mut error) }.is_null());
+        assert_eq!(error, ERROR_NULL_POINTER);
+
+        let formatted = unsafe { tari_amount_format(999) };
+        assert_eq!(unsafe { CStr::from_ptr(formatted) }.to_str().unwrap(), "999 µT");
+        unsafe { tari_address_utils_string_destroy(formatted) };
+    }
+}
@@ -0,0 +1,24 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! # Tari address utilities
+//!
+//! Validation, encoding and formatting of Tari addresses, emoji IDs and Minotari amounts, with a minimal dependency
+//! tree. This lets exchanges and payment processors validate addresses without pulling in the rest of the Tari code
+//! base, and is what `tari_common_types` uses for checksums and the emoji table. The crate is `no_std` (with `alloc`)
+//! when the default `std` feature is disabled, and exports a C ABI with the `ffi` feature.
+extern crate alloc;
+
+pub mod address;
+pub mod amount;
+pub mod dammsum;
+pub mod emoji;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod network;
+
+pub use address::{AddressError, TariAddressBytes};
+pub use amount::{format_micro_minotari, parse_micro_minotari, AmountError};
+pub use network::AddressNetwork;
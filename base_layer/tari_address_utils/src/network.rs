@@ -0,0 +1,60 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use core::fmt;
+
+/// The network an address belongs to, as encoded in its first byte. The byte values must match
+/// `tari_common::configuration::Network`.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AddressNetwork {
+    MainNet = 0x00,
+    StageNet = 0x01,
+    NextNet = 0x02,
+    LocalNet = 0x10,
+    Igor = 0x24,
+    Esmeralda = 0x26,
+}
+
+impl AddressNetwork {
+    pub const ALL: [AddressNetwork; 6] = [
+        AddressNetwork::MainNet,
+        AddressNetwork::StageNet,
+        AddressNetwork::NextNet,
+        AddressNetwork::LocalNet,
+        AddressNetwork::Igor,
+        AddressNetwork::Esmeralda,
+    ];
+
+    pub fn as_byte(self) -> u8 {
+        self as u8
+    }
+
+    pub const fn as_key_str(self) -> &'static str {
+        match self {
+            AddressNetwork::MainNet => "mainnet",
+            AddressNetwork::StageNet => "stagenet",
+            AddressNetwork::NextNet => "nextnet",
+            AddressNetwork::Igor => "igor",
+            AddressNetwork::Esmeralda => "esmeralda",
+            AddressNetwork::LocalNet => "localnet",
+        }
+    }
+}
+
+impl TryFrom<u8> for AddressNetwork {
+    type Error = u8;
+
+    fn try_from(v: u8) -> Result<Self, Self::Error> {
+        AddressNetwork::ALL
+            .into_iter()
+            .find(|network| network.as_byte() == v)
+            .ok_or(v)
+    }
+}
+
+impl fmt::Display for AddressNetwork {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_key_str())
+    }
+}
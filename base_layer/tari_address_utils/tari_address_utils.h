@@ -0,0 +1,73 @@
+// Copyright 2024. The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Error codes written to `error_out`:
+ * 0 - success
+ * 1 - null pointer argument, 2 - argument is not valid UTF-8, 3 - result could not be represented as a C string
+ * 101 - invalid size, 102 - invalid network, 103 - invalid features, 104 - invalid checksum, 105 - invalid emoji,
+ * 106 - invalid character, 107 - invalid public key, 108 - invalid network encoding, 109 - invalid features encoding,
+ * 110 - not an emoji, base58 or hex address
+ * 201 - invalid amount, 202 - too many decimals, 203 - amount too large
+ */
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Validates an address given as an emoji, base58 or hex string.
+ *
+ * ## Arguments
+ * `address` - The address string
+ * `network_out` - Set to the network byte of the address if it is valid, may be null
+ * `features_out` - Set to the feature bits of the address if it is valid, may be null
+ * `error_out` - Set to the reason the address is invalid, may be null
+ *
+ * ## Returns
+ * `bool` - True if the address is valid
+ */
+bool tari_address_validate(const char *address,
+                           uint8_t *network_out,
+                           uint8_t *features_out,
+                           int *error_out);
+
+/**
+ * Converts an address given as an emoji, base58 or hex string to its emoji representation, or returns null if the
+ * address is invalid. The result must be freed with `tari_address_utils_string_destroy`.
+ */
+char *tari_address_to_emoji(const char *address,
+                            int *error_out);
+
+/**
+ * Converts an address given as an emoji, base58 or hex string to its base58 representation, or returns null if the
+ * address is invalid. The result must be freed with `tari_address_utils_string_destroy`.
+ */
+char *tari_address_to_base58(const char *address,
+                             int *error_out);
+
+/**
+ * Formats an amount of µT, e.g. `999 µT` or `1.500000 T`. The result must be freed with
+ * `tari_address_utils_string_destroy`.
+ */
+char *tari_amount_format(uint64_t micro_minotari);
+
+/**
+ * Parses an amount such as `1.5 T` or `1000 µT` into µT. Returns 0 with `error_out` set if it could not be parsed.
+ */
+uint64_t tari_amount_parse(const char *amount,
+                           int *error_out);
+
+/**
+ * Frees a string returned by this library
+ */
+void tari_address_utils_string_destroy(char *s);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
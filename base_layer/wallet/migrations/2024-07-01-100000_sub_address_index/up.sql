@@ -0,0 +1,2 @@
+ALTER TABLE outputs
+    ADD sub_address_index BIGINT NULL;
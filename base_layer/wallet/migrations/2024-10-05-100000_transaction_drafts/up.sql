@@ -0,0 +1,7 @@
+CREATE TABLE transaction_drafts
+(
+    name       TEXT PRIMARY KEY NOT NULL,
+    payload    TEXT             NOT NULL,
+    created_at DATETIME         NOT NULL,
+    updated_at DATETIME         NOT NULL
+);
@@ -0,0 +1,151 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::{sync::Arc, time::Duration};
+
+use futures::future;
+use log::*;
+use tari_common::configuration::Network;
+use tari_comms::types::CommsPublicKey;
+use tari_p2p::status_beacon::{fetch_status_beacon, BeaconConsensus};
+use tari_utilities::{epoch_time::EpochTime, hex::Hex};
+use tokio::{
+    sync::RwLock,
+    time::{self, MissedTickBehavior},
+};
+
+use crate::base_node_service::{
+    config::BaseNodeServiceConfig,
+    handle::{BaseNodeEvent, BaseNodeEventSender},
+    service::BaseNodeState,
+};
+
+const LOG_TARGET: &str = "wallet::base_node_service::beacon_monitor";
+
+/// Periodically compares the tip of the connected base node against the consensus of the signed network status
+/// beacons, and warns if the base node appears to be far behind or on a minority fork.
+pub struct StatusBeaconMonitor {
+    urls: Vec<String>,
+    trusted_signers: Vec<CommsPublicKey>,
+    network: Network,
+    check_interval: Duration,
+    max_age: Duration,
+    max_divergence: u64,
+    state: Arc<RwLock<BaseNodeState>>,
+    event_publisher: BaseNodeEventSender,
+    is_diverged: bool,
+}
+
+impl StatusBeaconMonitor {
+    /// Returns None if no beacon URLs are configured
+    pub fn new(
+        config: &BaseNodeServiceConfig,
+        network: Network,
+        state: Arc<RwLock<BaseNodeState>>,
+        event_publisher: BaseNodeEventSender,
+    ) -> Option<Self> {
+        if config.status_beacon_urls.is_empty() {
+            return None;
+        }
+        let trusted_signers = config
+            .status_beacon_signers
+            .iter()
+            .filter_map(|signer| match CommsPublicKey::from_hex(signer) {
+                Ok(pk) => Some(pk),
+                Err(e) => {
+                    warn!(target: LOG_TARGET, "Ignoring invalid status beacon signer '{}': {}", signer, e);
+                    None
+                },
+            })
+            .collect();
+        Some(Self {
+            urls: config.status_beacon_urls.clone().into_vec(),
+            trusted_signers,
+            network,
+            check_interval: config.status_beacon_check_interval,
+            max_age: config.status_beacon_max_age,
+            max_divergence: config.status_beacon_max_divergence,
+            state,
+            event_publisher,
+            is_diverged: false,
+        })
+    }
+
+    pub async fn run(mut self) {
+        let mut interval = time::interval(self.check_interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            self.check_beacons().await;
+        }
+    }
+
+    async fn check_beacons(&mut self) {
+        let Some(local_height) = self
+            .state
+            .read()
+            .await
+            .chain_metadata
+            .as_ref()
+            .map(|metadata| metadata.best_block_height())
+        else {
+            trace!(target: LOG_TARGET, "No base node tip yet, skipping status beacon check");
+            return;
+        };
+
+        let results = future::join_all(
+            self.urls
+                .iter()
+                .map(|url| fetch_status_beacon(url, self.network, &self.trusted_signers)),
+        )
+        .await;
+        let beacons = self
+            .urls
+            .iter()
+            .zip(results)
+            .filter_map(|(url, result)| match result {
+                Ok(beacon) => Some(beacon),
+                Err(e) => {
+                    debug!(target: LOG_TARGET, "Failed to fetch status beacon from {}: {}", url, e);
+                    None
+                },
+            })
+            .collect::<Vec<_>>();
+
+        let now = EpochTime::now().as_u64();
+        let Some(consensus) = BeaconConsensus::from_beacons(&beacons, now, self.max_age, 1) else {
+            debug!(target: LOG_TARGET, "No recent status beacons available");
+            return;
+        };
+
+        let divergence = consensus.divergence(local_height);
+        if divergence <= self.max_divergence {
+            if self.is_diverged {
+                info!(
+                    target: LOG_TARGET,
+                    "Base node tip #{} agrees with the status beacons again (#{})", local_height, consensus.tip_height
+                );
+                self.is_diverged = false;
+            }
+            return;
+        }
+        warn!(
+            target: LOG_TARGET,
+            "Base node tip #{} differs from the consensus of {} status beacon(s) (#{}) by {} blocks. The base node may \
+             be behind or on a minority fork.",
+            local_height,
+            consensus.num_beacons,
+            consensus.tip_height,
+            divergence
+        );
+        if !self.is_diverged {
+            self.is_diverged = true;
+            let _size = self
+                .event_publisher
+                .send(Arc::new(BaseNodeEvent::TipDivergesFromBeacons(
+                    local_height,
+                    consensus.tip_height,
+                )));
+        }
+    }
+}
@@ -23,7 +23,7 @@
 use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
-use tari_common::configuration::serializers;
+use tari_common::configuration::{serializers, StringList};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -35,6 +35,23 @@ pub struct BaseNodeServiceConfig {
     pub base_node_rpc_pool_size: usize,
     /// This is the size of the event channel used to communicate base node events to the wallet
     pub event_channel_size: usize,
+    /// The time without a new block after which the base node is considered stalled and the best of the other base
+    /// node peers is selected instead. Failover is disabled if this is zero or not set.
+    #[serde(with = "serializers::optional_seconds")]
+    pub base_node_stall_timeout: Option<Duration>,
+    /// URLs of signed network status beacons. The base node tip is compared against the consensus of the beacons
+    /// and a warning is raised if it diverges. Disabled if empty.
+    pub status_beacon_urls: StringList,
+    /// Hex encoded public keys of the trusted status beacon signers. Beacons from any signer are accepted if empty.
+    pub status_beacon_signers: StringList,
+    /// The interval at which the status beacons are fetched
+    #[serde(with = "serializers::seconds")]
+    pub status_beacon_check_interval: Duration,
+    /// Beacons older than this are ignored
+    #[serde(with = "serializers::seconds")]
+    pub status_beacon_max_age: Duration,
+    /// The number of blocks that the base node tip may differ from the beacon consensus before a warning is raised
+    pub status_beacon_max_divergence: u64,
 }
 
 impl Default for BaseNodeServiceConfig {
@@ -43,6 +60,12 @@ impl Default for BaseNodeServiceConfig {
             base_node_monitor_max_refresh_interval: Duration::from_secs(30),
             base_node_rpc_pool_size: 10,
             event_channel_size: 250,
+            base_node_stall_timeout: Some(Duration::from_secs(15 * 60)),
+            status_beacon_urls: StringList::default(),
+            status_beacon_signers: StringList::default(),
+            status_beacon_check_interval: Duration::from_secs(5 * 60),
+            status_beacon_max_age: Duration::from_secs(10 * 60),
+            status_beacon_max_divergence: 20,
         }
     }
 }
@@ -23,6 +23,7 @@
 use std::{fmt, fmt::Formatter, sync::Arc, time::Duration};
 
 use tari_common_types::{chain_metadata::ChainMetadata, types::BlockHash};
+use tari_comms::peer_manager::NodeId;
 use tari_service_framework::reply_channel::SenderService;
 use tokio::sync::broadcast;
 use tower::Service;
@@ -47,6 +48,10 @@ pub enum BaseNodeServiceResponse {
 pub enum BaseNodeEvent {
     BaseNodeStateChanged(BaseNodeState),
     NewBlockDetected(BlockHash, u64),
+    /// The first base node stalled and the second one was selected instead
+    BaseNodeFailover(NodeId, NodeId),
+    /// The base node tip height differs from the status beacon consensus height by more than the configured maximum
+    TipDivergesFromBeacons(u64, u64),
 }
 
 impl fmt::Display for BaseNodeEvent {
@@ -58,6 +63,16 @@ impl fmt::Display for BaseNodeEvent {
             BaseNodeEvent::NewBlockDetected(hash, height) => {
                 write!(f, "NewBlockDetected: {} ({})", height, hash)
             },
+            BaseNodeEvent::BaseNodeFailover(stalled, selected) => {
+                write!(f, "BaseNodeFailover: {} stalled, selected {}", stalled, selected)
+            },
+            BaseNodeEvent::TipDivergesFromBeacons(local_height, beacon_height) => {
+                write!(
+                    f,
+                    "TipDivergesFromBeacons: base node at {}, beacons at {}",
+                    local_height, beacon_height
+                )
+            },
         }
     }
 }
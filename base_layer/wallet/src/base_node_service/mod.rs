@@ -25,7 +25,9 @@ pub mod error;
 pub mod handle;
 pub mod service;
 
+mod beacon_monitor;
 mod monitor;
+mod peer_scores;
 
 use log::*;
 use tari_service_framework::{
@@ -34,6 +34,7 @@ use log::*;
 use tari_common_types::{chain_metadata::ChainMetadata, types::BlockHash as BlockHashType};
 use tari_comms::{
     backoff::{Backoff, ExponentialBackoff},
+    peer_manager::NodeId,
     protocol::rpc::RpcError,
 };
 use tokio::{sync::RwLock, time};
@@ -41,9 +42,10 @@ use tokio::{sync::RwLock, time};
 use crate::{
     base_node_service::{
         handle::{BaseNodeEvent, BaseNodeEventSender},
+        peer_scores::BaseNodePeerScores,
         service::BaseNodeState,
     },
-    connectivity_service::WalletConnectivityInterface,
+    connectivity_service::{BaseNodePeerManager, WalletConnectivityInterface},
     error::WalletStorageError,
     storage::database::{WalletBackend, WalletDatabase},
 };
@@ -58,6 +60,10 @@ pub struct BaseNodeMonitor<TBackend, TWalletConnectivity> {
     db: WalletDatabase<TBackend>,
     wallet_connectivity: TWalletConnectivity,
     event_publisher: BaseNodeEventSender,
+    stall_timeout: Option<Duration>,
+    peer_scores: BaseNodePeerScores,
+    /// The current base node and when it last reported a new tip, or was selected
+    last_progress: Option<(NodeId, Instant)>,
 }
 
 impl<TBackend, TWalletConnectivity> BaseNodeMonitor<TBackend, TWalletConnectivity>
@@ -71,6 +77,7 @@ where
         db: WalletDatabase<TBackend>,
         wallet_connectivity: TWalletConnectivity,
         event_publisher: BaseNodeEventSender,
+        stall_timeout: Option<Duration>,
     ) -> Self {
         Self {
             max_interval,
@@ -80,6 +87,9 @@ where
             db,
             wallet_connectivity,
             event_publisher,
+            stall_timeout: stall_timeout.filter(|t| !t.is_zero()),
+            peer_scores: BaseNodePeerScores::default(),
+            last_progress: None,
         }
     }
 
@@ -106,6 +116,9 @@ where
                         latency: None,
                     })
                     .await;
+                    if let Some(node_id) = self.wallet_connectivity.get_current_base_node_peer_node_id() {
+                        self.check_for_stall(node_id, false);
+                    }
                     continue;
                 },
                 Err(e @ BaseNodeMonitorError::InvalidBaseNodeResponse(_)) |
@@ -183,6 +196,10 @@ where
                 if is_synced { "Synced" } else { "Syncing..." },
                 latency.as_millis()
             );
+            self.peer_scores.record_tip(&base_node_id, best_block_height, latency);
+            if self.check_for_stall(base_node_id, new_block) {
+                continue;
+            }
 
             // If there's a new block, try again immediately,
             if new_block {
@@ -204,6 +221,65 @@ where
         Ok(())
     }
 
+    /// Tracks when the current base node last reported a new tip, and selects another base node if it has not done so
+    /// within the stall timeout. Returns true if another base node was selected.
+    fn check_for_stall(&mut self, node_id: NodeId, new_block: bool) -> bool {
+        let stall_timeout = match self.stall_timeout {
+            Some(t) => t,
+            None => return false,
+        };
+        let since = match &self.last_progress {
+            Some((current, since)) if *current == node_id && !new_block => *since,
+            _ => {
+                self.last_progress = Some((node_id, Instant::now()));
+                return false;
+            },
+        };
+        if since.elapsed() < stall_timeout {
+            return false;
+        }
+        // Restart the timer, so that a stall without any other base node to select is only reported once per timeout
+        self.last_progress = Some((node_id.clone(), Instant::now()));
+        self.peer_scores.record_stall(&node_id);
+
+        let peers = match self.wallet_connectivity.get_base_node_peer_manager_state() {
+            Some((_, peers)) => peers,
+            None => return false,
+        };
+        let index = match self.peer_scores.select_failover(&peers, &node_id, stall_timeout) {
+            Some(index) => index,
+            None => {
+                warn!(
+                    target: LOG_TARGET,
+                    "Base node {} has not reported a new block for {:.0?}, and there are no other base node peers to \
+                     select",
+                    node_id,
+                    stall_timeout
+                );
+                return false;
+            },
+        };
+        let selected = peers[index].node_id.clone();
+        match BaseNodePeerManager::new(index, peers) {
+            Ok(peer_manager) => {
+                warn!(
+                    target: LOG_TARGET,
+                    "Base node {} has not reported a new block for {:.0?}, selecting base node {}",
+                    node_id,
+                    stall_timeout,
+                    selected
+                );
+                self.wallet_connectivity.set_base_node(peer_manager);
+                self.publish_event(BaseNodeEvent::BaseNodeFailover(node_id, selected));
+                true
+            },
+            Err(e) => {
+                error!(target: LOG_TARGET, "Could not select another base node: {}", e);
+                false
+            },
+        }
+    }
+
     // returns true if a new block, otherwise false
     async fn update_state(&self, new_state: BaseNodeState) -> bool {
         let mut lock = self.state.write().await;
@@ -0,0 +1,110 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Scores of the base node peers the wallet has used, to choose which one to fail over to when the current one stalls.
+//!
+//! Peers are ranked first by whether they stalled recently, then by how far their last known tip is behind the best
+//! tip seen from any peer, and then by latency. Peers that have not been used yet are assumed to be up to date, so
+//! they are tried before peers that are known to lag.
+
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use tari_comms::peer_manager::{NodeId, Peer};
+
+#[derive(Debug, Clone, Default)]
+struct PeerScore {
+    best_block_height: Option<u64>,
+    latency: Option<Duration>,
+    stalled_at: Option<Instant>,
+}
+
+#[derive(Debug, Default)]
+pub struct BaseNodePeerScores {
+    scores: HashMap<NodeId, PeerScore>,
+}
+
+impl BaseNodePeerScores {
+    /// Records the tip reported by a peer, and the latency of the request
+    pub fn record_tip(&mut self, node_id: &NodeId, best_block_height: u64, latency: Duration) {
+        let score = self.scores.entry(node_id.clone()).or_default();
+        score.best_block_height = Some(best_block_height);
+        score.latency = Some(latency);
+    }
+
+    /// Records that a peer did not report a new tip for too long
+    pub fn record_stall(&mut self, node_id: &NodeId) {
+        self.scores.entry(node_id.clone()).or_default().stalled_at = Some(Instant::now());
+    }
+
+    /// Returns the index in `peers` of the best peer other than `current`, or `None` if there is no other peer. A
+    /// stall is forgotten after `stall_timeout`.
+    pub fn select_failover(&self, peers: &[Peer], current: &NodeId, stall_timeout: Duration) -> Option<usize> {
+        let best_known_height = self.scores.values().filter_map(|s| s.best_block_height).max();
+        peers
+            .iter()
+            .enumerate()
+            .filter(|(_, peer)| peer.node_id != *current)
+            .min_by_key(|(_, peer)| {
+                let score = self.scores.get(&peer.node_id).cloned().unwrap_or_default();
+                let recently_stalled = score.stalled_at.map_or(false, |t| t.elapsed() < stall_timeout);
+                let lag = match (best_known_height, score.best_block_height) {
+                    (Some(best), Some(height)) => best.saturating_sub(height),
+                    _ => 0,
+                };
+                (recently_stalled, lag, score.latency.unwrap_or_default())
+            })
+            .map(|(index, _)| index)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tari_comms::{
+        net_address::MultiaddressesWithStats,
+        peer_manager::{PeerFeatures, PeerFlags},
+        types::CommsPublicKey,
+    };
+    use tari_crypto::keys::PublicKey;
+
+    use super::*;
+
+    fn peer() -> Peer {
+        let (_, public_key) = CommsPublicKey::random_keypair(&mut rand::rngs::OsRng);
+        Peer::new(
+            public_key.clone(),
+            NodeId::from_key(&public_key),
+            MultiaddressesWithStats::default(),
+            PeerFlags::empty(),
+            PeerFeatures::COMMUNICATION_NODE,
+            vec![],
+            String::new(),
+        )
+    }
+
+    #[test]
+    fn it_selects_the_best_other_peer() {
+        let peers = vec![peer(), peer(), peer(), peer()];
+        let timeout = Duration::from_secs(600);
+        let mut scores = BaseNodePeerScores::default();
+        // Unknown peers are tried in order
+        assert_eq!(scores.select_failover(&peers, &peers[0].node_id, timeout), Some(1));
+
+        scores.record_tip(&peers[1].node_id, 100, Duration::from_millis(50));
+        scores.record_tip(&peers[2].node_id, 98, Duration::from_millis(10));
+        scores.record_tip(&peers[3].node_id, 100, Duration::from_millis(20));
+        assert_eq!(scores.select_failover(&peers, &peers[0].node_id, timeout), Some(3));
+
+        scores.record_stall(&peers[3].node_id);
+        assert_eq!(scores.select_failover(&peers, &peers[0].node_id, timeout), Some(1));
+        assert_eq!(scores.select_failover(&peers, &peers[1].node_id, timeout), Some(0));
+        assert_eq!(
+            scores.select_failover(&peers, &peers[0].node_id, Duration::ZERO),
+            Some(3)
+        );
+
+        assert_eq!(scores.select_failover(&peers[..1], &peers[0].node_id, timeout), None);
+    }
+}
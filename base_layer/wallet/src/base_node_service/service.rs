@@ -25,6 +25,7 @@ use std::{sync::Arc, time::Duration};
 use chrono::NaiveDateTime;
 use futures::{future, StreamExt};
 use log::*;
+use tari_common::configuration::Network;
 use tari_common_types::chain_metadata::ChainMetadata;
 use tari_comms::peer_manager::NodeId;
 use tari_service_framework::reply_channel::Receiver;
@@ -37,7 +38,7 @@ use super::{
     handle::{BaseNodeEventSender, BaseNodeServiceRequest, BaseNodeServiceResponse},
 };
 use crate::{
-    base_node_service::monitor::BaseNodeMonitor,
+    base_node_service::{beacon_monitor::StatusBeaconMonitor, monitor::BaseNodeMonitor},
     connectivity_service::WalletConnectivityHandle,
     storage::database::{WalletBackend, WalletDatabase},
 };
@@ -97,6 +98,7 @@ where T: WalletBackend + 'static
     /// Starts the service.
     pub async fn start(mut self) -> Result<(), BaseNodeServiceError> {
         self.spawn_monitor();
+        self.spawn_beacon_monitor();
 
         let mut request_stream = self
             .request_stream
@@ -131,6 +133,7 @@ where T: WalletBackend + 'static
             self.db.clone(),
             self.wallet_connectivity.clone(),
             self.event_publisher.clone(),
+            self.config.base_node_stall_timeout,
         );
 
         let shutdown_signal = self.shutdown_signal.clone();
@@ -141,6 +144,24 @@ where T: WalletBackend + 'static
         });
     }
 
+    fn spawn_beacon_monitor(&self) {
+        let Some(monitor) = StatusBeaconMonitor::new(
+            &self.config,
+            Network::get_current_or_user_setting_or_default(),
+            self.state.clone(),
+            self.event_publisher.clone(),
+        ) else {
+            return;
+        };
+
+        let shutdown_signal = self.shutdown_signal.clone();
+        tokio::spawn(async move {
+            let monitor_fut = monitor.run();
+            futures::pin_mut!(monitor_fut);
+            future::select(shutdown_signal, monitor_fut).await;
+        });
+    }
+
     /// This handler is called when requests arrive from the various streams
     async fn handle_request(
         &mut self,
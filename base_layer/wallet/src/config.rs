@@ -32,14 +32,19 @@ use tari_common::{
     configuration::{serializers, Network, StringList},
     SubConfigPath,
 };
-use tari_common_types::grpc_authentication::GrpcAuthentication;
+use tari_common_types::{backup_config::BackupConfig, grpc_authentication::GrpcAuthentication};
 use tari_comms::multiaddr::Multiaddr;
 use tari_p2p::P2pConfig;
 use tari_utilities::SafePassword;
 
 use crate::{
     base_node_service::config::BaseNodeServiceConfig,
+    deposit_tracking_service::config::DepositTrackingServiceConfig,
+    metadata_sync::config::MetadataSyncConfig,
     output_manager_service::config::OutputManagerServiceConfig,
+    push_notification_service::config::PushNotificationServiceConfig,
+    reorg_monitor_service::config::ReorgMonitorServiceConfig,
+    sweep_service::config::SweepServiceConfig,
     transaction_service::config::TransactionServiceConfig,
 };
 
@@ -68,6 +73,18 @@ pub struct WalletConfig {
     /// The base_node_service_config config settings
     #[serde(rename = "base_node")]
     pub base_node_service_config: BaseNodeServiceConfig,
+    /// The push notification relay settings
+    #[serde(rename = "push_notifications")]
+    pub push_notification_service_config: PushNotificationServiceConfig,
+    /// The deposit tracking settings
+    #[serde(rename = "deposit_tracking")]
+    pub deposit_tracking_service_config: DepositTrackingServiceConfig,
+    /// The hot wallet sweep settings
+    #[serde(rename = "sweep")]
+    pub sweep_service_config: SweepServiceConfig,
+    /// The reorg monitor settings
+    #[serde(rename = "reorg_monitor")]
+    pub reorg_monitor_service_config: ReorgMonitorServiceConfig,
     /// The relative path to store persistent data
     pub data_dir: PathBuf,
     /// The relative path to the config directory
@@ -84,6 +101,9 @@ pub struct WalletConfig {
     pub contacts_auto_ping_interval: Duration,
     /// How long a contact may be not seen before being determined to be offline
     pub contacts_online_ping_window: usize,
+    /// If true, the wallet announces that it is online to its contacts. Only contacts that have added this wallet as
+    /// a contact too will see it.
+    pub contacts_publish_presence: bool,
     /// When running the console wallet in command mode, how long to wait for sent transactions.
     #[serde(with = "serializers::seconds")]
     pub command_send_wait_timeout: Duration,
@@ -101,10 +121,36 @@ pub struct WalletConfig {
     pub grpc_authentication: GrpcAuthentication,
     /// GRPC tls enabled
     pub grpc_tls_enabled: bool,
+    /// If true, a JSON-RPC 2.0 compatibility server will bind to the configured address and listen for HTTP requests.
+    pub json_rpc_enabled: bool,
+    /// JSON-RPC bind address of the wallet
+    pub json_rpc_address: Option<Multiaddr>,
+    /// JSON-RPC HTTP basic authentication mode
+    pub json_rpc_authentication: GrpcAuthentication,
     /// A custom base node peer that will be used to obtain metadata from
     pub custom_base_node: Option<String>,
+    /// If set, the base node service peers, or the peer seeds if there are none, are used as failover peers for the
+    /// custom base node
+    pub custom_base_node_failover: bool,
     /// A list of base node peers that the wallet should use for service requests and tracking chain state
     pub base_node_service_peers: StringList,
+    /// The gRPC address of a trusted base node with the light wallet server enabled. If set, the sync command
+    /// monitors the chain, scans for outputs and broadcasts pending transactions over gRPC instead of the p2p network.
+    pub base_node_grpc_address: Option<Multiaddr>,
+    /// The authentication of the trusted base node's gRPC server
+    pub base_node_grpc_authentication: GrpcAuthentication,
+    /// The gRPC address of a remote signer that holds the spend key. If set, new and recovered wallets are remote
+    /// signer wallets, which ask the signer for every signature that needs the spend key.
+    pub remote_signer_address: Option<Multiaddr>,
+    /// The domain name in the remote signer's server certificate
+    pub remote_signer_tls_domain_name: String,
+    /// The CA certificate that the remote signer's server certificate is signed with, relative to the config directory
+    pub remote_signer_ca_cert_filename: String,
+    /// The client certificate that the wallet authenticates to the remote signer with, relative to the config
+    /// directory
+    pub remote_signer_client_cert_filename: String,
+    /// The private key of the client certificate, relative to the config directory
+    pub remote_signer_client_key_filename: String,
     /// The amount of times wallet recovery will be retried before being abandoned
     pub recovery_retry_limit: usize,
     /// The default uT fee per gram to use for transaction fees
@@ -121,6 +167,10 @@ pub struct WalletConfig {
     /// responsiveness of the wallet with slightly delayed balance updates
     #[serde(with = "serializers::seconds")]
     pub balance_enquiry_cooldown_period: Duration,
+    /// The periodic encrypted backup settings
+    pub backup: BackupConfig,
+    /// The settings for syncing contacts, drafts and application values with the wallet's other devices
+    pub metadata_sync: MetadataSyncConfig,
 }
 
 impl Default for WalletConfig {
@@ -138,6 +188,10 @@ impl Default for WalletConfig {
             buffer_size: 50_000,
             network: Default::default(),
             base_node_service_config: Default::default(),
+            push_notification_service_config: Default::default(),
+            deposit_tracking_service_config: Default::default(),
+            sweep_service_config: Default::default(),
+            reorg_monitor_service_config: Default::default(),
             data_dir: PathBuf::from_str("data/wallet").unwrap(),
             config_dir: PathBuf::from_str("config/wallet").unwrap(),
             db_file: PathBuf::from_str("db/console_wallet.db").unwrap(),
@@ -145,6 +199,7 @@ impl Default for WalletConfig {
             password: None,
             contacts_auto_ping_interval: Duration::from_secs(30),
             contacts_online_ping_window: 30,
+            contacts_publish_presence: true,
             command_send_wait_stage: TransactionStage::Broadcast,
             command_send_wait_timeout: Duration::from_secs(300),
             notify_file: None,
@@ -152,14 +207,27 @@ impl Default for WalletConfig {
             grpc_address: None,
             grpc_authentication: GrpcAuthentication::default(),
             grpc_tls_enabled: false,
+            json_rpc_enabled: false,
+            json_rpc_address: None,
+            json_rpc_authentication: GrpcAuthentication::default(),
             custom_base_node: None,
+            custom_base_node_failover: false,
             base_node_service_peers: StringList::default(),
+            base_node_grpc_address: None,
+            base_node_grpc_authentication: GrpcAuthentication::default(),
+            remote_signer_address: None,
+            remote_signer_tls_domain_name: "localhost".to_string(),
+            remote_signer_ca_cert_filename: "remote_signer_ca.pem".to_string(),
+            remote_signer_client_cert_filename: "remote_signer_client.pem".to_string(),
+            remote_signer_client_key_filename: "remote_signer_client.key".to_string(),
             recovery_retry_limit: 3,
             fee_per_gram: 5,
             num_required_confirmations: 3,
             use_libtor: true,
             identity_file: None,
             balance_enquiry_cooldown_period: Duration::from_secs(5),
+            backup: BackupConfig::default(),
+            metadata_sync: MetadataSyncConfig::default(),
         }
     }
 }
@@ -181,6 +249,7 @@ impl WalletConfig {
         if !self.db_file.is_absolute() {
             self.db_file = self.data_dir.join(self.db_file.as_path());
         }
+        self.backup.set_base_path(&self.data_dir);
         self.p2p.set_base_path(base_path);
     }
 }
@@ -0,0 +1,22 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DepositTrackingServiceConfig {
+    /// The number of confirmations after which a deposit is finalised
+    pub finality_confirmations: u64,
+    /// The size of the channel that deposit events are published on
+    pub event_channel_size: usize,
+}
+
+impl Default for DepositTrackingServiceConfig {
+    fn default() -> Self {
+        Self {
+            finality_confirmations: 10,
+            event_channel_size: 250,
+        }
+    }
+}
@@ -0,0 +1,121 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::{fmt, sync::Arc};
+
+use tari_service_framework::reply_channel::SenderService;
+use tokio::sync::broadcast;
+use tower::Service;
+
+use crate::deposit_tracking_service::{
+    error::DepositTrackingError,
+    tracker::{DepositEvent, DepositWatch},
+};
+
+pub type DepositEventSender = broadcast::Sender<Arc<DepositEvent>>;
+pub type DepositEventReceiver = broadcast::Receiver<Arc<DepositEvent>>;
+
+#[derive(Debug)]
+pub enum DepositTrackingRequest {
+    Watch(DepositWatch),
+    Unwatch(DepositWatch),
+    GetWatches,
+    GetUnacknowledgedEvents,
+    AcknowledgeEvents(u64),
+}
+
+impl fmt::Display for DepositTrackingRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Watch(watch) => write!(f, "Watch({})", watch),
+            Self::Unwatch(watch) => write!(f, "Unwatch({})", watch),
+            Self::GetWatches => write!(f, "GetWatches"),
+            Self::GetUnacknowledgedEvents => write!(f, "GetUnacknowledgedEvents"),
+            Self::AcknowledgeEvents(sequence) => write!(f, "AcknowledgeEvents({})", sequence),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum DepositTrackingResponse {
+    WatchUpdated(bool),
+    Watches(Vec<DepositWatch>),
+    Events(Vec<DepositEvent>),
+    EventsAcknowledged(usize),
+}
+
+/// Tracks incoming transactions to watched addresses and payment IDs through their lifecycle. Events are kept until
+/// they are acknowledged, so a consumer that reads [DepositTrackingHandle::get_unacknowledged_events] and acknowledges
+/// the events it has processed receives every event exactly once, across restarts of the wallet. The event stream
+/// publishes the same events as they occur, but events published while nothing is subscribed are not replayed.
+#[derive(Clone)]
+pub struct DepositTrackingHandle {
+    handle: SenderService<DepositTrackingRequest, Result<DepositTrackingResponse, DepositTrackingError>>,
+    event_stream_sender: DepositEventSender,
+}
+
+impl DepositTrackingHandle {
+    pub fn new(
+        handle: SenderService<DepositTrackingRequest, Result<DepositTrackingResponse, DepositTrackingError>>,
+        event_stream_sender: DepositEventSender,
+    ) -> Self {
+        Self {
+            handle,
+            event_stream_sender,
+        }
+    }
+
+    pub fn get_event_stream(&self) -> DepositEventReceiver {
+        self.event_stream_sender.subscribe()
+    }
+
+    /// Tracks incoming transactions to the address or with the payment ID as deposits. Returns false if it was
+    /// already watched.
+    pub async fn watch(&mut self, watch: DepositWatch) -> Result<bool, DepositTrackingError> {
+        match self.handle.call(DepositTrackingRequest::Watch(watch)).await?? {
+            DepositTrackingResponse::WatchUpdated(added) => Ok(added),
+            _ => Err(DepositTrackingError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Stops tracking new deposits for the address or payment ID. Deposits that were already seen are tracked until
+    /// they are finalised or cancelled. Returns false if it was not watched.
+    pub async fn unwatch(&mut self, watch: DepositWatch) -> Result<bool, DepositTrackingError> {
+        match self.handle.call(DepositTrackingRequest::Unwatch(watch)).await?? {
+            DepositTrackingResponse::WatchUpdated(removed) => Ok(removed),
+            _ => Err(DepositTrackingError::UnexpectedApiResponse),
+        }
+    }
+
+    pub async fn get_watches(&mut self) -> Result<Vec<DepositWatch>, DepositTrackingError> {
+        match self.handle.call(DepositTrackingRequest::GetWatches).await?? {
+            DepositTrackingResponse::Watches(watches) => Ok(watches),
+            _ => Err(DepositTrackingError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Returns the events that have not been acknowledged, in sequence order
+    pub async fn get_unacknowledged_events(&mut self) -> Result<Vec<DepositEvent>, DepositTrackingError> {
+        match self
+            .handle
+            .call(DepositTrackingRequest::GetUnacknowledgedEvents)
+            .await??
+        {
+            DepositTrackingResponse::Events(events) => Ok(events),
+            _ => Err(DepositTrackingError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Acknowledges the events up to and including `sequence`, which are then no longer returned. Returns the number
+    /// of events acknowledged.
+    pub async fn acknowledge_events(&mut self, sequence: u64) -> Result<usize, DepositTrackingError> {
+        match self
+            .handle
+            .call(DepositTrackingRequest::AcknowledgeEvents(sequence))
+            .await??
+        {
+            DepositTrackingResponse::EventsAcknowledged(num_events) => Ok(num_events),
+            _ => Err(DepositTrackingError::UnexpectedApiResponse),
+        }
+    }
+}
@@ -0,0 +1,87 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Tracks incoming transactions to watched addresses and payment IDs as deposits, and emits an event when a deposit is
+//! seen, gains confirmations, is finalised, is reorged out or is cancelled. The events are persisted with the state of
+//! the deposits, so every event is delivered exactly once to a consumer that acknowledges them, even across restarts.
+
+pub mod config;
+pub mod error;
+pub mod handle;
+pub mod service;
+pub mod tracker;
+
+use log::*;
+use tari_service_framework::{
+    async_trait,
+    reply_channel,
+    ServiceInitializationError,
+    ServiceInitializer,
+    ServiceInitializerContext,
+};
+use tokio::sync::broadcast;
+
+use crate::{
+    deposit_tracking_service::{
+        config::DepositTrackingServiceConfig,
+        handle::DepositTrackingHandle,
+        service::DepositTrackingService,
+    },
+    storage::database::{WalletBackend, WalletDatabase},
+    transaction_service::handle::TransactionServiceHandle,
+};
+
+const LOG_TARGET: &str = "wallet::deposit_tracking_service";
+
+pub const DEPOSIT_TRACKING_STATE_KEY: &str = "deposit_tracking_state";
+
+pub struct DepositTrackingServiceInitializer<T>
+where T: WalletBackend + 'static
+{
+    config: DepositTrackingServiceConfig,
+    db: WalletDatabase<T>,
+}
+
+impl<T> DepositTrackingServiceInitializer<T>
+where T: WalletBackend + 'static
+{
+    pub fn new(config: DepositTrackingServiceConfig, db: WalletDatabase<T>) -> Self {
+        Self { config, db }
+    }
+}
+
+#[async_trait]
+impl<T> ServiceInitializer for DepositTrackingServiceInitializer<T>
+where T: WalletBackend + 'static
+{
+    async fn initialize(&mut self, context: ServiceInitializerContext) -> Result<(), ServiceInitializationError> {
+        info!(target: LOG_TARGET, "Wallet deposit tracking service initializing.");
+        let (sender, request_stream) = reply_channel::unbounded();
+        let (event_publisher, _) = broadcast::channel(self.config.event_channel_size);
+        context.register_handle(DepositTrackingHandle::new(sender, event_publisher.clone()));
+
+        let config = self.config.clone();
+        let db = self.db.clone();
+        context.spawn_when_ready(move |handles| async move {
+            let transaction_service = handles.expect_handle::<TransactionServiceHandle>();
+
+            let result = DepositTrackingService::new(
+                config,
+                db,
+                request_stream,
+                transaction_service,
+                event_publisher,
+                handles.get_shutdown_signal(),
+            )
+            .start()
+            .await;
+
+            info!(
+                target: LOG_TARGET,
+                "Wallet Deposit Tracking Service shutdown with result {:?}", result
+            );
+        });
+
+        Ok(())
+    }
+}
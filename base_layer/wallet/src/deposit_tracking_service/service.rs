@@ -0,0 +1,230 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::sync::Arc;
+
+use futures::StreamExt;
+use log::*;
+use tari_common_types::transaction::TxId;
+use tari_service_framework::reply_channel::Receiver;
+use tari_shutdown::ShutdownSignal;
+use tokio::sync::broadcast;
+
+use crate::{
+    deposit_tracking_service::{
+        config::DepositTrackingServiceConfig,
+        error::DepositTrackingError,
+        handle::{DepositEventSender, DepositTrackingRequest, DepositTrackingResponse},
+        tracker::{DepositEvent, DepositObservation, DepositTracker},
+        DEPOSIT_TRACKING_STATE_KEY,
+    },
+    storage::database::{WalletBackend, WalletDatabase},
+    transaction_service::{
+        handle::{TransactionEvent, TransactionServiceHandle},
+        storage::models::WalletTransaction,
+    },
+};
+
+const LOG_TARGET: &str = "wallet::deposit_tracking_service::service";
+
+pub struct DepositTrackingService<T>
+where T: WalletBackend + 'static
+{
+    config: DepositTrackingServiceConfig,
+    db: WalletDatabase<T>,
+    tracker: DepositTracker,
+    request_stream: Option<Receiver<DepositTrackingRequest, Result<DepositTrackingResponse, DepositTrackingError>>>,
+    transaction_service: TransactionServiceHandle,
+    event_publisher: DepositEventSender,
+    shutdown_signal: ShutdownSignal,
+}
+
+impl<T> DepositTrackingService<T>
+where T: WalletBackend + 'static
+{
+    pub fn new(
+        config: DepositTrackingServiceConfig,
+        db: WalletDatabase<T>,
+        request_stream: Receiver<DepositTrackingRequest, Result<DepositTrackingResponse, DepositTrackingError>>,
+        transaction_service: TransactionServiceHandle,
+        event_publisher: DepositEventSender,
+        shutdown_signal: ShutdownSignal,
+    ) -> Self {
+        Self {
+            config,
+            db,
+            tracker: DepositTracker::default(),
+            request_stream: Some(request_stream),
+            transaction_service,
+            event_publisher,
+            shutdown_signal,
+        }
+    }
+
+    pub async fn start(mut self) -> Result<(), DepositTrackingError> {
+        let mut request_stream = self
+            .request_stream
+            .take()
+            .expect("Deposit Tracking Service initialized without request_stream");
+        let mut event_stream = self.transaction_service.get_event_stream();
+        let mut shutdown_signal = self.shutdown_signal.clone();
+
+        if let Some(state) = self.db.get_client_key_value(DEPOSIT_TRACKING_STATE_KEY.to_string())? {
+            self.tracker = serde_json::from_str(&state)?;
+        }
+        // Catch up with the transactions that changed while the wallet was not running
+        self.update_all_transactions().await?;
+
+        debug!(target: LOG_TARGET, "Deposit Tracking Service started");
+        loop {
+            tokio::select! {
+                Some(request_context) = request_stream.next() => {
+                    let (request, reply_tx) = request_context.split();
+                    let response = self.handle_request(request).await.map_err(|e| {
+                        error!(target: LOG_TARGET, "Error handling request: {:?}", e);
+                        e
+                    });
+                    let _result = reply_tx.send(response).inspect_err(|_| {
+                        warn!(target: LOG_TARGET, "Failed to send reply");
+                    });
+                },
+                event = event_stream.recv() => {
+                    let result = match event {
+                        Ok(event) => self.handle_transaction_event(&event).await,
+                        Err(broadcast::error::RecvError::Lagged(n)) => {
+                            warn!(
+                                target: LOG_TARGET,
+                                "Deposit Tracking Service missed {} transaction events, checking all transactions", n
+                            );
+                            self.update_all_transactions().await
+                        },
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    };
+                    if let Err(e) = result {
+                        warn!(target: LOG_TARGET, "Failed to update deposits: {}", e);
+                    }
+                },
+                _ = shutdown_signal.wait() => {
+                    info!(
+                        target: LOG_TARGET,
+                        "Deposit Tracking Service shutting down because the shutdown signal was received"
+                    );
+                    break;
+                },
+            }
+        }
+        Ok(())
+    }
+
+    async fn handle_request(
+        &mut self,
+        request: DepositTrackingRequest,
+    ) -> Result<DepositTrackingResponse, DepositTrackingError> {
+        trace!(target: LOG_TARGET, "Handling Deposit Tracking Service Request: {}", request);
+        match request {
+            DepositTrackingRequest::Watch(watch) => {
+                let mut tracker = self.tracker.clone();
+                if !tracker.watch(watch) {
+                    return Ok(DepositTrackingResponse::WatchUpdated(false));
+                }
+                self.commit(tracker, Vec::new())?;
+                // Transactions that were received before the watch was added are deposits too
+                self.update_all_transactions().await?;
+                Ok(DepositTrackingResponse::WatchUpdated(true))
+            },
+            DepositTrackingRequest::Unwatch(watch) => {
+                let mut tracker = self.tracker.clone();
+                let removed = tracker.unwatch(&watch);
+                if removed {
+                    self.commit(tracker, Vec::new())?;
+                }
+                Ok(DepositTrackingResponse::WatchUpdated(removed))
+            },
+            DepositTrackingRequest::GetWatches => Ok(DepositTrackingResponse::Watches(self.tracker.watches().to_vec())),
+            DepositTrackingRequest::GetUnacknowledgedEvents => Ok(DepositTrackingResponse::Events(
+                self.tracker.unacknowledged_events().to_vec(),
+            )),
+            DepositTrackingRequest::AcknowledgeEvents(sequence) => {
+                let mut tracker = self.tracker.clone();
+                let num_events = tracker.acknowledge(sequence);
+                if num_events > 0 {
+                    self.commit(tracker, Vec::new())?;
+                }
+                Ok(DepositTrackingResponse::EventsAcknowledged(num_events))
+            },
+        }
+    }
+
+    async fn handle_transaction_event(&mut self, event: &TransactionEvent) -> Result<(), DepositTrackingError> {
+        match event {
+            TransactionEvent::ReceivedFinalizedTransaction(tx_id) |
+            TransactionEvent::TransactionImported(tx_id) |
+            TransactionEvent::TransactionBroadcast(tx_id) |
+            TransactionEvent::DetectedTransactionUnconfirmed { tx_id, .. } |
+            TransactionEvent::DetectedTransactionConfirmed { tx_id, .. } |
+            TransactionEvent::TransactionMined { tx_id, .. } |
+            TransactionEvent::TransactionMinedUnconfirmed { tx_id, .. } => self.update_transactions(&[*tx_id]).await,
+            TransactionEvent::TransactionCancelled(..) => self.update_all_transactions().await,
+            // Reorgs are detected during validation, which does not publish an event for every transaction it unmines
+            TransactionEvent::TransactionValidationCompleted(_) => {
+                let tx_ids = self.tracker.tracked_tx_ids();
+                self.update_transactions(&tx_ids).await
+            },
+            _ => Ok(()),
+        }
+    }
+
+    async fn update_transactions(&mut self, tx_ids: &[TxId]) -> Result<(), DepositTrackingError> {
+        let mut tracker = self.tracker.clone();
+        let mut events = Vec::new();
+        for tx_id in tx_ids {
+            match self.transaction_service.get_any_transaction(*tx_id).await? {
+                Some(WalletTransaction::Completed(tx)) => {
+                    events.extend(tracker.update(&DepositObservation::from(&tx), self.config.finality_confirmations));
+                },
+                Some(_) => {},
+                // Cancelled transactions are only returned with the cancelled transactions
+                None if tracker.is_tracking(*tx_id) => return self.update_all_transactions().await,
+                None => {},
+            }
+        }
+        if events.is_empty() {
+            return Ok(());
+        }
+        self.commit(tracker, events)
+    }
+
+    async fn update_all_transactions(&mut self) -> Result<(), DepositTrackingError> {
+        let mut transactions = self.transaction_service.get_completed_transactions().await?;
+        transactions.extend(self.transaction_service.get_cancelled_completed_transactions().await?);
+        let mut transactions = transactions.into_values().collect::<Vec<_>>();
+        transactions.sort_by_key(|tx| tx.timestamp);
+
+        let mut tracker = self.tracker.clone();
+        let mut events = Vec::new();
+        for tx in &transactions {
+            events.extend(tracker.update(&DepositObservation::from(tx), self.config.finality_confirmations));
+        }
+        if events.is_empty() {
+            return Ok(());
+        }
+        self.commit(tracker, events)
+    }
+
+    /// Persists the updated tracker before publishing the events that resulted from the update, so that the events
+    /// are not published unless they were stored
+    fn commit(&mut self, tracker: DepositTracker, events: Vec<DepositEvent>) -> Result<(), DepositTrackingError> {
+        self.db
+            .set_client_key_value(DEPOSIT_TRACKING_STATE_KEY.to_string(), serde_json::to_string(&tracker)?)?;
+        self.tracker = tracker;
+        for event in events {
+            debug!(
+                target: LOG_TARGET,
+                "Deposit event #{} for transaction {}: {}", event.sequence, event.tx_id, event.kind
+            );
+            // Nobody might be subscribed, the event remains available until it is acknowledged
+            let _size = self.event_publisher.send(Arc::new(event));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,401 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
+
+use serde::{Deserialize, Serialize};
+use tari_common_types::{
+    tari_address::TariAddress,
+    transaction::{TransactionDirection, TxId},
+    types::BlockHash,
+};
+use tari_core::transactions::{tari_amount::MicroMinotari, transaction_components::encrypted_data::PaymentId};
+
+use crate::transaction_service::storage::models::CompletedTransaction;
+
+/// An address or payment ID that incoming transactions are tracked as deposits for
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DepositWatch {
+    Address(TariAddress),
+    PaymentId(PaymentId),
+}
+
+impl fmt::Display for DepositWatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DepositWatch::Address(address) => write!(f, "address {}", address),
+            DepositWatch::PaymentId(payment_id) => write!(f, "payment ID {}", payment_id),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DepositEventKind {
+    /// The deposit was received by the wallet, but has not been mined
+    Seen,
+    /// The deposit has the given number of confirmations, which is less than the finality depth
+    Confirmed {
+        confirmations: u64,
+        mined_height: u64,
+        mined_in_block: BlockHash,
+    },
+    /// The deposit reached the finality depth. No further events are emitted for it.
+    Finalised {
+        confirmations: u64,
+        mined_height: u64,
+        mined_in_block: BlockHash,
+    },
+    /// The block the deposit was mined in was reorged out. The deposit is unconfirmed again until it is mined in
+    /// another block.
+    ReorgedOut { mined_in_block: BlockHash },
+    /// The deposit was cancelled and will not be mined. No further events are emitted for it.
+    Cancelled,
+}
+
+impl fmt::Display for DepositEventKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DepositEventKind::Seen => write!(f, "Seen"),
+            DepositEventKind::Confirmed {
+                confirmations,
+                mined_height,
+                ..
+            } => write!(
+                f,
+                "Confirmed ({} confirmations at height {})",
+                confirmations, mined_height
+            ),
+            DepositEventKind::Finalised {
+                confirmations,
+                mined_height,
+                ..
+            } => write!(
+                f,
+                "Finalised ({} confirmations at height {})",
+                confirmations, mined_height
+            ),
+            DepositEventKind::ReorgedOut { mined_in_block } => write!(f, "ReorgedOut (block {})", mined_in_block),
+            DepositEventKind::Cancelled => write!(f, "Cancelled"),
+        }
+    }
+}
+
+/// A change in the lifecycle of a deposit. Sequence numbers increase by one with every event.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DepositEvent {
+    pub sequence: u64,
+    pub tx_id: TxId,
+    pub amount: MicroMinotari,
+    pub payment_id: Option<PaymentId>,
+    pub kind: DepositEventKind,
+}
+
+/// The state of an incoming transaction that is relevant to deposit tracking
+#[derive(Debug, Clone)]
+pub struct DepositObservation {
+    pub tx_id: TxId,
+    pub amount: MicroMinotari,
+    pub direction: TransactionDirection,
+    pub destination_address: TariAddress,
+    pub payment_id: Option<PaymentId>,
+    pub cancelled: bool,
+    /// The height and hash of the block the transaction was mined in
+    pub mined: Option<(u64, BlockHash)>,
+    pub confirmations: u64,
+}
+
+impl From<&CompletedTransaction> for DepositObservation {
+    fn from(tx: &CompletedTransaction) -> Self {
+        Self {
+            tx_id: tx.tx_id,
+            amount: tx.amount,
+            direction: tx.direction.clone(),
+            destination_address: tx.destination_address.clone(),
+            payment_id: tx.payment_id.clone(),
+            cancelled: tx.cancelled.is_some(),
+            mined: tx.mined_height.zip(tx.mined_in_block),
+            confirmations: tx.confirmations.unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct TrackedDeposit {
+    mined_in_block: Option<BlockHash>,
+    confirmations: u64,
+}
+
+/// The watched addresses and payment IDs, the deposits that have not reached finality, and the events that have not
+/// been acknowledged. Every event is derived from a state transition of a deposit, and the new state and the event
+/// are stored together, so an event is never emitted twice or lost as long as the tracker is persisted after every
+/// update.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DepositTracker {
+    watches: Vec<DepositWatch>,
+    deposits: BTreeMap<u64, TrackedDeposit>,
+    /// Deposits that were finalised or cancelled, which are never tracked again
+    completed: BTreeSet<u64>,
+    unacknowledged: Vec<DepositEvent>,
+    next_sequence: u64,
+}
+
+impl DepositTracker {
+    /// Returns false if the watch already exists
+    pub fn watch(&mut self, watch: DepositWatch) -> bool {
+        if self.watches.contains(&watch) {
+            return false;
+        }
+        self.watches.push(watch);
+        true
+    }
+
+    /// Returns false if the watch did not exist. Deposits that are already tracked are tracked until they are
+    /// finalised or cancelled.
+    pub fn unwatch(&mut self, watch: &DepositWatch) -> bool {
+        let num_watches = self.watches.len();
+        self.watches.retain(|w| w != watch);
+        self.watches.len() != num_watches
+    }
+
+    pub fn watches(&self) -> &[DepositWatch] {
+        &self.watches
+    }
+
+    pub fn unacknowledged_events(&self) -> &[DepositEvent] {
+        &self.unacknowledged
+    }
+
+    /// Removes the events up to and including `sequence`, and returns the number removed
+    pub fn acknowledge(&mut self, sequence: u64) -> usize {
+        let num_events = self.unacknowledged.len();
+        self.unacknowledged.retain(|e| e.sequence > sequence);
+        num_events - self.unacknowledged.len()
+    }
+
+    pub fn is_tracking(&self, tx_id: TxId) -> bool {
+        self.deposits.contains_key(&tx_id.as_u64())
+    }
+
+    pub fn tracked_tx_ids(&self) -> Vec<TxId> {
+        self.deposits.keys().map(|tx_id| TxId::from(*tx_id)).collect()
+    }
+
+    fn is_watched(&self, observation: &DepositObservation) -> bool {
+        self.watches.iter().any(|watch| match watch {
+            DepositWatch::Address(address) => *address == observation.destination_address,
+            DepositWatch::PaymentId(payment_id) => observation.payment_id.as_ref() == Some(payment_id),
+        })
+    }
+
+    /// Updates the deposit with the latest state of its transaction and returns the events that resulted from it. The
+    /// tracker only changes if events are returned. Transactions that are not incoming, or are not for a watched
+    /// address or payment ID, are ignored.
+    pub fn update(&mut self, observation: &DepositObservation, finality_confirmations: u64) -> Vec<DepositEvent> {
+        let tx_id = observation.tx_id.as_u64();
+        if self.completed.contains(&tx_id) {
+            return Vec::new();
+        }
+        let mut kinds = Vec::new();
+        let mut deposit = match self.deposits.remove(&tx_id) {
+            Some(deposit) => deposit,
+            None => {
+                if observation.direction != TransactionDirection::Inbound ||
+                    observation.cancelled ||
+                    !self.is_watched(observation)
+                {
+                    return Vec::new();
+                }
+                kinds.push(DepositEventKind::Seen);
+                TrackedDeposit::default()
+            },
+        };
+
+        if let Some(mined_in_block) = deposit.mined_in_block {
+            if observation.mined.map(|(_, hash)| hash) != Some(mined_in_block) {
+                kinds.push(DepositEventKind::ReorgedOut { mined_in_block });
+                deposit = TrackedDeposit::default();
+            }
+        }
+
+        let mut is_completed = false;
+        if observation.cancelled {
+            kinds.push(DepositEventKind::Cancelled);
+            is_completed = true;
+        } else if let Some((mined_height, mined_in_block)) = observation.mined {
+            if observation.confirmations > deposit.confirmations {
+                deposit.mined_in_block = Some(mined_in_block);
+                deposit.confirmations = observation.confirmations;
+                if deposit.confirmations >= finality_confirmations {
+                    kinds.push(DepositEventKind::Finalised {
+                        confirmations: deposit.confirmations,
+                        mined_height,
+                        mined_in_block,
+                    });
+                    is_completed = true;
+                } else {
+                    kinds.push(DepositEventKind::Confirmed {
+                        confirmations: deposit.confirmations,
+                        mined_height,
+                        mined_in_block,
+                    });
+                }
+            }
+        }
+
+        if is_completed {
+            self.completed.insert(tx_id);
+        } else {
+            self.deposits.insert(tx_id, deposit);
+        }
+
+        let events = kinds
+            .into_iter()
+            .map(|kind| {
+                let sequence = self.next_sequence;
+                self.next_sequence += 1;
+                DepositEvent {
+                    sequence,
+                    tx_id: observation.tx_id,
+                    amount: observation.amount,
+                    payment_id: observation.payment_id.clone(),
+                    kind,
+                }
+            })
+            .collect::<Vec<_>>();
+        self.unacknowledged.extend(events.iter().cloned());
+        events
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tari_common::configuration::Network;
+    use tari_common_types::types::FixedHash;
+
+    use super::*;
+
+    fn observation(payment_id: u64) -> DepositObservation {
+        DepositObservation {
+            tx_id: TxId::from(payment_id),
+            amount: MicroMinotari(1_000),
+            direction: TransactionDirection::Inbound,
+            destination_address: TariAddress::default(),
+            payment_id: Some(PaymentId::U64(payment_id)),
+            cancelled: false,
+            mined: None,
+            confirmations: 0,
+        }
+    }
+
+    fn mined(mut observation: DepositObservation, block: u8, confirmations: u64) -> DepositObservation {
+        observation.mined = Some((100, FixedHash::from([block; 32])));
+        observation.confirmations = confirmations;
+        observation
+    }
+
+    fn kinds(events: Vec<DepositEvent>) -> Vec<DepositEventKind> {
+        events.into_iter().map(|e| e.kind).collect()
+    }
+
+    #[test]
+    fn it_only_tracks_watched_incoming_transactions() {
+        let mut tracker = DepositTracker::default();
+        assert!(tracker.watch(DepositWatch::PaymentId(PaymentId::U64(1))));
+        assert!(!tracker.watch(DepositWatch::PaymentId(PaymentId::U64(1))));
+        let address = TariAddress::new_single_address_with_interactive_only(Default::default(), Network::LocalNet);
+        assert!(tracker.watch(DepositWatch::Address(address.clone())));
+
+        assert!(tracker.update(&observation(2), 3).is_empty());
+        let mut outgoing = observation(1);
+        outgoing.direction = TransactionDirection::Outbound;
+        assert!(tracker.update(&outgoing, 3).is_empty());
+
+        assert_eq!(kinds(tracker.update(&observation(1), 3)), vec![DepositEventKind::Seen]);
+        assert!(tracker.update(&observation(1), 3).is_empty());
+        let mut to_address = observation(3);
+        to_address.destination_address = address;
+        assert_eq!(kinds(tracker.update(&to_address, 3)), vec![DepositEventKind::Seen]);
+        assert_eq!(tracker.tracked_tx_ids().len(), 2);
+    }
+
+    #[test]
+    fn it_emits_each_lifecycle_event_once() {
+        let mut tracker = DepositTracker::default();
+        tracker.watch(DepositWatch::PaymentId(PaymentId::U64(1)));
+        let block = FixedHash::from([1u8; 32]);
+
+        assert_eq!(kinds(tracker.update(&mined(observation(1), 1, 1), 3)), vec![
+            DepositEventKind::Seen,
+            DepositEventKind::Confirmed {
+                confirmations: 1,
+                mined_height: 100,
+                mined_in_block: block
+            }
+        ]);
+        assert!(tracker.update(&mined(observation(1), 1, 1), 3).is_empty());
+        assert_eq!(kinds(tracker.update(&mined(observation(1), 1, 4), 3)), vec![
+            DepositEventKind::Finalised {
+                confirmations: 4,
+                mined_height: 100,
+                mined_in_block: block
+            }
+        ]);
+        assert!(tracker.update(&mined(observation(1), 1, 5), 3).is_empty());
+        assert!(!tracker.is_tracking(TxId::from(1u64)));
+
+        let sequences = tracker
+            .unacknowledged_events()
+            .iter()
+            .map(|e| e.sequence)
+            .collect::<Vec<_>>();
+        assert_eq!(sequences, vec![0, 1, 2]);
+        assert_eq!(tracker.acknowledge(1), 2);
+        assert_eq!(tracker.unacknowledged_events()[0].sequence, 2);
+    }
+
+    #[test]
+    fn it_detects_reorgs() {
+        let mut tracker = DepositTracker::default();
+        tracker.watch(DepositWatch::PaymentId(PaymentId::U64(1)));
+        tracker.update(&mined(observation(1), 1, 2), 3);
+
+        // Mined in another block
+        assert_eq!(kinds(tracker.update(&mined(observation(1), 2, 1), 3)), vec![
+            DepositEventKind::ReorgedOut {
+                mined_in_block: FixedHash::from([1u8; 32])
+            },
+            DepositEventKind::Confirmed {
+                confirmations: 1,
+                mined_height: 100,
+                mined_in_block: FixedHash::from([2u8; 32])
+            }
+        ]);
+
+        // No longer mined, and then cancelled
+        assert_eq!(kinds(tracker.update(&observation(1), 3)), vec![
+            DepositEventKind::ReorgedOut {
+                mined_in_block: FixedHash::from([2u8; 32])
+            }
+        ]);
+        let mut cancelled = observation(1);
+        cancelled.cancelled = true;
+        assert_eq!(kinds(tracker.update(&cancelled, 3)), vec![DepositEventKind::Cancelled]);
+        assert!(tracker.update(&mined(observation(1), 3, 5), 3).is_empty());
+    }
+
+    #[test]
+    fn it_survives_a_restart() {
+        let mut tracker = DepositTracker::default();
+        tracker.watch(DepositWatch::PaymentId(PaymentId::U64(1)));
+        tracker.update(&mined(observation(1), 1, 1), 3);
+
+        let mut restored: DepositTracker = serde_json::from_str(&serde_json::to_string(&tracker).unwrap()).unwrap();
+        assert_eq!(restored.unacknowledged_events(), tracker.unacknowledged_events());
+        assert!(restored.update(&mined(observation(1), 1, 1), 3).is_empty());
+        let events = restored.update(&mined(observation(1), 1, 2), 3);
+        assert_eq!(events[0].sequence, 2);
+    }
+}
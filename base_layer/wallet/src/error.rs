@@ -43,6 +43,7 @@ use crate::{
     base_node_service::error::BaseNodeServiceError,
     connectivity_service::WalletConnectivityError,
     output_manager_service::error::OutputManagerError,
+    push_notification_service::error::PushNotificationError,
     storage::database::DbKey,
     transaction_service::error::TransactionServiceError,
     utxo_scanner_service::error::UtxoScannerError,
@@ -106,6 +107,8 @@ pub enum WalletError {
     PublicAddressNotSet,
     #[error("Wallet connectivity error: `{0}`")]
     WalletConnectivityError(#[from] WalletConnectivityError),
+    #[error("Push notification error: `{0}`")]
+    PushNotificationError(#[from] PushNotificationError),
 }
 
 pub const LOG_TARGET: &str = "minotari::application";
@@ -186,6 +189,8 @@ pub enum WalletStorageError {
     RecoverySeedError(String),
     #[error("Bad encryption version: `{0}`")]
     BadEncryptionVersion(String),
+    #[error("Invalid application data: {0}")]
+    InvalidAppData(String),
 }
 
 impl From<HexError> for WalletStorageError {
@@ -11,10 +11,16 @@
 mod macros;
 pub mod base_node_service;
 pub mod connectivity_service;
+pub mod deposit_tracking_service;
 pub mod error;
+pub mod light_wallet;
+pub mod metadata_sync;
 mod operation_id;
 pub mod output_manager_service;
+pub mod push_notification_service;
+pub mod reorg_monitor_service;
 pub mod storage;
+pub mod sweep_service;
 pub mod test_utils;
 pub mod transaction_service;
 
@@ -0,0 +1,43 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Client side of the light wallet server protocol.
+//!
+//! A light wallet syncs from a base node running in light wallet server mode without handing it any keys. It either
+//! downloads the compact output filter of each block and only looks closer at blocks that match its known outputs
+//! and scripts, or downloads the compact outputs of a range of blocks and trial-decrypts them locally with the view
+//! key. Full outputs are only fetched for matches. The transport is abstracted by [`LightWalletSource`] so the same
+//! scanner can be driven over gRPC or any other channel.
+//!
+//! A wallet that is not running can still be woken up: it registers a [`PushWatch`] with the server, which posts the
+//! wallet's pre-encrypted notification to a push relay whenever a new block matches the watched filter items. Only
+//! spends of known outputs and payments to known scripts are matched, so stealth one-sided and interactive receives
+//! to the wallet do not trigger a notification.
+
+mod error;
+pub use error::LightWalletError;
+
+mod scanner;
+pub use scanner::{LightWalletScanResult, LightWalletScanner, RecoveredLightWalletOutput, SpentLightWalletOutput};
+
+mod source;
+pub use source::{CompactBlockOutputs, LightWalletSource, PushWatch};
@@ -0,0 +1,435 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::collections::HashSet;
+
+use log::*;
+use tari_common_types::types::{CommitmentFactory, FixedHash, PrivateKey};
+use tari_core::transactions::{
+    tari_amount::MicroMinotari,
+    transaction_components::{encrypted_data::PaymentId, CompactOutput, EncryptedData, TransactionOutput},
+};
+use tari_crypto::commitment::HomomorphicCommitmentFactory;
+use tari_script::TariScript;
+use tari_utilities::ByteArray;
+
+use crate::light_wallet::{LightWalletError, LightWalletSource, PushWatch};
+
+const LOG_TARGET: &str = "wallet::light_wallet::scanner";
+
+/// The number of blocks requested from the server in a single call
+const DEFAULT_BATCH_SIZE: u64 = 100;
+
+/// An output that was recognised by trial decryption with the view key
+#[derive(Debug, Clone)]
+pub struct RecoveredLightWalletOutput {
+    pub height: u64,
+    pub value: MicroMinotari,
+    pub payment_id: PaymentId,
+    pub output: TransactionOutput,
+}
+
+/// A known output that was spent in the block at `height`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpentLightWalletOutput {
+    pub height: u64,
+    pub output_hash: FixedHash,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LightWalletScanResult {
+    /// The height up to which (inclusive) the chain was scanned
+    pub scanned_to_height: u64,
+    pub recovered: Vec<RecoveredLightWalletOutput>,
+    pub spent: Vec<SpentLightWalletOutput>,
+}
+
+/// Scans a light wallet server for outputs belonging to the wallet. The server only ever sees height ranges and the
+/// hashes of outputs that matched, never the view key.
+pub struct LightWalletScanner<S> {
+    source: S,
+    view_key: PrivateKey,
+    known_outputs: HashSet<FixedHash>,
+    known_scripts: HashSet<Vec<u8>>,
+    batch_size: u64,
+}
+
+impl<S: LightWalletSource> LightWalletScanner<S> {
+    pub fn new(source: S, view_key: PrivateKey) -> Self {
+        Self {
+            source,
+            view_key,
+            known_outputs: HashSet::new(),
+            known_scripts: HashSet::new(),
+            batch_size: DEFAULT_BATCH_SIZE,
+        }
+    }
+
+    /// Outputs the wallet already owns, so that spends of them can be detected
+    pub fn with_known_outputs<I: IntoIterator<Item = FixedHash>>(mut self, output_hashes: I) -> Self {
+        self.known_outputs.extend(output_hashes);
+        self
+    }
+
+    /// Scripts the wallet expects to be paid to, e.g. its one-sided payment scripts
+    pub fn with_known_scripts<'a, I: IntoIterator<Item = &'a TariScript>>(mut self, scripts: I) -> Self {
+        self.known_scripts.extend(scripts.into_iter().map(|s| s.to_bytes()));
+        self
+    }
+
+    pub fn with_batch_size(mut self, batch_size: u64) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    pub fn known_outputs(&self) -> &HashSet<FixedHash> {
+        &self.known_outputs
+    }
+
+    /// The block filter items of the known outputs and scripts
+    pub fn filter_items(&self) -> Vec<Vec<u8>> {
+        self.known_outputs
+            .iter()
+            .map(|hash| hash.to_vec())
+            .chain(self.known_scripts.iter().cloned())
+            .collect()
+    }
+
+    /// Registers a push watch for the known outputs and scripts
+    pub async fn register_push_watch(
+        &mut self,
+        relay_url: String,
+        device_token: String,
+        payload: String,
+    ) -> Result<(), LightWalletError> {
+        let watch = PushWatch {
+            relay_url,
+            device_token,
+            payload,
+            items: self.filter_items(),
+        };
+        self.source.register_push_watch(watch).await
+    }
+
+    pub fn into_source(self) -> S {
+        self.source
+    }
+
+    /// Returns the heights in `start_height..=end_height` whose block filter matches one of the known outputs or
+    /// scripts. Filters have false positives but no false negatives, so blocks that are not returned are guaranteed
+    /// not to spend a known output or pay to a known script.
+    pub async fn find_candidate_heights(
+        &mut self,
+        start_height: u64,
+        end_height: u64,
+    ) -> Result<Vec<u64>, LightWalletError> {
+        check_range(start_height, end_height)?;
+        let mut candidates = Vec::new();
+        let items = self.filter_items();
+        if items.is_empty() {
+            return Ok(candidates);
+        }
+        let mut start = start_height;
+        while start <= end_height {
+            let end = self.batch_end(start, end_height);
+            let filters = self.source.get_block_filters(start, end).await?;
+            candidates.extend(
+                filters
+                    .into_iter()
+                    .filter(|(_, filter)| filter.matches_any(items.iter()))
+                    .map(|(height, _)| height),
+            );
+            start = end + 1;
+        }
+        Ok(candidates)
+    }
+
+    /// Scans `start_height..=end_height` by trial-decrypting every compact output with the view key. Outputs that
+    /// decrypt to a value and mask that open their commitment are fetched in full and added to the known outputs, so
+    /// a spend of them later in the same scan is also reported.
+    pub async fn scan(
+        &mut self,
+        start_height: u64,
+        end_height: u64,
+    ) -> Result<LightWalletScanResult, LightWalletError> {
+        check_range(start_height, end_height)?;
+        let mut result = LightWalletScanResult {
+            scanned_to_height: start_height.saturating_sub(1),
+            ..Default::default()
+        };
+        let mut start = start_height;
+        while start <= end_height {
+            let end = self.batch_end(start, end_height);
+            let blocks = self.source.get_compact_block_outputs(start, end).await?;
+            let mut matches = Vec::new();
+            for block in blocks {
+                if block.height < start || block.height > end {
+                    return Err(LightWalletError::InvalidResponse(format!(
+                        "Block at height {} is outside the requested range {}..={}",
+                        block.height, start, end
+                    )));
+                }
+                for hash in block.spent_output_hashes {
+                    if self.known_outputs.remove(&hash) {
+                        result.spent.push(SpentLightWalletOutput {
+                            height: block.height,
+                            output_hash: hash,
+                        });
+                    }
+                }
+                for output in &block.outputs {
+                    if let Some((value, payment_id)) = self.try_decrypt(output) {
+                        self.known_outputs.insert(output.output_hash);
+                        matches.push((block.height, value, payment_id, output.output_hash));
+                    }
+                }
+            }
+            if !matches.is_empty() {
+                result.recovered.extend(self.fetch_matches(matches).await?);
+            }
+            result.scanned_to_height = end;
+            start = end + 1;
+        }
+        debug!(
+            target: LOG_TARGET,
+            "Light wallet scan of {}..={} recovered {} output(s) and found {} spend(s)",
+            start_height,
+            end_height,
+            result.recovered.len(),
+            result.spent.len()
+        );
+        Ok(result)
+    }
+
+    fn try_decrypt(&self, output: &CompactOutput) -> Option<(MicroMinotari, PaymentId)> {
+        let (value, mask, payment_id) = EncryptedData::decrypt_output_data(
+            &self.view_key,
+            &output.commitment,
+            &output.encrypted_data,
+            output.version,
+        )
+        .ok()?;
+        // Decryption can succeed by chance, the commitment is what proves the output is ours
+        if CommitmentFactory::default().open_value(&mask, value.as_u64(), &output.commitment) {
+            Some((value, payment_id))
+        } else {
+            None
+        }
+    }
+
+    async fn fetch_matches(
+        &mut self,
+        matches: Vec<(u64, MicroMinotari, PaymentId, FixedHash)>,
+    ) -> Result<Vec<RecoveredLightWalletOutput>, LightWalletError> {
+        let hashes = matches.iter().map(|(_, _, _, hash)| *hash).collect();
+        let mut outputs = self.source.fetch_outputs(hashes).await?;
+        let mut recovered = Vec::with_capacity(matches.len());
+        for (height, value, payment_id, hash) in matches {
+            // An output that is already spent may have been pruned, in which case only the spend is of interest
+            let Some(pos) = outputs.iter().position(|o| o.hash() == hash) else {
+                warn!(
+                    target: LOG_TARGET,
+                    "Light wallet server did not return matched output {} at height {}", hash, height
+                );
+                continue;
+            };
+            recovered.push(RecoveredLightWalletOutput {
+                height,
+                value,
+                payment_id,
+                output: outputs.swap_remove(pos),
+            });
+        }
+        Ok(recovered)
+    }
+
+    fn batch_end(&self, start: u64, end_height: u64) -> u64 {
+        start.saturating_add(self.batch_size - 1).min(end_height)
+    }
+}
+
+fn check_range(start: u64, end: u64) -> Result<(), LightWalletError> {
+    if start > end {
+        return Err(LightWalletError::InvalidHeightRange { start, end });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use async_trait::async_trait;
+    use tari_core::blocks::BlockOutputFilter;
+
+    use super::*;
+    use crate::light_wallet::CompactBlockOutputs;
+
+    struct MockSource {
+        filters: Vec<(u64, BlockOutputFilter)>,
+        blocks: Vec<CompactBlockOutputs>,
+        filter_calls: usize,
+        push_watches: Vec<PushWatch>,
+    }
+
+    #[async_trait]
+    impl LightWalletSource for MockSource {
+        async fn get_tip_height(&mut self) -> Result<u64, LightWalletError> {
+            Ok(self.blocks.last().map(|b| b.height).unwrap_or_default())
+        }
+
+        async fn get_block_filters(
+            &mut self,
+            start_height: u64,
+            end_height: u64,
+        ) -> Result<Vec<(u64, BlockOutputFilter)>, LightWalletError> {
+            self.filter_calls += 1;
+            Ok(self
+                .filters
+                .iter()
+                .filter(|(h, _)| (start_height..=end_height).contains(h))
+                .cloned()
+                .collect())
+        }
+
+        async fn get_compact_block_outputs(
+            &mut self,
+            start_height: u64,
+            end_height: u64,
+        ) -> Result<Vec<CompactBlockOutputs>, LightWalletError> {
+            Ok(self
+                .blocks
+                .iter()
+                .filter(|b| (start_height..=end_height).contains(&b.height))
+                .cloned()
+                .collect())
+        }
+
+        async fn fetch_outputs(&mut self, _: Vec<FixedHash>) -> Result<Vec<TransactionOutput>, LightWalletError> {
+            Ok(vec![])
+        }
+
+        async fn register_push_watch(&mut self, watch: PushWatch) -> Result<(), LightWalletError> {
+            self.push_watches.retain(|w| w.device_token != watch.device_token);
+            self.push_watches.push(watch);
+            Ok(())
+        }
+
+        async fn unregister_push_watch(&mut self, device_token: String) -> Result<(), LightWalletError> {
+            self.push_watches.retain(|w| w.device_token != device_token);
+            Ok(())
+        }
+    }
+
+    fn block(height: u64, spent: Vec<FixedHash>) -> CompactBlockOutputs {
+        CompactBlockOutputs {
+            height,
+            block_hash: FixedHash::from([u8::try_from(height).unwrap(); 32]),
+            timestamp: 0,
+            outputs: vec![],
+            spent_output_hashes: spent,
+        }
+    }
+
+    #[tokio::test]
+    async fn it_finds_candidate_heights_from_filters() {
+        let known = FixedHash::from([1u8; 32]);
+        let filters = (1..=10)
+            .map(|h: u8| {
+                let item = if h == 7 { known.to_vec() } else { vec![h; 32] };
+                (u64::from(h), BlockOutputFilter::new(FixedHash::from([h; 32]), [item]))
+            })
+            .collect();
+        let source = MockSource {
+            filters,
+            blocks: vec![],
+            filter_calls: 0,
+            push_watches: vec![],
+        };
+        let mut scanner = LightWalletScanner::new(source, PrivateKey::default())
+            .with_known_outputs([known])
+            .with_batch_size(3);
+        let heights = scanner.find_candidate_heights(1, 10).await.unwrap();
+        assert_eq!(heights, vec![7]);
+        assert_eq!(scanner.into_source().filter_calls, 4);
+    }
+
+    #[tokio::test]
+    async fn it_reports_spends_of_known_outputs() {
+        let known = FixedHash::from([1u8; 32]);
+        let other = FixedHash::from([2u8; 32]);
+        let source = MockSource {
+            filters: vec![],
+            blocks: vec![block(1, vec![other]), block(2, vec![known]), block(3, vec![known])],
+            filter_calls: 0,
+            push_watches: vec![],
+        };
+        let mut scanner = LightWalletScanner::new(source, PrivateKey::default()).with_known_outputs([known]);
+        let result = scanner.scan(1, 3).await.unwrap();
+        assert_eq!(result.scanned_to_height, 3);
+        assert!(result.recovered.is_empty());
+        assert_eq!(result.spent, vec![SpentLightWalletOutput {
+            height: 2,
+            output_hash: known
+        }]);
+        assert!(scanner.known_outputs().is_empty());
+        assert!(matches!(
+            scanner.scan(3, 1).await,
+            Err(LightWalletError::InvalidHeightRange { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn it_registers_push_watches_for_known_items() {
+        let known = FixedHash::from([1u8; 32]);
+        let script = TariScript::default();
+        let source = MockSource {
+            filters: vec![],
+            blocks: vec![],
+            filter_calls: 0,
+            push_watches: vec![],
+        };
+        let mut scanner = LightWalletScanner::new(source, PrivateKey::default())
+            .with_known_outputs([known])
+            .with_known_scripts([&script]);
+        scanner
+            .register_push_watch(
+                "http://127.0.0.1:8080".to_string(),
+                "device".to_string(),
+                "00".to_string(),
+            )
+            .await
+            .unwrap();
+        scanner
+            .register_push_watch(
+                "http://127.0.0.1:8080".to_string(),
+                "device".to_string(),
+                "01".to_string(),
+            )
+            .await
+            .unwrap();
+        let watches = scanner.into_source().push_watches;
+        assert_eq!(watches.len(), 1);
+        assert_eq!(watches[0].payload, "01");
+        assert_eq!(watches[0].items.len(), 2);
+        assert!(watches[0].items.contains(&known.to_vec()));
+        assert!(watches[0].items.contains(&script.to_bytes()));
+    }
+}
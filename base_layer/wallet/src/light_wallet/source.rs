@@ -0,0 +1,82 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use async_trait::async_trait;
+use tari_common_types::types::FixedHash;
+use tari_core::{
+    blocks::BlockOutputFilter,
+    transactions::transaction_components::{CompactOutput, TransactionOutput},
+};
+
+use crate::light_wallet::LightWalletError;
+
+/// The compact outputs of a block, and the hashes of the outputs it spends
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompactBlockOutputs {
+    pub height: u64,
+    pub block_hash: FixedHash,
+    pub timestamp: u64,
+    pub outputs: Vec<CompactOutput>,
+    pub spent_output_hashes: Vec<FixedHash>,
+}
+
+/// Asks a light wallet server to post `payload` to the push notification relay at `relay_url` whenever a new block
+/// contains one of `items`, so that a backgrounded app can be woken up while the wallet is not running. The payload is
+/// encrypted by the wallet, so the server and the relay only learn that something happened, not what.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PushWatch {
+    pub relay_url: String,
+    pub device_token: String,
+    /// The hex encoded encrypted notification
+    pub payload: String,
+    /// Block filter items, i.e. the hashes of outputs the wallet owns and the scripts it expects to be paid to
+    pub items: Vec<Vec<u8>>,
+}
+
+/// A light wallet server. Height ranges are inclusive and results are returned in ascending height order.
+#[async_trait]
+pub trait LightWalletSource: Send {
+    async fn get_tip_height(&mut self) -> Result<u64, LightWalletError>;
+
+    async fn get_block_filters(
+        &mut self,
+        start_height: u64,
+        end_height: u64,
+    ) -> Result<Vec<(u64, BlockOutputFilter)>, LightWalletError>;
+
+    async fn get_compact_block_outputs(
+        &mut self,
+        start_height: u64,
+        end_height: u64,
+    ) -> Result<Vec<CompactBlockOutputs>, LightWalletError>;
+
+    /// Fetches full outputs by output hash. Outputs that are not found (e.g. already spent and pruned) are omitted.
+    async fn fetch_outputs(
+        &mut self,
+        output_hashes: Vec<FixedHash>,
+    ) -> Result<Vec<TransactionOutput>, LightWalletError>;
+
+    /// Registers a push watch, replacing any previous watch for the same device token
+    async fn register_push_watch(&mut self, watch: PushWatch) -> Result<(), LightWalletError>;
+
+    async fn unregister_push_watch(&mut self, device_token: String) -> Result<(), LightWalletError>;
+}
@@ -0,0 +1,33 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tari_common::configuration::serializers;
+use tari_common_types::backup_config::{S3Config, WebDavConfig};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MetadataSyncConfig {
+    /// Sync the wallet metadata with the configured store
+    pub enabled: bool,
+    /// The time between syncs
+    #[serde(with = "serializers::seconds")]
+    pub interval: Duration,
+    /// Sync through an S3-compatible object store
+    pub s3: Option<S3Config>,
+    /// Sync through a WebDAV server
+    pub webdav: Option<WebDavConfig>,
+}
+
+impl Default for MetadataSyncConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval: Duration::from_secs(5 * 60),
+            s3: None,
+            webdav: None,
+        }
+    }
+}
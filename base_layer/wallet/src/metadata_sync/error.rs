@@ -0,0 +1,23 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use tari_contacts::contacts_service::error::ContactsServiceError;
+use thiserror::Error;
+
+use crate::error::WalletStorageError;
+
+#[derive(Debug, Error)]
+pub enum MetadataSyncError {
+    #[error("The wallet has no seed to derive the metadata sync key from")]
+    NoSeed,
+    #[error("Metadata sync encryption error: {0}")]
+    EncryptionError(String),
+    #[error("Metadata sync serialization error: {0}")]
+    SerializationError(#[from] serde_json::Error),
+    #[error("Invalid synced metadata: {0}")]
+    InvalidMetadata(String),
+    #[error("Wallet storage error: `{0}`")]
+    WalletStorageError(#[from] WalletStorageError),
+    #[error("Contacts service error: `{0}`")]
+    ContactsServiceError(#[from] ContactsServiceError),
+}
@@ -0,0 +1,234 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::collections::BTreeMap;
+
+use blake2::Blake2b;
+use chacha20poly1305::{Key, KeyInit, XChaCha20Poly1305};
+use digest::consts::U32;
+use serde::{Deserialize, Serialize};
+use tari_common_types::encryption::{decrypt_bytes_integral_nonce, encrypt_bytes_integral_nonce};
+use tari_crypto::{hash_domain, hashing::DomainSeparatedHasher};
+use tari_key_manager::cipher_seed::CipherSeed;
+use tari_utilities::{hex::Hex, Hidden};
+
+use crate::{metadata_sync::error::MetadataSyncError, transaction_service::drafts::DraftRecipient};
+
+hash_domain!(MetadataSyncKeyDomain, "com.tari.base_layer.wallet.metadata_sync", 0);
+
+const METADATA_SYNC_ENCRYPTION_DOMAIN: &[u8] = b"com.tari.base_layer.wallet.metadata_sync.encryption";
+/// The file extension of the encrypted metadata in the remote store
+pub const METADATA_BLOB_EXTENSION: &str = "tsync";
+
+/// The key that the synced metadata is encrypted with, and the name it is stored under. Both are derived from the
+/// wallet seed, so every wallet restored from the same seed finds and decrypts the same metadata, while the name
+/// reveals nothing about the wallet to the store.
+pub struct MetadataSyncKey {
+    encryption_key: Hidden<[u8; 32]>,
+    blob_name: String,
+}
+
+impl MetadataSyncKey {
+    pub fn from_seed(seed: &CipherSeed) -> Self {
+        let blob_id = hash_seed(seed, "blob_name");
+        Self {
+            encryption_key: Hidden::hide(hash_seed(seed, "encryption_key")),
+            blob_name: format!("wallet-metadata-{}.{}", blob_id.to_hex(), METADATA_BLOB_EXTENSION),
+        }
+    }
+
+    pub fn blob_name(&self) -> &str {
+        &self.blob_name
+    }
+
+    fn cipher(&self) -> XChaCha20Poly1305 {
+        XChaCha20Poly1305::new(Key::from_slice(self.encryption_key.reveal()))
+    }
+}
+
+fn hash_seed(seed: &CipherSeed, label: &'static str) -> [u8; 32] {
+    let hasher =
+        DomainSeparatedHasher::<Blake2b<U32>, MetadataSyncKeyDomain>::new_with_label(label).chain(seed.entropy());
+    digest::Digest::finalize(hasher).into()
+}
+
+/// A contact as it is synced. Liveness data is particular to each device and is not synced.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncedContact {
+    pub alias: String,
+    pub favourite: bool,
+}
+
+/// A transaction draft as it is synced. Each device keeps its own timestamps.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncedDraft {
+    pub notes: String,
+    pub recipients: Vec<DraftRecipient>,
+}
+
+/// The wallet metadata that is synced between devices. Keys and outputs are never part of it, since they are
+/// recovered from the seed and the chain.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WalletMetadata {
+    /// Contacts by base58 address
+    #[serde(default)]
+    pub contacts: BTreeMap<String, SyncedContact>,
+    /// Transaction drafts by name
+    #[serde(default)]
+    pub drafts: BTreeMap<String, SyncedDraft>,
+    /// Application values, such as the labels and notes that applications keep, by `<namespace>/<key>`
+    #[serde(default)]
+    pub app_values: BTreeMap<String, String>,
+}
+
+impl WalletMetadata {
+    /// Merges the metadata of this device with the metadata in the store. `base` is the metadata as it was after the
+    /// last sync of this device. An entry that this device has not changed since then takes the stored value, which
+    /// carries the additions, edits and removals of the other devices. An entry that this device has changed keeps
+    /// its local value, also when another device changed it too.
+    pub fn merge(base: &Self, local: &Self, remote: &Self) -> Self {
+        Self {
+            contacts: merge_entries(&base.contacts, &local.contacts, &remote.contacts),
+            drafts: merge_entries(&base.drafts, &local.drafts, &remote.drafts),
+            app_values: merge_entries(&base.app_values, &local.app_values, &remote.app_values),
+        }
+    }
+
+    /// Encrypts the metadata. The result is the nonce followed by the ciphertext and tag.
+    pub fn encrypt(&self, key: &MetadataSyncKey) -> Result<Vec<u8>, MetadataSyncError> {
+        let plaintext = Hidden::hide(serde_json::to_vec(self)?);
+        encrypt_bytes_integral_nonce(&key.cipher(), METADATA_SYNC_ENCRYPTION_DOMAIN.to_vec(), plaintext)
+            .map_err(MetadataSyncError::EncryptionError)
+    }
+
+    pub fn decrypt(key: &MetadataSyncKey, ciphertext: &[u8]) -> Result<Self, MetadataSyncError> {
+        let plaintext =
+            decrypt_bytes_integral_nonce(&key.cipher(), METADATA_SYNC_ENCRYPTION_DOMAIN.to_vec(), ciphertext)
+                .map_err(MetadataSyncError::EncryptionError)?;
+        Ok(serde_json::from_slice(&plaintext)?)
+    }
+}
+
+fn merge_entries<V: Clone + PartialEq>(
+    base: &BTreeMap<String, V>,
+    local: &BTreeMap<String, V>,
+    remote: &BTreeMap<String, V>,
+) -> BTreeMap<String, V> {
+    local
+        .keys()
+        .chain(remote.keys())
+        .filter_map(|key| {
+            let local_value = local.get(key);
+            let value = if local_value == base.get(key) {
+                remote.get(key)
+            } else {
+                local_value
+            };
+            value.map(|v| (key.clone(), v.clone()))
+        })
+        .collect()
+}
+
+/// Returns the entries that differ between `from` and `to`, with the value in `to`, or `None` if it was removed
+pub(super) fn changed_entries<'a, V: PartialEq>(
+    from: &'a BTreeMap<String, V>,
+    to: &'a BTreeMap<String, V>,
+) -> Vec<(&'a String, Option<&'a V>)> {
+    let mut changes = to
+        .iter()
+        .filter(|(key, value)| from.get(*key) != Some(*value))
+        .map(|(key, value)| (key, Some(value)))
+        .collect::<Vec<_>>();
+    changes.extend(from.keys().filter(|key| !to.contains_key(*key)).map(|key| (key, None)));
+    changes
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn values(entries: &[(&str, &str)]) -> BTreeMap<String, String> {
+        entries.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    fn metadata(app_values: &[(&str, &str)]) -> WalletMetadata {
+        WalletMetadata {
+            app_values: values(app_values),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn it_merges_changes_from_both_sides() {
+        let base = metadata(&[
+            ("a/kept", "1"),
+            ("a/edited", "1"),
+            ("a/removed", "1"),
+            ("a/edited_here", "1"),
+        ]);
+        let local = metadata(&[
+            ("a/kept", "1"),
+            ("a/edited", "1"),
+            ("a/removed", "1"),
+            ("a/edited_here", "2"),
+            ("a/new_here", "1"),
+        ]);
+        let remote = metadata(&[
+            ("a/kept", "1"),
+            ("a/edited", "2"),
+            ("a/edited_here", "3"),
+            ("a/new", "1"),
+        ]);
+        let merged = WalletMetadata::merge(&base, &local, &remote);
+        assert_eq!(
+            merged,
+            metadata(&[
+                ("a/kept", "1"),
+                ("a/edited", "2"),
+                ("a/edited_here", "2"),
+                ("a/new_here", "1"),
+                ("a/new", "1")
+            ])
+        );
+
+        // A fresh install takes everything, and removals on this device are kept
+        assert_eq!(
+            WalletMetadata::merge(&WalletMetadata::default(), &WalletMetadata::default(), &remote),
+            remote
+        );
+        assert_eq!(
+            WalletMetadata::merge(&remote, &metadata(&[("a/kept", "1")]), &remote),
+            metadata(&[("a/kept", "1")])
+        );
+    }
+
+    #[test]
+    fn it_lists_changed_entries() {
+        let from = values(&[("a", "1"), ("b", "1"), ("c", "1")]);
+        let to = values(&[("a", "1"), ("b", "2"), ("d", "1")]);
+        let b = "b".to_string();
+        let c = "c".to_string();
+        let d = "d".to_string();
+        let two = "2".to_string();
+        let one = "1".to_string();
+        assert_eq!(changed_entries(&from, &to), vec![
+            (&b, Some(&two)),
+            (&d, Some(&one)),
+            (&c, None)
+        ]);
+    }
+
+    #[test]
+    fn it_derives_the_key_from_the_seed() {
+        let seed = CipherSeed::new();
+        let key = MetadataSyncKey::from_seed(&seed);
+        assert_eq!(key.blob_name(), MetadataSyncKey::from_seed(&seed).blob_name());
+        let other_key = MetadataSyncKey::from_seed(&CipherSeed::new());
+        assert_ne!(key.blob_name(), other_key.blob_name());
+
+        let metadata = metadata(&[("labels/tx_1", "Rent")]);
+        let ciphertext = metadata.encrypt(&key).unwrap();
+        assert_eq!(WalletMetadata::decrypt(&key, &ciphertext).unwrap(), metadata);
+        assert!(WalletMetadata::decrypt(&other_key, &ciphertext).is_err());
+    }
+}
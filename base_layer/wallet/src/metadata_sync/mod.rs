@@ -0,0 +1,211 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Syncing of wallet metadata between the devices that run the same wallet. Contacts, transaction drafts and
+//! application values, such as labels and notes, are encrypted with a key derived from the wallet seed and kept in a
+//! store that the user chooses, so that a reinstalled or secondary wallet gets the metadata that keys and outputs
+//! recovered from the seed lack. The store only ever holds the encrypted metadata.
+//!
+//! Each device merges the stored metadata with its own, applies the result locally and stores it again if it changed
+//! (see [WalletMetadata::merge]). This module only produces and consumes the encrypted metadata; reading and writing
+//! the store is up to the application.
+
+pub mod config;
+pub mod error;
+mod metadata;
+
+use std::collections::HashMap;
+
+use chrono::Utc;
+use log::*;
+pub use metadata::{MetadataSyncKey, SyncedContact, SyncedDraft, WalletMetadata, METADATA_BLOB_EXTENSION};
+use tari_common_types::tari_address::TariAddress;
+use tari_contacts::contacts_service::{handle::ContactsServiceHandle, types::Contact};
+
+use crate::{
+    metadata_sync::{error::MetadataSyncError, metadata::changed_entries},
+    storage::database::{WalletBackend, WalletDatabase},
+    transaction_service::drafts::TransactionDraft,
+};
+
+const LOG_TARGET: &str = "wallet::metadata_sync";
+
+/// The client key value under which the metadata as of the last completed sync is kept
+pub const METADATA_SYNC_BASE_KEY: &str = "metadata_sync_base";
+
+/// The result of merging the stored metadata into the wallet
+pub struct MetadataSyncOutcome {
+    /// The number of contacts, drafts and application values that were added, changed or removed in this wallet
+    pub num_applied: usize,
+    /// The encrypted metadata to write to the store, if the stored metadata is out of date
+    pub upload: Option<Vec<u8>>,
+    merged: WalletMetadata,
+}
+
+pub struct MetadataSync<T> {
+    db: WalletDatabase<T>,
+    contacts: ContactsServiceHandle,
+    key: MetadataSyncKey,
+}
+
+impl<T> MetadataSync<T>
+where T: WalletBackend + 'static
+{
+    /// Fails if the wallet has no seed, e.g. if its keys are kept on a hardware device
+    pub fn new(db: WalletDatabase<T>, contacts: ContactsServiceHandle) -> Result<Self, MetadataSyncError> {
+        let seed = db.get_master_seed()?.ok_or(MetadataSyncError::NoSeed)?;
+        Ok(Self {
+            key: MetadataSyncKey::from_seed(&seed),
+            db,
+            contacts,
+        })
+    }
+
+    /// The name the encrypted metadata is stored under
+    pub fn blob_name(&self) -> &str {
+        self.key.blob_name()
+    }
+
+    /// Merges the stored metadata, if there is any, into the wallet. The outcome must be passed to
+    /// [MetadataSync::complete] once its upload has been written to the store.
+    pub async fn merge_stored(&mut self, stored: Option<&[u8]>) -> Result<MetadataSyncOutcome, MetadataSyncError> {
+        let stored = stored
+            .map(|data| WalletMetadata::decrypt(&self.key, data))
+            .transpose()?;
+        let local = self.collect().await?;
+        let merged = match &stored {
+            Some(stored) => WalletMetadata::merge(&self.load_base()?, &local, stored),
+            None => local.clone(),
+        };
+        let num_applied = self.apply(&local, &merged).await?;
+        let upload = if stored.as_ref() == Some(&merged) {
+            None
+        } else {
+            Some(merged.encrypt(&self.key)?)
+        };
+        Ok(MetadataSyncOutcome {
+            num_applied,
+            upload,
+            merged,
+        })
+    }
+
+    /// Records the merged metadata as the base of the next merge. Until this is called, changes made on this device
+    /// are not considered synced, so they are not lost if the upload failed.
+    pub fn complete(&self, outcome: MetadataSyncOutcome) -> Result<(), MetadataSyncError> {
+        self.db.set_client_key_value(
+            METADATA_SYNC_BASE_KEY.to_string(),
+            serde_json::to_string(&outcome.merged)?,
+        )?;
+        Ok(())
+    }
+
+    fn load_base(&self) -> Result<WalletMetadata, MetadataSyncError> {
+        match self.db.get_client_key_value(METADATA_SYNC_BASE_KEY.to_string())? {
+            Some(base) => Ok(serde_json::from_str(&base)?),
+            None => Ok(WalletMetadata::default()),
+        }
+    }
+
+    async fn collect(&mut self) -> Result<WalletMetadata, MetadataSyncError> {
+        let contacts = self
+            .contacts
+            .get_contacts()
+            .await?
+            .into_iter()
+            .map(|contact| {
+                (contact.address.to_base58(), SyncedContact {
+                    alias: contact.alias,
+                    favourite: contact.favourite,
+                })
+            })
+            .collect();
+        let drafts = self
+            .db
+            .fetch_transaction_drafts()?
+            .into_iter()
+            .map(|draft| {
+                (draft.name, SyncedDraft {
+                    notes: draft.notes,
+                    recipients: draft.recipients,
+                })
+            })
+            .collect();
+        Ok(WalletMetadata {
+            contacts,
+            drafts,
+            app_values: self.db.get_all_app_values()?,
+        })
+    }
+
+    /// Makes the wallet metadata match `merged`, and returns the number of entries that were changed
+    async fn apply(&mut self, local: &WalletMetadata, merged: &WalletMetadata) -> Result<usize, MetadataSyncError> {
+        let contact_changes = changed_entries(&local.contacts, &merged.contacts);
+        let draft_changes = changed_entries(&local.drafts, &merged.drafts);
+        let app_value_changes = changed_entries(&local.app_values, &merged.app_values);
+        let num_applied = contact_changes.len() + draft_changes.len() + app_value_changes.len();
+
+        if !contact_changes.is_empty() {
+            let mut existing = self
+                .contacts
+                .get_contacts()
+                .await?
+                .into_iter()
+                .map(|contact| (contact.address.to_base58(), contact))
+                .collect::<HashMap<_, _>>();
+            for (address, change) in contact_changes {
+                let tari_address = TariAddress::from_base58(address)
+                    .map_err(|e| MetadataSyncError::InvalidMetadata(format!("Invalid contact address: {}", e)))?;
+                match change {
+                    Some(synced) => {
+                        let contact = match existing.remove(address) {
+                            Some(contact) => Contact {
+                                alias: synced.alias.clone(),
+                                favourite: synced.favourite,
+                                ..contact
+                            },
+                            None => Contact::new(synced.alias.clone(), tari_address, None, None, synced.favourite),
+                        };
+                        self.contacts.upsert_contact(contact).await?;
+                    },
+                    None => {
+                        self.contacts.remove_contact(tari_address).await?;
+                    },
+                }
+            }
+        }
+        for (name, change) in draft_changes {
+            match change {
+                Some(draft) => {
+                    // The timestamps are set by the database
+                    let now = Utc::now().naive_utc();
+                    self.db.save_transaction_draft(TransactionDraft {
+                        name: name.clone(),
+                        notes: draft.notes.clone(),
+                        recipients: draft.recipients.clone(),
+                        created_at: now,
+                        updated_at: now,
+                    })?
+                },
+                None => {
+                    self.db.delete_transaction_draft(name)?;
+                },
+            }
+        }
+        for (path, change) in app_value_changes {
+            let (namespace, key) = path
+                .split_once('/')
+                .ok_or_else(|| MetadataSyncError::InvalidMetadata(format!("Invalid application value key {}", path)))?;
+            match change {
+                Some(value) => self.db.set_app_value(namespace, key, value.clone())?,
+                None => {
+                    self.db.clear_app_value(namespace, key)?;
+                },
+            }
+        }
+        if num_applied > 0 {
+            debug!(target: LOG_TARGET, "Applied {} synced metadata change(s)", num_applied);
+        }
+        Ok(num_applied)
+    }
+}
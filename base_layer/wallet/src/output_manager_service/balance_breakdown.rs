@@ -0,0 +1,240 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Filtered balance queries. Outputs are narrowed down with an [OutputFilter] and their values are summed per
+//! [BalanceCategory], so that applications can show what is available, pending, time-locked and still maturing
+//! without approximating it from the totals of [Balance](crate::output_manager_service::service::Balance).
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use tari_core::transactions::{tari_amount::MicroMinotari, transaction_components::encrypted_data::PaymentId};
+
+use crate::output_manager_service::storage::{models::DbWalletOutput, OutputSource, OutputStatus};
+
+/// The statuses of the outputs that count towards a balance
+pub const BALANCE_OUTPUT_STATUSES: [OutputStatus; 7] = [
+    OutputStatus::Unspent,
+    OutputStatus::UnspentMinedUnconfirmed,
+    OutputStatus::EncumberedToBeReceived,
+    OutputStatus::ShortTermEncumberedToBeReceived,
+    OutputStatus::EncumberedToBeSpent,
+    OutputStatus::ShortTermEncumberedToBeSpent,
+    OutputStatus::SpentMinedUnconfirmed,
+];
+
+/// Restricts the outputs that a balance or listing is calculated over. The default filter matches every output.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutputFilter {
+    /// Leave out coinbase outputs that have not matured. Without a chain tip, no coinbase is known to be mature.
+    pub only_matured_coinbase: bool,
+    /// Only include outputs that were received in one-sided or stealth one-sided transactions
+    pub only_one_sided: bool,
+    /// Leave out outputs that cannot be spent at the chain tip because of their maturity or script lock height
+    pub exclude_time_locked: bool,
+    /// Only include outputs that were received with this payment ID, which is commonly used to tag deposits
+    pub payment_id: Option<PaymentId>,
+}
+
+impl OutputFilter {
+    pub fn matches(&self, output: &DbWalletOutput, tip: Option<u64>) -> bool {
+        let is_coinbase = output.source == OutputSource::Coinbase;
+        let is_locked = is_time_locked(
+            output.wallet_output.features.maturity,
+            output.wallet_output.script_lock_height,
+            tip,
+        );
+        if self.only_matured_coinbase && is_coinbase && (tip.is_none() || is_locked) {
+            return false;
+        }
+        if self.only_one_sided && !matches!(output.source, OutputSource::OneSided | OutputSource::StealthOneSided) {
+            return false;
+        }
+        if self.exclude_time_locked && is_locked {
+            return false;
+        }
+        match &self.payment_id {
+            Some(payment_id) => output.payment_id == *payment_id,
+            None => true,
+        }
+    }
+}
+
+fn is_time_locked(maturity: u64, script_lock_height: u64, tip: Option<u64>) -> bool {
+    tip.is_some_and(|tip| maturity > tip || script_lock_height > tip)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BalanceCategory {
+    /// Can be spent now
+    Available,
+    /// Confirmed, but cannot be spent until the maturity or script lock height is reached
+    TimeLocked,
+    /// A mined coinbase that has not reached its maturity
+    ImmatureCoinbase,
+    /// Due to be received, but not yet confirmed
+    PendingIncoming,
+    /// Encumbered in an outbound transaction that has not been confirmed
+    PendingOutgoing,
+}
+
+impl BalanceCategory {
+    /// The category that an output counts towards, if any. Time locks are only known if the chain tip is.
+    pub fn of(
+        status: OutputStatus,
+        source: OutputSource,
+        maturity: u64,
+        script_lock_height: u64,
+        tip: Option<u64>,
+    ) -> Option<Self> {
+        match status {
+            OutputStatus::Unspent if is_time_locked(maturity, script_lock_height, tip) => {
+                if source == OutputSource::Coinbase {
+                    Some(BalanceCategory::ImmatureCoinbase)
+                } else {
+                    Some(BalanceCategory::TimeLocked)
+                }
+            },
+            OutputStatus::Unspent => Some(BalanceCategory::Available),
+            // Coinbases are only expected once they are mined
+            OutputStatus::EncumberedToBeReceived if source == OutputSource::Coinbase => None,
+            OutputStatus::EncumberedToBeReceived |
+            OutputStatus::ShortTermEncumberedToBeReceived |
+            OutputStatus::UnspentMinedUnconfirmed => Some(BalanceCategory::PendingIncoming),
+            OutputStatus::EncumberedToBeSpent |
+            OutputStatus::ShortTermEncumberedToBeSpent |
+            OutputStatus::SpentMinedUnconfirmed => Some(BalanceCategory::PendingOutgoing),
+            OutputStatus::Spent | OutputStatus::Invalid | OutputStatus::CancelledInbound | OutputStatus::NotStored => {
+                None
+            },
+        }
+    }
+
+    pub fn of_output(output: &DbWalletOutput, tip: Option<u64>) -> Option<Self> {
+        Self::of(
+            output.status,
+            output.source,
+            output.wallet_output.features.maturity,
+            output.wallet_output.script_lock_height,
+            tip,
+        )
+    }
+}
+
+/// The balance of the outputs matching a filter, per category
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BalanceBreakdown {
+    pub available: MicroMinotari,
+    pub time_locked: MicroMinotari,
+    pub immature_coinbase: MicroMinotari,
+    pub pending_incoming: MicroMinotari,
+    pub pending_outgoing: MicroMinotari,
+    /// The chain tip that time locks were evaluated at, None if it was unknown and nothing is counted as time-locked
+    pub tip: Option<u64>,
+}
+
+impl BalanceBreakdown {
+    pub fn from_outputs<'a, I>(outputs: I, filter: &OutputFilter, tip: Option<u64>) -> Self
+    where I: IntoIterator<Item = &'a DbWalletOutput> {
+        let mut breakdown = Self {
+            tip,
+            ..Default::default()
+        };
+        for output in outputs {
+            if !filter.matches(output, tip) {
+                continue;
+            }
+            if let Some(category) = BalanceCategory::of_output(output, tip) {
+                breakdown.add(category, output.wallet_output.value);
+            }
+        }
+        breakdown
+    }
+
+    pub fn add(&mut self, category: BalanceCategory, value: MicroMinotari) {
+        let balance = match category {
+            BalanceCategory::Available => &mut self.available,
+            BalanceCategory::TimeLocked => &mut self.time_locked,
+            BalanceCategory::ImmatureCoinbase => &mut self.immature_coinbase,
+            BalanceCategory::PendingIncoming => &mut self.pending_incoming,
+            BalanceCategory::PendingOutgoing => &mut self.pending_outgoing,
+        };
+        *balance += value;
+    }
+
+    /// The funds that belong to the wallet once everything pending has confirmed and every lock has expired
+    pub fn total(&self) -> MicroMinotari {
+        self.available + self.time_locked + self.immature_coinbase + self.pending_incoming
+    }
+}
+
+impl fmt::Display for BalanceBreakdown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Available: {}", self.available)?;
+        writeln!(f, "Time locked: {}", self.time_locked)?;
+        writeln!(f, "Immature coinbase: {}", self.immature_coinbase)?;
+        writeln!(f, "Pending incoming: {}", self.pending_incoming)?;
+        writeln!(f, "Pending outgoing: {}", self.pending_outgoing)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_categorises_unspent_outputs_by_their_locks() {
+        let of = |source, maturity, lock_height, tip| {
+            BalanceCategory::of(OutputStatus::Unspent, source, maturity, lock_height, tip)
+        };
+        assert_eq!(
+            of(OutputSource::Standard, 0, 0, Some(10)),
+            Some(BalanceCategory::Available)
+        );
+        assert_eq!(
+            of(OutputSource::Standard, 0, 11, Some(10)),
+            Some(BalanceCategory::TimeLocked)
+        );
+        assert_eq!(
+            of(OutputSource::Coinbase, 11, 0, Some(10)),
+            Some(BalanceCategory::ImmatureCoinbase)
+        );
+        assert_eq!(
+            of(OutputSource::Coinbase, 10, 0, Some(10)),
+            Some(BalanceCategory::Available)
+        );
+        // Locks are unknown without a tip
+        assert_eq!(
+            of(OutputSource::Coinbase, 11, 0, None),
+            Some(BalanceCategory::Available)
+        );
+    }
+
+    #[test]
+    fn it_categorises_pending_outputs() {
+        let of = |status, source| BalanceCategory::of(status, source, 0, 0, Some(10));
+        assert_eq!(
+            of(OutputStatus::UnspentMinedUnconfirmed, OutputSource::OneSided),
+            Some(BalanceCategory::PendingIncoming)
+        );
+        assert_eq!(of(OutputStatus::EncumberedToBeReceived, OutputSource::Coinbase), None);
+        assert_eq!(
+            of(OutputStatus::ShortTermEncumberedToBeSpent, OutputSource::Standard),
+            Some(BalanceCategory::PendingOutgoing)
+        );
+        assert_eq!(of(OutputStatus::Spent, OutputSource::Standard), None);
+    }
+
+    #[test]
+    fn it_sums_per_category() {
+        let mut breakdown = BalanceBreakdown::default();
+        breakdown.add(BalanceCategory::Available, MicroMinotari(100));
+        breakdown.add(BalanceCategory::Available, MicroMinotari(50));
+        breakdown.add(BalanceCategory::ImmatureCoinbase, MicroMinotari(20));
+        breakdown.add(BalanceCategory::PendingOutgoing, MicroMinotari(70));
+        assert_eq!(breakdown.available, MicroMinotari(150));
+        assert_eq!(breakdown.immature_coinbase, MicroMinotari(20));
+        assert_eq!(breakdown.total(), MicroMinotari(170));
+    }
+}
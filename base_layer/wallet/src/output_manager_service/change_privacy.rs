@@ -0,0 +1,155 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Optional change splitting to make amount-based transaction graph analysis harder. When enabled, the change of a
+//! transaction is split over several outputs with randomised values, so that the change output can no longer be
+//! identified by its value alone, and occasionally an unspent output is split in a self-spend.
+
+use std::{fmt, str::FromStr};
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use tari_core::transactions::tari_amount::MicroMinotari;
+
+/// Change is only split into parts that are at least this large, so that the decoy outputs are not dust
+pub const MIN_DECOY_CHANGE_VALUE: MicroMinotari = MicroMinotari(10_000);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangePrivacyLevel {
+    /// A single change output is created
+    #[default]
+    Off,
+    /// Change is split into two outputs
+    Low,
+    /// Change is split into two or three outputs, and some transactions are followed by a self-spend
+    Medium,
+    /// Change is split into three or four outputs, and more transactions are followed by a self-spend
+    High,
+}
+
+impl ChangePrivacyLevel {
+    pub fn is_enabled(self) -> bool {
+        self != ChangePrivacyLevel::Off
+    }
+
+    /// The number of additional change outputs to create, chosen at random within the range of the level
+    pub fn num_decoy_outputs<R: Rng>(self, rng: &mut R) -> usize {
+        match self {
+            ChangePrivacyLevel::Off => 0,
+            ChangePrivacyLevel::Low => 1,
+            ChangePrivacyLevel::Medium => rng.gen_range(1..=2),
+            ChangePrivacyLevel::High => rng.gen_range(2..=3),
+        }
+    }
+
+    /// The probability that a transaction is followed by a self-spend
+    pub fn self_spend_probability(self) -> f64 {
+        match self {
+            ChangePrivacyLevel::Off | ChangePrivacyLevel::Low => 0.0,
+            ChangePrivacyLevel::Medium => 0.05,
+            ChangePrivacyLevel::High => 0.15,
+        }
+    }
+
+    pub fn should_self_spend<R: Rng>(self, rng: &mut R) -> bool {
+        let probability = self.self_spend_probability();
+        probability > 0.0 && rng.gen_bool(probability)
+    }
+}
+
+impl fmt::Display for ChangePrivacyLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChangePrivacyLevel::Off => write!(f, "off"),
+            ChangePrivacyLevel::Low => write!(f, "low"),
+            ChangePrivacyLevel::Medium => write!(f, "medium"),
+            ChangePrivacyLevel::High => write!(f, "high"),
+        }
+    }
+}
+
+impl FromStr for ChangePrivacyLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "off" => Ok(ChangePrivacyLevel::Off),
+            "low" => Ok(ChangePrivacyLevel::Low),
+            "medium" => Ok(ChangePrivacyLevel::Medium),
+            "high" => Ok(ChangePrivacyLevel::High),
+            _ => Err(format!("Invalid change privacy level '{}'", s)),
+        }
+    }
+}
+
+/// Splits `value` into `num_parts` randomised values that sum to `value`, each at least `min_value`. Returns None if
+/// the value is too small to split.
+pub fn split_value<R: Rng>(
+    value: MicroMinotari,
+    num_parts: usize,
+    min_value: MicroMinotari,
+    rng: &mut R,
+) -> Option<Vec<MicroMinotari>> {
+    let num_parts_u64 = u64::try_from(num_parts).ok()?;
+    if num_parts_u64 == 0 {
+        return None;
+    }
+    let reserved = min_value.as_u64().checked_mul(num_parts_u64)?;
+    let remainder = value.as_u64().checked_sub(reserved)?;
+
+    let weights = (0..num_parts).map(|_| rng.gen_range(1..=100u64)).collect::<Vec<_>>();
+    let total_weight = u128::from(weights.iter().sum::<u64>());
+    let mut parts = weights
+        .iter()
+        .map(|w| {
+            let share = u128::from(remainder) * u128::from(*w) / total_weight;
+            // The share is never larger than the remainder
+            min_value.as_u64() + u64::try_from(share).unwrap_or(remainder)
+        })
+        .collect::<Vec<_>>();
+    // Rounding leftovers go to the last part so that the parts sum to the value exactly
+    let allocated = parts.iter().sum::<u64>();
+    if let Some(last) = parts.last_mut() {
+        *last += value.as_u64() - allocated;
+    }
+    Some(parts.into_iter().map(MicroMinotari::from).collect())
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::OsRng;
+
+    use super::*;
+
+    #[test]
+    fn it_splits_the_value_exactly() {
+        for num_parts in 1..5 {
+            let parts = split_value(MicroMinotari(1_234_567), num_parts, MIN_DECOY_CHANGE_VALUE, &mut OsRng).unwrap();
+            assert_eq!(parts.len(), num_parts);
+            assert_eq!(parts.iter().copied().sum::<MicroMinotari>(), MicroMinotari(1_234_567));
+            assert!(parts.iter().all(|p| *p >= MIN_DECOY_CHANGE_VALUE));
+        }
+    }
+
+    #[test]
+    fn it_does_not_split_small_values() {
+        assert!(split_value(MicroMinotari(19_999), 2, MIN_DECOY_CHANGE_VALUE, &mut OsRng).is_none());
+        assert!(split_value(MicroMinotari(1_000_000), 0, MIN_DECOY_CHANGE_VALUE, &mut OsRng).is_none());
+        let parts = split_value(MicroMinotari(20_000), 2, MIN_DECOY_CHANGE_VALUE, &mut OsRng).unwrap();
+        assert_eq!(parts, vec![MIN_DECOY_CHANGE_VALUE, MIN_DECOY_CHANGE_VALUE]);
+    }
+
+    #[test]
+    fn it_follows_the_privacy_level() {
+        assert!(!ChangePrivacyLevel::default().is_enabled());
+        assert_eq!(ChangePrivacyLevel::Off.num_decoy_outputs(&mut OsRng), 0);
+        assert!(!ChangePrivacyLevel::Off.should_self_spend(&mut OsRng));
+        assert!(!ChangePrivacyLevel::Low.should_self_spend(&mut OsRng));
+        for _ in 0..20 {
+            assert!((2..=3).contains(&ChangePrivacyLevel::High.num_decoy_outputs(&mut OsRng)));
+        }
+        assert_eq!("Medium".parse::<ChangePrivacyLevel>(), Ok(ChangePrivacyLevel::Medium));
+        assert!("max".parse::<ChangePrivacyLevel>().is_err());
+    }
+}
@@ -22,6 +22,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::output_manager_service::change_privacy::ChangePrivacyLevel;
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct OutputManagerServiceConfig {
@@ -45,6 +47,12 @@ pub struct OutputManagerServiceConfig {
     pub autoignore_onesided_utxos: bool,
     /// The number of seconds that have to pass for the wallet to run revalidation of invalid UTXOs on startup.
     pub num_of_seconds_to_revalidate_invalid_utxos: u64,
+    /// The number of deterministic sub-addresses, starting at index 1, that are scanned for when receiving stealth
+    /// one-sided payments. Index 0 is the wallet's primary address and is always scanned for.
+    pub sub_address_lookahead: u64,
+    /// Split the change of sent transactions into several outputs with randomised values, and occasionally split an
+    /// unspent output in a self-spend, to make amount-based transaction graph analysis harder. This costs extra fees.
+    pub change_privacy_level: ChangePrivacyLevel,
 }
 
 impl Default for OutputManagerServiceConfig {
@@ -57,6 +65,8 @@ impl Default for OutputManagerServiceConfig {
             tx_validator_batch_size: 100,
             autoignore_onesided_utxos: false,
             num_of_seconds_to_revalidate_invalid_utxos: 60 * 60 * 24 * 3,
+            sub_address_lookahead: 0,
+            change_privacy_level: ChangePrivacyLevel::Off,
         }
     }
 }
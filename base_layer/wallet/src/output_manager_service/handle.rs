@@ -31,7 +31,16 @@ use tari_core::{
     covenants::Covenant,
     transactions::{
         tari_amount::MicroMinotari,
-        transaction_components::{OutputFeatures, Transaction, TransactionOutput, WalletOutput, WalletOutputBuilder},
+        transaction_components::{
+            encrypted_data::EncryptedDataRecord,
+            OutputFeatures,
+            Transaction,
+            TransactionOutput,
+            VaultOpening,
+            VaultScript,
+            WalletOutput,
+            WalletOutputBuilder,
+        },
         transaction_protocol::{sender::TransactionSenderMessage, TransactionMetadata},
         ReceiverTransactionProtocol,
         SenderTransactionProtocol,
@@ -45,9 +54,13 @@ use tokio::sync::broadcast;
 use tower::Service;
 
 use crate::output_manager_service::{
+    balance_breakdown::{BalanceBreakdown, OutputFilter},
     error::OutputManagerError,
-    service::{Balance, OutputInfoByTxId, UseOutput},
-    storage::models::{DbWalletOutput, KnownOneSidedPaymentScript, SpendingPriority},
+    service::{Balance, OutputInfoByTxId, UseOutput, ValidatorNodeRegistrationInfo},
+    storage::{
+        models::{DbWalletOutput, KnownOneSidedPaymentScript, SpendingPriority},
+        OutputSource,
+    },
     UtxoSelectionCriteria,
 };
 
@@ -55,6 +68,7 @@ use crate::output_manager_service::{
 #[allow(clippy::large_enum_variant)]
 pub enum OutputManagerRequest {
     GetBalance,
+    GetBalanceBreakdown(OutputFilter),
     AddOutput((Box<WalletOutput>, Option<SpendingPriority>)),
     AddOutputWithTxId((TxId, Box<WalletOutput>, Option<SpendingPriority>)),
     AddUnvalidatedOutput((TxId, Box<WalletOutput>, Option<SpendingPriority>)),
@@ -109,6 +123,7 @@ pub enum OutputManagerRequest {
     CancelTransaction(TxId),
     GetSpentOutputs,
     GetUnspentOutputs,
+    GetFilteredUnspentOutputs(OutputFilter),
     GetInvalidOutputs,
     ValidateUtxos,
     RevalidateTxos,
@@ -134,6 +149,7 @@ pub enum OutputManagerRequest {
 
     ScanForRecoverableOutputs(Vec<(TransactionOutput, Option<TxId>)>),
     ScanOutputs(Vec<(TransactionOutput, Option<TxId>)>),
+    ScanOutputsForAllBranches(Vec<(TransactionOutput, Option<TxId>)>),
     AddKnownOneSidedPaymentScript(KnownOneSidedPaymentScript),
     CreateOutputWithFeatures {
         value: MicroMinotari,
@@ -143,7 +159,23 @@ pub enum OutputManagerRequest {
     ReinstateCancelledInboundTx(TxId),
     CreateClaimShaAtomicSwapTransaction(HashOutput, PublicKey, MicroMinotari),
     CreateHtlcRefundTransaction(HashOutput, MicroMinotari),
+    CreateVaultTransaction {
+        tx_id: TxId,
+        amount: MicroMinotari,
+        recovery_key: PublicKey,
+        unlock_delay: u64,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+    },
+    CreateVaultRecoveryKey,
+    ExportVaultOpening(HashOutput),
+    CreateVaultClawbackTransaction(Box<VaultOpening>, MicroMinotari),
+    GetVaultOutputs,
+    GetValidatorNodeRegistrations,
+    GetApplicationDataOutputs(Option<u32>),
+    CreatePrivacySelfSpend(MicroMinotari),
     GetOutputInfoByTxId(TxId),
+    GetEncryptedDataRecords(Commitment),
 }
 
 impl fmt::Display for OutputManagerRequest {
@@ -153,6 +185,7 @@ impl fmt::Display for OutputManagerRequest {
         use OutputManagerRequest::*;
         match self {
             GetBalance => write!(f, "GetBalance"),
+            GetBalanceBreakdown(v) => write!(f, "GetBalanceBreakdown ({:?})", v),
             AddOutput((v, _)) => write!(f, "AddOutput ({})", v.value),
             AddOutputWithTxId((t, v, _)) => write!(f, "AddOutputWithTxId ({}: {})", t, v.value),
             AddUnvalidatedOutput((t, v, _)) => {
@@ -209,6 +242,7 @@ impl fmt::Display for OutputManagerRequest {
             CancelTransaction(v) => write!(f, "CancelTransaction ({})", v),
             GetSpentOutputs => write!(f, "GetSpentOutputs"),
             GetUnspentOutputs => write!(f, "GetUnspentOutputs"),
+            GetFilteredUnspentOutputs(v) => write!(f, "GetFilteredUnspentOutputs ({:?})", v),
             GetInvalidOutputs => write!(f, "GetInvalidOutputs"),
             ValidateUtxos => write!(f, "ValidateUtxos"),
             RevalidateTxos => write!(f, "RevalidateTxos"),
@@ -245,6 +279,7 @@ impl fmt::Display for OutputManagerRequest {
             ),
             ScanForRecoverableOutputs(_) => write!(f, "ScanForRecoverableOutputs"),
             ScanOutputs(_) => write!(f, "ScanOutputs"),
+            ScanOutputsForAllBranches(_) => write!(f, "ScanOutputsForAllBranches"),
             AddKnownOneSidedPaymentScript(_) => write!(f, "AddKnownOneSidedPaymentScript"),
             CreateOutputWithFeatures { value, features } => {
                 write!(f, "CreateOutputWithFeatures({}, {})", value, features,)
@@ -261,8 +296,31 @@ impl fmt::Display for OutputManagerRequest {
                 "CreateHtlcRefundTransaction(output hash: {}, , fee_per_gram: {} )",
                 output, fee_per_gram,
             ),
+            CreateVaultTransaction {
+                tx_id,
+                amount,
+                unlock_delay,
+                ..
+            } => write!(
+                f,
+                "CreateVaultTransaction(tx_id: {}, amount: {}, unlock_delay: {})",
+                tx_id, amount, unlock_delay
+            ),
+            CreateVaultRecoveryKey => write!(f, "CreateVaultRecoveryKey"),
+            ExportVaultOpening(output) => write!(f, "ExportVaultOpening(output hash: {})", output),
+            CreateVaultClawbackTransaction(opening, fee_per_gram) => write!(
+                f,
+                "CreateVaultClawbackTransaction(commitment: {}, fee_per_gram: {})",
+                opening.output.commitment.to_hex(),
+                fee_per_gram,
+            ),
+            GetVaultOutputs => write!(f, "GetVaultOutputs"),
+            GetValidatorNodeRegistrations => write!(f, "GetValidatorNodeRegistrations"),
+            GetApplicationDataOutputs(id) => write!(f, "GetApplicationDataOutputs: {:?}", id),
+            CreatePrivacySelfSpend(fee_per_gram) => write!(f, "CreatePrivacySelfSpend: {}", fee_per_gram),
 
             GetOutputInfoByTxId(t) => write!(f, "GetOutputInfoByTxId: {}", t),
+            GetEncryptedDataRecords(c) => write!(f, "GetEncryptedDataRecords: {}", c.to_hex()),
         }
     }
 }
@@ -271,6 +329,7 @@ impl fmt::Display for OutputManagerRequest {
 #[derive(Debug, Clone)]
 pub enum OutputManagerResponse {
     Balance(Balance),
+    BalanceBreakdown(BalanceBreakdown),
     OutputAdded,
     ConvertedToTransactionOutput(Box<TransactionOutput>),
     OutputMetadataSignatureUpdated,
@@ -315,6 +374,13 @@ pub enum OutputManagerResponse {
     ReinstatedCancelledInboundTx,
     ClaimHtlcTransaction((TxId, MicroMinotari, MicroMinotari, Transaction)),
     OutputInfoByTxId(OutputInfoByTxId),
+    VaultOutputs(Vec<(DbWalletOutput, VaultScript)>),
+    VaultRecoveryKey(PublicKey),
+    VaultOpening(Box<VaultOpening>),
+    ValidatorNodeRegistrations(Vec<ValidatorNodeRegistrationInfo>),
+    ApplicationDataOutputs(Vec<DbWalletOutput>),
+    PrivacySelfSpend(Option<(TxId, Transaction, MicroMinotari)>),
+    EncryptedDataRecords(Vec<EncryptedDataRecord>),
     CoinPreview((Vec<MicroMinotari>, MicroMinotari)),
 }
 
@@ -359,6 +425,7 @@ pub struct RecoveredOutput {
     pub tx_id: TxId,
     pub output: WalletOutput,
     pub hash: FixedHash,
+    pub source: OutputSource,
 }
 
 #[derive(Clone)]
@@ -476,6 +543,21 @@ impl OutputManagerHandle {
         }
     }
 
+    /// The balance of the outputs that match the filter, per category
+    pub async fn get_balance_breakdown(
+        &mut self,
+        filter: OutputFilter,
+    ) -> Result<BalanceBreakdown, OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::GetBalanceBreakdown(filter))
+            .await??
+        {
+            OutputManagerResponse::BalanceBreakdown(b) => Ok(b),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
     pub async fn revalidate_all_outputs(&mut self) -> Result<u64, OutputManagerError> {
         match self.handle.call(OutputManagerRequest::RevalidateTxos).await?? {
             OutputManagerResponse::TxoValidationStarted(request_key) => Ok(request_key),
@@ -609,6 +691,21 @@ impl OutputManagerHandle {
         }
     }
 
+    /// The unspent outputs that match the filter, sorted from lowest value to highest
+    pub async fn get_filtered_unspent_outputs(
+        &mut self,
+        filter: OutputFilter,
+    ) -> Result<Vec<DbWalletOutput>, OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::GetFilteredUnspentOutputs(filter))
+            .await??
+        {
+            OutputManagerResponse::UnspentOutputs(s) => Ok(s),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
     pub async fn get_invalid_outputs(&mut self) -> Result<Vec<WalletOutput>, OutputManagerError> {
         match self.handle.call(OutputManagerRequest::GetInvalidOutputs).await?? {
             OutputManagerResponse::InvalidOutputs(s) => Ok(s),
@@ -735,6 +832,129 @@ impl OutputManagerHandle {
         }
     }
 
+    /// Creates a time-locked vault output to self. The owner may only spend the output once `unlock_delay` blocks have
+    /// passed since it was mined, but the holder of the recovery key may sweep it at any time. The opening of the vault
+    /// must be exported with `export_vault_opening` and handed to the recovery key holder.
+    pub async fn create_vault_transaction(
+        &mut self,
+        tx_id: TxId,
+        amount: MicroMinotari,
+        recovery_key: PublicKey,
+        unlock_delay: u64,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+    ) -> Result<(MicroMinotari, Transaction), OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::CreateVaultTransaction {
+                tx_id,
+                amount,
+                recovery_key,
+                unlock_delay,
+                selection_criteria,
+                fee_per_gram,
+            })
+            .await??
+        {
+            OutputManagerResponse::PayToSelfTransaction(outputs) => Ok(outputs),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Creates a recovery key for vaults that this wallet should be able to claw back. The public key is handed to the
+    /// vault owner, and the private key never leaves this wallet.
+    pub async fn create_vault_recovery_key(&mut self) -> Result<PublicKey, OutputManagerError> {
+        match self.handle.call(OutputManagerRequest::CreateVaultRecoveryKey).await?? {
+            OutputManagerResponse::VaultRecoveryKey(key) => Ok(key),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Exports the opening of the vault output with the given hash, for the recovery key holder to claw it back with
+    pub async fn export_vault_opening(&mut self, output: HashOutput) -> Result<VaultOpening, OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::ExportVaultOpening(output))
+            .await??
+        {
+            OutputManagerResponse::VaultOpening(opening) => Ok(*opening),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Sweeps a vault into this wallet through the recovery path, from the opening exported by the vault owner. The
+    /// vault recovery key must have been created by this wallet with `create_vault_recovery_key`.
+    pub async fn create_vault_clawback_transaction(
+        &mut self,
+        opening: VaultOpening,
+        fee_per_gram: MicroMinotari,
+    ) -> Result<(TxId, MicroMinotari, MicroMinotari, Transaction), OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::CreateVaultClawbackTransaction(
+                Box::new(opening),
+                fee_per_gram,
+            ))
+            .await??
+        {
+            OutputManagerResponse::ClaimHtlcTransaction(ct) => Ok(ct),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Returns all vault outputs known to this wallet, spent or unspent, along with their vault parameters
+    pub async fn get_vault_outputs(&mut self) -> Result<Vec<(DbWalletOutput, VaultScript)>, OutputManagerError> {
+        match self.handle.call(OutputManagerRequest::GetVaultOutputs).await?? {
+            OutputManagerResponse::VaultOutputs(outputs) => Ok(outputs),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Returns the spent and unspent outputs that anchor application data, optionally for one application only
+    pub async fn get_application_data_outputs(
+        &mut self,
+        application_id: Option<u32>,
+    ) -> Result<Vec<DbWalletOutput>, OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::GetApplicationDataOutputs(application_id))
+            .await??
+        {
+            OutputManagerResponse::ApplicationDataOutputs(outputs) => Ok(outputs),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Occasionally creates a transaction that splits a spendable output to itself, as set by the change privacy
+    /// level. Returns the transaction id, transaction and amount if one was created.
+    pub async fn create_privacy_self_spend(
+        &mut self,
+        fee_per_gram: MicroMinotari,
+    ) -> Result<Option<(TxId, Transaction, MicroMinotari)>, OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::CreatePrivacySelfSpend(fee_per_gram))
+            .await??
+        {
+            OutputManagerResponse::PrivacySelfSpend(tx) => Ok(tx),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Returns the validator node registrations made by this wallet, with their confirmation and expiry
+    pub async fn get_validator_node_registrations(
+        &mut self,
+    ) -> Result<Vec<ValidatorNodeRegistrationInfo>, OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::GetValidatorNodeRegistrations)
+            .await??
+        {
+            OutputManagerResponse::ValidatorNodeRegistrations(registrations) => Ok(registrations),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
     pub async fn create_claim_sha_atomic_swap_transaction(
         &mut self,
         output: HashOutput,
@@ -779,6 +999,22 @@ impl OutputManagerHandle {
         }
     }
 
+    /// Scans the outputs for those belonging to any of the wallet's keys in a single pass, which covers both
+    /// `scan_for_recoverable_outputs` and `scan_outputs_for_one_sided_payments`
+    pub async fn scan_outputs_for_all_branches(
+        &mut self,
+        outputs: Vec<(TransactionOutput, Option<TxId>)>,
+    ) -> Result<Vec<RecoveredOutput>, OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::ScanOutputsForAllBranches(outputs))
+            .await??
+        {
+            OutputManagerResponse::RewoundOutputs(outputs) => Ok(outputs),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
     pub async fn add_known_script(&mut self, script: KnownOneSidedPaymentScript) -> Result<(), OutputManagerError> {
         match self
             .handle
@@ -948,4 +1184,20 @@ impl OutputManagerHandle {
             _ => Err(OutputManagerError::UnexpectedApiResponse),
         }
     }
+
+    /// Reads the typed records stored in the encrypted data of one of our outputs, which must be encrypted to the
+    /// wallet's view key or be a one-sided payment to the wallet
+    pub async fn get_encrypted_data_records(
+        &mut self,
+        commitment: Commitment,
+    ) -> Result<Vec<EncryptedDataRecord>, OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::GetEncryptedDataRecords(commitment))
+            .await??
+        {
+            OutputManagerResponse::EncryptedDataRecords(records) => Ok(records),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
 }
@@ -20,7 +20,7 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{str::FromStr, time::Instant};
+use std::{iter, str::FromStr, time::Instant};
 
 use log::*;
 use tari_common_types::{
@@ -58,6 +58,9 @@ const LOG_TARGET: &str = "wallet::output_manager_service::recovery";
 pub(crate) struct StandardUtxoRecoverer<TBackend: OutputManagerBackend + 'static, TKeyManagerInterface> {
     master_key_manager: TKeyManagerInterface,
     db: OutputManagerDatabase<TBackend>,
+    /// Imported keys that outputs may be encrypted to, or whose script may pay, besides the view key and the key
+    /// manager branches
+    imported_key_ids: Vec<TariKeyId>,
 }
 
 impl<TBackend, TKeyManagerInterface> StandardUtxoRecoverer<TBackend, TKeyManagerInterface>
@@ -66,7 +69,18 @@ where
     TKeyManagerInterface: TransactionKeyManagerInterface,
 {
     pub fn new(master_key_manager: TKeyManagerInterface, db: OutputManagerDatabase<TBackend>) -> Self {
-        Self { master_key_manager, db }
+        Self {
+            master_key_manager,
+            db,
+            imported_key_ids: Vec::new(),
+        }
+    }
+
+    /// Also recovers the outputs whose encrypted data can be decrypted with, or whose script key is, one of the given
+    /// imported keys
+    pub fn with_imported_keys(mut self, imported_key_ids: Vec<TariKeyId>) -> Self {
+        self.imported_key_ids = imported_key_ids;
+        self
     }
 
     /// Attempt to rewind all of the given transaction outputs into key_manager outputs. If they can be rewound then add
@@ -135,15 +149,10 @@ where
 
         let mut rewound_outputs_with_tx_id: Vec<RecoveredOutput> = Vec::new();
         for (output, has_known_script, hash, tx_id) in &mut rewound_outputs {
-            let db_output = DbWalletOutput::from_wallet_output(
-                output.clone(),
-                &self.master_key_manager,
-                None,
-                Self::output_source(output, *has_known_script),
-                None,
-                None,
-            )
-            .await?;
+            let source = Self::output_source(output, *has_known_script);
+            let db_output =
+                DbWalletOutput::from_wallet_output(output.clone(), &self.master_key_manager, None, source, None, None)
+                    .await?;
             let tx_id = match tx_id {
                 Some(id) => *id,
                 None => TxId::new_random(),
@@ -162,6 +171,7 @@ where
                 output: output.clone(),
                 tx_id,
                 hash: *hash,
+                source,
             });
             trace!(
                 target: LOG_TARGET,
@@ -228,7 +238,11 @@ where
                         .master_key_manager
                         .find_script_key_id_from_commitment_mask_key_id(spending_key, Some(public_key))
                         .await?;
-                    if let Some(script_key_id) = result {
+                    let imported_key_id = self
+                        .imported_key_ids
+                        .iter()
+                        .find(|key_id| matches!(key_id, KeyId::Imported { key } if *key == **public_key));
+                    if let Some(script_key_id) = result.or_else(|| imported_key_id.cloned()) {
                         (ExecutionStack::default(), script_key_id)
                     } else {
                         // The spending key is recoverable but we dont know how to calculate the script key
@@ -255,14 +269,20 @@ where
             Err(OutputManagerStorageError::ValueNotFound) => {},
             Err(e) => return Err(e.into()),
         };
-        let (key, committed_value, payment_id) =
-            match self.master_key_manager.try_output_key_recovery(output, None).await {
-                Ok(value) => value,
+        // The encrypted data is tried with the view key first, and then with each of the imported keys
+        let recovery_key_ids = iter::once(None).chain(self.imported_key_ids.iter().map(Some));
+        for recovery_key_id in recovery_key_ids {
+            match self
+                .master_key_manager
+                .try_output_key_recovery(output, recovery_key_id)
+                .await
+            {
+                Ok(value) => return Ok(Some(value)),
                 // Key manager errors here are actual errors and should not be suppressed.
                 Err(TransactionError::KeyManagerError(e)) => return Err(TransactionError::KeyManagerError(e).into()),
-                Err(_) => return Ok(None),
-            };
-
-        Ok(Some((key, committed_value, payment_id)))
+                Err(_) => {},
+            }
+        }
+        Ok(None)
     }
 }
@@ -19,14 +19,20 @@
 // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
-use std::{collections::HashMap, convert::TryInto, fmt, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    convert::TryInto,
+    fmt,
+    sync::Arc,
+};
 
 use diesel::result::{DatabaseErrorKind, Error as DieselError};
 use futures::{pin_mut, StreamExt};
 use log::*;
-use rand::{rngs::OsRng, RngCore};
+use rand::{rngs::OsRng, seq::SliceRandom, RngCore};
 use tari_common::configuration::Network;
 use tari_common_types::{
+    epoch::VnEpoch,
     key_branches::TransactionKeyManagerBranch,
     tari_address::{TariAddress, TariAddressFeatures},
     transaction::TxId,
@@ -48,15 +54,18 @@ use tari_core::{
         key_manager::{TariKeyId, TransactionKeyManagerInterface},
         tari_amount::MicroMinotari,
         transaction_components::{
-            encrypted_data::PaymentId,
+            encrypted_data::{EncryptedDataRecord, PaymentId},
             EncryptedData,
             KernelFeatures,
             OutputFeatures,
+            OutputType,
             RangeProofType,
             Transaction,
             TransactionError,
             TransactionOutput,
             TransactionOutputVersion,
+            VaultOpening,
+            VaultScript,
             WalletOutput,
             WalletOutputBuilder,
         },
@@ -87,6 +96,8 @@ use crate::{
     base_node_service::handle::{BaseNodeEvent, BaseNodeServiceHandle},
     connectivity_service::WalletConnectivityInterface,
     output_manager_service::{
+        balance_breakdown::{BalanceBreakdown, OutputFilter, BALANCE_OUTPUT_STATUSES},
+        change_privacy::{split_value, MIN_DECOY_CHANGE_VALUE},
         config::OutputManagerServiceConfig,
         error::{OutputManagerError, OutputManagerProtocolError, OutputManagerStorageError},
         handle::{
@@ -305,13 +316,15 @@ where
                 .update_output_metadata_signature(*uo)
                 .map(|_| OutputManagerResponse::OutputMetadataSignatureUpdated),
             OutputManagerRequest::GetBalance => {
-                let current_tip_for_time_lock_calculation = match self.base_node_service.get_chain_metadata().await {
-                    Ok(metadata) => metadata.map(|m| m.best_block_height()),
-                    Err(_) => None,
-                };
+                let current_tip_for_time_lock_calculation = self.get_current_tip_for_time_lock_calculation().await;
                 self.get_balance(current_tip_for_time_lock_calculation)
                     .map(OutputManagerResponse::Balance)
             },
+            OutputManagerRequest::GetBalanceBreakdown(filter) => {
+                let current_tip_for_time_lock_calculation = self.get_current_tip_for_time_lock_calculation().await;
+                self.get_balance_breakdown(&filter, current_tip_for_time_lock_calculation)
+                    .map(OutputManagerResponse::BalanceBreakdown)
+            },
             OutputManagerRequest::GetRecipientTransaction(tsm) => self
                 .get_default_recipient_transaction(tsm)
                 .await
@@ -384,6 +397,15 @@ where
                 let outputs = self.fetch_unspent_outputs()?;
                 Ok(OutputManagerResponse::UnspentOutputs(outputs))
             },
+            OutputManagerRequest::GetFilteredUnspentOutputs(filter) => {
+                let current_tip_for_time_lock_calculation = self.get_current_tip_for_time_lock_calculation().await;
+                let outputs = self
+                    .fetch_unspent_outputs()?
+                    .into_iter()
+                    .filter(|o| filter.matches(o, current_tip_for_time_lock_calculation))
+                    .collect();
+                Ok(OutputManagerResponse::UnspentOutputs(outputs))
+            },
             OutputManagerRequest::ValidateUtxos => {
                 self.validate_outputs().map(OutputManagerResponse::TxoValidationStarted)
             },
@@ -456,6 +478,10 @@ where
                 .scan_outputs_for_one_sided_payments(outputs)
                 .await
                 .map(OutputManagerResponse::ScanOutputs),
+            OutputManagerRequest::ScanOutputsForAllBranches(outputs) => self
+                .scan_outputs_for_all_branches(outputs)
+                .await
+                .map(OutputManagerResponse::RewoundOutputs),
             OutputManagerRequest::AddKnownOneSidedPaymentScript(known_script) => self
                 .add_known_script(known_script)
                 .map(|_| OutputManagerResponse::AddKnownOneSidedPaymentScript),
@@ -489,13 +515,91 @@ where
                 .create_htlc_refund_transaction(output, fee_per_gram)
                 .await
                 .map(OutputManagerResponse::ClaimHtlcTransaction),
+            OutputManagerRequest::CreateVaultTransaction {
+                tx_id,
+                amount,
+                recovery_key,
+                unlock_delay,
+                selection_criteria,
+                fee_per_gram,
+            } => self
+                .create_vault_transaction(
+                    tx_id,
+                    amount,
+                    recovery_key,
+                    unlock_delay,
+                    selection_criteria,
+                    fee_per_gram,
+                )
+                .await
+                .map(OutputManagerResponse::PayToSelfTransaction),
+            OutputManagerRequest::CreateVaultRecoveryKey => self
+                .create_vault_recovery_key()
+                .await
+                .map(OutputManagerResponse::VaultRecoveryKey),
+            OutputManagerRequest::ExportVaultOpening(output) => self
+                .export_vault_opening(output)
+                .await
+                .map(|opening| OutputManagerResponse::VaultOpening(Box::new(opening))),
+            OutputManagerRequest::CreateVaultClawbackTransaction(opening, fee_per_gram) => self
+                .create_vault_clawback_transaction(*opening, fee_per_gram)
+                .await
+                .map(OutputManagerResponse::ClaimHtlcTransaction),
+            OutputManagerRequest::GetVaultOutputs => self.get_vault_outputs().map(OutputManagerResponse::VaultOutputs),
+            OutputManagerRequest::CreatePrivacySelfSpend(fee_per_gram) => self
+                .create_privacy_self_spend(fee_per_gram)
+                .await
+                .map(OutputManagerResponse::PrivacySelfSpend),
+            OutputManagerRequest::GetApplicationDataOutputs(application_id) => self
+                .get_application_data_outputs(application_id)
+                .map(OutputManagerResponse::ApplicationDataOutputs),
+            OutputManagerRequest::GetValidatorNodeRegistrations => self
+                .get_validator_node_registrations()
+                .await
+                .map(OutputManagerResponse::ValidatorNodeRegistrations),
             OutputManagerRequest::GetOutputInfoByTxId(tx_id) => {
                 let output_statuses_by_tx_id = self.get_output_info_by_tx_id(tx_id)?;
                 Ok(OutputManagerResponse::OutputInfoByTxId(output_statuses_by_tx_id))
             },
+            OutputManagerRequest::GetEncryptedDataRecords(commitment) => self
+                .get_encrypted_data_records(commitment)
+                .await
+                .map(OutputManagerResponse::EncryptedDataRecords),
         }
     }
 
+    async fn get_encrypted_data_records(
+        &self,
+        commitment: Commitment,
+    ) -> Result<Vec<EncryptedDataRecord>, OutputManagerError> {
+        let output = self.resources.db.fetch_by_commitment(commitment)?;
+        let encrypted_data = &output.wallet_output.encrypted_data;
+        let version = &output.wallet_output.version;
+        if let Ok(records) = self
+            .resources
+            .key_manager
+            .decrypt_output_records(&output.commitment, encrypted_data, version, None)
+            .await
+        {
+            return Ok(records);
+        }
+        // One-sided payments are encrypted to a key shared with the sender instead of the view key
+        let view_key = self.resources.key_manager.get_view_key().await?;
+        let shared_secret = self
+            .resources
+            .key_manager
+            .get_diffie_hellman_shared_secret(&view_key.key_id, &output.wallet_output.sender_offset_public_key)
+            .await?;
+        let encryption_private_key = shared_secret_to_output_encryption_key(&shared_secret)?;
+        let encryption_key_id = self.resources.key_manager.import_key(encryption_private_key).await?;
+        let records = self
+            .resources
+            .key_manager
+            .decrypt_output_records(&output.commitment, encrypted_data, version, Some(&encryption_key_id))
+            .await?;
+        Ok(records)
+    }
+
     fn get_output_info_by_tx_id(&self, tx_id: TxId) -> Result<OutputInfoByTxId, OutputManagerError> {
         let outputs = self.resources.db.fetch_outputs_by_tx_id(tx_id)?;
         let statuses = outputs.clone().into_iter().map(|uo| uo.status).collect();
@@ -550,6 +654,7 @@ where
                     e
                 });
             },
+            BaseNodeEvent::BaseNodeFailover(..) | BaseNodeEvent::TipDivergesFromBeacons(..) => {},
         }
     }
 
@@ -745,12 +850,33 @@ where
             .with_script_key(script_key.key_id))
     }
 
+    async fn get_current_tip_for_time_lock_calculation(&mut self) -> Option<u64> {
+        match self.base_node_service.get_chain_metadata().await {
+            Ok(metadata) => metadata.map(|m| m.best_block_height()),
+            Err(_) => None,
+        }
+    }
+
     fn get_balance(&self, current_tip_for_time_lock_calculation: Option<u64>) -> Result<Balance, OutputManagerError> {
         let balance = self.resources.db.get_balance(current_tip_for_time_lock_calculation)?;
         trace!(target: LOG_TARGET, "Balance: {:?}", balance);
         Ok(balance)
     }
 
+    fn get_balance_breakdown(
+        &self,
+        filter: &OutputFilter,
+        current_tip_for_time_lock_calculation: Option<u64>,
+    ) -> Result<BalanceBreakdown, OutputManagerError> {
+        let outputs = self.resources.db.fetch_outputs_by_query(OutputBackendQuery {
+            status: BALANCE_OUTPUT_STATUSES.to_vec(),
+            ..Default::default()
+        })?;
+        let breakdown = BalanceBreakdown::from_outputs(&outputs, filter, current_tip_for_time_lock_calculation);
+        trace!(target: LOG_TARGET, "Balance breakdown: {:?}", breakdown);
+        Ok(breakdown)
+    }
+
     /// Request a receiver transaction be generated from the supplied Sender Message
     #[allow(clippy::too_many_lines)]
     async fn get_default_recipient_transaction(
@@ -951,7 +1077,7 @@ where
     }
 
     /// Prepare a Sender Transaction Protocol for the amount and fee_per_gram specified. If required a change output
-    /// will be produced.
+    /// will be produced, which is split into several outputs if change privacy is enabled.
     #[allow(clippy::too_many_lines)]
     pub async fn prepare_transaction_to_send(
         &mut self,
@@ -989,13 +1115,16 @@ where
                         .map_err(|e| OutputManagerError::ConversionError(e.to_string()))?,
             );
 
+        // Decoy change outputs are paid for up front, and are dropped again below if the change is too small to split
+        let num_decoys = self.resources.config.change_privacy_level.num_decoy_outputs(&mut OsRng);
+        let decoys_byte_size = self.default_features_and_scripts_size()? * num_decoys;
         let input_selection = self
             .select_utxos(
                 amount,
                 selection_criteria,
                 fee_per_gram,
-                1,
-                features_and_scripts_byte_size,
+                1 + num_decoys,
+                features_and_scripts_byte_size + decoys_byte_size,
             )
             .await?;
 
@@ -1044,13 +1173,38 @@ where
             self.resources.interactive_tari_address.clone(),
         );
 
+        let mut change_output = Vec::<DbWalletOutput>::new();
+        if num_decoys > 0 && input_selection.requires_change_output() {
+            let expected_change = input_selection
+                .total_value()
+                .saturating_sub(amount + input_selection.as_final_fee());
+            // The remaining part is left to the builder as the regular change output
+            if let Some(parts) = split_value(expected_change, num_decoys + 1, MIN_DECOY_CHANGE_VALUE, &mut OsRng) {
+                for value in parts.into_iter().take(num_decoys) {
+                    let (output, sender_offset_key_id) = self
+                        .output_to_self(OutputFeatures::default(), value, Covenant::default())
+                        .await?;
+                    builder
+                        .with_output(output.wallet_output.clone(), sender_offset_key_id)
+                        .await
+                        .map_err(|e| OutputManagerError::BuildError(e.to_string()))?;
+                    change_output.push(output);
+                }
+                debug!(
+                    target: LOG_TARGET,
+                    "Split change of transaction (TxId: {}) into {} outputs",
+                    tx_id,
+                    change_output.len() + 1
+                );
+            }
+        }
+
         let stp = builder
             .build()
             .await
             .map_err(|e| OutputManagerError::BuildError(e.message))?;
 
         // If a change output was created add it to the pending_outputs list.
-        let mut change_output = Vec::<DbWalletOutput>::new();
         if input_selection.requires_change_output() {
             let wallet_output = stp.get_change_output()?.ok_or_else(|| {
                 OutputManagerError::BuildError(
@@ -1299,9 +1453,12 @@ where
         let mut aggregated_script_public_key_shares = PublicKey::default();
         trace!(target: LOG_TARGET, "encumber_aggregate_utxo: created deterministic encryption key");
         // Decrypt the output secrets and create a new input as WalletOutput (unblinded)
-        let input = if let Ok((amount, commitment_mask, payment_id)) =
-            EncryptedData::decrypt_data(&encryption_private_key, &output.commitment, &output.encrypted_data)
-        {
+        let input = if let Ok((amount, commitment_mask, payment_id)) = EncryptedData::decrypt_output_data(
+            &encryption_private_key,
+            &output.commitment,
+            &output.encrypted_data,
+            output.version,
+        ) {
             if output.verify_mask(&self.resources.factories.range_proof, &commitment_mask, amount.as_u64())? {
                 let script_key = self
                     .pre_mine_script_key_from_payment_id(payment_id.clone(), tx_id)
@@ -1663,9 +1820,12 @@ where
             .fold(tari_common_types::types::PublicKey::default(), |acc, x| acc + x);
         let encryption_private_key = public_key_to_output_encryption_key(&sum_public_keys)?;
         // Decrypt the output secrets and create a new input as WalletOutput (unblinded)
-        let input = if let Ok((amount, spending_key, payment_id)) =
-            EncryptedData::decrypt_data(&encryption_private_key, &output.commitment, &output.encrypted_data)
-        {
+        let input = if let Ok((amount, spending_key, payment_id)) = EncryptedData::decrypt_output_data(
+            &encryption_private_key,
+            &output.commitment,
+            &output.encrypted_data,
+            output.version,
+        ) {
             if output.verify_mask(&self.resources.factories.range_proof, &spending_key, amount.as_u64())? {
                 let spending_key_id = self.resources.key_manager.import_key(spending_key).await?;
                 let script_key = self
@@ -1993,6 +2153,250 @@ where
         Ok((fee, tx))
     }
 
+    /// Create a transaction that locks `amount` into a vault output owned by this wallet. The output can only be spent
+    /// by this wallet once `unlock_delay` blocks have passed, but the holder of the recovery key can sweep it at any
+    /// time.
+    #[allow(clippy::too_many_lines)]
+    async fn create_vault_transaction(
+        &mut self,
+        tx_id: TxId,
+        amount: MicroMinotari,
+        recovery_key: PublicKey,
+        unlock_delay: u64,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+    ) -> Result<(MicroMinotari, Transaction), OutputManagerError> {
+        if unlock_delay == 0 {
+            return Err(OutputManagerError::InvalidArgument(
+                "Vault unlock delay must be greater than zero".to_string(),
+            ));
+        }
+        let tip_height = match self.base_node_service.get_chain_metadata().await? {
+            Some(metadata) => metadata.best_block_height(),
+            None => self.last_seen_tip_height.ok_or(OutputManagerError::BaseNodeNotSynced)?,
+        };
+
+        let (commitment_mask_key, script_key) = self
+            .resources
+            .key_manager
+            .get_next_commitment_mask_and_script_key()
+            .await?;
+        let vault = VaultScript::new(script_key.pub_key.clone(), recovery_key, unlock_delay);
+        // The vault cannot be mined before the next block, so this is the earliest that the owner path unlocks at
+        let unlock_height = vault.unlock_height(tip_height.saturating_add(1));
+        let script = vault.to_script()?;
+        let output_features = OutputFeatures::default();
+        let covenant = Covenant::default();
+
+        let features_and_scripts_byte_size = self
+            .resources
+            .consensus_constants
+            .transaction_weight_params()
+            .round_up_features_and_scripts_size(
+                output_features
+                    .get_serialized_size()
+                    .map_err(|e| OutputManagerError::ConversionError(e.to_string()))? +
+                    script
+                        .get_serialized_size()
+                        .map_err(|e| OutputManagerError::ConversionError(e.to_string()))? +
+                    covenant
+                        .get_serialized_size()
+                        .map_err(|e| OutputManagerError::ConversionError(e.to_string()))?,
+            );
+
+        let input_selection = self
+            .select_utxos(
+                amount,
+                selection_criteria,
+                fee_per_gram,
+                1,
+                features_and_scripts_byte_size,
+            )
+            .await?;
+
+        let mut builder = SenderTransactionProtocol::builder(
+            self.resources.consensus_constants.clone(),
+            self.resources.key_manager.clone(),
+        );
+        builder
+            .with_lock_height(0)
+            .with_fee_per_gram(fee_per_gram)
+            .with_prevent_fee_gt_amount(self.resources.config.prevent_fee_gt_amount)
+            .with_kernel_features(KernelFeatures::empty())
+            .with_tx_id(tx_id);
+
+        for kmo in input_selection.iter() {
+            builder.with_input(kmo.wallet_output.clone()).await?;
+        }
+
+        let payment_id = PaymentId::Address(self.resources.interactive_tari_address.clone());
+        let encrypted_data = self
+            .resources
+            .key_manager
+            .encrypt_data_for_recovery(&commitment_mask_key.key_id, None, amount.as_u64(), payment_id.clone())
+            .await?;
+        let minimum_value_promise = MicroMinotari::zero();
+        let metadata_message = TransactionOutput::metadata_signature_message_from_parts(
+            &TransactionOutputVersion::get_current_version(),
+            &script,
+            &output_features,
+            &covenant,
+            &encrypted_data,
+            &minimum_value_promise,
+        );
+        let sender_offset = self
+            .resources
+            .key_manager
+            .get_next_key(TransactionKeyManagerBranch::SenderOffset.get_branch_key())
+            .await?;
+        let metadata_signature = self
+            .resources
+            .key_manager
+            .get_metadata_signature(
+                &commitment_mask_key.key_id,
+                &PrivateKey::from(amount),
+                &sender_offset.key_id,
+                &TransactionOutputVersion::get_current_version(),
+                &metadata_message,
+                output_features.range_proof_type,
+            )
+            .await?;
+        // The script lock height keeps the vault out of coin selection until the owner path is spendable
+        let output = DbWalletOutput::from_wallet_output(
+            WalletOutput::new_current_version(
+                amount,
+                commitment_mask_key.key_id,
+                output_features,
+                script,
+                VaultScript::owner_input_data(),
+                script_key.key_id,
+                sender_offset.pub_key,
+                metadata_signature,
+                unlock_height,
+                covenant,
+                encrypted_data,
+                minimum_value_promise,
+                payment_id,
+                &self.resources.key_manager,
+            )
+            .await?,
+            &self.resources.key_manager,
+            None,
+            OutputSource::Vault,
+            Some(tx_id),
+            None,
+        )
+        .await?;
+
+        builder
+            .with_output(output.wallet_output.clone(), sender_offset.key_id)
+            .await
+            .map_err(|e| OutputManagerError::BuildError(e.to_string()))?;
+
+        let mut outputs = vec![output];
+
+        let (change_commitment_mask_key_id, change_script_public_key) = self
+            .resources
+            .key_manager
+            .get_next_commitment_mask_and_script_key()
+            .await?;
+        builder.with_change_data(
+            script!(PushPubKey(Box::new(change_script_public_key.pub_key.clone())))?,
+            ExecutionStack::default(),
+            change_script_public_key.key_id.clone(),
+            change_commitment_mask_key_id.key_id,
+            Covenant::default(),
+            self.resources.interactive_tari_address.clone(),
+        );
+
+        let mut stp = builder
+            .build()
+            .await
+            .map_err(|e| OutputManagerError::BuildError(e.message))?;
+
+        if input_selection.requires_change_output() {
+            let wallet_output = stp.get_change_output()?.ok_or_else(|| {
+                OutputManagerError::BuildError(
+                    "There should be a change output metadata signature available".to_string(),
+                )
+            })?;
+            let change_output = DbWalletOutput::from_wallet_output(
+                wallet_output,
+                &self.resources.key_manager,
+                None,
+                OutputSource::default(),
+                Some(tx_id),
+                None,
+            )
+            .await?;
+            outputs.push(change_output);
+        }
+
+        trace!(
+            target: LOG_TARGET,
+            "Encumber vault transaction ({}) outputs, unlock height {}.",
+            tx_id,
+            unlock_height
+        );
+        self.resources
+            .db
+            .encumber_outputs(tx_id, input_selection.into_selected(), outputs)?;
+        self.confirm_encumberance(tx_id)?;
+        let fee = stp.get_fee_amount()?;
+        stp.finalize(&self.resources.key_manager).await?;
+        let tx = stp.into_transaction()?;
+
+        Ok((fee, tx))
+    }
+
+    /// Returns all spent and unspent vault outputs, along with the vault parameters recovered from their scripts
+    fn get_vault_outputs(&self) -> Result<Vec<(DbWalletOutput, VaultScript)>, OutputManagerError> {
+        let mut outputs = self.resources.db.fetch_all_unspent_outputs()?;
+        outputs.extend(self.resources.db.fetch_spent_outputs()?);
+        Ok(outputs
+            .into_iter()
+            .filter(|o| o.source == OutputSource::Vault)
+            .filter_map(|o| VaultScript::from_script(&o.wallet_output.script).map(|vault| (o, vault)))
+            .collect())
+    }
+
+    /// Returns all spent and unspent outputs that anchor application data, optionally for one application only
+    fn get_application_data_outputs(
+        &self,
+        application_id: Option<u32>,
+    ) -> Result<Vec<DbWalletOutput>, OutputManagerError> {
+        let mut outputs = self.resources.db.fetch_all_unspent_outputs()?;
+        outputs.extend(self.resources.db.fetch_spent_outputs()?);
+        Ok(outputs
+            .into_iter()
+            .filter(|o| {
+                o.wallet_output
+                    .features
+                    .application_data()
+                    .is_some_and(|data| application_id.map_or(true, |id| data.application_id == id))
+            })
+            .collect())
+    }
+
+    async fn get_validator_node_registrations(
+        &mut self,
+    ) -> Result<Vec<ValidatorNodeRegistrationInfo>, OutputManagerError> {
+        let tip_height = match self.base_node_service.get_chain_metadata().await? {
+            Some(metadata) => Some(metadata.best_block_height()),
+            None => self.last_seen_tip_height,
+        };
+        let outputs = self
+            .resources
+            .db
+            .fetch_with_features(OutputType::ValidatorNodeRegistration)?;
+        Ok(outputs
+            .iter()
+            .filter_map(|o| {
+                ValidatorNodeRegistrationInfo::from_output(o, tip_height, &self.resources.consensus_constants)
+            })
+            .collect())
+    }
+
     /// Confirm that a transaction has finished being negotiated between parties so the short-term encumberance can be
     /// made official
     fn confirm_encumberance(&mut self, tx_id: TxId) -> Result<(), OutputManagerError> {
@@ -2448,6 +2852,42 @@ where
         Ok((tx_id, stp.into_transaction()?, accumulated_amount + fee))
     }
 
+    /// Occasionally, as set by the change privacy level, splits a random spendable output into two outputs with
+    /// randomised values. Returns None if no self-spend is made.
+    async fn create_privacy_self_spend(
+        &mut self,
+        fee_per_gram: MicroMinotari,
+    ) -> Result<Option<(TxId, Transaction, MicroMinotari)>, OutputManagerError> {
+        if !self.resources.config.change_privacy_level.should_self_spend(&mut OsRng) {
+            return Ok(None);
+        }
+        let tip_height = self
+            .base_node_service
+            .get_chain_metadata()
+            .await?
+            .map(|m| m.best_block_height());
+        let candidates = self.resources.db.fetch_unspent_outputs_for_spending(
+            &UtxoSelectionCriteria::default(),
+            MicroMinotari::zero(),
+            tip_height,
+        )?;
+        let Some(output) = candidates.choose(&mut OsRng).cloned() else {
+            return Ok(None);
+        };
+        let Some(parts) = split_value(output.wallet_output.value, 2, MIN_DECOY_CHANGE_VALUE, &mut OsRng) else {
+            return Ok(None);
+        };
+        debug!(
+            target: LOG_TARGET,
+            "Splitting output {} in a self-spend for change privacy",
+            output.commitment.to_hex()
+        );
+        // The other part, less the fee, is returned as change
+        self.create_coin_split(vec![output], parts[0], 1, fee_per_gram)
+            .await
+            .map(Some)
+    }
+
     #[allow(clippy::too_many_lines)]
     async fn create_coin_split(
         &mut self,
@@ -2745,9 +3185,27 @@ where
             .iter()
             .fold(MicroMinotari::zero(), |acc, x| acc + x.wallet_output.value);
 
-        let fee =
-            self.get_fee_calc()
-                .calculate(fee_per_gram, 1, src_outputs.len(), 1, default_features_and_scripts_size);
+        let fee_calc = self.get_fee_calc();
+        let kernel_features = [KernelFeatures::empty()];
+        let output_types = [OutputFeatures::default().output_type];
+        let fee = fee_calc.calculate_with_features(
+            fee_per_gram,
+            &kernel_features,
+            src_outputs.len(),
+            &output_types,
+            default_features_and_scripts_size,
+        );
+        if fee_calc
+            .weighting()
+            .is_consolidation(&kernel_features, src_outputs.len(), &output_types)
+        {
+            debug!(
+                target: LOG_TARGET,
+                "Coin join of {} outputs is a consolidation, fee discounted to {}",
+                src_outputs.len(),
+                fee
+            );
+        }
 
         let accumulated_amount = accumulated_amount_with_fee.saturating_sub(fee);
 
@@ -2944,9 +3402,12 @@ where
             )
             .await?;
         let encryption_key = shared_secret_to_output_encryption_key(&shared_secret)?;
-        if let Ok((amount, spending_key, payment_id)) =
-            EncryptedData::decrypt_data(&encryption_key, &output.commitment, &output.encrypted_data)
-        {
+        if let Ok((amount, spending_key, payment_id)) = EncryptedData::decrypt_output_data(
+            &encryption_key,
+            &output.commitment,
+            &output.encrypted_data,
+            output.version,
+        ) {
             if output.verify_mask(&self.resources.factories.range_proof, &spending_key, amount.as_u64())? {
                 let spending_key_id = self.resources.key_manager.import_key(spending_key).await?;
                 let rewound_output = WalletOutput::new_with_rangeproof(
@@ -3121,6 +3582,150 @@ where
         Ok((tx_id, fee, amount - fee, tx))
     }
 
+    /// Create a key that this wallet can claw vaults back with. It is derived from the wallet seed, so it is never
+    /// stored and survives a wallet recovery.
+    async fn create_vault_recovery_key(&mut self) -> Result<PublicKey, OutputManagerError> {
+        let key = self
+            .resources
+            .key_manager
+            .get_next_key(TransactionKeyManagerBranch::RandomKey.get_branch_key())
+            .await?;
+        Ok(key.pub_key)
+    }
+
+    /// Export the opening of one of this wallet's vaults, to be handed to the recovery key holder
+    async fn export_vault_opening(&self, output_hash: HashOutput) -> Result<VaultOpening, OutputManagerError> {
+        let output = self.resources.db.get_unspent_output(output_hash)?.wallet_output;
+        if VaultScript::from_script(&output.script).is_none() {
+            return Err(OutputManagerError::InvalidArgument(format!(
+                "Output {} is not a vault",
+                output_hash
+            )));
+        }
+        let commitment_mask = self
+            .resources
+            .key_manager
+            .get_private_key(&output.spending_key_id)
+            .await?;
+        let transaction_output = output.to_transaction_output(&self.resources.key_manager).await?;
+        Ok(VaultOpening::new(transaction_output, output.value, commitment_mask))
+    }
+
+    /// Sweep a vault into this wallet through the recovery path, from the vault opening exported by its owner. This
+    /// wallet must hold the recovery key of the vault, which is looked up by its derivation path instead of being
+    /// imported. The swept output is an ordinary output of this wallet, so it is encrypted to this wallet's view key.
+    pub async fn create_vault_clawback_transaction(
+        &mut self,
+        opening: VaultOpening,
+        fee_per_gram: MicroMinotari,
+    ) -> Result<(TxId, MicroMinotari, MicroMinotari, Transaction), OutputManagerError> {
+        let vault = opening
+            .vault()
+            .ok_or_else(|| OutputManagerError::InvalidArgument("The output is not a vault".to_string()))?;
+        if !opening.output.verify_mask(
+            &self.resources.factories.range_proof,
+            &opening.commitment_mask,
+            opening.value.as_u64(),
+        )? {
+            return Err(OutputManagerError::InvalidArgument(
+                "The vault opening does not open the vault commitment".to_string(),
+            ));
+        }
+        let branch = TransactionKeyManagerBranch::RandomKey.get_branch_key();
+        let index = self
+            .resources
+            .key_manager
+            .find_key_index(branch.clone(), &vault.recovery_key)
+            .await
+            .map_err(|_| {
+                OutputManagerError::InvalidArgument("The vault recovery key does not belong to this wallet".to_string())
+            })?;
+        let recovery_key_id = KeyId::Managed { branch, index };
+        let commitment_mask_key_id = self.resources.key_manager.import_key(opening.commitment_mask).await?;
+
+        let amount = opening.value;
+        let vault_output = opening.output;
+        let input = WalletOutput::new_with_rangeproof(
+            vault_output.version,
+            amount,
+            commitment_mask_key_id,
+            vault_output.features,
+            vault_output.script,
+            VaultScript::clawback_input_data(),
+            recovery_key_id,
+            vault_output.sender_offset_public_key,
+            vault_output.metadata_signature,
+            0,
+            vault_output.covenant,
+            vault_output.encrypted_data,
+            vault_output.minimum_value_promise,
+            vault_output.proof,
+            PaymentId::Empty,
+        );
+
+        let mut builder = SenderTransactionProtocol::builder(
+            self.resources.consensus_constants.clone(),
+            self.resources.key_manager.clone(),
+        );
+        builder
+            .with_lock_height(0)
+            .with_fee_per_gram(fee_per_gram)
+            .with_message("Vault clawback".to_string())
+            .with_kernel_features(KernelFeatures::empty())
+            .with_prevent_fee_gt_amount(self.resources.config.prevent_fee_gt_amount)
+            .with_input(input)
+            .await?;
+
+        let (change_commitment_mask_key, change_script_key) = self
+            .resources
+            .key_manager
+            .get_next_commitment_mask_and_script_key()
+            .await?;
+        builder.with_change_data(
+            script!(PushPubKey(Box::new(change_script_key.pub_key.clone())))?,
+            ExecutionStack::default(),
+            change_script_key.key_id,
+            change_commitment_mask_key.key_id,
+            Covenant::default(),
+            self.resources.interactive_tari_address.clone(),
+        );
+
+        let mut stp = builder
+            .build()
+            .await
+            .map_err(|e| OutputManagerError::BuildError(e.message))?;
+
+        let tx_id = stp.get_tx_id()?;
+
+        let wallet_output = stp.get_change_output()?.ok_or_else(|| {
+            OutputManagerError::BuildError("There should be a change output metadata signature available".to_string())
+        })?;
+        let swept_output = DbWalletOutput::from_wallet_output(
+            wallet_output,
+            &self.resources.key_manager,
+            None,
+            OutputSource::VaultClawback,
+            Some(tx_id),
+            None,
+        )
+        .await?;
+
+        trace!(target: LOG_TARGET, "Clawing back vault with transaction ({}).", tx_id);
+
+        let fee = stp.get_fee_amount()?;
+
+        stp.finalize(&self.resources.key_manager).await?;
+
+        let tx = stp.into_transaction()?;
+
+        // The vault belongs to the owner's wallet, so only the swept output is recorded here
+        self.resources
+            .db
+            .encumber_outputs(tx_id, Vec::new(), vec![swept_output])?;
+        self.confirm_encumberance(tx_id)?;
+        Ok((tx_id, fee, amount - fee, tx))
+    }
+
     /// Persist a one-sided payment script for a Comms Public/Private key. These are the scripts that this wallet knows
     /// to look for when scanning for one-sided payments
     fn add_known_script(&mut self, known_script: KnownOneSidedPaymentScript) -> Result<(), OutputManagerError> {
@@ -3142,6 +3747,29 @@ where
         Ok(())
     }
 
+    /// Scans the outputs for those belonging to any of the wallet's keys in a single pass: outputs encrypted to the
+    /// view key or to one of the stored imported keys, whether their commitment mask is derived from a key manager
+    /// branch or has to be imported, outputs paying a stored imported script key, outputs paying a known one-sided
+    /// script key and stealth one-sided outputs paying one of the wallet's (sub-)addresses. Outputs that are recovered
+    /// as standard outputs are not scanned again for one-sided payments.
+    async fn scan_outputs_for_all_branches(
+        &mut self,
+        outputs: Vec<(TransactionOutput, Option<TxId>)>,
+    ) -> Result<Vec<RecoveredOutput>, OutputManagerError> {
+        let imported_key_ids = self.resources.key_manager.get_imported_key_ids().await?;
+        let mut recovered = StandardUtxoRecoverer::new(self.resources.key_manager.clone(), self.resources.db.clone())
+            .with_imported_keys(imported_key_ids)
+            .scan_and_recover_outputs(outputs.clone())
+            .await?;
+        let recovered_hashes = recovered.iter().map(|o| o.hash).collect::<HashSet<_>>();
+        let remaining = outputs
+            .into_iter()
+            .filter(|(output, _)| !recovered_hashes.contains(&output.hash()))
+            .collect();
+        recovered.extend(self.scan_outputs_for_one_sided_payments(remaining).await?);
+        Ok(recovered)
+    }
+
     // Scanning outputs addressed to this wallet
     #[allow(clippy::too_many_lines)]
     async fn scan_outputs_for_one_sided_payments(
@@ -3161,6 +3789,7 @@ where
         }
 
         let view_key = self.resources.key_manager.get_view_key().await?;
+        let sub_address_spend_keys = self.get_sub_address_spend_keys().await?;
 
         let mut scanned_outputs = vec![];
 
@@ -3176,9 +3805,12 @@ where
                     let encryption_key = shared_secret_to_output_encryption_key(&shared_secret)?;
                     let script_private_key = matched_key.clone().1;
 
-                    if let Ok((committed_value, spending_key, payment_id)) =
-                        EncryptedData::decrypt_data(&encryption_key, &output.commitment, &output.encrypted_data)
-                    {
+                    if let Ok((committed_value, spending_key, payment_id)) = EncryptedData::decrypt_output_data(
+                        &encryption_key,
+                        &output.commitment,
+                        &output.encrypted_data,
+                        output.version,
+                    ) {
                         if output.verify_mask(
                             &self.resources.factories.range_proof,
                             &spending_key,
@@ -3203,7 +3835,7 @@ where
                                 payment_id,
                             );
 
-                            scanned_outputs.push((rewound_output, OutputSource::OneSided, tx_id));
+                            scanned_outputs.push((rewound_output, OutputSource::OneSided, tx_id, None));
                         }
                     }
                 }
@@ -3217,7 +3849,12 @@ where
 
                     let encryption_key = shared_secret_to_output_encryption_key(&shared_secret)?;
                     if let Ok((committed_value, commitment_mask_private_key, payment_id)) =
-                        EncryptedData::decrypt_data(&encryption_key, &output.commitment, &output.encrypted_data)
+                        EncryptedData::decrypt_output_data(
+                            &encryption_key,
+                            &output.commitment,
+                            &output.encrypted_data,
+                            output.version,
+                        )
                     {
                         let commitment_mask_key_id = &self
                             .resources
@@ -3230,25 +3867,32 @@ where
                             &commitment_mask_private_key,
                             committed_value.into(),
                         )? {
-                            let script_spending_key = self
+                            // The script key is `K_s + H(k_mask)·G`, so strip the stealth component to recover the
+                            // spend key that the sender used and match it against our (sub-)address spend keys.
+                            let stealth_component = self
                                 .resources
                                 .key_manager
-                                .stealth_address_script_spending_key(
-                                    commitment_mask_key_id,
-                                    &self.resources.key_manager.get_spend_key().await?.pub_key,
-                                )
+                                .stealth_address_script_spending_key(commitment_mask_key_id, &PublicKey::default())
                                 .await?;
-
-                            if script_spending_key != **scanned_pk {
+                            let candidate_spend_key = scanned_pk.as_ref() - &stealth_component;
+                            let Some(sub_address_index) = sub_address_spend_keys.get(&candidate_spend_key).copied()
+                            else {
                                 continue;
-                            }
+                            };
                             let commitment_mask = self
                                 .resources
                                 .key_manager
                                 .import_key(commitment_mask_private_key)
                                 .await?;
-                            let script_key = TariKeyId::Derived {
-                                key: SerializedKeyString::from(commitment_mask.to_string()),
+                            let script_key = if sub_address_index == 0 {
+                                TariKeyId::Derived {
+                                    key: SerializedKeyString::from(commitment_mask.to_string()),
+                                }
+                            } else {
+                                self.resources
+                                    .key_manager
+                                    .import_sub_address_script_key(&commitment_mask, sub_address_index)
+                                    .await?
                             };
 
                             let rewound_output = WalletOutput::new_with_rangeproof(
@@ -3269,7 +3913,12 @@ where
                                 payment_id,
                             );
 
-                            scanned_outputs.push((rewound_output, OutputSource::StealthOneSided, tx_id));
+                            scanned_outputs.push((
+                                rewound_output,
+                                OutputSource::StealthOneSided,
+                                tx_id,
+                                Some(sub_address_index).filter(|i| *i > 0),
+                            ));
                         }
                     }
                 }
@@ -3279,16 +3928,27 @@ where
         self.import_onesided_outputs(scanned_outputs).await
     }
 
+    /// Returns the spend keys of the primary address (index 0) and of the configured number of sub-addresses, keyed to
+    /// their index.
+    async fn get_sub_address_spend_keys(&self) -> Result<HashMap<PublicKey, u64>, OutputManagerError> {
+        let mut spend_keys = HashMap::new();
+        for index in 0..=self.resources.config.sub_address_lookahead {
+            let spend_key = self.resources.key_manager.get_sub_address_spend_key(index).await?;
+            spend_keys.insert(spend_key, index);
+        }
+        Ok(spend_keys)
+    }
+
     // Import scanned outputs into the wallet
     async fn import_onesided_outputs(
         &self,
-        scanned_outputs: Vec<(WalletOutput, OutputSource, Option<TxId>)>,
+        scanned_outputs: Vec<(WalletOutput, OutputSource, Option<TxId>, Option<u64>)>,
     ) -> Result<Vec<RecoveredOutput>, OutputManagerError> {
         let mut rewound_outputs = Vec::with_capacity(scanned_outputs.len());
 
-        for (output, output_source, tx_id) in scanned_outputs {
+        for (output, output_source, tx_id, sub_address_index) in scanned_outputs {
             let tx_id = tx_id.unwrap_or(TxId::new_random());
-            let db_output = DbWalletOutput::from_wallet_output(
+            let mut db_output = DbWalletOutput::from_wallet_output(
                 output.clone(),
                 &self.resources.key_manager,
                 None,
@@ -3297,6 +3957,7 @@ where
                 None,
             )
             .await?;
+            db_output.sub_address_index = sub_address_index;
             let hash = db_output.hash;
 
             match self
@@ -3312,7 +3973,12 @@ where
                         db_output.wallet_output.value,
                     );
 
-                    rewound_outputs.push(RecoveredOutput { output, tx_id, hash })
+                    rewound_outputs.push(RecoveredOutput {
+                        output,
+                        tx_id,
+                        hash,
+                        source: output_source,
+                    })
                 },
                 Err(OutputManagerStorageError::DuplicateOutput) => {
                     warn!(
@@ -3443,6 +4109,93 @@ impl UtxoSelection {
     }
 }
 
+/// How far a validator node registration made by this wallet has progressed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidatorNodeRegistrationStatus {
+    /// The registration has not been mined yet
+    NotMined,
+    /// The registration has been mined and the validator node is registered from the next epoch
+    AwaitingActivation,
+    /// The validator node is registered in the current epoch
+    Active,
+    /// The validity period of the registration has passed
+    Expired,
+    /// The registration output was spent, which deregisters the validator node
+    Deregistered,
+}
+
+impl fmt::Display for ValidatorNodeRegistrationStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidatorNodeRegistrationStatus::NotMined => write!(f, "Not mined"),
+            ValidatorNodeRegistrationStatus::AwaitingActivation => write!(f, "Awaiting activation"),
+            ValidatorNodeRegistrationStatus::Active => write!(f, "Active"),
+            ValidatorNodeRegistrationStatus::Expired => write!(f, "Expired"),
+            ValidatorNodeRegistrationStatus::Deregistered => write!(f, "Deregistered"),
+        }
+    }
+}
+
+/// A validator node registration output of this wallet, with the epochs it is valid for once mined
+#[derive(Debug, Clone)]
+pub struct ValidatorNodeRegistrationInfo {
+    pub validator_node_public_key: PublicKey,
+    pub commitment: Commitment,
+    pub value: MicroMinotari,
+    pub tx_id: Option<TxId>,
+    pub status: ValidatorNodeRegistrationStatus,
+    pub mined_height: Option<u64>,
+    pub confirmations: u64,
+    /// The first epoch in which the validator node is registered
+    pub start_epoch: Option<VnEpoch>,
+    /// The epoch from which the validator node is no longer registered
+    pub end_epoch: Option<VnEpoch>,
+    /// The height of the first block of the end epoch
+    pub expiry_height: Option<u64>,
+}
+
+impl ValidatorNodeRegistrationInfo {
+    /// Returns the registration held in the output, unless the output was cancelled or is invalid. The epochs are
+    /// derived the same way the base node derives them when the registration is mined.
+    fn from_output(output: &DbWalletOutput, tip_height: Option<u64>, constants: &ConsensusConstants) -> Option<Self> {
+        if matches!(output.status, OutputStatus::Invalid | OutputStatus::CancelledInbound) {
+            return None;
+        }
+        let registration = output.wallet_output.features.validator_node_registration()?;
+        let mined_height = output.mined_height;
+        let confirmations = match (mined_height, tip_height) {
+            (Some(mined_height), Some(tip_height)) => tip_height.saturating_sub(mined_height).saturating_add(1),
+            _ => 0,
+        };
+        let start_epoch = mined_height.map(|h| constants.block_height_to_epoch(h) + VnEpoch(1));
+        let end_epoch = start_epoch.map(|e| e + constants.validator_node_validity_period_epochs());
+        let current_epoch = constants.block_height_to_epoch(tip_height.unwrap_or_default());
+        let status = if matches!(output.status, OutputStatus::Spent | OutputStatus::SpentMinedUnconfirmed) {
+            ValidatorNodeRegistrationStatus::Deregistered
+        } else if start_epoch.is_none() {
+            ValidatorNodeRegistrationStatus::NotMined
+        } else if start_epoch.is_some_and(|e| current_epoch < e) {
+            ValidatorNodeRegistrationStatus::AwaitingActivation
+        } else if end_epoch.is_some_and(|e| current_epoch < e) {
+            ValidatorNodeRegistrationStatus::Active
+        } else {
+            ValidatorNodeRegistrationStatus::Expired
+        };
+        Some(Self {
+            validator_node_public_key: registration.public_key().clone(),
+            commitment: output.commitment.clone(),
+            value: output.wallet_output.value,
+            tx_id: output.received_in_tx_id,
+            status,
+            mined_height,
+            confirmations,
+            start_epoch,
+            end_epoch,
+            expiry_height: end_epoch.map(|e| constants.epoch_to_block_height(e)),
+        })
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct OutputInfoByTxId {
     pub statuses: Vec<OutputStatus>,
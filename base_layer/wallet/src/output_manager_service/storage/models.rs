@@ -57,6 +57,8 @@ pub struct DbWalletOutput {
     pub received_in_tx_id: Option<TxId>,
     pub spent_in_tx_id: Option<TxId>,
     pub payment_id: PaymentId,
+    /// The index of the deterministic sub-address this output was received on, if any
+    pub sub_address_index: Option<u64>,
 }
 
 impl DbWalletOutput {
@@ -85,6 +87,7 @@ impl DbWalletOutput {
             received_in_tx_id,
             spent_in_tx_id,
             payment_id,
+            sub_address_index: None,
         })
     }
 }
@@ -39,6 +39,8 @@ pub enum OutputSource {
     Burn,
     ValidatorNodeRegistration,
     CodeTemplateRegistration,
+    Vault,
+    VaultClawback,
 }
 
 impl TryFrom<i32> for OutputSource {
@@ -56,9 +58,11 @@ impl TryFrom<i32> for OutputSource {
             7 => OutputSource::Burn,
             8 => OutputSource::ValidatorNodeRegistration,
             9 => OutputSource::CodeTemplateRegistration,
+            10 => OutputSource::Vault,
+            11 => OutputSource::VaultClawback,
             _ => {
                 return Err(OutputManagerStorageError::ConversionError {
-                    reason: "Was expecting value between 0 and 7 for OutputSource".to_string(),
+                    reason: "Was expecting value between 0 and 11 for OutputSource".to_string(),
                 })
             },
         })
@@ -33,7 +33,11 @@ use diesel::{
 use log::*;
 pub use new_output_sql::NewOutputSql;
 pub use output_sql::OutputSql;
-use tari_common_sqlite::{sqlite_connection_pool::PooledDbConnection, util::diesel_ext::ExpectedRowsExtension};
+use tari_common_sqlite::{
+    integrity::{quarantine_row, IntegrityIssue, IntegrityReport, RowKey},
+    sqlite_connection_pool::PooledDbConnection,
+    util::diesel_ext::ExpectedRowsExtension,
+};
 use tari_common_types::{
     transaction::TxId,
     types::{Commitment, FixedHash},
@@ -114,6 +118,53 @@ impl OutputManagerSqliteDatabase {
         }
         Ok(())
     }
+
+    /// Checks that every output can be deserialized and that its mined and spent heights are recorded together with
+    /// their blocks. If `quarantine` is set, the inconsistent outputs are moved out of the way. Returns the report and
+    /// the consistent outputs with their row ids, so that callers can check them against the key manager.
+    pub fn verify_integrity(
+        &self,
+        quarantine: bool,
+    ) -> Result<(IntegrityReport, Vec<(i32, DbWalletOutput)>), OutputManagerStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        let mut report = IntegrityReport::default();
+        let mut outputs = Vec::new();
+
+        for row in OutputSql::index(&mut conn)? {
+            report.rows_checked += 1;
+            let id = row.id;
+            let problem = if row.mined_height.is_some() != row.mined_in_block.is_some() {
+                Some("The mined height and block hash must be set together".to_string())
+            } else if row.marked_deleted_at_height.is_some() != row.marked_deleted_in_block.is_some() {
+                Some("The spent height and block hash must be set together".to_string())
+            } else {
+                match row.to_db_wallet_output() {
+                    Ok(output) => {
+                        outputs.push((id, output));
+                        None
+                    },
+                    Err(e) => Some(e.to_string()),
+                }
+            };
+            if let Some(problem) = problem {
+                let mut issue = IntegrityIssue::new("outputs", id, problem);
+                if quarantine {
+                    quarantine_row(&mut conn, "outputs", "id", &RowKey::Integer(id))?;
+                    issue.quarantined = true;
+                }
+                report.issues.push(issue);
+            }
+        }
+
+        Ok((report, outputs))
+    }
+
+    /// Moves the output with row id `id` out of the way, for outputs found to be inconsistent
+    pub fn quarantine_output(&self, id: i32) -> Result<(), OutputManagerStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        quarantine_row(&mut conn, "outputs", "id", &RowKey::Integer(id))?;
+        Ok(())
+    }
 }
 
 impl OutputManagerBackend for OutputManagerSqliteDatabase {
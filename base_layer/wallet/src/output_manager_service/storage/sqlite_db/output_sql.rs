@@ -110,6 +110,7 @@ pub struct OutputSql {
     pub source: i32,
     pub last_validation_timestamp: Option<NaiveDateTime>,
     pub payment_id: Option<Vec<u8>>,
+    pub sub_address_index: Option<i64>,
 }
 
 impl OutputSql {
@@ -823,6 +824,7 @@ impl OutputSql {
             received_in_tx_id: self.received_in_tx_id.map(|d| (d as u64).into()),
             spent_in_tx_id: self.spent_in_tx_id.map(|d| (d as u64).into()),
             payment_id,
+            sub_address_index: self.sub_address_index.map(|i| i as u64),
         })
     }
 }
@@ -0,0 +1,46 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use tari_p2p::push_relay::PushRelayError;
+use thiserror::Error;
+
+use crate::error::WalletStorageError;
+
+#[derive(Debug, Error)]
+pub enum PushNotificationError {
+    #[error("Invalid push notification key, it must be {0} bytes")]
+    InvalidNotificationKey(usize),
+    #[error("Malformed push notification registration: {0}")]
+    MalformedRegistration(String),
+    #[error("Push notification encryption error: {0}")]
+    EncryptionError(String),
+    #[error("Push notification serialization error: {0}")]
+    SerializationError(#[from] serde_json::Error),
+    #[error("Push notification relay error: {0}")]
+    RelayError(#[from] PushRelayError),
+    #[error("The privacy profile only permits a push notification relay on a loopback address, not {0}")]
+    ClearnetRelay(String),
+    #[error("This wallet was built without support for push notification relays")]
+    RelayNotSupported,
+    #[error("Wallet storage error: `{0}`")]
+    WalletStorageError(#[from] WalletStorageError),
+}
@@ -0,0 +1,139 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tari_utilities::{hex::Hex, Hidden};
+use tokio::sync::watch;
+
+use crate::push_notification_service::{
+    config::PushNotificationServiceConfig,
+    error::PushNotificationError,
+    payload::PushNotificationPayload,
+    NOTIFICATION_KEY_SIZE,
+};
+
+/// The device a wallet's push notifications are delivered to, and the relay that delivers them. The device token is
+/// opaque to the wallet and is only meaningful to the relay, while the notification key is shared between the wallet
+/// and the app on the device so that the relay cannot read the notifications it forwards.
+#[derive(Clone, Debug)]
+pub struct PushNotificationRegistration {
+    pub relay_url: String,
+    pub device_token: String,
+    notification_key: Hidden<[u8; NOTIFICATION_KEY_SIZE]>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoredRegistration {
+    relay_url: String,
+    device_token: String,
+    notification_key: String,
+}
+
+impl PushNotificationRegistration {
+    pub fn new(
+        relay_url: String,
+        device_token: String,
+        notification_key: &[u8],
+    ) -> Result<Self, PushNotificationError> {
+        let key = <[u8; NOTIFICATION_KEY_SIZE]>::try_from(notification_key)
+            .map_err(|_| PushNotificationError::InvalidNotificationKey(NOTIFICATION_KEY_SIZE))?;
+        Ok(Self {
+            relay_url,
+            device_token,
+            notification_key: Hidden::hide(key),
+        })
+    }
+
+    pub fn notification_key(&self) -> &[u8; NOTIFICATION_KEY_SIZE] {
+        self.notification_key.reveal()
+    }
+
+    /// The hex encoded, encrypted `BlockMatched` notification that a light wallet server posts to the relay on the
+    /// wallet's behalf. The server can forward it but cannot read it.
+    pub fn wake_payload(&self) -> Result<String, PushNotificationError> {
+        Ok(PushNotificationPayload::block_matched()
+            .encrypt(self.notification_key())?
+            .to_hex())
+    }
+
+    /// Serializes the registration for storage in the (encrypted) wallet database
+    pub fn to_storage_string(&self) -> Result<String, PushNotificationError> {
+        let stored = StoredRegistration {
+            relay_url: self.relay_url.clone(),
+            device_token: self.device_token.clone(),
+            notification_key: self.notification_key.reveal().to_hex(),
+        };
+        Ok(serde_json::to_string(&stored)?)
+    }
+
+    pub fn from_storage_string(value: &str) -> Result<Self, PushNotificationError> {
+        let stored: StoredRegistration = serde_json::from_str(value)?;
+        let key = Vec::<u8>::from_hex(&stored.notification_key)
+            .map_err(|e| PushNotificationError::MalformedRegistration(e.to_string()))?;
+        Self::new(stored.relay_url, stored.device_token, &key)
+    }
+}
+
+/// The Push Notification Service Handle is used to register and unregister the device that receives the wallet's
+/// push notifications
+#[derive(Clone)]
+pub struct PushNotificationHandle {
+    config: PushNotificationServiceConfig,
+    registration: Arc<watch::Sender<Option<PushNotificationRegistration>>>,
+}
+
+impl PushNotificationHandle {
+    pub fn new(
+        config: PushNotificationServiceConfig,
+        registration: watch::Sender<Option<PushNotificationRegistration>>,
+    ) -> Self {
+        Self {
+            config,
+            registration: Arc::new(registration),
+        }
+    }
+
+    /// Checks that the relay is permitted by the privacy profile without registering it
+    pub fn check_relay(&self, relay_url: &str) -> Result<(), PushNotificationError> {
+        if self.config.permits_relay(relay_url) {
+            Ok(())
+        } else {
+            Err(PushNotificationError::ClearnetRelay(relay_url.to_string()))
+        }
+    }
+
+    pub fn register(&self, registration: PushNotificationRegistration) -> Result<(), PushNotificationError> {
+        self.check_relay(&registration.relay_url)?;
+        self.registration.send_replace(Some(registration));
+        Ok(())
+    }
+
+    pub fn unregister(&self) {
+        self.registration.send_replace(None);
+    }
+
+    pub fn registration(&self) -> Option<PushNotificationRegistration> {
+        self.registration.borrow().clone()
+    }
+}
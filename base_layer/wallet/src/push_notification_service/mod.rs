@@ -0,0 +1,140 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Opt-in push notifications for incoming transactions. A mobile app registers a relay, a device token and a
+//! notification key with the wallet. Whenever a transaction addressed to the wallet arrives, the wallet encrypts a
+//! small notification with that key and posts it to the relay, which forwards it to the device so that a backgrounded
+//! app can wake up and complete the receive. Nothing is sent until a device is registered.
+//!
+//! A backgrounded app is usually suspended, so it cannot post notifications for itself. While it is running, a light
+//! wallet client also registers an encrypted `BlockMatched` notification with its light wallet server, which posts it
+//! to the relay whenever a new block matches the wallet's filter items. When the privacy profile is enabled, only a
+//! relay on a loopback address is accepted.
+
+pub mod config;
+pub mod error;
+pub mod handle;
+pub mod payload;
+pub mod service;
+
+use log::*;
+use tari_service_framework::{async_trait, ServiceInitializationError, ServiceInitializer, ServiceInitializerContext};
+use tokio::sync::watch;
+
+use crate::{
+    push_notification_service::{
+        config::PushNotificationServiceConfig,
+        handle::{PushNotificationHandle, PushNotificationRegistration},
+        service::PushNotificationService,
+    },
+    storage::database::{WalletBackend, WalletDatabase},
+    transaction_service::handle::TransactionServiceHandle,
+};
+
+const LOG_TARGET: &str = "wallet::push_notification_service";
+
+/// The size of the symmetric key shared between the wallet and the app that receives its notifications
+pub const NOTIFICATION_KEY_SIZE: usize = 32;
+/// The client key value under which the device registration is persisted
+pub const PUSH_NOTIFICATION_REGISTRATION_KEY: &str = "push_notification_registration";
+
+pub struct PushNotificationServiceInitializer<T>
+where T: WalletBackend + 'static
+{
+    config: PushNotificationServiceConfig,
+    db: WalletDatabase<T>,
+}
+
+impl<T> PushNotificationServiceInitializer<T>
+where T: WalletBackend + 'static
+{
+    pub fn new(config: PushNotificationServiceConfig, db: WalletDatabase<T>) -> Self {
+        Self { config, db }
+    }
+
+    fn load_registration(&self) -> Option<PushNotificationRegistration> {
+        let value = match self
+            .db
+            .get_client_key_value(PUSH_NOTIFICATION_REGISTRATION_KEY.to_string())
+        {
+            Ok(value) => value?,
+            Err(e) => {
+                warn!(target: LOG_TARGET, "Could not read push notification registration: {}", e);
+                return None;
+            },
+        };
+        let registration = PushNotificationRegistration::from_storage_string(&value)
+            .inspect_err(|e| warn!(target: LOG_TARGET, "Ignoring push notification registration: {}", e))
+            .ok()?;
+        if !self.config.permits_relay(&registration.relay_url) {
+            warn!(
+                target: LOG_TARGET,
+                "Ignoring push notification registration, the privacy profile does not permit the relay at {}",
+                registration.relay_url
+            );
+            return None;
+        }
+        Some(registration)
+    }
+}
+
+#[async_trait]
+impl<T> ServiceInitializer for PushNotificationServiceInitializer<T>
+where T: WalletBackend + 'static
+{
+    async fn initialize(&mut self, context: ServiceInitializerContext) -> Result<(), ServiceInitializationError> {
+        let (registration_sender, registration_receiver) = watch::channel(self.load_registration());
+
+        // The handle is always available so that registrations are persisted even if they cannot be acted on
+        context.register_handle(PushNotificationHandle::new(self.config.clone(), registration_sender));
+
+        if cfg!(not(feature = "push-notifications")) {
+            debug!(
+                target: LOG_TARGET,
+                "Wallet was built without the `push-notifications` feature, push notifications are disabled"
+            );
+            return Ok(());
+        }
+        info!(target: LOG_TARGET, "Wallet push notification service initializing.");
+
+        let config = self.config.clone();
+        context.spawn_when_ready(move |handles| async move {
+            let transaction_service = handles.expect_handle::<TransactionServiceHandle>();
+
+            let result = PushNotificationService::new(
+                config,
+                registration_receiver,
+                transaction_service,
+                handles.get_shutdown_signal(),
+            )
+            .start()
+            .await;
+
+            info!(
+                target: LOG_TARGET,
+                "Wallet Push Notification Service shutdown with result {:?}", result
+            );
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,132 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use chacha20poly1305::{Key, KeyInit, XChaCha20Poly1305};
+use serde::{Deserialize, Serialize};
+use tari_common_types::{
+    encryption::{decrypt_bytes_integral_nonce, encrypt_bytes_integral_nonce},
+    transaction::TxId,
+};
+use tari_utilities::Hidden;
+
+use crate::{
+    push_notification_service::{error::PushNotificationError, NOTIFICATION_KEY_SIZE},
+    transaction_service::handle::TransactionEvent,
+};
+
+const PUSH_NOTIFICATION_DOMAIN: &[u8] = b"com.tari.base_layer.wallet.push_notification";
+
+/// The events that result in a push notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PushNotificationKind {
+    /// An interactive transaction was received and is waiting to be finalized by the sender
+    ReceivedTransaction,
+    /// The sender finalized an interactive transaction
+    ReceivedFinalizedTransaction,
+    /// A one-sided or imported transaction was found on chain
+    TransactionImported,
+    /// A light wallet server found a new block that pays to or spends one of the wallet's watched items, while the
+    /// wallet itself may not be running
+    BlockMatched,
+}
+
+/// The plaintext of a push notification. Only the app holding the notification key can read it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PushNotificationPayload {
+    pub kind: PushNotificationKind,
+    /// The transaction that the notification is for. Not known for `BlockMatched` notifications.
+    pub tx_id: Option<TxId>,
+}
+
+impl PushNotificationPayload {
+    pub fn from_event(event: &TransactionEvent) -> Option<Self> {
+        let (kind, tx_id) = match event {
+            TransactionEvent::ReceivedTransaction(tx_id) => (PushNotificationKind::ReceivedTransaction, *tx_id),
+            TransactionEvent::ReceivedFinalizedTransaction(tx_id) => {
+                (PushNotificationKind::ReceivedFinalizedTransaction, *tx_id)
+            },
+            TransactionEvent::TransactionImported(tx_id) => (PushNotificationKind::TransactionImported, *tx_id),
+            _ => return None,
+        };
+        Some(Self {
+            kind,
+            tx_id: Some(tx_id),
+        })
+    }
+
+    /// The notification a light wallet server sends on the wallet's behalf. It is encrypted by the wallet in advance,
+    /// so the server never learns the notification key.
+    pub fn block_matched() -> Self {
+        Self {
+            kind: PushNotificationKind::BlockMatched,
+            tx_id: None,
+        }
+    }
+
+    /// Encrypts the payload with the notification key. The result is the nonce followed by the ciphertext and tag.
+    pub fn encrypt(&self, notification_key: &[u8; NOTIFICATION_KEY_SIZE]) -> Result<Vec<u8>, PushNotificationError> {
+        let plaintext = Hidden::hide(serde_json::to_vec(self)?);
+        encrypt_bytes_integral_nonce(&cipher(notification_key), PUSH_NOTIFICATION_DOMAIN.to_vec(), plaintext)
+            .map_err(PushNotificationError::EncryptionError)
+    }
+
+    pub fn decrypt(
+        notification_key: &[u8; NOTIFICATION_KEY_SIZE],
+        ciphertext: &[u8],
+    ) -> Result<Self, PushNotificationError> {
+        let plaintext =
+            decrypt_bytes_integral_nonce(&cipher(notification_key), PUSH_NOTIFICATION_DOMAIN.to_vec(), ciphertext)
+                .map_err(PushNotificationError::EncryptionError)?;
+        Ok(serde_json::from_slice(&plaintext)?)
+    }
+}
+
+fn cipher(notification_key: &[u8; NOTIFICATION_KEY_SIZE]) -> XChaCha20Poly1305 {
+    XChaCha20Poly1305::new(Key::from_slice(notification_key))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_encrypts_and_decrypts_payloads() {
+        let key = [7u8; NOTIFICATION_KEY_SIZE];
+        let payload =
+            PushNotificationPayload::from_event(&TransactionEvent::ReceivedTransaction(123u64.into())).unwrap();
+        let ciphertext = payload.encrypt(&key).unwrap();
+        assert_eq!(PushNotificationPayload::decrypt(&key, &ciphertext).unwrap(), payload);
+
+        let other_key = [8u8; NOTIFICATION_KEY_SIZE];
+        assert!(PushNotificationPayload::decrypt(&other_key, &ciphertext).is_err());
+        assert!(PushNotificationPayload::decrypt(&key, &ciphertext[1..]).is_err());
+    }
+
+    #[test]
+    fn it_only_notifies_for_incoming_transactions() {
+        assert!(PushNotificationPayload::from_event(&TransactionEvent::TransactionBroadcast(1u64.into())).is_none());
+        let payload = PushNotificationPayload::from_event(&TransactionEvent::TransactionImported(2u64.into())).unwrap();
+        assert_eq!(payload.kind, PushNotificationKind::TransactionImported);
+        assert_eq!(payload.tx_id, Some(2u64.into()));
+    }
+}
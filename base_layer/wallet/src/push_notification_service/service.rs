@@ -0,0 +1,131 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use log::*;
+use tari_p2p::push_relay::PushRelayRequest;
+use tari_shutdown::ShutdownSignal;
+use tari_utilities::hex::Hex;
+use tokio::sync::{broadcast, watch};
+
+use crate::{
+    push_notification_service::{
+        config::PushNotificationServiceConfig,
+        error::PushNotificationError,
+        handle::PushNotificationRegistration,
+        payload::PushNotificationPayload,
+    },
+    transaction_service::handle::{TransactionEvent, TransactionServiceHandle},
+};
+
+const LOG_TARGET: &str = "wallet::push_notification_service::service";
+
+/// The push notification service posts an encrypted notification to the registered relay for every incoming
+/// transaction while a device is registered, so that the relay can wake the app on that device.
+pub struct PushNotificationService {
+    #[cfg_attr(not(feature = "push-notifications"), allow(dead_code))]
+    config: PushNotificationServiceConfig,
+    registration: watch::Receiver<Option<PushNotificationRegistration>>,
+    transaction_service: TransactionServiceHandle,
+    shutdown_signal: ShutdownSignal,
+}
+
+impl PushNotificationService {
+    pub fn new(
+        config: PushNotificationServiceConfig,
+        registration: watch::Receiver<Option<PushNotificationRegistration>>,
+        transaction_service: TransactionServiceHandle,
+        shutdown_signal: ShutdownSignal,
+    ) -> Self {
+        Self {
+            config,
+            registration,
+            transaction_service,
+            shutdown_signal,
+        }
+    }
+
+    pub async fn start(self) -> Result<(), PushNotificationError> {
+        let mut event_stream = self.transaction_service.get_event_stream();
+        let mut shutdown_signal = self.shutdown_signal.clone();
+        debug!(target: LOG_TARGET, "Push Notification Service started");
+        loop {
+            tokio::select! {
+                event = event_stream.recv() => match event {
+                    Ok(event) => self.handle_event(&event).await,
+                    Err(broadcast::error::RecvError::Lagged(n)) => {
+                        warn!(target: LOG_TARGET, "Push Notification Service missed {} transaction events", n);
+                    },
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+                _ = shutdown_signal.wait() => {
+                    info!(
+                        target: LOG_TARGET,
+                        "Push Notification Service shutting down because the shutdown signal was received"
+                    );
+                    break;
+                },
+            }
+        }
+        Ok(())
+    }
+
+    async fn handle_event(&self, event: &TransactionEvent) {
+        let Some(payload) = PushNotificationPayload::from_event(event) else {
+            return;
+        };
+        let Some(registration) = self.registration.borrow().clone() else {
+            trace!(target: LOG_TARGET, "No device registered for push notifications");
+            return;
+        };
+        if let Err(e) = self.notify(&registration, &payload).await {
+            warn!(
+                target: LOG_TARGET,
+                "Failed to send {:?} push notification: {}", payload.kind, e
+            );
+        }
+    }
+
+    async fn notify(
+        &self,
+        registration: &PushNotificationRegistration,
+        payload: &PushNotificationPayload,
+    ) -> Result<(), PushNotificationError> {
+        let request = PushRelayRequest {
+            device_token: registration.device_token.clone(),
+            payload: payload.encrypt(registration.notification_key())?.to_hex(),
+        };
+        self.post_to_relay(&registration.relay_url, &request).await?;
+        debug!(target: LOG_TARGET, "Sent {:?} push notification", payload.kind);
+        Ok(())
+    }
+
+    #[cfg(feature = "push-notifications")]
+    async fn post_to_relay(&self, relay_url: &str, request: &PushRelayRequest) -> Result<(), PushNotificationError> {
+        tari_p2p::push_relay::post_to_relay(relay_url, request, self.config.relay_request_timeout).await?;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "push-notifications"))]
+    async fn post_to_relay(&self, _relay_url: &str, _request: &PushRelayRequest) -> Result<(), PushNotificationError> {
+        Err(PushNotificationError::RelayNotSupported)
+    }
+}
@@ -0,0 +1,19 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReorgMonitorServiceConfig {
+    /// The size of the channel that reorg events are published on
+    pub event_channel_size: usize,
+}
+
+impl Default for ReorgMonitorServiceConfig {
+    fn default() -> Self {
+        Self {
+            event_channel_size: 100,
+        }
+    }
+}
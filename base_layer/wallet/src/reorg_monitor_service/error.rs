@@ -0,0 +1,31 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use tari_comms::protocol::rpc::RpcError;
+use tari_service_framework::reply_channel::TransportChannelError;
+use thiserror::Error;
+
+use crate::{
+    output_manager_service::error::{OutputManagerError, OutputManagerStorageError},
+    transaction_service::error::{TransactionServiceError, TransactionStorageError},
+};
+
+#[derive(Debug, Error)]
+pub enum ReorgMonitorError {
+    #[error("No base node is available to check the chain against")]
+    NoBaseNode,
+    #[error("Base node RPC error: `{0}`")]
+    RpcError(#[from] RpcError),
+    #[error("Invalid block header from the base node: {0}")]
+    InvalidBlockHeader(String),
+    #[error("Output manager storage error: `{0}`")]
+    OutputManagerStorageError(#[from] OutputManagerStorageError),
+    #[error("Transaction storage error: `{0}`")]
+    TransactionStorageError(#[from] TransactionStorageError),
+    #[error("Output manager error: `{0}`")]
+    OutputManagerError(#[from] OutputManagerError),
+    #[error("Transaction service error: `{0}`")]
+    TransactionServiceError(#[from] TransactionServiceError),
+    #[error("Transport channel error: `{0}`")]
+    TransportChannelError(#[from] TransportChannelError),
+}
@@ -0,0 +1,74 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::{fmt, sync::Arc};
+
+use tari_service_framework::reply_channel::SenderService;
+use tokio::sync::broadcast;
+use tower::Service;
+
+use crate::reorg_monitor_service::{
+    error::ReorgMonitorError,
+    reorg::{ReorgHandled, ReorgMonitorEvent},
+};
+
+pub type ReorgMonitorEventSender = broadcast::Sender<Arc<ReorgMonitorEvent>>;
+pub type ReorgMonitorEventReceiver = broadcast::Receiver<Arc<ReorgMonitorEvent>>;
+
+#[derive(Debug)]
+pub enum ReorgMonitorRequest {
+    CheckForReorg,
+    GetLastReorg,
+}
+
+impl fmt::Display for ReorgMonitorRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CheckForReorg => write!(f, "CheckForReorg"),
+            Self::GetLastReorg => write!(f, "GetLastReorg"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ReorgMonitorResponse {
+    Reorg(Option<ReorgHandled>),
+}
+
+/// Checks the blocks that the wallet's transactions and outputs were mined in against the main chain of the base node
+/// whenever a new block is detected, and reverts everything that was mined in reorged blocks.
+#[derive(Clone)]
+pub struct ReorgMonitorHandle {
+    handle: SenderService<ReorgMonitorRequest, Result<ReorgMonitorResponse, ReorgMonitorError>>,
+    event_stream_sender: ReorgMonitorEventSender,
+}
+
+impl ReorgMonitorHandle {
+    pub fn new(
+        handle: SenderService<ReorgMonitorRequest, Result<ReorgMonitorResponse, ReorgMonitorError>>,
+        event_stream_sender: ReorgMonitorEventSender,
+    ) -> Self {
+        Self {
+            handle,
+            event_stream_sender,
+        }
+    }
+
+    pub fn get_event_stream(&self) -> ReorgMonitorEventReceiver {
+        self.event_stream_sender.subscribe()
+    }
+
+    /// Checks for a reorg now, and returns what was reverted if one was found
+    pub async fn check_for_reorg(&mut self) -> Result<Option<ReorgHandled>, ReorgMonitorError> {
+        match self.handle.call(ReorgMonitorRequest::CheckForReorg).await?? {
+            ReorgMonitorResponse::Reorg(reorg) => Ok(reorg),
+        }
+    }
+
+    /// Returns the last reorg that was handled since the wallet started
+    pub async fn get_last_reorg(&mut self) -> Result<Option<ReorgHandled>, ReorgMonitorError> {
+        match self.handle.call(ReorgMonitorRequest::GetLastReorg).await?? {
+            ReorgMonitorResponse::Reorg(reorg) => Ok(reorg),
+        }
+    }
+}
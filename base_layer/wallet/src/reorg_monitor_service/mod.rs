@@ -0,0 +1,115 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Keeps the wallet consistent with the main chain of its base node through reorgs. Whenever a new block is detected,
+//! the blocks that transactions and outputs were recorded as mined and spent in are compared with the main chain, from
+//! the highest down. Transactions mined in reorged blocks are reverted to pending, outputs mined or spent in them are
+//! unmined or unspent, a revalidation of all transactions and outputs is started, and a single event summarising the
+//! reorg is published.
+
+pub mod config;
+pub mod error;
+pub mod handle;
+pub mod reorg;
+pub mod service;
+
+use log::*;
+use tari_service_framework::{
+    async_trait,
+    reply_channel,
+    ServiceInitializationError,
+    ServiceInitializer,
+    ServiceInitializerContext,
+};
+use tokio::sync::broadcast;
+
+use crate::{
+    base_node_service::handle::BaseNodeServiceHandle,
+    connectivity_service::WalletConnectivityHandle,
+    output_manager_service::{
+        handle::OutputManagerHandle,
+        storage::database::{OutputManagerBackend, OutputManagerDatabase},
+    },
+    reorg_monitor_service::{
+        config::ReorgMonitorServiceConfig,
+        handle::ReorgMonitorHandle,
+        service::ReorgMonitorService,
+    },
+    transaction_service::{
+        handle::TransactionServiceHandle,
+        storage::database::{TransactionBackend, TransactionDatabase},
+    },
+};
+
+const LOG_TARGET: &str = "wallet::reorg_monitor_service";
+
+pub struct ReorgMonitorServiceInitializer<TTransactionBackend, TOutputManagerBackend>
+where
+    TTransactionBackend: TransactionBackend + 'static,
+    TOutputManagerBackend: OutputManagerBackend + 'static,
+{
+    config: ReorgMonitorServiceConfig,
+    transaction_db: TransactionDatabase<TTransactionBackend>,
+    output_db: OutputManagerDatabase<TOutputManagerBackend>,
+}
+
+impl<TTransactionBackend, TOutputManagerBackend>
+    ReorgMonitorServiceInitializer<TTransactionBackend, TOutputManagerBackend>
+where
+    TTransactionBackend: TransactionBackend + 'static,
+    TOutputManagerBackend: OutputManagerBackend + 'static,
+{
+    pub fn new(
+        config: ReorgMonitorServiceConfig,
+        transaction_backend: TTransactionBackend,
+        output_db: OutputManagerDatabase<TOutputManagerBackend>,
+    ) -> Self {
+        Self {
+            config,
+            transaction_db: TransactionDatabase::new(transaction_backend),
+            output_db,
+        }
+    }
+}
+
+#[async_trait]
+impl<TTransactionBackend, TOutputManagerBackend> ServiceInitializer
+    for ReorgMonitorServiceInitializer<TTransactionBackend, TOutputManagerBackend>
+where
+    TTransactionBackend: TransactionBackend + 'static,
+    TOutputManagerBackend: OutputManagerBackend + 'static,
+{
+    async fn initialize(&mut self, context: ServiceInitializerContext) -> Result<(), ServiceInitializationError> {
+        info!(target: LOG_TARGET, "Wallet reorg monitor service initializing.");
+        let (sender, request_stream) = reply_channel::unbounded();
+        let (event_publisher, _) = broadcast::channel(self.config.event_channel_size);
+        context.register_handle(ReorgMonitorHandle::new(sender, event_publisher.clone()));
+
+        let transaction_db = self.transaction_db.clone();
+        let output_db = self.output_db.clone();
+        context.spawn_when_ready(move |handles| async move {
+            let connectivity = handles.expect_handle::<WalletConnectivityHandle>();
+            let base_node_service = handles.expect_handle::<BaseNodeServiceHandle>();
+            let output_manager_service = handles.expect_handle::<OutputManagerHandle>();
+            let transaction_service = handles.expect_handle::<TransactionServiceHandle>();
+
+            let result = ReorgMonitorService::new(
+                transaction_db,
+                output_db,
+                request_stream,
+                connectivity,
+                base_node_service,
+                output_manager_service,
+                transaction_service,
+                event_publisher,
+                handles.get_shutdown_signal(),
+            )
+            .start()
+            .await;
+
+            info!(target: LOG_TARGET, "Wallet Reorg Monitor Service shutdown with result {:?}", result);
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,190 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt,
+};
+
+use chrono::NaiveDateTime;
+use tari_common_types::{transaction::TxId, types::BlockHash};
+
+use crate::output_manager_service::service::Balance;
+
+/// Compares the blocks that the wallet recorded its transactions and outputs as mined (or spent) in with the main chain
+/// of the base node. The heights are walked down from the highest, and the walk stops at the first height where a
+/// recorded block is still in the main chain, because every block below it then is too.
+#[derive(Debug, Default)]
+pub struct ReorgScan {
+    recorded: BTreeMap<u64, HashSet<BlockHash>>,
+    reorged_blocks: HashSet<BlockHash>,
+    reorg_height: Option<u64>,
+    complete: bool,
+}
+
+impl ReorgScan {
+    pub fn new<I>(mined_blocks: I) -> Self
+    where I: IntoIterator<Item = (u64, BlockHash)> {
+        let mut recorded = BTreeMap::<u64, HashSet<BlockHash>>::new();
+        for (height, hash) in mined_blocks {
+            recorded.entry(height).or_default().insert(hash);
+        }
+        Self {
+            recorded,
+            ..Default::default()
+        }
+    }
+
+    /// The height to look up the main chain block at next, or None once the scan is complete
+    pub fn next_height(&self) -> Option<u64> {
+        if self.complete {
+            return None;
+        }
+        self.recorded.keys().next_back().copied()
+    }
+
+    /// Records the hash of the main chain block at the height returned by `next_height`, or None if the main chain is
+    /// not that long
+    pub fn record_main_chain_block(&mut self, main_chain_hash: Option<BlockHash>) {
+        if self.complete {
+            return;
+        }
+        let Some((height, hashes)) = self.recorded.pop_last() else {
+            return;
+        };
+        let mut in_main_chain = false;
+        for hash in hashes {
+            if Some(hash) == main_chain_hash {
+                in_main_chain = true;
+            } else {
+                self.reorged_blocks.insert(hash);
+                self.reorg_height = Some(height);
+            }
+        }
+        self.complete = in_main_chain;
+    }
+
+    /// The reorg found by the scan, if any
+    pub fn into_reorg(self) -> Option<DetectedReorg> {
+        Some(DetectedReorg {
+            reorg_height: self.reorg_height?,
+            reorged_blocks: self.reorged_blocks,
+        })
+    }
+}
+
+/// Recorded blocks that are no longer in the main chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DetectedReorg {
+    /// The lowest height that a recorded block was reorged out at
+    pub reorg_height: u64,
+    pub reorged_blocks: HashSet<BlockHash>,
+}
+
+/// A summary of everything that was reverted in the wallet because of a reorg
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReorgHandled {
+    /// The lowest height that a block the wallet recorded as mined was reorged out at
+    pub reorg_height: u64,
+    /// The transactions that were mined in reorged blocks, and are pending again
+    pub reverted_transactions: Vec<TxId>,
+    /// The number of outputs that were mined in reorged blocks, and are being revalidated
+    pub unmined_outputs: usize,
+    /// The number of outputs that were spent in reorged blocks, and are unspent again
+    pub unspent_outputs: usize,
+    /// The balance after the reverted transactions and outputs were taken out of it
+    pub balance: Balance,
+    pub timestamp: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReorgMonitorEvent {
+    ReorgHandled(ReorgHandled),
+}
+
+impl fmt::Display for ReorgMonitorEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReorgMonitorEvent::ReorgHandled(reorg) => write!(
+                f,
+                "Reorg at height {} handled: {} transaction(s) reverted to pending, {} output(s) unmined and {} \
+                 output(s) unspent, available balance {}",
+                reorg.reorg_height,
+                reorg.reverted_transactions.len(),
+                reorg.unmined_outputs,
+                reorg.unspent_outputs,
+                reorg.balance.available_balance
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn hash(n: u8) -> BlockHash {
+        BlockHash::from([n; 32])
+    }
+
+    /// Runs a scan against a main chain where the block at each height has the hash `height`, up to and including
+    /// `tip`, and returns the heights that were looked up
+    fn scan(scan: &mut ReorgScan, tip: u64) -> Vec<u64> {
+        let mut looked_up = Vec::new();
+        while let Some(height) = scan.next_height() {
+            looked_up.push(height);
+            scan.record_main_chain_block((height <= tip).then(|| hash(u8::try_from(height).unwrap())));
+        }
+        looked_up
+    }
+
+    #[test]
+    fn it_stops_at_the_first_block_in_the_main_chain() {
+        let mut reorg_scan = ReorgScan::new(vec![(3, hash(3)), (7, hash(7)), (5, hash(5))]);
+        assert_eq!(scan(&mut reorg_scan, 10), vec![7]);
+        assert_eq!(reorg_scan.into_reorg(), None);
+        // Nothing to check in an empty wallet
+        let mut reorg_scan = ReorgScan::new(vec![]);
+        assert!(scan(&mut reorg_scan, 10).is_empty());
+        assert_eq!(reorg_scan.into_reorg(), None);
+    }
+
+    #[test]
+    fn it_finds_the_reorged_blocks() {
+        let mut reorg_scan = ReorgScan::new(vec![(3, hash(3)), (7, hash(70)), (5, hash(50)), (7, hash(71))]);
+        assert_eq!(scan(&mut reorg_scan, 10), vec![7, 5, 3]);
+        assert_eq!(
+            reorg_scan.into_reorg(),
+            Some(DetectedReorg {
+                reorg_height: 5,
+                reorged_blocks: [hash(70), hash(71), hash(50)].into_iter().collect(),
+            })
+        );
+    }
+
+    #[test]
+    fn it_finds_blocks_above_a_shorter_main_chain() {
+        let mut reorg_scan = ReorgScan::new(vec![(3, hash(3)), (7, hash(7))]);
+        assert_eq!(scan(&mut reorg_scan, 6), vec![7, 3]);
+        assert_eq!(
+            reorg_scan.into_reorg(),
+            Some(DetectedReorg {
+                reorg_height: 7,
+                reorged_blocks: [hash(7)].into_iter().collect(),
+            })
+        );
+    }
+
+    #[test]
+    fn it_finds_stale_blocks_at_a_height_in_the_main_chain() {
+        let mut reorg_scan = ReorgScan::new(vec![(5, hash(5)), (5, hash(50))]);
+        assert_eq!(scan(&mut reorg_scan, 10), vec![5]);
+        assert_eq!(
+            reorg_scan.into_reorg(),
+            Some(DetectedReorg {
+                reorg_height: 5,
+                reorged_blocks: [hash(50)].into_iter().collect(),
+            })
+        );
+    }
+}
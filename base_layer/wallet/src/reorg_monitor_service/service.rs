@@ -0,0 +1,269 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::{convert::TryInto, sync::Arc};
+
+use chrono::Utc;
+use futures::StreamExt;
+use log::*;
+use tari_common_types::types::BlockHash;
+use tari_comms::protocol::rpc::RpcError::RequestFailed;
+use tari_core::{base_node::rpc::BaseNodeWalletRpcClient, blocks::BlockHeader};
+use tari_service_framework::reply_channel::Receiver;
+use tari_shutdown::ShutdownSignal;
+
+use crate::{
+    base_node_service::handle::{BaseNodeEvent, BaseNodeServiceHandle},
+    connectivity_service::{WalletConnectivityHandle, WalletConnectivityInterface},
+    output_manager_service::{
+        handle::OutputManagerHandle,
+        storage::database::{OutputManagerBackend, OutputManagerDatabase},
+    },
+    reorg_monitor_service::{
+        error::ReorgMonitorError,
+        handle::{ReorgMonitorEventSender, ReorgMonitorRequest, ReorgMonitorResponse},
+        reorg::{DetectedReorg, ReorgHandled, ReorgMonitorEvent, ReorgScan},
+    },
+    transaction_service::{
+        handle::TransactionServiceHandle,
+        storage::database::{TransactionBackend, TransactionDatabase},
+    },
+};
+
+const LOG_TARGET: &str = "wallet::reorg_monitor_service::service";
+
+pub struct ReorgMonitorService<TTransactionBackend, TOutputManagerBackend>
+where
+    TTransactionBackend: TransactionBackend + 'static,
+    TOutputManagerBackend: OutputManagerBackend + 'static,
+{
+    transaction_db: TransactionDatabase<TTransactionBackend>,
+    output_db: OutputManagerDatabase<TOutputManagerBackend>,
+    last_reorg: Option<ReorgHandled>,
+    request_stream: Option<Receiver<ReorgMonitorRequest, Result<ReorgMonitorResponse, ReorgMonitorError>>>,
+    connectivity: WalletConnectivityHandle,
+    base_node_service: BaseNodeServiceHandle,
+    output_manager_service: OutputManagerHandle,
+    transaction_service: TransactionServiceHandle,
+    event_publisher: ReorgMonitorEventSender,
+    shutdown_signal: ShutdownSignal,
+}
+
+impl<TTransactionBackend, TOutputManagerBackend> ReorgMonitorService<TTransactionBackend, TOutputManagerBackend>
+where
+    TTransactionBackend: TransactionBackend + 'static,
+    TOutputManagerBackend: OutputManagerBackend + 'static,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        transaction_db: TransactionDatabase<TTransactionBackend>,
+        output_db: OutputManagerDatabase<TOutputManagerBackend>,
+        request_stream: Receiver<ReorgMonitorRequest, Result<ReorgMonitorResponse, ReorgMonitorError>>,
+        connectivity: WalletConnectivityHandle,
+        base_node_service: BaseNodeServiceHandle,
+        output_manager_service: OutputManagerHandle,
+        transaction_service: TransactionServiceHandle,
+        event_publisher: ReorgMonitorEventSender,
+        shutdown_signal: ShutdownSignal,
+    ) -> Self {
+        Self {
+            transaction_db,
+            output_db,
+            last_reorg: None,
+            request_stream: Some(request_stream),
+            connectivity,
+            base_node_service,
+            output_manager_service,
+            transaction_service,
+            event_publisher,
+            shutdown_signal,
+        }
+    }
+
+    pub async fn start(mut self) -> Result<(), ReorgMonitorError> {
+        let mut request_stream = self
+            .request_stream
+            .take()
+            .expect("Reorg Monitor Service initialized without request_stream");
+        let mut base_node_service_event_stream = self.base_node_service.get_event_stream();
+        let mut shutdown_signal = self.shutdown_signal.clone();
+
+        debug!(target: LOG_TARGET, "Reorg Monitor Service started");
+        loop {
+            tokio::select! {
+                event = base_node_service_event_stream.recv() => {
+                    match event {
+                        Ok(event) => match *event {
+                            BaseNodeEvent::NewBlockDetected(..) | BaseNodeEvent::BaseNodeFailover(..) => {
+                                if let Err(e) = self.check_for_reorg().await {
+                                    warn!(target: LOG_TARGET, "Failed to check for a reorg: {}", e);
+                                }
+                            },
+                            _ => {},
+                        },
+                        Err(e) => {
+                            debug!(target: LOG_TARGET, "Lagging read on base node event broadcast channel: {}", e);
+                        },
+                    }
+                },
+                Some(request_context) = request_stream.next() => {
+                    let (request, reply_tx) = request_context.split();
+                    let response = self.handle_request(request).await.map_err(|e| {
+                        error!(target: LOG_TARGET, "Error handling request: {:?}", e);
+                        e
+                    });
+                    let _result = reply_tx.send(response).inspect_err(|_| {
+                        warn!(target: LOG_TARGET, "Failed to send reply");
+                    });
+                },
+                _ = shutdown_signal.wait() => {
+                    info!(
+                        target: LOG_TARGET,
+                        "Reorg Monitor Service shutting down because the shutdown signal was received"
+                    );
+                    break;
+                },
+            }
+        }
+        Ok(())
+    }
+
+    async fn handle_request(
+        &mut self,
+        request: ReorgMonitorRequest,
+    ) -> Result<ReorgMonitorResponse, ReorgMonitorError> {
+        trace!(target: LOG_TARGET, "Handling Reorg Monitor Service Request: {}", request);
+        match request {
+            ReorgMonitorRequest::CheckForReorg => Ok(ReorgMonitorResponse::Reorg(self.check_for_reorg().await?)),
+            ReorgMonitorRequest::GetLastReorg => Ok(ReorgMonitorResponse::Reorg(self.last_reorg.clone())),
+        }
+    }
+
+    /// Compares the blocks that transactions and outputs were recorded as mined and spent in with the main chain, and
+    /// handles a reorg if any of them are no longer in it
+    async fn check_for_reorg(&mut self) -> Result<Option<ReorgHandled>, ReorgMonitorError> {
+        let mut scan = ReorgScan::new(self.recorded_blocks()?);
+        if scan.next_height().is_none() {
+            return Ok(None);
+        }
+        let mut client = self
+            .connectivity
+            .obtain_base_node_wallet_rpc_client()
+            .await
+            .ok_or(ReorgMonitorError::NoBaseNode)?;
+        while let Some(height) = scan.next_height() {
+            let main_chain_hash = get_main_chain_block_hash(&mut client, height).await?;
+            scan.record_main_chain_block(main_chain_hash);
+        }
+        let Some(reorg) = scan.into_reorg() else {
+            return Ok(None);
+        };
+
+        let reorg = self.handle_reorg(reorg).await?;
+        info!(target: LOG_TARGET, "{}", ReorgMonitorEvent::ReorgHandled(reorg.clone()));
+        self.last_reorg = Some(reorg.clone());
+        // Nobody might be subscribed
+        let _size = self
+            .event_publisher
+            .send(Arc::new(ReorgMonitorEvent::ReorgHandled(reorg.clone())));
+        Ok(Some(reorg))
+    }
+
+    fn recorded_blocks(&self) -> Result<Vec<(u64, BlockHash)>, ReorgMonitorError> {
+        let mut blocks = Vec::new();
+        for tx in self.transaction_db.get_completed_transactions()?.values() {
+            if let (Some(height), Some(hash)) = (tx.mined_height, tx.mined_in_block) {
+                blocks.push((height, hash));
+            }
+        }
+        let outputs = self
+            .output_db
+            .fetch_mined_unspent_outputs()?
+            .into_iter()
+            .chain(self.output_db.fetch_spent_outputs()?);
+        for output in outputs {
+            if let (Some(height), Some(hash)) = (output.mined_height, output.mined_in_block) {
+                blocks.push((height, hash));
+            }
+            if let (Some(height), Some(hash)) = (output.marked_deleted_at_height, output.marked_deleted_in_block) {
+                blocks.push((height, hash));
+            }
+        }
+        Ok(blocks)
+    }
+
+    /// Reverts the transactions and outputs that were mined or spent in the reorged blocks, and starts a revalidation
+    /// of all of them against the new main chain
+    async fn handle_reorg(&mut self, reorg: DetectedReorg) -> Result<ReorgHandled, ReorgMonitorError> {
+        warn!(
+            target: LOG_TARGET,
+            "{} block(s) that the wallet recorded transactions or outputs in have been reorged out, the lowest at \
+             height {}",
+            reorg.reorged_blocks.len(),
+            reorg.reorg_height
+        );
+        let is_reorged = |hash: Option<BlockHash>| hash.is_some_and(|hash| reorg.reorged_blocks.contains(&hash));
+
+        let mut reverted_transactions = Vec::new();
+        for (tx_id, tx) in self.transaction_db.get_completed_transactions()? {
+            if is_reorged(tx.mined_in_block) {
+                self.transaction_db.set_transaction_as_unmined(tx_id)?;
+                reverted_transactions.push(tx_id);
+            }
+        }
+        reverted_transactions.sort_by_key(|tx_id| tx_id.as_u64());
+
+        // Spends are reverted before the outputs themselves, so that an output that was both mined and spent in
+        // reorged blocks ends up unmined
+        let unspent = self
+            .output_db
+            .fetch_spent_outputs()?
+            .into_iter()
+            .filter(|output| is_reorged(output.marked_deleted_in_block))
+            .map(|output| (output.hash, false))
+            .collect::<Vec<_>>();
+        let unspent_outputs = unspent.len();
+        if !unspent.is_empty() {
+            self.output_db.mark_outputs_as_unspent(unspent)?;
+        }
+        let unmined = self
+            .output_db
+            .fetch_mined_unspent_outputs()?
+            .into_iter()
+            .chain(self.output_db.fetch_spent_outputs()?)
+            .filter(|output| is_reorged(output.mined_in_block))
+            .map(|output| output.hash)
+            .collect::<Vec<_>>();
+        let unmined_outputs = unmined.len();
+        if !unmined.is_empty() {
+            self.output_db.set_outputs_to_unmined_and_invalid(unmined)?;
+        }
+
+        let balance = self.output_manager_service.get_balance().await?;
+        self.output_manager_service.validate_txos().await?;
+        self.transaction_service.validate_transactions().await?;
+
+        Ok(ReorgHandled {
+            reorg_height: reorg.reorg_height,
+            reverted_transactions,
+            unmined_outputs,
+            unspent_outputs,
+            balance,
+            timestamp: Utc::now().naive_utc(),
+        })
+    }
+}
+
+/// The hash of the main chain block at `height`, or None if the main chain is not that long
+async fn get_main_chain_block_hash(
+    client: &mut BaseNodeWalletRpcClient,
+    height: u64,
+) -> Result<Option<BlockHash>, ReorgMonitorError> {
+    let header = match client.get_header_by_height(height).await {
+        Ok(header) => header,
+        Err(RequestFailed(status)) if status.as_status_code().is_not_found() => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let header: BlockHeader = header.try_into().map_err(ReorgMonitorError::InvalidBlockHeader)?;
+    Ok(Some(header.hash()))
+}
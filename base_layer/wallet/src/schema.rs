@@ -119,6 +119,7 @@ diesel::table! {
         source -> Integer,
         last_validation_timestamp -> Nullable<Timestamp>,
         payment_id -> Nullable<Binary>,
+        sub_address_index -> Nullable<BigInt>,
     }
 }
 
@@ -132,6 +133,15 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    transaction_drafts (name) {
+        name -> Text,
+        payload -> Text,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     wallet_settings (key) {
         key -> Text,
@@ -148,5 +158,6 @@ diesel::allow_tables_to_appear_in_same_query!(
     outbound_transactions,
     outputs,
     scanned_blocks,
+    transaction_drafts,
     wallet_settings,
 );
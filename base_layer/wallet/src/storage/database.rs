@@ -21,6 +21,7 @@
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use std::{
+    collections::BTreeMap,
     fmt::{Display, Error, Formatter},
     sync::Arc,
 };
@@ -36,7 +37,11 @@ use tari_comms::{
 use tari_key_manager::cipher_seed::CipherSeed;
 use tari_utilities::SafePassword;
 
-use crate::{error::WalletStorageError, utxo_scanner_service::service::ScannedBlock};
+use crate::{
+    error::WalletStorageError,
+    transaction_service::drafts::TransactionDraft,
+    utxo_scanner_service::service::ScannedBlock,
+};
 
 const LOG_TARGET: &str = "wallet::database";
 
@@ -72,8 +77,26 @@ pub trait WalletBackend: Send + Sync + Clone {
     fn fetch_burnt_proof(&self, id: u32) -> Result<(u32, String, String, NaiveDateTime), WalletStorageError>;
     fn fetch_burnt_proofs(&self) -> Result<Vec<(u32, String, String, NaiveDateTime)>, WalletStorageError>;
     fn delete_burnt_proof(&self, id: u32) -> Result<(), WalletStorageError>;
+
+    /// Inserts the draft, or replaces the draft with the same name while keeping its creation time
+    fn save_transaction_draft(&self, draft: TransactionDraft) -> Result<(), WalletStorageError>;
+    fn fetch_transaction_draft(&self, name: &str) -> Result<Option<TransactionDraft>, WalletStorageError>;
+    /// Returns all drafts, the most recently updated first
+    fn fetch_transaction_drafts(&self) -> Result<Vec<TransactionDraft>, WalletStorageError>;
+    /// Returns false if there was no draft with the name
+    fn delete_transaction_draft(&self, name: &str) -> Result<bool, WalletStorageError>;
+
+    /// Returns the client keys that start with `prefix`, sorted
+    fn fetch_client_keys(&self, prefix: &str) -> Result<Vec<String>, WalletStorageError>;
 }
 
+/// Application data is stored as client values under keys of the form `app/<namespace>/<key>`
+const APP_DATA_KEY_PREFIX: &str = "app/";
+/// The longest application namespace or key, in bytes
+pub const MAX_APP_DATA_KEY_LENGTH: usize = 128;
+/// The largest application value, in bytes. Application data is meant for small state, not files.
+pub const MAX_APP_DATA_VALUE_SIZE: usize = 64 * 1024;
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum DbKey {
     CommsAddress,
@@ -326,6 +349,47 @@ where T: WalletBackend + 'static
         Ok(c)
     }
 
+    /// Stores a value in the namespace of an application. Values are encrypted like all client values and are part of
+    /// the wallet database, and so of its backups.
+    pub fn set_app_value(&self, namespace: &str, key: &str, value: String) -> Result<(), WalletStorageError> {
+        if value.len() > MAX_APP_DATA_VALUE_SIZE {
+            return Err(WalletStorageError::InvalidAppData(format!(
+                "The value is {} bytes, the limit is {} bytes",
+                value.len(),
+                MAX_APP_DATA_VALUE_SIZE
+            )));
+        }
+        self.set_client_key_value(app_data_key(namespace, key)?, value)
+    }
+
+    pub fn get_app_value(&self, namespace: &str, key: &str) -> Result<Option<String>, WalletStorageError> {
+        self.get_client_key_value(app_data_key(namespace, key)?)
+    }
+
+    /// Returns false if there was no value for the key
+    pub fn clear_app_value(&self, namespace: &str, key: &str) -> Result<bool, WalletStorageError> {
+        self.clear_client_value(app_data_key(namespace, key)?)
+    }
+
+    /// Returns the keys in the namespace of an application, sorted
+    pub fn get_app_keys(&self, namespace: &str) -> Result<Vec<String>, WalletStorageError> {
+        validate_app_data_name("namespace", namespace)?;
+        let prefix = format!("{}{}/", APP_DATA_KEY_PREFIX, namespace);
+        let keys = self.db.fetch_client_keys(&prefix)?;
+        Ok(keys.into_iter().map(|k| k[prefix.len()..].to_string()).collect())
+    }
+
+    /// Returns the values of all applications by `<namespace>/<key>`
+    pub fn get_all_app_values(&self) -> Result<BTreeMap<String, String>, WalletStorageError> {
+        let mut values = BTreeMap::new();
+        for key in self.db.fetch_client_keys(APP_DATA_KEY_PREFIX)? {
+            if let Some(value) = self.get_client_key_value(key.clone())? {
+                values.insert(key[APP_DATA_KEY_PREFIX.len()..].to_string(), value);
+            }
+        }
+        Ok(values)
+    }
+
     pub fn get_wallet_birthday(&self) -> Result<u16, WalletStorageError> {
         let result = match self.db.fetch(&DbKey::WalletBirthday) {
             Ok(None) => Err(WalletStorageError::ValueNotFound(DbKey::WalletBirthday)),
@@ -389,6 +453,22 @@ where T: WalletBackend + 'static
         self.db.delete_burnt_proof(id)
     }
 
+    pub fn save_transaction_draft(&self, draft: TransactionDraft) -> Result<(), WalletStorageError> {
+        self.db.save_transaction_draft(draft)
+    }
+
+    pub fn fetch_transaction_draft(&self, name: &str) -> Result<Option<TransactionDraft>, WalletStorageError> {
+        self.db.fetch_transaction_draft(name)
+    }
+
+    pub fn fetch_transaction_drafts(&self) -> Result<Vec<TransactionDraft>, WalletStorageError> {
+        self.db.fetch_transaction_drafts()
+    }
+
+    pub fn delete_transaction_draft(&self, name: &str) -> Result<bool, WalletStorageError> {
+        self.db.delete_transaction_draft(name)
+    }
+
     pub fn get_wallet_type(&self) -> Result<Option<WalletType>, WalletStorageError> {
         match self.db.fetch(&DbKey::WalletType) {
             Ok(None) => Ok(None),
@@ -448,17 +528,46 @@ fn unexpected_result<T>(req: DbKey, res: DbValue) -> Result<T, WalletStorageErro
     Err(WalletStorageError::UnexpectedResult(msg))
 }
 
+fn app_data_key(namespace: &str, key: &str) -> Result<String, WalletStorageError> {
+    validate_app_data_name("namespace", namespace)?;
+    validate_app_data_name("key", key)?;
+    Ok(format!("{}{}/{}", APP_DATA_KEY_PREFIX, namespace, key))
+}
+
+/// Namespaces and keys are non-empty and made of ASCII letters, digits, `-`, `_` and `.`
+fn validate_app_data_name(kind: &str, name: &str) -> Result<(), WalletStorageError> {
+    if name.is_empty() || name.len() > MAX_APP_DATA_KEY_LENGTH {
+        return Err(WalletStorageError::InvalidAppData(format!(
+            "The {} must be 1 to {} bytes long",
+            kind, MAX_APP_DATA_KEY_LENGTH
+        )));
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        return Err(WalletStorageError::InvalidAppData(format!(
+            "The {} `{}` may only contain ASCII letters, digits, `-`, `_` and `.`",
+            kind, name
+        )));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod test {
     use tari_key_manager::cipher_seed::CipherSeed;
     use tari_test_utils::random::string;
     use tari_utilities::SafePassword;
-    use tempfile::tempdir;
-
-    use crate::storage::{
-        database::WalletDatabase,
-        sqlite_db::wallet::WalletSqliteDatabase,
-        sqlite_utilities::run_migration_and_create_sqlite_connection,
+    use tempfile::{tempdir, TempDir};
+
+    use crate::{
+        error::WalletStorageError,
+        storage::{
+            database::{WalletDatabase, MAX_APP_DATA_KEY_LENGTH, MAX_APP_DATA_VALUE_SIZE},
+            sqlite_db::wallet::WalletSqliteDatabase,
+            sqlite_utilities::run_migration_and_create_sqlite_connection,
+        },
     };
 
     #[test]
@@ -507,4 +616,82 @@ mod test {
 
         assert!(!db.clear_client_value(client_key_values[0].0.clone()).unwrap());
     }
+
+    fn create_db() -> (WalletDatabase<WalletSqliteDatabase>, TempDir) {
+        let db_folder = tempdir().unwrap();
+        let db_path = db_folder.path().join(format!("{}.sqlite3", string(8)));
+        let connection = run_migration_and_create_sqlite_connection(db_path, 16).unwrap();
+        let passphrase = SafePassword::from("my secret lovely passphrase");
+        let db = WalletDatabase::new(WalletSqliteDatabase::new(connection, passphrase).unwrap());
+        (db, db_folder)
+    }
+
+    #[test]
+    fn it_isolates_app_data_by_namespace() {
+        let (db, _db_folder) = create_db();
+        db.set_app_value("pos", "till.1", "open".to_string()).unwrap();
+        db.set_app_value("pos", "till.2", "closed".to_string()).unwrap();
+        db.set_app_value("game", "till.1", "level 3".to_string()).unwrap();
+        // Neither a namespace that is a prefix of another nor one that only differs in case sees its keys
+        db.set_app_value("po", "till.3", "open".to_string()).unwrap();
+        db.set_app_value("POS", "till.4", "open".to_string()).unwrap();
+        // Client values outside of the application data are not visible
+        db.set_client_key_value("apps/pos/till.5".to_string(), "not app data".to_string())
+            .unwrap();
+        db.set_client_key_value("pos/till.5".to_string(), "not app data".to_string())
+            .unwrap();
+
+        assert_eq!(db.get_app_value("pos", "till.1").unwrap().unwrap(), "open");
+        assert_eq!(db.get_app_value("game", "till.1").unwrap().unwrap(), "level 3");
+        assert!(db.get_app_value("game", "till.2").unwrap().is_none());
+        assert_eq!(db.get_app_keys("pos").unwrap(), vec!["till.1", "till.2"]);
+        assert_eq!(db.get_app_keys("po").unwrap(), vec!["till.3"]);
+        assert_eq!(db.get_app_keys("POS").unwrap(), vec!["till.4"]);
+        assert!(db.get_app_keys("p").unwrap().is_empty());
+
+        assert!(db.clear_app_value("pos", "till.1").unwrap());
+        assert!(!db.clear_app_value("pos", "till.1").unwrap());
+        assert_eq!(db.get_app_keys("pos").unwrap(), vec!["till.2"]);
+        assert_eq!(db.get_app_value("game", "till.1").unwrap().unwrap(), "level 3");
+        assert_eq!(db.get_all_app_values().unwrap().into_keys().collect::<Vec<_>>(), vec![
+            "POS/till.4",
+            "game/till.1",
+            "po/till.3",
+            "pos/till.2"
+        ]);
+    }
+
+    #[test]
+    fn it_validates_app_data_names() {
+        let (db, _db_folder) = create_db();
+        db.set_app_value("my-app_1.0", "Key-1_a.b", String::new()).unwrap();
+        db.set_app_value(&"n".repeat(MAX_APP_DATA_KEY_LENGTH), "key", String::new())
+            .unwrap();
+        db.set_app_value("pos", "big", "x".repeat(MAX_APP_DATA_VALUE_SIZE))
+            .unwrap();
+
+        let long_name = "n".repeat(MAX_APP_DATA_KEY_LENGTH + 1);
+        for (namespace, key) in [
+            ("", "key"),
+            ("pos", ""),
+            ("pos/till", "1"),
+            ("pos", "till/1"),
+            ("pos", "till 1"),
+            ("pos", "till%"),
+            ("pós", "till"),
+            (long_name.as_str(), "key"),
+        ] {
+            assert!(matches!(
+                db.set_app_value(namespace, key, String::new()),
+                Err(WalletStorageError::InvalidAppData(_))
+            ));
+            assert!(db.get_app_value(namespace, key).is_err());
+            assert!(db.clear_app_value(namespace, key).is_err());
+        }
+        assert!(db.get_app_keys("pos/till").is_err());
+        assert!(matches!(
+            db.set_app_value("pos", "big", "x".repeat(MAX_APP_DATA_VALUE_SIZE + 1)),
+            Err(WalletStorageError::InvalidAppData(_))
+        ));
+    }
 }
@@ -0,0 +1,70 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Verification of the whole wallet database, to detect silent corruption before it causes a failed spend.
+
+use log::*;
+use tari_common_sqlite::integrity::{IntegrityIssue, IntegrityReport};
+use tari_common_types::types::PublicKey;
+use tari_core::transactions::key_manager::TransactionKeyManagerInterface;
+use tari_key_manager::key_manager_service::{storage::sqlite_db::KeyManagerSqliteDatabase, KeyManagerServiceError};
+
+use crate::{
+    error::WalletError,
+    output_manager_service::{error::OutputManagerError, storage::sqlite_db::OutputManagerSqliteDatabase},
+    storage::{sqlite_db::wallet::WalletSqliteDatabase, sqlite_utilities::wallet_db_connection::WalletDbConnection},
+    transaction_service::{error::TransactionServiceError, storage::sqlite_db::TransactionServiceSqliteDatabase},
+};
+
+const LOG_TARGET: &str = "wallet::storage::integrity";
+
+/// Checks every encrypted field of the wallet database, the state of every transaction, and that the commitment of
+/// every output matches the one derived from its spending key and value. If `quarantine` is set, inconsistent rows
+/// are moved into `quarantined_<table>` tables, except for key material, which is only ever reported.
+pub async fn verify_wallet_db<KM: TransactionKeyManagerInterface>(
+    wallet_backend: &WalletSqliteDatabase,
+    transaction_backend: &TransactionServiceSqliteDatabase,
+    output_backend: &OutputManagerSqliteDatabase,
+    key_manager_backend: &KeyManagerSqliteDatabase<WalletDbConnection>,
+    key_manager: &KM,
+    quarantine: bool,
+) -> Result<IntegrityReport, WalletError> {
+    let mut report = wallet_backend.verify_integrity(quarantine)?;
+    report.merge(
+        key_manager_backend
+            .verify_integrity::<PublicKey>()
+            .map_err(KeyManagerServiceError::from)?,
+    );
+    report.merge(
+        transaction_backend
+            .verify_integrity(quarantine)
+            .map_err(TransactionServiceError::from)?,
+    );
+
+    let (output_report, outputs) = output_backend
+        .verify_integrity(quarantine)
+        .map_err(OutputManagerError::from)?;
+    report.merge(output_report);
+    for (id, output) in outputs {
+        // A key manager that can't derive commitments, such as a disconnected hardware wallet, must not cause valid
+        // outputs to be quarantined, so its errors abort the verification
+        if output.wallet_output.commitment(key_manager).await? == output.commitment {
+            continue;
+        }
+        let mut issue = IntegrityIssue::new(
+            "outputs",
+            id,
+            "The commitment does not match the spending key and value",
+        );
+        if quarantine {
+            output_backend.quarantine_output(id).map_err(OutputManagerError::from)?;
+            issue.quarantined = true;
+        }
+        report.issues.push(issue);
+    }
+
+    for issue in &report.issues {
+        warn!(target: LOG_TARGET, "Inconsistent row: {}", issue);
+    }
+    Ok(report)
+}
@@ -31,5 +31,6 @@
 //     any unwanted changes)
 
 pub mod database;
+pub mod integrity;
 pub mod sqlite_db;
 pub mod sqlite_utilities;
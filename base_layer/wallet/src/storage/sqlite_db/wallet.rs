@@ -33,12 +33,16 @@ use argon2::password_hash::{
 };
 use blake2::Blake2b;
 use chacha20poly1305::{Key, KeyInit, XChaCha20Poly1305};
-use chrono::NaiveDateTime;
+use chrono::{NaiveDateTime, Utc};
 use diesel::{prelude::*, result::Error};
 use digest::{consts::U32, generic_array::GenericArray, FixedOutput};
 use itertools::Itertools;
 use log::*;
-use tari_common_sqlite::sqlite_connection_pool::PooledDbConnection;
+use serde::{Deserialize, Serialize};
+use tari_common_sqlite::{
+    integrity::{quarantine_row, IntegrityIssue, IntegrityReport, RowKey},
+    sqlite_connection_pool::PooledDbConnection,
+};
 use tari_common_types::{
     chain_metadata::ChainMetadata,
     encryption::{decrypt_bytes_integral_nonce, encrypt_bytes_integral_nonce, Encryptable},
@@ -63,12 +67,13 @@ use zeroize::Zeroize;
 
 use crate::{
     error::WalletStorageError,
-    schema::{burnt_proofs, client_key_values, wallet_settings},
+    schema::{burnt_proofs, client_key_values, transaction_drafts, wallet_settings},
     storage::{
         database::{DbKey, DbKeyValuePair, DbValue, WalletBackend, WriteOperation},
         sqlite_db::scanned_blocks::ScannedBlockSql,
         sqlite_utilities::wallet_db_connection::WalletDbConnection,
     },
+    transaction_service::drafts::{DraftRecipient, TransactionDraft},
     utxo_scanner_service::service::ScannedBlock,
 };
 
@@ -490,6 +495,69 @@ impl WalletSqliteDatabase {
         let cipher = acquire_read_lock!(self.cipher);
         (*cipher).clone()
     }
+
+    /// Checks that the encrypted wallet settings, client key-values and burnt proofs can be authenticated and
+    /// decrypted. If `quarantine` is set, client key-values and burnt proofs that fail are moved out of the way; the
+    /// master seed and Tor identity are only reported.
+    pub fn verify_integrity(&self, quarantine: bool) -> Result<IntegrityReport, WalletStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        let mut report = IntegrityReport::default();
+
+        report.rows_checked += 2;
+        if let Err(e) = self.get_master_seed(&mut conn) {
+            report.issues.push(IntegrityIssue::new(
+                "wallet_settings",
+                DbKey::MasterSeed.to_key_string(),
+                e,
+            ));
+        }
+        if let Err(e) = self.get_tor_id(&mut conn) {
+            report
+                .issues
+                .push(IntegrityIssue::new("wallet_settings", DbKey::TorId.to_key_string(), e));
+        }
+
+        for kv in ClientKeyValueSql::index(&mut conn)? {
+            report.rows_checked += 1;
+            let key = kv.key.clone();
+            if let Err(e) = self.decrypt_value(kv) {
+                let mut issue = IntegrityIssue::new("client_key_values", &key, e);
+                if quarantine {
+                    quarantine_row(&mut conn, "client_key_values", "key", &RowKey::Text(key))?;
+                    issue.quarantined = true;
+                }
+                report.issues.push(issue);
+            }
+        }
+
+        for proof in BurntProofSql::index(&mut conn)? {
+            report.rows_checked += 1;
+            let id = proof.id;
+            if let Err(e) = self.decrypt_value(proof) {
+                let mut issue = IntegrityIssue::new("burnt_proofs", id, e);
+                if quarantine {
+                    quarantine_row(&mut conn, "burnt_proofs", "id", &RowKey::Integer(id))?;
+                    issue.quarantined = true;
+                }
+                report.issues.push(issue);
+            }
+        }
+
+        for draft in TransactionDraftSql::index(&mut conn)? {
+            report.rows_checked += 1;
+            let name = draft.name.clone();
+            if let Err(e) = self.decrypt_value(draft).and_then(TransactionDraftSql::into_draft) {
+                let mut issue = IntegrityIssue::new("transaction_drafts", &name, e);
+                if quarantine {
+                    quarantine_row(&mut conn, "transaction_drafts", "name", &RowKey::Text(name))?;
+                    issue.quarantined = true;
+                }
+                report.issues.push(issue);
+            }
+        }
+
+        Ok(report)
+    }
 }
 
 impl WalletBackend for WalletSqliteDatabase {
@@ -726,6 +794,54 @@ impl WalletBackend for WalletSqliteDatabase {
         BurntProofSql::delete(id, &mut conn)?;
         Ok(())
     }
+
+    fn save_transaction_draft(&self, mut draft: TransactionDraft) -> Result<(), WalletStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        let cipher = acquire_read_lock!(self.cipher);
+
+        conn.transaction::<_, WalletStorageError, _>(|conn| {
+            let now = Utc::now().naive_utc();
+            draft.created_at = TransactionDraftSql::get(&draft.name, conn)?.map_or(now, |d| d.created_at);
+            draft.updated_at = now;
+            TransactionDraftSql::new(&draft, &cipher)?.replace(conn)
+        })
+    }
+
+    fn fetch_transaction_draft(&self, name: &str) -> Result<Option<TransactionDraft>, WalletStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        TransactionDraftSql::get(name, &mut conn)?
+            .map(|entry| self.decrypt_value(entry).and_then(TransactionDraftSql::into_draft))
+            .transpose()
+    }
+
+    fn fetch_transaction_drafts(&self) -> Result<Vec<TransactionDraft>, WalletStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        let drafts = TransactionDraftSql::index(&mut conn)?;
+
+        Ok(drafts
+            .into_iter()
+            .filter_map(|entry| {
+                let name = entry.name.clone();
+                match self.decrypt_value(entry).and_then(TransactionDraftSql::into_draft) {
+                    Ok(draft) => Some(draft),
+                    Err(e) => {
+                        error!(target: LOG_TARGET, "Failed to decrypt transaction draft '{}': {}", name, e);
+                        None
+                    },
+                }
+            })
+            .collect_vec())
+    }
+
+    fn delete_transaction_draft(&self, name: &str) -> Result<bool, WalletStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        TransactionDraftSql::delete(name, &mut conn)
+    }
+
+    fn fetch_client_keys(&self, prefix: &str) -> Result<Vec<String>, WalletStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        ClientKeyValueSql::keys_with_prefix(prefix, &mut conn)
+    }
 }
 
 /// Derive a secondary database key and associated commitment
@@ -920,7 +1036,6 @@ impl ClientKeyValueSql {
         client_kv.encrypt(cipher).map_err(WalletStorageError::AeadError)
     }
 
-    #[allow(dead_code)]
     pub fn index(conn: &mut SqliteConnection) -> Result<Vec<Self>, WalletStorageError> {
         Ok(client_key_values::table.load::<ClientKeyValueSql>(conn)?)
     }
@@ -944,6 +1059,22 @@ impl ClientKeyValueSql {
             })
     }
 
+    /// The keys are not encrypted, so they are filtered in the query. The keys that start with the prefix are those
+    /// from the prefix up to the prefix with its last character incremented. `LIKE` is not used, as it ignores case in
+    /// SQLite and treats `_` as a wildcard.
+    pub fn keys_with_prefix(prefix: &str, conn: &mut SqliteConnection) -> Result<Vec<String>, WalletStorageError> {
+        let mut query = client_key_values::table
+            .select(client_key_values::key)
+            .filter(client_key_values::key.ge(prefix))
+            .order_by(client_key_values::key.asc())
+            .into_boxed();
+        let mut chars = prefix.chars();
+        if let Some(next) = chars.next_back().and_then(|c| char::from_u32(u32::from(c) + 1)) {
+            query = query.filter(client_key_values::key.lt(format!("{}{}", chars.as_str(), next)));
+        }
+        Ok(query.load::<String>(conn)?)
+    }
+
     pub fn clear(key: &str, conn: &mut SqliteConnection) -> Result<bool, WalletStorageError> {
         let num_deleted =
             diesel::delete(client_key_values::table.filter(client_key_values::key.eq(key))).execute(conn)?;
@@ -1090,10 +1221,120 @@ impl Encryptable<XChaCha20Poly1305> for BurntProofSql {
     }
 }
 
+/// The encrypted part of a transaction draft
+#[derive(Serialize, Deserialize)]
+struct TransactionDraftPayload {
+    notes: String,
+    recipients: Vec<DraftRecipient>,
+}
+
+#[derive(Clone, Debug, Queryable, Insertable, PartialEq)]
+#[diesel(table_name = transaction_drafts)]
+struct TransactionDraftSql {
+    name: String,
+    payload: String,
+    created_at: NaiveDateTime,
+    updated_at: NaiveDateTime,
+}
+
+impl TransactionDraftSql {
+    pub fn new(draft: &TransactionDraft, cipher: &XChaCha20Poly1305) -> Result<Self, WalletStorageError> {
+        let payload = TransactionDraftPayload {
+            notes: draft.notes.clone(),
+            recipients: draft.recipients.clone(),
+        };
+        let entry = Self {
+            name: draft.name.clone(),
+            payload: serde_json::to_string(&payload)?,
+            created_at: draft.created_at,
+            updated_at: draft.updated_at,
+        };
+        entry.encrypt(cipher).map_err(WalletStorageError::AeadError)
+    }
+
+    /// Converts a decrypted entry
+    pub fn into_draft(self) -> Result<TransactionDraft, WalletStorageError> {
+        let payload = serde_json::from_str::<TransactionDraftPayload>(&self.payload)?;
+        Ok(TransactionDraft {
+            name: self.name,
+            notes: payload.notes,
+            recipients: payload.recipients,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        })
+    }
+
+    pub fn index(conn: &mut SqliteConnection) -> Result<Vec<Self>, WalletStorageError> {
+        Ok(transaction_drafts::table
+            .order(transaction_drafts::updated_at.desc())
+            .load::<TransactionDraftSql>(conn)?)
+    }
+
+    pub fn replace(&self, conn: &mut SqliteConnection) -> Result<(), WalletStorageError> {
+        diesel::replace_into(transaction_drafts::table)
+            .values(self)
+            .execute(conn)?;
+        Ok(())
+    }
+
+    pub fn get(name: &str, conn: &mut SqliteConnection) -> Result<Option<Self>, WalletStorageError> {
+        Ok(transaction_drafts::table
+            .filter(transaction_drafts::name.eq(name))
+            .first::<TransactionDraftSql>(conn)
+            .optional()?)
+    }
+
+    pub fn delete(name: &str, conn: &mut SqliteConnection) -> Result<bool, WalletStorageError> {
+        let num_deleted =
+            diesel::delete(transaction_drafts::table.filter(transaction_drafts::name.eq(name))).execute(conn)?;
+        Ok(num_deleted > 0)
+    }
+}
+
+impl Encryptable<XChaCha20Poly1305> for TransactionDraftSql {
+    fn domain(&self, field_name: &'static str) -> Vec<u8> {
+        [Self::TRANSACTION_DRAFT, self.name.as_bytes(), field_name.as_bytes()]
+            .concat()
+            .to_vec()
+    }
+
+    fn encrypt(mut self, cipher: &XChaCha20Poly1305) -> Result<Self, String> {
+        self.payload = encrypt_bytes_integral_nonce(
+            cipher,
+            self.domain("payload"),
+            Hidden::hide(self.payload.as_bytes().to_vec()),
+        )?
+        .to_hex();
+
+        Ok(self)
+    }
+
+    fn decrypt(mut self, cipher: &XChaCha20Poly1305) -> Result<Self, String> {
+        let mut decrypted_value = decrypt_bytes_integral_nonce(
+            cipher,
+            self.domain("payload"),
+            &from_hex(self.payload.as_str()).map_err(|e| e.to_string())?,
+        )?;
+
+        self.payload = from_utf8(decrypted_value.as_slice())
+            .map_err(|e| e.to_string())?
+            .to_string();
+
+        // we zeroize the decrypted value
+        decrypted_value.zeroize();
+
+        Ok(self)
+    }
+}
+
 #[cfg(test)]
 mod test {
+    use chrono::NaiveDateTime;
     use tari_common_sqlite::sqlite_connection_pool::PooledDbConnection;
-    use tari_common_types::encryption::{decrypt_bytes_integral_nonce, Encryptable};
+    use tari_common_types::{
+        encryption::{decrypt_bytes_integral_nonce, Encryptable},
+        tari_address::TariAddress,
+    };
     use tari_key_manager::cipher_seed::CipherSeed;
     use tari_test_utils::random::string;
     use tari_utilities::{
@@ -1103,10 +1344,13 @@ mod test {
     };
     use tempfile::tempdir;
 
-    use crate::storage::{
-        database::{DbKey, DbValue, WalletBackend},
-        sqlite_db::wallet::{ClientKeyValueSql, WalletSettingSql, WalletSqliteDatabase},
-        sqlite_utilities::run_migration_and_create_sqlite_connection,
+    use crate::{
+        storage::{
+            database::{DbKey, DbValue, WalletBackend},
+            sqlite_db::wallet::{ClientKeyValueSql, TransactionDraftSql, WalletSettingSql, WalletSqliteDatabase},
+            sqlite_utilities::run_migration_and_create_sqlite_connection,
+        },
+        transaction_service::drafts::{DraftPaymentType, DraftRecipient, TransactionDraft},
     };
     #[test]
     fn test_passphrase() {
@@ -1342,4 +1586,53 @@ mod test {
 
         assert_eq!(decrypted_db_seed, seed_bytes);
     }
+
+    #[test]
+    fn test_transaction_drafts() {
+        let db_name = format!("{}.sqlite3", string(8).as_str());
+        let db_tempdir = tempdir().unwrap();
+        let db_folder = db_tempdir.path().to_str().unwrap().to_string();
+        let connection = run_migration_and_create_sqlite_connection(format!("{}{}", db_folder, db_name), 16).unwrap();
+        let passphrase = SafePassword::from("an example very very secret key.".to_string());
+        let db = WalletSqliteDatabase::new(connection.clone(), passphrase).unwrap();
+
+        let mut draft = TransactionDraft {
+            name: "payroll".to_string(),
+            notes: "Approved by finance".to_string(),
+            recipients: vec![DraftRecipient {
+                address: TariAddress::default(),
+                amount: 1_000.into(),
+                fee_per_gram: 5.into(),
+                message: "March".to_string(),
+                payment_type: DraftPaymentType::OneSided,
+                payment_id: vec![],
+            }],
+            created_at: NaiveDateTime::default(),
+            updated_at: NaiveDateTime::default(),
+        };
+        db.save_transaction_draft(draft.clone()).unwrap();
+        let saved = db.fetch_transaction_draft("payroll").unwrap().unwrap();
+        assert_eq!(saved.recipients, draft.recipients);
+        assert_eq!(saved.notes, draft.notes);
+        assert_ne!(saved.created_at, NaiveDateTime::default());
+
+        // The payload is only stored encrypted
+        let mut conn = connection.get_pooled_connection().unwrap();
+        let stored = TransactionDraftSql::get("payroll", &mut conn).unwrap().unwrap();
+        assert!(!stored.payload.contains("finance"));
+
+        draft.recipients.push(draft.recipients[0].clone());
+        db.save_transaction_draft(draft.clone()).unwrap();
+        draft.name = "rent".to_string();
+        db.save_transaction_draft(draft).unwrap();
+        let drafts = db.fetch_transaction_drafts().unwrap();
+        assert_eq!(drafts.len(), 2);
+        let edited = drafts.iter().find(|d| d.name == "payroll").unwrap();
+        assert_eq!(edited.created_at, saved.created_at);
+        assert_eq!(edited.total_amount(), 2_000.into());
+
+        assert!(db.delete_transaction_draft("payroll").unwrap());
+        assert!(!db.delete_transaction_draft("payroll").unwrap());
+        assert!(db.fetch_transaction_draft("payroll").unwrap().is_none());
+    }
 }
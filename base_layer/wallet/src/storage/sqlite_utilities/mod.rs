@@ -22,6 +22,7 @@
 
 use std::{fs::File, ops::DerefMut, path::Path, time::Duration};
 
+use diesel::{sql_query, sql_types::Text, RunQueryDsl};
 use diesel_migrations::{EmbeddedMigrations, MigrationHarness};
 use fs2::FileExt;
 use log::*;
@@ -153,7 +154,7 @@ pub fn initialize_sqlite_database_backends<P: AsRef<Path>>(
     let wallet_backend = WalletSqliteDatabase::new(connection.clone(), passphrase)?;
     let transaction_backend = TransactionServiceSqliteDatabase::new(connection.clone(), wallet_backend.cipher());
     let output_manager_backend = OutputManagerSqliteDatabase::new(connection.clone());
-    let contacts_backend = ContactsServiceSqliteDatabase::init(connection.clone());
+    let contacts_backend = ContactsServiceSqliteDatabase::init_with_cipher(connection.clone(), wallet_backend.cipher());
     let key_manager_backend = KeyManagerSqliteDatabase::init(connection, wallet_backend.cipher());
     Ok((
         wallet_backend,
@@ -187,3 +188,27 @@ pub fn get_last_network<P: AsRef<Path>>(db_path: P) -> Result<Option<String>, Wa
 
     WalletSettingSql::get(&DbKey::LastAccessedNetwork, pool.get_pooled_connection()?.deref_mut())
 }
+
+/// Writes a consistent copy of the wallet database at `db_path` to `backup_path`, which must not exist. This is safe
+/// to call while the wallet is running.
+pub fn backup_sqlite_database<P: AsRef<Path>, Q: AsRef<Path>>(
+    db_path: P,
+    backup_path: Q,
+) -> Result<(), WalletStorageError> {
+    let path_str = db_path
+        .as_ref()
+        .to_str()
+        .ok_or(WalletStorageError::InvalidUnicodePath)?;
+    let backup_path_str = backup_path
+        .as_ref()
+        .to_str()
+        .ok_or(WalletStorageError::InvalidUnicodePath)?;
+
+    let mut pool = SqliteConnectionPool::new(String::from(path_str), 1, true, true, Duration::from_secs(60));
+    pool.create_pool()?;
+
+    sql_query("VACUUM INTO ?")
+        .bind::<Text, _>(backup_path_str)
+        .execute(pool.get_pooled_connection()?.deref_mut())?;
+    Ok(())
+}
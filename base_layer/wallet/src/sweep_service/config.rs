@@ -0,0 +1,26 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tari_common::configuration::serializers;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SweepServiceConfig {
+    /// The time between checks of the available balance against the sweep policy
+    #[serde(with = "serializers::seconds")]
+    pub check_interval: Duration,
+    /// The size of the channel that sweep events are published on
+    pub event_channel_size: usize,
+}
+
+impl Default for SweepServiceConfig {
+    fn default() -> Self {
+        Self {
+            check_interval: Duration::from_secs(60),
+            event_channel_size: 100,
+        }
+    }
+}
@@ -0,0 +1,29 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use tari_service_framework::reply_channel::TransportChannelError;
+use thiserror::Error;
+
+use crate::{
+    error::WalletStorageError,
+    output_manager_service::error::OutputManagerError,
+    transaction_service::error::TransactionServiceError,
+};
+
+#[derive(Debug, Error)]
+pub enum SweepError {
+    #[error("Invalid sweep policy: {0}")]
+    InvalidPolicy(String),
+    #[error("Sweep state serialization error: {0}")]
+    SerializationError(#[from] serde_json::Error),
+    #[error("Wallet storage error: `{0}`")]
+    WalletStorageError(#[from] WalletStorageError),
+    #[error("Output manager error: `{0}`")]
+    OutputManagerError(#[from] OutputManagerError),
+    #[error("Transaction service error: `{0}`")]
+    TransactionServiceError(#[from] TransactionServiceError),
+    #[error("Transport channel error: `{0}`")]
+    TransportChannelError(#[from] TransportChannelError),
+    #[error("Unexpected API response")]
+    UnexpectedApiResponse,
+}
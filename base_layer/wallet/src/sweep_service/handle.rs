@@ -0,0 +1,88 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::{fmt, sync::Arc};
+
+use tari_service_framework::reply_channel::SenderService;
+use tokio::sync::broadcast;
+use tower::Service;
+
+use crate::sweep_service::{
+    error::SweepError,
+    policy::{SweepEvent, SweepPolicy, SweepRecord},
+};
+
+pub type SweepEventSender = broadcast::Sender<Arc<SweepEvent>>;
+pub type SweepEventReceiver = broadcast::Receiver<Arc<SweepEvent>>;
+
+#[derive(Debug)]
+pub enum SweepRequest {
+    SetPolicy(Option<SweepPolicy>),
+    GetPolicy,
+    GetSweeps,
+}
+
+impl fmt::Display for SweepRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SetPolicy(Some(policy)) => write!(f, "SetPolicy({})", policy),
+            Self::SetPolicy(None) => write!(f, "SetPolicy(None)"),
+            Self::GetPolicy => write!(f, "GetPolicy"),
+            Self::GetSweeps => write!(f, "GetSweeps"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SweepResponse {
+    PolicySet,
+    Policy(Option<SweepPolicy>),
+    Sweeps(Vec<SweepRecord>),
+}
+
+/// Sweeps excess funds from a hot wallet to a cold address, and requests top-ups when the float runs low, according to
+/// a policy that is persisted in the wallet database.
+#[derive(Clone)]
+pub struct SweepHandle {
+    handle: SenderService<SweepRequest, Result<SweepResponse, SweepError>>,
+    event_stream_sender: SweepEventSender,
+}
+
+impl SweepHandle {
+    pub fn new(
+        handle: SenderService<SweepRequest, Result<SweepResponse, SweepError>>,
+        event_stream_sender: SweepEventSender,
+    ) -> Self {
+        Self {
+            handle,
+            event_stream_sender,
+        }
+    }
+
+    pub fn get_event_stream(&self) -> SweepEventReceiver {
+        self.event_stream_sender.subscribe()
+    }
+
+    /// Sets the sweep policy, or disables sweeping if None. The balance is checked against a new policy immediately.
+    pub async fn set_policy(&mut self, policy: Option<SweepPolicy>) -> Result<(), SweepError> {
+        match self.handle.call(SweepRequest::SetPolicy(policy)).await?? {
+            SweepResponse::PolicySet => Ok(()),
+            _ => Err(SweepError::UnexpectedApiResponse),
+        }
+    }
+
+    pub async fn get_policy(&mut self) -> Result<Option<SweepPolicy>, SweepError> {
+        match self.handle.call(SweepRequest::GetPolicy).await?? {
+            SweepResponse::Policy(policy) => Ok(policy),
+            _ => Err(SweepError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Returns the most recent sweeps, newest first
+    pub async fn get_sweeps(&mut self) -> Result<Vec<SweepRecord>, SweepError> {
+        match self.handle.call(SweepRequest::GetSweeps).await?? {
+            SweepResponse::Sweeps(sweeps) => Ok(sweeps),
+            _ => Err(SweepError::UnexpectedApiResponse),
+        }
+    }
+}
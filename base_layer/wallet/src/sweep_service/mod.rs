@@ -0,0 +1,83 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Pairs a hot wallet with a cold (watch-only) address. The hot wallet keeps a small float: available funds above a
+//! threshold are swept to the cold address, and a top-up is requested when the available balance drops below a floor.
+//! The policy is persisted in the wallet database and an event is published for every sweep and top-up request.
+
+pub mod config;
+pub mod error;
+pub mod handle;
+pub mod policy;
+pub mod service;
+
+use log::*;
+use tari_service_framework::{
+    async_trait,
+    reply_channel,
+    ServiceInitializationError,
+    ServiceInitializer,
+    ServiceInitializerContext,
+};
+use tokio::sync::broadcast;
+
+use crate::{
+    output_manager_service::handle::OutputManagerHandle,
+    storage::database::{WalletBackend, WalletDatabase},
+    sweep_service::{config::SweepServiceConfig, handle::SweepHandle, service::SweepService},
+    transaction_service::handle::TransactionServiceHandle,
+};
+
+const LOG_TARGET: &str = "wallet::sweep_service";
+
+pub const SWEEP_STATE_KEY: &str = "sweep_state";
+
+pub struct SweepServiceInitializer<T>
+where T: WalletBackend + 'static
+{
+    config: SweepServiceConfig,
+    db: WalletDatabase<T>,
+}
+
+impl<T> SweepServiceInitializer<T>
+where T: WalletBackend + 'static
+{
+    pub fn new(config: SweepServiceConfig, db: WalletDatabase<T>) -> Self {
+        Self { config, db }
+    }
+}
+
+#[async_trait]
+impl<T> ServiceInitializer for SweepServiceInitializer<T>
+where T: WalletBackend + 'static
+{
+    async fn initialize(&mut self, context: ServiceInitializerContext) -> Result<(), ServiceInitializationError> {
+        info!(target: LOG_TARGET, "Wallet sweep service initializing.");
+        let (sender, request_stream) = reply_channel::unbounded();
+        let (event_publisher, _) = broadcast::channel(self.config.event_channel_size);
+        context.register_handle(SweepHandle::new(sender, event_publisher.clone()));
+
+        let config = self.config.clone();
+        let db = self.db.clone();
+        context.spawn_when_ready(move |handles| async move {
+            let output_manager_service = handles.expect_handle::<OutputManagerHandle>();
+            let transaction_service = handles.expect_handle::<TransactionServiceHandle>();
+
+            let result = SweepService::new(
+                config,
+                db,
+                request_stream,
+                output_manager_service,
+                transaction_service,
+                event_publisher,
+                handles.get_shutdown_signal(),
+            )
+            .start()
+            .await;
+
+            info!(target: LOG_TARGET, "Wallet Sweep Service shutdown with result {:?}", result);
+        });
+
+        Ok(())
+    }
+}
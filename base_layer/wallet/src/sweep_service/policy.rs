@@ -0,0 +1,231 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::{collections::VecDeque, fmt};
+
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use tari_common_types::{tari_address::TariAddress, transaction::TxId};
+use tari_core::transactions::tari_amount::MicroMinotari;
+
+/// The number of sweeps that are kept in the history
+const MAX_SWEEP_HISTORY: usize = 100;
+
+/// Keeps a small float in a hot wallet. Available funds above `sweep_threshold` are swept to `cold_address`, leaving
+/// `float` in the wallet, and a top-up is requested when the available balance drops below `top_up_floor`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SweepPolicy {
+    /// The address that excess funds are swept to, usually that of a watch-only wallet. Sweeps are sent as stealth
+    /// one-sided transactions, so the cold wallet does not need to be online.
+    pub cold_address: TariAddress,
+    /// Funds are swept when the available balance exceeds this
+    pub sweep_threshold: MicroMinotari,
+    /// The available balance that is left in the hot wallet after a sweep, less the fee of the sweep
+    pub float: MicroMinotari,
+    /// A top-up is requested when the available balance drops below this
+    pub top_up_floor: MicroMinotari,
+    /// The fee per gram of sweep transactions
+    pub fee_per_gram: MicroMinotari,
+}
+
+impl SweepPolicy {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.float >= self.sweep_threshold {
+            return Err(format!(
+                "The float ({}) must be less than the sweep threshold ({})",
+                self.float, self.sweep_threshold
+            ));
+        }
+        if self.top_up_floor > self.float {
+            return Err(format!(
+                "The top-up floor ({}) must not be more than the float ({})",
+                self.top_up_floor, self.float
+            ));
+        }
+        if self.fee_per_gram == MicroMinotari::zero() {
+            return Err("The fee per gram must be greater than zero".to_string());
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for SweepPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "sweep above {} to {} leaving {}, top up below {}",
+            self.sweep_threshold, self.cold_address, self.float, self.top_up_floor
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SweepAction {
+    /// Send this amount to the cold address
+    Sweep(MicroMinotari),
+    /// Request this amount from the cold wallet to restore the float
+    RequestTopUp(MicroMinotari),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SweepEvent {
+    /// Excess funds were swept to the cold address
+    Swept(SweepRecord),
+    /// Sending a sweep failed, it is retried at the next check
+    SweepFailed { amount: MicroMinotari, reason: String },
+    /// The available balance dropped below the top-up floor, and this amount is needed to restore the float
+    TopUpRequested {
+        available_balance: MicroMinotari,
+        amount: MicroMinotari,
+    },
+}
+
+impl fmt::Display for SweepEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SweepEvent::Swept(record) => write!(
+                f,
+                "Swept {} to {} (TxId: {})",
+                record.amount, record.cold_address, record.tx_id
+            ),
+            SweepEvent::SweepFailed { amount, reason } => write!(f, "Failed to sweep {}: {}", amount, reason),
+            SweepEvent::TopUpRequested {
+                available_balance,
+                amount,
+            } => write!(
+                f,
+                "Top-up of {} requested, the available balance is {}",
+                amount, available_balance
+            ),
+        }
+    }
+}
+
+/// A sweep that was sent to the cold address
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SweepRecord {
+    pub tx_id: TxId,
+    pub amount: MicroMinotari,
+    pub cold_address: TariAddress,
+    pub timestamp: NaiveDateTime,
+}
+
+/// The persisted state of the sweep service
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SweepState {
+    policy: Option<SweepPolicy>,
+    /// Set when a top-up was requested, so that it is only requested once until the balance recovers
+    top_up_requested: bool,
+    sweeps: VecDeque<SweepRecord>,
+}
+
+impl SweepState {
+    pub fn policy(&self) -> Option<&SweepPolicy> {
+        self.policy.as_ref()
+    }
+
+    pub fn set_policy(&mut self, policy: Option<SweepPolicy>) {
+        self.policy = policy;
+        self.top_up_requested = false;
+    }
+
+    /// The most recent sweeps, newest first
+    pub fn sweeps(&self) -> impl Iterator<Item = &SweepRecord> {
+        self.sweeps.iter().rev()
+    }
+
+    /// Decides what to do for the given available balance, and records a top-up request
+    pub fn next_action(&mut self, available_balance: MicroMinotari) -> Option<SweepAction> {
+        let policy = self.policy.as_ref()?;
+        if available_balance > policy.sweep_threshold {
+            return Some(SweepAction::Sweep(available_balance - policy.float));
+        }
+        if available_balance >= policy.top_up_floor {
+            self.top_up_requested = false;
+            return None;
+        }
+        if self.top_up_requested {
+            return None;
+        }
+        self.top_up_requested = true;
+        Some(SweepAction::RequestTopUp(policy.float - available_balance))
+    }
+
+    pub fn record_sweep(&mut self, record: SweepRecord) {
+        self.sweeps.push_back(record);
+        while self.sweeps.len() > MAX_SWEEP_HISTORY {
+            self.sweeps.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn policy() -> SweepPolicy {
+        SweepPolicy {
+            cold_address: TariAddress::default(),
+            sweep_threshold: MicroMinotari(10_000),
+            float: MicroMinotari(5_000),
+            top_up_floor: MicroMinotari(1_000),
+            fee_per_gram: MicroMinotari(5),
+        }
+    }
+
+    #[test]
+    fn it_validates_the_policy() {
+        assert!(policy().validate().is_ok());
+        let mut invalid = policy();
+        invalid.float = invalid.sweep_threshold;
+        assert!(invalid.validate().is_err());
+        let mut invalid = policy();
+        invalid.top_up_floor = MicroMinotari(6_000);
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn it_sweeps_above_the_threshold() {
+        let mut state = SweepState::default();
+        assert_eq!(state.next_action(MicroMinotari(50_000)), None);
+        state.set_policy(Some(policy()));
+        assert_eq!(state.next_action(MicroMinotari(10_000)), None);
+        assert_eq!(
+            state.next_action(MicroMinotari(12_000)),
+            Some(SweepAction::Sweep(MicroMinotari(7_000)))
+        );
+    }
+
+    #[test]
+    fn it_requests_a_top_up_once_below_the_floor() {
+        let mut state = SweepState::default();
+        state.set_policy(Some(policy()));
+        assert_eq!(
+            state.next_action(MicroMinotari(800)),
+            Some(SweepAction::RequestTopUp(MicroMinotari(4_200)))
+        );
+        assert_eq!(state.next_action(MicroMinotari(500)), None);
+        // Recovering above the floor resets the request
+        assert_eq!(state.next_action(MicroMinotari(3_000)), None);
+        assert_eq!(
+            state.next_action(MicroMinotari(900)),
+            Some(SweepAction::RequestTopUp(MicroMinotari(4_100)))
+        );
+    }
+
+    #[test]
+    fn it_bounds_the_sweep_history() {
+        let mut state = SweepState::default();
+        let max = u64::try_from(MAX_SWEEP_HISTORY).unwrap();
+        for i in 0..=max {
+            state.record_sweep(SweepRecord {
+                tx_id: TxId::from(i),
+                amount: MicroMinotari(1),
+                cold_address: TariAddress::default(),
+                timestamp: NaiveDateTime::default(),
+            });
+        }
+        assert_eq!(state.sweeps().count(), MAX_SWEEP_HISTORY);
+        assert_eq!(state.sweeps().next().unwrap().tx_id, TxId::from(max));
+    }
+}
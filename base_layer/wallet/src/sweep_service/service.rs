@@ -0,0 +1,191 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use futures::StreamExt;
+use log::*;
+use tari_core::transactions::{
+    tari_amount::MicroMinotari,
+    transaction_components::{encrypted_data::PaymentId, OutputFeatures},
+};
+use tari_service_framework::reply_channel::Receiver;
+use tari_shutdown::ShutdownSignal;
+use tokio::time::{self, MissedTickBehavior};
+
+use crate::{
+    output_manager_service::{handle::OutputManagerHandle, UtxoSelectionCriteria},
+    storage::database::{WalletBackend, WalletDatabase},
+    sweep_service::{
+        config::SweepServiceConfig,
+        error::SweepError,
+        handle::{SweepEventSender, SweepRequest, SweepResponse},
+        policy::{SweepAction, SweepEvent, SweepPolicy, SweepRecord, SweepState},
+        SWEEP_STATE_KEY,
+    },
+    transaction_service::handle::TransactionServiceHandle,
+};
+
+const LOG_TARGET: &str = "wallet::sweep_service::service";
+
+pub struct SweepService<T>
+where T: WalletBackend + 'static
+{
+    config: SweepServiceConfig,
+    db: WalletDatabase<T>,
+    state: SweepState,
+    request_stream: Option<Receiver<SweepRequest, Result<SweepResponse, SweepError>>>,
+    output_manager_service: OutputManagerHandle,
+    transaction_service: TransactionServiceHandle,
+    event_publisher: SweepEventSender,
+    shutdown_signal: ShutdownSignal,
+}
+
+impl<T> SweepService<T>
+where T: WalletBackend + 'static
+{
+    pub fn new(
+        config: SweepServiceConfig,
+        db: WalletDatabase<T>,
+        request_stream: Receiver<SweepRequest, Result<SweepResponse, SweepError>>,
+        output_manager_service: OutputManagerHandle,
+        transaction_service: TransactionServiceHandle,
+        event_publisher: SweepEventSender,
+        shutdown_signal: ShutdownSignal,
+    ) -> Self {
+        Self {
+            config,
+            db,
+            state: SweepState::default(),
+            request_stream: Some(request_stream),
+            output_manager_service,
+            transaction_service,
+            event_publisher,
+            shutdown_signal,
+        }
+    }
+
+    pub async fn start(mut self) -> Result<(), SweepError> {
+        let mut request_stream = self
+            .request_stream
+            .take()
+            .expect("Sweep Service initialized without request_stream");
+        let mut shutdown_signal = self.shutdown_signal.clone();
+
+        if let Some(state) = self.db.get_client_key_value(SWEEP_STATE_KEY.to_string())? {
+            self.state = serde_json::from_str(&state)?;
+        }
+        let mut interval = time::interval(self.config.check_interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        debug!(target: LOG_TARGET, "Sweep Service started");
+        loop {
+            tokio::select! {
+                Some(request_context) = request_stream.next() => {
+                    let (request, reply_tx) = request_context.split();
+                    let response = self.handle_request(request).await.map_err(|e| {
+                        error!(target: LOG_TARGET, "Error handling request: {:?}", e);
+                        e
+                    });
+                    let _result = reply_tx.send(response).inspect_err(|_| {
+                        warn!(target: LOG_TARGET, "Failed to send reply");
+                    });
+                },
+                _ = interval.tick() => {
+                    if let Err(e) = self.check_balance().await {
+                        warn!(target: LOG_TARGET, "Failed to check the balance against the sweep policy: {}", e);
+                    }
+                },
+                _ = shutdown_signal.wait() => {
+                    info!(target: LOG_TARGET, "Sweep Service shutting down because the shutdown signal was received");
+                    break;
+                },
+            }
+        }
+        Ok(())
+    }
+
+    async fn handle_request(&mut self, request: SweepRequest) -> Result<SweepResponse, SweepError> {
+        trace!(target: LOG_TARGET, "Handling Sweep Service Request: {}", request);
+        match request {
+            SweepRequest::SetPolicy(policy) => {
+                if let Some(policy) = &policy {
+                    policy.validate().map_err(SweepError::InvalidPolicy)?;
+                }
+                let mut state = self.state.clone();
+                state.set_policy(policy);
+                self.commit(state)?;
+                if let Err(e) = self.check_balance().await {
+                    warn!(target: LOG_TARGET, "Failed to check the balance against the new sweep policy: {}", e);
+                }
+                Ok(SweepResponse::PolicySet)
+            },
+            SweepRequest::GetPolicy => Ok(SweepResponse::Policy(self.state.policy().cloned())),
+            SweepRequest::GetSweeps => Ok(SweepResponse::Sweeps(self.state.sweeps().cloned().collect())),
+        }
+    }
+
+    async fn check_balance(&mut self) -> Result<(), SweepError> {
+        let Some(policy) = self.state.policy().cloned() else {
+            return Ok(());
+        };
+        let balance = self.output_manager_service.get_balance().await?;
+        let mut state = self.state.clone();
+        let action = state.next_action(balance.available_balance);
+        let event = match action {
+            Some(SweepAction::Sweep(amount)) => match self.sweep(&policy, amount).await {
+                Ok(record) => {
+                    state.record_sweep(record.clone());
+                    Some(SweepEvent::Swept(record))
+                },
+                Err(e) => Some(SweepEvent::SweepFailed {
+                    amount,
+                    reason: e.to_string(),
+                }),
+            },
+            Some(SweepAction::RequestTopUp(amount)) => Some(SweepEvent::TopUpRequested {
+                available_balance: balance.available_balance,
+                amount,
+            }),
+            None => None,
+        };
+        if state != self.state {
+            self.commit(state)?;
+        }
+        if let Some(event) = event {
+            info!(target: LOG_TARGET, "{}", event);
+            // Nobody might be subscribed
+            let _size = self.event_publisher.send(Arc::new(event));
+        }
+        Ok(())
+    }
+
+    async fn sweep(&mut self, policy: &SweepPolicy, amount: MicroMinotari) -> Result<SweepRecord, SweepError> {
+        let tx_id = self
+            .transaction_service
+            .send_one_sided_to_stealth_address_transaction(
+                policy.cold_address.clone(),
+                amount,
+                UtxoSelectionCriteria::default(),
+                OutputFeatures::default(),
+                policy.fee_per_gram,
+                "Sweep to cold wallet".to_string(),
+                PaymentId::Empty,
+            )
+            .await?;
+        Ok(SweepRecord {
+            tx_id,
+            amount,
+            cold_address: policy.cold_address.clone(),
+            timestamp: Utc::now().naive_utc(),
+        })
+    }
+
+    fn commit(&mut self, state: SweepState) -> Result<(), SweepError> {
+        self.db
+            .set_client_key_value(SWEEP_STATE_KEY.to_string(), serde_json::to_string(&state)?)?;
+        self.state = state;
+        Ok(())
+    }
+}
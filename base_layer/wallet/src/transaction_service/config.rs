@@ -26,6 +26,8 @@ use log::*;
 use serde::{Deserialize, Serialize};
 use tari_common::configuration::serializers;
 
+use crate::transaction_service::spending_policy::SpendingPolicyConfig;
+
 const LOG_TARGET: &str = "wallet::transaction_service::config";
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -69,6 +71,12 @@ pub struct TransactionServiceConfig {
     /// This is the timeout period that will be used to re-submit transactions not found in the mempool
     #[serde(with = "serializers::seconds")]
     pub transaction_mempool_resubmission_window: Duration,
+    /// If set, each transaction is first submitted to the base node after a random delay of up to this many seconds,
+    /// so that the submission timing cannot be linked to the moment the transaction was created
+    #[serde(with = "serializers::optional_seconds")]
+    pub broadcast_delay_max: Option<Duration>,
+    /// The limits and approval rules that outgoing payments are checked against
+    pub spending_policy: SpendingPolicyConfig,
 }
 
 impl Default for TransactionServiceConfig {
@@ -87,6 +95,8 @@ impl Default for TransactionServiceConfig {
             transaction_routing_mechanism: TransactionRoutingMechanism::default(),
             transaction_event_channel_size: 1000,
             transaction_mempool_resubmission_window: Duration::from_secs(600),
+            broadcast_delay_max: None,
+            spending_policy: SpendingPolicyConfig::default(),
         }
     }
 }
@@ -0,0 +1,652 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Diagnoses transactions that appear to be stuck. The stored state of a transaction is combined with the state of its
+//! protocols in the transaction service and the latest responses from the base node, and explained in plain terms
+//! along with the remediations that are safe to apply.
+
+use std::{
+    collections::HashMap,
+    fmt::{Display, Error, Formatter},
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tari_common_types::transaction::{TransactionStatus, TxId};
+
+use crate::transaction_service::{
+    config::TransactionServiceConfig,
+    storage::models::{
+        CompletedTransaction,
+        InboundTransaction,
+        OutboundTransaction,
+        TxCancellationReason,
+        WalletTransaction,
+    },
+};
+
+/// A response from the base node to the broadcast protocol
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BaseNodeResponse {
+    /// The RPC call to the base node failed
+    RpcFailed,
+    /// The base node is not synced and could not process the request
+    NotSynced,
+    /// The transaction was accepted into the mempool
+    Accepted,
+    /// The transaction was rejected by the mempool
+    Rejected(TxCancellationReason),
+    /// The transaction is already mined
+    AlreadyMined,
+    /// The transaction was found in the mempool
+    InMempool,
+    /// The transaction was found in a block
+    Mined,
+    /// The transaction was neither in the mempool nor mined
+    NotFound,
+}
+
+impl Display for BaseNodeResponse {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self {
+            Self::RpcFailed => write!(f, "the request to the base node failed"),
+            Self::NotSynced => write!(f, "the base node is not synced"),
+            Self::Accepted => write!(f, "accepted into the mempool"),
+            Self::Rejected(reason) => write!(f, "rejected by the mempool ({})", reason),
+            Self::AlreadyMined => write!(f, "already mined"),
+            Self::InMempool => write!(f, "found in the mempool"),
+            Self::Mined => write!(f, "found in a block"),
+            Self::NotFound => write!(f, "not found in the mempool or the chain"),
+        }
+    }
+}
+
+/// The broadcast history of a transaction since the wallet was started
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BroadcastRecord {
+    pub num_responses: u32,
+    pub last_response: BaseNodeResponse,
+    pub last_response_timestamp: NaiveDateTime,
+}
+
+/// Records the responses the broadcast protocols receive from the base node. This is kept in memory only.
+#[derive(Debug, Clone, Default)]
+pub struct BroadcastJournal {
+    records: Arc<Mutex<HashMap<TxId, BroadcastRecord>>>,
+}
+
+impl BroadcastJournal {
+    pub fn record(&self, tx_id: TxId, response: BaseNodeResponse) {
+        let now = Utc::now().naive_utc();
+        let mut records = self.records.lock().expect("BroadcastJournal lock poisoned");
+        records
+            .entry(tx_id)
+            .and_modify(|record| {
+                record.num_responses = record.num_responses.saturating_add(1);
+                record.last_response = response;
+                record.last_response_timestamp = now;
+            })
+            .or_insert(BroadcastRecord {
+                num_responses: 1,
+                last_response: response,
+                last_response_timestamp: now,
+            });
+    }
+
+    pub fn get(&self, tx_id: TxId) -> Option<BroadcastRecord> {
+        self.records
+            .lock()
+            .expect("BroadcastJournal lock poisoned")
+            .get(&tx_id)
+            .cloned()
+    }
+}
+
+/// The state of a transaction in the transaction service that is not stored in the database
+#[derive(Debug, Clone, Default)]
+pub struct ProtocolState {
+    /// A send or receive protocol is running for the transaction
+    pub negotiation_protocol_running: bool,
+    /// A broadcast protocol is running for the transaction
+    pub broadcast_protocol_running: bool,
+    /// A base node has been set
+    pub base_node_set: bool,
+    pub broadcast: Option<BroadcastRecord>,
+}
+
+/// A remediation that can be applied to a stuck transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Remediation {
+    /// Restart the negotiation protocol, which resends the transaction or reply to the counterparty
+    Resend,
+    /// Cancel the pending transaction and release its funds
+    Cancel,
+    /// Restart the broadcast protocol, which resubmits the transaction to the base node
+    Rebroadcast,
+}
+
+impl Display for Remediation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self {
+            Self::Resend => write!(f, "resend"),
+            Self::Cancel => write!(f, "cancel"),
+            Self::Rebroadcast => write!(f, "rebroadcast"),
+        }
+    }
+}
+
+impl FromStr for Remediation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "resend" => Ok(Self::Resend),
+            "cancel" => Ok(Self::Cancel),
+            "rebroadcast" => Ok(Self::Rebroadcast),
+            _ => Err(format!(
+                "Unknown remediation '{}', expected resend, cancel or rebroadcast",
+                s
+            )),
+        }
+    }
+}
+
+/// Where a transaction is in its lifecycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TransactionStage {
+    /// An outbound transaction is waiting for the reply of the recipient
+    AwaitingReply,
+    /// An inbound transaction is waiting for the sender to finalize it
+    AwaitingFinalization,
+    /// A completed transaction has not been accepted by the base node yet
+    AwaitingBroadcast,
+    /// The transaction is in the mempool
+    InMempool,
+    /// The transaction is mined, but does not have the required number of confirmations
+    AwaitingConfirmations,
+    /// The transaction is mined and confirmed, or was imported from the chain
+    Finished,
+    Cancelled,
+}
+
+impl Display for TransactionStage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self {
+            Self::AwaitingReply => write!(f, "waiting for the recipient's reply"),
+            Self::AwaitingFinalization => write!(f, "waiting for the sender to finalize"),
+            Self::AwaitingBroadcast => write!(f, "waiting to be accepted by the base node"),
+            Self::InMempool => write!(f, "in the mempool"),
+            Self::AwaitingConfirmations => write!(f, "waiting for confirmations"),
+            Self::Finished => write!(f, "finished"),
+            Self::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionDiagnosis {
+    pub tx_id: TxId,
+    pub stage: TransactionStage,
+    /// Whether the transaction is not progressing as expected
+    pub is_stuck: bool,
+    /// Plain explanations of the state of the transaction
+    pub findings: Vec<String>,
+    /// The remediations that are safe to apply, most appropriate first
+    pub remediations: Vec<Remediation>,
+}
+
+impl TransactionDiagnosis {
+    pub fn allows(&self, remediation: Remediation) -> bool {
+        self.remediations.contains(&remediation)
+    }
+}
+
+impl Display for TransactionDiagnosis {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        writeln!(f, "Transaction {}: {}", self.tx_id, self.stage)?;
+        for finding in &self.findings {
+            writeln!(f, "  - {}", finding)?;
+        }
+        if self.remediations.is_empty() {
+            write!(f, "No remediation is available")
+        } else {
+            let remediations = self.remediations.iter().map(|r| r.to_string()).collect::<Vec<_>>();
+            write!(f, "Available remediations: {}", remediations.join(", "))
+        }
+    }
+}
+
+/// Diagnoses the transaction at time `now`
+pub fn diagnose(
+    tx: &WalletTransaction,
+    state: &ProtocolState,
+    config: &TransactionServiceConfig,
+    now: NaiveDateTime,
+) -> TransactionDiagnosis {
+    match tx {
+        WalletTransaction::PendingOutbound(tx) => diagnose_outbound(tx, state, config, now),
+        WalletTransaction::PendingInbound(tx) => diagnose_inbound(tx, state, config, now),
+        WalletTransaction::Completed(tx) => diagnose_completed(tx, state, config, now),
+    }
+}
+
+fn diagnose_outbound(
+    tx: &OutboundTransaction,
+    state: &ProtocolState,
+    config: &TransactionServiceConfig,
+    now: NaiveDateTime,
+) -> TransactionDiagnosis {
+    let mut diagnosis = pending_diagnosis(tx.tx_id, TransactionStage::AwaitingReply, tx.cancelled);
+    if tx.cancelled {
+        return diagnosis;
+    }
+    diagnosis.findings.push(describe_delivery(
+        "transaction",
+        tx.send_count,
+        tx.direct_send_success,
+        tx.last_send_timestamp,
+        now,
+    ));
+    if tx.status == TransactionStatus::Queued {
+        diagnosis
+            .findings
+            .push("The transaction is queued because the recipient could not be reached yet".to_string());
+    }
+    diagnose_negotiation(
+        &mut diagnosis,
+        tx.timestamp,
+        state,
+        config,
+        now,
+        "recipient has not replied",
+    );
+    diagnosis
+}
+
+fn diagnose_inbound(
+    tx: &InboundTransaction,
+    state: &ProtocolState,
+    config: &TransactionServiceConfig,
+    now: NaiveDateTime,
+) -> TransactionDiagnosis {
+    let mut diagnosis = pending_diagnosis(tx.tx_id, TransactionStage::AwaitingFinalization, tx.cancelled);
+    if tx.cancelled {
+        return diagnosis;
+    }
+    diagnosis.findings.push(describe_delivery(
+        "reply",
+        tx.send_count,
+        tx.direct_send_success,
+        tx.last_send_timestamp,
+        now,
+    ));
+    diagnose_negotiation(
+        &mut diagnosis,
+        tx.timestamp,
+        state,
+        config,
+        now,
+        "sender has not finalized the transaction",
+    );
+    diagnosis
+}
+
+fn pending_diagnosis(tx_id: TxId, stage: TransactionStage, cancelled: bool) -> TransactionDiagnosis {
+    let mut diagnosis = TransactionDiagnosis {
+        tx_id,
+        stage,
+        is_stuck: false,
+        findings: vec![],
+        remediations: vec![],
+    };
+    if cancelled {
+        diagnosis.stage = TransactionStage::Cancelled;
+        diagnosis
+            .findings
+            .push("The transaction was cancelled before it was completed".to_string());
+    }
+    diagnosis
+}
+
+fn describe_delivery(
+    what: &str,
+    send_count: u32,
+    direct_send_success: bool,
+    last_send_timestamp: Option<NaiveDateTime>,
+    now: NaiveDateTime,
+) -> String {
+    match last_send_timestamp {
+        None => format!("The {} has not been sent to the counterparty yet", what),
+        Some(timestamp) => {
+            let route = if direct_send_success {
+                "was delivered directly"
+            } else {
+                "could not be delivered directly and was left with store-and-forward nodes"
+            };
+            format!(
+                "The {} has been sent {} time(s) and {}, most recently {} ago",
+                what,
+                send_count,
+                route,
+                format_elapsed(now - timestamp)
+            )
+        },
+    }
+}
+
+fn diagnose_negotiation(
+    diagnosis: &mut TransactionDiagnosis,
+    timestamp: NaiveDateTime,
+    state: &ProtocolState,
+    config: &TransactionServiceConfig,
+    now: NaiveDateTime,
+    waiting_for: &str,
+) {
+    let age = now - timestamp;
+    let cancellation_timeout = chrono::Duration::from_std(config.pending_transaction_cancellation_timeout)
+        .unwrap_or_else(|_| chrono::Duration::max_value());
+    if state.negotiation_protocol_running {
+        diagnosis.findings.push(format!(
+            "The {} after {}. It will be resent every {} and cancelled automatically {} after it was created",
+            waiting_for,
+            format_elapsed(age),
+            format_elapsed(chrono::Duration::from_std(config.transaction_resend_period).unwrap_or_default()),
+            format_elapsed(cancellation_timeout)
+        ));
+        diagnosis.is_stuck = age > cancellation_timeout / 2;
+        if diagnosis.is_stuck {
+            diagnosis.findings.push(
+                "The counterparty may be offline. Ask them to open their wallet, or cancel the transaction".to_string(),
+            );
+        }
+    } else {
+        diagnosis.is_stuck = true;
+        diagnosis.findings.push(format!(
+            "The {} after {} and no protocol is running for the transaction, so nothing will be resent",
+            waiting_for,
+            format_elapsed(age)
+        ));
+        diagnosis.remediations.push(Remediation::Resend);
+    }
+    diagnosis.remediations.push(Remediation::Cancel);
+}
+
+fn diagnose_completed(
+    tx: &CompletedTransaction,
+    state: &ProtocolState,
+    config: &TransactionServiceConfig,
+    now: NaiveDateTime,
+) -> TransactionDiagnosis {
+    let mut diagnosis = TransactionDiagnosis {
+        tx_id: tx.tx_id,
+        stage: TransactionStage::Finished,
+        is_stuck: false,
+        findings: vec![],
+        remediations: vec![],
+    };
+    if let Some(reason) = tx.cancelled {
+        diagnosis.stage = TransactionStage::Cancelled;
+        diagnosis.findings.push(format!(
+            "The transaction was cancelled: {}",
+            describe_cancellation(reason)
+        ));
+        return diagnosis;
+    }
+
+    match tx.status {
+        TransactionStatus::Completed | TransactionStatus::Broadcast => {
+            diagnosis.stage = if tx.status == TransactionStatus::Completed {
+                TransactionStage::AwaitingBroadcast
+            } else {
+                TransactionStage::InMempool
+            };
+            diagnose_broadcast(&mut diagnosis, tx, state, config, now);
+        },
+        TransactionStatus::MinedUnconfirmed => {
+            diagnosis.stage = TransactionStage::AwaitingConfirmations;
+            diagnosis.findings.push(format!(
+                "The transaction was mined at height {} and has {} of the {} required confirmations",
+                tx.mined_height
+                    .map(|h| h.to_string())
+                    .unwrap_or_else(|| "?".to_string()),
+                tx.confirmations.unwrap_or_default(),
+                config.num_confirmations_required
+            ));
+            if !state.broadcast_protocol_running {
+                diagnosis.findings.push(
+                    "Confirmations are updated by transaction validation, which runs when the base node reports a new \
+                     block"
+                        .to_string(),
+                );
+            }
+        },
+        TransactionStatus::Rejected => {
+            diagnosis.stage = TransactionStage::Cancelled;
+            diagnosis
+                .findings
+                .push("The transaction was rejected by the mempool".to_string());
+        },
+        _ => {
+            diagnosis
+                .findings
+                .push(format!("The transaction is {}, nothing needs to be done", tx.status));
+        },
+    }
+    diagnosis
+}
+
+fn diagnose_broadcast(
+    diagnosis: &mut TransactionDiagnosis,
+    tx: &CompletedTransaction,
+    state: &ProtocolState,
+    config: &TransactionServiceConfig,
+    now: NaiveDateTime,
+) {
+    if !state.base_node_set {
+        diagnosis.is_stuck = true;
+        diagnosis
+            .findings
+            .push("No base node is set, so the transaction cannot be broadcast. Set a base node".to_string());
+        return;
+    }
+
+    match &state.broadcast {
+        None => diagnosis
+            .findings
+            .push("The base node has not responded to the broadcast protocol since the wallet started".to_string()),
+        Some(record) => {
+            diagnosis.findings.push(format!(
+                "The base node has responded {} time(s). Most recently, {} ago, the transaction was {}",
+                record.num_responses,
+                format_elapsed(now - record.last_response_timestamp),
+                record.last_response
+            ));
+            match record.last_response {
+                BaseNodeResponse::RpcFailed => diagnosis.findings.push(
+                    "The connection to the base node is failing. Check that it is online or choose another base node"
+                        .to_string(),
+                ),
+                BaseNodeResponse::NotSynced => diagnosis
+                    .findings
+                    .push("Submission will be retried once the base node has synced".to_string()),
+                BaseNodeResponse::NotFound => diagnosis.findings.push(
+                    "The transaction may have been evicted from the mempool. It will be resubmitted once before it is \
+                     cancelled"
+                        .to_string(),
+                ),
+                _ => {},
+            }
+        },
+    }
+
+    let age = now - tx.timestamp;
+    let monitoring_timeout = chrono::Duration::from_std(config.broadcast_monitoring_timeout).unwrap_or_default();
+    if state.broadcast_protocol_running {
+        diagnosis.is_stuck = state
+            .broadcast
+            .as_ref()
+            .map_or(age > monitoring_timeout * 10, |record| {
+                matches!(
+                    record.last_response,
+                    BaseNodeResponse::RpcFailed | BaseNodeResponse::NotSynced
+                )
+            });
+        diagnosis.findings.push(format!(
+            "The broadcast protocol is running and checks with the base node every {}",
+            format_elapsed(monitoring_timeout)
+        ));
+    } else {
+        diagnosis.is_stuck = true;
+        diagnosis.findings.push(format!(
+            "The transaction was completed {} ago but no broadcast protocol is running for it",
+            format_elapsed(age)
+        ));
+        diagnosis.remediations.push(Remediation::Rebroadcast);
+    }
+}
+
+fn describe_cancellation(reason: TxCancellationReason) -> &'static str {
+    match reason {
+        TxCancellationReason::Unknown => "the reason is unknown",
+        TxCancellationReason::UserCancelled => "it was cancelled by a user",
+        TxCancellationReason::Timeout => "the counterparty did not respond in time",
+        TxCancellationReason::DoubleSpend => "one of its inputs was already spent",
+        TxCancellationReason::Orphan => "one of its inputs does not exist on the chain",
+        TxCancellationReason::TimeLocked => "one of its inputs is still time locked",
+        TxCancellationReason::InvalidTransaction => "the base node found it to be invalid",
+        TxCancellationReason::Oversized => "it is too large to be mined",
+    }
+}
+
+fn format_elapsed(duration: chrono::Duration) -> String {
+    let secs = duration.num_seconds().max(0);
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m", secs / 60),
+        3600..=86399 => format!("{}h {}m", secs / 3600, (secs % 3600) / 60),
+        _ => format!("{}d {}h", secs / 86400, (secs % 86400) / 3600),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tari_common_types::{
+        tari_address::TariAddress,
+        transaction::TransactionDirection,
+        types::{PrivateKey, Signature},
+    };
+    use tari_core::transactions::transaction_components::Transaction;
+
+    use super::*;
+
+    fn completed(status: TransactionStatus, timestamp: NaiveDateTime) -> WalletTransaction {
+        WalletTransaction::Completed(CompletedTransaction {
+            tx_id: 1u64.into(),
+            source_address: TariAddress::default(),
+            destination_address: TariAddress::default(),
+            amount: 100.into(),
+            fee: 5.into(),
+            transaction: Transaction::new(vec![], vec![], vec![], PrivateKey::default(), PrivateKey::default()),
+            status,
+            message: String::new(),
+            timestamp,
+            cancelled: None,
+            direction: TransactionDirection::Outbound,
+            send_count: 1,
+            last_send_timestamp: Some(timestamp),
+            transaction_signature: Signature::default(),
+            confirmations: None,
+            mined_height: None,
+            mined_in_block: None,
+            mined_timestamp: None,
+            payment_id: None,
+        })
+    }
+
+    fn now() -> NaiveDateTime {
+        NaiveDateTime::parse_from_str("2024-03-01 12:00", "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn it_offers_rebroadcast_when_no_protocol_is_running() {
+        let tx = completed(TransactionStatus::Completed, now() - chrono::Duration::hours(2));
+        let state = ProtocolState {
+            base_node_set: true,
+            ..Default::default()
+        };
+        let diagnosis = diagnose(&tx, &state, &TransactionServiceConfig::default(), now());
+        assert_eq!(diagnosis.stage, TransactionStage::AwaitingBroadcast);
+        assert!(diagnosis.is_stuck);
+        assert_eq!(diagnosis.remediations, vec![Remediation::Rebroadcast]);
+        assert!(!diagnosis.allows(Remediation::Cancel));
+        assert_eq!("Rebroadcast".parse::<Remediation>().unwrap(), Remediation::Rebroadcast);
+    }
+
+    #[test]
+    fn it_explains_base_node_responses() {
+        let tx = completed(TransactionStatus::Broadcast, now() - chrono::Duration::minutes(5));
+        let state = ProtocolState {
+            base_node_set: true,
+            broadcast_protocol_running: true,
+            broadcast: Some(BroadcastRecord {
+                num_responses: 3,
+                last_response: BaseNodeResponse::NotSynced,
+                last_response_timestamp: now() - chrono::Duration::seconds(30),
+            }),
+            ..Default::default()
+        };
+        let diagnosis = diagnose(&tx, &state, &TransactionServiceConfig::default(), now());
+        assert_eq!(diagnosis.stage, TransactionStage::InMempool);
+        assert!(diagnosis.is_stuck);
+        assert!(diagnosis.remediations.is_empty());
+        assert!(diagnosis.findings.iter().any(|f| f.contains("not synced")));
+
+        let state = ProtocolState {
+            base_node_set: false,
+            ..state
+        };
+        let diagnosis = diagnose(&tx, &state, &TransactionServiceConfig::default(), now());
+        assert!(diagnosis.findings.iter().any(|f| f.contains("No base node is set")));
+    }
+
+    #[test]
+    fn it_does_not_offer_remediations_for_finished_transactions() {
+        let mut tx = completed(TransactionStatus::MinedConfirmed, now());
+        let diagnosis = diagnose(
+            &tx,
+            &ProtocolState::default(),
+            &TransactionServiceConfig::default(),
+            now(),
+        );
+        assert_eq!(diagnosis.stage, TransactionStage::Finished);
+        assert!(!diagnosis.is_stuck);
+        assert!(diagnosis.remediations.is_empty());
+
+        if let WalletTransaction::Completed(ref mut tx) = tx {
+            tx.status = TransactionStatus::Completed;
+            tx.cancelled = Some(TxCancellationReason::DoubleSpend);
+        }
+        let diagnosis = diagnose(
+            &tx,
+            &ProtocolState::default(),
+            &TransactionServiceConfig::default(),
+            now(),
+        );
+        assert_eq!(diagnosis.stage, TransactionStage::Cancelled);
+        assert!(diagnosis.remediations.is_empty());
+    }
+
+    #[test]
+    fn it_records_broadcast_responses() {
+        let journal = BroadcastJournal::default();
+        let tx_id = TxId::from(1u64);
+        assert!(journal.get(tx_id).is_none());
+        journal.record(tx_id, BaseNodeResponse::Accepted);
+        journal.clone().record(tx_id, BaseNodeResponse::InMempool);
+        let record = journal.get(tx_id).unwrap();
+        assert_eq!(record.num_responses, 2);
+        assert_eq!(record.last_response, BaseNodeResponse::InMempool);
+    }
+}
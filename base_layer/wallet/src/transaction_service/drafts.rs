@@ -0,0 +1,47 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Named transaction drafts, which are prepared and stored in the wallet, reviewed and edited, and sent later in one
+//! go. A draft is not consumed when it is sent, so it can also be used as a template for recurring payments.
+
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use tari_common_types::tari_address::TariAddress;
+use tari_core::transactions::tari_amount::MicroMinotari;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DraftPaymentType {
+    Interactive,
+    OneSided,
+    OneSidedToStealthAddress,
+}
+
+/// One payment of a draft
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DraftRecipient {
+    pub address: TariAddress,
+    pub amount: MicroMinotari,
+    pub fee_per_gram: MicroMinotari,
+    pub message: String,
+    pub payment_type: DraftPaymentType,
+    /// The encoded payment id of one-sided payments
+    pub payment_id: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionDraft {
+    /// Identifies the draft. Saving a draft with the name of an existing one replaces it.
+    pub name: String,
+    /// Free text for the reviewers of the draft
+    pub notes: String,
+    pub recipients: Vec<DraftRecipient>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl TransactionDraft {
+    /// The sum of the amounts of all recipients, excluding fees
+    pub fn total_amount(&self) -> MicroMinotari {
+        self.recipients.iter().map(|r| r.amount).sum()
+    }
+}
@@ -32,6 +32,7 @@ use tari_common_types::{
 use tari_comms::{connectivity::ConnectivityError, peer_manager::node_id::NodeIdError, protocol::rpc::RpcError};
 use tari_comms_dht::outbound::DhtOutboundError;
 use tari_core::transactions::{
+    payment_proof::PaymentProofError,
     transaction_components::{EncryptedDataError, TransactionError},
     transaction_protocol::TransactionProtocolError,
 };
@@ -48,6 +49,8 @@ use crate::{
     error::WalletStorageError,
     output_manager_service::error::OutputManagerError,
     transaction_service::{
+        doctor::Remediation,
+        spending_policy::SpendingPolicyViolation,
         storage::{database::DbKey, sqlite_db::CompletedTransactionConversionError},
         utc::NegativeDurationError,
     },
@@ -198,6 +201,16 @@ pub enum TransactionServiceError {
     NotSupported(String),
     #[error("Tari script error: {0}")]
     ScriptError(#[from] ScriptError),
+    #[error("Spending policy violation: {0}")]
+    SpendingPolicyViolation(#[from] SpendingPolicyViolation),
+    #[error("A proof of payment cannot be produced for transaction {tx_id} because {reason}")]
+    PaymentProofUnavailable { tx_id: TxId, reason: String },
+    #[error("Payment proof error: {0}")]
+    PaymentProofError(#[from] PaymentProofError),
+    #[error("Code template registration `{0}` is not signed by its author")]
+    InvalidTemplateRegistrationSignature(String),
+    #[error("The remediation `{remediation}` is not available for transaction {tx_id}")]
+    RemediationUnavailable { tx_id: TxId, remediation: Remediation },
 }
 
 impl From<RangeProofError> for TransactionServiceError {
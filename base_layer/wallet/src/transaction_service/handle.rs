@@ -39,9 +39,11 @@ use tari_core::{
     mempool::FeePerGramStat,
     proto,
     transactions::{
+        payment_proof::PaymentProof,
         tari_amount::MicroMinotari,
         transaction_components::{
-            encrypted_data::PaymentId,
+            encrypted_data::{EncryptedDataRecord, PaymentId},
+            ApplicationDataPayload,
             BuildInfo,
             CodeTemplateRegistration,
             OutputFeatures,
@@ -62,7 +64,9 @@ use tower::Service;
 use crate::{
     output_manager_service::{service::UseOutput, UtxoSelectionCriteria},
     transaction_service::{
+        doctor::{Remediation, TransactionDiagnosis},
         error::TransactionServiceError,
+        spending_policy::PaymentApproval,
         storage::models::{
             CompletedTransaction,
             InboundTransaction,
@@ -137,6 +141,14 @@ pub enum TransactionServiceRequest {
         fee_per_gram: MicroMinotari,
         message: String,
     },
+    AnchorApplicationData {
+        application_id: u32,
+        payload: ApplicationDataPayload,
+        amount: MicroMinotari,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+        message: String,
+    },
     RegisterCodeTemplate {
         author_public_key: PublicKey,
         author_signature: Signature,
@@ -157,6 +169,16 @@ pub enum TransactionServiceRequest {
         message: String,
         payment_id: PaymentId,
     },
+    SendOneSidedTransactionWithRecords {
+        destination: TariAddress,
+        amount: MicroMinotari,
+        selection_criteria: UtxoSelectionCriteria,
+        output_features: Box<OutputFeatures>,
+        fee_per_gram: MicroMinotari,
+        message: String,
+        payment_id: PaymentId,
+        records: Vec<EncryptedDataRecord>,
+    },
     SendOneSidedToStealthAddressTransaction {
         destination: TariAddress,
         amount: MicroMinotari,
@@ -196,6 +218,14 @@ pub enum TransactionServiceRequest {
     GetFeePerGramStatsPerBlock {
         count: usize,
     },
+    GeneratePaymentProof(TxId),
+    GetPendingApprovals,
+    /// Sends a payment that was held by the spending policy. The limits are checked again.
+    ApprovePayment(u64),
+    RejectPayment(u64),
+    DiagnoseTransaction(TxId),
+    /// Applies a remediation that the diagnosis of the transaction allows
+    ApplyRemediation(TxId, Remediation),
 }
 
 impl fmt::Display for TransactionServiceRequest {
@@ -325,6 +355,18 @@ impl fmt::Display for TransactionServiceRequest {
                 message,
                 ..
             } => write!(f, "Registering VN ({}, {})", validator_node_public_key, message),
+            Self::AnchorApplicationData {
+                application_id,
+                payload,
+                message,
+                ..
+            } => write!(
+                f,
+                "AnchorApplicationData (application id: {}, {} bytes, {})",
+                application_id,
+                payload.len(),
+                message
+            ),
             Self::SendOneSidedTransaction {
                 destination,
                 amount,
@@ -335,6 +377,20 @@ impl fmt::Display for TransactionServiceRequest {
                 "SendOneSidedTransaction (to {}, {}, {})",
                 destination, amount, message
             ),
+            Self::SendOneSidedTransactionWithRecords {
+                destination,
+                amount,
+                message,
+                records,
+                ..
+            } => write!(
+                f,
+                "SendOneSidedTransactionWithRecords (to {}, {}, {}, {} record(s))",
+                destination,
+                amount,
+                message,
+                records.len()
+            ),
             Self::SendOneSidedToStealthAddressTransaction {
                 destination,
                 amount,
@@ -380,6 +436,12 @@ impl fmt::Display for TransactionServiceRequest {
             TransactionServiceRequest::RegisterCodeTemplate { template_name, .. } => {
                 write!(f, "RegisterCodeTemplate: {}", template_name)
             },
+            Self::GeneratePaymentProof(tx_id) => write!(f, "GeneratePaymentProof({})", tx_id),
+            Self::GetPendingApprovals => write!(f, "GetPendingApprovals"),
+            Self::ApprovePayment(id) => write!(f, "ApprovePayment({})", id),
+            Self::RejectPayment(id) => write!(f, "RejectPayment({})", id),
+            Self::DiagnoseTransaction(tx_id) => write!(f, "DiagnoseTransaction({})", tx_id),
+            Self::ApplyRemediation(tx_id, remediation) => write!(f, "ApplyRemediation({}, {})", tx_id, remediation),
         }
     }
 }
@@ -425,6 +487,11 @@ pub enum TransactionServiceResponse {
     CompletedTransactionValidityChanged,
     ShaAtomicSwapTransactionSent(Box<(TxId, PublicKey, TransactionOutput)>),
     FeePerGramStatsPerBlock(FeePerGramStatsResponse),
+    PendingApprovals(Vec<PaymentApproval>),
+    PaymentRejected,
+    PaymentProof(Box<PaymentProof>),
+    TransactionDiagnosis(Box<TransactionDiagnosis>),
+    RemediationApplied,
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq, Default)]
@@ -477,6 +544,12 @@ pub enum TransactionEvent {
         is_valid: bool,
     },
     TransactionValidationStateChanged(OperationId),
+    /// Number of unconfirmed transactions checked against the base node so far, out of the total to check
+    TransactionValidationProgress {
+        operation_id: OperationId,
+        checked: u64,
+        total: u64,
+    },
     TransactionValidationCompleted(OperationId),
     TransactionValidationFailed(OperationId, u64),
     Error(String),
@@ -552,6 +625,16 @@ impl fmt::Display for TransactionEvent {
             TransactionEvent::TransactionValidationStateChanged(operation_id) => {
                 write!(f, "Transaction validation state changed: {operation_id}")
             },
+            TransactionEvent::TransactionValidationProgress {
+                operation_id,
+                checked,
+                total,
+            } => {
+                write!(
+                    f,
+                    "Transaction validation(#{operation_id}) checked {checked} of {total}"
+                )
+            },
             TransactionEvent::TransactionValidationCompleted(operation_id) => {
                 write!(f, "Transaction validation(#{operation_id}) completed")
             },
@@ -671,6 +754,33 @@ impl TransactionServiceHandle {
         }
     }
 
+    /// Sends an output to this wallet that anchors the application data to the base layer
+    pub async fn anchor_application_data(
+        &mut self,
+        application_id: u32,
+        payload: ApplicationDataPayload,
+        amount: MicroMinotari,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+        message: String,
+    ) -> Result<TxId, TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::AnchorApplicationData {
+                application_id,
+                payload,
+                amount,
+                selection_criteria,
+                fee_per_gram,
+                message,
+            })
+            .await??
+        {
+            TransactionServiceResponse::TransactionSent(tx_id) => Ok(tx_id),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
     pub async fn register_code_template(
         &mut self,
         author_public_key: PublicKey,
@@ -731,6 +841,38 @@ impl TransactionServiceHandle {
         }
     }
 
+    /// Sends a one-sided transaction like `send_one_sided_transaction`, storing typed records (memo, refund address)
+    /// in the encrypted data of the recipient's output after the payment id
+    pub async fn send_one_sided_transaction_with_records(
+        &mut self,
+        destination: TariAddress,
+        amount: MicroMinotari,
+        selection_criteria: UtxoSelectionCriteria,
+        output_features: OutputFeatures,
+        fee_per_gram: MicroMinotari,
+        message: String,
+        payment_id: PaymentId,
+        records: Vec<EncryptedDataRecord>,
+    ) -> Result<TxId, TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::SendOneSidedTransactionWithRecords {
+                destination,
+                amount,
+                selection_criteria,
+                output_features: Box::new(output_features),
+                fee_per_gram,
+                message,
+                payment_id,
+                records,
+            })
+            .await??
+        {
+            TransactionServiceResponse::TransactionSent(tx_id) => Ok(tx_id),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
     /// Burns the given amount of Tari from the wallet
     pub async fn burn_tari(
         &mut self,
@@ -1196,4 +1338,82 @@ impl TransactionServiceHandle {
             _ => Err(TransactionServiceError::UnexpectedApiResponse),
         }
     }
+
+    /// The payments held by the spending policy until they are approved
+    pub async fn get_pending_approvals(&mut self) -> Result<Vec<PaymentApproval>, TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::GetPendingApprovals)
+            .await??
+        {
+            TransactionServiceResponse::PendingApprovals(approvals) => Ok(approvals),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Sends a held payment and returns the id of its transaction
+    pub async fn approve_payment(&mut self, approval_id: u64) -> Result<TxId, TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::ApprovePayment(approval_id))
+            .await??
+        {
+            TransactionServiceResponse::TransactionSent(tx_id) |
+            TransactionServiceResponse::BurntTransactionSent { tx_id, .. } => Ok(tx_id),
+            TransactionServiceResponse::ShaAtomicSwapTransactionSent(boxed) => Ok(boxed.0),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    pub async fn reject_payment(&mut self, approval_id: u64) -> Result<(), TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::RejectPayment(approval_id))
+            .await??
+        {
+            TransactionServiceResponse::PaymentRejected => Ok(()),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Produces a signed proof that this wallet paid the destination of the given mined one-sided transaction, which
+    /// anyone can verify against the chain
+    pub async fn generate_payment_proof(&mut self, tx_id: TxId) -> Result<PaymentProof, TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::GeneratePaymentProof(tx_id))
+            .await??
+        {
+            TransactionServiceResponse::PaymentProof(proof) => Ok(*proof),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Explains why a transaction is not progressing and which remediations are safe to apply
+    pub async fn diagnose_transaction(&mut self, tx_id: TxId) -> Result<TransactionDiagnosis, TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::DiagnoseTransaction(tx_id))
+            .await??
+        {
+            TransactionServiceResponse::TransactionDiagnosis(diagnosis) => Ok(*diagnosis),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Applies a remediation to a transaction. Fails if the diagnosis of the transaction does not allow it.
+    pub async fn apply_remediation(
+        &mut self,
+        tx_id: TxId,
+        remediation: Remediation,
+    ) -> Result<(), TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::ApplyRemediation(tx_id, remediation))
+            .await??
+        {
+            TransactionServiceResponse::RemediationApplied => Ok(()),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
 }
@@ -66,10 +66,14 @@ use crate::{
 };
 
 pub mod config;
+pub mod doctor;
+pub mod drafts;
 pub mod error;
 pub mod handle;
 pub mod protocols;
+pub mod reporting;
 pub mod service;
+pub mod spending_policy;
 pub mod storage;
 pub mod tasks;
 mod utc;
@@ -28,6 +28,7 @@ use std::{
 
 use futures::FutureExt;
 use log::*;
+use rand::{rngs::OsRng, Rng};
 use tari_common_types::{
     transaction::{TransactionStatus, TxId},
     types::Signature,
@@ -41,10 +42,12 @@ use tari_core::{
 };
 use tari_utilities::hex::Hex;
 use tokio::{sync::watch, time::sleep};
+use tracing::instrument;
 
 use crate::{
     connectivity_service::WalletConnectivityInterface,
     transaction_service::{
+        doctor::BaseNodeResponse,
         error::{TransactionServiceError, TransactionServiceProtocolError},
         handle::TransactionEvent,
         protocols::check_transaction_size,
@@ -88,11 +91,31 @@ where
     }
 
     /// The task that defines the execution of the protocol.
+    #[instrument(
+        name = "wallet::transaction_broadcast_protocol",
+        level = "debug",
+        skip_all,
+        fields(tx_id = %self.tx_id)
+    )]
     pub async fn execute(mut self) -> Result<TxId, TransactionServiceProtocolError<TxId>> {
         let mut shutdown = self.resources.shutdown_signal.clone();
         let mut current_base_node_watcher = self.resources.connectivity.get_current_base_node_watcher();
         let mut timeout_update_receiver = self.timeout_update_receiver.clone();
 
+        if let Some(delay_max) = self.resources.config.broadcast_delay_max.filter(|d| !d.is_zero()) {
+            let delay = OsRng.gen_range(Duration::ZERO..=delay_max);
+            debug!(
+                target: LOG_TARGET,
+                "Transaction Broadcast protocol (TxId: {}) delaying the broadcast by {:.2?}", self.tx_id, delay
+            );
+            tokio::select! {
+                _ = sleep(delay) => {},
+                _ = shutdown.wait() => {
+                    return Err(TransactionServiceProtocolError::new(self.tx_id, TransactionServiceError::Shutdown));
+                },
+            }
+        }
+
         // Main protocol loop
         loop {
             let mut client = self
@@ -215,6 +238,7 @@ where
                     target: LOG_TARGET,
                     "Submit Transaction RPC Call to Base Node failed: {}", e
                 );
+                self.record_response(BaseNodeResponse::RpcFailed);
                 return Ok(false);
             },
         };
@@ -224,6 +248,7 @@ where
                 target: LOG_TARGET,
                 "Base Node reports not being synced, submission will be retried."
             );
+            self.record_response(BaseNodeResponse::NotSynced);
             return Ok(false);
         }
 
@@ -256,6 +281,7 @@ where
                 ),
             };
 
+            self.record_response(BaseNodeResponse::Rejected(reason));
             self.cancel_transaction(reason).await;
 
             let _size = self
@@ -279,12 +305,14 @@ where
                  validation protocol.",
                 self.tx_id
             );
+            self.record_response(BaseNodeResponse::AlreadyMined);
         } else {
             info!(
                 target: LOG_TARGET,
                 "Transaction (TxId: {}) successfully submitted to UnconfirmedPool", self.tx_id
             );
             trace!(target: LOG_TARGET, "submit_transaction ({}) - {}", self.tx_id, tx,);
+            self.record_response(BaseNodeResponse::Accepted);
             self.resources
                 .db
                 .broadcast_completed_transaction(self.tx_id)
@@ -330,6 +358,7 @@ where
                     target: LOG_TARGET,
                     "Transaction Query RPC Call to Base Node failed: {}", e
                 );
+                self.record_response(BaseNodeResponse::RpcFailed);
                 return Ok(false);
             },
         };
@@ -342,6 +371,7 @@ where
                 target: LOG_TARGET,
                 "Base Node reports not being synced, submission will be retried."
             );
+            self.record_response(BaseNodeResponse::NotSynced);
             return Ok(false);
         }
 
@@ -351,8 +381,10 @@ where
                 target: LOG_TARGET,
                 "Broadcast transaction detected as mined, will be managed by transaction validation protocol"
             );
+            self.record_response(BaseNodeResponse::Mined);
             Ok(true)
         } else if response.location != TxLocation::InMempool {
+            self.record_response(BaseNodeResponse::NotFound);
             if self.last_rejection.is_none() ||
                 self.last_rejection.unwrap().elapsed() >
                     self.resources.config.transaction_mempool_resubmission_window
@@ -398,6 +430,7 @@ where
                 target: LOG_TARGET,
                 "Transaction (TxId: {}) found in mempool.", self.tx_id
             );
+            self.record_response(BaseNodeResponse::InMempool);
             Ok(true)
         }
     }
@@ -430,6 +463,10 @@ where
         }
     }
 
+    fn record_response(&self, response: BaseNodeResponse) {
+        self.resources.broadcast_journal.record(self.tx_id, response);
+    }
+
     async fn cancel_transaction(&mut self, reason: TxCancellationReason) {
         if let Err(e) = self
             .resources
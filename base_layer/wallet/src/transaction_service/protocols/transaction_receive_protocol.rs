@@ -38,6 +38,7 @@ use tokio::{
     sync::{mpsc, oneshot},
     time::sleep,
 };
+use tracing::instrument;
 
 use crate::{
     connectivity_service::WalletConnectivityInterface,
@@ -99,6 +100,7 @@ where
         }
     }
 
+    #[instrument(name = "wallet::transaction_receive_protocol", level = "debug", skip_all, fields(tx_id = %self.id))]
     pub async fn execute(mut self) -> Result<TxId, TransactionServiceProtocolError<TxId>> {
         info!(
             target: LOG_TARGET,
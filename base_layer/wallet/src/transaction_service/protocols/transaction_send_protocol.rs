@@ -55,6 +55,7 @@ use tokio::{
     sync::{mpsc::Receiver, oneshot},
     time::sleep,
 };
+use tracing::instrument;
 
 use crate::{
     connectivity_service::WalletConnectivityInterface,
@@ -142,6 +143,7 @@ where
     }
 
     /// Execute the Transaction Send Protocol as an async task.
+    #[instrument(name = "wallet::transaction_send_protocol", level = "debug", skip_all, fields(tx_id = %self.id))]
     pub async fn execute(
         mut self,
     ) -> Result<crate::transaction_service::service::TransactionSendResult, TransactionServiceProtocolError<TxId>> {
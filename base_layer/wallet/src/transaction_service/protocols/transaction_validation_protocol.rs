@@ -41,6 +41,7 @@ use tari_core::{
     proto::{base_node::Signatures as SignaturesProto, types::Signature as SignatureProto},
 };
 use tari_utilities::hex::Hex;
+use tracing::instrument;
 
 use crate::{
     connectivity_service::WalletConnectivityInterface,
@@ -88,6 +89,12 @@ where
         }
     }
 
+    #[instrument(
+        name = "wallet::transaction_validation_protocol",
+        level = "debug",
+        skip_all,
+        fields(operation_id = %self.operation_id)
+    )]
     pub async fn execute(mut self) -> Result<OperationId, TransactionServiceProtocolError<OperationId>> {
         let mut base_node_wallet_client = self
             .connectivity
@@ -108,6 +115,8 @@ where
             .for_protocol(self.operation_id)
             .unwrap();
 
+        let total = u64::try_from(unconfirmed_transactions.len()).unwrap_or(u64::MAX);
+        let mut checked = 0u64;
         let mut state_changed = false;
         for batch in unconfirmed_transactions.chunks(self.config.max_tx_query_batch_size) {
             let (mined, unmined, tip_info) = self
@@ -150,6 +159,12 @@ where
                         .await?;
                 }
             }
+            checked = checked.saturating_add(u64::try_from(batch.len()).unwrap_or(u64::MAX));
+            self.publish_event(TransactionEvent::TransactionValidationProgress {
+                operation_id: self.operation_id,
+                checked,
+                total,
+            });
         }
         if state_changed {
             self.publish_event(TransactionEvent::TransactionValidationStateChanged(self.operation_id));
@@ -0,0 +1,198 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Balance history and aggregates reconstructed from the completed transactions, for drawing balance charts.
+//!
+//! Only transactions that were mined or imported from the chain are counted, on the day they were mined. An inbound
+//! transaction adds its amount to the balance, and an outbound one subtracts its amount and fee.
+
+use chrono::{Duration, NaiveDate};
+use tari_common_types::transaction::{TransactionDirection, TransactionStatus};
+use tari_core::transactions::tari_amount::MicroMinotari;
+
+use crate::transaction_service::storage::models::CompletedTransaction;
+
+/// The movements on one day and the balance at its end
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DailyBalance {
+    pub date: NaiveDate,
+    pub inflow: MicroMinotari,
+    pub outflow: MicroMinotari,
+    pub fees: MicroMinotari,
+    pub mined: MicroMinotari,
+    pub balance: MicroMinotari,
+}
+
+/// The balance history over a date range
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BalanceReport {
+    /// The balance at the start of the first day
+    pub opening_balance: MicroMinotari,
+    /// The balance at the end of the last day
+    pub closing_balance: MicroMinotari,
+    /// Everything received, including coinbases
+    pub total_inflow: MicroMinotari,
+    /// Everything sent, excluding fees
+    pub total_outflow: MicroMinotari,
+    pub total_fees_paid: MicroMinotari,
+    pub total_mined: MicroMinotari,
+    /// One entry for every day from the first to the last, including days without transactions
+    pub days: Vec<DailyBalance>,
+}
+
+impl BalanceReport {
+    /// Builds the report for the days from `from` to `to` inclusive. Returns `None` if `from` is after `to`.
+    pub fn new<'a, I>(transactions: I, from: NaiveDate, to: NaiveDate) -> Option<Self>
+    where I: IntoIterator<Item = &'a CompletedTransaction> {
+        if from > to {
+            return None;
+        }
+        let mut report = Self {
+            days: (0..Self::num_days(from, to))
+                .map(|offset| DailyBalance {
+                    date: from + Duration::days(offset),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        };
+
+        for tx in transactions.into_iter().filter(|tx| counts_towards_balance(tx)) {
+            let date = tx.mined_timestamp.unwrap_or(tx.timestamp).date();
+            if date > to {
+                continue;
+            }
+            let inbound = tx.direction == TransactionDirection::Inbound;
+            if date < from {
+                report.opening_balance = if inbound {
+                    report.opening_balance.saturating_add(tx.amount)
+                } else {
+                    report.opening_balance.saturating_sub(tx.amount + tx.fee)
+                };
+                continue;
+            }
+            let offset = usize::try_from((date - from).num_days()).unwrap_or_default();
+            let day = &mut report.days[offset];
+            if inbound {
+                day.inflow += tx.amount;
+                if tx.status.is_coinbase() {
+                    day.mined += tx.amount;
+                }
+            } else {
+                day.outflow += tx.amount;
+                day.fees += tx.fee;
+            }
+        }
+
+        let mut balance = report.opening_balance;
+        for day in &mut report.days {
+            balance = balance
+                .saturating_add(day.inflow)
+                .saturating_sub(day.outflow + day.fees);
+            day.balance = balance;
+            report.total_inflow += day.inflow;
+            report.total_outflow += day.outflow;
+            report.total_fees_paid += day.fees;
+            report.total_mined += day.mined;
+        }
+        report.closing_balance = balance;
+        Some(report)
+    }
+
+    /// The number of days from `from` to `to` inclusive
+    pub fn num_days(from: NaiveDate, to: NaiveDate) -> i64 {
+        (to - from + Duration::days(1)).num_days()
+    }
+}
+
+fn counts_towards_balance(tx: &CompletedTransaction) -> bool {
+    use TransactionStatus::*;
+    tx.cancelled.is_none() &&
+        matches!(
+            tx.status,
+            MinedUnconfirmed |
+                MinedConfirmed |
+                Imported |
+                OneSidedUnconfirmed |
+                OneSidedConfirmed |
+                CoinbaseUnconfirmed |
+                CoinbaseConfirmed
+        )
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::NaiveDateTime;
+    use tari_common_types::{
+        tari_address::TariAddress,
+        types::{PrivateKey, Signature},
+    };
+    use tari_core::transactions::transaction_components::Transaction;
+
+    use super::*;
+    use crate::transaction_service::storage::models::TxCancellationReason;
+
+    fn transaction(
+        direction: TransactionDirection,
+        status: TransactionStatus,
+        amount: u64,
+        fee: u64,
+        mined_at: &str,
+    ) -> CompletedTransaction {
+        let mined_timestamp = NaiveDateTime::parse_from_str(mined_at, "%Y-%m-%d %H:%M").unwrap();
+        CompletedTransaction {
+            tx_id: Default::default(),
+            source_address: TariAddress::default(),
+            destination_address: TariAddress::default(),
+            amount: amount.into(),
+            fee: fee.into(),
+            transaction: Transaction::new(vec![], vec![], vec![], PrivateKey::default(), PrivateKey::default()),
+            status,
+            message: String::new(),
+            timestamp: mined_timestamp,
+            cancelled: None,
+            direction,
+            send_count: 0,
+            last_send_timestamp: None,
+            transaction_signature: Signature::default(),
+            confirmations: None,
+            mined_height: None,
+            mined_in_block: None,
+            mined_timestamp: Some(mined_timestamp),
+            payment_id: None,
+        }
+    }
+
+    #[test]
+    fn it_reconstructs_daily_balances() {
+        use TransactionDirection::*;
+        use TransactionStatus::*;
+        let mut cancelled = transaction(Inbound, MinedConfirmed, 1_000, 0, "2024-03-02 08:00");
+        cancelled.cancelled = Some(TxCancellationReason::UserCancelled);
+        let transactions = vec![
+            transaction(Inbound, MinedConfirmed, 500, 0, "2024-02-28 12:00"),
+            transaction(Inbound, CoinbaseConfirmed, 200, 0, "2024-03-01 01:00"),
+            transaction(Outbound, MinedConfirmed, 100, 5, "2024-03-01 23:59"),
+            transaction(Inbound, OneSidedUnconfirmed, 50, 0, "2024-03-03 10:00"),
+            transaction(Outbound, Broadcast, 300, 5, "2024-03-03 11:00"),
+            transaction(Inbound, MinedConfirmed, 400, 0, "2024-03-04 00:00"),
+            cancelled,
+        ];
+        let from = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        let to = NaiveDate::from_ymd_opt(2024, 3, 3).unwrap();
+        let report = BalanceReport::new(&transactions, from, to).unwrap();
+
+        assert_eq!(report.opening_balance, 500.into());
+        assert_eq!(report.closing_balance, 645.into());
+        assert_eq!(report.total_inflow, 250.into());
+        assert_eq!(report.total_outflow, 100.into());
+        assert_eq!(report.total_fees_paid, 5.into());
+        assert_eq!(report.total_mined, 200.into());
+        let balances = report.days.iter().map(|d| u64::from(d.balance)).collect::<Vec<_>>();
+        assert_eq!(balances, vec![595, 595, 645]);
+        assert_eq!(report.days[2].date, to);
+
+        assert!(BalanceReport::new(&transactions, to, from).is_none());
+        assert_eq!(BalanceReport::new(&transactions, from, from).unwrap().days.len(), 1);
+    }
+}
@@ -23,16 +23,15 @@
 use std::{
     collections::{HashMap, HashSet},
     convert::TryInto,
+    iter,
     sync::Arc,
     time::{Duration, Instant},
 };
 
 use chrono::{NaiveDateTime, Utc};
-use digest::Digest;
 use futures::{pin_mut, stream::FuturesUnordered, Stream, StreamExt};
 use log::*;
-use rand::rngs::OsRng;
-use sha2::Sha256;
+use rand::{rngs::OsRng, RngCore};
 use tari_common::configuration::Network;
 use tari_common_types::{
     burnt_proof::BurntProof,
@@ -52,14 +51,18 @@ use tari_core::{
     proto::{base_node as base_node_proto, base_node::FetchMatchingUtxos},
     transactions::{
         key_manager::TransactionKeyManagerInterface,
+        payment_proof::PaymentProof,
         tari_amount::MicroMinotari,
         transaction_components::{
-            encrypted_data::PaymentId,
+            encrypted_data::{EncryptedDataRecord, PaymentId},
             CodeTemplateRegistration,
+            EncryptedData,
+            HtlcScript,
             KernelFeatures,
             OutputFeatures,
             Transaction,
             TransactionOutput,
+            TransactionOutputVersion,
             WalletOutputBuilder,
         },
         transaction_protocol::{
@@ -75,9 +78,8 @@ use tari_core::{
 use tari_crypto::{
     keys::{PublicKey as PKtrait, SecretKey},
     ristretto::pedersen::PedersenCommitment,
-    tari_utilities::ByteArray,
 };
-use tari_key_manager::key_manager_service::KeyId;
+use tari_key_manager::key_manager_service::{KeyId, KeyManagerServiceError};
 use tari_p2p::domain_message::DomainMessage;
 use tari_script::{push_pubkey_script, script, CheckSigSchnorrSignature, ExecutionStack, ScriptContext, TariScript};
 use tari_service_framework::{reply_channel, reply_channel::Receiver};
@@ -99,6 +101,7 @@ use crate::{
     storage::database::{WalletBackend, WalletDatabase},
     transaction_service::{
         config::TransactionServiceConfig,
+        doctor::{self, BroadcastJournal, ProtocolState, Remediation, TransactionDiagnosis},
         error::{TransactionServiceError, TransactionServiceProtocolError},
         handle::{
             FeePerGramStatsResponse,
@@ -114,6 +117,7 @@ use crate::{
             transaction_send_protocol::{TransactionSendProtocol, TransactionSendProtocolStage},
             transaction_validation_protocol::TransactionValidationProtocol,
         },
+        spending_policy::{PaymentApproval, SpendingPolicy, SpendingPolicyViolation, SPENDING_WINDOW_HOURS},
         storage::{
             database::{TransactionBackend, TransactionDatabase},
             models::{
@@ -186,6 +190,8 @@ pub struct TransactionService<
     last_seen_tip_height: Option<u64>,
     validation_in_progress: Arc<Mutex<()>>,
     consensus_manager: ConsensusManager,
+    spending_policy: SpendingPolicy,
+    pending_approvals: HashMap<u64, (PaymentApproval, TransactionServiceRequest)>,
 }
 
 impl<
@@ -281,6 +287,7 @@ where
             shutdown_signal,
             consensus_manager: consensus_manager.clone(),
             wallet_type,
+            broadcast_journal: BroadcastJournal::default(),
         };
         let power_mode = PowerMode::default();
         let timeout = match power_mode {
@@ -288,6 +295,9 @@ where
             PowerMode::Normal => config.broadcast_monitoring_timeout,
         };
         let timeout_update_watch = Watch::new(timeout);
+        let spending_policy = SpendingPolicy::new(&config.spending_policy).map_err(|e| {
+            TransactionServiceError::InvalidAddress(format!("Spending policy allowed destination: {}", e))
+        })?;
 
         Ok(Self {
             config,
@@ -312,6 +322,8 @@ where
             last_seen_tip_height: None,
             validation_in_progress: Arc::new(Mutex::new(())),
             consensus_manager,
+            spending_policy,
+            pending_approvals: HashMap::new(),
         })
     }
 
@@ -606,6 +618,27 @@ where
         let mut reply_channel = Some(reply_channel);
 
         trace!(target: LOG_TARGET, "Handling Service Request: {}", request);
+        let (request, is_approved) = match request {
+            TransactionServiceRequest::ApprovePayment(approval_id) => match self.pending_approvals.remove(&approval_id)
+            {
+                Some((_, request)) => (request, true),
+                None => {
+                    let _result = reply_channel
+                        .take()
+                        .expect("reply_channel is Some")
+                        .send(Err(SpendingPolicyViolation::UnknownApproval(approval_id).into()));
+                    return Ok(());
+                },
+            },
+            request => (request, false),
+        };
+        let request = match self.apply_spending_policy(request, is_approved).await {
+            Ok(request) => request,
+            Err(e) => {
+                let _result = reply_channel.take().expect("reply_channel is Some").send(Err(e));
+                return Ok(());
+            },
+        };
         let response = match request {
             TransactionServiceRequest::SendTransaction {
                 destination,
@@ -629,6 +662,12 @@ where
                     rp,
                 )
                 .await?;
+                if let Err(e) = self
+                    .create_privacy_self_spend(fee_per_gram, transaction_broadcast_join_handles)
+                    .await
+                {
+                    warn!(target: LOG_TARGET, "Failed to create change privacy self-spend: {}", e);
+                }
                 return Ok(());
             },
             TransactionServiceRequest::SendOneSidedTransaction {
@@ -648,6 +687,30 @@ where
                     fee_per_gram,
                     message,
                     payment_id,
+                    Vec::new(),
+                    transaction_broadcast_join_handles,
+                )
+                .await
+                .map(TransactionServiceResponse::TransactionSent),
+            TransactionServiceRequest::SendOneSidedTransactionWithRecords {
+                destination,
+                amount,
+                selection_criteria,
+                output_features,
+                fee_per_gram,
+                message,
+                payment_id,
+                records,
+            } => self
+                .send_one_sided_transaction(
+                    destination,
+                    amount,
+                    selection_criteria,
+                    *output_features,
+                    fee_per_gram,
+                    message,
+                    payment_id,
+                    records,
                     transaction_broadcast_join_handles,
                 )
                 .await
@@ -796,6 +859,30 @@ where
                 .await?;
                 return Ok(());
             },
+            TransactionServiceRequest::AnchorApplicationData {
+                application_id,
+                payload,
+                amount,
+                selection_criteria,
+                fee_per_gram,
+                message,
+            } => {
+                let rp = reply_channel.take().expect("Cannot be missing");
+                self.send_transaction(
+                    self.resources.interactive_tari_address.clone(),
+                    amount,
+                    selection_criteria,
+                    OutputFeatures::for_application_data(application_id, payload),
+                    fee_per_gram,
+                    message,
+                    TransactionMetadata::default(),
+                    send_transaction_join_handles,
+                    transaction_broadcast_join_handles,
+                    rp,
+                )
+                .await?;
+                return Ok(());
+            },
             TransactionServiceRequest::RegisterCodeTemplate {
                 author_public_key,
                 author_signature,
@@ -967,6 +1054,45 @@ where
                 self.handle_get_fee_per_gram_stats_per_block_request(count, reply_channel);
                 return Ok(());
             },
+            TransactionServiceRequest::GeneratePaymentProof(tx_id) => self
+                .generate_payment_proof(tx_id)
+                .await
+                .map(|proof| TransactionServiceResponse::PaymentProof(Box::new(proof))),
+            TransactionServiceRequest::GetPendingApprovals => {
+                let mut approvals = self
+                    .pending_approvals
+                    .values()
+                    .map(|(approval, _)| approval.clone())
+                    .collect::<Vec<_>>();
+                approvals.sort_by_key(|approval| approval.requested_at);
+                Ok(TransactionServiceResponse::PendingApprovals(approvals))
+            },
+            TransactionServiceRequest::ApprovePayment(approval_id) => {
+                // Approvals are resolved into the held request above
+                Err(SpendingPolicyViolation::UnknownApproval(approval_id).into())
+            },
+            TransactionServiceRequest::RejectPayment(approval_id) => {
+                match self.pending_approvals.remove(&approval_id) {
+                    Some(_) => {
+                        info!(target: LOG_TARGET, "Payment awaiting approval {} rejected", approval_id);
+                        Ok(TransactionServiceResponse::PaymentRejected)
+                    },
+                    None => Err(SpendingPolicyViolation::UnknownApproval(approval_id).into()),
+                }
+            },
+            TransactionServiceRequest::DiagnoseTransaction(tx_id) => self
+                .diagnose_transaction(tx_id)
+                .map(|diagnosis| TransactionServiceResponse::TransactionDiagnosis(Box::new(diagnosis))),
+            TransactionServiceRequest::ApplyRemediation(tx_id, remediation) => self
+                .apply_remediation(
+                    tx_id,
+                    remediation,
+                    send_transaction_join_handles,
+                    receive_transaction_join_handles,
+                    transaction_broadcast_join_handles,
+                )
+                .await
+                .map(|_| TransactionServiceResponse::RemediationApplied),
         };
 
         // If the individual handlers did not already send the API response then do it here.
@@ -978,6 +1104,93 @@ where
         Ok(())
     }
 
+    /// Checks an outgoing payment against the spending policy. Returns the request if it may be sent, and holds it if
+    /// it must be approved first. An approved request is checked against the limits again, since other payments may
+    /// have been sent in the meantime.
+    async fn apply_spending_policy(
+        &mut self,
+        request: TransactionServiceRequest,
+        is_approved: bool,
+    ) -> Result<TransactionServiceRequest, TransactionServiceError> {
+        if self.spending_policy.is_unrestricted() {
+            return Ok(request);
+        }
+        let (destination, amount) = match &request {
+            TransactionServiceRequest::SendTransaction {
+                destination, amount, ..
+            } |
+            TransactionServiceRequest::SendOneSidedTransaction {
+                destination, amount, ..
+            } |
+            TransactionServiceRequest::SendOneSidedTransactionWithRecords {
+                destination, amount, ..
+            } |
+            TransactionServiceRequest::SendOneSidedToStealthAddressTransaction {
+                destination, amount, ..
+            } |
+            TransactionServiceRequest::SendShaAtomicSwapTransaction(destination, amount, ..) => {
+                (Some(destination.clone()), *amount)
+            },
+            TransactionServiceRequest::BurnTari { amount, .. } => (None, *amount),
+            TransactionServiceRequest::ScrapeWallet { destination, .. } => {
+                let balance = self.resources.output_manager_service.get_balance().await?;
+                (Some(destination.clone()), balance.available_balance)
+            },
+            _ => return Ok(request),
+        };
+
+        let spent = self.spent_in_spending_window()?;
+        self.spending_policy.check(destination.as_ref(), amount, spent)?;
+        if is_approved {
+            return Ok(request);
+        }
+        if let Some(threshold) = self.spending_policy.approval_threshold_for(amount) {
+            let approval_id = OsRng.next_u64();
+            info!(
+                target: LOG_TARGET,
+                "Payment of {} is above the approval threshold of {}, holding it as approval {}",
+                amount,
+                threshold,
+                approval_id
+            );
+            let approval = PaymentApproval {
+                approval_id,
+                destination,
+                amount,
+                requested_at: Utc::now().naive_utc(),
+            };
+            self.pending_approvals.insert(approval_id, (approval, request));
+            return Err(SpendingPolicyViolation::ApprovalRequired {
+                approval_id,
+                amount,
+                threshold,
+            }
+            .into());
+        }
+        Ok(request)
+    }
+
+    /// The amount, including fees, of the outbound transactions made in the current spending window that were not
+    /// cancelled
+    fn spent_in_spending_window(&self) -> Result<MicroMinotari, TransactionServiceError> {
+        let since = Utc::now().naive_utc() - chrono::Duration::hours(SPENDING_WINDOW_HOURS);
+        let pending = self
+            .db
+            .get_pending_outbound_transactions()?
+            .into_values()
+            .filter(|tx| !tx.cancelled && tx.timestamp >= since)
+            .map(|tx| tx.amount + tx.fee);
+        let completed = self
+            .db
+            .get_completed_transactions()?
+            .into_values()
+            .filter(|tx| {
+                tx.direction == TransactionDirection::Outbound && tx.cancelled.is_none() && tx.timestamp >= since
+            })
+            .map(|tx| tx.amount + tx.fee);
+        Ok(pending.chain(completed).sum())
+    }
+
     fn handle_get_fee_per_gram_stats_per_block_request(
         &self,
         count: usize,
@@ -1042,6 +1255,7 @@ where
 
                 self.last_seen_tip_height = Some(height);
             },
+            BaseNodeEvent::BaseNodeFailover(..) | BaseNodeEvent::TipDivergesFromBeacons(..) => {},
         }
     }
 
@@ -1438,20 +1652,19 @@ where
         self.verify_send(&destination, TariAddressFeatures::create_one_sided_only())?;
         // this can be anything, so lets generate a random private key
         let pre_image = PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng));
-        let hash: [u8; 32] = Sha256::digest(pre_image.as_bytes()).into();
 
         // lets make the unlock height a day from now, 2 min blocks which gives us 30 blocks per hour * 24 hours
         let tip_height = self.last_seen_tip_height.unwrap_or(0);
         let height = tip_height + (24 * 30);
 
         // lets create the HTLC script
-        let script = script!(
-            HashSha256 PushHash(Box::new(hash)) Equal IfThen
-                PushPubKey(Box::new(destination.public_spend_key().clone()))
-            Else
-                CheckHeightVerify(height) PushPubKey(Box::new(self.resources.one_sided_tari_address.public_spend_key().clone()))
-            EndIf
-        )?;
+        let script = HtlcScript::from_pre_image(
+            &pre_image,
+            destination.public_spend_key().clone(),
+            self.resources.one_sided_tari_address.public_spend_key().clone(),
+            height,
+        )
+        .to_script()?;
 
         // Empty covenant
         let covenant = Covenant::default();
@@ -1669,6 +1882,7 @@ where
         >,
         recipient_script: Option<TariScript>,
         payment_id: PaymentId,
+        records: Vec<EncryptedDataRecord>,
     ) -> Result<TxId, TransactionServiceError> {
         let tx_id = TxId::new_random();
         let payment_id = match payment_id {
@@ -1677,6 +1891,21 @@ where
             _ => payment_id,
         };
         self.verify_send(&dest_address, TariAddressFeatures::create_one_sided_only())?;
+        // Records are carried by outputs of a version that wallets reading a bare payment id never accept, so they are
+        // only sent once consensus permits that version
+        if !records.is_empty() {
+            let tip_height = self.last_seen_tip_height.unwrap_or(0);
+            let permitted_versions = &self
+                .consensus_manager
+                .consensus_constants(tip_height)
+                .output_version_range()
+                .outputs;
+            if !permitted_versions.contains(&TransactionOutputVersion::V1) {
+                return Err(TransactionServiceError::OneSidedTransactionError(
+                    "Typed encrypted data records are not permitted on this network yet".to_string(),
+                ));
+            }
+        }
 
         // For a stealth transaction, the script is not provided because the public key that should be included
         // is not known at this stage. This will only be known later. For now,
@@ -1796,13 +2025,29 @@ where
                     .features
                     .clone(),
             )
-            .with_script(script)
-            .encrypt_data_for_recovery(
-                &self.resources.transaction_key_manager_service,
-                Some(&encryption_key),
-                payment_id.clone(),
-            )
-            .await?
+            .with_script(script);
+        // Typed records carry the payment id as their first record, so that the recipient reads it either way
+        let output = if records.is_empty() {
+            output
+                .encrypt_data_for_recovery(
+                    &self.resources.transaction_key_manager_service,
+                    Some(&encryption_key),
+                    payment_id.clone(),
+                )
+                .await?
+        } else {
+            let records = iter::once(EncryptedDataRecord::PaymentId(payment_id.clone()))
+                .chain(records)
+                .collect::<Vec<_>>();
+            output
+                .encrypt_records_for_recovery(
+                    &self.resources.transaction_key_manager_service,
+                    Some(&encryption_key),
+                    &records,
+                )
+                .await?
+        };
+        let output = output
             .with_input_data(Default::default())
             .with_sender_offset_public_key(sender_offset_public_key)
             .with_script_key(KeyId::Zero)
@@ -2106,6 +2351,7 @@ where
     /// 'dest_pubkey': The Comms pubkey of the recipient node
     /// 'amount': The amount of Tari to send to the recipient
     /// 'fee_per_gram': The amount of fee per transaction gram to be included in transaction
+    /// 'records': Typed records to encrypt for the recipient together with the payment id, if any
     pub async fn send_one_sided_transaction(
         &mut self,
         destination: TariAddress,
@@ -2115,6 +2361,7 @@ where
         fee_per_gram: MicroMinotari,
         message: String,
         payment_id: PaymentId,
+        records: Vec<EncryptedDataRecord>,
         transaction_broadcast_join_handles: &mut FuturesUnordered<
             JoinHandle<Result<TxId, TransactionServiceProtocolError<TxId>>>,
         >,
@@ -2130,6 +2377,7 @@ where
             transaction_broadcast_join_handles,
             Some(push_pubkey_script(&dest_pubkey)),
             payment_id,
+            records,
         )
         .await
     }
@@ -2160,8 +2408,15 @@ where
             .map(OutputFeatures::create_burn_confidential_output)
             .unwrap_or_else(OutputFeatures::create_burn_output);
 
+        // Burns to a claim key commit to it in the kernel so that the funds can later be provably claimed
+        let kernel_features = if claim_public_key.is_some() {
+            KernelFeatures::create_claimable_burn()
+        } else {
+            KernelFeatures::create_burn()
+        };
+
         // Prepare sender part of the transaction
-        let tx_meta = TransactionMetadata::new_with_features(0.into(), 0, KernelFeatures::create_burn());
+        let tx_meta = TransactionMetadata::new_with_features(0.into(), 0, kernel_features);
         let mut stp = self
             .resources
             .output_manager_service
@@ -2294,11 +2549,11 @@ where
         let mut ownership_proof = None;
         let commitment = recipient_reply.output.commitment.clone();
 
-        if let Some(claim_public_key) = claim_public_key {
+        if let Some(ref claim_public_key) = claim_public_key {
             ownership_proof = Some(
                 self.resources
                     .transaction_key_manager_service
-                    .generate_burn_proof(&commitment_mask_key.key_id, &amount.into(), &claim_public_key)
+                    .generate_burn_proof(&commitment_mask_key.key_id, &amount.into(), claim_public_key)
                     .await?,
             );
         }
@@ -2365,6 +2620,7 @@ where
             commitment,
             ownership_proof,
             range_proof,
+            claim_public_key,
         }))
     }
 
@@ -2415,6 +2671,13 @@ where
         >,
         reply_channel: oneshot::Sender<Result<TransactionServiceResponse, TransactionServiceError>>,
     ) -> Result<(), TransactionServiceError> {
+        // Base nodes reject registrations that are not signed by the author, so fail before paying for one
+        if !template_registration.is_valid_signature() {
+            let _result = reply_channel.send(Err(TransactionServiceError::InvalidTemplateRegistrationSignature(
+                template_registration.template_name.to_string(),
+            )));
+            return Ok(());
+        }
         self.send_transaction(
             self.resources.interactive_tari_address.clone(),
             0.into(),
@@ -2458,10 +2721,78 @@ where
             transaction_broadcast_join_handles,
             None, // The stealth address for the script will be calculated in the next step
             payment_id,
+            Vec::new(),
         )
         .await
     }
 
+    /// Produces a signed proof that this wallet paid the destination of a mined one-sided transaction, which reveals
+    /// the opening of the payment output only. Interactive payments cannot be proven, because the sender never learns
+    /// the commitment mask of the recipient's output.
+    pub async fn generate_payment_proof(&mut self, tx_id: TxId) -> Result<PaymentProof, TransactionServiceError> {
+        let unavailable = |reason: &str| TransactionServiceError::PaymentProofUnavailable {
+            tx_id,
+            reason: reason.to_string(),
+        };
+        let transaction = self.db.get_completed_transaction(tx_id)?;
+        if transaction.direction != TransactionDirection::Outbound {
+            return Err(unavailable("it is not an outbound transaction"));
+        }
+        if transaction.mined_height.is_none() {
+            return Err(unavailable("it has not been mined"));
+        }
+        let kernel_excess_sig = transaction
+            .transaction
+            .body
+            .kernels()
+            .first()
+            .map(|kernel| kernel.excess_sig.clone())
+            .ok_or_else(|| unavailable("it has no kernel"))?;
+
+        let key_manager = &self.resources.transaction_key_manager_service;
+        let view_key = key_manager.get_private_view_key().await?;
+        let branch = TransactionKeyManagerBranch::OneSidedSenderOffset.get_branch_key();
+        for output in transaction.transaction.body.outputs() {
+            // Our change output is encrypted to our own view key and its sender offset key is on another branch
+            if EncryptedData::decrypt_output_data(&view_key, &output.commitment, &output.encrypted_data, output.version)
+                .is_ok()
+            {
+                continue;
+            }
+            let index = match key_manager
+                .find_key_index(&branch, &output.sender_offset_public_key)
+                .await
+            {
+                Ok(index) => index,
+                Err(KeyManagerServiceError::KeyNotFoundInKeyChain) => continue,
+                Err(e) => return Err(e.into()),
+            };
+            let sender_offset_private_key = key_manager
+                .get_private_key(&KeyId::Managed {
+                    branch: branch.clone(),
+                    index,
+                })
+                .await?;
+            let mut proof = PaymentProof::new(
+                self.resources.one_sided_tari_address.clone(),
+                transaction.destination_address.clone(),
+                transaction.amount,
+                kernel_excess_sig,
+                output.hash(),
+                sender_offset_private_key,
+            );
+            let spend_key = key_manager.get_spend_key().await?;
+            let nonce = key_manager.get_random_key().await?;
+            let challenge = proof.signing_challenge(&nonce.pub_key);
+            proof.signature = key_manager
+                .sign_with_nonce_and_challenge(&spend_key.key_id, &nonce.key_id, &challenge)
+                .await?;
+            proof.verify_output(output, &self.resources.factories)?;
+            return Ok(proof);
+        }
+        Err(unavailable("it has no one-sided payment output"))
+    }
+
     /// Accept the public reply from a recipient and apply the reply to the relevant transaction protocol
     /// # Arguments
     /// 'recipient_reply' - The public response from a recipient with data required to complete the transaction
@@ -2704,6 +3035,62 @@ where
         Ok(())
     }
 
+    /// Explains the state of a transaction and the remediations that are safe to apply to it
+    fn diagnose_transaction(&self, tx_id: TxId) -> Result<TransactionDiagnosis, TransactionServiceError> {
+        let tx = self
+            .db
+            .get_any_transaction(tx_id)?
+            .ok_or(TransactionServiceError::TransactionDoesNotExistError)?;
+        let state = ProtocolState {
+            negotiation_protocol_running: self.pending_transaction_reply_senders.contains_key(&tx_id) ||
+                self.finalized_transaction_senders.contains_key(&tx_id),
+            broadcast_protocol_running: self.active_transaction_broadcast_protocols.contains(&tx_id),
+            base_node_set: self.resources.connectivity.is_base_node_set(),
+            broadcast: self.resources.broadcast_journal.get(tx_id),
+        };
+        Ok(doctor::diagnose(
+            &tx,
+            &state,
+            &self.resources.config,
+            Utc::now().naive_utc(),
+        ))
+    }
+
+    /// Applies a remediation to a transaction, if the diagnosis of the transaction allows it
+    async fn apply_remediation(
+        &mut self,
+        tx_id: TxId,
+        remediation: Remediation,
+        send_transaction_join_handles: &mut FuturesUnordered<
+            JoinHandle<Result<TransactionSendResult, TransactionServiceProtocolError<TxId>>>,
+        >,
+        receive_transaction_join_handles: &mut FuturesUnordered<
+            JoinHandle<Result<TxId, TransactionServiceProtocolError<TxId>>>,
+        >,
+        transaction_broadcast_join_handles: &mut FuturesUnordered<
+            JoinHandle<Result<TxId, TransactionServiceProtocolError<TxId>>>,
+        >,
+    ) -> Result<(), TransactionServiceError> {
+        if !self.diagnose_transaction(tx_id)?.allows(remediation) {
+            return Err(TransactionServiceError::RemediationUnavailable { tx_id, remediation });
+        }
+        info!(
+            target: LOG_TARGET,
+            "Applying remediation '{}' to transaction (TxId: {})", remediation, tx_id
+        );
+        match remediation {
+            Remediation::Resend => self.restart_transaction_negotiation_protocols(
+                send_transaction_join_handles,
+                receive_transaction_join_handles,
+            ),
+            Remediation::Cancel => self.cancel_pending_transaction(tx_id).await,
+            Remediation::Rebroadcast => {
+                let completed_tx = self.db.get_completed_transaction(tx_id)?;
+                self.broadcast_completed_transaction(completed_tx, transaction_broadcast_join_handles)
+            },
+        }
+    }
+
     /// Handle a Transaction Cancelled message received from the Comms layer
     pub async fn handle_transaction_cancelled_message(
         &mut self,
@@ -3636,6 +4023,35 @@ where
         Ok(())
     }
 
+    /// Follows a sent transaction with a self-spend if the change privacy level calls for one
+    async fn create_privacy_self_spend(
+        &mut self,
+        fee_per_gram: MicroMinotari,
+        transaction_broadcast_join_handles: &mut FuturesUnordered<
+            JoinHandle<Result<TxId, TransactionServiceProtocolError<TxId>>>,
+        >,
+    ) -> Result<(), TransactionServiceError> {
+        let Some((tx_id, tx, amount)) = self
+            .resources
+            .output_manager_service
+            .create_privacy_self_spend(fee_per_gram)
+            .await?
+        else {
+            return Ok(());
+        };
+        let fee = tx.body.get_total_fee()?;
+        debug!(target: LOG_TARGET, "Submitting change privacy self-spend (TxId: {})", tx_id);
+        self.submit_transaction_to_self(
+            transaction_broadcast_join_handles,
+            tx_id,
+            tx,
+            fee,
+            amount,
+            "Change privacy self-spend".to_string(),
+        )
+        .await
+    }
+
     /// Check if a Recovery Status is currently stored in the databse, this indicates that a wallet recovery is in
     /// progress
     fn check_recovery_status(&self) -> Result<(), TransactionServiceError> {
@@ -3665,10 +4081,13 @@ where
             )));
         }
         if sending_method.contains(TariAddressFeatures::create_interactive_only()) &&
-            matches!(*self.resources.wallet_type, WalletType::Ledger(_))
+            matches!(
+                *self.resources.wallet_type,
+                WalletType::Ledger(_) | WalletType::RemoteSigner(_)
+            )
         {
             return Err(TransactionServiceError::NotSupported(
-                "Interactive transactions are not supported on Ledger wallets".to_string(),
+                "Interactive transactions are not supported on Ledger or remote signer wallets".to_string(),
             ));
         }
         Ok(())
@@ -3692,6 +4111,7 @@ pub struct TransactionServiceResources<TBackend, TWalletConnectivity, TKeyManage
     pub config: TransactionServiceConfig,
     pub shutdown_signal: ShutdownSignal,
     pub wallet_type: Arc<WalletType>,
+    pub broadcast_journal: BroadcastJournal,
 }
 
 #[derive(Default, Clone, Copy)]
@@ -0,0 +1,206 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Spending limits and the approval policy of the transaction service.
+//!
+//! Every outgoing payment is checked against the configured rules before it is started: a maximum per transaction, a
+//! maximum that may be spent in any 24 hour window, and an allowlist of destinations. Payments above the approval
+//! threshold are held by the service until they are approved or rejected. Held payments are kept in memory only, so
+//! they are dropped when the wallet restarts.
+
+use std::str::FromStr;
+
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use tari_common::configuration::StringList;
+use tari_common_types::tari_address::{TariAddress, TariAddressError};
+use tari_core::transactions::tari_amount::MicroMinotari;
+use thiserror::Error;
+
+/// The length of the window over which the daily limit applies
+pub const SPENDING_WINDOW_HOURS: i64 = 24;
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(default)]
+pub struct SpendingPolicyConfig {
+    /// The maximum amount, including fees, that may be sent in any 24 hour window
+    pub daily_limit: Option<MicroMinotari>,
+    /// The maximum amount of a single transaction
+    pub max_transaction_amount: Option<MicroMinotari>,
+    /// If not empty, payments may only be sent to these addresses. Burns are not allowed then.
+    pub allowed_destinations: StringList,
+    /// Payments above this amount are held until they are approved
+    pub approval_threshold: Option<MicroMinotari>,
+}
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum SpendingPolicyViolation {
+    #[error("Amount `{amount}` exceeds the maximum transaction amount of `{limit}`")]
+    ExceedsTransactionLimit {
+        amount: MicroMinotari,
+        limit: MicroMinotari,
+    },
+    #[error("Amount `{amount}` exceeds the daily limit of `{limit}`, `{spent}` has been spent in the last 24 hours")]
+    ExceedsDailyLimit {
+        amount: MicroMinotari,
+        spent: MicroMinotari,
+        limit: MicroMinotari,
+    },
+    #[error("Destination `{0}` is not in the list of allowed destinations")]
+    DestinationNotAllowed(String),
+    #[error(
+        "Amount `{amount}` is above the approval threshold of `{threshold}`, approval `{approval_id}` is required"
+    )]
+    ApprovalRequired {
+        approval_id: u64,
+        amount: MicroMinotari,
+        threshold: MicroMinotari,
+    },
+    #[error("No payment awaiting approval with id `{0}`")]
+    UnknownApproval(u64),
+}
+
+/// A payment held until it is approved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaymentApproval {
+    pub approval_id: u64,
+    /// `None` for burns
+    pub destination: Option<TariAddress>,
+    pub amount: MicroMinotari,
+    pub requested_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SpendingPolicy {
+    daily_limit: Option<MicroMinotari>,
+    max_transaction_amount: Option<MicroMinotari>,
+    allowed_destinations: Option<Vec<TariAddress>>,
+    approval_threshold: Option<MicroMinotari>,
+}
+
+impl SpendingPolicy {
+    /// Fails if one of the allowed destinations is not a valid address, rather than allowing every destination.
+    pub fn new(config: &SpendingPolicyConfig) -> Result<Self, TariAddressError> {
+        let allowed_destinations = if config.allowed_destinations.is_empty() {
+            None
+        } else {
+            Some(
+                config
+                    .allowed_destinations
+                    .iter()
+                    .map(|address| TariAddress::from_str(address))
+                    .collect::<Result<_, _>>()?,
+            )
+        };
+        Ok(Self {
+            daily_limit: config.daily_limit,
+            max_transaction_amount: config.max_transaction_amount,
+            allowed_destinations,
+            approval_threshold: config.approval_threshold,
+        })
+    }
+
+    /// Returns true if no rule is configured, so payments need not be checked at all
+    pub fn is_unrestricted(&self) -> bool {
+        self.daily_limit.is_none() &&
+            self.max_transaction_amount.is_none() &&
+            self.allowed_destinations.is_none() &&
+            self.approval_threshold.is_none()
+    }
+
+    /// Checks a payment of `amount` to `destination`, or a burn if it is `None`, given the amount `spent` in the
+    /// current spending window. The approval threshold is checked separately.
+    pub fn check(
+        &self,
+        destination: Option<&TariAddress>,
+        amount: MicroMinotari,
+        spent: MicroMinotari,
+    ) -> Result<(), SpendingPolicyViolation> {
+        if let Some(allowed) = &self.allowed_destinations {
+            let is_allowed = destination.map_or(false, |destination| {
+                allowed
+                    .iter()
+                    .any(|a| a.public_spend_key() == destination.public_spend_key())
+            });
+            if !is_allowed {
+                return Err(SpendingPolicyViolation::DestinationNotAllowed(
+                    destination.map_or_else(|| "burn".to_string(), |d| d.to_base58()),
+                ));
+            }
+        }
+        if let Some(limit) = self.max_transaction_amount {
+            if amount > limit {
+                return Err(SpendingPolicyViolation::ExceedsTransactionLimit { amount, limit });
+            }
+        }
+        if let Some(limit) = self.daily_limit {
+            if spent.saturating_add(amount) > limit {
+                return Err(SpendingPolicyViolation::ExceedsDailyLimit { amount, spent, limit });
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the threshold if a payment of `amount` must be approved before it is sent
+    pub fn approval_threshold_for(&self, amount: MicroMinotari) -> Option<MicroMinotari> {
+        self.approval_threshold.filter(|threshold| amount > *threshold)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tari_common::configuration::Network;
+    use tari_common_types::types::PublicKey;
+    use tari_crypto::keys::PublicKey as PublicKeyTrait;
+
+    use super::*;
+
+    fn address() -> TariAddress {
+        let (_, public_key) = PublicKey::random_keypair(&mut rand::rngs::OsRng);
+        TariAddress::new_single_address_with_interactive_only(public_key, Network::LocalNet)
+    }
+
+    #[test]
+    fn it_enforces_the_configured_rules() {
+        let allowed = address();
+        let config = SpendingPolicyConfig {
+            daily_limit: Some(1_000.into()),
+            max_transaction_amount: Some(600.into()),
+            allowed_destinations: vec![allowed.to_base58()].into(),
+            approval_threshold: Some(400.into()),
+        };
+        let policy = SpendingPolicy::new(&config).unwrap();
+        assert!(!policy.is_unrestricted());
+
+        assert!(policy.check(Some(&allowed), 600.into(), 400.into()).is_ok());
+        assert!(matches!(
+            policy.check(Some(&allowed), 601.into(), 0.into()),
+            Err(SpendingPolicyViolation::ExceedsTransactionLimit { .. })
+        ));
+        assert!(matches!(
+            policy.check(Some(&allowed), 600.into(), 401.into()),
+            Err(SpendingPolicyViolation::ExceedsDailyLimit { .. })
+        ));
+        assert!(matches!(
+            policy.check(Some(&address()), 1.into(), 0.into()),
+            Err(SpendingPolicyViolation::DestinationNotAllowed(_))
+        ));
+        assert!(matches!(
+            policy.check(None, 1.into(), 0.into()),
+            Err(SpendingPolicyViolation::DestinationNotAllowed(_))
+        ));
+
+        assert_eq!(policy.approval_threshold_for(400.into()), None);
+        assert_eq!(policy.approval_threshold_for(401.into()), Some(400.into()));
+
+        assert!(SpendingPolicy::new(&SpendingPolicyConfig::default())
+            .unwrap()
+            .is_unrestricted());
+        let invalid = SpendingPolicyConfig {
+            allowed_destinations: vec!["not an address".to_string()].into(),
+            ..Default::default()
+        };
+        assert!(SpendingPolicy::new(&invalid).is_err());
+    }
+}
@@ -21,7 +21,7 @@
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     convert::{TryFrom, TryInto},
     sync::{Arc, RwLock},
 };
@@ -30,7 +30,11 @@ use chacha20poly1305::XChaCha20Poly1305;
 use chrono::{NaiveDateTime, Utc};
 use diesel::{prelude::*, result::Error as DieselError};
 use log::*;
-use tari_common_sqlite::{sqlite_connection_pool::PooledDbConnection, util::diesel_ext::ExpectedRowsExtension};
+use tari_common_sqlite::{
+    integrity::{quarantine_row, IntegrityIssue, IntegrityReport, RowKey},
+    sqlite_connection_pool::PooledDbConnection,
+    util::diesel_ext::ExpectedRowsExtension,
+};
 use tari_common_types::{
     encryption::{decrypt_bytes_integral_nonce, encrypt_bytes_integral_nonce, Encryptable},
     tari_address::TariAddress,
@@ -199,6 +203,91 @@ impl TransactionServiceSqliteDatabase {
             DbKey::AnyTransaction(_) => Err(TransactionStorageError::OperationNotSupported),
         }
     }
+
+    /// Checks that every transaction can be authenticated, decrypted and deserialized, that the mined state of every
+    /// completed transaction agrees with its status, and that no transaction is both pending and completed. If
+    /// `quarantine` is set, the inconsistent rows are moved out of the way.
+    pub fn verify_integrity(&self, quarantine: bool) -> Result<IntegrityReport, TransactionStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        let cipher = acquire_read_lock!(self.cipher);
+        let mut report = IntegrityReport::default();
+        let mut problems: Vec<(&'static str, i64, String)> = Vec::new();
+
+        let mut completed_tx_ids = HashSet::new();
+        for row in CompletedTransactionSql::index(&mut conn)? {
+            report.rows_checked += 1;
+            let tx_id = row.tx_id;
+            completed_tx_ids.insert(tx_id);
+            let problem = match CompletedTransaction::try_from(row, &cipher) {
+                Ok(tx) => completed_transaction_problem(&tx),
+                Err(e) => Some(e.to_string()),
+            };
+            if let Some(problem) = problem {
+                problems.push(("completed_transactions", tx_id, problem));
+            }
+        }
+
+        let pending_problem = |cancelled: bool, tx_id: i64| {
+            (!cancelled && completed_tx_ids.contains(&tx_id))
+                .then(|| "The transaction is both pending and completed".to_string())
+        };
+        for row in InboundTransactionSql::index(&mut conn)? {
+            report.rows_checked += 1;
+            let (tx_id, cancelled) = (row.tx_id, row.cancelled != 0);
+            let result = InboundTransaction::try_from(row, &cipher);
+            if let Some(problem) = result
+                .err()
+                .map(|e| e.to_string())
+                .or_else(|| pending_problem(cancelled, tx_id))
+            {
+                problems.push(("inbound_transactions", tx_id, problem));
+            }
+        }
+        for row in OutboundTransactionSql::index(&mut conn)? {
+            report.rows_checked += 1;
+            let (tx_id, cancelled) = (row.tx_id, row.cancelled != 0);
+            let result = OutboundTransaction::try_from(row, &cipher);
+            if let Some(problem) = result
+                .err()
+                .map(|e| e.to_string())
+                .or_else(|| pending_problem(cancelled, tx_id))
+            {
+                problems.push(("outbound_transactions", tx_id, problem));
+            }
+        }
+
+        for (table, tx_id, problem) in problems {
+            let mut issue = IntegrityIssue::new(table, tx_id, problem);
+            if quarantine {
+                quarantine_row(&mut conn, table, "tx_id", &RowKey::BigInt(tx_id))?;
+                issue.quarantined = true;
+            }
+            report.issues.push(issue);
+        }
+
+        Ok(report)
+    }
+}
+
+/// Describes how the mined state of `tx` disagrees with its status, if it does
+fn completed_transaction_problem(tx: &CompletedTransaction) -> Option<String> {
+    if tx.mined_height.is_some() != tx.mined_in_block.is_some() {
+        return Some("The mined height and block hash must be set together".to_string());
+    }
+    match tx.status {
+        TransactionStatus::MinedUnconfirmed | TransactionStatus::MinedConfirmed if tx.mined_height.is_none() => {
+            Some(format!("The status is {} but there is no mined height", tx.status))
+        },
+        TransactionStatus::Completed |
+        TransactionStatus::Broadcast |
+        TransactionStatus::Queued |
+        TransactionStatus::Pending
+            if tx.mined_height.is_some() =>
+        {
+            Some(format!("The status is {} but there is a mined height", tx.status))
+        },
+        _ => None,
+    }
 }
 
 impl TransactionBackend for TransactionServiceSqliteDatabase {
@@ -1181,7 +1270,6 @@ impl InboundTransactionSql {
         Ok(())
     }
 
-    #[allow(dead_code)]
     pub fn index(conn: &mut SqliteConnection) -> Result<Vec<InboundTransactionSql>, TransactionStorageError> {
         Ok(inbound_transactions::table.load::<InboundTransactionSql>(conn)?)
     }
@@ -1445,7 +1533,6 @@ impl OutboundTransactionSql {
         Ok(())
     }
 
-    #[allow(dead_code)]
     pub fn index(conn: &mut SqliteConnection) -> Result<Vec<OutboundTransactionSql>, TransactionStorageError> {
         Ok(outbound_transactions::table.load::<OutboundTransactionSql>(conn)?)
     }
@@ -1710,7 +1797,6 @@ impl CompletedTransactionSql {
         Ok(())
     }
 
-    #[allow(dead_code)]
     pub fn index(conn: &mut SqliteConnection) -> Result<Vec<CompletedTransactionSql>, TransactionStorageError> {
         Ok(completed_transactions::table.load::<CompletedTransactionSql>(conn)?)
     }
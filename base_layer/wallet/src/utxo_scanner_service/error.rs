@@ -63,6 +63,14 @@ pub enum UtxoScannerError {
     FixedHashSizeError(#[from] FixedHashSizeError),
     #[error("Connectivity has shut down")]
     ConnectivityShutdown,
+    #[error(
+        "The base node only keeps the full outputs of blocks from height {full_outputs_from_height}, but the scan \
+         starts at height {scan_height}"
+    )]
+    OutputsPruned {
+        full_outputs_from_height: u64,
+        scan_height: u64,
+    },
 }
 
 impl From<HexError> for UtxoScannerError {
@@ -20,13 +20,56 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::time::Duration;
+use std::{collections::BTreeMap, fmt, time::Duration};
 
 use tari_comms::peer_manager::NodeId;
-use tari_core::transactions::tari_amount::MicroMinotari;
+use tari_core::transactions::{key_manager::TariKeyId, tari_amount::MicroMinotari};
+use tari_key_manager::key_manager_service::KeyId;
 use tokio::sync::{broadcast, watch};
 
-use crate::util::watch::Watch;
+use crate::{output_manager_service::storage::OutputSource, util::watch::Watch};
+
+/// The kind of wallet key that a recovered output was found with
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RecoveryBranch {
+    /// The commitment mask was found on this key manager branch
+    Managed(String),
+    /// The commitment mask is not on a key manager branch and was imported
+    Imported,
+    /// The output pays a known one-sided script key
+    OneSided,
+    /// The output pays one of the wallet's (sub-)addresses with a stealth one-sided script
+    StealthOneSided,
+}
+
+impl RecoveryBranch {
+    pub fn new(source: OutputSource, spending_key_id: &TariKeyId) -> Self {
+        match (source, spending_key_id) {
+            (OutputSource::OneSided, _) => RecoveryBranch::OneSided,
+            (OutputSource::StealthOneSided, _) => RecoveryBranch::StealthOneSided,
+            (_, KeyId::Managed { branch, .. }) => RecoveryBranch::Managed(branch.clone()),
+            _ => RecoveryBranch::Imported,
+        }
+    }
+}
+
+impl fmt::Display for RecoveryBranch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecoveryBranch::Managed(branch) => write!(f, "Branch '{}'", branch),
+            RecoveryBranch::Imported => write!(f, "Imported keys"),
+            RecoveryBranch::OneSided => write!(f, "One-sided"),
+            RecoveryBranch::StealthOneSided => write!(f, "Stealth one-sided"),
+        }
+    }
+}
+
+/// The outputs recovered with one kind of wallet key during a scan
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BranchRecoveryStats {
+    pub num_recovered: u64,
+    pub value_recovered: MicroMinotari,
+}
 
 #[derive(Debug, Clone)]
 pub enum UtxoScannerEvent {
@@ -48,12 +91,14 @@ pub enum UtxoScannerEvent {
         current_height: u64,
         tip_height: u64,
     },
-    /// Completed Recovery (Number scanned, Num of Recovered outputs, Value of recovered outputs, Time taken)
+    /// Completed Recovery (Number scanned, Num of Recovered outputs, Value of recovered outputs, Time taken, Recovered
+    /// outputs per kind of wallet key)
     Completed {
         final_height: u64,
         num_recovered: u64,
         value_recovered: MicroMinotari,
         time_taken: Duration,
+        recovered_by_branch: BTreeMap<RecoveryBranch, BranchRecoveryStats>,
     },
     /// Scanning process has failed and scanning process has exited
     ScanningFailed,
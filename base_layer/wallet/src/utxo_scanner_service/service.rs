@@ -20,6 +20,8 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use std::collections::BTreeMap;
+
 use chrono::NaiveDateTime;
 use futures::FutureExt;
 use log::*;
@@ -102,6 +104,9 @@ where
             num_retries: 1,
             mode: self.mode.clone(),
             shutdown_signal,
+            recovered_by_branch: BTreeMap::new(),
+            allow_pruned_outputs: false,
+            num_pruned_peers: 0,
         }
     }
 
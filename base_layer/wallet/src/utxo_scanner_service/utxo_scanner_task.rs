@@ -21,6 +21,7 @@
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use std::{
+    collections::BTreeMap,
     convert::{TryFrom, TryInto},
     time::{Duration, Instant},
 };
@@ -59,11 +60,12 @@ use tokio::sync::broadcast;
 use crate::{
     connectivity_service::WalletConnectivityInterface,
     error::WalletError,
+    output_manager_service::storage::OutputSource,
     storage::database::WalletBackend,
     transaction_service::error::{TransactionServiceError, TransactionStorageError},
     utxo_scanner_service::{
         error::UtxoScannerError,
-        handle::UtxoScannerEvent,
+        handle::{BranchRecoveryStats, RecoveryBranch, UtxoScannerEvent},
         service::{ScannedBlock, UtxoScannerResources, SCANNED_BLOCK_CACHE_SIZE},
         uxto_scanner_service_builder::UtxoScannerMode,
         RECOVERY_KEY,
@@ -81,6 +83,13 @@ pub struct UtxoScannerTask<TBackend, TWalletConnectivity> {
     pub(crate) peer_index: usize,
     pub(crate) mode: UtxoScannerMode,
     pub(crate) shutdown_signal: ShutdownSignal,
+    /// The outputs recovered per kind of wallet key during this run
+    pub(crate) recovered_by_branch: BTreeMap<RecoveryBranch, BranchRecoveryStats>,
+    /// Set once none of the peers keeps the spent outputs of the blocks to scan, after which a pruned node is used to
+    /// scan only their unspent outputs
+    pub(crate) allow_pruned_outputs: bool,
+    /// The number of peers in this round that were skipped because they pruned the spent outputs of the blocks to scan
+    pub(crate) num_pruned_peers: usize,
 }
 impl<TBackend, TWalletConnectivity> UtxoScannerTask<TBackend, TWalletConnectivity>
 where
@@ -114,6 +123,9 @@ where
                         return Ok(());
                     },
                     Err(e) => {
+                        if matches!(e, UtxoScannerError::OutputsPruned { .. }) {
+                            self.num_pruned_peers += 1;
+                        }
                         warn!(
                             target: LOG_TARGET,
                             "Failed to scan UTXO's from base node {}: {}", peer, e
@@ -127,6 +139,17 @@ where
                     },
                 },
                 None => {
+                    if !self.allow_pruned_outputs && self.num_pruned_peers > 0 {
+                        warn!(
+                            target: LOG_TARGET,
+                            "None of the base nodes keeps the spent outputs of the blocks to scan, only their unspent \
+                             outputs will be scanned"
+                        );
+                        self.allow_pruned_outputs = true;
+                        self.peer_index = 0;
+                        continue;
+                    }
+                    self.num_pruned_peers = 0;
                     self.publish_event(UtxoScannerEvent::ScanningRoundFailed {
                         num_retries: self.num_retries,
                         retry_limit: self.retry_limit,
@@ -170,6 +193,7 @@ where
             num_recovered: num_outputs_recovered,
             value_recovered: total_value,
             time_taken: elapsed,
+            recovered_by_branch: self.recovered_by_branch.clone(),
         });
 
         // Presence of scanning keys are used to determine if a wallet is busy with recovery or not.
@@ -229,7 +253,7 @@ where
 
         let timer = Instant::now();
         loop {
-            let tip_header = self.get_chain_tip_header(&mut client).await?;
+            let (tip_header, full_outputs_from_height) = self.get_chain_tip(&mut client).await?;
             let tip_header_hash = tip_header.hash();
             let last_scanned_block = self.get_last_scanned_block(tip_header.height, &mut client).await?;
 
@@ -266,8 +290,9 @@ where
                 // The node does not know of any of our cached headers so we will start the scan anew from the
                 // wallet birthday
                 self.resources.db.clear_scanned_blocks()?;
+                // The seed of these wallets does not hold the birthday of their keys
                 let birthday_height_hash = match self.resources.db.get_wallet_type()? {
-                    Some(WalletType::ProvidedKeys(_)) => {
+                    Some(WalletType::ProvidedKeys(_) | WalletType::RemoteSigner(_)) => {
                         let header_proto = client.get_header_by_height(0).await?;
                         let header = BlockHeader::try_from(header_proto).map_err(UtxoScannerError::ConversionError)?;
                         HeightHash {
@@ -296,6 +321,16 @@ where
                 ));
             }
 
+            // The peer is only used if it still has the spent outputs of the blocks to scan, so that the history of
+            // the outputs that the wallet finds is complete
+            let require_full_outputs = !self.allow_pruned_outputs;
+            if require_full_outputs && next_block_to_scan.height < full_outputs_from_height {
+                return Err(UtxoScannerError::OutputsPruned {
+                    full_outputs_from_height,
+                    scan_height: next_block_to_scan.height,
+                });
+            }
+
             debug!(
                 target: LOG_TARGET,
                 "Scanning UTXO's from height = {} to current tip_height = {} (starting header_hash: {})",
@@ -310,6 +345,7 @@ where
                     next_block_to_scan.header_hash,
                     tip_header_hash,
                     tip_header.height,
+                    require_full_outputs,
                 )
                 .await?;
             if num_scanned == 0 {
@@ -340,16 +376,17 @@ where
         Ok(RpcClientLease::new(client))
     }
 
-    async fn get_chain_tip_header(
+    /// The tip header of the peer, and the lowest height from which it keeps the full outputs of every block
+    async fn get_chain_tip(
         &self,
         client: &mut BaseNodeWalletRpcClient,
-    ) -> Result<BlockHeader, UtxoScannerError> {
+    ) -> Result<(BlockHeader, u64), UtxoScannerError> {
         let tip_info = client.get_tip_info().await?;
         let chain_height = tip_info.metadata.map(|m| m.best_block_height()).unwrap_or(0);
         let end_header = client.get_header_by_height(chain_height).await?;
         let end_header = BlockHeader::try_from(end_header).map_err(UtxoScannerError::ConversionError)?;
 
-        Ok(end_header)
+        Ok((end_header, tip_info.full_outputs_from_height))
     }
 
     async fn get_last_scanned_block(
@@ -455,6 +492,7 @@ where
         start_header_hash: HashOutput,
         end_header_hash: HashOutput,
         tip_height: u64,
+        require_full_outputs: bool,
     ) -> Result<(u64, u64, MicroMinotari), UtxoScannerError> {
         // Setting how often the progress event and log should occur during scanning. Defined in blocks
         const PROGRESS_REPORT_INTERVAL: u64 = 100;
@@ -466,6 +504,7 @@ where
         let request = SyncUtxosByBlockRequest {
             start_header_hash: start_header_hash.to_vec(),
             end_header_hash: end_header_hash.to_vec(),
+            require_full_outputs,
         };
 
         let start = Instant::now();
@@ -572,80 +611,70 @@ where
         &mut self,
         outputs: Vec<TransactionOutput>,
         height: u64,
-    ) -> Result<Vec<(WalletOutput, String, ImportStatus, TxId, TransactionOutput)>, UtxoScannerError> {
-        let mut found_outputs: Vec<(WalletOutput, String, ImportStatus, TxId, TransactionOutput)> = Vec::new();
+    ) -> Result<
+        Vec<(
+            WalletOutput,
+            String,
+            ImportStatus,
+            RecoveryBranch,
+            TxId,
+            TransactionOutput,
+        )>,
+        UtxoScannerError,
+    > {
         let start = Instant::now();
-        found_outputs.append(
-            &mut self
-                .resources
-                .output_manager_service
-                .scan_for_recoverable_outputs(outputs.clone().into_iter().map(|o| (o, None)).collect())
-                .await?
-                .into_iter()
-                .map(|ro| -> Result<_, UtxoScannerError> {
-                    let (message, status) = if ro.output.features.is_coinbase() {
-                        (
-                            format!("Coinbase for height: {}", height),
-                            ImportStatus::CoinbaseUnconfirmed,
-                        )
-                    } else {
-                        (self.resources.recovery_message.clone(), ImportStatus::Imported)
-                    };
-                    let output = outputs.iter().find(|o| o.hash() == ro.hash).ok_or_else(|| {
-                        UtxoScannerError::UtxoScanningError(format!("Output '{}' not found", ro.hash.to_hex()))
-                    })?;
-                    Ok((ro.output, message, status, ro.tx_id, output.clone()))
-                })
-                .collect::<Result<Vec<_>, _>>()?,
-        );
-        let scanned_time = start.elapsed();
-        let start = Instant::now();
-
-        found_outputs.append(
-            &mut self
-                .resources
-                .output_manager_service
-                .scan_outputs_for_one_sided_payments(outputs.clone().into_iter().map(|o| (o, None)).collect())
-                .await?
-                .into_iter()
-                .map(|ro| -> Result<_, UtxoScannerError> {
-                    let (message, status) = if ro.output.features.is_coinbase() {
-                        (
-                            format!("Coinbase for height: {}", height),
-                            ImportStatus::CoinbaseUnconfirmed,
-                        )
-                    } else {
-                        (
-                            self.resources.recovery_message.clone(),
-                            ImportStatus::OneSidedUnconfirmed,
-                        )
-                    };
-                    let output = outputs.iter().find(|o| o.hash() == ro.hash).ok_or_else(|| {
-                        UtxoScannerError::UtxoScanningError(format!("Output '{}' not found", ro.hash.to_hex()))
-                    })?;
-                    Ok((ro.output, message, status, ro.tx_id, output.clone()))
-                })
-                .collect::<Result<Vec<_>, _>>()?,
-        );
-        let one_sided_time = start.elapsed();
+        let found_outputs = self
+            .resources
+            .output_manager_service
+            .scan_outputs_for_all_branches(outputs.clone().into_iter().map(|o| (o, None)).collect())
+            .await?
+            .into_iter()
+            .map(|ro| -> Result<_, UtxoScannerError> {
+                let (message, status) = if ro.output.features.is_coinbase() {
+                    (
+                        format!("Coinbase for height: {}", height),
+                        ImportStatus::CoinbaseUnconfirmed,
+                    )
+                } else if matches!(ro.source, OutputSource::OneSided | OutputSource::StealthOneSided) {
+                    (
+                        self.resources.recovery_message.clone(),
+                        ImportStatus::OneSidedUnconfirmed,
+                    )
+                } else {
+                    (self.resources.recovery_message.clone(), ImportStatus::Imported)
+                };
+                let branch = RecoveryBranch::new(ro.source, &ro.output.spending_key_id);
+                let output = outputs.iter().find(|o| o.hash() == ro.hash).ok_or_else(|| {
+                    UtxoScannerError::UtxoScanningError(format!("Output '{}' not found", ro.hash.to_hex()))
+                })?;
+                Ok((ro.output, message, status, branch, ro.tx_id, output.clone()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
         trace!(
             target: LOG_TARGET,
-            "Scanned for outputs: outputs took {} ms , one-sided took {} ms",
-            scanned_time.as_millis(),
-            one_sided_time.as_millis(),
+            "Scanned {} outputs for all key branches in {} ms",
+            outputs.len(),
+            start.elapsed().as_millis(),
         );
         Ok(found_outputs)
     }
 
     async fn import_utxos_to_transaction_service(
         &mut self,
-        utxos: Vec<(WalletOutput, String, ImportStatus, TxId, TransactionOutput)>,
+        utxos: Vec<(
+            WalletOutput,
+            String,
+            ImportStatus,
+            RecoveryBranch,
+            TxId,
+            TransactionOutput,
+        )>,
         current_height: u64,
         mined_timestamp: NaiveDateTime,
     ) -> Result<(u64, MicroMinotari), UtxoScannerError> {
         let mut num_recovered = 0u64;
         let mut total_amount = MicroMinotari::from(0);
-        for (wo, message, import_status, tx_id, to) in utxos {
+        for (wo, message, import_status, branch, tx_id, to) in utxos {
             let source_address = if wo.features.is_coinbase() {
                 // It's a coinbase, so we know we mined it (we do mining with cold wallets).
                 self.resources.one_sided_tari_address.clone()
@@ -671,6 +700,9 @@ where
                 Ok(_) => {
                     num_recovered = num_recovered.saturating_add(1);
                     total_amount += wo.value;
+                    let stats = self.recovered_by_branch.entry(branch).or_default();
+                    stats.num_recovered = stats.num_recovered.saturating_add(1);
+                    stats.value_recovered += wo.value;
                 },
                 Err(WalletError::TransactionServiceError(TransactionServiceError::TransactionStorageError(
                     TransactionStorageError::DuplicateOutput,
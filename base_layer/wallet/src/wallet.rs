@@ -54,7 +54,12 @@ use tari_core::{
     consensus::{ConsensusManager, NetworkConsensus},
     covenants::Covenant,
     transactions::{
-        key_manager::{SecretTransactionKeyManagerInterface, TariKeyId, TransactionKeyManagerInitializer},
+        key_manager::{
+            RemoteSigner,
+            SecretTransactionKeyManagerInterface,
+            TariKeyId,
+            TransactionKeyManagerInitializer,
+        },
         tari_amount::MicroMinotari,
         transaction_components::{encrypted_data::PaymentId, EncryptedData, OutputFeatures, UnblindedOutput},
         CryptoFactories,
@@ -92,6 +97,7 @@ use crate::{
         WalletConnectivityInterface,
     },
     consts,
+    deposit_tracking_service::{handle::DepositTrackingHandle, DepositTrackingServiceInitializer},
     error::{WalletError, WalletStorageError},
     output_manager_service::{
         error::OutputManagerError,
@@ -102,7 +108,14 @@ use crate::{
         },
         OutputManagerServiceInitializer,
     },
+    push_notification_service::{
+        handle::{PushNotificationHandle, PushNotificationRegistration},
+        PushNotificationServiceInitializer,
+        PUSH_NOTIFICATION_REGISTRATION_KEY,
+    },
+    reorg_monitor_service::{handle::ReorgMonitorHandle, ReorgMonitorServiceInitializer},
     storage::database::{WalletBackend, WalletDatabase},
+    sweep_service::{handle::SweepHandle, SweepServiceInitializer},
     transaction_service::{
         handle::TransactionServiceHandle,
         storage::database::TransactionBackend,
@@ -138,6 +151,10 @@ pub struct Wallet<T, U, V, W, TKeyManagerInterface> {
     pub contacts_service: ContactsServiceHandle,
     pub base_node_service: BaseNodeServiceHandle,
     pub utxo_scanner_service: UtxoScannerHandle,
+    pub push_notification_service: PushNotificationHandle,
+    pub deposit_tracking_service: DepositTrackingHandle,
+    pub sweep_service: SweepHandle,
+    pub reorg_monitor_service: ReorgMonitorHandle,
     pub updater_service: Option<SoftwareUpdaterHandle>,
     pub db: WalletDatabase<T>,
     pub output_db: OutputManagerDatabase<V>,
@@ -173,6 +190,7 @@ where
         shutdown_signal: ShutdownSignal,
         master_seed: CipherSeed,
         wallet_type: Option<WalletType>,
+        remote_signer: Option<Arc<dyn RemoteSigner>>,
         user_agent: String,
     ) -> Result<Self, WalletError> {
         let wallet_type = Arc::new(read_or_create_wallet_type(wallet_type, &wallet_database)?);
@@ -201,16 +219,19 @@ where
                 factories.clone(),
                 config.network.into(),
             ))
-            .add_initializer(TransactionKeyManagerInitializer::new(
-                key_manager_backend,
-                master_seed,
-                factories.clone(),
-                wallet_type.clone(),
-            ))
+            .add_initializer(
+                TransactionKeyManagerInitializer::new(
+                    key_manager_backend,
+                    master_seed,
+                    factories.clone(),
+                    wallet_type.clone(),
+                )
+                .with_remote_signer(remote_signer),
+            )
             .add_initializer(TransactionServiceInitializer::<U, T, TKeyManagerInterface>::new(
                 config.transaction_service_config,
                 peer_message_subscription_factory.clone(),
-                transaction_backend,
+                transaction_backend.clone(),
                 node_identity.clone(),
                 config.network,
                 consensus_manager,
@@ -227,12 +248,15 @@ where
                 },
                 peer_message_subscription_factory.clone(),
             ))
-            .add_initializer(ContactsServiceInitializer::new(
-                contacts_backend,
-                peer_message_subscription_factory,
-                config.contacts_auto_ping_interval,
-                config.contacts_online_ping_window,
-            ))
+            .add_initializer(
+                ContactsServiceInitializer::new(
+                    contacts_backend,
+                    peer_message_subscription_factory,
+                    config.contacts_auto_ping_interval,
+                    config.contacts_online_ping_window,
+                )
+                .with_presence_publishing(config.contacts_publish_presence),
+            )
             .add_initializer(BaseNodeServiceInitializer::new(
                 config.base_node_service_config.clone(),
                 wallet_database.clone(),
@@ -242,6 +266,23 @@ where
                 wallet_database.clone(),
                 factories.clone(),
                 config.network,
+            ))
+            .add_initializer(PushNotificationServiceInitializer::new(
+                config.push_notification_service_config,
+                wallet_database.clone(),
+            ))
+            .add_initializer(DepositTrackingServiceInitializer::new(
+                config.deposit_tracking_service_config,
+                wallet_database.clone(),
+            ))
+            .add_initializer(SweepServiceInitializer::new(
+                config.sweep_service_config,
+                wallet_database.clone(),
+            ))
+            .add_initializer(ReorgMonitorServiceInitializer::new(
+                config.reorg_monitor_service_config,
+                transaction_backend,
+                output_manager_database.clone(),
             ));
 
         // Check if we have update config. FFI wallets don't do this, the update on mobile is done differently.
@@ -312,6 +353,10 @@ where
 
         let base_node_service_handle = handles.expect_handle::<BaseNodeServiceHandle>();
         let utxo_scanner_service_handle = handles.expect_handle::<UtxoScannerHandle>();
+        let push_notification_handle = handles.expect_handle::<PushNotificationHandle>();
+        let deposit_tracking_handle = handles.expect_handle::<DepositTrackingHandle>();
+        let sweep_handle = handles.expect_handle::<SweepHandle>();
+        let reorg_monitor_handle = handles.expect_handle::<ReorgMonitorHandle>();
         let wallet_connectivity = handles.expect_handle::<WalletConnectivityHandle>();
         let updater_handle = if auto_update.is_update_enabled() {
             Some(handles.expect_handle::<SoftwareUpdaterHandle>())
@@ -355,6 +400,10 @@ where
             contacts_service: contacts_handle,
             base_node_service: base_node_service_handle,
             utxo_scanner_service: utxo_scanner_service_handle,
+            push_notification_service: push_notification_handle,
+            deposit_tracking_service: deposit_tracking_handle,
+            sweep_service: sweep_handle,
+            reorg_monitor_service: reorg_monitor_handle,
             updater_service: updater_handle,
             wallet_connectivity,
             db: wallet_database,
@@ -499,7 +548,9 @@ where
         let comms_key = self.key_manager_service.get_comms_key().await?;
         let features = match *self.wallet_type {
             WalletType::DerivedKeys => TariAddressFeatures::default(),
-            WalletType::Ledger(_) | WalletType::ProvidedKeys(_) => TariAddressFeatures::create_interactive_only(),
+            WalletType::Ledger(_) | WalletType::ProvidedKeys(_) | WalletType::RemoteSigner(_) => {
+                TariAddressFeatures::create_interactive_only()
+            },
         };
         Ok(TariAddress::new_dual_address(
             view_key.pub_key,
@@ -520,6 +571,21 @@ where
         ))
     }
 
+    /// Returns the deterministic one-sided sub-address at `index`, e.g. to assign a unique deposit address to each
+    /// customer of an exchange. Sub-addresses share the wallet's view key and are recoverable from the seed words;
+    /// index 0 is the wallet's primary one-sided address. Incoming outputs are only recognised for indices within the
+    /// configured `sub_address_lookahead`.
+    pub async fn get_wallet_sub_address(&self, index: u64) -> Result<TariAddress, KeyManagerServiceError> {
+        let view_key = self.key_manager_service.get_view_key().await?;
+        let spend_key = self.key_manager_service.get_sub_address_spend_key(index).await?;
+        Ok(TariAddress::new_dual_address(
+            view_key.pub_key,
+            spend_key,
+            self.network.as_network(),
+            TariAddressFeatures::create_one_sided_only(),
+        ))
+    }
+
     pub async fn get_wallet_id(&self) -> Result<WalletIdentity, WalletError> {
         let address_interactive = self.get_wallet_interactive_address().await?;
         let address_one_sided = self.get_wallet_one_sided_address().await?;
@@ -770,6 +836,32 @@ where
         }
     }
 
+    /// Registers the device that push notifications for incoming transactions are sent to via `relay_url`, replacing
+    /// any previous registration. The registration is persisted so that it survives a restart.
+    pub fn register_push_notifications(
+        &self,
+        relay_url: String,
+        device_token: String,
+        notification_key: &[u8],
+    ) -> Result<(), WalletError> {
+        let registration = PushNotificationRegistration::new(relay_url, device_token, notification_key)?;
+        self.push_notification_service.check_relay(&registration.relay_url)?;
+        self.db.set_client_key_value(
+            PUSH_NOTIFICATION_REGISTRATION_KEY.to_string(),
+            registration.to_storage_string()?,
+        )?;
+        self.push_notification_service.register(registration)?;
+        Ok(())
+    }
+
+    /// Stops sending push notifications and forgets the registered device
+    pub fn unregister_push_notifications(&self) -> Result<(), WalletError> {
+        self.db
+            .clear_client_value(PUSH_NOTIFICATION_REGISTRATION_KEY.to_string())?;
+        self.push_notification_service.unregister();
+        Ok(())
+    }
+
     /// Utility function to find out if there is data in the database indicating that there is an incomplete recovery
     /// process in progress
     pub fn is_recovery_in_progress(&self) -> Result<bool, WalletError> {
@@ -155,6 +155,12 @@ async fn create_wallet(
         rpc_max_simultaneous_sessions: 0,
         rpc_max_sessions_per_peer: 0,
         listener_self_liveness_check_interval: None,
+        cover_traffic_interval: None,
+        traffic_padding: false,
+        rpc_max_pending_requests: None,
+        rpc_max_requests_per_minute_per_method: None,
+        rpc_max_requests_per_minute_per_peer: None,
+        max_inbound_connections_per_subnet: None,
     };
 
     let sql_database_path = comms_config
@@ -693,6 +699,12 @@ async fn test_import_utxo() {
         rpc_max_simultaneous_sessions: 0,
         rpc_max_sessions_per_peer: 0,
         listener_self_liveness_check_interval: None,
+        cover_traffic_interval: None,
+        traffic_padding: false,
+        rpc_max_pending_requests: None,
+        rpc_max_requests_per_minute_per_method: None,
+        rpc_max_requests_per_minute_per_peer: None,
+        max_inbound_connections_per_subnet: None,
     };
     let config = WalletConfig {
         p2p: comms_config,
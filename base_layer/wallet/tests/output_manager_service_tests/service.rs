@@ -47,7 +47,7 @@ use tari_common_types::{
     key_branches::TransactionKeyManagerBranch,
     tari_address::TariAddress,
     transaction::TxId,
-    types::{ComAndPubSignature, FixedHash, PublicKey},
+    types::{ComAndPubSignature, FixedHash, PrivateKey, PublicKey},
 };
 use tari_comms::{
     peer_manager::{NodeIdentity, PeerFeatures},
@@ -65,13 +65,20 @@ use tari_core::{
         key_manager::{create_memory_db_key_manager, MemoryDbKeyManager, TransactionKeyManagerInterface},
         tari_amount::{uT, MicroMinotari, T},
         test_helpers::{create_wallet_output_with_data, TestParams},
-        transaction_components::{encrypted_data::PaymentId, OutputFeatures, TransactionOutput, WalletOutput},
+        transaction_components::{
+            encrypted_data::PaymentId,
+            OutputFeatures,
+            TransactionOutput,
+            VaultScript,
+            WalletOutput,
+        },
         transaction_protocol::{sender::TransactionSenderMessage, TransactionMetadata},
         weight::TransactionWeight,
         CryptoFactories,
         SenderTransactionProtocol,
     },
 };
+use tari_crypto::keys::SecretKey;
 use tari_key_manager::key_manager_service::{KeyId, KeyManagerInterface};
 use tari_script::{inputs, script, TariScript};
 use tari_service_framework::reply_channel;
@@ -2305,3 +2312,166 @@ async fn recovered_output_key_not_in_keychain() {
         "It should not reach an error condition or return an output"
     );
 }
+
+#[tokio::test]
+async fn scan_for_all_branches_recovers_imported_key_outputs() {
+    let (connection, _tempdir) = get_temp_sqlite_database_connection();
+    let backend = OutputManagerSqliteDatabase::new(connection.clone());
+    let mut oms = setup_output_manager_service(backend.clone(), true).await;
+
+    // The output is encrypted to, and its script pays, a key that was imported into the wallet
+    let imported_key_id = oms
+        .key_manager_handle
+        .import_key(PrivateKey::random(&mut OsRng))
+        .await
+        .unwrap();
+    let imported_public_key = oms
+        .key_manager_handle
+        .get_public_key_at_key_id(&imported_key_id)
+        .await
+        .unwrap();
+    let commitment_mask_key = oms
+        .key_manager_handle
+        .get_next_key(TransactionKeyManagerBranch::CommitmentMask.get_branch_key())
+        .await
+        .unwrap();
+    let amount = MicroMinotari::from(5_000);
+    let encrypted_data = oms
+        .key_manager_handle
+        .encrypt_data_for_recovery(
+            &commitment_mask_key.key_id,
+            Some(&imported_key_id),
+            amount.as_u64(),
+            PaymentId::Empty,
+        )
+        .await
+        .unwrap();
+    let wallet_output = WalletOutput::new_current_version(
+        amount,
+        commitment_mask_key.key_id.clone(),
+        OutputFeatures::default(),
+        script!(PushPubKey(Box::new(imported_public_key))).unwrap(),
+        inputs!(),
+        imported_key_id.clone(),
+        PublicKey::default(),
+        ComAndPubSignature::default(),
+        0,
+        Covenant::new(),
+        encrypted_data,
+        MicroMinotari::zero(),
+        PaymentId::Empty,
+        &oms.key_manager_handle,
+    )
+    .await
+    .unwrap();
+    let output = wallet_output
+        .to_transaction_output(&oms.key_manager_handle)
+        .await
+        .unwrap();
+
+    // Only the view key is tried when scanning for recoverable outputs
+    let recovered_outputs = oms
+        .output_manager_handle
+        .scan_for_recoverable_outputs(vec![(output.clone(), None)])
+        .await
+        .unwrap();
+    assert!(recovered_outputs.is_empty());
+
+    let recovered_outputs = oms
+        .output_manager_handle
+        .scan_outputs_for_all_branches(vec![(output, None)])
+        .await
+        .unwrap();
+    assert_eq!(recovered_outputs.len(), 1);
+    assert_eq!(recovered_outputs[0].output.value, amount);
+    assert_eq!(recovered_outputs[0].output.spending_key_id, commitment_mask_key.key_id);
+    assert_eq!(recovered_outputs[0].output.script_key_id, imported_key_id);
+}
+
+#[tokio::test]
+async fn test_vault_clawback_by_recovery_key_holder() {
+    let (connection, _tempdir) = get_temp_sqlite_database_connection();
+    let backend = OutputManagerSqliteDatabase::new(connection.clone());
+    let mut owner = setup_output_manager_service(backend.clone(), true).await;
+    let (connection, _tempdir) = get_temp_sqlite_database_connection();
+    let mut holder = setup_output_manager_service(OutputManagerSqliteDatabase::new(connection), true).await;
+
+    let recovery_key = holder.output_manager_handle.create_vault_recovery_key().await.unwrap();
+
+    let uo = make_input(
+        &mut OsRng.clone(),
+        MicroMinotari::from(50_000),
+        &OutputFeatures::default(),
+        &owner.key_manager_handle,
+    )
+    .await;
+    owner.output_manager_handle.add_output(uo.clone(), None).await.unwrap();
+    backend
+        .mark_outputs_as_unspent(vec![(uo.hash(&owner.key_manager_handle).await.unwrap(), true)])
+        .unwrap();
+
+    let amount = MicroMinotari::from(20_000);
+    let fee_per_gram = MicroMinotari::from(5);
+    let (_, tx) = owner
+        .output_manager_handle
+        .create_vault_transaction(
+            TxId::new_random(),
+            amount,
+            recovery_key,
+            10,
+            UtxoSelectionCriteria::default(),
+            fee_per_gram,
+        )
+        .await
+        .unwrap();
+    let vault_output = tx
+        .body
+        .outputs()
+        .iter()
+        .find(|o| VaultScript::from_script(&o.script).is_some())
+        .unwrap();
+    backend
+        .mark_outputs_as_unspent(vec![(vault_output.hash(), true)])
+        .unwrap();
+
+    let opening = owner
+        .output_manager_handle
+        .export_vault_opening(vault_output.hash())
+        .await
+        .unwrap();
+
+    // The owner does not hold the recovery key, so cannot claw the vault back
+    let err = owner
+        .output_manager_handle
+        .create_vault_clawback_transaction(opening.clone(), fee_per_gram)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, OutputManagerError::InvalidArgument(_)));
+
+    let (_, fee, swept_amount, clawback) = holder
+        .output_manager_handle
+        .create_vault_clawback_transaction(opening, fee_per_gram)
+        .await
+        .unwrap();
+    assert_eq!(swept_amount, amount - fee);
+    assert_eq!(clawback.body.inputs().len(), 1);
+    assert_eq!(
+        clawback.body.inputs()[0].commitment().unwrap(),
+        &vault_output.commitment
+    );
+    assert_eq!(clawback.body.outputs().len(), 1);
+
+    // The swept output is encrypted to the holder, not to the vault owner
+    let swept_output = &clawback.body.outputs()[0];
+    let (_, recovered_value, _) = holder
+        .key_manager_handle
+        .try_output_key_recovery(swept_output, None)
+        .await
+        .unwrap();
+    assert_eq!(recovered_value, swept_amount);
+    assert!(owner
+        .key_manager_handle
+        .try_output_key_recovery(swept_output, None)
+        .await
+        .is_err());
+}
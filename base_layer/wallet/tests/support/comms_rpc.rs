@@ -97,6 +97,7 @@ pub struct BaseNodeWalletRpcMockState {
     get_header_by_height_calls: Arc<Mutex<Vec<u64>>>,
     get_height_at_time_calls: Arc<Mutex<Vec<u64>>>,
     sync_utxo_by_block_calls: Arc<Mutex<Vec<(HashOutput, HashOutput)>>>,
+    require_full_outputs_calls: Arc<Mutex<Vec<bool>>>,
     submit_transaction_response: Arc<Mutex<TxSubmissionResponse>>,
     transaction_query_response: Arc<Mutex<TxQueryResponse>>,
     transaction_query_batch_response: Arc<Mutex<TxQueryBatchResponsesProto>>,
@@ -127,6 +128,7 @@ impl BaseNodeWalletRpcMockState {
             get_header_by_height_calls: Arc::new(Mutex::new(vec![])),
             get_height_at_time_calls: Arc::new(Mutex::new(vec![])),
             sync_utxo_by_block_calls: Arc::new(Mutex::new(vec![])),
+            require_full_outputs_calls: Arc::new(Mutex::new(vec![])),
             submit_transaction_response: Arc::new(Mutex::new(TxSubmissionResponse {
                 accepted: true,
                 rejection_reason: TxSubmissionRejectionReason::None,
@@ -156,6 +158,7 @@ impl BaseNodeWalletRpcMockState {
                     timestamp: EpochTime::now().as_u64(),
                 }),
                 is_synced: true,
+                full_outputs_from_height: 0,
             })),
             utxo_query_response: Arc::new(Mutex::new(UtxoQueryResponses {
                 responses: vec![],
@@ -322,6 +325,11 @@ impl BaseNodeWalletRpcMockState {
         acquire_lock!(self.sync_utxo_by_block_calls).drain(..).collect()
     }
 
+    /// Whether each `sync_utxos_by_block` call required the full outputs of the blocks
+    pub fn take_require_full_outputs_calls(&self) -> Vec<bool> {
+        acquire_lock!(self.require_full_outputs_calls).drain(..).collect()
+    }
+
     pub fn pop_sync_utxos_by_block_calls(&self) -> Option<(HashOutput, HashOutput)> {
         acquire_lock!(self.sync_utxo_by_block_calls).pop()
     }
@@ -774,7 +782,9 @@ impl BaseNodeWalletService for BaseNodeWalletRpcMockService {
         let SyncUtxosByBlockRequest {
             start_header_hash,
             end_header_hash,
+            require_full_outputs,
         } = request.into_message();
+        acquire_lock!(self.state.require_full_outputs_calls).push(require_full_outputs);
 
         let mut sync_utxo_by_block_lock = acquire_lock!(self.state.sync_utxo_by_block_calls);
         (*sync_utxo_by_block_lock).push((
@@ -939,6 +949,7 @@ mod test {
         service_state.set_tip_info_response(TipInfoResponse {
             metadata: Some(chain_metadata),
             is_synced: false,
+            full_outputs_from_height: 0,
         });
 
         let resp = client.get_tip_info().await.unwrap();
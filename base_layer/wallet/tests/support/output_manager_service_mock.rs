@@ -27,9 +27,10 @@ use log::*;
 use minotari_wallet::output_manager_service::{
     error::OutputManagerError,
     handle::{OutputManagerEvent, OutputManagerHandle, OutputManagerRequest, OutputManagerResponse, RecoveredOutput},
-    storage::models::DbWalletOutput,
+    storage::{models::DbWalletOutput, OutputSource},
 };
 use tari_common_types::transaction::TxId;
+use tari_core::transactions::transaction_components::TransactionOutput;
 use tari_service_framework::{reply_channel, reply_channel::Receiver};
 use tari_shutdown::ShutdownSignal;
 use tokio::sync::{broadcast, broadcast::Sender, oneshot};
@@ -98,22 +99,7 @@ impl OutputManagerServiceMock {
         info!(target: LOG_TARGET, "Handling Request: {}", request);
         match request {
             OutputManagerRequest::ScanForRecoverableOutputs(requested_outputs) => {
-                let lock = acquire_lock!(self.state.recoverable_outputs);
-                let outputs = (*lock)
-                    .clone()
-                    .into_iter()
-                    .filter_map(|dbuo| {
-                        if requested_outputs.iter().any(|ro| dbuo.commitment == ro.0.commitment) {
-                            Some(RecoveredOutput {
-                                output: dbuo.wallet_output,
-                                tx_id: TxId::new_random(),
-                                hash: dbuo.hash,
-                            })
-                        } else {
-                            None
-                        }
-                    })
-                    .collect();
+                let outputs = self.recovered_outputs(&self.state.recoverable_outputs, &requested_outputs, None);
 
                 let _result = reply_tx
                     .send(Ok(OutputManagerResponse::RewoundOutputs(outputs)))
@@ -122,32 +108,60 @@ impl OutputManagerServiceMock {
                     });
             },
             OutputManagerRequest::ScanOutputs(requested_outputs) => {
-                let lock = acquire_lock!(self.state.one_sided_payments);
-                let outputs = (*lock)
-                    .clone()
-                    .into_iter()
-                    .filter_map(|dbuo| {
-                        if requested_outputs.iter().any(|ro| dbuo.commitment == ro.0.commitment) {
-                            Some(RecoveredOutput {
-                                output: dbuo.wallet_output,
-                                tx_id: TxId::new_random(),
-                                hash: dbuo.hash,
-                            })
-                        } else {
-                            None
-                        }
-                    })
-                    .collect();
+                let outputs = self.recovered_outputs(
+                    &self.state.one_sided_payments,
+                    &requested_outputs,
+                    Some(OutputSource::OneSided),
+                );
                 let _result = reply_tx
                     .send(Ok(OutputManagerResponse::ScanOutputs(outputs)))
                     .inspect_err(|_| {
                         warn!(target: LOG_TARGET, "Failed to send reply");
                     });
             },
+            OutputManagerRequest::ScanOutputsForAllBranches(requested_outputs) => {
+                let mut outputs = self.recovered_outputs(&self.state.recoverable_outputs, &requested_outputs, None);
+                outputs.extend(self.recovered_outputs(
+                    &self.state.one_sided_payments,
+                    &requested_outputs,
+                    Some(OutputSource::OneSided),
+                ));
+                let _result = reply_tx
+                    .send(Ok(OutputManagerResponse::RewoundOutputs(outputs)))
+                    .inspect_err(|_| {
+                        warn!(target: LOG_TARGET, "Failed to send reply");
+                    });
+            },
             OutputManagerRequest::ValidateUtxos => {},
             _ => panic!("Output Manager Service Mock does not support this call"),
         }
     }
+
+    /// The outputs in `state` that were requested, reported with `source` instead of their own source if it is given
+    fn recovered_outputs(
+        &self,
+        state: &Arc<Mutex<Vec<DbWalletOutput>>>,
+        requested_outputs: &[(TransactionOutput, Option<TxId>)],
+        source: Option<OutputSource>,
+    ) -> Vec<RecoveredOutput> {
+        let lock = acquire_lock!(state);
+        (*lock)
+            .clone()
+            .into_iter()
+            .filter_map(|dbuo| {
+                if requested_outputs.iter().any(|ro| dbuo.commitment == ro.0.commitment) {
+                    Some(RecoveredOutput {
+                        output: dbuo.wallet_output,
+                        tx_id: TxId::new_random(),
+                        hash: dbuo.hash,
+                        source: source.unwrap_or(dbuo.source),
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -34,6 +34,7 @@ use minotari_wallet::{
     storage::sqlite_utilities::run_migration_and_create_sqlite_connection,
     transaction_service::{
         config::TransactionServiceConfig,
+        doctor::BroadcastJournal,
         error::TransactionServiceError,
         handle::{TransactionEvent, TransactionEventReceiver, TransactionEventSender},
         protocols::{
@@ -194,6 +195,7 @@ pub async fn setup() -> (
         },
         shutdown_signal: shutdown.to_signal(),
         wallet_type,
+        broadcast_journal: BroadcastJournal::default(),
     };
 
     (
@@ -331,6 +331,7 @@ async fn test_utxo_scanner_recovery() {
     test_interface.rpc_service_state.set_tip_info_response(TipInfoResponse {
         metadata: Some(chain_metadata),
         is_synced: true,
+        full_outputs_from_height: 0,
     });
 
     // Adding half the outputs of the blocks to the OMS mock
@@ -376,16 +377,82 @@ async fn test_utxo_scanner_recovery() {
                     num_recovered,
                     value_recovered,
                     time_taken: _,
+                    recovered_by_branch,
                 } = event.unwrap() {
                     assert_eq!(final_height, NUM_BLOCKS - 1);
                     assert_eq!(num_recovered, total_outputs_to_recover);
                     assert_eq!(value_recovered, total_amount_to_recover);
+                    let branch_total = recovered_by_branch.values().map(|stats| stats.num_recovered).sum::<u64>();
+                    assert_eq!(branch_total, num_recovered);
                     break;
                 }
             }
         }
     }
 }
+
+#[tokio::test]
+async fn test_utxo_scanner_recovery_from_pruned_node() {
+    let key_manager = create_memory_db_key_manager().unwrap();
+    let mut test_interface = setup(key_manager.clone(), UtxoScannerMode::Recovery, None, None, None).await;
+
+    let cipher_seed = CipherSeed::new();
+    let birthday_epoch_time = get_birthday_from_unix_epoch_in_seconds(cipher_seed.birthday(), 14u16);
+    test_interface.wallet_db.set_master_seed(cipher_seed).unwrap();
+
+    const NUM_BLOCKS: u64 = 11;
+    const BIRTHDAY_OFFSET: u64 = 5;
+
+    let TestBlockData {
+        block_headers,
+        utxos_by_block,
+        ..
+    } = generate_block_headers_and_utxos(0, NUM_BLOCKS, birthday_epoch_time, BIRTHDAY_OFFSET, false, &key_manager)
+        .await;
+
+    test_interface.rpc_service_state.set_utxos_by_block(utxos_by_block);
+    test_interface.rpc_service_state.set_blocks(block_headers.clone());
+
+    let chain_metadata = ChainMetadata {
+        best_block_height: NUM_BLOCKS - 1,
+        best_block_hash: block_headers.get(&(NUM_BLOCKS - 1)).unwrap().clone().hash().to_vec(),
+        accumulated_difficulty: Vec::new(),
+        pruned_height: NUM_BLOCKS - 2,
+        timestamp: 0,
+    };
+    // The only peer has pruned the spent outputs of every block but the tip
+    test_interface.rpc_service_state.set_tip_info_response(TipInfoResponse {
+        metadata: Some(chain_metadata),
+        is_synced: true,
+        full_outputs_from_height: NUM_BLOCKS - 1,
+    });
+
+    let mut scanner_event_stream = test_interface.scanner_handle.get_event_receiver();
+
+    tokio::spawn(test_interface.scanner_service.take().unwrap().run());
+
+    let delay = time::sleep(Duration::from_secs(60));
+    tokio::pin!(delay);
+    loop {
+        tokio::select! {
+            _ = &mut delay => {
+                panic!("Completed event should have arrived by now.");
+            }
+            event = scanner_event_stream.recv() => {
+                if let UtxoScannerEvent::Completed { final_height, .. } = event.unwrap() {
+                    assert_eq!(final_height, NUM_BLOCKS - 1);
+                    break;
+                }
+            }
+        }
+    }
+    // The peer was skipped in the first round, and only used for the unspent outputs once no other peer had them
+    assert_eq!(
+        test_interface.rpc_service_state.take_require_full_outputs_calls(),
+        vec![false]
+    );
+}
+
 #[tokio::test]
 #[allow(clippy::too_many_lines)]
 async fn test_utxo_scanner_recovery_with_restart() {
@@ -430,6 +497,7 @@ async fn test_utxo_scanner_recovery_with_restart() {
     test_interface.rpc_service_state.set_tip_info_response(TipInfoResponse {
         metadata: Some(chain_metadata.clone()),
         is_synced: true,
+        full_outputs_from_height: 0,
     });
 
     // Adding half the outputs of the blocks to the OMS mock
@@ -513,6 +581,7 @@ async fn test_utxo_scanner_recovery_with_restart() {
         .set_tip_info_response(TipInfoResponse {
             metadata: Some(chain_metadata),
             is_synced: true,
+            full_outputs_from_height: 0,
         });
     test_interface2
         .oms_mock_state
@@ -532,7 +601,8 @@ async fn test_utxo_scanner_recovery_with_restart() {
                     final_height,
                     num_recovered,
                     value_recovered,
-                    time_taken: _,} = event.unwrap() {
+                    time_taken: _,
+                    recovered_by_branch: _,} = event.unwrap() {
                     assert_eq!(final_height, NUM_BLOCKS-1);
                     assert_eq!(num_recovered, total_outputs_to_recover);
                     assert_eq!(value_recovered, total_amount_to_recover);
@@ -599,6 +669,7 @@ async fn test_utxo_scanner_recovery_with_restart_and_reorg() {
     test_interface.rpc_service_state.set_tip_info_response(TipInfoResponse {
         metadata: Some(chain_metadata.clone()),
         is_synced: true,
+        full_outputs_from_height: 0,
     });
 
     // Adding half the outputs of the blocks to the OMS mock
@@ -681,6 +752,7 @@ async fn test_utxo_scanner_recovery_with_restart_and_reorg() {
         .set_tip_info_response(TipInfoResponse {
             metadata: Some(chain_metadata),
             is_synced: true,
+            full_outputs_from_height: 0,
         });
 
     // calculate new recoverable outputs for the reorg
@@ -729,6 +801,7 @@ async fn test_utxo_scanner_recovery_with_restart_and_reorg() {
                     num_recovered,
                     value_recovered,
                     time_taken: _,
+                    recovered_by_branch: _,
                 } = event.unwrap()
                 {
                     assert_eq!(final_height, 9);
@@ -809,6 +882,7 @@ async fn test_utxo_scanner_scanned_block_cache_clearing() {
     test_interface.rpc_service_state.set_tip_info_response(TipInfoResponse {
         metadata: Some(chain_metadata),
         is_synced: true,
+        full_outputs_from_height: 0,
     });
 
     let first_block_header = block_headers.get(&(800)).unwrap().clone();
@@ -839,7 +913,8 @@ async fn test_utxo_scanner_scanned_block_cache_clearing() {
                     final_height:_,
                     num_recovered:_,
                     value_recovered:_,
-                    time_taken: _,} = event.unwrap(){
+                    time_taken: _,
+                    recovered_by_branch: _,} = event.unwrap(){
                     break;
                 }
             }
@@ -907,6 +982,7 @@ async fn test_utxo_scanner_one_sided_payments() {
     test_interface.rpc_service_state.set_tip_info_response(TipInfoResponse {
         metadata: Some(chain_metadata),
         is_synced: true,
+        full_outputs_from_height: 0,
     });
 
     // Adding half the outputs of the blocks to the OMS mock
@@ -953,7 +1029,8 @@ async fn test_utxo_scanner_one_sided_payments() {
                     final_height,
                     num_recovered,
                     value_recovered,
-                    time_taken: _,} = event.unwrap() {
+                    time_taken: _,
+                    recovered_by_branch: _,} = event.unwrap() {
                     assert_eq!(final_height, NUM_BLOCKS-1);
                     assert_eq!(num_recovered, total_outputs_to_recover);
                     assert_eq!(value_recovered, total_amount_to_recover);
@@ -1029,6 +1106,7 @@ async fn test_utxo_scanner_one_sided_payments() {
     test_interface.rpc_service_state.set_tip_info_response(TipInfoResponse {
         metadata: Some(chain_metadata.clone()),
         is_synced: true,
+        full_outputs_from_height: 0,
     });
     time::sleep(Duration::from_secs(5)).await;
 
@@ -1052,7 +1130,8 @@ async fn test_utxo_scanner_one_sided_payments() {
                     final_height,
                     num_recovered: _,
                     value_recovered: _,
-                    time_taken: _,} = event.unwrap() {
+                    time_taken: _,
+                    recovered_by_branch: _,} = event.unwrap() {
                     assert_eq!(final_height, NUM_BLOCKS);
 
                     break;
@@ -1117,6 +1196,7 @@ async fn test_birthday_timestamp_over_chain() {
     test_interface.rpc_service_state.set_tip_info_response(TipInfoResponse {
         metadata: Some(chain_metadata),
         is_synced: true,
+        full_outputs_from_height: 0,
     });
 
     // birthday duration from unix epoch should be at least the genesis block timestamp
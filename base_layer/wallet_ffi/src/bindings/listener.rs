@@ -0,0 +1,125 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use log::*;
+use minotari_wallet::{
+    transaction_service::handle::TransactionEvent,
+    utxo_scanner_service::handle::UtxoScannerEvent,
+    WalletSqlite,
+};
+
+use crate::bindings::{ConnectivityStatus, TransactionUpdate, WalletBalance};
+
+const LOG_TARGET: &str = "wallet_ffi::bindings::listener";
+
+/// Implemented by the client application to receive wallet events. Methods are called from a wallet runtime thread
+/// and should return promptly.
+#[uniffi::export(callback_interface)]
+pub trait WalletListener: Send + Sync {
+    fn on_balance_updated(&self, balance: WalletBalance);
+    fn on_transaction_updated(&self, update: TransactionUpdate);
+    fn on_connectivity_changed(&self, status: ConnectivityStatus);
+    fn on_scanning_progress(&self, current_height: u64, tip_height: u64);
+}
+
+/// Forwards wallet service events to the listener until the wallet shuts down.
+pub(crate) async fn forward_events(wallet: WalletSqlite, listener: Box<dyn WalletListener>) {
+    let mut transaction_events = wallet.transaction_service.get_event_stream();
+    let mut output_manager_events = wallet.output_manager_service.get_event_stream();
+    let mut scanner_events = wallet.utxo_scanner_service.clone().get_event_receiver();
+    let mut connectivity_status = wallet.wallet_connectivity.get_connectivity_status_watch();
+    let mut shutdown_signal = wallet.comms.shutdown_signal();
+
+    loop {
+        tokio::select! {
+            result = transaction_events.recv() => {
+                match result {
+                    Ok(event) => {
+                        if let Some(update) = transaction_update(&event) {
+                            listener.on_transaction_updated(update);
+                        }
+                        refresh_balance(&wallet, listener.as_ref()).await;
+                    },
+                    Err(e) => debug!(target: LOG_TARGET, "Transaction event stream lagged: {}", e),
+                }
+            },
+            result = output_manager_events.recv() => {
+                match result {
+                    Ok(_) => refresh_balance(&wallet, listener.as_ref()).await,
+                    Err(e) => debug!(target: LOG_TARGET, "Output manager event stream lagged: {}", e),
+                }
+            },
+            result = scanner_events.recv() => {
+                if let Ok(UtxoScannerEvent::Progress { current_height, tip_height }) = result {
+                    listener.on_scanning_progress(current_height, tip_height);
+                }
+            },
+            Ok(_) = connectivity_status.changed() => {
+                let status = *connectivity_status.borrow();
+                listener.on_connectivity_changed(status.into());
+            },
+            _ = shutdown_signal.wait() => {
+                info!(target: LOG_TARGET, "Wallet listener shutting down because the shutdown signal was received");
+                break;
+            },
+        }
+    }
+}
+
+async fn refresh_balance(wallet: &WalletSqlite, listener: &dyn WalletListener) {
+    match wallet.output_manager_service.clone().get_balance().await {
+        Ok(balance) => listener.on_balance_updated(balance.into()),
+        Err(e) => warn!(target: LOG_TARGET, "Could not fetch balance for listener: {}", e),
+    }
+}
+
+fn transaction_update(event: &TransactionEvent) -> Option<TransactionUpdate> {
+    let update = match event {
+        TransactionEvent::ReceivedTransaction(tx_id) => TransactionUpdate::Received { tx_id: tx_id.as_u64() },
+        TransactionEvent::ReceivedTransactionReply(tx_id) => TransactionUpdate::ReplyReceived { tx_id: tx_id.as_u64() },
+        TransactionEvent::ReceivedFinalizedTransaction(tx_id) => TransactionUpdate::Finalized { tx_id: tx_id.as_u64() },
+        TransactionEvent::TransactionBroadcast(tx_id) => TransactionUpdate::Broadcast { tx_id: tx_id.as_u64() },
+        TransactionEvent::TransactionMinedUnconfirmed {
+            tx_id,
+            num_confirmations,
+            ..
+        } |
+        TransactionEvent::DetectedTransactionUnconfirmed {
+            tx_id,
+            num_confirmations,
+            ..
+        } => TransactionUpdate::MinedUnconfirmed {
+            tx_id: tx_id.as_u64(),
+            confirmations: *num_confirmations,
+        },
+        TransactionEvent::TransactionMined { tx_id, .. } |
+        TransactionEvent::DetectedTransactionConfirmed { tx_id, .. } => {
+            TransactionUpdate::Mined { tx_id: tx_id.as_u64() }
+        },
+        TransactionEvent::TransactionCancelled(tx_id, reason) => TransactionUpdate::Cancelled {
+            tx_id: tx_id.as_u64(),
+            reason: format!("{:?}", reason),
+        },
+        _ => return None,
+    };
+    Some(update)
+}
@@ -0,0 +1,347 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! UniFFI bindings for the wallet.
+//!
+//! This is a safe, object based interface over the same wallet that the C interface in this crate exposes. Kotlin,
+//! Swift and Python bindings are generated from it with the `uniffi-bindgen` binary, see the crate README. Only
+//! compiled with the `uniffi` feature.
+
+use std::{str::FromStr, sync::Arc};
+
+use log::*;
+use minotari_wallet::{
+    base_node_service::config::BaseNodeServiceConfig,
+    error::WalletError,
+    output_manager_service::{storage::database::OutputManagerDatabase, UtxoSelectionCriteria},
+    storage::{database::WalletDatabase, sqlite_utilities::initialize_sqlite_database_backends},
+    transaction_service::config::TransactionServiceConfig,
+    Wallet,
+    WalletConfig,
+    WalletSqlite,
+};
+use tari_common::{
+    configuration::{DnsNameServerList, StringList},
+    network_check::set_network_if_choice_valid,
+};
+use tari_common_types::{tari_address::TariAddress, transaction::TxId, types::PublicKey, wallet_types::WalletType};
+use tari_comms::multiaddr::Multiaddr;
+use tari_core::{
+    consensus::ConsensusManager,
+    transactions::{
+        tari_amount::MicroMinotari,
+        transaction_components::{encrypted_data::PaymentId, OutputFeatures},
+        CryptoFactories,
+    },
+};
+use tari_crypto::tari_utilities::Hidden;
+use tari_key_manager::{cipher_seed::CipherSeed, mnemonic::MnemonicLanguage, SeedWords};
+use tari_p2p::{auto_update::AutoUpdateConfig, Network, PeerSeedsConfig, TransportType};
+use tari_shutdown::Shutdown;
+use tari_utilities::{hex::Hex, SafePassword};
+use tokio::{runtime::Runtime, sync::Mutex};
+
+pub use self::{error::WalletBindingError, listener::WalletListener, types::*};
+use crate::{
+    error::{InterfaceError, LibWalletError},
+    wallet_setup::{load_node_identity, set_random_seed_base_node},
+};
+
+mod error;
+mod listener;
+mod types;
+
+const LOG_TARGET: &str = "wallet_ffi::bindings";
+
+/// A running wallet. The wallet keeps its own tokio runtime and is shut down when `shutdown` is called or the object
+/// is dropped.
+#[derive(uniffi::Object)]
+pub struct MobileWallet {
+    wallet: WalletSqlite,
+    runtime: Runtime,
+    shutdown: Mutex<Shutdown>,
+}
+
+#[uniffi::export]
+impl MobileWallet {
+    /// Opens the wallet described by `options`, creating it if the database does not exist, and starts forwarding
+    /// wallet events to `listener`.
+    #[uniffi::constructor]
+    #[allow(clippy::too_many_lines)]
+    pub fn open(options: WalletOptions, listener: Box<dyn WalletListener>) -> Result<Arc<Self>, WalletBindingError> {
+        let network =
+            Network::from_str(&options.network).map_err(|_| InterfaceError::InvalidArgument("network".to_string()))?;
+        set_network_if_choice_valid(network).map_err(|e| InterfaceError::InvalidArgument(e.to_string()))?;
+        info!(target: LOG_TARGET, "network set to {}", network);
+
+        let recovery_seed = match options.seed_words {
+            Some(ref words) => {
+                let seed_words = SeedWords::new(words.iter().cloned().map(Hidden::hide).collect());
+                let seed_passphrase = options.seed_passphrase.clone().map(SafePassword::from);
+                let seed = CipherSeed::from_mnemonic(&seed_words, seed_passphrase)
+                    .map_err(|e| LibWalletError::from(WalletError::KeyManagerError(e)))?;
+                Some(seed)
+            },
+            None => None,
+        };
+
+        let dns_seed_name_servers = if options.dns_seed_name_servers.is_empty() {
+            PeerSeedsConfig::default().dns_seed_name_servers
+        } else {
+            DnsNameServerList::from_str(&options.dns_seed_name_servers.join(","))
+                .map_err(|e| InterfaceError::InvalidArgument(format!("dns_seed_name_servers: {}", e)))?
+        };
+
+        let mut comms_config = options.comms_config()?;
+        let runtime = Runtime::new().map_err(|e| InterfaceError::TokioError(e.to_string()))?;
+
+        let sql_database_path = comms_config
+            .datastore_path
+            .join(&comms_config.peer_database_name)
+            .with_extension("sqlite3");
+        let (wallet_backend, transaction_backend, output_manager_backend, contacts_backend, key_manager_backend) =
+            initialize_sqlite_database_backends(sql_database_path, SafePassword::from(options.passphrase), 16)
+                .map_err(|e| LibWalletError::from(WalletError::WalletStorageError(e)))?;
+        let wallet_database = WalletDatabase::new(wallet_backend);
+        let output_manager_database = OutputManagerDatabase::new(output_manager_backend.clone());
+
+        if let TransportType::Tor = comms_config.transport.transport_type {
+            comms_config.transport.tor.identity = wallet_database.get_tor_id().ok().flatten();
+        }
+
+        let (master_seed, node_identity) = load_node_identity(recovery_seed, &wallet_database, &comms_config)
+            .map_err(|e| LibWalletError::from(WalletError::WalletStorageError(e)))?;
+
+        let wallet_config = WalletConfig {
+            override_from: None,
+            transaction_service_config: TransactionServiceConfig {
+                direct_send_timeout: comms_config.dht.discovery_request_timeout,
+                ..Default::default()
+            },
+            p2p: comms_config,
+            base_node_service_config: BaseNodeServiceConfig { ..Default::default() },
+            network,
+            ..Default::default()
+        };
+        let peer_seeds = PeerSeedsConfig {
+            dns_seed_name_servers,
+            dns_seeds_use_dnssec: options.use_dns_sec,
+            dns_seeds: StringList::from(options.dns_seeds),
+            ..Default::default()
+        };
+        let consensus_manager = ConsensusManager::builder(network)
+            .build()
+            .map_err(|e| InterfaceError::InternalError(e.to_string()))?;
+
+        let shutdown = Shutdown::new();
+        let user_agent = format!("tari/wallet_ffi/{}", env!("CARGO_PKG_VERSION"));
+        let mut wallet = runtime
+            .block_on(Wallet::start(
+                wallet_config,
+                peer_seeds,
+                AutoUpdateConfig::default(),
+                node_identity,
+                consensus_manager,
+                CryptoFactories::default(),
+                wallet_database,
+                output_manager_database,
+                transaction_backend,
+                output_manager_backend,
+                contacts_backend,
+                key_manager_backend,
+                shutdown.to_signal(),
+                master_seed,
+                Some(WalletType::default()),
+                None,
+                user_agent,
+            ))
+            .map_err(LibWalletError::from)?;
+
+        runtime
+            .block_on(set_random_seed_base_node(&mut wallet))
+            .map_err(LibWalletError::from)?;
+        runtime.spawn(listener::forward_events(wallet.clone(), listener));
+
+        Ok(Arc::new(Self {
+            wallet,
+            runtime,
+            shutdown: Mutex::new(shutdown),
+        }))
+    }
+
+    /// The base58 address other wallets use to send interactive transactions to this wallet
+    pub fn interactive_address(&self) -> Result<String, WalletBindingError> {
+        let address = self
+            .runtime
+            .block_on(self.wallet.get_wallet_interactive_address())
+            .map_err(LibWalletError::from)?;
+        Ok(address.to_base58())
+    }
+
+    /// The base58 address other wallets use to send one-sided transactions to this wallet
+    pub fn one_sided_address(&self) -> Result<String, WalletBindingError> {
+        let address = self
+            .runtime
+            .block_on(self.wallet.get_wallet_one_sided_address())
+            .map_err(LibWalletError::from)?;
+        Ok(address.to_base58())
+    }
+
+    pub fn balance(&self) -> Result<WalletBalance, WalletBindingError> {
+        let balance = self
+            .runtime
+            .block_on(self.wallet.output_manager_service.clone().get_balance())
+            .map_err(|e| LibWalletError::from(WalletError::OutputManagerError(e)))?;
+        Ok(balance.into())
+    }
+
+    /// The English seed words of the wallet's master seed
+    pub fn seed_words(&self) -> Result<Vec<String>, WalletBindingError> {
+        let seed_words = self
+            .wallet
+            .get_seed_words(&MnemonicLanguage::English)
+            .map_err(LibWalletError::from)?;
+        (0..seed_words.len())
+            .map(|i| {
+                seed_words
+                    .get_word(i)
+                    .cloned()
+                    .map_err(|e| LibWalletError::from(e).into())
+            })
+            .collect()
+    }
+
+    /// Sends `amount` µT to the base58 or emoji `destination` and returns the transaction id. A one-sided transaction
+    /// is sent to the recipient's stealth address, in which case an optional payment id may be attached.
+    pub fn send_transaction(
+        &self,
+        destination: String,
+        amount: u64,
+        fee_per_gram: u64,
+        message: String,
+        one_sided: bool,
+        payment_id: Option<String>,
+    ) -> Result<u64, WalletBindingError> {
+        let destination = TariAddress::from_str(&destination).map_err(LibWalletError::from)?;
+        let mut transaction_service = self.wallet.transaction_service.clone();
+        let result = if one_sided {
+            let payment_id = payment_id.map_or(PaymentId::Empty, |id| PaymentId::Open(id.into_bytes()));
+            self.runtime
+                .block_on(transaction_service.send_one_sided_to_stealth_address_transaction(
+                    destination,
+                    MicroMinotari::from(amount),
+                    UtxoSelectionCriteria::default(),
+                    OutputFeatures::default(),
+                    MicroMinotari::from(fee_per_gram),
+                    message,
+                    payment_id,
+                ))
+        } else {
+            self.runtime.block_on(transaction_service.send_transaction(
+                destination,
+                MicroMinotari::from(amount),
+                UtxoSelectionCriteria::default(),
+                OutputFeatures::default(),
+                MicroMinotari::from(fee_per_gram),
+                message,
+            ))
+        };
+        let tx_id = result.map_err(|e| LibWalletError::from(WalletError::TransactionServiceError(e)))?;
+        Ok(tx_id.as_u64())
+    }
+
+    pub fn cancel_pending_transaction(&self, tx_id: u64) -> Result<(), WalletBindingError> {
+        self.runtime
+            .block_on(
+                self.wallet
+                    .transaction_service
+                    .clone()
+                    .cancel_transaction(TxId::from(tx_id)),
+            )
+            .map_err(|e| LibWalletError::from(WalletError::TransactionServiceError(e)))?;
+        Ok(())
+    }
+
+    /// All completed transactions, newest first
+    pub fn completed_transactions(&self) -> Result<Vec<WalletTransaction>, WalletBindingError> {
+        let transactions = self
+            .runtime
+            .block_on(self.wallet.transaction_service.clone().get_completed_transactions())
+            .map_err(|e| LibWalletError::from(WalletError::TransactionServiceError(e)))?;
+        let mut transactions = transactions
+            .into_values()
+            .map(WalletTransaction::from)
+            .collect::<Vec<_>>();
+        transactions.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        Ok(transactions)
+    }
+
+    /// Sets the base node the wallet uses, given its hex public key and an optional multiaddr
+    pub fn set_base_node_peer(&self, public_key: String, address: Option<String>) -> Result<(), WalletBindingError> {
+        let public_key = PublicKey::from_hex(&public_key).map_err(LibWalletError::from)?;
+        let address = address
+            .map(|a| Multiaddr::from_str(&a))
+            .transpose()
+            .map_err(LibWalletError::from)?;
+        self.runtime
+            .block_on(self.wallet.clone().set_base_node_peer(public_key, address, None))
+            .map_err(LibWalletError::from)?;
+        Ok(())
+    }
+
+    /// Starts validating the wallet's outputs against the base node, returning the request key
+    pub fn start_txo_validation(&self) -> Result<u64, WalletBindingError> {
+        let request_key = self
+            .runtime
+            .block_on(self.wallet.output_manager_service.clone().validate_txos())
+            .map_err(|e| LibWalletError::from(WalletError::OutputManagerError(e)))?;
+        Ok(request_key)
+    }
+
+    /// Starts validating the wallet's transactions against the base node, returning the request key
+    pub fn start_transaction_validation(&self) -> Result<u64, WalletBindingError> {
+        let operation_id = self
+            .runtime
+            .block_on(self.wallet.transaction_service.clone().validate_transactions())
+            .map_err(|e| LibWalletError::from(WalletError::TransactionServiceError(e)))?;
+        Ok(operation_id.as_u64())
+    }
+
+    /// Shuts the wallet down and waits for its services to stop. The wallet can not be used afterwards.
+    pub fn shutdown(&self) {
+        self.runtime.block_on(async {
+            let mut shutdown = self.shutdown.lock().await;
+            if shutdown.is_triggered() {
+                return;
+            }
+            shutdown.trigger();
+            self.wallet.clone().wait_until_shutdown().await;
+        });
+    }
+}
+
+impl Drop for MobileWallet {
+    fn drop(&mut self) {
+        debug!(target: LOG_TARGET, "Mobile wallet dropped, shutting down");
+        self.shutdown();
+    }
+}
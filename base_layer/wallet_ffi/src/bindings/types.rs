@@ -0,0 +1,260 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{convert::TryFrom, num::NonZeroU16, path::PathBuf, str::FromStr, time::Duration};
+
+use minotari_wallet::{
+    connectivity_service::OnlineStatus,
+    output_manager_service::service::Balance,
+    transaction_service::storage::models::CompletedTransaction,
+};
+use tari_comms::{multiaddr::Multiaddr, net_address::MultiaddrRangeList, transports::MemoryTransport};
+use tari_crypto::tari_utilities::hex;
+use tari_p2p::{
+    transport::MemoryTransportConfig,
+    SocksAuthentication,
+    TcpTransportConfig,
+    TorControlAuthentication,
+    TorTransportConfig,
+    TransportConfig,
+    TransportType,
+};
+
+use crate::{bindings::WalletBindingError, error::InterfaceError, wallet_setup::mobile_comms_config, TariCommsConfig};
+
+/// Configuration needed to open (or create) a wallet.
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct WalletOptions {
+    /// Directory holding the wallet and peer databases
+    pub datastore_path: String,
+    /// Base name of the wallet and peer databases
+    pub database_name: String,
+    /// Passphrase used to encrypt the wallet database
+    pub passphrase: String,
+    /// Network name, e.g. "mainnet" or "nextnet"
+    pub network: String,
+    pub transport: WalletTransport,
+    /// Public address advertised to peers; ignored for the Tor transport
+    pub public_address: String,
+    pub discovery_timeout_secs: u64,
+    pub saf_message_duration_secs: u64,
+    pub dns_seeds: Vec<String>,
+    /// Name servers used to resolve the DNS seeds, e.g. "1.1.1.1:853/cloudflare-dns.com"
+    pub dns_seed_name_servers: Vec<String>,
+    pub use_dns_sec: bool,
+    /// Seed words to recover the wallet from. Only used when the wallet database does not exist yet
+    pub seed_words: Option<Vec<String>>,
+    pub seed_passphrase: Option<String>,
+}
+
+#[derive(Debug, Clone, uniffi::Enum)]
+pub enum WalletTransport {
+    Memory,
+    Tcp {
+        listener_address: String,
+    },
+    Tor {
+        control_address: String,
+        /// Hex encoded tor control cookie, if cookie authentication is used
+        control_cookie: Option<String>,
+        onion_port: u16,
+        socks_username: Option<String>,
+        socks_password: Option<String>,
+        proxy_bypass_for_outbound_tcp: bool,
+    },
+}
+
+impl TryFrom<WalletTransport> for TransportConfig {
+    type Error = WalletBindingError;
+
+    fn try_from(transport: WalletTransport) -> Result<Self, Self::Error> {
+        match transport {
+            WalletTransport::Memory => {
+                let port = MemoryTransport::acquire_next_memsocket_port();
+                let listener_address: Multiaddr = format!("/memory/{}", port)
+                    .parse()
+                    .expect("Should be able to create memory address");
+                Ok(TransportConfig {
+                    transport_type: TransportType::Memory,
+                    memory: MemoryTransportConfig { listener_address },
+                    ..Default::default()
+                })
+            },
+            WalletTransport::Tcp { listener_address } => Ok(TransportConfig {
+                transport_type: TransportType::Tcp,
+                tcp: TcpTransportConfig {
+                    listener_address: parse_multiaddr(&listener_address, "listener_address")?,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+            WalletTransport::Tor {
+                control_address,
+                control_cookie,
+                onion_port,
+                socks_username,
+                socks_password,
+                proxy_bypass_for_outbound_tcp,
+            } => {
+                let control_auth = match control_cookie {
+                    Some(cookie) => {
+                        hex::from_hex(&cookie)
+                            .map_err(|_| InterfaceError::InvalidArgument("control_cookie".to_string()))?;
+                        TorControlAuthentication::hex(cookie)
+                    },
+                    None => TorControlAuthentication::None,
+                };
+                let socks_auth = match (socks_username, socks_password) {
+                    (Some(username), Some(password)) => SocksAuthentication::UsernamePassword { username, password },
+                    _ => SocksAuthentication::None,
+                };
+                let onion_port = NonZeroU16::new(onion_port)
+                    .ok_or_else(|| InterfaceError::InvalidArgument("onion_port must be greater than 0".to_string()))?;
+                Ok(TransportConfig {
+                    transport_type: TransportType::Tor,
+                    tor: TorTransportConfig {
+                        control_address: parse_multiaddr(&control_address, "control_address")?,
+                        control_auth,
+                        // The wallet will populate this from the db
+                        identity: None,
+                        onion_port,
+                        socks_auth,
+                        proxy_bypass_for_outbound_tcp,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+            },
+        }
+    }
+}
+
+impl WalletOptions {
+    pub(crate) fn comms_config(&self) -> Result<TariCommsConfig, WalletBindingError> {
+        let transport = TransportConfig::try_from(self.transport.clone())?;
+        let public_address = if transport.transport_type == TransportType::Tor && self.public_address.is_empty() {
+            Multiaddr::empty()
+        } else {
+            parse_multiaddr(&self.public_address, "public_address")?
+        };
+        Ok(mobile_comms_config(
+            public_address,
+            transport,
+            self.database_name.clone(),
+            PathBuf::from(&self.datastore_path),
+            Duration::from_secs(self.discovery_timeout_secs),
+            Duration::from_secs(self.saf_message_duration_secs),
+            MultiaddrRangeList::from(vec![]),
+        ))
+    }
+}
+
+fn parse_multiaddr(value: &str, field: &str) -> Result<Multiaddr, WalletBindingError> {
+    Multiaddr::from_str(value).map_err(|_| InterfaceError::InvalidArgument(field.to_string()).into())
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct WalletBalance {
+    pub available: u64,
+    /// Amount of the available balance that is time-locked, if known
+    pub time_locked: Option<u64>,
+    pub pending_incoming: u64,
+    pub pending_outgoing: u64,
+}
+
+impl From<Balance> for WalletBalance {
+    fn from(balance: Balance) -> Self {
+        Self {
+            available: balance.available_balance.as_u64(),
+            time_locked: balance.time_locked_balance.map(|v| v.as_u64()),
+            pending_incoming: balance.pending_incoming_balance.as_u64(),
+            pending_outgoing: balance.pending_outgoing_balance.as_u64(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct WalletTransaction {
+    pub tx_id: u64,
+    pub source_address: String,
+    pub destination_address: String,
+    pub amount: u64,
+    pub fee: u64,
+    /// Display name of the `TransactionStatus`
+    pub status: String,
+    /// Display name of the `TransactionDirection`
+    pub direction: String,
+    pub message: String,
+    /// Unix timestamp in seconds
+    pub timestamp: i64,
+    pub mined_height: Option<u64>,
+    pub confirmations: Option<u64>,
+    pub is_cancelled: bool,
+}
+
+impl From<CompletedTransaction> for WalletTransaction {
+    fn from(tx: CompletedTransaction) -> Self {
+        Self {
+            tx_id: tx.tx_id.as_u64(),
+            source_address: tx.source_address.to_base58(),
+            destination_address: tx.destination_address.to_base58(),
+            amount: tx.amount.as_u64(),
+            fee: tx.fee.as_u64(),
+            status: tx.status.to_string(),
+            direction: tx.direction.to_string(),
+            message: tx.message,
+            timestamp: tx.timestamp.timestamp(),
+            mined_height: tx.mined_height,
+            confirmations: tx.confirmations,
+            is_cancelled: tx.cancelled.is_some(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum ConnectivityStatus {
+    Connecting,
+    Online,
+    Offline,
+}
+
+impl From<OnlineStatus> for ConnectivityStatus {
+    fn from(status: OnlineStatus) -> Self {
+        match status {
+            OnlineStatus::Connecting => ConnectivityStatus::Connecting,
+            OnlineStatus::Online => ConnectivityStatus::Online,
+            OnlineStatus::Offline => ConnectivityStatus::Offline,
+        }
+    }
+}
+
+/// A change to a transaction, delivered to `WalletListener::on_transaction_updated`
+#[derive(Debug, Clone, uniffi::Enum)]
+pub enum TransactionUpdate {
+    Received { tx_id: u64 },
+    ReplyReceived { tx_id: u64 },
+    Finalized { tx_id: u64 },
+    Broadcast { tx_id: u64 },
+    MinedUnconfirmed { tx_id: u64, confirmations: u64 },
+    Mined { tx_id: u64 },
+    Cancelled { tx_id: u64, reason: String },
+}
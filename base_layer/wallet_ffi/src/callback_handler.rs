@@ -34,8 +34,23 @@
 //! `callback_base_node_sync_complete` - This is called when a Base Node Sync process is completed or times out. The
 //! request_key is used to identify which request this callback references and a result of true means it was successful
 //! and false that the process timed out and new one will be started
+//!
+//! The [ProgressCallbacks] are optional and can be registered, replaced or cleared at any time while the wallet is
+//! running:
+//!
+//! `scanning_progress` - This is called as the UTXO scanner progresses with the current scanned height and the chain
+//! tip height
+//!
+//! `base_node_connection` - This is called when the UTXO scanner's connection to its base node changes state
+//!
+//! `transaction_validation_progress` - This is called after each batch of unconfirmed transactions is checked against
+//! the base node with the request_key, the number of transactions checked so far and the total to check
 
-use std::{ffi::c_void, ops::Deref, sync::Arc};
+use std::{
+    ffi::c_void,
+    ops::Deref,
+    sync::{Arc, RwLock},
+};
 
 use log::*;
 use minotari_wallet::{
@@ -72,6 +87,30 @@ unsafe impl Send for Context {}
 
 const LOG_TARGET: &str = "wallet::transaction_service::callback_handler";
 
+/// States reported through the `base_node_connection` progress callback, along with the meaning of the accompanying
+/// value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BaseNodeConnectionState {
+    /// Value is unused
+    Connecting = 0,
+    /// Value is the connection latency in milliseconds
+    Connected = 1,
+    /// Value is the number of retries so far
+    ConnectionFailed = 2,
+    /// Value is the number of retries so far
+    ScanningRoundFailed = 3,
+}
+
+/// Optional progress callbacks that can be registered after the wallet has started
+#[derive(Clone, Copy, Default)]
+pub struct ProgressCallbacks {
+    pub scanning_progress: Option<unsafe extern "C" fn(context: *mut c_void, u64, u64)>,
+    pub base_node_connection: Option<unsafe extern "C" fn(context: *mut c_void, u64, u64)>,
+    pub transaction_validation_progress: Option<unsafe extern "C" fn(context: *mut c_void, u64, u64, u64)>,
+}
+
+pub type SharedProgressCallbacks = Arc<RwLock<ProgressCallbacks>>;
+
 pub struct CallbackHandler<TBackend>
 where TBackend: TransactionBackend + 'static
 {
@@ -106,6 +145,7 @@ where TBackend: TransactionBackend + 'static
     balance_cache: Balance,
     connectivity_status_watch: watch::Receiver<OnlineStatus>,
     contacts_liveness_events: broadcast::Receiver<Arc<ContactsLivenessEvent>>,
+    progress_callbacks: SharedProgressCallbacks,
 }
 
 impl<TBackend> CallbackHandler<TBackend>
@@ -126,6 +166,7 @@ where TBackend: TransactionBackend + 'static
         comms_address: TariAddress,
         connectivity_status_watch: watch::Receiver<OnlineStatus>,
         contacts_liveness_events: broadcast::Receiver<Arc<ContactsLivenessEvent>>,
+        progress_callbacks: SharedProgressCallbacks,
         callback_received_transaction: unsafe extern "C" fn(context: *mut c_void, *mut InboundTransaction),
         callback_received_transaction_reply: unsafe extern "C" fn(context: *mut c_void, *mut CompletedTransaction),
         callback_received_finalized_transaction: unsafe extern "C" fn(context: *mut c_void, *mut CompletedTransaction),
@@ -254,6 +295,7 @@ where TBackend: TransactionBackend + 'static
             balance_cache: Balance::zero(),
             connectivity_status_watch,
             contacts_liveness_events,
+            progress_callbacks,
         }
     }
 
@@ -316,6 +358,9 @@ where TBackend: TransactionBackend + 'static
                                 TransactionEvent::TransactionValidationStateChanged(_request_key)  => {
                                     self.trigger_balance_refresh().await;
                                 },
+                                TransactionEvent::TransactionValidationProgress{operation_id, checked, total} => {
+                                    self.transaction_validation_progress_event(operation_id.as_u64(), checked, total);
+                                },
                                 TransactionEvent::TransactionValidationCompleted(request_key)  => {
                                     self.transaction_validation_complete_event(request_key.as_u64(), 0);
                                     self.trigger_balance_refresh().await;
@@ -366,17 +411,41 @@ where TBackend: TransactionBackend + 'static
                         Ok(event) => {
                             match event {
                                 UtxoScannerEvent::Progress {
-                                    current_height,..
+                                    current_height,
+                                    tip_height,
                                 }=> {
                                     self.scanned_height_changed(current_height);
+                                    self.scanning_progress_event(current_height, tip_height);
                                 }
                                 UtxoScannerEvent::Completed {
                                     final_height,
                                     ..
                                 }=> {
-                                self.scanned_height_changed(final_height);
+                                    self.scanned_height_changed(final_height);
+                                    self.scanning_progress_event(final_height, final_height);
+                                },
+                                UtxoScannerEvent::ConnectingToBaseNode(_) => {
+                                    self.base_node_connection_event(BaseNodeConnectionState::Connecting, 0);
+                                },
+                                UtxoScannerEvent::ConnectedToBaseNode(_, latency) => {
+                                    let latency = u64::try_from(latency.as_millis()).unwrap_or(u64::MAX);
+                                    self.base_node_connection_event(BaseNodeConnectionState::Connected, latency);
+                                },
+                                UtxoScannerEvent::ConnectionFailedToBaseNode { num_retries, .. } => {
+                                    let num_retries = u64::try_from(num_retries).unwrap_or(u64::MAX);
+                                    self.base_node_connection_event(
+                                        BaseNodeConnectionState::ConnectionFailed,
+                                        num_retries,
+                                    );
+                                },
+                                UtxoScannerEvent::ScanningRoundFailed { num_retries, .. } => {
+                                    let num_retries = u64::try_from(num_retries).unwrap_or(u64::MAX);
+                                    self.base_node_connection_event(
+                                        BaseNodeConnectionState::ScanningRoundFailed,
+                                        num_retries,
+                                    );
                                 },
-                                _ => {}
+                                UtxoScannerEvent::ScanningFailed => {}
                             }
                         },
                         Err(e) => {
@@ -415,6 +484,7 @@ where TBackend: TransactionBackend + 'static
                                 BaseNodeEvent::NewBlockDetected(_hash, _new_block_number) => {
                                     //
                                 },
+                                BaseNodeEvent::BaseNodeFailover(..) | BaseNodeEvent::TipDivergesFromBeacons(..) => {},
                             }
                         },
                         Err(_e) => error!(target: LOG_TARGET, "failed to receive base node state event"),
@@ -431,7 +501,7 @@ where TBackend: TransactionBackend + 'static
                                     );
                                     self.trigger_contacts_refresh(data.deref().clone());
                                 }
-                                ContactsLivenessEvent::NetworkSilence => {},
+                                ContactsLivenessEvent::StatusChanged(_) | ContactsLivenessEvent::NetworkSilence => {},
                             }
                         }
                         Err(broadcast::error::RecvError::Lagged(n)) => {
@@ -712,6 +782,46 @@ where TBackend: TransactionBackend + 'static
         }
     }
 
+    fn progress_callbacks(&self) -> ProgressCallbacks {
+        self.progress_callbacks.read().map(|c| *c).unwrap_or_default()
+    }
+
+    fn scanning_progress_event(&mut self, current_height: u64, tip_height: u64) {
+        if let Some(callback) = self.progress_callbacks().scanning_progress {
+            debug!(target: LOG_TARGET, "Calling Scanning Progress callback function");
+            unsafe {
+                (callback)(self.context.0, current_height, tip_height);
+            }
+        }
+    }
+
+    fn base_node_connection_event(&mut self, state: BaseNodeConnectionState, value: u64) {
+        if let Some(callback) = self.progress_callbacks().base_node_connection {
+            debug!(
+                target: LOG_TARGET,
+                "Calling Base Node Connection callback function with state {:?}", state
+            );
+            unsafe {
+                (callback)(self.context.0, state as u64, value);
+            }
+        }
+    }
+
+    fn transaction_validation_progress_event(&mut self, request_key: u64, checked: u64, total: u64) {
+        if let Some(callback) = self.progress_callbacks().transaction_validation_progress {
+            debug!(
+                target: LOG_TARGET,
+                "Calling Transaction Validation Progress callback function for Request Key: {} ({}/{})",
+                request_key,
+                checked,
+                total
+            );
+            unsafe {
+                (callback)(self.context.0, request_key, checked, total);
+            }
+        }
+    }
+
     // casting here is okay as we dont care about the super high latency
     #[allow(clippy::cast_possible_truncation)]
     fn base_node_state_changed(&mut self, state: BaseNodeState) {
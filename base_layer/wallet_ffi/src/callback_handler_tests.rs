@@ -6,7 +6,7 @@ mod test {
     use std::{
         ffi::c_void,
         mem::size_of,
-        sync::{Arc, Mutex},
+        sync::{Arc, Mutex, RwLock},
         thread,
         time::{Duration, SystemTime},
     };
@@ -63,7 +63,7 @@ mod test {
     };
 
     use crate::{
-        callback_handler::{CallbackHandler, Context},
+        callback_handler::{CallbackHandler, Context, ProgressCallbacks},
         ffi_basenode_state::TariBaseNodeState,
         output_manager_service_mock::MockOutputManagerService,
     };
@@ -96,6 +96,9 @@ mod test {
         pub connectivity_status_callback_called: u64,
         pub wallet_scanner_height_callback_called: u64,
         pub base_node_state_changed_callback_invoked: bool,
+        pub scanning_progress_callback_called: u64,
+        pub base_node_connection_callback_called: u64,
+        pub transaction_validation_progress_callback_called: u64,
     }
 
     impl CallbackState {
@@ -126,6 +129,9 @@ mod test {
                 connectivity_status_callback_called: 0,
                 wallet_scanner_height_callback_called: 0,
                 base_node_state_changed_callback_invoked: false,
+                scanning_progress_callback_called: 0,
+                base_node_connection_callback_called: 0,
+                transaction_validation_progress_callback_called: 0,
             }
         }
     }
@@ -291,6 +297,29 @@ mod test {
         drop(Box::from_raw(state))
     }
 
+    unsafe extern "C" fn scanning_progress_callback(_context: *mut c_void, current_height: u64, tip_height: u64) {
+        let mut lock = CALLBACK_STATE.lock().unwrap();
+        lock.scanning_progress_callback_called += current_height + tip_height;
+        drop(lock);
+    }
+
+    unsafe extern "C" fn base_node_connection_callback(_context: *mut c_void, state: u64, value: u64) {
+        let mut lock = CALLBACK_STATE.lock().unwrap();
+        lock.base_node_connection_callback_called += state + value + 1;
+        drop(lock);
+    }
+
+    unsafe extern "C" fn transaction_validation_progress_callback(
+        _context: *mut c_void,
+        request_key: u64,
+        checked: u64,
+        total: u64,
+    ) {
+        let mut lock = CALLBACK_STATE.lock().unwrap();
+        lock.transaction_validation_progress_callback_called += request_key + checked + total;
+        drop(lock);
+    }
+
     #[test]
     // casting casting is okay in tests
     #[allow(clippy::cast_possible_truncation)]
@@ -516,6 +545,11 @@ mod test {
             comms_address,
             connectivity_rx,
             contacts_liveness_events,
+            Arc::new(RwLock::new(ProgressCallbacks {
+                scanning_progress: Some(scanning_progress_callback),
+                base_node_connection: Some(base_node_connection_callback),
+                transaction_validation_progress: Some(transaction_validation_progress_callback),
+            })),
             received_tx_callback,
             received_tx_reply_callback,
             received_tx_finalized_callback,
@@ -787,6 +821,13 @@ mod test {
         transaction_event_sender
             .send(Arc::new(TransactionEvent::TransactionValidationFailed(0u64.into(), 3)))
             .unwrap();
+        transaction_event_sender
+            .send(Arc::new(TransactionEvent::TransactionValidationProgress {
+                operation_id: 1u64.into(),
+                checked: 5,
+                total: 10,
+            }))
+            .unwrap();
 
         balance.pending_incoming_balance += faux_unconfirmed_tx.amount;
         mock_output_manager_service_state.set_balance(balance.clone());
@@ -877,6 +918,15 @@ mod test {
 
         thread::sleep(Duration::from_secs(10));
 
+        utxo_scanner_events_sender
+            .send(UtxoScannerEvent::ConnectingToBaseNode(NodeId::new()))
+            .unwrap();
+        utxo_scanner_events_sender
+            .send(UtxoScannerEvent::ConnectedToBaseNode(
+                NodeId::new(),
+                Duration::from_millis(25),
+            ))
+            .unwrap();
         utxo_scanner_events_sender
             .send(UtxoScannerEvent::Progress {
                 current_height: 500,
@@ -891,6 +941,7 @@ mod test {
                 num_recovered: 0,
                 value_recovered: 0.into(),
                 time_taken: Duration::from_secs(0),
+                recovered_by_branch: Default::default(),
             })
             .unwrap();
 
@@ -920,6 +971,9 @@ mod test {
         assert_eq!(lock.callback_transaction_validation_complete, 13);
         assert_eq!(lock.connectivity_status_callback_called, 7);
         assert_eq!(lock.wallet_scanner_height_callback_called, 1100);
+        assert_eq!(lock.scanning_progress_callback_called, 2300);
+        assert_eq!(lock.base_node_connection_callback_called, 28);
+        assert_eq!(lock.transaction_validation_progress_callback_called, 16);
 
         drop(lock);
     }
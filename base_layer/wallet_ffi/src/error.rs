@@ -23,7 +23,10 @@ use log::*;
 use minotari_wallet::{
     error::{WalletError, WalletStorageError},
     output_manager_service::error::{OutputManagerError, OutputManagerStorageError},
-    transaction_service::error::{TransactionServiceError, TransactionStorageError},
+    transaction_service::{
+        error::{TransactionServiceError, TransactionStorageError},
+        spending_policy::SpendingPolicyViolation,
+    },
 };
 use tari_common_types::tari_address::TariAddressError;
 use tari_comms::multiaddr;
@@ -241,6 +244,30 @@ impl From<WalletError> for LibWalletError {
                 code: 212,
                 message: format!("{:?}", w),
             },
+            WalletError::TransactionServiceError(TransactionServiceError::SpendingPolicyViolation(
+                SpendingPolicyViolation::ExceedsTransactionLimit { .. },
+            )) => Self {
+                code: 213,
+                message: format!("{:?}", w),
+            },
+            WalletError::TransactionServiceError(TransactionServiceError::SpendingPolicyViolation(
+                SpendingPolicyViolation::ExceedsDailyLimit { .. },
+            )) => Self {
+                code: 214,
+                message: format!("{:?}", w),
+            },
+            WalletError::TransactionServiceError(TransactionServiceError::SpendingPolicyViolation(
+                SpendingPolicyViolation::DestinationNotAllowed(_),
+            )) => Self {
+                code: 215,
+                message: format!("{:?}", w),
+            },
+            WalletError::TransactionServiceError(TransactionServiceError::SpendingPolicyViolation(
+                SpendingPolicyViolation::ApprovalRequired { .. } | SpendingPolicyViolation::UnknownApproval(_),
+            )) => Self {
+                code: 216,
+                message: format!("{:?}", w),
+            },
             WalletError::TransactionServiceError(_) => Self {
                 code: 211,
                 message: format!("{:?}", w),
@@ -332,6 +359,10 @@ impl From<WalletError> for LibWalletError {
                 code: 434,
                 message: format!("{:?}", w),
             },
+            WalletError::PushNotificationError(_) => Self {
+                code: 435,
+                message: format!("{:?}", w),
+            },
             // these are general catch errors to try and reduce 999 when we get it with zero additional logging
             WalletError::SetLoggerError(_) => Self {
                 code: 994,
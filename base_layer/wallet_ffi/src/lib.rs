@@ -106,15 +106,15 @@ use minotari_wallet::{
         },
     },
     utxo_scanner_service::{service::UtxoScannerService, RECOVERY_KEY},
-    wallet::{derive_comms_secret_key, read_or_create_master_seed, WalletMessageSigningDomain},
+    wallet::WalletMessageSigningDomain,
     Wallet,
     WalletConfig,
     WalletSqlite,
 };
 use num_traits::FromPrimitive;
-use rand::{prelude::SliceRandom, rngs::OsRng};
+use rand::rngs::OsRng;
 use tari_common::{
-    configuration::{DnsNameServerList, MultiaddrList, StringList},
+    configuration::{DnsNameServerList, StringList},
     network_check::set_network_if_choice_valid,
 };
 use tari_common_types::{
@@ -127,16 +127,8 @@ use tari_common_types::{
 use tari_comms::{
     multiaddr::Multiaddr,
     net_address::{MultiaddrRange, MultiaddrRangeList, IP4_TCP_TEST_ADDR_RANGE},
-    peer_manager::{NodeIdentity, PeerQuery},
+    peer_manager::PeerQuery,
     transports::MemoryTransport,
-    types::CommsPublicKey,
-};
-use tari_comms_dht::{
-    store_forward::SafConfig,
-    DbConnectionUrl,
-    DhtConfig,
-    DhtConnectivityConfig,
-    NetworkDiscoveryConfig,
 };
 use tari_contacts::contacts_service::{handle::ContactsServiceHandle, types::Contact};
 use tari_core::{
@@ -189,12 +181,15 @@ use tokio::runtime::Runtime;
 use zeroize::Zeroize;
 
 use crate::{
-    callback_handler::{CallbackHandler, Context},
+    callback_handler::{CallbackHandler, Context, ProgressCallbacks, SharedProgressCallbacks},
     enums::SeedWordPushResult,
     error::{InterfaceError, TransactionError},
     tasks::recovery_event_monitoring,
+    wallet_setup::{load_node_identity, mobile_comms_config, set_random_seed_base_node},
 };
 
+#[cfg(feature = "uniffi")]
+pub mod bindings;
 mod callback_handler;
 #[cfg(test)]
 mod callback_handler_tests;
@@ -204,12 +199,16 @@ mod ffi_basenode_state;
 #[cfg(test)]
 mod output_manager_service_mock;
 mod tasks;
+mod wallet_setup;
 
 mod consts {
     // Import the auto-generated const values from the Manifest and Git
     include!(concat!(env!("OUT_DIR"), "/consts.rs"));
 }
 
+#[cfg(feature = "uniffi")]
+uniffi::setup_scaffolding!();
+
 const LOG_TARGET: &str = "wallet_ffi";
 
 pub type TariTransportConfig = TransportConfig;
@@ -264,6 +263,7 @@ pub struct TariWallet {
     runtime: Runtime,
     shutdown: Shutdown,
     context: Context,
+    progress_callbacks: SharedProgressCallbacks,
 }
 
 #[derive(Debug)]
@@ -3558,6 +3558,7 @@ pub unsafe extern "C" fn liveness_data_get_last_seen(
 /// |   0 | Ping             |
 /// |   1 | Pong             |
 /// |   2 | NoMessage        |
+/// |   3 | Presence         |
 ///
 /// # Safety
 /// The ```liveness_data_destroy``` method must be called when finished with a TariContactsLivenessData to prevent a
@@ -5385,18 +5386,10 @@ pub unsafe extern "C" fn comms_config_create(
         return ptr::null_mut();
     }
 
-    let dht_database_path = datastore_path.join("dht.db");
-
     let public_address = public_address_str.parse::<Multiaddr>();
 
     match public_address {
         Ok(public_address) => {
-            let addresses = if (*transport).transport_type == TransportType::Tor {
-                MultiaddrList::default()
-            } else {
-                MultiaddrList::from(vec![public_address])
-            };
-
             let excluded_dial_addresses = if exclude_dial_test_addresses {
                 let multi_addr_range = match MultiaddrRange::from_str(IP4_TCP_TEST_ADDR_RANGE) {
                     Ok(val) => val,
@@ -5411,48 +5404,15 @@ pub unsafe extern "C" fn comms_config_create(
                 MultiaddrRangeList::from(vec![])
             };
 
-            let config = TariCommsConfig {
-                override_from: None,
-                public_addresses: addresses,
-                transport: (*transport).clone(),
-                auxiliary_tcp_listener_address: None,
+            let config = mobile_comms_config(
+                public_address,
+                (*transport).clone(),
+                database_name_string,
                 datastore_path,
-                peer_database_name: database_name_string,
-                max_concurrent_inbound_tasks: 25,
-                max_concurrent_outbound_tasks: 50,
-                dht: DhtConfig {
-                    num_neighbouring_nodes: 5,
-                    num_random_nodes: 1,
-                    minimize_connections: true,
-                    discovery_request_timeout: Duration::from_secs(discovery_timeout_in_secs),
-                    database_url: DbConnectionUrl::File(dht_database_path),
-                    auto_join: true,
-                    saf: SafConfig {
-                        msg_validity: Duration::from_secs(saf_message_duration_in_secs),
-                        // Ensure that SAF messages are requested automatically
-                        auto_request: true,
-                        ..Default::default()
-                    },
-                    network_discovery: NetworkDiscoveryConfig {
-                        min_desired_peers: 16,
-                        initial_peer_sync_delay: Some(Duration::from_secs(25)),
-                        ..Default::default()
-                    },
-                    connectivity: DhtConnectivityConfig {
-                        update_interval: Duration::from_secs(5 * 60),
-                        minimum_desired_tcpv4_node_ratio: 0.0,
-                        ..Default::default()
-                    },
-                    excluded_dial_addresses,
-                    ..Default::default()
-                },
-                allow_test_addresses: true,
-                listener_liveness_allowlist_cidrs: StringList::new(),
-                listener_liveness_max_sessions: 0,
-                rpc_max_simultaneous_sessions: 0,
-                rpc_max_sessions_per_peer: 0,
-                listener_self_liveness_check_interval: None,
-            };
+                Duration::from_secs(discovery_timeout_in_secs),
+                Duration::from_secs(saf_message_duration_in_secs),
+                excluded_dial_addresses,
+            );
 
             Box::into_raw(Box::new(config))
         },
@@ -6052,53 +6012,7 @@ pub unsafe extern "C" fn wallet_create(
         comms_config.transport.tor.identity = wallet_database.get_tor_id().ok().flatten();
     }
 
-    let result = runtime.block_on(async {
-        let master_seed = read_or_create_master_seed(recovery_seed, &wallet_database)
-            .map_err(|err| WalletStorageError::RecoverySeedError(err.to_string()))?;
-        let comms_secret_key = derive_comms_secret_key(&master_seed)
-            .map_err(|err| WalletStorageError::RecoverySeedError(err.to_string()))?;
-
-        let node_features = wallet_database.get_node_features()?.unwrap_or_default();
-        let node_addresses = if comms_config.public_addresses.is_empty() {
-            match wallet_database.get_node_address()? {
-                Some(addr) => MultiaddrList::from(vec![addr]),
-                None => MultiaddrList::default(),
-            }
-        } else {
-            comms_config.public_addresses.clone()
-        };
-        debug!(target: LOG_TARGET, "We have the following addresses");
-        for address in &node_addresses {
-            debug!(target: LOG_TARGET, "Address: {}", address);
-        }
-        let identity_sig = wallet_database.get_comms_identity_signature()?;
-
-        // This checks if anything has changed by validating the previous signature and if invalid, setting identity_sig
-        // to None
-        let identity_sig = identity_sig.filter(|sig| {
-            let comms_public_key = CommsPublicKey::from_secret_key(&comms_secret_key);
-            sig.is_valid(&comms_public_key, node_features, &node_addresses)
-        });
-
-        // SAFETY: we are manually checking the validity of this signature before adding Some(..)
-        let node_identity = Arc::new(NodeIdentity::with_signature_unchecked(
-            comms_secret_key,
-            node_addresses.to_vec(),
-            node_features,
-            identity_sig,
-        ));
-        if !node_identity.is_signed() {
-            node_identity.sign();
-            // unreachable panic: signed above
-            let sig = node_identity
-                .identity_signature_read()
-                .as_ref()
-                .expect("unreachable panic")
-                .clone();
-            wallet_database.set_comms_identity_signature(sig)?;
-        }
-        Ok((master_seed, node_identity))
-    });
+    let result = load_node_identity(recovery_seed, &wallet_database, &comms_config);
 
     let (master_seed, node_identity) = match result {
         Ok(tuple) => tuple,
@@ -6163,6 +6077,7 @@ pub unsafe extern "C" fn wallet_create(
         shutdown.to_signal(),
         master_seed,
         Some(WalletType::default()),
+        None,
         user_agent,
     ));
 
@@ -6178,32 +6093,15 @@ pub unsafe extern "C" fn wallet_create(
             };
 
             // Lets set the base node peers
-            let peer_manager = w.comms.peer_manager();
-            let query = PeerQuery::new().select_where(|p| p.is_seed());
-            let peers = runtime.block_on(peer_manager.perform_query(query)).unwrap_or_default();
-
-            if !peers.is_empty() {
-                let selected_base_node = peers.choose(&mut OsRng).expect("base_nodes is not empty").clone();
-                let net_address = selected_base_node.addresses.best().expect("No addresses for base node");
-                match runtime.block_on(async {
-                    w.set_base_node_peer(
-                        selected_base_node.public_key.clone(),
-                        Some(net_address.address().clone()),
-                        Some(peers.to_vec()),
-                    )
-                    .await
-                }) {
-                    Ok(_) => (),
-                    Err(e) => {
-                        error = LibWalletError::from(e).code;
-                        ptr::swap(error_out, &mut error as *mut c_int);
-                        return ptr::null_mut();
-                    },
-                }
+            if let Err(e) = runtime.block_on(set_random_seed_base_node(&mut w)) {
+                error = LibWalletError::from(e).code;
+                ptr::swap(error_out, &mut error as *mut c_int);
+                return ptr::null_mut();
             }
 
             let mut utxo_scanner = w.utxo_scanner_service.clone();
             let context = Context(context);
+            let progress_callbacks = SharedProgressCallbacks::default();
             // Start Callback Handler
             let callback_handler = CallbackHandler::new(
                 context,
@@ -6218,6 +6116,7 @@ pub unsafe extern "C" fn wallet_create(
                 wallet_address,
                 w.wallet_connectivity.get_connectivity_status_watch(),
                 w.contacts_service.get_contacts_liveness_event_stream(),
+                progress_callbacks.clone(),
                 callback_received_transaction,
                 callback_received_transaction_reply,
                 callback_received_finalized_transaction,
@@ -6245,6 +6144,7 @@ pub unsafe extern "C" fn wallet_create(
                 runtime,
                 shutdown,
                 context,
+                progress_callbacks,
             };
 
             Box::into_raw(Box::new(tari_wallet))
@@ -8662,6 +8562,99 @@ pub unsafe extern "C" fn wallet_set_normal_power_mode(wallet: *mut TariWallet, e
     }
 }
 
+/// Register the callback that is called as the wallet scans the blockchain for its outputs, replacing any previously
+/// registered one. Passing null clears the callback.
+///
+/// ## Arguments
+/// `wallet` - The TariWallet pointer
+/// `callback_scanning_progress` - The callback function pointer matching the function signature. It is called with the
+/// height scanned so far and the chain tip height, so that the two can be used to show a progress bar.
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn wallet_set_scanning_progress_callback(
+    wallet: *mut TariWallet,
+    callback_scanning_progress: Option<unsafe extern "C" fn(context: *mut c_void, u64, u64)>,
+    error_out: *mut c_int,
+) {
+    update_progress_callbacks(wallet, error_out, |callbacks| {
+        callbacks.scanning_progress = callback_scanning_progress;
+    });
+}
+
+/// Register the callback that is called when the wallet's scanning connection to its base node changes state,
+/// replacing any previously registered one. Passing null clears the callback.
+///
+/// ## Arguments
+/// `wallet` - The TariWallet pointer
+/// `callback_base_node_connection` - The callback function pointer matching the function signature. The first
+/// parameter is the new state, encoded as an integer, and the meaning of the second depends on the state:
+///     Connecting,             // 0 - unused
+///     Connected,              // 1 - the connection latency in milliseconds
+///     ConnectionFailed,       // 2 - the number of retries so far
+///     ScanningRoundFailed,    // 3 - the number of retries so far
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn wallet_set_base_node_connection_callback(
+    wallet: *mut TariWallet,
+    callback_base_node_connection: Option<unsafe extern "C" fn(context: *mut c_void, u64, u64)>,
+    error_out: *mut c_int,
+) {
+    update_progress_callbacks(wallet, error_out, |callbacks| {
+        callbacks.base_node_connection = callback_base_node_connection;
+    });
+}
+
+/// Register the callback that is called as a transaction validation process progresses, replacing any previously
+/// registered one. Passing null clears the callback.
+///
+/// ## Arguments
+/// `wallet` - The TariWallet pointer
+/// `callback_transaction_validation_progress` - The callback function pointer matching the function signature. It is
+/// called with the request key returned by `wallet_start_transaction_validation`, the number of transactions checked so
+/// far and the total number of transactions to check.
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn wallet_set_transaction_validation_progress_callback(
+    wallet: *mut TariWallet,
+    callback_transaction_validation_progress: Option<unsafe extern "C" fn(context: *mut c_void, u64, u64, u64)>,
+    error_out: *mut c_int,
+) {
+    update_progress_callbacks(wallet, error_out, |callbacks| {
+        callbacks.transaction_validation_progress = callback_transaction_validation_progress;
+    });
+}
+
+unsafe fn update_progress_callbacks<F: FnOnce(&mut ProgressCallbacks)>(
+    wallet: *mut TariWallet,
+    error_out: *mut c_int,
+    update: F,
+) {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if wallet.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return;
+    }
+
+    match (*wallet).progress_callbacks.write() {
+        Ok(mut callbacks) => update(&mut callbacks),
+        Err(e) => {
+            error = LibWalletError::from(InterfaceError::InternalError(e.to_string())).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+        },
+    }
+}
+
 /// Set a Key Value in the Wallet storage used for Client Key Value store
 ///
 /// ## Arguments
@@ -8863,6 +8856,459 @@ pub unsafe extern "C" fn wallet_clear_value(
     }
 }
 
+/// Stores a Value in the namespace of an application in the Wallet storage. Values are encrypted and are part of the
+/// wallet database, and so of its backups.
+///
+/// ## Arguments
+/// `wallet` - The TariWallet pointer.
+/// `namespace` - The pointer to a Utf8 string representing the namespace of the application. Namespaces and keys are
+/// 1 to 128 ASCII letters, digits, `-`, `_` and `.`
+/// `key` - The pointer to a Utf8 string representing the Key
+/// `value` - The pointer to a Utf8 string representing the Value to be stored, at most 64 KiB
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `bool` - Return a boolean value indicating the operation's success or failure. The error_ptr will hold the error
+/// code if there was a failure
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn wallet_set_app_value(
+    wallet: *mut TariWallet,
+    namespace: *const c_char,
+    key: *const c_char,
+    value: *const c_char,
+    error_out: *mut c_int,
+) -> bool {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+
+    if wallet.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return false;
+    }
+
+    let namespace_string;
+    if namespace.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("namespace".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return false;
+    } else {
+        match CStr::from_ptr(namespace).to_str() {
+            Ok(v) => {
+                namespace_string = v.to_owned();
+            },
+            _ => {
+                error = LibWalletError::from(InterfaceError::PointerError("namespace".to_string())).code;
+                ptr::swap(error_out, &mut error as *mut c_int);
+                return false;
+            },
+        }
+    }
+
+    let key_string;
+    if key.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("key".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return false;
+    } else {
+        match CStr::from_ptr(key).to_str() {
+            Ok(v) => {
+                key_string = v.to_owned();
+            },
+            _ => {
+                error = LibWalletError::from(InterfaceError::PointerError("key".to_string())).code;
+                ptr::swap(error_out, &mut error as *mut c_int);
+                return false;
+            },
+        }
+    }
+
+    let value_string;
+    if value.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("value".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return false;
+    } else {
+        match CStr::from_ptr(value).to_str() {
+            Ok(v) => {
+                value_string = v.to_owned();
+            },
+            _ => {
+                error = LibWalletError::from(InterfaceError::PointerError("value".to_string())).code;
+                ptr::swap(error_out, &mut error as *mut c_int);
+                return false;
+            },
+        }
+    }
+
+    match (*wallet)
+        .wallet
+        .db
+        .set_app_value(&namespace_string, &key_string, value_string)
+    {
+        Ok(_) => true,
+        Err(e) => {
+            error = LibWalletError::from(WalletError::WalletStorageError(e)).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            false
+        },
+    }
+}
+
+/// Gets a Value that was previously stored in the namespace of an application in the Wallet storage
+///
+/// ## Arguments
+/// `wallet` - The TariWallet pointer.
+/// `namespace` - The pointer to a Utf8 string representing the namespace of the application. Namespaces and keys are
+/// 1 to 128 ASCII letters, digits, `-`, `_` and `.`
+/// `key` - The pointer to a Utf8 string representing the Key
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `*mut c_char` - Returns a pointer to a char array of the Value string. Note that it returns an null pointer if an
+/// error occured or if there is no Value for the Key.
+///
+/// # Safety
+/// The ```string_destroy``` method must be called when finished with a string from rust to prevent a memory leak
+#[no_mangle]
+pub unsafe extern "C" fn wallet_get_app_value(
+    wallet: *mut TariWallet,
+    namespace: *const c_char,
+    key: *const c_char,
+    error_out: *mut c_int,
+) -> *mut c_char {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+
+    if wallet.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
+    }
+
+    let namespace_string;
+    if namespace.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("namespace".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
+    } else {
+        match CStr::from_ptr(namespace).to_str() {
+            Ok(v) => {
+                namespace_string = v.to_owned();
+            },
+            _ => {
+                error = LibWalletError::from(InterfaceError::PointerError("namespace".to_string())).code;
+                ptr::swap(error_out, &mut error as *mut c_int);
+                return ptr::null_mut();
+            },
+        }
+    }
+
+    let key_string;
+    if key.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("key".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
+    } else {
+        match CStr::from_ptr(key).to_str() {
+            Ok(v) => {
+                key_string = v.to_owned();
+            },
+            _ => {
+                error = LibWalletError::from(InterfaceError::PointerError("key".to_string())).code;
+                ptr::swap(error_out, &mut error as *mut c_int);
+                return ptr::null_mut();
+            },
+        }
+    }
+
+    match (*wallet).wallet.db.get_app_value(&namespace_string, &key_string) {
+        Ok(result) => match result {
+            None => {
+                error = LibWalletError::from(WalletError::WalletStorageError(WalletStorageError::ValuesNotFound)).code;
+                ptr::swap(error_out, &mut error as *mut c_int);
+                ptr::null_mut()
+            },
+            Some(value) => {
+                let v = CString::new(value).expect("Should be able to make a CString");
+                CString::into_raw(v)
+            },
+        },
+        Err(e) => {
+            error = LibWalletError::from(WalletError::WalletStorageError(e)).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            ptr::null_mut()
+        },
+    }
+}
+
+/// Clears a Value in the namespace of an application in the Wallet storage
+///
+/// ## Arguments
+/// `wallet` - The TariWallet pointer.
+/// `namespace` - The pointer to a Utf8 string representing the namespace of the application. Namespaces and keys are
+/// 1 to 128 ASCII letters, digits, `-`, `_` and `.`
+/// `key` - The pointer to a Utf8 string representing the Key
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `bool` - Returns true if a Value was cleared, false if there was no Value for the Key or if an error occurred. The
+/// error_ptr will hold the error code if there was a failure
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn wallet_clear_app_value(
+    wallet: *mut TariWallet,
+    namespace: *const c_char,
+    key: *const c_char,
+    error_out: *mut c_int,
+) -> bool {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+
+    if wallet.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return false;
+    }
+
+    let namespace_string;
+    if namespace.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("namespace".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return false;
+    } else {
+        match CStr::from_ptr(namespace).to_str() {
+            Ok(v) => {
+                namespace_string = v.to_owned();
+            },
+            _ => {
+                error = LibWalletError::from(InterfaceError::PointerError("namespace".to_string())).code;
+                ptr::swap(error_out, &mut error as *mut c_int);
+                return false;
+            },
+        }
+    }
+
+    let key_string;
+    if key.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("key".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return false;
+    } else {
+        match CStr::from_ptr(key).to_str() {
+            Ok(v) => {
+                key_string = v.to_owned();
+            },
+            _ => {
+                error = LibWalletError::from(InterfaceError::PointerError("key".to_string())).code;
+                ptr::swap(error_out, &mut error as *mut c_int);
+                return false;
+            },
+        }
+    }
+
+    match (*wallet).wallet.db.clear_app_value(&namespace_string, &key_string) {
+        Ok(result) => result,
+        Err(e) => {
+            error = LibWalletError::from(WalletError::WalletStorageError(e)).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            false
+        },
+    }
+}
+
+/// Gets the Keys in the namespace of an application in the Wallet storage, sorted
+///
+/// ## Arguments
+/// `wallet` - The TariWallet pointer.
+/// `namespace` - The pointer to a Utf8 string representing the namespace of the application. Namespaces and keys are
+/// 1 to 128 ASCII letters, digits, `-`, `_` and `.`
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `*mut TariVector` - Returns a TariVector of Text items with the Keys. Note that it returns a null pointer if an
+/// error occurred.
+///
+/// # Safety
+/// `destroy_tari_vector()` must be called after use.
+#[no_mangle]
+pub unsafe extern "C" fn wallet_get_app_keys(
+    wallet: *mut TariWallet,
+    namespace: *const c_char,
+    error_out: *mut c_int,
+) -> *mut TariVector {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+
+    if wallet.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
+    }
+
+    let namespace_string;
+    if namespace.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("namespace".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
+    } else {
+        match CStr::from_ptr(namespace).to_str() {
+            Ok(v) => {
+                namespace_string = v.to_owned();
+            },
+            _ => {
+                error = LibWalletError::from(InterfaceError::PointerError("namespace".to_string())).code;
+                ptr::swap(error_out, &mut error as *mut c_int);
+                return ptr::null_mut();
+            },
+        }
+    }
+
+    match (*wallet).wallet.db.get_app_keys(&namespace_string) {
+        Ok(keys) => Box::into_raw(Box::new(TariVector::from(keys))),
+        Err(e) => {
+            error = LibWalletError::from(WalletError::WalletStorageError(e)).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            ptr::null_mut()
+        },
+    }
+}
+
+/// Registers the device that push notifications for incoming transactions are sent to, replacing any previous
+/// registration. Each notification is encrypted with `notification_key` and posted to `relay_url` together with
+/// `device_token`, so the relay can deliver it without being able to read it. Notifications are only sent if the
+/// library was built with the `push-notifications` feature, and only while the wallet is running. A backgrounded app
+/// is woken by a light wallet server that holds a push watch for the wallet instead.
+///
+/// ## Arguments
+/// `wallet` - The TariWallet pointer.
+/// `relay_url` - The pointer to a Utf8 string representing the URL of the push notification relay
+/// `device_token` - The pointer to a Utf8 string representing the device token issued by the platform's push service
+/// `notification_key` - The pointer to a ByteVector containing the 32 byte key that notifications are encrypted with
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `bool` - Return a boolean value indicating the operation's success or failure. The error_ptr will hold the error
+/// code if there was a failure
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn wallet_register_push_notifications(
+    wallet: *mut TariWallet,
+    relay_url: *const c_char,
+    device_token: *const c_char,
+    notification_key: *const ByteVector,
+    error_out: *mut c_int,
+) -> bool {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+
+    if wallet.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return false;
+    }
+
+    let relay_url_string;
+    if relay_url.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("relay_url".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return false;
+    } else {
+        match CStr::from_ptr(relay_url).to_str() {
+            Ok(v) => {
+                relay_url_string = v.to_owned();
+            },
+            _ => {
+                error = LibWalletError::from(InterfaceError::PointerError("relay_url".to_string())).code;
+                ptr::swap(error_out, &mut error as *mut c_int);
+                return false;
+            },
+        }
+    }
+
+    let device_token_string;
+    if device_token.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("device_token".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return false;
+    } else {
+        match CStr::from_ptr(device_token).to_str() {
+            Ok(v) => {
+                device_token_string = v.to_owned();
+            },
+            _ => {
+                error = LibWalletError::from(InterfaceError::PointerError("device_token".to_string())).code;
+                ptr::swap(error_out, &mut error as *mut c_int);
+                return false;
+            },
+        }
+    }
+
+    if notification_key.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("notification_key".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return false;
+    }
+
+    match (*wallet)
+        .wallet
+        .register_push_notifications(relay_url_string, device_token_string, &(*notification_key).0)
+    {
+        Ok(()) => true,
+        Err(e) => {
+            error = LibWalletError::from(e).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            false
+        },
+    }
+}
+
+/// Stops sending push notifications and forgets the registered device
+///
+/// ## Arguments
+/// `wallet` - The TariWallet pointer.
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `bool` - Return a boolean value indicating the operation's success or failure. The error_ptr will hold the error
+/// code if there was a failure
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn wallet_unregister_push_notifications(wallet: *mut TariWallet, error_out: *mut c_int) -> bool {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+
+    if wallet.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return false;
+    }
+
+    match (*wallet).wallet.unregister_push_notifications() {
+        Ok(()) => true,
+        Err(e) => {
+            error = LibWalletError::from(e).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            false
+        },
+    }
+}
+
 /// Check if a Wallet has the data of an In Progress Recovery in its database.
 ///
 /// ## Arguments
@@ -9589,7 +10035,7 @@ mod test {
     };
     use once_cell::sync::Lazy;
     use tari_common_types::{emoji, tari_address::TariAddressFeatures, types::PrivateKey};
-    use tari_comms::peer_manager::PeerFeatures;
+    use tari_comms::peer_manager::{NodeIdentity, PeerFeatures};
     use tari_contacts::contacts_service::types::{ChatBody, Direction, Message, MessageId, MessageMetadata};
     use tari_core::{
         covenant,
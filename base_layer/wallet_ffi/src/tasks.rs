@@ -107,6 +107,7 @@ pub async fn recovery_event_monitoring(
                 num_recovered,
                 value_recovered,
                 time_taken: elapsed,
+                ..
             }) => {
                 let rate = (final_height as f32) * 1000f32 / (elapsed.as_millis() as f32);
                 info!(
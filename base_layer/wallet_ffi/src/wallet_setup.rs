@@ -0,0 +1,203 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Wallet bootstrap steps shared by the C `wallet_create` entry point and the UniFFI bindings.
+
+use std::{path::PathBuf, sync::Arc, time::Duration};
+
+use log::*;
+use minotari_wallet::{
+    error::{WalletError, WalletStorageError},
+    storage::database::{WalletBackend, WalletDatabase},
+    wallet::{derive_comms_secret_key, read_or_create_master_seed},
+    WalletSqlite,
+};
+use rand::{prelude::SliceRandom, rngs::OsRng};
+use tari_common::configuration::{MultiaddrList, StringList};
+use tari_comms::{
+    multiaddr::Multiaddr,
+    net_address::MultiaddrRangeList,
+    peer_manager::{NodeIdentity, PeerQuery},
+    types::CommsPublicKey,
+};
+use tari_comms_dht::{
+    store_forward::SafConfig,
+    DbConnectionUrl,
+    DhtConfig,
+    DhtConnectivityConfig,
+    NetworkDiscoveryConfig,
+};
+use tari_crypto::keys::PublicKey;
+use tari_key_manager::cipher_seed::CipherSeed;
+use tari_p2p::{TransportConfig, TransportType};
+
+use crate::TariCommsConfig;
+
+const LOG_TARGET: &str = "wallet_ffi::wallet_setup";
+
+/// Builds the comms configuration used by mobile wallets. Tor transports advertise no public address since the onion
+/// address is only known once the hidden service has been created.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn mobile_comms_config(
+    public_address: Multiaddr,
+    transport: TransportConfig,
+    database_name: String,
+    datastore_path: PathBuf,
+    discovery_timeout: Duration,
+    saf_message_duration: Duration,
+    excluded_dial_addresses: MultiaddrRangeList,
+) -> TariCommsConfig {
+    let dht_database_path = datastore_path.join("dht.db");
+    let public_addresses = if transport.transport_type == TransportType::Tor {
+        MultiaddrList::default()
+    } else {
+        MultiaddrList::from(vec![public_address])
+    };
+
+    TariCommsConfig {
+        override_from: None,
+        public_addresses,
+        transport,
+        auxiliary_tcp_listener_address: None,
+        datastore_path,
+        peer_database_name: database_name,
+        max_concurrent_inbound_tasks: 25,
+        max_concurrent_outbound_tasks: 50,
+        dht: DhtConfig {
+            num_neighbouring_nodes: 5,
+            num_random_nodes: 1,
+            minimize_connections: true,
+            discovery_request_timeout: discovery_timeout,
+            database_url: DbConnectionUrl::File(dht_database_path),
+            auto_join: true,
+            saf: SafConfig {
+                msg_validity: saf_message_duration,
+                // Ensure that SAF messages are requested automatically
+                auto_request: true,
+                ..Default::default()
+            },
+            network_discovery: NetworkDiscoveryConfig {
+                min_desired_peers: 16,
+                initial_peer_sync_delay: Some(Duration::from_secs(25)),
+                ..Default::default()
+            },
+            connectivity: DhtConnectivityConfig {
+                update_interval: Duration::from_secs(5 * 60),
+                minimum_desired_tcpv4_node_ratio: 0.0,
+                ..Default::default()
+            },
+            excluded_dial_addresses,
+            ..Default::default()
+        },
+        allow_test_addresses: true,
+        listener_liveness_allowlist_cidrs: StringList::new(),
+        listener_liveness_max_sessions: 0,
+        rpc_max_simultaneous_sessions: 0,
+        rpc_max_sessions_per_peer: 0,
+        listener_self_liveness_check_interval: None,
+        cover_traffic_interval: None,
+        traffic_padding: false,
+        rpc_max_pending_requests: None,
+        rpc_max_requests_per_minute_per_method: None,
+        rpc_max_requests_per_minute_per_peer: None,
+        max_inbound_connections_per_subnet: None,
+    }
+}
+
+/// Reads (or creates from the optional recovery seed) the wallet master seed and derives the comms node identity from
+/// it. The identity signature stored in the database is reused if it is still valid for the current features and
+/// addresses, otherwise the identity is re-signed and the new signature persisted.
+pub(crate) fn load_node_identity<T: WalletBackend + 'static>(
+    recovery_seed: Option<CipherSeed>,
+    wallet_database: &WalletDatabase<T>,
+    comms_config: &TariCommsConfig,
+) -> Result<(CipherSeed, Arc<NodeIdentity>), WalletStorageError> {
+    let master_seed = read_or_create_master_seed(recovery_seed, wallet_database)
+        .map_err(|err| WalletStorageError::RecoverySeedError(err.to_string()))?;
+    let comms_secret_key =
+        derive_comms_secret_key(&master_seed).map_err(|err| WalletStorageError::RecoverySeedError(err.to_string()))?;
+
+    let node_features = wallet_database.get_node_features()?.unwrap_or_default();
+    let node_addresses = if comms_config.public_addresses.is_empty() {
+        match wallet_database.get_node_address()? {
+            Some(addr) => MultiaddrList::from(vec![addr]),
+            None => MultiaddrList::default(),
+        }
+    } else {
+        comms_config.public_addresses.clone()
+    };
+    debug!(target: LOG_TARGET, "We have the following addresses");
+    for address in &node_addresses {
+        debug!(target: LOG_TARGET, "Address: {}", address);
+    }
+    let identity_sig = wallet_database.get_comms_identity_signature()?;
+
+    // This checks if anything has changed by validating the previous signature and if invalid, setting identity_sig
+    // to None
+    let identity_sig = identity_sig.filter(|sig| {
+        let comms_public_key = CommsPublicKey::from_secret_key(&comms_secret_key);
+        sig.is_valid(&comms_public_key, node_features, &node_addresses)
+    });
+
+    // SAFETY: we are manually checking the validity of this signature before adding Some(..)
+    let node_identity = Arc::new(NodeIdentity::with_signature_unchecked(
+        comms_secret_key,
+        node_addresses.to_vec(),
+        node_features,
+        identity_sig,
+    ));
+    if !node_identity.is_signed() {
+        node_identity.sign();
+        // unreachable panic: signed above
+        let sig = node_identity
+            .identity_signature_read()
+            .as_ref()
+            .expect("unreachable panic")
+            .clone();
+        wallet_database.set_comms_identity_signature(sig)?;
+    }
+    Ok((master_seed, node_identity))
+}
+
+/// Picks a random seed peer as the wallet's base node, keeping the remaining seeds as backup peers. Does nothing if no
+/// seed peers are known yet.
+pub(crate) async fn set_random_seed_base_node(wallet: &mut WalletSqlite) -> Result<(), WalletError> {
+    let query = PeerQuery::new().select_where(|p| p.is_seed());
+    let peers = wallet
+        .comms
+        .peer_manager()
+        .perform_query(query)
+        .await
+        .unwrap_or_default();
+
+    if let Some(selected_base_node) = peers.choose(&mut OsRng).cloned() {
+        let net_address = selected_base_node.addresses.best().expect("No addresses for base node");
+        wallet
+            .set_base_node_peer(
+                selected_base_node.public_key.clone(),
+                Some(net_address.address().clone()),
+                Some(peers.to_vec()),
+            )
+            .await?;
+    }
+    Ok(())
+}
@@ -0,0 +1,6 @@
+// Copyright 2024. The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+fn main() {
+    uniffi::uniffi_bindgen_main()
+}
@@ -1831,6 +1831,7 @@ char *liveness_data_get_last_seen(TariContactsLivenessData *liveness_data,
  * |   0 | Ping             |
  * |   1 | Pong             |
  * |   2 | NoMessage        |
+ * |   3 | Presence         |
  *
  * # Safety
  * The ```liveness_data_destroy``` method must be called when finished with a TariContactsLivenessData to prevent a
@@ -3883,6 +3884,69 @@ void wallet_set_low_power_mode(struct TariWallet *wallet,
 void wallet_set_normal_power_mode(struct TariWallet *wallet,
                                   int *error_out);
 
+/**
+ * Register the callback that is called as the wallet scans the blockchain for its outputs, replacing any previously
+ * registered one. Passing null clears the callback.
+ *
+ * ## Arguments
+ * `wallet` - The TariWallet pointer
+ * `callback_scanning_progress` - The callback function pointer matching the function signature. It is called with the
+ * height scanned so far and the chain tip height, so that the two can be used to show a progress bar.
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ * # Safety
+ * None
+ */
+void wallet_set_scanning_progress_callback(struct TariWallet *wallet,
+                                           void (*callback_scanning_progress)(void *context,
+                                                                              uint64_t,
+                                                                              uint64_t),
+                                           int *error_out);
+
+/**
+ * Register the callback that is called when the wallet's scanning connection to its base node changes state,
+ * replacing any previously registered one. Passing null clears the callback.
+ *
+ * ## Arguments
+ * `wallet` - The TariWallet pointer
+ * `callback_base_node_connection` - The callback function pointer matching the function signature. The first
+ * parameter is the new state, encoded as an integer, and the meaning of the second depends on the state:
+ *     Connecting,             // 0 - unused
+ *     Connected,              // 1 - the connection latency in milliseconds
+ *     ConnectionFailed,       // 2 - the number of retries so far
+ *     ScanningRoundFailed,    // 3 - the number of retries so far
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ * # Safety
+ * None
+ */
+void wallet_set_base_node_connection_callback(struct TariWallet *wallet,
+                                              void (*callback_base_node_connection)(void *context,
+                                                                                    uint64_t,
+                                                                                    uint64_t),
+                                              int *error_out);
+
+/**
+ * Register the callback that is called as a transaction validation process progresses, replacing any previously
+ * registered one. Passing null clears the callback.
+ *
+ * ## Arguments
+ * `wallet` - The TariWallet pointer
+ * `callback_transaction_validation_progress` - The callback function pointer matching the function signature. It is
+ * called with the request key returned by `wallet_start_transaction_validation`, the number of transactions checked so
+ * far and the total number of transactions to check.
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ * # Safety
+ * None
+ */
+void wallet_set_transaction_validation_progress_callback(struct TariWallet *wallet,
+                                                         void (*callback_transaction_validation_progress)(void *context,
+                                                                                                          uint64_t,
+                                                                                                          uint64_t,
+                                                                                                          uint64_t),
+                                                         int *error_out);
+
 /**
  * Set a Key Value in the Wallet storage used for Client Key Value store
  *
@@ -3945,6 +4009,145 @@ bool wallet_clear_value(struct TariWallet *wallet,
                         const char *key,
                         int *error_out);
 
+/**
+ * Stores a Value in the namespace of an application in the Wallet storage. Values are encrypted and are part of the
+ * wallet database, and so of its backups.
+ *
+ * ## Arguments
+ * `wallet` - The TariWallet pointer.
+ * `namespace` - The pointer to a Utf8 string representing the namespace of the application. Namespaces and keys are
+ * 1 to 128 ASCII letters, digits, `-`, `_` and `.`
+ * `key` - The pointer to a Utf8 string representing the Key
+ * `value` - The pointer to a Utf8 string representing the Value to be stored, at most 64 KiB
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `bool` - Return a boolean value indicating the operation's success or failure. The error_ptr will hold the error
+ * code if there was a failure
+ *
+ * # Safety
+ * None
+ */
+bool wallet_set_app_value(struct TariWallet *wallet,
+                          const char *namespace,
+                          const char *key,
+                          const char *value,
+                          int *error_out);
+
+/**
+ * Gets a Value that was previously stored in the namespace of an application in the Wallet storage
+ *
+ * ## Arguments
+ * `wallet` - The TariWallet pointer.
+ * `namespace` - The pointer to a Utf8 string representing the namespace of the application. Namespaces and keys are
+ * 1 to 128 ASCII letters, digits, `-`, `_` and `.`
+ * `key` - The pointer to a Utf8 string representing the Key
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `*mut c_char` - Returns a pointer to a char array of the Value string. Note that it returns an null pointer if an
+ * error occured or if there is no Value for the Key.
+ *
+ * # Safety
+ * The ```string_destroy``` method must be called when finished with a string from rust to prevent a memory leak
+ */
+char *wallet_get_app_value(struct TariWallet *wallet,
+                           const char *namespace,
+                           const char *key,
+                           int *error_out);
+
+/**
+ * Clears a Value in the namespace of an application in the Wallet storage
+ *
+ * ## Arguments
+ * `wallet` - The TariWallet pointer.
+ * `namespace` - The pointer to a Utf8 string representing the namespace of the application. Namespaces and keys are
+ * 1 to 128 ASCII letters, digits, `-`, `_` and `.`
+ * `key` - The pointer to a Utf8 string representing the Key
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `bool` - Returns true if a Value was cleared, false if there was no Value for the Key or if an error occurred. The
+ * error_ptr will hold the error code if there was a failure
+ *
+ * # Safety
+ * None
+ */
+bool wallet_clear_app_value(struct TariWallet *wallet,
+                            const char *namespace,
+                            const char *key,
+                            int *error_out);
+
+/**
+ * Gets the Keys in the namespace of an application in the Wallet storage, sorted
+ *
+ * ## Arguments
+ * `wallet` - The TariWallet pointer.
+ * `namespace` - The pointer to a Utf8 string representing the namespace of the application. Namespaces and keys are
+ * 1 to 128 ASCII letters, digits, `-`, `_` and `.`
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `*mut TariVector` - Returns a TariVector of Text items with the Keys. Note that it returns a null pointer if an
+ * error occurred.
+ *
+ * # Safety
+ * `destroy_tari_vector()` must be called after use.
+ */
+struct TariVector *wallet_get_app_keys(struct TariWallet *wallet,
+                                       const char *namespace,
+                                       int *error_out);
+
+/**
+ * Registers the device that push notifications for incoming transactions are sent to, replacing any previous
+ * registration. Each notification is encrypted with `notification_key` and posted to `relay_url` together with
+ * `device_token`, so the relay can deliver it without being able to read it. Notifications are only sent if the
+ * library was built with the `push-notifications` feature, and only while the wallet is running. A backgrounded app
+ * is woken by a light wallet server that holds a push watch for the wallet instead.
+ *
+ * ## Arguments
+ * `wallet` - The TariWallet pointer.
+ * `relay_url` - The pointer to a Utf8 string representing the URL of the push notification relay
+ * `device_token` - The pointer to a Utf8 string representing the device token issued by the platform's push service
+ * `notification_key` - The pointer to a ByteVector containing the 32 byte key that notifications are encrypted with
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `bool` - Return a boolean value indicating the operation's success or failure. The error_ptr will hold the error
+ * code if there was a failure
+ *
+ * # Safety
+ * None
+ */
+bool wallet_register_push_notifications(struct TariWallet *wallet,
+                                        const char *relay_url,
+                                        const char *device_token,
+                                        const struct ByteVector *notification_key,
+                                        int *error_out);
+
+/**
+ * Stops sending push notifications and forgets the registered device
+ *
+ * ## Arguments
+ * `wallet` - The TariWallet pointer.
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `bool` - Return a boolean value indicating the operation's success or failure. The error_ptr will hold the error
+ * code if there was a failure
+ *
+ * # Safety
+ * None
+ */
+bool wallet_unregister_push_notifications(struct TariWallet *wallet,
+                                          int *error_out);
+
 /**
  * Check if a Wallet has the data of an In Progress Recovery in its database.
  *
@@ -47,6 +47,7 @@ mod config_list;
 mod dns_name_server_list;
 mod multiaddr_list;
 pub mod name_server;
+pub mod reload;
 pub mod serializers;
 mod string_list;
 pub mod utils;
@@ -0,0 +1,188 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Building blocks for applying changes to the configuration file while an application is running. Applications
+//! decide which settings they can apply live; changes to any other setting are reported as needing a restart.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use serde_json::Value;
+
+/// Detects changes to a configuration file from its modification time
+#[derive(Debug, Clone)]
+pub struct ConfigFileWatcher {
+    path: PathBuf,
+    last_modified: Option<SystemTime>,
+}
+
+impl ConfigFileWatcher {
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let last_modified = modified_time(&path);
+        Self { path, last_modified }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns true if the file was modified, created or removed since the watcher was created or this was last
+    /// called
+    pub fn has_changed(&mut self) -> bool {
+        let modified = modified_time(&self.path);
+        if modified == self.last_modified {
+            return false;
+        }
+        self.last_modified = modified;
+        true
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Returns the dotted keys of all the values that differ between `old` and `new`, typically configuration structs
+/// serialized with `serde_json::to_value`. Objects are compared field by field, any other values as a whole.
+pub fn changed_keys(old: &Value, new: &Value) -> Vec<String> {
+    let mut keys = Vec::new();
+    collect_changed_keys("", old, new, &mut keys);
+    keys.sort();
+    keys
+}
+
+fn collect_changed_keys(prefix: &str, old: &Value, new: &Value, keys: &mut Vec<String>) {
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            for (name, old_value) in old {
+                let key = join_key(prefix, name);
+                match new.get(name) {
+                    Some(new_value) => collect_changed_keys(&key, old_value, new_value, keys),
+                    None => keys.push(key),
+                }
+            }
+            for name in new.keys().filter(|name| !old.contains_key(*name)) {
+                keys.push(join_key(prefix, name));
+            }
+        },
+        (old, new) if old != new => keys.push(prefix.to_string()),
+        _ => {},
+    }
+}
+
+fn join_key(prefix: &str, name: &str) -> String {
+    if prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", prefix, name)
+    }
+}
+
+/// The outcome of reloading the configuration
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReloadReport {
+    /// Keys whose new values were applied to the running application
+    pub applied: Vec<String>,
+    /// Keys whose new values only take effect after a restart
+    pub restart_required: Vec<String>,
+}
+
+impl ReloadReport {
+    /// Sorts the `changed` keys into those that are, or are within, one of the `reloadable` keys, and the rest
+    pub fn classify<I: IntoIterator<Item = String>>(changed: I, reloadable: &[&str]) -> Self {
+        let (applied, restart_required) = changed
+            .into_iter()
+            .partition(|key| reloadable.iter().any(|prefix| is_within(key, prefix)));
+        Self {
+            applied,
+            restart_required,
+        }
+    }
+
+    /// Returns true if `key`, or any key within it, was applied
+    pub fn is_applied(&self, key: &str) -> bool {
+        self.applied.iter().any(|applied| is_within(applied, key))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.applied.is_empty() && self.restart_required.is_empty()
+    }
+}
+
+/// Returns true if `key` is `prefix` or a key within it
+fn is_within(key: &str, prefix: &str) -> bool {
+    key.strip_prefix(prefix)
+        .map_or(false, |rest| rest.is_empty() || rest.starts_with('.'))
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn it_finds_changed_keys() {
+        let old = json!({
+            "base_node": {
+                "mempool": { "min_fee": 0, "capacity": 10 },
+                "peers": ["a", "b"],
+                "removed": true,
+            },
+            "metrics": { "address": null },
+        });
+        let new = json!({
+            "base_node": {
+                "mempool": { "min_fee": 5, "capacity": 10 },
+                "peers": ["a"],
+                "added": 1,
+            },
+            "metrics": { "address": "127.0.0.1:9000" },
+        });
+        assert_eq!(changed_keys(&old, &new), vec![
+            "base_node.added",
+            "base_node.mempool.min_fee",
+            "base_node.peers",
+            "base_node.removed",
+            "metrics.address",
+        ]);
+        assert!(changed_keys(&old, &old).is_empty());
+    }
+
+    #[test]
+    fn it_classifies_changed_keys() {
+        let changed = vec![
+            "base_node.mempool.min_fee".to_string(),
+            "base_node.mempool_service.timeout".to_string(),
+            "metrics".to_string(),
+            "base_node.grpc_enabled".to_string(),
+        ];
+        let report = ReloadReport::classify(changed, &["base_node.mempool", "metrics"]);
+        assert_eq!(report.applied, vec!["base_node.mempool.min_fee", "metrics"]);
+        assert!(report.is_applied("base_node.mempool"));
+        assert!(report.is_applied("metrics"));
+        assert!(!report.is_applied("base_node.mempool_service"));
+        assert_eq!(report.restart_required, vec![
+            "base_node.mempool_service.timeout",
+            "base_node.grpc_enabled"
+        ]);
+        assert!(ReloadReport::classify(vec![], &["metrics"]).is_empty());
+    }
+
+    #[test]
+    fn it_detects_file_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let mut watcher = ConfigFileWatcher::new(&path);
+        assert!(!watcher.has_changed());
+        fs::write(&path, "a = 1").unwrap();
+        assert!(watcher.has_changed());
+        assert!(!watcher.has_changed());
+        fs::remove_file(&path).unwrap();
+        assert!(watcher.has_changed());
+    }
+}
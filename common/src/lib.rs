@@ -66,7 +66,15 @@ pub use configuration::{
 };
 pub mod dir_utils;
 
-pub use logging::initialize_logging;
+pub use logging::{
+    initialize_logging,
+    log_filter_directives,
+    set_log_field,
+    set_log_filter_directives,
+    JsonLinesEncoder,
+    LogFilterDirective,
+    LogFormat,
+};
 
 pub const DEFAULT_CONFIG: &str = "config/config.toml";
 pub const DEFAULT_BASE_NODE_LOG_CONFIG: &str = "config/log4rs_base_node.yml";
@@ -22,18 +22,283 @@
 //
 
 use std::{
+    collections::BTreeMap,
+    fmt,
     fs,
     fs::File,
     io::{Read, Write},
     path::Path,
+    str::FromStr,
+    sync::{Mutex, RwLock},
+    thread,
 };
 
-use log4rs::config::RawConfig;
+use chrono::Local;
+use log::{LevelFilter, Record};
+use log4rs::{
+    config::{Config, Deserialize, Deserializers, Logger, RawConfig, Root},
+    encode::{self, Encode},
+    Handle,
+};
+use serde_json::{json, Map, Value};
 
 use crate::ConfigError;
 
-/// Set up application-level logging using the Log4rs configuration file specified in
-pub fn initialize_logging(config_file: &Path, base_path: &Path, default: &str) -> Result<(), ConfigError> {
+/// The kind under which [JsonLinesEncoder] is registered, for use in log4rs configuration files
+pub const JSON_LINES_ENCODER_KIND: &str = "json_lines";
+
+/// Fields added to every JSON log line, such as the node identity
+static GLOBAL_LOG_FIELDS: RwLock<BTreeMap<String, String>> = RwLock::new(BTreeMap::new());
+/// The running log configuration, which runtime log filter directives are applied to
+static LOGGING: Mutex<Option<LoggingState>> = Mutex::new(None);
+
+struct LoggingState {
+    handle: Handle,
+    /// The parsed configuration file, from which the configuration is rebuilt when the log filters change
+    config: serde_yaml::Value,
+    /// The levels set at runtime, by target. The root logger has an empty target.
+    overrides: BTreeMap<String, LevelFilter>,
+}
+
+/// The format of the log output
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// As configured by the encoders of the log configuration file
+    #[default]
+    Text,
+    /// Every appender writes one JSON object per line, see [JsonLinesEncoder]
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("Invalid log format '{}', expected 'text' or 'json'", s)),
+        }
+    }
+}
+
+impl fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Text => write!(f, "text"),
+            Self::Json => write!(f, "json"),
+        }
+    }
+}
+
+/// The log level of a target and the targets within it, e.g. `comms::dht=debug`, or of the root logger if the target
+/// is empty, written as just the level
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogFilterDirective {
+    pub target: String,
+    pub level: LevelFilter,
+}
+
+impl FromStr for LogFilterDirective {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (target, level) = s.trim().rsplit_once('=').unwrap_or(("", s.trim()));
+        let target = target.trim();
+        if target.contains(char::is_whitespace) {
+            return Err(format!("Invalid log target '{}'", target));
+        }
+        let level = level
+            .trim()
+            .parse()
+            .map_err(|_| format!("Invalid log level '{}' in '{}'", level.trim(), s.trim()))?;
+        Ok(Self {
+            target: target.to_string(),
+            level,
+        })
+    }
+}
+
+impl fmt::Display for LogFilterDirective {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.target.is_empty() {
+            write!(f, "{}", self.level.as_str().to_lowercase())
+        } else {
+            write!(f, "{}={}", self.target, self.level.as_str().to_lowercase())
+        }
+    }
+}
+
+/// Returns the effective log filter directives: the level of the root logger and of each configured logger, including
+/// those set at runtime
+pub fn log_filter_directives() -> Result<Vec<LogFilterDirective>, ConfigError> {
+    let logging = LOGGING.lock().unwrap_or_else(|e| e.into_inner());
+    let state = logging
+        .as_ref()
+        .ok_or_else(|| ConfigError::new("Logging has not been initialized", None))?;
+    let config = build_config(&state.config, &state.overrides)?;
+    Ok(effective_directives(&config))
+}
+
+/// Sets the level of each target of `directives` in the running log configuration, after removing all levels set at
+/// runtime if `reset` is true. Levels set at runtime are not written to the configuration file and are lost on
+/// restart. Returns the effective log filter directives.
+pub fn set_log_filter_directives(
+    directives: Vec<LogFilterDirective>,
+    reset: bool,
+) -> Result<Vec<LogFilterDirective>, ConfigError> {
+    let mut logging = LOGGING.lock().unwrap_or_else(|e| e.into_inner());
+    let state = logging
+        .as_mut()
+        .ok_or_else(|| ConfigError::new("Logging has not been initialized", None))?;
+    let mut overrides = if reset {
+        BTreeMap::new()
+    } else {
+        state.overrides.clone()
+    };
+    overrides.extend(directives.into_iter().map(|d| (d.target, d.level)));
+    let config = build_config(&state.config, &overrides)?;
+    let directives = effective_directives(&config);
+    state.handle.set_config(config);
+    state.overrides = overrides;
+    Ok(directives)
+}
+
+/// Builds the log4rs configuration from the parsed configuration file, with the levels of `overrides`
+fn build_config(value: &serde_yaml::Value, overrides: &BTreeMap<String, LevelFilter>) -> Result<Config, ConfigError> {
+    let config: RawConfig = serde_yaml::from_value(value.clone())
+        .map_err(|e| ConfigError::new("Could not parse the log configuration", Some(e.to_string())))?;
+
+    let mut deserializers = Deserializers::default();
+    deserializers.insert(JSON_LINES_ENCODER_KIND, JsonLinesEncoderDeserializer);
+    // As with `log4rs::init_raw_config`, appenders that can't be created are reported and left out
+    let (appenders, mut errors) = config.appenders_lossy(&deserializers);
+    errors.handle();
+
+    let mut loggers = config.loggers();
+    for (target, level) in overrides.iter().filter(|(target, _)| !target.is_empty()) {
+        match loggers.iter().position(|l| l.name() == target) {
+            Some(i) => {
+                let logger = Logger::builder()
+                    .appenders(loggers[i].appenders().iter().cloned())
+                    .additive(loggers[i].additive())
+                    .build(target.clone(), *level);
+                loggers[i] = logger;
+            },
+            // A new logger writes to the appenders of its parent
+            None => loggers.push(Logger::builder().build(target.clone(), *level)),
+        }
+    }
+    let mut root = config.root();
+    if let Some(level) = overrides.get("") {
+        root = Root::builder()
+            .appenders(root.appenders().iter().cloned())
+            .build(*level);
+    }
+
+    Config::builder()
+        .appenders(appenders)
+        .loggers(loggers)
+        .build(root)
+        .map_err(|e| ConfigError::new("Invalid log configuration", Some(e.to_string())))
+}
+
+fn effective_directives(config: &Config) -> Vec<LogFilterDirective> {
+    let mut directives = vec![LogFilterDirective {
+        target: String::new(),
+        level: config.root().level(),
+    }];
+    directives.extend(config.loggers().iter().map(|logger| LogFilterDirective {
+        target: logger.name().to_string(),
+        level: logger.level(),
+    }));
+    directives
+}
+
+/// Adds a field to every JSON log line from now on, e.g. the identity of the node once it is known
+pub fn set_log_field<T: ToString>(key: &str, value: T) {
+    if let Ok(mut fields) = GLOBAL_LOG_FIELDS.write() {
+        fields.insert(key.to_string(), value.to_string());
+    }
+}
+
+/// A log4rs encoder that writes each record as one line of JSON, so that logs can be shipped to log aggregators
+/// without parsing. Besides the record itself, a line holds the logging context (MDC) of the thread, which carries
+/// the span context of the node's tasks, and the fields set with [set_log_field].
+#[derive(Debug, Default)]
+pub struct JsonLinesEncoder;
+
+impl JsonLinesEncoder {
+    fn to_json(record: &Record) -> Value {
+        let mut context = Map::new();
+        log_mdc::iter(|key, value| {
+            context.insert(key.to_string(), Value::String(value.to_string()));
+        });
+        let mut line = json!({
+            "time": Local::now().to_rfc3339(),
+            "level": record.level().as_str(),
+            "target": record.target(),
+            "module": record.module_path(),
+            "file": record.file(),
+            "line": record.line(),
+            "thread": thread::current().name(),
+            "message": record.args().to_string(),
+            "context": context,
+        });
+        if let (Some(line), Ok(fields)) = (line.as_object_mut(), GLOBAL_LOG_FIELDS.read()) {
+            for (key, value) in fields.iter() {
+                line.entry(key.clone()).or_insert_with(|| Value::String(value.clone()));
+            }
+        }
+        line
+    }
+}
+
+impl Encode for JsonLinesEncoder {
+    fn encode(&self, w: &mut dyn encode::Write, record: &Record) -> anyhow::Result<()> {
+        serde_json::to_writer(&mut *w, &Self::to_json(record))?;
+        w.write_all(b"\n")?;
+        Ok(())
+    }
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct JsonLinesEncoderConfig {}
+
+struct JsonLinesEncoderDeserializer;
+
+impl Deserialize for JsonLinesEncoderDeserializer {
+    type Config = JsonLinesEncoderConfig;
+    type Trait = dyn Encode;
+
+    fn deserialize(&self, _config: Self::Config, _: &Deserializers) -> anyhow::Result<Box<dyn Encode>> {
+        Ok(Box::<JsonLinesEncoder>::default())
+    }
+}
+
+/// Replaces the encoder of every appender with the JSON lines encoder
+fn use_json_encoders(config: &mut serde_yaml::Value) {
+    if let Some(appenders) = config.get_mut("appenders").and_then(|a| a.as_mapping_mut()) {
+        for (_, appender) in appenders.iter_mut() {
+            if let Some(appender) = appender.as_mapping_mut() {
+                let mut encoder = serde_yaml::Mapping::new();
+                encoder.insert("kind".into(), JSON_LINES_ENCODER_KIND.into());
+                appender.insert("encoder".into(), serde_yaml::Value::Mapping(encoder));
+            }
+        }
+    }
+}
+
+/// Set up application-level logging using the Log4rs configuration file specified in `config_file`, creating it from
+/// `default` if it does not exist. With [LogFormat::Json] all appenders write JSON lines, whatever their configured
+/// encoder. The `json_lines` encoder can also be configured per appender in the configuration file.
+pub fn initialize_logging(
+    config_file: &Path,
+    base_path: &Path,
+    default: &str,
+    format: LogFormat,
+) -> Result<(), ConfigError> {
     println!(
         "Initializing logging according to {:?}",
         config_file.to_str().unwrap_or("[??]")
@@ -65,9 +330,17 @@ pub fn initialize_logging(config_file: &Path, base_path: &Path, default: &str) -
 
     let contents = contents.replace("{{log_dir}}", &replace_str);
 
-    let config: RawConfig =
+    let mut config: serde_yaml::Value =
         serde_yaml::from_str(&contents).expect("Could not parse the contents of the log file as yaml");
-    log4rs::init_raw_config(config).expect("Could not initialize logging");
+    if format == LogFormat::Json {
+        use_json_encoders(&mut config);
+    }
+    let handle = log4rs::init_config(build_config(&config, &BTreeMap::new())?).expect("Could not initialize logging");
+    *LOGGING.lock().unwrap_or_else(|e| e.into_inner()) = Some(LoggingState {
+        handle,
+        config,
+        overrides: BTreeMap::new(),
+    });
 
     Ok(())
 }
@@ -127,6 +400,93 @@ macro_rules! log_if_error_fmt {
 
 #[cfg(test)]
 mod test {
+    use super::*;
+
+    #[test]
+    fn it_writes_records_as_json_lines() {
+        set_log_field("node_id", "abc123");
+        log_mdc::insert("sync_peer", "def456");
+        let line = JsonLinesEncoder::to_json(
+            &Record::builder()
+                .args(format_args!("Block {} added", 42))
+                .level(log::Level::Info)
+                .target("c::bn::test")
+                .line(Some(7))
+                .build(),
+        );
+        log_mdc::remove("sync_peer");
+        assert_eq!(line["message"], "Block 42 added");
+        assert_eq!(line["level"], "INFO");
+        assert_eq!(line["target"], "c::bn::test");
+        assert_eq!(line["line"], 7);
+        assert_eq!(line["node_id"], "abc123");
+        assert_eq!(line["context"]["sync_peer"], "def456");
+    }
+
+    #[test]
+    fn it_replaces_the_configured_encoders() {
+        let mut config: serde_yaml::Value = serde_yaml::from_str(
+            "appenders:\n  stdout:\n    kind: console\n    encoder:\n      pattern: \"{m}{n}\"\nroot:\n  level: info\n",
+        )
+        .unwrap();
+        use_json_encoders(&mut config);
+        assert_eq!(
+            config["appenders"]["stdout"]["encoder"]["kind"],
+            JSON_LINES_ENCODER_KIND
+        );
+        assert_eq!(config["appenders"]["stdout"]["kind"], "console");
+    }
+
+    #[test]
+    fn it_parses_log_filter_directives() {
+        let directive = "comms::dht=debug".parse::<LogFilterDirective>().unwrap();
+        assert_eq!(directive.target, "comms::dht");
+        assert_eq!(directive.level, LevelFilter::Debug);
+        assert_eq!(directive.to_string(), "comms::dht=debug");
+        let directive = "warn".parse::<LogFilterDirective>().unwrap();
+        assert_eq!(directive.target, "");
+        assert_eq!(directive.to_string(), "warn");
+        assert!("c::mempool=loud".parse::<LogFilterDirective>().is_err());
+        assert!("c mempool=info".parse::<LogFilterDirective>().is_err());
+    }
+
+    #[test]
+    fn it_applies_the_log_level_overrides() {
+        let config: serde_yaml::Value = serde_yaml::from_str(
+            r#"
+appenders:
+  stdout:
+    kind: console
+root:
+  level: warn
+  appenders:
+    - stdout
+loggers:
+  comms:
+    level: info
+    appenders:
+      - stdout
+    additive: false
+"#,
+        )
+        .unwrap();
+        let overrides = vec![
+            ("".to_string(), LevelFilter::Error),
+            ("comms".to_string(), LevelFilter::Debug),
+            ("c::mempool".to_string(), LevelFilter::Trace),
+        ]
+        .into_iter()
+        .collect();
+        let directives = effective_directives(&build_config(&config, &overrides).unwrap());
+        let directives = directives.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(directives, vec!["error", "comms=debug", "c::mempool=trace"]);
+
+        let config = build_config(&config, &overrides).unwrap();
+        let comms = config.loggers().iter().find(|l| l.name() == "comms").unwrap();
+        assert!(!comms.additive());
+        assert_eq!(comms.appenders(), ["stdout".to_string()]);
+    }
+
     #[test]
     fn log_if_error() {
         let err = Result::<(), _>::Err("What a shame");
@@ -0,0 +1,160 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Reporting of inconsistent rows found when verifying a database, and quarantining of such rows.
+//!
+//! A quarantined row is moved unchanged from its table into a `quarantined_<table>` table with the same columns, so
+//! that it no longer affects the application but can still be inspected, or restored by hand.
+
+use std::fmt::{Display, Formatter};
+
+use diesel::{
+    sql_query,
+    sql_types::{BigInt, Binary, Integer, Text},
+    Connection,
+    RunQueryDsl,
+    SqliteConnection,
+};
+
+/// An inconsistent row
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntegrityIssue {
+    pub table: &'static str,
+    /// Identifies the row within its table
+    pub row: String,
+    pub problem: String,
+    /// Whether the row was moved to the quarantine table
+    pub quarantined: bool,
+}
+
+impl IntegrityIssue {
+    pub fn new<R: Display, P: Display>(table: &'static str, row: R, problem: P) -> Self {
+        Self {
+            table,
+            row: row.to_string(),
+            problem: problem.to_string(),
+            quarantined: false,
+        }
+    }
+}
+
+impl Display for IntegrityIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}: {}", self.table, self.row, self.problem)?;
+        if self.quarantined {
+            write!(f, " (quarantined)")?;
+        }
+        Ok(())
+    }
+}
+
+/// The outcome of verifying one or more tables
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntegrityReport {
+    pub rows_checked: usize,
+    pub issues: Vec<IntegrityIssue>,
+}
+
+impl IntegrityReport {
+    pub fn merge(&mut self, other: IntegrityReport) {
+        self.rows_checked += other.rows_checked;
+        self.issues.extend(other.issues);
+    }
+
+    pub fn is_consistent(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// The primary key of a row to quarantine
+#[derive(Debug, Clone)]
+pub enum RowKey {
+    Integer(i32),
+    BigInt(i64),
+    Text(String),
+    Binary(Vec<u8>),
+}
+
+/// Moves the row of `table` whose `key_column` equals `key` into `quarantined_<table>`, creating that table if
+/// necessary. `table` and `key_column` are interpolated into the SQL, so they must never come from user input.
+pub fn quarantine_row(
+    conn: &mut SqliteConnection,
+    table: &str,
+    key_column: &str,
+    key: &RowKey,
+) -> Result<(), diesel::result::Error> {
+    conn.transaction(|conn| {
+        sql_query(format!(
+            "CREATE TABLE IF NOT EXISTS quarantined_{0} AS SELECT * FROM {0} WHERE 0",
+            table
+        ))
+        .execute(conn)?;
+        let copy = format!(
+            "INSERT INTO quarantined_{0} SELECT * FROM {0} WHERE {1} = ?",
+            table, key_column
+        );
+        let delete = format!("DELETE FROM {} WHERE {} = ?", table, key_column);
+        for query in [copy, delete] {
+            let query = sql_query(query);
+            match key {
+                RowKey::Integer(k) => query.bind::<Integer, _>(*k).execute(conn)?,
+                RowKey::BigInt(k) => query.bind::<BigInt, _>(*k).execute(conn)?,
+                RowKey::Text(k) => query.bind::<Text, _>(k).execute(conn)?,
+                RowKey::Binary(k) => query.bind::<Binary, _>(k).execute(conn)?,
+            };
+        }
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use diesel::QueryableByName;
+
+    use super::*;
+
+    #[derive(QueryableByName)]
+    struct Count {
+        #[diesel(sql_type = Integer)]
+        count: i32,
+    }
+
+    fn count(conn: &mut SqliteConnection, table: &str) -> i32 {
+        sql_query(format!("SELECT COUNT(*) AS count FROM {}", table))
+            .get_result::<Count>(conn)
+            .unwrap()
+            .count
+    }
+
+    #[test]
+    fn it_quarantines_rows() {
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        sql_query("CREATE TABLE things (id INTEGER PRIMARY KEY NOT NULL, name TEXT NOT NULL)")
+            .execute(&mut conn)
+            .unwrap();
+        sql_query("INSERT INTO things (id, name) VALUES (1, 'a'), (2, 'b'), (3, 'c')")
+            .execute(&mut conn)
+            .unwrap();
+
+        quarantine_row(&mut conn, "things", "id", &RowKey::Integer(2)).unwrap();
+        quarantine_row(&mut conn, "things", "name", &RowKey::Text("c".to_string())).unwrap();
+        assert_eq!(count(&mut conn, "things"), 1);
+        assert_eq!(count(&mut conn, "quarantined_things"), 2);
+    }
+
+    #[test]
+    fn it_merges_reports() {
+        let mut report = IntegrityReport {
+            rows_checked: 2,
+            issues: vec![],
+        };
+        assert!(report.is_consistent());
+        report.merge(IntegrityReport {
+            rows_checked: 3,
+            issues: vec![IntegrityIssue::new("things", 1, "Bad MAC")],
+        });
+        assert_eq!(report.rows_checked, 5);
+        assert!(!report.is_consistent());
+        assert_eq!(report.issues[0].to_string(), "things 1: Bad MAC");
+    }
+}
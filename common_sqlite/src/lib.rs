@@ -23,5 +23,6 @@
 pub mod connection;
 mod connection_options;
 pub mod error;
+pub mod integrity;
 pub mod sqlite_connection_pool;
 pub mod util;
@@ -119,12 +119,31 @@ impl UnspawnedCommsNode {
         self
     }
 
+    /// Set the public TCP listener address. This is an alias to `CommsBuilder::with_public_tcp_listener_address`.
+    pub fn with_public_tcp_listener_address(mut self, listener_address: Multiaddr) -> Self {
+        self.builder = self.builder.with_public_tcp_listener_address(listener_address);
+        self
+    }
+
+    /// Prefer onion addresses for outbound dials. This is an alias to `CommsBuilder::prefer_onion_dial_addresses`.
+    pub fn prefer_onion_dial_addresses(mut self, prefer_onion: bool) -> Self {
+        self.builder = self.builder.prefer_onion_dial_addresses(prefer_onion);
+        self
+    }
+
     /// Set the tor hidden service controller to associate with this comms instance
     pub fn with_hidden_service_controller(mut self, hidden_service_ctl: tor::HiddenServiceController) -> Self {
         self.builder.hidden_service_ctl = Some(hidden_service_ctl);
         self
     }
 
+    /// Set the handle to the tor hidden service controller used by the transport. This allows the hidden service to be
+    /// managed (e.g. key rotation) through the spawned `CommsNode`.
+    pub fn with_hidden_service_handle(mut self, hidden_service_handle: tor::HiddenServiceControllerHandle) -> Self {
+        self.builder.hidden_service_handle = Some(hidden_service_handle);
+        self
+    }
+
     /// Spawn a new node using the specified [Transport](crate::transports::Transport).
     #[allow(clippy::too_many_lines)]
     pub async fn spawn_with_transport<TTransport>(self, transport: TTransport) -> Result<CommsNode, CommsBuilderError>
@@ -149,6 +168,7 @@ impl UnspawnedCommsNode {
             dial_backoff,
             connection_manager_config,
             connectivity_config,
+            hidden_service_handle,
             ..
         } = builder;
 
@@ -239,6 +259,7 @@ impl UnspawnedCommsNode {
             node_identity,
             peer_manager,
             liveness_watch,
+            hidden_service_handle,
             complete_signals: ext_context.drain_complete_signals(),
         })
     }
@@ -282,6 +303,8 @@ pub struct CommsNode {
     peer_manager: Arc<PeerManager>,
     /// Current liveness status
     liveness_watch: watch::Receiver<SelfLivenessStatus>,
+    /// Handle to the tor hidden service controller, if the tor transport is used
+    hidden_service_handle: Option<tor::HiddenServiceControllerHandle>,
     /// The 'reciprocal' shutdown signals for each comms service
     complete_signals: Vec<ShutdownSignal>,
 }
@@ -326,6 +349,11 @@ impl CommsNode {
         self.connectivity_requester.clone()
     }
 
+    /// Returns a handle to the tor hidden service controller, or None if this node does not use the tor transport
+    pub fn hidden_service(&self) -> Option<tor::HiddenServiceControllerHandle> {
+        self.hidden_service_handle.clone()
+    }
+
     /// Returns a new `ShutdownSignal`
     pub fn shutdown_signal(&self) -> ShutdownSignal {
         self.shutdown_signal.clone()
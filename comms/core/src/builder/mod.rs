@@ -48,7 +48,7 @@ use crate::{
     net_address::MultiaddrRange,
     peer_manager::{NodeIdentity, PeerManager},
     peer_validator::PeerValidatorConfig,
-    protocol::{NodeNetworkInfo, ProtocolExtensions},
+    protocol::{Capabilities, NodeNetworkInfo, ProtocolExtensions},
     tor,
     types::CommsDatabase,
 };
@@ -125,6 +125,7 @@ pub struct CommsBuilder {
     node_identity: Option<Arc<NodeIdentity>>,
     dial_backoff: BoxedBackoff,
     hidden_service_ctl: Option<tor::HiddenServiceController>,
+    hidden_service_handle: Option<tor::HiddenServiceControllerHandle>,
     connection_manager_config: ConnectionManagerConfig,
     connectivity_config: ConnectivityConfig,
     shutdown_signal: Option<ShutdownSignal>,
@@ -139,6 +140,7 @@ impl Default for CommsBuilder {
             node_identity: None,
             dial_backoff: Box::new(ConstantBackoff::new(Duration::from_millis(500))),
             hidden_service_ctl: None,
+            hidden_service_handle: None,
             connection_manager_config: ConnectionManagerConfig::default(),
             connectivity_config: ConnectivityConfig::default(),
             shutdown_signal: None,
@@ -185,6 +187,12 @@ impl CommsBuilder {
         self
     }
 
+    /// Set the optional protocol capabilities that this node advertises to peers during the identity exchange
+    pub fn with_capabilities(mut self, capabilities: Capabilities) -> Self {
+        self.connection_manager_config.network_info.capabilities = capabilities;
+        self
+    }
+
     /// Set a network major and minor version as per [RFC-173 Versioning](https://rfc.tari.com/RFC-0173_Versioning.html)
     pub fn with_node_version(mut self, major_version: u8, minor_version: u8) -> Self {
         self.connection_manager_config.network_info.major_version = major_version;
@@ -236,6 +244,21 @@ impl CommsBuilder {
         self
     }
 
+    /// Sets a public TCP listener address that is bound _in addition to_ the primary listener. This allows a node to
+    /// accept connections over both its primary transport (e.g. a Tor hidden service) and clearnet TCP. This is
+    /// optional.
+    pub fn with_public_tcp_listener_address(mut self, listener_address: Multiaddr) -> Self {
+        self.connection_manager_config.public_tcp_listener_address = Some(listener_address);
+        self
+    }
+
+    /// When set, onion addresses advertised by a peer are dialed before any other address type. This is useful for
+    /// dual-stack nodes that want to use the anonymity transport for outbound connections where possible.
+    pub fn prefer_onion_dial_addresses(mut self, prefer_onion: bool) -> Self {
+        self.connection_manager_config.prefer_onion_dial_addresses = prefer_onion;
+        self
+    }
+
     /// Sets the maximum allowed liveness sessions. Liveness is typically used by tools like docker or kubernetes to
     /// detect that the node is live. Defaults to 0 (disabled)
     pub fn with_listener_liveness_max_sessions(mut self, max_sessions: usize) -> Self {
@@ -273,6 +296,12 @@ impl CommsBuilder {
         self
     }
 
+    /// Limits the number of inbound connections from a single /24 (IPv4) or /48 (IPv6) subnet. None disables the limit.
+    pub fn with_max_inbound_connections_per_subnet(mut self, max_connections: Option<usize>) -> Self {
+        self.connectivity_config.max_inbound_connections_per_subnet = max_connections;
+        self
+    }
+
     /// Call to disable connection reaping. Usually you would want to have this enabled, however there are some test
     /// cases where disabling this is desirable.
     pub fn disable_connection_reaping(mut self) -> Self {
@@ -36,7 +36,7 @@ use crate::{
     peer_validator::{validate_peer_identity_claim, PeerValidatorConfig, PeerValidatorError},
     proto::identity::PeerIdentityMsg,
     protocol,
-    protocol::{NodeNetworkInfo, ProtocolId},
+    protocol::{Capabilities, NodeNetworkInfo, ProtocolId},
     types::CommsPublicKey,
     PeerManager,
 };
@@ -62,12 +62,17 @@ impl ValidatedPeerIdentityExchange {
     pub fn user_agent(&self) -> &str {
         self.metadata.user_agent.as_str()
     }
+
+    pub fn capabilities(&self) -> &Capabilities {
+        &self.metadata.capabilities
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct PeerIdentityMetadata {
     pub user_agent: String,
     pub supported_protocols: Vec<ProtocolId>,
+    pub capabilities: Capabilities,
 }
 
 /// Performs the identity exchange protocol on the given socket.
@@ -107,6 +112,7 @@ pub(super) fn validate_peer_identity_message(
         supported_protocols,
         user_agent,
         identity_signature,
+        capabilities,
     } = peer_identity_msg;
 
     // Perform basic length checks before parsing
@@ -150,6 +156,7 @@ pub(super) fn validate_peer_identity_message(
     }
 
     let supported_protocols = supported_protocols.into_iter().map(ProtocolId::from).collect();
+    let capabilities = Capabilities::try_from_proto(capabilities).map_err(PeerValidatorError::from)?;
 
     let addresses = addresses
         .into_iter()
@@ -172,6 +179,7 @@ pub(super) fn validate_peer_identity_message(
         metadata: PeerIdentityMetadata {
             user_agent,
             supported_protocols,
+            capabilities,
         },
     })
 }
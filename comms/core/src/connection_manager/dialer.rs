@@ -59,7 +59,7 @@ use crate::{
     noise::{NoiseConfig, NoiseSocket},
     peer_manager::{NodeId, NodeIdentity, Peer, PeerManager},
     protocol::ProtocolId,
-    transports::Transport,
+    transports::{predicate::is_onion_address, Transport},
     types::CommsPublicKey,
 };
 
@@ -479,6 +479,7 @@ where
             conn_man_notifier,
             our_supported_protocols,
             peer_identity.metadata.supported_protocols.clone(),
+            config.network_info.capabilities.negotiate(peer_identity.capabilities()),
         );
 
         Ok((peer_connection, peer_identity))
@@ -516,7 +517,7 @@ where
             tokio::select! {
                 _ = delay => {
                     debug!(target: LOG_TARGET, "[Attempt {}] Connecting to peer '{}'", current_state.num_attempts(), current_state.peer().node_id.short_str());
-                    match Self::dial_peer(current_state, &noise_config, &current_transport, config.network_info.network_wire_byte, config.excluded_dial_addresses.clone()).await {
+                    match Self::dial_peer(current_state, &noise_config, &current_transport, config.network_info.network_wire_byte, config.excluded_dial_addresses.clone(), config.prefer_onion_dial_addresses).await {
                         (state, Ok((socket, addr))) => {
                             debug!(target: LOG_TARGET, "Dial succeeded for peer '{}' after {} attempt(s)", state.peer().node_id.short_str(), state.num_attempts());
                             break (state, Ok((socket, addr)));
@@ -558,11 +559,12 @@ where
         transport: &TTransport,
         network_byte: u8,
         excluded_dial_addresses: Vec<MultiaddrRange>,
+        prefer_onion_dial_addresses: bool,
     ) -> (
         DialState,
         Result<(NoiseSocket<TTransport::Output>, Multiaddr), ConnectionManagerError>,
     ) {
-        let addresses = dial_state
+        let mut addresses = dial_state
             .peer()
             .addresses
             .clone()
@@ -571,6 +573,10 @@ where
             .filter(|&a| !excluded_dial_addresses.iter().any(|excluded| excluded.contains(a)))
             .cloned()
             .collect::<Vec<_>>();
+        if prefer_onion_dial_addresses {
+            // Stable sort so that the existing address order is otherwise preserved
+            addresses.sort_by_key(|a| !is_onion_address(a));
+        }
         if addresses.is_empty() {
             let node_id_hex = dial_state.peer().node_id.clone().to_hex();
             trace!(
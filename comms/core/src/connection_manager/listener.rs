@@ -427,7 +427,11 @@ where
             CONNECTION_DIRECTION,
             conn_man_notifier,
             our_supported_protocols,
-            valid_peer_identity.metadata.supported_protocols,
+            valid_peer_identity.metadata.supported_protocols.clone(),
+            config
+                .network_info
+                .capabilities
+                .negotiate(valid_peer_identity.capabilities()),
         );
 
         peer_manager.add_peer(peer).await?;
@@ -132,6 +132,12 @@ pub struct ConnectionManagerConfig {
     /// If set, an additional TCP-only p2p listener will be started. This is useful for local wallet connections.
     /// Default: None (disabled)
     pub auxiliary_tcp_listener_address: Option<Multiaddr>,
+    /// If set, a public TCP listener is started alongside the primary listener. Unlike the auxiliary listener, this
+    /// listener is intended to be advertised to the network, allowing a node to serve both Tor and clearnet peers.
+    /// Default: None (disabled)
+    pub public_tcp_listener_address: Option<Multiaddr>,
+    /// If true, onion addresses are dialed before any other address type advertised by a peer. Default: false
+    pub prefer_onion_dial_addresses: bool,
     /// Peer validation configuration. See [PeerValidatorConfig]
     pub peer_validation_config: PeerValidatorConfig,
     /// Addresses that should never be dialed
@@ -155,6 +161,8 @@ impl Default for ConnectionManagerConfig {
             liveness_cidr_allowlist: vec![cidr::AnyIpCidr::V4("127.0.0.1/32".parse().unwrap())],
             self_liveness_self_check_interval: None,
             auxiliary_tcp_listener_address: None,
+            public_tcp_listener_address: None,
+            prefer_onion_dial_addresses: false,
             peer_validation_config: PeerValidatorConfig::default(),
             noise_handshake_recv_timeout: Duration::from_secs(6),
             excluded_dial_addresses: vec![],
@@ -167,6 +175,7 @@ impl Default for ConnectionManagerConfig {
 pub struct ListenerInfo {
     bind_address: Multiaddr,
     aux_bind_address: Option<Multiaddr>,
+    public_tcp_bind_address: Option<Multiaddr>,
 }
 
 impl ListenerInfo {
@@ -180,6 +189,11 @@ impl ListenerInfo {
     pub fn auxiliary_bind_address(&self) -> Option<&Multiaddr> {
         self.aux_bind_address.as_ref()
     }
+
+    /// The public TCP address that was bound on if dual-stack listening is enabled.
+    pub fn public_tcp_bind_address(&self) -> Option<&Multiaddr> {
+        self.public_tcp_bind_address.as_ref()
+    }
 }
 
 /// The actor responsible for connection management.
@@ -190,6 +204,7 @@ pub(crate) struct ConnectionManager<TTransport, TBackoff> {
     dialer: Option<Dialer<TTransport, TBackoff>>,
     listener: Option<PeerListener<TTransport>>,
     aux_listener: Option<PeerListener<TcpTransport>>,
+    public_tcp_listener: Option<PeerListener<TcpTransport>>,
     peer_manager: Arc<PeerManager>,
     shutdown_signal: Option<ShutdownSignal>,
     protocols: Protocols<Substream>,
@@ -251,6 +266,20 @@ where
             )
         });
 
+        let public_tcp_listener = config.public_tcp_listener_address.take().map(|addr| {
+            info!(target: LOG_TARGET, "Starting public TCP listener on {}", addr);
+            PeerListener::new(
+                config.clone(),
+                addr,
+                TcpTransport::new(),
+                noise_config.clone(),
+                internal_event_tx.clone(),
+                peer_manager.clone(),
+                node_identity.clone(),
+                shutdown_signal.clone(),
+            )
+        });
+
         let dialer = Dialer::new(
             config,
             node_identity,
@@ -274,6 +303,7 @@ where
             listener: Some(listener),
             listener_info: None,
             aux_listener,
+            public_tcp_listener,
             listening_notifiers: Vec::new(),
             connection_manager_events_tx,
             complete_trigger: Shutdown::new(),
@@ -357,6 +387,7 @@ where
             Ok(bind_address) => ListenerInfo {
                 bind_address,
                 aux_bind_address: None,
+                public_tcp_bind_address: None,
             },
             Err(err) => return Err(err),
         };
@@ -368,6 +399,13 @@ where
             listener_info.aux_bind_address = Some(addr);
         }
 
+        if let Some(mut listener) = self.public_tcp_listener.take() {
+            listener.set_supported_protocols(self.protocols.get_supported_protocols());
+            let addr = listener.listen().await?;
+            debug!(target: LOG_TARGET, "Public TCP listener bound to address {}", addr);
+            listener_info.public_tcp_bind_address = Some(addr);
+        }
+
         Ok(listener_info)
     }
 
@@ -21,11 +21,13 @@
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use std::{
+    collections::HashMap,
     fmt,
     future::Future,
     sync::{
         atomic::{AtomicUsize, Ordering},
         Arc,
+        RwLock,
     },
     time::{Duration, Instant},
 };
@@ -54,9 +56,9 @@ use crate::protocol::rpc::{
 use crate::{
     framing,
     framing::CanonicalFraming,
-    multiplexing::{Control, IncomingSubstreams, Substream, Yamux, YamuxControlError},
+    multiplexing::{ByteCounter, Control, IncomingSubstreams, Substream, Yamux, YamuxControlError},
     peer_manager::{NodeId, PeerFeatures},
-    protocol::{ProtocolId, ProtocolNegotiation},
+    protocol::{NegotiatedCapabilities, ProtocolId, ProtocolNegotiation},
     utils::atomic_ref_counter::AtomicRefCounter,
     Minimized,
 };
@@ -76,6 +78,7 @@ pub fn create(
     event_notifier: mpsc::Sender<ConnectionManagerEvent>,
     our_supported_protocols: Arc<Vec<ProtocolId>>,
     their_supported_protocols: Vec<ProtocolId>,
+    capabilities: NegotiatedCapabilities,
 ) -> PeerConnection {
     trace!(
         target: LOG_TARGET,
@@ -86,6 +89,7 @@ pub fn create(
     let (peer_tx, peer_rx) = mpsc::channel(1);
     let id = ID_COUNTER.fetch_add(1, Ordering::SeqCst); // Monotonic
     let substream_counter = connection.substream_counter();
+    let byte_counter = connection.byte_counter();
     let peer_conn = PeerConnection::new(
         id,
         peer_tx,
@@ -94,6 +98,8 @@ pub fn create(
         peer_addr,
         direction,
         substream_counter,
+        byte_counter,
+        capabilities,
     );
     let peer_actor = PeerConnectionActor::new(
         id,
@@ -104,6 +110,7 @@ pub fn create(
         event_notifier,
         our_supported_protocols,
         their_supported_protocols,
+        peer_conn.negotiated_protocols.clone(),
     );
     tokio::spawn(peer_actor.run());
 
@@ -125,6 +132,9 @@ pub enum PeerConnectionRequest {
 /// ID type for peer connections
 pub type ConnectionId = usize;
 
+/// The number of substreams that have been negotiated for each protocol over the lifetime of a connection
+type NegotiatedProtocols = Arc<RwLock<HashMap<ProtocolId, usize>>>;
+
 /// Request handle for an active peer connection
 #[derive(Debug, Clone)]
 pub struct PeerConnection {
@@ -136,7 +146,10 @@ pub struct PeerConnection {
     direction: ConnectionDirection,
     started_at: Instant,
     substream_counter: AtomicRefCounter,
+    byte_counter: ByteCounter,
+    negotiated_protocols: NegotiatedProtocols,
     handle_counter: Arc<()>,
+    capabilities: Arc<NegotiatedCapabilities>,
 }
 
 impl PeerConnection {
@@ -148,6 +161,8 @@ impl PeerConnection {
         address: Multiaddr,
         direction: ConnectionDirection,
         substream_counter: AtomicRefCounter,
+        byte_counter: ByteCounter,
+        capabilities: NegotiatedCapabilities,
     ) -> Self {
         Self {
             id,
@@ -158,7 +173,10 @@ impl PeerConnection {
             direction,
             started_at: Instant::now(),
             substream_counter,
+            byte_counter,
+            negotiated_protocols: Default::default(),
             handle_counter: Arc::new(()),
+            capabilities: Arc::new(capabilities),
         }
     }
 
@@ -174,6 +192,12 @@ impl PeerConnection {
         self.direction
     }
 
+    /// The optional protocol capabilities supported by both this node and the peer, and the version of each that
+    /// was selected during the identity exchange
+    pub fn capabilities(&self) -> &NegotiatedCapabilities {
+        &self.capabilities
+    }
+
     pub fn known_address(&self) -> Option<&Multiaddr> {
         if self.direction.is_outbound() {
             Some(self.address())
@@ -212,6 +236,28 @@ impl PeerConnection {
         Arc::strong_count(&self.handle_counter)
     }
 
+    /// Total number of bytes read from the peer over all substreams of this connection
+    pub fn bytes_read(&self) -> u64 {
+        self.byte_counter.bytes_read()
+    }
+
+    /// Total number of bytes written to the peer over all substreams of this connection
+    pub fn bytes_written(&self) -> u64 {
+        self.byte_counter.bytes_written()
+    }
+
+    /// Returns each protocol that has been negotiated on this connection, in either direction, along with the number
+    /// of substreams that were opened for it.
+    pub fn negotiated_protocols(&self) -> Vec<(ProtocolId, usize)> {
+        let lock = self
+            .negotiated_protocols
+            .read()
+            .expect("PeerConnection negotiated_protocols lock poisoned");
+        let mut protocols = lock.iter().map(|(p, n)| (p.clone(), *n)).collect::<Vec<_>>();
+        protocols.sort_by(|(a, _), (b, _)| a.cmp(b));
+        protocols
+    }
+
     pub async fn open_substream(
         &mut self,
         protocol_id: &ProtocolId,
@@ -333,6 +379,7 @@ struct PeerConnectionActor {
     inbound_protocol_negotiations:
         FuturesUnordered<BoxFuture<'static, Result<(ProtocolId, Substream), PeerConnectionError>>>,
     their_supported_protocols: Vec<ProtocolId>,
+    negotiated_protocols: NegotiatedProtocols,
 }
 
 impl PeerConnectionActor {
@@ -345,6 +392,7 @@ impl PeerConnectionActor {
         event_notifier: mpsc::Sender<ConnectionManagerEvent>,
         our_supported_protocols: Arc<Vec<ProtocolId>>,
         their_supported_protocols: Vec<ProtocolId>,
+        negotiated_protocols: NegotiatedProtocols,
     ) -> Self {
         Self {
             id,
@@ -357,6 +405,7 @@ impl PeerConnectionActor {
             our_supported_protocols,
             inbound_protocol_negotiations: FuturesUnordered::new(),
             their_supported_protocols,
+            negotiated_protocols,
         }
     }
 
@@ -449,6 +498,7 @@ impl PeerConnectionActor {
     ) {
         match result {
             Ok((selected_protocol, stream)) => {
+                self.record_negotiated_protocol(&selected_protocol);
                 self.notify_event(ConnectionManagerEvent::NewInboundSubstream(
                     self.peer_node_id.clone(),
                     selected_protocol,
@@ -507,9 +557,18 @@ impl PeerConnectionActor {
             time::timeout(PROTOCOL_NEGOTIATION_TIMEOUT, fut).await??
         };
 
+        self.record_negotiated_protocol(&selected_protocol);
         Ok(NegotiatedSubstream::new(selected_protocol, stream))
     }
 
+    fn record_negotiated_protocol(&self, protocol: &ProtocolId) {
+        let mut lock = self
+            .negotiated_protocols
+            .write()
+            .expect("PeerConnection negotiated_protocols lock poisoned");
+        *lock.entry(protocol.clone()).or_default() += 1;
+    }
+
     async fn notify_event(&mut self, event: ConnectionManagerEvent) {
         let _result = self.event_notifier.send(event).await;
     }
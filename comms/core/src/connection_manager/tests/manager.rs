@@ -290,6 +290,102 @@ async fn dial_success_aux_tcp_listener() {
     assert_eq!(buf, MSG);
 }
 
+#[tokio::test]
+#[allow(clippy::similar_names)]
+async fn dial_success_public_tcp_listener() {
+    static TEST_PROTO: ProtocolId = ProtocolId::from_static(b"/test/valid");
+    let shutdown = Shutdown::new();
+
+    let node_identity1 = build_node_identity(PeerFeatures::empty());
+    let node_identity2 = build_node_identity(PeerFeatures::empty());
+
+    let (proto_tx1, mut proto_rx1) = mpsc::channel(1);
+    let (proto_tx2, _) = mpsc::channel(1);
+
+    // Setup connection manager 1
+    let peer_manager1 = build_peer_manager();
+
+    let mut protocols = Protocols::new();
+    protocols.add([TEST_PROTO.clone()], &proto_tx1);
+    let mut conn_man1 = build_connection_manager(
+        {
+            let mut config = TestNodeConfig {
+                node_identity: node_identity1.clone(),
+                ..Default::default()
+            };
+            config.connection_manager_config.public_tcp_listener_address =
+                Some("/ip4/127.0.0.1/tcp/0".parse().unwrap());
+            config.connection_manager_config.network_info.user_agent = "node1".to_string();
+            config
+        },
+        MemoryTransport,
+        peer_manager1.clone(),
+        protocols,
+        shutdown.to_signal(),
+    );
+    // This is required for the test to pass. Because we do not have a Connectivity actor to receive the event and hold
+    // onto the PeerConnection handle, the connection would drop in this test.
+    let _event_sub1 = conn_man1.get_event_subscription();
+
+    let tcp_listener_addr = conn_man1
+        .wait_until_listening()
+        .await
+        .unwrap()
+        .public_tcp_bind_address()
+        .unwrap()
+        .clone();
+
+    let peer_manager2 = build_peer_manager();
+    peer_manager2
+        .add_peer(Peer::new(
+            node_identity1.public_key().clone(),
+            node_identity1.node_id().clone(),
+            MultiaddressesWithStats::from_addresses_with_source(vec![tcp_listener_addr], &PeerAddressSource::Config),
+            PeerFlags::empty(),
+            PeerFeatures::COMMUNICATION_CLIENT,
+            Default::default(),
+            Default::default(),
+        ))
+        .await
+        .unwrap();
+    let mut protocols = Protocols::new();
+    protocols.add([TEST_PROTO.clone()], &proto_tx2);
+    let mut conn_man2 = build_connection_manager(
+        {
+            let mut config = TestNodeConfig {
+                node_identity: node_identity2.clone(),
+                ..Default::default()
+            };
+            config.connection_manager_config.listener_address = "/ip4/127.0.0.1/tcp/0".parse().unwrap();
+            config.connection_manager_config.network_info.user_agent = "node2".to_string();
+            config
+        },
+        // Node 2 needs to use the tcp transport to connect to node1's tcp socket
+        TcpTransport::new(),
+        peer_manager2.clone(),
+        protocols,
+        shutdown.to_signal(),
+    );
+    conn_man2.wait_until_listening().await.unwrap();
+
+    let mut connection = conn_man2.dial_peer(node_identity1.node_id().clone()).await.unwrap();
+    assert_eq!(connection.peer_node_id(), node_identity1.node_id());
+
+    let mut substream_out = connection.open_substream(&TEST_PROTO).await.unwrap();
+
+    const MSG: &[u8] = b"Dual stack!";
+    substream_out.stream.write_all(MSG).await.unwrap();
+
+    let protocol_in = proto_rx1.recv().await.unwrap();
+    assert_eq!(protocol_in.protocol, &TEST_PROTO);
+    unpack_enum!(ProtocolEvent::NewInboundSubstream(node_id, substream_in) = protocol_in.event);
+    assert_eq!(&node_id, node_identity2.node_id());
+
+    let mut buf = [0u8; MSG.len()];
+    substream_in.read_exact(&mut buf).await.unwrap();
+    assert_eq!(buf, MSG);
+}
+
 #[tokio::test]
 async fn simultaneous_dial_events() {
     let mut shutdown = Shutdown::new();
@@ -52,6 +52,10 @@ pub struct ConnectivityConfig {
     /// The closest number of peer connections to maintain; connections above the threshold will be removed
     /// (default: disabled)
     pub maintain_n_closest_connections_only: Option<usize>,
+    /// The maximum number of inbound connections allowed from a single subnet (/24 for IPv4, /48 for IPv6). When this
+    /// limit is reached, further inbound connections from that subnet are rejected.
+    /// (default: disabled)
+    pub max_inbound_connections_per_subnet: Option<usize>,
 }
 
 impl Default for ConnectivityConfig {
@@ -66,6 +70,7 @@ impl Default for ConnectivityConfig {
             connection_tie_break_linger: Duration::from_secs(2),
             expire_peer_last_seen_duration: Duration::from_secs(24 * 60 * 60),
             maintain_n_closest_connections_only: None,
+            max_inbound_connections_per_subnet: None,
         }
     }
 }
@@ -44,6 +44,7 @@ use super::{
     error::ConnectivityError,
     requester::{ConnectivityEvent, ConnectivityRequest},
     selection::ConnectivitySelection,
+    subnet::SubnetGroup,
     ConnectivityEventTx,
 };
 use crate::{
@@ -280,6 +281,9 @@ impl ConnectivityManagerActor {
                     self.allow_list.remove(index);
                 }
             },
+            SetMaxInboundConnectionsPerSubnet(max_connections) => {
+                self.config.max_inbound_connections_per_subnet = max_connections;
+            },
             GetAllowList(reply) => {
                 let allow_list = self.allow_list.clone();
                 let _result = reply.send(allow_list);
@@ -630,6 +634,10 @@ impl ConnectivityManagerActor {
                     },
                     TieBreak::UseNew | TieBreak::None => {},
                 }
+                if self.enforce_subnet_connection_limit(new_conn).await {
+                    // The new connection was rejected and will not be added to the pool
+                    return Ok(());
+                }
             },
             PeerDisconnected(id, node_id, _minimized) => {
                 if let Some(conn) = self.pool.get_connection(node_id) {
@@ -825,6 +833,53 @@ impl ConnectivityManagerActor {
         }
     }
 
+    /// Enforces `max_inbound_connections_per_subnet` for a new inbound connection. If the subnet of the new connection
+    /// is at capacity, the new connection is rejected so that established connections cannot be displaced by an
+    /// attacker that opens many connections from the same subnet.
+    ///
+    /// Returns true if the new connection was rejected, otherwise false.
+    async fn enforce_subnet_connection_limit(&mut self, new_conn: &PeerConnection) -> bool {
+        let Some(max_connections) = self.config.max_inbound_connections_per_subnet else {
+            return false;
+        };
+        if !new_conn.direction().is_inbound() || self.allow_list.contains(new_conn.peer_node_id()) {
+            return false;
+        }
+        let Some(group) = SubnetGroup::from_address(new_conn.address()) else {
+            return false;
+        };
+
+        let num_group_conns = self
+            .pool
+            .filter_connection_states(|state| state.is_connected())
+            .into_iter()
+            .filter(|conn| {
+                conn.direction().is_inbound() &&
+                    conn.peer_node_id() != new_conn.peer_node_id() &&
+                    !self.allow_list.contains(conn.peer_node_id()) &&
+                    SubnetGroup::from_address(conn.address()) == Some(group)
+            })
+            .count();
+        if num_group_conns < max_connections {
+            return false;
+        }
+
+        debug!(
+            target: LOG_TARGET,
+            "Rejecting inbound connection (id: {}, peer: {}, address: {}) because the subnet connection limit ({}) was \
+             reached",
+            new_conn.id(),
+            new_conn.peer_node_id().short_str(),
+            new_conn.address(),
+            max_connections
+        );
+        #[cfg(feature = "metrics")]
+        super::metrics::subnet_rejections().inc();
+
+        let _result = new_conn.clone().disconnect_silent(Minimized::Yes).await;
+        true
+    }
+
     /// Two connections to the same peer have been created. This function deterministically determines which peer
     /// connection to close. It does this by comparing our NodeId to that of the peer. This rule enables both sides to
     /// agree which connection to disconnect
@@ -57,3 +57,15 @@ pub fn banned_peers_counter(peer: &NodeId) -> IntCounter {
 
     METER.with_label_values(&[peer.to_string().as_str()])
 }
+
+pub fn subnet_rejections() -> IntCounter {
+    static METER: Lazy<IntCounter> = Lazy::new(|| {
+        tari_metrics::register_int_counter(
+            "comms::connectivity::subnet_rejections",
+            "The number of inbound connections rejected because the subnet connection limit was reached",
+        )
+        .unwrap()
+    });
+
+    METER.clone()
+}
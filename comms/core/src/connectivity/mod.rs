@@ -51,5 +51,8 @@ pub use requester::{ConnectivityEvent, ConnectivityEventRx, ConnectivityEventTx,
 mod selection;
 pub use selection::ConnectivitySelection;
 
+mod subnet;
+pub use subnet::SubnetGroup;
+
 #[cfg(test)]
 mod test;
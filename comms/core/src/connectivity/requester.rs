@@ -106,6 +106,7 @@ pub enum ConnectivityRequest {
     GetAllowList(oneshot::Sender<Vec<NodeId>>),
     GetPeerStats(NodeId, oneshot::Sender<Option<Peer>>),
     GetNodeIdentity(oneshot::Sender<NodeIdentity>),
+    SetMaxInboundConnectionsPerSubnet(Option<usize>),
 }
 
 /// Handle to make requests and read events from the ConnectivityManager actor.
@@ -316,6 +317,19 @@ impl ConnectivityRequester {
         Ok(())
     }
 
+    /// Changes the maximum number of inbound connections allowed from a single subnet. Existing connections are not
+    /// affected, the new limit is enforced as new connections are made.
+    pub async fn set_max_inbound_connections_per_subnet(
+        &mut self,
+        max_connections: Option<usize>,
+    ) -> Result<(), ConnectivityError> {
+        self.sender
+            .send(ConnectivityRequest::SetMaxInboundConnectionsPerSubnet(max_connections))
+            .await
+            .map_err(|_| ConnectivityError::ActorDisconnected)?;
+        Ok(())
+    }
+
     /// Returns a Future that resolves when the connectivity actor has started.
     pub async fn wait_started(&mut self) -> Result<(), ConnectivityError> {
         let (reply_tx, reply_rx) = oneshot::channel();
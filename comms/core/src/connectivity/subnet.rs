@@ -0,0 +1,109 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::net::IpAddr;
+
+use multiaddr::{Multiaddr, Protocol};
+
+/// The network group of a peer address, used to limit the number of connections a single hosting provider or
+/// operator can occupy. IPv4 addresses are grouped by /24 and IPv6 addresses by /48.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SubnetGroup {
+    Ipv4([u8; 3]),
+    Ipv6([u8; 6]),
+}
+
+impl SubnetGroup {
+    /// Returns the subnet group for the given address, or None if the address is not a routable IP address (e.g.
+    /// onion, DNS, memory or loopback addresses). Inbound Tor connections arrive via the local tor proxy on a
+    /// loopback address and are therefore never grouped.
+    pub fn from_address(addr: &Multiaddr) -> Option<Self> {
+        let ip = match addr.iter().next()? {
+            Protocol::Ip4(ip) => IpAddr::V4(ip),
+            Protocol::Ip6(ip) => IpAddr::V6(ip),
+            _ => return None,
+        };
+        Self::from_ip(ip)
+    }
+
+    pub fn from_ip(ip: IpAddr) -> Option<Self> {
+        if ip.is_loopback() || ip.is_unspecified() {
+            return None;
+        }
+        match ip {
+            IpAddr::V4(ip) => {
+                let [a, b, c, _] = ip.octets();
+                Some(SubnetGroup::Ipv4([a, b, c]))
+            },
+            IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+                Some(ip) => Self::from_ip(IpAddr::V4(ip)),
+                None => {
+                    let mut prefix = [0u8; 6];
+                    prefix.copy_from_slice(&ip.octets()[..6]);
+                    Some(SubnetGroup::Ipv6(prefix))
+                },
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn group(addr: &str) -> Option<SubnetGroup> {
+        SubnetGroup::from_address(&addr.parse().unwrap())
+    }
+
+    #[test]
+    fn it_groups_ipv4_by_24() {
+        assert_eq!(group("/ip4/10.1.2.3/tcp/1234"), group("/ip4/10.1.2.200/tcp/4321"));
+        assert_ne!(group("/ip4/10.1.2.3/tcp/1234"), group("/ip4/10.1.3.3/tcp/1234"));
+        assert_eq!(group("/ip4/10.1.2.3/tcp/1234"), Some(SubnetGroup::Ipv4([10, 1, 2])));
+    }
+
+    #[test]
+    fn it_groups_ipv6_by_48() {
+        assert_eq!(
+            group("/ip6/2001:db8:1::1/tcp/1234"),
+            group("/ip6/2001:db8:1:ffff::2/tcp/1234")
+        );
+        assert_ne!(
+            group("/ip6/2001:db8:1::1/tcp/1234"),
+            group("/ip6/2001:db8:2::1/tcp/1234")
+        );
+    }
+
+    #[test]
+    fn it_groups_ipv4_mapped_ipv6_as_ipv4() {
+        assert_eq!(group("/ip6/::ffff:10.1.2.3/tcp/1234"), group("/ip4/10.1.2.4/tcp/1234"));
+    }
+
+    #[test]
+    fn it_does_not_group_non_routable_addresses() {
+        assert!(group("/ip4/127.0.0.1/tcp/1234").is_none());
+        assert!(group("/ip6/::1/tcp/1234").is_none());
+        assert!(group("/memory/1234").is_none());
+        assert!(group("/dns4/example.com/tcp/1234").is_none());
+        assert!(group("/onion3/vww6ybal4bd7szmgncyruucpgfkqahzddi37ktceo3ah7ngmcopnpyyd:1234").is_none());
+    }
+}
@@ -25,7 +25,10 @@ use std::{sync::Arc, time::Duration};
 use futures::{future, StreamExt};
 use tari_shutdown::Shutdown;
 use tari_test_utils::{collect_try_recv, streams, unpack_enum};
-use tokio::sync::{broadcast, mpsc};
+use tokio::{
+    sync::{broadcast, mpsc},
+    time,
+};
 
 use super::{
     config::ConnectivityConfig,
@@ -35,12 +38,17 @@ use super::{
     selection::ConnectivitySelection,
 };
 use crate::{
-    connection_manager::{ConnectionManagerError, ConnectionManagerEvent},
+    connection_manager::{ConnectionManagerError, ConnectionManagerEvent, PeerConnectionRequest},
     connectivity::ConnectivityEventRx,
     peer_manager::{Peer, PeerFeatures},
     test_utils::{
         build_peer_manager,
-        mocks::{create_connection_manager_mock, create_peer_connection_mock_pair, ConnectionManagerMockState},
+        mocks::{
+            create_connection_manager_mock,
+            create_dummy_peer_connection,
+            create_peer_connection_mock_pair,
+            ConnectionManagerMockState,
+        },
         node_identity::{build_many_node_identities, build_node_identity},
     },
     Minimized,
@@ -457,3 +465,43 @@ async fn pool_management() {
     let conns = connectivity.get_active_connections().await.unwrap();
     assert!(conns.is_empty());
 }
+
+#[tokio::test]
+async fn it_rejects_inbound_connections_above_the_subnet_limit() {
+    let (mut connectivity, mut event_stream, _node_identity, peer_manager, cm_mock_state, _shutdown) =
+        setup_connectivity_manager(ConnectivityConfig {
+            max_inbound_connections_per_subnet: Some(2),
+            ..Default::default()
+        });
+    let peers = add_test_peers(&peer_manager, 3).await;
+    // All dummy connections are inbound from the same /24 subnet
+    let (connections, mut request_rxs): (Vec<_>, Vec<_>) = peers
+        .iter()
+        .map(|peer| create_dummy_peer_connection(peer.node_id.clone()))
+        .unzip();
+
+    let mut events = collect_try_recv!(event_stream, take = 1, timeout = Duration::from_secs(10));
+    unpack_enum!(ConnectivityEvent::ConnectivityStateInitialized = events.remove(0));
+
+    for conn in &connections {
+        cm_mock_state.publish_event(ConnectionManagerEvent::PeerConnected(conn.clone().into()));
+    }
+
+    // The newest connection is rejected
+    let request = time::timeout(Duration::from_secs(10), request_rxs[2].recv())
+        .await
+        .unwrap()
+        .unwrap();
+    unpack_enum!(PeerConnectionRequest::Disconnect(is_silent, reply_tx, _minimized) = request);
+    assert!(is_silent);
+    reply_tx.send(Ok(())).unwrap();
+
+    // The established connections are kept
+    let conns = connectivity.get_active_connections().await.unwrap();
+    assert_eq!(conns.len(), 2);
+    for conn in connections.iter().take(2) {
+        assert!(conns.iter().any(|c| c.peer_node_id() == conn.peer_node_id()));
+    }
+    assert!(request_rxs[0].try_recv().is_err());
+    assert!(request_rxs[1].try_recv().is_err());
+}
@@ -20,7 +20,17 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{future::poll_fn, io, marker::PhantomData, pin::Pin, task::Poll};
+use std::{
+    future::poll_fn,
+    io,
+    marker::PhantomData,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    task::Poll,
+};
 
 use futures::{channel::oneshot, task::Context, Stream};
 use tokio::{
@@ -46,6 +56,7 @@ pub struct Yamux {
     control: Control,
     incoming: IncomingSubstreams,
     substream_counter: AtomicRefCounter,
+    byte_counter: ByteCounter,
 }
 
 impl Yamux {
@@ -60,13 +71,16 @@ impl Yamux {
         let config = yamux::Config::default();
 
         let substream_counter = AtomicRefCounter::new();
+        let byte_counter = ByteCounter::default();
         let connection = yamux::Connection::new(socket.compat(), config, mode);
-        let (control, incoming) = Self::spawn_incoming_stream_worker(connection, substream_counter.clone());
+        let (control, incoming) =
+            Self::spawn_incoming_stream_worker(connection, substream_counter.clone(), byte_counter.clone());
 
         Ok(Self {
             control,
             incoming,
             substream_counter,
+            byte_counter,
         })
     }
 
@@ -75,16 +89,17 @@ impl Yamux {
     fn spawn_incoming_stream_worker<TSocket>(
         connection: yamux::Connection<TSocket>,
         counter: AtomicRefCounter,
+        byte_counter: ByteCounter,
     ) -> (Control, IncomingSubstreams)
     where
         TSocket: futures::AsyncRead + futures::AsyncWrite + Unpin + Send + Sync + 'static,
     {
         let (incoming_tx, incoming_rx) = mpsc::channel(10);
         let (request_tx, request_rx) = mpsc::channel(1);
-        let incoming = YamuxWorker::new(incoming_tx, request_rx, counter.clone());
+        let incoming = YamuxWorker::new(incoming_tx, request_rx, counter.clone(), byte_counter.clone());
         let control = Control::new(request_tx);
         tokio::spawn(incoming.run(connection));
-        (control, IncomingSubstreams::new(incoming_rx, counter))
+        (control, IncomingSubstreams::new(incoming_rx, counter, byte_counter))
     }
 
     /// Get the yamux control struct
@@ -111,6 +126,38 @@ impl Yamux {
     pub(crate) fn substream_counter(&self) -> AtomicRefCounter {
         self.substream_counter.clone()
     }
+
+    /// Return the ByteCounter that tallies bytes read and written on all substreams of this connection
+    pub fn byte_counter(&self) -> ByteCounter {
+        self.byte_counter.clone()
+    }
+}
+
+/// Tallies the total number of bytes read from and written to all substreams of a single connection
+#[derive(Debug, Clone, Default)]
+pub struct ByteCounter {
+    read: Arc<AtomicU64>,
+    written: Arc<AtomicU64>,
+}
+
+impl ByteCounter {
+    /// Total number of bytes read from the remote
+    pub fn bytes_read(&self) -> u64 {
+        self.read.load(Ordering::Relaxed)
+    }
+
+    /// Total number of bytes written to the remote
+    pub fn bytes_written(&self) -> u64 {
+        self.written.load(Ordering::Relaxed)
+    }
+
+    fn add_read(&self, n: usize) {
+        self.read.fetch_add(n as u64, Ordering::Relaxed);
+    }
+
+    fn add_written(&self, n: usize) {
+        self.written.fetch_add(n as u64, Ordering::Relaxed);
+    }
 }
 
 #[derive(Debug)]
@@ -152,13 +199,19 @@ impl Control {
 pub struct IncomingSubstreams {
     inner: mpsc::Receiver<yamux::Stream>,
     substream_counter: AtomicRefCounter,
+    byte_counter: ByteCounter,
 }
 
 impl IncomingSubstreams {
-    pub(self) fn new(inner: mpsc::Receiver<yamux::Stream>, substream_counter: AtomicRefCounter) -> Self {
+    pub(self) fn new(
+        inner: mpsc::Receiver<yamux::Stream>,
+        substream_counter: AtomicRefCounter,
+        byte_counter: ByteCounter,
+    ) -> Self {
         Self {
             inner,
             substream_counter,
+            byte_counter,
         }
     }
 
@@ -175,6 +228,7 @@ impl Stream for IncomingSubstreams {
             Some(stream) => Poll::Ready(Some(Substream {
                 stream: stream.compat(),
                 _counter_guard: self.substream_counter.new_guard(),
+                byte_counter: self.byte_counter.clone(),
             })),
             None => Poll::Ready(None),
         }
@@ -186,6 +240,7 @@ impl Stream for IncomingSubstreams {
 pub struct Substream {
     stream: Compat<yamux::Stream>,
     _counter_guard: AtomicRefCounterGuard,
+    byte_counter: ByteCounter,
 }
 
 impl StreamId for Substream {
@@ -198,6 +253,7 @@ impl tokio::io::AsyncRead for Substream {
     fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<io::Result<()>> {
         match Pin::new(&mut self.stream).poll_read(cx, buf) {
             Poll::Ready(Ok(())) => {
+                self.byte_counter.add_read(buf.filled().len());
                 #[cfg(feature = "metrics")]
                 super::metrics::TOTAL_BYTES_READ.inc_by(buf.filled().len() as u64);
                 Poll::Ready(Ok(()))
@@ -211,7 +267,13 @@ impl tokio::io::AsyncWrite for Substream {
     fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
         #[cfg(feature = "metrics")]
         super::metrics::TOTAL_BYTES_WRITTEN.inc_by(buf.len() as u64);
-        Pin::new(&mut self.stream).poll_write(cx, buf)
+        match Pin::new(&mut self.stream).poll_write(cx, buf) {
+            Poll::Ready(Ok(n)) => {
+                self.byte_counter.add_written(n);
+                Poll::Ready(Ok(n))
+            },
+            res => res,
+        }
     }
 
     fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
@@ -233,6 +295,7 @@ struct YamuxWorker<TSocket> {
     incoming_substreams: mpsc::Sender<yamux::Stream>,
     request_rx: mpsc::Receiver<YamuxRequest>,
     counter: AtomicRefCounter,
+    byte_counter: ByteCounter,
     _phantom: PhantomData<TSocket>,
 }
 
@@ -243,11 +306,13 @@ where TSocket: futures::AsyncRead + futures::AsyncWrite + Unpin + Send + Sync +
         incoming_substreams: mpsc::Sender<yamux::Stream>,
         request_rx: mpsc::Receiver<YamuxRequest>,
         counter: AtomicRefCounter,
+        byte_counter: ByteCounter,
     ) -> Self {
         Self {
             incoming_substreams,
             request_rx,
             counter,
+            byte_counter,
             _phantom: PhantomData,
         }
     }
@@ -323,6 +388,7 @@ where TSocket: futures::AsyncRead + futures::AsyncWrite + Unpin + Send + Sync +
                     .send(result.map(|stream| Substream {
                         stream: stream.compat(),
                         _counter_guard: self.counter.new_guard(),
+                        byte_counter: self.byte_counter.clone(),
                     }))
                     .is_err()
                 {
@@ -433,6 +499,40 @@ mod test {
         assert_eq!(listener.substream_count(), 0);
     }
 
+    #[tokio::test]
+    async fn byte_count() -> io::Result<()> {
+        let (dialer, listener) = MemorySocket::new_pair();
+        let msg = b"Oathbringer";
+
+        let dialer = Yamux::upgrade_connection(dialer, ConnectionDirection::Outbound)?;
+        let dialer_bytes = dialer.byte_counter();
+        let mut dialer_control = dialer.get_yamux_control();
+
+        let mut listener = Yamux::upgrade_connection(listener, ConnectionDirection::Inbound)?;
+        let listener_bytes = listener.byte_counter();
+
+        let mut substream = dialer_control.open_stream().await.unwrap();
+        substream.write_all(msg).await?;
+        substream.shutdown().await?;
+
+        let mut substream = listener
+            .incoming
+            .next()
+            .await
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "no substream"))?;
+
+        let mut buf = Vec::new();
+        substream.read_to_end(&mut buf).await?;
+        assert_eq!(buf, msg);
+
+        assert_eq!(dialer_bytes.bytes_written(), msg.len() as u64);
+        assert_eq!(dialer_bytes.bytes_read(), 0);
+        assert_eq!(listener_bytes.bytes_read(), msg.len() as u64);
+        assert_eq!(listener_bytes.bytes_written(), 0);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn close() -> io::Result<()> {
         let (dialer, listener) = MemorySocket::new_pair();
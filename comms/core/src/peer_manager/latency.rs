@@ -0,0 +1,269 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use crate::peer_manager::NodeId;
+
+/// Upper bounds (in milliseconds) of the RTT histogram buckets. Samples greater than the last bound are counted in an
+/// overflow bucket.
+const RTT_BUCKET_BOUNDS_MS: [u64; 8] = [50, 100, 250, 500, 1_000, 2_500, 5_000, 10_000];
+const NUM_RTT_BUCKETS: usize = RTT_BUCKET_BOUNDS_MS.len() + 1;
+/// Once this many samples are held, all buckets are halved so that recent measurements dominate the histogram.
+const MAX_HISTOGRAM_SAMPLES: u32 = 100;
+/// A peer needs at least this many samples before it can be considered persistently slow.
+const MIN_SAMPLES_FOR_PENALTY: u32 = 5;
+/// A peer whose median RTT is at or above this threshold is considered persistently slow.
+pub const SLOW_PEER_RTT_THRESHOLD: Duration = Duration::from_millis(2_500);
+
+/// A decaying histogram of round-trip times measured for a single peer.
+#[derive(Debug, Clone, Default)]
+pub struct RttHistogram {
+    buckets: [u32; NUM_RTT_BUCKETS],
+    num_samples: u32,
+    total_millis: u64,
+    last_rtt: Option<Duration>,
+}
+
+impl RttHistogram {
+    /// Add an RTT sample to the histogram
+    pub fn add_sample(&mut self, rtt: Duration) {
+        if self.num_samples >= MAX_HISTOGRAM_SAMPLES {
+            self.decay();
+        }
+        let millis = u64::try_from(rtt.as_millis()).unwrap_or(u64::MAX);
+        let index = RTT_BUCKET_BOUNDS_MS
+            .iter()
+            .position(|bound| millis <= *bound)
+            .unwrap_or(NUM_RTT_BUCKETS - 1);
+        self.buckets[index] += 1;
+        self.num_samples += 1;
+        self.total_millis = self.total_millis.saturating_add(millis);
+        self.last_rtt = Some(rtt);
+    }
+
+    fn decay(&mut self) {
+        for bucket in &mut self.buckets {
+            *bucket /= 2;
+        }
+        let num_samples = self.buckets.iter().sum::<u32>();
+        self.total_millis = self
+            .total_millis
+            .checked_div(u64::from(self.num_samples))
+            .unwrap_or(0)
+            .saturating_mul(u64::from(num_samples));
+        self.num_samples = num_samples;
+    }
+
+    /// Returns the upper bound of the bucket that contains the given quantile (0.0 - 1.0), or None if no samples
+    /// have been recorded.
+    pub fn quantile(&self, quantile: f64) -> Option<Duration> {
+        if self.num_samples == 0 {
+            return None;
+        }
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let target = ((f64::from(self.num_samples) * quantile.clamp(0.0, 1.0)).ceil() as u32).max(1);
+        let mut count = 0;
+        for (i, bucket) in self.buckets.iter().enumerate() {
+            count += *bucket;
+            if count >= target {
+                let bound = RTT_BUCKET_BOUNDS_MS
+                    .get(i)
+                    .copied()
+                    .unwrap_or(RTT_BUCKET_BOUNDS_MS[RTT_BUCKET_BOUNDS_MS.len() - 1]);
+                return Some(Duration::from_millis(bound));
+            }
+        }
+        None
+    }
+
+    /// Returns a snapshot of the statistics for this histogram, or None if no samples have been recorded.
+    pub fn stats(&self) -> Option<RttStats> {
+        let avg = self.total_millis.checked_div(u64::from(self.num_samples))?;
+        Some(RttStats {
+            avg: Duration::from_millis(avg),
+            p50: self.quantile(0.5)?,
+            p90: self.quantile(0.9)?,
+            last: self.last_rtt?,
+            num_samples: self.num_samples,
+        })
+    }
+}
+
+/// A snapshot of the RTT measurements of a peer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RttStats {
+    /// The mean RTT of the samples in the histogram
+    pub avg: Duration,
+    /// The upper bound of the histogram bucket containing the median RTT
+    pub p50: Duration,
+    /// The upper bound of the histogram bucket containing the 90th percentile RTT
+    pub p90: Duration,
+    /// The most recently measured RTT
+    pub last: Duration,
+    /// The number of (decayed) samples in the histogram
+    pub num_samples: u32,
+}
+
+impl RttStats {
+    /// Returns true if enough samples have been recorded and the median RTT is at or above the
+    /// [SLOW_PEER_RTT_THRESHOLD].
+    pub fn is_persistently_slow(&self) -> bool {
+        self.num_samples >= MIN_SAMPLES_FOR_PENALTY && self.p50 >= SLOW_PEER_RTT_THRESHOLD
+    }
+}
+
+impl fmt::Display for RttStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "avg: {:.0?}, p50: <={:.0?}, p90: <={:.0?}, last: {:.0?} ({} sample(s))",
+            self.avg, self.p50, self.p90, self.last, self.num_samples
+        )?;
+        if self.is_persistently_slow() {
+            write!(f, " SLOW")?;
+        }
+        Ok(())
+    }
+}
+
+/// Records RTT histograms for peers from liveness pings and RPC round trips. Measurements are kept in memory only.
+/// This type is cheap to clone and all clones share the same measurements.
+#[derive(Debug, Clone, Default)]
+pub struct PeerLatencyTracker {
+    histograms: Arc<RwLock<HashMap<NodeId, RttHistogram>>>,
+}
+
+impl PeerLatencyTracker {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Record an RTT sample for the given peer
+    pub fn record(&self, node_id: &NodeId, rtt: Duration) {
+        let mut lock = self.histograms.write().expect("PeerLatencyTracker lock poisoned");
+        match lock.get_mut(node_id) {
+            Some(histogram) => histogram.add_sample(rtt),
+            None => {
+                let mut histogram = RttHistogram::default();
+                histogram.add_sample(rtt);
+                lock.insert(node_id.clone(), histogram);
+            },
+        }
+    }
+
+    /// Returns the RTT statistics for the given peer, if any samples have been recorded
+    pub fn get_stats(&self, node_id: &NodeId) -> Option<RttStats> {
+        self.histograms
+            .read()
+            .expect("PeerLatencyTracker lock poisoned")
+            .get(node_id)
+            .and_then(|h| h.stats())
+    }
+
+    /// Returns true if the given peer has been persistently slow to respond
+    pub fn is_persistently_slow(&self, node_id: &NodeId) -> bool {
+        self.get_stats(node_id).is_some_and(|s| s.is_persistently_slow())
+    }
+
+    /// Removes all measurements for the given peer
+    pub fn remove(&self, node_id: &NodeId) {
+        self.histograms
+            .write()
+            .expect("PeerLatencyTracker lock poisoned")
+            .remove(node_id);
+    }
+
+    /// Sorts the given items so that peers with a low average RTT come first, followed by peers for which no RTT has
+    /// been measured. Persistently slow peers are always placed last. The sort is stable so that the existing order is
+    /// preserved between peers that compare equal.
+    pub fn sort_by_latency<T, F>(&self, items: &mut [T], get_node_id: F)
+    where F: Fn(&T) -> &NodeId {
+        let lock = self.histograms.read().expect("PeerLatencyTracker lock poisoned");
+        items.sort_by_cached_key(|item| match lock.get(get_node_id(item)).and_then(|h| h.stats()) {
+            Some(stats) if stats.is_persistently_slow() => (2u8, stats.avg),
+            Some(stats) => (0, stats.avg),
+            None => (1, Duration::ZERO),
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn node_id(byte: u8) -> NodeId {
+        NodeId::try_from(vec![byte; NodeId::byte_size()].as_slice()).unwrap()
+    }
+
+    #[test]
+    fn it_calculates_stats() {
+        let mut histogram = RttHistogram::default();
+        assert!(histogram.stats().is_none());
+        for ms in [40, 60, 80, 200, 3000] {
+            histogram.add_sample(Duration::from_millis(ms));
+        }
+        let stats = histogram.stats().unwrap();
+        assert_eq!(stats.num_samples, 5);
+        assert_eq!(stats.avg, Duration::from_millis(676));
+        assert_eq!(stats.p50, Duration::from_millis(100));
+        assert_eq!(stats.p90, Duration::from_millis(5_000));
+        assert_eq!(stats.last, Duration::from_millis(3000));
+        assert!(!stats.is_persistently_slow());
+    }
+
+    #[test]
+    fn it_decays_old_samples() {
+        let mut histogram = RttHistogram::default();
+        for _ in 0..MAX_HISTOGRAM_SAMPLES {
+            histogram.add_sample(Duration::from_millis(20));
+        }
+        for _ in 0..MAX_HISTOGRAM_SAMPLES {
+            histogram.add_sample(Duration::from_secs(4));
+        }
+        let stats = histogram.stats().unwrap();
+        assert!(stats.num_samples <= MAX_HISTOGRAM_SAMPLES);
+        assert!(stats.is_persistently_slow());
+    }
+
+    #[test]
+    fn it_sorts_slow_peers_last() {
+        let tracker = PeerLatencyTracker::new();
+        let (fast, unknown, slow, medium) = (node_id(1), node_id(2), node_id(3), node_id(4));
+        for _ in 0..MIN_SAMPLES_FOR_PENALTY {
+            tracker.record(&fast, Duration::from_millis(30));
+            tracker.record(&slow, Duration::from_secs(6));
+            tracker.record(&medium, Duration::from_millis(400));
+        }
+        assert!(tracker.is_persistently_slow(&slow));
+        assert!(!tracker.is_persistently_slow(&unknown));
+
+        let mut peers = vec![slow.clone(), unknown.clone(), medium.clone(), fast.clone()];
+        tracker.sort_by_latency(&mut peers, |n| n);
+        assert_eq!(peers, vec![fast, medium, unknown, slow]);
+    }
+}
@@ -39,8 +39,10 @@ use crate::{
         NodeDistance,
         NodeId,
         PeerFeatures,
+        PeerLatencyTracker,
         PeerManagerError,
         PeerQuery,
+        SyncPeerQualityTracker,
     },
     types::{CommsDatabase, CommsPublicKey},
 };
@@ -50,6 +52,8 @@ use crate::{
 pub struct PeerManager {
     // yo dawg, I heard you like wrappers, so I wrapped your wrapper in a wrapper so you can wrap while you wrap
     peer_storage: RwLock<PeerStorage<CachedStore<PeerId, Peer, KeyValueWrapper<CommsDatabase>>>>,
+    latency_tracker: PeerLatencyTracker,
+    sync_quality_tracker: SyncPeerQualityTracker,
     _file_lock: Option<File>,
 }
 
@@ -59,6 +63,8 @@ impl PeerManager {
         let storage = PeerStorage::new_indexed(CachedStore::new(KeyValueWrapper::new(database)))?;
         Ok(Self {
             peer_storage: RwLock::new(storage),
+            latency_tracker: PeerLatencyTracker::new(),
+            sync_quality_tracker: SyncPeerQualityTracker::new(),
             _file_lock: file_lock,
         })
     }
@@ -68,6 +74,16 @@ impl PeerManager {
         migrations::migrate(database).map_err(|err| PeerManagerError::MigrationError(err.to_string()))
     }
 
+    /// Returns the in-memory RTT histograms recorded for peers
+    pub fn latency_tracker(&self) -> &PeerLatencyTracker {
+        &self.latency_tracker
+    }
+
+    /// Returns the in-memory chain sync quality statistics recorded for peers
+    pub fn sync_quality_tracker(&self) -> &SyncPeerQualityTracker {
+        &self.sync_quality_tracker
+    }
+
     pub async fn count(&self) -> usize {
         self.peer_storage.read().await.count()
     }
@@ -90,6 +106,7 @@ impl PeerManager {
     pub async fn delete_peer(&self, node_id: &NodeId) -> Result<(), PeerManagerError> {
         let mut lock = self.peer_storage.write().await;
         lock.delete_peer(node_id)?;
+        self.latency_tracker.remove(node_id);
         #[cfg(feature = "metrics")]
         {
             let count = lock.count();
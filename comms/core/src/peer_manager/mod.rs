@@ -96,6 +96,12 @@ pub use peer_features::PeerFeatures;
 mod peer_id;
 pub(crate) use peer_id::PeerId;
 
+mod latency;
+pub use latency::{PeerLatencyTracker, RttHistogram, RttStats, SLOW_PEER_RTT_THRESHOLD};
+
+mod sync_quality;
+pub use sync_quality::{SyncPeerQualityTracker, SyncQualityStats};
+
 mod manager;
 pub use manager::PeerManager;
 
@@ -0,0 +1,252 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+use crate::peer_manager::NodeId;
+
+/// Once this much transfer time has been recorded for a peer, the recorded bytes and time are halved so that recent
+/// transfers dominate the throughput.
+const THROUGHPUT_WINDOW: Duration = Duration::from_secs(60);
+/// A peer that stalled this many sync attempts in a row is only selected after all other sync peers.
+const MAX_CONSECUTIVE_STALLS: u32 = 3;
+
+#[derive(Debug, Clone, Default)]
+struct SyncQuality {
+    bytes_received: u64,
+    transfer_time: Duration,
+    num_stalls: u32,
+    consecutive_stalls: u32,
+    num_invalid_data: u32,
+    blacklisted_until: Option<Instant>,
+    blacklist_reason: Option<String>,
+}
+
+impl SyncQuality {
+    fn add_transfer(&mut self, bytes: u64, elapsed: Duration) {
+        if self.transfer_time >= THROUGHPUT_WINDOW {
+            self.bytes_received /= 2;
+            self.transfer_time /= 2;
+        }
+        self.bytes_received = self.bytes_received.saturating_add(bytes);
+        self.transfer_time = self.transfer_time.saturating_add(elapsed);
+    }
+
+    fn is_blacklisted(&self, now: Instant) -> bool {
+        self.blacklisted_until.is_some_and(|until| until > now)
+    }
+
+    fn stats(&self, now: Instant) -> SyncQualityStats {
+        let millis = u64::try_from(self.transfer_time.as_millis()).unwrap_or(u64::MAX);
+        SyncQualityStats {
+            bytes_per_sec: self
+                .bytes_received
+                .saturating_mul(1000)
+                .checked_div(millis)
+                .unwrap_or_default(),
+            num_stalls: self.num_stalls,
+            consecutive_stalls: self.consecutive_stalls,
+            num_invalid_data: self.num_invalid_data,
+            blacklisted_for: self
+                .blacklisted_until
+                .and_then(|until| until.checked_duration_since(now))
+                .filter(|remaining| !remaining.is_zero()),
+            blacklist_reason: self.blacklist_reason.clone(),
+        }
+    }
+}
+
+/// A snapshot of how well a peer has served chain sync data
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncQualityStats {
+    /// The recent rate at which the peer sent sync data
+    pub bytes_per_sec: u64,
+    /// The number of times the peer was too slow to continue syncing from
+    pub num_stalls: u32,
+    /// The number of sync attempts in a row that the peer was too slow for
+    pub consecutive_stalls: u32,
+    /// The number of times the peer sent invalid sync data
+    pub num_invalid_data: u32,
+    /// The time left before the peer may be selected for sync again, if it is blacklisted
+    pub blacklisted_for: Option<Duration>,
+    /// The reason the peer was last blacklisted
+    pub blacklist_reason: Option<String>,
+}
+
+impl SyncQualityStats {
+    /// Returns true if the peer stalled too many sync attempts in a row to be preferred as a sync peer
+    pub fn is_poor(&self) -> bool {
+        self.consecutive_stalls >= MAX_CONSECUTIVE_STALLS
+    }
+}
+
+impl fmt::Display for SyncQualityStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} B/s, {} stall(s) ({} consecutive), {} invalid",
+            self.bytes_per_sec, self.num_stalls, self.consecutive_stalls, self.num_invalid_data
+        )?;
+        if self.is_poor() {
+            write!(f, " POOR")?;
+        }
+        if let Some(remaining) = self.blacklisted_for {
+            write!(
+                f,
+                ", blacklisted for {:.0?} ({})",
+                remaining,
+                self.blacklist_reason.as_deref().unwrap_or("unknown reason")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Records the throughput, stalls and invalid data of peers serving chain sync, and temporarily blacklists peers that
+/// served invalid data so that sync does not select them again. Statistics are kept in memory only. This type is cheap
+/// to clone and all clones share the same statistics.
+#[derive(Debug, Clone, Default)]
+pub struct SyncPeerQualityTracker {
+    peers: Arc<RwLock<HashMap<NodeId, SyncQuality>>>,
+}
+
+impl SyncPeerQualityTracker {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    fn update<F>(&self, node_id: &NodeId, f: F)
+    where F: FnOnce(&mut SyncQuality) {
+        let mut lock = self.peers.write().expect("SyncPeerQualityTracker lock poisoned");
+        f(lock.entry(node_id.clone()).or_default());
+    }
+
+    /// Record that `bytes` of sync data were received from the peer in `elapsed` time
+    pub fn record_transfer(&self, node_id: &NodeId, bytes: u64, elapsed: Duration) {
+        self.update(node_id, |quality| quality.add_transfer(bytes, elapsed));
+    }
+
+    /// Record that the peer was too slow to continue syncing from
+    pub fn record_stall(&self, node_id: &NodeId) {
+        self.update(node_id, |quality| {
+            quality.num_stalls += 1;
+            quality.consecutive_stalls += 1;
+        });
+    }
+
+    /// Record that a sync with the peer completed
+    pub fn record_sync_completed(&self, node_id: &NodeId) {
+        self.update(node_id, |quality| quality.consecutive_stalls = 0);
+    }
+
+    /// Record that the peer sent invalid sync data, and blacklist it as a sync peer for `period`
+    pub fn blacklist(&self, node_id: &NodeId, reason: String, period: Duration) {
+        self.update(node_id, |quality| {
+            quality.num_invalid_data += 1;
+            quality.blacklisted_until = Instant::now().checked_add(period);
+            quality.blacklist_reason = Some(reason);
+        });
+    }
+
+    /// Returns true if the peer may not be selected for sync until its blacklisting expires
+    pub fn is_blacklisted(&self, node_id: &NodeId) -> bool {
+        self.peers
+            .read()
+            .expect("SyncPeerQualityTracker lock poisoned")
+            .get(node_id)
+            .is_some_and(|q| q.is_blacklisted(Instant::now()))
+    }
+
+    /// Returns the sync quality statistics for the given peer, if it has served sync data
+    pub fn get_stats(&self, node_id: &NodeId) -> Option<SyncQualityStats> {
+        self.peers
+            .read()
+            .expect("SyncPeerQualityTracker lock poisoned")
+            .get(node_id)
+            .map(|q| q.stats(Instant::now()))
+    }
+
+    /// Removes blacklisted peers from the given sync peer candidates, unless every candidate is blacklisted, and moves
+    /// poor peers to the end so that sync rotates away from them. The sort is stable so that the existing order is
+    /// preserved between the remaining peers.
+    pub fn rank_sync_peers<T, F>(&self, items: &mut Vec<T>, get_node_id: F)
+    where F: Fn(&T) -> &NodeId {
+        let now = Instant::now();
+        let lock = self.peers.read().expect("SyncPeerQualityTracker lock poisoned");
+        if items
+            .iter()
+            .any(|item| !lock.get(get_node_id(item)).is_some_and(|q| q.is_blacklisted(now)))
+        {
+            items.retain(|item| !lock.get(get_node_id(item)).is_some_and(|q| q.is_blacklisted(now)));
+        }
+        items.sort_by_cached_key(|item| {
+            lock.get(get_node_id(item))
+                .is_some_and(|q| q.consecutive_stalls >= MAX_CONSECUTIVE_STALLS)
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn node_id(byte: u8) -> NodeId {
+        NodeId::try_from(vec![byte; NodeId::byte_size()].as_slice()).unwrap()
+    }
+
+    #[test]
+    fn it_calculates_throughput() {
+        let tracker = SyncPeerQualityTracker::new();
+        let peer = node_id(1);
+        assert!(tracker.get_stats(&peer).is_none());
+        tracker.record_transfer(&peer, 5_000, Duration::from_millis(500));
+        tracker.record_transfer(&peer, 5_000, Duration::from_millis(500));
+        let stats = tracker.get_stats(&peer).unwrap();
+        assert_eq!(stats.bytes_per_sec, 10_000);
+        assert_eq!(stats.num_stalls, 0);
+        assert!(stats.blacklisted_for.is_none());
+    }
+
+    #[test]
+    fn it_blacklists_peers_that_sent_invalid_data() {
+        let tracker = SyncPeerQualityTracker::new();
+        let (bad, expired) = (node_id(1), node_id(2));
+        tracker.blacklist(&bad, "invalid header".to_string(), Duration::from_secs(60));
+        tracker.blacklist(&expired, "invalid block".to_string(), Duration::ZERO);
+        assert!(tracker.is_blacklisted(&bad));
+        assert!(!tracker.is_blacklisted(&expired));
+
+        let stats = tracker.get_stats(&bad).unwrap();
+        assert_eq!(stats.num_invalid_data, 1);
+        assert!(stats.blacklisted_for.is_some());
+        assert_eq!(stats.blacklist_reason.as_deref(), Some("invalid header"));
+    }
+
+    #[test]
+    fn it_ranks_sync_peers() {
+        let tracker = SyncPeerQualityTracker::new();
+        let (stalling, bad, good, unknown) = (node_id(1), node_id(2), node_id(3), node_id(4));
+        for _ in 0..MAX_CONSECUTIVE_STALLS {
+            tracker.record_stall(&stalling);
+            tracker.record_stall(&good);
+        }
+        tracker.record_sync_completed(&good);
+        tracker.blacklist(&bad, "invalid block".to_string(), Duration::from_secs(60));
+        assert!(tracker.get_stats(&stalling).unwrap().is_poor());
+        assert!(!tracker.get_stats(&good).unwrap().is_poor());
+
+        let mut peers = vec![stalling.clone(), bad.clone(), good.clone(), unknown.clone()];
+        tracker.rank_sync_peers(&mut peers, |n| n);
+        assert_eq!(peers, vec![good, unknown, stalling]);
+
+        // A blacklisted peer is kept if there is no other sync peer
+        let mut peers = vec![bad.clone()];
+        tracker.rank_sync_peers(&mut peers, |n| n);
+        assert_eq!(peers, vec![bad]);
+    }
+}
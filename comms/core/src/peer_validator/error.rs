@@ -22,7 +22,7 @@
 
 use std::time::Duration;
 
-use crate::{bans::BAN_DURATION_LONG, peer_manager::NodeId};
+use crate::{bans::BAN_DURATION_LONG, peer_manager::NodeId, protocol::CapabilityError};
 
 /// Validation errors for peers shared on the network
 #[derive(Debug, Clone, thiserror::Error)]
@@ -47,6 +47,8 @@ pub enum PeerValidatorError {
     PeerIdentityProtocolIdTooLong { length: usize, max: usize },
     #[error("Peer provided a user agent that exceeds the maximum length: expected max {max} but got {length}")]
     PeerIdentityUserAgentTooLong { length: usize, max: usize },
+    #[error("Peer provided invalid capabilities: {0}")]
+    PeerIdentityInvalidCapabilities(#[from] CapabilityError),
 }
 
 impl PeerValidatorError {
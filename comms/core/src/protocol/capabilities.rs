@@ -0,0 +1,279 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! # Protocol capabilities
+//!
+//! Optional protocols and protocol variants that a node supports are advertised as named capabilities, each with the
+//! set of versions of that capability that the node is able to speak. Capabilities are sent as part of the identity
+//! exchange and, once both sides have exchanged them, each node independently selects the highest version of every
+//! capability that both nodes support. Because both nodes perform the same deterministic selection, no further round
+//! trip is needed.
+//!
+//! Unlike protocol IDs, which must match exactly, capabilities allow a node to support several variants of a
+//! protocol and fall back to an older variant when talking to older peers. Nodes that do not advertise capabilities
+//! simply have none in common with us.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
+
+use thiserror::Error;
+
+use crate::proto::identity::ProtocolCapability;
+
+/// The maximum number of capabilities that a peer may advertise
+pub const MAX_CAPABILITIES: usize = 16;
+/// The maximum length in bytes of a capability name
+pub const MAX_CAPABILITY_NAME_LENGTH: usize = 32;
+/// The maximum number of versions that may be advertised for a single capability
+pub const MAX_CAPABILITY_VERSIONS: usize = 8;
+
+/// The set of capabilities (and versions of each) supported by a node.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Capabilities {
+    inner: BTreeMap<String, BTreeSet<u32>>,
+}
+
+impl Capabilities {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Adds a capability with the given supported versions
+    pub fn with_capability<T: Into<String>, I: IntoIterator<Item = u32>>(mut self, name: T, versions: I) -> Self {
+        self.add(name, versions);
+        self
+    }
+
+    /// Adds the given versions of a capability. Versions are merged if the capability has already been added.
+    pub fn add<T: Into<String>, I: IntoIterator<Item = u32>>(&mut self, name: T, versions: I) -> &mut Self {
+        self.inner.entry(name.into()).or_default().extend(versions);
+        self
+    }
+
+    /// Returns true if the given version of the capability is supported
+    pub fn supports(&self, name: &str, version: u32) -> bool {
+        self.inner
+            .get(name)
+            .map_or(false, |versions| versions.contains(&version))
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &BTreeSet<u32>)> {
+        self.inner.iter().map(|(name, versions)| (name.as_str(), versions))
+    }
+
+    /// Selects the highest mutually-supported version of each capability that both we and the peer support
+    pub fn negotiate(&self, theirs: &Capabilities) -> NegotiatedCapabilities {
+        let selected = self
+            .inner
+            .iter()
+            .filter_map(|(name, ours)| {
+                let their_versions = theirs.inner.get(name)?;
+                let version = ours.intersection(their_versions).next_back()?;
+                Some((name.clone(), *version))
+            })
+            .collect();
+        NegotiatedCapabilities { selected }
+    }
+
+    pub(crate) fn to_proto(&self) -> Vec<ProtocolCapability> {
+        self.inner
+            .iter()
+            .map(|(name, versions)| ProtocolCapability {
+                name: name.clone(),
+                versions: versions.iter().copied().collect(),
+            })
+            .collect()
+    }
+
+    /// Converts capabilities received from a peer, enforcing the capability limits
+    pub(crate) fn try_from_proto(capabilities: Vec<ProtocolCapability>) -> Result<Self, CapabilityError> {
+        if capabilities.len() > MAX_CAPABILITIES {
+            return Err(CapabilityError::TooManyCapabilities {
+                length: capabilities.len(),
+                max: MAX_CAPABILITIES,
+            });
+        }
+        let mut caps = Capabilities::new();
+        for capability in capabilities {
+            if capability.name.is_empty() || capability.name.len() > MAX_CAPABILITY_NAME_LENGTH {
+                return Err(CapabilityError::InvalidNameLength {
+                    length: capability.name.len(),
+                    max: MAX_CAPABILITY_NAME_LENGTH,
+                });
+            }
+            if capability.versions.len() > MAX_CAPABILITY_VERSIONS {
+                return Err(CapabilityError::TooManyVersions {
+                    name: capability.name,
+                    length: capability.versions.len(),
+                    max: MAX_CAPABILITY_VERSIONS,
+                });
+            }
+            caps.add(capability.name, capability.versions);
+        }
+        Ok(caps)
+    }
+}
+
+impl fmt::Display for Capabilities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let caps = self
+            .iter()
+            .map(|(name, versions)| {
+                let versions = versions.iter().map(ToString::to_string).collect::<Vec<_>>().join(",");
+                format!("{}[{}]", name, versions)
+            })
+            .collect::<Vec<_>>();
+        write!(f, "{}", caps.join(" "))
+    }
+}
+
+/// The capabilities selected for a connection, and the version of each that will be spoken.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NegotiatedCapabilities {
+    selected: BTreeMap<String, u32>,
+}
+
+impl NegotiatedCapabilities {
+    /// Returns the negotiated version of the capability, or None if the peer does not support any version of the
+    /// capability that we support
+    pub fn version(&self, name: &str) -> Option<u32> {
+        self.selected.get(name).copied()
+    }
+
+    /// Returns true if some version of the capability was negotiated
+    pub fn contains(&self, name: &str) -> bool {
+        self.selected.contains_key(name)
+    }
+
+    pub fn len(&self) -> usize {
+        self.selected.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.selected.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.selected.iter().map(|(name, version)| (name.as_str(), *version))
+    }
+}
+
+impl fmt::Display for NegotiatedCapabilities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let caps = self
+            .iter()
+            .map(|(name, version)| format!("{}/{}", name, version))
+            .collect::<Vec<_>>();
+        write!(f, "{}", caps.join(" "))
+    }
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum CapabilityError {
+    #[error("Peer provided too many capabilities: expected max {max} but got {length}")]
+    TooManyCapabilities { length: usize, max: usize },
+    #[error("Peer provided a capability name with an invalid length: expected 1 to {max} but got {length}")]
+    InvalidNameLength { length: usize, max: usize },
+    #[error("Peer provided too many versions for capability '{name}': expected max {max} but got {length}")]
+    TooManyVersions { name: String, length: usize, max: usize },
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_selects_the_highest_common_version() {
+        let ours = Capabilities::new()
+            .with_capability("compact-blocks", [1, 2, 3])
+            .with_capability("mempool-recon", [1])
+            .with_capability("pq-handshake", [2]);
+        let theirs = Capabilities::new()
+            .with_capability("compact-blocks", [1, 2])
+            .with_capability("mempool-recon", [2])
+            .with_capability("pq-handshake", [1, 2, 5]);
+
+        let negotiated = ours.negotiate(&theirs);
+        assert_eq!(negotiated.version("compact-blocks"), Some(2));
+        assert_eq!(negotiated.version("mempool-recon"), None);
+        assert_eq!(negotiated.version("pq-handshake"), Some(2));
+        assert_eq!(negotiated.len(), 2);
+        // Negotiation is symmetric
+        assert_eq!(theirs.negotiate(&ours), negotiated);
+    }
+
+    #[test]
+    fn it_negotiates_nothing_with_legacy_peers() {
+        let ours = Capabilities::new().with_capability("compact-blocks", [1]);
+        let negotiated = ours.negotiate(&Capabilities::new());
+        assert!(negotiated.is_empty());
+        assert!(!negotiated.contains("compact-blocks"));
+    }
+
+    #[test]
+    fn it_enforces_limits_on_received_capabilities() {
+        let caps = Capabilities::new()
+            .with_capability("a", [1, 2])
+            .with_capability("b", [1]);
+        let received = Capabilities::try_from_proto(caps.to_proto()).unwrap();
+        assert_eq!(received, caps);
+
+        let too_many = (0..=MAX_CAPABILITIES)
+            .map(|i| ProtocolCapability {
+                name: i.to_string(),
+                versions: vec![1],
+            })
+            .collect();
+        assert!(matches!(
+            Capabilities::try_from_proto(too_many),
+            Err(CapabilityError::TooManyCapabilities { .. })
+        ));
+
+        let long_name = vec![ProtocolCapability {
+            name: "x".repeat(MAX_CAPABILITY_NAME_LENGTH + 1),
+            versions: vec![1],
+        }];
+        assert!(matches!(
+            Capabilities::try_from_proto(long_name),
+            Err(CapabilityError::InvalidNameLength { .. })
+        ));
+
+        let too_many_versions = vec![ProtocolCapability {
+            name: "a".to_string(),
+            versions: (0..=MAX_CAPABILITY_VERSIONS as u32).collect(),
+        }];
+        assert!(matches!(
+            Capabilities::try_from_proto(too_many_versions),
+            Err(CapabilityError::TooManyVersions { .. })
+        ));
+    }
+}
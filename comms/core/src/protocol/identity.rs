@@ -71,6 +71,7 @@ where
         supported_protocols,
         user_agent: network_info.user_agent,
         identity_signature: node_identity.identity_signature_read().as_ref().map(Into::into),
+        capabilities: network_info.capabilities.to_proto(),
     }
     .to_encoded_bytes();
 
@@ -220,7 +221,7 @@ mod test {
 
     use crate::{
         peer_manager::PeerFeatures,
-        protocol::{IdentityProtocolError, NodeNetworkInfo},
+        protocol::{Capabilities, IdentityProtocolError, NodeNetworkInfo},
         test_utils::node_identity::build_node_identity,
         transports::{MemoryTransport, Transport},
     };
@@ -245,6 +246,7 @@ mod test {
                 &[],
                 NodeNetworkInfo {
                     minor_version: 1,
+                    capabilities: Capabilities::new().with_capability("test", [1, 2]),
                     ..Default::default()
                 },
                 &mut in_sock,
@@ -274,8 +276,12 @@ mod test {
                 .map(|a| a.to_vec())
                 .collect::<Vec<_>>()
         );
+        assert_eq!(identity1.capabilities.len(), 1);
+        assert_eq!(identity1.capabilities[0].name, "test");
+        assert_eq!(identity1.capabilities[0].versions, vec![1, 2]);
 
         assert_eq!(identity2.features, node_identity2.features().bits());
+        assert!(identity2.capabilities.is_empty());
         assert_eq!(
             identity2.addresses,
             node_identity2
@@ -31,7 +31,10 @@ use crate::{
     message::InboundMessage,
     pipeline,
     protocol::{
-        messaging::MessagingEventSender,
+        messaging::{
+            padding::{padded_protocol_id, TrafficPaddingConfig},
+            MessagingEventSender,
+        },
         ProtocolExtension,
         ProtocolExtensionContext,
         ProtocolExtensionError,
@@ -54,6 +57,7 @@ pub struct MessagingProtocolExtension<TInPipe, TOutPipe, TOutReq> {
     enable_message_received_event: bool,
     ban_duration: Duration,
     protocol_id: ProtocolId,
+    traffic_padding: Option<TrafficPaddingConfig>,
 }
 
 impl<TInPipe, TOutPipe, TOutReq> MessagingProtocolExtension<TInPipe, TOutPipe, TOutReq> {
@@ -68,6 +72,7 @@ impl<TInPipe, TOutPipe, TOutReq> MessagingProtocolExtension<TInPipe, TOutPipe, T
             pipeline,
             enable_message_received_event: false,
             ban_duration: Duration::from_secs(10 * 60),
+            traffic_padding: None,
         }
     }
 
@@ -84,6 +89,13 @@ impl<TInPipe, TOutPipe, TOutReq> MessagingProtocolExtension<TInPipe, TOutPipe, T
         self.ban_duration = ban_duration;
         self
     }
+
+    /// Enables the traffic padding privacy mode. Outbound messages are padded to size buckets and cover traffic is sent
+    /// on idle sessions to peers that support it. Disabled by default.
+    pub fn with_traffic_padding(mut self, config: TrafficPaddingConfig) -> Self {
+        self.traffic_padding = Some(config);
+        self
+    }
 }
 
 impl<TInPipe, TOutPipe, TOutReq> ProtocolExtension for MessagingProtocolExtension<TInPipe, TOutPipe, TOutReq>
@@ -98,7 +110,10 @@ where
 {
     fn install(mut self: Box<Self>, context: &mut ProtocolExtensionContext) -> Result<(), ProtocolExtensionError> {
         let (proto_tx, proto_rx) = mpsc::channel(MESSAGING_PROTOCOL_EVENTS_BUFFER_SIZE);
-        context.add_protocol(&[self.protocol_id.clone()], &proto_tx);
+        context.add_protocol(
+            &[self.protocol_id.clone(), padded_protocol_id(&self.protocol_id)],
+            &proto_tx,
+        );
 
         let (inbound_message_tx, inbound_message_rx) = mpsc::channel(INBOUND_MESSAGE_BUFFER_SIZE);
 
@@ -113,7 +128,8 @@ where
             context.shutdown_signal(),
         )
         .set_message_received_event_enabled(self.enable_message_received_event)
-        .with_ban_duration(self.ban_duration);
+        .with_ban_duration(self.ban_duration)
+        .with_traffic_padding(self.traffic_padding);
 
         context.register_complete_signal(messaging.complete_signal());
 
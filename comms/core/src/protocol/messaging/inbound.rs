@@ -32,7 +32,7 @@ use tokio::{
 
 #[cfg(feature = "metrics")]
 use super::metrics;
-use super::{MessagingEvent, MessagingProtocol};
+use super::{padding, MessagingEvent, MessagingProtocol};
 use crate::{message::InboundMessage, peer_manager::NodeId};
 
 const LOG_TARGET: &str = "comms::protocol::messaging::inbound";
@@ -43,6 +43,7 @@ pub struct InboundMessaging {
     inbound_message_tx: mpsc::Sender<InboundMessage>,
     messaging_events_tx: broadcast::Sender<MessagingEvent>,
     enable_message_received_event: bool,
+    is_padded: bool,
     shutdown_signal: ShutdownSignal,
 }
 
@@ -52,6 +53,7 @@ impl InboundMessaging {
         inbound_message_tx: mpsc::Sender<InboundMessage>,
         messaging_events_tx: broadcast::Sender<MessagingEvent>,
         enable_message_received_event: bool,
+        is_padded: bool,
         shutdown_signal: ShutdownSignal,
     ) -> Self {
         Self {
@@ -59,6 +61,7 @@ impl InboundMessaging {
             inbound_message_tx,
             messaging_events_tx,
             enable_message_received_event,
+            is_padded,
             shutdown_signal,
         }
     }
@@ -78,8 +81,20 @@ impl InboundMessaging {
         tokio::pin!(stream);
 
         while let Either::Right((Some(result), _)) = future::select(self.shutdown_signal.wait(), stream.next()).await {
+            let result = if self.is_padded {
+                result.and_then(padding::decode_frame)
+            } else {
+                result.map(Some)
+            };
             match result {
-                Ok(raw_msg) => {
+                Ok(None) => {
+                    trace!(
+                        target: LOG_TARGET,
+                        "Discarded cover frame from peer '{}'",
+                        peer.short_str()
+                    );
+                },
+                Ok(Some(raw_msg)) => {
                     #[cfg(feature = "metrics")]
                     metrics::inbound_message_count(&self.peer).inc();
                     let msg_len = raw_msg.len();
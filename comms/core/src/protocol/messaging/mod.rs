@@ -37,6 +37,8 @@ mod inbound;
 #[cfg(feature = "metrics")]
 mod metrics;
 mod outbound;
+mod padding;
+pub use padding::TrafficPaddingConfig;
 mod protocol;
 pub use protocol::{MessagingEvent, MessagingEventReceiver, MessagingEventSender, MessagingProtocol, SendFailReason};
 
@@ -20,22 +20,29 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::time::Instant;
+use std::{sync::Arc, time::Instant};
 
 use futures::{future, SinkExt, StreamExt};
-use tokio::{pin, sync::mpsc};
+use tokio::{pin, sync::mpsc, time};
 use tracing::{debug, error, span, trace, Instrument, Level};
 
 #[cfg(feature = "metrics")]
 use super::metrics;
-use super::{error::MessagingProtocolError, MessagingEvent, MessagingProtocol, SendFailReason};
+use super::{
+    error::MessagingProtocolError,
+    padding::{padded_protocol_id, TrafficPaddingConfig},
+    protocol::MAX_FRAME_LENGTH,
+    MessagingEvent,
+    MessagingProtocol,
+    SendFailReason,
+};
 use crate::{
-    connection_manager::{NegotiatedSubstream, PeerConnection},
+    connection_manager::{NegotiatedSubstream, PeerConnection, PeerConnectionError},
     connectivity::{ConnectivityError, ConnectivityRequester},
     message::OutboundMessage,
     multiplexing::Substream,
     peer_manager::NodeId,
-    protocol::ProtocolId,
+    protocol::{ProtocolError, ProtocolId},
     stream_id::StreamId,
 };
 
@@ -45,6 +52,13 @@ const LOG_TARGET: &str = "comms::protocol::messaging::outbound";
 /// and because the connection manager already retries dialing a number of times for each requested dial.
 const MAX_SEND_RETRIES: usize = 1;
 
+/// A frame to be written to the outbound messaging substream
+enum OutboundFrame {
+    Message(OutboundMessage),
+    /// A cover frame that is sent when the session is idle and the traffic padding privacy mode is enabled
+    Cover,
+}
+
 /// Actor for outbound messaging for a peer. This is spawned lazily when an outbound message must be sent.
 pub struct OutboundMessaging {
     connectivity: ConnectivityRequester,
@@ -53,6 +67,7 @@ pub struct OutboundMessaging {
     retry_queue_tx: mpsc::UnboundedSender<OutboundMessage>,
     peer_node_id: NodeId,
     protocol_id: ProtocolId,
+    traffic_padding: Option<Arc<TrafficPaddingConfig>>,
 }
 
 impl OutboundMessaging {
@@ -63,6 +78,7 @@ impl OutboundMessaging {
         retry_queue_tx: mpsc::UnboundedSender<OutboundMessage>,
         peer_node_id: NodeId,
         protocol_id: ProtocolId,
+        traffic_padding: Option<Arc<TrafficPaddingConfig>>,
     ) -> Self {
         Self {
             connectivity,
@@ -71,6 +87,7 @@ impl OutboundMessaging {
             retry_queue_tx,
             peer_node_id,
             protocol_id,
+            traffic_padding,
         }
     }
 
@@ -225,6 +242,30 @@ impl OutboundMessaging {
         &mut self,
         conn: &mut PeerConnection,
     ) -> Result<NegotiatedSubstream<Substream>, MessagingProtocolError> {
+        if self.traffic_padding.is_some() {
+            match conn.open_substream(&padded_protocol_id(&self.protocol_id)).await {
+                Ok(substream) => return Ok(substream),
+                Err(PeerConnectionError::ProtocolError(ProtocolError::ProtocolOutboundNegotiationFailed {
+                    ..
+                })) => {
+                    debug!(
+                        target: LOG_TARGET,
+                        "Peer '{}' does not support padded messaging. Falling back to unpadded messaging.",
+                        self.peer_node_id
+                    );
+                },
+                Err(err) => {
+                    debug!(
+                        target: LOG_TARGET,
+                        "MessagingProtocol failed to open a padded substream to peer '{}' because '{}'",
+                        self.peer_node_id,
+                        err
+                    );
+                    return Err(err.into());
+                },
+            }
+        }
+
         match conn.open_substream(&self.protocol_id).await {
             Ok(substream) => Ok(substream),
             Err(err) => {
@@ -245,8 +286,12 @@ impl OutboundMessaging {
         let Self {
             mut messages_rx,
             peer_node_id,
+            protocol_id,
+            traffic_padding,
             ..
         } = self;
+        // Padding is only used if the peer negotiated the padded protocol
+        let padding = traffic_padding.filter(|_| substream.protocol != protocol_id);
         let span = span!(
             Level::DEBUG,
             "start_forwarding_messages",
@@ -256,29 +301,63 @@ impl OutboundMessaging {
         let stream_id = substream.stream.stream_id();
         debug!(
             target: LOG_TARGET,
-            "Starting direct message forwarding for peer `{}` (stream: {})", peer_node_id, stream_id
+            "Starting direct message forwarding for peer `{}` (stream: {}, padded: {})",
+            peer_node_id,
+            stream_id,
+            padding.is_some()
         );
 
         let (sink, mut remote_stream) = MessagingProtocol::framed(substream.stream).split();
 
-        // Convert unbounded channel to a stream
-        let outbound_stream = futures::stream::unfold(&mut messages_rx, |rx| async move {
-            let v = rx.recv().await;
-            v.map(|v| (v, rx))
+        // Convert unbounded channel to a stream. If cover traffic is enabled, a cover frame is emitted whenever no
+        // message is sent within the (randomised) cover traffic delay.
+        let cover_padding = padding.clone();
+        let outbound_stream = futures::stream::unfold(&mut messages_rx, move |rx| {
+            let cover_delay = cover_padding.as_ref().and_then(|p| p.next_cover_delay());
+            async move {
+                match cover_delay {
+                    Some(delay) => tokio::select! {
+                        v = rx.recv() => v.map(|v| (OutboundFrame::Message(v), rx)),
+                        _ = time::sleep(delay) => Some((OutboundFrame::Cover, rx)),
+                    },
+                    None => {
+                        let v = rx.recv().await;
+                        v.map(|v| (OutboundFrame::Message(v), rx))
+                    },
+                }
+            }
         });
 
         #[cfg(feature = "metrics")]
         let outbound_count = metrics::outbound_message_count(&peer_node_id);
-        let stream = outbound_stream.map(|mut out_msg| {
-            #[cfg(feature = "metrics")]
-            outbound_count.inc();
-            trace!(
-                target: LOG_TARGET,
-                "Message for peer '{}' sending {} on stream {}", peer_node_id, out_msg, stream_id
-            );
+        let stream = outbound_stream.map(|frame| {
+            let body = match frame {
+                OutboundFrame::Message(mut out_msg) => {
+                    #[cfg(feature = "metrics")]
+                    outbound_count.inc();
+                    trace!(
+                        target: LOG_TARGET,
+                        "Message for peer '{}' sending {} on stream {}", peer_node_id, out_msg, stream_id
+                    );
 
-            out_msg.reply_success();
-            Result::<_, MessagingProtocolError>::Ok(out_msg.body)
+                    out_msg.reply_success();
+                    match padding.as_ref() {
+                        Some(padding) => padding.encode_message(&out_msg.body, MAX_FRAME_LENGTH),
+                        None => out_msg.body,
+                    }
+                },
+                OutboundFrame::Cover => {
+                    trace!(
+                        target: LOG_TARGET,
+                        "Sending cover frame to peer '{}' on stream {}", peer_node_id, stream_id
+                    );
+                    padding
+                        .as_ref()
+                        .expect("cover frames are only emitted when traffic padding is enabled")
+                        .encode_cover(MAX_FRAME_LENGTH)
+                },
+            };
+            Result::<_, MessagingProtocolError>::Ok(body)
         });
 
         // Stop the stream as soon as the disconnection occurs, this allows the outbound stream to terminate as soon as
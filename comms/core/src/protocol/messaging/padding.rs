@@ -0,0 +1,217 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Opt-in traffic padding for the messaging protocol.
+//!
+//! When enabled, messages are sent on a separate protocol (the messaging protocol id suffixed with `/padded`). Each
+//! frame is prefixed with a frame kind and the payload length, and is padded up to a fixed size bucket so that the
+//! frame length does not reveal the size of the message. Idle outbound sessions additionally send cover frames at
+//! randomised intervals, which the receiver discards.
+
+use std::{cmp, io, time::Duration};
+
+use bytes::{BufMut, Bytes, BytesMut};
+use rand::{rngs::OsRng, Rng};
+
+use crate::protocol::ProtocolId;
+
+const PADDED_PROTOCOL_SUFFIX: &[u8] = b"/padded";
+/// The frame kind (1 byte) and payload length (4 bytes)
+const HEADER_LEN: usize = 5;
+const FRAME_KIND_MESSAGE: u8 = 0;
+const FRAME_KIND_COVER: u8 = 1;
+
+/// Configuration for the traffic padding privacy mode
+#[derive(Debug, Clone)]
+pub struct TrafficPaddingConfig {
+    /// The frame sizes (in bytes) that messages are padded to. A message is padded to the smallest bucket that can
+    /// contain it, messages larger than the largest bucket are padded to a multiple of the largest bucket.
+    pub size_buckets: Vec<usize>,
+    /// If set, a cover frame is sent when an outbound messaging session has been idle for this long. A random jitter
+    /// of up to half the interval is added to each delay.
+    pub cover_traffic_interval: Option<Duration>,
+}
+
+impl Default for TrafficPaddingConfig {
+    fn default() -> Self {
+        Self {
+            size_buckets: vec![512, 2 * 1024, 8 * 1024, 32 * 1024, 128 * 1024],
+            cover_traffic_interval: Some(Duration::from_secs(30)),
+        }
+    }
+}
+
+impl TrafficPaddingConfig {
+    /// Returns a new config with size buckets sorted and zero-size buckets removed
+    pub(super) fn normalized(mut self) -> Self {
+        self.size_buckets.retain(|b| *b > HEADER_LEN);
+        self.size_buckets.sort_unstable();
+        self.size_buckets.dedup();
+        self
+    }
+
+    fn padded_len(&self, payload_len: usize, max_frame_len: usize) -> usize {
+        let min_len = payload_len + HEADER_LEN;
+        let padded = match self.size_buckets.iter().find(|b| **b >= min_len) {
+            Some(bucket) => *bucket,
+            None => match self.size_buckets.last() {
+                Some(largest) => min_len.div_ceil(*largest) * largest,
+                None => min_len,
+            },
+        };
+        // Never pad a frame beyond the maximum frame length, unless the message itself does not fit
+        cmp::max(cmp::min(padded, max_frame_len), min_len)
+    }
+
+    /// Encodes a message frame, padded to the appropriate bucket size
+    pub(super) fn encode_message(&self, payload: &[u8], max_frame_len: usize) -> Bytes {
+        encode_frame(
+            FRAME_KIND_MESSAGE,
+            payload,
+            self.padded_len(payload.len(), max_frame_len),
+        )
+    }
+
+    /// Encodes a cover frame, padded to the smallest bucket size
+    pub(super) fn encode_cover(&self, max_frame_len: usize) -> Bytes {
+        encode_frame(FRAME_KIND_COVER, &[], self.padded_len(0, max_frame_len))
+    }
+
+    /// Returns the randomised delay before a cover frame should be sent on an idle session, or None if cover traffic
+    /// is disabled
+    pub(super) fn next_cover_delay(&self) -> Option<Duration> {
+        self.cover_traffic_interval.map(|interval| {
+            let max_jitter_ms = u64::try_from(interval.as_millis() / 2).unwrap_or(u64::MAX);
+            interval + Duration::from_millis(OsRng.gen_range(0..=max_jitter_ms))
+        })
+    }
+}
+
+/// Returns the protocol id used for padded messaging for the given messaging protocol id
+pub(super) fn padded_protocol_id(protocol_id: &ProtocolId) -> ProtocolId {
+    let mut id = BytesMut::with_capacity(protocol_id.len() + PADDED_PROTOCOL_SUFFIX.len());
+    id.extend_from_slice(protocol_id);
+    id.extend_from_slice(PADDED_PROTOCOL_SUFFIX);
+    id.freeze()
+}
+
+fn encode_frame(kind: u8, payload: &[u8], frame_len: usize) -> Bytes {
+    let mut frame = BytesMut::with_capacity(frame_len);
+    frame.put_u8(kind);
+    frame.put_u32(u32::try_from(payload.len()).expect("payload length is bounded by the maximum frame length"));
+    frame.extend_from_slice(payload);
+    frame.resize(frame_len, 0);
+    frame.freeze()
+}
+
+/// Decodes a padded frame. Returns None for cover frames, which should be discarded. An `InvalidData` error is returned
+/// for malformed frames.
+pub(super) fn decode_frame(mut frame: BytesMut) -> Result<Option<BytesMut>, io::Error> {
+    if frame.len() < HEADER_LEN {
+        return Err(invalid_frame("frame is shorter than the header"));
+    }
+    let kind = frame[0];
+    let payload_len = u32::from_be_bytes([frame[1], frame[2], frame[3], frame[4]]) as usize;
+    if payload_len > frame.len() - HEADER_LEN {
+        return Err(invalid_frame("payload length exceeds the frame length"));
+    }
+    match kind {
+        FRAME_KIND_MESSAGE => {
+            let mut payload = frame.split_off(HEADER_LEN);
+            payload.truncate(payload_len);
+            Ok(Some(payload))
+        },
+        FRAME_KIND_COVER => Ok(None),
+        _ => Err(invalid_frame("unknown frame kind")),
+    }
+}
+
+fn invalid_frame(details: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("Invalid padded messaging frame: {}", details),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const MAX_FRAME_LEN: usize = 1024 * 1024;
+
+    fn config() -> TrafficPaddingConfig {
+        TrafficPaddingConfig {
+            size_buckets: vec![1024, 0, 256],
+            cover_traffic_interval: Some(Duration::from_secs(10)),
+        }
+        .normalized()
+    }
+
+    #[test]
+    fn it_pads_messages_to_size_buckets() {
+        let config = config();
+        assert_eq!(config.encode_message(&[1u8; 10], MAX_FRAME_LEN).len(), 256);
+        assert_eq!(config.encode_message(&[1u8; 251], MAX_FRAME_LEN).len(), 256);
+        assert_eq!(config.encode_message(&[1u8; 252], MAX_FRAME_LEN).len(), 1024);
+        assert_eq!(config.encode_message(&[1u8; 1500], MAX_FRAME_LEN).len(), 2048);
+        assert_eq!(config.encode_cover(MAX_FRAME_LEN).len(), 256);
+        // Padding does not exceed the maximum frame length
+        assert_eq!(config.encode_message(&[1u8; 1500], 1600).len(), 1600);
+    }
+
+    #[test]
+    fn it_decodes_padded_frames() {
+        let config = config();
+        let frame = config.encode_message(b"hello", MAX_FRAME_LEN);
+        let payload = decode_frame(BytesMut::from(&frame[..])).unwrap().unwrap();
+        assert_eq!(&payload[..], b"hello");
+
+        let frame = config.encode_cover(MAX_FRAME_LEN);
+        assert!(decode_frame(BytesMut::from(&frame[..])).unwrap().is_none());
+    }
+
+    #[test]
+    fn it_rejects_malformed_frames() {
+        assert!(decode_frame(BytesMut::from(&[0u8, 0, 0][..])).is_err());
+        assert!(decode_frame(BytesMut::from(&[0u8, 0, 0, 0, 10, 1, 2][..])).is_err());
+        assert!(decode_frame(BytesMut::from(&[9u8, 0, 0, 0, 0][..])).is_err());
+    }
+
+    #[test]
+    fn it_jitters_the_cover_traffic_delay() {
+        let config = config();
+        let delay = config.next_cover_delay().unwrap();
+        assert!(delay >= Duration::from_secs(10));
+        assert!(delay <= Duration::from_secs(15));
+        let config = TrafficPaddingConfig {
+            cover_traffic_interval: None,
+            ..config
+        };
+        assert!(config.next_cover_delay().is_none());
+    }
+
+    #[test]
+    fn it_derives_the_padded_protocol_id() {
+        let id = padded_protocol_id(&ProtocolId::from_static(b"t/msg/0.1"));
+        assert_eq!(&id[..], b"t/msg/0.1/padded");
+    }
+}
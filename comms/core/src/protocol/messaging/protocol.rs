@@ -24,6 +24,7 @@ use std::{
     collections::{hash_map::Entry, HashMap},
     fmt,
     fmt::Display,
+    sync::Arc,
     time::Duration,
 };
 
@@ -45,7 +46,11 @@ use crate::{
     multiplexing::Substream,
     peer_manager::NodeId,
     protocol::{
-        messaging::{inbound::InboundMessaging, outbound::OutboundMessaging},
+        messaging::{
+            inbound::InboundMessaging,
+            outbound::OutboundMessaging,
+            padding::{padded_protocol_id, TrafficPaddingConfig},
+        },
         ProtocolEvent,
         ProtocolId,
         ProtocolNotification,
@@ -55,7 +60,7 @@ use crate::{
 const LOG_TARGET: &str = "comms::protocol::messaging";
 const INTERNAL_MESSAGING_EVENT_CHANNEL_SIZE: usize = 10;
 
-const MAX_FRAME_LENGTH: usize = 8 * 1_024 * 1_024;
+pub(super) const MAX_FRAME_LENGTH: usize = 8 * 1_024 * 1_024;
 
 pub type MessagingEventSender = broadcast::Sender<MessagingEvent>;
 pub type MessagingEventReceiver = broadcast::Receiver<MessagingEvent>;
@@ -110,6 +115,7 @@ pub struct MessagingProtocol {
     messaging_events_tx: MessagingEventSender,
     enable_message_received_event: bool,
     ban_duration: Option<Duration>,
+    traffic_padding: Option<Arc<TrafficPaddingConfig>>,
     inbound_message_tx: mpsc::Sender<InboundMessage>,
     internal_messaging_event_tx: mpsc::Sender<MessagingEvent>,
     internal_messaging_event_rx: mpsc::Receiver<MessagingEvent>,
@@ -146,6 +152,7 @@ impl MessagingProtocol {
             internal_messaging_event_rx,
             internal_messaging_event_tx,
             ban_duration: None,
+            traffic_padding: None,
             retry_queue_tx,
             retry_queue_rx,
             inbound_message_tx,
@@ -270,6 +277,12 @@ impl MessagingProtocol {
         Ok(())
     }
 
+    /// Enables the traffic padding privacy mode for outbound messaging. Padded inbound messaging is always accepted.
+    pub fn with_traffic_padding(mut self, config: Option<TrafficPaddingConfig>) -> Self {
+        self.traffic_padding = config.map(|c| Arc::new(c.normalized()));
+        self
+    }
+
     fn send_message(&mut self, out_msg: OutboundMessage) -> Result<(), MessagingProtocolError> {
         trace!(target: LOG_TARGET, "Received request to send message ({})", out_msg);
         let peer_node_id = out_msg.peer_node_id.clone();
@@ -289,6 +302,7 @@ impl MessagingProtocol {
                         peer_node_id,
                         self.retry_queue_tx.clone(),
                         self.protocol_id.clone(),
+                        self.traffic_padding.clone(),
                     );
                     break entry.insert(sender);
                 },
@@ -318,6 +332,7 @@ impl MessagingProtocol {
         peer_node_id: NodeId,
         retry_queue_tx: mpsc::UnboundedSender<OutboundMessage>,
         protocol_id: ProtocolId,
+        traffic_padding: Option<Arc<TrafficPaddingConfig>>,
     ) -> mpsc::UnboundedSender<OutboundMessage> {
         let (msg_tx, msg_rx) = mpsc::unbounded_channel();
         let outbound_messaging = OutboundMessaging::new(
@@ -327,12 +342,13 @@ impl MessagingProtocol {
             retry_queue_tx,
             peer_node_id,
             protocol_id,
+            traffic_padding,
         );
         tokio::spawn(outbound_messaging.run());
         msg_tx
     }
 
-    fn spawn_inbound_handler(&mut self, peer: NodeId, substream: Substream) {
+    fn spawn_inbound_handler(&mut self, peer: NodeId, substream: Substream, is_padded: bool) {
         if let Some(handle) = self.active_inbound.get(&peer) {
             if handle.is_finished() {
                 self.active_inbound.remove(&peer);
@@ -351,6 +367,7 @@ impl MessagingProtocol {
             inbound_message_tx,
             messaging_events_tx,
             self.enable_message_received_event,
+            is_padded,
             self.shutdown_signal.clone(),
         );
         let handle = tokio::spawn(inbound_messaging.run(substream));
@@ -367,7 +384,8 @@ impl MessagingProtocol {
                     node_id.short_str()
                 );
 
-                self.spawn_inbound_handler(node_id, substream);
+                let is_padded = notification.protocol == padded_protocol_id(&self.protocol_id);
+                self.spawn_inbound_handler(node_id, substream, is_padded);
             },
         }
     }
@@ -33,7 +33,10 @@ use tokio::{
     time,
 };
 
-use super::protocol::{MessagingEventReceiver, MessagingProtocol};
+use super::{
+    padding::{padded_protocol_id, TrafficPaddingConfig},
+    protocol::{MessagingEventReceiver, MessagingProtocol, MAX_FRAME_LENGTH},
+};
 use crate::{
     message::{InboundMessage, MessageTag, MessagingReplyRx, OutboundMessage},
     multiplexing::Substream,
@@ -161,6 +164,40 @@ async fn new_inbound_substream_handling() {
     assert_eq!(*node_id, expected_node_id);
 }
 
+#[tokio::test]
+async fn new_inbound_padded_substream_handling() {
+    let (_, _, _, proto_tx, _, mut inbound_msg_rx, _, _shutdown) = spawn_messaging_protocol().await;
+
+    let expected_node_id = node_id::random();
+    let (_, muxer_ours, mut muxer_theirs) = transport::build_multiplexed_connections().await;
+    let stream_ours = muxer_ours.get_yamux_control().open_stream().await.unwrap();
+
+    let padding = TrafficPaddingConfig::default().normalized();
+    let mut framed_ours = MessagingProtocol::framed(stream_ours);
+    framed_ours.send(padding.encode_cover(MAX_FRAME_LENGTH)).await.unwrap();
+    framed_ours
+        .send(padding.encode_message(&TEST_MSG1, MAX_FRAME_LENGTH))
+        .await
+        .unwrap();
+
+    let stream_theirs = muxer_theirs.incoming_mut().next().await.unwrap();
+    proto_tx
+        .send(ProtocolNotification::new(
+            padded_protocol_id(&MESSAGING_PROTOCOL_ID),
+            ProtocolEvent::NewInboundSubstream(expected_node_id.clone(), stream_theirs),
+        ))
+        .await
+        .unwrap();
+
+    // The cover frame is discarded and the padding is removed from the message
+    let in_msg = time::timeout(Duration::from_secs(5), inbound_msg_rx.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(in_msg.source_peer, expected_node_id);
+    assert_eq!(in_msg.body, TEST_MSG1);
+}
+
 #[tokio::test]
 async fn send_message_request() {
     let (_, node_identity, conn_man_mock, _, request_tx, _, _, _shutdown) = spawn_messaging_protocol().await;
@@ -20,6 +20,16 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+mod capabilities;
+pub use capabilities::{
+    Capabilities,
+    CapabilityError,
+    NegotiatedCapabilities,
+    MAX_CAPABILITIES,
+    MAX_CAPABILITY_NAME_LENGTH,
+    MAX_CAPABILITY_VERSIONS,
+};
+
 mod error;
 pub use error::ProtocolError;
 
@@ -50,6 +50,7 @@ use futures::{
 use log::*;
 use prost::Message;
 use tari_shutdown::{Shutdown, ShutdownSignal};
+use tari_telemetry::propagation::{self, TraceContextCarrier};
 use tokio::{
     io::{AsyncRead, AsyncWrite},
     sync::{mpsc, oneshot, watch, Mutex},
@@ -62,7 +63,7 @@ use super::message::RpcMethod;
 use crate::{
     framing::CanonicalFraming,
     message::MessageExt,
-    peer_manager::NodeId,
+    peer_manager::{NodeId, PeerLatencyTracker, SyncPeerQualityTracker},
     proto,
     protocol::{
         rpc,
@@ -102,6 +103,20 @@ impl RpcClient {
         framed: CanonicalFraming<TSubstream>,
         protocol_name: ProtocolId,
     ) -> Result<Self, RpcError>
+    where
+        TSubstream: AsyncRead + AsyncWrite + Unpin + Send + StreamId + 'static,
+    {
+        Self::connect_inner(config, node_id, framed, protocol_name, None, None).await
+    }
+
+    async fn connect_inner<TSubstream>(
+        config: RpcClientConfig,
+        node_id: NodeId,
+        framed: CanonicalFraming<TSubstream>,
+        protocol_name: ProtocolId,
+        latency_tracker: Option<PeerLatencyTracker>,
+        sync_quality_tracker: Option<SyncPeerQualityTracker>,
+    ) -> Result<Self, RpcError>
     where
         TSubstream: AsyncRead + AsyncWrite + Unpin + Send + StreamId + 'static,
     {
@@ -121,6 +136,8 @@ impl RpcClient {
                 node_id,
                 request_rx,
                 last_request_latency_tx,
+                latency_tracker,
+                sync_quality_tracker,
                 framed,
                 ready_tx,
                 protocol_name,
@@ -207,6 +224,8 @@ pub struct RpcClientBuilder<TClient> {
     config: RpcClientConfig,
     protocol_id: Option<ProtocolId>,
     node_id: Option<NodeId>,
+    latency_tracker: Option<PeerLatencyTracker>,
+    sync_quality_tracker: Option<SyncPeerQualityTracker>,
     _client: PhantomData<TClient>,
 }
 
@@ -216,6 +235,8 @@ impl<TClient> Default for RpcClientBuilder<TClient> {
             config: Default::default(),
             protocol_id: None,
             node_id: None,
+            latency_tracker: None,
+            sync_quality_tracker: None,
             _client: PhantomData,
         }
     }
@@ -266,6 +287,19 @@ impl<TClient> RpcClientBuilder<TClient> {
         self.node_id = Some(node_id);
         self
     }
+
+    /// Record the RTT of the handshake and each request in the given tracker, keyed by the node_id of the peer
+    pub fn with_latency_tracker(mut self, latency_tracker: PeerLatencyTracker) -> Self {
+        self.latency_tracker = Some(latency_tracker);
+        self
+    }
+
+    /// Record the bytes received in each response and the time taken to receive them in the given tracker, keyed by
+    /// the node_id of the peer
+    pub fn with_sync_quality_tracker(mut self, sync_quality_tracker: SyncPeerQualityTracker) -> Self {
+        self.sync_quality_tracker = Some(sync_quality_tracker);
+        self
+    }
 }
 
 impl<TClient> RpcClientBuilder<TClient>
@@ -274,7 +308,7 @@ where TClient: From<RpcClient> + NamedProtocolService
     /// Negotiates and establishes a session to the peer's RPC service
     pub async fn connect<TSubstream>(self, framed: CanonicalFraming<TSubstream>) -> Result<TClient, RpcError>
     where TSubstream: AsyncRead + AsyncWrite + Unpin + Send + StreamId + 'static {
-        RpcClient::connect(
+        RpcClient::connect_inner(
             self.config,
             self.node_id.unwrap_or_default(),
             framed,
@@ -282,6 +316,8 @@ where TClient: From<RpcClient> + NamedProtocolService
                 .as_ref()
                 .cloned()
                 .unwrap_or_else(|| ProtocolId::from_static(TClient::PROTOCOL_NAME)),
+            self.latency_tracker,
+            self.sync_quality_tracker,
         )
         .await
         .map(Into::into)
@@ -380,14 +416,21 @@ impl Service<BaseRequest<Bytes>> for ClientConnector {
     fn call(&mut self, request: BaseRequest<Bytes>) -> Self::Future {
         let (reply, reply_rx) = oneshot::channel();
         let inner = self.inner.clone();
+        let span = span!(Level::DEBUG, "rpc::client::request", method = request.method.id());
+        let trace_context = propagation::inject_span_context(&span);
         async move {
             inner
-                .send(ClientRequest::SendRequest { request, reply })
+                .send(ClientRequest::SendRequest {
+                    request,
+                    trace_context,
+                    reply,
+                })
                 .await
                 .map_err(|_| RpcError::ClientClosed)?;
 
             reply_rx.await.map_err(|_| RpcError::RequestCancelled)
         }
+        .instrument(span)
         .boxed()
     }
 }
@@ -397,6 +440,8 @@ struct RpcClientWorker<TSubstream> {
     node_id: NodeId,
     request_rx: mpsc::Receiver<ClientRequest>,
     last_request_latency_tx: watch::Sender<Option<Duration>>,
+    latency_tracker: Option<PeerLatencyTracker>,
+    sync_quality_tracker: Option<SyncPeerQualityTracker>,
     framed: CanonicalFraming<TSubstream>,
     // Request ids are limited to u16::MAX because varint encoding is used over the wire and the magnitude of the value
     // sent determines the byte size. A u16 will be more than enough for the purpose
@@ -414,6 +459,8 @@ where TSubstream: AsyncRead + AsyncWrite + Unpin + Send + StreamId
         node_id: NodeId,
         request_rx: mpsc::Receiver<ClientRequest>,
         last_request_latency_tx: watch::Sender<Option<Duration>>,
+        latency_tracker: Option<PeerLatencyTracker>,
+        sync_quality_tracker: Option<SyncPeerQualityTracker>,
         framed: CanonicalFraming<TSubstream>,
         ready_tx: oneshot::Sender<Result<(), RpcError>>,
         protocol_id: ProtocolId,
@@ -427,11 +474,26 @@ where TSubstream: AsyncRead + AsyncWrite + Unpin + Send + StreamId
             next_request_id: 0,
             ready_tx: Some(ready_tx),
             last_request_latency_tx,
+            latency_tracker,
+            sync_quality_tracker,
             protocol_id,
             shutdown_signal,
         }
     }
 
+    fn record_latency(&self, latency: Duration) {
+        let _ = self.last_request_latency_tx.send(Some(latency));
+        if let Some(tracker) = self.latency_tracker.as_ref() {
+            tracker.record(&self.node_id, latency);
+        }
+    }
+
+    fn record_transfer(&self, bytes: usize, elapsed: Duration) {
+        if let Some(tracker) = self.sync_quality_tracker.as_ref() {
+            tracker.record_transfer(&self.node_id, bytes as u64, elapsed);
+        }
+    }
+
     fn protocol_name(&self) -> Cow<'_, str> {
         String::from_utf8_lossy(&self.protocol_id)
     }
@@ -459,7 +521,7 @@ where TSubstream: AsyncRead + AsyncWrite + Unpin + Send + StreamId
                     self.protocol_name(),
                     latency
                 );
-                let _ = self.last_request_latency_tx.send(Some(latency));
+                self.record_latency(latency);
                 if let Some(r) = self.ready_tx.take() {
                     let _result = r.send(Ok(()));
                 }
@@ -529,8 +591,12 @@ where TSubstream: AsyncRead + AsyncWrite + Unpin + Send + StreamId
     async fn handle_request(&mut self, req: ClientRequest) -> Result<(), RpcError> {
         use ClientRequest::{SendPing, SendRequest};
         match req {
-            SendRequest { request, reply } => {
-                self.do_request_response(request, reply).await?;
+            SendRequest {
+                request,
+                trace_context,
+                reply,
+            } => {
+                self.do_request_response(request, trace_context, reply).await?;
             },
             SendPing(reply) => {
                 self.do_ping_pong(reply).await?;
@@ -610,6 +676,7 @@ where TSubstream: AsyncRead + AsyncWrite + Unpin + Send + StreamId
     async fn do_request_response(
         &mut self,
         request: BaseRequest<Bytes>,
+        trace_context: TraceContextCarrier,
         reply: oneshot::Sender<mpsc::Receiver<Result<Response<Bytes>, RpcStatus>>>,
     ) -> Result<(), RpcError> {
         #[cfg(feature = "metrics")]
@@ -622,6 +689,7 @@ where TSubstream: AsyncRead + AsyncWrite + Unpin + Send + StreamId
             method,
             deadline: self.config.deadline.map(|t| t.as_secs()).unwrap_or(0),
             flags: 0,
+            trace_context,
             payload: request.message.to_vec(),
         };
 
@@ -660,6 +728,7 @@ where TSubstream: AsyncRead + AsyncWrite + Unpin + Send + StreamId
             return Ok(());
         }
         let partial_latency = timer.elapsed();
+        let mut transfer_timer = Instant::now();
 
         loop {
             if self.shutdown_signal.is_triggered() {
@@ -697,8 +766,10 @@ where TSubstream: AsyncRead + AsyncWrite + Unpin + Send + StreamId
             let resp = match resp_result {
                 Ok((resp, time_to_first_msg)) => {
                     if let Some(t) = time_to_first_msg {
-                        let _ = self.last_request_latency_tx.send(Some(partial_latency + t));
+                        self.record_latency(partial_latency + t);
                     }
+                    self.record_transfer(resp.payload.len(), transfer_timer.elapsed());
+                    transfer_timer = Instant::now();
                     trace!(
                         target: LOG_TARGET,
                         "Received response ({} byte(s)) from request #{} (protocol = {}, method={})",
@@ -895,6 +966,8 @@ where TSubstream: AsyncRead + AsyncWrite + Unpin + Send + StreamId
 pub enum ClientRequest {
     SendRequest {
         request: BaseRequest<Bytes>,
+        /// The trace context of the calling span, sent to the server so that its spans join the caller's trace
+        trace_context: TraceContextCarrier,
         reply: oneshot::Sender<mpsc::Receiver<Result<Response<Bytes>, RpcStatus>>>,
     },
     SendPing(oneshot::Sender<Result<Duration, RpcStatus>>),
@@ -54,7 +54,16 @@ pub use body::{Body, ClientStreaming, IntoBody, Streaming};
 mod context;
 
 mod server;
-pub use server::{mock, NamedProtocolService, RpcServer, RpcServerBuilder, RpcServerError, RpcServerHandle};
+pub use server::{
+    mock,
+    NamedProtocolService,
+    RpcRateLimit,
+    RpcServer,
+    RpcServerBuilder,
+    RpcServerError,
+    RpcServerHandle,
+    RpcSessionInfo,
+};
 
 mod client;
 pub use client::{
@@ -22,13 +22,14 @@
 
 use tokio::sync::{mpsc, oneshot};
 
-use super::RpcServerError;
+use super::{RpcServerError, RpcSessionInfo};
 use crate::peer_manager::NodeId;
 
 #[derive(Debug)]
 pub enum RpcServerRequest {
     GetNumActiveSessions(oneshot::Sender<usize>),
     GetNumActiveSessionsForPeer(NodeId, oneshot::Sender<usize>),
+    GetActiveSessions(oneshot::Sender<Vec<RpcSessionInfo>>),
 }
 
 #[derive(Debug, Clone)]
@@ -58,4 +59,14 @@ impl RpcServerHandle {
             .map_err(|_| RpcServerError::RequestCanceled)?;
         resp.await.map_err(Into::into)
     }
+
+    /// Returns details of all active sessions, including the methods that have been called in each session
+    pub async fn get_active_sessions(&mut self) -> Result<Vec<RpcSessionInfo>, RpcServerError> {
+        let (req, resp) = oneshot::channel();
+        self.sender
+            .send(RpcServerRequest::GetActiveSessions(req))
+            .await
+            .map_err(|_| RpcServerError::RequestCanceled)?;
+        resp.await.map_err(Into::into)
+    }
 }
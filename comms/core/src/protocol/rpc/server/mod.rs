@@ -35,8 +35,13 @@ mod metrics;
 pub mod mock;
 
 mod early_close;
+mod rate_limit;
+pub use rate_limit::RpcRateLimit;
+use rate_limit::RpcRateLimiter;
+
 mod router;
 
+mod session;
 use std::{
     borrow::Cow,
     cmp,
@@ -55,6 +60,9 @@ use futures::{future, stream::FuturesUnordered, SinkExt, StreamExt};
 use log::*;
 use prost::Message;
 use router::Router;
+pub use session::RpcSessionInfo;
+use session::{RpcSessionGuard, RpcSessionRegistry};
+use tari_telemetry::propagation;
 use tokio::{sync::mpsc, task::JoinHandle, time};
 use tokio_stream::Stream;
 use tower::{make::MakeService, Service};
@@ -179,6 +187,9 @@ pub struct RpcServerBuilder {
     maximum_sessions_per_client: Option<usize>,
     minimum_client_deadline: Duration,
     handshake_timeout: Duration,
+    per_peer_rate_limit: Option<RpcRateLimit>,
+    per_method_rate_limit: Option<RpcRateLimit>,
+    maximum_pending_requests: Option<usize>,
 }
 
 impl RpcServerBuilder {
@@ -211,6 +222,27 @@ impl RpcServerBuilder {
         self
     }
 
+    /// Limits the rate of requests that a single peer may make across all of its sessions. Requests that exceed the
+    /// limit are rejected with a `SlowDown` status.
+    pub fn with_per_peer_rate_limit(mut self, limit: RpcRateLimit) -> Self {
+        self.per_peer_rate_limit = Some(limit);
+        self
+    }
+
+    /// Limits the rate of requests that a single peer may make to any one RPC method. Requests that exceed the limit
+    /// are rejected with a `SlowDown` status.
+    pub fn with_per_method_rate_limit(mut self, limit: RpcRateLimit) -> Self {
+        self.per_method_rate_limit = Some(limit);
+        self
+    }
+
+    /// Limits the number of requests that are being processed across all sessions at any one time. Once this limit is
+    /// reached, further requests are rejected with a `SlowDown` status until the load decreases.
+    pub fn with_maximum_pending_requests(mut self, limit: usize) -> Self {
+        self.maximum_pending_requests = Some(limit);
+        self
+    }
+
     pub fn finish(self) -> RpcServer {
         let (request_tx, request_rx) = mpsc::channel(10);
         RpcServer {
@@ -228,6 +260,9 @@ impl Default for RpcServerBuilder {
             maximum_sessions_per_client: None,
             minimum_client_deadline: Duration::from_secs(1),
             handshake_timeout: Duration::from_secs(15),
+            per_peer_rate_limit: None,
+            per_method_rate_limit: None,
+            maximum_pending_requests: None,
         }
     }
 }
@@ -240,7 +275,9 @@ pub(super) struct PeerRpcServer<TSvc, TCommsProvider> {
     comms_provider: TCommsProvider,
     request_rx: mpsc::Receiver<RpcServerRequest>,
     sessions: HashMap<NodeId, usize>,
+    session_registry: RpcSessionRegistry,
     tasks: FuturesUnordered<JoinHandle<NodeId>>,
+    rate_limiter: RpcRateLimiter,
 }
 
 impl<TSvc, TCommsProvider> PeerRpcServer<TSvc, TCommsProvider>
@@ -271,12 +308,18 @@ where
                 Some(num) => BoundedExecutor::new(num),
                 None => BoundedExecutor::allow_maximum(),
             },
+            rate_limiter: RpcRateLimiter::new(
+                config.per_peer_rate_limit,
+                config.per_method_rate_limit,
+                config.maximum_pending_requests,
+            ),
             config,
             service,
             protocol_notifications: Some(protocol_notifications),
             comms_provider,
             request_rx,
             sessions: HashMap::new(),
+            session_registry: RpcSessionRegistry::new(),
             tasks: FuturesUnordered::new(),
         }
     }
@@ -331,6 +374,9 @@ where
                 let num_active = self.sessions.get(&node_id).copied().unwrap_or(0);
                 let _ = reply.send(num_active);
             },
+            GetActiveSessions(reply) => {
+                let _ = reply.send(self.session_registry.sessions());
+            },
         }
     }
 
@@ -461,13 +507,16 @@ where
             "Server negotiated RPC v{} with client node `{}`", version, node_id
         );
 
+        let session = self.session_registry.register(node_id.clone(), protocol.clone());
         let service = ActivePeerRpcService::new(
             self.config.clone(),
             protocol,
             node_id.clone(),
+            session,
             service,
             framed,
             self.comms_provider.clone(),
+            self.rate_limiter.clone(),
         );
 
         let node_id = node_id.clone();
@@ -500,6 +549,8 @@ struct ActivePeerRpcService<TSvc, TCommsProvider> {
     service: TSvc,
     framed: EarlyClose<CanonicalFraming<Substream>>,
     comms_provider: TCommsProvider,
+    rate_limiter: RpcRateLimiter,
+    session: RpcSessionGuard,
     logging_context_string: Arc<String>,
 }
 
@@ -512,9 +563,11 @@ where
         config: RpcServerBuilder,
         protocol: ProtocolId,
         node_id: NodeId,
+        session: RpcSessionGuard,
         service: TSvc,
         framed: CanonicalFraming<Substream>,
         comms_provider: TCommsProvider,
+        rate_limiter: RpcRateLimiter,
     ) -> Self {
         Self {
             logging_context_string: Arc::new(format!(
@@ -530,6 +583,8 @@ where
             service,
             framed: EarlyClose::new(framed),
             comms_provider,
+            rate_limiter,
+            session,
         }
     }
 
@@ -588,6 +643,7 @@ where
                         );
                         return Err(err);
                     }
+                    self.session.on_request_complete();
                     let elapsed = start.elapsed();
                     trace!(
                         target: LOG_TARGET,
@@ -679,18 +735,47 @@ where
             method.id()
         );
 
+        // Held until the response has been fully sent
+        let _pending_guard = match self.rate_limiter.check(&self.node_id, &self.protocol, method.id()) {
+            Ok(guard) => guard,
+            Err(status) => {
+                debug!(
+                    target: LOG_TARGET,
+                    "({}) Rejecting request for method {}: {}",
+                    self.logging_context_string,
+                    method.id(),
+                    status
+                );
+                let slow_down = proto::rpc::RpcResponse {
+                    request_id,
+                    status: status.as_code(),
+                    flags: RpcMessageFlags::FIN.bits().into(),
+                    payload: status.to_details_bytes(),
+                };
+                #[cfg(feature = "metrics")]
+                metrics::status_error_counter(&self.node_id, &self.protocol, status.as_status_code()).inc();
+                self.framed.send(slow_down.to_encoded_bytes().into()).await?;
+                return Ok(());
+            },
+        };
+
+        self.session.on_request_started(method.id());
         let req = Request::with_context(
             self.create_request_context(request_id),
             method,
             decoded_msg.payload.into(),
         );
 
+        // The service call joins the caller's trace, but is only sampled if this node samples it
+        let span = span!(Level::DEBUG, "rpc::server::request", method = method.id());
+        propagation::set_parent_from_carrier(&span, &decoded_msg.trace_context);
         let service_call = log_timing(
             self.logging_context_string.clone(),
             request_id,
             "service call",
             self.service.call(req),
-        );
+        )
+        .instrument(span);
         let service_result = time::timeout(deadline, service_call).await;
         let service_result = match service_result {
             Ok(v) => v,
@@ -0,0 +1,330 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use super::RpcStatus;
+use crate::{peer_manager::NodeId, protocol::ProtocolId};
+
+/// A token bucket rate limit of `max_requests` per `period`. Up to `max_requests` may be made in a burst, after which
+/// requests are permitted at the refill rate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RpcRateLimit {
+    pub max_requests: u32,
+    pub period: Duration,
+}
+
+impl RpcRateLimit {
+    pub fn new(max_requests: u32, period: Duration) -> Self {
+        Self { max_requests, period }
+    }
+
+    pub fn per_second(max_requests: u32) -> Self {
+        Self::new(max_requests, Duration::from_secs(1))
+    }
+
+    pub fn per_minute(max_requests: u32) -> Self {
+        Self::new(max_requests, Duration::from_secs(60))
+    }
+
+    fn is_unlimited(&self) -> bool {
+        self.max_requests == 0 || self.period.is_zero()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(limit: &RpcRateLimit, now: Instant) -> Self {
+        Self {
+            tokens: f64::from(limit.max_requests),
+            last_refill: now,
+        }
+    }
+
+    fn refill(&mut self, limit: &RpcRateLimit, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        let max = f64::from(limit.max_requests);
+        let refill = elapsed.as_secs_f64() * max / limit.period.as_secs_f64();
+        self.tokens = (self.tokens + refill).min(max);
+        self.last_refill = now;
+    }
+
+    fn has_token(&self) -> bool {
+        self.tokens >= 1.0
+    }
+
+    fn take(&mut self) {
+        self.tokens -= 1.0;
+    }
+
+    fn is_full(&self, limit: &RpcRateLimit) -> bool {
+        self.tokens >= f64::from(limit.max_requests)
+    }
+}
+
+#[derive(Debug, Default)]
+struct RateLimiterState {
+    peers: HashMap<NodeId, TokenBucket>,
+    methods: HashMap<(NodeId, ProtocolId, u32), TokenBucket>,
+    last_pruned: Option<Instant>,
+}
+
+/// Shared between all sessions of an RPC server. Enforces per-peer and per-method request rate limits as well as a
+/// maximum number of requests that are being processed at any one time.
+#[derive(Debug, Clone)]
+pub(super) struct RpcRateLimiter {
+    per_peer: Option<RpcRateLimit>,
+    per_method: Option<RpcRateLimit>,
+    maximum_pending_requests: Option<usize>,
+    state: Arc<Mutex<RateLimiterState>>,
+    num_pending: Arc<AtomicUsize>,
+}
+
+impl RpcRateLimiter {
+    pub fn new(
+        per_peer: Option<RpcRateLimit>,
+        per_method: Option<RpcRateLimit>,
+        maximum_pending_requests: Option<usize>,
+    ) -> Self {
+        Self {
+            per_peer: per_peer.filter(|l| !l.is_unlimited()),
+            per_method: per_method.filter(|l| !l.is_unlimited()),
+            maximum_pending_requests: maximum_pending_requests.filter(|n| *n > 0),
+            state: Arc::new(Mutex::new(RateLimiterState::default())),
+            num_pending: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Checks whether the given request may proceed. On success, a guard is returned that must be held until the
+    /// request has been fully processed. Otherwise, a `SlowDown` status is returned that should be sent to the client.
+    pub fn check(
+        &self,
+        node_id: &NodeId,
+        protocol: &ProtocolId,
+        method: u32,
+    ) -> Result<PendingRequestGuard, RpcStatus> {
+        self.check_at(node_id, protocol, method, Instant::now())
+    }
+
+    fn check_at(
+        &self,
+        node_id: &NodeId,
+        protocol: &ProtocolId,
+        method: u32,
+        now: Instant,
+    ) -> Result<PendingRequestGuard, RpcStatus> {
+        // The pending request slot is reserved before any tokens are taken, so that a request rejected because the
+        // server is busy does not count against the rate limits. The slot is released again if a rate limit rejects
+        // the request, when the guard is dropped.
+        let num_pending = self.num_pending.fetch_add(1, Ordering::SeqCst);
+        let guard = PendingRequestGuard {
+            num_pending: self.num_pending.clone(),
+        };
+        if let Some(max) = self.maximum_pending_requests {
+            if num_pending >= max {
+                return Err(RpcStatus::slow_down("Server is busy"));
+            }
+        }
+
+        if self.per_peer.is_some() || self.per_method.is_some() {
+            let mut state = self.state.lock().expect("RpcRateLimiter lock poisoned");
+            self.prune_idle(&mut state, now);
+            let RateLimiterState { peers, methods, .. } = &mut *state;
+
+            // Check both buckets before taking a token from either, so that a request rejected by the method limit
+            // does not count against the peer limit and vice versa.
+            let peer_bucket = self.per_peer.as_ref().map(|limit| {
+                let bucket = peers
+                    .entry(node_id.clone())
+                    .or_insert_with(|| TokenBucket::new(limit, now));
+                bucket.refill(limit, now);
+                bucket
+            });
+            if peer_bucket.as_ref().is_some_and(|b| !b.has_token()) {
+                return Err(RpcStatus::slow_down("Peer request rate limit exceeded"));
+            }
+
+            let method_bucket = self.per_method.as_ref().map(|limit| {
+                let bucket = methods
+                    .entry((node_id.clone(), protocol.clone(), method))
+                    .or_insert_with(|| TokenBucket::new(limit, now));
+                bucket.refill(limit, now);
+                bucket
+            });
+            if method_bucket.as_ref().is_some_and(|b| !b.has_token()) {
+                return Err(RpcStatus::slow_down("Method request rate limit exceeded"));
+            }
+
+            peer_bucket.into_iter().chain(method_bucket).for_each(TokenBucket::take);
+        }
+
+        Ok(guard)
+    }
+
+    pub fn num_pending_requests(&self) -> usize {
+        self.num_pending.load(Ordering::SeqCst)
+    }
+
+    /// Removes buckets that have fully refilled, as they are indistinguishable from a new bucket.
+    fn prune_idle(&self, state: &mut RateLimiterState, now: Instant) {
+        let interval = max_period(self.per_peer, self.per_method);
+        if state
+            .last_pruned
+            .is_some_and(|t| now.saturating_duration_since(t) < interval)
+        {
+            return;
+        }
+        state.last_pruned = Some(now);
+        if let Some(limit) = self.per_peer {
+            state.peers.retain(|_, bucket| {
+                bucket.refill(&limit, now);
+                !bucket.is_full(&limit)
+            });
+        }
+        if let Some(limit) = self.per_method {
+            state.methods.retain(|_, bucket| {
+                bucket.refill(&limit, now);
+                !bucket.is_full(&limit)
+            });
+        }
+    }
+}
+
+fn max_period(a: Option<RpcRateLimit>, b: Option<RpcRateLimit>) -> Duration {
+    a.into_iter().chain(b).map(|l| l.period).max().unwrap_or_default()
+}
+
+/// Decrements the number of pending requests when dropped
+#[derive(Debug)]
+pub(super) struct PendingRequestGuard {
+    num_pending: Arc<AtomicUsize>,
+}
+
+impl Drop for PendingRequestGuard {
+    fn drop(&mut self) {
+        self.num_pending.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn node_id(n: u8) -> NodeId {
+        NodeId::try_from([n; 13].as_slice()).unwrap()
+    }
+
+    const PROTOCOL: ProtocolId = ProtocolId::from_static(b"/test/1");
+
+    #[test]
+    fn it_limits_requests_per_peer() {
+        let limiter = RpcRateLimiter::new(Some(RpcRateLimit::per_second(2)), None, None);
+        let now = Instant::now();
+        let a = node_id(1);
+        let b = node_id(2);
+        assert!(limiter.check_at(&a, &PROTOCOL, 0, now).is_ok());
+        assert!(limiter.check_at(&a, &PROTOCOL, 1, now).is_ok());
+        let err = limiter.check_at(&a, &PROTOCOL, 2, now).unwrap_err();
+        assert!(err.is_slow_down());
+        // Other peers are unaffected
+        assert!(limiter.check_at(&b, &PROTOCOL, 0, now).is_ok());
+        // Tokens are refilled over time
+        assert!(limiter
+            .check_at(&a, &PROTOCOL, 0, now + Duration::from_millis(500))
+            .is_ok());
+        assert!(limiter
+            .check_at(&a, &PROTOCOL, 0, now + Duration::from_millis(500))
+            .is_err());
+    }
+
+    #[test]
+    fn it_limits_requests_per_method() {
+        let limiter = RpcRateLimiter::new(
+            Some(RpcRateLimit::per_second(3)),
+            Some(RpcRateLimit::per_second(1)),
+            None,
+        );
+        let now = Instant::now();
+        let a = node_id(1);
+        assert!(limiter.check_at(&a, &PROTOCOL, 0, now).is_ok());
+        assert!(limiter.check_at(&a, &PROTOCOL, 0, now).unwrap_err().is_slow_down());
+        assert!(limiter.check_at(&a, &PROTOCOL, 1, now).is_ok());
+        assert!(limiter.check_at(&a, &PROTOCOL, 2, now).is_ok());
+        // Peer limit reached
+        assert!(limiter.check_at(&a, &PROTOCOL, 3, now).unwrap_err().is_slow_down());
+    }
+
+    #[test]
+    fn it_limits_pending_requests() {
+        let limiter = RpcRateLimiter::new(None, None, Some(2));
+        let now = Instant::now();
+        let a = node_id(1);
+        let guard1 = limiter.check_at(&a, &PROTOCOL, 0, now).unwrap();
+        let _guard2 = limiter.check_at(&a, &PROTOCOL, 0, now).unwrap();
+        assert!(limiter.check_at(&a, &PROTOCOL, 0, now).unwrap_err().is_slow_down());
+        assert_eq!(limiter.num_pending_requests(), 2);
+        drop(guard1);
+        assert_eq!(limiter.num_pending_requests(), 1);
+        assert!(limiter.check_at(&a, &PROTOCOL, 0, now).is_ok());
+    }
+
+    #[test]
+    fn it_does_not_take_tokens_for_requests_rejected_as_busy() {
+        let limiter = RpcRateLimiter::new(Some(RpcRateLimit::per_second(2)), None, Some(1));
+        let now = Instant::now();
+        let a = node_id(1);
+        let guard = limiter.check_at(&a, &PROTOCOL, 0, now).unwrap();
+        assert!(limiter.check_at(&a, &PROTOCOL, 0, now).unwrap_err().is_slow_down());
+        assert!(limiter.check_at(&a, &PROTOCOL, 0, now).unwrap_err().is_slow_down());
+        drop(guard);
+        // The busy rejections did not use up the remaining token
+        let guard = limiter.check_at(&a, &PROTOCOL, 0, now).unwrap();
+        drop(guard);
+        // A request rejected by the rate limit does not hold a pending request slot
+        assert!(limiter.check_at(&a, &PROTOCOL, 0, now).unwrap_err().is_slow_down());
+        assert_eq!(limiter.num_pending_requests(), 0);
+    }
+
+    #[test]
+    fn it_prunes_idle_buckets() {
+        let limiter = RpcRateLimiter::new(Some(RpcRateLimit::per_second(1)), None, None);
+        let now = Instant::now();
+        limiter.check_at(&node_id(1), &PROTOCOL, 0, now).unwrap();
+        limiter
+            .check_at(&node_id(2), &PROTOCOL, 0, now + Duration::from_secs(2))
+            .unwrap();
+        assert_eq!(limiter.state.lock().unwrap().peers.len(), 1);
+    }
+}
@@ -0,0 +1,188 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use crate::{peer_manager::NodeId, protocol::ProtocolId};
+
+/// A snapshot of an active RPC session
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RpcSessionInfo {
+    pub node_id: NodeId,
+    pub protocol: ProtocolId,
+    /// Time elapsed since the session was established
+    pub age: Duration,
+    /// The method currently being processed, if any
+    pub current_method: Option<u32>,
+    /// The total number of requests received in this session
+    pub num_requests: u64,
+    /// The number of requests received for each method id, ordered by method id
+    pub methods: Vec<(u32, u64)>,
+}
+
+#[derive(Debug)]
+struct SessionState {
+    node_id: NodeId,
+    protocol: ProtocolId,
+    started_at: Instant,
+    current_method: Option<u32>,
+    num_requests: u64,
+    methods: HashMap<u32, u64>,
+}
+
+impl SessionState {
+    fn to_info(&self) -> RpcSessionInfo {
+        let mut methods = self.methods.iter().map(|(m, n)| (*m, *n)).collect::<Vec<_>>();
+        methods.sort_unstable();
+        RpcSessionInfo {
+            node_id: self.node_id.clone(),
+            protocol: self.protocol.clone(),
+            age: self.started_at.elapsed(),
+            current_method: self.current_method,
+            num_requests: self.num_requests,
+            methods,
+        }
+    }
+}
+
+/// Keeps track of all active RPC sessions and the methods that are called on them
+#[derive(Debug, Clone, Default)]
+pub(super) struct RpcSessionRegistry {
+    next_id: Arc<AtomicU64>,
+    sessions: Arc<Mutex<HashMap<u64, SessionState>>>,
+}
+
+impl RpcSessionRegistry {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Registers a new session. The session is removed from the registry when the returned guard is dropped.
+    pub fn register(&self, node_id: NodeId, protocol: ProtocolId) -> RpcSessionGuard {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.lock().insert(id, SessionState {
+            node_id,
+            protocol,
+            started_at: Instant::now(),
+            current_method: None,
+            num_requests: 0,
+            methods: HashMap::new(),
+        });
+        RpcSessionGuard {
+            id,
+            registry: self.clone(),
+        }
+    }
+
+    /// Returns all active sessions, oldest first
+    pub fn sessions(&self) -> Vec<RpcSessionInfo> {
+        let mut sessions = self.lock().values().map(SessionState::to_info).collect::<Vec<_>>();
+        sessions.sort_by(|a, b| b.age.cmp(&a.age));
+        sessions
+    }
+
+    fn update<F: FnOnce(&mut SessionState)>(&self, id: u64, f: F) {
+        if let Some(state) = self.lock().get_mut(&id) {
+            f(state);
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<u64, SessionState>> {
+        self.sessions.lock().expect("RpcSessionRegistry lock poisoned")
+    }
+}
+
+/// Held by an active session. Removes the session from the registry when dropped.
+#[derive(Debug)]
+pub(super) struct RpcSessionGuard {
+    id: u64,
+    registry: RpcSessionRegistry,
+}
+
+impl RpcSessionGuard {
+    pub fn on_request_started(&self, method: u32) {
+        self.registry.update(self.id, |state| {
+            state.current_method = Some(method);
+            state.num_requests += 1;
+            *state.methods.entry(method).or_default() += 1;
+        });
+    }
+
+    pub fn on_request_complete(&self) {
+        self.registry.update(self.id, |state| {
+            state.current_method = None;
+        });
+    }
+}
+
+impl Drop for RpcSessionGuard {
+    fn drop(&mut self) {
+        self.registry.lock().remove(&self.id);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_tracks_methods_for_active_sessions() {
+        let registry = RpcSessionRegistry::new();
+        let protocol = ProtocolId::from_static(b"/test/1");
+        let guard = registry.register(NodeId::default(), protocol.clone());
+        guard.on_request_started(2);
+        guard.on_request_complete();
+        guard.on_request_started(1);
+        guard.on_request_complete();
+        guard.on_request_started(2);
+
+        let sessions = registry.sessions();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].protocol, protocol);
+        assert_eq!(sessions[0].current_method, Some(2));
+        assert_eq!(sessions[0].num_requests, 3);
+        assert_eq!(sessions[0].methods, vec![(1, 1), (2, 2)]);
+
+        guard.on_request_complete();
+        assert_eq!(registry.sessions()[0].current_method, None);
+    }
+
+    #[test]
+    fn it_removes_sessions_when_the_guard_is_dropped() {
+        let registry = RpcSessionRegistry::new();
+        let guard1 = registry.register(NodeId::default(), ProtocolId::from_static(b"/test/1"));
+        let _guard2 = registry.register(NodeId::default(), ProtocolId::from_static(b"/test/2"));
+        assert_eq!(registry.sessions().len(), 2);
+        drop(guard1);
+        let sessions = registry.sessions();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].protocol, ProtocolId::from_static(b"/test/2"));
+    }
+}
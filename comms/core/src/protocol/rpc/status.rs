@@ -106,6 +106,15 @@ impl RpcStatus {
         }
     }
 
+    /// Returns a status indicating that the server is shedding load for this peer and the client should back off before
+    /// retrying.
+    pub fn slow_down<T: ToString + ?Sized>(details: &T) -> Self {
+        Self {
+            code: RpcStatusCode::SlowDown,
+            details: details.to_string(),
+        }
+    }
+
     /// Returns a closure that logs the given error and returns a generic general error that does not leak any
     /// potentially sensitive error information. Use this function with map_err to catch "miscellaneous" errors.
     pub fn log_internal_error<'a, E: std::error::Error + 'a>(target: &'a str) -> impl Fn(E) -> Self + 'a {
@@ -145,6 +154,10 @@ impl RpcStatus {
     pub fn is_not_found(&self) -> bool {
         self.code.is_not_found()
     }
+
+    pub fn is_slow_down(&self) -> bool {
+        self.code.is_slow_down()
+    }
 }
 
 impl Display for RpcStatus {
@@ -239,6 +252,8 @@ pub enum RpcStatusCode {
     Forbidden = 9,
     /// RPC conflict error
     Conflict = 10,
+    /// The server is rate limiting this peer, the client should back off and retry later
+    SlowDown = 11,
     // The following status represents anything that is not recognised (i.e not one of the above codes).
     /// Unrecognised RPC status code
     InvalidRpcStatusCode,
@@ -257,6 +272,10 @@ impl RpcStatusCode {
         self == Self::Timeout
     }
 
+    pub fn is_slow_down(self) -> bool {
+        self == Self::SlowDown
+    }
+
     pub fn as_u32(&self) -> u32 {
         *self as u32
     }
@@ -282,6 +301,7 @@ impl From<u32> for RpcStatusCode {
             8 => ProtocolError,
             9 => Forbidden,
             10 => Conflict,
+            11 => SlowDown,
             _ => InvalidRpcStatusCode,
         }
     }
@@ -307,6 +327,7 @@ mod test {
         assert_eq!(RpcStatusCode::from(ProtocolError as u32), ProtocolError);
         assert_eq!(RpcStatusCode::from(Forbidden as u32), Forbidden);
         assert_eq!(RpcStatusCode::from(Conflict as u32), Conflict);
+        assert_eq!(RpcStatusCode::from(SlowDown as u32), SlowDown);
         assert_eq!(RpcStatusCode::from(123), InvalidRpcStatusCode);
     }
 
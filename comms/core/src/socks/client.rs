@@ -209,14 +209,14 @@ where TSocket: AsyncRead + AsyncWrite + Unpin
             0x00 => {
                 // No auth
             },
-            0x02 => {
+            // Username/password auth (RFC 1929), only if we offered it
+            0x02 if self.authentication.id() == 0x02 => {
                 self.password_authentication_protocol().await?;
             },
             0xff => {
                 return Err(SocksError::NoAcceptableAuthMethods);
             },
-            m if m != self.authentication.id() => return Err(SocksError::UnknownAuthMethod),
-            _ => unimplemented!(),
+            _ => return Err(SocksError::UnknownAuthMethod),
         }
 
         Ok(())
@@ -472,3 +472,88 @@ where TSocket: AsyncRead + AsyncWrite + Unpin
         Ok(self.len - self.ptr)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use tokio::io::duplex;
+
+    use super::*;
+
+    fn password_auth() -> Authentication {
+        Authentication::Password {
+            username: "User".to_string(),
+            password: "secret".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn it_authenticates_with_username_and_password() {
+        let (client_sock, mut server_sock) = duplex(1024);
+        let server = tokio::spawn(async move {
+            let mut buf = [0u8; 4];
+            server_sock.read_exact(&mut buf).await.unwrap();
+            assert_eq!(buf, [0x05, 2, 0x00, 0x02]);
+            server_sock.write_all(&[0x05, 0x02]).await.unwrap();
+
+            let mut buf = [0u8; 13];
+            server_sock.read_exact(&mut buf).await.unwrap();
+            assert_eq!(&buf[..], b"\x01\x04User\x06secret");
+            server_sock.write_all(&[0x01, 0x00]).await.unwrap();
+        });
+
+        let mut protocol = SocksProtocol::new(client_sock);
+        protocol.set_authentication(password_auth());
+        protocol.authenticate().await.unwrap();
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn it_returns_an_error_if_the_proxy_rejects_the_credentials() {
+        let (client_sock, mut server_sock) = duplex(1024);
+        tokio::spawn(async move {
+            let mut buf = [0u8; 4];
+            server_sock.read_exact(&mut buf).await.unwrap();
+            server_sock.write_all(&[0x05, 0x02]).await.unwrap();
+            let mut buf = [0u8; 13];
+            server_sock.read_exact(&mut buf).await.unwrap();
+            server_sock.write_all(&[0x01, 0x01]).await.unwrap();
+        });
+
+        let mut protocol = SocksProtocol::new(client_sock);
+        protocol.set_authentication(password_auth());
+        let err = protocol.authenticate().await.unwrap_err();
+        assert!(matches!(err, SocksError::PasswordAuthFailure(0x01)));
+    }
+
+    #[tokio::test]
+    async fn it_rejects_an_auth_method_that_was_not_offered() {
+        let (client_sock, mut server_sock) = duplex(1024);
+        tokio::spawn(async move {
+            let mut buf = [0u8; 3];
+            server_sock.read_exact(&mut buf).await.unwrap();
+            server_sock.write_all(&[0x05, 0x02]).await.unwrap();
+        });
+
+        let mut protocol = SocksProtocol::new(client_sock);
+        let err = protocol.authenticate().await.unwrap_err();
+        assert!(matches!(err, SocksError::UnknownAuthMethod));
+    }
+
+    #[test]
+    fn it_validates_credential_lengths() {
+        let mut client = Socks5Client::new(duplex(1).0);
+        client.with_authentication(password_auth()).unwrap();
+        client
+            .with_authentication(Authentication::Password {
+                username: String::new(),
+                password: "secret".to_string(),
+            })
+            .unwrap_err();
+        client
+            .with_authentication(Authentication::Password {
+                username: "user".to_string(),
+                password: "x".repeat(256),
+            })
+            .unwrap_err();
+    }
+}
@@ -291,6 +291,7 @@ impl ConnectivityManagerMock {
             },
             AddPeerToAllowList(_) => {},
             RemovePeerFromAllowList(_) => {},
+            SetMaxInboundConnectionsPerSubnet(_) => {},
             GetActiveConnections(reply) => {
                 self.state
                     .with_state(|state| reply.send(state.active_conns.values().cloned().collect()).unwrap())
@@ -64,6 +64,8 @@ pub fn create_dummy_peer_connection(node_id: NodeId) -> (PeerConnection, mpsc::R
             addr,
             ConnectionDirection::Inbound,
             AtomicRefCounter::new(),
+            Default::default(),
+            Default::default(),
         ),
         rx,
     )
@@ -101,6 +103,8 @@ pub async fn create_peer_connection_mock_pair(
             listen_addr.clone(),
             ConnectionDirection::Inbound,
             mock_state_in.substream_counter(),
+            Default::default(),
+            Default::default(),
         ),
         mock_state_in,
         PeerConnection::new(
@@ -111,6 +115,8 @@ pub async fn create_peer_connection_mock_pair(
             listen_addr,
             ConnectionDirection::Outbound,
             mock_state_out.substream_counter(),
+            Default::default(),
+            Default::default(),
         ),
         mock_state_out,
     )
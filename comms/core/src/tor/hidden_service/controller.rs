@@ -20,15 +20,26 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{fs, io, net::SocketAddr, sync::Arc, time::Duration};
+use std::{
+    fs,
+    io,
+    net::SocketAddr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
-use futures::{future, future::Either, pin_mut, StreamExt};
+use futures::StreamExt;
 use log::*;
 use tari_shutdown::OptionalShutdownSignal;
 use tari_utilities::hex::Hex;
 use thiserror::Error;
-use tokio::{sync::broadcast, time};
+use tokio::{
+    sync::{broadcast, mpsc},
+    time,
+};
 
+#[cfg(feature = "metrics")]
+use super::metrics;
 use crate::{
     multiaddr::Multiaddr,
     socks,
@@ -37,7 +48,10 @@ use crate::{
             commands::{AddOnionFlag, AddOnionResponse},
             TorControlEvent,
         },
-        hidden_service::TorProxyOpts,
+        hidden_service::{
+            handle::{HiddenServiceControllerHandle, HiddenServiceControllerRequest, TorHealthStatus},
+            TorProxyOpts,
+        },
         Authentication,
         HiddenService,
         HsFlags,
@@ -52,6 +66,9 @@ use crate::{
 
 const LOG_TARGET: &str = "comms::tor::hidden_service_controller";
 
+/// The time to wait between attempts to reconnect to the tor control port
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(5);
+
 #[derive(Debug, Error)]
 pub enum HiddenServiceControllerError {
     #[error("Tor client is not connected")]
@@ -74,6 +91,16 @@ pub enum HiddenServiceControllerError {
     UnrecognizedAuthenticationMethod(String),
     #[error("Failed to load tor cookie file: {0}")]
     FailedToLoadCookieFile(io::Error),
+    #[error("The hidden service controller is not running")]
+    ControllerNotRunning,
+}
+
+#[derive(Debug, Default)]
+struct HealthState {
+    is_network_live: Option<bool>,
+    num_reconnects: usize,
+    num_rotations: usize,
+    last_disconnected: Option<Instant>,
 }
 
 pub struct HiddenServiceController {
@@ -88,6 +115,9 @@ pub struct HiddenServiceController {
     is_authenticated: bool,
     proxy_opts: TorProxyOpts,
     shutdown_signal: OptionalShutdownSignal,
+    request_tx: mpsc::Sender<HiddenServiceControllerRequest>,
+    request_rx: Option<mpsc::Receiver<HiddenServiceControllerRequest>>,
+    health: HealthState,
 }
 
 impl HiddenServiceController {
@@ -102,6 +132,7 @@ impl HiddenServiceController {
         proxy_opts: TorProxyOpts,
         shutdown_signal: OptionalShutdownSignal,
     ) -> Self {
+        let (request_tx, request_rx) = mpsc::channel(10);
         Self {
             client: None,
             control_server_addr,
@@ -114,9 +145,17 @@ impl HiddenServiceController {
             is_authenticated: false,
             proxy_opts,
             shutdown_signal,
+            request_tx,
+            request_rx: Some(request_rx),
+            health: HealthState::default(),
         }
     }
 
+    /// Returns a handle that can be used to manage the hidden service once it has been created
+    pub fn get_handle(&self) -> HiddenServiceControllerHandle {
+        HiddenServiceControllerHandle::new(self.request_tx.clone())
+    }
+
     /// The address to which all tor traffic is proxied. A TCP socket should be bound to this address to receive traffic
     /// for this hidden service.
     pub fn proxied_address(&self) -> Multiaddr {
@@ -144,41 +183,52 @@ impl HiddenServiceController {
         let hidden_service = self.create_hidden_service_from_identity().await?;
         let mut shutdown_signal = hidden_service.shutdown_signal.clone();
         let mut event_stream = self.client.as_ref().unwrap().get_event_stream();
+        let mut request_rx = self
+            .request_rx
+            .take()
+            .expect("HiddenServiceController::create_hidden_service called more than once");
+        #[cfg(feature = "metrics")]
+        metrics::control_port_connected().set(1);
 
         tokio::spawn({
             async move {
                 loop {
-                    let either = future::select(&mut shutdown_signal, event_stream.next()).await;
-                    match either {
-                        Either::Left(_) => {
+                    tokio::select! {
+                        _ = &mut shutdown_signal => {
                             debug!(
                                 target: LOG_TARGET,
                                 "Tor controller shut down because the shutdown signal was received"
                             );
                             break;
                         },
-                        Either::Right((Some(Ok(TorControlEvent::TorControlDisconnected)), shutdown_signal)) => {
-                            let event_tx = self
-                                .client
-                                .as_ref()
-                                .map(|c| c.event_sender().clone())
-                                .expect("HiddenServiceController::client was None");
-                            warn!(
-                                target: LOG_TARGET,
-                                "Tor control server disconnected. Attempting to reestablish connection..."
-                            );
-                            if let Err(err) = self.reestablish_hidden_service(event_tx, shutdown_signal).await {
-                                error!(
+                        Some(request) = request_rx.recv() => {
+                            self.handle_request(request).await;
+                        },
+                        event = event_stream.next() => match event {
+                            Some(Ok(TorControlEvent::TorControlDisconnected)) => {
+                                let event_tx = self
+                                    .client
+                                    .as_ref()
+                                    .map(|c| c.event_sender().clone())
+                                    .expect("HiddenServiceController::client was None");
+                                warn!(
                                     target: LOG_TARGET,
-                                    "Failed to reestablish connection to tor control server because '{:?}'", err
+                                    "Tor control server disconnected. Attempting to reestablish connection..."
                                 );
-                                break;
-                            }
-                        },
-                        Either::Right((Some(Ok(evt)), _)) => {
-                            trace!(target: LOG_TARGET, "Tor control event: {:?}", evt);
+                                if let Err(err) = self.reestablish_hidden_service(event_tx, &mut shutdown_signal).await {
+                                    error!(
+                                        target: LOG_TARGET,
+                                        "Failed to reestablish connection to tor control server because '{:?}'", err
+                                    );
+                                    break;
+                                }
+                            },
+                            Some(Ok(evt)) => {
+                                trace!(target: LOG_TARGET, "Tor control event: {:?}", evt);
+                                self.handle_event(&evt);
+                            },
+                            _ => {},
                         },
-                        _ => {},
                     }
                 }
             }
@@ -200,39 +250,132 @@ impl HiddenServiceController {
         event_tx: broadcast::Sender<TorControlEvent>,
         shutdown_signal: &mut OptionalShutdownSignal,
     ) -> Result<(), HiddenServiceControllerError> {
-        let mut signal = Some(shutdown_signal);
+        // The new control port connection must be authenticated again before it can be used
+        self.is_authenticated = false;
+        self.health.last_disconnected = Some(Instant::now());
+        #[cfg(feature = "metrics")]
+        metrics::control_port_connected().set(0);
+
         loop {
             warn!(
                 target: LOG_TARGET,
                 "Attempting to reestablish control port connection at '{}'", self.control_server_addr
             );
-            let connect_fut = TorControlPortClient::connect(self.control_server_addr.clone(), event_tx.clone());
-            pin_mut!(connect_fut);
-            let either = future::select(connect_fut, signal.take().expect("signal was None")).await;
-            match either {
-                Either::Left((Ok(client), _)) => {
+            let result = tokio::select! {
+                result = self.reconnect_and_restore(event_tx.clone()) => result,
+                _ = &mut *shutdown_signal => break Err(HiddenServiceControllerError::ShutdownSignalInterrupt),
+            };
+            match result {
+                Ok(()) => {
                     info!(target: LOG_TARGET, "Connection to tor control port re-established");
-                    self.client = Some(client);
-                    self.authenticate().await?;
-                    self.set_events().await?;
-                    let _result = self.create_hidden_service_from_identity().await;
+                    self.health.num_reconnects += 1;
+                    #[cfg(feature = "metrics")]
+                    {
+                        metrics::control_port_connected().set(1);
+                        metrics::control_port_reconnects().inc();
+                    }
                     break Ok(());
                 },
-                Either::Left((Err(err), shutdown_signal)) => {
-                    signal = Some(shutdown_signal);
+                Err(err) => {
                     warn!(
                         target: LOG_TARGET,
                         "Failed to reestablish connection with tor control server because '{:?}'", err
                     );
-                    warn!(target: LOG_TARGET, "Will attempt again in 5 seconds...");
-                    time::sleep(Duration::from_secs(5)).await;
+                    warn!(
+                        target: LOG_TARGET,
+                        "Will attempt again in {:.0?}...", RECONNECT_INTERVAL
+                    );
+                    tokio::select! {
+                        _ = time::sleep(RECONNECT_INTERVAL) => {},
+                        _ = &mut *shutdown_signal => break Err(HiddenServiceControllerError::ShutdownSignalInterrupt),
+                    }
                 },
+            }
+        }
+    }
 
-                Either::Right(_) => {
-                    break Err(HiddenServiceControllerError::ShutdownSignalInterrupt);
-                },
+    /// Connects and re-authenticates to the control port and restores the hidden service using the current identity
+    async fn reconnect_and_restore(
+        &mut self,
+        event_tx: broadcast::Sender<TorControlEvent>,
+    ) -> Result<(), HiddenServiceControllerError> {
+        let client = TorControlPortClient::connect(self.control_server_addr.clone(), event_tx).await?;
+        self.client = Some(client);
+        self.authenticate().await?;
+        self.set_events().await?;
+        self.create_hidden_service_from_identity().await?;
+        Ok(())
+    }
+
+    async fn handle_request(&mut self, request: HiddenServiceControllerRequest) {
+        match request {
+            HiddenServiceControllerRequest::RotateIdentity(reply) => {
+                let _result = reply.send(self.rotate_identity().await);
+            },
+            HiddenServiceControllerRequest::GetHealthStatus(reply) => {
+                let _result = reply.send(self.health_status());
+            },
+        }
+    }
+
+    fn handle_event(&mut self, event: &TorControlEvent) {
+        let is_live = match event {
+            TorControlEvent::NetworkLivenessUp => true,
+            TorControlEvent::NetworkLivenessDown => {
+                warn!(target: LOG_TARGET, "Tor reports that the network is down");
+                false
+            },
+            _ => return,
+        };
+        self.health.is_network_live = Some(is_live);
+        #[cfg(feature = "metrics")]
+        metrics::network_liveness().set(i64::from(is_live));
+    }
+
+    fn health_status(&self) -> TorHealthStatus {
+        TorHealthStatus {
+            is_control_port_connected: self.is_authenticated &&
+                self.client.as_ref().map_or(false, |c| c.is_connected()),
+            is_network_live: self.health.is_network_live,
+            num_reconnects: self.health.num_reconnects,
+            num_rotations: self.health.num_rotations,
+            last_disconnected: self.health.last_disconnected.map(|t| t.elapsed()),
+            service_id: self.identity.as_ref().map(|id| id.service_id.clone()),
+        }
+    }
+
+    /// Creates a new onion service with a fresh key and removes the previous one
+    async fn rotate_identity(&mut self) -> Result<TorIdentity, HiddenServiceControllerError> {
+        let flags = self.onion_flags();
+        let port_mapping = self.proxied_port_mapping;
+        let resp = self.client_mut()?.add_onion(flags, port_mapping, None).await?;
+        let private_key = resp
+            .private_key
+            .clone()
+            .expect("Tor server MUST return private key according to spec");
+        let new_identity = TorIdentity {
+            private_key,
+            service_id: resp.service_id,
+            onion_port: resp.onion_port,
+        };
+
+        if let Some(old_identity) = self.identity.replace(new_identity.clone()) {
+            if let Err(err) = self.client_mut()?.del_onion(&old_identity.service_id).await {
+                warn!(
+                    target: LOG_TARGET,
+                    "Failed to remove previous onion service '{}': {}", old_identity.service_id, err
+                );
             }
         }
+
+        self.health.num_rotations += 1;
+        #[cfg(feature = "metrics")]
+        metrics::identity_rotations().inc();
+        info!(
+            target: LOG_TARGET,
+            "Rotated onion service key. New service id is '{}'", new_identity.service_id
+        );
+        Ok(new_identity)
     }
 
     fn client_mut(&mut self) -> Result<&mut TorControlPortClient, HiddenServiceControllerError> {
@@ -341,7 +484,7 @@ impl HiddenServiceController {
         debug!(target: LOG_TARGET, "Tor SOCKS address is '{}'", socks_addr);
 
         // Initialize a onion hidden service - either from the given private key or by creating a new one
-        match self.identity.take() {
+        match self.identity.clone() {
             Some(identity) => {
                 let resp = self.create_or_reuse_onion(&identity).await?;
                 self.identity = Some(TorIdentity {
@@ -380,6 +523,14 @@ impl HiddenServiceController {
         })
     }
 
+    fn onion_flags(&self) -> Vec<AddOnionFlag> {
+        let mut flags = Vec::new();
+        if self.hs_flags.contains(HsFlags::DETACH) {
+            flags.push(AddOnionFlag::Detach);
+        }
+        flags
+    }
+
     pub fn set_proxied_addr(&mut self, addr: &Multiaddr) {
         self.proxied_port_mapping.set_proxied_addr(
             multiaddr_to_socketaddr(addr).expect("set_proxied_addr: multiaddr must be a valid TCP socket address"),
@@ -390,11 +541,7 @@ impl HiddenServiceController {
         &mut self,
         identity: &TorIdentity,
     ) -> Result<AddOnionResponse, HiddenServiceControllerError> {
-        let mut flags = Vec::new();
-        if self.hs_flags.contains(HsFlags::DETACH) {
-            flags.push(AddOnionFlag::Detach);
-        }
-
+        let flags = self.onion_flags();
         let port_mapping = self.proxied_port_mapping;
 
         let client = self.client_mut()?;
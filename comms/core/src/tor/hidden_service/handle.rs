@@ -0,0 +1,118 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{fmt, time::Duration};
+
+use tokio::sync::{mpsc, oneshot};
+
+use crate::tor::{HiddenServiceControllerError, TorIdentity};
+
+/// Requests that can be made to a running hidden service controller
+#[derive(Debug)]
+pub(super) enum HiddenServiceControllerRequest {
+    RotateIdentity(oneshot::Sender<Result<TorIdentity, HiddenServiceControllerError>>),
+    GetHealthStatus(oneshot::Sender<TorHealthStatus>),
+}
+
+/// A snapshot of the health of the Tor control port connection and hidden service
+#[derive(Debug, Clone, Default)]
+pub struct TorHealthStatus {
+    /// True if the control port connection is established and authenticated
+    pub is_control_port_connected: bool,
+    /// The last network liveness reported by the tor daemon, or None if no NETWORK_LIVENESS event has been received
+    pub is_network_live: Option<bool>,
+    /// The number of times the control port connection has been re-established
+    pub num_reconnects: usize,
+    /// The number of times the onion service key has been rotated
+    pub num_rotations: usize,
+    /// The time since the control port connection was last lost, if it has ever been lost
+    pub last_disconnected: Option<Duration>,
+    /// The service id of the active hidden service
+    pub service_id: Option<String>,
+}
+
+impl fmt::Display for TorHealthStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Control port: {}",
+            if self.is_control_port_connected {
+                "connected"
+            } else {
+                "disconnected"
+            }
+        )?;
+        writeln!(f, "Network liveness: {}", match self.is_network_live {
+            Some(true) => "up",
+            Some(false) => "down",
+            None => "unknown",
+        })?;
+        writeln!(f, "Reconnects: {}", self.num_reconnects)?;
+        writeln!(f, "Key rotations: {}", self.num_rotations)?;
+        if let Some(elapsed) = self.last_disconnected {
+            writeln!(f, "Last disconnected: {:.0?} ago", elapsed)?;
+        }
+        if let Some(service_id) = self.service_id.as_ref() {
+            writeln!(f, "Service ID: {}", service_id)?;
+        }
+        Ok(())
+    }
+}
+
+/// Handle to a running [HiddenServiceController](crate::tor::HiddenServiceController). This is obtained before the
+/// controller is handed to the transport and remains usable for the lifetime of the hidden service.
+#[derive(Debug, Clone)]
+pub struct HiddenServiceControllerHandle {
+    sender: mpsc::Sender<HiddenServiceControllerRequest>,
+}
+
+impl HiddenServiceControllerHandle {
+    pub(super) fn new(sender: mpsc::Sender<HiddenServiceControllerRequest>) -> Self {
+        Self { sender }
+    }
+
+    /// Replaces the onion service with one using a newly generated key. The old onion service is removed once the new
+    /// one has been created. Returns the new identity, which the caller is responsible for persisting and
+    /// advertising to the network.
+    pub async fn rotate_identity(&self) -> Result<TorIdentity, HiddenServiceControllerError> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.sender
+            .send(HiddenServiceControllerRequest::RotateIdentity(reply_tx))
+            .await
+            .map_err(|_| HiddenServiceControllerError::ControllerNotRunning)?;
+        reply_rx
+            .await
+            .map_err(|_| HiddenServiceControllerError::ControllerNotRunning)?
+    }
+
+    /// Returns the current health status of the Tor control port connection
+    pub async fn get_health_status(&self) -> Result<TorHealthStatus, HiddenServiceControllerError> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.sender
+            .send(HiddenServiceControllerRequest::GetHealthStatus(reply_tx))
+            .await
+            .map_err(|_| HiddenServiceControllerError::ControllerNotRunning)?;
+        reply_rx
+            .await
+            .map_err(|_| HiddenServiceControllerError::ControllerNotRunning)
+    }
+}
@@ -26,6 +26,12 @@ pub use builder::{HiddenServiceBuilder, HiddenServiceBuilderError, HsFlags};
 mod controller;
 pub use controller::{HiddenServiceController, HiddenServiceControllerError};
 
+mod handle;
+pub use handle::{HiddenServiceControllerHandle, TorHealthStatus};
+
+#[cfg(feature = "metrics")]
+mod metrics;
+
 mod proxy_opts;
 use std::fmt;
 
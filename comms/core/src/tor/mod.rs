@@ -49,6 +49,8 @@ pub use hidden_service::{
     HiddenServiceBuilderError,
     HiddenServiceController,
     HiddenServiceControllerError,
+    HiddenServiceControllerHandle,
     HsFlags,
+    TorHealthStatus,
     TorIdentity,
 };
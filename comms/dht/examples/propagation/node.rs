@@ -85,6 +85,7 @@ pub async fn create<P: AsRef<Path>>(
             minor_version: 0,
             network_wire_byte: 0x25,
             user_agent: "/tari/propagator/0.0.1".to_string(),
+            capabilities: Default::default(),
         })
         .with_node_identity(node_identity.clone())
         .with_dial_backoff(ConstantBackoff::new(Duration::from_millis(500)))
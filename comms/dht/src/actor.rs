@@ -60,6 +60,7 @@ use crate::{
     outbound::{DhtOutboundError, OutboundMessageRequester, SendMessageParams},
     proto::{dht::JoinMessage, envelope::DhtMessageType},
     storage::{DbConnection, DhtDatabase, DhtMetadataKey, StorageError},
+    store_forward::SafEpochKeyStore,
     DhtConfig,
     DhtDiscoveryRequester,
 };
@@ -285,6 +286,7 @@ pub struct DhtActor {
     shutdown_signal: ShutdownSignal,
     request_rx: mpsc::Receiver<DhtRequest>,
     msg_hash_dedup_cache: DedupCacheDatabase,
+    saf_epoch_keys: SafEpochKeyStore,
 }
 
 impl DhtActor {
@@ -298,15 +300,25 @@ impl DhtActor {
         outbound_requester: OutboundMessageRequester,
         request_rx: mpsc::Receiver<DhtRequest>,
         discovery: DhtDiscoveryRequester,
+        saf_epoch_keys: SafEpochKeyStore,
         shutdown_signal: ShutdownSignal,
     ) -> Self {
         debug!(
             target: LOG_TARGET,
-            "Message dedup cache will be trimmed to capacity every {}s",
-            config.dedup_cache_trim_interval.as_secs()
+            "Message dedup cache will be trimmed to capacity every {}s (adaptive sizing {})",
+            config.dedup_cache_trim_interval.as_secs(),
+            if config.dedup_cache_adaptive_sizing {
+                "enabled"
+            } else {
+                "disabled"
+            }
         );
         Self {
-            msg_hash_dedup_cache: DedupCacheDatabase::new(conn.clone(), config.dedup_cache_capacity),
+            msg_hash_dedup_cache: DedupCacheDatabase::new(
+                conn.clone(),
+                config.dedup_cache_capacity,
+                config.dedup_cache_ttl,
+            ),
             config,
             database: DhtDatabase::new(conn),
             outbound_requester,
@@ -316,6 +328,7 @@ impl DhtActor {
             discovery,
             shutdown_signal,
             request_rx,
+            saf_epoch_keys,
         }
     }
 
@@ -346,6 +359,7 @@ impl DhtActor {
 
         let mut dedup_cache_trim_ticker = time::interval(self.config.dedup_cache_trim_interval);
         dedup_cache_trim_ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let mut last_dedup_cache_trim = None;
 
         loop {
             tokio::select! {
@@ -361,6 +375,13 @@ impl DhtActor {
                 },
 
                 _ = dedup_cache_trim_ticker.tick() => {
+                    if self.config.dedup_cache_adaptive_sizing {
+                        // The first tick fires immediately, so there is no message rate to sample yet
+                        if let Some(last_trim) = last_dedup_cache_trim.replace(Instant::now()) {
+                            self.msg_hash_dedup_cache
+                                .adjust_capacity(last_trim.elapsed(), self.config.dedup_cache_max_capacity);
+                        }
+                    }
                     if let Err(err) = self.msg_hash_dedup_cache.trim_entries() {
                         error!(target: LOG_TARGET, "Error when trimming message dedup cache: {:?}", err);
                     }
@@ -416,11 +437,13 @@ impl DhtActor {
                 let peer_manager = Arc::clone(&self.peer_manager);
                 let outbound_requester = self.outbound_requester.clone();
                 let excluded_dial_addresses = self.config.excluded_dial_addresses.clone();
+                let saf_epoch_keys = self.saf_epoch_keys.clone();
                 Box::pin(Self::broadcast_join(
                     node_identity,
                     peer_manager,
                     excluded_dial_addresses.into_vec(),
                     outbound_requester,
+                    saf_epoch_keys,
                 ))
             },
             MsgHashCacheInsert {
@@ -535,6 +558,7 @@ impl DhtActor {
         peer_manager: Arc<PeerManager>,
         excluded_dial_addresses: Vec<MultiaddrRange>,
         mut outbound_requester: OutboundMessageRequester,
+        saf_epoch_keys: SafEpochKeyStore,
     ) -> Result<(), DhtActorError> {
         DhtActor::check_if_addresses_excluded(
             excluded_dial_addresses,
@@ -542,7 +566,8 @@ impl DhtActor {
             node_identity.node_id().clone(),
         )
         .await?;
-        let message = JoinMessage::from(&node_identity);
+        let mut message = JoinMessage::from(&node_identity);
+        message.saf_epoch_keys = saf_epoch_keys.announcement().as_ref().map(Into::into);
 
         debug!(target: LOG_TARGET, "Sending Join message to closest peers");
 
@@ -623,18 +648,24 @@ impl DhtActor {
             },
             SelectedPeers(peers) => peers,
             Broadcast(exclude) => {
+                // Select more connections than needed so that persistently slow peers can be passed over in favour of
+                // peers with a low RTT
                 let connections = connectivity
                     .select_connections(ConnectivitySelection::random_nodes(
-                        config.broadcast_factor,
+                        config.broadcast_factor.saturating_mul(2),
                         exclude.clone(),
                     ))
                     .await?;
 
-                let candidates = connections
+                let mut candidates = connections
                     .iter()
                     .map(|c| c.peer_node_id())
                     .cloned()
                     .collect::<Vec<_>>();
+                peer_manager
+                    .latency_tracker()
+                    .sort_by_latency(&mut candidates, |node_id| node_id);
+                candidates.truncate(config.broadcast_factor);
 
                 if candidates.is_empty() {
                     warn!(
@@ -1005,6 +1036,7 @@ mod test {
             outbound_requester,
             actor_rx,
             discovery,
+            SafEpochKeyStore::disabled(),
             shutdown.to_signal(),
         );
 
@@ -1049,6 +1081,7 @@ mod test {
                 outbound_requester,
                 actor_rx,
                 discovery,
+                SafEpochKeyStore::disabled(),
                 shutdown_signal,
             )
             .spawn();
@@ -1124,6 +1157,7 @@ mod test {
             outbound_requester,
             actor_rx,
             discovery,
+            SafEpochKeyStore::disabled(),
             shutdown.to_signal(),
         );
 
@@ -1174,6 +1208,7 @@ mod test {
             outbound_requester,
             actor_rx,
             discovery,
+            SafEpochKeyStore::disabled(),
             shutdown.to_signal(),
         );
 
@@ -1236,6 +1271,47 @@ mod test {
         }
     }
 
+    #[tokio::test]
+    async fn dedup_cache_adaptive_sizing() {
+        let min_capacity = 10;
+        let max_capacity = 150;
+        let dedup_cache_db = DedupCacheDatabase::new(db_connection().await, min_capacity, Duration::from_secs(100));
+
+        for i in 0..20u8 {
+            dedup_cache_db
+                .add_msg_hash(&[1u8, 2, i], &CommsPublicKey::default())
+                .unwrap();
+        }
+        // Duplicates do not count towards the message rate
+        dedup_cache_db
+            .add_msg_hash(&[1u8, 2, 0], &CommsPublicKey::default())
+            .unwrap();
+
+        // 20 new messages in 10s at a TTL of 100s requires a capacity of 200, the capacity moves half way there
+        let capacity = dedup_cache_db.adjust_capacity(Duration::from_secs(10), 1_000);
+        assert_eq!(capacity, 105);
+        assert_eq!(dedup_cache_db.capacity(), 105);
+
+        // No new messages, the capacity shrinks back towards the minimum
+        let capacity = dedup_cache_db.adjust_capacity(Duration::from_secs(10), 1_000);
+        assert_eq!(capacity, 52);
+        let capacity = dedup_cache_db.adjust_capacity(Duration::from_secs(100), 1_000);
+        assert_eq!(capacity, 26);
+        for _ in 0..5 {
+            dedup_cache_db.adjust_capacity(Duration::from_secs(100), 1_000);
+        }
+        assert_eq!(dedup_cache_db.capacity(), min_capacity);
+
+        // The capacity never exceeds the maximum
+        for i in 0..100u8 {
+            dedup_cache_db
+                .add_msg_hash(&[3u8, 4, i], &CommsPublicKey::default())
+                .unwrap();
+        }
+        let capacity = dedup_cache_db.adjust_capacity(Duration::from_secs(1), max_capacity);
+        assert_eq!(capacity, max_capacity);
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
     async fn test_select_peers() {
         let node_identity = make_node_identity();
@@ -1269,6 +1345,7 @@ mod test {
             outbound_requester,
             actor_rx,
             discovery,
+            SafEpochKeyStore::disabled(),
             shutdown.to_signal(),
         );
 
@@ -1370,6 +1447,7 @@ mod test {
             outbound_requester,
             actor_rx,
             discovery,
+            SafEpochKeyStore::disabled(),
             shutdown.to_signal(),
         );
 
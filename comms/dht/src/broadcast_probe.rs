@@ -0,0 +1,361 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Diagnostics for measuring how well broadcasts propagate through the network. A probe is propagated like a join
+//! message, and each node that has `respond_to_broadcast_probes` enabled sends a receipt directly back to the origin,
+//! naming the peer that it first received the probe from. The origin collects receipts for a while and reports the
+//! coverage, hop counts and latencies of the probe.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use log::*;
+use rand::{rngs::OsRng, RngCore};
+use tari_comms::{
+    peer_manager::{NodeIdentity, PeerManager, PeerManagerError, PeerQuery},
+    types::CommsPublicKey,
+};
+use thiserror::Error;
+use tokio::time;
+
+use crate::{
+    envelope::NodeDestination,
+    outbound::{DhtOutboundError, OutboundMessageRequester, SendMessageParams},
+    proto::{dht::BroadcastProbeMessage, envelope::DhtMessageType},
+};
+
+const LOG_TARGET: &str = "comms::dht::broadcast_probe";
+
+#[derive(Debug, Error)]
+pub enum BroadcastProbeError {
+    #[error("DhtOutboundError: {0}")]
+    DhtOutboundError(#[from] DhtOutboundError),
+    #[error("PeerManagerError: {0}")]
+    PeerManagerError(#[from] PeerManagerError),
+}
+
+/// A receipt for a probe sent by this node
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProbeReceipt {
+    /// The node that received the probe
+    pub responder: CommsPublicKey,
+    /// The peer that the responder first received the probe from
+    pub received_from: CommsPublicKey,
+    /// The time between sending the probe and receiving the receipt
+    pub latency: Duration,
+}
+
+struct PendingProbe {
+    started: Instant,
+    receipts: Vec<ProbeReceipt>,
+}
+
+/// The probes that this node is waiting for receipts for. This is cheap to clone.
+#[derive(Clone, Default)]
+pub struct BroadcastProbes {
+    pending: Arc<Mutex<HashMap<u64, PendingProbe>>>,
+}
+
+impl BroadcastProbes {
+    fn start(&self, probe_id: u64) {
+        self.pending
+            .lock()
+            .expect("BroadcastProbes lock poisoned")
+            .insert(probe_id, PendingProbe {
+                started: Instant::now(),
+                receipts: Vec::new(),
+            });
+    }
+
+    /// Records a receipt for a pending probe. Returns false if the probe is not pending or the responder has already
+    /// sent a receipt for it.
+    pub(crate) fn add_receipt(&self, probe_id: u64, responder: CommsPublicKey, received_from: CommsPublicKey) -> bool {
+        let mut pending = self.pending.lock().expect("BroadcastProbes lock poisoned");
+        let Some(probe) = pending.get_mut(&probe_id) else {
+            return false;
+        };
+        if probe.receipts.iter().any(|r| r.responder == responder) {
+            return false;
+        }
+        let latency = probe.started.elapsed();
+        probe.receipts.push(ProbeReceipt {
+            responder,
+            received_from,
+            latency,
+        });
+        true
+    }
+
+    fn finish(&self, probe_id: u64) -> Vec<ProbeReceipt> {
+        self.pending
+            .lock()
+            .expect("BroadcastProbes lock poisoned")
+            .remove(&probe_id)
+            .map(|probe| probe.receipts)
+            .unwrap_or_default()
+    }
+}
+
+/// Sends broadcast probes and collects their receipts
+#[derive(Clone)]
+pub struct BroadcastProbeRequester {
+    node_identity: Arc<NodeIdentity>,
+    peer_manager: Arc<PeerManager>,
+    outbound_requester: OutboundMessageRequester,
+    probes: BroadcastProbes,
+}
+
+impl BroadcastProbeRequester {
+    pub(crate) fn new(
+        node_identity: Arc<NodeIdentity>,
+        peer_manager: Arc<PeerManager>,
+        outbound_requester: OutboundMessageRequester,
+        probes: BroadcastProbes,
+    ) -> Self {
+        Self {
+            node_identity,
+            peer_manager,
+            outbound_requester,
+            probes,
+        }
+    }
+
+    /// Propagates a probe through the network and reports on the receipts that arrive within `wait`
+    pub async fn probe(&mut self, wait: Duration) -> Result<BroadcastProbeReport, BroadcastProbeError> {
+        let probe_id = OsRng.next_u64();
+        self.probes.start(probe_id);
+        debug!(target: LOG_TARGET, "Sending broadcast probe {}", probe_id);
+        let result = self
+            .outbound_requester
+            .send_message_no_header_no_wait(
+                SendMessageParams::new()
+                    .propagate(NodeDestination::Unknown, vec![])
+                    .with_debug_info("Sending broadcast probe".to_string())
+                    .with_dht_message_type(DhtMessageType::BroadcastProbe)
+                    .force_origin()
+                    .finish(),
+                BroadcastProbeMessage { probe_id },
+            )
+            .await;
+        if let Err(err) = result {
+            self.probes.finish(probe_id);
+            return Err(err.into());
+        }
+
+        time::sleep(wait).await;
+        let receipts = self.probes.finish(probe_id);
+        let num_known_nodes = self
+            .peer_manager
+            .perform_query(
+                PeerQuery::new()
+                    .select_where(|peer| peer.features.is_node() && !peer.is_banned() && !peer.is_offline()),
+            )
+            .await?
+            .len();
+        Ok(BroadcastProbeReport::new(
+            probe_id,
+            self.node_identity.public_key(),
+            receipts,
+            num_known_nodes,
+        ))
+    }
+}
+
+/// The receipts that were received for a probe
+#[derive(Debug, Clone)]
+pub struct BroadcastProbeReport {
+    pub probe_id: u64,
+    /// The receipts in the order that they were received
+    pub receipts: Vec<ProbeReceipt>,
+    /// The number of hops that the probe took to reach each responder. Responders that received the probe from a
+    /// peer that did not respond are not included.
+    pub hops: HashMap<CommsPublicKey, usize>,
+    /// The number of base nodes known to this node that are not banned or offline
+    pub num_known_nodes: usize,
+}
+
+impl BroadcastProbeReport {
+    pub fn new(probe_id: u64, origin: &CommsPublicKey, receipts: Vec<ProbeReceipt>, num_known_nodes: usize) -> Self {
+        let hops = hop_counts(origin, &receipts);
+        Self {
+            probe_id,
+            receipts,
+            hops,
+            num_known_nodes,
+        }
+    }
+
+    /// The fraction of known base nodes that sent a receipt
+    pub fn coverage(&self) -> f64 {
+        if self.num_known_nodes == 0 {
+            return 0.0;
+        }
+        (self.receipts.len() as f64 / self.num_known_nodes as f64).min(1.0)
+    }
+
+    pub fn max_hops(&self) -> Option<usize> {
+        self.hops.values().max().copied()
+    }
+
+    /// Returns the latency that `percentile` percent of receipts arrived within
+    pub fn latency_percentile(&self, percentile: usize) -> Option<Duration> {
+        let mut latencies = self.receipts.iter().map(|r| r.latency).collect::<Vec<_>>();
+        if latencies.is_empty() {
+            return None;
+        }
+        latencies.sort();
+        let index = (latencies.len() - 1) * percentile.min(100) / 100;
+        latencies.get(index).copied()
+    }
+
+    /// The number of responders at each hop count, starting at one hop
+    pub fn hop_histogram(&self) -> Vec<usize> {
+        let mut histogram = vec![0; self.max_hops().unwrap_or(0)];
+        for hops in self.hops.values() {
+            histogram[hops - 1] += 1;
+        }
+        histogram
+    }
+}
+
+impl fmt::Display for BroadcastProbeReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Probe: {}", self.probe_id)?;
+        writeln!(
+            f,
+            "Receipts: {} of {} known base node(s) ({:.1}%)",
+            self.receipts.len(),
+            self.num_known_nodes,
+            self.coverage() * 100.0
+        )?;
+        for (hops, count) in self.hop_histogram().iter().enumerate() {
+            writeln!(f, "{} hop(s): {}", hops + 1, count)?;
+        }
+        let unknown_hops = self.receipts.len() - self.hops.len();
+        if unknown_hops > 0 {
+            writeln!(f, "Unknown hops: {}", unknown_hops)?;
+        }
+        if let (Some(p50), Some(p90), Some(max)) = (
+            self.latency_percentile(50),
+            self.latency_percentile(90),
+            self.latency_percentile(100),
+        ) {
+            writeln!(f, "Latency: p50 {:.2?}, p90 {:.2?}, max {:.2?}", p50, p90, max)?;
+        }
+        Ok(())
+    }
+}
+
+/// Follows the chain of peers that each responder received the probe from back to the origin
+fn hop_counts(origin: &CommsPublicKey, receipts: &[ProbeReceipt]) -> HashMap<CommsPublicKey, usize> {
+    let received_from = receipts
+        .iter()
+        .map(|r| (&r.responder, &r.received_from))
+        .collect::<HashMap<_, _>>();
+    let mut hops = HashMap::with_capacity(received_from.len());
+    for responder in received_from.keys() {
+        let mut path = Vec::new();
+        let mut current = *responder;
+        let base = loop {
+            if current == origin {
+                break Some(0);
+            }
+            if let Some(n) = hops.get(current) {
+                break Some(*n);
+            }
+            // A cycle can only be reported by misbehaving peers
+            if path.contains(&current) {
+                break None;
+            }
+            path.push(current);
+            match received_from.get(current) {
+                Some(from) => current = *from,
+                None => break None,
+            }
+        };
+        if let Some(base) = base {
+            for (i, pk) in path.into_iter().rev().enumerate() {
+                hops.insert(pk.clone(), base + i + 1);
+            }
+        }
+    }
+    hops
+}
+
+#[cfg(test)]
+mod test {
+    use tari_crypto::keys::PublicKey;
+
+    use super::*;
+
+    fn random_pk() -> CommsPublicKey {
+        CommsPublicKey::random_keypair(&mut OsRng).1
+    }
+
+    fn receipt(responder: &CommsPublicKey, received_from: &CommsPublicKey, millis: u64) -> ProbeReceipt {
+        ProbeReceipt {
+            responder: responder.clone(),
+            received_from: received_from.clone(),
+            latency: Duration::from_millis(millis),
+        }
+    }
+
+    #[test]
+    fn it_counts_hops_back_to_the_origin() {
+        let origin = random_pk();
+        let (a, b, c, d, unknown) = (random_pk(), random_pk(), random_pk(), random_pk(), random_pk());
+        // Receipts can arrive before the receipt of the peer they were received from
+        let receipts = vec![
+            receipt(&c, &b, 30),
+            receipt(&a, &origin, 10),
+            receipt(&b, &a, 20),
+            receipt(&d, &unknown, 15),
+        ];
+        let report = BroadcastProbeReport::new(1, &origin, receipts, 8);
+        assert_eq!(report.hops.get(&a), Some(&1));
+        assert_eq!(report.hops.get(&b), Some(&2));
+        assert_eq!(report.hops.get(&c), Some(&3));
+        assert_eq!(report.hops.get(&d), None);
+        assert_eq!(report.max_hops(), Some(3));
+        assert_eq!(report.hop_histogram(), vec![1, 1, 1]);
+        assert!((report.coverage() - 0.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn it_ignores_cycles() {
+        let origin = random_pk();
+        let (a, b) = (random_pk(), random_pk());
+        let report = BroadcastProbeReport::new(1, &origin, vec![receipt(&a, &b, 10), receipt(&b, &a, 10)], 2);
+        assert!(report.hops.is_empty());
+        assert_eq!(report.max_hops(), None);
+    }
+
+    #[test]
+    fn it_calculates_latency_percentiles() {
+        let origin = random_pk();
+        let receipts = (1..=10).map(|i| receipt(&random_pk(), &origin, i * 10)).collect();
+        let report = BroadcastProbeReport::new(1, &origin, receipts, 10);
+        assert_eq!(report.latency_percentile(50), Some(Duration::from_millis(50)));
+        assert_eq!(report.latency_percentile(100), Some(Duration::from_millis(100)));
+        assert_eq!(report.latency_percentile(0), Some(Duration::from_millis(10)));
+        let empty = BroadcastProbeReport::new(1, &origin, vec![], 10);
+        assert_eq!(empty.latency_percentile(50), None);
+    }
+
+    #[test]
+    fn it_only_records_receipts_for_pending_probes() {
+        let probes = BroadcastProbes::default();
+        let (a, b) = (random_pk(), random_pk());
+        assert!(!probes.add_receipt(1, a.clone(), b.clone()));
+        probes.start(1);
+        assert!(probes.add_receipt(1, a.clone(), b.clone()));
+        assert!(!probes.add_receipt(1, a.clone(), b.clone()));
+        assert_eq!(probes.finish(1).len(), 1);
+        assert!(!probes.add_receipt(1, b, a));
+        assert!(probes.finish(1).is_empty());
+    }
+}
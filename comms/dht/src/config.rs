@@ -60,13 +60,26 @@ pub struct DhtConfig {
     /// Default: 4
     pub propagation_factor: usize,
     pub saf: SafConfig,
-    /// The max capacity of the message hash cache
+    /// The max capacity of the message hash cache. If `dedup_cache_adaptive_sizing` is enabled, this is the minimum
+    /// capacity.
     /// Default: 2,500
     pub dedup_cache_capacity: usize,
     /// The periodic trim interval for items in the message hash cache
     /// Default: 300s (5 mins)
     #[serde(with = "serializers::seconds")]
     pub dedup_cache_trim_interval: Duration,
+    /// Entries in the message hash cache that have not been seen for this long are removed on the next trim
+    /// Default: 1 hour
+    #[serde(with = "serializers::seconds")]
+    pub dedup_cache_ttl: Duration,
+    /// Set to true to resize the message hash cache on each trim so that it can hold every unique message received
+    /// within `dedup_cache_ttl` at the observed message rate, bounded by `dedup_cache_capacity` and
+    /// `dedup_cache_max_capacity`
+    /// Default: true
+    pub dedup_cache_adaptive_sizing: bool,
+    /// The maximum capacity of the message hash cache when adaptive sizing is enabled
+    /// Default: 50,000
+    pub dedup_cache_max_capacity: usize,
     /// The number of occurrences of a message is allowed to pass through the DHT pipeline before being
     /// deduped/discarded
     /// Default: 1
@@ -83,6 +96,10 @@ pub struct DhtConfig {
     /// Default: 10 minutes
     #[serde(with = "serializers::seconds")]
     pub join_cooldown_interval: Duration,
+    /// Set to true to propagate broadcast probes and send a receipt back to the origin of each probe. Probes are
+    /// used by network operators to measure broadcast health, and are discarded if this is false.
+    /// Default: false
+    pub respond_to_broadcast_probes: bool,
     pub connectivity: DhtConnectivityConfig,
     /// Network discovery config
     pub network_discovery: NetworkDiscoveryConfig,
@@ -185,12 +202,16 @@ impl Default for DhtConfig {
             saf: Default::default(),
             dedup_cache_capacity: 2_500,
             dedup_cache_trim_interval: Duration::from_secs(5 * 60),
+            dedup_cache_ttl: Duration::from_secs(60 * 60),
+            dedup_cache_adaptive_sizing: true,
+            dedup_cache_max_capacity: 50_000,
             dedup_allowed_message_occurrences: 1,
             database_url: DbConnectionUrl::Memory,
             discovery_request_timeout: Duration::from_secs(2 * 60),
             connectivity: DhtConnectivityConfig::default(),
             auto_join: false,
             join_cooldown_interval: Duration::from_secs(10 * 60),
+            respond_to_broadcast_probes: false,
             network_discovery: Default::default(),
             ban_duration: Duration::from_secs(2 * 60 * 60),
             ban_duration_short: Duration::from_secs(10 * 60),
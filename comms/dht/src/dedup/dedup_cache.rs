@@ -20,12 +20,22 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use std::{
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
 use chrono::{NaiveDateTime, Utc};
 use diesel::{dsl, result::DatabaseErrorKind, sql_types, ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl};
 use log::*;
 use tari_comms::types::CommsPublicKey;
 use tari_utilities::hex::{to_hex, Hex};
 
+#[cfg(feature = "metrics")]
+use super::metrics;
 use crate::{
     schema::dedup_cache,
     storage::{DbConnection, StorageError},
@@ -46,16 +56,32 @@ struct DedupCacheEntry {
 #[derive(Clone)]
 pub struct DedupCacheDatabase {
     connection: DbConnection,
-    capacity: usize,
+    min_capacity: usize,
+    capacity: Arc<AtomicUsize>,
+    ttl: Duration,
+    misses_since_resize: Arc<AtomicU64>,
 }
 
 impl DedupCacheDatabase {
-    pub fn new(connection: DbConnection, capacity: usize) -> Self {
+    pub fn new(connection: DbConnection, capacity: usize, ttl: Duration) -> Self {
         debug!(
             target: LOG_TARGET,
-            "Message dedup cache capacity initialized at {}", capacity,
+            "Message dedup cache capacity initialized at {} with a TTL of {:.0?}", capacity, ttl
         );
-        Self { connection, capacity }
+        #[cfg(feature = "metrics")]
+        metrics::capacity().set(i64::try_from(capacity).unwrap_or(i64::MAX));
+        Self {
+            connection,
+            min_capacity: capacity,
+            capacity: Arc::new(AtomicUsize::new(capacity)),
+            ttl,
+            misses_since_resize: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Returns the current capacity of the cache
+    pub fn capacity(&self) -> usize {
+        self.capacity.load(Ordering::Relaxed)
     }
 
     /// Adds the body hash to the cache, returning the number of hits (inclusive) that have been recorded for this body
@@ -63,13 +89,55 @@ impl DedupCacheDatabase {
     pub fn add_msg_hash(&self, msg_hash: &[u8], public_key: &CommsPublicKey) -> Result<u32, StorageError> {
         let hit_count = self.insert_body_hash_or_update_stats(&to_hex(msg_hash), &public_key.to_hex())?;
 
-        if hit_count == 0 {
-            warn!(
+        match hit_count {
+            0 => {
+                warn!(
+                    target: LOG_TARGET,
+                    "Unable to insert new entry into message dedup cache"
+                );
+            },
+            1 => {
+                self.misses_since_resize.fetch_add(1, Ordering::Relaxed);
+                #[cfg(feature = "metrics")]
+                metrics::misses().inc();
+            },
+            _ => {
+                #[cfg(feature = "metrics")]
+                metrics::hits().inc();
+            },
+        }
+        Ok(hit_count)
+    }
+
+    /// Resizes the cache so that it can hold every unique message received within the TTL at the message rate
+    /// observed since the last resize. The capacity moves half way towards the target on each call to dampen
+    /// short bursts, and is kept between the initial capacity and `max_capacity`. Returns the new capacity.
+    pub fn adjust_capacity(&self, elapsed: Duration, max_capacity: usize) -> usize {
+        let misses = self.misses_since_resize.swap(0, Ordering::Relaxed);
+        let current = self.capacity();
+        if elapsed.is_zero() {
+            return current;
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let rate = misses as f64 / elapsed.as_secs_f64();
+        #[allow(clippy::cast_possible_truncation)]
+        #[allow(clippy::cast_sign_loss)]
+        let target = (rate * self.ttl.as_secs_f64()).ceil() as usize;
+        let new_capacity = (current.saturating_add(target) / 2)
+            .min(max_capacity)
+            .max(self.min_capacity);
+
+        if new_capacity != current {
+            debug!(
                 target: LOG_TARGET,
-                "Unable to insert new entry into message dedup cache"
+                "Message dedup cache resized from {} to {} ({:.2} new messages/s)", current, new_capacity, rate
             );
+            self.capacity.store(new_capacity, Ordering::Relaxed);
+            #[cfg(feature = "metrics")]
+            metrics::capacity().set(i64::try_from(new_capacity).unwrap_or(i64::MAX));
         }
-        Ok(hit_count)
+        new_capacity
     }
 
     pub fn get_hit_count(&self, body_hash: &[u8]) -> Result<u32, StorageError> {
@@ -84,19 +152,30 @@ impl DedupCacheDatabase {
         Ok(hit_count.unwrap_or(0) as u32)
     }
 
-    /// Trims the dedup cache to the configured limit by removing the oldest entries
+    /// Trims the dedup cache by removing entries that have not been seen within the TTL, and then the oldest entries
+    /// until the cache is within its capacity
     pub fn trim_entries(&self) -> Result<usize, StorageError> {
         #[allow(clippy::cast_possible_wrap)]
-        let capacity = self.capacity as i64;
-        let mut num_removed = 0;
+        let capacity = self.capacity() as i64;
         let mut conn = self.connection.get_pooled_connection()?;
+
+        let mut num_expired = 0;
+        let expired_before = chrono::Duration::from_std(self.ttl)
+            .ok()
+            .and_then(|ttl| Utc::now().naive_utc().checked_sub_signed(ttl));
+        if let Some(expired_before) = expired_before {
+            num_expired = diesel::delete(dedup_cache::table.filter(dedup_cache::last_hit_at.lt(expired_before)))
+                .execute(&mut conn)?;
+        }
+
+        let mut num_evicted = 0;
         let msg_count = dedup_cache::table
             .select(dsl::count(dedup_cache::id))
             .first::<i64>(&mut conn)?;
         // Hysteresis added to minimize database impact
         if msg_count > capacity {
             let remove_count = msg_count - capacity;
-            num_removed = diesel::sql_query(
+            num_evicted = diesel::sql_query(
                 "DELETE FROM dedup_cache WHERE id IN (SELECT id FROM dedup_cache ORDER BY last_hit_at ASC LIMIT $1)",
             )
             .bind::<sql_types::BigInt, _>(remove_count)
@@ -104,9 +183,21 @@ impl DedupCacheDatabase {
         }
         debug!(
             target: LOG_TARGET,
-            "Message dedup cache: count {}, capacity {}, removed {}", msg_count, capacity, num_removed,
+            "Message dedup cache: count {}, capacity {}, expired {}, evicted {}",
+            msg_count,
+            capacity,
+            num_expired,
+            num_evicted,
         );
-        Ok(num_removed)
+
+        #[cfg(feature = "metrics")]
+        {
+            metrics::evictions("expired").inc_by(num_expired as u64);
+            metrics::evictions("capacity").inc_by(num_evicted as u64);
+            metrics::size().set(msg_count.min(capacity));
+        }
+
+        Ok(num_expired + num_evicted)
     }
 
     /// Insert new row into the table or updates an existing row. Returns the number of hits for this body hash.
@@ -25,6 +25,8 @@
 //! Keeps track of messages seen before by this node and discards duplicates.
 
 mod dedup_cache;
+#[cfg(feature = "metrics")]
+mod metrics;
 
 use std::task::Poll;
 
@@ -39,6 +39,7 @@ use tower::{layer::Layer, Service, ServiceBuilder};
 use self::outbound::OutboundMessageRequester;
 use crate::{
     actor::{DhtActor, DhtRequest, DhtRequester},
+    broadcast_probe::{BroadcastProbeRequester, BroadcastProbes},
     connectivity::{DhtConnectivity, MetricsCollector, MetricsCollectorHandle},
     discovery::{DhtDiscoveryRequest, DhtDiscoveryRequester, DhtDiscoveryService},
     event::{DhtEventReceiver, DhtEventSender},
@@ -51,9 +52,15 @@ use crate::{
     outbound::DhtOutboundRequest,
     proto::envelope::DhtMessageType,
     rpc,
-    storage::{DbConnection, StorageError},
+    storage::{DbConnection, DhtDatabase, StorageError},
     store_forward,
-    store_forward::{StoreAndForwardError, StoreAndForwardRequest, StoreAndForwardRequester, StoreAndForwardService},
+    store_forward::{
+        SafEpochKeyStore,
+        StoreAndForwardError,
+        StoreAndForwardRequest,
+        StoreAndForwardRequester,
+        StoreAndForwardService,
+    },
     DedupLayer,
     DhtActorError,
     DhtBuilder,
@@ -105,6 +112,10 @@ pub struct Dht {
     event_publisher: DhtEventSender,
     /// Used by MetricsLayer to collect metrics and to inform heuristics for peer banning
     metrics_collector: MetricsCollectorHandle,
+    /// Store and forward epoch keys for this node and its peers
+    saf_epoch_keys: SafEpochKeyStore,
+    /// Broadcast probes sent by this node that are waiting for receipts
+    broadcast_probes: BroadcastProbes,
 }
 
 impl Dht {
@@ -124,10 +135,17 @@ impl Dht {
 
         let metrics_collector = MetricsCollector::spawn();
 
+        let conn = DbConnection::connect_and_migrate(&config.database_url)
+            .map_err(DhtInitializationError::DatabaseMigrationFailed)?;
+        let saf_epoch_keys =
+            SafEpochKeyStore::initialize(&config.saf, node_identity.clone(), DhtDatabase::new(conn.clone()))?;
+
         let dht = Self {
             node_identity,
             peer_manager,
             metrics_collector,
+            saf_epoch_keys,
+            broadcast_probes: BroadcastProbes::default(),
             config: Arc::new(config),
             outbound_tx,
             dht_sender,
@@ -138,9 +156,6 @@ impl Dht {
             event_publisher,
         };
 
-        let conn = DbConnection::connect_and_migrate(&dht.config.database_url.clone())
-            .map_err(DhtInitializationError::DatabaseMigrationFailed)?;
-
         dht.network_discovery_service(shutdown_signal.clone()).spawn();
         dht.connectivity_service(shutdown_signal.clone()).spawn();
         dht.store_and_forward_service(
@@ -183,6 +198,7 @@ impl Dht {
             self.outbound_requester(),
             request_receiver,
             self.discovery_service_requester(),
+            self.saf_epoch_keys.clone(),
             shutdown_signal,
         )
     }
@@ -270,6 +286,16 @@ impl Dht {
         StoreAndForwardRequester::new(self.saf_sender.clone())
     }
 
+    /// Returns a requester that sends broadcast probes and reports on their receipts
+    pub fn broadcast_probe_requester(&self) -> BroadcastProbeRequester {
+        BroadcastProbeRequester::new(
+            Arc::clone(&self.node_identity),
+            Arc::clone(&self.peer_manager),
+            self.outbound_requester(),
+            self.broadcast_probes.clone(),
+        )
+    }
+
     /// Get a subscription to `DhtEvents`
     pub fn subscribe_dht_events(&self) -> DhtEventReceiver {
         self.event_publisher.subscribe()
@@ -305,6 +331,7 @@ impl Dht {
                 self.config.clone(),
                 self.node_identity.clone(),
                 self.connectivity.clone(),
+                self.saf_epoch_keys.clone(),
             ))
             .layer(DedupLayer::new(
                 self.dht_requester(),
@@ -333,6 +360,7 @@ impl Dht {
                 Arc::clone(&self.node_identity),
                 self.outbound_requester(),
                 self.saf_response_signal_sender.clone(),
+                self.saf_epoch_keys.clone(),
             ))
             .layer(inbound::DhtHandlerLayer::new(
                 self.config.clone(),
@@ -341,6 +369,8 @@ impl Dht {
                 self.dht_requester(),
                 self.discovery_service_requester(),
                 self.outbound_requester(),
+                self.saf_epoch_keys.clone(),
+                self.broadcast_probes.clone(),
             ))
             .into_inner()
     }
@@ -371,6 +401,7 @@ impl Dht {
                 Arc::clone(&self.node_identity),
                 self.dht_requester(),
                 self.discovery_service_requester(),
+                self.saf_epoch_keys.clone(),
                 &self.config,
             ))
             .layer(MessageLoggingLayer::new(format!(
@@ -23,6 +23,7 @@
 use std::cmp;
 
 use rand::{rngs::OsRng, RngCore};
+use tari_comms::{message::MessageExt, wrap_in_envelope_body, BytesMut};
 
 /// Trait that exposes conversion to a protobuf i32 enum type.
 pub trait ToProtoEnum {
@@ -67,6 +68,15 @@ impl<T> OutboundDomainMessage<T> {
     }
 }
 
+impl<T: prost::Message> OutboundDomainMessage<T> {
+    /// Encodes this message as a cleartext envelope body with a propagation header. Since the propagation header has a
+    /// fixed nonce, every node that propagates the same message produces an identical body.
+    pub fn into_propagation_body(self) -> BytesMut {
+        let header = self.to_propagation_header();
+        wrap_in_envelope_body!(header, self.inner).encode_into_bytes_mut()
+    }
+}
+
 pub use crate::proto::message_header::MessageHeader;
 
 impl MessageHeader {
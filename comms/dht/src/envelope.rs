@@ -103,7 +103,10 @@ impl DhtMessageType {
     }
 
     pub fn is_dht_message(self) -> bool {
-        self.is_dht_discovery() || self.is_dht_discovery_response() || self.is_dht_join()
+        self.is_dht_discovery() ||
+            self.is_dht_discovery_response() ||
+            self.is_dht_join() ||
+            self.is_dht_broadcast_probe()
     }
 
     pub fn is_forwardable(self) -> bool {
@@ -122,6 +125,13 @@ impl DhtMessageType {
         matches!(self, DhtMessageType::Join)
     }
 
+    pub fn is_dht_broadcast_probe(self) -> bool {
+        matches!(
+            self,
+            DhtMessageType::BroadcastProbe | DhtMessageType::BroadcastProbeReceipt
+        )
+    }
+
     pub fn is_saf_message(self) -> bool {
         use DhtMessageType::{SafRequestMessages, SafStoredMessages};
         matches!(self, SafRequestMessages | SafStoredMessages)
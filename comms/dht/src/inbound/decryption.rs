@@ -20,7 +20,7 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{convert::TryInto, sync::Arc, task::Poll, time::Duration};
+use std::{convert::TryInto, iter, sync::Arc, task::Poll, time::Duration};
 
 use futures::{future::BoxFuture, task::Context};
 use log::*;
@@ -30,7 +30,7 @@ use tari_comms::{
     message::EnvelopeBody,
     peer_manager::NodeIdentity,
     pipeline::PipelineError,
-    types::CommsDHKE,
+    types::{CommsDHKE, CommsPublicKey, CommsSecretKey},
     BytesMut,
 };
 use tari_utilities::ByteArray;
@@ -41,6 +41,7 @@ use crate::{
     crypt,
     inbound::message::{DecryptedDhtMessage, DhtInboundMessage, ValidatedDhtInboundMessage},
     message_signature::{MessageSignature, ProtoMessageSignature},
+    store_forward::SafEpochKeyStore,
     DhtConfig,
 };
 
@@ -65,14 +66,21 @@ pub struct DecryptionLayer {
     node_identity: Arc<NodeIdentity>,
     connectivity: ConnectivityRequester,
     config: Arc<DhtConfig>,
+    saf_epoch_keys: SafEpochKeyStore,
 }
 
 impl DecryptionLayer {
-    pub fn new(config: Arc<DhtConfig>, node_identity: Arc<NodeIdentity>, connectivity: ConnectivityRequester) -> Self {
+    pub fn new(
+        config: Arc<DhtConfig>,
+        node_identity: Arc<NodeIdentity>,
+        connectivity: ConnectivityRequester,
+        saf_epoch_keys: SafEpochKeyStore,
+    ) -> Self {
         Self {
             node_identity,
             connectivity,
             config,
+            saf_epoch_keys,
         }
     }
 }
@@ -85,6 +93,7 @@ impl<S> Layer<S> for DecryptionLayer {
             self.config.clone(),
             self.node_identity.clone(),
             self.connectivity.clone(),
+            self.saf_epoch_keys.clone(),
             service,
         )
     }
@@ -96,6 +105,7 @@ pub struct DecryptionService<S> {
     config: Arc<DhtConfig>,
     node_identity: Arc<NodeIdentity>,
     connectivity: ConnectivityRequester,
+    saf_epoch_keys: SafEpochKeyStore,
     inner: S,
 }
 
@@ -104,12 +114,14 @@ impl<S> DecryptionService<S> {
         config: Arc<DhtConfig>,
         node_identity: Arc<NodeIdentity>,
         connectivity: ConnectivityRequester,
+        saf_epoch_keys: SafEpochKeyStore,
         service: S,
     ) -> Self {
         Self {
             node_identity,
             connectivity,
             config,
+            saf_epoch_keys,
             inner: service,
         }
     }
@@ -133,6 +145,7 @@ where
             self.inner.clone(),
             Arc::clone(&self.node_identity),
             self.connectivity.clone(),
+            self.saf_epoch_keys.clone(),
             self.config.ban_duration,
             msg,
         ))
@@ -146,6 +159,7 @@ where S: Service<DecryptedDhtMessage, Response = (), Error = PipelineError>
         next_service: S,
         node_identity: Arc<NodeIdentity>,
         mut connectivity: ConnectivityRequester,
+        saf_epoch_keys: SafEpochKeyStore,
         ban_duration: Duration,
         message: DhtInboundMessage,
     ) -> Result<(), PipelineError> {
@@ -153,7 +167,7 @@ where S: Service<DecryptedDhtMessage, Response = (), Error = PipelineError>
         let source = message.source_peer.clone();
         let trace_id = message.dht_header.message_tag;
         let tag = message.tag;
-        match Self::validate_and_decrypt_message(node_identity, message).await {
+        match Self::validate_and_decrypt_message(node_identity, &saf_epoch_keys, message).await {
             Ok(msg) => {
                 trace!(target: LOG_TARGET, "Passing onto next service (Trace: {})", msg.tag);
                 next_service.oneshot(msg).await
@@ -188,6 +202,7 @@ where S: Service<DecryptedDhtMessage, Response = (), Error = PipelineError>
     #[allow(clippy::too_many_lines)]
     async fn validate_and_decrypt_message(
         node_identity: Arc<NodeIdentity>,
+        saf_epoch_keys: &SafEpochKeyStore,
         message: DhtInboundMessage,
     ) -> Result<DecryptedDhtMessage, DecryptionError> {
         // Perform initial checks and check the message signature if needed
@@ -231,33 +246,26 @@ where S: Service<DecryptedDhtMessage, Response = (), Error = PipelineError>
             .ephemeral_public_key
             .as_ref()
             .ok_or(DecryptionError::BadEncryptedMessageSemantics)?;
-        let shared_ephemeral_secret = CommsDHKE::new(node_identity.secret_key(), ephemeral_public_key);
         let message = validated_msg.message();
-
-        // Unmask the sender public key using an offset mask derived from the ECDH exchange
-        let mask = crypt::generate_key_mask(&shared_ephemeral_secret)
-            .map_err(|_| DecryptionError::MessageRejectDecryptionFailed)?;
-        let mask_inverse = mask.invert().ok_or(DecryptionError::MessageRejectDecryptionFailed)?;
         let sender_masked_public_key = validated_msg
             .authenticated_origin()
             .ok_or(DecryptionError::MessageRejectDecryptionFailed)?;
-        let sender_public_key = mask_inverse * sender_masked_public_key;
 
-        trace!(
-            target: LOG_TARGET,
-            "Attempting to decrypt message body from origin public key '{}', {} (Trace: {})",
-            sender_public_key,
-            message.tag,
-            message.dht_header.message_tag
-        );
+        // The message may have been encrypted to our key for the epoch in which it expires, or to our identity key
+        let epoch_secret_key = header
+            .expires
+            .and_then(|expires| saf_epoch_keys.secret_key_for(expires));
+        let mut result = Err(DecryptionError::MessageRejectDecryptionFailed);
+        for secret_key in epoch_secret_key.iter().chain(iter::once(node_identity.secret_key())) {
+            result =
+                Self::attempt_decrypt_with_key(secret_key, ephemeral_public_key, sender_masked_public_key, message);
+            if result.is_ok() {
+                break;
+            }
+        }
 
-        // Decrypt and verify the message
-        match Self::attempt_decrypt_message_body(
-            &shared_ephemeral_secret,
-            &message.body,
-            sender_masked_public_key.as_bytes(),
-        ) {
-            Ok(message_body) => {
+        match result {
+            Ok((message_body, sender_public_key)) => {
                 debug!(
                     target: LOG_TARGET,
                     "Message successfully decrypted, {} (Trace: {})", message.tag, message.dht_header.message_tag
@@ -329,6 +337,39 @@ where S: Service<DecryptedDhtMessage, Response = (), Error = PipelineError>
         ))
     }
 
+    /// Completes the ephemeral key exchange using the given secret key, unmasks the sender public key and attempts to
+    /// decrypt the message body.
+    fn attempt_decrypt_with_key(
+        secret_key: &CommsSecretKey,
+        ephemeral_public_key: &CommsPublicKey,
+        sender_masked_public_key: &CommsPublicKey,
+        message: &DhtInboundMessage,
+    ) -> Result<(EnvelopeBody, CommsPublicKey), DecryptionError> {
+        let shared_ephemeral_secret = CommsDHKE::new(secret_key, ephemeral_public_key);
+
+        // Unmask the sender public key using an offset mask derived from the ECDH exchange
+        let mask = crypt::generate_key_mask(&shared_ephemeral_secret)
+            .map_err(|_| DecryptionError::MessageRejectDecryptionFailed)?;
+        let mask_inverse = mask.invert().ok_or(DecryptionError::MessageRejectDecryptionFailed)?;
+        let sender_public_key = mask_inverse * sender_masked_public_key;
+
+        trace!(
+            target: LOG_TARGET,
+            "Attempting to decrypt message body from origin public key '{}', {} (Trace: {})",
+            sender_public_key,
+            message.tag,
+            message.dht_header.message_tag
+        );
+
+        // Decrypt and verify the message
+        let message_body = Self::attempt_decrypt_message_body(
+            &shared_ephemeral_secret,
+            &message.body,
+            sender_masked_public_key.as_bytes(),
+        )?;
+        Ok((message_body, sender_public_key))
+    }
+
     fn attempt_decrypt_message_body(
         shared_secret: &CommsDHKE,
         message_body: &[u8],
@@ -434,7 +475,13 @@ mod test {
                 future::ready(Result::<(), PipelineError>::Ok(()))
             }
         });
-        let mut service = DecryptionService::new(Default::default(), node_identity, connectivity, service);
+        let mut service = DecryptionService::new(
+            Default::default(),
+            node_identity,
+            connectivity,
+            SafEpochKeyStore::disabled(),
+            service,
+        );
 
         // Receive the message and check for the expected error
         let err = service.call(message).await.unwrap_err();
@@ -470,7 +517,13 @@ mod test {
                 future::ready(Result::<(), PipelineError>::Ok(()))
             }
         });
-        let mut service = DecryptionService::new(Default::default(), node_identity, connectivity, service);
+        let mut service = DecryptionService::new(
+            Default::default(),
+            node_identity,
+            connectivity,
+            SafEpochKeyStore::disabled(),
+            service,
+        );
 
         // Receive the message and assert there were no errors
         block_on(service.call(message)).unwrap();
@@ -496,7 +549,13 @@ mod test {
         let service = service_fn(|_: DecryptedDhtMessage| future::ready(Result::<(), PipelineError>::Ok(())));
         let node_identity = make_node_identity();
         let (connectivity, _) = create_connectivity_mock();
-        let mut service = DecryptionService::new(Default::default(), node_identity, connectivity, service);
+        let mut service = DecryptionService::new(
+            Default::default(),
+            node_identity,
+            connectivity,
+            SafEpochKeyStore::disabled(),
+            service,
+        );
 
         counter_context!(cx, counter);
 
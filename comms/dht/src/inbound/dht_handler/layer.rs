@@ -26,7 +26,14 @@ use tari_comms::peer_manager::{NodeIdentity, PeerManager};
 use tower::layer::Layer;
 
 use super::middleware::DhtHandlerMiddleware;
-use crate::{discovery::DhtDiscoveryRequester, outbound::OutboundMessageRequester, DhtConfig, DhtRequester};
+use crate::{
+    broadcast_probe::BroadcastProbes,
+    discovery::DhtDiscoveryRequester,
+    outbound::OutboundMessageRequester,
+    store_forward::SafEpochKeyStore,
+    DhtConfig,
+    DhtRequester,
+};
 
 pub struct DhtHandlerLayer {
     config: Arc<DhtConfig>,
@@ -35,6 +42,8 @@ pub struct DhtHandlerLayer {
     dht: DhtRequester,
     outbound_service: OutboundMessageRequester,
     discovery_requester: DhtDiscoveryRequester,
+    saf_epoch_keys: SafEpochKeyStore,
+    broadcast_probes: BroadcastProbes,
 }
 
 impl DhtHandlerLayer {
@@ -45,6 +54,8 @@ impl DhtHandlerLayer {
         dht: DhtRequester,
         discovery_requester: DhtDiscoveryRequester,
         outbound_service: OutboundMessageRequester,
+        saf_epoch_keys: SafEpochKeyStore,
+        broadcast_probes: BroadcastProbes,
     ) -> Self {
         Self {
             config,
@@ -53,6 +64,8 @@ impl DhtHandlerLayer {
             dht,
             outbound_service,
             discovery_requester,
+            saf_epoch_keys,
+            broadcast_probes,
         }
     }
 }
@@ -69,6 +82,8 @@ impl<S> Layer<S> for DhtHandlerLayer {
             self.dht.clone(),
             self.discovery_requester.clone(),
             self.config.clone(),
+            self.saf_epoch_keys.clone(),
+            self.broadcast_probes.clone(),
         )
     }
 }
@@ -31,9 +31,11 @@ use tower::Service;
 
 use super::task::ProcessDhtMessage;
 use crate::{
+    broadcast_probe::BroadcastProbes,
     discovery::DhtDiscoveryRequester,
     inbound::DecryptedDhtMessage,
     outbound::OutboundMessageRequester,
+    store_forward::SafEpochKeyStore,
     DhtConfig,
     DhtRequester,
 };
@@ -47,6 +49,8 @@ pub struct DhtHandlerMiddleware<S> {
     outbound_service: OutboundMessageRequester,
     discovery_requester: DhtDiscoveryRequester,
     config: Arc<DhtConfig>,
+    saf_epoch_keys: SafEpochKeyStore,
+    broadcast_probes: BroadcastProbes,
 }
 
 impl<S> DhtHandlerMiddleware<S> {
@@ -58,6 +62,8 @@ impl<S> DhtHandlerMiddleware<S> {
         dht: DhtRequester,
         discovery_requester: DhtDiscoveryRequester,
         config: Arc<DhtConfig>,
+        saf_epoch_keys: SafEpochKeyStore,
+        broadcast_probes: BroadcastProbes,
     ) -> Self {
         Self {
             next_service,
@@ -67,6 +73,8 @@ impl<S> DhtHandlerMiddleware<S> {
             outbound_service,
             discovery_requester,
             config,
+            saf_epoch_keys,
+            broadcast_probes,
         }
     }
 }
@@ -95,6 +103,8 @@ where
                 self.discovery_requester.clone(),
                 message,
                 self.config.clone(),
+                self.saf_epoch_keys.clone(),
+                self.broadcast_probes.clone(),
             )
             .run(),
         )
@@ -20,7 +20,10 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{convert::TryInto, sync::Arc};
+use std::{
+    convert::{TryFrom, TryInto},
+    sync::Arc,
+};
 
 use log::*;
 use tari_comms::{
@@ -35,16 +38,25 @@ use tower::{Service, ServiceExt};
 
 use crate::{
     actor::OffenceSeverity,
+    broadcast_probe::BroadcastProbes,
     discovery::DhtDiscoveryRequester,
     envelope::NodeDestination,
     inbound::{error::DhtInboundError, message::DecryptedDhtMessage},
     outbound::{OutboundMessageRequester, SendMessageParams},
     peer_validator::{DhtPeerValidatorError, PeerValidator},
     proto::{
-        dht::{DiscoveryMessage, DiscoveryResponseMessage, JoinMessage},
+        dht::{
+            BroadcastProbeMessage,
+            BroadcastProbeReceiptMessage,
+            DiscoveryMessage,
+            DiscoveryResponseMessage,
+            JoinMessage,
+            SafEpochKeys,
+        },
         envelope::DhtMessageType,
     },
     rpc::UnvalidatedPeerInfo,
+    store_forward::{SafEpochKeyAnnouncement, SafEpochKeyStore},
     DhtConfig,
     DhtRequester,
 };
@@ -60,6 +72,8 @@ pub struct ProcessDhtMessage<S> {
     message: Option<DecryptedDhtMessage>,
     discovery_requester: DhtDiscoveryRequester,
     config: Arc<DhtConfig>,
+    saf_epoch_keys: SafEpochKeyStore,
+    broadcast_probes: BroadcastProbes,
 }
 
 impl<S> ProcessDhtMessage<S>
@@ -74,6 +88,8 @@ where S: Service<DecryptedDhtMessage, Response = (), Error = PipelineError>
         discovery_requester: DhtDiscoveryRequester,
         message: DecryptedDhtMessage,
         config: Arc<DhtConfig>,
+        saf_epoch_keys: SafEpochKeyStore,
+        broadcast_probes: BroadcastProbes,
     ) -> Self {
         Self {
             next_service,
@@ -84,6 +100,8 @@ where S: Service<DecryptedDhtMessage, Response = (), Error = PipelineError>
             discovery_requester,
             message: Some(message),
             config,
+            saf_epoch_keys,
+            broadcast_probes,
         }
     }
 
@@ -128,6 +146,8 @@ where S: Service<DecryptedDhtMessage, Response = (), Error = PipelineError>
             DhtMessageType::Join => self.handle_join(message).await?,
             DhtMessageType::Discovery => self.handle_discover(message).await?,
             DhtMessageType::DiscoveryResponse => self.handle_discover_response(message).await?,
+            DhtMessageType::BroadcastProbe => self.handle_broadcast_probe(message).await?,
+            DhtMessageType::BroadcastProbeReceipt => self.handle_broadcast_probe_receipt(message).await?,
             // Not a DHT message, call downstream middleware
             _ => {
                 trace!(
@@ -215,6 +235,7 @@ where S: Service<DecryptedDhtMessage, Response = (), Error = PipelineError>
             "Received join Message from '{}' {}", authenticated_pk, join_msg
         );
 
+        let saf_epoch_keys = join_msg.saf_epoch_keys.clone();
         let validator = PeerValidator::new(&self.config);
         let maybe_existing = self.peer_manager.find_by_public_key(&authenticated_pk).await?;
         let valid_peer = self
@@ -225,6 +246,7 @@ where S: Service<DecryptedDhtMessage, Response = (), Error = PipelineError>
                     .map_err(Into::into),
             )
             .await?;
+        self.add_saf_epoch_keys(&authenticated_pk, saf_epoch_keys);
 
         let is_banned = valid_peer.is_banned();
         let valid_peer_node_id = valid_peer.node_id.clone();
@@ -342,6 +364,7 @@ where S: Service<DecryptedDhtMessage, Response = (), Error = PipelineError>
             return Ok(());
         }
 
+        self.add_saf_epoch_keys(authenticated_origin, discover_msg.saf_epoch_keys.clone());
         self.discovery_requester
             .notify_discovery_response_received(discover_msg)
             .await?;
@@ -435,6 +458,7 @@ where S: Service<DecryptedDhtMessage, Response = (), Error = PipelineError>
             peer_features: self.node_identity.features().bits(),
             nonce,
             identity_signature: self.node_identity.identity_signature_read().as_ref().map(Into::into),
+            saf_epoch_keys: self.saf_epoch_keys.announcement().as_ref().map(Into::into),
         };
 
         trace!(target: LOG_TARGET, "Sending discovery response to {}", dest_public_key);
@@ -454,6 +478,164 @@ where S: Service<DecryptedDhtMessage, Response = (), Error = PipelineError>
         Ok(())
     }
 
+    /// Sends a receipt for a broadcast probe back to its origin and propagates the probe further
+    async fn handle_broadcast_probe(&mut self, message: DecryptedDhtMessage) -> Result<(), DhtInboundError> {
+        if !self.config.respond_to_broadcast_probes {
+            trace!(
+                target: LOG_TARGET,
+                "Discarding broadcast probe because respond_to_broadcast_probes is disabled"
+            );
+            return Ok(());
+        }
+
+        let DecryptedDhtMessage {
+            decryption_result,
+            dht_header,
+            source_peer,
+            authenticated_origin,
+            ..
+        } = message;
+
+        let Some(origin) = authenticated_origin else {
+            warn!(
+                target: LOG_TARGET,
+                "Received BroadcastProbeMessage that did not have an authenticated origin from source peer {}. Banning source", source_peer
+            );
+            self.dht
+                .ban_peer(
+                    source_peer.public_key.clone(),
+                    OffenceSeverity::Low,
+                    "Received BroadcastProbeMessage that did not have an authenticated origin",
+                )
+                .await;
+            return Ok(());
+        };
+
+        if origin == *self.node_identity.public_key() {
+            debug!(target: LOG_TARGET, "Received our own broadcast probe. Discarding it.");
+            return Ok(());
+        }
+
+        let body = decryption_result.expect("already checked that this message decrypted successfully");
+        let probe = self
+            .ban_on_offence(
+                &origin,
+                body.decode_part::<BroadcastProbeMessage>(0)
+                    .map_err(Into::into)
+                    .and_then(|o| o.ok_or(DhtInboundError::InvalidMessageBody)),
+            )
+            .await?;
+
+        debug!(
+            target: LOG_TARGET,
+            "Received broadcast probe {} from '{}' via '{}'",
+            probe.probe_id,
+            origin,
+            source_peer.node_id.short_str()
+        );
+
+        let receipt = BroadcastProbeReceiptMessage {
+            probe_id: probe.probe_id,
+            received_from: source_peer.public_key.to_vec(),
+        };
+        self.outbound_service
+            .send_message_no_header_no_wait(
+                SendMessageParams::new()
+                    .direct_public_key(origin.clone())
+                    .with_debug_info("Sending broadcast probe receipt".to_string())
+                    .with_destination(NodeDestination::Unknown)
+                    .with_dht_message_type(DhtMessageType::BroadcastProbeReceipt)
+                    .force_origin()
+                    .finish(),
+                receipt,
+            )
+            .await?;
+
+        self.outbound_service
+            .send_raw_no_wait(
+                SendMessageParams::new()
+                    .propagate(NodeDestination::Unknown, vec![
+                        NodeId::from_public_key(&origin),
+                        source_peer.node_id.clone(),
+                    ])
+                    .with_debug_info("Propagating broadcast probe".to_string())
+                    .with_dht_header(dht_header)
+                    .finish(),
+                body.encode_into_bytes_mut(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn handle_broadcast_probe_receipt(&mut self, message: DecryptedDhtMessage) -> Result<(), DhtInboundError> {
+        let msg = message
+            .success()
+            .expect("already checked that this message decrypted successfully");
+
+        let Some(responder) = message.authenticated_origin.as_ref() else {
+            warn!(
+                target: LOG_TARGET,
+                "Received BroadcastProbeReceiptMessage that did not have an authenticated origin: {}. Banning source", message
+            );
+            self.dht
+                .ban_peer(
+                    message.source_peer.public_key.clone(),
+                    OffenceSeverity::Low,
+                    "Received BroadcastProbeReceiptMessage that did not have an authenticated origin",
+                )
+                .await;
+            return Ok(());
+        };
+
+        let receipt = self
+            .ban_on_offence(
+                responder,
+                msg.decode_part::<BroadcastProbeReceiptMessage>(0)
+                    .map_err(Into::into)
+                    .and_then(|o| o.ok_or(DhtInboundError::InvalidMessageBody)),
+            )
+            .await?;
+        let received_from = self
+            .ban_on_offence(
+                responder,
+                CommsPublicKey::from_canonical_bytes(&receipt.received_from)
+                    .map_err(|_| DhtInboundError::InvalidMessageBody),
+            )
+            .await?;
+
+        if !self
+            .broadcast_probes
+            .add_receipt(receipt.probe_id, responder.clone(), received_from)
+        {
+            debug!(
+                target: LOG_TARGET,
+                "Ignoring receipt from '{}' for broadcast probe {} that is not pending", responder, receipt.probe_id
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Records a store and forward epoch key announcement from a peer. Invalid announcements are ignored, and messages
+    /// to the peer are encrypted to its identity key.
+    fn add_saf_epoch_keys(&self, public_key: &CommsPublicKey, saf_epoch_keys: Option<SafEpochKeys>) {
+        let Some(saf_epoch_keys) = saf_epoch_keys else {
+            return;
+        };
+        match SafEpochKeyAnnouncement::try_from(saf_epoch_keys) {
+            Ok(announcement) => {
+                self.saf_epoch_keys.add_peer_announcement(public_key, announcement);
+            },
+            Err(err) => {
+                debug!(
+                    target: LOG_TARGET,
+                    "Ignoring malformed SAF epoch key announcement from peer '{}': {}", public_key, err
+                );
+            },
+        }
+    }
+
     async fn ban_on_offence<T>(
         &mut self,
         authenticated_pk: &CommsPublicKey,
@@ -74,6 +74,9 @@ mod test_utils;
 mod actor;
 pub use actor::{DhtActorError, DhtRequest, DhtRequester};
 
+mod broadcast_probe;
+pub use broadcast_probe::{BroadcastProbeError, BroadcastProbeReport, BroadcastProbeRequester, ProbeReceipt};
+
 mod builder;
 pub use builder::DhtBuilder;
 
@@ -101,7 +104,7 @@ mod storage;
 pub use storage::DbConnectionUrl;
 
 mod dedup;
-pub use dedup::DedupLayer;
+pub use dedup::{create_message_hash, DedupLayer};
 
 mod filter;
 mod logging_middleware;
@@ -147,3 +150,18 @@ pub fn comms_dht_hash_domain_key_mask() -> DomainSeparatedHasher<Blake2b<U64>, D
 pub fn comms_dht_hash_domain_message_signature() -> DomainSeparatedHasher<Blake2b<U64>, DHTCommsHashDomain> {
     DomainSeparatedHasher::<Blake2b<U64>, DHTCommsHashDomain>::new_with_label("message_signature")
 }
+
+/// Hash domain used to ratchet store and forward epoch key seeds
+pub fn comms_dht_hash_domain_saf_epoch_seed() -> DomainSeparatedHasher<Blake2b<U32>, DHTCommsHashDomain> {
+    DomainSeparatedHasher::<Blake2b<U32>, DHTCommsHashDomain>::new_with_label("saf_epoch_seed")
+}
+
+/// Hash domain used to derive store and forward epoch secret keys from an epoch seed
+pub fn comms_dht_hash_domain_saf_epoch_key() -> DomainSeparatedHasher<Blake2b<U64>, DHTCommsHashDomain> {
+    DomainSeparatedHasher::<Blake2b<U64>, DHTCommsHashDomain>::new_with_label("saf_epoch_key")
+}
+
+/// Hash domain used for store and forward epoch key announcement signatures
+pub fn comms_dht_hash_domain_saf_epoch_announcement() -> DomainSeparatedHasher<Blake2b<U64>, DHTCommsHashDomain> {
+    DomainSeparatedHasher::<Blake2b<U64>, DHTCommsHashDomain>::new_with_label("saf_epoch_announcement")
+}
@@ -60,6 +60,7 @@ use crate::{
         SendMessageResponse,
     },
     proto::envelope::DhtMessageType,
+    store_forward::SafEpochKeyStore,
     version::DhtProtocolVersion,
     DhtConfig,
 };
@@ -70,6 +71,7 @@ pub struct BroadcastLayer {
     dht_requester: DhtRequester,
     dht_discovery_requester: DhtDiscoveryRequester,
     node_identity: Arc<NodeIdentity>,
+    saf_epoch_keys: SafEpochKeyStore,
     message_validity_window: chrono::Duration,
     protocol_version: DhtProtocolVersion,
 }
@@ -79,12 +81,14 @@ impl BroadcastLayer {
         node_identity: Arc<NodeIdentity>,
         dht_requester: DhtRequester,
         dht_discovery_requester: DhtDiscoveryRequester,
+        saf_epoch_keys: SafEpochKeyStore,
         config: &DhtConfig,
     ) -> Self {
         BroadcastLayer {
             dht_requester,
             dht_discovery_requester,
             node_identity,
+            saf_epoch_keys,
             message_validity_window: chrono::Duration::from_std(config.saf.msg_validity)
                 .expect("message_validity_window is too large"),
             protocol_version: config.protocol_version,
@@ -101,6 +105,7 @@ impl<S> Layer<S> for BroadcastLayer {
             Arc::clone(&self.node_identity),
             self.dht_requester.clone(),
             self.dht_discovery_requester.clone(),
+            self.saf_epoch_keys.clone(),
             self.message_validity_window,
             self.protocol_version,
         )
@@ -115,6 +120,7 @@ pub struct BroadcastMiddleware<S> {
     dht_requester: DhtRequester,
     dht_discovery_requester: DhtDiscoveryRequester,
     node_identity: Arc<NodeIdentity>,
+    saf_epoch_keys: SafEpochKeyStore,
     message_validity_window: chrono::Duration,
     protocol_version: DhtProtocolVersion,
 }
@@ -125,6 +131,7 @@ impl<S> BroadcastMiddleware<S> {
         node_identity: Arc<NodeIdentity>,
         dht_requester: DhtRequester,
         dht_discovery_requester: DhtDiscoveryRequester,
+        saf_epoch_keys: SafEpochKeyStore,
         message_validity_window: chrono::Duration,
         protocol_version: DhtProtocolVersion,
     ) -> Self {
@@ -133,6 +140,7 @@ impl<S> BroadcastMiddleware<S> {
             dht_requester,
             dht_discovery_requester,
             node_identity,
+            saf_epoch_keys,
             message_validity_window,
             protocol_version,
         }
@@ -159,6 +167,7 @@ where
                 Arc::clone(&self.node_identity),
                 self.dht_requester.clone(),
                 self.dht_discovery_requester.clone(),
+                self.saf_epoch_keys.clone(),
                 msg,
                 self.message_validity_window,
                 self.protocol_version,
@@ -173,6 +182,7 @@ struct BroadcastTask<S> {
     node_identity: Arc<NodeIdentity>,
     dht_requester: DhtRequester,
     dht_discovery_requester: DhtDiscoveryRequester,
+    saf_epoch_keys: SafEpochKeyStore,
     request: Option<DhtOutboundRequest>,
     message_validity_window: chrono::Duration,
     protocol_version: DhtProtocolVersion,
@@ -187,6 +197,7 @@ where S: Service<DhtOutboundMessage, Response = (), Error = PipelineError>
         node_identity: Arc<NodeIdentity>,
         dht_requester: DhtRequester,
         dht_discovery_requester: DhtDiscoveryRequester,
+        saf_epoch_keys: SafEpochKeyStore,
         request: DhtOutboundRequest,
         message_validity_window: chrono::Duration,
         protocol_version: DhtProtocolVersion,
@@ -196,6 +207,7 @@ where S: Service<DhtOutboundMessage, Response = (), Error = PipelineError>
             node_identity,
             dht_requester,
             dht_discovery_requester,
+            saf_epoch_keys,
             request: Some(request),
             message_validity_window,
             protocol_version,
@@ -493,9 +505,16 @@ where S: Service<DhtOutboundMessage, Response = (), Error = PipelineError>
             OutboundEncryption::EncryptFor(recipient_public_key) => {
                 trace!(target: LOG_TARGET, "Encrypting message for {}", recipient_public_key);
 
+                // If the recipient has announced a key for the epoch in which this message expires, encrypt to that
+                // key so that the message cannot be decrypted once the recipient has erased it. Otherwise, fall back
+                // to the recipient identity key.
+                let epoch_public_key =
+                    expires.and_then(|expires| self.saf_epoch_keys.peer_public_key_for(recipient_public_key, expires));
+                let encryption_public_key = epoch_public_key.as_ref().unwrap_or(&**recipient_public_key);
+
                 // Perform an ephemeral ECDH exchange against the recipient public key
                 let (ephemeral_secret_key, ephemeral_public_key) = CommsPublicKey::random_keypair(&mut OsRng);
-                let shared_ephemeral_secret = CommsDHKE::new(&ephemeral_secret_key, &**recipient_public_key);
+                let shared_ephemeral_secret = CommsDHKE::new(&ephemeral_secret_key, encryption_public_key);
 
                 // Produce a masked sender public key using an offset mask derived from the ECDH exchange
                 let mask = crypt::generate_key_mask(&shared_ephemeral_secret)
@@ -624,6 +643,7 @@ mod test {
             node_identity,
             dht_requester,
             dht_discover_requester,
+            SafEpochKeyStore::disabled(),
             chrono::Duration::seconds(10800),
             DhtProtocolVersion::latest(),
         );
@@ -667,6 +687,7 @@ mod test {
             Arc::new(node_identity),
             dht_requester,
             dht_discover_requester,
+            SafEpochKeyStore::disabled(),
             chrono::Duration::seconds(10800),
             DhtProtocolVersion::latest(),
         );
@@ -715,6 +736,7 @@ mod test {
             Arc::new(node_identity),
             dht_requester,
             dht_discover_requester,
+            SafEpochKeyStore::disabled(),
             chrono::Duration::seconds(10800),
             DhtProtocolVersion::latest(),
         );
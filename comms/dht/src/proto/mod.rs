@@ -80,6 +80,7 @@ impl<T: AsRef<NodeIdentity>> From<T> for JoinMessage {
             peer_features: node_identity.features().bits(),
             nonce: OsRng.next_u64(),
             identity_signature: node_identity.identity_signature_read().as_ref().map(Into::into),
+            saf_epoch_keys: None,
         }
     }
 }
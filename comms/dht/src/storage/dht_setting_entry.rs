@@ -31,6 +31,8 @@ pub enum DhtMetadataKey {
     OfflineTimestamp,
     /// Timestamp of the most recent SAF message received
     LastSafMessageReceived,
+    /// Store and forward epoch key ratchet state
+    SafEpochKeySeed,
 }
 
 impl fmt::Display for DhtMetadataKey {
@@ -61,6 +61,19 @@ pub struct SafConfig {
     /// The maximum number of peer nodes that a message must be closer than to get stored by SAF
     /// Default: 8
     pub num_neighbouring_nodes: usize,
+    /// When true, messages to peers that have announced store and forward epoch keys are encrypted to the key for the
+    /// epoch in which the message expires, and this node announces its own epoch keys. Keys for epochs older than the
+    /// retention window are erased, which limits the messages exposed by a later compromise of the node identity key.
+    /// Default: true
+    pub epoch_keys_enabled: bool,
+    /// The duration of a single epoch key period. Default: 1 day
+    #[serde(with = "serializers::seconds")]
+    pub epoch_key_period: Duration,
+    /// The number of future epoch public keys to announce to peers. Default: 7
+    pub epoch_key_lookahead: u64,
+    /// The number of past epochs for which secret keys are retained, so that stored messages can still be decrypted.
+    /// This should cover the high priority message storage TTL. Default: 3
+    pub epoch_key_retention: u64,
 }
 
 impl Default for SafConfig {
@@ -76,6 +89,10 @@ impl Default for SafConfig {
             max_message_size: 512 * 1024,
             max_inflight_request_age: Duration::from_secs(120),
             num_neighbouring_nodes: 8,
+            epoch_keys_enabled: true,
+            epoch_key_period: Duration::from_secs(24 * 60 * 60), // 1 day
+            epoch_key_lookahead: 7,
+            epoch_key_retention: 3,
         }
     }
 }
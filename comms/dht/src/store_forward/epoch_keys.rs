@@ -0,0 +1,580 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Forward-secret epoch keys for store and forward message encryption.
+//!
+//! Messages sent to a peer are normally encrypted using an ECDH exchange against the peer's identity public key. Since
+//! store and forward nodes hold on to these messages for days, a later compromise of the recipient's identity key
+//! would expose every stored message. To limit this window, each node derives a sequence of epoch keys from a hash
+//! ratchet and announces the public keys for the upcoming epochs (signed by its identity key) in its join and
+//! discovery response messages. A sender that has a valid announcement for the recipient encrypts to the recipient's
+//! key for the epoch in which the message expires. The recipient only retains the ratchet seed for epochs that may
+//! still have messages in flight, so secrets for older epochs cannot be recovered from the node's current state.
+
+use std::{
+    collections::HashMap,
+    convert::TryFrom,
+    fmt,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use digest::{generic_array::GenericArray, Digest, FixedOutput};
+use log::*;
+use rand::{rngs::OsRng, RngCore};
+use tari_comms::{
+    peer_manager::NodeIdentity,
+    types::{CommsPublicKey, CommsSecretKey, Signature},
+};
+use tari_crypto::keys::{PublicKey, SecretKey};
+use tari_utilities::{epoch_time::EpochTime, hidden_type, safe_array::SafeArray, ByteArray, Hidden};
+use zeroize::Zeroize;
+
+use crate::{
+    comms_dht_hash_domain_saf_epoch_announcement,
+    comms_dht_hash_domain_saf_epoch_key,
+    comms_dht_hash_domain_saf_epoch_seed,
+    proto::dht as proto,
+    storage::{DhtDatabase, DhtMetadataKey, StorageError},
+    store_forward::SafConfig,
+};
+
+const LOG_TARGET: &str = "comms::dht::storeforward::epoch_keys";
+
+/// The shortest epoch period that will be accepted in a peer announcement
+const MIN_EPOCH_PERIOD: Duration = Duration::from_secs(60 * 60);
+/// The maximum number of epoch public keys that will be accepted in a peer announcement
+const MAX_ANNOUNCED_KEYS: usize = 32;
+/// The maximum number of peer announcements that are held in memory
+const MAX_PEER_ANNOUNCEMENTS: usize = 10_000;
+/// The size of the persisted ratchet state (8 byte base epoch followed by the 32 byte seed)
+const RATCHET_STATE_SIZE: usize = 8 + 32;
+
+hidden_type!(EpochSeed, SafeArray<u8, 32>);
+hidden_type!(EpochKeyBytes, SafeArray<u8, 64>);
+
+/// A one-way hash ratchet over epoch seeds. The seed for epoch `n + 1` is the hash of the seed for epoch `n`, so
+/// discarding a seed makes the keys for that and all earlier epochs unrecoverable.
+struct EpochRatchet {
+    base_epoch: u64,
+    seed: EpochSeed,
+}
+
+impl EpochRatchet {
+    fn random(base_epoch: u64) -> Self {
+        let mut seed = EpochSeed::from(SafeArray::default());
+        OsRng.fill_bytes(&mut seed.reveal_mut()[..]);
+        Self { base_epoch, seed }
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != RATCHET_STATE_SIZE {
+            return None;
+        }
+        let mut base_epoch = [0u8; 8];
+        base_epoch.copy_from_slice(&bytes[..8]);
+        let mut seed = EpochSeed::from(SafeArray::default());
+        seed.reveal_mut().copy_from_slice(&bytes[8..]);
+        Some(Self {
+            base_epoch: u64::from_le_bytes(base_epoch),
+            seed,
+        })
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(RATCHET_STATE_SIZE);
+        bytes.extend_from_slice(&self.base_epoch.to_le_bytes());
+        bytes.extend_from_slice(&self.seed.reveal()[..]);
+        bytes
+    }
+
+    fn next_seed(seed: &EpochSeed) -> EpochSeed {
+        let mut next = EpochSeed::from(SafeArray::default());
+        FixedOutput::finalize_into(
+            comms_dht_hash_domain_saf_epoch_seed().chain(&seed.reveal()[..]),
+            GenericArray::from_mut_slice(next.reveal_mut()),
+        );
+        next
+    }
+
+    /// Ratchets the seed forward so that the oldest retained epoch is `epoch`. Returns true if the ratchet advanced.
+    fn advance_to(&mut self, epoch: u64) -> bool {
+        if epoch <= self.base_epoch {
+            return false;
+        }
+        // If the node was offline for longer than the retention window, all intermediate seeds are still derived so
+        // that keys announced before going offline remain valid.
+        while self.base_epoch < epoch {
+            self.seed = Self::next_seed(&self.seed);
+            self.base_epoch += 1;
+        }
+        true
+    }
+
+    /// Derives the secret key for the given epoch, or None if the epoch is before the oldest retained epoch or more
+    /// than `max_span` epochs ahead of it.
+    fn secret_key(&self, epoch: u64, max_span: u64) -> Option<CommsSecretKey> {
+        let offset = epoch.checked_sub(self.base_epoch)?;
+        if offset > max_span {
+            return None;
+        }
+        let mut seed = EpochSeed::from(self.seed.reveal().clone());
+        for _ in 0..offset {
+            seed = Self::next_seed(&seed);
+        }
+        let mut key_bytes = EpochKeyBytes::from(SafeArray::default());
+        FixedOutput::finalize_into(
+            comms_dht_hash_domain_saf_epoch_key().chain(&seed.reveal()[..]),
+            GenericArray::from_mut_slice(key_bytes.reveal_mut()),
+        );
+        CommsSecretKey::from_uniform_bytes(key_bytes.reveal()).ok()
+    }
+}
+
+/// A signed announcement of the epoch public keys for a node.
+#[derive(Debug, Clone)]
+pub struct SafEpochKeyAnnouncement {
+    period: Duration,
+    start_epoch: u64,
+    public_keys: Vec<CommsPublicKey>,
+    signature: Signature,
+}
+
+impl SafEpochKeyAnnouncement {
+    fn sign_new(
+        secret_key: &CommsSecretKey,
+        period: Duration,
+        start_epoch: u64,
+        public_keys: Vec<CommsPublicKey>,
+    ) -> Self {
+        let public_key = CommsPublicKey::from_secret_key(secret_key);
+        let (secret_nonce, public_nonce) = CommsPublicKey::random_keypair(&mut OsRng);
+        let challenge = Self::construct_challenge(&public_key, &public_nonce, period, start_epoch, &public_keys);
+        let signature = Signature::sign_raw_uniform(secret_key, secret_nonce, &challenge)
+            .expect("unreachable panic: challenge hash digest is the correct length");
+        Self {
+            period,
+            start_epoch,
+            public_keys,
+            signature,
+        }
+    }
+
+    /// Returns true if this announcement is well-formed and signed by the given public key
+    pub fn is_valid(&self, public_key: &CommsPublicKey) -> bool {
+        if self.period < MIN_EPOCH_PERIOD {
+            return false;
+        }
+        if self.public_keys.is_empty() || self.public_keys.len() > MAX_ANNOUNCED_KEYS {
+            return false;
+        }
+        let challenge = Self::construct_challenge(
+            public_key,
+            self.signature.get_public_nonce(),
+            self.period,
+            self.start_epoch,
+            &self.public_keys,
+        );
+        self.signature.verify_raw_uniform(public_key, &challenge)
+    }
+
+    /// Returns the announced public key for the epoch containing the given timestamp, if any
+    pub fn public_key_for(&self, timestamp: EpochTime) -> Option<&CommsPublicKey> {
+        let epoch = epoch_of(timestamp, self.period);
+        let index = epoch.checked_sub(self.start_epoch)?;
+        self.public_keys.get(usize::try_from(index).ok()?)
+    }
+
+    /// The epoch after the last announced key
+    fn end_epoch(&self) -> u64 {
+        self.start_epoch.saturating_add(self.public_keys.len() as u64)
+    }
+
+    fn construct_challenge(
+        public_key: &CommsPublicKey,
+        public_nonce: &CommsPublicKey,
+        period: Duration,
+        start_epoch: u64,
+        public_keys: &[CommsPublicKey],
+    ) -> [u8; 64] {
+        // e = H(P||R||m)
+        let challenge = comms_dht_hash_domain_saf_epoch_announcement()
+            .chain(public_key.as_bytes())
+            .chain(public_nonce.as_bytes())
+            .chain(period.as_secs().to_le_bytes())
+            .chain(start_epoch.to_le_bytes());
+        let challenge = public_keys
+            .iter()
+            .fold(challenge, |challenge, pk| challenge.chain(pk.as_bytes()))
+            .finalize();
+
+        let mut output = [0u8; 64];
+        output.copy_from_slice(challenge.as_ref());
+        output
+    }
+}
+
+impl fmt::Display for SafEpochKeyAnnouncement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "SafEpochKeyAnnouncement(period = {:.0?}, epochs = {}..{})",
+            self.period,
+            self.start_epoch,
+            self.end_epoch()
+        )
+    }
+}
+
+impl From<&SafEpochKeyAnnouncement> for proto::SafEpochKeys {
+    fn from(value: &SafEpochKeyAnnouncement) -> Self {
+        Self {
+            period: value.period.as_secs(),
+            start_epoch: value.start_epoch,
+            public_keys: value.public_keys.iter().map(|pk| pk.to_vec()).collect(),
+            public_nonce: value.signature.get_public_nonce().to_vec(),
+            signature: value.signature.get_signature().to_vec(),
+        }
+    }
+}
+
+impl TryFrom<proto::SafEpochKeys> for SafEpochKeyAnnouncement {
+    type Error = anyhow::Error;
+
+    fn try_from(value: proto::SafEpochKeys) -> Result<Self, Self::Error> {
+        if value.public_keys.len() > MAX_ANNOUNCED_KEYS {
+            return Err(anyhow::anyhow!("SafEpochKeys contains too many public keys"));
+        }
+        let public_keys = value
+            .public_keys
+            .iter()
+            .map(|pk| CommsPublicKey::from_canonical_bytes(pk))
+            .collect::<Result<_, _>>()
+            .map_err(|e| anyhow::anyhow!("SafEpochKeys invalid public key: {}", e))?;
+        let public_nonce = CommsPublicKey::from_canonical_bytes(&value.public_nonce)
+            .map_err(|e| anyhow::anyhow!("SafEpochKeys invalid public nonce: {}", e))?;
+        let signature = CommsSecretKey::from_canonical_bytes(&value.signature)
+            .map_err(|e| anyhow::anyhow!("SafEpochKeys invalid signature: {}", e))?;
+        Ok(Self {
+            period: Duration::from_secs(value.period),
+            start_epoch: value.start_epoch,
+            public_keys,
+            signature: Signature::new(public_nonce, signature),
+        })
+    }
+}
+
+/// Returns the epoch number containing the given timestamp
+fn epoch_of(timestamp: EpochTime, period: Duration) -> u64 {
+    timestamp.as_u64() / period.as_secs().max(1)
+}
+
+struct EpochKeyStoreInner {
+    period: Duration,
+    lookahead: u64,
+    retention: u64,
+    node_identity: Arc<NodeIdentity>,
+    database: Option<DhtDatabase>,
+    ratchet: RwLock<EpochRatchet>,
+    peer_announcements: RwLock<HashMap<CommsPublicKey, SafEpochKeyAnnouncement>>,
+}
+
+impl EpochKeyStoreInner {
+    fn persist(&self, ratchet: &EpochRatchet) -> Result<(), StorageError> {
+        let Some(db) = self.database.as_ref() else {
+            return Ok(());
+        };
+        db.set_metadata_value_bytes(DhtMetadataKey::SafEpochKeySeed, ratchet.to_bytes())
+    }
+}
+
+/// Holds this node's epoch key ratchet and the epoch key announcements received from peers. This is cheap to clone.
+#[derive(Clone)]
+pub struct SafEpochKeyStore {
+    inner: Option<Arc<EpochKeyStoreInner>>,
+}
+
+impl SafEpochKeyStore {
+    /// Loads the persisted epoch key ratchet, or creates a new one if none exists. If epoch keys are disabled in the
+    /// config, a disabled store is returned.
+    pub fn initialize(
+        config: &SafConfig,
+        node_identity: Arc<NodeIdentity>,
+        database: DhtDatabase,
+    ) -> Result<Self, StorageError> {
+        if !config.epoch_keys_enabled {
+            return Ok(Self::disabled());
+        }
+
+        let current_epoch = epoch_of(EpochTime::now(), config.epoch_key_period);
+        let ratchet = match database.get_metadata_value_bytes(DhtMetadataKey::SafEpochKeySeed)? {
+            Some(mut bytes) => {
+                let ratchet = EpochRatchet::from_bytes(&bytes);
+                bytes.zeroize();
+                ratchet.unwrap_or_else(|| {
+                    warn!(
+                        target: LOG_TARGET,
+                        "Stored SAF epoch key state is invalid. Generating a new epoch key ratchet."
+                    );
+                    EpochRatchet::random(current_epoch)
+                })
+            },
+            None => EpochRatchet::random(current_epoch),
+        };
+
+        let store = Self::new(config, node_identity, Some(database), ratchet);
+        if !store.ratchet()? {
+            // Always persist on startup in case a new ratchet was generated
+            let inner = store.inner.as_ref().expect("store was just created as enabled");
+            inner.persist(&inner.ratchet.read().expect("SafEpochKeyStore lock poisoned"))?;
+        }
+        Ok(store)
+    }
+
+    /// Returns a store that does not announce or use epoch keys. Messages are encrypted to and decrypted with the
+    /// node identity key only.
+    pub fn disabled() -> Self {
+        Self { inner: None }
+    }
+
+    fn new(
+        config: &SafConfig,
+        node_identity: Arc<NodeIdentity>,
+        database: Option<DhtDatabase>,
+        ratchet: EpochRatchet,
+    ) -> Self {
+        Self {
+            inner: Some(Arc::new(EpochKeyStoreInner {
+                period: config.epoch_key_period,
+                lookahead: config.epoch_key_lookahead,
+                retention: config.epoch_key_retention,
+                node_identity,
+                database,
+                ratchet: RwLock::new(ratchet),
+                peer_announcements: RwLock::new(HashMap::new()),
+            })),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.inner.is_some()
+    }
+
+    /// Erases the seeds for epochs that are older than the retention window, persisting the new ratchet state.
+    /// Returns true if the ratchet advanced.
+    pub fn ratchet(&self) -> Result<bool, StorageError> {
+        let Some(inner) = self.inner.as_ref() else {
+            return Ok(false);
+        };
+        let current_epoch = epoch_of(EpochTime::now(), inner.period);
+        let oldest_epoch = current_epoch.saturating_sub(inner.retention);
+        if inner.ratchet.read().expect("SafEpochKeyStore lock poisoned").base_epoch >= oldest_epoch {
+            return Ok(false);
+        }
+        let mut ratchet = inner.ratchet.write().expect("SafEpochKeyStore lock poisoned");
+        if !ratchet.advance_to(oldest_epoch) {
+            return Ok(false);
+        }
+        debug!(
+            target: LOG_TARGET,
+            "SAF epoch key ratchet advanced to epoch {}", ratchet.base_epoch
+        );
+        inner.persist(&ratchet)?;
+        Ok(true)
+    }
+
+    /// Returns a signed announcement of this node's epoch public keys for the current epoch and the configured
+    /// number of lookahead epochs.
+    pub fn announcement(&self) -> Option<SafEpochKeyAnnouncement> {
+        let inner = self.inner.as_ref()?;
+        if let Err(err) = self.ratchet() {
+            warn!(target: LOG_TARGET, "Failed to persist SAF epoch key ratchet: {}", err);
+        }
+        let current_epoch = epoch_of(EpochTime::now(), inner.period);
+        let ratchet = inner.ratchet.read().expect("SafEpochKeyStore lock poisoned");
+        let max_span = inner.retention.saturating_add(inner.lookahead);
+        let public_keys = (current_epoch..=current_epoch.saturating_add(inner.lookahead))
+            .take(MAX_ANNOUNCED_KEYS)
+            .map(|epoch| ratchet.secret_key(epoch, max_span))
+            .map(|sk| sk.map(|sk| CommsPublicKey::from_secret_key(&sk)))
+            .collect::<Option<Vec<_>>>()?;
+        Some(SafEpochKeyAnnouncement::sign_new(
+            inner.node_identity.secret_key(),
+            inner.period,
+            current_epoch,
+            public_keys,
+        ))
+    }
+
+    /// Returns this node's secret key for the epoch containing the given expiry timestamp. None is returned if epoch
+    /// keys are disabled or the key for that epoch has been erased.
+    pub fn secret_key_for(&self, expires: EpochTime) -> Option<CommsSecretKey> {
+        let inner = self.inner.as_ref()?;
+        if let Err(err) = self.ratchet() {
+            warn!(target: LOG_TARGET, "Failed to persist SAF epoch key ratchet: {}", err);
+        }
+        let epoch = epoch_of(expires, inner.period);
+        let ratchet = inner.ratchet.read().expect("SafEpochKeyStore lock poisoned");
+        ratchet.secret_key(epoch, inner.retention.saturating_add(inner.lookahead).saturating_add(1))
+    }
+
+    /// Validates and records an epoch key announcement from a peer. Returns true if the announcement was accepted.
+    pub fn add_peer_announcement(&self, public_key: &CommsPublicKey, announcement: SafEpochKeyAnnouncement) -> bool {
+        let Some(inner) = self.inner.as_ref() else {
+            return false;
+        };
+        if !announcement.is_valid(public_key) {
+            debug!(
+                target: LOG_TARGET,
+                "Ignoring invalid SAF epoch key announcement from peer '{}'", public_key
+            );
+            return false;
+        }
+
+        let mut announcements = inner
+            .peer_announcements
+            .write()
+            .expect("SafEpochKeyStore lock poisoned");
+        if let Some(existing) = announcements.get(public_key) {
+            // Do not allow an older announcement to replace a newer one
+            if existing.start_epoch > announcement.start_epoch {
+                return false;
+            }
+        }
+        if announcements.len() >= MAX_PEER_ANNOUNCEMENTS && !announcements.contains_key(public_key) {
+            // Evict the announcement that expires the soonest
+            let evict = announcements
+                .iter()
+                .min_by_key(|(_, a)| a.end_epoch().saturating_mul(a.period.as_secs()))
+                .map(|(pk, _)| pk.clone());
+            if let Some(pk) = evict {
+                announcements.remove(&pk);
+            }
+        }
+        trace!(
+            target: LOG_TARGET,
+            "Recorded {} from peer '{}'", announcement, public_key
+        );
+        announcements.insert(public_key.clone(), announcement);
+        true
+    }
+
+    /// Returns the public key that a message expiring at `expires` should be encrypted to for the given peer. If the
+    /// peer has not announced a key for that epoch, None is returned and the peer's identity key should be used.
+    pub fn peer_public_key_for(&self, peer_public_key: &CommsPublicKey, expires: EpochTime) -> Option<CommsPublicKey> {
+        let inner = self.inner.as_ref()?;
+        let announcements = inner.peer_announcements.read().expect("SafEpochKeyStore lock poisoned");
+        announcements
+            .get(peer_public_key)
+            .and_then(|a| a.public_key_for(expires))
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tari_comms::types::CommsDHKE;
+
+    use super::*;
+    use crate::test_utils::make_node_identity;
+
+    fn config() -> SafConfig {
+        SafConfig {
+            epoch_key_period: Duration::from_secs(24 * 60 * 60),
+            epoch_key_lookahead: 3,
+            epoch_key_retention: 2,
+            ..Default::default()
+        }
+    }
+
+    fn make_store(node_identity: Arc<NodeIdentity>) -> SafEpochKeyStore {
+        let config = config();
+        let current_epoch = epoch_of(EpochTime::now(), config.epoch_key_period);
+        SafEpochKeyStore::new(&config, node_identity, None, EpochRatchet::random(current_epoch))
+    }
+
+    #[test]
+    fn it_ratchets_forward_and_erases_old_epochs() {
+        let mut ratchet = EpochRatchet::random(10);
+        let key_11 = ratchet.secret_key(11, 10).unwrap();
+        let key_12 = ratchet.secret_key(12, 10).unwrap();
+        assert_ne!(key_11, key_12);
+
+        assert!(ratchet.advance_to(11));
+        assert!(!ratchet.advance_to(11));
+        assert!(ratchet.secret_key(10, 10).is_none());
+        assert_eq!(ratchet.secret_key(11, 10).unwrap(), key_11);
+        assert_eq!(ratchet.secret_key(12, 10).unwrap(), key_12);
+        assert!(ratchet.secret_key(22, 10).is_none());
+
+        let restored = EpochRatchet::from_bytes(&ratchet.to_bytes()).unwrap();
+        assert_eq!(restored.base_epoch, 11);
+        assert_eq!(restored.secret_key(12, 10).unwrap(), key_12);
+        assert!(EpochRatchet::from_bytes(&[0u8; 12]).is_none());
+    }
+
+    #[test]
+    fn it_encrypts_to_an_announced_epoch_key() {
+        let recipient = make_node_identity();
+        let sender = make_node_identity();
+        let recipient_store = make_store(recipient.clone());
+        let sender_store = make_store(sender);
+
+        let announcement = recipient_store.announcement().unwrap();
+        assert!(announcement.is_valid(recipient.public_key()));
+        let decoded = SafEpochKeyAnnouncement::try_from(proto::SafEpochKeys::from(&announcement)).unwrap();
+        assert!(sender_store.add_peer_announcement(recipient.public_key(), decoded));
+
+        let expires = EpochTime::now();
+        let epoch_pk = sender_store
+            .peer_public_key_for(recipient.public_key(), expires)
+            .unwrap();
+        assert_ne!(epoch_pk, *recipient.public_key());
+
+        let (e_sk, e_pk) = CommsPublicKey::random_keypair(&mut OsRng);
+        let sender_secret = CommsDHKE::new(&e_sk, &epoch_pk);
+        let epoch_sk = recipient_store.secret_key_for(expires).unwrap();
+        let recipient_secret = CommsDHKE::new(&epoch_sk, &e_pk);
+        assert_eq!(sender_secret.as_bytes(), recipient_secret.as_bytes());
+    }
+
+    #[test]
+    fn it_rejects_announcements_signed_by_another_key() {
+        let node = make_node_identity();
+        let other = make_node_identity();
+        let store = make_store(make_node_identity());
+        let announcement = make_store(other).announcement().unwrap();
+        assert!(!announcement.is_valid(node.public_key()));
+        assert!(!store.add_peer_announcement(node.public_key(), announcement));
+        assert!(store.peer_public_key_for(node.public_key(), EpochTime::now()).is_none());
+    }
+
+    #[test]
+    fn it_does_nothing_when_disabled() {
+        let node = make_node_identity();
+        let store = SafEpochKeyStore::disabled();
+        assert!(store.announcement().is_none());
+        assert!(store.secret_key_for(EpochTime::now()).is_none());
+        let announcement = make_store(node.clone()).announcement().unwrap();
+        assert!(!store.add_peer_announcement(node.public_key(), announcement));
+    }
+}
@@ -36,6 +36,9 @@ pub use error::StoreAndForwardError;
 mod config;
 pub use config::SafConfig;
 
+mod epoch_keys;
+pub use epoch_keys::{SafEpochKeyAnnouncement, SafEpochKeyStore};
+
 mod message;
 
 mod saf_handler;
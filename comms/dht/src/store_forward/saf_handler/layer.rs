@@ -30,7 +30,7 @@ use super::middleware::MessageHandlerMiddleware;
 use crate::{
     actor::DhtRequester,
     outbound::OutboundMessageRequester,
-    store_forward::{SafConfig, StoreAndForwardRequester},
+    store_forward::{SafConfig, SafEpochKeyStore, StoreAndForwardRequester},
 };
 
 /// Layer responsible for handling SAF protocol messages.
@@ -41,6 +41,7 @@ pub struct MessageHandlerLayer {
     node_identity: Arc<NodeIdentity>,
     outbound_service: OutboundMessageRequester,
     saf_response_signal_sender: mpsc::Sender<()>,
+    saf_epoch_keys: SafEpochKeyStore,
 }
 
 impl MessageHandlerLayer {
@@ -51,6 +52,7 @@ impl MessageHandlerLayer {
         node_identity: Arc<NodeIdentity>,
         outbound_service: OutboundMessageRequester,
         saf_response_signal_sender: mpsc::Sender<()>,
+        saf_epoch_keys: SafEpochKeyStore,
     ) -> Self {
         Self {
             config,
@@ -60,6 +62,7 @@ impl MessageHandlerLayer {
 
             outbound_service,
             saf_response_signal_sender,
+            saf_epoch_keys,
         }
     }
 }
@@ -76,6 +79,7 @@ impl<S> Layer<S> for MessageHandlerLayer {
             Arc::clone(&self.node_identity),
             self.outbound_service.clone(),
             self.saf_response_signal_sender.clone(),
+            self.saf_epoch_keys.clone(),
         )
     }
 }
@@ -32,7 +32,7 @@ use crate::{
     actor::DhtRequester,
     inbound::DecryptedDhtMessage,
     outbound::OutboundMessageRequester,
-    store_forward::{SafConfig, StoreAndForwardRequester},
+    store_forward::{SafConfig, SafEpochKeyStore, StoreAndForwardRequester},
 };
 
 #[derive(Clone)]
@@ -44,6 +44,7 @@ pub struct MessageHandlerMiddleware<S> {
     node_identity: Arc<NodeIdentity>,
     outbound_service: OutboundMessageRequester,
     saf_response_signal_sender: mpsc::Sender<()>,
+    saf_epoch_keys: SafEpochKeyStore,
 }
 
 impl<S> MessageHandlerMiddleware<S> {
@@ -55,6 +56,7 @@ impl<S> MessageHandlerMiddleware<S> {
         node_identity: Arc<NodeIdentity>,
         outbound_service: OutboundMessageRequester,
         saf_response_signal_sender: mpsc::Sender<()>,
+        saf_epoch_keys: SafEpochKeyStore,
     ) -> Self {
         Self {
             config,
@@ -65,6 +67,7 @@ impl<S> MessageHandlerMiddleware<S> {
 
             outbound_service,
             saf_response_signal_sender,
+            saf_epoch_keys,
         }
     }
 }
@@ -93,6 +96,7 @@ where
                 Arc::clone(&self.node_identity),
                 message,
                 self.saf_response_signal_sender.clone(),
+                self.saf_epoch_keys.clone(),
             )
             .run(),
         )
@@ -23,6 +23,7 @@
 use std::{
     cmp,
     convert::{TryFrom, TryInto},
+    iter,
     sync::Arc,
 };
 
@@ -46,6 +47,7 @@ use crate::{
     crypt,
     dedup,
     envelope::{epochtime_to_datetime, DhtMessageError, DhtMessageHeader, NodeDestination},
+    error::DhtEncryptError,
     inbound::{DecryptedDhtMessage, DhtInboundMessage},
     message_signature::{MessageSignature, MessageSignatureError, ProtoMessageSignature},
     outbound::{OutboundMessageRequester, SendMessageParams},
@@ -63,6 +65,7 @@ use crate::{
         error::StoreAndForwardError,
         service::FetchStoredMessageQuery,
         SafConfig,
+        SafEpochKeyStore,
         StoreAndForwardRequester,
     },
 };
@@ -78,6 +81,7 @@ pub struct MessageHandlerTask<S> {
     message: Option<DecryptedDhtMessage>,
     saf_requester: StoreAndForwardRequester,
     saf_response_signal_sender: mpsc::Sender<()>,
+    saf_epoch_keys: SafEpochKeyStore,
 }
 
 impl<S> MessageHandlerTask<S>
@@ -92,6 +96,7 @@ where S: Service<DecryptedDhtMessage, Response = (), Error = PipelineError>
         node_identity: Arc<NodeIdentity>,
         message: DecryptedDhtMessage,
         saf_response_signal_sender: mpsc::Sender<()>,
+        saf_epoch_keys: SafEpochKeyStore,
     ) -> Self {
         Self {
             config,
@@ -102,6 +107,7 @@ where S: Service<DecryptedDhtMessage, Response = (), Error = PipelineError>
             node_identity,
             message: Some(message),
             saf_response_signal_sender,
+            saf_epoch_keys,
         }
     }
 
@@ -460,8 +466,12 @@ where S: Service<DecryptedDhtMessage, Response = (), Error = PipelineError>
         Self::check_destination_for(node_identity.public_key(), &dht_header).await?;
 
         // Attempt to decrypt the message (if applicable), and deserialize it
-        let (authenticated_pk, decrypted_body) =
-            Self::authenticate_and_decrypt_if_required(node_identity, &dht_header, &message.body)?;
+        let (authenticated_pk, decrypted_body) = Self::authenticate_and_decrypt_if_required(
+            node_identity,
+            &self.saf_epoch_keys,
+            &dht_header,
+            &message.body,
+        )?;
 
         // Check that the message has not already been received.
         Self::check_duplicate(
@@ -512,6 +522,7 @@ where S: Service<DecryptedDhtMessage, Response = (), Error = PipelineError>
 
     fn authenticate_and_decrypt_if_required(
         node_identity: &NodeIdentity,
+        saf_epoch_keys: &SafEpochKeyStore,
         header: &DhtMessageHeader,
         body: &[u8],
     ) -> Result<(Option<CommsPublicKey>, EnvelopeBody), StoreAndForwardError> {
@@ -534,10 +545,25 @@ where S: Service<DecryptedDhtMessage, Response = (), Error = PipelineError>
                 body.len()
             );
 
-            let shared_ephemeral_secret = CommsDHKE::new(node_identity.secret_key(), ephemeral_public_key);
-            let key_message = crypt::generate_key_message(&shared_ephemeral_secret);
-            let mut decrypted_bytes = BytesMut::from(body);
-            crypt::decrypt_message(&key_message, &mut decrypted_bytes, masked_sender_public_key.as_bytes())?;
+            // The message may have been encrypted to our key for the epoch in which it expires, or to our identity key
+            let epoch_secret_key = header
+                .expires
+                .and_then(|expires| saf_epoch_keys.secret_key_for(expires));
+            let mut decrypted = None;
+            for secret_key in epoch_secret_key.iter().chain(iter::once(node_identity.secret_key())) {
+                let shared_ephemeral_secret = CommsDHKE::new(secret_key, ephemeral_public_key);
+                let key_message = crypt::generate_key_message(&shared_ephemeral_secret);
+                let mut decrypted_bytes = BytesMut::from(body);
+                if crypt::decrypt_message(&key_message, &mut decrypted_bytes, masked_sender_public_key.as_bytes())
+                    .is_ok()
+                {
+                    decrypted = Some((shared_ephemeral_secret, decrypted_bytes));
+                    break;
+                }
+            }
+            let (shared_ephemeral_secret, decrypted_bytes) = decrypted.ok_or(StoreAndForwardError::DhtEncryptError(
+                DhtEncryptError::InvalidAuthenticatedDecryption,
+            ))?;
             let envelope_body =
                 EnvelopeBody::decode(decrypted_bytes.freeze()).map_err(|_| StoreAndForwardError::DecryptionFailed)?;
             if envelope_body.is_empty() {
@@ -863,6 +889,7 @@ mod test {
             node_identity.clone(),
             message.clone(),
             saf_response_signal_sender.clone(),
+            SafEpochKeyStore::disabled(),
         );
 
         task::spawn(task.run());
@@ -920,6 +947,7 @@ mod test {
             node_identity.clone(),
             message,
             saf_response_signal_sender,
+            SafEpochKeyStore::disabled(),
         );
 
         task::spawn(task.run());
@@ -1041,6 +1069,7 @@ mod test {
             node_identity,
             message,
             saf_response_signal_sender,
+            SafEpochKeyStore::disabled(),
         );
 
         task.run().await.unwrap();
@@ -1150,6 +1179,7 @@ mod test {
             node_identity,
             message,
             saf_response_signal_sender,
+            SafEpochKeyStore::disabled(),
         );
 
         let err = task.run().await.unwrap_err();
@@ -1219,6 +1249,7 @@ mod test {
             node_identity,
             message,
             saf_response_signal_sender,
+            SafEpochKeyStore::disabled(),
         );
 
         task.run().await.unwrap();
@@ -1292,6 +1323,7 @@ mod test {
             node_identity.clone(),
             message.clone(),
             saf_response_signal_sender.clone(),
+            SafEpochKeyStore::disabled(),
         );
 
         task.run().await.unwrap();
@@ -1312,6 +1344,7 @@ mod test {
             node_identity,
             message,
             saf_response_signal_sender,
+            SafEpochKeyStore::disabled(),
         );
 
         task.run().await.unwrap();
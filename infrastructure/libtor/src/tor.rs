@@ -20,14 +20,20 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{fmt, io, net::TcpListener, path::PathBuf, thread};
+use std::{
+    fmt,
+    io,
+    net::{SocketAddr, TcpListener},
+    path::PathBuf,
+    thread,
+};
 
 use derivative::Derivative;
 use libtor::{LogDestination, LogLevel, TorFlag};
 use log::*;
 use rand::{distributions::Alphanumeric, thread_rng, Rng};
 use tari_common::exit_codes::{ExitCode, ExitError};
-use tari_p2p::{TorControlAuthentication, TransportConfig, TransportType};
+use tari_p2p::{TorControlAuthentication, TorTransportConfig, TransportConfig, TransportType};
 use tempfile::{tempdir, NamedTempFile, TempDir, TempPath};
 use tor_hash_passwd::EncryptedKey;
 
@@ -51,6 +57,8 @@ pub struct Tor {
     #[derivative(Debug = "ignore")]
     passphrase: TorPassword,
     socks_port: u16,
+    bridges: Vec<String>,
+    client_transport_plugins: Vec<String>,
     temp_dir: Option<TempDir>,
     temp_file: Option<TempPath>,
 }
@@ -64,6 +72,8 @@ impl Default for Tor {
             log_level: LogLevel::Err,
             passphrase: TorPassword(None),
             socks_port: 0,
+            bridges: vec![],
+            client_transport_plugins: vec![],
             temp_dir: None,
             temp_file: None,
         }
@@ -127,6 +137,41 @@ impl Tor {
         }
     }
 
+    /// Use the bridges and pluggable transports of the Tor comms transport config. Every pluggable transport used by a
+    /// bridge must have a client transport plugin.
+    pub fn set_bridges(&mut self, config: &TorTransportConfig) -> Result<(), ExitError> {
+        let mut plugin_transports = Vec::new();
+        for plugin in &config.client_transport_plugins {
+            let mut parts = plugin.split_whitespace();
+            match (parts.next(), parts.next(), parts.next()) {
+                (Some(transports), Some("exec"), Some(_)) => plugin_transports.extend(transports.split(',')),
+                _ => {
+                    let e = format!(
+                        "Invalid client transport plugin '{}', expected '<transport> exec <path> [args]'",
+                        plugin
+                    );
+                    return Err(ExitError::new(ExitCode::ConfigError, e));
+                },
+            }
+        }
+        for bridge in &config.bridges {
+            let transport = bridge.split_whitespace().next().ok_or_else(|| {
+                ExitError::new(ExitCode::ConfigError, "Empty Tor bridge line in the transport config")
+            })?;
+            // A vanilla bridge line starts with the bridge address
+            if transport.parse::<SocketAddr>().is_err() && !plugin_transports.contains(&transport) {
+                let e = format!(
+                    "Tor bridge '{}' uses the '{}' pluggable transport, which has no client transport plugin",
+                    bridge, transport
+                );
+                return Err(ExitError::new(ExitCode::ConfigError, e));
+            }
+        }
+        self.bridges = config.bridges.clone();
+        self.client_transport_plugins = config.client_transport_plugins.clone();
+        Ok(())
+    }
+
     /// Run the Tor instance in the background and return a handle to the thread.
     pub fn run_background(self) -> thread::JoinHandle<Result<u8, libtor::Error>> {
         info!(target: LOG_TARGET, "Starting Tor instance");
@@ -138,6 +183,8 @@ impl Tor {
             log_level,
             log_destination,
             passphrase,
+            bridges,
+            client_transport_plugins,
             ..
         } = self;
 
@@ -166,6 +213,17 @@ impl Tor {
             tor.flag(TorFlag::ControlPort(control_port));
         }
 
+        if !bridges.is_empty() {
+            info!(target: LOG_TARGET, "Connecting to Tor through {} bridge(s)", bridges.len());
+            tor.flag(TorFlag::Custom("UseBridges 1".to_string()));
+            for bridge in bridges {
+                tor.flag(TorFlag::Custom(format!("Bridge {}", bridge)));
+            }
+            for plugin in client_transport_plugins {
+                tor.flag(TorFlag::Custom(format!("ClientTransportPlugin {}", plugin)));
+            }
+        }
+
         if let Some(secret) = passphrase.0 {
             let hash = EncryptedKey::hash_password(&secret).to_string();
             tor.flag(TorFlag::HashedControlPassword(hash));
@@ -0,0 +1,62 @@
+//  Copyright 2026. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use tari_script::{reference, ExecutionStack, ScriptContext, TariScript};
+
+// Input layout: `block_height (8 bytes LE) | script length (2 bytes LE) | script bytes | input stack bytes`
+fuzz_target!(|data: &[u8]| {
+    if data.len() < 10 {
+        return;
+    }
+    let (height, data) = data.split_at(8);
+    let (len, data) = data.split_at(2);
+    let height = u64::from_le_bytes(height.try_into().expect("8 bytes"));
+    let len = usize::from(u16::from_le_bytes(len.try_into().expect("2 bytes"))).min(data.len());
+    let (script_bytes, stack_bytes) = data.split_at(len);
+
+    let Ok(script) = TariScript::from_bytes(script_bytes) else {
+        return;
+    };
+    assert_eq!(
+        TariScript::from_bytes(&script.to_bytes()).expect("serialized script must parse"),
+        script
+    );
+    let Ok(inputs) = ExecutionStack::from_bytes(stack_bytes) else {
+        return;
+    };
+    assert_eq!(
+        ExecutionStack::from_bytes(&inputs.to_bytes()).expect("serialized stack must parse"),
+        inputs
+    );
+
+    let context = ScriptContext::new(height, &[0u8; 32], &Default::default());
+    let result = script.execute_with_context(&inputs, &context);
+    // The worst-case cost is an upper bound on every execution path, so metering at that budget must never fail
+    assert_eq!(
+        script.execute_with_budget(&inputs, &context, script.execution_cost()),
+        result
+    );
+    reference::assert_equivalent(&script, &inputs, &context);
+});
@@ -52,6 +52,8 @@ pub enum ScriptError {
     InvalidDigest,
     #[error("A compare opcode failed, aborting the script immediately with reason: `{0}`")]
     CompareFailed(String),
+    #[error("The script exceeded its execution budget")]
+    ExecutionBudgetExceeded,
     #[error("Max sized vector error: {0}")]
     MaxSizeVecError(#[from] MaxSizeVecError),
 }
@@ -17,9 +17,12 @@
 
 mod error;
 mod op_codes;
+#[cfg(any(test, feature = "fuzzing"))]
+pub mod reference;
 mod script;
 mod script_context;
 mod serde;
+mod simulation;
 mod stack;
 
 pub use error::ScriptError;
@@ -33,8 +36,9 @@ pub use op_codes::{
     OpcodeVersion,
     ScalarValue,
 };
-pub use script::{ScriptOpcodes, TariScript};
-pub use script_context::ScriptContext;
+pub use script::{ScriptOpcodes, TariScript, MAX_MULTISIG_LIMIT};
+pub use script_context::{script_message, ScriptContext};
+pub use simulation::{ScriptFailure, ScriptSimulation};
 pub use stack::{ExecutionStack, StackItem};
 use tari_crypto::{
     hash_domain,
@@ -43,6 +47,7 @@ use tari_crypto::{
 };
 
 hash_domain!(CheckSigHashDomain, "com.tari.script.check_sig", 1);
+hash_domain!(ScriptMessageHashDomain, "com.tari.script.script_message", 1);
 
 /// The type used for `CheckSig`, `CheckMultiSig`, and related opcodes' signatures
 pub type CheckSigSchnorrSignature = SchnorrSignature<RistrettoPublicKey, RistrettoSecretKey, CheckSigHashDomain>;
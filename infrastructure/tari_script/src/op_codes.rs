@@ -30,6 +30,7 @@ pub type Message = [u8; MESSAGE_LENGTH];
 const PUBLIC_KEY_LENGTH: usize = 32;
 const MESSAGE_LENGTH: usize = 32;
 type MultiSigArgs = (u8, u8, Vec<RistrettoPublicKey>, Box<Message>, usize);
+type ThresholdArgs = (u8, u8, Vec<RistrettoPublicKey>, usize);
 
 /// Convert a slice into a HashValue.
 ///
@@ -83,6 +84,7 @@ const OP_CHECK_HEIGHT_VERIFY: u8 = 0x66;
 const OP_CHECK_HEIGHT: u8 = 0x67;
 const OP_COMPARE_HEIGHT_VERIFY: u8 = 0x68;
 const OP_COMPARE_HEIGHT: u8 = 0x69;
+const OP_CHECK_RELATIVE_HEIGHT_VERIFY: u8 = 0x6a;
 
 // Opcode constants: Stack Manipulation
 const OP_DROP: u8 = 0x70;
@@ -119,6 +121,7 @@ const OP_HASH_SHA256: u8 = 0xb1;
 const OP_HASH_SHA3: u8 = 0xb2;
 const OP_TO_RISTRETTO_POINT: u8 = 0xb3;
 const OP_CHECK_MULTI_SIG_VERIFY_AGGREGATE_PUB_KEY: u8 = 0xb4;
+const OP_CHECK_MULTI_SIG_THRESHOLD: u8 = 0xb5;
 
 // Opcode constants: Miscellaneous
 const OP_RETURN: u8 = 0x60;
@@ -126,6 +129,12 @@ const OP_IF_THEN: u8 = 0x61;
 const OP_ELSE: u8 = 0x62;
 const OP_END_IF: u8 = 0x63;
 
+// Execution costs, in abstract units
+const BASE_COST: u64 = 1;
+const HASH_COST: u64 = 10;
+const POINT_COST: u64 = 50;
+const SIGNATURE_COST: u64 = 100;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Opcode {
     // Block Height Checks
@@ -148,6 +157,10 @@ pub enum Opcode {
     /// current height. Fails with `InvalidInput` if there is not a valid integer value on top of the stack. Fails
     /// with `StackUnderflow` if the stack is empty.
     CompareHeight,
+    /// Compare the current block height to the height that the UTXO was mined at. Fails with `VerifyFailed` if fewer
+    /// than `delay` blocks have passed since then. Validation that is not linked to the chain does not know the mined
+    /// height, so the check is skipped there and enforced when the input is checked against the UTXO set.
+    CheckRelativeHeightVerify(u64),
 
     // Stack Manipulation
     /// No op. Does nothing. Never fails.
@@ -262,6 +275,17 @@ pub enum Opcode {
     /// Identical to CheckMultiSig, except that the aggregate of the public keys is pushed to the stack if multiple
     /// signature validation succeeds. Fails with `VerifyFailed` if any signature is invalid.
     CheckMultiSigVerifyAggregatePubKey(u8, u8, Vec<RistrettoPublicKey>, Box<Message>),
+    /// Pops exactly `m` signatures from the stack and validates them against the `n` public keys in the same manner as
+    /// CheckMultiSig, except that the signed message is not fixed in the script. Instead, each signature must sign the
+    /// script message of the UTXO being spent (see `ScriptContext::script_message`), which commits to the UTXO's
+    /// commitment, so that signatures cannot be replayed against another output locked by the same script. If
+    /// signature validation for m unique signatures succeeds, push 1 to the stack, otherwise push 0.
+    /// Fails with `ValueExceedsBounds` if `m` == 0 or if `n` == 0 or if `m` > `n` or if `n` > `MAX_MULTISIG_LIMIT`
+    /// (32) or if the number of public keys provided != `n`.
+    /// Fails with `StackUnderflow` if the stack has fewer than m items.
+    /// Fails with `IncompatibleTypes` if any of the top m elements is not a Signature.
+    /// Only valid from opcode version 1.
+    CheckMultiSigThreshold(u8, u8, Vec<RistrettoPublicKey>),
     /// Pops the top element from the stack (either a scalar or a hash), parses it canonically as a Ristretto secret
     /// key if possible, computes the corresponding Ristretto public key, and pushes this value to the stack.
     /// Fails with `StackUnderflow` if the stack is empty.
@@ -324,6 +348,22 @@ impl Opcode {
             Opcode::IfThen |
             Opcode::Else |
             Opcode::EndIf => OpcodeVersion::V0,
+            Opcode::CheckMultiSigThreshold(..) | Opcode::CheckRelativeHeightVerify(..) => OpcodeVersion::V1,
+        }
+    }
+
+    /// The execution cost of this opcode, measured in abstract units roughly proportional to the validation CPU time it
+    /// consumes. Multisig opcodes verify each public key at most once, so they are charged per key.
+    pub fn execution_cost(&self) -> u64 {
+        match self {
+            Opcode::HashBlake256 | Opcode::HashSha256 | Opcode::HashSha3 => HASH_COST,
+            Opcode::ToRistrettoPoint => POINT_COST,
+            Opcode::CheckSig(..) | Opcode::CheckSigVerify(..) => SIGNATURE_COST,
+            Opcode::CheckMultiSig(_, n, ..) |
+            Opcode::CheckMultiSigVerify(_, n, ..) |
+            Opcode::CheckMultiSigVerifyAggregatePubKey(_, n, ..) |
+            Opcode::CheckMultiSigThreshold(_, n, _) => BASE_COST + SIGNATURE_COST * u64::from(*n),
+            _ => BASE_COST,
         }
     }
 
@@ -357,6 +397,10 @@ impl Opcode {
             },
             OP_COMPARE_HEIGHT_VERIFY => Ok((CompareHeightVerify, &bytes[1..])),
             OP_COMPARE_HEIGHT => Ok((CompareHeight, &bytes[1..])),
+            OP_CHECK_RELATIVE_HEIGHT_VERIFY => {
+                let (delay, size) = u64::decode_var(&bytes[1..]).ok_or(ScriptError::InvalidData)?;
+                Ok((CheckRelativeHeightVerify(delay), &bytes[size + 1..]))
+            },
             OP_NOP => Ok((Nop, &bytes[1..])),
             OP_PUSH_ZERO => Ok((PushZero, &bytes[1..])),
             OP_PUSH_ONE => Ok((PushOne, &bytes[1..])),
@@ -432,6 +476,10 @@ impl Opcode {
                 let (m, n, keys, msg, end) = Opcode::read_multisig_args(bytes)?;
                 Ok((CheckMultiSigVerifyAggregatePubKey(m, n, keys, msg), &bytes[end..]))
             },
+            OP_CHECK_MULTI_SIG_THRESHOLD => {
+                let (m, n, keys, end) = Opcode::read_threshold_args(bytes)?;
+                Ok((CheckMultiSigThreshold(m, n, keys), &bytes[end..]))
+            },
             OP_TO_RISTRETTO_POINT => Ok((ToRistrettoPoint, &bytes[1..])),
             OP_RETURN => Ok((Return, &bytes[1..])),
             OP_IF_THEN => Ok((IfThen, &bytes[1..])),
@@ -459,6 +507,22 @@ impl Opcode {
         Ok((*m, *n, keys, msg, end))
     }
 
+    fn read_threshold_args(bytes: &[u8]) -> Result<ThresholdArgs, ScriptError> {
+        if bytes.len() < 3 {
+            return Err(ScriptError::InvalidData);
+        }
+        let m = &bytes[1];
+        let n = &bytes[2];
+        let num = *n as usize;
+        let end = 3 + num * PUBLIC_KEY_LENGTH;
+        if bytes.len() < end {
+            return Err(ScriptError::InvalidData);
+        }
+        let keys = slice_to_vec_pubkeys(&bytes[3..end], num)?;
+
+        Ok((*m, *n, keys, end))
+    }
+
     /// Convert an opcode into its binary representation and append it to the array. The function returns the byte slice
     /// that matches the opcode as a convenience
     pub fn to_bytes<'a>(&self, array: &'a mut Vec<u8>) -> &'a [u8] {
@@ -480,6 +544,12 @@ impl Opcode {
             },
             CompareHeightVerify => array.push(OP_COMPARE_HEIGHT_VERIFY),
             CompareHeight => array.push(OP_COMPARE_HEIGHT),
+            CheckRelativeHeightVerify(delay) => {
+                array.push(OP_CHECK_RELATIVE_HEIGHT_VERIFY);
+                let mut buf = [0u8; 10];
+                let used = delay.encode_var(&mut buf[..]);
+                array.extend_from_slice(&buf[0..used]);
+            },
             Nop => array.push(OP_NOP),
             PushZero => array.push(OP_PUSH_ZERO),
             PushOne => array.push(OP_PUSH_ONE),
@@ -548,6 +618,12 @@ impl Opcode {
                 }
                 array.extend_from_slice(msg.deref());
             },
+            CheckMultiSigThreshold(m, n, public_keys) => {
+                array.extend_from_slice(&[OP_CHECK_MULTI_SIG_THRESHOLD, *m, *n]);
+                for public_key in public_keys {
+                    array.extend(public_key.as_bytes());
+                }
+            },
             ToRistrettoPoint => array.push(OP_TO_RISTRETTO_POINT),
             Return => array.push(OP_RETURN),
             IfThen => array.push(OP_IF_THEN),
@@ -568,6 +644,7 @@ impl fmt::Display for Opcode {
             CheckHeight(height) => write!(fmt, "CheckHeight({})", *height),
             CompareHeightVerify => write!(fmt, "CompareHeightVerify"),
             CompareHeight => write!(fmt, "CompareHeight"),
+            CheckRelativeHeightVerify(delay) => write!(fmt, "CheckRelativeHeightVerify({})", *delay),
             Nop => write!(fmt, "Nop"),
             PushZero => write!(fmt, "PushZero"),
             PushOne => write!(fmt, "PushOne"),
@@ -625,6 +702,10 @@ impl fmt::Display for Opcode {
                     (*msg).to_hex()
                 )
             },
+            CheckMultiSigThreshold(m, n, public_keys) => {
+                let keys: Vec<String> = public_keys.iter().map(|p| p.to_hex()).collect();
+                write!(fmt, "CheckMultiSigThreshold({}, {}, [{}])", *m, *n, keys.join(", "))
+            },
             ToRistrettoPoint => write!(fmt, "ToRistrettoPoint"),
             Return => write!(fmt, "Return"),
             IfThen => write!(fmt, "IfThen"),
@@ -638,6 +719,7 @@ impl fmt::Display for Opcode {
 #[repr(u8)]
 pub enum OpcodeVersion {
     V0 = 0,
+    V1 = 1,
 }
 
 #[cfg(test)]
@@ -691,6 +773,12 @@ mod test {
         }
         test_check_height(&Opcode::CheckHeight(63), 0x67, "CheckHeight(63)");
         test_check_height(&Opcode::CheckHeightVerify(63), 0x66, "CheckHeightVerify(63)");
+        test_check_height(
+            &Opcode::CheckRelativeHeightVerify(63),
+            0x6a,
+            "CheckRelativeHeightVerify(63)",
+        );
+        assert_eq!(Opcode::CheckRelativeHeightVerify(63).get_version(), OpcodeVersion::V1);
     }
 
     #[test]
@@ -883,6 +971,42 @@ mod test {
         );
     }
 
+    #[test]
+    fn check_multisig_threshold() {
+        assert!(matches!(
+            Opcode::read_next(&[OP_CHECK_MULTI_SIG_THRESHOLD]),
+            Err(ScriptError::InvalidData)
+        ));
+        let bytes = &[
+            0xb5, 1, 2, 156, 139, 197, 249, 13, 34, 17, 145, 116, 142, 141, 215, 104, 111, 9, 225, 17, 75, 75, 173,
+            164, 195, 103, 237, 88, 174, 25, 156, 81, 235, 16, 11, 86, 233, 240, 24, 177, 56, 186, 132, 53, 33, 179,
+            36, 58, 41, 216, 23, 48, 195, 164, 194, 81, 8, 177, 8, 177, 202, 71, 194, 19, 45, 181, 105,
+        ];
+        // Truncated public keys are rejected
+        assert!(matches!(Opcode::read_next(&bytes[..40]), Err(ScriptError::InvalidData)));
+        let keys = vec![
+            RistrettoPublicKey::from_hex("9c8bc5f90d221191748e8dd7686f09e1114b4bada4c367ed58ae199c51eb100b").unwrap(),
+            RistrettoPublicKey::from_hex("56e9f018b138ba843521b3243a29d81730c3a4c25108b108b1ca47c2132db569").unwrap(),
+        ];
+        let op = Opcode::CheckMultiSigThreshold(1, 2, keys);
+        let (opcode, rem) = Opcode::read_next(bytes).unwrap();
+        assert_eq!(opcode, op);
+        assert!(rem.is_empty());
+        let mut arr = vec![];
+        op.to_bytes(&mut arr);
+        assert_eq!(arr, bytes);
+        assert_eq!(
+            format!("{}", op).as_str(),
+            "CheckMultiSigThreshold(1, 2, [9c8bc5f90d221191748e8dd7686f09e1114b4bada4c367ed58ae199c51eb100b, \
+             56e9f018b138ba843521b3243a29d81730c3a4c25108b108b1ca47c2132db569])"
+        );
+        assert_eq!(op.get_version(), OpcodeVersion::V1);
+        assert_eq!(
+            Opcode::CheckMultiSig(1, 2, vec![], Box::new([0u8; 32])).get_version(),
+            OpcodeVersion::V0
+        );
+    }
+
     #[test]
     fn deserialise_no_param_opcodes() {
         fn test_opcode(code: u8, expected: &Opcode) {
@@ -0,0 +1,491 @@
+//  Copyright 2026. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! A deliberately simple reference interpreter for TariScript, used to differentially test [TariScript] execution.
+//!
+//! The reference interpreter favours obviousness over speed. It tracks conditional execution as a stack of frames
+//! rather than the branch state machine used by the consensus interpreter, so that a bug in one is unlikely to be
+//! mirrored in the other. Signature opcodes are not modelled; scripts containing them are reported as unsupported.
+
+use std::mem;
+
+use blake2::Blake2b;
+use digest::{consts::U32, Digest};
+use sha2::Sha256;
+use sha3::Sha3_256;
+use tari_crypto::{
+    keys::PublicKey,
+    ristretto::{RistrettoPublicKey, RistrettoSecretKey},
+};
+use tari_utilities::ByteArray;
+
+use crate::{stack::MAX_STACK_SIZE, ExecutionStack, Opcode, ScriptContext, ScriptError, StackItem, TariScript};
+
+/// Returns true if every opcode in the script is modelled by the reference interpreter
+pub fn is_supported(script: &TariScript) -> bool {
+    script.as_slice().iter().all(|op| {
+        !matches!(
+            op,
+            Opcode::CheckSig(_) |
+                Opcode::CheckSigVerify(_) |
+                Opcode::CheckMultiSig(..) |
+                Opcode::CheckMultiSigVerify(..) |
+                Opcode::CheckMultiSigVerifyAggregatePubKey(..) |
+                Opcode::CheckMultiSigThreshold(..)
+        )
+    })
+}
+
+/// Executes the script with the reference interpreter. Returns `None` if the script contains opcodes that the
+/// reference interpreter does not model.
+pub fn execute(
+    script: &TariScript,
+    inputs: &ExecutionStack,
+    context: &ScriptContext,
+) -> Option<Result<StackItem, ScriptError>> {
+    if !is_supported(script) {
+        return None;
+    }
+    let mut stack = inputs.clone();
+    let mut items = Vec::with_capacity(stack.size());
+    while let Some(item) = stack.pop() {
+        items.push(item);
+    }
+    items.reverse();
+    Some(Machine::new(items, context.block_height(), context.mined_height()).run(script.as_slice()))
+}
+
+/// Executes the script with both interpreters and panics if they disagree on the outcome. Errors are compared by
+/// variant only.
+pub fn assert_equivalent(script: &TariScript, inputs: &ExecutionStack, context: &ScriptContext) {
+    let Some(expected) = execute(script, inputs, context) else {
+        return;
+    };
+    let actual = script.execute_with_context(inputs, context);
+    let agrees = match (&expected, &actual) {
+        (Ok(a), Ok(b)) => a == b,
+        (Err(a), Err(b)) => mem::discriminant(a) == mem::discriminant(b),
+        _ => false,
+    };
+    assert!(
+        agrees,
+        "Interpreters diverged for script `{}` with inputs {:?} at height {}: reference = {:?}, consensus = {:?}",
+        script,
+        inputs,
+        context.block_height(),
+        expected,
+        actual
+    );
+}
+
+struct Frame {
+    parent_active: bool,
+    condition: bool,
+    in_else: bool,
+}
+
+struct Machine {
+    stack: Vec<StackItem>,
+    frames: Vec<Frame>,
+    block_height: u64,
+    mined_height: Option<u64>,
+}
+
+impl Machine {
+    fn new(stack: Vec<StackItem>, block_height: u64, mined_height: Option<u64>) -> Self {
+        Self {
+            stack,
+            frames: Vec::new(),
+            block_height,
+            mined_height,
+        }
+    }
+
+    fn is_active(&self) -> bool {
+        match self.frames.last() {
+            Some(frame) => frame.parent_active && frame.condition != frame.in_else,
+            None => true,
+        }
+    }
+
+    fn run(mut self, ops: &[Opcode]) -> Result<StackItem, ScriptError> {
+        for op in ops {
+            match op {
+                Opcode::IfThen => {
+                    let parent_active = self.is_active();
+                    let condition = if parent_active {
+                        match self.pop()? {
+                            StackItem::Number(1) => true,
+                            StackItem::Number(0) => false,
+                            _ => return Err(ScriptError::InvalidInput),
+                        }
+                    } else {
+                        false
+                    };
+                    self.frames.push(Frame {
+                        parent_active,
+                        condition,
+                        in_else: false,
+                    });
+                },
+                Opcode::Else => {
+                    let frame = self.frames.last_mut().ok_or(ScriptError::InvalidOpcode)?;
+                    if frame.in_else {
+                        return Err(ScriptError::InvalidOpcode);
+                    }
+                    frame.in_else = true;
+                },
+                Opcode::EndIf => {
+                    let frame = self.frames.pop().ok_or(ScriptError::InvalidOpcode)?;
+                    if !frame.in_else {
+                        return Err(ScriptError::MissingOpcode);
+                    }
+                },
+                op if self.is_active() => self.step(op)?,
+                _ => {},
+            }
+        }
+
+        if !self.frames.is_empty() {
+            return Err(ScriptError::MissingOpcode);
+        }
+        if self.stack.len() != 1 {
+            return Err(ScriptError::NonUnitLengthStack);
+        }
+        self.pop()
+    }
+
+    fn push(&mut self, item: StackItem) -> Result<(), ScriptError> {
+        if self.stack.len() >= MAX_STACK_SIZE {
+            return Err(ScriptError::StackOverflow);
+        }
+        self.stack.push(item);
+        Ok(())
+    }
+
+    fn push_bool(&mut self, value: bool) -> Result<(), ScriptError> {
+        self.push(StackItem::Number(i64::from(value)))
+    }
+
+    fn pop(&mut self) -> Result<StackItem, ScriptError> {
+        self.stack.pop().ok_or(ScriptError::StackUnderflow)
+    }
+
+    fn pop_number(&mut self) -> Result<i64, ScriptError> {
+        match self.pop()? {
+            StackItem::Number(n) => Ok(n),
+            _ => Err(ScriptError::InvalidInput),
+        }
+    }
+
+    fn signed_height(&self) -> Result<i128, ScriptError> {
+        if i64::try_from(self.block_height).is_err() {
+            return Err(ScriptError::ValueExceedsBounds);
+        }
+        Ok(i128::from(self.block_height))
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn step(&mut self, op: &Opcode) -> Result<(), ScriptError> {
+        match op {
+            Opcode::CheckHeightVerify(height) => {
+                if self.block_height < *height {
+                    return Err(ScriptError::VerifyFailed);
+                }
+            },
+            Opcode::CheckRelativeHeightVerify(delay) => {
+                if let Some(mined_height) = self.mined_height {
+                    if self.block_height < mined_height.saturating_add(*delay) {
+                        return Err(ScriptError::VerifyFailed);
+                    }
+                }
+            },
+            Opcode::CheckHeight(height) => {
+                if i64::try_from(*height).is_err() {
+                    return Err(ScriptError::ValueExceedsBounds);
+                }
+                let diff = self.signed_height()? - i128::from(*height);
+                self.push(StackItem::Number(i64::try_from(diff)?))?;
+            },
+            Opcode::CompareHeightVerify => {
+                let target = self.pop_number()?;
+                let target = u64::try_from(target).map_err(|_| ScriptError::ValueExceedsBounds)?;
+                if self.block_height < target {
+                    return Err(ScriptError::VerifyFailed);
+                }
+            },
+            Opcode::CompareHeight => {
+                let target = self.pop_number()?;
+                let diff = self.signed_height()? - i128::from(target);
+                let diff = i64::try_from(diff).map_err(|_| ScriptError::CompareFailed("overflow".to_string()))?;
+                self.push(StackItem::Number(diff))?;
+            },
+            Opcode::Nop => {},
+            Opcode::PushZero => self.push(StackItem::Number(0))?,
+            Opcode::PushOne => self.push(StackItem::Number(1))?,
+            Opcode::PushHash(h) => self.push(StackItem::Hash(**h))?,
+            Opcode::PushInt(n) => self.push(StackItem::Number(*n))?,
+            Opcode::PushPubKey(p) => self.push(StackItem::PublicKey(*p.clone()))?,
+            Opcode::Drop => {
+                self.pop()?;
+            },
+            Opcode::Dup => {
+                let top = self.stack.last().cloned().ok_or(ScriptError::StackUnderflow)?;
+                self.push(top)?;
+            },
+            Opcode::RevRot => {
+                if self.stack.len() < 3 {
+                    return Err(ScriptError::StackUnderflow);
+                }
+                let top = self.pop()?;
+                let at = self.stack.len() - 2;
+                self.stack.insert(at, top);
+            },
+            Opcode::GeZero => {
+                let n = self.pop_number()?;
+                self.push_bool(n >= 0)?;
+            },
+            Opcode::GtZero => {
+                let n = self.pop_number()?;
+                self.push_bool(n > 0)?;
+            },
+            Opcode::LeZero => {
+                let n = self.pop_number()?;
+                self.push_bool(n <= 0)?;
+            },
+            Opcode::LtZero => {
+                let n = self.pop_number()?;
+                self.push_bool(n < 0)?;
+            },
+            Opcode::Add => {
+                let a = self.pop()?;
+                let b = self.pop()?;
+                let sum = match (a, b) {
+                    (StackItem::Number(a), StackItem::Number(b)) => {
+                        StackItem::Number(a.checked_add(b).ok_or(ScriptError::ValueExceedsBounds)?)
+                    },
+                    (StackItem::Commitment(a), StackItem::Commitment(b)) => StackItem::Commitment(&a + &b),
+                    (StackItem::PublicKey(a), StackItem::PublicKey(b)) => StackItem::PublicKey(&a + &b),
+                    _ => return Err(ScriptError::IncompatibleTypes),
+                };
+                self.push(sum)?;
+            },
+            Opcode::Sub => {
+                let a = self.pop()?;
+                let b = self.pop()?;
+                let diff = match (a, b) {
+                    (StackItem::Number(a), StackItem::Number(b)) => {
+                        StackItem::Number(b.checked_sub(a).ok_or(ScriptError::ValueExceedsBounds)?)
+                    },
+                    (StackItem::Commitment(a), StackItem::Commitment(b)) => StackItem::Commitment(&b - &a),
+                    _ => return Err(ScriptError::IncompatibleTypes),
+                };
+                self.push(diff)?;
+            },
+            Opcode::Equal => {
+                let equal = self.pop_equal()?;
+                self.push_bool(equal)?;
+            },
+            Opcode::EqualVerify => {
+                if !self.pop_equal()? {
+                    return Err(ScriptError::VerifyFailed);
+                }
+            },
+            Opcode::Or(n) => {
+                let found = self.pop_or(*n)?;
+                self.push_bool(found)?;
+            },
+            Opcode::OrVerify(n) => {
+                if !self.pop_or(*n)? {
+                    return Err(ScriptError::VerifyFailed);
+                }
+            },
+            Opcode::HashBlake256 => self.hash::<Blake2b<U32>>()?,
+            Opcode::HashSha256 => self.hash::<Sha256>()?,
+            Opcode::HashSha3 => self.hash::<Sha3_256>()?,
+            Opcode::ToRistrettoPoint => {
+                let bytes = match self.pop()? {
+                    StackItem::Hash(h) => h,
+                    StackItem::Scalar(s) => s,
+                    _ => return Err(ScriptError::IncompatibleTypes),
+                };
+                let k = RistrettoSecretKey::from_canonical_bytes(&bytes).map_err(|_| ScriptError::InvalidInput)?;
+                self.push(StackItem::PublicKey(RistrettoPublicKey::from_secret_key(&k)))?;
+            },
+            Opcode::Return => return Err(ScriptError::Return),
+            Opcode::IfThen |
+            Opcode::Else |
+            Opcode::EndIf |
+            Opcode::CheckSig(_) |
+            Opcode::CheckSigVerify(_) |
+            Opcode::CheckMultiSig(..) |
+            Opcode::CheckMultiSigVerify(..) |
+            Opcode::CheckMultiSigVerifyAggregatePubKey(..) |
+            Opcode::CheckMultiSigThreshold(..) => unreachable!("not dispatched to step"),
+        }
+        Ok(())
+    }
+
+    fn pop_equal(&mut self) -> Result<bool, ScriptError> {
+        let a = self.pop()?;
+        let b = self.pop()?;
+        match (a, b) {
+            (StackItem::Number(a), StackItem::Number(b)) => Ok(a == b),
+            (StackItem::Hash(a), StackItem::Hash(b)) => Ok(a == b),
+            (StackItem::Commitment(a), StackItem::Commitment(b)) => Ok(a == b),
+            (StackItem::PublicKey(a), StackItem::PublicKey(b)) => Ok(a == b),
+            (StackItem::Signature(a), StackItem::Signature(b)) => Ok(a == b),
+            _ => Err(ScriptError::IncompatibleTypes),
+        }
+    }
+
+    fn pop_or(&mut self, n: u8) -> Result<bool, ScriptError> {
+        let n = usize::from(n);
+        if self.stack.len() < n {
+            return Err(ScriptError::StackUnderflow);
+        }
+        let candidates = self.stack.split_off(self.stack.len() - n);
+        let item = self.pop()?;
+        if candidates
+            .iter()
+            .any(|c| mem::discriminant(c) != mem::discriminant(&item))
+        {
+            return Err(ScriptError::InvalidInput);
+        }
+        Ok(candidates.contains(&item))
+    }
+
+    fn hash<D: Digest>(&mut self) -> Result<(), ScriptError> {
+        let digest = match self.pop()? {
+            StackItem::Hash(h) => D::digest(h),
+            StackItem::PublicKey(p) => D::digest(p.as_bytes()),
+            StackItem::Commitment(c) => D::digest(c.as_bytes()),
+            _ => return Err(ScriptError::IncompatibleTypes),
+        };
+        let mut hash = [0u8; 32];
+        hash.copy_from_slice(digest.as_slice());
+        self.push(StackItem::Hash(hash))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use tari_crypto::{keys::SecretKey, ristretto::pedersen::PedersenCommitment};
+
+    use super::*;
+    use crate::{script, HashValue};
+
+    const NUMBERS: [i64; 7] = [0, 1, 2, -1, 100, i64::MAX, i64::MIN];
+    const HEIGHTS: [u64; 5] = [0, 1, 100, i64::MAX as u64, u64::MAX];
+
+    fn random_public_key(rng: &mut StdRng) -> RistrettoPublicKey {
+        RistrettoPublicKey::from_secret_key(&RistrettoSecretKey::random(rng))
+    }
+
+    fn random_item(rng: &mut StdRng) -> StackItem {
+        match rng.gen_range(0..6) {
+            0 | 1 => StackItem::Number(NUMBERS[rng.gen_range(0..NUMBERS.len())]),
+            2 => StackItem::Hash(rng.gen()),
+            3 => StackItem::PublicKey(random_public_key(rng)),
+            4 => StackItem::Commitment(PedersenCommitment::from_public_key(&random_public_key(rng))),
+            _ => {
+                let mut scalar = [0u8; 32];
+                scalar.copy_from_slice(RistrettoSecretKey::random(rng).as_bytes());
+                StackItem::Scalar(scalar)
+            },
+        }
+    }
+
+    fn random_opcode(rng: &mut StdRng) -> Opcode {
+        let height = HEIGHTS[rng.gen_range(0..HEIGHTS.len())];
+        match rng.gen_range(0..30) {
+            0 => Opcode::CheckHeightVerify(height),
+            1 => Opcode::CheckHeight(height),
+            2 => Opcode::CompareHeightVerify,
+            3 => Opcode::CompareHeight,
+            4 => Opcode::Nop,
+            5 => Opcode::PushZero,
+            6 => Opcode::PushOne,
+            7 => Opcode::PushHash(Box::new(rng.gen())),
+            8 => Opcode::PushInt(NUMBERS[rng.gen_range(0..NUMBERS.len())]),
+            9 => Opcode::PushPubKey(Box::new(random_public_key(rng))),
+            10 => Opcode::Drop,
+            11 => Opcode::Dup,
+            12 => Opcode::RevRot,
+            13 => Opcode::GeZero,
+            14 => Opcode::GtZero,
+            15 => Opcode::LeZero,
+            16 => Opcode::LtZero,
+            17 => Opcode::Add,
+            18 => Opcode::Sub,
+            19 => Opcode::Equal,
+            20 => Opcode::EqualVerify,
+            21 => Opcode::Or(rng.gen_range(0..4)),
+            22 => Opcode::OrVerify(rng.gen_range(0..4)),
+            23 => Opcode::HashBlake256,
+            24 => Opcode::HashSha256,
+            25 => Opcode::HashSha3,
+            26 => Opcode::ToRistrettoPoint,
+            27 => Opcode::IfThen,
+            28 => Opcode::Else,
+            _ => Opcode::EndIf,
+        }
+    }
+
+    #[test]
+    fn it_agrees_with_the_consensus_interpreter_on_random_scripts() {
+        let mut rng = StdRng::seed_from_u64(0x7a51);
+        for _ in 0..5_000 {
+            let ops = (0..rng.gen_range(1..40)).map(|_| random_opcode(&mut rng)).collect();
+            let script = TariScript::new(ops).unwrap();
+            let inputs = ExecutionStack::new((0..rng.gen_range(0..5)).map(|_| random_item(&mut rng)).collect());
+            let height = HEIGHTS[rng.gen_range(0..HEIGHTS.len())];
+            let context = ScriptContext::new(height, &HashValue::default(), &PedersenCommitment::default());
+            assert_equivalent(&script, &inputs, &context);
+        }
+    }
+
+    #[test]
+    fn it_agrees_on_conditional_edge_cases() {
+        let context = ScriptContext::default();
+        let scripts = [
+            script!(PushOne IfThen PushZero Else PushOne EndIf).unwrap(),
+            script!(PushZero IfThen PushZero IfThen Return Else Return EndIf Else PushOne EndIf).unwrap(),
+            script!(PushOne Else EndIf).unwrap(),
+            script!(PushOne IfThen PushOne EndIf).unwrap(),
+            script!(PushOne IfThen PushOne Else Else EndIf).unwrap(),
+            script!(PushOne EndIf).unwrap(),
+            script!(PushZero IfThen Return Else PushOne).unwrap(),
+        ];
+        for script in &scripts {
+            assert_equivalent(script, &ExecutionStack::default(), &context);
+        }
+    }
+
+    #[test]
+    fn it_does_not_model_signature_opcodes() {
+        let script = script!(CheckSig(Box::default())).unwrap();
+        assert!(!is_supported(&script));
+        assert!(execute(&script, &ExecutionStack::default(), &ScriptContext::default()).is_none());
+    }
+}
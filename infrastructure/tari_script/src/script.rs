@@ -43,6 +43,7 @@ use crate::{
     Opcode,
     ScriptContext,
     ScriptError,
+    ScriptSimulation,
     StackItem,
 };
 
@@ -56,7 +57,8 @@ macro_rules! script {
     }}
 }
 
-const MAX_MULTISIG_LIMIT: u8 = 32;
+/// The maximum number of public keys that a multisig opcode may reference
+pub const MAX_MULTISIG_LIMIT: u8 = 32;
 const MAX_SCRIPT_BYTES: usize = 4096;
 
 /// The sized vector of opcodes that make up a script
@@ -143,35 +145,81 @@ impl TariScript {
         inputs: &ExecutionStack,
         context: &ScriptContext,
     ) -> Result<StackItem, ScriptError> {
+        self.simulate(inputs, context).into_result()
+    }
+
+    /// Execute the script with the given inputs and context, failing with `ExecutionBudgetExceeded` as soon as the
+    /// total cost of the executed opcodes exceeds `budget`. If successful, returns the final stack item.
+    pub fn execute_with_budget(
+        &self,
+        inputs: &ExecutionStack,
+        context: &ScriptContext,
+        budget: u64,
+    ) -> Result<StackItem, ScriptError> {
+        self.simulate_with_budget(inputs, context, budget).into_result()
+    }
+
+    /// Dry-runs the script with the given inputs and context. The returned [ScriptSimulation] holds the stack as it was
+    /// when execution stopped and, on failure, the error along with the index of the opcode that raised it.
+    pub fn simulate(&self, inputs: &ExecutionStack, context: &ScriptContext) -> ScriptSimulation {
+        self.simulate_with_budget(inputs, context, u64::MAX)
+    }
+
+    /// Dry-runs the script as [simulate](TariScript::simulate) does, metering the cost of each executed opcode against
+    /// `budget`.
+    pub fn simulate_with_budget(
+        &self,
+        inputs: &ExecutionStack,
+        context: &ScriptContext,
+        budget: u64,
+    ) -> ScriptSimulation {
         // Copy all inputs onto the stack
         let mut stack = inputs.clone();
 
         // Local execution state
         let mut state = ExecutionState::default();
+        let mut cost = 0u64;
 
-        for opcode in self.script.iter() {
-            if self.should_execute(opcode, &state)? {
-                self.execute_opcode(opcode, &mut stack, context, &mut state)?
-            } else {
-                continue;
+        for (i, opcode) in self.script.iter().enumerate() {
+            let result = match self.should_execute(opcode, &state) {
+                Ok(true) => {
+                    cost = cost.saturating_add(opcode.execution_cost());
+                    if cost > budget {
+                        return ScriptSimulation::failed(stack, cost, ScriptError::ExecutionBudgetExceeded, Some(i));
+                    }
+                    self.execute_opcode(opcode, &mut stack, context, &mut state)
+                },
+                Ok(false) => continue,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                return ScriptSimulation::failed(stack, cost, e, Some(i));
             }
         }
 
         // the script has finished but there was an open IfThen or Else!
         if !state.if_stack.is_empty() {
-            return Err(ScriptError::MissingOpcode);
+            return ScriptSimulation::failed(stack, cost, ScriptError::MissingOpcode, None);
         }
 
         // After the script completes, it is successful if and only if it has not aborted, and there is exactly a single
         // element on the stack. The script fails if the stack is empty, or contains more than one element, or aborts
         // early.
         if stack.size() == 1 {
-            stack.pop().ok_or(ScriptError::NonUnitLengthStack)
+            ScriptSimulation::success(stack, cost)
         } else {
-            Err(ScriptError::NonUnitLengthStack)
+            ScriptSimulation::failed(stack, cost, ScriptError::NonUnitLengthStack, None)
         }
     }
 
+    /// Returns the worst-case execution cost of the script. Scripts cannot loop, so this is the sum of the costs of all
+    /// of its opcodes, and is an upper bound on the cost of any execution path.
+    pub fn execution_cost(&self) -> u64 {
+        self.script
+            .iter()
+            .fold(0u64, |acc, op| acc.saturating_add(op.execution_cost()))
+    }
+
     /// Returns the number of script op codes
     pub fn size(&self) -> usize {
         self.script.len()
@@ -258,6 +306,9 @@ impl TariScript {
             CheckHeight(height) => TariScript::handle_check_height(stack, *height, ctx.block_height()),
             CompareHeightVerify => TariScript::handle_compare_height_verify(stack, ctx.block_height()),
             CompareHeight => TariScript::handle_compare_height(stack, ctx.block_height()),
+            CheckRelativeHeightVerify(delay) => {
+                TariScript::handle_check_relative_height_verify(*delay, ctx.mined_height(), ctx.block_height())
+            },
             Nop => Ok(()),
             PushZero => stack.push(Number(0)),
             PushOne => stack.push(Number(1)),
@@ -327,6 +378,16 @@ impl TariScript {
                     Err(ScriptError::VerifyFailed)
                 }
             },
+            CheckMultiSigThreshold(m, n, public_keys) => {
+                if self
+                    .check_multisig(stack, *m, *n, public_keys, ctx.script_message())?
+                    .is_some()
+                {
+                    stack.push(Number(1))
+                } else {
+                    stack.push(Number(0))
+                }
+            },
             ToRistrettoPoint => self.handle_to_ristretto_point(stack),
             Return => Err(ScriptError::Return),
             IfThen => TariScript::handle_if_then(stack, state),
@@ -343,6 +404,18 @@ impl TariScript {
         }
     }
 
+    fn handle_check_relative_height_verify(
+        delay: u64,
+        mined_height: Option<u64>,
+        block_height: u64,
+    ) -> Result<(), ScriptError> {
+        // Without the mined height the check is left to validation against the chain
+        let Some(mined_height) = mined_height else {
+            return Ok(());
+        };
+        TariScript::handle_check_height_verify(mined_height.saturating_add(delay), block_height)
+    }
+
     fn handle_check_height(stack: &mut ExecutionStack, height: u64, block_height: u64) -> Result<(), ScriptError> {
         let height = i64::try_from(height)?;
         let block_height = i64::try_from(block_height)?;
@@ -748,6 +821,7 @@ mod test {
         op_codes::{slice_to_boxed_hash, slice_to_boxed_message, HashValue, Message},
         CheckSigSchnorrSignature,
         ExecutionStack,
+        Opcode,
         Opcode::CheckMultiSigVerifyAggregatePubKey,
         ScriptContext,
         StackItem,
@@ -940,6 +1014,25 @@ mod test {
         }
     }
 
+    #[test]
+    fn op_check_relative_height_verify() {
+        let script = script!(CheckRelativeHeightVerify(5)).unwrap();
+        let inputs = inputs!(1);
+
+        for block_height in 100..105 {
+            let ctx = context_with_height(block_height).with_mined_height(100);
+            let err = script.execute_with_context(&inputs, &ctx).unwrap_err();
+            assert!(matches!(err, ScriptError::VerifyFailed));
+        }
+        for block_height in 105..=110 {
+            let ctx = context_with_height(block_height).with_mined_height(100);
+            assert_eq!(script.execute_with_context(&inputs, &ctx).unwrap(), Number(1));
+        }
+        // The check is left to chain validation when the mined height is not known
+        let ctx = context_with_height(0);
+        assert_eq!(script.execute_with_context(&inputs, &ctx).unwrap(), Number(1));
+    }
+
     #[test]
     fn op_compare_height() {
         let script = script!(CompareHeight).unwrap();
@@ -1545,6 +1638,56 @@ mod test {
         assert_eq!(result, Number(1));
     }
 
+    #[test]
+    fn check_multisig_threshold() {
+        use crate::{op_codes::Opcode::CheckMultiSigThreshold, script_message, StackItem::Number};
+        let mut rng = rand::thread_rng();
+        let (k_alice, p_alice) = RistrettoPublicKey::random_keypair(&mut rng);
+        let (k_bob, p_bob) = RistrettoPublicKey::random_keypair(&mut rng);
+        let (k_carol, p_carol) = RistrettoPublicKey::random_keypair(&mut rng);
+        let (k_eve, _) = RistrettoPublicKey::random_keypair(&mut rng);
+        let commitment = PedersenCommitment::from_public_key(&RistrettoPublicKey::random_keypair(&mut rng).1);
+        let other_commitment = PedersenCommitment::from_public_key(&RistrettoPublicKey::random_keypair(&mut rng).1);
+        let ctx = ScriptContext::new(1, &HashValue::default(), &commitment);
+        let other_ctx = ScriptContext::new(1, &HashValue::default(), &other_commitment);
+        assert_eq!(ctx.script_message(), script_message(&commitment));
+        assert_ne!(ctx.script_message(), other_ctx.script_message());
+
+        let m = script_message(&commitment);
+        let s_alice = CheckSigSchnorrSignature::sign(&k_alice, m, &mut rng).unwrap();
+        let s_bob = CheckSigSchnorrSignature::sign(&k_bob, m, &mut rng).unwrap();
+        let s_carol = CheckSigSchnorrSignature::sign(&k_carol, m, &mut rng).unwrap();
+        let s_eve = CheckSigSchnorrSignature::sign(&k_eve, m, &mut rng).unwrap();
+
+        // 2 of 3
+        let keys = vec![p_alice.clone(), p_bob.clone(), p_carol.clone()];
+        let script = TariScript::new(vec![CheckMultiSigThreshold(2, 3, keys.clone())]).unwrap();
+
+        let inputs = inputs!(s_alice.clone(), s_bob.clone());
+        assert_eq!(script.execute_with_context(&inputs, &ctx).unwrap(), Number(1));
+        let inputs = inputs!(s_bob.clone(), s_carol.clone());
+        assert_eq!(script.execute_with_context(&inputs, &ctx).unwrap(), Number(1));
+        let inputs = inputs!(s_alice.clone(), s_eve.clone());
+        assert_eq!(script.execute_with_context(&inputs, &ctx).unwrap(), Number(0));
+        let inputs = inputs!(s_alice.clone(), s_alice.clone());
+        assert_eq!(script.execute_with_context(&inputs, &ctx).unwrap(), Number(0));
+
+        // Signatures for one output cannot be used to spend another output locked by the same script
+        let inputs = inputs!(s_alice.clone(), s_bob.clone());
+        assert_eq!(script.execute_with_context(&inputs, &other_ctx).unwrap(), Number(0));
+
+        // Not enough signatures
+        let inputs = inputs!(s_alice);
+        let err = script.execute_with_context(&inputs, &ctx).unwrap_err();
+        assert_eq!(err, ScriptError::StackUnderflow);
+
+        // m > n
+        let script = TariScript::new(vec![CheckMultiSigThreshold(4, 3, keys)]).unwrap();
+        let inputs = inputs!(s_bob);
+        let err = script.execute_with_context(&inputs, &ctx).unwrap_err();
+        assert_eq!(err, ScriptError::ValueExceedsBounds);
+    }
+
     #[test]
     fn pay_to_public_key_hash() {
         use crate::StackItem::PublicKey;
@@ -1885,4 +2028,61 @@ mod test {
         assert!(stack_item.is_ok());
         assert_eq!(stack_item.unwrap(), Number(-76))
     }
+
+    #[test]
+    fn simulate_reports_failing_opcode_and_stack() {
+        let script = script!(PushInt(5) PushInt(7) EqualVerify PushOne).unwrap();
+        let inputs = ExecutionStack::new(vec![Number(1)]);
+        let sim = script.simulate(&inputs, &context_with_height(10));
+        assert!(!sim.is_success());
+        let failure = sim.failure.clone().unwrap();
+        assert_eq!(failure.error, ScriptError::VerifyFailed);
+        assert_eq!(failure.opcode_index, Some(2));
+        assert_eq!(sim.stack, ExecutionStack::new(vec![Number(1)]));
+
+        let script = script!(PushInt(5) PushInt(7)).unwrap();
+        let sim = script.simulate(&ExecutionStack::default(), &context_with_height(10));
+        let failure = sim.failure.clone().unwrap();
+        assert_eq!(failure.error, ScriptError::NonUnitLengthStack);
+        assert_eq!(failure.opcode_index, None);
+        assert_eq!(sim.stack.size(), 2);
+
+        let script = script!(PushInt(5)).unwrap();
+        let sim = script.simulate(&ExecutionStack::default(), &context_with_height(10));
+        assert!(sim.is_success());
+        assert_eq!(sim.into_result().unwrap(), Number(5));
+    }
+
+    #[test]
+    fn execution_budget_limits_pathological_scripts() {
+        let script = TariScript::new(vec![Opcode::HashBlake256; 500]).unwrap();
+        let inputs = ExecutionStack::new(vec![Hash([0u8; 32])]);
+        let ctx = ScriptContext::default();
+        assert_eq!(script.execution_cost(), 5000);
+
+        assert!(script.execute_with_budget(&inputs, &ctx, 5000).is_ok());
+        let sim = script.simulate_with_budget(&inputs, &ctx, 4999);
+        let failure = sim.failure.unwrap();
+        assert_eq!(failure.error, ScriptError::ExecutionBudgetExceeded);
+        assert_eq!(failure.opcode_index, Some(499));
+        assert_eq!(sim.cost, 5000);
+
+        // Only the executed branch is charged
+        let script = script!(PushOne IfThen PushInt(1) Else HashBlake256 HashBlake256 EndIf).unwrap();
+        let sim = script.simulate(&ExecutionStack::default(), &ctx);
+        assert!(sim.is_success());
+        assert_eq!(sim.cost, 5);
+        assert_eq!(script.execution_cost(), 25);
+    }
+
+    #[test]
+    fn multisig_cost_scales_with_keys() {
+        let mut rng = rand::thread_rng();
+        let keys = (0..32)
+            .map(|_| RistrettoPublicKey::random_keypair(&mut rng).1)
+            .collect::<Vec<_>>();
+        let msg = slice_to_boxed_message(&[0u8; 32]);
+        let script = TariScript::new(vec![Opcode::CheckMultiSig(1, 32, keys, msg)]).unwrap();
+        assert_eq!(script.execution_cost(), 3201);
+    }
 }
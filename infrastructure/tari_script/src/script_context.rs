@@ -15,9 +15,12 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use tari_crypto::ristretto::pedersen::PedersenCommitment;
+use blake2::Blake2b;
+use digest::{consts::U32, generic_array::GenericArray};
+use tari_crypto::{hashing::DomainSeparatedHasher, ristretto::pedersen::PedersenCommitment};
+use tari_utilities::ByteArray;
 
-use crate::HashValue;
+use crate::{HashValue, Message, ScriptMessageHashDomain};
 
 /// Contextual data for use in Tari scripts. The context will typically be unambiguously and deterministically
 /// populated by nodes that are executing the script.
@@ -29,6 +32,8 @@ pub struct ScriptContext {
     prev_block_hash: HashValue,
     /// The commitment of the UTXO that is attached to this script
     commitment: PedersenCommitment,
+    /// The height that the UTXO was mined at, if the context is linked to the chain
+    mined_height: Option<u64>,
 }
 
 impl ScriptContext {
@@ -37,9 +42,16 @@ impl ScriptContext {
             block_height: height,
             prev_block_hash: *prev_hash,
             commitment: com.clone(),
+            mined_height: None,
         }
     }
 
+    /// Adds the height that the UTXO was mined at, which `CheckRelativeHeightVerify` is checked against
+    pub fn with_mined_height(mut self, mined_height: u64) -> Self {
+        self.mined_height = Some(mined_height);
+        self
+    }
+
     pub fn block_height(&self) -> u64 {
         self.block_height
     }
@@ -51,4 +63,23 @@ impl ScriptContext {
     pub fn commitment(&self) -> &PedersenCommitment {
         &self.commitment
     }
+
+    pub fn mined_height(&self) -> Option<u64> {
+        self.mined_height
+    }
+
+    /// The message that `CheckMultiSigThreshold` signatures must sign to spend the UTXO attached to this context
+    pub fn script_message(&self) -> Message {
+        script_message(&self.commitment)
+    }
+}
+
+/// Calculates the script message for the UTXO with the given commitment. Signers use this to produce the signatures
+/// required by `CheckMultiSigThreshold` before the spending transaction is built.
+pub fn script_message(commitment: &PedersenCommitment) -> Message {
+    let mut message = Message::default();
+    DomainSeparatedHasher::<Blake2b<U32>, ScriptMessageHashDomain>::new_with_label("script_message")
+        .chain(commitment.as_bytes())
+        .finalize_into(GenericArray::from_mut_slice(&mut message));
+    message
 }
@@ -0,0 +1,75 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::{ExecutionStack, ScriptError, StackItem};
+
+/// The outcome of a script dry-run, as produced by [TariScript::simulate](crate::TariScript::simulate). Unlike
+/// `execute`, a simulation retains the stack at the point where execution stopped, along with the position of the
+/// opcode that failed, which makes it useful for debugging scripts before funds are committed to them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptSimulation {
+    /// The state of the stack when execution stopped
+    pub stack: ExecutionStack,
+    /// The total execution cost of the opcodes that were executed
+    pub cost: u64,
+    /// The reason execution failed, if it did
+    pub failure: Option<ScriptFailure>,
+}
+
+/// Describes why and where a simulated script failed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptFailure {
+    pub error: ScriptError,
+    /// The index of the opcode that failed. This is `None` if every opcode completed but the script was left in an
+    /// invalid final state, i.e. an unclosed branch or a stack that does not contain exactly one item.
+    pub opcode_index: Option<usize>,
+}
+
+impl ScriptSimulation {
+    pub(crate) fn success(stack: ExecutionStack, cost: u64) -> Self {
+        Self {
+            stack,
+            cost,
+            failure: None,
+        }
+    }
+
+    pub(crate) fn failed(stack: ExecutionStack, cost: u64, error: ScriptError, opcode_index: Option<usize>) -> Self {
+        Self {
+            stack,
+            cost,
+            failure: Some(ScriptFailure { error, opcode_index }),
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.failure.is_none()
+    }
+
+    /// Converts the simulation into the result that `execute_with_context` would have returned
+    pub fn into_result(mut self) -> Result<StackItem, ScriptError> {
+        match self.failure {
+            Some(failure) => Err(failure.error),
+            None => self.stack.pop().ok_or(ScriptError::NonUnitLengthStack),
+        }
+    }
+}
@@ -0,0 +1,40 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use serde::{Deserialize, Serialize};
+use tari_common::SubConfigPath;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TelemetryConfig {
+    override_from: Option<String>,
+    /// The OTLP gRPC endpoint to export spans to, e.g. `http://localhost:4317`. Tracing export is disabled if this is
+    /// not set.
+    pub otlp_endpoint: Option<String>,
+    /// The fraction of traces that are sampled. The sampling decision of a remote peer is ignored, so that peers
+    /// cannot make this node export more spans than this.
+    pub sample_ratio: f64,
+    /// Send the trace context of outbound requests to peers and continue the traces of inbound requests. Disabled by
+    /// the privacy profile, since the trace and span IDs link requests to the same node across connections.
+    pub propagate_trace_context: bool,
+    /// Span filter directives, in the same syntax as `RUST_LOG`
+    pub filter: String,
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            override_from: None,
+            otlp_endpoint: None,
+            sample_ratio: 1.0,
+            propagate_trace_context: true,
+            filter: "debug".to_string(),
+        }
+    }
+}
+
+impl SubConfigPath for TelemetryConfig {
+    fn main_key_prefix() -> &'static str {
+        "telemetry"
+    }
+}
@@ -0,0 +1,20 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+#[cfg(feature = "otlp")]
+use opentelemetry::trace::TraceError;
+use thiserror::Error;
+use tracing_subscriber::{filter::ParseError, util::TryInitError};
+
+#[derive(Debug, Error)]
+pub enum TelemetryError {
+    #[cfg(feature = "otlp")]
+    #[error("Could not install the OTLP span exporter: {0}")]
+    ExporterError(#[from] TraceError),
+    #[error("Spans cannot be exported to {0} because the application was built without the `telemetry` feature")]
+    ExporterNotSupported(String),
+    #[error("Invalid span filter: {0}")]
+    InvalidFilter(#[from] ParseError),
+    #[error("Could not set the global tracing subscriber: {0}")]
+    SubscriberError(#[from] TryInitError),
+}
@@ -0,0 +1,109 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use opentelemetry::global;
+#[cfg(feature = "otlp")]
+use opentelemetry::{trace::TracerProvider as _, KeyValue};
+#[cfg(feature = "otlp")]
+use opentelemetry_otlp::WithExportConfig;
+#[cfg(feature = "otlp")]
+use opentelemetry_sdk::{propagation::TraceContextPropagator, runtime, trace::Config, Resource};
+#[cfg(feature = "otlp")]
+use tracing_subscriber::EnvFilter;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, Layer, Registry};
+
+#[cfg(feature = "otlp")]
+use crate::sampler::LocalSampler;
+use crate::{TelemetryConfig, TelemetryError};
+
+type BoxedLayer = Box<dyn Layer<Registry> + Send + Sync>;
+
+/// Flushes and shuts down the span exporter when dropped. Applications should hold this for their lifetime.
+#[must_use = "the span exporter is shut down when the guard is dropped"]
+pub struct TelemetryGuard {
+    _private: (),
+}
+
+impl Drop for TelemetryGuard {
+    fn drop(&mut self) {
+        global::shutdown_tracer_provider();
+    }
+}
+
+/// Installs the global tracing subscriber, with the tokio-console layer if `tokio_console` is set and the OTLP span
+/// exporter if an endpoint is configured. The exporter registers the W3C Trace Context propagator used to carry trace
+/// context across service boundaries, unless `propagate_trace_context` is disabled. This must be called from within a
+/// tokio runtime.
+///
+/// Returns `Ok(None)` if neither is enabled, in which case no subscriber is installed.
+pub fn install(
+    service_name: &str,
+    instance_id: &str,
+    config: &TelemetryConfig,
+    tokio_console: bool,
+) -> Result<Option<TelemetryGuard>, TelemetryError> {
+    let mut layers = Vec::<BoxedLayer>::new();
+    if tokio_console {
+        layers.push(
+            console_subscriber::ConsoleLayer::builder()
+                .with_default_env()
+                .spawn()
+                .boxed(),
+        );
+    }
+    if let Some(endpoint) = config.otlp_endpoint.as_ref() {
+        layers.push(otlp_layer(service_name, instance_id, endpoint, config)?);
+    }
+    if layers.is_empty() {
+        return Ok(None);
+    }
+    // Shuts the exporter down again should the subscriber fail to install
+    let guard = TelemetryGuard { _private: () };
+
+    tracing_subscriber::registry().with(layers).try_init()?;
+
+    Ok(Some(guard))
+}
+
+#[cfg(feature = "otlp")]
+fn otlp_layer(
+    service_name: &str,
+    instance_id: &str,
+    endpoint: &str,
+    config: &TelemetryConfig,
+) -> Result<BoxedLayer, TelemetryError> {
+    let filter = EnvFilter::try_new(&config.filter)?;
+    let provider = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(opentelemetry_otlp::new_exporter().tonic().with_endpoint(endpoint))
+        .with_trace_config(
+            Config::default()
+                .with_sampler(LocalSampler::new(config.sample_ratio))
+                .with_resource(Resource::new(vec![
+                    KeyValue::new("service.name", service_name.to_string()),
+                    KeyValue::new("service.instance.id", instance_id.to_string()),
+                ])),
+        )
+        .install_batch(runtime::Tokio)?;
+    let tracer = provider.tracer("tari");
+    global::set_tracer_provider(provider);
+    // Without a propagator, no trace context is sent to or accepted from peers
+    if config.propagate_trace_context {
+        global::set_text_map_propagator(TraceContextPropagator::new());
+    }
+
+    Ok(tracing_opentelemetry::layer()
+        .with_tracer(tracer)
+        .with_filter(filter)
+        .boxed())
+}
+
+#[cfg(not(feature = "otlp"))]
+fn otlp_layer(
+    _service_name: &str,
+    _instance_id: &str,
+    endpoint: &str,
+    _config: &TelemetryConfig,
+) -> Result<BoxedLayer, TelemetryError> {
+    Err(TelemetryError::ExporterNotSupported(endpoint.to_string()))
+}
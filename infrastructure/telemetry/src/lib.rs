@@ -0,0 +1,29 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! OpenTelemetry distributed tracing for Tari applications.
+//!
+//! Libraries create `tracing` spans as usual and use the [propagation] helpers to carry the trace context across
+//! service boundaries (e.g. comms RPC requests). Applications install the global tracing subscriber with [install],
+//! which requires the `subscriber` feature, and export spans over OTLP with the `otlp` feature. Until an exporter is
+//! installed, the propagation helpers are no-ops.
+
+pub mod propagation;
+
+#[cfg(feature = "subscriber")]
+mod config;
+#[cfg(feature = "subscriber")]
+pub use config::TelemetryConfig;
+
+#[cfg(feature = "subscriber")]
+mod error;
+#[cfg(feature = "subscriber")]
+pub use error::TelemetryError;
+
+#[cfg(feature = "subscriber")]
+mod install;
+#[cfg(feature = "subscriber")]
+pub use install::{install, TelemetryGuard};
+
+#[cfg(feature = "otlp")]
+mod sampler;
@@ -0,0 +1,67 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::collections::HashMap;
+
+use opentelemetry::global;
+use tracing::Span;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+/// Carries trace context across a service boundary, keyed by the W3C Trace Context field names (e.g. `traceparent`).
+pub type TraceContextCarrier = HashMap<String, String>;
+
+/// Returns the trace context of the given span, to be sent along with an outbound request. The carrier is empty if
+/// no exporter has been installed or the span is disabled.
+pub fn inject_span_context(span: &Span) -> TraceContextCarrier {
+    let mut carrier = TraceContextCarrier::new();
+    global::get_text_map_propagator(|propagator| propagator.inject_context(&span.context(), &mut carrier));
+    carrier
+}
+
+/// Sets the remote trace context received with an inbound request as the parent of the given span, so that the span
+/// is exported as part of the caller's trace. Whether the span is sampled is still decided by this node, whatever the
+/// caller sampled. An empty carrier leaves the span unchanged.
+pub fn set_parent_from_carrier(span: &Span, carrier: &TraceContextCarrier) {
+    if carrier.is_empty() {
+        return;
+    }
+    let parent = global::get_text_map_propagator(|propagator| propagator.extract(carrier));
+    span.set_parent(parent);
+}
+
+#[cfg(test)]
+mod test {
+    use opentelemetry::trace::{TraceContextExt, TracerProvider};
+    use opentelemetry_sdk::{propagation::TraceContextPropagator, trace::TracerProvider as SdkTracerProvider};
+    use tracing::subscriber::with_default;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use super::*;
+
+    #[test]
+    fn it_propagates_the_trace_id_across_a_boundary() {
+        global::set_text_map_propagator(TraceContextPropagator::new());
+        let tracer = SdkTracerProvider::builder().build().tracer("test");
+        let subscriber = tracing_subscriber::registry().with(tracing_opentelemetry::layer().with_tracer(tracer));
+
+        with_default(subscriber, || {
+            let client_span = tracing::info_span!("client");
+            let carrier = inject_span_context(&client_span);
+            assert!(carrier.contains_key("traceparent"));
+
+            let server_span = tracing::info_span!("server");
+            set_parent_from_carrier(&server_span, &carrier);
+            assert_eq!(
+                server_span.context().span().span_context().trace_id(),
+                client_span.context().span().span_context().trace_id()
+            );
+        });
+    }
+
+    #[test]
+    fn it_is_a_no_op_for_an_empty_carrier() {
+        let carrier = inject_span_context(&Span::none());
+        assert!(carrier.is_empty());
+        set_parent_from_carrier(&Span::none(), &carrier);
+    }
+}
@@ -0,0 +1,125 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use opentelemetry::{
+    trace::{Link, SamplingDecision, SamplingResult, SpanKind, TraceContextExt, TraceId},
+    Context,
+    KeyValue,
+};
+use opentelemetry_sdk::trace::{Sampler, ShouldSample};
+use rand::Rng;
+
+/// Samples a fraction of traces, and follows the sampling decision of the parent span if it is local. Unlike
+/// `Sampler::ParentBased`, the sampling decision of a remote parent is ignored: peers choose both the flags and the
+/// trace ID of the trace context they send, so either would let them make this node sample and export every span of
+/// their requests. Spans with a remote parent are still exported as part of its trace when they are sampled.
+#[derive(Debug, Clone)]
+pub struct LocalSampler {
+    sample_ratio: f64,
+}
+
+impl LocalSampler {
+    pub fn new(sample_ratio: f64) -> Self {
+        Self { sample_ratio }
+    }
+}
+
+impl ShouldSample for LocalSampler {
+    fn should_sample(
+        &self,
+        parent_context: Option<&Context>,
+        trace_id: TraceId,
+        name: &str,
+        span_kind: &SpanKind,
+        attributes: &[KeyValue],
+        links: &[Link],
+    ) -> SamplingResult {
+        let parent = parent_context
+            .map(|cx| cx.span().span_context().clone())
+            .filter(|parent| parent.is_valid());
+        let Some(parent) = parent else {
+            // The trace ID of a root span is generated locally
+            return Sampler::TraceIdRatioBased(self.sample_ratio).should_sample(
+                parent_context,
+                trace_id,
+                name,
+                span_kind,
+                attributes,
+                links,
+            );
+        };
+        let is_sampled = if parent.is_remote() {
+            rand::thread_rng().gen_bool(self.sample_ratio.clamp(0.0, 1.0))
+        } else {
+            parent.is_sampled()
+        };
+        SamplingResult {
+            decision: if is_sampled {
+                SamplingDecision::RecordAndSample
+            } else {
+                SamplingDecision::Drop
+            },
+            attributes: Vec::new(),
+            trace_state: parent.trace_state().clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use opentelemetry::trace::{SpanContext, SpanId, TraceFlags, TraceState};
+
+    use super::*;
+
+    fn decision(sampler: &LocalSampler, parent: Option<(bool, bool)>) -> SamplingDecision {
+        let parent_context = parent.map(|(is_remote, is_sampled)| {
+            let flags = if is_sampled {
+                TraceFlags::SAMPLED
+            } else {
+                TraceFlags::default()
+            };
+            Context::new().with_remote_span_context(SpanContext::new(
+                TraceId::from_u128(1),
+                SpanId::from_u64(1),
+                flags,
+                is_remote,
+                TraceState::default(),
+            ))
+        });
+        sampler
+            .should_sample(
+                parent_context.as_ref(),
+                TraceId::from_u128(1),
+                "test",
+                &SpanKind::Internal,
+                &[],
+                &[],
+            )
+            .decision
+    }
+
+    #[test]
+    fn it_ignores_the_sampling_decision_of_a_remote_parent() {
+        let never = LocalSampler::new(0.0);
+        assert_eq!(decision(&never, Some((true, true))), SamplingDecision::Drop);
+        assert_eq!(decision(&never, None), SamplingDecision::Drop);
+        let always = LocalSampler::new(1.0);
+        assert_eq!(
+            decision(&always, Some((true, false))),
+            SamplingDecision::RecordAndSample
+        );
+        assert_eq!(decision(&always, None), SamplingDecision::RecordAndSample);
+    }
+
+    #[test]
+    fn it_follows_the_sampling_decision_of_a_local_parent() {
+        assert_eq!(
+            decision(&LocalSampler::new(0.0), Some((false, true))),
+            SamplingDecision::RecordAndSample
+        );
+        assert_eq!(
+            decision(&LocalSampler::new(1.0), Some((false, false))),
+            SamplingDecision::Drop
+        );
+    }
+}
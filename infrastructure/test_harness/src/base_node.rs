@@ -0,0 +1,288 @@
+//   Copyright 2022. The Tari Project
+//
+//   Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//   following conditions are met:
+//
+//   1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//   disclaimer.
+//
+//   2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//   following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//   3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//   products derived from this software without specific prior written permission.
+//
+//   THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//   INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//   DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//   SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//   SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//   WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//   USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    convert::TryInto,
+    fmt::{Debug, Formatter},
+    net::TcpListener,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+    time::Duration,
+};
+
+use minotari_app_utilities::identity_management::save_as_json;
+use minotari_node::{run_base_node, ApplicationConfig, BaseNodeConfig, GrpcMethod};
+use minotari_node_grpc_client::BaseNodeGrpcClient;
+use rand::rngs::OsRng;
+use tari_common::{
+    configuration::{CommonConfig, MultiaddrList},
+    network_check::set_network_if_choice_valid,
+};
+use tari_comms::{multiaddr::Multiaddr, peer_manager::PeerFeatures, NodeIdentity};
+use tari_comms_dht::{DbConnectionUrl, DhtConfig};
+use tari_p2p::{Network, PeerSeedsConfig, TransportType};
+use tari_shutdown::Shutdown;
+use tokio::task;
+use tonic::transport::Channel;
+
+use crate::{get_port, wait_for_service};
+
+#[derive(Clone)]
+pub struct BaseNodeProcess {
+    pub name: String,
+    pub port: u64,
+    pub grpc_port: u64,
+    pub identity: NodeIdentity,
+    pub temp_dir_path: PathBuf,
+    pub is_seed_node: bool,
+    pub seed_nodes: Vec<String>,
+    pub config: BaseNodeConfig,
+    pub kill_signal: Shutdown,
+}
+
+impl Drop for BaseNodeProcess {
+    fn drop(&mut self) {
+        self.kill();
+    }
+}
+
+// NOTE: implemented to skip `cx`, because BaseNodeContext doesn't implement Debug
+impl Debug for BaseNodeProcess {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BaseNodeProcess")
+            .field("name", &self.name)
+            .field("port", &self.port)
+            .field("grpc_port", &self.grpc_port)
+            .field("identity", &self.identity)
+            .field("temp_dir_path", &self.temp_dir_path)
+            .field("is_seed_node", &self.is_seed_node)
+            .finish()
+    }
+}
+
+/// Builds and spawns an in-process base node on localnet. The node listens on TCP on localhost, with all gRPC methods
+/// enabled.
+pub struct BaseNodeBuilder {
+    name: String,
+    base_dir: PathBuf,
+    is_seed_node: bool,
+    seed_nodes: Vec<String>,
+    peer_addresses: Vec<String>,
+    config: BaseNodeConfig,
+    existing: Option<(u64, u64, PathBuf, NodeIdentity)>,
+}
+
+impl BaseNodeBuilder {
+    /// A new base node whose data is kept in a subdirectory of `base_dir`
+    pub fn new<S: Into<String>, P: AsRef<Path>>(name: S, base_dir: P) -> Self {
+        Self {
+            name: name.into(),
+            base_dir: base_dir.as_ref().to_path_buf(),
+            is_seed_node: false,
+            seed_nodes: vec![],
+            peer_addresses: vec![],
+            config: BaseNodeConfig::default(),
+            existing: None,
+        }
+    }
+
+    /// Marks the node as a seed node. This is informational only, see [BaseNodeProcess::is_seed_node].
+    pub fn seed_node(mut self, is_seed_node: bool) -> Self {
+        self.is_seed_node = is_seed_node;
+        self
+    }
+
+    /// Connects to the given nodes on startup
+    pub fn with_peers<'a, I: IntoIterator<Item = &'a BaseNodeProcess>>(mut self, peers: I) -> Self {
+        for peer in peers {
+            self.seed_nodes.push(peer.name.clone());
+            self.peer_addresses.push(peer.peer_address());
+        }
+        self
+    }
+
+    /// The starting configuration. Settings that the harness depends on, such as the network, addresses and data
+    /// paths, are overridden.
+    pub fn with_config(mut self, config: BaseNodeConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Spawns the node and waits until its p2p and gRPC ports are listening
+    #[allow(clippy::too_many_lines)]
+    pub async fn spawn(self) -> BaseNodeProcess {
+        std::env::set_var("TARI_NETWORK", "localnet");
+        set_network_if_choice_valid(Network::LocalNet).unwrap();
+
+        let (port, grpc_port, temp_dir_path, base_node_identity) = match self.existing {
+            Some(existing) => existing,
+            None => {
+                // each spawned base node will use different ports
+                let port = get_port(18000..18499).unwrap();
+                let grpc_port = get_port(18500..18999).unwrap();
+                // create a new temporary directory
+                let temp_dir_path = self
+                    .base_dir
+                    .join("base_nodes")
+                    .join(format!("{}_grpc_port_{}", self.name, grpc_port));
+
+                let base_node_address = Multiaddr::from_str(&format!("/ip4/127.0.0.1/tcp/{}", port)).unwrap();
+                let base_node_identity =
+                    NodeIdentity::random(&mut OsRng, base_node_address, PeerFeatures::COMMUNICATION_NODE);
+                save_as_json(temp_dir_path.join("base_node.json"), &base_node_identity).unwrap();
+                (port, grpc_port, temp_dir_path, base_node_identity)
+            },
+        };
+
+        println!("Base node identity: {}", base_node_identity);
+
+        let shutdown = Shutdown::new();
+        let process = BaseNodeProcess {
+            name: self.name.clone(),
+            port,
+            grpc_port,
+            identity: base_node_identity.clone(),
+            temp_dir_path: temp_dir_path.clone(),
+            is_seed_node: self.is_seed_node,
+            seed_nodes: self.seed_nodes,
+            config: self.config.clone(),
+            kill_signal: shutdown.clone(),
+        };
+
+        let name = self.name;
+        let is_seed_node = self.is_seed_node;
+        let peer_addresses = self.peer_addresses;
+        let base_node_config = self.config;
+        let mut common_config = CommonConfig::default();
+        common_config.base_path = temp_dir_path.clone();
+        task::spawn(async move {
+            let mut base_node_config = ApplicationConfig {
+                common: common_config,
+                base_node: base_node_config,
+                peer_seeds: PeerSeedsConfig {
+                    peer_seeds: peer_addresses.into(),
+                    dns_seeds_use_dnssec: false,
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+
+            println!("Using base_node temp_dir: {}", temp_dir_path.clone().display());
+            base_node_config.base_node.network = Network::LocalNet;
+            base_node_config.base_node.grpc_enabled = true;
+            base_node_config.base_node.grpc_address =
+                Some(format!("/ip4/127.0.0.1/tcp/{}", grpc_port).parse().unwrap());
+            base_node_config.base_node.report_grpc_error = true;
+            base_node_config.base_node.metadata_auto_ping_interval = Duration::from_secs(15);
+
+            base_node_config.base_node.data_dir = temp_dir_path.to_path_buf();
+            base_node_config.base_node.identity_file = PathBuf::from("base_node_id.json");
+            base_node_config.base_node.tor_identity_file = PathBuf::from("base_node_tor_id.json");
+            base_node_config.base_node.max_randomx_vms = 1;
+
+            base_node_config.base_node.lmdb_path = temp_dir_path.to_path_buf();
+            base_node_config.base_node.p2p.transport.transport_type = TransportType::Tcp;
+            base_node_config.base_node.p2p.transport.tcp.listener_address =
+                format!("/ip4/127.0.0.1/tcp/{}", port).parse().unwrap();
+            base_node_config.base_node.p2p.public_addresses = MultiaddrList::from(vec![base_node_config
+                .base_node
+                .p2p
+                .transport
+                .tcp
+                .listener_address
+                .clone()]);
+            base_node_config.base_node.p2p.dht = DhtConfig::default_local_test();
+            base_node_config.base_node.p2p.dht.database_url = DbConnectionUrl::file(format!("{}-dht.sqlite", port));
+            base_node_config.base_node.p2p.dht.network_discovery.enabled = true;
+            base_node_config.base_node.p2p.allow_test_addresses = true;
+            base_node_config.base_node.storage.orphan_storage_capacity = 10;
+            if base_node_config.base_node.storage.pruning_horizon != 0 {
+                base_node_config.base_node.storage.pruning_interval = 1;
+            };
+            base_node_config.base_node.grpc_server_allow_methods = GrpcMethod::ALL_VARIANTS.to_vec().into();
+
+            // Hierarchically set the base path for all configs
+            base_node_config.base_node.set_base_path(temp_dir_path.clone());
+
+            println!(
+                "Initializing base node: name={}; port={}; grpc_port={}; is_seed_node={}",
+                name, port, grpc_port, is_seed_node
+            );
+
+            let result = run_base_node(shutdown, Arc::new(base_node_identity), Arc::new(base_node_config)).await;
+            if let Err(e) = result {
+                panic!("{:?}", e);
+            }
+        });
+
+        wait_for_service(port).await;
+        wait_for_service(grpc_port).await;
+        process
+    }
+}
+
+impl BaseNodeProcess {
+    /// A builder that restarts this node with the same identity, ports, data and configuration. The node must have
+    /// been killed first.
+    pub fn restart(&self) -> BaseNodeBuilder {
+        BaseNodeBuilder {
+            name: self.name.clone(),
+            base_dir: self.temp_dir_path.clone(),
+            is_seed_node: self.is_seed_node,
+            seed_nodes: vec![],
+            peer_addresses: vec![],
+            config: self.config.clone(),
+            existing: Some((
+                self.port,
+                self.grpc_port,
+                self.temp_dir_path.clone(),
+                self.identity.clone(),
+            )),
+        }
+    }
+
+    /// The address of this node in the form accepted for peer seeds
+    pub fn peer_address(&self) -> String {
+        self.identity.to_peer().to_short_string()
+    }
+
+    pub async fn get_grpc_client(&self) -> anyhow::Result<BaseNodeGrpcClient<Channel>> {
+        Ok(BaseNodeGrpcClient::connect(format!("http://127.0.0.1:{}", self.grpc_port)).await?)
+    }
+
+    pub fn kill(&mut self) {
+        self.kill_signal.trigger();
+        loop {
+            // lets wait till the port is cleared
+            if TcpListener::bind(("127.0.0.1", self.port.try_into().unwrap())).is_ok() {
+                break;
+            }
+        }
+        loop {
+            // lets wait till the port is cleared
+            if TcpListener::bind(("127.0.0.1", self.grpc_port.try_into().unwrap())).is_ok() {
+                break;
+            }
+        }
+    }
+}
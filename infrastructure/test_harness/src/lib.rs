@@ -0,0 +1,73 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Spins up real Tari base nodes and console wallets in-process on localnet, so that end-to-end tests can be written
+//! against them. This is the machinery behind the Tari cucumber tests.
+//!
+//! ```no_run
+//! # async fn example() {
+//! use tari_test_harness::network::TestNetwork;
+//!
+//! let dir = std::env::temp_dir().join("my_test");
+//! let network = TestNetwork::builder(&dir)
+//!     .with_seed_node("seed")
+//!     .with_base_node("node")
+//!     .with_wallet("alice", "node")
+//!     .build()
+//!     .await;
+//! network.mine_blocks("node", 5, "alice").await;
+//! let balance = network
+//!     .wallet("alice")
+//!     .get_grpc_client()
+//!     .await
+//!     .unwrap()
+//!     .get_balance(Default::default())
+//!     .await
+//!     .unwrap();
+//! # }
+//! ```
+//!
+//! Nodes and wallets can also be spawned individually with [base_node::BaseNodeBuilder] and
+//! [wallet::WalletBuilder]. Processes shut down when they are dropped.
+
+use std::{convert::TryFrom, net::TcpListener, ops::Range, time::Duration};
+
+use rand::Rng;
+
+pub mod base_node;
+pub mod mining;
+pub mod network;
+pub mod wallet;
+
+pub fn get_port(range: Range<u16>) -> Option<u64> {
+    let min = range.clone().min().expect("A minimum possible port number");
+    let max = range.max().expect("A maximum possible port number");
+
+    loop {
+        let port = rand::thread_rng().gen_range(min..max);
+
+        if TcpListener::bind(("127.0.0.1", port)).is_ok() {
+            return Some(u64::from(port));
+        }
+    }
+}
+
+pub async fn wait_for_service(port: u64) {
+    // The idea is that if the port is taken it means the service is running.
+    // If the port is not taken the service hasn't come up yet
+    let max_tries = 4 * 60;
+    let mut attempts = 0;
+
+    loop {
+        if TcpListener::bind(("127.0.0.1", u16::try_from(port).unwrap())).is_err() {
+            return;
+        }
+
+        if attempts >= max_tries {
+            panic!("Service on port {} never started", port);
+        }
+
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        attempts += 1;
+    }
+}
@@ -0,0 +1,163 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use minotari_wallet_grpc_client::grpc;
+use rand::rngs::OsRng;
+use tari_common::configuration::Network;
+use tari_common_types::{tari_address::TariAddress, types::PrivateKey};
+use tari_core::{
+    consensus::ConsensusManager,
+    transactions::key_manager::{create_memory_db_key_manager, MemoryDbKeyManager, TariKeyId},
+};
+use tari_crypto::keys::SecretKey;
+use tari_key_manager::key_manager_service::KeyManagerInterface;
+
+use crate::{
+    base_node::{BaseNodeBuilder, BaseNodeProcess},
+    mining::mine_blocks_without_wallet,
+    wallet::{WalletBuilder, WalletProcess},
+};
+
+/// Describes a set of nodes and wallets to spawn together. Seed nodes are spawned first, and every other node and
+/// wallet uses all of the seed nodes as peers.
+pub struct TestNetworkBuilder {
+    base_dir: PathBuf,
+    seed_nodes: Vec<String>,
+    base_nodes: Vec<String>,
+    wallets: Vec<(String, String)>,
+}
+
+impl TestNetworkBuilder {
+    pub fn with_seed_node<S: Into<String>>(mut self, name: S) -> Self {
+        self.seed_nodes.push(name.into());
+        self
+    }
+
+    pub fn with_base_node<S: Into<String>>(mut self, name: S) -> Self {
+        self.base_nodes.push(name.into());
+        self
+    }
+
+    /// Adds a wallet that uses the base node `base_node`, which must also be added to the network
+    pub fn with_wallet<S: Into<String>, T: Into<String>>(mut self, name: S, base_node: T) -> Self {
+        self.wallets.push((name.into(), base_node.into()));
+        self
+    }
+
+    /// Spawns the network. Panics if a process fails to start.
+    pub async fn build(self) -> TestNetwork {
+        let key_manager = create_memory_db_key_manager().unwrap();
+        let script_key_id = key_manager.import_key(PrivateKey::random(&mut OsRng)).await.unwrap();
+        let mut network = TestNetwork {
+            base_nodes: HashMap::new(),
+            wallets: HashMap::new(),
+            key_manager,
+            script_key_id,
+            consensus_manager: ConsensusManager::builder(Network::LocalNet).build().unwrap(),
+        };
+
+        let mut seeds = Vec::with_capacity(self.seed_nodes.len());
+        for name in self.seed_nodes {
+            let node = BaseNodeBuilder::new(name.clone(), &self.base_dir)
+                .seed_node(true)
+                .spawn()
+                .await;
+            seeds.push(name.clone());
+            network.base_nodes.insert(name, node);
+        }
+        let mut base_nodes = Vec::with_capacity(self.base_nodes.len());
+        for name in self.base_nodes {
+            let node = BaseNodeBuilder::new(name.clone(), &self.base_dir)
+                .with_peers(seeds.iter().map(|seed| &network.base_nodes[seed]))
+                .spawn()
+                .await;
+            base_nodes.push((name, node));
+        }
+        network.base_nodes.extend(base_nodes);
+
+        let mut wallets = Vec::with_capacity(self.wallets.len());
+        for (name, base_node) in self.wallets {
+            let base_node = network
+                .base_nodes
+                .get(&base_node)
+                .unwrap_or_else(|| panic!("Wallet {} uses unknown base node {}", name, base_node));
+            let wallet = WalletBuilder::new(name.clone(), &self.base_dir)
+                .with_base_node(base_node)
+                .with_peers(seeds.iter().map(|seed| &network.base_nodes[seed]))
+                .spawn()
+                .await;
+            wallets.push((name, wallet));
+        }
+        network.wallets.extend(wallets);
+
+        network
+    }
+}
+
+/// A running set of base nodes and wallets. Everything is shut down when the network is dropped.
+pub struct TestNetwork {
+    base_nodes: HashMap<String, BaseNodeProcess>,
+    wallets: HashMap<String, WalletProcess>,
+    key_manager: MemoryDbKeyManager,
+    script_key_id: TariKeyId,
+    consensus_manager: ConsensusManager,
+}
+
+impl TestNetwork {
+    /// Starts describing a network whose data is kept in `base_dir`
+    pub fn builder<P: AsRef<Path>>(base_dir: P) -> TestNetworkBuilder {
+        TestNetworkBuilder {
+            base_dir: base_dir.as_ref().to_path_buf(),
+            seed_nodes: vec![],
+            base_nodes: vec![],
+            wallets: vec![],
+        }
+    }
+
+    /// Panics if there is no base node called `name`
+    pub fn base_node(&self, name: &str) -> &BaseNodeProcess {
+        self.base_nodes
+            .get(name)
+            .unwrap_or_else(|| panic!("Unknown base node {}", name))
+    }
+
+    /// Panics if there is no wallet called `name`
+    pub fn wallet(&self, name: &str) -> &WalletProcess {
+        self.wallets
+            .get(name)
+            .unwrap_or_else(|| panic!("Unknown wallet {}", name))
+    }
+
+    /// Mines `num_blocks` blocks on `base_node`, paying the coinbases to `wallet`. Blocks are submitted directly, as
+    /// localnet has minimal difficulty.
+    pub async fn mine_blocks(&self, base_node: &str, num_blocks: u64, wallet: &str) {
+        let mut base_client = self.base_node(base_node).get_grpc_client().await.unwrap();
+        let address = self
+            .wallet(wallet)
+            .get_grpc_client()
+            .await
+            .unwrap()
+            .get_address(grpc::Empty {})
+            .await
+            .unwrap()
+            .into_inner()
+            .interactive_address;
+        let address = TariAddress::from_bytes(&address).unwrap();
+        mine_blocks_without_wallet(
+            &mut base_client,
+            num_blocks,
+            0,
+            &self.key_manager,
+            &self.script_key_id,
+            &address,
+            false,
+            &self.consensus_manager,
+        )
+        .await;
+    }
+}
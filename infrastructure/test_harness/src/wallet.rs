@@ -0,0 +1,304 @@
+//   Copyright 2022. The Tari Project
+//
+//   Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//   following conditions are met:
+//
+//   1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//   disclaimer.
+//
+//   2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//   following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//   3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//   products derived from this software without specific prior written permission.
+//
+//   THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//   INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//   DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//   SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//   SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//   WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//   USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+    thread,
+    time::Duration,
+};
+
+use minotari_app_grpc::tari_rpc::SetBaseNodeRequest;
+use minotari_app_utilities::common_cli_args::CommonCliArgs;
+use minotari_console_wallet::{run_wallet_with_cli, Cli};
+use minotari_wallet::{transaction_service::config::TransactionRoutingMechanism, WalletConfig};
+use minotari_wallet_grpc_client::WalletGrpcClient;
+use tari_common::{
+    configuration::{CommonConfig, MultiaddrList},
+    network_check::set_network_if_choice_valid,
+};
+use tari_comms::multiaddr::Multiaddr;
+use tari_comms_dht::{DbConnectionUrl, DhtConfig};
+use tari_p2p::{auto_update::AutoUpdateConfig, Network, PeerSeedsConfig, TransportType};
+use tari_shutdown::Shutdown;
+use tokio::runtime;
+use tonic::transport::Channel;
+
+use crate::{base_node::BaseNodeProcess, get_port, wait_for_service};
+
+#[derive(Clone, Debug)]
+pub struct WalletProcess {
+    pub config: WalletConfig,
+    pub grpc_port: u64,
+    pub kill_signal: Shutdown,
+    pub name: String,
+    pub port: u64,
+    pub temp_dir_path: PathBuf,
+}
+
+impl Drop for WalletProcess {
+    fn drop(&mut self) {
+        self.kill();
+    }
+}
+
+/// Builds and spawns an in-process console wallet on localnet, in non-interactive gRPC mode with the password `test`
+pub struct WalletBuilder {
+    name: String,
+    base_dir: PathBuf,
+    base_node: Option<(String, u64)>,
+    peer_addresses: Vec<String>,
+    routing_mechanism: Option<TransactionRoutingMechanism>,
+    cli: Option<Cli>,
+    config: WalletConfig,
+    existing: Option<(u64, u64, PathBuf)>,
+}
+
+impl WalletBuilder {
+    /// A new wallet whose data is kept in a subdirectory of `base_dir`
+    pub fn new<S: Into<String>, P: AsRef<Path>>(name: S, base_dir: P) -> Self {
+        let mut config = WalletConfig::default();
+        config.base_node_service_config.base_node_monitor_max_refresh_interval = Duration::from_secs(5);
+        Self {
+            name: name.into(),
+            base_dir: base_dir.as_ref().to_path_buf(),
+            base_node: None,
+            peer_addresses: vec![],
+            routing_mechanism: None,
+            cli: None,
+            config,
+            existing: None,
+        }
+    }
+
+    /// Sets the base node that the wallet uses once it has started
+    pub fn with_base_node(mut self, base_node: &BaseNodeProcess) -> Self {
+        self.base_node = Some((base_node.identity.public_key().to_string(), base_node.port));
+        self
+    }
+
+    /// Connects to the given nodes on startup
+    pub fn with_peers<'a, I: IntoIterator<Item = &'a BaseNodeProcess>>(mut self, peers: I) -> Self {
+        self.peer_addresses
+            .extend(peers.into_iter().map(BaseNodeProcess::peer_address));
+        self
+    }
+
+    pub fn with_routing_mechanism(mut self, routing_mechanism: TransactionRoutingMechanism) -> Self {
+        self.routing_mechanism = Some(routing_mechanism);
+        self
+    }
+
+    /// The command line the wallet is started with. Defaults to [get_default_cli].
+    pub fn with_cli(mut self, cli: Cli) -> Self {
+        self.cli = Some(cli);
+        self
+    }
+
+    /// The starting configuration. Settings that the harness depends on, such as the network, addresses and data
+    /// paths, are overridden.
+    pub fn with_config(mut self, config: WalletConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Spawns the wallet on its own runtime and waits until its p2p and gRPC ports are listening
+    #[allow(clippy::too_many_lines)]
+    pub async fn spawn(self) -> WalletProcess {
+        std::env::set_var("TARI_NETWORK", "localnet");
+        set_network_if_choice_valid(Network::LocalNet).unwrap();
+
+        let (port, grpc_port, temp_dir_path) = match self.existing {
+            Some(existing) => existing,
+            None => {
+                // each spawned wallet will use different ports
+                let port = get_port(18000..18499).unwrap();
+                let grpc_port = get_port(18500..18999).unwrap();
+                let temp_dir_path = self
+                    .base_dir
+                    .join("wallets")
+                    .join(format!("{}_grpc_port_{}", self.name, grpc_port));
+                (port, grpc_port, temp_dir_path)
+            },
+        };
+
+        let base_node = self.base_node;
+        let base_node_cloned = base_node.clone();
+        let peer_addresses = self.peer_addresses;
+        let routing_mechanism = self.routing_mechanism;
+        let cli = self.cli;
+        let shutdown = Shutdown::new();
+        let mut send_to_thread_shutdown = shutdown.clone();
+
+        let temp_dir = temp_dir_path.clone();
+
+        let mut common_config = CommonConfig::default();
+        common_config.base_path = temp_dir_path.clone();
+        let wallet_cfg = self.config.clone();
+        thread::spawn(move || {
+            let mut wallet_app_config = minotari_console_wallet::ApplicationConfig {
+                common: common_config,
+                auto_update: AutoUpdateConfig::default(),
+                wallet: wallet_cfg,
+                peer_seeds: PeerSeedsConfig {
+                    peer_seeds: peer_addresses.into(),
+                    ..Default::default()
+                },
+            };
+
+            eprintln!("Using wallet temp_dir: {}", temp_dir_path.clone().display());
+
+            wallet_app_config.wallet.identity_file = Some(temp_dir_path.clone().join("wallet_id.json"));
+            wallet_app_config.wallet.network = Network::LocalNet;
+            wallet_app_config.wallet.password = Some("test".into());
+            wallet_app_config.wallet.grpc_enabled = true;
+            wallet_app_config.wallet.grpc_address =
+                Some(Multiaddr::from_str(&format!("/ip4/127.0.0.1/tcp/{}", grpc_port)).unwrap());
+            wallet_app_config.wallet.db_file = PathBuf::from("console_wallet.db");
+            wallet_app_config.wallet.contacts_auto_ping_interval = Duration::from_secs(2);
+            wallet_app_config
+                .wallet
+                .base_node_service_config
+                .base_node_monitor_max_refresh_interval = Duration::from_secs(15);
+            wallet_app_config.wallet.p2p.transport.transport_type = TransportType::Tcp;
+            wallet_app_config.wallet.p2p.transport.tcp.listener_address =
+                Multiaddr::from_str(&format!("/ip4/127.0.0.1/tcp/{}", port)).unwrap();
+            wallet_app_config.wallet.p2p.public_addresses = MultiaddrList::from(vec![wallet_app_config
+                .wallet
+                .p2p
+                .transport
+                .tcp
+                .listener_address
+                .clone()]);
+            wallet_app_config.wallet.p2p.dht = DhtConfig::default_local_test();
+            wallet_app_config.wallet.p2p.dht.database_url = DbConnectionUrl::file(format!("{}-dht.sqlite", port));
+            wallet_app_config.wallet.p2p.allow_test_addresses = true;
+            if let Some(mech) = routing_mechanism {
+                wallet_app_config
+                    .wallet
+                    .transaction_service_config
+                    .transaction_routing_mechanism = mech;
+            }
+
+            // FIXME: wallet doesn't pick up the custom base node for some reason atm
+            wallet_app_config.wallet.custom_base_node =
+                base_node_cloned.map(|(pubkey, port)| format!("{}::/ip4/127.0.0.1/tcp/{}", pubkey, port));
+
+            wallet_app_config.wallet.set_base_path(temp_dir_path.clone());
+
+            let rt = runtime::Builder::new_multi_thread().enable_all().build().unwrap();
+
+            let mut cli = cli.unwrap_or_else(get_default_cli);
+            // We expect only a file name to be passed in, now we put it in the right directory.
+            if let Some(file_name) = cli.seed_words_file_name {
+                cli.seed_words_file_name = Some(temp_dir_path.join(file_name));
+            }
+
+            if let Err(e) = run_wallet_with_cli(&mut send_to_thread_shutdown, rt, &mut wallet_app_config, cli) {
+                panic!("{:?}", e);
+            }
+        });
+
+        let process = WalletProcess {
+            config: self.config,
+            name: self.name,
+            port,
+            grpc_port,
+            temp_dir_path: temp_dir,
+            kill_signal: shutdown,
+        };
+
+        tokio::time::sleep(Duration::from_secs(5)).await;
+
+        wait_for_service(port).await;
+        wait_for_service(grpc_port).await;
+
+        if let Some((public_key_hex, port)) = base_node {
+            let mut wallet_client = process.get_grpc_client().await.expect("wallet grpc client");
+            let _resp = wallet_client
+                .set_base_node(SetBaseNodeRequest {
+                    net_address: format!("/ip4/127.0.0.1/tcp/{}", port),
+                    public_key_hex,
+                })
+                .await
+                .unwrap();
+        }
+
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        process
+    }
+}
+
+pub fn get_default_cli() -> Cli {
+    Cli {
+        // CommonCliArgs are ignored in test, it's used only to override the config in the main.rs of the wallet.
+        common: CommonCliArgs {
+            base_path: Default::default(),
+            config: Default::default(),
+            log_config: None,
+            log_path: None,
+            log_format: None,
+            network: None,
+            profile: None,
+            config_property_overrides: vec![],
+        },
+        password: None,
+        change_password: false,
+        recovery: false,
+        seed_words: None,
+        seed_words_file_name: None,
+        non_interactive_mode: true,
+        input_file: None,
+        command: None,
+        wallet_notify: None,
+        command_mode_auto_exit: false,
+        grpc_enabled: true,
+        grpc_address: None,
+        daemon: false,
+        pid_file: None,
+        privacy: false,
+        command2: None,
+        profile_with_tokio_console: false,
+        view_private_key: None,
+        spend_key: None,
+    }
+}
+
+impl WalletProcess {
+    /// A builder that restarts this wallet with the same ports, data and configuration. The wallet must have been
+    /// killed first.
+    pub fn restart(&self) -> WalletBuilder {
+        WalletBuilder {
+            existing: Some((self.port, self.grpc_port, self.temp_dir_path.clone())),
+            ..WalletBuilder::new(self.name.clone(), &self.temp_dir_path).with_config(self.config.clone())
+        }
+    }
+
+    pub async fn get_grpc_client(&self) -> anyhow::Result<WalletGrpcClient<Channel>> {
+        let wallet_addr = format!("http://127.0.0.1:{}", self.grpc_port);
+        Ok(WalletGrpcClient::connect(wallet_addr.as_str()).await?)
+    }
+
+    pub fn kill(&mut self) {
+        self.kill_signal.trigger();
+    }
+}
@@ -20,9 +20,9 @@
 //   WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //   USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{convert::TryFrom, net::TcpListener, ops::Range, path::PathBuf, process, time::Duration};
+use std::{path::PathBuf, process};
 
-use rand::Rng;
+pub use tari_test_harness::{get_port, wait_for_service};
 
 pub mod base_node_process;
 pub mod chat_client;
@@ -38,44 +38,11 @@ pub mod world;
 
 pub use world::TariWorld;
 
-pub fn get_port(range: Range<u16>) -> Option<u64> {
-    let min = range.clone().min().expect("A minimum possible port number");
-    let max = range.max().expect("A maximum possible port number");
-
-    loop {
-        let port = rand::thread_rng().gen_range(min..max);
-
-        if TcpListener::bind(("127.0.0.1", port)).is_ok() {
-            return Some(u64::from(port));
-        }
-    }
-}
-
 pub fn get_base_dir() -> PathBuf {
     let crate_root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
     crate_root.join(format!("tests/temp/cucumber_{}", process::id()))
 }
 
-pub async fn wait_for_service(port: u64) {
-    // The idea is that if the port is taken it means the service is running.
-    // If the port is not taken the service hasn't come up yet
-    let max_tries = 4 * 60;
-    let mut attempts = 0;
-
-    loop {
-        if TcpListener::bind(("127.0.0.1", u16::try_from(port).unwrap())).is_err() {
-            return;
-        }
-
-        if attempts >= max_tries {
-            panic!("Service on port {} never started", port);
-        }
-
-        tokio::time::sleep(Duration::from_millis(250)).await;
-        attempts += 1;
-    }
-}
-
 pub async fn get_peer_addresses(world: &TariWorld, peers: &[String]) -> Vec<String> {
     peers
         .iter()
@@ -97,7 +97,9 @@ impl MergeMiningProxyProcess {
                     config: config_path.into_os_string().into_string().unwrap(),
                     log_config: None,
                     log_path: None,
+                    log_format: None,
                     network: Some("localnet".to_string().try_into().unwrap()),
+                    profile: None,
                     config_property_overrides: vec![
                         ("merge_mining_proxy.listener_address".to_string(), proxy_full_address),
                         (
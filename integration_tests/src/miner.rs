@@ -20,37 +20,17 @@
 //   WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //   USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{convert::TryFrom, time::Duration};
-
-use minotari_app_grpc::tari_rpc::{
-    pow_algo::PowAlgos,
-    Block,
-    NewBlockTemplate,
-    NewBlockTemplateRequest,
-    PowAlgo,
-    TransactionOutput as GrpcTransactionOutput,
-};
 use minotari_app_utilities::common_cli_args::CommonCliArgs;
 use minotari_miner::{run_miner, Cli};
-use minotari_node_grpc_client::BaseNodeGrpcClient;
 use minotari_wallet_grpc_client::{grpc, WalletGrpcClient};
 use tari_common::{configuration::Network, network_check::set_network_if_choice_valid};
 use tari_common_types::tari_address::TariAddress;
-use tari_core::{
-    consensus::ConsensusManager,
-    transactions::{
-        generate_coinbase_with_wallet_output,
-        key_manager::{MemoryDbKeyManager, TariKeyId},
-        tari_amount::MicroMinotari,
-        transaction_components::{encrypted_data::PaymentId, CoinBaseExtra, RangeProofType, WalletOutput},
-    },
-};
+use tari_test_harness::mining::{create_block_template_with_coinbase, mine_block_without_wallet_with_template};
+pub use tari_test_harness::mining::{mine_block, mine_block_before_submit, mine_blocks_without_wallet};
 use tonic::transport::Channel;
 
 use crate::TariWorld;
 
-type BaseNodeClient = BaseNodeGrpcClient<Channel>;
-
 #[derive(Clone, Debug)]
 pub struct MinerProcess {
     pub name: String,
@@ -118,6 +98,7 @@ impl MinerProcess {
                 config: config_path.into_os_string().into_string().unwrap(),
                 log_config: None,
                 log_path: None,
+                log_format: None,
                 config_property_overrides: vec![
                     (
                         "miner.base_node_grpc_address".to_string(),
@@ -131,6 +112,7 @@ impl MinerProcess {
                     ),
                 ],
                 network: Some(Network::LocalNet),
+                profile: None,
             },
             mine_until_height: None,
             miner_max_blocks: blocks,
@@ -151,164 +133,6 @@ pub async fn create_wallet_client(world: &TariWorld, wallet_name: String) -> any
     Ok(WalletGrpcClient::connect(wallet_addr.as_str()).await?)
 }
 
-pub async fn mine_blocks_without_wallet(
-    base_client: &mut BaseNodeClient,
-    num_blocks: u64,
-    weight: u64,
-    key_manager: &MemoryDbKeyManager,
-    script_key_id: &TariKeyId,
-    wallet_payment_address: &TariAddress,
-    stealth_payment: bool,
-    consensus_manager: &ConsensusManager,
-) {
-    for _ in 0..num_blocks {
-        mine_block_without_wallet(
-            base_client,
-            weight,
-            key_manager,
-            script_key_id,
-            wallet_payment_address,
-            stealth_payment,
-            consensus_manager,
-        )
-        .await;
-        tokio::time::sleep(Duration::from_millis(100)).await;
-    }
-
-    // Give some time for the base node and wallet to sync the new blocks
-    tokio::time::sleep(Duration::from_secs(5)).await;
-}
-
-pub async fn mine_block(
-    base_client: &mut BaseNodeClient,
-    key_manager: &MemoryDbKeyManager,
-    script_key_id: &TariKeyId,
-    wallet_payment_address: &TariAddress,
-    stealth_payment: bool,
-    consensus_manager: &ConsensusManager,
-) {
-    let (block_template, _wallet_output) = create_block_template_with_coinbase(
-        base_client,
-        0,
-        key_manager,
-        script_key_id,
-        wallet_payment_address,
-        stealth_payment,
-        consensus_manager,
-    )
-    .await;
-
-    // Ask the base node for a valid block using the template
-    let block_result = base_client
-        .get_new_block(block_template.clone())
-        .await
-        .unwrap()
-        .into_inner();
-    let block = block_result.block.unwrap();
-
-    // We don't need to mine, as Localnet blocks have difficulty 1s
-    let _sumbmit_res = base_client.submit_block(block).await.unwrap();
-    println!(
-        "Block successfully mined at height {:?}",
-        block_template.header.unwrap().height
-    );
-}
-
-async fn mine_block_without_wallet(
-    base_client: &mut BaseNodeClient,
-    weight: u64,
-    key_manager: &MemoryDbKeyManager,
-    script_key_id: &TariKeyId,
-    wallet_payment_address: &TariAddress,
-    stealth_payment: bool,
-    consensus_manager: &ConsensusManager,
-) {
-    let (block_template, _wallet_output) = create_block_template_with_coinbase(
-        base_client,
-        weight,
-        key_manager,
-        script_key_id,
-        wallet_payment_address,
-        stealth_payment,
-        consensus_manager,
-    )
-    .await;
-    mine_block_without_wallet_with_template(base_client, block_template).await;
-}
-
-async fn mine_block_without_wallet_with_template(base_client: &mut BaseNodeClient, block_template: NewBlockTemplate) {
-    // Ask the base node for a valid block using the template
-    let block_result = base_client
-        .get_new_block(block_template.clone())
-        .await
-        .unwrap()
-        .into_inner();
-    let block = block_result.block.unwrap();
-
-    // We don't need to mine, as Localnet blocks have difficulty 1s
-    let _submit_res = base_client.submit_block(block).await.unwrap();
-    println!(
-        "Block successfully mined at height {:?}",
-        block_template.header.unwrap().height
-    );
-}
-
-async fn create_block_template_with_coinbase(
-    base_client: &mut BaseNodeClient,
-    weight: u64,
-    key_manager: &MemoryDbKeyManager,
-    script_key_id: &TariKeyId,
-    wallet_payment_address: &TariAddress,
-    stealth_payment: bool,
-    consensus_manager: &ConsensusManager,
-) -> (NewBlockTemplate, WalletOutput) {
-    // get the block template from the base node
-    let template_req = NewBlockTemplateRequest {
-        algo: Some(PowAlgo {
-            pow_algo: PowAlgos::Sha3x.into(),
-        }),
-        max_weight: weight,
-    };
-
-    let template_response = base_client
-        .get_new_block_template(template_req)
-        .await
-        .unwrap()
-        .into_inner();
-
-    let mut block_template = template_response.new_block_template.clone().unwrap();
-
-    let template = template_response.new_block_template.as_ref().unwrap();
-    let miner_data = template_response.miner_data.as_ref().unwrap();
-    let fee = miner_data.total_fees;
-    let reward = miner_data.reward;
-    let height = template.header.as_ref().unwrap().height;
-
-    // add the coinbase outputs and kernels to the block template
-    let (_, coinbase_output, coinbase_kernel, coinbase_wallet_output) = generate_coinbase_with_wallet_output(
-        MicroMinotari::from(fee),
-        MicroMinotari::from(reward),
-        height,
-        &CoinBaseExtra::default(),
-        key_manager,
-        script_key_id,
-        wallet_payment_address,
-        stealth_payment,
-        consensus_manager.consensus_constants(height),
-        RangeProofType::BulletProofPlus,
-        PaymentId::Empty,
-    )
-    .await
-    .unwrap();
-    let body = block_template.body.as_mut().unwrap();
-
-    let grpc_output = GrpcTransactionOutput::try_from(coinbase_output).unwrap();
-    body.outputs.push(grpc_output);
-    body.kernels.push(coinbase_kernel.into());
-
-    (block_template, coinbase_wallet_output)
-}
-
 pub async fn mine_block_with_coinbase_on_node(world: &mut TariWorld, base_node: String, coinbase_name: String) {
     let mut client = world
         .base_nodes
@@ -331,27 +155,3 @@ pub async fn mine_block_with_coinbase_on_node(world: &mut TariWorld, base_node:
     world.utxos.insert(coinbase_name, wallet_output);
     mine_block_without_wallet_with_template(&mut client, template).await;
 }
-
-pub async fn mine_block_before_submit(
-    client: &mut BaseNodeClient,
-    key_manager: &MemoryDbKeyManager,
-    script_key_id: &TariKeyId,
-    wallet_payment_address: &TariAddress,
-    stealth_payment: bool,
-    consensus_manager: &ConsensusManager,
-) -> Block {
-    let (template, _wallet_output) = create_block_template_with_coinbase(
-        client,
-        0,
-        key_manager,
-        script_key_id,
-        wallet_payment_address,
-        stealth_payment,
-        consensus_manager,
-    )
-    .await;
-
-    let new_block = client.get_new_block(template).await.unwrap().into_inner();
-
-    new_block.block.unwrap()
-}
@@ -31,7 +31,7 @@ use std::{
 
 use cucumber::{event::ScenarioFinished, writer, writer::Verbosity, World as _};
 use log::*;
-use tari_common::initialize_logging;
+use tari_common::{initialize_logging, LogFormat};
 use tari_integration_tests::TariWorld;
 use tokio::runtime::Runtime;
 
@@ -55,6 +55,7 @@ fn main() {
         &PathBuf::from("log4rs/cucumber.yml"),
         &PathBuf::from("./"),
         include_str!("../log4rs/cucumber.yml"),
+        LogFormat::Text,
     )
     .expect("logging not configured");
     let stdout_buffer = Arc::new(Mutex::new(Vec::<u8>::new()));
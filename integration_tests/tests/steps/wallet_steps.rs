@@ -111,7 +111,7 @@ async fn wait_for_wallet_to_have_micro_tari(world: &mut TariWorld, wallet: Strin
     for _ in 0..=num_retries {
         let _result = client.validate_all_transactions(ValidateRequest {}).await;
         curr_amount = client
-            .get_balance(GetBalanceRequest {})
+            .get_balance(GetBalanceRequest::default())
             .await
             .unwrap()
             .into_inner()
@@ -622,7 +622,7 @@ async fn wait_for_wallet_to_have_less_than_micro_tari(world: &mut TariWorld, wal
     println!("Waiting for wallet {} to have less than {} uT", wallet, amount);
 
     let num_retries = 100;
-    let request = GetBalanceRequest {};
+    let request = GetBalanceRequest::default();
 
     for _ in 0..num_retries {
         let balance_res = client.get_balance(request).await.unwrap().into_inner();
@@ -1528,7 +1528,7 @@ async fn wallet_has_tari(world: &mut TariWorld, wallet: String, amount: u64) {
     for _ in 0..num_retries {
         let _result = wallet_client.validate_all_transactions(ValidateRequest {}).await;
         let balance_res = wallet_client
-            .get_balance(GetBalanceRequest {})
+            .get_balance(GetBalanceRequest::default())
             .await
             .unwrap()
             .into_inner();
@@ -1595,7 +1595,7 @@ async fn wallet_with_tari_connected_to_base_node(
     for _ in 0..num_retries {
         let _result = wallet_client.validate_all_transactions(ValidateRequest {}).await;
         let balance_res = wallet_client
-            .get_balance(GetBalanceRequest {})
+            .get_balance(GetBalanceRequest::default())
             .await
             .unwrap()
             .into_inner();
@@ -2080,7 +2080,7 @@ async fn wait_for_wallet_to_have_less_than_amount(world: &mut TariWorld, wallet:
     for _ in 0..=num_retries {
         let _result = client.validate_all_transactions(ValidateRequest {}).await;
         curr_amount = client
-            .get_balance(GetBalanceRequest {})
+            .get_balance(GetBalanceRequest::default())
             .await
             .unwrap()
             .into_inner()